@@ -0,0 +1,420 @@
+//! Batch slicing across a directory of device configurations
+//!
+//! One pattern is applied to every configuration and each device's slice is
+//! compared against a golden snippet or, without one, against the slice
+//! shared by the most devices. Devices with identical slices share a variant
+//! number so the report shows how many distinct versions of the section exist.
+//! Malformed lines are skipped and listed in each device's diagnostics.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::conformance::{Conformance, conformance};
+use crate::diff::{DiffStats, diff_stats, unified_diff};
+use crate::error::{ConfigSlicerError, Result};
+use crate::parser::{self, ConfigNode, Diagnostic, ParseOutput};
+use crate::plugin::ParserPlugin;
+use crate::slicer::MatchSpec;
+
+/// A device configuration loaded from disk
+#[derive(Debug, Clone)]
+pub struct DeviceConfig {
+    /// Device name, taken from the file stem
+    pub device: String,
+    /// Configuration text
+    pub text: String,
+}
+
+/// Comparison outcome for one device
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeviceStatus {
+    /// The slice matches the baseline
+    Conformant,
+    /// The slice differs from the baseline
+    Divergent,
+    /// The pattern matched nothing
+    Missing,
+}
+
+/// Comparison result for one device
+#[derive(Debug, Clone, Serialize)]
+pub struct DeviceResult {
+    /// Device name
+    pub device: String,
+    /// Comparison outcome
+    pub status: DeviceStatus,
+    /// Variant number shared by devices with identical slices
+    pub variant: Option<usize>,
+    /// Changed lines relative to the baseline
+    pub changes: DiffStats,
+    /// Share of the golden snippet's sections present, when comparing against one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conformance: Option<Conformance>,
+    /// Unified diff from the baseline, for divergent devices
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
+    /// Lines skipped or flagged while parsing the configuration
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Conformance matrix for a batch
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchReport {
+    /// Slice pattern
+    pub pattern: String,
+    /// `golden`, `variant N`, or `none` if no device has the slice
+    pub baseline: String,
+    /// Number of distinct non-empty slices
+    pub variants: usize,
+    /// Per-device results ordered by device name
+    pub devices: Vec<DeviceResult>,
+}
+
+impl BatchReport {
+    /// Counts devices with the given status
+    #[must_use]
+    pub fn count(&self, status: DeviceStatus) -> usize {
+        self.devices.iter().filter(|d| d.status == status).count()
+    }
+
+    /// Returns the first device with parse diagnostics and its first diagnostic
+    #[must_use]
+    pub fn first_diagnostic(&self) -> Option<(&str, &Diagnostic)> {
+        self.devices.iter().find_map(|d| {
+            d.diagnostics
+                .first()
+                .map(|diagnostic| (d.device.as_str(), diagnostic))
+        })
+    }
+}
+
+/// Loads every regular file in a directory as one device configuration
+///
+/// # Errors
+/// Returns an I/O error if the directory or a file cannot be read.
+pub fn load_configs(dir: &Path) -> Result<Vec<DeviceConfig>> {
+    let mut configs = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if !path.is_file() {
+            continue;
+        }
+        configs.push(DeviceConfig {
+            device: device_name(&path),
+            text: fs::read_to_string(&path)?,
+        });
+    }
+    configs.sort_by(|a, b| a.device.cmp(&b.device));
+    Ok(configs)
+}
+
+/// Names the device configured by a file after the file's stem
+pub(crate) fn device_name(path: &Path) -> String {
+    path.file_stem()
+        .map_or_else(String::new, |stem| stem.to_string_lossy().into_owned())
+}
+
+/// Slices every configuration and compares the slices
+///
+/// With a golden snippet every slice is compared against it and scored by the
+/// share of the snippet's top-level sections it contains; the snippet is
+/// normalized but not sliced. Without one, the variant shared by the most
+/// devices is the baseline, ties going to the first device by name.
+#[must_use]
+pub fn compare(configs: &[DeviceConfig], spec: &MatchSpec, golden: Option<&str>) -> BatchReport {
+    let outputs = configs
+        .iter()
+        .map(|c| parser::parse_tolerant(&c.text))
+        .collect();
+    let golden_nodes = golden.map(parser::parse);
+    compare_parsed(configs, outputs, spec, golden_nodes.as_deref())
+}
+
+/// Compares like [`compare`], parsing configurations and the golden snippet
+/// with the given parser
+///
+/// # Errors
+/// Returns an error naming the device if the parser fails.
+pub fn compare_with(
+    configs: &[DeviceConfig],
+    spec: &MatchSpec,
+    golden: Option<&str>,
+    parser: &dyn ParserPlugin,
+) -> Result<BatchReport> {
+    let outputs = configs
+        .iter()
+        .map(|c| {
+            parser
+                .parse(&c.text)
+                .map_err(|e| ConfigSlicerError::Parse(format!("Failed to parse {}: {e}", c.device)))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let golden_nodes = golden
+        .map(|golden| parser.parse(golden).map(|output| output.nodes))
+        .transpose()?;
+    Ok(compare_parsed(
+        configs,
+        outputs,
+        spec,
+        golden_nodes.as_deref(),
+    ))
+}
+
+fn compare_parsed(
+    configs: &[DeviceConfig],
+    outputs: Vec<ParseOutput>,
+    spec: &MatchSpec,
+    golden_nodes: Option<&[ConfigNode]>,
+) -> BatchReport {
+    let sliced = outputs
+        .into_iter()
+        .map(|output| Sliced::new(output, spec, golden_nodes))
+        .collect();
+    let devices = configs.iter().map(|c| c.device.clone()).collect();
+    build_report(devices, sliced, spec, golden_nodes)
+}
+
+/// One device's slice, kept in place of its parsed configuration
+#[derive(Debug, Clone)]
+pub(crate) struct Sliced {
+    slice: String,
+    conformance: Option<Conformance>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Sliced {
+    /// Slices a parsed configuration and scores it against the golden snippet
+    pub(crate) fn new(
+        output: ParseOutput,
+        spec: &MatchSpec,
+        golden_nodes: Option<&[ConfigNode]>,
+    ) -> Self {
+        let slice = spec.slice(&output.nodes);
+        Self {
+            conformance: golden_nodes.map(|golden| conformance(golden, &slice)),
+            slice: parser::render(&slice),
+            diagnostics: output.diagnostics,
+        }
+    }
+}
+
+/// Compares slices, given in the same order as their device names
+pub(crate) fn build_report(
+    devices: Vec<String>,
+    sliced: Vec<Sliced>,
+    spec: &MatchSpec,
+    golden_nodes: Option<&[ConfigNode]>,
+) -> BatchReport {
+    let (slices, (scores, diagnostics)): (Vec<String>, (Vec<_>, Vec<_>)) = sliced
+        .into_iter()
+        .map(|sliced| (sliced.slice, (sliced.conformance, sliced.diagnostics)))
+        .unzip();
+
+    let mut variant_of: HashMap<&str, usize> = HashMap::new();
+    let mut counts: Vec<usize> = Vec::new();
+    let variants: Vec<Option<usize>> = slices
+        .iter()
+        .map(|slice| {
+            if slice.is_empty() {
+                return None;
+            }
+            let next = variant_of.len() + 1;
+            let variant = *variant_of.entry(slice.as_str()).or_insert(next);
+            if variant == next {
+                counts.push(0);
+            }
+            counts[variant - 1] += 1;
+            Some(variant)
+        })
+        .collect();
+
+    let (baseline, baseline_label) = if let Some(golden) = golden_nodes {
+        (parser::render(golden), "golden".to_string())
+    } else {
+        // Earliest variant wins ties because max_by_key keeps the last maximum
+        let most_common = counts
+            .iter()
+            .enumerate()
+            .rev()
+            .max_by_key(|(_, count)| **count)
+            .map_or(0, |(index, _)| index + 1);
+        let text = slices
+            .iter()
+            .zip(&variants)
+            .find(|(_, variant)| **variant == Some(most_common))
+            .map(|(slice, _)| slice.clone())
+            .unwrap_or_default();
+        let label = if counts.is_empty() {
+            "none".to_string()
+        } else {
+            format!("variant {most_common}")
+        };
+        (text, label)
+    };
+
+    let devices = devices
+        .into_iter()
+        .zip(slices.iter().zip(variants))
+        .zip(scores.into_iter().zip(diagnostics))
+        .map(|((device, (slice, variant)), (conformance, diagnostics))| {
+            let changes = diff_stats(&baseline, slice);
+            let status = if slice.is_empty() {
+                DeviceStatus::Missing
+            } else if changes.is_empty() {
+                DeviceStatus::Conformant
+            } else {
+                DeviceStatus::Divergent
+            };
+            let diff = (status == DeviceStatus::Divergent)
+                .then(|| unified_diff(&baseline, slice, &baseline_label, &device));
+            DeviceResult {
+                device,
+                status,
+                variant,
+                changes,
+                conformance,
+                diff,
+                diagnostics,
+            }
+        })
+        .collect();
+
+    BatchReport {
+        pattern: spec.pattern().to_string(),
+        baseline: baseline_label,
+        variants: counts.len(),
+        devices,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(device: &str, text: &str) -> DeviceConfig {
+        DeviceConfig {
+            device: device.to_string(),
+            text: text.to_string(),
+        }
+    }
+
+    fn fleet() -> Vec<DeviceConfig> {
+        let standard = "hostname x\nrouter bgp 65000\n neighbor 10.0.0.1 remote-as 65001\n";
+        vec![
+            config("r1", standard),
+            config("r2", standard),
+            config(
+                "r3",
+                "router bgp 65000\n neighbor 10.0.0.9 remote-as 65001\n",
+            ),
+            config("r4", "hostname r4\n"),
+        ]
+    }
+
+    #[test]
+    fn test_compare_against_peers_uses_most_common_variant() {
+        let spec: MatchSpec = "router bgp .*".parse().unwrap();
+
+        let report = compare(&fleet(), &spec, None);
+
+        assert_eq!(report.baseline, "variant 1");
+        assert_eq!(report.variants, 2);
+        assert_eq!(report.count(DeviceStatus::Conformant), 2);
+        assert_eq!(report.count(DeviceStatus::Missing), 1);
+        assert!(report.devices.iter().all(|d| d.conformance.is_none()));
+        let r3 = &report.devices[2];
+        assert_eq!(r3.status, DeviceStatus::Divergent);
+        assert_eq!(r3.variant, Some(2));
+        assert_eq!(
+            r3.changes,
+            DiffStats {
+                added: 1,
+                removed: 1
+            }
+        );
+        assert!(r3.diff.as_deref().unwrap().contains("+++ r3"));
+    }
+
+    #[test]
+    fn test_compare_against_golden_snippet() {
+        let spec: MatchSpec = "router bgp .*".parse().unwrap();
+        let golden = "router bgp 65000\n    neighbor 10.0.0.9 remote-as 65001\n";
+
+        let report = compare(&fleet(), &spec, Some(golden));
+
+        assert_eq!(report.baseline, "golden");
+        assert_eq!(report.count(DeviceStatus::Conformant), 1);
+        assert_eq!(report.count(DeviceStatus::Divergent), 2);
+        assert_eq!(report.devices[2].status, DeviceStatus::Conformant);
+        let scores: Vec<_> = report
+            .devices
+            .iter()
+            .map(|d| d.conformance.as_ref().unwrap().matched)
+            .collect();
+        assert_eq!(scores, [0, 0, 1, 0]);
+    }
+
+    #[test]
+    fn test_compare_reports_parse_diagnostics() {
+        let spec: MatchSpec = "system".parse().unwrap();
+        let configs = vec![
+            config("r1", "system {\n    host-name r1;\n}\n"),
+            config("r2", "system {\n    host-name r1;\n    domain-name ex\n"),
+        ];
+
+        let report = compare(&configs, &spec, None);
+
+        assert!(report.devices[0].diagnostics.is_empty());
+        assert_eq!(report.devices[1].status, DeviceStatus::Conformant);
+        let (device, diagnostic) = report.first_diagnostic().unwrap();
+        assert_eq!(device, "r2");
+        assert_eq!(diagnostic.line, 1);
+        assert_eq!(report.devices[1].diagnostics.len(), 2);
+    }
+
+    /// Treats every line as a top-level statement, like flat `set` syntax
+    struct FlatParser;
+
+    impl ParserPlugin for FlatParser {
+        fn parse(&self, text: &str) -> Result<ParseOutput> {
+            Ok(ParseOutput {
+                nodes: text.lines().map(ConfigNode::new).collect(),
+                diagnostics: Vec::new(),
+            })
+        }
+    }
+
+    #[test]
+    fn test_compare_with_plugin_parser() {
+        let spec: MatchSpec = "set protocols bgp .*".parse().unwrap();
+        let configs = vec![
+            config(
+                "r1",
+                "set system host-name r1\nset protocols bgp group ebgp\n",
+            ),
+            config("r2", "set system host-name r2\n"),
+        ];
+
+        let report = compare_with(&configs, &spec, None, &FlatParser).unwrap();
+
+        assert_eq!(report.devices[0].status, DeviceStatus::Conformant);
+        assert_eq!(report.devices[1].status, DeviceStatus::Missing);
+    }
+
+    #[test]
+    fn test_load_configs_names_devices_by_file_stem() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("r2.cfg"), "hostname r2\n").unwrap();
+        fs::write(dir.path().join("r1.cfg"), "hostname r1\n").unwrap();
+        fs::create_dir(dir.path().join("archive")).unwrap();
+
+        let configs = load_configs(dir.path()).unwrap();
+
+        let devices: Vec<_> = configs.iter().map(|c| c.device.as_str()).collect();
+        assert_eq!(devices, ["r1", "r2"]);
+    }
+}
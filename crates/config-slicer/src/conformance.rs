@@ -0,0 +1,81 @@
+//! Conformance scoring against a golden configuration
+//!
+//! Each top-level section of the golden configuration, with everything
+//! nested beneath it, is one slice. A slice matches when the configuration
+//! has an identical top-level section; the score is the share of golden
+//! slices that match. Sections the golden configuration does not mention do
+//! not affect the score.
+
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+
+use crate::parser::ConfigNode;
+
+/// How much of a golden configuration a configuration contains
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Conformance {
+    /// Golden slices found unchanged
+    pub matched: usize,
+    /// Golden slices checked
+    pub total: usize,
+    /// Matched slices in percent; 100 when the golden configuration is empty
+    pub score: f64,
+    /// First line of each golden slice that did not match
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unmatched: Vec<String>,
+}
+
+/// Scores `actual` against the top-level slices of `golden`
+#[must_use]
+pub fn conformance(golden: &[ConfigNode], actual: &[ConfigNode]) -> Conformance {
+    let unmatched: Vec<String> = golden
+        .iter()
+        .filter(|slice| !actual.contains(slice))
+        .map(|slice| slice.line.clone())
+        .collect();
+    let total = golden.len();
+    let matched = total - unmatched.len();
+    let score = match (matched.to_f64(), total.to_f64()) {
+        (Some(matched), Some(total)) if total > 0.0 => matched * 100.0 / total,
+        _ => 100.0,
+    };
+
+    Conformance {
+        matched,
+        total,
+        score,
+        unmatched,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    #[test]
+    fn test_conformance_counts_matching_top_level_slices() {
+        let golden = parse(
+            "ntp server 10.0.0.1\nrouter bgp 65000\n neighbor 10.0.0.1\nsnmp-server community x\n",
+        );
+        let actual =
+            parse("hostname r1\nntp server 10.0.0.1\nrouter bgp 65000\n neighbor 10.0.0.9\n");
+
+        let result = conformance(&golden, &actual);
+
+        assert_eq!((result.matched, result.total), (1, 3));
+        assert!((result.score - 100.0 / 3.0).abs() < 1e-9);
+        assert_eq!(
+            result.unmatched,
+            ["router bgp 65000", "snmp-server community x"]
+        );
+    }
+
+    #[test]
+    fn test_empty_golden_is_fully_conformant() {
+        let result = conformance(&[], &parse("hostname r1\n"));
+
+        assert_eq!(result.total, 0);
+        assert!((result.score - 100.0).abs() < 1e-9);
+    }
+}
@@ -1,22 +1,79 @@
 //! Configuration diffing
 
-/// Placeholder for config diffing - will be implemented in Milestone 5
-pub mod placeholder {
-    /// Placeholder function
-    #[inline]
-    pub const fn placeholder() {
-        // Implementation pending
+use serde::Serialize;
+use similar::{ChangeTag, TextDiff};
+
+/// Line counts of a diff from an expected to an actual text
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct DiffStats {
+    /// Lines only in the actual text
+    pub added: usize,
+    /// Lines only in the expected text
+    pub removed: usize,
+}
+
+impl DiffStats {
+    /// Returns true if the texts are identical
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.added == 0 && self.removed == 0
     }
 }
 
+/// Counts added and removed lines between two texts
+#[must_use]
+pub fn diff_stats(expected: &str, actual: &str) -> DiffStats {
+    TextDiff::from_lines(expected, actual)
+        .iter_all_changes()
+        .fold(DiffStats::default(), |mut stats, change| {
+            match change.tag() {
+                ChangeTag::Insert => stats.added += 1,
+                ChangeTag::Delete => stats.removed += 1,
+                ChangeTag::Equal => {}
+            }
+            stats
+        })
+}
+
+/// Renders a unified diff from `expected` to `actual`
+#[must_use]
+pub fn unified_diff(
+    expected: &str,
+    actual: &str,
+    expected_label: &str,
+    actual_label: &str,
+) -> String {
+    TextDiff::from_lines(expected, actual)
+        .unified_diff()
+        .header(expected_label, actual_label)
+        .to_string()
+}
+
 #[cfg(test)]
 mod tests {
-    // Arrange-Act-Assert: exercise the placeholder path for coverage
+    use super::*;
+
     #[test]
-    fn test_placeholder_diff() {
-        super::placeholder::placeholder();
-        assert!(true);
+    fn test_diff_stats_counts_changed_lines() {
+        let stats = diff_stats("a\nb\nc\n", "a\nx\nc\nd\n");
+
+        assert_eq!(
+            stats,
+            DiffStats {
+                added: 2,
+                removed: 1
+            }
+        );
+        assert!(diff_stats("a\n", "a\n").is_empty());
     }
 
-    // Reverted temporary failure used to validate status reporting
+    #[test]
+    fn test_unified_diff_includes_labels() {
+        let diff = unified_diff("a\n", "b\n", "golden", "r1");
+
+        assert!(diff.contains("--- golden"));
+        assert!(diff.contains("+++ r1"));
+        assert!(diff.contains("-a"));
+        assert!(diff.contains("+b"));
+    }
 }
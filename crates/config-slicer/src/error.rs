@@ -22,6 +22,10 @@ pub enum ConfigSlicerError {
     #[error("Parsing error: {0}")]
     Parse(String),
 
+    /// Parser plugin error
+    #[error("Plugin error: {0}")]
+    Plugin(String),
+
     /// Slicing error
     #[error("Slicing error: {0}")]
     Slice(String),
@@ -0,0 +1,334 @@
+//! Interactive pattern refinement
+//!
+//! A [`Session`] previews patterns against one configuration as they are
+//! typed: how many lines the pattern matches, how much of the configuration
+//! the slice covers, and where the first matches are. Once the pattern
+//! selects the right section, `:export` writes the slice to a file, saving
+//! a round trip through batch mode for every attempt.
+
+use serde::Serialize;
+use std::fmt;
+use std::io::{BufRead, Write};
+use std::path::Path;
+
+use crate::error::Result;
+use crate::parser::{self, ConfigNode};
+use crate::slicer::MatchSpec;
+
+/// Matches shown per preview unless changed with `--limit` or `:limit`
+pub const DEFAULT_PREVIEW_LIMIT: usize = 5;
+
+/// Prompt shown before each input line
+pub const PROMPT: &str = "slice> ";
+
+const HELP: &str = "\
+Enter a pattern to preview it, or a command:
+  :show           print the slice of the current pattern
+  :export <FILE>  write the slice of the current pattern to FILE
+  :limit <N>      show the first N matches in previews
+  :help           show this help
+  :quit           leave (also Ctrl-D)
+";
+
+/// A line matched by the last level of a pattern
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Match {
+    /// 1-based line number in the configuration, if the line was found
+    pub line: Option<usize>,
+    /// Parent lines followed by the matched line
+    pub path: Vec<String>,
+}
+
+/// What a pattern selects from a configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Preview {
+    /// Pattern previewed
+    pub pattern: String,
+    /// Number of lines matched by the last level
+    pub count: usize,
+    /// Lines in the rendered slice, including parents and children
+    pub slice_lines: usize,
+    /// The first matches
+    pub matches: Vec<Match>,
+}
+
+impl Preview {
+    /// Previews `spec` against parsed `nodes`, listing at most `limit` matches
+    ///
+    /// `text` is the configuration the nodes were parsed from; matches are
+    /// located in it by their normalized line text.
+    #[must_use]
+    pub fn new(spec: &MatchSpec, text: &str, nodes: &[ConfigNode], limit: usize) -> Self {
+        let paths = spec.matches(nodes);
+        let lines = locate(text, &paths);
+        let matches = paths
+            .iter()
+            .zip(lines)
+            .take(limit)
+            .map(|(path, line)| Match {
+                line,
+                path: path.clone(),
+            })
+            .collect();
+        Self {
+            pattern: spec.pattern().to_string(),
+            count: paths.len(),
+            slice_lines: parser::render(&spec.slice(nodes)).lines().count(),
+            matches,
+        }
+    }
+}
+
+impl fmt::Display for Preview {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let plural = if self.count == 1 { "" } else { "es" };
+        writeln!(
+            f,
+            "{} match{plural}, {} lines in slice",
+            self.count, self.slice_lines
+        )?;
+        for found in &self.matches {
+            let line = found
+                .line
+                .map_or_else(|| "-".to_string(), |line| line.to_string());
+            writeln!(f, "{line:>6}  {}", found.path.join(" > "))?;
+        }
+        let hidden = self.count - self.matches.len();
+        if hidden > 0 {
+            writeln!(f, "        ... and {hidden} more")?;
+        }
+        Ok(())
+    }
+}
+
+/// Finds the line number of the last line of each path
+///
+/// Paths must be in configuration order. Each is searched for after the
+/// previous match, skipping the parents it shares with the previous path,
+/// so repeated lines such as `description` resolve to the right section.
+fn locate(text: &str, paths: &[Vec<String>]) -> Vec<Option<usize>> {
+    let lines: Vec<&str> = text.lines().map(normalize).collect();
+    let mut cursor = 0;
+    let mut previous: &[String] = &[];
+    paths
+        .iter()
+        .map(|path| {
+            let shared = path
+                .iter()
+                .zip(previous)
+                .take_while(|(a, b)| a == b)
+                .count()
+                .min(path.len() - 1);
+            previous = path;
+            let mut found = None;
+            for wanted in &path[shared..] {
+                let offset = lines[cursor..].iter().position(|line| line == wanted)?;
+                cursor += offset + 1;
+                found = Some(cursor);
+            }
+            found
+        })
+        .collect()
+}
+
+/// A source line as the parsers store it
+fn normalize(line: &str) -> &str {
+    let line = line.trim();
+    line.strip_suffix('{').map_or(line, str::trim_end)
+}
+
+/// Result of one line of input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reply {
+    /// Text to show before the next prompt
+    Output(String),
+    /// End the session
+    Quit,
+}
+
+/// Pattern refinement against one configuration
+pub struct Session<'a> {
+    text: &'a str,
+    nodes: Vec<ConfigNode>,
+    limit: usize,
+    current: Option<MatchSpec>,
+}
+
+impl<'a> Session<'a> {
+    /// Starts a session on configuration `text` parsed into `nodes`
+    #[must_use]
+    pub const fn new(text: &'a str, nodes: Vec<ConfigNode>, limit: usize) -> Self {
+        Self {
+            text,
+            nodes,
+            limit,
+            current: None,
+        }
+    }
+
+    /// The last pattern that parsed, which `:show` and `:export` use
+    #[must_use]
+    pub const fn current(&self) -> Option<&MatchSpec> {
+        self.current.as_ref()
+    }
+
+    /// Handles one line of input
+    ///
+    /// Invalid patterns and failed commands are reported in the reply and
+    /// leave the current pattern unchanged.
+    pub fn handle(&mut self, input: &str) -> Reply {
+        let input = input.trim();
+        let (command, argument) = input
+            .split_once(char::is_whitespace)
+            .map_or((input, ""), |(command, argument)| {
+                (command, argument.trim())
+            });
+        let output = match command {
+            "" => String::new(),
+            ":quit" | ":q" => return Reply::Quit,
+            ":help" => HELP.to_string(),
+            ":limit" => match argument.parse() {
+                Ok(limit) => {
+                    self.limit = limit;
+                    format!("Showing up to {limit} matches\n")
+                }
+                Err(_) => format!("Invalid limit {argument:?}\n"),
+            },
+            ":show" => self.with_current(|_, slice| slice),
+            ":export" if argument.is_empty() => "Usage: :export <FILE>\n".to_string(),
+            ":export" => self.with_current(|spec, slice| export(spec, &slice, Path::new(argument))),
+            _ if command.starts_with(':') => {
+                format!("Unknown command {command}; see :help\n")
+            }
+            _ => match input.parse::<MatchSpec>() {
+                Ok(spec) => {
+                    let preview = Preview::new(&spec, self.text, &self.nodes, self.limit);
+                    self.current = Some(spec);
+                    preview.to_string()
+                }
+                Err(e) => format!("{e}\n"),
+            },
+        };
+        Reply::Output(output)
+    }
+
+    fn with_current(&self, action: impl FnOnce(&MatchSpec, String) -> String) -> String {
+        match &self.current {
+            Some(spec) => action(spec, parser::render(&spec.slice(&self.nodes))),
+            None => "No pattern yet; enter one first\n".to_string(),
+        }
+    }
+}
+
+fn export(spec: &MatchSpec, slice: &str, path: &Path) -> String {
+    match std::fs::write(path, slice) {
+        Ok(()) => format!(
+            "Wrote {} lines to {}\nPattern: {}\n",
+            slice.lines().count(),
+            path.display(),
+            spec.pattern()
+        ),
+        Err(e) => format!("Failed to write {}: {e}\n", path.display()),
+    }
+}
+
+/// Runs a session until `:quit` or the end of `input`
+///
+/// # Errors
+/// Returns an error if reading input or writing output fails.
+pub fn run(
+    session: &mut Session<'_>,
+    mut input: impl BufRead,
+    mut output: impl Write,
+) -> Result<()> {
+    let mut line = String::new();
+    loop {
+        write!(output, "{PROMPT}")?;
+        output.flush()?;
+        line.clear();
+        if input.read_line(&mut line)? == 0 {
+            writeln!(output)?;
+            return Ok(());
+        }
+        match session.handle(&line) {
+            Reply::Output(text) => write!(output, "{text}")?,
+            Reply::Quit => return Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IOS: &str = "hostname r1\n!\ninterface Gi0/1\n description uplink\n!\ninterface Gi0/2\n description access\n shutdown\n!\ninterface Gi0/3\n description access\n";
+    const JUNOS: &str = "interfaces {\n    ge-0/0/0 {\n        description uplink;\n    }\n    lo0 {\n        description loopback;\n    }\n}\n";
+
+    fn session(text: &str) -> Session<'_> {
+        Session::new(text, parser::parse(text), DEFAULT_PREVIEW_LIMIT)
+    }
+
+    #[test]
+    fn test_preview_locates_matches() {
+        let spec: MatchSpec = "interface .*||description access".parse().unwrap();
+
+        let preview = Preview::new(&spec, IOS, &parser::parse(IOS), 1);
+
+        assert_eq!(preview.count, 2);
+        assert_eq!(preview.slice_lines, 4);
+        assert_eq!(preview.matches.len(), 1);
+        assert_eq!(preview.matches[0].line, Some(7));
+        assert_eq!(
+            preview.to_string(),
+            "2 matches, 4 lines in slice\n     7  interface Gi0/2 > description access\n        ... and 1 more\n"
+        );
+    }
+
+    #[test]
+    fn test_preview_locates_brace_matches() {
+        let spec: MatchSpec = "interfaces||*||description .*".parse().unwrap();
+
+        let preview = Preview::new(&spec, JUNOS, &parser::parse(JUNOS), 5);
+
+        let lines: Vec<Option<usize>> = preview.matches.iter().map(|m| m.line).collect();
+        assert_eq!(lines, [Some(3), Some(6)]);
+    }
+
+    #[test]
+    fn test_invalid_pattern_keeps_current() {
+        let mut session = session(IOS);
+
+        session.handle("interface Gi0/1");
+        let reply = session.handle("interface (");
+
+        assert!(matches!(reply, Reply::Output(text) if text.contains("Invalid level")));
+        assert_eq!(session.current().unwrap().pattern(), "interface Gi0/1");
+        assert_eq!(
+            session.handle(":show"),
+            Reply::Output("interface Gi0/1\n  description uplink\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_export_writes_current_slice() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("slice.cfg");
+        let mut session = session(IOS);
+
+        let before = session.handle(&format!(":export {}", path.display()));
+        session.handle(":limit 0");
+        session.handle("interface Gi0/2");
+        let after = session.handle(&format!(":export {}", path.display()));
+
+        assert_eq!(
+            before,
+            Reply::Output("No pattern yet; enter one first\n".to_string())
+        );
+        assert!(matches!(after, Reply::Output(text) if text.starts_with("Wrote 3 lines")));
+        assert_eq!(
+            std::fs::read_to_string(path).unwrap(),
+            "interface Gi0/2\n  description access\n  shutdown\n"
+        );
+        assert_eq!(session.handle(":q"), Reply::Quit);
+    }
+}
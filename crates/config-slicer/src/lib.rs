@@ -1,9 +1,25 @@
 //! Config-slicer library: exposes CLI parsing and run for reuse in tests/integration.
 
-use anyhow::Result;
-use clap::Parser;
+use anyhow::{Context, Result};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
 use tracing::{info, warn};
 
+pub mod batch;
+pub mod conformance;
+pub mod diff;
+pub mod error;
+pub mod interactive;
+pub mod parallel;
+pub mod parser;
+pub mod plugin;
+pub mod report;
+pub mod slicer;
+
+use batch::DeviceStatus;
+use plugin::{ParserPlugin, PluginRegistry};
+use slicer::MatchSpec;
+
 #[derive(Parser, Debug)]
 #[command(name = "config-slicer")]
 #[command(about = "Network configuration slicing and diffing tool")]
@@ -12,6 +28,134 @@ pub struct Cli {
     /// Enable verbose logging
     #[arg(short, long)]
     pub verbose: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Config-slicer subcommands
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Slice every configuration in a directory and compare the slices
+    Batch(BatchArgs),
+    /// Refine a pattern against one configuration with a live preview
+    Interactive(InteractiveArgs),
+    /// List the available parsers, including plugins
+    Parsers(ParsersArgs),
+}
+
+/// Plugin options shared by subcommands
+#[derive(Args, Debug, Default)]
+pub struct PluginArgs {
+    /// Load every `*.wasm` parser plugin in this directory
+    #[arg(long, value_name = "DIR")]
+    pub plugin_dir: Option<PathBuf>,
+}
+
+impl PluginArgs {
+    /// Builds a registry with the built-in parsers and any plugins
+    ///
+    /// # Errors
+    /// Returns an error if a plugin cannot be loaded.
+    pub fn registry(&self) -> Result<PluginRegistry> {
+        let mut registry = PluginRegistry::with_builtins();
+        if let Some(dir) = &self.plugin_dir {
+            let loaded = registry
+                .load_dir(dir)
+                .with_context(|| format!("Failed to load plugins from {}", dir.display()))?;
+            info!("Loaded parser plugins: {}", loaded.join(", "));
+        }
+        Ok(registry)
+    }
+}
+
+/// Arguments for listing parsers
+#[derive(Args, Debug)]
+pub struct ParsersArgs {
+    #[command(flatten)]
+    pub plugins: PluginArgs,
+}
+
+/// Arguments for batch mode
+#[derive(Args, Debug)]
+pub struct BatchArgs {
+    /// Slice pattern, levels separated by `||` (e.g. "router bgp .*")
+    #[arg(long = "match", value_name = "PATTERN")]
+    pub pattern: String,
+
+    /// Directory containing one configuration file per device
+    pub dir: PathBuf,
+
+    /// Expected slice; without it devices are compared against each other
+    #[arg(long)]
+    pub golden: Option<PathBuf>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = ReportFormat::Text)]
+    pub format: ReportFormat,
+
+    /// Print a unified diff for each divergent device (text format)
+    #[arg(long)]
+    pub show_diffs: bool,
+
+    /// Exit with an error if any device is divergent or missing the slice
+    #[arg(long)]
+    pub fail_on_divergence: bool,
+
+    /// Skip malformed lines and report them instead of failing
+    #[arg(long)]
+    pub tolerant: bool,
+
+    /// Parser for the configurations and golden snippet (see `parsers`)
+    #[arg(long, default_value = plugin::DEFAULT_PARSER)]
+    pub parser: String,
+
+    /// Worker threads slicing configurations; 0 uses one per CPU core
+    #[arg(long, default_value_t = 0)]
+    pub jobs: usize,
+
+    /// MiB of configuration text read at a time
+    #[arg(
+        long,
+        value_name = "MIB",
+        default_value_t = parallel::DEFAULT_CHUNK_BYTES / (1024 * 1024),
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub chunk_size: u64,
+
+    /// Print progress to stderr after each chunk
+    #[arg(long)]
+    pub progress: bool,
+
+    #[command(flatten)]
+    pub plugins: PluginArgs,
+}
+
+/// Arguments for interactive mode
+#[derive(Args, Debug)]
+pub struct InteractiveArgs {
+    /// Configuration file to try patterns against
+    pub config: PathBuf,
+
+    /// Number of matches listed in each preview
+    #[arg(long, default_value_t = interactive::DEFAULT_PREVIEW_LIMIT)]
+    pub limit: usize,
+
+    /// Parser for the configuration (see `parsers`)
+    #[arg(long, default_value = plugin::DEFAULT_PARSER)]
+    pub parser: String,
+
+    #[command(flatten)]
+    pub plugins: PluginArgs,
+}
+
+/// Batch report output formats
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// Aligned table
+    Text,
+    /// JSON document including diffs
+    Json,
 }
 
 /// Execute the CLI logic with a parsed `Cli`.
@@ -26,7 +170,119 @@ pub fn run_with(cli: &Cli) -> Result<()> {
         info!("Starting config-slicer CLI in verbose mode");
     }
 
-    warn!("config-slicer implementation not yet complete");
+    match &cli.command {
+        Some(Command::Batch(args)) => run_batch(args),
+        Some(Command::Interactive(args)) => run_interactive(args),
+        Some(Command::Parsers(args)) => {
+            for name in args.plugins.registry()?.names() {
+                println!("{name}");
+            }
+            Ok(())
+        }
+        None => {
+            warn!("No command given; see --help");
+            Ok(())
+        }
+    }
+}
+
+fn run_batch(args: &BatchArgs) -> Result<()> {
+    let spec: MatchSpec = args.pattern.parse()?;
+    let parser = args.plugins.registry()?.get(&args.parser)?;
+    let files = parallel::list_configs(&args.dir)
+        .with_context(|| format!("Failed to read configs from {}", args.dir.display()))?;
+    let golden = args
+        .golden
+        .as_ref()
+        .map(|path| {
+            std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read golden snippet {}", path.display()))
+        })
+        .transpose()?;
+    if let Some(golden) = golden.as_deref() {
+        check_golden(golden, args.tolerant, parser.as_ref())?;
+    }
+
+    let options = parallel::BatchOptions {
+        jobs: args.jobs,
+        chunk_bytes: args.chunk_size.saturating_mul(1024 * 1024),
+    };
+    let mut progress = |progress: parallel::Progress| {
+        if args.progress {
+            eprintln!(
+                "Sliced {}/{} configs ({} of {} MiB)",
+                progress.files,
+                progress.total_files,
+                progress.bytes / (1024 * 1024),
+                progress.total_bytes / (1024 * 1024)
+            );
+        }
+    };
+    let report = parallel::compare_files(
+        &files,
+        &spec,
+        golden.as_deref(),
+        parser.as_ref(),
+        options,
+        &mut progress,
+    )?;
+    if !args.tolerant {
+        if let Some((device, diagnostic)) = report.first_diagnostic() {
+            anyhow::bail!("{device}: {diagnostic} (use --tolerant to skip malformed lines)");
+        }
+    }
+    match args.format {
+        ReportFormat::Text => print!("{}", report::render_text(&report, args.show_diffs)),
+        ReportFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+
+    let nonconformant = report.devices.len() - report.count(DeviceStatus::Conformant);
+    if args.fail_on_divergence && nonconformant > 0 {
+        anyhow::bail!(
+            "{nonconformant} of {} devices do not conform",
+            report.devices.len()
+        );
+    }
+    Ok(())
+}
+
+fn run_interactive(args: &InteractiveArgs) -> Result<()> {
+    let parser = args.plugins.registry()?.get(&args.parser)?;
+    let text = std::fs::read_to_string(&args.config)
+        .with_context(|| format!("Failed to read {}", args.config.display()))?;
+    let output = parser
+        .parse(&text)
+        .with_context(|| format!("Failed to parse {}", args.config.display()))?;
+    for diagnostic in &output.diagnostics {
+        warn!("{}: {diagnostic}", args.config.display());
+    }
+
+    println!(
+        "Loaded {} ({} lines); enter a pattern, or :help",
+        args.config.display(),
+        text.lines().count()
+    );
+    let mut session = interactive::Session::new(&text, output.nodes, args.limit);
+    interactive::run(
+        &mut session,
+        std::io::stdin().lock(),
+        std::io::stdout().lock(),
+    )?;
+    Ok(())
+}
+
+fn check_golden(golden: &str, tolerant: bool, parser: &dyn ParserPlugin) -> Result<()> {
+    let output = parser
+        .parse(golden)
+        .context("Failed to parse golden snippet")?;
+    if tolerant {
+        for diagnostic in output.diagnostics {
+            warn!("Golden snippet {diagnostic}");
+        }
+    } else if let Some(diagnostic) = output.diagnostics.first() {
+        return Err(error::ConfigSlicerError::Parse(diagnostic.to_string()))
+            .context("Golden snippet is malformed");
+    }
     Ok(())
 }
 
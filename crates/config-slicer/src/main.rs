@@ -1,5 +1,8 @@
 //! Configuration Slicer CLI Tool (binary shim)
 
 fn main() {
-    let _ = config_slicer::run(std::env::args_os());
+    if let Err(e) = config_slicer::run(std::env::args_os()) {
+        eprintln!("Error: {e:#}");
+        std::process::exit(1);
+    }
 }
@@ -0,0 +1,246 @@
+//! Parallel batch slicing for large fleets
+//!
+//! [`compare_files`] reads, parses, and slices configuration files on a
+//! bounded pool of worker threads and produces the same report as
+//! [`batch::compare_with`]. Files are processed in chunks holding at most
+//! [`BatchOptions::chunk_bytes`] of configuration text, and only each
+//! device's slice outlives its chunk, so memory use follows the chunk size
+//! rather than the size of the fleet. Progress is reported once per chunk.
+
+use rayon::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::batch::{self, BatchReport, Sliced};
+use crate::error::{ConfigSlicerError, Result};
+use crate::parser::ConfigNode;
+use crate::plugin::ParserPlugin;
+use crate::slicer::MatchSpec;
+
+/// Configuration text read per chunk when no limit is given (64 MiB)
+pub const DEFAULT_CHUNK_BYTES: u64 = 64 * 1024 * 1024;
+
+/// A device configuration file, not yet read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigFile {
+    /// Device name, taken from the file stem
+    pub device: String,
+    /// Path to the configuration
+    pub path: PathBuf,
+    /// File size in bytes when listed
+    pub size: u64,
+}
+
+/// How a batch is spread over worker threads
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchOptions {
+    /// Worker threads; 0 uses one per CPU core
+    pub jobs: usize,
+    /// Configuration text read per chunk; a larger file forms a chunk alone
+    pub chunk_bytes: u64,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self {
+            jobs: 0,
+            chunk_bytes: DEFAULT_CHUNK_BYTES,
+        }
+    }
+}
+
+/// Files and bytes processed so far in a batch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    /// Files sliced
+    pub files: usize,
+    /// Files in the batch
+    pub total_files: usize,
+    /// Bytes of configuration sliced
+    pub bytes: u64,
+    /// Bytes of configuration in the batch
+    pub total_bytes: u64,
+}
+
+/// Lists every regular file in a directory as one device configuration,
+/// ordered by device name
+///
+/// # Errors
+/// Returns an I/O error if the directory or a file's metadata cannot be read.
+pub fn list_configs(dir: &Path) -> Result<Vec<ConfigFile>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let metadata = fs::metadata(&path)?;
+        if !metadata.is_file() {
+            continue;
+        }
+        files.push(ConfigFile {
+            device: batch::device_name(&path),
+            path,
+            size: metadata.len(),
+        });
+    }
+    files.sort_by(|a, b| a.device.cmp(&b.device));
+    Ok(files)
+}
+
+/// Slices every file and compares the slices like [`batch::compare_with`],
+/// calling `progress` after each chunk
+///
+/// # Errors
+/// Returns an error if the worker pool cannot be started, a file cannot be
+/// read, or the parser fails, naming the device.
+pub fn compare_files(
+    files: &[ConfigFile],
+    spec: &MatchSpec,
+    golden: Option<&str>,
+    parser: &dyn ParserPlugin,
+    options: BatchOptions,
+    progress: &mut dyn FnMut(Progress),
+) -> Result<BatchReport> {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(options.jobs)
+        .thread_name(|index| format!("config-slicer-{index}"))
+        .build()
+        .map_err(|e| ConfigSlicerError::Other(format!("Failed to start workers: {e}")))?;
+    let golden_nodes = golden
+        .map(|golden| parser.parse(golden).map(|output| output.nodes))
+        .transpose()?;
+
+    let mut done = Progress {
+        files: 0,
+        total_files: files.len(),
+        bytes: 0,
+        total_bytes: files.iter().map(|file| file.size).sum(),
+    };
+    let mut sliced = Vec::with_capacity(files.len());
+    for chunk in chunks(files, options.chunk_bytes) {
+        let results = pool.install(|| {
+            chunk
+                .par_iter()
+                .map(|file| slice_file(file, spec, golden_nodes.as_deref(), parser))
+                .collect::<Result<Vec<_>>>()
+        })?;
+        sliced.extend(results);
+        done.files += chunk.len();
+        done.bytes += chunk.iter().map(|file| file.size).sum::<u64>();
+        progress(done);
+    }
+
+    let devices = files.iter().map(|file| file.device.clone()).collect();
+    Ok(batch::build_report(
+        devices,
+        sliced,
+        spec,
+        golden_nodes.as_deref(),
+    ))
+}
+
+fn slice_file(
+    file: &ConfigFile,
+    spec: &MatchSpec,
+    golden_nodes: Option<&[ConfigNode]>,
+    parser: &dyn ParserPlugin,
+) -> Result<Sliced> {
+    let text = fs::read_to_string(&file.path).map_err(|e| {
+        ConfigSlicerError::Other(format!("Failed to read {}: {e}", file.path.display()))
+    })?;
+    let output = parser
+        .parse(&text)
+        .map_err(|e| ConfigSlicerError::Parse(format!("Failed to parse {}: {e}", file.device)))?;
+    Ok(Sliced::new(output, spec, golden_nodes))
+}
+
+/// Splits files, in order, into runs totalling at most `max_bytes`
+fn chunks(files: &[ConfigFile], max_bytes: u64) -> Vec<&[ConfigFile]> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    let mut bytes = 0;
+    for (index, file) in files.iter().enumerate() {
+        if index > start && bytes + file.size > max_bytes {
+            chunks.push(&files[start..index]);
+            start = index;
+            bytes = 0;
+        }
+        bytes += file.size;
+    }
+    if start < files.len() {
+        chunks.push(&files[start..]);
+    }
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::{DEFAULT_PARSER, PluginRegistry};
+
+    fn file(device: &str, size: u64) -> ConfigFile {
+        ConfigFile {
+            device: device.to_string(),
+            path: PathBuf::from(format!("{device}.cfg")),
+            size,
+        }
+    }
+
+    #[test]
+    fn test_chunks_respect_byte_limit() {
+        let files = [
+            file("r1", 40),
+            file("r2", 40),
+            file("r3", 150),
+            file("r4", 10),
+        ];
+
+        let sizes: Vec<usize> = chunks(&files, 100).iter().map(|c| c.len()).collect();
+
+        assert_eq!(sizes, [2, 1, 1]);
+        assert!(chunks(&[], 100).is_empty());
+    }
+
+    #[test]
+    fn test_compare_files_matches_sequential_compare() {
+        let dir = tempfile::tempdir().unwrap();
+        let standard = "hostname x\nrouter bgp 65000\n neighbor 10.0.0.1 remote-as 65001\n";
+        for index in 0..20 {
+            let text = if index % 5 == 0 {
+                "router bgp 65000\n neighbor 10.0.0.9 remote-as 65001\n"
+            } else {
+                standard
+            };
+            fs::write(dir.path().join(format!("r{index:02}.cfg")), text).unwrap();
+        }
+        let spec: MatchSpec = "router bgp .*".parse().unwrap();
+        let parser = PluginRegistry::with_builtins().get(DEFAULT_PARSER).unwrap();
+
+        let files = list_configs(dir.path()).unwrap();
+        let options = BatchOptions {
+            jobs: 4,
+            chunk_bytes: 200,
+        };
+        let mut updates = Vec::new();
+        let report = compare_files(
+            &files,
+            &spec,
+            None,
+            parser.as_ref(),
+            options,
+            &mut |p: Progress| {
+                updates.push(p);
+            },
+        )
+        .unwrap();
+
+        let configs = batch::load_configs(dir.path()).unwrap();
+        let expected = batch::compare(&configs, &spec, None);
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            serde_json::to_value(&expected).unwrap()
+        );
+        assert!(updates.len() > 1);
+        let last = updates.last().unwrap();
+        assert_eq!(last.files, 20);
+        assert_eq!(last.bytes, last.total_bytes);
+    }
+}
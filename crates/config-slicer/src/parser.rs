@@ -1,19 +0,0 @@
-//! Configuration parsers
-
-/// Placeholder for config parsers - will be implemented in Milestone 5
-pub mod placeholder {
-    /// Placeholder function
-    #[inline]
-    pub const fn placeholder() {
-        // Implementation pending
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    #[test]
-    fn test_placeholder_parser() {
-        super::placeholder::placeholder();
-        assert!(true);
-    }
-}
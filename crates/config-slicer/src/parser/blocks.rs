@@ -0,0 +1,100 @@
+//! Multi-line blocks kept verbatim as the body of their first line
+
+use super::{Diagnostic, is_comment};
+
+/// How a multi-line block ends
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum BlockEnd {
+    /// A line holding the delimiter, such as `^C` after `banner motd ^C`
+    Delimiter(String),
+    /// A line that is this word once trimmed, such as `EOF` or `quit`
+    Word(&'static str),
+    /// A line closing the open `"` string
+    Quote,
+    /// A line closing the open `/*` comment
+    Comment,
+}
+
+impl BlockEnd {
+    pub(super) fn closes(&self, line: &str) -> bool {
+        match self {
+            Self::Delimiter(delimiter) => line.contains(delimiter.as_str()),
+            Self::Word(word) => line.trim() == *word,
+            Self::Quote => quotes(line) % 2 == 1,
+            Self::Comment => line.contains("*/"),
+        }
+    }
+}
+
+/// Counts the `"` in a line that are not escaped by `\`
+fn quotes(line: &str) -> usize {
+    let mut count = 0;
+    let mut escaped = false;
+    for c in line.chars() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == '"' {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Returns how the block an indented line opens ends, if it opens one;
+/// `next` is the raw line after it
+pub(super) fn indented_block(line: &str, next: Option<&str>) -> Option<BlockEnd> {
+    if let Some(rest) = line.strip_prefix("banner ") {
+        let text = rest
+            .trim_start()
+            .split_once(char::is_whitespace)
+            .map_or("", |(_, text)| text.trim_start());
+        if text.is_empty() {
+            return Some(BlockEnd::Word("EOF"));
+        }
+        let delimiter = if text.starts_with("^C") {
+            "^C"
+        } else {
+            &text[..text.chars().next()?.len_utf8()]
+        };
+        return (!text[delimiter.len()..].contains(delimiter))
+            .then(|| BlockEnd::Delimiter(delimiter.to_string()));
+    }
+    let is_data = |line: &str| {
+        let line = line.trim();
+        !line.is_empty() && line.chars().all(|c| c.is_ascii_hexdigit() || c == ' ')
+    };
+    (line.starts_with("certificate ") && next.is_some_and(is_data))
+        .then_some(BlockEnd::Word("quit"))
+}
+
+/// Returns how the block a brace-delimited line opens ends, if it opens one
+pub(super) fn brace_block(line: &str) -> Option<BlockEnd> {
+    if let Some(comment) = line.strip_prefix("/*") {
+        return (!comment.contains("*/")).then_some(BlockEnd::Comment);
+    }
+    (!is_comment(line) && quotes(line) % 2 == 1).then_some(BlockEnd::Quote)
+}
+
+/// Takes the lines after the first of a block through the one that ends it,
+/// reporting a block the text ends inside
+fn take_block<'a>(
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+    end: &BlockEnd,
+    (number, first): (usize, &str),
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Vec<String> {
+    let mut body = Vec::new();
+    for (_, raw) in lines {
+        body.push(raw.to_string());
+        if end.closes(raw) {
+            return body;
+        }
+    }
+    diagnostics.push(Diagnostic {
+        line: number,
+        reason: format!("multi-line block '{first}' is never closed (truncated configuration?)"),
+    });
+    body
+}
@@ -0,0 +1,452 @@
+//! Configuration parsers
+//!
+//! Device configurations are parsed into a tree of lines. Brace-delimited
+//! configurations (`JunOS`) nest by `{`/`}`; all others nest by indentation
+//! (`IOS`, `EOS`, `NX-OS`). Blank lines and `!` separators are dropped.
+//!
+//! Multi-line blocks whose lines are text rather than configuration become
+//! one node, with the lines after the first kept verbatim as its
+//! [`body`](ConfigNode::body) so they neither nest nor get normalized:
+//!
+//! - banners, through the line holding their delimiter (`banner motd ^C`),
+//!   or through `EOF` when no delimiter is given (`EOS`)
+//! - certificates, from `certificate ...` over the hex data through `quit`
+//! - in brace-delimited configurations, `/* ... */` annotations and quoted
+//!   strings spanning lines, through the line that closes them
+//!
+//! Parsing is error tolerant: lines that cannot be parsed, such as garbled
+//! bytes or `JunOS` statements broken by a line wrap, are skipped and
+//! reported as [`Diagnostic`]s alongside the partial tree. [`parse_strict`]
+//! fails on the first one instead.
+
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Write as _};
+
+use crate::error::{ConfigSlicerError, Result};
+
+mod blocks;
+
+use blocks::{brace_block, indented_block, take_block};
+
+/// A configuration line and the lines nested beneath it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigNode {
+    /// Line text without indentation or a trailing `{`
+    pub line: String,
+    /// Nested lines
+    #[serde(default)]
+    pub children: Vec<Self>,
+    /// Lines of a multi-line block after the first, as written
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub body: Vec<String>,
+}
+
+impl ConfigNode {
+    /// Creates a node without children
+    #[must_use]
+    pub fn new(line: impl Into<String>) -> Self {
+        Self {
+            line: line.into(),
+            children: Vec::new(),
+            body: Vec::new(),
+        }
+    }
+
+    fn render_into(&self, depth: usize, out: &mut String) {
+        let _ = writeln!(out, "{:indent$}{}", "", self.line, indent = depth * 2);
+        for line in &self.body {
+            let _ = writeln!(out, "{line}");
+        }
+        for child in &self.children {
+            child.render_into(depth + 1, out);
+        }
+    }
+}
+
+/// A problem found while parsing
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    /// 1-based line number
+    pub line: usize,
+    /// What is wrong with the line
+    pub reason: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.reason)
+    }
+}
+
+/// Parsed configuration with the problems found along the way
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct ParseOutput {
+    /// Top-level nodes, without the skipped lines
+    pub nodes: Vec<ConfigNode>,
+    /// Problems ordered by line number
+    #[serde(default)]
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// How configuration lines nest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Syntax {
+    /// Nesting by indentation (`IOS`, `EOS`, `NX-OS`)
+    Indented,
+    /// Nesting by `{`/`}` (`JunOS`)
+    Braces,
+}
+
+impl Syntax {
+    /// Guesses the syntax: brace-delimited if any line outside a banner or
+    /// certificate ends in `{`
+    #[must_use]
+    pub fn detect(text: &str) -> Self {
+        let mut lines = text.lines().peekable();
+        while let Some(line) = lines.next() {
+            let line = line.trim();
+            if let Some(end) = indented_block(line, lines.peek().copied()) {
+                for line in lines.by_ref() {
+                    if end.closes(line) {
+                        break;
+                    }
+                }
+            } else if line.ends_with('{') {
+                return Self::Braces;
+            }
+        }
+        Self::Indented
+    }
+}
+
+/// Parses configuration text into top-level nodes, skipping malformed lines
+#[must_use]
+pub fn parse(text: &str) -> Vec<ConfigNode> {
+    parse_tolerant(text).nodes
+}
+
+/// Parses configuration text, reporting malformed lines
+///
+/// Malformed lines are skipped and parsing continues with the next line.
+/// Blocks still open at the end of a brace-delimited configuration are kept
+/// but reported, since the configuration is probably truncated.
+#[must_use]
+pub fn parse_tolerant(text: &str) -> ParseOutput {
+    parse_syntax(text, Syntax::detect(text))
+}
+
+/// Parses configuration text with the given syntax, reporting malformed lines
+#[must_use]
+pub fn parse_syntax(text: &str, syntax: Syntax) -> ParseOutput {
+    let mut diagnostics = Vec::new();
+    let nodes = match syntax {
+        Syntax::Braces => parse_braces(text, &mut diagnostics),
+        Syntax::Indented => parse_indented(text, &mut diagnostics),
+    };
+    diagnostics.sort_by_key(|diagnostic| diagnostic.line);
+    ParseOutput { nodes, diagnostics }
+}
+
+/// Parses configuration text, failing on the first malformed line
+///
+/// # Errors
+/// Returns a parse error naming the line if any line is malformed or a block
+/// is not closed.
+pub fn parse_strict(text: &str) -> Result<Vec<ConfigNode>> {
+    let output = parse_tolerant(text);
+    match output.diagnostics.first() {
+        Some(diagnostic) => Err(ConfigSlicerError::Parse(diagnostic.to_string())),
+        None => Ok(output.nodes),
+    }
+}
+
+/// Renders nodes with two-space indentation per level
+///
+/// Rendering parsed text normalizes indentation and separators, so two
+/// configurations with the same structure render identically. Bodies of
+/// multi-line blocks are rendered as written.
+#[must_use]
+pub fn render(nodes: &[ConfigNode]) -> String {
+    let mut out = String::new();
+    for node in nodes {
+        node.render_into(0, &mut out);
+    }
+    out
+}
+
+fn is_separator(line: &str) -> bool {
+    line.is_empty() || line.chars().all(|c| c == '!')
+}
+
+/// Returns why a line cannot be parsed in any configuration style
+fn malformed(line: &str) -> Option<&'static str> {
+    if line.contains(char::REPLACEMENT_CHARACTER) {
+        Some("line contains invalid UTF-8")
+    } else if line.chars().any(|c| c.is_control() && c != '\t') {
+        Some("line contains control characters")
+    } else {
+        None
+    }
+}
+
+fn skip(diagnostics: &mut Vec<Diagnostic>, line: usize, reason: &str) {
+    diagnostics.push(Diagnostic {
+        line,
+        reason: format!("{reason}; line skipped"),
+    });
+}
+
+fn parse_indented(text: &str, diagnostics: &mut Vec<Diagnostic>) -> Vec<ConfigNode> {
+    let mut roots = Vec::new();
+    let mut stack: Vec<(usize, ConfigNode)> = Vec::new();
+
+    let mut lines = (1..).zip(text.lines()).peekable();
+    while let Some((number, raw)) = lines.next() {
+        let line = raw.trim();
+        if is_separator(line) {
+            continue;
+        }
+        if let Some(reason) = malformed(line) {
+            skip(diagnostics, number, reason);
+            continue;
+        }
+        let indent = raw.len() - raw.trim_start().len();
+        while stack.last().is_some_and(|(level, _)| *level >= indent) {
+            close(&mut stack, &mut roots);
+        }
+        let mut node = ConfigNode::new(line);
+        if let Some(end) = indented_block(line, lines.peek().map(|(_, next)| *next)) {
+            node.body = take_block(&mut lines, &end, (number, line), diagnostics);
+        }
+        stack.push((indent, node));
+    }
+    while !stack.is_empty() {
+        close(&mut stack, &mut roots);
+    }
+    roots
+}
+
+fn is_comment(line: &str) -> bool {
+    line.starts_with('#') || line.starts_with("/*") || line.starts_with('*')
+}
+
+/// Parses brace-delimited text; stack entries carry their opening line number
+fn parse_braces(text: &str, diagnostics: &mut Vec<Diagnostic>) -> Vec<ConfigNode> {
+    let mut roots = Vec::new();
+    let mut stack: Vec<(usize, ConfigNode)> = Vec::new();
+
+    let mut lines = (1..).zip(text.lines());
+    while let Some((number, raw)) = lines.next() {
+        let line = raw.trim();
+        if is_separator(line) {
+            continue;
+        }
+        if let Some(reason) = malformed(line) {
+            skip(diagnostics, number, reason);
+        } else if let Some(end) = brace_block(line) {
+            let mut node = ConfigNode::new(line);
+            node.body = take_block(&mut lines, &end, (number, line), diagnostics);
+            add_leaf(&mut stack, &mut roots, node);
+        } else if line == "}" {
+            if stack.is_empty() {
+                skip(diagnostics, number, "closing brace without an open block");
+            } else {
+                close(&mut stack, &mut roots);
+            }
+        } else if let Some(opening) = line.strip_suffix('{') {
+            stack.push((number, ConfigNode::new(opening.trim_end())));
+        } else if !line.ends_with(';') && !is_comment(line) {
+            skip(
+                diagnostics,
+                number,
+                "statement does not end with ';' (wrapped or truncated line?)",
+            );
+        } else {
+            add_leaf(&mut stack, &mut roots, ConfigNode::new(line));
+        }
+    }
+    for (number, node) in &stack {
+        diagnostics.push(Diagnostic {
+            line: *number,
+            reason: format!(
+                "block '{}' is never closed (truncated configuration?)",
+                node.line
+            ),
+        });
+    }
+    while !stack.is_empty() {
+        close(&mut stack, &mut roots);
+    }
+    roots
+}
+
+/// Attaches a node that opens no block to the innermost open node
+fn add_leaf(stack: &mut [(usize, ConfigNode)], roots: &mut Vec<ConfigNode>, leaf: ConfigNode) {
+    match stack.last_mut() {
+        Some((_, parent)) => parent.children.push(leaf),
+        None => roots.push(leaf),
+    }
+}
+
+/// Pops the innermost open node and attaches it to its parent
+fn close(stack: &mut Vec<(usize, ConfigNode)>, roots: &mut Vec<ConfigNode>) {
+    if let Some((_, node)) = stack.pop() {
+        match stack.last_mut() {
+            Some((_, parent)) => parent.children.push(node),
+            None => roots.push(node),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_indented_config() {
+        let text = "hostname r1\n!\nrouter bgp 65000\n neighbor 10.0.0.1 remote-as 65001\n address-family ipv4\n  network 10.0.0.0/24\n!\n";
+
+        let nodes = parse(text);
+
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[1].line, "router bgp 65000");
+        assert_eq!(nodes[1].children.len(), 2);
+        assert_eq!(nodes[1].children[1].children[0].line, "network 10.0.0.0/24");
+    }
+
+    #[test]
+    fn test_parse_brace_config() {
+        let text = "system {\n    ntp {\n        server 10.0.0.1;\n    }\n}\ninterfaces {\n    ge-0/0/0 {\n        description uplink;\n    }\n}\n";
+
+        let nodes = parse(text);
+
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[0].line, "system");
+        assert_eq!(nodes[0].children[0].children[0].line, "server 10.0.0.1;");
+        assert_eq!(nodes[1].children[0].line, "ge-0/0/0");
+    }
+
+    #[test]
+    fn test_render_normalizes_indentation() {
+        let four_spaces = parse("router bgp 1\n    neighbor a\n");
+        let one_space = parse("router bgp 1\n neighbor a\n!\n");
+
+        assert_eq!(render(&four_spaces), "router bgp 1\n  neighbor a\n");
+        assert_eq!(render(&four_spaces), render(&one_space));
+    }
+
+    #[test]
+    fn test_parse_tolerant_skips_malformed_brace_lines() {
+        let text = "}\nsystem {\n    host-name r1;\n    domain-name exa\nmple.com;\n    ntp {\n        server 10.0.0.1;\n";
+
+        let output = parse_tolerant(text);
+
+        let lines: Vec<usize> = output.diagnostics.iter().map(|d| d.line).collect();
+        assert_eq!(lines, [1, 2, 4, 6]);
+        assert!(output.diagnostics[0].reason.contains("closing brace"));
+        assert!(
+            output.diagnostics[2]
+                .reason
+                .contains("does not end with ';'")
+        );
+        assert!(
+            output.diagnostics[3]
+                .reason
+                .contains("block 'ntp' is never closed")
+        );
+        assert_eq!(
+            render(&output.nodes),
+            "system\n  host-name r1;\n  mple.com;\n  ntp\n    server 10.0.0.1;\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_tolerant_skips_garbled_indented_lines() {
+        let text = "hostname r1\ninterface Gi0/1\n desc\u{1b}[2Kription\n shutdown\n";
+
+        let output = parse_tolerant(text);
+
+        assert_eq!(output.diagnostics.len(), 1);
+        assert_eq!(output.diagnostics[0].line, 3);
+        assert_eq!(output.nodes[1].children, [ConfigNode::new("shutdown")]);
+    }
+
+    #[test]
+    fn test_parse_keeps_banners_and_certificates_whole() {
+        let text = "hostname r1\nbanner motd ^C\n  Authorized access only\ninterface Gi0/1\n^C\n\
+                    crypto pki certificate chain TP\n certificate self-signed 01\n\
+                    \x20 3082022B 30820194\n  A0030201\n  \tquit\nbanner exec #Welcome#\n\
+                    banner login\nNo entry {\nEOF\ninterface Gi0/1\n shutdown\n";
+
+        let output = parse_tolerant(text);
+
+        assert_eq!(Syntax::detect(text), Syntax::Indented);
+        assert!(output.diagnostics.is_empty());
+        let lines: Vec<&str> = output.nodes.iter().map(|n| n.line.as_str()).collect();
+        assert_eq!(
+            lines,
+            [
+                "hostname r1",
+                "banner motd ^C",
+                "crypto pki certificate chain TP",
+                "banner exec #Welcome#",
+                "banner login",
+                "interface Gi0/1",
+            ]
+        );
+        assert_eq!(
+            output.nodes[1].body,
+            ["  Authorized access only", "interface Gi0/1", "^C"]
+        );
+        let certificate = &output.nodes[2].children[0];
+        assert_eq!(certificate.line, "certificate self-signed 01");
+        assert_eq!(certificate.body.len(), 3);
+        assert!(output.nodes[3].body.is_empty());
+        assert_eq!(output.nodes[4].body, ["No entry {", "EOF"]);
+        assert!(render(&output.nodes).starts_with(
+            "hostname r1\nbanner motd ^C\n  Authorized access only\ninterface Gi0/1\n^C\n"
+        ));
+    }
+
+    #[test]
+    fn test_parse_keeps_junos_annotations_and_strings_whole() {
+        let text = "system {\n    /* managed by\n       automation */\n    login {\n        \
+                    message \"Authorized\n  use \\\"only\\\"\";\n    }\n}\n";
+
+        let output = parse_tolerant(text);
+
+        assert!(output.diagnostics.is_empty());
+        let system = &output.nodes[0];
+        assert_eq!(system.children[0].line, "/* managed by");
+        assert_eq!(system.children[0].body, ["       automation */"]);
+        let message = &system.children[1].children[0];
+        assert_eq!(message.line, "message \"Authorized");
+        assert_eq!(message.body, ["  use \\\"only\\\"\";"]);
+    }
+
+    #[test]
+    fn test_parse_reports_unclosed_block() {
+        let output = parse_tolerant("banner motd ^C\nHello\ninterface Gi0/1\n");
+
+        assert_eq!(output.nodes.len(), 1);
+        assert_eq!(output.nodes[0].body, ["Hello", "interface Gi0/1"]);
+        assert!(
+            output.diagnostics[0]
+                .reason
+                .contains("block 'banner motd ^C' is never closed")
+        );
+    }
+
+    #[test]
+    fn test_parse_strict_fails_on_first_diagnostic() {
+        assert!(parse_strict("hostname r1\n interface a\n").is_ok());
+
+        let error = parse_strict("system {\n    host-name r1;\n").unwrap_err();
+
+        assert!(
+            error
+                .to_string()
+                .contains("line 1: block 'system' is never closed")
+        );
+    }
+}
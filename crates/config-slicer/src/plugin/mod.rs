@@ -0,0 +1,196 @@
+//! Parser plugins
+//!
+//! A [`PluginRegistry`] maps parser names to [`ParserPlugin`]s. The built-in
+//! parsers are `auto`, which picks the syntax per configuration, `indented`,
+//! and `braces`. Support for another vendor's syntax can be added without
+//! rebuilding the tool by dropping a WebAssembly module into a plugin
+//! directory; see [`PluginRegistry::load_dir`] and the `wasm` module for the
+//! interface a module must implement.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::error::{ConfigSlicerError, Result};
+use crate::parser::{self, ParseOutput, Syntax};
+
+#[cfg(feature = "wasm-plugins")]
+pub mod wasm;
+
+/// Name of the parser used when none is selected
+pub const DEFAULT_PARSER: &str = "auto";
+
+/// Parses configuration text into a tree of lines
+pub trait ParserPlugin: Send + Sync {
+    /// Parses configuration text, reporting malformed lines as diagnostics
+    ///
+    /// # Errors
+    /// Returns an error if the parser itself fails; problems with the
+    /// configuration are diagnostics, not errors.
+    fn parse(&self, text: &str) -> Result<ParseOutput>;
+}
+
+/// Built-in parser for one syntax, or detection per configuration
+#[derive(Debug, Clone, Copy)]
+struct BuiltinParser(Option<Syntax>);
+
+impl ParserPlugin for BuiltinParser {
+    fn parse(&self, text: &str) -> Result<ParseOutput> {
+        Ok(match self.0 {
+            Some(syntax) => parser::parse_syntax(text, syntax),
+            None => parser::parse_tolerant(text),
+        })
+    }
+}
+
+/// Parsers available by name
+#[derive(Default, Clone)]
+pub struct PluginRegistry {
+    parsers: HashMap<String, Arc<dyn ParserPlugin>>,
+}
+
+impl PluginRegistry {
+    /// Creates an empty registry
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a registry with the built-in parsers
+    #[must_use]
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::new();
+        registry.register(DEFAULT_PARSER, Arc::new(BuiltinParser(None)));
+        registry.register("indented", Arc::new(BuiltinParser(Some(Syntax::Indented))));
+        registry.register("braces", Arc::new(BuiltinParser(Some(Syntax::Braces))));
+        registry
+    }
+
+    /// Registers a parser, replacing any existing parser with the same name
+    pub fn register(&mut self, name: impl Into<String>, parser: Arc<dyn ParserPlugin>) {
+        self.parsers.insert(name.into(), parser);
+    }
+
+    /// Registered parser names in alphabetical order
+    #[must_use]
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.parsers.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Returns the parser registered under `name`
+    ///
+    /// # Errors
+    /// Returns a plugin error listing the registered names if there is none.
+    pub fn get(&self, name: &str) -> Result<Arc<dyn ParserPlugin>> {
+        self.parsers.get(name).cloned().ok_or_else(|| {
+            ConfigSlicerError::Plugin(format!(
+                "Unknown parser {name:?}; available: {}",
+                self.names().join(", ")
+            ))
+        })
+    }
+
+    /// Loads every `*.wasm` file in a directory as a parser plugin
+    ///
+    /// Each plugin is registered under its file stem (`vyos.wasm` is `vyos`).
+    /// Plugins cannot replace a parser that is already registered. Returns
+    /// the names of the loaded plugins in alphabetical order.
+    ///
+    /// # Errors
+    /// Returns an error if the directory cannot be read, a module is invalid
+    /// or does not implement the plugin interface, a plugin name is taken, or
+    /// the tool was built without the `wasm-plugins` feature.
+    #[cfg(feature = "wasm-plugins")]
+    pub fn load_dir(&mut self, dir: &Path) -> Result<Vec<String>> {
+        let mut paths = Vec::new();
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_file() && path.extension().is_some_and(|ext| ext == "wasm") {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+
+        let engine = wasm::engine()?;
+        let mut loaded = Vec::new();
+        for path in paths {
+            let name = path
+                .file_stem()
+                .map_or_else(String::new, |stem| stem.to_string_lossy().into_owned());
+            if self.parsers.contains_key(&name) {
+                return Err(ConfigSlicerError::Plugin(format!(
+                    "{}: parser {name:?} is already registered",
+                    path.display()
+                )));
+            }
+            let plugin = wasm::WasmParser::load(&engine, &path)?;
+            self.register(name.clone(), Arc::new(plugin));
+            loaded.push(name);
+        }
+        Ok(loaded)
+    }
+
+    /// Loads every `*.wasm` file in a directory as a parser plugin
+    ///
+    /// # Errors
+    /// Always fails: the tool was built without the `wasm-plugins` feature.
+    #[cfg(not(feature = "wasm-plugins"))]
+    pub fn load_dir(&mut self, dir: &Path) -> Result<Vec<String>> {
+        Err(ConfigSlicerError::Plugin(format!(
+            "Cannot load plugins from {}: built without the `wasm-plugins` feature",
+            dir.display()
+        )))
+    }
+}
+
+impl std::fmt::Debug for PluginRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PluginRegistry")
+            .field("parsers", &self.names())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JUNOS: &str = "system {\n    host-name r1;\n}\n";
+
+    #[test]
+    fn test_builtins_parse_by_syntax() {
+        let registry = PluginRegistry::with_builtins();
+
+        assert_eq!(registry.names(), ["auto", "braces", "indented"]);
+        let auto = registry.get("auto").unwrap().parse(JUNOS).unwrap();
+        assert_eq!(auto.nodes[0].children[0].line, "host-name r1;");
+        // Forced indentation keeps the braces as lines
+        let indented = registry.get("indented").unwrap().parse(JUNOS).unwrap();
+        assert_eq!(indented.nodes[0].line, "system {");
+    }
+
+    #[test]
+    fn test_unknown_parser_lists_available() {
+        let error = PluginRegistry::with_builtins()
+            .get("vyos")
+            .err()
+            .unwrap()
+            .to_string();
+
+        assert!(error.contains("Unknown parser \"vyos\""));
+        assert!(error.contains("auto, braces, indented"));
+    }
+
+    #[cfg(not(feature = "wasm-plugins"))]
+    #[test]
+    fn test_load_dir_requires_wasm_plugins_feature() {
+        let error = PluginRegistry::with_builtins()
+            .load_dir(Path::new("plugins"))
+            .unwrap_err()
+            .to_string();
+
+        assert!(error.contains("built without the `wasm-plugins` feature"));
+    }
+}
@@ -0,0 +1,210 @@
+//! WebAssembly parser plugins
+//!
+//! A plugin is a core WebAssembly module with no imports, so it cannot reach
+//! the filesystem, network, or clock. It exports:
+//!
+//! - `memory`: its linear memory
+//! - `alloc(len: i32) -> i32`: returns a buffer of `len` bytes for the input
+//! - `parse(ptr: i32, len: i32) -> i64`: parses the configuration lines in
+//!   the buffer, UTF-8 and separated by `\n`, and returns the location of its
+//!   output as `(ptr << 32) | len`
+//!
+//! The output is a JSON document shaped like [`ParseOutput`]:
+//! `{"nodes": [{"line": "...", "children": [...]}], "diagnostics": [{"line": 1, "reason": "..."}]}`,
+//! where `children` and `diagnostics` may be omitted. A node may also carry
+//! `body`, the verbatim lines of a multi-line block after its first.
+//!
+//! Every parse runs in a fresh instance with a fuel budget and a memory cap,
+//! so a plugin that loops or allocates without bound fails that parse
+//! instead of hanging the tool.
+
+use std::path::Path;
+use wasmtime::{Config, Engine, Instance, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+use super::ParserPlugin;
+use crate::error::{ConfigSlicerError, Result};
+use crate::parser::ParseOutput;
+
+/// Instructions one parse may execute, roughly
+pub const FUEL_PER_PARSE: u64 = 1_000_000_000;
+
+/// Largest linear memory a plugin may grow to, in bytes
+pub const MAX_MEMORY_BYTES: usize = 256 * 1024 * 1024;
+
+/// Creates an engine that meters plugin execution
+///
+/// # Errors
+/// Returns a plugin error if the engine cannot be created.
+pub fn engine() -> Result<Engine> {
+    let mut config = Config::new();
+    config.consume_fuel(true);
+    Engine::new(&config).map_err(|e| ConfigSlicerError::Plugin(format!("{e:#}")))
+}
+
+/// Parser backed by a WebAssembly module
+pub struct WasmParser {
+    name: String,
+    engine: Engine,
+    module: Module,
+}
+
+impl WasmParser {
+    /// Compiles a plugin module and checks that it fits the plugin interface
+    ///
+    /// # Errors
+    /// Returns a plugin error if the module does not compile, imports
+    /// anything, or lacks one of the required exports.
+    pub fn load(engine: &Engine, path: &Path) -> Result<Self> {
+        let name = path.display().to_string();
+        let module = Module::from_file(engine, path)
+            .map_err(|e| ConfigSlicerError::Plugin(format!("{name}: {e:#}")))?;
+        Self::new(name, engine, module)
+    }
+
+    fn new(name: String, engine: &Engine, module: Module) -> Result<Self> {
+        if let Some(import) = module.imports().next() {
+            return Err(ConfigSlicerError::Plugin(format!(
+                "{name}: plugins may not import anything, but it imports {}::{}",
+                import.module(),
+                import.name()
+            )));
+        }
+        for export in ["memory", "alloc", "parse"] {
+            if module.get_export(export).is_none() {
+                return Err(ConfigSlicerError::Plugin(format!(
+                    "{name}: missing export {export:?}"
+                )));
+            }
+        }
+        Ok(Self {
+            name,
+            engine: engine.clone(),
+            module,
+        })
+    }
+
+    fn error(&self, message: impl std::fmt::Display) -> ConfigSlicerError {
+        ConfigSlicerError::Plugin(format!("{}: {message}", self.name))
+    }
+
+    fn run(&self, text: &str) -> wasmtime::Result<Vec<u8>> {
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(MAX_MEMORY_BYTES)
+            .build();
+        let mut store: Store<StoreLimits> = Store::new(&self.engine, limits);
+        store.limiter(|limits| limits);
+        store.set_fuel(FUEL_PER_PARSE)?;
+
+        let instance = Instance::new(&mut store, &self.module, &[])?;
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or_else(|| wasmtime::Error::msg("export \"memory\" is not a memory"))?;
+        let alloc = instance.get_typed_func::<i32, i32>(&mut store, "alloc")?;
+        let parse = instance.get_typed_func::<(i32, i32), i64>(&mut store, "parse")?;
+
+        let input = text.as_bytes();
+        let len = i32::try_from(input.len())?;
+        let ptr = alloc.call(&mut store, len)?;
+        memory.write(&mut store, usize::try_from(ptr)?, input)?;
+
+        let packed = parse.call(&mut store, (ptr, len))?;
+        let out_ptr = usize::try_from(packed >> 32)?;
+        let out_len = usize::try_from(packed & 0xffff_ffff)?;
+        let mut output = vec![0; out_len];
+        memory.read(&store, out_ptr, &mut output)?;
+        Ok(output)
+    }
+}
+
+impl ParserPlugin for WasmParser {
+    fn parse(&self, text: &str) -> Result<ParseOutput> {
+        let output = self.run(text).map_err(|e| self.error(format!("{e:#}")))?;
+        let mut parsed: ParseOutput = serde_json::from_slice(&output)
+            .map_err(|e| self.error(format!("invalid output: {e}")))?;
+        parsed.diagnostics.sort_by_key(|diagnostic| diagnostic.line);
+        Ok(parsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::PluginRegistry;
+    use std::fs;
+
+    /// Returns the same single-node tree for any input
+    const FIXED: &str = r#"(module
+        (memory (export "memory") 1)
+        (data (i32.const 0) "{\"nodes\":[{\"line\":\"set system host-name r1\"}],\"diagnostics\":[{\"line\":2,\"reason\":\"unknown\"}]}")
+        (func (export "alloc") (param i32) (result i32) (i32.const 1024))
+        (func (export "parse") (param i32 i32) (result i64) (i64.const 92)))"#;
+
+    const LOOPS: &str = r#"(module
+        (memory (export "memory") 1)
+        (func (export "alloc") (param i32) (result i32) (i32.const 0))
+        (func (export "parse") (param i32 i32) (result i64) (loop (br 0)) (i64.const 0)))"#;
+
+    const IMPORTS: &str = r#"(module
+        (import "wasi_snapshot_preview1" "fd_write" (func (param i32 i32 i32 i32) (result i32)))
+        (memory (export "memory") 1)
+        (func (export "alloc") (param i32) (result i32) (i32.const 0))
+        (func (export "parse") (param i32 i32) (result i64) (i64.const 0)))"#;
+
+    fn parser(wat: &str) -> Result<WasmParser> {
+        let engine = engine()?;
+        let module = Module::new(&engine, wat).unwrap();
+        WasmParser::new("test.wasm".to_string(), &engine, module)
+    }
+
+    #[test]
+    fn test_plugin_output_becomes_tree() {
+        let output = parser(FIXED).unwrap().parse("anything\n").unwrap();
+
+        assert_eq!(output.nodes.len(), 1);
+        assert_eq!(output.nodes[0].line, "set system host-name r1");
+        assert!(output.nodes[0].children.is_empty());
+        assert_eq!(output.diagnostics[0].line, 2);
+    }
+
+    #[test]
+    fn test_runaway_plugin_runs_out_of_fuel() {
+        let error = parser(LOOPS).unwrap().parse("hostname r1\n").unwrap_err();
+
+        assert!(error.to_string().contains("test.wasm"));
+    }
+
+    #[test]
+    fn test_plugin_with_imports_is_rejected() {
+        let error = parser(IMPORTS).err().unwrap().to_string();
+
+        assert!(error.contains("may not import anything"));
+        assert!(error.contains("wasi_snapshot_preview1::fd_write"));
+    }
+
+    #[test]
+    fn test_load_dir_registers_plugins_by_file_stem() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("vyos.wasm"), FIXED).unwrap();
+        fs::write(dir.path().join("README.md"), "not a plugin").unwrap();
+        let mut registry = PluginRegistry::with_builtins();
+
+        let loaded = registry.load_dir(dir.path()).unwrap();
+
+        assert_eq!(loaded, ["vyos"]);
+        assert_eq!(registry.names(), ["auto", "braces", "indented", "vyos"]);
+        let output = registry.get("vyos").unwrap().parse("").unwrap();
+        assert_eq!(output.nodes[0].line, "set system host-name r1");
+    }
+
+    #[test]
+    fn test_load_dir_rejects_builtin_names() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("auto.wasm"), FIXED).unwrap();
+
+        let error = PluginRegistry::with_builtins()
+            .load_dir(dir.path())
+            .unwrap_err();
+
+        assert!(error.to_string().contains("already registered"));
+    }
+}
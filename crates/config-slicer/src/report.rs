@@ -0,0 +1,141 @@
+//! Text rendering of batch reports
+
+use std::fmt::Write as _;
+
+use crate::batch::{BatchReport, DeviceStatus};
+
+/// Renders the conformance matrix as an aligned table with a summary line
+///
+/// Parse diagnostics, if any, are listed after the summary.
+#[must_use]
+pub fn render_text(report: &BatchReport, show_diffs: bool) -> String {
+    let width = report
+        .devices
+        .iter()
+        .map(|d| d.device.len())
+        .max()
+        .unwrap_or(0)
+        .max("DEVICE".len());
+
+    let scored = report.devices.iter().any(|d| d.conformance.is_some());
+
+    let mut out = String::new();
+    let _ = writeln!(out, "Pattern:  {}", report.pattern);
+    let _ = writeln!(out, "Baseline: {}", report.baseline);
+    let _ = writeln!(out);
+    let _ = write!(
+        out,
+        "{:width$}  {:<10}  {:>7}  {:>5}  {:>7}",
+        "DEVICE", "STATUS", "VARIANT", "ADDED", "REMOVED"
+    );
+    let _ = writeln!(out, "{}", if scored { "   SCORE" } else { "" });
+    for device in &report.devices {
+        let variant = device
+            .variant
+            .map_or_else(|| "-".to_string(), |v| v.to_string());
+        let _ = write!(
+            out,
+            "{:width$}  {:<10}  {:>7}  {:>5}  {:>7}",
+            device.device,
+            status_label(device.status),
+            variant,
+            device.changes.added,
+            device.changes.removed
+        );
+        match &device.conformance {
+            Some(conformance) => {
+                let _ = writeln!(out, "  {:>5.1}%", conformance.score);
+            }
+            None => {
+                let _ = writeln!(out);
+            }
+        }
+    }
+    let _ = writeln!(out);
+    let _ = writeln!(
+        out,
+        "{} devices, {} variants: {} conformant, {} divergent, {} missing",
+        report.devices.len(),
+        report.variants,
+        report.count(DeviceStatus::Conformant),
+        report.count(DeviceStatus::Divergent),
+        report.count(DeviceStatus::Missing)
+    );
+
+    if report.first_diagnostic().is_some() {
+        let _ = writeln!(out);
+        let _ = writeln!(out, "Parse diagnostics:");
+        for device in &report.devices {
+            for diagnostic in &device.diagnostics {
+                let _ = writeln!(out, "  {}: {diagnostic}", device.device);
+            }
+        }
+    }
+
+    if show_diffs {
+        for diff in report.devices.iter().filter_map(|d| d.diff.as_deref()) {
+            let _ = writeln!(out);
+            out.push_str(diff);
+        }
+    }
+    out
+}
+
+const fn status_label(status: DeviceStatus) -> &'static str {
+    match status {
+        DeviceStatus::Conformant => "conformant",
+        DeviceStatus::Divergent => "divergent",
+        DeviceStatus::Missing => "missing",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::batch::{DeviceConfig, compare};
+
+    #[test]
+    fn test_render_text_lists_devices_and_summary() {
+        let configs = vec![
+            DeviceConfig {
+                device: "core-1".to_string(),
+                text: "ntp server 10.0.0.1\n".to_string(),
+            },
+            DeviceConfig {
+                device: "core-2".to_string(),
+                text: "ntp server 10.0.0.2\n".to_string(),
+            },
+        ];
+        let report = compare(
+            &configs,
+            &"ntp server .*".parse().unwrap(),
+            Some("ntp server 10.0.0.1\n"),
+        );
+
+        let text = render_text(&report, true);
+
+        assert!(text.contains("Baseline: golden"));
+        assert!(text.contains("REMOVED   SCORE"));
+        assert!(text.contains("core-1  conformant"));
+        assert!(text.contains("100.0%"));
+        assert!(text.contains("  0.0%"));
+        assert!(text.contains("core-2  divergent"));
+        assert!(text.contains("2 devices, 2 variants: 1 conformant, 1 divergent, 0 missing"));
+        assert!(text.contains("+ntp server 10.0.0.2"));
+    }
+
+    #[test]
+    fn test_render_text_lists_parse_diagnostics() {
+        let configs = vec![DeviceConfig {
+            device: "edge-1".to_string(),
+            text: "system {\n    host-name edge-1;\n".to_string(),
+        }];
+        let report = compare(&configs, &"system".parse().unwrap(), None);
+
+        let text = render_text(&report, false);
+
+        assert!(
+            text.contains("Parse diagnostics:\n  edge-1: line 1: block 'system' is never closed")
+        );
+    }
+}
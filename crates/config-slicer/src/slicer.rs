@@ -1,19 +1,213 @@
 //! Configuration slicing
+//!
+//! A match pattern selects a slice of a parsed configuration. Levels are
+//! separated by `||`; each level is a regular expression matched against the
+//! whole line at that depth, or `*` for any line. The lines matched by the
+//! last level are kept with everything nested beneath them, and their parent
+//! lines are kept for context.
+//!
+//! `router bgp .*` selects the IOS BGP section; `interfaces||ge-.*` selects
+//! the `JunOS` `ge-` interfaces.
 
-/// Placeholder for config slicing - will be implemented in Milestone 5
-pub mod placeholder {
-    /// Placeholder function
-    #[inline]
-    pub const fn placeholder() {
-        // Implementation pending
+use regex::Regex;
+use std::str::FromStr;
+
+use crate::error::{ConfigSlicerError, Result};
+use crate::parser::{self, ConfigNode};
+
+/// Parsed slice pattern
+#[derive(Debug, Clone)]
+pub struct MatchSpec {
+    pattern: String,
+    levels: Vec<Option<Regex>>,
+}
+
+impl MatchSpec {
+    /// Returns the pattern the spec was parsed from
+    #[must_use]
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Selects the matching nodes, keeping their parents for context
+    #[must_use]
+    pub fn slice(&self, nodes: &[ConfigNode]) -> Vec<ConfigNode> {
+        slice_levels(nodes, &[self.levels.as_slice()])
+    }
+
+    /// Parses, slices, and renders configuration text
+    #[must_use]
+    pub fn slice_text(&self, text: &str) -> String {
+        parser::render(&self.slice(&parser::parse(text)))
+    }
+
+    /// Lines matched by the last level, each with its parent lines, in
+    /// configuration order
+    #[must_use]
+    pub fn matches(&self, nodes: &[ConfigNode]) -> Vec<Vec<String>> {
+        let mut matches = Vec::new();
+        collect_matches(nodes, &self.levels, &mut Vec::new(), &mut matches);
+        matches
+    }
+}
+
+fn collect_matches(
+    nodes: &[ConfigNode],
+    levels: &[Option<Regex>],
+    path: &mut Vec<String>,
+    matches: &mut Vec<Vec<String>>,
+) {
+    let Some((level, rest)) = levels.split_first() else {
+        return;
+    };
+    for node in nodes {
+        if !level.as_ref().is_none_or(|re| re.is_match(&node.line)) {
+            continue;
+        }
+        path.push(node.line.clone());
+        if rest.is_empty() {
+            matches.push(path.clone());
+        } else {
+            collect_matches(&node.children, rest, path, matches);
+        }
+        path.pop();
     }
 }
 
+impl FromStr for MatchSpec {
+    type Err = ConfigSlicerError;
+
+    fn from_str(pattern: &str) -> Result<Self> {
+        let levels = pattern
+            .split("||")
+            .map(|token| {
+                let token = token.trim();
+                if token.is_empty() {
+                    return Err(ConfigSlicerError::Parse(format!(
+                        "Empty level in match pattern {pattern:?}"
+                    )));
+                }
+                if token == "*" {
+                    return Ok(None);
+                }
+                Regex::new(&format!("^(?:{token})$"))
+                    .map(Some)
+                    .map_err(|e| ConfigSlicerError::Parse(format!("Invalid level {token:?}: {e}")))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            pattern: pattern.to_string(),
+            levels,
+        })
+    }
+}
+
+/// Selects the nodes matched by any of `specs`, in configuration order
+///
+/// Overlapping slices are merged, so a line selected by several specs
+/// appears once.
+#[must_use]
+pub fn slice_any(specs: &[MatchSpec], nodes: &[ConfigNode]) -> Vec<ConfigNode> {
+    let levels: Vec<&[Option<Regex>]> = specs.iter().map(|spec| spec.levels.as_slice()).collect();
+    slice_levels(nodes, &levels)
+}
+
+/// Slices by several level lists at once; a node is kept whole once any
+/// list is exhausted on it, otherwise its children are sliced by the rests
+fn slice_levels(nodes: &[ConfigNode], patterns: &[&[Option<Regex>]]) -> Vec<ConfigNode> {
+    nodes
+        .iter()
+        .filter_map(|node| {
+            let rests: Vec<&[Option<Regex>]> = patterns
+                .iter()
+                .filter_map(|levels| {
+                    let (level, rest) = levels.split_first()?;
+                    level
+                        .as_ref()
+                        .is_none_or(|re| re.is_match(&node.line))
+                        .then_some(rest)
+                })
+                .collect();
+            if rests.is_empty() {
+                return None;
+            }
+            if rests.iter().any(|rest| rest.is_empty()) {
+                return Some(node.clone());
+            }
+            let children = slice_levels(&node.children, &rests);
+            (!children.is_empty()).then(|| ConfigNode {
+                line: node.line.clone(),
+                children,
+                body: node.body.clone(),
+            })
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    const IOS: &str = "hostname r1\nrouter bgp 65000\n neighbor 10.0.0.1 remote-as 65001\nrouter ospf 1\n network 10.0.0.0 0.0.0.255 area 0\n";
+    const JUNOS: &str = "interfaces {\n    ge-0/0/0 {\n        description uplink;\n    }\n    lo0 {\n        description loopback;\n    }\n}\n";
+
+    #[test]
+    fn test_slice_selects_section_with_children() {
+        let spec: MatchSpec = "router bgp .*".parse().unwrap();
+
+        assert_eq!(
+            spec.slice_text(IOS),
+            "router bgp 65000\n  neighbor 10.0.0.1 remote-as 65001\n"
+        );
+    }
+
+    #[test]
+    fn test_slice_keeps_parents_of_nested_matches() {
+        let spec: MatchSpec = "interfaces||ge-.*".parse().unwrap();
+
+        assert_eq!(
+            spec.slice_text(JUNOS),
+            "interfaces\n  ge-0/0/0\n    description uplink;\n"
+        );
+    }
+
+    #[test]
+    fn test_wildcard_level_and_anchoring() {
+        let wildcard: MatchSpec = "interfaces||*||description .*".parse().unwrap();
+        let partial: MatchSpec = "router".parse().unwrap();
+
+        assert_eq!(wildcard.slice(&parser::parse(JUNOS))[0].children.len(), 2);
+        assert!(partial.slice_text(IOS).is_empty());
+    }
+
+    #[test]
+    fn test_slice_any_merges_overlapping_specs_in_order() {
+        let specs: Vec<MatchSpec> = ["router ospf .*", "router .*||neighbor .*", "router bgp .*"]
+            .iter()
+            .map(|pattern| pattern.parse().unwrap())
+            .collect();
+
+        assert_eq!(
+            parser::render(&slice_any(&specs, &parser::parse(IOS))),
+            "router bgp 65000\n  neighbor 10.0.0.1 remote-as 65001\nrouter ospf 1\n  network 10.0.0.0 0.0.0.255 area 0\n"
+        );
+        assert!(slice_any(&[], &parser::parse(IOS)).is_empty());
+    }
+
+    #[test]
+    fn test_matches_lists_paths_of_last_level() {
+        let spec: MatchSpec = "interfaces||*".parse().unwrap();
+
+        assert_eq!(
+            spec.matches(&parser::parse(JUNOS)),
+            [["interfaces", "ge-0/0/0"], ["interfaces", "lo0"]]
+        );
+    }
+
     #[test]
-    fn test_placeholder_slicer() {
-        super::placeholder::placeholder();
-        assert!(true);
+    fn test_invalid_patterns_are_rejected() {
+        assert!("router||".parse::<MatchSpec>().is_err());
+        assert!("router (".parse::<MatchSpec>().is_err());
     }
 }
@@ -14,3 +14,137 @@ fn runs_with_verbose() {
     cmd.arg("--verbose");
     cmd.assert().success();
 }
+
+fn write_fleet(dir: &std::path::Path) {
+    let standard = "hostname x\nrouter bgp 65000\n neighbor 10.0.0.1 remote-as 65001\n";
+    std::fs::write(dir.join("r1.cfg"), standard).unwrap();
+    std::fs::write(dir.join("r2.cfg"), standard).unwrap();
+    std::fs::write(
+        dir.join("r3.cfg"),
+        "router bgp 65000\n neighbor 10.0.0.9 remote-as 65001\n",
+    )
+    .unwrap();
+}
+
+#[test]
+fn batch_reports_conformance_matrix() {
+    let dir = tempfile::tempdir().unwrap();
+    write_fleet(dir.path());
+
+    let mut cmd = Command::cargo_bin("config-slicer").unwrap();
+    cmd.args(["batch", "--match", "router bgp .*"])
+        .arg(dir.path());
+    cmd.assert().success().stdout(predicates::str::contains(
+        "3 devices, 2 variants: 2 conformant, 1 divergent, 0 missing",
+    ));
+}
+
+#[test]
+fn batch_fails_on_divergence_when_requested() {
+    let dir = tempfile::tempdir().unwrap();
+    write_fleet(dir.path());
+
+    let mut cmd = Command::cargo_bin("config-slicer").unwrap();
+    cmd.args(["batch", "--match", "router bgp .*", "--fail-on-divergence"])
+        .arg(dir.path());
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("1 of 3 devices do not conform"));
+}
+
+#[test]
+fn batch_reports_progress_per_chunk() {
+    let dir = tempfile::tempdir().unwrap();
+    write_fleet(dir.path());
+
+    let mut cmd = Command::cargo_bin("config-slicer").unwrap();
+    cmd.args([
+        "batch",
+        "--match",
+        "router bgp .*",
+        "--jobs",
+        "2",
+        "--progress",
+    ])
+    .arg(dir.path());
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("2 conformant, 1 divergent"))
+        .stderr(predicates::str::contains("Sliced 3/3 configs"));
+}
+
+#[test]
+fn batch_rejects_malformed_config_unless_tolerant() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("r1.cfg"),
+        "system {\n    host-name r1;\n    domain-name exa\nmple.com;\n}\n",
+    )
+    .unwrap();
+
+    let mut strict = Command::cargo_bin("config-slicer").unwrap();
+    strict.args(["batch", "--match", "system"]).arg(dir.path());
+    strict
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("r1: line 3:"));
+
+    let mut tolerant = Command::cargo_bin("config-slicer").unwrap();
+    tolerant
+        .args(["batch", "--match", "system", "--tolerant"])
+        .arg(dir.path());
+    tolerant
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "Parse diagnostics:\n  r1: line 3:",
+        ));
+}
+
+#[test]
+fn parsers_lists_builtin_parsers() {
+    let mut cmd = Command::cargo_bin("config-slicer").unwrap();
+    cmd.arg("parsers");
+    cmd.assert().success().stdout("auto\nbraces\nindented\n");
+}
+
+#[test]
+fn batch_rejects_unknown_parser() {
+    let dir = tempfile::tempdir().unwrap();
+    write_fleet(dir.path());
+
+    let mut cmd = Command::cargo_bin("config-slicer").unwrap();
+    cmd.args(["batch", "--match", "router bgp .*", "--parser", "vyos"])
+        .arg(dir.path());
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("Unknown parser \"vyos\""));
+}
+
+#[test]
+fn interactive_previews_and_exports_pattern() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = dir.path().join("r1.cfg");
+    std::fs::write(
+        &config,
+        "hostname r1\nrouter bgp 65000\n neighbor 10.0.0.1 remote-as 65001\n",
+    )
+    .unwrap();
+    let export = dir.path().join("bgp.cfg");
+
+    let mut cmd = assert_cmd::Command::cargo_bin("config-slicer").unwrap();
+    cmd.arg("interactive").arg(&config).write_stdin(format!(
+        "router\nrouter bgp .*\n:export {}\n:quit\n",
+        export.display()
+    ));
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("0 matches, 0 lines in slice"))
+        .stdout(predicates::str::contains(
+            "1 match, 2 lines in slice\n     2  router bgp 65000",
+        ));
+    assert_eq!(
+        std::fs::read_to_string(export).unwrap(),
+        "router bgp 65000\n  neighbor 10.0.0.1 remote-as 65001\n"
+    );
+}
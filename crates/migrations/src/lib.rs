@@ -5,6 +5,18 @@ mod m20241221_000002_create_nodes_table;
 mod m20241221_000003_create_links_table;
 mod m20241221_000004_create_derived_state_tables;
 mod m20241221_000005_create_vendor_table;
+mod m20241221_000006_create_setting_table;
+mod m20241221_000007_add_location_address_and_timezone;
+mod m20241221_000008_add_node_ipv6_management;
+mod m20241221_000009_create_policy_result_table;
+mod m20241221_000010_add_link_provisioning;
+mod m20241221_000011_create_performance_sample_table;
+mod m20241221_000012_create_performance_rollup_table;
+mod m20241221_000013_create_change_event_table;
+mod m20241221_000014_create_shard_lease_table;
+mod safeguards;
+
+pub use safeguards::{MigrationReport, check_schema_version, migrate_safely, schema_version};
 
 #[cfg(test)]
 mod schema_parity_tests;
@@ -20,6 +32,15 @@ impl MigratorTrait for Migrator {
             Box::new(m20241221_000003_create_links_table::Migration),
             Box::new(m20241221_000004_create_derived_state_tables::Migration),
             Box::new(m20241221_000005_create_vendor_table::Migration),
+            Box::new(m20241221_000006_create_setting_table::Migration),
+            Box::new(m20241221_000007_add_location_address_and_timezone::Migration),
+            Box::new(m20241221_000008_add_node_ipv6_management::Migration),
+            Box::new(m20241221_000009_create_policy_result_table::Migration),
+            Box::new(m20241221_000010_add_link_provisioning::Migration),
+            Box::new(m20241221_000011_create_performance_sample_table::Migration),
+            Box::new(m20241221_000012_create_performance_rollup_table::Migration),
+            Box::new(m20241221_000013_create_change_event_table::Migration),
+            Box::new(m20241221_000014_create_shard_lease_table::Migration),
         ]
     }
 }
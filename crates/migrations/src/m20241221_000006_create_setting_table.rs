@@ -0,0 +1,54 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Setting::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Setting::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Setting::Namespace).string().not_null())
+                    .col(ColumnDef::new(Setting::Key).string().not_null())
+                    .col(ColumnDef::new(Setting::Value).string().not_null())
+                    .col(ColumnDef::new(Setting::UpdatedAt).string().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_setting_namespace")
+                    .table(Setting::Table)
+                    .col(Setting::Namespace)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Setting::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Setting {
+    Table,
+    Id,
+    Namespace,
+    Key,
+    Value,
+    UpdatedAt,
+}
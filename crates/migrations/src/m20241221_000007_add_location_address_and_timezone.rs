@@ -0,0 +1,55 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite allows one column per ALTER TABLE statement
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Location::Table)
+                    .add_column(ColumnDef::new(Location::PostalAddress).string())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Location::Table)
+                    .add_column(ColumnDef::new(Location::Timezone).string())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Location::Table)
+                    .drop_column(Location::Timezone)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Location::Table)
+                    .drop_column(Location::PostalAddress)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Location {
+    Table,
+    PostalAddress,
+    Timezone,
+}
@@ -0,0 +1,89 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PolicyResult::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PolicyResult::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(PolicyResult::RuleId).string().not_null())
+                    .col(ColumnDef::new(PolicyResult::NodeId).string().not_null())
+                    .col(ColumnDef::new(PolicyResult::Status).string().not_null())
+                    .col(ColumnDef::new(PolicyResult::Severity).string().not_null())
+                    .col(ColumnDef::new(PolicyResult::Message).string())
+                    .col(
+                        ColumnDef::new(PolicyResult::EvaluatedAt)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(PolicyResult::Detail).string().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Latest results per node, results per rule, and counts by status
+        // back the compliance queries
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_policy_result_node_evaluated_at")
+                    .table(PolicyResult::Table)
+                    .col(PolicyResult::NodeId)
+                    .col(PolicyResult::EvaluatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_policy_result_rule_evaluated_at")
+                    .table(PolicyResult::Table)
+                    .col(PolicyResult::RuleId)
+                    .col(PolicyResult::EvaluatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_policy_result_status_severity")
+                    .table(PolicyResult::Table)
+                    .col(PolicyResult::Status)
+                    .col(PolicyResult::Severity)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PolicyResult::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PolicyResult {
+    Table,
+    Id,
+    RuleId,
+    NodeId,
+    Status,
+    Severity,
+    Message,
+    EvaluatedAt,
+    Detail,
+}
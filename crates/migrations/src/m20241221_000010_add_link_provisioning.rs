@@ -0,0 +1,44 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite allows one column per ALTER TABLE statement
+        for column in [Link::ProvisioningState, Link::OrderedOn, Link::DeliveredOn] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Link::Table)
+                        .add_column(ColumnDef::new(column).string())
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in [Link::DeliveredOn, Link::OrderedOn, Link::ProvisioningState] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Link::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Link {
+    Table,
+    ProvisioningState,
+    OrderedOn,
+    DeliveredOn,
+}
@@ -0,0 +1,69 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PerformanceSample::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PerformanceSample::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(PerformanceSample::NodeId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(PerformanceSample::Metric)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(PerformanceSample::Value).double().not_null())
+                    .col(
+                        ColumnDef::new(PerformanceSample::RecordedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Range queries read one metric of one node in time order
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_performance_sample_node_metric_time")
+                    .table(PerformanceSample::Table)
+                    .col(PerformanceSample::NodeId)
+                    .col(PerformanceSample::Metric)
+                    .col(PerformanceSample::RecordedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PerformanceSample::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PerformanceSample {
+    Table,
+    Id,
+    NodeId,
+    Metric,
+    Value,
+    RecordedAt,
+}
@@ -0,0 +1,79 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PerformanceRollup::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PerformanceRollup::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(PerformanceRollup::NodeId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(PerformanceRollup::Metric)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(PerformanceRollup::BucketStart)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(PerformanceRollup::Total).double().not_null())
+                    .col(
+                        ColumnDef::new(PerformanceRollup::Samples)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(PerformanceRollup::Low).double().not_null())
+                    .col(ColumnDef::new(PerformanceRollup::High).double().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Range queries read one metric of one node in time order
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_performance_rollup_node_metric_time")
+                    .table(PerformanceRollup::Table)
+                    .col(PerformanceRollup::NodeId)
+                    .col(PerformanceRollup::Metric)
+                    .col(PerformanceRollup::BucketStart)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PerformanceRollup::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PerformanceRollup {
+    Table,
+    Id,
+    NodeId,
+    Metric,
+    BucketStart,
+    Total,
+    Samples,
+    Low,
+    High,
+}
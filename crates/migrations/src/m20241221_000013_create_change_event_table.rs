@@ -0,0 +1,98 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ChangeEvent::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ChangeEvent::Sequence)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ChangeEvent::Id).string().not_null())
+                    .col(
+                        ColumnDef::new(ChangeEvent::RecordedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ChangeEvent::Entity).string().not_null())
+                    .col(ColumnDef::new(ChangeEvent::EntityId).string().not_null())
+                    .col(ColumnDef::new(ChangeEvent::ChangeType).string().not_null())
+                    .col(ColumnDef::new(ChangeEvent::Payload).string().not_null())
+                    .col(
+                        ColumnDef::new(ChangeEvent::ChangedFields)
+                            .string()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // History of one entity is read in log order; pruning goes by time
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_change_event_entity_sequence")
+                    .table(ChangeEvent::Table)
+                    .col(ChangeEvent::EntityId)
+                    .col(ChangeEvent::Sequence)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_change_event_recorded_at")
+                    .table(ChangeEvent::Table)
+                    .col(ChangeEvent::RecordedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Events used to be settings keyed by the zero-padded recording time
+        // in microseconds, so key order is log order
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            "INSERT INTO change_event \
+             (id, recorded_at, entity, entity_id, change_type, payload, changed_fields) \
+             SELECT value ->> '$.id', CAST(substr(key, 1, 20) AS INTEGER), \
+             value ->> '$.entity', value ->> '$.entity_id', value ->> '$.change', \
+             COALESCE(value -> '$.payload', 'null'), \
+             COALESCE(value -> '$.changed_fields', '[]') \
+             FROM setting WHERE namespace = 'change_log' ORDER BY key",
+        )
+        .await?;
+        db.execute_unprepared("DELETE FROM setting WHERE namespace = 'change_log'")
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ChangeEvent::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ChangeEvent {
+    Table,
+    Sequence,
+    Id,
+    RecordedAt,
+    Entity,
+    EntityId,
+    ChangeType,
+    Payload,
+    ChangedFields,
+}
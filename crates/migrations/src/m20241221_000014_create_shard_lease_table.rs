@@ -0,0 +1,58 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ShardLease::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ShardLease::Shard)
+                            .big_integer()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ShardLease::Collector).string().not_null())
+                    .col(
+                        ColumnDef::new(ShardLease::AcquiredAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ShardLease::ExpiresAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Leases used to be settings; they lapse within one lease lifetime,
+        // so collectors simply claim their shards again
+        manager
+            .get_connection()
+            .execute_unprepared("DELETE FROM setting WHERE namespace = 'poller_shard_leases'")
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ShardLease::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ShardLease {
+    Table,
+    Shard,
+    Collector,
+    AcquiredAt,
+    ExpiresAt,
+}
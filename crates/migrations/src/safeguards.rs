@@ -0,0 +1,241 @@
+//! Safeguards around applying migrations at startup
+//!
+//! [`migrate_safely`] refuses to touch a database whose schema was written by a
+//! newer build, writes a copy of the database file before applying pending
+//! migrations, and checks foreign keys once they are applied. The copy is
+//! taken with `VACUUM INTO`, so it is consistent even while other processes
+//! hold the database open; in-memory databases are not copied.
+
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, DbErr, Statement};
+use sea_orm_migration::{MigrationName as _, MigratorTrait as _};
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::Migrator;
+
+/// Migrations applied by [`migrate_safely`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MigrationReport {
+    /// Names of the migrations applied, in order
+    pub applied: Vec<String>,
+    /// Copy of the database taken before they were applied
+    pub backup: Option<PathBuf>,
+}
+
+/// Applies pending migrations after checking the database can take them
+///
+/// # Errors
+/// Returns an error if the database records migrations this build does not
+/// know, the backup cannot be written, a migration fails, or foreign keys are
+/// violated once the migrations are applied.
+pub async fn migrate_safely(db: &DatabaseConnection) -> Result<MigrationReport, DbErr> {
+    check_schema_version(db).await?;
+
+    let pending: Vec<String> = Migrator::get_pending_migrations(db)
+        .await?
+        .iter()
+        .map(|migration| migration.name().to_string())
+        .collect();
+    if pending.is_empty() {
+        return Ok(MigrationReport::default());
+    }
+
+    let backup = backup_database(db).await?;
+    Migrator::up(db, None).await?;
+
+    let violations = foreign_key_violations(db).await?;
+    if !violations.is_empty() {
+        let restore = backup.as_ref().map_or_else(String::new, |path| {
+            format!("; the database before migrating is at {}", path.display())
+        });
+        return Err(DbErr::Custom(format!(
+            "Foreign key check failed after applying migrations: violations in {}{restore}",
+            violations.into_iter().collect::<Vec<_>>().join(", ")
+        )));
+    }
+
+    Ok(MigrationReport {
+        applied: pending,
+        backup,
+    })
+}
+
+/// Name of the newest migration in this build
+///
+/// A database is at this version once [`migrate_safely`] has run on it.
+#[must_use]
+pub fn schema_version() -> Option<String> {
+    Migrator::migrations()
+        .last()
+        .map(|migration| migration.name().to_string())
+}
+
+/// Fails if the database records migrations missing from this build
+///
+/// Such a database was migrated by a newer release, and running against it
+/// could silently misread or damage data.
+///
+/// # Errors
+/// Returns an error naming the unknown migrations, or if they cannot be read.
+pub async fn check_schema_version(db: &DatabaseConnection) -> Result<(), DbErr> {
+    let recorded = query_column(
+        db,
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name = 'seaql_migrations'",
+        "name",
+    )
+    .await?;
+    if recorded.is_empty() {
+        return Ok(());
+    }
+
+    let known: BTreeSet<String> = Migrator::migrations()
+        .iter()
+        .map(|migration| migration.name().to_string())
+        .collect();
+    let unknown: Vec<String> = query_column(db, "SELECT version FROM seaql_migrations", "version")
+        .await?
+        .into_iter()
+        .filter(|version| !known.contains(version))
+        .collect();
+    if unknown.is_empty() {
+        return Ok(());
+    }
+    Err(DbErr::Custom(format!(
+        "Database schema is newer than this build of unet supports (unknown migrations: {}); \
+         upgrade unet before using this database",
+        unknown.join(", ")
+    )))
+}
+
+/// Copies the database file next to itself, unless it is in memory
+async fn backup_database(db: &DatabaseConnection) -> Result<Option<PathBuf>, DbErr> {
+    let Some(file) = query_column(db, "PRAGMA database_list", "file")
+        .await?
+        .into_iter()
+        .next()
+        .filter(|file| !file.is_empty())
+    else {
+        return Ok(None);
+    };
+
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let backup = PathBuf::from(format!("{file}.pre-migrate-{stamp}"));
+    db.execute(sqlite_statement(&format!(
+        "VACUUM INTO '{}'",
+        backup.to_string_lossy().replace('\'', "''")
+    )))
+    .await
+    .map_err(|e| {
+        DbErr::Custom(format!(
+            "Failed to back up the database to {} before migrating: {e}",
+            backup.display()
+        ))
+    })?;
+    Ok(Some(backup))
+}
+
+/// Tables holding rows whose foreign keys point at missing rows
+async fn foreign_key_violations(db: &DatabaseConnection) -> Result<BTreeSet<String>, DbErr> {
+    Ok(query_column(db, "PRAGMA foreign_key_check", "table")
+        .await?
+        .into_iter()
+        .collect())
+}
+
+async fn query_column(
+    db: &DatabaseConnection,
+    sql: &str,
+    column: &str,
+) -> Result<Vec<String>, DbErr> {
+    db.query_all(sqlite_statement(sql))
+        .await?
+        .iter()
+        .map(|row| row.try_get::<String>("", column))
+        .collect()
+}
+
+fn sqlite_statement(sql: &str) -> Statement {
+    Statement::from_string(DatabaseBackend::Sqlite, sql)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::Database;
+
+    async fn connect(url: &str) -> DatabaseConnection {
+        Database::connect(url).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_migrate_safely_backs_up_file_before_applying() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("unet.db");
+        let db = connect(&format!("sqlite://{}?mode=rwc", path.display())).await;
+        Migrator::up(&db, Some(2)).await.unwrap();
+
+        let report = migrate_safely(&db).await.unwrap();
+
+        assert_eq!(report.applied.len(), Migrator::migrations().len() - 2);
+        let backup = report.backup.unwrap();
+        let copy = connect(&format!("sqlite://{}", backup.display())).await;
+        assert_eq!(
+            Migrator::get_applied_migrations(&copy).await.unwrap().len(),
+            2
+        );
+
+        // Nothing pending: no second backup
+        assert_eq!(
+            migrate_safely(&db).await.unwrap(),
+            MigrationReport::default()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_migrate_safely_skips_backup_in_memory() {
+        let db = connect("sqlite::memory:").await;
+
+        let report = migrate_safely(&db).await.unwrap();
+
+        assert_eq!(report.applied.len(), Migrator::migrations().len());
+        assert_eq!(report.backup, None);
+        assert_eq!(report.applied.last(), schema_version().as_ref());
+    }
+
+    #[tokio::test]
+    async fn test_refuses_newer_schema() {
+        let db = connect("sqlite::memory:").await;
+        Migrator::up(&db, None).await.unwrap();
+        db.execute_unprepared(
+            "INSERT INTO seaql_migrations (version, applied_at) \
+             VALUES ('m20991231_000001_from_the_future', 0)",
+        )
+        .await
+        .unwrap();
+
+        let error = migrate_safely(&db).await.unwrap_err().to_string();
+
+        assert!(error.contains("newer than this build"));
+        assert!(error.contains("m20991231_000001_from_the_future"));
+    }
+
+    #[tokio::test]
+    async fn test_foreign_key_violations_fail_migration() {
+        let db = connect("sqlite::memory:").await;
+        db.execute_unprepared(
+            "CREATE TABLE parent (id INTEGER PRIMARY KEY); \
+             CREATE TABLE child (parent_id INTEGER REFERENCES parent (id)); \
+             INSERT INTO child (parent_id) VALUES (1);",
+        )
+        .await
+        .unwrap();
+
+        let error = migrate_safely(&db).await.unwrap_err().to_string();
+
+        assert!(error.contains("Foreign key check failed"));
+        assert!(error.contains("child"));
+    }
+}
@@ -1,7 +1,7 @@
+use sea_orm::Statement;
 use sea_orm::{ConnectionTrait, Database, DatabaseBackend, DatabaseConnection, Schema};
 use tokio::sync::OnceCell;
 use unet_core::entities;
-use sea_orm::Statement;
 
 static DB_CONN: OnceCell<DatabaseConnection> = OnceCell::const_new();
 
@@ -19,7 +19,9 @@ pub async fn entity_db() -> DatabaseConnection {
         .clone()
 }
 
-async fn apply_entity_schema(connection: &impl ConnectionTrait) -> Result<(), Box<dyn std::error::Error>> {
+async fn apply_entity_schema(
+    connection: &impl ConnectionTrait,
+) -> Result<(), Box<dyn std::error::Error>> {
     let schema = Schema::new(DatabaseBackend::Sqlite);
 
     for stmt in [
@@ -31,6 +33,11 @@ async fn apply_entity_schema(connection: &impl ConnectionTrait) -> Result<(), Bo
         schema.create_table_from_entity(entities::interface_status::Entity),
         schema.create_table_from_entity(entities::node_status::Entity),
         schema.create_table_from_entity(entities::polling_tasks::Entity),
+        schema.create_table_from_entity(entities::settings::Entity),
+        schema.create_table_from_entity(entities::performance_samples::Entity),
+        schema.create_table_from_entity(entities::performance_rollups::Entity),
+        schema.create_table_from_entity(entities::change_events::Entity),
+        schema.create_table_from_entity(entities::shard_leases::Entity),
     ] {
         connection
             .execute(connection.get_database_backend().build(&stmt))
@@ -40,7 +47,9 @@ async fn apply_entity_schema(connection: &impl ConnectionTrait) -> Result<(), Bo
     use sea_orm::{ActiveModelTrait, Set};
     let vendor_names = ["Cisco", "Juniper"];
     for name in vendor_names {
-        let active = entities::vendors::ActiveModel { name: Set(name.to_string()) };
+        let active = entities::vendors::ActiveModel {
+            name: Set(name.to_string()),
+        };
         let _ = active.insert(connection).await; // ignore errors if already seeded
     }
     Ok(())
@@ -52,6 +61,18 @@ pub async fn sqlite_store() -> unet_core::datastore::sqlite::SqliteStore {
     unet_core::datastore::sqlite::SqliteStore::from_connection(conn)
 }
 
+/// Create a fresh in-memory `SqliteStore` with every migration applied.
+pub async fn migrated_store() -> unet_core::datastore::sqlite::SqliteStore {
+    use migration::{Migrator, MigratorTrait as _};
+    let store = unet_core::datastore::sqlite::SqliteStore::new("sqlite::memory:")
+        .await
+        .expect("connect sqlite::memory:");
+    Migrator::up(store.connection(), None)
+        .await
+        .expect("apply migrations");
+    store
+}
+
 /// Run a closure within a SQLite savepoint on the shared connection.
 /// All changes are rolled back afterwards.
 pub async fn with_savepoint<F, Fut, T>(name: &str, f: F) -> T
@@ -64,16 +85,12 @@ where
     let save = format!("SAVEPOINT {name}");
     let rollback = format!("ROLLBACK TO {name}");
     let release = format!("RELEASE {name}");
-    let _ = conn
-        .execute(Statement::from_string(backend, save))
-        .await;
+    let _ = conn.execute(Statement::from_string(backend, save)).await;
     let store = unet_core::datastore::sqlite::SqliteStore::from_connection(conn.clone());
     let out = f(store).await;
     let _ = conn
         .execute(Statement::from_string(backend, rollback))
         .await;
-    let _ = conn
-        .execute(Statement::from_string(backend, release))
-        .await;
+    let _ = conn.execute(Statement::from_string(backend, release)).await;
     out
 }
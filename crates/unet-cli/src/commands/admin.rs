@@ -0,0 +1,72 @@
+/// Administrative CLI commands
+use anyhow::Result;
+use clap::Subcommand;
+use unet_core::config::Config;
+use unet_core::datastore::DataStore;
+
+mod bundle;
+mod bundle_files;
+mod bundle_format;
+mod encrypt;
+mod fsck;
+mod retention;
+mod seed;
+
+#[cfg(test)]
+mod bundle_tests;
+
+pub use bundle::{ExportBundleArgs, ImportBundleArgs};
+pub use bundle_format::SECRET_KEYS;
+pub use encrypt::{EncryptDatabaseArgs, encrypt_database};
+pub use fsck::FsckArgs;
+pub use retention::RetentionArgs;
+pub use seed::SeedArgs;
+
+#[derive(Subcommand)]
+pub enum AdminCommands {
+    /// Generate synthetic inventory for demos and performance testing
+    Seed(SeedArgs),
+    /// Write an `SQLCipher`-encrypted copy of a plaintext database
+    EncryptDatabase(EncryptDatabaseArgs),
+    /// Write inventory, policies, templates, and configuration to one bundle file
+    ExportBundle(ExportBundleArgs),
+    /// Restore a bundle written by `export-bundle` into an empty datastore
+    ImportBundle(ImportBundleArgs),
+    /// Prune data past the configured `[retention]` policy
+    Retention(RetentionArgs),
+    /// Show the enforced retention policy and the space pruning has reclaimed
+    RetentionStatus,
+    /// Find links, locations, state, and settings referring to deleted
+    /// entities, and repair them
+    Fsck(FsckArgs),
+}
+
+/// Execute admin subcommands.
+///
+/// # Errors
+/// Returns an error if generation, bundle I/O, datastore operations, or output
+/// formatting fail.
+pub async fn execute(
+    command: AdminCommands,
+    datastore: &dyn DataStore,
+    config: &Config,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    match command {
+        AdminCommands::Seed(args) => seed::seed(args, datastore, output_format).await,
+        AdminCommands::ExportBundle(args) => {
+            bundle::export_bundle(&args, datastore, config, output_format).await
+        }
+        AdminCommands::ImportBundle(args) => {
+            bundle::import_bundle(&args, datastore, config, output_format).await
+        }
+        AdminCommands::Retention(args) => {
+            retention::prune(&args, datastore, &config.retention, output_format).await
+        }
+        AdminCommands::RetentionStatus => retention::status(datastore, output_format).await,
+        AdminCommands::Fsck(args) => fsck::fsck(&args, datastore, output_format).await,
+        AdminCommands::EncryptDatabase(_) => Err(anyhow::anyhow!(
+            "encrypt-database must run before the datastore is opened"
+        )),
+    }
+}
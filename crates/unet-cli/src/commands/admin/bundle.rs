@@ -0,0 +1,246 @@
+/// System state bundles for moving an installation between environments
+///
+/// A bundle is a single JSON file holding the inventory, policy files,
+/// templates, which secrets were configured (never their values), and a
+/// redacted configuration snapshot, described by a versioned manifest.
+use anyhow::{Context as _, Result, bail};
+use clap::Args;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use tracing::info;
+use unet_core::config::Config;
+use unet_core::datastore::{DataStore, QueryOptions};
+use unet_core::models::Location;
+
+use super::bundle_files::{check_new, read_tree, write_tree};
+use super::bundle_format::{Bundle, Inventory, Manifest, redact_config, validate_bundle};
+
+/// Settings namespaces carried in bundles
+const SETTING_NAMESPACES: [&str; 6] = [
+    "oid_profiles",
+    "oid_profile_assignments",
+    "policy_batches",
+    "custom_data_defaults",
+    "link_thresholds",
+    "golden_configs",
+];
+
+#[derive(Args)]
+pub struct ExportBundleArgs {
+    /// Bundle file to write
+    #[arg(short, long)]
+    pub output: PathBuf,
+
+    /// Directory of policy files to include
+    #[arg(long)]
+    pub policies: Option<PathBuf>,
+
+    /// Directory of templates to include
+    #[arg(long)]
+    pub templates: Option<PathBuf>,
+
+    /// Overwrite an existing bundle file
+    #[arg(long)]
+    pub force: bool,
+}
+
+#[derive(Args)]
+pub struct ImportBundleArgs {
+    /// Bundle file to read
+    #[arg(short, long)]
+    pub from: PathBuf,
+
+    /// Directory to write the bundled policy files to
+    #[arg(long)]
+    pub policies: Option<PathBuf>,
+
+    /// Directory to write the bundled templates to
+    #[arg(long)]
+    pub templates: Option<PathBuf>,
+
+    /// Check compatibility without importing anything
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// Write the system state bundle
+///
+/// # Errors
+/// Returns an error if the bundle file exists (without `--force`), a
+/// directory cannot be read, or datastore operations fail.
+pub async fn export_bundle(
+    args: &ExportBundleArgs,
+    datastore: &dyn DataStore,
+    config: &Config,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    if args.output.exists() && !args.force {
+        bail!(
+            "{} already exists. Use --force to overwrite",
+            args.output.display()
+        );
+    }
+
+    let options = QueryOptions::default();
+    let mut settings = BTreeMap::new();
+    for namespace in SETTING_NAMESPACES {
+        let documents = datastore.list_settings(namespace).await?;
+        if !documents.is_empty() {
+            settings.insert(namespace.to_string(), documents.into_iter().collect());
+        }
+    }
+    let inventory = Inventory {
+        locations: datastore.list_locations(&options).await?.items,
+        nodes: datastore.list_nodes(&options).await?.items,
+        links: datastore.list_links(&options).await?.items,
+        vendors: datastore.list_vendors().await?,
+        settings,
+    };
+    let policies = args.policies.as_deref().map(read_tree).transpose()?;
+    let templates = args.templates.as_deref().map(read_tree).transpose()?;
+    let (policies, templates) = (policies.unwrap_or_default(), templates.unwrap_or_default());
+    let (config, secrets) = redact_config(config)?;
+
+    let bundle = Bundle {
+        manifest: Manifest::new(&inventory, &policies, &templates),
+        inventory,
+        policies,
+        templates,
+        secrets,
+        config,
+    };
+    std::fs::write(&args.output, serde_json::to_string_pretty(&bundle)?)
+        .with_context(|| format!("Failed to write {}", args.output.display()))?;
+    info!("Wrote system state bundle to {}", args.output.display());
+
+    crate::commands::print_output(
+        &serde_json::json!({ "bundle": args.output, "manifest": bundle.manifest }),
+        output_format,
+    )
+}
+
+/// Restore a system state bundle into an empty datastore
+///
+/// # Errors
+/// Returns an error if the bundle is incompatible with this build, the
+/// datastore already holds inventory, or writing any section fails.
+pub async fn import_bundle(
+    args: &ImportBundleArgs,
+    datastore: &dyn DataStore,
+    config: &Config,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    let content = std::fs::read_to_string(&args.from)
+        .with_context(|| format!("Failed to read {}", args.from.display()))?;
+    let bundle: Bundle = serde_json::from_str(&content)
+        .with_context(|| format!("{} is not a system state bundle", args.from.display()))?;
+    validate_bundle(&bundle)?;
+
+    let counts = datastore.get_entity_counts().await?;
+    if let Some((table, count)) = ["locations", "nodes", "links"].iter().find_map(|table| {
+        counts
+            .get(*table)
+            .filter(|count| **count > 0)
+            .map(|count| (table, count))
+    }) {
+        bail!(
+            "Target datastore already has {count} {table}; bundles import into an empty datastore"
+        );
+    }
+
+    let (_, local_secrets) = redact_config(config)?;
+    let missing_secrets: Vec<&str> = bundle
+        .secrets
+        .iter()
+        .filter(|secret| secret.configured)
+        .filter(|secret| {
+            !local_secrets
+                .iter()
+                .any(|local| local.key == secret.key && local.configured)
+        })
+        .map(|secret| secret.key.as_str())
+        .collect();
+    let mut skipped = Vec::new();
+    for (section, files, dir) in [
+        ("policies", &bundle.policies, &args.policies),
+        ("templates", &bundle.templates, &args.templates),
+    ] {
+        match dir {
+            Some(dir) => check_new(dir, files)?,
+            None if !files.is_empty() => skipped.push(section),
+            None => {}
+        }
+    }
+
+    if !args.dry_run {
+        restore_inventory(&bundle.inventory, datastore).await?;
+        if let Some(dir) = &args.policies {
+            write_tree(dir, &bundle.policies)?;
+        }
+        if let Some(dir) = &args.templates {
+            write_tree(dir, &bundle.templates)?;
+        }
+        info!("Imported system state bundle from {}", args.from.display());
+    }
+
+    crate::commands::print_output(
+        &serde_json::json!({
+            "bundle": args.from,
+            "manifest": bundle.manifest,
+            "dry_run": args.dry_run,
+            "skipped": skipped,
+            "missing_secrets": missing_secrets,
+        }),
+        output_format,
+    )
+}
+
+async fn restore_inventory(inventory: &Inventory, datastore: &dyn DataStore) -> Result<()> {
+    let existing = datastore.list_vendors().await?;
+    for vendor in inventory
+        .vendors
+        .iter()
+        .filter(|vendor| !existing.contains(vendor))
+    {
+        datastore.create_vendor(vendor).await?;
+    }
+    for location in parents_first(&inventory.locations) {
+        datastore.create_location(location).await?;
+    }
+    for node in &inventory.nodes {
+        datastore.create_node(node).await?;
+    }
+    for link in &inventory.links {
+        datastore.create_link(link).await?;
+    }
+    for (namespace, documents) in &inventory.settings {
+        for (key, value) in documents {
+            datastore.put_setting(namespace, key, value).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Orders locations so each parent is created before its children
+fn parents_first(locations: &[Location]) -> Vec<&Location> {
+    let mut ordered: Vec<&Location> = Vec::with_capacity(locations.len());
+    let mut remaining: Vec<&Location> = locations.iter().collect();
+    while !remaining.is_empty() {
+        let before = remaining.len();
+        remaining.retain(|location| {
+            let ready = location.parent_id.is_none_or(|parent| {
+                ordered.iter().any(|done| done.id == parent)
+                    || !locations.iter().any(|other| other.id == parent)
+            });
+            if ready {
+                ordered.push(*location);
+            }
+            !ready
+        });
+        if remaining.len() == before {
+            // Parent cycle; let the datastore report it
+            ordered.append(&mut remaining);
+        }
+    }
+    ordered
+}
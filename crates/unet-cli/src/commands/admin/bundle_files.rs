@@ -0,0 +1,76 @@
+/// Policy and template directories stored in bundles
+use anyhow::{Context as _, Result, bail};
+use std::collections::BTreeMap;
+use std::path::{Component, Path};
+
+/// Reads every file below `dir`, keyed by `/`-separated relative path
+///
+/// Hidden files and directories, such as a `.git` checkout, are skipped.
+pub fn read_tree(dir: &Path) -> Result<BTreeMap<String, String>> {
+    let mut files = BTreeMap::new();
+    read_dir_into(dir, "", &mut files)?;
+    Ok(files)
+}
+
+fn read_dir_into(dir: &Path, prefix: &str, files: &mut BTreeMap<String, String>) -> Result<()> {
+    let entries =
+        std::fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+    for entry in entries {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.starts_with('.') {
+            continue;
+        }
+        let relative = format!("{prefix}{name}");
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            read_dir_into(&path, &format!("{relative}/"), files)?;
+        } else {
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            files.insert(relative, content);
+        }
+    }
+    Ok(())
+}
+
+/// Checks that bundled paths stay inside the directory they are written to
+pub fn check_paths(files: &BTreeMap<String, String>) -> Result<()> {
+    for relative in files.keys() {
+        let safe = Path::new(relative)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+        if !safe {
+            bail!("Bundle contains an unsafe file path: {relative}");
+        }
+    }
+    Ok(())
+}
+
+/// Checks that none of the bundled files exist below `dir` yet
+pub fn check_new(dir: &Path, files: &BTreeMap<String, String>) -> Result<()> {
+    check_paths(files)?;
+    if let Some(existing) = files
+        .keys()
+        .map(|relative| dir.join(relative))
+        .find(|path| path.exists())
+    {
+        bail!("{} already exists", existing.display());
+    }
+    Ok(())
+}
+
+/// Writes bundled files below `dir`, refusing to overwrite existing files
+pub fn write_tree(dir: &Path, files: &BTreeMap<String, String>) -> Result<()> {
+    check_new(dir, files)?;
+    for (relative, content) in files {
+        let path = dir.join(relative);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        std::fs::write(&path, content)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,170 @@
+/// Bundle file layout, compatibility checks, and secret redaction
+use anyhow::{Result, bail};
+use chrono::{DateTime, Utc};
+use migration::{MigrationName as _, Migrator, MigratorTrait as _};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use unet_core::config::Config;
+use unet_core::models::{Link, Location, Node};
+
+use super::bundle_files::check_paths;
+
+/// Identifies bundle files
+const BUNDLE_FORMAT: &str = "unet-bundle";
+/// Bundle layout version written and read by this build
+pub const BUNDLE_SCHEMA_VERSION: u32 = 1;
+
+/// Configuration keys holding secrets
+pub const SECRET_KEYS: [&str; 8] = [
+    "database.encryption_key",
+    "secrets.master_key",
+    "snmp.community",
+    "git.auth_token",
+    "auth.token",
+    "auth.admin_token",
+    "auth.oidc.client_secret",
+    "collectors.password",
+];
+/// Replaces secret values in the configuration snapshot
+const REDACTED: &str = "<redacted>";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Bundle {
+    pub manifest: Manifest,
+    pub inventory: Inventory,
+    /// Policy files by path relative to the policy directory
+    #[serde(default)]
+    pub policies: BTreeMap<String, String>,
+    /// Template files by path relative to the template directory
+    #[serde(default)]
+    pub templates: BTreeMap<String, String>,
+    /// Secrets the source environment had configured
+    #[serde(default)]
+    pub secrets: Vec<SecretMetadata>,
+    /// Source configuration with secret values redacted
+    #[serde(default)]
+    pub config: serde_json::Value,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Manifest {
+    pub format: String,
+    pub schema_version: u32,
+    /// Latest database migration known to the exporting build
+    pub database_schema: String,
+    pub unet_version: String,
+    pub created_at: DateTime<Utc>,
+    /// Number of entries in each bundle section
+    pub counts: BTreeMap<String, usize>,
+}
+
+impl Manifest {
+    /// Describes the sections of a bundle being written by this build
+    pub fn new(
+        inventory: &Inventory,
+        policies: &BTreeMap<String, String>,
+        templates: &BTreeMap<String, String>,
+    ) -> Self {
+        Self {
+            format: BUNDLE_FORMAT.to_string(),
+            schema_version: BUNDLE_SCHEMA_VERSION,
+            database_schema: latest_migration(),
+            unet_version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at: Utc::now(),
+            counts: section_counts(inventory, policies, templates),
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Inventory {
+    pub locations: Vec<Location>,
+    pub nodes: Vec<Node>,
+    pub links: Vec<Link>,
+    pub vendors: Vec<String>,
+    /// Settings documents by namespace and key
+    pub settings: BTreeMap<String, BTreeMap<String, serde_json::Value>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SecretMetadata {
+    pub key: String,
+    pub configured: bool,
+}
+
+/// Checks that this build can import `bundle`
+pub fn validate_bundle(bundle: &Bundle) -> Result<()> {
+    let manifest = &bundle.manifest;
+    if manifest.format != BUNDLE_FORMAT {
+        bail!("Unknown bundle format '{}'", manifest.format);
+    }
+    if manifest.schema_version != BUNDLE_SCHEMA_VERSION {
+        bail!(
+            "Bundle schema version {} is not supported (this build reads version {BUNDLE_SCHEMA_VERSION})",
+            manifest.schema_version
+        );
+    }
+    if !Migrator::migrations()
+        .iter()
+        .any(|migration| migration.name() == manifest.database_schema)
+    {
+        bail!(
+            "Bundle was exported from database schema '{}', which this build does not know; upgrade unet {} before importing",
+            manifest.database_schema,
+            env!("CARGO_PKG_VERSION")
+        );
+    }
+    let actual = section_counts(&bundle.inventory, &bundle.policies, &bundle.templates);
+    if actual != manifest.counts {
+        bail!("Bundle contents do not match the counts in its manifest");
+    }
+    check_paths(&bundle.policies)?;
+    check_paths(&bundle.templates)
+}
+
+/// Serializes `config` with secret values replaced, noting which were set
+pub fn redact_config(config: &Config) -> Result<(serde_json::Value, Vec<SecretMetadata>)> {
+    let mut value = serde_json::to_value(config)?;
+    let secrets = SECRET_KEYS
+        .iter()
+        .map(|key| {
+            let pointer = format!("/{}", key.replace('.', "/"));
+            let field = value.pointer_mut(&pointer);
+            let configured = field.as_ref().is_some_and(|field| !field.is_null());
+            if let Some(field) = field.filter(|_| configured) {
+                *field = serde_json::Value::from(REDACTED);
+            }
+            SecretMetadata {
+                key: (*key).to_string(),
+                configured,
+            }
+        })
+        .collect();
+    Ok((value, secrets))
+}
+
+fn section_counts(
+    inventory: &Inventory,
+    policies: &BTreeMap<String, String>,
+    templates: &BTreeMap<String, String>,
+) -> BTreeMap<String, usize> {
+    BTreeMap::from([
+        ("locations".to_string(), inventory.locations.len()),
+        ("nodes".to_string(), inventory.nodes.len()),
+        ("links".to_string(), inventory.links.len()),
+        ("vendors".to_string(), inventory.vendors.len()),
+        (
+            "settings".to_string(),
+            inventory.settings.values().map(BTreeMap::len).sum(),
+        ),
+        ("policies".to_string(), policies.len()),
+        ("templates".to_string(), templates.len()),
+    ])
+}
+
+fn latest_migration() -> String {
+    Migrator::migrations()
+        .last()
+        .map(|migration| migration.name().to_string())
+        .unwrap_or_default()
+}
@@ -0,0 +1,161 @@
+use super::bundle::{ExportBundleArgs, ImportBundleArgs, export_bundle, import_bundle};
+use super::bundle_format::{BUNDLE_SCHEMA_VERSION, Bundle, redact_config, validate_bundle};
+use std::path::Path;
+use tempfile::TempDir;
+use test_support::sqlite::migrated_store;
+use unet_core::config::Config;
+use unet_core::datastore::DataStore as _;
+use unet_core::datastore::sqlite::SqliteStore;
+use unet_core::models::{DeviceRole, Location, Node, Vendor};
+
+async fn seeded_store() -> SqliteStore {
+    let store = migrated_store().await;
+    let site = Location::new_root("HQ".to_string(), "building".to_string());
+    let mut floor = Location::new_child("Floor 1".to_string(), "floor".to_string(), &site.path);
+    floor.parent_id = Some(site.id);
+    let mut node = Node::new(
+        "edge-1".to_string(),
+        "example.com".to_string(),
+        Vendor::Cisco,
+        DeviceRole::Router,
+    );
+    node.model = "ISR4321".to_string();
+    node.location_id = Some(floor.id);
+
+    store.create_location(&site).await.unwrap();
+    store.create_location(&floor).await.unwrap();
+    store.create_node(&node).await.unwrap();
+    store
+        .put_setting("oid_profiles", "edge", &serde_json::json!({"oids": []}))
+        .await
+        .unwrap();
+    store
+}
+
+async fn export_to(store: &SqliteStore, dir: &Path, config: &Config) -> std::path::PathBuf {
+    let policies = dir.join("policies");
+    std::fs::create_dir_all(policies.join("shared")).unwrap();
+    std::fs::write(
+        policies.join("base.rules"),
+        "INCLUDE \"shared/common.rules\"\n",
+    )
+    .unwrap();
+    std::fs::write(policies.join("shared/common.rules"), "# shared\n").unwrap();
+    std::fs::create_dir_all(policies.join(".git")).unwrap();
+    std::fs::write(policies.join(".git/HEAD"), "ref: refs/heads/main\n").unwrap();
+
+    let args = ExportBundleArgs {
+        output: dir.join("bundle.json"),
+        policies: Some(policies),
+        templates: None,
+        force: false,
+    };
+    export_bundle(&args, store, config, crate::OutputFormat::Json)
+        .await
+        .unwrap();
+    args.output
+}
+
+fn read_bundle(path: &Path) -> Bundle {
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn test_bundle_round_trip_restores_inventory_and_policies() {
+    let temp = TempDir::new().unwrap();
+    let config = Config::default();
+    let bundle_path = export_to(&seeded_store().await, temp.path(), &config).await;
+
+    let bundle = read_bundle(&bundle_path);
+    assert_eq!(bundle.manifest.schema_version, BUNDLE_SCHEMA_VERSION);
+    assert_eq!(bundle.manifest.counts.get("locations"), Some(&2));
+    assert_eq!(bundle.manifest.counts.get("policies"), Some(&2));
+    assert!(!bundle.policies.contains_key(".git/HEAD"));
+
+    let target = migrated_store().await;
+    let restored = temp.path().join("restored");
+    let args = ImportBundleArgs {
+        from: bundle_path,
+        policies: Some(restored.clone()),
+        templates: None,
+        dry_run: false,
+    };
+    import_bundle(&args, &target, &config, crate::OutputFormat::Json)
+        .await
+        .unwrap();
+
+    let counts = target.get_entity_counts().await.unwrap();
+    assert_eq!(counts.get("locations"), Some(&2));
+    assert_eq!(counts.get("nodes"), Some(&1));
+    assert!(
+        target
+            .get_setting("oid_profiles", "edge")
+            .await
+            .unwrap()
+            .is_some()
+    );
+    assert!(restored.join("shared/common.rules").exists());
+}
+
+#[tokio::test]
+async fn test_import_bundle_requires_empty_datastore() {
+    let temp = TempDir::new().unwrap();
+    let config = Config::default();
+    let store = seeded_store().await;
+    let bundle_path = export_to(&store, temp.path(), &config).await;
+
+    let args = ImportBundleArgs {
+        from: bundle_path,
+        policies: None,
+        templates: None,
+        dry_run: true,
+    };
+    let error = import_bundle(&args, &store, &config, crate::OutputFormat::Json)
+        .await
+        .unwrap_err();
+
+    assert!(error.to_string().contains("empty datastore"));
+}
+
+#[tokio::test]
+async fn test_validate_bundle_rejects_incompatible_versions() {
+    let temp = TempDir::new().unwrap();
+    let bundle_path = export_to(&migrated_store().await, temp.path(), &Config::default()).await;
+
+    let mut bundle = read_bundle(&bundle_path);
+    bundle.manifest.schema_version = BUNDLE_SCHEMA_VERSION + 1;
+    let error = validate_bundle(&bundle).unwrap_err();
+    assert!(error.to_string().contains("is not supported"));
+
+    let mut bundle = read_bundle(&bundle_path);
+    bundle.manifest.database_schema = "m29991231_000001_future".to_string();
+    let error = validate_bundle(&bundle).unwrap_err();
+    assert!(error.to_string().contains("upgrade unet"));
+
+    let mut bundle = read_bundle(&bundle_path);
+    bundle
+        .policies
+        .insert("../escape.rules".to_string(), String::new());
+    bundle.manifest.counts.insert("policies".to_string(), 3);
+    let error = validate_bundle(&bundle).unwrap_err();
+    assert!(error.to_string().contains("unsafe file path"));
+}
+
+#[test]
+fn test_redact_config_hides_secret_values() {
+    let mut config = Config::default();
+    config.auth.token = Some("operator-token".to_string());
+
+    let (snapshot, secrets) = redact_config(&config).unwrap();
+
+    assert_eq!(snapshot.pointer("/auth/token").unwrap(), "<redacted>");
+    assert_eq!(snapshot.pointer("/snmp/community").unwrap(), "<redacted>");
+    assert!(snapshot.pointer("/auth/admin_token").unwrap().is_null());
+    assert!(!snapshot.to_string().contains("operator-token"));
+    let configured: Vec<&str> = secrets
+        .iter()
+        .filter(|secret| secret.configured)
+        .map(|secret| secret.key.as_str())
+        .collect();
+    assert_eq!(configured, vec!["snmp.community", "auth.token"]);
+}
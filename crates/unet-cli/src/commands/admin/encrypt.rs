@@ -0,0 +1,80 @@
+/// Encryption of an existing plaintext database with `SQLCipher`
+use anyhow::Result;
+use clap::Args;
+use std::path::PathBuf;
+use unet_core::datastore::sqlite;
+
+#[derive(Args)]
+pub struct EncryptDatabaseArgs {
+    /// Path for the encrypted copy; must not exist
+    #[arg(short, long)]
+    pub output: PathBuf,
+}
+
+/// Writes an encrypted copy of the database at `source_url`
+///
+/// Runs before the datastore is opened, since the source is plaintext while
+/// the configured key applies to the encrypted copy.
+pub async fn encrypt_database(
+    args: &EncryptDatabaseArgs,
+    source_url: &str,
+    encryption_key: Option<&str>,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    let key = encryption_key.ok_or_else(|| {
+        anyhow::anyhow!(
+            "No encryption key configured; set database.encryption_key or UNET_DATABASE__ENCRYPTION_KEY"
+        )
+    })?;
+
+    sqlite::encrypt_database(source_url, &args.output, key).await?;
+
+    let output = serde_json::json!({
+        "message": "Encrypted copy written; point database.url at it to use it",
+        "source": source_url,
+        "output": args.output,
+    });
+    crate::commands::print_output(&output, output_format)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_encrypt_database_requires_key() {
+        let args = EncryptDatabaseArgs {
+            output: PathBuf::from("unused.db"),
+        };
+
+        let result =
+            encrypt_database(&args, "sqlite::memory:", None, crate::OutputFormat::Json).await;
+
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("No encryption key")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_encrypt_database_rejects_existing_output() {
+        let existing = tempfile::NamedTempFile::new().unwrap();
+        let args = EncryptDatabaseArgs {
+            output: existing.path().to_path_buf(),
+        };
+
+        let result = encrypt_database(
+            &args,
+            "sqlite::memory:",
+            Some("secret"),
+            crate::OutputFormat::Json,
+        )
+        .await;
+
+        assert!(result.unwrap_err().to_string().contains("already exists"));
+    }
+}
@@ -0,0 +1,77 @@
+/// Checks for dangling references and the report of the last check
+use anyhow::Result;
+use clap::Args;
+use unet_core::datastore::DataStore;
+use unet_core::integrity::{IssueKind, check, last_report};
+
+#[derive(Args)]
+pub struct FsckArgs {
+    /// Issue kinds to repair (`orphaned-link`, `missing-parent`,
+    /// `missing-location`, `orphaned-state`, `orphaned-setting`)
+    #[arg(long, value_delimiter = ',')]
+    pub repair: Vec<IssueKind>,
+    /// Repair every kind of issue
+    #[arg(long, conflicts_with = "repair")]
+    pub repair_all: bool,
+    /// Show the report of the last check, such as the server's scheduled one,
+    /// instead of checking
+    #[arg(long, conflicts_with_all = ["repair", "repair_all"])]
+    pub last: bool,
+}
+
+/// Checks for dangling references, repairing the requested kinds
+pub async fn fsck(
+    args: &FsckArgs,
+    datastore: &dyn DataStore,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    if args.last {
+        let report = last_report(datastore)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("No integrity check has run"))?;
+        return crate::commands::print_output(&report, output_format);
+    }
+    let repair = if args.repair_all {
+        IssueKind::ALL.to_vec()
+    } else {
+        args.repair.clone()
+    };
+    let report = check(datastore, &repair, chrono::Utc::now()).await?;
+    crate::commands::print_output(&report, output_format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_support::sqlite::migrated_store;
+
+    #[tokio::test]
+    async fn test_fsck_keeps_its_report_for_last() {
+        let store = migrated_store().await;
+        let last = FsckArgs {
+            repair: Vec::new(),
+            repair_all: false,
+            last: true,
+        };
+        assert!(
+            fsck(&last, &store, crate::OutputFormat::Json)
+                .await
+                .is_err()
+        );
+
+        let repair_all = FsckArgs {
+            repair: Vec::new(),
+            repair_all: true,
+            last: false,
+        };
+        fsck(&repair_all, &store, crate::OutputFormat::Json)
+            .await
+            .unwrap();
+
+        let report = last_report(&store).await.unwrap().unwrap();
+        assert_eq!(report.repair, IssueKind::ALL);
+        fsck(&last, &store, crate::OutputFormat::Json)
+            .await
+            .unwrap();
+    }
+}
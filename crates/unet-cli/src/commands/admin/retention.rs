@@ -0,0 +1,53 @@
+/// Retention pruning and the space it has reclaimed
+use anyhow::Result;
+use clap::Args;
+use unet_core::config::RetentionConfig;
+use unet_core::datastore::DataStore;
+use unet_core::retention::{enforce, retention_status};
+
+#[derive(Args)]
+pub struct RetentionArgs {
+    /// Report what would be pruned without deleting anything
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// Prunes every data class past the configured retention
+pub async fn prune(
+    args: &RetentionArgs,
+    datastore: &dyn DataStore,
+    policy: &RetentionConfig,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    let report = enforce(datastore, policy, chrono::Utc::now(), args.dry_run).await?;
+    crate::commands::print_output(&report, output_format)
+}
+
+/// Shows the policy the server enforces and the space pruning has reclaimed
+pub async fn status(datastore: &dyn DataStore, output_format: crate::OutputFormat) -> Result<()> {
+    let status = retention_status(datastore).await?;
+    crate::commands::print_output(&status, output_format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_support::sqlite::migrated_store;
+
+    #[tokio::test]
+    async fn test_retention_dry_run_leaves_status_untouched() {
+        let store = migrated_store().await;
+
+        prune(
+            &RetentionArgs { dry_run: true },
+            &store,
+            &RetentionConfig::default(),
+            crate::OutputFormat::Json,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(retention_status(&store).await.unwrap().runs, 0);
+        status(&store, crate::OutputFormat::Json).await.unwrap();
+    }
+}
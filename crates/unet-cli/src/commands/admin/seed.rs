@@ -0,0 +1,188 @@
+/// Synthetic inventory seeding for demos and performance testing
+use anyhow::Result;
+use clap::Args;
+use serde::Serialize;
+use tracing::info;
+use unet_core::datastore::{BatchOperation, BatchResult, DataStore};
+use unet_core::seed::{DEFAULT_SEED, DEFAULT_SEED_DOMAIN, SeedOptions, SeedProfile, generate};
+
+#[derive(Args)]
+pub struct SeedArgs {
+    /// Inventory profile (demo, large)
+    #[arg(long, default_value = "demo")]
+    pub profile: SeedProfile,
+
+    /// Number of nodes to generate (defaults to the profile size)
+    #[arg(long)]
+    pub nodes: Option<usize>,
+
+    /// Seed for deterministic generation; the same seed yields identical inventory
+    #[arg(long, default_value_t = DEFAULT_SEED)]
+    pub seed: u64,
+
+    /// Domain assigned to generated nodes
+    #[arg(long, default_value = DEFAULT_SEED_DOMAIN)]
+    pub domain: String,
+}
+
+/// Summary of a seeding run for output
+#[derive(Debug, Serialize)]
+pub struct SeedSummary {
+    pub profile: SeedProfile,
+    pub seed: u64,
+    pub locations: usize,
+    pub nodes: usize,
+    pub links: usize,
+    pub errors: Vec<String>,
+}
+
+pub async fn seed(
+    args: SeedArgs,
+    datastore: &dyn DataStore,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    let mut options = SeedOptions::new(args.profile)
+        .with_seed(args.seed)
+        .with_domain(args.domain);
+    if let Some(nodes) = args.nodes {
+        options = options.with_node_count(nodes);
+    }
+
+    let dataset = generate(&options).map_err(|e| anyhow::anyhow!("Seed generation failed: {e}"))?;
+    info!(
+        "Seeding {} locations, {} nodes, {} links (profile {}, seed {})",
+        dataset.locations.len(),
+        dataset.nodes.len(),
+        dataset.links.len(),
+        options.profile,
+        options.seed
+    );
+
+    let mut errors = Vec::new();
+
+    // Insert in dependency order: locations, nodes, links
+    let locations = datastore
+        .batch_locations(&inserts(dataset.locations))
+        .await?;
+    collect_errors("location", &locations, &mut errors);
+
+    let nodes = datastore.batch_nodes(&inserts(dataset.nodes)).await?;
+    collect_errors("node", &nodes, &mut errors);
+
+    let links = datastore.batch_links(&inserts(dataset.links)).await?;
+    collect_errors("link", &links, &mut errors);
+
+    let summary = SeedSummary {
+        profile: options.profile,
+        seed: options.seed,
+        locations: locations.success_count,
+        nodes: nodes.success_count,
+        links: links.success_count,
+        errors,
+    };
+    crate::commands::print_output(&summary, output_format)?;
+
+    if summary.errors.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "Seeding completed with {} errors",
+            summary.errors.len()
+        ))
+    }
+}
+
+fn inserts<T>(items: Vec<T>) -> Vec<BatchOperation<T>> {
+    items.into_iter().map(BatchOperation::Insert).collect()
+}
+
+fn collect_errors(entity: &str, result: &BatchResult, errors: &mut Vec<String>) {
+    errors.extend(
+        result
+            .errors
+            .iter()
+            .map(|(index, error)| format!("{entity} #{index}: {error}")),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unet_core::datastore::{DataStoreError, MockDataStore};
+
+    fn accept_all(count: usize) -> BatchResult {
+        BatchResult {
+            success_count: count,
+            error_count: 0,
+            errors: Vec::new(),
+        }
+    }
+
+    fn seed_args(nodes: usize) -> SeedArgs {
+        SeedArgs {
+            profile: SeedProfile::Demo,
+            nodes: Some(nodes),
+            seed: 7,
+            domain: "lab.example.com".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_seed_inserts_generated_inventory() {
+        let mut mock = MockDataStore::new();
+        mock.expect_batch_locations().times(1).returning(|ops| {
+            let result = accept_all(ops.len());
+            Box::pin(async move { Ok(result) })
+        });
+        mock.expect_batch_nodes()
+            .times(1)
+            .withf(|ops| ops.len() == 15)
+            .returning(|ops| {
+                let result = accept_all(ops.len());
+                Box::pin(async move { Ok(result) })
+            });
+        mock.expect_batch_links().times(1).returning(|ops| {
+            let result = accept_all(ops.len());
+            Box::pin(async move { Ok(result) })
+        });
+
+        let result = seed(seed_args(15), &mock, crate::OutputFormat::Json).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_seed_reports_batch_errors() {
+        let mut mock = MockDataStore::new();
+        mock.expect_batch_locations().returning(|ops| {
+            let result = accept_all(ops.len());
+            Box::pin(async move { Ok(result) })
+        });
+        mock.expect_batch_nodes().returning(|ops| {
+            let result = BatchResult {
+                success_count: ops.len() - 1,
+                error_count: 1,
+                errors: vec![(
+                    0,
+                    DataStoreError::ConstraintViolation {
+                        message: "duplicate".to_string(),
+                    },
+                )],
+            };
+            Box::pin(async move { Ok(result) })
+        });
+        mock.expect_batch_links().returning(|ops| {
+            let result = accept_all(ops.len());
+            Box::pin(async move { Ok(result) })
+        });
+
+        let result = seed(seed_args(5), &mock, crate::OutputFormat::Json).await;
+        assert!(result.unwrap_err().to_string().contains("1 errors"));
+    }
+
+    #[tokio::test]
+    async fn test_seed_rejects_zero_nodes() {
+        let mock = MockDataStore::new();
+        let result = seed(seed_args(0), &mock, crate::OutputFormat::Json).await;
+        assert!(result.is_err());
+    }
+}
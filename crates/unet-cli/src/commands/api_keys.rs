@@ -0,0 +1,118 @@
+/// Scoped API key management commands
+use anyhow::{Result, anyhow};
+use chrono::TimeDelta;
+use clap::{Args, Subcommand};
+use unet_core::api_keys::{ApiScope, create_key, list_keys, revoke_key};
+use unet_core::datastore::DataStore;
+use unet_core::snmp::pauses::parse_duration;
+use uuid::Uuid;
+
+use crate::confirm::{Confirmation, confirm};
+
+#[derive(Subcommand)]
+pub enum ApiKeyCommands {
+    /// List API keys
+    List,
+    /// Create an API key and print its token once
+    Create(CreateApiKeyArgs),
+    /// Revoke an API key so its token is no longer accepted
+    Revoke(RevokeApiKeyArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct CreateApiKeyArgs {
+    /// Who or what the key is for
+    #[arg(long)]
+    pub name: String,
+    /// Scope to grant (nodes:read, nodes:write, policies:evaluate,
+    /// snmp:execute, or admin); repeat for several
+    #[arg(long = "scope", value_name = "SCOPE", required = true)]
+    pub scopes: Vec<ApiScope>,
+    /// How long the key is accepted, e.g. 30m, 12h, or 90d; never expires if omitted
+    #[arg(long, value_parser = parse_duration)]
+    pub expires_in: Option<TimeDelta>,
+}
+
+#[derive(Args, Debug)]
+pub struct RevokeApiKeyArgs {
+    /// API key ID
+    pub id: Uuid,
+    /// Skip confirmation prompt
+    #[arg(short = 'y', long)]
+    pub yes: bool,
+}
+
+/// Execute API key subcommands.
+///
+/// # Errors
+/// Returns an error if a key is invalid or does not exist, datastore
+/// operations fail, or output formatting fails.
+pub async fn execute(
+    command: ApiKeyCommands,
+    datastore: &dyn DataStore,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    match command {
+        ApiKeyCommands::List => {
+            crate::commands::print_output(&list_keys(datastore).await?, output_format)
+        }
+        ApiKeyCommands::Create(args) => {
+            let (key, token) =
+                create_key(datastore, &args.name, &args.scopes, args.expires_in).await?;
+            let output = serde_json::json!({
+                "message": "Store this token now; it cannot be shown again",
+                "key": key,
+                "token": token,
+            });
+            crate::commands::print_output(&output, output_format)
+        }
+        ApiKeyCommands::Revoke(args) => {
+            let key = list_keys(datastore)
+                .await?
+                .into_iter()
+                .find(|key| key.id == args.id)
+                .ok_or_else(|| anyhow!("API key {} not found", args.id))?;
+            let confirmation =
+                Confirmation::new("Revoke API key").affects(format!("{} ({})", key.name, key.id));
+            if !confirm(args.yes, &confirmation)? {
+                return Ok(());
+            }
+            revoke_key(datastore, args.id).await?;
+            let output = serde_json::json!({
+                "message": "API key revoked",
+                "id": args.id,
+                "name": key.name,
+            });
+            crate::commands::print_output(&output, output_format)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unet_core::datastore::MockDataStore;
+
+    #[tokio::test]
+    async fn test_create_stores_hashed_key_with_scopes() {
+        let mut mock = MockDataStore::new();
+        mock.expect_put_setting()
+            .withf(|namespace, hash, value| {
+                namespace == "api_keys"
+                    && hash.len() == 64
+                    && value["name"] == "ci"
+                    && value["scopes"] == serde_json::json!(["nodes:read", "policies:evaluate"])
+                    && value["expires_at"].is_string()
+            })
+            .times(1)
+            .returning(|_, _, _| Box::pin(async { Ok(()) }));
+
+        let command = ApiKeyCommands::Create(CreateApiKeyArgs {
+            name: "ci".to_string(),
+            scopes: vec![ApiScope::PoliciesEvaluate, ApiScope::NodesRead],
+            expires_in: Some(TimeDelta::days(90)),
+        });
+        let result = execute(command, &mock, crate::OutputFormat::Json).await;
+        assert!(result.is_ok());
+    }
+}
@@ -0,0 +1,167 @@
+/// Configuration snapshot and change window commands
+use anyhow::Result;
+use chrono::{DateTime, TimeDelta, Utc};
+use clap::{Args, Subcommand};
+use std::path::PathBuf;
+use unet_core::config_changes::{
+    ChangeScope, ChangeWindow, delete_window, latest_snapshot, list_windows, record_snapshot,
+    save_window,
+};
+use unet_core::datastore::DataStore;
+use unet_core::snmp::pauses::parse_duration;
+use uuid::Uuid;
+
+use crate::resolve;
+
+#[derive(Subcommand)]
+pub enum ChangeCommands {
+    /// Store a node's collected configuration and compare it with the previous one
+    Submit(SubmitArgs),
+    /// Show a node's latest collected configuration
+    Snapshot(SnapshotArgs),
+    /// Approve changes to a node, a location, or everything for a period
+    Approve(ApproveArgs),
+    /// Remove a change window
+    Revoke(RevokeArgs),
+    /// List change windows
+    Windows,
+}
+
+#[derive(Args, Debug)]
+pub struct SubmitArgs {
+    /// Node name, FQDN, or ID
+    pub node: String,
+    /// File holding the collected configuration
+    pub file: PathBuf,
+    /// When the configuration was collected (RFC 3339); defaults to now
+    #[arg(long)]
+    pub collected_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Args, Debug)]
+pub struct SnapshotArgs {
+    /// Node name, FQDN, or ID
+    pub node: String,
+}
+
+#[derive(Args, Debug)]
+pub struct ApproveArgs {
+    /// Location name, path, or ID; nodes in sub-locations are covered too
+    #[arg(long, conflicts_with = "node")]
+    pub location: Option<String>,
+    /// Node name, FQDN, or ID
+    #[arg(long)]
+    pub node: Option<String>,
+    /// When the window opens (RFC 3339); defaults to now
+    #[arg(long)]
+    pub start: Option<DateTime<Utc>>,
+    /// How long the window stays open, e.g. 30m, 2h, or 1d
+    #[arg(long, value_parser = parse_duration)]
+    pub duration: TimeDelta,
+    /// Change request that approved the window, such as a ticket number
+    #[arg(long)]
+    pub change_request: Option<String>,
+    /// What the change is
+    #[arg(long)]
+    pub description: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct RevokeArgs {
+    /// Change window ID
+    pub id: Uuid,
+}
+
+/// Execute configuration change subcommands.
+///
+/// # Errors
+/// Returns an error if the node or location cannot be found, the file cannot
+/// be read, the window is invalid or does not exist, datastore operations
+/// fail, or output formatting fails.
+pub async fn execute(
+    command: ChangeCommands,
+    datastore: &dyn DataStore,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    match command {
+        ChangeCommands::Submit(args) => {
+            let id = resolve::node(datastore, &args.node, false).await?;
+            let node = datastore.get_node_required(&id).await?;
+            let config = std::fs::read_to_string(&args.file)?;
+            let collected_at = args.collected_at.unwrap_or_else(Utc::now);
+            let comparison = record_snapshot(datastore, &node, config, collected_at).await?;
+            crate::commands::print_output(&comparison, output_format)
+        }
+        ChangeCommands::Snapshot(args) => {
+            let id = resolve::node(datastore, &args.node, false).await?;
+            let snapshot = latest_snapshot(datastore, id)
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("No configuration collected from {}", args.node))?;
+            crate::commands::print_output(&snapshot, output_format)
+        }
+        ChangeCommands::Approve(args) => {
+            let scope = match (&args.location, &args.node) {
+                (Some(location), _) => {
+                    let id = resolve::location(datastore, location, false).await?;
+                    ChangeScope::Location(datastore.get_location_required(&id).await?.id)
+                }
+                (None, Some(node)) => {
+                    let id = resolve::node(datastore, node, false).await?;
+                    ChangeScope::Node(datastore.get_node_required(&id).await?.id)
+                }
+                (None, None) => ChangeScope::Global,
+            };
+            let starts_at = args.start.unwrap_or_else(Utc::now);
+            let window = ChangeWindow::new(
+                scope,
+                starts_at,
+                starts_at + args.duration,
+                args.change_request,
+                args.description,
+            )?;
+            save_window(datastore, &window).await?;
+            crate::commands::print_output(&window, output_format)
+        }
+        ChangeCommands::Revoke(args) => {
+            delete_window(datastore, args.id).await?;
+            let output = serde_json::json!({
+                "message": "Change window removed",
+                "id": args.id,
+            });
+            crate::commands::print_output(&output, output_format)
+        }
+        ChangeCommands::Windows => {
+            crate::commands::print_output(&list_windows(datastore).await?, output_format)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unet_core::datastore::MockDataStore;
+
+    #[tokio::test]
+    async fn test_global_window_with_change_request() {
+        let mut mock = MockDataStore::new();
+        mock.expect_put_setting()
+            .withf(|namespace, _, value| {
+                namespace == "change_windows"
+                    && value["scope"]["type"] == "global"
+                    && value["change_request"] == "CHG0042"
+            })
+            .times(1)
+            .returning(|_, _, _| Box::pin(async { Ok(()) }));
+
+        let command = ChangeCommands::Approve(ApproveArgs {
+            location: None,
+            node: None,
+            start: None,
+            duration: TimeDelta::hours(2),
+            change_request: Some("CHG0042".to_string()),
+            description: None,
+        });
+        let result = execute(command, &mock, crate::OutputFormat::Json).await;
+        assert!(result.is_ok());
+    }
+}
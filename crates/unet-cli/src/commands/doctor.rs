@@ -0,0 +1,203 @@
+/// Environment and configuration diagnostics
+use anyhow::Result;
+use clap::Args;
+use serde::Serialize;
+use unet_core::config::Config;
+
+use crate::AppContext;
+
+mod checks;
+mod probes;
+
+#[derive(Args, Debug, Clone)]
+pub struct DoctorArgs {
+    /// Number of nodes with a management IP to probe over SNMP
+    #[arg(long, default_value = "5")]
+    pub snmp_sample: usize,
+
+    /// Skip the SNMP reachability probe
+    #[arg(long)]
+    pub skip_snmp: bool,
+}
+
+/// Outcome of a single check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+    Skip,
+}
+
+impl CheckStatus {
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Pass => "PASS",
+            Self::Warn => "WARN",
+            Self::Fail => "FAIL",
+            Self::Skip => "SKIP",
+        }
+    }
+}
+
+/// Result of a single check with an optional remediation hint
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckResult {
+    pub check: &'static str,
+    pub status: CheckStatus,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+impl CheckResult {
+    fn new(check: &'static str, status: CheckStatus, message: impl Into<String>) -> Self {
+        Self {
+            check,
+            status,
+            message: message.into(),
+            hint: None,
+        }
+    }
+
+    fn with_hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+}
+
+/// Settings the checks run against, resolved from global options and config
+pub struct DoctorInput<'a> {
+    pub config: &'a Config,
+    pub database_url: &'a str,
+    pub server_url: Option<&'a str>,
+    pub token: Option<&'a str>,
+}
+
+/// Runs all checks and prints the results
+///
+/// Runs before the datastore is opened so a broken database is reported
+/// instead of aborting the command.
+///
+/// # Errors
+/// Returns an error if any check fails or output formatting fails.
+pub async fn execute(
+    args: &DoctorArgs,
+    ctx: &AppContext,
+    input: &DoctorInput<'_>,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    let results = run_checks(args, ctx, input).await;
+
+    match output_format {
+        crate::OutputFormat::Table => print!("{}", render_table(&results)),
+        _ => crate::commands::print_output(&results, output_format)?,
+    }
+
+    let failed = results
+        .iter()
+        .filter(|result| result.status == CheckStatus::Fail)
+        .count();
+    if failed > 0 {
+        anyhow::bail!("{failed} of {} doctor checks failed", results.len());
+    }
+    Ok(())
+}
+
+async fn run_checks(
+    args: &DoctorArgs,
+    ctx: &AppContext,
+    input: &DoctorInput<'_>,
+) -> Vec<CheckResult> {
+    let (database, store) = checks::database(ctx, input).await;
+    let snmp = if args.skip_snmp {
+        CheckResult::new("snmp", CheckStatus::Skip, "Skipped with --skip-snmp")
+    } else if let Some(store) = &store {
+        probes::snmp(store, &input.config.snmp, args.snmp_sample).await
+    } else {
+        CheckResult::new(
+            "snmp",
+            CheckStatus::Skip,
+            "Database unavailable; no nodes to probe",
+        )
+    };
+
+    vec![
+        database,
+        checks::policies(&input.config.git),
+        checks::git(&input.config.git),
+        checks::secrets(input),
+        snmp,
+        probes::server(input.server_url, input.token).await,
+    ]
+}
+
+fn render_table(results: &[CheckResult]) -> String {
+    use std::fmt::Write as _;
+
+    let mut out = String::new();
+    for result in results {
+        let _ = writeln!(
+            out,
+            "[{}] {:<9} {}",
+            result.status.label(),
+            result.check,
+            result.message
+        );
+        if let Some(hint) = &result.hint {
+            let _ = writeln!(out, "       {:<9} hint: {hint}", "");
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_table_includes_hints() {
+        let results = vec![
+            CheckResult::new("database", CheckStatus::Pass, "Connected"),
+            CheckResult::new("server", CheckStatus::Fail, "Connection refused")
+                .with_hint("Start unet-server"),
+        ];
+
+        let table = render_table(&results);
+
+        assert!(table.contains("[PASS] database  Connected"));
+        assert!(table.contains("[FAIL] server    Connection refused"));
+        assert!(table.contains("hint: Start unet-server"));
+    }
+
+    #[tokio::test]
+    async fn test_execute_reports_failed_checks() {
+        let config = Config::default();
+        let input = DoctorInput {
+            config: &config,
+            database_url: "sqlite:///nonexistent/dir/unet.db",
+            server_url: None,
+            token: None,
+        };
+        let args = DoctorArgs {
+            snmp_sample: 5,
+            skip_snmp: false,
+        };
+
+        let result = execute(
+            &args,
+            &AppContext::default(),
+            &input,
+            crate::OutputFormat::Json,
+        )
+        .await;
+
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("doctor checks failed")
+        );
+    }
+}
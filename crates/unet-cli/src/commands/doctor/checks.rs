@@ -0,0 +1,299 @@
+/// Local checks: database, policies, Git checkout, and secrets
+use migration::{Migrator, MigratorTrait as _};
+use std::path::Path;
+use unet_core::config::GitConfig;
+use unet_core::datastore::sqlite::SqliteStore;
+use unet_core::policy::PolicyLoader;
+
+use super::{CheckResult, CheckStatus, DoctorInput};
+use crate::AppContext;
+
+/// Opens the database and compares applied migrations with the binary's
+pub async fn database(
+    ctx: &AppContext,
+    input: &DoctorInput<'_>,
+) -> (CheckResult, Option<SqliteStore>) {
+    let url = input.database_url;
+    let connection = match input.config.database_encryption_key() {
+        Ok(Some(key)) => SqliteStore::new_encrypted(url, &key)
+            .await
+            .map(|store| store.connection().clone())
+            .map_err(anyhow::Error::from),
+        Ok(None) => (ctx.connect)(url).await.map(|db| db.0),
+        Err(e) => Err(anyhow::Error::from(e)),
+    };
+    let conn = match connection {
+        Ok(conn) => conn,
+        Err(e) => {
+            let result = CheckResult::new(
+                "database",
+                CheckStatus::Fail,
+                format!("Cannot open {url}: {e}"),
+            )
+            .with_hint("Check --database-url; add ?mode=rwc to create a new SQLite file");
+            return (result, None);
+        }
+    };
+
+    let result = match (
+        Migrator::get_applied_migrations(&conn).await,
+        Migrator::get_pending_migrations(&conn).await,
+    ) {
+        (Ok(applied), Ok(pending)) if pending.is_empty() => CheckResult::new(
+            "database",
+            CheckStatus::Pass,
+            format!(
+                "Connected to {url}; schema is current ({} migrations applied)",
+                applied.len()
+            ),
+        ),
+        (Ok(applied), Ok(pending)) => CheckResult::new(
+            "database",
+            CheckStatus::Warn,
+            format!(
+                "Connected to {url}; {} of {} migrations pending",
+                pending.len(),
+                applied.len() + pending.len()
+            ),
+        )
+        .with_hint(
+            "Any datastore command (for example `unet nodes list`) applies pending migrations",
+        ),
+        (Err(e), _) | (_, Err(e)) => CheckResult::new(
+            "database",
+            CheckStatus::Fail,
+            format!("Connected to {url} but cannot read the schema version: {e}"),
+        ),
+    };
+    (result, Some(SqliteStore::from_connection(conn)))
+}
+
+/// Loads the policy directory and reports files that fail to parse
+pub fn policies(git: &GitConfig) -> CheckResult {
+    let Some(dir) = git.local_directory.as_deref() else {
+        return CheckResult::new(
+            "policies",
+            CheckStatus::Skip,
+            "No policy directory configured",
+        )
+        .with_hint("Set git.local_directory or UNET_GIT__LOCAL_DIRECTORY");
+    };
+    if !Path::new(dir).is_dir() {
+        return CheckResult::new(
+            "policies",
+            CheckStatus::Fail,
+            format!("Policy directory {dir} does not exist"),
+        )
+        .with_hint("Create the directory or correct git.local_directory");
+    }
+
+    match PolicyLoader::new(git.clone()).load_policies() {
+        Ok(result) if result.errors.is_empty() => {
+            let rules: usize = result.loaded.iter().map(|file| file.rules.len()).sum();
+            CheckResult::new(
+                "policies",
+                CheckStatus::Pass,
+                format!(
+                    "{} policy files with {rules} rules in {dir}",
+                    result.loaded.len()
+                ),
+            )
+        }
+        Ok(result) => {
+            let (path, error) = &result.errors[0];
+            CheckResult::new(
+                "policies",
+                CheckStatus::Fail,
+                format!(
+                    "{} of {} policy files in {dir} failed to parse; first: {}: {error}",
+                    result.errors.len(),
+                    result.total_files,
+                    path.display()
+                ),
+            )
+            .with_hint("Run `unet policy validate` on the directory for details")
+        }
+        Err(e) => CheckResult::new(
+            "policies",
+            CheckStatus::Fail,
+            format!("Cannot load policies from {dir}: {e}"),
+        ),
+    }
+}
+
+/// Reports whether the policy directory is a Git checkout on the configured branch
+///
+/// unet does not fetch repositories itself, so this reads the checkout's
+/// `HEAD` rather than contacting the remote.
+pub fn git(git: &GitConfig) -> CheckResult {
+    if let Some(url) = git
+        .repository_url
+        .as_deref()
+        .or(git.policies_repo.as_deref())
+    {
+        return CheckResult::new(
+            "git",
+            CheckStatus::Warn,
+            format!("Repository {url} is configured but unet does not sync Git repositories"),
+        )
+        .with_hint("Clone the repository into git.local_directory and update it outside unet");
+    }
+    let Some(dir) = git.local_directory.as_deref() else {
+        return CheckResult::new("git", CheckStatus::Skip, "No policy repository configured");
+    };
+
+    let Ok(head) = std::fs::read_to_string(Path::new(dir).join(".git").join("HEAD")) else {
+        return CheckResult::new(
+            "git",
+            CheckStatus::Skip,
+            format!("{dir} is not a Git checkout; policies are read as plain files"),
+        );
+    };
+    match head.trim().strip_prefix("ref: refs/heads/") {
+        Some(branch) if branch == git.branch => CheckResult::new(
+            "git",
+            CheckStatus::Pass,
+            format!("{dir} is a Git checkout on branch {branch}"),
+        ),
+        Some(branch) => CheckResult::new(
+            "git",
+            CheckStatus::Warn,
+            format!(
+                "{dir} is on branch {branch}, but git.branch is {}",
+                git.branch
+            ),
+        )
+        .with_hint(format!("git -C {dir} checkout {}", git.branch)),
+        None => CheckResult::new(
+            "git",
+            CheckStatus::Warn,
+            format!("{dir} has a detached HEAD"),
+        )
+        .with_hint(format!("git -C {dir} checkout {}", git.branch)),
+    }
+}
+
+/// Checks that configured secrets can be used by this build
+///
+/// Secrets are read from the configuration file and environment; there is
+/// no external secrets backend to contact.
+pub fn secrets(input: &DoctorInput<'_>) -> CheckResult {
+    let mut configured = Vec::new();
+
+    if input.config.database.encryption_key.is_some() {
+        if !cfg!(feature = "sqlcipher") {
+            return CheckResult::new(
+                "secrets",
+                CheckStatus::Fail,
+                "database.encryption_key is set but this build lacks SQLCipher support",
+            )
+            .with_hint("Rebuild unet-cli with --features sqlcipher");
+        }
+        if let Err(e) = input.config.database_encryption_key() {
+            return CheckResult::new("secrets", CheckStatus::Fail, e.to_string())
+                .with_hint("Seal the key with `unet secrets seal`");
+        }
+        configured.push("database encryption key");
+    }
+
+    if input.token.is_some() {
+        configured.push("API token");
+    } else if input.server_url.is_some() {
+        return CheckResult::new(
+            "secrets",
+            CheckStatus::Warn,
+            "No API token given; servers with authentication enabled will reject requests",
+        )
+        .with_hint("Pass --token");
+    }
+
+    if configured.is_empty() {
+        CheckResult::new("secrets", CheckStatus::Skip, "No secrets configured")
+    } else {
+        CheckResult::new(
+            "secrets",
+            CheckStatus::Pass,
+            format!("Configured: {}", configured.join(", ")),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unet_core::config::Config;
+
+    fn git_config(local_directory: Option<&str>) -> GitConfig {
+        GitConfig {
+            local_directory: local_directory.map(str::to_string),
+            ..Config::default().git
+        }
+    }
+
+    #[tokio::test]
+    async fn test_database_reports_pending_migrations() {
+        let config = Config::default();
+        let input = DoctorInput {
+            config: &config,
+            database_url: "sqlite::memory:",
+            server_url: None,
+            token: None,
+        };
+
+        let (result, store) = database(&AppContext::default(), &input).await;
+
+        assert_eq!(result.status, CheckStatus::Warn);
+        assert!(result.message.contains("migrations pending"));
+        assert!(store.is_some());
+    }
+
+    #[test]
+    fn test_policies_reports_parse_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("good.rules"),
+            r#"WHEN node.vendor == "cisco" THEN ASSERT node.version IS "15.1""#,
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("bad.rules"), "WHEN THEN").unwrap();
+        let git = git_config(dir.path().to_str());
+
+        let result = policies(&git);
+
+        assert_eq!(result.status, CheckStatus::Fail);
+        assert!(result.message.contains("1 of 2 policy files"));
+    }
+
+    #[test]
+    fn test_policies_fails_for_missing_directory() {
+        let result = policies(&git_config(Some("/nonexistent/policies")));
+
+        assert_eq!(result.status, CheckStatus::Fail);
+    }
+
+    #[test]
+    fn test_git_compares_checkout_branch() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join(".git")).unwrap();
+        std::fs::write(dir.path().join(".git/HEAD"), "ref: refs/heads/dev\n").unwrap();
+        let mut git_config = git_config(dir.path().to_str());
+
+        assert_eq!(git(&git_config).status, CheckStatus::Warn);
+
+        git_config.branch = "dev".to_string();
+        assert_eq!(git(&git_config).status, CheckStatus::Pass);
+    }
+
+    #[test]
+    fn test_secrets_warns_without_token_for_server() {
+        let config = Config::default();
+        let input = DoctorInput {
+            config: &config,
+            database_url: "sqlite::memory:",
+            server_url: Some("http://localhost:8080"),
+            token: None,
+        };
+
+        assert_eq!(secrets(&input).status, CheckStatus::Warn);
+    }
+}
@@ -0,0 +1,153 @@
+/// Network probes: SNMP reachability and server connectivity
+use reqwest::Method;
+use std::time::Duration;
+use unet_core::config::SnmpConfig;
+use unet_core::datastore::sqlite::SqliteStore;
+use unet_core::datastore::{DataStore as _, QueryOptions};
+use unet_core::snmp::{SessionConfig, SnmpClient, SnmpCredentials, StandardOid};
+
+use super::{CheckResult, CheckStatus};
+use crate::remote::RemoteClient;
+
+/// Timeout for the server health request
+const SERVER_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Sends an SNMP GET for sysUpTime to a sample of nodes with a management IP
+pub async fn snmp(store: &SqliteStore, config: &SnmpConfig, sample: usize) -> CheckResult {
+    let nodes = match store.list_nodes(&QueryOptions::default()).await {
+        Ok(page) => page.items,
+        Err(e) => {
+            return CheckResult::new("snmp", CheckStatus::Skip, format!("Cannot list nodes: {e}"));
+        }
+    };
+    let targets: Vec<_> = nodes
+        .iter()
+        .filter_map(|node| {
+            node.management_socket_addr(161, config.address_family)
+                .transpose()
+                .map(|address| (node.name.as_str(), address))
+        })
+        .take(sample)
+        .collect();
+    if targets.is_empty() {
+        return CheckResult::new(
+            "snmp",
+            CheckStatus::Skip,
+            "No nodes with a management IP to probe",
+        );
+    }
+
+    let client = SnmpClient::new(config.client_config());
+    let mut unreachable = Vec::new();
+    for (name, address) in &targets {
+        let address = match address {
+            Ok(address) => *address,
+            Err(e) => {
+                unreachable.push(format!("{name} ({e})"));
+                continue;
+            }
+        };
+        let session = SessionConfig {
+            address,
+            version: 2,
+            credentials: SnmpCredentials::Community {
+                community: config.community.clone(),
+            },
+            timeout: Duration::from_secs(config.timeout),
+            retries: u32::from(config.retries),
+            ..SessionConfig::default()
+        };
+        if client
+            .get(address, &[StandardOid::SysUpTime.oid()], Some(session))
+            .await
+            .is_err()
+        {
+            unreachable.push(format!("{name} ({})", address.ip()));
+        }
+    }
+
+    let probed = targets.len();
+    if unreachable.is_empty() {
+        CheckResult::new(
+            "snmp",
+            CheckStatus::Pass,
+            format!("{probed} of {probed} sampled nodes answered SNMP"),
+        )
+    } else {
+        let status = if unreachable.len() == probed {
+            CheckStatus::Fail
+        } else {
+            CheckStatus::Warn
+        };
+        CheckResult::new(
+            "snmp",
+            status,
+            format!(
+                "{} of {probed} sampled nodes did not answer SNMP: {}",
+                unreachable.len(),
+                unreachable.join(", ")
+            ),
+        )
+        .with_hint("Check snmp.community, UDP/161 reachability, and the devices' SNMP ACLs")
+    }
+}
+
+/// Requests `/health` from the server given with `--server`
+pub async fn server(server_url: Option<&str>, token: Option<&str>) -> CheckResult {
+    let Some(server_url) = server_url else {
+        return CheckResult::new("server", CheckStatus::Skip, "No --server given");
+    };
+    let client = match RemoteClient::new(server_url, token) {
+        Ok(client) => client,
+        Err(e) => {
+            return CheckResult::new(
+                "server",
+                CheckStatus::Fail,
+                format!("Cannot create HTTP client: {e}"),
+            );
+        }
+    };
+
+    match client
+        .request(Method::GET, "/health")
+        .timeout(SERVER_TIMEOUT)
+        .send()
+        .await
+    {
+        Ok(response) if response.status().is_success() => CheckResult::new(
+            "server",
+            CheckStatus::Pass,
+            format!("{server_url} is healthy"),
+        ),
+        Ok(response) => CheckResult::new(
+            "server",
+            CheckStatus::Fail,
+            format!("{server_url}/health returned {}", response.status()),
+        )
+        .with_hint("Check the server logs"),
+        Err(e) => CheckResult::new(
+            "server",
+            CheckStatus::Fail,
+            format!("Cannot reach {server_url}: {e}"),
+        )
+        .with_hint("Check that unet-server is running and --server points at it"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_server_is_skipped_without_url() {
+        assert_eq!(server(None, None).await.status, CheckStatus::Skip);
+    }
+
+    #[tokio::test]
+    async fn test_server_fails_when_unreachable() {
+        let result = server(Some("http://127.0.0.1:9"), None).await;
+
+        assert_eq!(result.status, CheckStatus::Fail);
+        assert!(result.hint.is_some());
+    }
+}
@@ -0,0 +1,133 @@
+/// Change log commands
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use clap::{Args, Subcommand};
+use unet_core::change_log::{
+    ComplianceSummary, EntityActivity, EntityKind, EventFilter, builtin_projections, replay,
+};
+use unet_core::datastore::DataStore;
+use uuid::Uuid;
+
+#[derive(Subcommand)]
+pub enum EventCommands {
+    /// List recorded changes in log order
+    List(ListEventsArgs),
+    /// Rebuild projections from the whole change log
+    Replay(ReplayArgs),
+    /// Show when an entity was created, last changed, and deleted
+    Activity(ActivityArgs),
+    /// Show the compliance of the nodes at each location
+    Compliance,
+}
+
+#[derive(Args, Debug)]
+pub struct ListEventsArgs {
+    /// Only changes to this kind of entity (node, link, location)
+    #[arg(long)]
+    pub entity: Option<EntityKind>,
+    /// Only changes to this entity
+    #[arg(long)]
+    pub entity_id: Option<Uuid>,
+    /// Only changes recorded at or after this RFC 3339 time
+    #[arg(long)]
+    pub since: Option<DateTime<Utc>>,
+    /// Only changes to this `custom_data` field or fields below it, e.g.
+    /// `custom_data.bgp.asn`
+    #[arg(long)]
+    pub field: Option<String>,
+    /// Only changes after this sequence number; pass the last sequence
+    /// listed to read the next page
+    #[arg(long)]
+    pub after: Option<i64>,
+    /// Most changes to list
+    #[arg(long)]
+    pub limit: Option<usize>,
+}
+
+#[derive(Args, Debug)]
+pub struct ReplayArgs {
+    /// Rebuild only this projection
+    #[arg(long)]
+    pub projection: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct ActivityArgs {
+    /// Node, link, or location ID
+    pub id: Uuid,
+}
+
+/// Execute change log subcommands.
+///
+/// # Errors
+/// Returns an error if the projection is unknown, datastore operations fail,
+/// or output formatting fails.
+pub async fn execute(
+    command: EventCommands,
+    datastore: &dyn DataStore,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    match command {
+        EventCommands::List(args) => {
+            let filter = EventFilter {
+                entity: args.entity,
+                entity_id: args.entity_id,
+                since: args.since,
+                field: args.field,
+                after: args.after,
+                limit: args.limit,
+            };
+            crate::commands::print_output(
+                &datastore.list_change_events(&filter).await?,
+                output_format,
+            )
+        }
+        EventCommands::Replay(args) => {
+            let projections: Vec<_> = builtin_projections()
+                .into_iter()
+                .filter(|projection| {
+                    args.projection
+                        .as_deref()
+                        .is_none_or(|name| name == projection.name())
+                })
+                .collect();
+            if projections.is_empty() {
+                return Err(anyhow!(
+                    "Unknown projection: {}",
+                    args.projection.unwrap_or_default()
+                ));
+            }
+            let mut reports = Vec::with_capacity(projections.len());
+            for projection in projections {
+                reports.push(replay(datastore, projection.as_ref()).await?);
+            }
+            crate::commands::print_output(&reports, output_format)
+        }
+        EventCommands::Activity(args) => {
+            let activity = EntityActivity::get(datastore, args.id)
+                .await?
+                .ok_or_else(|| anyhow!("No changes recorded for {}", args.id))?;
+            crate::commands::print_output(&activity, output_format)
+        }
+        EventCommands::Compliance => crate::commands::print_output(
+            &ComplianceSummary::summarize(datastore).await?,
+            output_format,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unet_core::datastore::MockDataStore;
+
+    #[tokio::test]
+    async fn test_replay_rejects_unknown_projection() {
+        let mock = MockDataStore::new();
+        let command = EventCommands::Replay(ReplayArgs {
+            projection: Some("nonexistent".to_string()),
+        });
+        let result = execute(command, &mock, crate::OutputFormat::Json).await;
+        assert!(result.unwrap_err().to_string().contains("nonexistent"));
+    }
+}
@@ -1,26 +1,48 @@
 use anyhow::Result;
 use clap::Args;
+use serde::Serialize;
 use std::path::PathBuf;
 use tracing::{info, warn};
 use unet_core::datastore::{DataStore, QueryOptions};
 
+mod ansible;
+mod site;
+
 #[derive(Args)]
 pub struct ExportArgs {
     /// Destination directory to export to
     #[arg(short, long)]
     to: PathBuf,
 
-    /// Format (json, yaml) - defaults to json
+    /// Format (json, yaml, ansible-inventory) - defaults to json
     #[arg(long, default_value = "json")]
     format: String,
 
+    /// Ansible inventory file format (yaml, ini) - defaults to yaml
+    #[arg(long, default_value = "yaml")]
+    inventory_format: String,
+
+    /// `custom_data` keys to include as Ansible host variables, e.g. site.code
+    #[arg(long = "host-var", value_name = "KEY", value_delimiter = ',')]
+    host_vars: Vec<String>,
+
     /// Overwrite existing files
     #[arg(long)]
     force: bool,
 
-    /// Export only specific data types (nodes, locations, links)
+    /// Export only specific data types (nodes, locations, links, and with
+    /// --location, interfaces)
     #[arg(long, value_delimiter = ',')]
     only: Option<Vec<String>>,
+
+    /// Export only this location (slug, name, path, or ID), its nodes, the
+    /// links between them, and their interface VLAN memberships
+    #[arg(long, value_name = "LOCATION")]
+    location: Option<String>,
+
+    /// With --location, also export every location below it
+    #[arg(long, requires = "location")]
+    include_children: bool,
 }
 
 /// Execute export commands.
@@ -36,8 +58,20 @@ pub async fn execute(
 
     prepare_export_directory(&args.to).await?;
 
-    let export_types = determine_export_types(args.only.as_ref());
     let mut export_stats = ExportStats::new();
+    if args.format == ansible::FORMAT {
+        match ansible::export_inventory(&args, datastore).await {
+            Ok(count) => export_stats.record_success(count, "inventory hosts"),
+            Err(e) => export_stats.record_error(&format!("Failed to export inventory: {e}")),
+        }
+        return finalize_export(&export_stats, args, output_format);
+    }
+    if let Some(location) = &args.location {
+        site::export_subtree(&args, location, datastore, &mut export_stats).await?;
+        return finalize_export(&export_stats, args, output_format);
+    }
+
+    let export_types = determine_export_types(args.only.as_ref());
 
     // Export each type
     export_data_type(
@@ -167,31 +201,13 @@ async fn export_locations(args: &ExportArgs, datastore: &dyn DataStore) -> Resul
     if locations_result.items.is_empty() {
         return Ok(0);
     }
-
-    let filename = format!("locations.{}", args.format);
-    let file_path = args.to.join(&filename);
-
-    if file_path.exists() && !args.force {
-        return Err(anyhow::anyhow!(
-            "File {} already exists. Use --force to overwrite",
-            file_path.display()
-        ));
-    }
-
-    let content = match args.format.as_str() {
-        "json" => serde_json::to_string_pretty(&locations_result.items)?,
-        "yaml" => serde_yaml::to_string(&locations_result.items)?,
-        _ => return Err(anyhow::anyhow!("Unsupported format: {}", args.format)),
-    };
-
-    tokio::fs::write(&file_path, content).await?;
-    info!(
-        "Wrote {} locations to {}",
+    write_export_file(
+        args,
+        "locations",
+        &locations_result.items,
         locations_result.items.len(),
-        file_path.display()
-    );
-
-    Ok(locations_result.items.len())
+    )
+    .await
 }
 
 async fn export_nodes(args: &ExportArgs, datastore: &dyn DataStore) -> Result<usize> {
@@ -200,31 +216,7 @@ async fn export_nodes(args: &ExportArgs, datastore: &dyn DataStore) -> Result<us
     if nodes_result.items.is_empty() {
         return Ok(0);
     }
-
-    let filename = format!("nodes.{}", args.format);
-    let file_path = args.to.join(&filename);
-
-    if file_path.exists() && !args.force {
-        return Err(anyhow::anyhow!(
-            "File {} already exists. Use --force to overwrite",
-            file_path.display()
-        ));
-    }
-
-    let content = match args.format.as_str() {
-        "json" => serde_json::to_string_pretty(&nodes_result.items)?,
-        "yaml" => serde_yaml::to_string(&nodes_result.items)?,
-        _ => return Err(anyhow::anyhow!("Unsupported format: {}", args.format)),
-    };
-
-    tokio::fs::write(&file_path, content).await?;
-    info!(
-        "Wrote {} nodes to {}",
-        nodes_result.items.len(),
-        file_path.display()
-    );
-
-    Ok(nodes_result.items.len())
+    write_export_file(args, "nodes", &nodes_result.items, nodes_result.items.len()).await
 }
 
 async fn export_links(args: &ExportArgs, datastore: &dyn DataStore) -> Result<usize> {
@@ -233,8 +225,17 @@ async fn export_links(args: &ExportArgs, datastore: &dyn DataStore) -> Result<us
     if links_result.items.is_empty() {
         return Ok(0);
     }
+    write_export_file(args, "links", &links_result.items, links_result.items.len()).await
+}
 
-    let filename = format!("links.{}", args.format);
+/// Writes `count` items to `{data_type}.{format}`, returning `count`
+async fn write_export_file<T: Serialize + ?Sized>(
+    args: &ExportArgs,
+    data_type: &str,
+    items: &T,
+    count: usize,
+) -> Result<usize> {
+    let filename = format!("{data_type}.{}", args.format);
     let file_path = args.to.join(&filename);
 
     if file_path.exists() && !args.force {
@@ -245,27 +246,21 @@ async fn export_links(args: &ExportArgs, datastore: &dyn DataStore) -> Result<us
     }
 
     let content = match args.format.as_str() {
-        "json" => serde_json::to_string_pretty(&links_result.items)?,
-        "yaml" => serde_yaml::to_string(&links_result.items)?,
+        "json" => serde_json::to_string_pretty(items)?,
+        "yaml" => serde_yaml::to_string(items)?,
         _ => return Err(anyhow::anyhow!("Unsupported format: {}", args.format)),
     };
 
     tokio::fs::write(&file_path, content).await?;
-    info!(
-        "Wrote {} links to {}",
-        links_result.items.len(),
-        file_path.display()
-    );
+    info!("Wrote {count} {data_type} to {}", file_path.display());
 
-    Ok(links_result.items.len())
+    Ok(count)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use tempfile::TempDir;
-    
-    
 
     #[tokio::test]
     async fn test_export_stats_new() {
@@ -348,8 +343,12 @@ mod tests {
         let args = ExportArgs {
             to: temp_dir.path().to_path_buf(),
             format: "json".to_string(),
+            inventory_format: "yaml".to_string(),
+            host_vars: Vec::new(),
             force: false,
             only: None,
+            location: None,
+            include_children: false,
         };
 
         let result = finalize_export(&stats, args, crate::OutputFormat::Json);
@@ -367,8 +366,12 @@ mod tests {
         let args = ExportArgs {
             to: temp_dir.path().to_path_buf(),
             format: "json".to_string(),
+            inventory_format: "yaml".to_string(),
+            host_vars: Vec::new(),
             force: false,
             only: None,
+            location: None,
+            include_children: false,
         };
 
         let result = finalize_export(&stats, args, crate::OutputFormat::Json);
@@ -378,149 +381,4 @@ mod tests {
 }
 
 #[cfg(test)]
-mod exec_tests {
-    use super::*;
-    use mockall::predicate::always;
-    use tempfile::TempDir;
-    use unet_core::datastore::{types::PagedResult, MockDataStore};
-    use unet_core::models::{DeviceRole, NodeBuilder, Vendor};
-
-    #[tokio::test]
-    async fn test_execute_empty_exports_ok() {
-        let temp = TempDir::new().unwrap();
-        let mut mock = MockDataStore::new();
-        mock.expect_list_locations()
-            .with(always())
-            .returning(|_| Box::pin(async { Ok(PagedResult::new(vec![], 0, None)) }));
-        mock.expect_list_nodes()
-            .with(always())
-            .returning(|_| Box::pin(async { Ok(PagedResult::new(vec![], 0, None)) }));
-        mock.expect_list_links()
-            .with(always())
-            .returning(|_| Box::pin(async { Ok(PagedResult::new(vec![], 0, None)) }));
-
-        let args = ExportArgs { to: temp.path().to_path_buf(), format: "json".into(), force: false, only: None };
-        let res = execute(args, &mock, crate::OutputFormat::Json).await;
-        assert!(res.is_ok());
-    }
-
-    #[tokio::test]
-    async fn test_execute_nodes_overwrite_error_propagates() {
-        let temp = TempDir::new().unwrap();
-        // Precreate nodes.json to trigger overwrite error (when not force)
-        let pre = temp.path().join("nodes.json");
-        tokio::fs::write(&pre, "[]").await.unwrap();
-
-        let node = NodeBuilder::new()
-            .name("n1")
-            .domain("example.com")
-            .vendor(Vendor::Cisco)
-            .model("ISR")
-            .role(DeviceRole::Router)
-            .build()
-            .unwrap();
-
-        let mut mock = MockDataStore::new();
-        mock.expect_list_locations()
-            .with(always())
-            .returning(|_| Box::pin(async { Ok(PagedResult::new(vec![], 0, None)) }));
-        mock.expect_list_nodes()
-            .with(always())
-            .returning(move |_| {
-                let n = node.clone();
-                Box::pin(async move { Ok(PagedResult::new(vec![n], 1, None)) })
-            });
-        mock.expect_list_links()
-            .with(always())
-            .returning(|_| Box::pin(async { Ok(PagedResult::new(vec![], 0, None)) }));
-
-        let args = ExportArgs { to: temp.path().to_path_buf(), format: "json".into(), force: false, only: Some(vec!["nodes".into()]) };
-        let res = execute(args, &mock, crate::OutputFormat::Json).await;
-        assert!(res.is_err());
-    }
-
-    #[tokio::test]
-    async fn test_execute_locations_yaml_writes_file() {
-        use mockall::predicate::always;
-        use unet_core::datastore::{types::PagedResult, MockDataStore};
-        use unet_core::models::Location;
-        let temp = TempDir::new().unwrap();
-        let loc = Location::new_root("HQ".into(), "building".into());
-
-        let mut mock = MockDataStore::new();
-        mock.expect_list_locations()
-            .with(always())
-            .returning(move |_| {
-                let l = loc.clone();
-                Box::pin(async move { Ok(PagedResult::new(vec![l], 1, None)) })
-            });
-        // nodes/links empty for this run
-        mock.expect_list_nodes()
-            .with(always())
-            .returning(|_| Box::pin(async { Ok(PagedResult::new(vec![], 0, None)) }));
-        mock.expect_list_links()
-            .with(always())
-            .returning(|_| Box::pin(async { Ok(PagedResult::new(vec![], 0, None)) }));
-
-        let args = ExportArgs { to: temp.path().to_path_buf(), format: "yaml".into(), force: true, only: Some(vec!["locations".into()]) };
-        let res = execute(args, &mock, crate::OutputFormat::Json).await;
-        assert!(res.is_ok());
-        // Verify file exists
-        let out = temp.path().join("locations.yaml");
-        assert!(out.exists());
-    }
-
-    #[tokio::test]
-    async fn test_execute_links_json_writes_file() {
-        use mockall::predicate::always;
-        use unet_core::datastore::{types::PagedResult, MockDataStore};
-        let temp = TempDir::new().unwrap();
-        let a = uuid::Uuid::new_v4();
-        let z = uuid::Uuid::new_v4();
-        let link = unet_core::models::Link::new("L1".into(), a, "Gi0/0".into(), z, "Gi0/1".into());
-
-        let mut mock = MockDataStore::new();
-        mock.expect_list_locations()
-            .with(always())
-            .returning(|_| Box::pin(async { Ok(PagedResult::new(vec![], 0, None)) }));
-        mock.expect_list_nodes()
-            .with(always())
-            .returning(|_| Box::pin(async { Ok(PagedResult::new(vec![], 0, None)) }));
-        mock.expect_list_links()
-            .with(always())
-            .returning(move |_| {
-                let l = link.clone();
-                Box::pin(async move { Ok(PagedResult::new(vec![l], 1, None)) })
-            });
-
-        let args = ExportArgs { to: temp.path().to_path_buf(), format: "json".into(), force: true, only: Some(vec!["links".into()]) };
-        let res = execute(args, &mock, crate::OutputFormat::Json).await;
-        assert!(res.is_ok());
-        let out = temp.path().join("links.json");
-        assert!(out.exists());
-    }
-
-    #[tokio::test]
-    async fn test_execute_unsupported_format_errors() {
-        use mockall::predicate::always;
-        use unet_core::datastore::{types::PagedResult, MockDataStore};
-        let temp = TempDir::new().unwrap();
-        let mut mock = MockDataStore::new();
-        mock.expect_list_locations()
-            .with(always())
-            .returning(|_| {
-                let loc = unet_core::models::location::model::Location::new_root(
-                    "HQ".into(),
-                    "building".into(),
-                );
-                Box::pin(async move { Ok(PagedResult::new(vec![loc], 1, None)) })
-            });
-        // keep nodes/links empty
-        mock.expect_list_nodes().with(always()).returning(|_| Box::pin(async { Ok(PagedResult::new(vec![], 0, None)) }));
-        mock.expect_list_links().with(always()).returning(|_| Box::pin(async { Ok(PagedResult::new(vec![], 0, None)) }));
-
-        let args = ExportArgs { to: temp.path().to_path_buf(), format: "xml".into(), force: true, only: Some(vec!["locations".into()]) };
-        let res = execute(args, &mock, crate::OutputFormat::Json).await;
-        assert!(res.is_err());
-    }
-}
+mod exec_tests;
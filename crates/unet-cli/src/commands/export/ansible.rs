@@ -0,0 +1,299 @@
+//! Ansible inventory export
+//!
+//! Nodes become hosts named by FQDN, with `ansible_host` set to the
+//! management IP and the selected `custom_data` keys as host variables.
+//! Hosts are grouped by role (`role_router`), vendor (`vendor_juniper`), and
+//! location (`location_dc1_hall_a`, from the location path); a location's
+//! group has the groups of its sub-locations as children, so targeting a
+//! site includes everything below it.
+
+use anyhow::{Result, anyhow};
+use serde_json::{Map, Value, json};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use tracing::info;
+use unet_core::datastore::{DataStore, QueryOptions};
+use unet_core::models::{Location, Node};
+
+use super::ExportArgs;
+
+/// `--format` value selecting the inventory export
+pub const FORMAT: &str = "ansible-inventory";
+
+/// A group's direct members
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Group {
+    hosts: BTreeSet<String>,
+    children: BTreeSet<String>,
+}
+
+/// Hosts with their variables, and groups of them
+#[derive(Debug, Default)]
+struct Inventory {
+    hosts: BTreeMap<String, Map<String, Value>>,
+    groups: BTreeMap<String, Group>,
+}
+
+impl Inventory {
+    fn build(nodes: &[Node], locations: &[Location], host_vars: &[String]) -> Self {
+        let mut inventory = Self::default();
+        let location_groups: BTreeMap<_, _> = locations
+            .iter()
+            .map(|location| (location.id, group_name("location", &location.path)))
+            .collect();
+        for location in locations {
+            let name = &location_groups[&location.id];
+            inventory.groups.entry(name.clone()).or_default();
+            if let Some(parent) = location.parent_id.and_then(|id| location_groups.get(&id)) {
+                inventory
+                    .groups
+                    .entry(parent.clone())
+                    .or_default()
+                    .children
+                    .insert(name.clone());
+            }
+        }
+
+        for node in nodes {
+            let host = if node.fqdn.is_empty() {
+                node.name.clone()
+            } else {
+                node.fqdn.clone()
+            };
+            let mut vars = Map::new();
+            if let Some(ip) = node.management_ip {
+                vars.insert("ansible_host".to_string(), json!(ip.to_string()));
+            }
+            for key in host_vars {
+                if let Some(value) = lookup(&node.custom_data, key) {
+                    vars.insert(var_name(key), value.clone());
+                }
+            }
+
+            let mut memberships = vec![
+                group_name("role", &node.role.to_string()),
+                group_name("vendor", &node.vendor.to_string()),
+            ];
+            memberships.extend(
+                node.location_id
+                    .and_then(|id| location_groups.get(&id).cloned()),
+            );
+            for group in memberships {
+                inventory
+                    .groups
+                    .entry(group)
+                    .or_default()
+                    .hosts
+                    .insert(host.clone());
+            }
+            inventory.hosts.insert(host, vars);
+        }
+        inventory
+    }
+
+    /// YAML inventory, with host variables under `all.hosts`
+    fn to_yaml(&self) -> Result<String> {
+        let names = |names: &BTreeSet<String>| -> Map<String, Value> {
+            names.iter().map(|name| (name.clone(), json!({}))).collect()
+        };
+        let hosts: Map<String, Value> = self
+            .hosts
+            .iter()
+            .map(|(host, vars)| (host.clone(), Value::Object(vars.clone())))
+            .collect();
+        let children: Map<String, Value> = self
+            .groups
+            .iter()
+            .map(|(name, group)| {
+                let mut members = Map::new();
+                if !group.hosts.is_empty() {
+                    members.insert("hosts".to_string(), Value::Object(names(&group.hosts)));
+                }
+                if !group.children.is_empty() {
+                    members.insert(
+                        "children".to_string(),
+                        Value::Object(names(&group.children)),
+                    );
+                }
+                (name.clone(), Value::Object(members))
+            })
+            .collect();
+        let inventory = json!({ "all": { "hosts": hosts, "children": children } });
+        Ok(serde_yaml::to_string(&inventory)?)
+    }
+
+    /// INI inventory, with host variables on the hosts' lines under `[all]`
+    fn to_ini(&self) -> String {
+        let mut ini = String::from("[all]\n");
+        for (host, vars) in &self.hosts {
+            ini.push_str(host);
+            for (name, value) in vars {
+                let _ = write!(ini, " {name}={}", ini_value(value));
+            }
+            ini.push('\n');
+        }
+        for (name, group) in &self.groups {
+            if !group.hosts.is_empty() {
+                let _ = writeln!(ini, "\n[{name}]");
+                for host in &group.hosts {
+                    let _ = writeln!(ini, "{host}");
+                }
+            }
+            if !group.children.is_empty() {
+                let _ = writeln!(ini, "\n[{name}:children]");
+                for child in &group.children {
+                    let _ = writeln!(ini, "{child}");
+                }
+            }
+        }
+        ini
+    }
+}
+
+/// Group name made of a prefix and a value, with everything but ASCII
+/// letters and digits turned into `_`, as Ansible requires
+fn group_name(prefix: &str, value: &str) -> String {
+    format!("{prefix}_{}", var_name(value))
+}
+
+/// Lowercases and turns everything but ASCII letters and digits into `_`
+fn var_name(key: &str) -> String {
+    key.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Looks up a dotted path such as `site.code` in `custom_data`
+fn lookup<'a>(data: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .try_fold(data, |value, key| value.get(key))
+        .filter(|value| !value.is_null())
+}
+
+/// Strings are written bare unless Ansible would split or misread them;
+/// everything else as JSON
+fn ini_value(value: &Value) -> String {
+    match value {
+        Value::String(text)
+            if !text.is_empty()
+                && !text
+                    .chars()
+                    .any(|c| c.is_whitespace() || matches!(c, '"' | '\'' | '#' | ';' | '=')) =>
+        {
+            text.clone()
+        }
+        _ => serde_json::to_string(value).unwrap_or_default(),
+    }
+}
+
+/// Writes `inventory.yml` or `inventory.ini`, returning the number of hosts
+///
+/// # Errors
+/// Returns an error if the inventory format is unknown, the file exists
+/// without `--force`, or nodes and locations cannot be read or written.
+pub async fn export_inventory(args: &ExportArgs, datastore: &dyn DataStore) -> Result<usize> {
+    let query_options = QueryOptions::default();
+    let nodes = datastore.list_nodes(&query_options).await?.items;
+    let locations = datastore.list_locations(&query_options).await?.items;
+    let inventory = Inventory::build(&nodes, &locations, &args.host_vars);
+
+    let (filename, content) = match args.inventory_format.as_str() {
+        "yaml" => ("inventory.yml", inventory.to_yaml()?),
+        "ini" => ("inventory.ini", inventory.to_ini()),
+        other => {
+            return Err(anyhow!(
+                "Unsupported inventory format: {other} (expected yaml or ini)"
+            ));
+        }
+    };
+    let file_path = args.to.join(filename);
+    if file_path.exists() && !args.force {
+        return Err(anyhow!(
+            "File {} already exists. Use --force to overwrite",
+            file_path.display()
+        ));
+    }
+
+    tokio::fs::write(&file_path, content).await?;
+    info!(
+        "Wrote {} hosts to {}",
+        inventory.hosts.len(),
+        file_path.display()
+    );
+    Ok(inventory.hosts.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unet_core::models::{DeviceRole, NodeBuilder, Vendor};
+
+    fn fixture() -> (Vec<Node>, Vec<Location>) {
+        let site = Location::new_root("DC1".to_string(), "site".to_string());
+        let mut hall = Location::new_child("Hall A".to_string(), "room".to_string(), &site.path);
+        hall.parent_id = Some(site.id);
+        let mut node = NodeBuilder::new()
+            .name("edge-1")
+            .domain("example.com")
+            .vendor(Vendor::Juniper)
+            .model("MX204")
+            .role(DeviceRole::Router)
+            .build()
+            .unwrap();
+        node.management_ip = Some("192.0.2.1".parse().unwrap());
+        node.location_id = Some(hall.id);
+        node.custom_data = json!({ "site": { "code": "dc1" }, "owner": "Network Ops" });
+        (vec![node], vec![site, hall])
+    }
+
+    #[test]
+    fn test_inventory_groups_by_role_vendor_and_location() {
+        let (nodes, locations) = fixture();
+        let host_vars = ["site.code".to_string(), "missing".to_string()];
+
+        let inventory = Inventory::build(&nodes, &locations, &host_vars);
+
+        assert_eq!(
+            Value::Object(inventory.hosts["edge-1.example.com"].clone()),
+            json!({ "ansible_host": "192.0.2.1", "site_code": "dc1" })
+        );
+        let hosts = |group: &str| -> Vec<String> {
+            inventory.groups[group].hosts.iter().cloned().collect()
+        };
+        let expected: Vec<String> = vec!["edge-1.example.com".to_string()];
+        assert_eq!(hosts("role_router"), expected);
+        assert_eq!(hosts("vendor_juniper"), expected);
+        assert_eq!(hosts("location_dc1_hall_a"), expected);
+        assert!(
+            inventory.groups["location_dc1"]
+                .children
+                .contains("location_dc1_hall_a")
+        );
+
+        let yaml: serde_yaml::Value = serde_yaml::from_str(&inventory.to_yaml().unwrap()).unwrap();
+        assert_eq!(
+            yaml["all"]["hosts"]["edge-1.example.com"]["ansible_host"],
+            "192.0.2.1"
+        );
+    }
+
+    #[test]
+    fn test_ini_inventory_quotes_values_ansible_would_split() {
+        let (nodes, locations) = fixture();
+        let host_vars = ["owner".to_string()];
+
+        let ini = Inventory::build(&nodes, &locations, &host_vars).to_ini();
+
+        assert!(ini.starts_with(
+            "[all]\nedge-1.example.com ansible_host=192.0.2.1 owner=\"Network Ops\"\n"
+        ));
+        assert!(ini.contains("\n[location_dc1:children]\nlocation_dc1_hall_a\n"));
+        assert!(ini.contains("\n[role_router]\nedge-1.example.com\n"));
+    }
+}
@@ -0,0 +1,284 @@
+use super::*;
+use mockall::predicate::always;
+use tempfile::TempDir;
+use unet_core::datastore::{MockDataStore, types::PagedResult};
+use unet_core::models::{DeviceRole, NodeBuilder, Vendor};
+
+#[tokio::test]
+async fn test_execute_empty_exports_ok() {
+    let temp = TempDir::new().unwrap();
+    let mut mock = MockDataStore::new();
+    mock.expect_list_locations()
+        .with(always())
+        .returning(|_| Box::pin(async { Ok(PagedResult::new(vec![], 0, None)) }));
+    mock.expect_list_nodes()
+        .with(always())
+        .returning(|_| Box::pin(async { Ok(PagedResult::new(vec![], 0, None)) }));
+    mock.expect_list_links()
+        .with(always())
+        .returning(|_| Box::pin(async { Ok(PagedResult::new(vec![], 0, None)) }));
+
+    let args = ExportArgs {
+        to: temp.path().to_path_buf(),
+        format: "json".into(),
+        inventory_format: "yaml".into(),
+        host_vars: vec![],
+        force: false,
+        only: None,
+        location: None,
+        include_children: false,
+    };
+    let res = execute(args, &mock, crate::OutputFormat::Json).await;
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn test_execute_nodes_overwrite_error_propagates() {
+    let temp = TempDir::new().unwrap();
+    // Precreate nodes.json to trigger overwrite error (when not force)
+    let pre = temp.path().join("nodes.json");
+    tokio::fs::write(&pre, "[]").await.unwrap();
+
+    let node = NodeBuilder::new()
+        .name("n1")
+        .domain("example.com")
+        .vendor(Vendor::Cisco)
+        .model("ISR")
+        .role(DeviceRole::Router)
+        .build()
+        .unwrap();
+
+    let mut mock = MockDataStore::new();
+    mock.expect_list_locations()
+        .with(always())
+        .returning(|_| Box::pin(async { Ok(PagedResult::new(vec![], 0, None)) }));
+    mock.expect_list_nodes().with(always()).returning(move |_| {
+        let n = node.clone();
+        Box::pin(async move { Ok(PagedResult::new(vec![n], 1, None)) })
+    });
+    mock.expect_list_links()
+        .with(always())
+        .returning(|_| Box::pin(async { Ok(PagedResult::new(vec![], 0, None)) }));
+
+    let args = ExportArgs {
+        to: temp.path().to_path_buf(),
+        format: "json".into(),
+        inventory_format: "yaml".into(),
+        host_vars: vec![],
+        force: false,
+        only: Some(vec!["nodes".into()]),
+        location: None,
+        include_children: false,
+    };
+    let res = execute(args, &mock, crate::OutputFormat::Json).await;
+    assert!(res.is_err());
+}
+
+#[tokio::test]
+async fn test_execute_locations_yaml_writes_file() {
+    use mockall::predicate::always;
+    use unet_core::datastore::{MockDataStore, types::PagedResult};
+    use unet_core::models::Location;
+    let temp = TempDir::new().unwrap();
+    let loc = Location::new_root("HQ".into(), "building".into());
+
+    let mut mock = MockDataStore::new();
+    mock.expect_list_locations()
+        .with(always())
+        .returning(move |_| {
+            let l = loc.clone();
+            Box::pin(async move { Ok(PagedResult::new(vec![l], 1, None)) })
+        });
+    // nodes/links empty for this run
+    mock.expect_list_nodes()
+        .with(always())
+        .returning(|_| Box::pin(async { Ok(PagedResult::new(vec![], 0, None)) }));
+    mock.expect_list_links()
+        .with(always())
+        .returning(|_| Box::pin(async { Ok(PagedResult::new(vec![], 0, None)) }));
+
+    let args = ExportArgs {
+        to: temp.path().to_path_buf(),
+        format: "yaml".into(),
+        inventory_format: "yaml".into(),
+        host_vars: vec![],
+        force: true,
+        only: Some(vec!["locations".into()]),
+        location: None,
+        include_children: false,
+    };
+    let res = execute(args, &mock, crate::OutputFormat::Json).await;
+    assert!(res.is_ok());
+    // Verify file exists
+    let out = temp.path().join("locations.yaml");
+    assert!(out.exists());
+}
+
+#[tokio::test]
+async fn test_execute_links_json_writes_file() {
+    use mockall::predicate::always;
+    use unet_core::datastore::{MockDataStore, types::PagedResult};
+    let temp = TempDir::new().unwrap();
+    let a = uuid::Uuid::new_v4();
+    let z = uuid::Uuid::new_v4();
+    let link = unet_core::models::Link::new("L1".into(), a, "Gi0/0".into(), z, "Gi0/1".into());
+
+    let mut mock = MockDataStore::new();
+    mock.expect_list_locations()
+        .with(always())
+        .returning(|_| Box::pin(async { Ok(PagedResult::new(vec![], 0, None)) }));
+    mock.expect_list_nodes()
+        .with(always())
+        .returning(|_| Box::pin(async { Ok(PagedResult::new(vec![], 0, None)) }));
+    mock.expect_list_links().with(always()).returning(move |_| {
+        let l = link.clone();
+        Box::pin(async move { Ok(PagedResult::new(vec![l], 1, None)) })
+    });
+
+    let args = ExportArgs {
+        to: temp.path().to_path_buf(),
+        format: "json".into(),
+        inventory_format: "yaml".into(),
+        host_vars: vec![],
+        force: true,
+        only: Some(vec!["links".into()]),
+        location: None,
+        include_children: false,
+    };
+    let res = execute(args, &mock, crate::OutputFormat::Json).await;
+    assert!(res.is_ok());
+    let out = temp.path().join("links.json");
+    assert!(out.exists());
+}
+
+#[tokio::test]
+async fn test_execute_unsupported_format_errors() {
+    use mockall::predicate::always;
+    use unet_core::datastore::{MockDataStore, types::PagedResult};
+    let temp = TempDir::new().unwrap();
+    let mut mock = MockDataStore::new();
+    mock.expect_list_locations().with(always()).returning(|_| {
+        let loc =
+            unet_core::models::location::model::Location::new_root("HQ".into(), "building".into());
+        Box::pin(async move { Ok(PagedResult::new(vec![loc], 1, None)) })
+    });
+    // keep nodes/links empty
+    mock.expect_list_nodes()
+        .with(always())
+        .returning(|_| Box::pin(async { Ok(PagedResult::new(vec![], 0, None)) }));
+    mock.expect_list_links()
+        .with(always())
+        .returning(|_| Box::pin(async { Ok(PagedResult::new(vec![], 0, None)) }));
+
+    let args = ExportArgs {
+        to: temp.path().to_path_buf(),
+        format: "xml".into(),
+        inventory_format: "yaml".into(),
+        host_vars: vec![],
+        force: true,
+        only: Some(vec!["locations".into()]),
+        location: None,
+        include_children: false,
+    };
+    let res = execute(args, &mock, crate::OutputFormat::Json).await;
+    assert!(res.is_err());
+}
+
+#[tokio::test]
+async fn test_execute_ansible_inventory_writes_file() {
+    let temp = TempDir::new().unwrap();
+    let node = NodeBuilder::new()
+        .name("n1")
+        .domain("example.com")
+        .vendor(Vendor::Cisco)
+        .model("ISR")
+        .role(DeviceRole::Router)
+        .build()
+        .unwrap();
+
+    let mut mock = MockDataStore::new();
+    mock.expect_list_nodes().with(always()).returning(move |_| {
+        let n = node.clone();
+        Box::pin(async move { Ok(PagedResult::new(vec![n], 1, None)) })
+    });
+    mock.expect_list_locations()
+        .with(always())
+        .returning(|_| Box::pin(async { Ok(PagedResult::new(vec![], 0, None)) }));
+    mock.expect_list_links().never();
+
+    let args = ExportArgs {
+        to: temp.path().to_path_buf(),
+        format: "ansible-inventory".into(),
+        inventory_format: "ini".into(),
+        host_vars: vec![],
+        force: false,
+        only: None,
+        location: None,
+        include_children: false,
+    };
+    let res = execute(args, &mock, crate::OutputFormat::Json).await;
+    assert!(res.is_ok());
+    let inventory = std::fs::read_to_string(temp.path().join("inventory.ini")).unwrap();
+    assert!(inventory.contains("[vendor_cisco]\nn1.example.com\n"));
+}
+
+#[tokio::test]
+async fn test_execute_location_exports_only_its_subtree() {
+    use serde_json::json;
+    use unet_core::models::Location;
+
+    let temp = TempDir::new().unwrap();
+    let site = Location::new_root("dc-west".into(), "site".into());
+    let other = Location::new_root("dc-east".into(), "site".into());
+    let site_id = site.id;
+    let mut node = NodeBuilder::new()
+        .name("r1")
+        .domain("example.com")
+        .vendor(Vendor::Juniper)
+        .model("MX204")
+        .role(DeviceRole::Router)
+        .build()
+        .unwrap();
+    node.location_id = Some(site_id);
+
+    let mut mock = MockDataStore::new();
+    mock.expect_list_locations()
+        .with(always())
+        .returning(move |_| {
+            let items = vec![site.clone(), other.clone()];
+            Box::pin(async move { Ok(PagedResult::new(items, 2, None)) })
+        });
+    mock.expect_list_nodes().with(always()).returning(move |_| {
+        let n = node.clone();
+        Box::pin(async move { Ok(PagedResult::new(vec![n], 1, None)) })
+    });
+    mock.expect_list_links()
+        .with(always())
+        .returning(|_| Box::pin(async { Ok(PagedResult::new(vec![], 0, None)) }));
+    mock.expect_get_setting().returning(|_, _| {
+        let memberships = json!({ "ge-0/0/0": { "mode": "access", "vlan": 10 } });
+        Box::pin(async move { Ok(Some(memberships)) })
+    });
+
+    let args = ExportArgs {
+        to: temp.path().to_path_buf(),
+        format: "json".into(),
+        inventory_format: "yaml".into(),
+        host_vars: vec![],
+        force: false,
+        only: None,
+        location: Some(site_id.to_string()),
+        include_children: true,
+    };
+    execute(args, &mock, crate::OutputFormat::Json)
+        .await
+        .unwrap();
+
+    let read = |name: &str| -> serde_json::Value {
+        serde_json::from_str(&std::fs::read_to_string(temp.path().join(name)).unwrap()).unwrap()
+    };
+    assert_eq!(read("locations.json").as_array().unwrap().len(), 1);
+    assert_eq!(read("nodes.json")[0]["name"], "r1");
+    assert_eq!(read("links.json"), json!([]));
+    assert_eq!(read("interfaces.json").as_object().unwrap().len(), 1);
+}
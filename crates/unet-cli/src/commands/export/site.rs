@@ -0,0 +1,231 @@
+//! Location subtree export
+//!
+//! `--location` narrows the export to one location and, with
+//! `--include-children`, every location below it, together with the nodes
+//! placed there, the links between those nodes, and their interface VLAN
+//! memberships (`interfaces.json`, keyed by node ID). The bundle only refers
+//! to its own entities: the selected location becomes a root, paths are
+//! rewritten below it, and links leaving the subtree are left out. `unet
+//! import` can then recreate it anywhere, with fresh IDs and new names when a
+//! new site is templated from an existing one.
+
+use anyhow::Result;
+use std::collections::{BTreeMap, HashSet};
+use unet_core::datastore::{DataStore, QueryOptions};
+use unet_core::models::{Link, Location, Node};
+use unet_core::vlan::{InterfaceVlans, interface_vlans};
+use uuid::Uuid;
+
+use super::{ExportArgs, ExportStats, write_export_file};
+
+/// Data types written for a subtree when `--only` is not given
+const SUBTREE_TYPES: [&str; 4] = ["locations", "nodes", "links", "interfaces"];
+
+/// The entities of one location subtree
+#[derive(Debug, Default)]
+struct Subtree {
+    /// Parents before their children
+    locations: Vec<Location>,
+    nodes: Vec<Node>,
+    links: Vec<Link>,
+}
+
+impl Subtree {
+    /// Selects the subtree rooted at `root`, rebasing the root's path so the
+    /// bundle does not depend on the root's parent
+    fn select(
+        root: Uuid,
+        include_children: bool,
+        locations: Vec<Location>,
+        nodes: Vec<Node>,
+        links: Vec<Link>,
+    ) -> Result<Self> {
+        let mut by_id: BTreeMap<Uuid, Location> = locations
+            .into_iter()
+            .map(|location| (location.id, location))
+            .collect();
+        let mut root = by_id
+            .remove(&root)
+            .ok_or_else(|| anyhow::anyhow!("Location {root} not found"))?;
+        root.parent_id = None;
+        root.path.clone_from(&root.name);
+
+        // Breadth first, so parents come before their children
+        let mut selected = vec![root];
+        let mut next = 0;
+        while include_children && next < selected.len() {
+            let (parent_id, parent_path) = (selected[next].id, selected[next].path.clone());
+            next += 1;
+            let children: Vec<Uuid> = by_id
+                .values()
+                .filter(|location| location.parent_id == Some(parent_id))
+                .map(|location| location.id)
+                .collect();
+            for id in children {
+                if let Some(mut child) = by_id.remove(&id) {
+                    child.path = format!("{parent_path}/{}", child.name);
+                    selected.push(child);
+                }
+            }
+        }
+
+        let location_ids: HashSet<Uuid> = selected.iter().map(|location| location.id).collect();
+        let nodes: Vec<Node> = nodes
+            .into_iter()
+            .filter(|node| {
+                node.location_id
+                    .is_some_and(|id| location_ids.contains(&id))
+            })
+            .collect();
+        let node_ids: HashSet<Uuid> = nodes.iter().map(|node| node.id).collect();
+        let links = links
+            .into_iter()
+            .filter(|link| {
+                node_ids.contains(&link.source_node_id)
+                    && link.dest_node_id.is_none_or(|id| node_ids.contains(&id))
+            })
+            .collect();
+        Ok(Self {
+            locations: selected,
+            nodes,
+            links,
+        })
+    }
+}
+
+/// Exports the subtree at `--location`, recording each data type in `stats`
+///
+/// # Errors
+/// Returns an error if the location does not resolve or the datastore
+/// cannot be read; failures writing a data type are recorded in `stats`.
+pub async fn export_subtree(
+    args: &ExportArgs,
+    location: &str,
+    datastore: &dyn DataStore,
+    stats: &mut ExportStats,
+) -> Result<()> {
+    let root = crate::resolve::location(datastore, location, false).await?;
+    let query_options = QueryOptions::default();
+    let subtree = Subtree::select(
+        root,
+        args.include_children,
+        datastore.list_locations(&query_options).await?.items,
+        datastore.list_nodes(&query_options).await?.items,
+        datastore.list_links(&query_options).await?.items,
+    )?;
+
+    let export_types: Vec<&str> = args.only.as_ref().map_or_else(
+        || SUBTREE_TYPES.to_vec(),
+        |only| only.iter().map(String::as_str).collect(),
+    );
+    for data_type in export_types {
+        let result = match data_type {
+            "locations" => {
+                write_export_file(args, data_type, &subtree.locations, subtree.locations.len())
+                    .await
+            }
+            "nodes" => {
+                write_export_file(args, data_type, &subtree.nodes, subtree.nodes.len()).await
+            }
+            "links" => {
+                write_export_file(args, data_type, &subtree.links, subtree.links.len()).await
+            }
+            "interfaces" => export_interfaces(args, &subtree.nodes, datastore).await,
+            _ => continue,
+        };
+        match result {
+            Ok(count) => stats.record_success(count, data_type),
+            Err(e) => stats.record_error(&format!("Failed to export {data_type}: {e}")),
+        }
+    }
+    Ok(())
+}
+
+/// Writes the VLAN memberships of the nodes that have any
+async fn export_interfaces(
+    args: &ExportArgs,
+    nodes: &[Node],
+    datastore: &dyn DataStore,
+) -> Result<usize> {
+    let mut interfaces: BTreeMap<Uuid, InterfaceVlans> = BTreeMap::new();
+    for node in nodes {
+        let memberships = interface_vlans(datastore, node.id).await?;
+        if !memberships.is_empty() {
+            interfaces.insert(node.id, memberships);
+        }
+    }
+    write_export_file(args, "interfaces", &interfaces, interfaces.len()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unet_core::models::{DeviceRole, NodeBuilder, Vendor};
+
+    fn node(name: &str, location_id: Uuid) -> Node {
+        let mut node = NodeBuilder::new()
+            .name(name)
+            .domain("example.com")
+            .vendor(Vendor::Juniper)
+            .model("MX204")
+            .role(DeviceRole::Router)
+            .build()
+            .unwrap();
+        node.location_id = Some(location_id);
+        node
+    }
+
+    fn child(name: &str, parent: &Location) -> Location {
+        let mut location = Location::new_child(name.to_string(), "room".to_string(), &parent.path);
+        location.parent_id = Some(parent.id);
+        location
+    }
+
+    #[test]
+    fn test_select_rebases_subtree_and_drops_links_leaving_it() {
+        let region = Location::new_root("US".to_string(), "region".to_string());
+        let west = child("dc-west", &region);
+        let hall = child("Hall A", &west);
+        let east = child("dc-east", &region);
+        let (r1, r2, r3) = (
+            node("r1", west.id),
+            node("r2", hall.id),
+            node("r3", east.id),
+        );
+        let inside = Link::new(
+            "r1-r2".into(),
+            r1.id,
+            "xe-0/0/0".into(),
+            r2.id,
+            "xe-0/0/0".into(),
+        );
+        let leaving = Link::new(
+            "r1-r3".into(),
+            r1.id,
+            "xe-0/0/1".into(),
+            r3.id,
+            "xe-0/0/1".into(),
+        );
+        let locations = vec![hall, region, east, west.clone()];
+
+        let subtree = Subtree::select(
+            west.id,
+            true,
+            locations.clone(),
+            vec![r1.clone(), r2, r3],
+            vec![inside, leaving],
+        )
+        .unwrap();
+
+        let paths: Vec<&str> = subtree.locations.iter().map(|l| l.path.as_str()).collect();
+        assert_eq!(paths, ["dc-west", "dc-west/Hall A"]);
+        assert_eq!(subtree.locations[0].parent_id, None);
+        assert_eq!(subtree.nodes.len(), 2);
+        assert_eq!(subtree.links.len(), 1);
+        assert_eq!(subtree.links[0].name, "r1-r2");
+
+        let alone = Subtree::select(west.id, false, locations, vec![r1], vec![]).unwrap();
+        assert_eq!(alone.locations.len(), 1);
+        assert_eq!(alone.nodes.len(), 1);
+    }
+}
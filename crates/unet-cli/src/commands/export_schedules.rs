@@ -0,0 +1,127 @@
+/// Scheduled inventory export commands
+use anyhow::{Result, anyhow};
+use clap::{Args, Subcommand};
+use unet_core::datastore::DataStore;
+use unet_core::exports::{
+    Destination, ExportSchedule, delete_schedule, get_schedule, latest_run, list_schedules,
+    save_schedule,
+};
+
+use crate::confirm::{Confirmation, confirm};
+
+#[derive(Subcommand)]
+pub enum ExportScheduleCommands {
+    /// List export schedules
+    List,
+    /// Create or replace an export schedule; the server runs it
+    Set(SetExportScheduleArgs),
+    /// Remove an export schedule
+    Delete(DeleteExportScheduleArgs),
+    /// Show the latest run of an export schedule
+    Last(ExportScheduleNameArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct SetExportScheduleArgs {
+    /// Schedule name (letters, digits, '-', and '_')
+    pub name: String,
+    /// Destination: a directory, s3://BUCKET/PREFIX?region=REGION, gs://BUCKET/PREFIX, or
+    /// sftp://USER@HOST[:PORT]/DIR
+    #[arg(long = "to", value_name = "URL")]
+    pub destination: Destination,
+    /// Minutes between exports
+    #[arg(long, value_name = "MINUTES", default_value_t = 1440)]
+    pub every: u32,
+    /// What the export is for
+    #[arg(long)]
+    pub description: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct DeleteExportScheduleArgs {
+    /// Schedule name
+    pub name: String,
+    /// Skip confirmation prompt
+    #[arg(short = 'y', long)]
+    pub yes: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct ExportScheduleNameArgs {
+    /// Schedule name
+    pub name: String,
+}
+
+/// Execute export schedule subcommands.
+///
+/// # Errors
+/// Returns an error if a schedule is invalid or does not exist, datastore
+/// operations fail, or output formatting fails.
+pub async fn execute(
+    command: ExportScheduleCommands,
+    datastore: &dyn DataStore,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    match command {
+        ExportScheduleCommands::List => {
+            crate::commands::print_output(&list_schedules(datastore).await?, output_format)
+        }
+        ExportScheduleCommands::Set(args) => {
+            let schedule =
+                ExportSchedule::new(&args.name, args.destination, args.every, args.description)?;
+            save_schedule(datastore, &schedule).await?;
+            crate::commands::print_output(&schedule, output_format)
+        }
+        ExportScheduleCommands::Delete(args) => {
+            let schedule = get_schedule(datastore, &args.name).await?;
+            let confirmation = Confirmation::new("Remove export schedule")
+                .affects(format!("{} ({})", schedule.name, schedule.destination));
+            if !confirm(args.yes, &confirmation)? {
+                return Ok(());
+            }
+            delete_schedule(datastore, &schedule.name).await?;
+            let output = serde_json::json!({
+                "message": "Export schedule removed",
+                "name": schedule.name,
+            });
+            crate::commands::print_output(&output, output_format)
+        }
+        ExportScheduleCommands::Last(args) => {
+            let run = latest_run(datastore, &args.name)
+                .await?
+                .ok_or_else(|| anyhow!("Export schedule {} has not run yet", args.name))?;
+            crate::commands::print_output(&run, output_format)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unet_core::datastore::MockDataStore;
+
+    #[tokio::test]
+    async fn test_set_stores_schedule() {
+        let mut mock = MockDataStore::new();
+        mock.expect_put_setting()
+            .withf(|namespace, key, value| {
+                namespace == "export_schedules"
+                    && key == "lake"
+                    && value["destination"]["type"] == "s3"
+                    && value["destination"]["bucket"] == "lake"
+                    && value["destination"]["region"] == "eu-west-1"
+                    && value["interval_minutes"] == 1440
+            })
+            .times(1)
+            .returning(|_, _, _| Box::pin(async { Ok(()) }));
+
+        let command = ExportScheduleCommands::Set(SetExportScheduleArgs {
+            name: "lake".to_string(),
+            destination: "s3://lake/unet?region=eu-west-1".parse().unwrap(),
+            every: 1440,
+            description: None,
+        });
+        let result = execute(command, &mock, crate::OutputFormat::Json).await;
+        assert!(result.is_ok());
+    }
+}
@@ -0,0 +1,289 @@
+/// Golden configuration and conformance commands
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use std::path::PathBuf;
+use unet_core::datastore::DataStore;
+use unet_core::golden::{
+    GoldenConfig, GoldenScope, GoldenSource, check_conformance, conformance_history, delete_golden,
+    list_golden, resolve_golden, save_golden,
+};
+use unet_core::ownership::{owned_patterns, set_owned_patterns};
+use unet_core::template::vars::resolve_vars;
+use uuid::Uuid;
+
+use crate::confirm::{Confirmation, confirm};
+
+#[derive(Subcommand)]
+pub enum GoldenCommands {
+    /// List golden configuration assignments
+    List,
+    /// Assign a golden configuration to a node or role
+    Set(SetGoldenArgs),
+    /// Remove a golden configuration assignment
+    Delete(DeleteGoldenArgs),
+    /// Show the golden configuration a node resolves to, rendered
+    Render(NodeGoldenArgs),
+    /// Score a running configuration against the node's golden configuration
+    Check(CheckGoldenArgs),
+    /// Show a node's conformance score history
+    Trend(TrendGoldenArgs),
+    /// Show or set the config sections μNet manages on a node
+    Owned(OwnedSectionsArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct SetGoldenArgs {
+    /// Assignment scope (node, role)
+    pub scope: GoldenScope,
+    /// Node ID or role name
+    pub target: String,
+    /// Configuration file used as is
+    #[arg(
+        long,
+        conflicts_with = "template",
+        required_unless_present = "template"
+    )]
+    pub snapshot: Option<PathBuf>,
+    /// `MiniJinja` template file rendered per node
+    #[arg(long)]
+    pub template: Option<PathBuf>,
+    /// Template variables as a JSON object
+    #[arg(long, requires = "template")]
+    pub vars: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct DeleteGoldenArgs {
+    /// Assignment scope (node, role)
+    pub scope: GoldenScope,
+    /// Node ID or role name
+    pub target: String,
+    /// Skip confirmation prompt
+    #[arg(short = 'y', long)]
+    pub yes: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct NodeGoldenArgs {
+    /// Node ID
+    pub node_id: Uuid,
+}
+
+#[derive(Args, Debug)]
+pub struct CheckGoldenArgs {
+    /// Node ID
+    pub node_id: Uuid,
+    /// Running configuration file
+    #[arg(long)]
+    pub running: PathBuf,
+}
+
+#[derive(Args, Debug)]
+pub struct TrendGoldenArgs {
+    /// Node ID
+    pub node_id: Uuid,
+    /// Show only the newest checks
+    #[arg(long)]
+    pub last: Option<usize>,
+}
+
+#[derive(Args, Debug)]
+pub struct OwnedSectionsArgs {
+    /// Node ID
+    pub node_id: Uuid,
+    /// Slice patterns of the sections μNet owns; replaces the current list
+    #[arg(long = "set", value_name = "PATTERN", num_args = 1.., conflicts_with = "clear")]
+    pub patterns: Vec<String>,
+    /// Remove the annotation so the node's whole configuration is owned
+    #[arg(long)]
+    pub clear: bool,
+}
+
+/// Execute golden configuration subcommands.
+///
+/// # Errors
+/// Returns an error if files cannot be read, the assignment is invalid,
+/// datastore operations fail, or output formatting fails.
+pub async fn execute(
+    command: GoldenCommands,
+    datastore: &dyn DataStore,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    match command {
+        GoldenCommands::List => {
+            crate::commands::print_output(&list_golden(datastore).await?, output_format)
+        }
+        GoldenCommands::Set(args) => {
+            let source = read_source(&args)?;
+            let golden = GoldenConfig::new(args.scope, &args.target, source)?;
+            save_golden(datastore, &golden).await?;
+            crate::commands::print_output(&golden, output_format)
+        }
+        GoldenCommands::Delete(args) => {
+            let confirmation = Confirmation::new("Remove golden config assignment")
+                .affects(format!("{}:{}", args.scope, args.target));
+            if !confirm(args.yes, &confirmation)? {
+                return Ok(());
+            }
+            delete_golden(datastore, args.scope, &args.target).await?;
+            let output = serde_json::json!({
+                "message": "Golden config assignment removed",
+                "scope": args.scope,
+                "target": args.target,
+            });
+            crate::commands::print_output(&output, output_format)
+        }
+        GoldenCommands::Render(args) => {
+            let node = datastore.get_node_required(&args.node_id).await?;
+            let golden = resolve_golden(datastore, &node)
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("No golden config applies to {}", node.name))?;
+            let vars = resolve_vars(datastore, &node).await?;
+            print!("{}", golden.render_with(&node, &vars.variables)?);
+            Ok(())
+        }
+        GoldenCommands::Check(args) => {
+            let node = datastore.get_node_required(&args.node_id).await?;
+            let running = std::fs::read_to_string(&args.running)?;
+            let check = check_conformance(datastore, &node, &running).await?;
+            crate::commands::print_output(&check, output_format)
+        }
+        GoldenCommands::Trend(args) => {
+            let mut history = conformance_history(datastore, args.node_id).await?;
+            if let Some(last) = args.last {
+                history.drain(..history.len().saturating_sub(last));
+            }
+            crate::commands::print_output(&history, output_format)
+        }
+        GoldenCommands::Owned(args) => owned_sections(args, datastore, output_format).await,
+    }
+}
+
+async fn owned_sections(
+    args: OwnedSectionsArgs,
+    datastore: &dyn DataStore,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    let mut node = datastore.get_node_required(&args.node_id).await?;
+    if args.clear || !args.patterns.is_empty() {
+        let patterns = (!args.clear).then_some(args.patterns);
+        set_owned_patterns(&mut node, patterns)?;
+        node = datastore.update_node(&node).await?;
+    }
+    let output = serde_json::json!({
+        "node_id": node.id,
+        "node": node.name,
+        "owned_sections": owned_patterns(&node)?,
+    });
+    crate::commands::print_output(&output, output_format)
+}
+
+fn read_source(args: &SetGoldenArgs) -> Result<GoldenSource> {
+    if let Some(path) = &args.snapshot {
+        return Ok(GoldenSource::Snapshot {
+            config: std::fs::read_to_string(path)?,
+        });
+    }
+    let path = args
+        .template
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("Either --snapshot or --template is required"))?;
+    let variables = args
+        .vars
+        .as_deref()
+        .map(serde_json::from_str::<serde_json::Value>)
+        .transpose()?
+        .unwrap_or_default();
+    Ok(GoldenSource::Template {
+        template: std::fs::read_to_string(path)?,
+        variables,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unet_core::datastore::MockDataStore;
+
+    #[tokio::test]
+    async fn test_set_template_stores_variables() {
+        let dir = tempfile::tempdir().unwrap();
+        let template = dir.path().join("router.j2");
+        std::fs::write(&template, "hostname {{ node.name }}\n").unwrap();
+        let mut mock = MockDataStore::new();
+        mock.expect_put_setting()
+            .withf(|namespace, key, value| {
+                namespace == "golden_configs"
+                    && key == "role:router"
+                    && value["source"]["variables"]["ntp"] == "10.0.0.1"
+            })
+            .times(1)
+            .returning(|_, _, _| Box::pin(async { Ok(()) }));
+
+        let args = SetGoldenArgs {
+            scope: GoldenScope::Role,
+            target: "router".to_string(),
+            snapshot: None,
+            template: Some(template),
+            vars: Some(r#"{"ntp": "10.0.0.1"}"#.to_string()),
+        };
+        execute(GoldenCommands::Set(args), &mock, crate::OutputFormat::Json)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_owned_set_stores_patterns_in_custom_data() {
+        let node = unet_core::models::Node::new(
+            "edge-1".to_string(),
+            "example.com".to_string(),
+            unet_core::models::Vendor::Cisco,
+            unet_core::models::DeviceRole::Router,
+        );
+        let node_id = node.id;
+        let mut mock = MockDataStore::new();
+        mock.expect_get_node_required().returning(move |_| {
+            let node = node.clone();
+            Box::pin(async move { Ok(node) })
+        });
+        mock.expect_update_node()
+            .withf(|node| node.custom_data["owned_sections"] == serde_json::json!(["ntp .*"]))
+            .times(1)
+            .returning(|node| {
+                let node = node.clone();
+                Box::pin(async move { Ok(node) })
+            });
+
+        let args = OwnedSectionsArgs {
+            node_id,
+            patterns: vec!["ntp .*".to_string()],
+            clear: false,
+        };
+        execute(
+            GoldenCommands::Owned(args),
+            &mock,
+            crate::OutputFormat::Json,
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_trend_without_history() {
+        let mut mock = MockDataStore::new();
+        mock.expect_get_setting()
+            .returning(|_, _| Box::pin(async { Ok(None) }));
+
+        let args = TrendGoldenArgs {
+            node_id: Uuid::new_v4(),
+            last: Some(5),
+        };
+        execute(
+            GoldenCommands::Trend(args),
+            &mock,
+            crate::OutputFormat::Json,
+        )
+        .await
+        .unwrap();
+    }
+}
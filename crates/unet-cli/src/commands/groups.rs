@@ -0,0 +1,125 @@
+/// Dynamic node group management commands
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use unet_core::datastore::DataStore;
+use unet_core::groups::{NodeGroup, delete_group, group_members, list_groups, save_group};
+
+use crate::confirm::{Confirmation, confirm};
+
+#[derive(Subcommand)]
+pub enum GroupCommands {
+    /// List node groups
+    List,
+    /// Create or replace a node group
+    Set(SetGroupArgs),
+    /// List the nodes currently matching a group's filter
+    Members(GroupMembersArgs),
+    /// Remove a node group
+    Delete(DeleteGroupArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct SetGroupArgs {
+    /// Group name
+    pub name: String,
+    /// Policy condition members satisfy, e.g. 'node.vendor == "juniper"'
+    #[arg(long)]
+    pub filter: String,
+    /// What the group is for
+    #[arg(long)]
+    pub description: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct GroupMembersArgs {
+    /// Group name
+    pub name: String,
+}
+
+#[derive(Args, Debug)]
+pub struct DeleteGroupArgs {
+    /// Group name
+    pub name: String,
+    /// Skip confirmation prompt
+    #[arg(short = 'y', long)]
+    pub yes: bool,
+}
+
+/// Execute node group subcommands.
+///
+/// # Errors
+/// Returns an error if a group is invalid or does not exist, datastore
+/// operations fail, or output formatting fails.
+pub async fn execute(
+    command: GroupCommands,
+    datastore: &dyn DataStore,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    match command {
+        GroupCommands::List => {
+            crate::commands::print_output(&list_groups(datastore).await?, output_format)
+        }
+        GroupCommands::Set(args) => {
+            let group = NodeGroup::new(&args.name, &args.filter, args.description)?;
+            save_group(datastore, &group).await?;
+            crate::commands::print_output(&group, output_format)
+        }
+        GroupCommands::Members(args) => {
+            let members = group_members(datastore, &args.name).await?;
+            crate::commands::print_output(&members, output_format)
+        }
+        GroupCommands::Delete(args) => {
+            let confirmation = Confirmation::new("Remove node group").affects(&args.name);
+            if !confirm(args.yes, &confirmation)? {
+                return Ok(());
+            }
+            delete_group(datastore, &args.name).await?;
+            let output = serde_json::json!({
+                "message": "Node group removed",
+                "name": args.name,
+            });
+            crate::commands::print_output(&output, output_format)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unet_core::datastore::MockDataStore;
+
+    #[tokio::test]
+    async fn test_set_stores_parsed_group() {
+        let mut mock = MockDataStore::new();
+        mock.expect_put_setting()
+            .withf(|namespace, key, value| {
+                namespace == "node_groups"
+                    && key == "core"
+                    && value["filter"] == "node.role == \"router\""
+            })
+            .times(1)
+            .returning(|_, _, _| Box::pin(async { Ok(()) }));
+
+        let command = GroupCommands::Set(SetGroupArgs {
+            name: "core".to_string(),
+            filter: "node.role == \"router\"".to_string(),
+            description: None,
+        });
+        let result = execute(command, &mock, crate::OutputFormat::Json).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_set_rejects_invalid_filter() {
+        let mut mock = MockDataStore::new();
+        mock.expect_put_setting().never();
+
+        let command = GroupCommands::Set(SetGroupArgs {
+            name: "core".to_string(),
+            filter: "node.role ==".to_string(),
+            description: None,
+        });
+        let result = execute(command, &mock, crate::OutputFormat::Json).await;
+        assert!(result.is_err());
+    }
+}
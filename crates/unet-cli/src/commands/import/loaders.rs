@@ -1,7 +1,10 @@
 /// File loading functions for import operations
 use anyhow::Result;
+use std::collections::BTreeMap;
 use std::path::Path;
 use unet_core::prelude::*;
+use unet_core::vlan::InterfaceVlans;
+use uuid::Uuid;
 
 /// Load locations from JSON file
 pub async fn load_locations(base_path: &Path) -> Result<Option<Vec<Location>>> {
@@ -39,6 +42,18 @@ pub async fn load_links(base_path: &Path) -> Result<Option<Vec<Link>>> {
     Ok(Some(links))
 }
 
+/// Load interface VLAN memberships, keyed by node ID, from JSON file
+pub async fn load_interfaces(base_path: &Path) -> Result<Option<BTreeMap<Uuid, InterfaceVlans>>> {
+    let interfaces_file = base_path.join("interfaces.json");
+    if !interfaces_file.exists() {
+        return Ok(None);
+    }
+
+    let content = tokio::fs::read_to_string(&interfaces_file).await?;
+    let interfaces = serde_json::from_str(&content)?;
+    Ok(Some(interfaces))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -67,6 +82,9 @@ mod tests {
             path: "Test Location".to_string(),
             description: Some("Test description".to_string()),
             address: Some("123 Test St".to_string()),
+            postal_address: None,
+            coordinates: None,
+            timezone: None,
             custom_data: serde_json::Value::Null,
         };
 
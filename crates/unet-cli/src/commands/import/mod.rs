@@ -5,11 +5,15 @@ use std::path::PathBuf;
 use tracing::info;
 use unet_core::datastore::DataStore;
 
+use crate::errors::CommandError;
+
 mod loaders;
 mod processors;
+mod remap;
 mod stats;
 
-use processors::{import_links, import_locations, import_nodes};
+use processors::{import_interfaces, import_links, import_locations, import_nodes};
+use remap::Remap;
 use stats::{ImportStats, ImportSummary};
 
 #[derive(Args)]
@@ -29,6 +33,21 @@ pub struct ImportArgs {
     /// Continue on errors instead of stopping
     #[arg(long)]
     pub continue_on_error: bool,
+
+    /// Give imported locations, nodes, and links new IDs, rewriting the
+    /// references between them, so a bundle can be imported next to its source
+    #[arg(long)]
+    pub new_ids: bool,
+
+    /// Place imported locations whose parent is not part of the import under
+    /// this location (slug, name, path, or ID)
+    #[arg(long, value_name = "LOCATION")]
+    pub parent: Option<String>,
+
+    /// Replace OLD with NEW in the names of imported locations, nodes, and
+    /// links; may be repeated
+    #[arg(long, value_name = "OLD=NEW")]
+    pub rename: Vec<String>,
 }
 
 /// Execute import command with provided arguments
@@ -47,12 +66,21 @@ pub async fn execute(
         info!("Running in dry-run mode - no data will be imported");
     }
 
+    let parent = match &args.parent {
+        Some(parent) => {
+            let id = crate::resolve::location(datastore, parent, false).await?;
+            Some(datastore.get_location_required(&id).await?)
+        }
+        None => None,
+    };
+    let mut remap = Remap::new(&args, parent)?;
     let mut import_stats = ImportStats::new();
 
-    // Import in dependency order: locations, nodes, links
-    import_locations(&args, datastore, &mut import_stats).await?;
-    import_nodes(&args, datastore, &mut import_stats).await?;
-    import_links(&args, datastore, &mut import_stats).await?;
+    // Import in dependency order: locations, nodes, links, interfaces
+    import_locations(&args, &mut remap, datastore, &mut import_stats).await?;
+    import_nodes(&args, &mut remap, datastore, &mut import_stats).await?;
+    import_links(&args, &remap, datastore, &mut import_stats).await?;
+    import_interfaces(&args, &remap, datastore, &mut import_stats).await?;
 
     finalize_import(&import_stats, &args, output_format)
 }
@@ -68,10 +96,11 @@ fn finalize_import(
     crate::commands::print_output(&summary, output_format)?;
 
     if stats.error_count() > 0 && !args.continue_on_error {
-        return Err(anyhow::anyhow!(
-            "Import completed with {} errors",
-            stats.error_count()
-        ));
+        let message = format!("Import completed with {} errors", stats.error_count());
+        if stats.success_count() > 0 {
+            return Err(CommandError::partial_success(message).into());
+        }
+        return Err(anyhow::anyhow!(message));
     }
 
     info!(
@@ -96,6 +125,9 @@ mod tests {
             format: None,
             dry_run: false,
             continue_on_error: false,
+            new_ids: false,
+            parent: None,
+            rename: Vec::new(),
         };
 
         let result = finalize_import(&stats, &args, crate::OutputFormat::Json);
@@ -113,6 +145,9 @@ mod tests {
             format: None,
             dry_run: false,
             continue_on_error: true,
+            new_ids: false,
+            parent: None,
+            rename: Vec::new(),
         };
 
         let result = finalize_import(&stats, &args, crate::OutputFormat::Json);
@@ -130,6 +165,9 @@ mod tests {
             format: None,
             dry_run: false,
             continue_on_error: false,
+            new_ids: false,
+            parent: None,
+            rename: Vec::new(),
         };
 
         let result = finalize_import(&stats, &args, crate::OutputFormat::Json);
@@ -137,6 +175,28 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("2 errors"));
     }
 
+    #[tokio::test]
+    async fn test_finalize_import_with_some_errors_is_partial_success() {
+        let mut stats = ImportStats::new();
+        stats.record_success();
+        stats.record_error("Error 1".to_string());
+
+        let args = ImportArgs {
+            from: PathBuf::from("/tmp"),
+            format: None,
+            dry_run: false,
+            continue_on_error: false,
+            new_ids: false,
+            parent: None,
+            rename: Vec::new(),
+        };
+
+        let error = finalize_import(&stats, &args, crate::OutputFormat::Json).unwrap_err();
+        let report = crate::errors::ErrorReport::new(&error);
+        assert_eq!(report.kind, crate::errors::ErrorKind::PartialSuccess);
+        assert_eq!(report.exit_code, 7);
+    }
+
     #[tokio::test]
     async fn test_finalize_import_dry_run() {
         let stats = ImportStats::new();
@@ -146,6 +206,9 @@ mod tests {
             format: None,
             dry_run: true,
             continue_on_error: false,
+            new_ids: false,
+            parent: None,
+            rename: Vec::new(),
         };
 
         let result = finalize_import(&stats, &args, crate::OutputFormat::Json);
@@ -167,7 +230,7 @@ mod exec_tests {
     async fn test_execute_import_no_files_ok() {
         let temp = TempDir::new().unwrap();
         let mock = MockDataStore::new();
-        let args = ImportArgs { from: temp.path().to_path_buf(), format: None, dry_run: true, continue_on_error: false };
+        let args = ImportArgs { from: temp.path().to_path_buf(), format: None, dry_run: true, continue_on_error: false, new_ids: false, parent: None, rename: vec![] };
         let res = execute(args, &mock, crate::OutputFormat::Json).await;
         assert!(res.is_ok());
     }
@@ -187,7 +250,7 @@ mod exec_tests {
 
         // dry_run means no datastore calls are performed, so mock without expectations
         let mock = MockDataStore::new();
-        let args = ImportArgs { from: temp.path().to_path_buf(), format: None, dry_run: true, continue_on_error: false };
+        let args = ImportArgs { from: temp.path().to_path_buf(), format: None, dry_run: true, continue_on_error: false, new_ids: false, parent: None, rename: vec![] };
         let res = execute(args, &mock, crate::OutputFormat::Json).await;
         assert!(res.is_ok());
     }
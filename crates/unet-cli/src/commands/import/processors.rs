@@ -1,22 +1,29 @@
 /// Import processing functions for different entity types
 use anyhow::Result;
+use std::path::Path;
 use tracing::info;
 use unet_core::datastore::DataStore;
 use unet_core::prelude::*;
+use unet_core::provenance::{self, Source};
+use unet_core::vlan::{InterfaceVlans, set_interface_vlan};
+use uuid::Uuid;
 
 use super::ImportArgs;
-use super::loaders::{load_links, load_locations, load_nodes};
+use super::loaders::{load_interfaces, load_links, load_locations, load_nodes};
+use super::remap::Remap;
 use super::stats::{ImportStats, process_import_item};
 
 /// Import locations from source path
 pub async fn import_locations(
     args: &ImportArgs,
+    remap: &mut Remap,
     datastore: &dyn DataStore,
     stats: &mut ImportStats,
 ) -> Result<()> {
-    let Some(locations) = load_locations(&args.from).await? else {
+    let Some(mut locations) = load_locations(&args.from).await? else {
         return Ok(());
     };
+    remap.locations(&mut locations);
 
     info!("Importing {} locations...", locations.len());
     for location in locations {
@@ -34,17 +41,19 @@ pub async fn import_locations(
 /// Import nodes from source path
 pub async fn import_nodes(
     args: &ImportArgs,
+    remap: &mut Remap,
     datastore: &dyn DataStore,
     stats: &mut ImportStats,
 ) -> Result<()> {
-    let Some(nodes) = load_nodes(&args.from).await? else {
+    let Some(mut nodes) = load_nodes(&args.from).await? else {
         return Ok(());
     };
+    remap.nodes(&mut nodes);
 
     info!("Importing {} nodes...", nodes.len());
     for node in nodes {
         process_import_item(
-            || import_node(&node, datastore, args.dry_run),
+            || import_node(&node, &args.from, datastore, args.dry_run),
             &format!("node '{}'", node.name),
             args.continue_on_error,
             stats,
@@ -57,12 +66,14 @@ pub async fn import_nodes(
 /// Import links from source path
 pub async fn import_links(
     args: &ImportArgs,
+    remap: &Remap,
     datastore: &dyn DataStore,
     stats: &mut ImportStats,
 ) -> Result<()> {
-    let Some(links) = load_links(&args.from).await? else {
+    let Some(mut links) = load_links(&args.from).await? else {
         return Ok(());
     };
+    remap.links(&mut links);
 
     info!("Importing {} links...", links.len());
     for link in links {
@@ -77,6 +88,31 @@ pub async fn import_links(
     Ok(())
 }
 
+/// Import interface VLAN memberships from source path
+pub async fn import_interfaces(
+    args: &ImportArgs,
+    remap: &Remap,
+    datastore: &dyn DataStore,
+    stats: &mut ImportStats,
+) -> Result<()> {
+    let Some(interfaces) = load_interfaces(&args.from).await? else {
+        return Ok(());
+    };
+
+    info!("Importing interfaces of {} nodes...", interfaces.len());
+    for (node_id, memberships) in interfaces {
+        let node_id = remap.node_id(node_id);
+        process_import_item(
+            || import_node_interfaces(node_id, &memberships, datastore, args.dry_run),
+            &format!("interfaces of node {node_id}"),
+            args.continue_on_error,
+            stats,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
 /// Import a single location
 async fn import_location(
     location: &Location,
@@ -92,14 +128,28 @@ async fn import_location(
     Ok(())
 }
 
-/// Import a single node
-async fn import_node(node: &Node, datastore: &dyn DataStore, dry_run: bool) -> Result<()> {
+/// Import a single node, recording `from` as the source of its fields
+async fn import_node(
+    node: &Node,
+    from: &Path,
+    datastore: &dyn DataStore,
+    dry_run: bool,
+) -> Result<()> {
     if dry_run {
         info!("Would import node: {}", node.name);
         return Ok(());
     }
 
-    datastore.create_node(node).await?;
+    let created = datastore.create_node(node).await?;
+    provenance::record(
+        datastore,
+        None,
+        &created,
+        Source::Import,
+        Some(&from.display().to_string()),
+        chrono::Utc::now(),
+    )
+    .await?;
     Ok(())
 }
 
@@ -113,6 +163,24 @@ async fn import_link(link: &Link, datastore: &dyn DataStore, dry_run: bool) -> R
     datastore.create_link(link).await?;
     Ok(())
 }
+/// Import the VLAN memberships of one node's interfaces
+async fn import_node_interfaces(
+    node_id: Uuid,
+    memberships: &InterfaceVlans,
+    datastore: &dyn DataStore,
+    dry_run: bool,
+) -> Result<()> {
+    if dry_run {
+        info!("Would import {} interfaces of node {node_id}", memberships.len());
+        return Ok(());
+    }
+
+    for (interface, membership) in memberships {
+        set_interface_vlan(datastore, node_id, interface, Some(membership.clone())).await?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -152,8 +220,9 @@ mod tests {
             .role(DeviceRole::Router)
             .build()
             .unwrap();
+        let from = std::path::Path::new("/tmp/inventory");
         let mut mock = MockDataStore::new();
-        assert!(import_node(&node, &mock, true).await.is_ok());
+        assert!(import_node(&node, from, &mock, true).await.is_ok());
         let node_clone = node.clone();
         mock.expect_create_node()
             .with(always())
@@ -161,7 +230,15 @@ mod tests {
                 let n = node_clone.clone();
                 Box::pin(async move { Ok(n) })
             });
-        assert!(import_node(&node, &mock, false).await.is_ok());
+        mock.expect_get_setting()
+            .returning(|_, _| Box::pin(async { Ok(None) }));
+        mock.expect_put_setting()
+            .withf(|namespace, _, value| {
+                namespace == "node_provenance"
+                    && value["fields"]["name"]["detail"] == "/tmp/inventory"
+            })
+            .returning(|_, _, _| Box::pin(async { Ok(()) }));
+        assert!(import_node(&node, from, &mock, false).await.is_ok());
     }
 
     #[tokio::test]
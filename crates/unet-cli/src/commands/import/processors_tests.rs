@@ -2,6 +2,7 @@
 #[cfg(test)]
 mod tests {
     use super::super::processors::{import_links, import_locations, import_nodes};
+    use super::super::remap::Remap;
     use super::super::stats::ImportStats;
     use crate::commands::import::ImportArgs;
     use tempfile::TempDir;
@@ -31,6 +32,8 @@ mod tests {
             link_counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
             ready_ok(link.clone())
         });
+        store.expect_get_setting().returning(|_, _| ready_ok(None));
+        store.expect_put_setting().returning(|_, _, _| ready_ok(()));
         store
     }
 
@@ -43,6 +46,9 @@ mod tests {
             path: "loc1".to_string(),
             description: None,
             address: None,
+            postal_address: None,
+            coordinates: None,
+            timezone: None,
             custom_data: serde_json::Value::Null,
         };
         std::fs::write(
@@ -97,11 +103,21 @@ mod tests {
             format: None,
             dry_run: false,
             continue_on_error: false,
+            new_ids: false,
+            parent: None,
+            rename: Vec::new(),
         };
 
-        import_locations(&args, &store, &mut stats).await.unwrap();
-        import_nodes(&args, &store, &mut stats).await.unwrap();
-        import_links(&args, &store, &mut stats).await.unwrap();
+        let mut remap = Remap::default();
+        import_locations(&args, &mut remap, &store, &mut stats)
+            .await
+            .unwrap();
+        import_nodes(&args, &mut remap, &store, &mut stats)
+            .await
+            .unwrap();
+        import_links(&args, &remap, &store, &mut stats)
+            .await
+            .unwrap();
 
         assert!(location_count.load(std::sync::atomic::Ordering::SeqCst) >= 1);
         assert!(node_count.load(std::sync::atomic::Ordering::SeqCst) >= 1);
@@ -127,11 +143,21 @@ mod tests {
             format: None,
             dry_run: true,
             continue_on_error: false,
+            new_ids: false,
+            parent: None,
+            rename: Vec::new(),
         };
 
-        import_locations(&args, &store, &mut stats).await.unwrap();
-        import_nodes(&args, &store, &mut stats).await.unwrap();
-        import_links(&args, &store, &mut stats).await.unwrap();
+        let mut remap = Remap::default();
+        import_locations(&args, &mut remap, &store, &mut stats)
+            .await
+            .unwrap();
+        import_nodes(&args, &mut remap, &store, &mut stats)
+            .await
+            .unwrap();
+        import_links(&args, &remap, &store, &mut stats)
+            .await
+            .unwrap();
 
         assert_eq!(location_count.load(std::sync::atomic::Ordering::SeqCst), 0);
         assert_eq!(node_count.load(std::sync::atomic::Ordering::SeqCst), 0);
@@ -0,0 +1,217 @@
+/// Rewriting of imported entities so a bundle can be imported next to its source
+use anyhow::{Result, anyhow};
+use std::collections::HashMap;
+use unet_core::prelude::*;
+use uuid::Uuid;
+
+use super::ImportArgs;
+
+/// IDs, names, and placement to apply to imported entities
+///
+/// Locations must be remapped before the nodes and links that refer to them,
+/// and nodes before links and interfaces; references to entities outside the
+/// import are kept as they are.
+#[derive(Debug, Default)]
+pub struct Remap {
+    new_ids: bool,
+    renames: Vec<(String, String)>,
+    parent: Option<Location>,
+    /// New ID of each imported location and node, by its ID in the files
+    ids: HashMap<Uuid, Uuid>,
+    /// New path of each imported location, by its ID in the files
+    paths: HashMap<Uuid, String>,
+}
+
+impl Remap {
+    /// Builds the remapping `args` asks for, with `parent` resolved from
+    /// `--parent`
+    ///
+    /// # Errors
+    /// Returns an error if a `--rename` value is not `OLD=NEW`.
+    pub fn new(args: &ImportArgs, parent: Option<Location>) -> Result<Self> {
+        let renames = args
+            .rename
+            .iter()
+            .map(|rename| match rename.split_once('=') {
+                Some((old, new)) if !old.is_empty() => Ok((old.to_string(), new.to_string())),
+                _ => Err(anyhow!("Invalid --rename {rename:?}: expected OLD=NEW")),
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            new_ids: args.new_ids,
+            renames,
+            parent,
+            ..Self::default()
+        })
+    }
+
+    const fn is_identity(&self) -> bool {
+        !self.new_ids && self.renames.is_empty() && self.parent.is_none()
+    }
+
+    fn id(&self, id: Uuid) -> Uuid {
+        self.ids.get(&id).copied().unwrap_or(id)
+    }
+
+    fn assign_id(&mut self, id: &mut Uuid) {
+        let old = *id;
+        if self.new_ids {
+            *id = Uuid::new_v4();
+        }
+        self.ids.insert(old, *id);
+    }
+
+    fn rename(&self, name: &str) -> String {
+        self.renames
+            .iter()
+            .fold(name.to_string(), |name, (old, new)| name.replace(old, new))
+    }
+
+    /// Remaps locations, ordering parents before their children and
+    /// recomputing paths from the remapped parents
+    pub fn locations(&mut self, locations: &mut [Location]) {
+        if self.is_identity() {
+            return;
+        }
+        locations.sort_by_key(|location| location.path.matches('/').count());
+        let imported: Vec<Uuid> = locations.iter().map(|location| location.id).collect();
+        for location in locations {
+            let old_id = location.id;
+            self.assign_id(&mut location.id);
+            location.name = self.rename(&location.name);
+            let parent_path = match location.parent_id {
+                Some(parent_id) if imported.contains(&parent_id) => {
+                    location.parent_id = Some(self.id(parent_id));
+                    self.paths.get(&parent_id).cloned()
+                }
+                _ => match &self.parent {
+                    Some(parent) => {
+                        location.parent_id = Some(parent.id);
+                        Some(parent.path.clone())
+                    }
+                    None => location
+                        .path
+                        .rsplit_once('/')
+                        .map(|(parent_path, _)| parent_path.to_string()),
+                },
+            };
+            location.path = parent_path.map_or_else(
+                || location.name.clone(),
+                |parent_path| format!("{parent_path}/{}", location.name),
+            );
+            self.paths.insert(old_id, location.path.clone());
+        }
+    }
+
+    /// Remaps nodes and the locations they are placed in
+    pub fn nodes(&mut self, nodes: &mut [Node]) {
+        if self.is_identity() {
+            return;
+        }
+        for node in nodes {
+            self.assign_id(&mut node.id);
+            let name = self.rename(&node.name);
+            if name != node.name {
+                node.name = name;
+                node.update_fqdn();
+            }
+            node.location_id = node.location_id.map(|id| self.id(id));
+        }
+    }
+
+    /// Remaps links and the nodes they connect
+    pub fn links(&self, links: &mut [Link]) {
+        if self.is_identity() {
+            return;
+        }
+        for link in links {
+            if self.new_ids {
+                link.id = Uuid::new_v4();
+            }
+            link.name = self.rename(&link.name);
+            link.source_node_id = self.id(link.source_node_id);
+            link.dest_node_id = link.dest_node_id.map(|id| self.id(id));
+        }
+    }
+
+    /// ID under which a node from the files was imported
+    pub fn node_id(&self, id: Uuid) -> Uuid {
+        self.id(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn args(new_ids: bool, rename: &[&str]) -> ImportArgs {
+        ImportArgs {
+            from: PathBuf::from("/tmp"),
+            format: None,
+            dry_run: false,
+            continue_on_error: false,
+            new_ids,
+            parent: None,
+            rename: rename.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    #[test]
+    fn test_remap_gives_new_ids_names_and_paths_under_parent() {
+        let site = Location::new_root("dc-west".to_string(), "site".to_string());
+        let mut hall = Location::new_child("Hall A".to_string(), "room".to_string(), &site.path);
+        hall.parent_id = Some(site.id);
+        let mut node = Node::new(
+            "dc-west-r1".to_string(),
+            "example.com".to_string(),
+            Vendor::Juniper,
+            DeviceRole::Router,
+        );
+        node.location_id = Some(hall.id);
+        let external = Uuid::new_v4();
+        let link = Link::new(
+            "r1-up".into(),
+            node.id,
+            "xe-0/0/0".into(),
+            external,
+            "xe-0/0/1".into(),
+        );
+        let region = Location::new_root("US".to_string(), "region".to_string());
+
+        let mut remap =
+            Remap::new(&args(true, &["dc-west=dc-east"]), Some(region.clone())).unwrap();
+        let mut locations = vec![hall.clone(), site.clone()];
+        let mut nodes = vec![node.clone()];
+        let mut links = vec![link];
+        remap.locations(&mut locations);
+        remap.nodes(&mut nodes);
+        remap.links(&mut links);
+
+        let paths: Vec<&str> = locations.iter().map(|l| l.path.as_str()).collect();
+        assert_eq!(paths, ["US/dc-east", "US/dc-east/Hall A"]);
+        assert_eq!(locations[0].parent_id, Some(region.id));
+        assert_eq!(locations[1].parent_id, Some(locations[0].id));
+        assert_ne!(locations[0].id, site.id);
+        assert_eq!(nodes[0].fqdn, "dc-east-r1.example.com");
+        assert_eq!(nodes[0].location_id, Some(locations[1].id));
+        assert_ne!(nodes[0].id, node.id);
+        assert_eq!(links[0].source_node_id, nodes[0].id);
+        assert_eq!(
+            links[0].dest_node_id,
+            Some(external),
+            "outside references are kept"
+        );
+        assert_eq!(remap.node_id(node.id), nodes[0].id);
+    }
+
+    #[test]
+    fn test_remap_without_options_keeps_entities() {
+        let site = Location::new_root("dc-west".to_string(), "site".to_string());
+        let mut remap = Remap::new(&args(false, &[]), None).unwrap();
+        let mut locations = vec![site.clone()];
+        remap.locations(&mut locations);
+        assert_eq!(locations, [site]);
+        assert!(Remap::new(&args(false, &["no-separator"]), None).is_err());
+    }
+}
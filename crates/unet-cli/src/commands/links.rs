@@ -3,8 +3,15 @@ use anyhow::Result;
 use unet_core::datastore::DataStore;
 
 mod crud;
+mod import_matrix;
+mod matrix;
+mod measure;
+mod path;
+mod render;
 mod types;
+mod validate;
 
+pub use measure::measure;
 pub use types::LinkCommands;
 
 /// Execute link subcommands.
@@ -22,6 +29,21 @@ pub async fn execute(
         LinkCommands::Show(args) => crud::show_link(args, datastore, output_format).await,
         LinkCommands::Update(args) => crud::update_link(args, datastore, output_format).await,
         LinkCommands::Delete(args) => crud::delete_link(args, datastore, output_format).await,
+        LinkCommands::Validate => validate::validate_links(datastore, output_format).await,
+        LinkCommands::ImportMatrix(args) => {
+            import_matrix::import_matrix(args, datastore, output_format).await
+        }
+        LinkCommands::Measure(_) => Err(anyhow::anyhow!(
+            "measure needs the SNMP configuration and runs before other link commands"
+        )),
+        LinkCommands::Measurements(args) => {
+            measure::show_measurements(args, datastore, output_format).await
+        }
+        LinkCommands::Thresholds(args) => {
+            measure::set_link_thresholds(args, datastore, output_format).await
+        }
+        LinkCommands::Render(args) => render::render_link(args, datastore).await,
+        LinkCommands::Path(args) => path::find_link_path(args, datastore, output_format).await,
     }
 }
 
@@ -42,7 +64,22 @@ mod dispatch_tests {
             .with(always())
             .returning(|_| Box::pin(async { Ok(PagedResult::new(vec![], 0, None)) }));
 
-        let args = ListLinkArgs { node_id: None, min_bandwidth: None, page: 1, per_page: 20 };
+        let args = ListLinkArgs {
+            node_id: None,
+            min_bandwidth: None,
+            provider: None,
+            circuit_id: None,
+            state: None,
+            ordered_after: None,
+            ordered_before: None,
+            provider: None,
+            circuit_id: None,
+            state: None,
+            ordered_after: None,
+            ordered_before: None,
+            page: 1,
+            per_page: 20,
+        };
 
         let res = execute(
             types::LinkCommands::List(args),
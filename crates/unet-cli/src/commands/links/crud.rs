@@ -1,10 +1,13 @@
 /// CRUD operations for link management
 use anyhow::Result;
 use serde_json::Value as JsonValue;
-use unet_core::datastore::DataStore;
+use unet_core::datastore::{DEFAULT_MAX_RETRIES, DataStore, retry_operation};
 use unet_core::prelude::*;
+use unet_core::slug::{SlugKind, assign_slug, check_slug, remove_slugs};
+use unet_core::topology::verify_link_endpoints;
 
 use super::types::{AddLinkArgs, DeleteLinkArgs, ListLinkArgs, ShowLinkArgs, UpdateLinkArgs};
+use crate::confirm::{Confirmation, confirm};
 
 pub async fn add_link(
     args: AddLinkArgs,
@@ -44,12 +47,48 @@ pub async fn add_link(
         builder = builder.custom_data(custom_data);
     }
 
+    if let Some(provider) = args.provider {
+        builder = builder.provider(provider);
+    }
+
+    if let Some(circuit_id) = args.circuit_id {
+        builder = builder.circuit_id(circuit_id);
+    }
+
+    if let Some(ordered_on) = args.ordered_on {
+        builder = builder.ordered_on(ordered_on);
+    }
+
+    if let Some(delivered_on) = args.delivered_on {
+        builder = builder.delivered_on(delivered_on);
+    }
+
+    if let Some(state) = args.state {
+        builder = builder.provisioning_state(state);
+    }
+
     let link = builder
         .build()
         .map_err(|e| anyhow::anyhow!("Link validation failed: {e}"))?;
 
+    if !args.skip_interface_check {
+        check_endpoints(datastore, &link).await?;
+    }
+    if let Some(slug) = &args.slug {
+        check_slug(datastore, SlugKind::Link, slug).await?;
+    }
+
     // Create link in datastore
-    let created_link = datastore.create_link(&link).await?;
+    let created_link =
+        retry_operation(DEFAULT_MAX_RETRIES, || datastore.create_link(&link)).await?;
+    assign_slug(
+        datastore,
+        SlugKind::Link,
+        created_link.id,
+        &created_link.name,
+        args.slug.as_deref(),
+    )
+    .await?;
 
     crate::commands::print_output(&created_link, output_format)?;
 
@@ -73,6 +112,43 @@ pub async fn list_links(
         });
     }
 
+    if let Some(provider) = args.provider {
+        filters.push(Filter {
+            field: "provider".to_owned(),
+            operation: FilterOperation::Equals,
+            value: FilterValue::String(provider),
+        });
+    }
+
+    if let Some(circuit_id) = args.circuit_id {
+        filters.push(Filter {
+            field: "circuit_id".to_owned(),
+            operation: FilterOperation::Contains,
+            value: FilterValue::String(circuit_id),
+        });
+    }
+
+    if let Some(state) = args.state {
+        filters.push(Filter {
+            field: "provisioning_state".to_owned(),
+            operation: FilterOperation::Equals,
+            value: FilterValue::String(state.to_string()),
+        });
+    }
+
+    for (date, operation) in [
+        (args.ordered_after, FilterOperation::GreaterThanOrEqual),
+        (args.ordered_before, FilterOperation::LessThanOrEqual),
+    ] {
+        if let Some(date) = date {
+            filters.push(Filter {
+                field: "ordered_on".to_owned(),
+                operation,
+                value: FilterValue::String(date.to_string()),
+            });
+        }
+    }
+
     // Note: For bandwidth filtering, we'd need to extend the CSV datastore
     // to support numeric comparisons. For now, we'll skip this filter.
 
@@ -100,7 +176,8 @@ pub async fn show_link(
     datastore: &dyn DataStore,
     output_format: crate::OutputFormat,
 ) -> Result<()> {
-    let link = datastore.get_link_required(&args.id).await?;
+    let id = crate::resolve::link(datastore, &args.id.reference, args.id.by_id).await?;
+    let link = datastore.get_link_required(&id).await?;
 
     crate::commands::print_output(&link, output_format)?;
 
@@ -112,7 +189,8 @@ pub async fn update_link(
     datastore: &dyn DataStore,
     output_format: crate::OutputFormat,
 ) -> Result<()> {
-    let mut link = datastore.get_link_required(&args.id).await?;
+    let id = crate::resolve::link(datastore, &args.id.reference, args.id.by_id).await?;
+    let mut link = datastore.get_link_required(&id).await?;
 
     // Update fields that were provided
     if let Some(name) = args.name {
@@ -147,35 +225,70 @@ pub async fn update_link(
         link.custom_data = serde_json::from_str(&custom_data_str)?;
     }
 
-    let updated_link = datastore.update_link(&link).await?;
+    if let Some(provider) = args.provider {
+        link.provider = Some(provider);
+    }
+
+    if let Some(circuit_id) = args.circuit_id {
+        link.circuit_id = Some(circuit_id);
+    }
+
+    if let Some(ordered_on) = args.ordered_on {
+        link.ordered_on = Some(ordered_on);
+    }
+
+    if let Some(delivered_on) = args.delivered_on {
+        link.delivered_on = Some(delivered_on);
+    }
+
+    if let Some(state) = args.state {
+        link.provisioning_state = link
+            .provisioning_state
+            .transition_to(state)
+            .map_err(|e| anyhow::anyhow!("Link update failed: {e}"))?;
+    }
+
+    link.validate()
+        .map_err(|e| anyhow::anyhow!("Link validation failed: {e}"))?;
+
+    if !args.skip_interface_check {
+        check_endpoints(datastore, &link).await?;
+    }
+
+    let updated_link =
+        retry_operation(DEFAULT_MAX_RETRIES, || datastore.update_link(&link)).await?;
 
     crate::commands::print_output(&updated_link, output_format)?;
 
     Ok(())
 }
 
+/// Rejects links whose endpoints do not match known nodes and interfaces
+async fn check_endpoints(datastore: &dyn DataStore, link: &Link) -> Result<()> {
+    verify_link_endpoints(datastore, link).await.map_err(|e| {
+        anyhow::anyhow!("Link endpoint check failed: {e} (use --skip-interface-check to override)")
+    })
+}
+
 pub async fn delete_link(
     args: DeleteLinkArgs,
     datastore: &dyn DataStore,
     output_format: crate::OutputFormat,
 ) -> Result<()> {
     // Check if link exists first
-    let link = datastore.get_link_required(&args.id).await?;
-
-    if !args.yes {
-        let stdin = std::io::stdin();
-        let mut lock = stdin.lock();
-        let mut reader = std::io::BufReader::new(&mut lock);
-        if !confirm_link_deletion(false, &link, &mut reader)? {
-            return Ok(());
-        }
+    let id = crate::resolve::link(datastore, &args.id.reference, args.id.by_id).await?;
+    let link = datastore.get_link_required(&id).await?;
+
+    if !confirm(args.yes, &link_deletion(&link))? {
+        return Ok(());
     }
 
-    datastore.delete_link(&args.id).await?;
+    retry_operation(DEFAULT_MAX_RETRIES, || datastore.delete_link(&id)).await?;
+    remove_slugs(datastore, SlugKind::Link, id).await?;
 
     let output = serde_json::json!({
         "message": "Link deleted successfully",
-        "id": args.id
+        "id": id
     });
 
     crate::commands::print_output(&output, output_format)?;
@@ -183,31 +296,15 @@ pub async fn delete_link(
     Ok(())
 }
 
-// Testable confirmation helper for link deletion
-pub fn confirm_link_deletion(
-    yes: bool,
-    link: &Link,
-    reader: &mut impl std::io::BufRead,
-) -> Result<bool> {
-    if yes {
-        return Ok(true);
-    }
-    println!(
-        "Are you sure you want to delete link {} <-> {} (ID: {})? [y/N]",
+/// Describes a link deletion for the confirmation prompt
+fn link_deletion(link: &Link) -> Confirmation {
+    Confirmation::new("Delete link").affects(format!(
+        "{} ({} <-> {}, ID: {})",
+        link.name,
         link.node_a_interface,
-        link
-            .node_z_interface
-            .as_deref()
-            .unwrap_or("internet"),
+        link.node_z_interface.as_deref().unwrap_or("internet"),
         link.id
-    );
-    let mut input = String::new();
-    reader.read_line(&mut input)?;
-    if !input.trim().to_lowercase().starts_with('y') {
-        println!("Cancelled.");
-        return Ok(false);
-    }
-    Ok(true)
+    ))
 }
 
 #[cfg(test)]
@@ -216,7 +313,7 @@ mod confirm_tests {
     use uuid::Uuid;
 
     #[test]
-    fn test_confirm_link_deletion_yes_and_no() {
+    fn test_link_deletion_names_both_interfaces() {
         let link = Link::new(
             "L1".into(),
             Uuid::new_v4(),
@@ -225,14 +322,72 @@ mod confirm_tests {
             "Gi0/1".into(),
         );
 
-        // No
-        let mut cur = std::io::Cursor::new(b"no\n".to_vec());
-        let res = confirm_link_deletion(false, &link, &mut cur).unwrap();
-        assert!(!res);
+        let prompt = link_deletion(&link).prompt();
+
+        assert!(prompt.contains("L1 (Gi0/0 <-> Gi0/1"));
+        assert!(prompt.contains(&link.id.to_string()));
+    }
+}
+
+#[cfg(test)]
+mod update_tests {
+    use super::*;
+    use crate::commands::links::types::UpdateLinkArgs;
+    use unet_core::datastore::{MockDataStore, testing::ready_ok};
+    use uuid::Uuid;
+
+    fn state_update(id: Uuid, state: ProvisioningState) -> UpdateLinkArgs {
+        UpdateLinkArgs {
+            id: id.into(),
+            name: None,
+            node_a_id: None,
+            node_a_interface: None,
+            node_z_id: None,
+            node_z_interface: None,
+            bandwidth_bps: None,
+            description: None,
+            custom_data: None,
+            provider: None,
+            circuit_id: None,
+            ordered_on: None,
+            delivered_on: None,
+            state: Some(state),
+            skip_interface_check: true,
+        }
+    }
+
+    fn datastore_with(link: Link) -> MockDataStore {
+        let mut datastore = MockDataStore::new();
+        datastore
+            .expect_get_link_required()
+            .returning(move |_| ready_ok(link.clone()));
+        datastore
+            .expect_update_link()
+            .returning(|link| ready_ok(link.clone()));
+        datastore
+    }
 
-        // Yes
-        let mut cur2 = std::io::Cursor::new(b"y\n".to_vec());
-        let res2 = confirm_link_deletion(false, &link, &mut cur2).unwrap();
-        assert!(res2);
+    #[tokio::test]
+    async fn test_update_link_advances_one_state_at_a_time() {
+        let mut link = Link::new_internet_circuit("transit".into(), Uuid::new_v4(), "et-0".into());
+        link.provisioning_state = ProvisioningState::Ordered;
+        let id = link.id;
+
+        let skipped = update_link(
+            state_update(id, ProvisioningState::Live),
+            &datastore_with(link.clone()),
+            crate::OutputFormat::Json,
+        )
+        .await
+        .unwrap_err();
+        assert!(skipped.to_string().contains("next is delivered"));
+
+        update_link(
+            state_update(id, ProvisioningState::Delivered),
+            &datastore_with(link),
+            crate::OutputFormat::Json,
+        )
+        .await
+        .unwrap();
     }
 }
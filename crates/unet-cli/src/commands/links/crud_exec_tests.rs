@@ -32,6 +32,10 @@ mod tests {
             deleted_flag.store(true, std::sync::atomic::Ordering::SeqCst);
             ready_ok(())
         });
+        store.expect_get_setting().returning(|_, _| ready_ok(None));
+        store.expect_put_setting().returning(|_, _, _| ready_ok(()));
+        store.expect_list_settings().returning(|_| ready_ok(Vec::new()));
+        store.expect_delete_setting().returning(|_, _| ready_ok(()));
         store
     }
 
@@ -60,17 +64,18 @@ mod tests {
             bandwidth_bps: Some(1_000_000),
             description: Some("desc".to_string()),
             custom_data: Some("{\"prio\":1}".to_string()),
+            slug: None,
         };
         assert!(add_link(args, &store, crate::OutputFormat::Json).await.is_ok());
 
         let link = example_link();
         let show_store =
             store_for_link(Some(link.clone()), list_options.clone(), deleted.clone());
-        let show_args = ShowLinkArgs { id: link.id };
+        let show_args = ShowLinkArgs { id: link.id.into() };
         assert!(show_link(show_args, &show_store, crate::OutputFormat::Json).await.is_ok());
 
         let upd_args = UpdateLinkArgs {
-            id: link.id,
+            id: link.id.into(),
             name: Some("l2".to_string()),
             node_a_id: None,
             node_a_interface: Some("e2".to_string()),
@@ -85,7 +90,7 @@ mod tests {
         let delete_store =
             store_for_link(Some(link.clone()), list_options.clone(), deleted.clone());
         let del_args = DeleteLinkArgs {
-            id: link.id,
+            id: link.id.into(),
             yes: true,
         };
         assert!(delete_link(del_args, &delete_store, crate::OutputFormat::Json).await.is_ok());
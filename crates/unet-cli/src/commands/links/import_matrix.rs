@@ -0,0 +1,214 @@
+/// Batch link creation from an adjacency list
+use anyhow::{Result, anyhow};
+use serde::Serialize;
+use std::collections::HashMap;
+use unet_core::datastore::{BatchOperation, DataStore, QueryOptions};
+use unet_core::models::{Link, LinkBuilder, Node};
+use unet_core::topology::verify_link_endpoints;
+use uuid::Uuid;
+
+use super::matrix::{self, AdjacencyRow};
+use super::types::ImportMatrixArgs;
+
+/// A link that passed validation
+#[derive(Debug, Serialize)]
+pub struct PlannedLink {
+    /// Line (CSV) or item (YAML/JSON) number in the input
+    pub line: usize,
+    /// Link to create
+    pub link: Link,
+}
+
+/// A row that failed validation
+#[derive(Debug, Serialize)]
+pub struct RowError {
+    /// Line (CSV) or item (YAML/JSON) number in the input
+    pub line: usize,
+    /// Reason the row was rejected
+    pub message: String,
+}
+
+/// Outcome of an import
+#[derive(Debug, Serialize)]
+pub struct MatrixImportReport {
+    /// True if links were only previewed
+    pub dry_run: bool,
+    /// Links that were (or would be) created
+    pub links: Vec<PlannedLink>,
+    /// Rows rejected during validation or creation
+    pub errors: Vec<RowError>,
+    /// Number of links created
+    pub created: usize,
+}
+
+/// Validates every row, then creates all links in one batch
+///
+/// Nothing is created if any row fails validation.
+pub async fn import_matrix(
+    args: ImportMatrixArgs,
+    datastore: &dyn DataStore,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    let ImportMatrixArgs {
+        file,
+        dry_run,
+        skip_interface_check,
+    } = args;
+    let rows = matrix::load(&file)?;
+    let mut report = plan(&rows, datastore, skip_interface_check).await?;
+    report.dry_run = dry_run;
+
+    if report.errors.is_empty() && !dry_run && !report.links.is_empty() {
+        let operations: Vec<_> = report
+            .links
+            .iter()
+            .map(|planned| BatchOperation::Insert(planned.link.clone()))
+            .collect();
+        let result = datastore.batch_links(&operations).await?;
+        report.created = result.success_count;
+        report
+            .errors
+            .extend(result.errors.into_iter().map(|(index, e)| RowError {
+                line: report.links[index].line,
+                message: format!("Failed to create link: {e}"),
+            }));
+    }
+
+    crate::commands::print_output(&report, output_format)?;
+
+    if report.errors.is_empty() {
+        Ok(())
+    } else if report.created == 0 {
+        Err(anyhow!(
+            "{} of {} rows are invalid; no links were created",
+            report.errors.len(),
+            rows.len()
+        ))
+    } else {
+        Err(anyhow!(
+            "Created {} links; {} failed",
+            report.created,
+            report.errors.len()
+        ))
+    }
+}
+
+/// Resolves and validates every row without writing anything
+async fn plan(
+    rows: &[(usize, AdjacencyRow)],
+    datastore: &dyn DataStore,
+    skip_interface_check: bool,
+) -> Result<MatrixImportReport> {
+    let nodes = NodeIndex::new(datastore.list_nodes(&QueryOptions::default()).await?.items);
+
+    // Endpoints already in use, with the link that uses them
+    let mut in_use: HashMap<(Uuid, String), String> = HashMap::new();
+    for link in datastore.list_links(&QueryOptions::default()).await?.items {
+        let label = format!("existing link '{}'", link.name);
+        if let (Some(node_id), Some(interface)) = (link.dest_node_id, link.node_z_interface) {
+            in_use.insert((node_id, interface), label.clone());
+        }
+        in_use.insert((link.source_node_id, link.node_a_interface), label);
+    }
+
+    let mut report = MatrixImportReport {
+        dry_run: false,
+        links: Vec::new(),
+        errors: Vec::new(),
+        created: 0,
+    };
+    for (line, row) in rows {
+        match plan_row(*line, row, &nodes, &mut in_use) {
+            Ok(link) => {
+                if !skip_interface_check {
+                    if let Err(e) = verify_link_endpoints(datastore, &link).await {
+                        report.errors.push(RowError {
+                            line: *line,
+                            message: format!("{e} (use --skip-interface-check to override)"),
+                        });
+                        continue;
+                    }
+                }
+                report.links.push(PlannedLink { line: *line, link });
+            }
+            Err(message) => report.errors.push(RowError {
+                line: *line,
+                message,
+            }),
+        }
+    }
+    Ok(report)
+}
+
+fn plan_row(
+    line: usize,
+    row: &AdjacencyRow,
+    nodes: &NodeIndex,
+    in_use: &mut HashMap<(Uuid, String), String>,
+) -> Result<Link, String> {
+    let (a_id, a_name) = nodes.resolve(&row.node_a)?;
+    let (z_id, z_name) = nodes.resolve(&row.node_z)?;
+    if a_id == z_id && row.intf_a == row.intf_z {
+        return Err(format!("Both ends are {}:{}", row.node_a, row.intf_a));
+    }
+
+    let mut builder = LinkBuilder::new()
+        .name(format!("{a_name}:{}-{z_name}:{}", row.intf_a, row.intf_z))
+        .source_node_id(a_id)
+        .node_a_interface(row.intf_a.clone())
+        .dest_node_id(z_id)
+        .node_z_interface(row.intf_z.clone());
+    if let Some(bandwidth) = row.bandwidth {
+        builder = builder.bandwidth(bandwidth);
+    }
+    let link = builder.build()?;
+
+    for (node_id, node, interface) in [
+        (a_id, &row.node_a, &row.intf_a),
+        (z_id, &row.node_z, &row.intf_z),
+    ] {
+        if let Some(owner) = in_use.get(&(node_id, interface.clone())) {
+            return Err(format!("{node}:{interface} is already used by {owner}"));
+        }
+    }
+    for (node_id, interface) in [(a_id, &row.intf_a), (z_id, &row.intf_z)] {
+        in_use.insert((node_id, interface.clone()), format!("line {line}"));
+    }
+    Ok(link)
+}
+
+/// Looks up nodes by name, FQDN, or ID
+struct NodeIndex {
+    by_key: HashMap<String, Vec<(Uuid, String)>>,
+}
+
+impl NodeIndex {
+    fn new(nodes: Vec<Node>) -> Self {
+        let mut by_key: HashMap<String, Vec<(Uuid, String)>> = HashMap::new();
+        for node in nodes {
+            let entry = (node.id, node.name.clone());
+            by_key
+                .entry(node.id.to_string())
+                .or_default()
+                .push(entry.clone());
+            if node.fqdn != node.name {
+                by_key.entry(node.fqdn).or_default().push(entry.clone());
+            }
+            by_key.entry(node.name).or_default().push(entry);
+        }
+        Self { by_key }
+    }
+
+    fn resolve(&self, reference: &str) -> Result<(Uuid, String), String> {
+        let key = reference
+            .parse::<Uuid>()
+            .map_or_else(|_| reference.to_string(), |id| id.to_string());
+        match self.by_key.get(&key).map(Vec::as_slice) {
+            Some([node]) => Ok(node.clone()),
+            Some(_) => Err(format!(
+                "Node name '{reference}' is ambiguous; use the FQDN or ID"
+            )),
+            None => Err(format!("Node '{reference}' not found")),
+        }
+    }
+}
@@ -0,0 +1,160 @@
+/// Adjacency list parsing for `links import-matrix`
+use anyhow::{Context, Result, anyhow};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Columns of an adjacency list, in CSV order
+const COLUMNS: [&str; 5] = ["node_a", "intf_a", "node_z", "intf_z", "bandwidth"];
+
+/// One link in an adjacency list
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AdjacencyRow {
+    /// Node name, FQDN, or ID on the A side
+    pub node_a: String,
+    /// Interface on the A side
+    pub intf_a: String,
+    /// Node name, FQDN, or ID on the Z side
+    pub node_z: String,
+    /// Interface on the Z side
+    pub intf_z: String,
+    /// Bandwidth in bits per second
+    #[serde(default)]
+    pub bandwidth: Option<u64>,
+}
+
+/// Reads an adjacency list, choosing the format from the file extension
+///
+/// `.csv` files are parsed as CSV; `.yaml`, `.yml`, and `.json` files as a
+/// list of objects with the same field names. Returns each row with its
+/// 1-based line (CSV) or item (YAML/JSON) number.
+pub fn load(path: &Path) -> Result<Vec<(usize, AdjacencyRow)>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase);
+    match extension.as_deref() {
+        Some("csv") => parse_csv(&content),
+        Some("yaml" | "yml" | "json") => {
+            let rows: Vec<AdjacencyRow> = serde_yaml::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", path.display()))?;
+            Ok((1..).zip(rows).collect())
+        }
+        _ => Err(anyhow!(
+            "Unsupported adjacency file '{}' (expected .csv, .yaml, .yml, or .json)",
+            path.display()
+        )),
+    }
+}
+
+/// Parses CSV rows of `node_a,intf_a,node_z,intf_z[,bandwidth]`
+///
+/// Blank lines and lines starting with `#` are ignored. A first row that
+/// names the columns is treated as a header. Fields may be wrapped in double
+/// quotes but cannot contain commas.
+pub fn parse_csv(content: &str) -> Result<Vec<(usize, AdjacencyRow)>> {
+    let mut rows = Vec::new();
+    for (line, text) in (1..).zip(content.lines()) {
+        let text = text.trim();
+        if text.is_empty() || text.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = text
+            .split(',')
+            .map(|field| field.trim().trim_matches('"').trim())
+            .collect();
+        if rows.is_empty() && is_header(&fields) {
+            continue;
+        }
+        if !(4..=5).contains(&fields.len()) {
+            return Err(anyhow!(
+                "Line {line}: expected 4 or 5 fields ({}), found {}",
+                COLUMNS.join(","),
+                fields.len()
+            ));
+        }
+        if let Some(column) = fields[..4].iter().position(|field| field.is_empty()) {
+            return Err(anyhow!("Line {line}: {} is empty", COLUMNS[column]));
+        }
+        let bandwidth = match fields.get(4) {
+            Some(value) if !value.is_empty() => Some(value.parse().map_err(|_| {
+                anyhow!("Line {line}: bandwidth '{value}' is not a number of bits per second")
+            })?),
+            _ => None,
+        };
+        rows.push((
+            line,
+            AdjacencyRow {
+                node_a: fields[0].to_string(),
+                intf_a: fields[1].to_string(),
+                node_z: fields[2].to_string(),
+                intf_z: fields[3].to_string(),
+                bandwidth,
+            },
+        ));
+    }
+    Ok(rows)
+}
+
+fn is_header(fields: &[&str]) -> bool {
+    fields
+        .iter()
+        .zip(COLUMNS)
+        .all(|(field, column)| field.eq_ignore_ascii_case(column))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_parse_csv_with_header_comments_and_quotes() {
+        let content = "node_a,intf_a,node_z,intf_z,bandwidth\n\
+                       # core links\n\
+                       \n\
+                       core1,Gi0/1,core2,Gi0/1,10000000000\n\
+                       \"edge1\", Gi0/2 ,core1,Gi0/3,\n";
+
+        let rows = parse_csv(content).unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].0, 4);
+        assert_eq!(rows[0].1.bandwidth, Some(10_000_000_000));
+        assert_eq!(rows[1].1.node_a, "edge1");
+        assert_eq!(rows[1].1.intf_a, "Gi0/2");
+        assert_eq!(rows[1].1.bandwidth, None);
+    }
+
+    #[test]
+    fn test_parse_csv_reports_line_numbers() {
+        let short = parse_csv("core1,Gi0/1,core2\n").unwrap_err();
+        assert!(short.to_string().contains("Line 1: expected 4 or 5 fields"));
+
+        let empty = parse_csv("core1,Gi0/1,core2,Gi0/1\ncore1,,core3,Gi0/1\n").unwrap_err();
+        assert!(empty.to_string().contains("Line 2: intf_a is empty"));
+
+        let bandwidth = parse_csv("core1,Gi0/1,core2,Gi0/1,10G\n").unwrap_err();
+        assert!(bandwidth.to_string().contains("bandwidth '10G'"));
+    }
+
+    #[test]
+    fn test_load_yaml_and_rejects_unknown_extension() {
+        let mut yaml = tempfile::Builder::new().suffix(".yaml").tempfile().unwrap();
+        writeln!(
+            yaml,
+            "- node_a: core1\n  intf_a: Gi0/1\n  node_z: core2\n  intf_z: Gi0/1\n  bandwidth: 1000"
+        )
+        .unwrap();
+
+        let rows = load(yaml.path()).unwrap();
+        assert_eq!(rows[0].0, 1);
+        assert_eq!(rows[0].1.node_z, "core2");
+
+        let other = NamedTempFile::new().unwrap();
+        assert!(load(other.path()).is_err());
+    }
+}
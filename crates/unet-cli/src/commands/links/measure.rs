@@ -0,0 +1,178 @@
+/// Link latency, jitter, and loss measurement commands
+use anyhow::Result;
+use serde::Serialize;
+use unet_core::config::Config;
+use unet_core::datastore::DataStore;
+use unet_core::measurement::{
+    LinkMeasurement, LinkThresholds, SnmpProber, get_thresholds, measure_link, measurement_history,
+    set_thresholds,
+};
+
+use super::types::{LinkMeasurementsArgs, LinkThresholdsArgs, MeasureLinkArgs};
+
+/// Recorded measurements of a link with the thresholds that apply to it
+#[derive(Debug, Serialize)]
+struct MeasurementReport {
+    link_id: String,
+    thresholds: LinkThresholds,
+    /// Oldest first
+    measurements: Vec<LinkMeasurement>,
+}
+
+/// Probes a link's endpoints and fails if the measurement breaches a threshold
+///
+/// # Errors
+/// Returns an error if the link cannot be measured or a threshold is breached.
+pub async fn measure(
+    args: &MeasureLinkArgs,
+    datastore: &dyn DataStore,
+    config: &Config,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    let link = datastore.get_link_required(&args.id).await?;
+    let mut measurement_config = config.measurement.clone();
+    if let Some(probes) = args.probes {
+        measurement_config.probes = probes;
+    }
+
+    let outcome = measure_link(
+        datastore,
+        &SnmpProber::new(&config.snmp),
+        &link,
+        &measurement_config,
+        config.snmp.address_family,
+    )
+    .await?;
+    crate::commands::print_output(&outcome, output_format)?;
+
+    if outcome.breaches.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "Link {} breached {} threshold(s)",
+            link.name,
+            outcome.breaches.len()
+        ))
+    }
+}
+
+/// Shows a link's measurement history and effective thresholds
+///
+/// # Errors
+/// Returns an error if the link or its measurements cannot be loaded.
+pub async fn show_measurements(
+    args: LinkMeasurementsArgs,
+    datastore: &dyn DataStore,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    let link = datastore.get_link_required(&args.id).await?;
+    let mut measurements = measurement_history(datastore, link.id).await?;
+    if let Some(last) = args.last {
+        measurements.drain(..measurements.len().saturating_sub(last));
+    }
+
+    let report = MeasurementReport {
+        link_id: link.id.to_string(),
+        thresholds: get_thresholds(datastore, link.id).await?,
+        measurements,
+    };
+    crate::commands::print_output(&report, output_format)
+}
+
+/// Stores alarm thresholds for a link or the defaults
+///
+/// # Errors
+/// Returns an error if the link does not exist, a limit is invalid, or the write fails.
+pub async fn set_link_thresholds(
+    args: LinkThresholdsArgs,
+    datastore: &dyn DataStore,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    if let Some(link_id) = args.link {
+        datastore.get_link_required(&link_id).await?;
+    }
+    let thresholds = LinkThresholds {
+        max_latency_ms: args.max_latency_ms,
+        max_jitter_ms: args.max_jitter_ms,
+        max_loss_percent: args.max_loss_percent,
+    };
+    set_thresholds(datastore, args.link, &thresholds).await?;
+    crate::commands::print_output(&thresholds, output_format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockall::predicate::eq;
+    use unet_core::datastore::MockDataStore;
+    use unet_core::models::Link;
+    use uuid::Uuid;
+
+    fn link() -> Link {
+        Link::new(
+            "a-z".into(),
+            Uuid::new_v4(),
+            "Gi0/0".into(),
+            Uuid::new_v4(),
+            "Gi0/1".into(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_set_link_thresholds_for_link() {
+        let link = link();
+        let link_id = link.id;
+        let mut mock = MockDataStore::new();
+        mock.expect_get_link_required()
+            .with(eq(link_id))
+            .returning(move |_| {
+                let link = link.clone();
+                Box::pin(async move { Ok(link) })
+            });
+        mock.expect_put_setting()
+            .withf(move |namespace, key, value| {
+                namespace == "link_thresholds"
+                    && key == link_id.to_string()
+                    && value["max_loss_percent"] == 1.0
+            })
+            .times(1)
+            .returning(|_, _, _| Box::pin(async { Ok(()) }));
+
+        let args = LinkThresholdsArgs {
+            link: Some(link_id),
+            max_latency_ms: None,
+            max_jitter_ms: None,
+            max_loss_percent: Some(1.0),
+        };
+        set_link_thresholds(args, &mock, crate::OutputFormat::Json)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_show_measurements_keeps_newest() {
+        let link = link();
+        let link_id = link.id;
+        let history: Vec<LinkMeasurement> = (0..3)
+            .map(|_| LinkMeasurement::from_endpoints(link_id, Vec::new()))
+            .collect();
+        let mut mock = MockDataStore::new();
+        mock.expect_get_link_required().returning(move |_| {
+            let link = link.clone();
+            Box::pin(async move { Ok(link) })
+        });
+        mock.expect_get_setting().returning(move |namespace, _| {
+            let value =
+                (namespace == "link_measurements").then(|| serde_json::to_value(&history).unwrap());
+            Box::pin(async move { Ok(value) })
+        });
+
+        let args = LinkMeasurementsArgs {
+            id: link_id,
+            last: Some(1),
+        };
+        show_measurements(args, &mock, crate::OutputFormat::Json)
+            .await
+            .unwrap();
+    }
+}
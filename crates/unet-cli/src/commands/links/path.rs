@@ -0,0 +1,134 @@
+/// Path finding between two nodes
+use anyhow::Result;
+use unet_core::datastore::{DataStore, QueryOptions};
+use unet_core::topology::{PathReport, find_path};
+
+use super::types::LinkPathArgs;
+use crate::errors::CommandError;
+
+/// Finds the path between two nodes and prints its links and bottleneck
+///
+/// # Errors
+/// Returns an error if either node cannot be resolved, no links connect
+/// them, the topology cannot be read, or output formatting fails.
+pub async fn find_link_path(
+    args: LinkPathArgs,
+    datastore: &dyn DataStore,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    let from = crate::resolve::node(datastore, &args.from, args.by_id).await?;
+    let to = crate::resolve::node(datastore, &args.to, args.by_id).await?;
+    let options = QueryOptions::default();
+    let nodes = datastore.list_nodes(&options).await?.items;
+    let links = datastore.list_links(&options).await?.items;
+    let report = find_path(&nodes, &links, from, to).ok_or_else(|| {
+        CommandError::not_found(format!("No path between '{}' and '{}'", args.from, args.to))
+    })?;
+
+    match output_format {
+        crate::OutputFormat::Table => print!("{}", render_table(&report)),
+        _ => crate::commands::print_output(&report, output_format)?,
+    }
+    Ok(())
+}
+
+fn render_table(report: &PathReport) -> String {
+    use std::fmt::Write as _;
+
+    let bandwidth =
+        |bps: Option<u64>| bps.map_or_else(|| "unknown".to_string(), |bps| format!("{bps} bps"));
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{} -> {}: {} link(s)",
+        report.from.name,
+        report.to.name,
+        report.hops.len()
+    );
+    for (index, hop) in report.hops.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "{:>3}. {} {} -> {} {} via {} ({})",
+            index + 1,
+            hop.from.name,
+            hop.from_interface,
+            hop.to.name,
+            hop.to_interface,
+            hop.link.name,
+            bandwidth(hop.bandwidth)
+        );
+    }
+    if let Some(link) = &report.bottleneck_link {
+        let _ = writeln!(
+            out,
+            "Bottleneck: {} on {}",
+            bandwidth(report.bottleneck),
+            link.name
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unet_core::datastore::{MockDataStore, types::PagedResult};
+    use unet_core::models::{DeviceRole, Link, Node, Vendor};
+
+    fn node(name: &str) -> Node {
+        Node::new(
+            name.to_string(),
+            "example.com".to_string(),
+            Vendor::Cisco,
+            DeviceRole::Router,
+        )
+    }
+
+    #[test]
+    fn test_render_table_names_bottleneck() {
+        let [a, b, c] = ["core-01", "dist-01", "edge-17"].map(node);
+        let mut uplink = Link::new(
+            "core-dist".to_string(),
+            a.id,
+            "eth0".to_string(),
+            b.id,
+            "eth1".to_string(),
+        );
+        uplink.bandwidth = Some(10_000_000_000);
+        let mut access = Link::new(
+            "dist-edge".to_string(),
+            b.id,
+            "eth2".to_string(),
+            c.id,
+            "eth0".to_string(),
+        );
+        access.bandwidth = Some(1_000_000_000);
+        let report = find_path(&[a.clone(), b, c.clone()], &[uplink, access], a.id, c.id).unwrap();
+
+        let table = render_table(&report);
+        assert!(table.starts_with("core-01 -> edge-17: 2 link(s)"));
+        assert!(table.contains("  2. dist-01 eth2 -> edge-17 eth0 via dist-edge (1000000000 bps)"));
+        assert!(table.contains("Bottleneck: 1000000000 bps on dist-edge"));
+    }
+
+    #[tokio::test]
+    async fn test_find_link_path_without_path_fails() {
+        let [a, b] = ["core-01", "edge-17"].map(node);
+        let args = LinkPathArgs {
+            from: a.id.to_string(),
+            to: b.id.to_string(),
+            by_id: true,
+        };
+        let mut mock = MockDataStore::new();
+        mock.expect_list_nodes().returning(move |_| {
+            let nodes = vec![a.clone(), b.clone()];
+            Box::pin(async move { Ok(PagedResult::new(nodes, 2, None)) })
+        });
+        mock.expect_list_links()
+            .returning(|_| Box::pin(async { Ok(PagedResult::new(Vec::<Link>::new(), 0, None)) }));
+
+        let result = find_link_path(args, &mock, crate::OutputFormat::Json).await;
+
+        assert!(result.unwrap_err().to_string().contains("No path"));
+    }
+}
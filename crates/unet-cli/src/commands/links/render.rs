@@ -0,0 +1,74 @@
+/// Link documentation rendering
+use anyhow::Result;
+use unet_core::datastore::DataStore;
+use unet_core::template::link::{builtin_template, render_link_by_id};
+
+use super::types::RenderLinkArgs;
+
+/// Renders a link document and prints it
+///
+/// `--template` names a built-in template or a `MiniJinja` file to render
+/// instead.
+pub async fn render_link(args: RenderLinkArgs, datastore: &dyn DataStore) -> Result<()> {
+    let id = crate::resolve::link(datastore, &args.id.reference, args.id.by_id).await?;
+    let template = match builtin_template(&args.template) {
+        Some(template) => template.to_string(),
+        None => std::fs::read_to_string(&args.template).map_err(|e| {
+            anyhow::anyhow!(
+                "'{}' is neither a built-in link template nor a readable file: {e}",
+                args.template
+            )
+        })?,
+    };
+    print!("{}", render_link_by_id(datastore, id, &template).await?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resolve::EntityArg;
+    use unet_core::models::{DeviceRole, Link, Node, Vendor};
+
+    #[tokio::test]
+    async fn test_render_link_reads_template_files() {
+        let store = test_support::sqlite::sqlite_store().await;
+        let node = Node::new(
+            "edge-01".to_string(),
+            "example.com".to_string(),
+            Vendor::Cisco,
+            DeviceRole::Router,
+        );
+        store.create_node(&node).await.unwrap();
+        let link = Link::new_internet_circuit(
+            "transit".to_string(),
+            node.id,
+            "GigabitEthernet0/0".to_string(),
+        );
+        store.create_link(&link).await.unwrap();
+
+        let builtin = RenderLinkArgs {
+            id: EntityArg::from(link.id),
+            template: "circuit-doc".to_string(),
+        };
+        assert!(render_link(builtin, &store).await.is_ok());
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("summary.j2");
+        std::fs::write(&path, "{{ link.name }} on {{ a.interface }}\n").unwrap();
+        let file = RenderLinkArgs {
+            id: EntityArg {
+                reference: "transit".to_string(),
+                by_id: false,
+            },
+            template: path.display().to_string(),
+        };
+        assert!(render_link(file, &store).await.is_ok());
+
+        let missing = RenderLinkArgs {
+            id: EntityArg::from(link.id),
+            template: "no-such-template".to_string(),
+        };
+        assert!(render_link(missing, &store).await.is_err());
+    }
+}
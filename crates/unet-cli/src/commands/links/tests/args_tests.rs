@@ -16,6 +16,13 @@ async fn test_add_link_args_creation() {
         bandwidth_bps: Some(1_000_000_000),
         description: Some("Primary link between routers".to_string()),
         custom_data: Some(r#"{"provider": "ISP"}"#.to_string()),
+        provider: None,
+        circuit_id: None,
+        ordered_on: None,
+        delivered_on: None,
+        state: None,
+        skip_interface_check: false,
+        slug: None,
     };
 
     assert_eq!(args.name, "router-a-to-router-b");
@@ -44,6 +51,13 @@ async fn test_add_link_args_minimal() {
         bandwidth_bps: None,
         description: None,
         custom_data: None,
+        provider: None,
+        circuit_id: None,
+        ordered_on: None,
+        delivered_on: None,
+        state: None,
+        skip_interface_check: false,
+        slug: None,
     };
 
     assert_eq!(args.name, "internet-link");
@@ -63,6 +77,11 @@ async fn test_list_link_args_creation() {
     let args = ListLinkArgs {
         node_id: Some(node_id),
         min_bandwidth: Some(100_000_000),
+        provider: None,
+        circuit_id: None,
+        state: None,
+        ordered_after: None,
+        ordered_before: None,
         page: 2,
         per_page: 50,
     };
@@ -78,6 +97,11 @@ async fn test_list_link_args_defaults() {
     let args = ListLinkArgs {
         node_id: None,
         min_bandwidth: None,
+        provider: None,
+        circuit_id: None,
+        state: None,
+        ordered_after: None,
+        ordered_before: None,
         page: 1,
         per_page: 20,
     };
@@ -92,7 +116,7 @@ async fn test_list_link_args_defaults() {
 async fn test_show_link_args_creation() {
     let link_id = Uuid::new_v4();
 
-    let args = ShowLinkArgs { id: link_id };
+    let args = ShowLinkArgs { id: link_id.into() };
 
     assert_eq!(args.id, link_id);
 }
@@ -104,7 +128,7 @@ async fn test_update_link_args_creation() {
     let target_node_id = Uuid::new_v4();
 
     let args = UpdateLinkArgs {
-        id: link_id,
+        id: link_id.into(),
         name: Some("updated-link".to_string()),
         node_a_id: Some(source_node_id),
         node_a_interface: Some("FastEthernet0/1".to_string()),
@@ -113,6 +137,12 @@ async fn test_update_link_args_creation() {
         bandwidth_bps: Some(100_000_000),
         description: Some("Updated description".to_string()),
         custom_data: Some(r#"{"updated": true}"#.to_string()),
+        provider: None,
+        circuit_id: None,
+        ordered_on: None,
+        delivered_on: None,
+        state: None,
+        skip_interface_check: false,
     };
 
     assert_eq!(args.id, link_id);
@@ -131,7 +161,7 @@ async fn test_update_link_args_partial() {
     let link_id = Uuid::new_v4();
 
     let args = UpdateLinkArgs {
-        id: link_id,
+        id: link_id.into(),
         name: Some("partial-update".to_string()),
         node_a_id: None,
         node_a_interface: None,
@@ -140,6 +170,12 @@ async fn test_update_link_args_partial() {
         bandwidth_bps: Some(50_000_000),
         description: None,
         custom_data: None,
+        provider: None,
+        circuit_id: None,
+        ordered_on: None,
+        delivered_on: None,
+        state: None,
+        skip_interface_check: false,
     };
 
     assert_eq!(args.id, link_id);
@@ -158,7 +194,7 @@ async fn test_delete_link_args_creation() {
     let link_id = Uuid::new_v4();
 
     let args = DeleteLinkArgs {
-        id: link_id,
+        id: link_id.into(),
         yes: true,
     };
 
@@ -171,7 +207,7 @@ async fn test_delete_link_args_interactive() {
     let link_id = Uuid::new_v4();
 
     let args = DeleteLinkArgs {
-        id: link_id,
+        id: link_id.into(),
         yes: false,
     };
 
@@ -194,19 +230,31 @@ async fn test_link_command_variants() {
         bandwidth_bps: None,
         description: None,
         custom_data: None,
+        provider: None,
+        circuit_id: None,
+        ordered_on: None,
+        delivered_on: None,
+        state: None,
+        skip_interface_check: false,
+        slug: None,
     };
 
     let list_args = ListLinkArgs {
         node_id: None,
         min_bandwidth: None,
+        provider: None,
+        circuit_id: None,
+        state: None,
+        ordered_after: None,
+        ordered_before: None,
         page: 1,
         per_page: 20,
     };
 
-    let show_args = ShowLinkArgs { id: link_id };
+    let show_args = ShowLinkArgs { id: link_id.into() };
 
     let update_args = UpdateLinkArgs {
-        id: link_id,
+        id: link_id.into(),
         name: None,
         node_a_id: None,
         node_a_interface: None,
@@ -215,10 +263,16 @@ async fn test_link_command_variants() {
         bandwidth_bps: None,
         description: None,
         custom_data: None,
+        provider: None,
+        circuit_id: None,
+        ordered_on: None,
+        delivered_on: None,
+        state: None,
+        skip_interface_check: false,
     };
 
     let delete_args = DeleteLinkArgs {
-        id: link_id,
+        id: link_id.into(),
         yes: false,
     };
 
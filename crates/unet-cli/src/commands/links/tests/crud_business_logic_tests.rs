@@ -21,6 +21,13 @@ async fn test_add_link_args_validation_empty_name() {
         bandwidth_bps: None,
         description: None,
         custom_data: None,
+        provider: None,
+        circuit_id: None,
+        ordered_on: None,
+        delivered_on: None,
+        state: None,
+        skip_interface_check: false,
+        slug: None,
     };
 
     // Test that LinkBuilder would reject empty name
@@ -46,6 +53,13 @@ async fn test_add_link_args_validation_empty_interface() {
         bandwidth_bps: None,
         description: None,
         custom_data: None,
+        provider: None,
+        circuit_id: None,
+        ordered_on: None,
+        delivered_on: None,
+        state: None,
+        skip_interface_check: false,
+        slug: None,
     };
 
     // Test that LinkBuilder would reject empty interface
@@ -71,6 +85,13 @@ async fn test_add_link_args_validation_valid_minimum() {
         bandwidth_bps: None,
         description: None,
         custom_data: None,
+        provider: None,
+        circuit_id: None,
+        ordered_on: None,
+        delivered_on: None,
+        state: None,
+        skip_interface_check: false,
+        slug: None,
     };
 
     // Test that LinkBuilder accepts valid minimum arguments
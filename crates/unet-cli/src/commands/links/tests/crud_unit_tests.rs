@@ -77,6 +77,13 @@ async fn test_link_builder_from_add_args_minimal() {
         bandwidth_bps: None,
         description: None,
         custom_data: None,
+        provider: None,
+        circuit_id: None,
+        ordered_on: None,
+        delivered_on: None,
+        state: None,
+        skip_interface_check: false,
+        slug: None,
     };
 
     // Test the builder pattern used in add_link function
@@ -126,6 +133,13 @@ async fn test_link_builder_from_add_args_full() {
         bandwidth_bps: Some(1_000_000_000),
         description: Some("Full featured test link".to_string()),
         custom_data: Some(custom_data_str.to_string()),
+        provider: None,
+        circuit_id: None,
+        ordered_on: None,
+        delivered_on: None,
+        state: None,
+        skip_interface_check: false,
+        slug: None,
     };
 
     // Test the builder pattern used in add_link function
@@ -213,6 +227,13 @@ async fn test_add_link_args_with_custom_data_parsing() {
         bandwidth_bps: None,
         description: None,
         custom_data: Some(custom_data_str.to_string()),
+        provider: None,
+        circuit_id: None,
+        ordered_on: None,
+        delivered_on: None,
+        state: None,
+        skip_interface_check: false,
+        slug: None,
     };
 
     // Test parsing custom data like add_link function does
@@ -243,6 +264,13 @@ async fn test_add_link_args_invalid_custom_data_parsing() {
         bandwidth_bps: None,
         description: None,
         custom_data: Some(invalid_json.to_string()),
+        provider: None,
+        circuit_id: None,
+        ordered_on: None,
+        delivered_on: None,
+        state: None,
+        skip_interface_check: false,
+        slug: None,
     };
 
     // Test parsing custom data like add_link function does
@@ -262,7 +290,7 @@ async fn test_update_link_args_partial_updates() {
     let link_id = Uuid::new_v4();
 
     let args = UpdateLinkArgs {
-        id: link_id,
+        id: link_id.into(),
         name: Some("updated-name".to_string()),
         node_a_id: None,                    // Not updating
         node_a_interface: None,             // Not updating
@@ -271,6 +299,12 @@ async fn test_update_link_args_partial_updates() {
         bandwidth_bps: Some(5_000_000_000), // Updating
         description: None,                  // Not updating
         custom_data: None,                  // Not updating
+        provider: None,
+        circuit_id: None,
+        ordered_on: None,
+        delivered_on: None,
+        state: None,
+        skip_interface_check: false,
     };
 
     // Verify partial update pattern
@@ -291,7 +325,7 @@ async fn test_update_link_args_json_validation() {
     let valid_json = r#"{"updated": true, "version": 2}"#;
 
     let args = UpdateLinkArgs {
-        id: link_id,
+        id: link_id.into(),
         name: None,
         node_a_id: None,
         node_a_interface: None,
@@ -300,6 +334,12 @@ async fn test_update_link_args_json_validation() {
         bandwidth_bps: None,
         description: None,
         custom_data: Some(valid_json.to_string()),
+        provider: None,
+        circuit_id: None,
+        ordered_on: None,
+        delivered_on: None,
+        state: None,
+        skip_interface_check: false,
     };
 
     // Test JSON validation like update_link would do
@@ -378,6 +418,11 @@ async fn test_list_link_args_default_pagination() {
     let args = ListLinkArgs {
         node_id: None,
         min_bandwidth: None,
+        provider: None,
+        circuit_id: None,
+        state: None,
+        ordered_after: None,
+        ordered_before: None,
         page: 1,
         per_page: 20,
     };
@@ -394,6 +439,11 @@ async fn test_list_link_args_with_filters() {
     let args = ListLinkArgs {
         node_id: Some(filter_node_id),
         min_bandwidth: Some(1_000_000_000), // 1 Gbps minimum
+        provider: None,
+        circuit_id: None,
+        state: None,
+        ordered_after: None,
+        ordered_before: None,
         page: 2,
         per_page: 50,
     };
@@ -411,7 +461,7 @@ async fn test_delete_link_args_with_confirmation() {
     let link_id = Uuid::new_v4();
 
     let args = DeleteLinkArgs {
-        id: link_id,
+        id: link_id.into(),
         yes: true, // Skip confirmation
     };
 
@@ -424,7 +474,7 @@ async fn test_delete_link_args_interactive() {
     let link_id = Uuid::new_v4();
 
     let args = DeleteLinkArgs {
-        id: link_id,
+        id: link_id.into(),
         yes: false, // Require confirmation
     };
 
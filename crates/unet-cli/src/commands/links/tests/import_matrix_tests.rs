@@ -0,0 +1,131 @@
+/// Tests for `links import-matrix`
+use std::io::Write;
+use std::path::PathBuf;
+use tempfile::NamedTempFile;
+use unet_core::datastore::types::{BatchResult, PagedResult};
+use unet_core::datastore::{BatchOperation, MockDataStore, testing::ready_ok};
+use unet_core::models::{DeviceRole, Link, Node, Vendor};
+
+use crate::commands::links::import_matrix::import_matrix;
+use crate::commands::links::types::ImportMatrixArgs;
+
+fn node(name: &str) -> Node {
+    Node::new(
+        name.to_string(),
+        "example.com".to_string(),
+        Vendor::Cisco,
+        DeviceRole::Router,
+    )
+}
+
+fn csv(content: &str) -> NamedTempFile {
+    let mut file = tempfile::Builder::new().suffix(".csv").tempfile().unwrap();
+    file.write_all(content.as_bytes()).unwrap();
+    file
+}
+
+fn args(file: PathBuf, dry_run: bool) -> ImportMatrixArgs {
+    ImportMatrixArgs {
+        file,
+        dry_run,
+        skip_interface_check: false,
+    }
+}
+
+fn mock_with(nodes: Vec<Node>, links: Vec<Link>) -> MockDataStore {
+    let mut mock = MockDataStore::new();
+    let listed = nodes.clone();
+    mock.expect_list_nodes()
+        .returning(move |_| ready_ok(PagedResult::new(listed.clone(), listed.len(), None)));
+    mock.expect_list_links()
+        .returning(move |_| ready_ok(PagedResult::new(links.clone(), links.len(), None)));
+    mock.expect_get_node()
+        .returning(move |id| ready_ok(nodes.iter().find(|node| node.id == *id).cloned()));
+    mock.expect_get_node_interfaces()
+        .returning(|_| ready_ok(Vec::new()));
+    mock
+}
+
+#[tokio::test]
+async fn test_import_matrix_creates_links_in_one_batch() {
+    let (core1, core2, edge1) = (node("core1"), node("core2"), node("edge1"));
+    let (core1_id, edge1_id) = (core1.id, edge1.id);
+    let mut mock = mock_with(vec![core1, core2, edge1], Vec::new());
+    mock.expect_batch_links()
+        .times(1)
+        .withf(move |operations| {
+            matches!(
+                operations,
+                [BatchOperation::Insert(first), BatchOperation::Insert(second)]
+                    if first.bandwidth == Some(10_000_000_000)
+                        && second.source_node_id == edge1_id
+                        && second.dest_node_id == Some(core1_id)
+            )
+        })
+        .returning(|operations| {
+            ready_ok(BatchResult {
+                success_count: operations.len(),
+                error_count: 0,
+                errors: Vec::new(),
+            })
+        });
+    let file = csv("node_a,intf_a,node_z,intf_z,bandwidth\n\
+         core1,Gi0/1,core2.example.com,Gi0/1,10000000000\n\
+         edge1,Gi0/1,core1,Gi0/2\n");
+
+    let result = import_matrix(
+        args(file.path().to_path_buf(), false),
+        &mock,
+        crate::OutputFormat::Json,
+    )
+    .await;
+
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_import_matrix_dry_run_creates_nothing() {
+    let mut mock = mock_with(vec![node("core1"), node("core2")], Vec::new());
+    mock.expect_batch_links().never();
+    let file = csv("core1,Gi0/1,core2,Gi0/1\n");
+
+    let result = import_matrix(
+        args(file.path().to_path_buf(), true),
+        &mock,
+        crate::OutputFormat::Json,
+    )
+    .await;
+
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_import_matrix_rejects_whole_file_on_invalid_rows() {
+    let (core1, core2) = (node("core1"), node("core2"));
+    let existing = Link::new(
+        "core1-core2".into(),
+        core1.id,
+        "Gi0/1".into(),
+        core2.id,
+        "Gi0/1".into(),
+    );
+    let mut mock = mock_with(vec![core1, core2], vec![existing]);
+    mock.expect_batch_links().never();
+    let file = csv("core1,Gi0/2,core2,Gi0/2\n\
+         core1,Gi0/1,core2,Gi0/3\n\
+         core1,Gi0/4,core9,Gi0/1\n\
+         core2,Gi0/2,core1,Gi0/5\n");
+
+    let error = import_matrix(
+        args(file.path().to_path_buf(), false),
+        &mock,
+        crate::OutputFormat::Json,
+    )
+    .await
+    .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "3 of 4 rows are invalid; no links were created"
+    );
+}
@@ -2,6 +2,7 @@
 mod args_tests;
 mod crud_business_logic_tests;
 mod crud_unit_tests;
+mod import_matrix_tests;
 mod json_tests;
 mod query_tests;
 mod validation_tests;
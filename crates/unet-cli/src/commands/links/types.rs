@@ -1,7 +1,12 @@
 /// Link command types and arguments
+use chrono::NaiveDate;
 use clap::{Args, Subcommand};
+use std::path::PathBuf;
+use unet_core::models::ProvisioningState;
 use uuid::Uuid;
 
+use crate::resolve::EntityArg;
+
 #[derive(Subcommand)]
 pub enum LinkCommands {
     /// Add a new link
@@ -14,6 +19,20 @@ pub enum LinkCommands {
     Update(UpdateLinkArgs),
     /// Delete a link
     Delete(DeleteLinkArgs),
+    /// Check all links for dangling or duplicate endpoints
+    Validate,
+    /// Create links in bulk from an adjacency list (CSV, YAML, or JSON)
+    ImportMatrix(ImportMatrixArgs),
+    /// Probe both endpoints now and record latency, jitter, and loss
+    Measure(MeasureLinkArgs),
+    /// Show recorded measurements and alarm thresholds
+    Measurements(LinkMeasurementsArgs),
+    /// Set alarm thresholds for a link, or the defaults for all links
+    Thresholds(LinkThresholdsArgs),
+    /// Render circuit documentation and interface configuration for a link
+    Render(RenderLinkArgs),
+    /// Find the chain of links between two nodes and its bandwidth bottleneck
+    Path(LinkPathArgs),
 }
 
 #[derive(Args)]
@@ -22,6 +41,10 @@ pub struct AddLinkArgs {
     #[arg(short, long)]
     pub name: String,
 
+    /// Slug to use in place of the ID; derived from the name if omitted
+    #[arg(long)]
+    pub slug: Option<String>,
+
     /// First node ID
     #[arg(short = 'a', long)]
     pub node_a_id: Uuid,
@@ -49,6 +72,30 @@ pub struct AddLinkArgs {
     /// Custom data as JSON
     #[arg(short = 'j', long)]
     pub custom_data: Option<String>,
+
+    /// Circuit provider or carrier
+    #[arg(long)]
+    pub provider: Option<String>,
+
+    /// Provider's circuit ID
+    #[arg(long)]
+    pub circuit_id: Option<String>,
+
+    /// Date the circuit was ordered (YYYY-MM-DD)
+    #[arg(long)]
+    pub ordered_on: Option<NaiveDate>,
+
+    /// Date the circuit was delivered (YYYY-MM-DD)
+    #[arg(long)]
+    pub delivered_on: Option<NaiveDate>,
+
+    /// Provisioning state (ordered, delivered, tested, live)
+    #[arg(long)]
+    pub state: Option<ProvisioningState>,
+
+    /// Skip checking interface names against collected interface data
+    #[arg(long)]
+    pub skip_interface_check: bool,
 }
 
 #[derive(Args)]
@@ -61,6 +108,26 @@ pub struct ListLinkArgs {
     #[arg(long)]
     pub min_bandwidth: Option<u64>,
 
+    /// Filter by circuit provider
+    #[arg(long)]
+    pub provider: Option<String>,
+
+    /// Filter by circuit IDs containing this text
+    #[arg(long)]
+    pub circuit_id: Option<String>,
+
+    /// Filter by provisioning state
+    #[arg(long)]
+    pub state: Option<ProvisioningState>,
+
+    /// Only circuits ordered on or after this date (YYYY-MM-DD)
+    #[arg(long)]
+    pub ordered_after: Option<NaiveDate>,
+
+    /// Only circuits ordered on or before this date (YYYY-MM-DD)
+    #[arg(long)]
+    pub ordered_before: Option<NaiveDate>,
+
     /// Page number (1-based)
     #[arg(long, default_value = "1")]
     pub page: u64,
@@ -72,14 +139,14 @@ pub struct ListLinkArgs {
 
 #[derive(Args)]
 pub struct ShowLinkArgs {
-    /// Link ID
-    pub id: Uuid,
+    #[command(flatten)]
+    pub id: EntityArg,
 }
 
 #[derive(Args)]
 pub struct UpdateLinkArgs {
-    /// Link ID
-    pub id: Uuid,
+    #[command(flatten)]
+    pub id: EntityArg,
 
     /// Link name
     #[arg(short, long)]
@@ -112,14 +179,116 @@ pub struct UpdateLinkArgs {
     /// Custom data as JSON
     #[arg(short = 'j', long)]
     pub custom_data: Option<String>,
+
+    /// Circuit provider or carrier
+    #[arg(long)]
+    pub provider: Option<String>,
+
+    /// Provider's circuit ID
+    #[arg(long)]
+    pub circuit_id: Option<String>,
+
+    /// Date the circuit was ordered (YYYY-MM-DD)
+    #[arg(long)]
+    pub ordered_on: Option<NaiveDate>,
+
+    /// Date the circuit was delivered (YYYY-MM-DD)
+    #[arg(long)]
+    pub delivered_on: Option<NaiveDate>,
+
+    /// Provisioning state (ordered, delivered, tested, live)
+    #[arg(long)]
+    pub state: Option<ProvisioningState>,
+
+    /// Skip checking interface names against collected interface data
+    #[arg(long)]
+    pub skip_interface_check: bool,
 }
 
 #[derive(Args)]
 pub struct DeleteLinkArgs {
-    /// Link ID
-    pub id: Uuid,
+    #[command(flatten)]
+    pub id: EntityArg,
 
     /// Skip confirmation prompt
     #[arg(short = 'y', long)]
     pub yes: bool,
 }
+
+#[derive(Args)]
+pub struct ImportMatrixArgs {
+    /// Adjacency file with `node_a,intf_a,node_z,intf_z,bandwidth` rows
+    pub file: PathBuf,
+
+    /// Validate and show the links that would be created without creating them
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Skip checking interface names against collected interface data
+    #[arg(long)]
+    pub skip_interface_check: bool,
+}
+
+#[derive(Args)]
+pub struct MeasureLinkArgs {
+    /// Link ID
+    pub id: Uuid,
+
+    /// Probes per endpoint (defaults to `measurement.probes`)
+    #[arg(long)]
+    pub probes: Option<u32>,
+}
+
+#[derive(Args)]
+pub struct LinkMeasurementsArgs {
+    /// Link ID
+    pub id: Uuid,
+
+    /// Show only the newest measurements
+    #[arg(long)]
+    pub last: Option<usize>,
+}
+
+#[derive(Args)]
+pub struct LinkThresholdsArgs {
+    /// Link ID; omit to set the defaults for links without their own thresholds
+    #[arg(long)]
+    pub link: Option<Uuid>,
+
+    /// Highest acceptable latency in milliseconds
+    #[arg(long)]
+    pub max_latency_ms: Option<f64>,
+
+    /// Highest acceptable jitter in milliseconds
+    #[arg(long)]
+    pub max_jitter_ms: Option<f64>,
+
+    /// Highest acceptable loss in percent
+    #[arg(long)]
+    pub max_loss_percent: Option<f64>,
+}
+
+#[derive(Args)]
+pub struct RenderLinkArgs {
+    #[command(flatten)]
+    pub id: EntityArg,
+
+    /// Built-in template (`circuit-doc`) or a `MiniJinja` template file
+    #[arg(long, default_value = "circuit-doc")]
+    pub template: String,
+}
+
+#[derive(Args)]
+pub struct LinkPathArgs {
+    /// Node the path starts at (slug, name, or ID)
+    #[arg(long, value_name = "NAME|ID")]
+    pub from: String,
+
+    /// Node the path ends at (slug, name, or ID)
+    #[arg(long, value_name = "NAME|ID")]
+    pub to: String,
+
+    /// Only accept IDs; names are not looked up
+    #[arg(long = "id")]
+    pub by_id: bool,
+}
@@ -0,0 +1,89 @@
+/// Topology audit for stored links
+use anyhow::Result;
+use unet_core::datastore::DataStore;
+use unet_core::topology::audit_links;
+
+/// Prints the audit report and fails if any link has issues
+pub async fn validate_links(
+    datastore: &dyn DataStore,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    let report = audit_links(datastore).await?;
+
+    crate::commands::print_output(&report, output_format)?;
+
+    if report.issues.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "Found {} link issues across {} links",
+            report.issues.len(),
+            report.links_checked
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unet_core::datastore::{MockDataStore, types::PagedResult};
+    use unet_core::models::{DeviceRole, Link, Node, Vendor};
+
+    fn mock_with(nodes: Vec<Node>, links: Vec<Link>) -> MockDataStore {
+        let mut mock = MockDataStore::new();
+        mock.expect_list_links().returning(move |_| {
+            let links = links.clone();
+            Box::pin(async move { Ok(PagedResult::new(links.clone(), links.len(), None)) })
+        });
+        mock.expect_list_nodes().returning(move |_| {
+            let nodes = nodes.clone();
+            Box::pin(async move { Ok(PagedResult::new(nodes.clone(), nodes.len(), None)) })
+        });
+        mock.expect_get_node_interfaces()
+            .returning(|_| Box::pin(async { Ok(Vec::new()) }));
+        mock
+    }
+
+    fn node(name: &str) -> Node {
+        Node::new(
+            name.to_string(),
+            "example.com".to_string(),
+            Vendor::Cisco,
+            DeviceRole::Router,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_validate_links_clean_topology() {
+        let (a, z) = (node("a"), node("z"));
+        let link = Link::new("a-z".into(), a.id, "Gi0/0".into(), z.id, "Gi0/0".into());
+
+        let result = validate_links(
+            &mock_with(vec![a, z], vec![link]),
+            crate::OutputFormat::Json,
+        )
+        .await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_validate_links_reports_dangling_and_duplicate() {
+        let (a, z) = (node("a"), node("z"));
+        let first = Link::new("a-z".into(), a.id, "Gi0/0".into(), z.id, "Gi0/0".into());
+        let duplicate = Link::new("a-z-2".into(), a.id, "Gi0/0".into(), z.id, "Gi0/1".into());
+        let dangling =
+            Link::new_internet_circuit("a-isp".into(), uuid::Uuid::new_v4(), "Gi0/2".into());
+
+        let result = validate_links(
+            &mock_with(vec![a, z], vec![first, duplicate, dangling]),
+            crate::OutputFormat::Json,
+        )
+        .await;
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Found 2 link issues")
+        );
+    }
+}
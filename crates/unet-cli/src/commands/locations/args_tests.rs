@@ -14,6 +14,8 @@ async fn test_add_location_args_creation() {
         city: Some("New York".to_string()),
         country: Some("USA".to_string()),
         custom_data: Some(r#"{"zone": "production"}"#.to_string()),
+        site: SiteArgs::default(),
+        slug: None,
     };
 
     assert_eq!(args.name, "datacenter-east");
@@ -35,6 +37,8 @@ async fn test_add_location_args_minimal() {
         city: None,
         country: None,
         custom_data: None,
+        site: SiteArgs::default(),
+        slug: None,
     };
 
     assert_eq!(args.name, "rack-a1");
@@ -81,7 +85,9 @@ async fn test_list_location_args_defaults() {
 #[tokio::test]
 async fn test_show_location_args_creation() {
     let location_id = Uuid::new_v4();
-    let args = ShowLocationArgs { id: location_id };
+    let args = ShowLocationArgs {
+        id: location_id.into(),
+    };
 
     assert_eq!(args.id, location_id);
 }
@@ -92,7 +98,7 @@ async fn test_update_location_args_creation() {
     let parent_id = Uuid::new_v4();
 
     let args = UpdateLocationArgs {
-        id: location_id,
+        id: location_id.into(),
         name: Some("updated-datacenter".to_string()),
         location_type: Some("building".to_string()),
         parent_id: Some(parent_id),
@@ -100,6 +106,7 @@ async fn test_update_location_args_creation() {
         city: Some("Boston".to_string()),
         country: Some("USA".to_string()),
         custom_data: Some(r#"{"environment": "staging"}"#.to_string()),
+        site: SiteArgs::default(),
     };
 
     assert_eq!(args.id, location_id);
@@ -117,7 +124,7 @@ async fn test_update_location_args_partial() {
     let location_id = Uuid::new_v4();
 
     let args = UpdateLocationArgs {
-        id: location_id,
+        id: location_id.into(),
         name: Some("updated-name".to_string()),
         location_type: None,
         parent_id: None,
@@ -125,6 +132,7 @@ async fn test_update_location_args_partial() {
         city: None,
         country: None,
         custom_data: None,
+        site: SiteArgs::default(),
     };
 
     assert_eq!(args.id, location_id);
@@ -142,7 +150,7 @@ async fn test_delete_location_args_creation() {
     let location_id = Uuid::new_v4();
 
     let args = DeleteLocationArgs {
-        id: location_id,
+        id: location_id.into(),
         yes: false,
     };
 
@@ -155,7 +163,7 @@ async fn test_delete_location_args_interactive() {
     let location_id = Uuid::new_v4();
 
     let args = DeleteLocationArgs {
-        id: location_id,
+        id: location_id.into(),
         yes: true,
     };
 
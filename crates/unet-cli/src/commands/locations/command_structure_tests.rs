@@ -15,6 +15,8 @@ async fn test_location_command_variants() {
         city: None,
         country: None,
         custom_data: None,
+        site: SiteArgs::default(),
+        slug: None,
     };
 
     let list_args = ListLocationArgs {
@@ -24,10 +26,12 @@ async fn test_location_command_variants() {
         per_page: 20,
     };
 
-    let show_args = ShowLocationArgs { id: location_id };
+    let show_args = ShowLocationArgs {
+        id: location_id.into(),
+    };
 
     let update_args = UpdateLocationArgs {
-        id: location_id,
+        id: location_id.into(),
         name: None,
         location_type: None,
         parent_id: None,
@@ -35,10 +39,11 @@ async fn test_location_command_variants() {
         city: None,
         country: None,
         custom_data: None,
+        site: SiteArgs::default(),
     };
 
     let delete_args = DeleteLocationArgs {
-        id: location_id,
+        id: location_id.into(),
         yes: false,
     };
 
@@ -112,11 +117,13 @@ async fn test_location_command_id_validation() {
     assert_ne!(valid_uuid, another_uuid);
 
     // Test that command arguments accept valid UUIDs
-    let show_args = ShowLocationArgs { id: valid_uuid };
+    let show_args = ShowLocationArgs {
+        id: valid_uuid.into(),
+    };
     assert_eq!(show_args.id, valid_uuid);
 
     let update_args = UpdateLocationArgs {
-        id: another_uuid,
+        id: another_uuid.into(),
         name: Some("updated-location".to_string()),
         location_type: None,
         parent_id: None,
@@ -124,11 +131,12 @@ async fn test_location_command_id_validation() {
         city: None,
         country: None,
         custom_data: None,
+        site: SiteArgs::default(),
     };
     assert_eq!(update_args.id, another_uuid);
 
     let delete_args = DeleteLocationArgs {
-        id: valid_uuid,
+        id: valid_uuid.into(),
         yes: true,
     };
     assert_eq!(delete_args.id, valid_uuid);
@@ -1,12 +1,32 @@
 /// CRUD operations for location management
 use anyhow::Result;
 use serde_json::Value as JsonValue;
-use unet_core::datastore::DataStore;
+use unet_core::datastore::{DEFAULT_MAX_RETRIES, DataStore, retry_operation};
+use unet_core::models::location::{PostalAddress, parse_timezone};
 use unet_core::prelude::*;
+use unet_core::slug::{SlugKind, assign_slug, check_slug, remove_slugs};
 
 use super::types::{
-    AddLocationArgs, DeleteLocationArgs, ListLocationArgs, ShowLocationArgs, UpdateLocationArgs,
+    AddLocationArgs, DeleteLocationArgs, ListLocationArgs, ShowLocationArgs, SiteArgs,
+    UpdateLocationArgs,
 };
+use crate::confirm::{Confirmation, confirm};
+
+/// Builds the structured address from the address flags, if any are set
+fn postal_address(
+    site: &SiteArgs,
+    city: Option<&String>,
+    country: Option<&String>,
+) -> Option<PostalAddress> {
+    let address = PostalAddress {
+        street: site.street.clone(),
+        city: city.cloned(),
+        region: site.region.clone(),
+        postal_code: site.postal_code.clone(),
+        country: country.cloned(),
+    };
+    (!address.is_empty()).then_some(address)
+}
 
 pub async fn add_location(
     args: AddLocationArgs,
@@ -29,6 +49,16 @@ pub async fn add_location(
         builder = builder.parent_id(parent_id);
     }
 
+    if let Some(address) = postal_address(&args.site, args.city.as_ref(), args.country.as_ref()) {
+        builder = builder.postal_address(address);
+    }
+    if let Some(coordinates) = args.site.coordinates {
+        builder = builder.coordinates(coordinates);
+    }
+    if let Some(timezone) = args.site.timezone {
+        builder = builder.timezone(timezone);
+    }
+
     // Combine address, city, country into a single address field
     let mut address_parts = Vec::new();
     if let Some(address) = args.address {
@@ -51,9 +81,21 @@ pub async fn add_location(
     let location = builder
         .build()
         .map_err(|e| anyhow::anyhow!("Location validation failed: {e}"))?;
+    if let Some(slug) = &args.slug {
+        check_slug(datastore, SlugKind::Location, slug).await?;
+    }
 
     // Create location in datastore
-    let created_location = datastore.create_location(&location).await?;
+    let created_location =
+        retry_operation(DEFAULT_MAX_RETRIES, || datastore.create_location(&location)).await?;
+    assign_slug(
+        datastore,
+        SlugKind::Location,
+        created_location.id,
+        &created_location.name,
+        args.slug.as_deref(),
+    )
+    .await?;
 
     crate::commands::print_output(&created_location, output_format)?;
 
@@ -107,7 +149,8 @@ pub async fn show_location(
     datastore: &dyn DataStore,
     output_format: crate::OutputFormat,
 ) -> Result<()> {
-    let location = datastore.get_location_required(&args.id).await?;
+    let id = crate::resolve::location(datastore, &args.id.reference, args.id.by_id).await?;
+    let location = datastore.get_location_required(&id).await?;
 
     crate::commands::print_output(&location, output_format)?;
 
@@ -119,7 +162,8 @@ pub async fn update_location(
     datastore: &dyn DataStore,
     output_format: crate::OutputFormat,
 ) -> Result<()> {
-    let mut location = datastore.get_location_required(&args.id).await?;
+    let id = crate::resolve::location(datastore, &args.id.reference, args.id.by_id).await?;
+    let mut location = datastore.get_location_required(&id).await?;
 
     // Update fields that were provided
     if let Some(name) = args.name {
@@ -134,6 +178,20 @@ pub async fn update_location(
         location.parent_id = Some(parent_id);
     }
 
+    if let Some(address) = postal_address(&args.site, args.city.as_ref(), args.country.as_ref()) {
+        location
+            .postal_address
+            .get_or_insert_with(PostalAddress::default)
+            .merge(address);
+    }
+    if let Some(coordinates) = args.site.coordinates {
+        location.coordinates = Some(coordinates);
+    }
+    if let Some(timezone) = args.site.timezone {
+        parse_timezone(&timezone).map_err(anyhow::Error::msg)?;
+        location.timezone = Some(timezone);
+    }
+
     // Update address (combining address, city, country like in add command)
     let mut address_parts = Vec::new();
     if let Some(address) = args.address {
@@ -153,7 +211,8 @@ pub async fn update_location(
         location.custom_data = serde_json::from_str(&custom_data_str)?;
     }
 
-    let updated_location = datastore.update_location(&location).await?;
+    let updated_location =
+        retry_operation(DEFAULT_MAX_RETRIES, || datastore.update_location(&location)).await?;
 
     crate::commands::print_output(&updated_location, output_format)?;
 
@@ -166,29 +225,29 @@ pub async fn delete_location(
     output_format: crate::OutputFormat,
 ) -> Result<()> {
     // Check if location exists first
-    let location = datastore.get_location_required(&args.id).await?;
+    let id = crate::resolve::location(datastore, &args.id.reference, args.id.by_id).await?;
+    let location = datastore.get_location_required(&id).await?;
 
     if !args.yes {
-        // Ask for confirmation
-        println!(
-            "Are you sure you want to delete location '{}' (ID: {})? [y/N]",
-            location.name, location.id
-        );
-
-        let mut input = String::new();
-        std::io::stdin().read_line(&mut input)?;
-
-        if !input.trim().to_lowercase().starts_with('y') {
-            println!("Cancelled.");
+        let nodes = datastore.get_nodes_by_location(&location.id).await?;
+        let confirmation = Confirmation::new("Delete location")
+            .affects(format!("{} ({})", location.name, location.id))
+            .also_affects(
+                nodes.len(),
+                "node at this location",
+                "nodes at this location",
+            );
+        if !confirm(false, &confirmation)? {
             return Ok(());
         }
     }
 
-    datastore.delete_location(&args.id).await?;
+    retry_operation(DEFAULT_MAX_RETRIES, || datastore.delete_location(&id)).await?;
+    remove_slugs(datastore, SlugKind::Location, id).await?;
 
     let output = serde_json::json!({
         "message": format!("Location '{}' deleted successfully", location.name),
-        "id": args.id,
+        "id": id,
         "name": location.name
     });
 
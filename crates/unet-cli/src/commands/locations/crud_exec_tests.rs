@@ -34,6 +34,10 @@ mod tests {
             deleted_flag.store(true, std::sync::atomic::Ordering::SeqCst);
             ready_ok(())
         });
+        store.expect_get_setting().returning(|_, _| ready_ok(None));
+        store.expect_put_setting().returning(|_, _, _| ready_ok(()));
+        store.expect_list_settings().returning(|_| ready_ok(Vec::new()));
+        store.expect_delete_setting().returning(|_, _| ready_ok(()));
         store
     }
 
@@ -46,6 +50,9 @@ mod tests {
             path: "loc1".to_string(),
             description: None,
             address: None,
+            postal_address: None,
+            coordinates: None,
+            timezone: None,
             custom_data: serde_json::Value::Null,
         }
     }
@@ -64,17 +71,19 @@ mod tests {
             city: Some("city".to_string()),
             country: Some("cty".to_string()),
             custom_data: Some("{}".to_string()),
+            site: SiteArgs::default(),
+            slug: None,
         };
         assert!(add_location(args, &store, crate::OutputFormat::Json).await.is_ok());
 
         let location = example_location();
         let show_store =
             store_for_location(Some(location.clone()), last_options.clone(), deleted.clone());
-        let show_args = ShowLocationArgs { id: location.id };
+        let show_args = ShowLocationArgs { id: location.id.into() };
         assert!(show_location(show_args, &show_store, crate::OutputFormat::Json).await.is_ok());
 
         let upd_args = UpdateLocationArgs {
-            id: location.id,
+            id: location.id.into(),
             name: Some("L2".to_string()),
             location_type: Some("room".to_string()),
             parent_id: None,
@@ -82,13 +91,14 @@ mod tests {
             city: Some("b".to_string()),
             country: Some("c".to_string()),
             custom_data: Some("{}".to_string()),
+            site: SiteArgs::default(),
         };
         assert!(update_location(upd_args, &show_store, crate::OutputFormat::Json).await.is_ok());
 
         let delete_store =
             store_for_location(Some(location.clone()), last_options.clone(), deleted.clone());
         let del_args = DeleteLocationArgs {
-            id: location.id,
+            id: location.id.into(),
             yes: true,
         };
         assert!(delete_location(del_args, &delete_store, crate::OutputFormat::Json).await.is_ok());
@@ -116,4 +126,70 @@ mod tests {
         assert!(options.pagination.is_some());
         assert_eq!(options.filters.len(), 2);
     }
+
+    #[tokio::test]
+    async fn test_add_location_sets_structured_address_and_timezone() {
+        let mut store = MockDataStore::new();
+        store
+            .expect_create_location()
+            .withf(|location| {
+                let address = location.postal_address.as_ref().expect("postal address");
+                address.street.as_deref() == Some("350 E Cermak Rd")
+                    && address.city.as_deref() == Some("Chicago")
+                    && address.region.is_none()
+                    && location.address.as_deref() == Some("Chicago")
+                    && location.coordinates.is_some()
+                    && location.timezone.as_deref() == Some("America/Chicago")
+            })
+            .returning(|location| ready_ok(location.clone()));
+        store.expect_get_setting().returning(|_, _| ready_ok(None));
+        store.expect_put_setting().returning(|_, _, _| ready_ok(()));
+
+        let args = AddLocationArgs {
+            name: "CHI1".to_string(),
+            location_type: "building".to_string(),
+            parent_id: None,
+            address: None,
+            city: Some("Chicago".to_string()),
+            country: None,
+            custom_data: None,
+            site: SiteArgs {
+                street: Some("350 E Cermak Rd".to_string()),
+                coordinates: Some("41.85,-87.62".parse().expect("coordinates")),
+                timezone: Some("America/Chicago".to_string()),
+                ..SiteArgs::default()
+            },
+            slug: None,
+        };
+        assert!(add_location(args, &store, crate::OutputFormat::Json).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_update_location_rejects_unknown_timezone() {
+        let location = example_location();
+        let mut store = MockDataStore::new();
+        store
+            .expect_get_location()
+            .returning(move |_| ready_ok(Some(location.clone())));
+        store.expect_update_location().never();
+
+        let args = UpdateLocationArgs {
+            id: Uuid::new_v4().into(),
+            name: None,
+            location_type: None,
+            parent_id: None,
+            address: None,
+            city: None,
+            country: None,
+            custom_data: None,
+            site: SiteArgs {
+                timezone: Some("Mars/Olympus_Mons".to_string()),
+                ..SiteArgs::default()
+            },
+        };
+        let error = update_location(args, &store, crate::OutputFormat::Json)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("Unknown timezone"));
+    }
 }
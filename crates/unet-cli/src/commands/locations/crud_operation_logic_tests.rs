@@ -0,0 +1,308 @@
+/// Business logic tests for location list, update, and delete operations
+use serde_json::Value as JsonValue;
+use uuid::Uuid;
+
+use crate::commands::locations::types::*;
+
+// PAGINATION CALCULATION TESTS
+
+#[tokio::test]
+async fn test_pagination_calculation_normal() {
+    let page = 3_u64;
+    let per_page = 25_u64;
+
+    let expected_offset = (page - 1) * per_page; // 2 * 25 = 50
+    let offset_conversion = usize::try_from(expected_offset);
+    let limit_conversion = usize::try_from(per_page);
+
+    assert!(offset_conversion.is_ok());
+    assert!(limit_conversion.is_ok());
+    assert_eq!(offset_conversion.unwrap(), 50);
+    assert_eq!(limit_conversion.unwrap(), 25);
+}
+
+#[tokio::test]
+async fn test_pagination_calculation_first_page() {
+    let page = 1_u64;
+    let per_page = 20_u64;
+
+    let expected_offset = (page - 1) * per_page; // 0 * 20 = 0
+    let offset_conversion = usize::try_from(expected_offset);
+
+    assert!(offset_conversion.is_ok());
+    assert_eq!(offset_conversion.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_pagination_calculation_large_values() {
+    let page = 1000_u64;
+    let per_page = 100_u64;
+
+    let expected_offset = (page - 1) * per_page; // 999 * 100 = 99,900
+    let offset_conversion = usize::try_from(expected_offset);
+    let limit_conversion = usize::try_from(per_page);
+
+    assert!(offset_conversion.is_ok());
+    assert!(limit_conversion.is_ok());
+    assert_eq!(offset_conversion.unwrap(), 99_900);
+    assert_eq!(limit_conversion.unwrap(), 100);
+}
+
+#[tokio::test]
+async fn test_pagination_calculation_overflow_risk() {
+    let page = u64::MAX;
+    let per_page = u64::MAX;
+
+    // This should overflow when calculating offset
+    let overflow_result = page.checked_mul(per_page);
+    assert!(overflow_result.is_none()); // Confirms overflow would occur
+
+    // The real calculation in code: (page - 1) * per_page
+    let safe_page = page - 1; // This is u64::MAX - 1
+    let safe_overflow_result = safe_page.checked_mul(per_page);
+    assert!(safe_overflow_result.is_none()); // Still overflows
+
+    // Converting overflowed values to usize should fail on some platforms
+    let conversion_result = usize::try_from(u64::MAX);
+    // This may or may not fail depending on the platform (32-bit vs 64-bit)
+    // On 64-bit systems, this might succeed, on 32-bit it would fail
+    if conversion_result.is_err() {
+        // Platform where usize is smaller than u64
+        // Test passes because conversion failed as expected
+    } else {
+        // 64-bit platform, but we can still test the overflow scenario
+        // by using a value that definitely overflows usize on any platform
+        // Test passes because we verified overflow potential
+    }
+}
+
+// FILTER AND SORT CONSTRUCTION TESTS
+
+#[tokio::test]
+async fn test_list_locations_filter_construction_by_type() {
+    use unet_core::prelude::{Filter, FilterOperation, FilterValue};
+
+    let location_type = "datacenter".to_string();
+
+    // Test filter construction similar to list_locations function
+    let filter = Filter {
+        field: "location_type".to_owned(),
+        operation: FilterOperation::Equals,
+        value: FilterValue::String(location_type.clone()),
+    };
+
+    assert_eq!(filter.field, "location_type");
+    assert!(matches!(filter.operation, FilterOperation::Equals));
+    match filter.value {
+        FilterValue::String(value) => assert_eq!(value, location_type),
+        _ => panic!("Expected String filter value"),
+    }
+}
+
+#[tokio::test]
+async fn test_list_locations_filter_construction_by_parent() {
+    use unet_core::prelude::{Filter, FilterOperation, FilterValue};
+
+    let parent_id = Uuid::new_v4();
+
+    // Test filter construction similar to list_locations function
+    let filter = Filter {
+        field: "parent_id".to_owned(),
+        operation: FilterOperation::Equals,
+        value: FilterValue::Uuid(parent_id),
+    };
+
+    assert_eq!(filter.field, "parent_id");
+    assert!(matches!(filter.operation, FilterOperation::Equals));
+    match filter.value {
+        FilterValue::Uuid(id) => assert_eq!(id, parent_id),
+        _ => panic!("Expected UUID filter value"),
+    }
+}
+
+#[tokio::test]
+async fn test_list_locations_sort_construction() {
+    use unet_core::prelude::{Sort, SortDirection};
+
+    // Test sort construction similar to list_locations function
+    let sort = Sort {
+        field: "name".to_owned(),
+        direction: SortDirection::Ascending,
+    };
+
+    assert_eq!(sort.field, "name");
+    assert!(matches!(sort.direction, SortDirection::Ascending));
+}
+
+#[tokio::test]
+async fn test_list_locations_query_options_construction() {
+    use unet_core::prelude::{
+        Filter, FilterOperation, FilterValue, Pagination, QueryOptions, Sort, SortDirection,
+    };
+
+    let location_type = "datacenter".to_string();
+    let parent_id = Uuid::new_v4();
+    let page = 2_u64;
+    let per_page = 30_u64;
+
+    // Construct QueryOptions similar to list_locations function
+    let filters = vec![
+        Filter {
+            field: "location_type".to_owned(),
+            operation: FilterOperation::Equals,
+            value: FilterValue::String(location_type),
+        },
+        Filter {
+            field: "parent_id".to_owned(),
+            operation: FilterOperation::Equals,
+            value: FilterValue::Uuid(parent_id),
+        },
+    ];
+
+    let sort = vec![Sort {
+        field: "name".to_owned(),
+        direction: SortDirection::Ascending,
+    }];
+
+    let offset = usize::try_from((page - 1) * per_page).unwrap();
+    let limit = usize::try_from(per_page).unwrap();
+
+    let pagination = Some(Pagination { offset, limit });
+
+    let options = QueryOptions {
+        filters,
+        sort,
+        pagination,
+    };
+
+    // Verify construction
+    assert_eq!(options.filters.len(), 2);
+    assert_eq!(options.sort.len(), 1);
+    assert!(options.pagination.is_some());
+
+    if let Some(ref pagination) = options.pagination {
+        assert_eq!(pagination.offset, 30); // (2-1) * 30 = 30
+        assert_eq!(pagination.limit, 30);
+    }
+}
+
+// UPDATE LOCATION ARGUMENT VALIDATION TESTS
+
+#[tokio::test]
+async fn test_update_location_partial_updates() {
+    let location_id = Uuid::new_v4();
+
+    // Test that individual fields can be updated
+    let args = UpdateLocationArgs {
+        id: location_id.into(),
+        name: Some("updated-name".to_string()),
+        location_type: None,
+        parent_id: None,
+        address: None,
+        city: None,
+        country: None,
+        custom_data: None,
+        site: SiteArgs::default(),
+    };
+
+    // Verify only name field is set for update
+    assert_eq!(args.id, location_id);
+    assert_eq!(args.name, Some("updated-name".to_string()));
+    assert_eq!(args.location_type, None);
+    assert_eq!(args.parent_id, None);
+    assert_eq!(args.address, None);
+    assert_eq!(args.city, None);
+    assert_eq!(args.country, None);
+    assert_eq!(args.custom_data, None);
+}
+
+#[tokio::test]
+async fn test_update_location_address_combination() {
+    let address = Some("456 New Ave".to_string());
+    let city = Some("Boston".to_string());
+    let country = Some("USA".to_string());
+
+    // Test address combination logic from update_location
+    let mut address_parts = Vec::new();
+    if let Some(addr) = address {
+        address_parts.push(addr);
+    }
+    if let Some(c) = city {
+        address_parts.push(c);
+    }
+    if let Some(co) = country {
+        address_parts.push(co);
+    }
+
+    let combined_address = if address_parts.is_empty() {
+        None
+    } else {
+        Some(address_parts.join(", "))
+    };
+
+    assert_eq!(
+        combined_address,
+        Some("456 New Ave, Boston, USA".to_string())
+    );
+}
+
+#[tokio::test]
+async fn test_update_location_custom_data_parsing() {
+    let custom_data_str = r#"{"environment": "staging", "capacity": 75}"#;
+    let result = serde_json::from_str::<JsonValue>(custom_data_str);
+
+    assert!(result.is_ok());
+    let value = result.unwrap();
+    assert_eq!(value["environment"], "staging");
+    assert_eq!(value["capacity"], 75);
+}
+
+// DELETE LOCATION CONFIRMATION TESTS
+
+#[tokio::test]
+async fn test_delete_location_confirmation_logic() {
+    // Test confirmation logic patterns
+    let input_variations = vec![
+        ("y", true),
+        ("Y", true),
+        ("yes", true),
+        ("YES", true),
+        ("Yes", true),
+        ("n", false),
+        ("N", false),
+        ("no", false),
+        ("NO", false),
+        ("No", false),
+        ("", false),
+        ("maybe", false),
+        ("quit", false),
+    ];
+
+    for (input, expected) in input_variations {
+        let should_proceed = input.trim().to_lowercase().starts_with('y');
+        assert_eq!(
+            should_proceed, expected,
+            "Input '{input}' should return {expected}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_delete_location_yes_flag_bypass() {
+    let location_id = Uuid::new_v4();
+
+    let args_with_yes = DeleteLocationArgs {
+        id: location_id.into(),
+        yes: true,
+    };
+
+    let args_without_yes = DeleteLocationArgs {
+        id: location_id.into(),
+        yes: false,
+    };
+
+    // When yes=true, no confirmation should be needed
+    assert!(args_with_yes.yes);
+    // When yes=false, confirmation should be required
+    assert!(!args_without_yes.yes);
+}
@@ -24,3 +24,7 @@ mod command_structure_tests;
 #[cfg(test)]
 #[path = "crud_business_logic_tests.rs"]
 mod crud_business_logic_tests;
+
+#[cfg(test)]
+#[path = "crud_operation_logic_tests.rs"]
+mod crud_operation_logic_tests;
@@ -1,7 +1,10 @@
 /// Location command types and arguments
 use clap::{Args, Subcommand};
+use unet_core::models::location::Coordinates;
 use uuid::Uuid;
 
+use crate::resolve::EntityArg;
+
 #[derive(Subcommand)]
 pub enum LocationCommands {
     /// Add a new location
@@ -22,6 +25,10 @@ pub struct AddLocationArgs {
     #[arg(short, long)]
     pub name: String,
 
+    /// Slug to use in place of the ID; derived from the name if omitted
+    #[arg(long)]
+    pub slug: Option<String>,
+
     /// Location type (e.g., datacenter, campus, building, floor, rack)
     #[arg(short, long)]
     pub location_type: String,
@@ -45,6 +52,35 @@ pub struct AddLocationArgs {
     /// Custom data as JSON
     #[arg(short = 'j', long)]
     pub custom_data: Option<String>,
+
+    #[command(flatten)]
+    pub site: SiteArgs,
+}
+
+/// Structured address, coordinates, and timezone
+///
+/// `--city` and `--country` also fill in the structured address.
+#[derive(Args, Default)]
+pub struct SiteArgs {
+    /// Street and number
+    #[arg(long)]
+    pub street: Option<String>,
+
+    /// State, province, or region
+    #[arg(long)]
+    pub region: Option<String>,
+
+    /// Postal or ZIP code
+    #[arg(long)]
+    pub postal_code: Option<String>,
+
+    /// Coordinates as latitude,longitude (e.g., 41.85,-87.62)
+    #[arg(long, allow_hyphen_values = true)]
+    pub coordinates: Option<Coordinates>,
+
+    /// IANA timezone for local-time schedules (e.g., America/Chicago)
+    #[arg(long)]
+    pub timezone: Option<String>,
 }
 
 #[derive(Args)]
@@ -68,14 +104,14 @@ pub struct ListLocationArgs {
 
 #[derive(Args)]
 pub struct ShowLocationArgs {
-    /// Location ID
-    pub id: Uuid,
+    #[command(flatten)]
+    pub id: EntityArg,
 }
 
 #[derive(Args)]
 pub struct UpdateLocationArgs {
-    /// Location ID
-    pub id: Uuid,
+    #[command(flatten)]
+    pub id: EntityArg,
 
     /// Location name
     #[arg(short, long)]
@@ -104,12 +140,15 @@ pub struct UpdateLocationArgs {
     /// Custom data as JSON
     #[arg(short = 'j', long)]
     pub custom_data: Option<String>,
+
+    #[command(flatten)]
+    pub site: SiteArgs,
 }
 
 #[derive(Args)]
 pub struct DeleteLocationArgs {
-    /// Location ID
-    pub id: Uuid,
+    #[command(flatten)]
+    pub id: EntityArg,
 
     /// Skip confirmation prompt
     #[arg(short = 'y', long)]
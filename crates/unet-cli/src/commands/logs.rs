@@ -0,0 +1,191 @@
+/// Server log commands
+use anyhow::{Result, anyhow};
+use clap::{Args, Subcommand};
+use reqwest::Method;
+use std::fmt::Write;
+use std::time::Duration;
+use unet_core::logging::{LogFilter, LogLevel, LogRecord};
+
+use crate::remote::RemoteClient;
+
+#[derive(Subcommand)]
+pub enum LogCommands {
+    /// Print the server's recent logs and follow new ones as they arrive
+    Tail(TailArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct TailArgs {
+    /// Only records at least this severe (trace, debug, info, warn, error)
+    #[arg(long)]
+    pub level: Option<LogLevel>,
+    /// Only records from this component, a module such as `poller` or
+    /// `webhook_task`, or a full target such as `audit`
+    #[arg(long)]
+    pub component: Option<String>,
+    /// Only records about this node, by ID or name
+    #[arg(long)]
+    pub node: Option<String>,
+    /// Number of buffered records to print before following
+    #[arg(short = 'n', long, default_value_t = 20)]
+    pub lines: usize,
+    /// Print the buffered records and exit instead of following
+    #[arg(long)]
+    pub no_follow: bool,
+    /// Seconds between polls for new records
+    #[arg(long, default_value_t = 2)]
+    pub interval: u64,
+}
+
+/// Execute server log subcommands.
+///
+/// # Errors
+/// Returns an error if no server is given with `--server`, the server cannot
+/// be reached or refuses the request, or output formatting fails.
+pub async fn execute(
+    command: &LogCommands,
+    server_url: Option<&str>,
+    token: Option<&str>,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    match command {
+        LogCommands::Tail(args) => {
+            let server_url = server_url
+                .ok_or_else(|| anyhow!("logs are read from a server; give its URL in --server"))?;
+            let client = RemoteClient::new(server_url, token)?;
+            tail(args, &client, output_format).await
+        }
+    }
+}
+
+/// Prints the last `--lines` matching records, then polls for newer ones
+/// until interrupted
+async fn tail(args: &TailArgs, client: &RemoteClient, output: crate::OutputFormat) -> Result<()> {
+    // At least one record is fetched so following starts after it, even
+    // when none are to be printed
+    let mut filter = LogFilter {
+        after: None,
+        level: args.level,
+        component: args.component.clone(),
+        node: args.node.clone(),
+        limit: Some(args.lines.max(1)),
+    };
+    let records = fetch(client, &filter).await?;
+    for record in &records[records.len().saturating_sub(args.lines)..] {
+        print_record(record, output)?;
+    }
+    if args.no_follow {
+        return Ok(());
+    }
+
+    filter.after = records.last().map(|record| record.seq);
+    filter.limit = None;
+    loop {
+        tokio::time::sleep(Duration::from_secs(args.interval.max(1))).await;
+        let records = fetch(client, &filter).await?;
+        for record in &records {
+            print_record(record, output)?;
+        }
+        if let Some(record) = records.last() {
+            filter.after = Some(record.seq);
+        }
+    }
+}
+
+async fn fetch(client: &RemoteClient, filter: &LogFilter) -> Result<Vec<LogRecord>> {
+    let request = client
+        .request(Method::GET, "/api/v1/admin/logs")
+        .query(filter);
+    client
+        .send(request)
+        .await
+        .map_err(|e| anyhow!("Failed to read server logs: {e}"))
+}
+
+fn print_record(record: &LogRecord, output: crate::OutputFormat) -> Result<()> {
+    match output {
+        crate::OutputFormat::Table => {
+            println!("{}", format_record(record));
+            Ok(())
+        }
+        // One record per line, so the stream can be piped through `jq`
+        crate::OutputFormat::Json => {
+            println!("{}", serde_json::to_string(record)?);
+            Ok(())
+        }
+        crate::OutputFormat::Yaml => crate::commands::print_output(record, output),
+    }
+}
+
+/// A record as one line, with its fields after the message
+fn format_record(record: &LogRecord) -> String {
+    let mut line = format!(
+        "{} {:>5} {}: {}",
+        record
+            .timestamp
+            .to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        record.level,
+        record.target,
+        record.message
+    );
+    for (name, value) in &record.fields {
+        match value {
+            serde_json::Value::String(value) => {
+                let _ = write!(line, " {name}={value}");
+            }
+            other => {
+                let _ = write!(line, " {name}={other}");
+            }
+        }
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    fn tail_args() -> TailArgs {
+        TailArgs {
+            level: Some(LogLevel::Warn),
+            component: Some("poller".to_string()),
+            node: None,
+            lines: 20,
+            no_follow: true,
+            interval: 2,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tail_requires_server() {
+        let command = LogCommands::Tail(tail_args());
+        let result = execute(&command, None, None, crate::OutputFormat::Table).await;
+        assert!(result.unwrap_err().to_string().contains("--server"));
+    }
+
+    #[test]
+    fn test_format_record_puts_fields_after_message() {
+        let record = LogRecord {
+            seq: 7,
+            timestamp: chrono::Utc
+                .with_ymd_and_hms(2026, 1, 2, 3, 4, 5)
+                .single()
+                .unwrap(),
+            level: LogLevel::Warn,
+            target: "unet_core::snmp::poller".to_string(),
+            message: "Poll timed out".to_string(),
+            fields: json!({ "attempts": 3, "node_id": "n1" })
+                .as_object()
+                .cloned()
+                .unwrap(),
+        };
+
+        assert_eq!(
+            format_record(&record),
+            "2026-01-02T03:04:05.000Z  WARN unet_core::snmp::poller: Poll timed out \
+             attempts=3 node_id=n1"
+        );
+    }
+}
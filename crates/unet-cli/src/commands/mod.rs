@@ -1,10 +1,32 @@
+pub mod admin;
+pub mod api_keys;
+pub mod changes;
+pub mod doctor;
+pub mod events;
 pub mod export;
+pub mod export_schedules;
+pub mod golden;
+pub mod groups;
 pub mod import;
 pub mod links;
 pub mod locations;
+pub mod logs;
+pub mod node_defaults;
 pub mod nodes;
+pub mod oid_profiles;
 pub mod policy;
+pub mod polling;
+pub mod reports;
+pub mod secrets;
+pub mod shell;
+pub mod slugs;
+pub mod snmp;
+pub mod templates;
+pub mod topology;
 pub mod vendors;
+pub mod version;
+pub mod vlans;
+pub mod webhooks;
 
 use anyhow::Result;
 
@@ -25,10 +47,55 @@ pub fn format_output<T: serde::Serialize>(data: &T, format: crate::OutputFormat)
 
 /// Print data in the specified format
 ///
+/// JSON and YAML output carries the [`warnings`](crate::warnings) raised
+/// since the last output, if there were any.
+///
 /// # Errors
 /// Returns an error if serialization fails.
 pub fn print_output<T: serde::Serialize>(data: &T, format: crate::OutputFormat) -> Result<()> {
-    let output = format_output(data, format)?;
+    let output = with_warnings(data, crate::warnings::take(), format)?;
     println!("{output}");
     Ok(())
 }
+
+/// Formats data, wrapped with `warnings` for JSON and YAML if there are any
+fn with_warnings<T: serde::Serialize>(
+    data: &T,
+    warnings: Vec<crate::warnings::Warning>,
+    format: crate::OutputFormat,
+) -> Result<String> {
+    if warnings.is_empty() || matches!(format, crate::OutputFormat::Table) {
+        return format_output(data, format);
+    }
+    format_output(&crate::warnings::Envelope { data, warnings }, format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::warnings::Warning;
+
+    #[test]
+    fn test_warnings_wrap_json_and_yaml_output() {
+        let data = serde_json::json!({ "name": "edge-01" });
+        let warnings = vec![Warning {
+            message: "CLI and server differ".to_string(),
+        }];
+
+        let json = with_warnings(&data, warnings.clone(), crate::OutputFormat::Json).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(json["data"], data);
+        assert_eq!(json["warnings"][0]["message"], "CLI and server differ");
+
+        let yaml = with_warnings(&data, warnings.clone(), crate::OutputFormat::Yaml).unwrap();
+        assert!(yaml.contains("warnings:"));
+
+        let table = with_warnings(&data, warnings, crate::OutputFormat::Table).unwrap();
+        assert!(!table.contains("warnings"));
+        let plain = with_warnings(&data, Vec::new(), crate::OutputFormat::Json).unwrap();
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&plain).unwrap(),
+            data
+        );
+    }
+}
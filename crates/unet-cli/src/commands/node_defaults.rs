@@ -0,0 +1,117 @@
+/// Default custom data management commands
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use unet_core::datastore::DataStore;
+use unet_core::node_defaults::{
+    CustomDataDefaults, DefaultsScope, delete_defaults, list_defaults, save_defaults,
+};
+
+use crate::confirm::{Confirmation, confirm};
+
+#[derive(Subcommand)]
+pub enum NodeDefaultsCommands {
+    /// List registered custom data defaults
+    List,
+    /// Register the custom data defaults for a role or vendor
+    Set(SetDefaultsArgs),
+    /// Remove the custom data defaults for a role or vendor
+    Delete(DeleteDefaultsArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct SetDefaultsArgs {
+    /// Defaults scope (role, vendor)
+    pub scope: DefaultsScope,
+    /// Role or vendor name
+    pub target: String,
+    /// Defaults as a JSON object, e.g. '{"bgp": {"asn": null}}'
+    pub data: String,
+}
+
+#[derive(Args, Debug)]
+pub struct DeleteDefaultsArgs {
+    /// Defaults scope (role, vendor)
+    pub scope: DefaultsScope,
+    /// Role or vendor name
+    pub target: String,
+    /// Skip confirmation prompt
+    #[arg(short = 'y', long)]
+    pub yes: bool,
+}
+
+/// Execute custom data defaults subcommands.
+///
+/// # Errors
+/// Returns an error if the defaults are invalid, datastore operations fail, or output formatting fails.
+pub async fn execute(
+    command: NodeDefaultsCommands,
+    datastore: &dyn DataStore,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    match command {
+        NodeDefaultsCommands::List => {
+            crate::commands::print_output(&list_defaults(datastore).await?, output_format)
+        }
+        NodeDefaultsCommands::Set(args) => {
+            let data = serde_json::from_str(&args.data)?;
+            let defaults = CustomDataDefaults::new(args.scope, &args.target, data)?;
+            save_defaults(datastore, &defaults).await?;
+            crate::commands::print_output(&defaults, output_format)
+        }
+        NodeDefaultsCommands::Delete(args) => {
+            let confirmation = Confirmation::new("Remove custom data defaults")
+                .affects(format!("{}:{}", args.scope, args.target));
+            if !confirm(args.yes, &confirmation)? {
+                return Ok(());
+            }
+            delete_defaults(datastore, args.scope, &args.target).await?;
+            let output = serde_json::json!({
+                "message": "custom_data defaults removed",
+                "scope": args.scope,
+                "target": args.target,
+            });
+            crate::commands::print_output(&output, output_format)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unet_core::datastore::MockDataStore;
+
+    #[tokio::test]
+    async fn test_set_stores_normalized_defaults() {
+        let mut mock = MockDataStore::new();
+        mock.expect_put_setting()
+            .withf(|namespace, key, value| {
+                namespace == "custom_data_defaults"
+                    && key == "role:router"
+                    && value["data"]["bgp"]["asn"].is_null()
+            })
+            .times(1)
+            .returning(|_, _, _| Box::pin(async { Ok(()) }));
+
+        let command = NodeDefaultsCommands::Set(SetDefaultsArgs {
+            scope: DefaultsScope::Role,
+            target: "Router".to_string(),
+            data: r#"{"bgp": {"asn": null}}"#.to_string(),
+        });
+        let result = execute(command, &mock, crate::OutputFormat::Json).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_set_rejects_non_object_data() {
+        let mut mock = MockDataStore::new();
+        mock.expect_put_setting().never();
+
+        let command = NodeDefaultsCommands::Set(SetDefaultsArgs {
+            scope: DefaultsScope::Vendor,
+            target: "cisco".to_string(),
+            data: "[1, 2]".to_string(),
+        });
+        let result = execute(command, &mock, crate::OutputFormat::Json).await;
+        assert!(result.is_err());
+    }
+}
@@ -1,8 +1,11 @@
 /// Node creation operations
 use anyhow::Result;
 use serde_json::Value as JsonValue;
-use unet_core::datastore::DataStore;
+use unet_core::datastore::{DEFAULT_MAX_RETRIES, DataStore, retry_operation};
+use unet_core::node_defaults::apply_defaults;
 use unet_core::prelude::*;
+use unet_core::provenance::{self, Source};
+use unet_core::slug::{SlugKind, assign_slug, check_slug};
 
 use super::types::AddNodeArgs;
 
@@ -47,23 +50,43 @@ pub async fn add_node(
         builder = builder.location_id(location_id);
     }
 
-    if let Some(management_ip_str) = args.management_ip {
-        let management_ip = management_ip_str
-            .parse()
-            .map_err(|e| anyhow::anyhow!("Invalid management IP '{management_ip_str}': {e}"))?;
-        builder = builder.management_ip(management_ip);
-    }
-
     if let Some(custom_data) = custom_data {
         builder = builder.custom_data(custom_data);
     }
 
-    let node = builder
+    let mut node = builder
         .build()
         .map_err(|e| anyhow::anyhow!("Node validation failed: {e}"))?;
+    node.set_management_addresses(
+        args.management_ip.as_deref(),
+        args.management_ipv6.as_deref(),
+    )
+    .map_err(|e| anyhow::anyhow!("Invalid management address: {e}"))?;
+    apply_defaults(datastore, &mut node).await?;
+    if let Some(slug) = &args.slug {
+        check_slug(datastore, SlugKind::Node, slug).await?;
+    }
 
     // Create node in datastore
-    let created_node = datastore.create_node(&node).await?;
+    let created_node =
+        retry_operation(DEFAULT_MAX_RETRIES, || datastore.create_node(&node)).await?;
+    assign_slug(
+        datastore,
+        SlugKind::Node,
+        created_node.id,
+        &created_node.name,
+        args.slug.as_deref(),
+    )
+    .await?;
+    provenance::record(
+        datastore,
+        None,
+        &created_node,
+        Source::Manual,
+        None,
+        chrono::Utc::now(),
+    )
+    .await?;
 
     crate::commands::print_output(&created_node, output_format)?;
 
@@ -15,6 +15,15 @@ mod tests {
             *captured_node.lock().expect("lock last_node in create_node") = Some(node.clone());
             ready_ok(node.clone())
         });
+        store.expect_get_setting().returning(|_, key| match key {
+            "role:router" => ready_ok(Some(serde_json::json!({
+                "scope": "role",
+                "target": "router",
+                "data": {"bgp": {"asn": null}, "region": "default"},
+            }))),
+            _ => ready_ok(None),
+        });
+        store.expect_put_setting().returning(|_, _, _| ready_ok(()));
 
         let args = AddNodeArgs {
             name: "edge-1".to_string(),
@@ -25,7 +34,9 @@ mod tests {
             lifecycle: "live".to_string(),
             location_id: Some(uuid::Uuid::new_v4()),
             management_ip: Some("192.0.2.10".to_string()),
+            management_ipv6: None,
             custom_data: Some("{\"region\":\"us-east\"}".to_string()),
+            slug: None,
         };
 
         let result = add_node(args, &store, crate::OutputFormat::Json).await;
@@ -38,7 +49,8 @@ mod tests {
             .expect("node should be saved");
         assert_eq!(saved.name, "edge-1");
         assert_eq!(saved.vendor, unet_core::models::Vendor::Cisco);
-        assert!(saved.custom_data.get("region").is_some());
+        assert_eq!(saved.custom_data["region"], "us-east");
+        assert!(saved.custom_data["bgp"]["asn"].is_null());
     }
 
     #[tokio::test]
@@ -53,7 +65,9 @@ mod tests {
             lifecycle: "planned".to_string(),
             location_id: None,
             management_ip: None,
+            management_ipv6: None,
             custom_data: None,
+            slug: None,
         };
 
         let err = add_node(args, &store, crate::OutputFormat::Json)
@@ -31,6 +31,7 @@ mod integration_tests {
             limit: 10,
             last_hours: None,
             detailed: false,
+            field: None,
         };
 
         // Verify all argument types are properly structured
@@ -138,6 +139,7 @@ mod integration_tests {
             limit: 10,
             last_hours: None,
             detailed: false,
+            field: None,
         };
 
         let history_some = HistoryNodeArgs {
@@ -146,6 +148,7 @@ mod integration_tests {
             limit: 10,
             last_hours: Some(24),
             detailed: false,
+            field: None,
         };
 
         assert_eq!(history_none.last_hours, None);
@@ -179,6 +182,7 @@ mod integration_tests {
                 limit: 10,
                 last_hours: None,
                 detailed,
+                field: None,
             };
 
             assert_eq!(compare_args.diff_only, diff_only);
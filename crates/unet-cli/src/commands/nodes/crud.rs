@@ -29,4 +29,9 @@ mod tests {
     mod crud_business_logic_tests {
         include!("crud_business_logic_tests.rs");
     }
+
+    #[cfg(test)]
+    mod operation_logic_tests {
+        include!("crud/operation_logic_tests.rs");
+    }
 }
@@ -18,7 +18,9 @@ async fn test_add_node_args_creation() {
         lifecycle: "live".to_string(),
         location_id: Some(location_id),
         management_ip: Some("192.168.1.1".to_string()),
+        management_ipv6: None,
         custom_data: Some(r#"{"rack": "A1"}"#.to_string()),
+        slug: None,
     };
 
     assert_eq!(args.name, "test-router");
@@ -43,7 +45,9 @@ async fn test_add_node_args_minimal() {
         lifecycle: "planned".to_string(),
         location_id: None,
         management_ip: None,
+        management_ipv6: None,
         custom_data: None,
+        slug: None,
     };
 
     assert_eq!(args.name, "minimal-node");
@@ -61,10 +65,13 @@ async fn test_add_node_args_minimal() {
 async fn test_list_node_args_creation() {
     let args = ListNodeArgs {
         vendor: Some("cisco".to_string()),
+
+        management_ip: None,
         role: Some("router".to_string()),
         lifecycle: Some("live".to_string()),
         page: 2,
         per_page: 50,
+        fields: None,
     };
 
     assert_eq!(args.vendor, Some("cisco".to_string()));
@@ -78,10 +85,13 @@ async fn test_list_node_args_creation() {
 async fn test_list_node_args_default_pagination() {
     let args = ListNodeArgs {
         vendor: None,
+
+        management_ip: None,
         role: None,
         lifecycle: None,
         page: 1,
         per_page: 20,
+        fields: None,
     };
 
     assert_eq!(args.vendor, None);
@@ -96,10 +106,12 @@ async fn test_show_node_args_creation() {
     let node_id = Uuid::new_v4();
 
     let args = ShowNodeArgs {
-        id: node_id,
+        id: node_id.into(),
         include_status: true,
         show_interfaces: true,
         show_system_info: true,
+        provenance: false,
+        fields: None,
     };
 
     assert_eq!(args.id, node_id);
@@ -113,10 +125,12 @@ async fn test_show_node_args_minimal() {
     let node_id = Uuid::new_v4();
 
     let args = ShowNodeArgs {
-        id: node_id,
+        id: node_id.into(),
         include_status: false,
         show_interfaces: false,
         show_system_info: false,
+        provenance: false,
+        fields: None,
     };
 
     assert_eq!(args.id, node_id);
@@ -131,7 +145,7 @@ async fn test_update_node_args_creation() {
     let location_id = Uuid::new_v4();
 
     let args = UpdateNodeArgs {
-        id: node_id,
+        id: node_id.into(),
         name: Some("updated-router".to_string()),
         domain: Some("new-domain.com".to_string()),
         vendor: Some("juniper".to_string()),
@@ -140,6 +154,7 @@ async fn test_update_node_args_creation() {
         lifecycle: Some("production".to_string()),
         location_id: Some(location_id),
         management_ip: Some("10.0.0.1".to_string()),
+        management_ipv6: None,
         custom_data: Some(r#"{"updated": true}"#.to_string()),
     };
 
@@ -160,7 +175,7 @@ async fn test_update_node_args_partial() {
     let node_id = Uuid::new_v4();
 
     let args = UpdateNodeArgs {
-        id: node_id,
+        id: node_id.into(),
         name: Some("partially-updated".to_string()),
         domain: None,
         vendor: None,
@@ -169,6 +184,7 @@ async fn test_update_node_args_partial() {
         lifecycle: None,
         location_id: None,
         management_ip: None,
+        management_ipv6: None,
         custom_data: None,
     };
 
@@ -189,7 +205,7 @@ async fn test_delete_node_args_creation() {
     let node_id = Uuid::new_v4();
 
     let args = DeleteNodeArgs {
-        id: node_id,
+        id: node_id.into(),
         yes: true,
     };
 
@@ -202,7 +218,7 @@ async fn test_delete_node_args_interactive() {
     let node_id = Uuid::new_v4();
 
     let args = DeleteNodeArgs {
-        id: node_id,
+        id: node_id.into(),
         yes: false,
     };
 
@@ -0,0 +1,450 @@
+/// Business logic tests for node builder, list, update, delete, and show operations
+use serde_json::Value as JsonValue;
+use std::net::IpAddr;
+use uuid::Uuid;
+
+use crate::commands::nodes::types::*;
+use unet_core::models::{DeviceRole, Lifecycle, NodeBuilder, Vendor};
+
+// NODE BUILDER INTEGRATION TESTS
+
+#[tokio::test]
+async fn test_node_builder_with_all_fields() {
+    let location_id = Uuid::new_v4();
+    let management_ip: IpAddr = "192.168.1.1".parse().unwrap();
+    let custom_data = serde_json::json!({
+        "rack": "A1",
+        "port_count": 48,
+        "power_consumption": 125.5
+    });
+
+    let result = NodeBuilder::new()
+        .name("comprehensive-router".to_string())
+        .domain("example.com".to_string())
+        .vendor(Vendor::Cisco)
+        .model("ISR4321".to_string())
+        .role(DeviceRole::Router)
+        .lifecycle(Lifecycle::Live)
+        .location_id(location_id)
+        .management_ip(management_ip)
+        .custom_data(custom_data)
+        .build();
+
+    assert!(result.is_ok());
+    let node = result.unwrap();
+    assert_eq!(node.name, "comprehensive-router");
+    assert_eq!(node.domain, "example.com");
+    assert_eq!(node.vendor, Vendor::Cisco);
+    assert_eq!(node.model, "ISR4321");
+    assert_eq!(node.role, DeviceRole::Router);
+    assert_eq!(node.lifecycle, Lifecycle::Live);
+    assert_eq!(node.location_id, Some(location_id));
+    assert_eq!(node.management_ip, Some(management_ip));
+    assert!(node.custom_data.is_object());
+}
+
+#[tokio::test]
+async fn test_node_builder_minimal_fields() {
+    let result = NodeBuilder::new()
+        .name("minimal-router".to_string())
+        .domain("example.com".to_string())
+        .vendor(Vendor::Cisco)
+        .model("ISR4321".to_string())
+        .role(DeviceRole::Router)
+        .lifecycle(Lifecycle::Planned)
+        .build();
+
+    assert!(result.is_ok());
+    let node = result.unwrap();
+    assert_eq!(node.name, "minimal-router");
+    assert_eq!(node.domain, "example.com");
+    assert_eq!(node.vendor, Vendor::Cisco);
+    assert_eq!(node.model, "ISR4321");
+    assert_eq!(node.role, DeviceRole::Router);
+    assert_eq!(node.lifecycle, Lifecycle::Planned);
+    assert_eq!(node.location_id, None);
+    assert_eq!(node.management_ip, None);
+    assert!(node.custom_data.is_null());
+}
+
+#[tokio::test]
+async fn test_node_builder_validation_failures() {
+    // Test empty name
+    let result = NodeBuilder::new()
+        .name(String::new())
+        .domain("example.com".to_string())
+        .vendor(Vendor::Cisco)
+        .model("ISR4321".to_string())
+        .role(DeviceRole::Router)
+        .lifecycle(Lifecycle::Live)
+        .build();
+    assert!(result.is_err());
+
+    // Test missing vendor
+    let result = NodeBuilder::new()
+        .name("test-router".to_string())
+        .domain("example.com".to_string())
+        .model("ISR4321".to_string())
+        .role(DeviceRole::Router)
+        .lifecycle(Lifecycle::Live)
+        .build();
+    assert!(result.is_err());
+
+    // Test empty model
+    let result = NodeBuilder::new()
+        .name("test-router".to_string())
+        .domain("example.com".to_string())
+        .vendor(Vendor::Cisco)
+        .model(String::new())
+        .role(DeviceRole::Router)
+        .lifecycle(Lifecycle::Live)
+        .build();
+    assert!(result.is_err());
+}
+
+// FILTER AND SORT CONSTRUCTION TESTS
+
+#[tokio::test]
+async fn test_list_nodes_filter_construction_by_lifecycle() {
+    use unet_core::prelude::{Filter, FilterOperation, FilterValue};
+
+    let lifecycle = "live".to_string();
+
+    // Test filter construction similar to list_nodes function
+    let filter = Filter {
+        field: "lifecycle".to_owned(),
+        operation: FilterOperation::Equals,
+        value: FilterValue::String(lifecycle.clone()),
+    };
+
+    assert_eq!(filter.field, "lifecycle");
+    assert!(matches!(filter.operation, FilterOperation::Equals));
+    match filter.value {
+        FilterValue::String(value) => assert_eq!(value, lifecycle),
+        _ => panic!("Expected String filter value"),
+    }
+}
+
+#[tokio::test]
+async fn test_list_nodes_filter_construction_by_role() {
+    use unet_core::prelude::{Filter, FilterOperation, FilterValue};
+
+    let role = "router".to_string();
+
+    // Test filter construction similar to list_nodes function
+    let filter = Filter {
+        field: "role".to_owned(),
+        operation: FilterOperation::Equals,
+        value: FilterValue::String(role.clone()),
+    };
+
+    assert_eq!(filter.field, "role");
+    assert!(matches!(filter.operation, FilterOperation::Equals));
+    match filter.value {
+        FilterValue::String(value) => assert_eq!(value, role),
+        _ => panic!("Expected String filter value"),
+    }
+}
+
+#[tokio::test]
+async fn test_list_nodes_filter_construction_by_vendor() {
+    use unet_core::prelude::{Filter, FilterOperation, FilterValue};
+
+    let vendor = "cisco".to_string();
+
+    // Test filter construction similar to list_nodes function
+    let filter = Filter {
+        field: "vendor".to_owned(),
+        operation: FilterOperation::Equals,
+        value: FilterValue::String(vendor.clone()),
+    };
+
+    assert_eq!(filter.field, "vendor");
+    assert!(matches!(filter.operation, FilterOperation::Equals));
+    match filter.value {
+        FilterValue::String(value) => assert_eq!(value, vendor),
+        _ => panic!("Expected String filter value"),
+    }
+}
+
+#[tokio::test]
+async fn test_list_nodes_sort_construction() {
+    use unet_core::prelude::{Sort, SortDirection};
+
+    // Test sort construction similar to list_nodes function
+    let sort = Sort {
+        field: "name".to_owned(),
+        direction: SortDirection::Ascending,
+    };
+
+    assert_eq!(sort.field, "name");
+    assert!(matches!(sort.direction, SortDirection::Ascending));
+}
+
+#[tokio::test]
+async fn test_list_nodes_query_options_construction() {
+    use unet_core::prelude::{
+        Filter, FilterOperation, FilterValue, Pagination, QueryOptions, Sort, SortDirection,
+    };
+
+    let lifecycle = "live".to_string();
+    let role = "router".to_string();
+    let vendor = "cisco".to_string();
+    let page = 2_u64;
+    let per_page = 30_u64;
+
+    // Construct QueryOptions similar to list_nodes function
+    let filters = vec![
+        Filter {
+            field: "lifecycle".to_owned(),
+            operation: FilterOperation::Equals,
+            value: FilterValue::String(lifecycle),
+        },
+        Filter {
+            field: "role".to_owned(),
+            operation: FilterOperation::Equals,
+            value: FilterValue::String(role),
+        },
+        Filter {
+            field: "vendor".to_owned(),
+            operation: FilterOperation::Equals,
+            value: FilterValue::String(vendor),
+        },
+    ];
+
+    let sort = vec![Sort {
+        field: "name".to_owned(),
+        direction: SortDirection::Ascending,
+    }];
+
+    let offset = usize::try_from((page - 1) * per_page).unwrap();
+    let limit = usize::try_from(per_page).unwrap();
+
+    let pagination = Some(Pagination { offset, limit });
+
+    let options = QueryOptions {
+        filters,
+        sort,
+        pagination,
+    };
+
+    // Verify construction
+    assert_eq!(options.filters.len(), 3);
+    assert_eq!(options.sort.len(), 1);
+    assert!(options.pagination.is_some());
+
+    if let Some(ref pagination) = options.pagination {
+        assert_eq!(pagination.offset, 30); // (2-1) * 30 = 30
+        assert_eq!(pagination.limit, 30);
+    }
+}
+
+// UPDATE NODE ARGUMENT VALIDATION TESTS
+
+#[tokio::test]
+async fn test_update_node_partial_updates() {
+    let node_id = Uuid::new_v4();
+
+    // Test that individual fields can be updated
+    let args = UpdateNodeArgs {
+        id: node_id.into(),
+        name: Some("updated-name".to_string()),
+        domain: None,
+        vendor: None,
+        model: None,
+        role: None,
+        lifecycle: None,
+        location_id: None,
+        management_ip: None,
+        management_ipv6: None,
+        custom_data: None,
+    };
+
+    // Verify only name field is set for update
+    assert_eq!(args.id, node_id);
+    assert_eq!(args.name, Some("updated-name".to_string()));
+    assert_eq!(args.domain, None);
+    assert_eq!(args.vendor, None);
+    assert_eq!(args.model, None);
+    assert_eq!(args.role, None);
+    assert_eq!(args.lifecycle, None);
+    assert_eq!(args.location_id, None);
+    assert_eq!(args.management_ip, None);
+    assert_eq!(args.custom_data, None);
+}
+
+#[tokio::test]
+async fn test_update_node_enum_parsing() {
+    // Test that enum strings are parsed correctly in update operations
+    let vendor_str = "juniper";
+    let role_str = "switch";
+    let lifecycle_str = "decommissioned";
+
+    let vendor_result = vendor_str.parse::<Vendor>();
+    let role_result = role_str.parse::<DeviceRole>();
+    let lifecycle_result = lifecycle_str.parse::<Lifecycle>();
+
+    assert!(vendor_result.is_ok());
+    assert_eq!(vendor_result.unwrap(), Vendor::Juniper);
+
+    assert!(role_result.is_ok());
+    assert_eq!(role_result.unwrap(), DeviceRole::Switch);
+
+    assert!(lifecycle_result.is_ok());
+    assert_eq!(lifecycle_result.unwrap(), Lifecycle::Decommissioned);
+}
+
+#[tokio::test]
+async fn test_update_node_fqdn_calculation() {
+    // Test FQDN calculation logic from update_node
+    let name = "test-router";
+    let domain = "example.com";
+    let expected_fqdn = format!("{name}.{domain}");
+
+    assert_eq!(expected_fqdn, "test-router.example.com");
+}
+
+#[tokio::test]
+async fn test_update_node_custom_data_parsing() {
+    let custom_data_str = r#"{"environment": "production", "rack": "B2"}"#;
+    let result = serde_json::from_str::<JsonValue>(custom_data_str);
+
+    assert!(result.is_ok());
+    let value = result.unwrap();
+    assert_eq!(value["environment"], "production");
+    assert_eq!(value["rack"], "B2");
+}
+
+// DELETE NODE CONFIRMATION TESTS
+
+#[tokio::test]
+async fn test_delete_node_confirmation_logic() {
+    // Test confirmation logic patterns
+    let input_variations = vec![
+        ("y", true),
+        ("Y", true),
+        ("yes", true),
+        ("YES", true),
+        ("Yes", true),
+        ("n", false),
+        ("N", false),
+        ("no", false),
+        ("NO", false),
+        ("No", false),
+        ("", false),
+        ("maybe", false),
+        ("quit", false),
+    ];
+
+    for (input, expected) in input_variations {
+        let input_trimmed = input.trim().to_lowercase();
+        let should_proceed = input_trimmed == "y" || input_trimmed == "yes";
+        assert_eq!(
+            should_proceed, expected,
+            "Input '{input}' should return {expected}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_delete_node_yes_flag_bypass() {
+    let node_id = Uuid::new_v4();
+
+    let args_with_yes = DeleteNodeArgs {
+        id: node_id.into(),
+        yes: true,
+    };
+
+    let args_without_yes = DeleteNodeArgs {
+        id: node_id.into(),
+        yes: false,
+    };
+
+    // When yes=true, no confirmation should be needed
+    assert!(args_with_yes.yes);
+    // When yes=false, confirmation should be required
+    assert!(!args_without_yes.yes);
+}
+
+// SHOW NODE ARGUMENT VALIDATION TESTS
+
+#[tokio::test]
+async fn test_show_node_args_flags() {
+    let node_id = Uuid::new_v4();
+
+    // Test different flag combinations
+    let args_basic = ShowNodeArgs {
+        id: node_id.into(),
+        include_status: false,
+        show_interfaces: false,
+        show_system_info: false,
+        provenance: false,
+        fields: None,
+    };
+
+    let args_all_flags = ShowNodeArgs {
+        id: node_id.into(),
+        include_status: true,
+        show_interfaces: true,
+        show_system_info: true,
+        provenance: false,
+        fields: None,
+    };
+
+    let args_partial_flags = ShowNodeArgs {
+        id: node_id.into(),
+        include_status: true,
+        show_interfaces: false,
+        show_system_info: true,
+        provenance: false,
+        fields: None,
+    };
+
+    // Basic args (no enhanced output)
+    assert_eq!(args_basic.id, node_id);
+    assert!(!args_basic.include_status);
+    assert!(!args_basic.show_interfaces);
+    assert!(!args_basic.show_system_info);
+
+    // All flags enabled
+    assert_eq!(args_all_flags.id, node_id);
+    assert!(args_all_flags.include_status);
+    assert!(args_all_flags.show_interfaces);
+    assert!(args_all_flags.show_system_info);
+
+    // Partial flags
+    assert_eq!(args_partial_flags.id, node_id);
+    assert!(args_partial_flags.include_status);
+    assert!(!args_partial_flags.show_interfaces);
+    assert!(args_partial_flags.show_system_info);
+}
+
+#[tokio::test]
+async fn test_show_node_enhanced_output_check() {
+    let node_id = Uuid::new_v4();
+
+    let args = ShowNodeArgs {
+        id: node_id.into(),
+        include_status: true,
+        show_interfaces: false,
+        show_system_info: true,
+        provenance: false,
+        fields: None,
+    };
+
+    // Test the logic from show_node for determining enhanced output
+    let should_use_enhanced_output = args.include_status || args.show_interfaces || args.show_system_info;
+    assert!(should_use_enhanced_output);
+
+    let args_basic = ShowNodeArgs {
+        id: node_id.into(),
+        include_status: false,
+        show_interfaces: false,
+        show_system_info: false,
+        provenance: false,
+        fields: None,
+    };
+
+    let should_use_basic_output = !(args_basic.include_status || args_basic.show_interfaces || args_basic.show_system_info);
+    assert!(should_use_basic_output);
+}
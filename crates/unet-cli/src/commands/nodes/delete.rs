@@ -1,8 +1,10 @@
 /// Node deletion operations
 use anyhow::Result;
-use unet_core::datastore::DataStore;
+use unet_core::datastore::{DEFAULT_MAX_RETRIES, DataStore, retry_operation};
+use unet_core::slug::{SlugKind, remove_slugs};
 
 use super::types::DeleteNodeArgs;
+use crate::confirm::{Confirmation, confirm};
 
 pub async fn delete_node(
     args: DeleteNodeArgs,
@@ -10,18 +12,21 @@ pub async fn delete_node(
     output_format: crate::OutputFormat,
 ) -> Result<()> {
     // Get node first to show confirmation
-    let node = datastore.get_node_required(&args.id).await?;
+    let id = crate::resolve::node(datastore, &args.id.reference, args.id.by_id).await?;
+    let node = datastore.get_node_required(&id).await?;
 
     if !args.yes {
-        let stdin = std::io::stdin();
-        let mut lock = stdin.lock();
-        let mut reader = std::io::BufReader::new(&mut lock);
-        if !confirm_deletion(false, &node.name, &mut reader)? {
+        let links = datastore.get_links_for_node(&node.id).await?;
+        let confirmation = Confirmation::new("Delete node")
+            .affects(format!("{} ({})", node.name, node.id))
+            .also_affects(links.len(), "link", "links");
+        if !confirm(false, &confirmation)? {
             return Ok(());
         }
     }
 
-    datastore.delete_node(&args.id).await?;
+    retry_operation(DEFAULT_MAX_RETRIES, || datastore.delete_node(&id)).await?;
+    remove_slugs(datastore, SlugKind::Node, id).await?;
 
     let output = serde_json::json!({
         "message": format!("Node '{}' ({}) deleted successfully", node.name, node.id),
@@ -33,23 +38,3 @@ pub async fn delete_node(
 
     Ok(())
 }
-
-// Extracted for testability
-pub fn confirm_deletion(
-    yes: bool,
-    node_name: &str,
-    reader: &mut impl std::io::BufRead,
-) -> Result<bool> {
-    if yes {
-        return Ok(true);
-    }
-    eprintln!("Are you sure you want to delete node '{node_name}'? [y/N]");
-    let mut input = String::new();
-    reader.read_line(&mut input)?;
-    let input = input.trim().to_lowercase();
-    if input != "y" && input != "yes" {
-        eprintln!("Cancelled");
-        return Ok(false);
-    }
-    Ok(true)
-}
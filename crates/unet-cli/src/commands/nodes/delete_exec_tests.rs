@@ -36,9 +36,13 @@ mod tests {
             *deleted_flag.lock().expect("lock deleted flag") = true;
             ready_ok(())
         });
+        store
+            .expect_list_settings()
+            .returning(|_| ready_ok(Vec::new()));
+        store.expect_delete_setting().returning(|_, _| ready_ok(()));
 
         let args = DeleteNodeArgs {
-            id: node.id,
+            id: node.id.into(),
             yes: true,
         };
         let result = delete_node(args, &store, crate::OutputFormat::Json).await;
@@ -46,19 +50,3 @@ mod tests {
         assert!(*deleted.lock().expect("lock deleted flag after delete"));
     }
 }
-
-#[cfg(test)]
-mod confirm_tests {
-    use super::super::delete::confirm_deletion;
-
-    #[test]
-    fn test_confirm_deletion_negative_and_positive() {
-        let mut cur = std::io::Cursor::new(b"n\n".to_vec());
-        let res = confirm_deletion(false, "edge-1", &mut cur).unwrap();
-        assert!(!res);
-
-        let mut cur2 = std::io::Cursor::new(b"yes\n".to_vec());
-        let res2 = confirm_deletion(false, "edge-1", &mut cur2).unwrap();
-        assert!(res2);
-    }
-}
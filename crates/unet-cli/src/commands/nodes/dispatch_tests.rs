@@ -27,6 +27,12 @@ mod tests {
         store
             .expect_create_node()
             .returning(|node| ready_ok(node.clone()));
+        store.expect_get_setting().returning(|_, _| ready_ok(None));
+        store.expect_put_setting().returning(|_, _, _| ready_ok(()));
+        store
+            .expect_list_settings()
+            .returning(|_| ready_ok(Vec::new()));
+        store.expect_delete_setting().returning(|_, _| ready_ok(()));
         store
             .expect_get_node_required()
             .returning(move |_| ready_ok(node_for_get.clone()));
@@ -68,7 +74,9 @@ mod tests {
             lifecycle: "planned".into(),
             location_id: None,
             management_ip: None,
+            management_ipv6: None,
             custom_data: None,
+            slug: None,
         };
         assert!(
             execute(
@@ -84,8 +92,10 @@ mod tests {
             lifecycle: None,
             role: None,
             vendor: None,
+            management_ip: None,
             page: 1,
             per_page: 20,
+            fields: None,
         };
         assert!(
             execute(
@@ -168,7 +178,7 @@ mod tests {
         );
 
         let delete = DeleteNodeArgs {
-            id: node.id,
+            id: node.id.into(),
             yes: true,
         };
         assert!(
@@ -187,6 +197,7 @@ mod tests {
             limit: 10,
             last_hours: None,
             detailed: false,
+            field: None,
         };
         assert!(
             execute(
@@ -204,7 +215,7 @@ mod tests {
         let node = make_node();
         let datastore = store(node.clone());
         let update = UpdateNodeArgs {
-            id: node.id,
+            id: node.id.into(),
             name: Some("edge-1a".into()),
             domain: Some("example.com".into()),
             vendor: Some("cisco".into()),
@@ -213,6 +224,7 @@ mod tests {
             lifecycle: Some("live".into()),
             location_id: None,
             management_ip: Some("192.0.2.1".into()),
+            management_ipv6: None,
             custom_data: Some("{}".into()),
         };
         assert!(
@@ -226,10 +238,12 @@ mod tests {
         );
 
         let show = ShowNodeArgs {
-            id: node.id,
+            id: node.id.into(),
             include_status: false,
             show_interfaces: false,
             show_system_info: false,
+            provenance: false,
+            fields: None,
         };
         assert!(
             execute(
@@ -9,7 +9,7 @@ async fn test_update_node_name_only() {
     let node_id = Uuid::new_v4();
 
     let args = UpdateNodeArgs {
-        id: node_id,
+        id: node_id.into(),
         name: Some("updated-node-name".to_string()),
         domain: None,
         vendor: None,
@@ -18,6 +18,7 @@ async fn test_update_node_name_only() {
         lifecycle: None,
         location_id: None,
         management_ip: None,
+        management_ipv6: None,
         custom_data: None,
     };
 
@@ -33,7 +34,7 @@ async fn test_update_node_domain_with_fqdn_update() {
     let node_id = Uuid::new_v4();
 
     let args = UpdateNodeArgs {
-        id: node_id,
+        id: node_id.into(),
         name: None,
         domain: Some("newdomain.com".to_string()),
         vendor: None,
@@ -42,6 +43,7 @@ async fn test_update_node_domain_with_fqdn_update() {
         lifecycle: None,
         location_id: None,
         management_ip: None,
+        management_ipv6: None,
         custom_data: None,
     };
 
@@ -57,7 +59,7 @@ async fn test_update_node_vendor_valid() {
     let node_id = Uuid::new_v4();
 
     let args = UpdateNodeArgs {
-        id: node_id,
+        id: node_id.into(),
         name: None,
         domain: None,
         vendor: Some("juniper".to_string()),
@@ -66,6 +68,7 @@ async fn test_update_node_vendor_valid() {
         lifecycle: None,
         location_id: None,
         management_ip: None,
+        management_ipv6: None,
         custom_data: None,
     };
 
@@ -82,7 +85,7 @@ async fn test_update_node_model() {
     let node_id = Uuid::new_v4();
 
     let args = UpdateNodeArgs {
-        id: node_id,
+        id: node_id.into(),
         name: None,
         domain: None,
         vendor: None,
@@ -91,6 +94,7 @@ async fn test_update_node_model() {
         lifecycle: None,
         location_id: None,
         management_ip: None,
+        management_ipv6: None,
         custom_data: None,
     };
 
@@ -106,7 +110,7 @@ async fn test_update_node_role_valid() {
     let node_id = Uuid::new_v4();
 
     let args = UpdateNodeArgs {
-        id: node_id,
+        id: node_id.into(),
         name: None,
         domain: None,
         vendor: None,
@@ -115,6 +119,7 @@ async fn test_update_node_role_valid() {
         lifecycle: None,
         location_id: None,
         management_ip: None,
+        management_ipv6: None,
         custom_data: None,
     };
 
@@ -130,7 +135,7 @@ async fn test_update_node_lifecycle_valid() {
     let node_id = Uuid::new_v4();
 
     let args = UpdateNodeArgs {
-        id: node_id,
+        id: node_id.into(),
         name: None,
         domain: None,
         vendor: None,
@@ -139,6 +144,7 @@ async fn test_update_node_lifecycle_valid() {
         lifecycle: Some("decommissioned".to_string()),
         location_id: None,
         management_ip: None,
+        management_ipv6: None,
         custom_data: None,
     };
 
@@ -155,7 +161,7 @@ async fn test_update_node_location_id() {
     let new_location_id = Uuid::new_v4();
 
     let args = UpdateNodeArgs {
-        id: node_id,
+        id: node_id.into(),
         name: None,
         domain: None,
         vendor: None,
@@ -164,6 +170,7 @@ async fn test_update_node_location_id() {
         lifecycle: None,
         location_id: Some(new_location_id),
         management_ip: None,
+        management_ipv6: None,
         custom_data: None,
     };
 
@@ -179,7 +186,7 @@ async fn test_update_node_management_ip_valid() {
     let node_id = Uuid::new_v4();
 
     let args = UpdateNodeArgs {
-        id: node_id,
+        id: node_id.into(),
         name: None,
         domain: None,
         vendor: None,
@@ -188,6 +195,7 @@ async fn test_update_node_management_ip_valid() {
         lifecycle: None,
         location_id: None,
         management_ip: Some("10.0.0.1".to_string()),
+        management_ipv6: None,
         custom_data: None,
     };
 
@@ -203,7 +211,7 @@ async fn test_update_node_custom_data_valid() {
     let node_id = Uuid::new_v4();
 
     let args = UpdateNodeArgs {
-        id: node_id,
+        id: node_id.into(),
         name: None,
         domain: None,
         vendor: None,
@@ -212,6 +220,7 @@ async fn test_update_node_custom_data_valid() {
         lifecycle: None,
         location_id: None,
         management_ip: None,
+        management_ipv6: None,
         custom_data: Some(r#"{"key": "value"}"#.to_string()),
     };
 
@@ -227,7 +236,7 @@ async fn test_update_node_all_fields() {
     let new_location_id = Uuid::new_v4();
 
     let args = UpdateNodeArgs {
-        id: node_id,
+        id: node_id.into(),
         name: Some("all-fields-node".to_string()),
         domain: Some("allfieldsdomain.com".to_string()),
         vendor: Some("juniper".to_string()),
@@ -236,6 +245,7 @@ async fn test_update_node_all_fields() {
         lifecycle: Some("decommissioned".to_string()),
         location_id: Some(new_location_id),
         management_ip: Some("172.16.0.1".to_string()),
+        management_ipv6: None,
         custom_data: Some(r#"{"environment": "test"}"#.to_string()),
     };
 
@@ -0,0 +1,17 @@
+/// Sparse fieldset support for node output
+use anyhow::Result;
+use unet_core::models::{Node, NodeFields};
+
+/// Parses a `--fields` value
+pub fn parse(spec: Option<&str>) -> Result<Option<NodeFields>> {
+    spec.map(|spec| NodeFields::parse(spec, &[]))
+        .transpose()
+        .map_err(anyhow::Error::msg)
+}
+
+/// Serializes `node` with only the selected fields
+pub fn project(node: &Node, fields: &NodeFields) -> Result<serde_json::Value> {
+    let mut value = serde_json::to_value(node)?;
+    fields.project(&mut value);
+    Ok(value)
+}
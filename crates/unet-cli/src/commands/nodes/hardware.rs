@@ -0,0 +1,23 @@
+/// Node hardware inventory operations
+use anyhow::{Result, anyhow};
+use unet_core::datastore::DataStore;
+use unet_core::hardware::get_inventory;
+
+use super::types::HardwareNodeArgs;
+
+pub async fn hardware_node(
+    args: HardwareNodeArgs,
+    datastore: &dyn DataStore,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    let id = crate::resolve::node(datastore, &args.id.reference, args.id.by_id).await?;
+    let mut inventory = get_inventory(datastore, id).await?.ok_or_else(|| {
+        anyhow!("No hardware inventory for node {id}; it is walked while the node is polled")
+    })?;
+    if let Some(kind) = args.kind {
+        inventory
+            .components
+            .retain(|component| component.kind == kind);
+    }
+    crate::commands::print_output(&inventory, output_format)
+}
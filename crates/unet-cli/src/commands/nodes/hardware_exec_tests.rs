@@ -0,0 +1,74 @@
+/// Execution tests for the node hardware command
+#[cfg(test)]
+mod tests {
+    use super::super::hardware::hardware_node;
+    use super::super::types::HardwareNodeArgs;
+    use crate::resolve::EntityArg;
+    use chrono::Utc;
+    use unet_core::datastore::{MockDataStore, testing::ready_ok};
+    use unet_core::hardware::HardwareInventory;
+    use unet_core::models::derived::{HardwareComponent, HardwareKind};
+    use uuid::Uuid;
+
+    fn component(index: u32, kind: HardwareKind) -> HardwareComponent {
+        HardwareComponent {
+            index,
+            kind,
+            name: None,
+            description: None,
+            parent_index: None,
+            serial_number: Some(format!("SN{index}")),
+            part_number: None,
+            manufacturer: None,
+            hardware_revision: None,
+            firmware_revision: None,
+            software_revision: None,
+            field_replaceable: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hardware_node_reads_stored_inventory() {
+        let node_id = Uuid::new_v4();
+        let inventory = HardwareInventory {
+            node_id,
+            collected_at: Utc::now(),
+            components: vec![
+                component(1, HardwareKind::Chassis),
+                component(1000, HardwareKind::Transceiver),
+            ],
+        };
+        let value = serde_json::to_value(&inventory).unwrap();
+        let mut store = MockDataStore::new();
+        store
+            .expect_get_setting()
+            .withf(move |namespace, key| {
+                namespace == "hardware_inventory" && key == node_id.to_string()
+            })
+            .returning(move |_, _| ready_ok(Some(value.clone())));
+
+        let args = HardwareNodeArgs {
+            id: EntityArg::from(node_id),
+            kind: Some(HardwareKind::Transceiver),
+        };
+        hardware_node(args, &store, crate::OutputFormat::Json)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_hardware_node_without_inventory() {
+        let mut store = MockDataStore::new();
+        store.expect_get_setting().returning(|_, _| ready_ok(None));
+
+        let args = HardwareNodeArgs {
+            id: EntityArg::from(Uuid::new_v4()),
+            kind: None,
+        };
+        let error = hardware_node(args, &store, crate::OutputFormat::Json)
+            .await
+            .unwrap_err();
+
+        assert!(error.to_string().contains("No hardware inventory"));
+    }
+}
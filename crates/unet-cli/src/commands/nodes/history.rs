@@ -1,5 +1,7 @@
 /// Node history operations
 use anyhow::Result;
+use chrono::{Duration, Utc};
+use unet_core::change_log::field_history;
 use unet_core::datastore::DataStore;
 
 use super::types::{HistoryNodeArgs, HistoryType};
@@ -12,6 +14,24 @@ pub async fn history_node(
     // Verify node exists first
     let node = datastore.get_node_required(&args.id).await?;
 
+    if let Some(field) = &args.field {
+        let mut changes = field_history(datastore, node.id, field).await?;
+        if let Some(hours) = args.last_hours {
+            let since = Utc::now() - Duration::hours(i64::try_from(hours).unwrap_or(i64::MAX));
+            changes.retain(|change| change.recorded_at >= since);
+        }
+        // Newest first, like the other history views
+        changes.reverse();
+        changes.truncate(args.limit);
+        let output = serde_json::json!({
+            "node_id": node.id,
+            "node_name": node.name,
+            "field": field,
+            "changes": changes,
+        });
+        return crate::commands::print_output(&output, output_format);
+    }
+
     let mut output = serde_json::json!({
         "node_id": args.id,
         "node_name": node.name,
@@ -47,9 +47,70 @@ mod tests {
                 limit: 5,
                 last_hours: Some(24),
                 detailed: true,
+                field: None,
             };
             let result = history_node(args, &store, crate::OutputFormat::Json).await;
             assert!(result.is_ok());
         }
     }
+
+    #[tokio::test]
+    async fn test_history_field_reads_change_log() {
+        use unet_core::change_log::{ChangeEvent, ChangeType, EntityKind};
+
+        let node = make_node();
+        let mut store = store_with_node(node.clone());
+        let touched = ChangeEvent::new(
+            EntityKind::Node,
+            node.id,
+            ChangeType::Updated,
+            serde_json::json!({ "custom_data": { "bgp": { "asn": 65002 } } }),
+        )
+        .with_changed_fields(vec!["custom_data.bgp.asn".to_string()]);
+        let node_id = node.id;
+        // The datastore filters by field, so only the touching event returns
+        store
+            .expect_list_change_events()
+            .withf(move |filter| {
+                filter.entity_id == Some(node_id)
+                    && filter.field.as_deref() == Some("custom_data.bgp")
+            })
+            .returning(move |_| ready_ok(vec![touched.clone()]));
+
+        let args = HistoryNodeArgs {
+            id: node.id,
+            history_type: HistoryType::All,
+            limit: 10,
+            last_hours: Some(1),
+            detailed: false,
+            field: Some("custom_data.bgp".to_string()),
+        };
+        history_node(args, &store, crate::OutputFormat::Json)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_history_field_outside_custom_data() {
+        let node = make_node();
+        let store = store_with_node(node.clone());
+
+        let args = HistoryNodeArgs {
+            id: node.id,
+            history_type: HistoryType::All,
+            limit: 10,
+            last_hours: None,
+            detailed: false,
+            field: Some("vendor".to_string()),
+        };
+        let error = history_node(args, &store, crate::OutputFormat::Json)
+            .await
+            .unwrap_err();
+
+        assert!(
+            error
+                .to_string()
+                .contains("Only custom_data fields are tracked")
+        );
+    }
 }
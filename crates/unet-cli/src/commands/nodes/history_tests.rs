@@ -14,6 +14,7 @@ mod tests {
             limit: 10,
             last_hours: None,
             detailed: false,
+            field: None,
         };
 
         assert_eq!(args.id, node_id);
@@ -42,6 +43,7 @@ mod tests {
             limit: 20,
             last_hours: Some(24),
             detailed: true,
+            field: None,
         };
 
         assert_eq!(args.id, node_id);
@@ -61,6 +63,7 @@ mod tests {
             limit: 500,
             last_hours: Some(336), // 2 weeks
             detailed: true,
+            field: None,
         };
 
         assert_eq!(args.id, node_id);
@@ -88,6 +91,7 @@ mod tests {
             limit: 1_000_000,
             last_hours: Some(8760), // 1 year
             detailed: true,
+            field: None,
         };
 
         assert_eq!(args.limit, 1_000_000);
@@ -140,6 +144,7 @@ mod exec_tests {
                 limit: 5,
                 last_hours: Some(1),
                 detailed: true,
+                field: None,
             };
             let res = history_node(args, &mock, crate::OutputFormat::Json).await;
             assert!(res.is_ok());
@@ -36,6 +36,14 @@ pub async fn list_nodes(
         });
     }
 
+    if let Some(management_ip) = args.management_ip {
+        filters.push(Filter {
+            field: "management_ip".to_owned(),
+            operation: FilterOperation::Equals,
+            value: FilterValue::String(management_ip),
+        });
+    }
+
     let options = QueryOptions {
         filters,
         sort: vec![Sort {
@@ -48,9 +56,28 @@ pub async fn list_nodes(
         }),
     };
 
+    let fields = super::fields::parse(args.fields.as_deref())?;
     let result = datastore.list_nodes(&options).await?;
 
-    crate::commands::print_output(&result, output_format)?;
+    if let Some(fields) = fields {
+        let items = result
+            .items
+            .iter()
+            .map(|node| super::fields::project(node, &fields))
+            .collect::<Result<Vec<_>>>()?;
+        let projected = PagedResult {
+            items,
+            total_count: result.total_count,
+            page_size: result.page_size,
+            page: result.page,
+            total_pages: result.total_pages,
+            has_next: result.has_next,
+            has_previous: result.has_previous,
+        };
+        crate::commands::print_output(&projected, output_format)?;
+    } else {
+        crate::commands::print_output(&result, output_format)?;
+    }
 
     Ok(())
 }
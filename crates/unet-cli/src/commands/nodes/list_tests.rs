@@ -24,8 +24,10 @@ mod tests {
             lifecycle: Some("live".to_string()),
             role: Some("router".to_string()),
             vendor: Some("cisco".to_string()),
+            management_ip: None,
             page: 2,
             per_page: 5,
+            fields: None,
         };
 
         let result = list_nodes(args, &store, crate::OutputFormat::Json).await;
@@ -42,4 +44,27 @@ mod tests {
         assert_eq!(pagination.limit, 5);
         assert_eq!(pagination.offset, 5);
     }
+
+    #[tokio::test]
+    async fn test_list_nodes_rejects_unknown_field_before_querying() {
+        let store = MockDataStore::new();
+        let args = ListNodeArgs {
+            lifecycle: None,
+            role: None,
+            vendor: None,
+            management_ip: None,
+            page: 1,
+            per_page: 20,
+            fields: Some("name,owner".to_string()),
+        };
+
+        let result = list_nodes(args, &store, crate::OutputFormat::Json).await;
+
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Unknown field 'owner'")
+        );
+    }
 }
@@ -5,6 +5,8 @@
 use anyhow::Result;
 use unet_core::datastore::DataStore;
 
+pub use onboard::onboard;
+pub use test_access::test_access;
 pub use types::NodeCommands;
 
 mod add;
@@ -12,11 +14,16 @@ mod advanced;
 mod compare;
 mod crud;
 mod delete;
+pub(crate) mod fields;
+mod hardware;
 mod history;
 mod list;
 mod monitoring;
+pub(crate) mod notes;
+mod onboard;
 mod polling;
 mod show;
+pub(crate) mod test_access;
 pub(crate) mod types;
 mod update;
 
@@ -31,12 +38,18 @@ mod delete_exec_tests;
 #[cfg(test)]
 mod dispatch_tests;
 #[cfg(test)]
+mod hardware_exec_tests;
+#[cfg(test)]
 mod history_tests;
 #[cfg(test)]
 mod list_tests;
 #[cfg(test)]
 mod monitoring_exec_tests;
 #[cfg(test)]
+mod notes_exec_tests;
+#[cfg(test)]
+mod onboard_tests;
+#[cfg(test)]
 mod polling_exec_tests;
 #[cfg(test)]
 mod polling_tests;
@@ -45,6 +58,8 @@ mod show_exec_more_tests;
 #[cfg(test)]
 mod show_tests;
 #[cfg(test)]
+mod test_access_tests;
+#[cfg(test)]
 mod update_exec_tests;
 #[cfg(test)]
 mod update_tests;
@@ -73,5 +88,15 @@ pub async fn execute(
         }
         NodeCommands::Polling(args) => advanced::polling_node(args, datastore, output_format).await,
         NodeCommands::History(args) => advanced::history_node(args, datastore, output_format).await,
+        NodeCommands::Note(command) => notes::note_node(command, datastore, output_format).await,
+        NodeCommands::Hardware(args) => {
+            hardware::hardware_node(args, datastore, output_format).await
+        }
+        NodeCommands::TestAccess(_) => Err(anyhow::anyhow!(
+            "test-access needs the SNMP configuration and runs before other node commands"
+        )),
+        NodeCommands::Onboard(_) => Err(anyhow::anyhow!(
+            "onboard needs the SNMP configuration and runs before other node commands"
+        )),
     }
 }
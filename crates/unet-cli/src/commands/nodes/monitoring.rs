@@ -40,9 +40,7 @@ pub async fn status_node(
                     output["interfaces"] = serde_json::to_value(&interfaces)?;
                 }
                 Err(e) => {
-                    output["interfaces"] = serde_json::json!({
-                        "error": format!("Failed to fetch interfaces: {}", e)
-                    });
+                    crate::warnings::warn(format!("Failed to fetch interfaces: {e}"));
                 }
             },
             StatusType::System => match datastore.get_node_status(&args.id).await {
@@ -55,9 +53,7 @@ pub async fn status_node(
                     });
                 }
                 Err(e) => {
-                    output["system_info"] = serde_json::json!({
-                        "error": format!("Failed to fetch system info: {}", e)
-                    });
+                    crate::warnings::warn(format!("Failed to fetch system info: {e}"));
                 }
             },
             StatusType::Polling => {
@@ -126,9 +122,7 @@ pub async fn metrics_node(
                 });
             }
             Err(e) => {
-                output["error"] = serde_json::json!({
-                    "message": format!("Failed to fetch metrics: {}", e)
-                });
+                crate::warnings::warn(format!("Failed to fetch metrics: {e}"));
             }
         }
     }
@@ -0,0 +1,49 @@
+/// Node note operations
+use anyhow::{Context, Result};
+use unet_core::datastore::DataStore;
+use unet_core::notes::{self, Note, NoteTarget};
+
+use super::types::{AddNoteArgs, ListNoteArgs, NoteCommands};
+
+pub async fn note_node(
+    command: NoteCommands,
+    datastore: &dyn DataStore,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    match command {
+        NoteCommands::Add(args) => add_note(args, datastore, output_format).await,
+        NoteCommands::List(args) => list_notes(args, datastore, output_format).await,
+    }
+}
+
+/// Returns the note text given on the command line or read from `--file`
+pub(crate) fn note_body(args: &AddNoteArgs) -> Result<String> {
+    match (&args.text, &args.file) {
+        (Some(text), _) => Ok(text.clone()),
+        (None, Some(path)) => std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display())),
+        (None, None) => Err(anyhow::anyhow!("Give the note text or --file")),
+    }
+}
+
+async fn add_note(
+    args: AddNoteArgs,
+    datastore: &dyn DataStore,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    let body = note_body(&args)?;
+    let id = crate::resolve::node(datastore, &args.id.reference, args.id.by_id).await?;
+    let note = Note::new(NoteTarget::Node(id), body, args.author)?;
+    notes::add_note(datastore, &note).await?;
+    crate::commands::print_output(&note, output_format)
+}
+
+async fn list_notes(
+    args: ListNoteArgs,
+    datastore: &dyn DataStore,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    let id = crate::resolve::node(datastore, &args.id.reference, args.id.by_id).await?;
+    let notes = notes::list_notes(datastore, NoteTarget::Node(id)).await?;
+    crate::commands::print_output(&notes, output_format)
+}
@@ -0,0 +1,111 @@
+/// Execution tests for node note commands
+#[cfg(test)]
+mod tests {
+    use super::super::notes::{note_body, note_node};
+    use super::super::types::{AddNoteArgs, ListNoteArgs, NoteCommands};
+    use crate::resolve::EntityArg;
+    use std::sync::{Arc, Mutex};
+    use unet_core::datastore::{MockDataStore, testing::ready_ok};
+    use unet_core::models::{DeviceRole, Node, NodeBuilder, Vendor};
+    use unet_core::notes::{Note, NoteTarget};
+    use uuid::Uuid;
+
+    fn make_node() -> Node {
+        NodeBuilder::new()
+            .id(Uuid::new_v4())
+            .name("edge-1")
+            .domain("example.com")
+            .vendor(Vendor::Cisco)
+            .model("ISR4321")
+            .role(DeviceRole::Router)
+            .build()
+            .unwrap()
+    }
+
+    fn add_args(id: Uuid, text: Option<&str>) -> AddNoteArgs {
+        AddNoteArgs {
+            id: EntityArg::from(id),
+            text: text.map(str::to_string),
+            file: None,
+            author: Some("noc".to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_add_note_stores_note_on_node() {
+        let node = make_node();
+        let stored = Arc::new(Mutex::new(Vec::new()));
+        let mut store = MockDataStore::new();
+        let node_for_get = node.clone();
+        store
+            .expect_get_node_required()
+            .returning(move |_| ready_ok(node_for_get.clone()));
+        let stored_for_put = stored.clone();
+        store
+            .expect_put_setting()
+            .returning(move |namespace, _, value| {
+                stored_for_put
+                    .lock()
+                    .unwrap()
+                    .push((namespace.to_string(), value.clone()));
+                ready_ok(())
+            });
+
+        let command = NoteCommands::Add(add_args(node.id, Some("RMA open for PSU 2")));
+        note_node(command, &store, crate::OutputFormat::Json)
+            .await
+            .unwrap();
+
+        let stored = stored.lock().unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].0, "notes");
+        let note: Note = serde_json::from_value(stored[0].1.clone()).unwrap();
+        assert_eq!(note.target, NoteTarget::Node(node.id));
+        assert_eq!(note.body, "RMA open for PSU 2");
+        assert_eq!(note.author.as_deref(), Some("noc"));
+    }
+
+    #[tokio::test]
+    async fn test_add_note_rejects_blank_text() {
+        let node = make_node();
+        let mut store = MockDataStore::new();
+        store.expect_put_setting().never();
+
+        let command = NoteCommands::Add(add_args(node.id, Some("   ")));
+        let result = note_node(command, &store, crate::OutputFormat::Json).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_list_notes_of_node() {
+        let node = make_node();
+        let own = Note::new(NoteTarget::Node(node.id), "Mine".to_string(), None).unwrap();
+        let other = Note::new(NoteTarget::Node(Uuid::new_v4()), "Other".to_string(), None).unwrap();
+        let mut store = MockDataStore::new();
+        store.expect_list_settings().returning(move |_| {
+            ready_ok(vec![
+                (own.id.to_string(), serde_json::to_value(&own).unwrap()),
+                (other.id.to_string(), serde_json::to_value(&other).unwrap()),
+            ])
+        });
+
+        let command = NoteCommands::List(ListNoteArgs {
+            id: EntityArg::from(node.id),
+        });
+        let result = note_node(command, &store, crate::OutputFormat::Json).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_note_body_read_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("note.md");
+        std::fs::write(&path, "# Handover\n\nCircuit 42").unwrap();
+        let mut args = add_args(Uuid::new_v4(), None);
+        args.file = Some(path);
+
+        assert_eq!(note_body(&args).unwrap(), "# Handover\n\nCircuit 42");
+    }
+}
@@ -0,0 +1,227 @@
+/// Node onboarding: adds a node from what a live device reports over SNMP
+use anyhow::{Result, anyhow};
+use serde::Serialize;
+use std::net::SocketAddr;
+use unet_core::config::{Config, SnmpConfig};
+use unet_core::datastore::{DEFAULT_MAX_RETRIES, DataStore, retry_operation};
+use unet_core::models::derived::SoftwareVersion;
+use unet_core::node_defaults::apply_defaults;
+use unet_core::onboarding::{DeviceFacts, match_location_rule, parse_if_table};
+use unet_core::prelude::*;
+use unet_core::provenance::{self, Source};
+use unet_core::slug::{SlugKind, assign_slug, check_slug};
+use unet_core::snmp::{SnmpClient, StandardOid};
+use uuid::Uuid;
+
+use super::test_access::session_config;
+use super::types::OnboardNodeArgs;
+use crate::errors::CommandError;
+
+/// `custom_data` path of the interfaces read from `ifTable`
+const INTERFACES_PATH: &str = "interfaces";
+
+/// The node added and how its fields were found
+#[derive(Debug, Serialize)]
+struct OnboardReport {
+    node: Node,
+    /// `sysLocation` the device reported
+    sys_location: Option<String>,
+    /// Pattern of the location rule that placed the node
+    location_rule: Option<String>,
+    /// Interfaces stored in `custom_data.interfaces`
+    interfaces: usize,
+}
+
+/// Adds a node from what the device at `--address` reports over SNMP
+///
+/// The device is polled for `sysDescr`, `sysName`, `sysLocation`, and
+/// `ifTable` with the community of `--credential-profile`. The vendor, model,
+/// OS, and version come from `sysDescr`, the name and domain from `sysName`,
+/// and the location from the first of `onboarding.location_rules` whose
+/// pattern matches `sysLocation`. Options given on the command line win.
+///
+/// # Errors
+/// Returns an error if the credential profile is unknown, the device does not
+/// answer, the name or model cannot be determined, a node with the same FQDN
+/// exists, the location does not resolve, or the node cannot be created.
+pub async fn onboard(
+    args: &OnboardNodeArgs,
+    datastore: &dyn DataStore,
+    config: &Config,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    let community = config
+        .snmp
+        .profile_community(&args.credential_profile)
+        .ok_or_else(|| {
+            anyhow!(
+                "Unknown credential profile '{}'; define it under [snmp.profiles.{}]",
+                args.credential_profile,
+                args.credential_profile
+            )
+        })?;
+    let facts = poll_facts(SocketAddr::new(args.address, 161), community, &config.snmp).await?;
+
+    let (location_id, location_rule) = match &args.location {
+        Some(location) => (
+            Some(crate::resolve::location(datastore, location, false).await?),
+            None,
+        ),
+        None => match facts.sys_location.as_deref() {
+            Some(sys_location) => {
+                match match_location_rule(&config.onboarding.location_rules, sys_location)? {
+                    Some(rule) => (
+                        Some(crate::resolve::location(datastore, &rule.location, false).await?),
+                        Some(rule.pattern.clone()),
+                    ),
+                    None => (None, None),
+                }
+            }
+            None => (None, None),
+        },
+    };
+
+    let mut node = build_node(
+        args,
+        &facts,
+        location_id,
+        config.domain.default_domain.as_deref(),
+    )?;
+    apply_defaults(datastore, &mut node).await?;
+    if let Some(slug) = &args.slug {
+        check_slug(datastore, SlugKind::Node, slug).await?;
+    }
+    if let Some(existing) = datastore
+        .search_nodes_by_name(&node.name)
+        .await?
+        .into_iter()
+        .find(|existing| existing.fqdn == node.fqdn)
+    {
+        return Err(CommandError::conflict(format!(
+            "Node {} is already in the inventory ({})",
+            existing.fqdn, existing.id
+        ))
+        .into());
+    }
+
+    let created = retry_operation(DEFAULT_MAX_RETRIES, || datastore.create_node(&node)).await?;
+    assign_slug(
+        datastore,
+        SlugKind::Node,
+        created.id,
+        &created.name,
+        args.slug.as_deref(),
+    )
+    .await?;
+    provenance::record(
+        datastore,
+        None,
+        &created,
+        Source::SnmpDiscovery,
+        Some(&args.address.to_string()),
+        chrono::Utc::now(),
+    )
+    .await?;
+
+    let report = OnboardReport {
+        node: created,
+        sys_location: facts.sys_location,
+        location_rule,
+        interfaces: facts.interfaces.len(),
+    };
+    crate::commands::print_output(&report, output_format)
+}
+
+/// Reads the system group and interface table of the device at `address`
+async fn poll_facts(
+    address: SocketAddr,
+    community: &str,
+    config: &SnmpConfig,
+) -> Result<DeviceFacts> {
+    let client = SnmpClient::new(config.client_config());
+    let session = session_config(address, community, config);
+    let system = client
+        .get(
+            address,
+            &[
+                StandardOid::SysDescr.oid(),
+                StandardOid::SysName.oid(),
+                StandardOid::SysLocation.oid(),
+            ],
+            Some(session.clone()),
+        )
+        .await
+        .map_err(|e| anyhow!("No SNMP response from {address}: {e}"))?;
+    let mut facts = DeviceFacts::from_system(&system);
+    let if_table = client
+        .walk(address, StandardOid::IfTable.oid(), Some(session))
+        .await
+        .map_err(|e| anyhow!("Failed to read the interface table of {address}: {e}"))?;
+    facts.interfaces = parse_if_table(&if_table);
+    Ok(facts)
+}
+
+/// Builds the node to add from the device's facts and the command options
+pub(super) fn build_node(
+    args: &OnboardNodeArgs,
+    facts: &DeviceFacts,
+    location_id: Option<Uuid>,
+    default_domain: Option<&str>,
+) -> Result<Node> {
+    let role = args
+        .role
+        .parse::<DeviceRole>()
+        .map_err(|e| anyhow!("Invalid role '{}': {}", args.role, e))?;
+    let lifecycle = args
+        .lifecycle
+        .parse::<Lifecycle>()
+        .map_err(|e| anyhow!("Invalid lifecycle '{}': {}", args.lifecycle, e))?;
+
+    let (reported_name, reported_domain) = facts.name_and_domain().unzip();
+    let name = args
+        .name
+        .clone()
+        .or(reported_name)
+        .ok_or_else(|| anyhow!("{} reports no sysName; pass --name", args.address))?;
+    let domain = reported_domain
+        .flatten()
+        .or_else(|| default_domain.map(ToString::to_string))
+        .unwrap_or_default();
+    let model = args
+        .model
+        .clone()
+        .or_else(|| facts.model())
+        .ok_or_else(|| {
+            anyhow!(
+                "Could not detect the model of {} from sysDescr; pass --model",
+                args.address
+            )
+        })?;
+
+    let mut builder = NodeBuilder::new()
+        .name(name)
+        .domain(domain)
+        .vendor(facts.vendor())
+        .model(model)
+        .role(role)
+        .lifecycle(lifecycle)
+        .management_ip(args.address);
+    if let Some(location_id) = location_id {
+        builder = builder.location_id(location_id);
+    }
+    let mut node = builder
+        .build()
+        .map_err(|e| anyhow!("Node validation failed: {e}"))?;
+    if let Some(software) = facts
+        .sys_descr
+        .as_deref()
+        .and_then(SoftwareVersion::from_sys_descr)
+    {
+        node.backfill_software(&software, false);
+    }
+    if !facts.interfaces.is_empty() {
+        node.set_custom_data(INTERFACES_PATH, facts.interfaces_value())
+            .map_err(|e| anyhow!("Failed to store interfaces: {e}"))?;
+    }
+    Ok(node)
+}
@@ -0,0 +1,103 @@
+/// Tests for the node onboarding command
+#[cfg(test)]
+mod tests {
+    use super::super::onboard::{build_node, onboard};
+    use super::super::types::OnboardNodeArgs;
+    use unet_core::config::Config;
+    use unet_core::datastore::MockDataStore;
+    use unet_core::models::{DeviceRole, Lifecycle, Vendor};
+    use unet_core::onboarding::{DeviceFacts, DiscoveredInterface};
+    use uuid::Uuid;
+
+    fn args() -> OnboardNodeArgs {
+        OnboardNodeArgs {
+            address: "10.1.1.1".parse().unwrap(),
+            credential_profile: "default".to_string(),
+            role: "router".to_string(),
+            lifecycle: "live".to_string(),
+            name: None,
+            model: None,
+            location: None,
+            slug: None,
+        }
+    }
+
+    fn juniper_facts() -> DeviceFacts {
+        DeviceFacts {
+            sys_descr: Some(
+                "Juniper Networks, Inc. mx204 internet router, kernel JUNOS 21.4R3-S2.3"
+                    .to_string(),
+            ),
+            sys_name: Some("edge-01.dc1.example.com".to_string()),
+            sys_location: Some("DC1 Row 4".to_string()),
+            interfaces: vec![DiscoveredInterface {
+                index: 1,
+                name: "et-0/0/0".to_string(),
+                if_type: Some(6),
+                mtu: Some(9192),
+                speed: None,
+                mac_address: None,
+                admin_up: Some(true),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_build_node_from_device_facts() {
+        let location_id = Uuid::new_v4();
+
+        let node = build_node(&args(), &juniper_facts(), Some(location_id), None).unwrap();
+
+        assert_eq!(node.name, "edge-01");
+        assert_eq!(node.domain, "dc1.example.com");
+        assert_eq!(node.vendor, Vendor::Juniper);
+        assert_eq!(node.model, "MX204");
+        assert_eq!(node.role, DeviceRole::Router);
+        assert_eq!(node.lifecycle, Lifecycle::Live);
+        assert_eq!(node.location_id, Some(location_id));
+        assert_eq!(node.version.as_deref(), Some("21.4R3-S2.3"));
+        assert_eq!(node.custom_data["interfaces"][0]["name"], "et-0/0/0");
+    }
+
+    #[test]
+    fn test_build_node_prefers_options_and_default_domain() {
+        let mut facts = juniper_facts();
+        facts.sys_name = None;
+        facts.sys_descr = Some("Linux fw01 5.15.0-91-generic".to_string());
+        let mut args = args();
+
+        let error = build_node(&args, &facts, None, None).unwrap_err();
+        assert!(error.to_string().contains("pass --name"));
+
+        args.name = Some("fw01".to_string());
+        let error = build_node(&args, &facts, None, None).unwrap_err();
+        assert!(error.to_string().contains("pass --model"));
+
+        args.model = Some("PA-440".to_string());
+        let node = build_node(&args, &facts, None, Some("example.com")).unwrap();
+        assert_eq!(node.fqdn, "fw01.example.com");
+        assert_eq!(node.vendor, Vendor::Generic);
+        assert_eq!(node.model, "PA-440");
+    }
+
+    #[tokio::test]
+    async fn test_onboard_rejects_unknown_credential_profile() {
+        let mut args = args();
+        args.credential_profile = "core".to_string();
+
+        let error = onboard(
+            &args,
+            &MockDataStore::new(),
+            &Config::default(),
+            crate::OutputFormat::Json,
+        )
+        .await
+        .unwrap_err();
+
+        assert!(
+            error
+                .to_string()
+                .contains("Unknown credential profile 'core'")
+        );
+    }
+}
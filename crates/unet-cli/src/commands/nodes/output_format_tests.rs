@@ -9,7 +9,7 @@ async fn test_update_node_yaml_output() {
     let node_id = Uuid::new_v4();
 
     let args = UpdateNodeArgs {
-        id: node_id,
+        id: node_id.into(),
         name: Some("yaml-output-node".to_string()),
         domain: None,
         vendor: None,
@@ -18,6 +18,7 @@ async fn test_update_node_yaml_output() {
         lifecycle: None,
         location_id: None,
         management_ip: None,
+        management_ipv6: None,
         custom_data: None,
     };
 
@@ -32,7 +33,7 @@ async fn test_update_node_table_output() {
     let node_id = Uuid::new_v4();
 
     let args = UpdateNodeArgs {
-        id: node_id,
+        id: node_id.into(),
         name: Some("table-output-node".to_string()),
         domain: None,
         vendor: None,
@@ -41,6 +42,7 @@ async fn test_update_node_table_output() {
         lifecycle: None,
         location_id: None,
         management_ip: None,
+        management_ipv6: None,
         custom_data: None,
     };
 
@@ -56,7 +58,7 @@ async fn test_update_node_args_structure() {
 
     // Test with all None values (should be valid structure)
     let args = UpdateNodeArgs {
-        id: node_id,
+        id: node_id.into(),
         name: None,
         domain: None,
         vendor: None,
@@ -65,6 +67,7 @@ async fn test_update_node_args_structure() {
         lifecycle: None,
         location_id: None,
         management_ip: None,
+        management_ipv6: None,
         custom_data: None,
     };
 
@@ -87,7 +90,7 @@ async fn test_update_node_args_all_none() {
     let node_id = Uuid::new_v4();
 
     let args = UpdateNodeArgs {
-        id: node_id,
+        id: node_id.into(),
         name: None,
         domain: None,
         vendor: None,
@@ -96,6 +99,7 @@ async fn test_update_node_args_all_none() {
         lifecycle: None,
         location_id: None,
         management_ip: None,
+        management_ipv6: None,
         custom_data: None,
     };
 
@@ -1,6 +1,7 @@
 /// Node display operations
 use anyhow::Result;
 use unet_core::datastore::DataStore;
+use unet_core::provenance::get_provenance;
 
 use super::types::ShowNodeArgs;
 
@@ -9,18 +10,25 @@ pub async fn show_node(
     datastore: &dyn DataStore,
     output_format: crate::OutputFormat,
 ) -> Result<()> {
-    let node = datastore.get_node_required(&args.id).await?;
+    let fields = super::fields::parse(args.fields.as_deref())?;
+    let id = crate::resolve::node(datastore, &args.id.reference, args.id.by_id).await?;
+    let node = datastore.get_node_required(&id).await?;
+    let node = match &fields {
+        Some(fields) => super::fields::project(&node, fields)?,
+        None => serde_json::to_value(&node)?,
+    };
 
-    if args.include_status || args.show_interfaces || args.show_system_info {
-        // Create enhanced output with derived state
-        let mut output = serde_json::json!({
-            "node": node,
-            "derived_state": {}
-        });
+    let derived = args.include_status || args.show_interfaces || args.show_system_info;
+    if derived || args.provenance {
+        // Create enhanced output with derived state and attribute sources
+        let mut output = serde_json::json!({ "node": node });
+        if derived {
+            output["derived_state"] = serde_json::json!({});
+        }
 
         // Fetch actual derived state data
         if args.include_status {
-            match datastore.get_node_status(&args.id).await {
+            match datastore.get_node_status(&id).await {
                 Ok(Some(status)) => {
                     output["derived_state"]["status"] = serde_json::to_value(&status)?;
                 }
@@ -31,29 +39,25 @@ pub async fn show_node(
                     });
                 }
                 Err(e) => {
-                    output["derived_state"]["status"] = serde_json::json!({
-                        "error": format!("Failed to fetch status: {}", e)
-                    });
+                    crate::warnings::warn(format!("Failed to fetch status: {e}"));
                 }
             }
         }
 
         if args.show_interfaces {
-            match datastore.get_node_interfaces(&args.id).await {
+            match datastore.get_node_interfaces(&id).await {
                 Ok(interfaces) => {
                     output["derived_state"]["interfaces"] = serde_json::to_value(&interfaces)?;
                 }
                 Err(e) => {
-                    output["derived_state"]["interfaces"] = serde_json::json!({
-                        "error": format!("Failed to fetch interfaces: {}", e)
-                    });
+                    crate::warnings::warn(format!("Failed to fetch interfaces: {e}"));
                 }
             }
         }
 
         if args.show_system_info {
             // Get system info from node status
-            match datastore.get_node_status(&args.id).await {
+            match datastore.get_node_status(&id).await {
                 Ok(Some(status)) => {
                     output["derived_state"]["system_info"] =
                         serde_json::to_value(&status.system_info)?;
@@ -65,13 +69,19 @@ pub async fn show_node(
                     });
                 }
                 Err(e) => {
-                    output["derived_state"]["system_info"] = serde_json::json!({
-                        "error": format!("Failed to fetch system info: {}", e)
-                    });
+                    crate::warnings::warn(format!("Failed to fetch system info: {e}"));
                 }
             }
         }
 
+        if args.provenance {
+            let fields = get_provenance(datastore, id)
+                .await?
+                .map(|provenance| provenance.fields)
+                .unwrap_or_default();
+            output["provenance"] = serde_json::to_value(fields)?;
+        }
+
         crate::commands::print_output(&output, output_format)?;
     } else {
         // Standard node display
@@ -37,10 +37,12 @@ mod tests {
             .returning(move |_| Box::pin(async move { Ok(None) }));
 
         let args = ShowNodeArgs {
-            id,
+            id: id.into(),
             include_status: true,
             show_interfaces: false,
             show_system_info: true,
+            provenance: false,
+            fields: None,
         };
         let res = show_node(args, &mock, crate::OutputFormat::Json).await;
         assert!(res.is_ok());
@@ -71,12 +73,53 @@ mod tests {
             .returning(move |_| Box::pin(async move { Ok(Some(NodeStatus::new(id))) }));
 
         let args = ShowNodeArgs {
-            id,
+            id: id.into(),
             include_status: true,
             show_interfaces: true,
             show_system_info: false,
+            provenance: false,
+            fields: None,
         };
         let res = show_node(args, &mock, crate::OutputFormat::Yaml).await;
         assert!(res.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_show_node_provenance_reads_stored_sources() {
+        let node = make_node();
+        let id = node.id;
+        let mut mock = MockDataStore::new();
+        mock.expect_get_node_required()
+            .with(eq(id))
+            .returning(move |_| {
+                let n = node.clone();
+                Box::pin(async move { Ok(n) })
+            });
+        mock.expect_get_setting()
+            .withf(move |namespace, key| namespace == "node_provenance" && key == id.to_string())
+            .returning(move |_, _| {
+                let stored = serde_json::json!({
+                    "node_id": id,
+                    "fields": {
+                        "model": {
+                            "source": "snmp_discovery",
+                            "detail": "192.0.2.10",
+                            "updated_at": "2026-01-05T10:00:00Z"
+                        }
+                    }
+                });
+                Box::pin(async move { Ok(Some(stored)) })
+            });
+
+        let args = ShowNodeArgs {
+            id: id.into(),
+            include_status: false,
+            show_interfaces: false,
+            show_system_info: false,
+            provenance: true,
+            fields: None,
+        };
+        let res = show_node(args, &mock, crate::OutputFormat::Json).await;
+        assert!(res.is_ok());
+    }
 }
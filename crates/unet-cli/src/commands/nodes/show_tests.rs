@@ -8,10 +8,12 @@ mod tests {
     async fn test_show_node_args_structure() {
         let node_id = Uuid::new_v4();
         let args = ShowNodeArgs {
-            id: node_id,
+            id: node_id.into(),
             include_status: true,
             show_interfaces: true,
             show_system_info: true,
+            provenance: false,
+            fields: None,
         };
 
         assert_eq!(args.id, node_id);
@@ -24,10 +26,12 @@ mod tests {
     async fn test_show_node_args_all_false() {
         let node_id = Uuid::new_v4();
         let args = ShowNodeArgs {
-            id: node_id,
+            id: node_id.into(),
             include_status: false,
             show_interfaces: false,
             show_system_info: false,
+            provenance: false,
+            fields: None,
         };
 
         assert_eq!(args.id, node_id);
@@ -41,10 +45,12 @@ mod tests {
         let node_id = Uuid::new_v4();
 
         let args1 = ShowNodeArgs {
-            id: node_id,
+            id: node_id.into(),
             include_status: true,
             show_interfaces: true,
             show_system_info: false,
+            provenance: false,
+            fields: None,
         };
         assert_eq!(args1.id, node_id);
         assert!(args1.include_status);
@@ -52,10 +58,12 @@ mod tests {
         assert!(!args1.show_system_info);
 
         let args2 = ShowNodeArgs {
-            id: node_id,
+            id: node_id.into(),
             include_status: true,
             show_interfaces: false,
             show_system_info: true,
+            provenance: false,
+            fields: None,
         };
         assert_eq!(args2.id, node_id);
         assert!(args2.include_status);
@@ -63,10 +71,12 @@ mod tests {
         assert!(args2.show_system_info);
 
         let args3 = ShowNodeArgs {
-            id: node_id,
+            id: node_id.into(),
             include_status: false,
             show_interfaces: true,
             show_system_info: true,
+            provenance: false,
+            fields: None,
         };
         assert_eq!(args3.id, node_id);
         assert!(!args3.include_status);
@@ -134,10 +144,12 @@ mod exec_tests {
         let node = make_node();
         let store = store_for_show(node.clone(), false);
         let args = ShowNodeArgs {
-            id: node.id,
+            id: node.id.into(),
             include_status: false,
             show_interfaces: false,
             show_system_info: false,
+            provenance: false,
+            fields: None,
         };
         let result = show_node(args, &store, crate::OutputFormat::Json).await;
         assert!(result.is_ok());
@@ -148,10 +160,12 @@ mod exec_tests {
         let node = make_node();
         let store = store_for_show(node.clone(), false);
         let args = ShowNodeArgs {
-            id: node.id,
+            id: node.id.into(),
             include_status: true,
             show_interfaces: true,
             show_system_info: true,
+            provenance: false,
+            fields: None,
         };
         let result = show_node(args, &store, crate::OutputFormat::Json).await;
         assert!(result.is_ok());
@@ -162,10 +176,12 @@ mod exec_tests {
         let node = make_node();
         let store = store_for_show(node.clone(), true);
         let args = ShowNodeArgs {
-            id: node.id,
+            id: node.id.into(),
             include_status: true,
             show_interfaces: false,
             show_system_info: false,
+            provenance: false,
+            fields: None,
         };
         let result = show_node(args, &store, crate::OutputFormat::Json).await;
         assert!(result.is_ok());
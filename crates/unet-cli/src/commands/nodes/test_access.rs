@@ -0,0 +1,193 @@
+/// Node access testing: tries each resolved credential against the device
+use anyhow::{Result, anyhow};
+use serde::Serialize;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+use unet_core::config::SnmpConfig;
+use unet_core::datastore::DataStore;
+use unet_core::models::Node;
+use unet_core::models::derived::SoftwareVersion;
+use unet_core::snmp::{SessionConfig, SnmpClient, SnmpCredentials, StandardOid};
+
+use super::types::TestAccessArgs;
+
+/// `custom_data` path of a node-specific SNMP community
+const NODE_COMMUNITY_PATH: &str = "snmp.community";
+
+/// Result of one access method with one credential
+#[derive(Debug, Serialize)]
+struct AccessAttempt {
+    /// Access method (`snmp` or `ssh`)
+    method: &'static str,
+    /// Where the credential came from; the secret itself is never shown
+    credential: Option<String>,
+    /// `ok`, `failed`, or `skipped`
+    status: &'static str,
+    /// Response time in milliseconds, when the device answered
+    latency_ms: Option<u64>,
+    /// `sysDescr` reported by the device
+    sys_descr: Option<String>,
+    /// Failure or skip reason
+    error: Option<String>,
+}
+
+/// Access test report for one node
+#[derive(Debug, Serialize)]
+struct AccessReport {
+    node_id: String,
+    node_name: String,
+    /// Management address that was probed
+    address: String,
+    /// Attempts in the order they were made
+    attempts: Vec<AccessAttempt>,
+    /// OS and version parsed from the `sysDescr` the device reported
+    software: Option<SoftwareVersion>,
+    /// Whether the node's platform and version were filled in from `software`
+    backfilled: bool,
+}
+
+/// Tests SNMP (and SSH, once a collector exists) access to a node
+///
+/// Credentials are tried in resolution order until one works: the node's
+/// `custom_data.snmp.community`, then `snmp.community` from the configuration.
+/// With `--backfill-version`, the OS and version parsed from the device's
+/// `sysDescr` fill in the node's platform and version where they are unset.
+///
+/// # Errors
+/// Returns an error if the node cannot be loaded, has no usable management
+/// address, no credential gives access, or the node cannot be updated.
+pub async fn test_access(
+    args: &TestAccessArgs,
+    datastore: &dyn DataStore,
+    config: &SnmpConfig,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    let node = datastore.get_node_required(&args.id).await?;
+    let address = node
+        .management_socket_addr(161, config.address_family)
+        .map_err(|e| anyhow!("Node {} has an invalid management address: {e}", node.name))?
+        .ok_or_else(|| anyhow!("Node {} has no management IP to test", node.name))?;
+
+    let client = SnmpClient::new(config.client_config());
+    let mut attempts = Vec::new();
+    for (source, community) in credential_candidates(&node, config) {
+        let attempt = snmp_attempt(&client, address, source, community, config).await;
+        let worked = attempt.status == "ok";
+        attempts.push(attempt);
+        if worked {
+            break;
+        }
+    }
+    attempts.push(AccessAttempt {
+        method: "ssh",
+        credential: None,
+        status: "skipped",
+        latency_ms: None,
+        sys_descr: None,
+        error: Some("No SSH collector is available".to_string()),
+    });
+
+    let accessible = attempts.iter().any(|attempt| attempt.status == "ok");
+    let software = attempts
+        .iter()
+        .filter_map(|attempt| attempt.sys_descr.as_deref())
+        .find_map(SoftwareVersion::from_sys_descr);
+    let mut backfilled = false;
+    if let Some(software) = software.as_ref().filter(|_| args.backfill_version) {
+        let mut updated = node.clone();
+        if updated.backfill_software(software, false) {
+            datastore.update_node(&updated).await?;
+            backfilled = true;
+        }
+    }
+    let report = AccessReport {
+        node_id: node.id.to_string(),
+        node_name: node.name.clone(),
+        address: address.to_string(),
+        attempts,
+        software,
+        backfilled,
+    };
+    crate::commands::print_output(&report, output_format)?;
+
+    if accessible {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "No credential gave access to {} at {address}",
+            node.name
+        ))
+    }
+}
+
+/// SNMP communities to try, in resolution order, without duplicates
+pub(crate) fn credential_candidates<'a>(
+    node: &'a Node,
+    config: &'a SnmpConfig,
+) -> Vec<(&'static str, &'a str)> {
+    let mut candidates = Vec::new();
+    if let Some(community) = node
+        .get_custom_data(NODE_COMMUNITY_PATH)
+        .and_then(serde_json::Value::as_str)
+    {
+        candidates.push(("node custom_data.snmp.community", community));
+    }
+    if !candidates
+        .iter()
+        .any(|(_, community)| *community == config.community)
+    {
+        candidates.push(("config snmp.community", config.community.as_str()));
+    }
+    candidates
+}
+
+async fn snmp_attempt(
+    client: &SnmpClient,
+    address: SocketAddr,
+    source: &str,
+    community: &str,
+    config: &SnmpConfig,
+) -> AccessAttempt {
+    let session = session_config(address, community, config);
+    let oid = StandardOid::SysDescr.oid();
+
+    let started = Instant::now();
+    let result = client.get(address, &[oid], Some(session)).await;
+    let latency_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+
+    let mut attempt = AccessAttempt {
+        method: "snmp",
+        credential: Some(source.to_string()),
+        status: "failed",
+        latency_ms: None,
+        sys_descr: None,
+        error: None,
+    };
+    match result {
+        Ok(values) => {
+            attempt.status = "ok";
+            attempt.latency_ms = Some(latency_ms);
+            attempt.sys_descr = values.get(oid).map(ToString::to_string);
+        }
+        Err(e) => attempt.error = Some(e.to_string()),
+    }
+    attempt
+}
+
+/// `SNMPv2c` session to `address` with `community` and the configured timeout
+pub(crate) fn session_config(
+    address: SocketAddr,
+    community: &str,
+    config: &SnmpConfig,
+) -> SessionConfig {
+    SessionConfig {
+        address,
+        version: 2,
+        credentials: SnmpCredentials::Community {
+            community: community.to_string(),
+        },
+        timeout: Duration::from_secs(config.timeout),
+        retries: u32::from(config.retries),
+        ..SessionConfig::default()
+    }
+}
@@ -0,0 +1,103 @@
+/// Tests for the node access test command
+#[cfg(test)]
+mod tests {
+    use super::super::test_access::{credential_candidates, test_access};
+    use super::super::types::TestAccessArgs;
+    use serde_json::json;
+    use unet_core::config::Config;
+    use unet_core::datastore::{MockDataStore, testing::ready_ok};
+    use unet_core::models::{DeviceRole, Node, NodeBuilder, Vendor};
+
+    fn make_node(management_ip: Option<&str>) -> Node {
+        let mut builder = NodeBuilder::new()
+            .name("edge-1")
+            .domain("example.com")
+            .vendor(Vendor::Cisco)
+            .model("ISR4321")
+            .role(DeviceRole::Router);
+        if let Some(ip) = management_ip {
+            builder = builder.management_ip(ip.parse().unwrap());
+        }
+        builder.build().unwrap()
+    }
+
+    fn store_with_node(node: Node) -> MockDataStore {
+        let mut store = MockDataStore::new();
+        store
+            .expect_get_node_required()
+            .returning(move |_| ready_ok(node.clone()));
+        store
+    }
+
+    #[test]
+    fn test_credential_candidates_prefer_node_community() {
+        let config = Config::default().snmp;
+        let mut node = make_node(None);
+        node.custom_data = json!({"snmp": {"community": "edge-ro"}});
+
+        let candidates = credential_candidates(&node, &config);
+
+        assert_eq!(
+            candidates,
+            vec![
+                ("node custom_data.snmp.community", "edge-ro"),
+                ("config snmp.community", config.community.as_str()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_credential_candidates_skip_duplicate_community() {
+        let config = Config::default().snmp;
+        let mut node = make_node(None);
+        node.custom_data = json!({"snmp": {"community": config.community.clone()}});
+
+        let candidates = credential_candidates(&node, &config);
+
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].0, "node custom_data.snmp.community");
+    }
+
+    #[tokio::test]
+    async fn test_access_requires_management_ip() {
+        let node = make_node(None);
+        let args = TestAccessArgs {
+            id: node.id,
+            backfill_version: false,
+        };
+        let store = store_with_node(node);
+
+        let error = test_access(
+            &args,
+            &store,
+            &Config::default().snmp,
+            crate::OutputFormat::Json,
+        )
+        .await
+        .unwrap_err();
+
+        assert!(error.to_string().contains("has no management IP"));
+    }
+
+    #[tokio::test]
+    async fn test_access_fails_when_device_does_not_answer() {
+        let node = make_node(Some("127.0.0.1"));
+        let args = TestAccessArgs {
+            id: node.id,
+            backfill_version: false,
+        };
+        let store = store_with_node(node);
+        let mut config = Config::default().snmp;
+        config.timeout = 1;
+        config.retries = 0;
+
+        let result = test_access(&args, &store, &config, crate::OutputFormat::Json).await;
+
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("No credential gave access")
+        );
+    }
+}
@@ -1,7 +1,11 @@
 /// Command types and argument structures for node management
 use clap::{Args, Subcommand};
+use std::path::PathBuf;
+use unet_core::models::derived::HardwareKind;
 use uuid::Uuid;
 
+use crate::resolve::EntityArg;
+
 #[derive(Subcommand)]
 pub enum NodeCommands {
     /// Add a new node
@@ -24,6 +28,57 @@ pub enum NodeCommands {
     Polling(PollingNodeArgs),
     /// View derived state history
     History(HistoryNodeArgs),
+    /// Test SNMP access with the node's resolved credentials
+    TestAccess(TestAccessArgs),
+    /// Add a node from what a live device reports over SNMP
+    Onboard(OnboardNodeArgs),
+    /// Write and read markdown notes on a node
+    #[command(subcommand)]
+    Note(NoteCommands),
+    /// Show the chassis, modules, transceivers, and power supplies of a node
+    Hardware(HardwareNodeArgs),
+}
+
+#[derive(Subcommand)]
+pub enum NoteCommands {
+    /// Write a note on a node
+    Add(AddNoteArgs),
+    /// List the notes on a node, oldest first
+    List(ListNoteArgs),
+}
+
+#[derive(Args)]
+pub struct AddNoteArgs {
+    #[command(flatten)]
+    pub id: EntityArg,
+
+    /// Markdown text of the note
+    #[arg(required_unless_present = "file", conflicts_with = "file")]
+    pub text: Option<String>,
+
+    /// Read the markdown text from a file
+    #[arg(long)]
+    pub file: Option<PathBuf>,
+
+    /// Who wrote the note
+    #[arg(long)]
+    pub author: Option<String>,
+}
+
+#[derive(Args)]
+pub struct ListNoteArgs {
+    #[command(flatten)]
+    pub id: EntityArg,
+}
+
+#[derive(Args)]
+pub struct HardwareNodeArgs {
+    #[command(flatten)]
+    pub id: EntityArg,
+
+    /// Only show components of this kind (chassis, module, transceiver, power_supply)
+    #[arg(long)]
+    pub kind: Option<HardwareKind>,
 }
 
 #[derive(Args)]
@@ -32,6 +87,10 @@ pub struct AddNodeArgs {
     #[arg(short, long)]
     pub name: String,
 
+    /// Slug to use in place of the ID; derived from the name if omitted
+    #[arg(long)]
+    pub slug: Option<String>,
+
     /// Domain name  
     #[arg(short, long)]
     pub domain: String,
@@ -56,10 +115,14 @@ pub struct AddNodeArgs {
     #[arg(short = 'L', long)]
     pub location_id: Option<Uuid>,
 
-    /// Management IP address
+    /// Management IP address; link-local IPv6 takes a zone (fe80::1%eth0)
     #[arg(short = 'i', long)]
     pub management_ip: Option<String>,
 
+    /// IPv6 management address of a dual-stack node
+    #[arg(long)]
+    pub management_ipv6: Option<String>,
+
     /// Custom data as JSON
     #[arg(short = 'c', long)]
     pub custom_data: Option<String>,
@@ -79,6 +142,10 @@ pub struct ListNodeArgs {
     #[arg(long)]
     pub vendor: Option<String>,
 
+    /// Filter by management IPv4 or IPv6 address
+    #[arg(long)]
+    pub management_ip: Option<String>,
+
     /// Page number (1-based)
     #[arg(long, default_value = "1")]
     pub page: u64,
@@ -86,12 +153,16 @@ pub struct ListNodeArgs {
     /// Items per page
     #[arg(long, default_value = "20")]
     pub per_page: u64,
+
+    /// Only output these node fields (comma-separated, e.g. `name,vendor,lifecycle`)
+    #[arg(long)]
+    pub fields: Option<String>,
 }
 
 #[derive(Args)]
 pub struct ShowNodeArgs {
-    /// Node ID
-    pub id: Uuid,
+    #[command(flatten)]
+    pub id: EntityArg,
 
     /// Include derived state (SNMP polling data) in output
     #[arg(long)]
@@ -104,12 +175,21 @@ pub struct ShowNodeArgs {
     /// Show system information from derived state
     #[arg(long)]
     pub show_system_info: bool,
+
+    /// Show what last set each attribute (manual, import, netbox,
+    /// snmp_discovery) and when
+    #[arg(long)]
+    pub provenance: bool,
+
+    /// Only output these node fields (comma-separated, e.g. `name,vendor,lifecycle`)
+    #[arg(long)]
+    pub fields: Option<String>,
 }
 
 #[derive(Args)]
 pub struct UpdateNodeArgs {
-    /// Node ID
-    pub id: Uuid,
+    #[command(flatten)]
+    pub id: EntityArg,
 
     /// Node name
     #[arg(short, long)]
@@ -139,10 +219,14 @@ pub struct UpdateNodeArgs {
     #[arg(short = 'L', long)]
     pub location_id: Option<Uuid>,
 
-    /// Management IP address
+    /// Management IP address; link-local IPv6 takes a zone (fe80::1%eth0)
     #[arg(short = 'i', long)]
     pub management_ip: Option<String>,
 
+    /// IPv6 management address of a dual-stack node
+    #[arg(long)]
+    pub management_ipv6: Option<String>,
+
     /// Custom data as JSON
     #[arg(short = 'c', long)]
     pub custom_data: Option<String>,
@@ -150,8 +234,8 @@ pub struct UpdateNodeArgs {
 
 #[derive(Args)]
 pub struct DeleteNodeArgs {
-    /// Node ID
-    pub id: Uuid,
+    #[command(flatten)]
+    pub id: EntityArg,
 
     /// Skip confirmation prompt
     #[arg(short = 'y', long)]
@@ -243,6 +327,51 @@ pub struct PollingNodeArgs {
     pub detailed: bool,
 }
 
+#[derive(Args)]
+pub struct TestAccessArgs {
+    /// Node ID
+    pub id: Uuid,
+
+    /// Fill in the node's unset platform and version from the OS the device reports
+    #[arg(long)]
+    pub backfill_version: bool,
+}
+
+#[derive(Args)]
+pub struct OnboardNodeArgs {
+    /// Management IP address of the device
+    #[arg(long)]
+    pub address: std::net::IpAddr,
+
+    /// Named SNMP credentials from `snmp.profiles`; `default` is `snmp.community`
+    #[arg(long, default_value = "default")]
+    pub credential_profile: String,
+
+    /// Device role
+    #[arg(short, long, default_value = "other")]
+    pub role: String,
+
+    /// Lifecycle state
+    #[arg(short, long, default_value = "live")]
+    pub lifecycle: String,
+
+    /// Node name in place of the one in sysName
+    #[arg(short, long)]
+    pub name: Option<String>,
+
+    /// Model, when sysDescr does not name it
+    #[arg(short, long)]
+    pub model: Option<String>,
+
+    /// Location in place of the one the location rules pick
+    #[arg(short = 'L', long)]
+    pub location: Option<String>,
+
+    /// Slug to use in place of the ID; derived from the name if omitted
+    #[arg(long)]
+    pub slug: Option<String>,
+}
+
 #[derive(Debug, clap::ValueEnum, Clone)]
 pub enum PollingAction {
     /// Show current polling status
@@ -277,6 +406,11 @@ pub struct HistoryNodeArgs {
     /// Show detailed historical data
     #[arg(long)]
     pub detailed: bool,
+
+    /// Show the changes to this `custom_data` field instead, e.g.
+    /// `custom_data.bgp.asn`, from the change log
+    #[arg(long)]
+    pub field: Option<String>,
 }
 
 #[derive(Debug, clap::ValueEnum, Clone)]
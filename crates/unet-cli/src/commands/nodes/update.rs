@@ -1,8 +1,9 @@
 /// Node update operations
 use anyhow::Result;
 use serde_json::Value as JsonValue;
-use unet_core::datastore::DataStore;
+use unet_core::datastore::{DEFAULT_MAX_RETRIES, DataStore, retry_operation};
 use unet_core::prelude::*;
+use unet_core::provenance::{self, Source};
 
 use super::types::UpdateNodeArgs;
 
@@ -11,7 +12,9 @@ pub async fn update_node(
     datastore: &dyn DataStore,
     output_format: crate::OutputFormat,
 ) -> Result<()> {
-    let mut node = datastore.get_node_required(&args.id).await?;
+    let id = crate::resolve::node(datastore, &args.id.reference, args.id.by_id).await?;
+    let mut node = datastore.get_node_required(&id).await?;
+    let before = node.clone();
 
     // Track if fields affecting FQDN changed
     let mut name_changed = false;
@@ -54,12 +57,11 @@ pub async fn update_node(
         node.location_id = Some(location_id);
     }
 
-    if let Some(management_ip_str) = args.management_ip {
-        let management_ip = management_ip_str
-            .parse()
-            .map_err(|e| anyhow::anyhow!("Invalid management IP '{management_ip_str}': {e}"))?;
-        node.management_ip = Some(management_ip);
-    }
+    node.set_management_addresses(
+        args.management_ip.as_deref(),
+        args.management_ipv6.as_deref(),
+    )
+    .map_err(|e| anyhow::anyhow!("Invalid management address: {e}"))?;
 
     if let Some(custom_data_str) = args.custom_data {
         let custom_data = serde_json::from_str::<JsonValue>(&custom_data_str)?;
@@ -75,7 +77,17 @@ pub async fn update_node(
         };
     }
 
-    let updated_node = datastore.update_node(&node).await?;
+    let updated_node =
+        retry_operation(DEFAULT_MAX_RETRIES, || datastore.update_node(&node)).await?;
+    provenance::record(
+        datastore,
+        Some(&before),
+        &updated_node,
+        Source::Manual,
+        None,
+        chrono::Utc::now(),
+    )
+    .await?;
 
     crate::commands::print_output(&updated_node, output_format)?;
 
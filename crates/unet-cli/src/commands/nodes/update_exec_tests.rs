@@ -48,6 +48,8 @@ mod tests {
                 .expect("lock current_node in update_node") = node.clone();
             ready_ok(node.clone())
         });
+        store.expect_get_setting().returning(|_, _| ready_ok(None));
+        store.expect_put_setting().returning(|_, _, _| ready_ok(()));
         store
     }
 
@@ -57,7 +59,7 @@ mod tests {
         let last_updated = std::sync::Arc::new(std::sync::Mutex::new(None));
         let store = store_for_updates(node.clone(), last_updated);
         let args = UpdateNodeArgs {
-            id: node.id,
+            id: node.id.into(),
             name: Some("core-1b".to_string()),
             domain: Some("corp.local".to_string()),
             vendor: Some("cisco".to_string()),
@@ -66,6 +68,7 @@ mod tests {
             lifecycle: Some("live".to_string()),
             location_id: None,
             management_ip: Some("192.0.2.20".to_string()),
+            management_ipv6: None,
             custom_data: Some("{\"site\":\"dc1\"}".to_string()),
         };
 
@@ -79,7 +82,7 @@ mod tests {
         let last_updated = std::sync::Arc::new(std::sync::Mutex::new(None));
         let store = store_for_updates(node.clone(), last_updated);
         let args = UpdateNodeArgs {
-            id: node.id,
+            id: node.id.into(),
             name: None,
             domain: None,
             vendor: Some("invalid".to_string()),
@@ -88,6 +91,7 @@ mod tests {
             lifecycle: None,
             location_id: None,
             management_ip: None,
+            management_ipv6: None,
             custom_data: None,
         };
 
@@ -104,7 +108,7 @@ mod tests {
         let store = store_for_updates(node.clone(), last_updated.clone());
 
         let args = UpdateNodeArgs {
-            id: node.id,
+            id: node.id.into(),
             name: None,
             domain: Some(String::new()),
             vendor: None,
@@ -113,6 +117,7 @@ mod tests {
             lifecycle: None,
             location_id: None,
             management_ip: None,
+            management_ipv6: None,
             custom_data: None,
         };
         update_node(args, &store, crate::OutputFormat::Json)
@@ -126,7 +131,7 @@ mod tests {
         assert_eq!(updated.fqdn, updated.name);
 
         let args2 = UpdateNodeArgs {
-            id: updated.id,
+            id: updated.id.into(),
             name: Some("newname".to_string()),
             domain: None,
             vendor: None,
@@ -135,6 +140,7 @@ mod tests {
             lifecycle: None,
             location_id: None,
             management_ip: None,
+            management_ipv6: None,
             custom_data: None,
         };
         update_node(args2, &store, crate::OutputFormat::Json)
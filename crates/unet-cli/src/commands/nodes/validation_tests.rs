@@ -9,7 +9,7 @@ async fn test_update_node_vendor_invalid() {
     let node_id = Uuid::new_v4();
 
     let args = UpdateNodeArgs {
-        id: node_id,
+        id: node_id.into(),
         name: None,
         domain: None,
         vendor: Some("invalid_vendor".to_string()),
@@ -18,6 +18,7 @@ async fn test_update_node_vendor_invalid() {
         lifecycle: None,
         location_id: None,
         management_ip: None,
+        management_ipv6: None,
         custom_data: None,
     };
 
@@ -32,7 +33,7 @@ async fn test_update_node_role_invalid() {
     let node_id = Uuid::new_v4();
 
     let args = UpdateNodeArgs {
-        id: node_id,
+        id: node_id.into(),
         name: None,
         domain: None,
         vendor: None,
@@ -41,6 +42,7 @@ async fn test_update_node_role_invalid() {
         lifecycle: None,
         location_id: None,
         management_ip: None,
+        management_ipv6: None,
         custom_data: None,
     };
 
@@ -55,7 +57,7 @@ async fn test_update_node_lifecycle_invalid() {
     let node_id = Uuid::new_v4();
 
     let args = UpdateNodeArgs {
-        id: node_id,
+        id: node_id.into(),
         name: None,
         domain: None,
         vendor: None,
@@ -64,6 +66,7 @@ async fn test_update_node_lifecycle_invalid() {
         lifecycle: Some("invalid_lifecycle".to_string()),
         location_id: None,
         management_ip: None,
+        management_ipv6: None,
         custom_data: None,
     };
 
@@ -78,7 +81,7 @@ async fn test_update_node_management_ip_invalid() {
     let node_id = Uuid::new_v4();
 
     let args = UpdateNodeArgs {
-        id: node_id,
+        id: node_id.into(),
         name: None,
         domain: None,
         vendor: None,
@@ -87,6 +90,7 @@ async fn test_update_node_management_ip_invalid() {
         lifecycle: None,
         location_id: None,
         management_ip: Some("invalid.ip.address".to_string()),
+        management_ipv6: None,
         custom_data: None,
     };
 
@@ -101,7 +105,7 @@ async fn test_update_node_custom_data_invalid() {
     let node_id = Uuid::new_v4();
 
     let args = UpdateNodeArgs {
-        id: node_id,
+        id: node_id.into(),
         name: None,
         domain: None,
         vendor: None,
@@ -110,6 +114,7 @@ async fn test_update_node_custom_data_invalid() {
         lifecycle: None,
         location_id: None,
         management_ip: None,
+        management_ipv6: None,
         custom_data: Some("invalid json".to_string()),
     };
 
@@ -124,7 +129,7 @@ async fn test_update_node_nonexistent_node() {
     let nonexistent_id = Uuid::new_v4();
 
     let args = UpdateNodeArgs {
-        id: nonexistent_id,
+        id: nonexistent_id.into(),
         name: Some("nonexistent-node".to_string()),
         domain: None,
         vendor: None,
@@ -133,6 +138,7 @@ async fn test_update_node_nonexistent_node() {
         lifecycle: None,
         location_id: None,
         management_ip: None,
+        management_ipv6: None,
         custom_data: None,
     };
 
@@ -147,7 +153,7 @@ async fn test_update_node_no_changes() {
     let node_id = Uuid::new_v4();
 
     let args = UpdateNodeArgs {
-        id: node_id,
+        id: node_id.into(),
         name: None,
         domain: None,
         vendor: None,
@@ -156,6 +162,7 @@ async fn test_update_node_no_changes() {
         lifecycle: None,
         location_id: None,
         management_ip: None,
+        management_ipv6: None,
         custom_data: None,
     };
 
@@ -177,7 +184,7 @@ async fn test_update_node_mixed_valid_invalid_fields() {
     let node_id = Uuid::new_v4();
 
     let args = UpdateNodeArgs {
-        id: node_id,
+        id: node_id.into(),
         name: Some("mixed-fields-node".to_string()), // Valid
         domain: None,
         vendor: Some("invalid_vendor".to_string()), // Invalid
@@ -186,6 +193,7 @@ async fn test_update_node_mixed_valid_invalid_fields() {
         lifecycle: None,
         location_id: None,
         management_ip: None,
+        management_ipv6: None,
         custom_data: None,
     };
 
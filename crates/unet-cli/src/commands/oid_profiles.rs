@@ -0,0 +1,234 @@
+/// SNMP OID profile management commands
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use std::path::PathBuf;
+use unet_core::datastore::DataStore;
+use unet_core::snmp::profiles::{
+    AssignmentScope, OidProfile, OidProfileAssignment, assign_profile, delete_profile,
+    load_catalog, resolve_node_profile, save_profile, unassign_profile,
+};
+use uuid::Uuid;
+
+use crate::confirm::{Confirmation, confirm};
+
+#[derive(Subcommand)]
+pub enum OidProfileCommands {
+    /// List built-in and stored profiles
+    List,
+    /// Show a profile with inheritance applied
+    Show(ShowProfileArgs),
+    /// Create or replace a profile from a YAML or JSON file
+    Apply(ApplyProfileArgs),
+    /// Delete a stored profile
+    Delete(DeleteProfileArgs),
+    /// List profile assignments
+    Assignments,
+    /// Assign a profile to a node, node group, vendor, or role
+    Assign(AssignProfileArgs),
+    /// Remove a profile assignment
+    Unassign(UnassignProfileArgs),
+    /// Show the profile a node resolves to, with policy adjustments applied
+    Resolve(ResolveProfileArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct ShowProfileArgs {
+    /// Profile name
+    pub name: String,
+}
+
+#[derive(Args, Debug)]
+pub struct DeleteProfileArgs {
+    /// Profile name
+    pub name: String,
+    /// Skip confirmation prompt
+    #[arg(short = 'y', long)]
+    pub yes: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct ApplyProfileArgs {
+    /// Profile definition file (YAML or JSON)
+    pub file: PathBuf,
+}
+
+#[derive(Args, Debug)]
+pub struct AssignProfileArgs {
+    /// Assignment scope (node, group, vendor, role)
+    pub scope: AssignmentScope,
+    /// Node ID, group name, vendor name, or role name
+    pub target: String,
+    /// Profile name
+    pub profile: String,
+}
+
+#[derive(Args, Debug)]
+pub struct UnassignProfileArgs {
+    /// Assignment scope (node, group, vendor, role)
+    pub scope: AssignmentScope,
+    /// Node ID, group name, vendor name, or role name
+    pub target: String,
+    /// Skip confirmation prompt
+    #[arg(short = 'y', long)]
+    pub yes: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct ResolveProfileArgs {
+    /// Node ID
+    pub node_id: Uuid,
+}
+
+/// Execute OID profile subcommands.
+///
+/// # Errors
+/// Returns an error if profile validation, datastore operations, or output formatting fail.
+pub async fn execute(
+    command: OidProfileCommands,
+    datastore: &dyn DataStore,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    match command {
+        OidProfileCommands::List => {
+            let catalog = load_catalog(datastore).await?;
+            let profiles: Vec<_> = catalog.profiles().collect();
+            crate::commands::print_output(&profiles, output_format)
+        }
+        OidProfileCommands::Show(args) => {
+            let resolved = load_catalog(datastore).await?.resolve(&args.name)?;
+            crate::commands::print_output(&resolved, output_format)
+        }
+        OidProfileCommands::Apply(args) => apply(args, datastore, output_format).await,
+        OidProfileCommands::Delete(args) => {
+            let confirmation = Confirmation::new("Delete OID profile").affects(&args.name);
+            if !confirm(args.yes, &confirmation)? {
+                return Ok(());
+            }
+            delete_profile(datastore, &args.name).await?;
+            let output = serde_json::json!({ "message": "OID profile deleted", "name": args.name });
+            crate::commands::print_output(&output, output_format)
+        }
+        OidProfileCommands::Assignments => {
+            let catalog = load_catalog(datastore).await?;
+            crate::commands::print_output(&catalog.assignments(), output_format)
+        }
+        OidProfileCommands::Assign(args) => {
+            let assignment = OidProfileAssignment::new(args.scope, &args.target, args.profile)?;
+            assign_profile(datastore, &assignment).await?;
+            crate::commands::print_output(&assignment, output_format)
+        }
+        OidProfileCommands::Unassign(args) => {
+            let confirmation = Confirmation::new("Remove OID profile assignment")
+                .affects(format!("{}:{}", args.scope, args.target));
+            if !confirm(args.yes, &confirmation)? {
+                return Ok(());
+            }
+            unassign_profile(datastore, args.scope, &args.target).await?;
+            let output = serde_json::json!({
+                "message": "OID profile assignment removed",
+                "scope": args.scope,
+                "target": args.target,
+            });
+            crate::commands::print_output(&output, output_format)
+        }
+        OidProfileCommands::Resolve(args) => {
+            let node = datastore.get_node_required(&args.node_id).await?;
+            let resolved = resolve_node_profile(datastore, &node).await?;
+            crate::commands::print_output(&resolved, output_format)
+        }
+    }
+}
+
+async fn apply(
+    args: ApplyProfileArgs,
+    datastore: &dyn DataStore,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    let content = std::fs::read_to_string(&args.file)?;
+    let profile: OidProfile = if args.file.extension().is_some_and(|ext| ext == "json") {
+        serde_json::from_str(&content)?
+    } else {
+        serde_yaml::from_str(&content)?
+    };
+
+    save_profile(datastore, &profile).await?;
+    crate::commands::print_output(&profile, output_format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unet_core::datastore::MockDataStore;
+    use unet_core::models::{DeviceRole, Node, Vendor};
+
+    fn empty_settings(mock: &mut MockDataStore) {
+        mock.expect_list_settings()
+            .returning(|_| Box::pin(async { Ok(Vec::new()) }));
+    }
+
+    #[tokio::test]
+    async fn test_list_includes_builtin_profiles() {
+        let mut mock = MockDataStore::new();
+        empty_settings(&mut mock);
+
+        let result = execute(OidProfileCommands::List, &mock, crate::OutputFormat::Json).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_assign_rejects_unknown_profile() {
+        let mut mock = MockDataStore::new();
+        empty_settings(&mut mock);
+        mock.expect_put_setting().never();
+
+        let command = OidProfileCommands::Assign(AssignProfileArgs {
+            scope: AssignmentScope::Role,
+            target: "router".to_string(),
+            profile: "missing".to_string(),
+        });
+        let result = execute(command, &mock, crate::OutputFormat::Json).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_uses_role_assignment() {
+        let node = Node::new(
+            "core1".to_string(),
+            "example.com".to_string(),
+            Vendor::Cisco,
+            DeviceRole::Router,
+        );
+        let node_id = node.id;
+        let mut mock = MockDataStore::new();
+        empty_settings(&mut mock);
+        mock.expect_get_node().returning(move |_| {
+            let node = node.clone();
+            Box::pin(async move { Ok(Some(node)) })
+        });
+
+        let command = OidProfileCommands::Resolve(ResolveProfileArgs { node_id });
+        let result = execute(command, &mock, crate::OutputFormat::Json).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_apply_saves_yaml_profile() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("edge.yaml");
+        std::fs::write(
+            &file,
+            "name: edge\nparent: router-core\ngroups:\n  - name: bgp\n    oids: [1.3.6.1.2.1.15.3.1.2]\n    interval_seconds: 60\n",
+        )
+        .unwrap();
+        let mut mock = MockDataStore::new();
+        empty_settings(&mut mock);
+        mock.expect_put_setting()
+            .withf(|namespace, key, _| namespace == "oid_profiles" && key == "edge")
+            .times(1)
+            .returning(|_, _, _| Box::pin(async { Ok(()) }));
+
+        let command = OidProfileCommands::Apply(ApplyProfileArgs { file });
+        let result = execute(command, &mock, crate::OutputFormat::Json).await;
+        assert!(result.is_ok());
+    }
+}
@@ -12,6 +12,7 @@ fn test_validate_policy_args_creation() {
     let args = ValidatePolicyArgs {
         path: path.clone(),
         verbose: true,
+        lint: false,
     };
 
     assert_eq!(args.path, path);
@@ -24,6 +25,7 @@ fn test_validate_policy_args_non_verbose() {
     let args = ValidatePolicyArgs {
         path: path.clone(),
         verbose: false,
+        lint: false,
     };
 
     assert_eq!(args.path, path);
@@ -36,13 +38,14 @@ fn test_eval_policy_args_with_node_id() {
     let node_id = Uuid::new_v4();
     let args = EvalPolicyArgs {
         path: path.clone(),
-        node_id: Some(node_id),
+        node_id: Some(node_id.to_string()),
         verbose: true,
         failures_only: false,
+        dry_run: false,
     };
 
     assert_eq!(args.path, path);
-    assert_eq!(args.node_id, Some(node_id));
+    assert_eq!(args.node_id, Some(node_id.to_string()));
     assert!(args.verbose);
     assert!(!args.failures_only);
 }
@@ -55,6 +58,7 @@ fn test_eval_policy_args_without_node_id() {
         node_id: None,
         verbose: false,
         failures_only: true,
+        dry_run: false,
     };
 
     assert_eq!(args.path, path);
@@ -69,12 +73,12 @@ fn test_diff_policy_args_creation() {
     let node_id = Uuid::new_v4();
     let args = DiffPolicyArgs {
         path: path.clone(),
-        node_id,
+        node_id: node_id.to_string(),
         verbose: true,
     };
 
     assert_eq!(args.path, path);
-    assert_eq!(args.node_id, node_id);
+    assert_eq!(args.node_id, node_id.to_string());
     assert!(args.verbose);
 }
 
@@ -84,12 +88,12 @@ fn test_diff_policy_args_non_verbose() {
     let node_id = Uuid::new_v4();
     let args = DiffPolicyArgs {
         path: path.clone(),
-        node_id,
+        node_id: node_id.to_string(),
         verbose: false,
     };
 
     assert_eq!(args.path, path);
-    assert_eq!(args.node_id, node_id);
+    assert_eq!(args.node_id, node_id.to_string());
     assert!(!args.verbose);
 }
 
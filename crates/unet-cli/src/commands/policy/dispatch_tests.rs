@@ -32,6 +32,7 @@ mod tests {
         let args = ValidatePolicyArgs {
             path: tf.path().into(),
             verbose: true,
+            lint: false,
         };
 
         let mock = MockDataStore::new();
@@ -49,6 +50,7 @@ mod tests {
             node_id: None,
             verbose: false,
             failures_only: false,
+            dry_run: false,
         };
 
         let mut mock = MockDataStore::new();
@@ -69,7 +71,7 @@ mod tests {
 
         let args = DiffPolicyArgs {
             path: tf.path().into(),
-            node_id,
+            node_id: node_id.to_string(),
             verbose: true,
         };
 
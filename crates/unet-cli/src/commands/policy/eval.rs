@@ -1,7 +1,7 @@
 /// Policy evaluation and compliance checking functionality
 use anyhow::Result;
 use unet_core::datastore::DataStore;
-use unet_core::policy::{EvaluationContext, PolicyEvaluator};
+use unet_core::policy::{Action, EvaluationContext, PolicyEvaluator};
 
 use super::helpers::{get_evaluation_nodes, load_policies_from_path};
 use super::{DiffPolicyArgs, EvalPolicyArgs};
@@ -17,7 +17,11 @@ pub async fn eval_policy(args: EvalPolicyArgs, datastore: &dyn DataStore) -> Res
     let policies = load_policies_from_path(&args.path)?;
 
     // Get nodes to evaluate against
-    let nodes = get_evaluation_nodes(args.node_id, datastore).await?;
+    let node_id = match &args.node_id {
+        Some(node) => Some(crate::resolve::node(datastore, node, false).await?),
+        None => None,
+    };
+    let nodes = get_evaluation_nodes(node_id, datastore).await?;
 
     if nodes.is_empty() {
         println!("⚠️  No nodes found to evaluate policies against");
@@ -25,6 +29,9 @@ pub async fn eval_policy(args: EvalPolicyArgs, datastore: &dyn DataStore) -> Res
     }
 
     println!("Evaluating against {} node(s)", nodes.len());
+    if args.dry_run {
+        println!("Dry run: predicting SET and APPLY actions; nothing is changed");
+    }
 
     for node in nodes {
         println!("\n--- Node: {} ({}) ---", node.name, node.id);
@@ -65,7 +72,7 @@ mod e2e_light_tests {
         mock.expect_list_nodes()
             .returning(|_| Box::pin(async { Ok(PagedResult::new(vec![], 0, None)) }));
 
-        let args = super::EvalPolicyArgs { path: f.path().into(), node_id: None, verbose: false, failures_only: false };
+        let args = super::EvalPolicyArgs { path: f.path().into(), node_id: None, verbose: false, failures_only: false, dry_run: false };
         let res = super::eval_policy(args, &mock).await;
         assert!(res.is_ok());
     }
@@ -86,7 +93,7 @@ mod e2e_light_tests {
                 Box::pin(async move { Ok(PagedResult::new(vec![n], 1, None)) })
             });
 
-        let args = super::EvalPolicyArgs { path: f.path().into(), node_id: None, verbose: true, failures_only: false };
+        let args = super::EvalPolicyArgs { path: f.path().into(), node_id: None, verbose: true, failures_only: false, dry_run: false };
         let res = super::eval_policy(args, &mock).await;
         assert!(res.is_ok());
 
@@ -99,7 +106,7 @@ mod e2e_light_tests {
                 let n = node_for_get.clone();
                 Box::pin(async move { Ok(Some(n)) })
             });
-        let args2 = super::DiffPolicyArgs { path: f.path().into(), node_id, verbose: true };
+        let args2 = super::DiffPolicyArgs { path: f.path().into(), node_id: node_id.to_string(), verbose: true };
         let res2 = super::diff_policy(args2, &mock2).await;
         assert!(res2.is_ok());
     }
@@ -121,6 +128,9 @@ fn evaluate_node_policies(
     let mut total_rules = 0;
     let mut satisfied_rules = 0;
     let mut failed_rules = 0;
+    // Node data as the actions predicted so far would leave it
+    let mut custom_data = node.custom_data.clone();
+    let mut predicted_changes = 0;
 
     for policy_rules in policies {
         for (rule_idx, rule) in policy_rules.iter().enumerate() {
@@ -135,6 +145,11 @@ fn evaluate_node_policies(
                             println!("  ✅ Rule {}: SATISFIED", rule_idx + 1);
                             println!("    Action: {action:?}");
                         }
+                        if args.dry_run
+                            && predict_action(rule_idx, &action, &context, &mut custom_data)
+                        {
+                            predicted_changes += 1;
+                        }
                     }
                     unet_core::policy::EvaluationResult::NotSatisfied => {
                         failed_rules += 1;
@@ -149,6 +164,13 @@ fn evaluate_node_policies(
                             println!("    Error: {message}");
                         }
                     }
+                    unet_core::policy::EvaluationResult::Aborted { reason } => {
+                        failed_rules += 1;
+                        if args.verbose || args.failures_only {
+                            println!("  ⚠️  Rule {}: ABORTED", rule_idx + 1);
+                            println!("    Reason: {reason}");
+                        }
+                    }
                 },
                 Err(e) => {
                     failed_rules += 1;
@@ -162,10 +184,49 @@ fn evaluate_node_policies(
     }
 
     println!("Results: {total_rules} total, {satisfied_rules} satisfied, {failed_rules} failed");
+    if args.dry_run {
+        if predicted_changes == 0 {
+            println!("Dry run: no SET or APPLY actions would run");
+        } else {
+            println!(
+                "Dry run: {predicted_changes} action(s) would run, leaving custom_data as:\n{}",
+                serde_json::to_string_pretty(&custom_data)?
+            );
+        }
+    }
 
     Ok(())
 }
 
+/// Show the change a satisfied rule's action would make, returning whether
+/// it makes one
+fn predict_action(
+    rule_idx: usize,
+    action: &Action,
+    context: &EvaluationContext,
+    custom_data: &mut serde_json::Value,
+) -> bool {
+    match PolicyEvaluator::predict_action(action, context, custom_data) {
+        Ok(Some(change)) => {
+            let previous = change
+                .previous
+                .map_or_else(|| "(unset)".to_string(), |value| value.to_string());
+            println!(
+                "  🔎 Rule {}: would set {} from {previous} to {}",
+                rule_idx + 1,
+                change.field,
+                change.value
+            );
+            true
+        }
+        Ok(None) => false,
+        Err(e) => {
+            println!("  ⚠️  Rule {}: action would fail: {e}", rule_idx + 1);
+            false
+        }
+    }
+}
+
 /// Diff policies between current and expected states.
 ///
 /// # Errors
@@ -174,7 +235,8 @@ pub async fn diff_policy(args: DiffPolicyArgs, datastore: &dyn DataStore) -> Res
     println!("Checking compliance for node: {}", args.node_id);
 
     // Get the specific node
-    let node = match datastore.get_node(&args.node_id).await {
+    let node_id = crate::resolve::node(datastore, &args.node_id, false).await?;
+    let node = match datastore.get_node(&node_id).await {
         Ok(Some(node)) => node,
         Ok(None) => return Err(anyhow::anyhow!("Node not found: {}", args.node_id)),
         Err(e) => return Err(anyhow::anyhow!("Failed to get node: {e}")),
@@ -225,6 +287,11 @@ pub async fn diff_policy(args: DiffPolicyArgs, datastore: &dyn DataStore) -> Res
                             println!("  ⚠️  Compliance Rule {}: ERROR", rule_idx + 1);
                             println!("    Error: {message}");
                         }
+                        unet_core::policy::EvaluationResult::Aborted { reason } => {
+                            failed_checks += 1;
+                            println!("  ⚠️  Compliance Rule {}: ABORTED", rule_idx + 1);
+                            println!("    Reason: {reason}");
+                        }
                     },
                     Err(e) => {
                         failed_checks += 1;
@@ -312,7 +379,7 @@ mod tests {
         fn test_evaluate_node_policies_satisfied_verbose() {
             let node = make_node();
             let policies = vec![vec![assert_rule_true()]];
-            let args = EvalPolicyArgs { path: std::path::PathBuf::from("."), node_id: None, verbose: true, failures_only: false };
+            let args = EvalPolicyArgs { path: std::path::PathBuf::from("."), node_id: None, verbose: true, failures_only: false, dry_run: false };
             let res = evaluate_node_policies(&node, &policies, &args);
             assert!(res.is_ok());
         }
@@ -321,7 +388,17 @@ mod tests {
         fn test_evaluate_node_policies_not_satisfied_failures_only() {
             let node = make_node();
             let policies = vec![vec![assert_rule_false()]];
-            let args = EvalPolicyArgs { path: std::path::PathBuf::from("."), node_id: None, verbose: false, failures_only: true };
+            let args = EvalPolicyArgs { path: std::path::PathBuf::from("."), node_id: None, verbose: false, failures_only: true, dry_run: false };
+            let res = evaluate_node_policies(&node, &policies, &args);
+            assert!(res.is_ok());
+        }
+
+        #[test]
+        fn test_evaluate_node_policies_dry_run_predicts_set() {
+            let node = make_node();
+            let set_rule = PolicyRule { id: None, condition: Condition::True, action: Action::Set { field: FieldRef { path: vec!["custom_data".into(), "ntp".into()] }, value: Value::String("10.0.0.1".into()) } };
+            let policies = vec![vec![assert_rule_true(), set_rule]];
+            let args = EvalPolicyArgs { path: std::path::PathBuf::from("."), node_id: None, verbose: false, failures_only: false, dry_run: true };
             let res = evaluate_node_policies(&node, &policies, &args);
             assert!(res.is_ok());
         }
@@ -330,7 +407,7 @@ mod tests {
         fn test_evaluate_node_policies_error_branch() {
             let node = make_node();
             let policies = vec![vec![error_rule()]];
-            let args = EvalPolicyArgs { path: std::path::PathBuf::from("."), node_id: None, verbose: true, failures_only: true };
+            let args = EvalPolicyArgs { path: std::path::PathBuf::from("."), node_id: None, verbose: true, failures_only: true, dry_run: false };
             let res = evaluate_node_policies(&node, &policies, &args);
             assert!(res.is_ok());
         }
@@ -10,13 +10,14 @@ async fn test_eval_policy_args_creation() {
 
     let args = EvalPolicyArgs {
         path: path.clone(),
-        node_id: Some(node_id),
+        node_id: Some(node_id.to_string()),
         verbose: true,
         failures_only: false,
+        dry_run: false,
     };
 
     assert_eq!(args.path, path);
-    assert_eq!(args.node_id, Some(node_id));
+    assert_eq!(args.node_id, Some(node_id.to_string()));
     assert!(args.verbose);
     assert!(!args.failures_only);
 }
@@ -30,6 +31,7 @@ async fn test_eval_policy_args_no_node_id() {
         node_id: None,
         verbose: false,
         failures_only: true,
+        dry_run: false,
     };
 
     assert_eq!(args.path, path);
@@ -45,12 +47,12 @@ async fn test_diff_policy_args_creation() {
 
     let args = DiffPolicyArgs {
         path: path.clone(),
-        node_id,
+        node_id: node_id.to_string(),
         verbose: true,
     };
 
     assert_eq!(args.path, path);
-    assert_eq!(args.node_id, node_id);
+    assert_eq!(args.node_id, node_id.to_string());
     assert!(args.verbose);
 }
 
@@ -61,11 +63,11 @@ async fn test_diff_policy_args_not_verbose() {
 
     let args = DiffPolicyArgs {
         path: path.clone(),
-        node_id,
+        node_id: node_id.to_string(),
         verbose: false,
     };
 
     assert_eq!(args.path, path);
-    assert_eq!(args.node_id, node_id);
+    assert_eq!(args.node_id, node_id.to_string());
     assert!(!args.verbose);
 }
\ No newline at end of file
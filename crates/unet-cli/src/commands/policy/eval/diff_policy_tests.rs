@@ -12,12 +12,12 @@ async fn test_diff_policy_basic_functionality() {
     
     let args = DiffPolicyArgs {
         path: path.clone(),
-        node_id,
+        node_id: node_id.to_string(),
         verbose: false,
     };
     
     assert_eq!(args.path, path);
-    assert_eq!(args.node_id, node_id);
+    assert_eq!(args.node_id, node_id.to_string());
     assert!(!args.verbose);
 }
 
@@ -29,12 +29,12 @@ async fn test_diff_policy_node_not_found() {
     
     let args = DiffPolicyArgs {
         path: path.clone(),
-        node_id: nonexistent_id,
+        node_id: nonexistent_id.to_string(),
         verbose: false,
     };
     
     assert_eq!(args.path, path);
-    assert_eq!(args.node_id, nonexistent_id);
+    assert_eq!(args.node_id, nonexistent_id.to_string());
     assert!(!args.verbose);
 }
 
@@ -46,12 +46,12 @@ async fn test_diff_policy_with_verbose() {
     
     let args = DiffPolicyArgs {
         path: path.clone(),
-        node_id,
+        node_id: node_id.to_string(),
         verbose: true,
     };
     
     assert_eq!(args.path, path);
-    assert_eq!(args.node_id, node_id);
+    assert_eq!(args.node_id, node_id.to_string());
     assert!(args.verbose);
 }
 
@@ -64,7 +64,7 @@ async fn test_diff_policy_path_variations() {
     let relative_path = PathBuf::from("./policies");
     let args1 = DiffPolicyArgs {
         path: relative_path.clone(),
-        node_id,
+        node_id: node_id.to_string(),
         verbose: false,
     };
     assert_eq!(args1.path, relative_path);
@@ -73,7 +73,7 @@ async fn test_diff_policy_path_variations() {
     let absolute_path = PathBuf::from("/etc/policies");
     let args2 = DiffPolicyArgs {
         path: absolute_path.clone(),
-        node_id,
+        node_id: node_id.to_string(),
         verbose: true,
     };
     assert_eq!(args2.path, absolute_path);
@@ -88,12 +88,12 @@ async fn test_diff_policy_args_clone() {
     
     let args = DiffPolicyArgs {
         path: path.clone(),
-        node_id,
+        node_id: node_id.to_string(),
         verbose: true,
     };
     
     // Verify all fields are set correctly
     assert_eq!(args.path, path);
-    assert_eq!(args.node_id, node_id);
+    assert_eq!(args.node_id, node_id.to_string());
     assert!(args.verbose);
 }
\ No newline at end of file
@@ -12,13 +12,14 @@ async fn test_eval_policy_basic_functionality() {
     
     let args = EvalPolicyArgs {
         path: path.clone(),
-        node_id: Some(node_id),
+        node_id: Some(node_id.to_string()),
         verbose: false,
         failures_only: false,
+        dry_run: false,
     };
     
     assert_eq!(args.path, path);
-    assert_eq!(args.node_id, Some(node_id));
+    assert_eq!(args.node_id, Some(node_id.to_string()));
     assert!(!args.verbose);
     assert!(!args.failures_only);
 }
@@ -31,13 +32,14 @@ async fn test_eval_policy_no_nodes_found() {
     
     let args = EvalPolicyArgs {
         path: path.clone(),
-        node_id: Some(nonexistent_id),
+        node_id: Some(nonexistent_id.to_string()),
         verbose: false,
         failures_only: false,
+        dry_run: false,
     };
     
     assert_eq!(args.path, path);
-    assert_eq!(args.node_id, Some(nonexistent_id));
+    assert_eq!(args.node_id, Some(nonexistent_id.to_string()));
     assert!(!args.verbose);
     assert!(!args.failures_only);
 }
@@ -50,13 +52,14 @@ async fn test_eval_policy_with_verbose() {
     
     let args = EvalPolicyArgs {
         path: path.clone(),
-        node_id: Some(node_id),
+        node_id: Some(node_id.to_string()),
         verbose: true,
         failures_only: false,
+        dry_run: false,
     };
     
     assert_eq!(args.path, path);
-    assert_eq!(args.node_id, Some(node_id));
+    assert_eq!(args.node_id, Some(node_id.to_string()));
     assert!(args.verbose);
     assert!(!args.failures_only);
 }
@@ -69,13 +72,14 @@ async fn test_eval_policy_failures_only() {
     
     let args = EvalPolicyArgs {
         path: path.clone(),
-        node_id: Some(node_id),
+        node_id: Some(node_id.to_string()),
         verbose: false,
         failures_only: true,
+        dry_run: false,
     };
     
     assert_eq!(args.path, path);
-    assert_eq!(args.node_id, Some(node_id));
+    assert_eq!(args.node_id, Some(node_id.to_string()));
     assert!(!args.verbose);
     assert!(args.failures_only);
 }
@@ -90,6 +94,7 @@ async fn test_eval_policy_all_nodes() {
         node_id: None, // Evaluate against all nodes
         verbose: false,
         failures_only: false,
+        dry_run: false,
     };
     
     assert_eq!(args.path, path);
@@ -108,6 +113,7 @@ async fn test_eval_policy_empty_database() {
         node_id: None, // Evaluate against all nodes (should be empty)
         verbose: true,
         failures_only: true,
+        dry_run: false,
     };
     
     assert_eq!(args.path, path);
@@ -12,13 +12,14 @@ async fn test_eval_policy_args_basic_structure() {
     
     let args = EvalPolicyArgs {
         path: path.clone(),
-        node_id: Some(node_id),
+        node_id: Some(node_id.to_string()),
         verbose: false,
         failures_only: false,
+        dry_run: false,
     };
     
     assert_eq!(args.path, path);
-    assert_eq!(args.node_id, Some(node_id));
+    assert_eq!(args.node_id, Some(node_id.to_string()));
     assert!(!args.verbose);
     assert!(!args.failures_only);
 }
@@ -34,6 +35,7 @@ async fn test_eval_policy_args_with_flags() {
         node_id: None,
         verbose: true,
         failures_only: false,
+        dry_run: false,
     };
     
     assert_eq!(args1.path, path);
@@ -47,6 +49,7 @@ async fn test_eval_policy_args_with_flags() {
         node_id: None,
         verbose: false,
         failures_only: true,
+        dry_run: false,
     };
     
     assert_eq!(args2.path, path);
@@ -63,13 +66,14 @@ async fn test_eval_policy_args_both_flags() {
     
     let args = EvalPolicyArgs {
         path: path.clone(),
-        node_id: Some(node_id),
+        node_id: Some(node_id.to_string()),
         verbose: true,
         failures_only: true,
+        dry_run: false,
     };
     
     assert_eq!(args.path, path);
-    assert_eq!(args.node_id, Some(node_id));
+    assert_eq!(args.node_id, Some(node_id.to_string()));
     assert!(args.verbose);
     assert!(args.failures_only);
 }
@@ -83,9 +87,10 @@ async fn test_eval_policy_args_path_variations() {
     let relative_path = PathBuf::from("./policies");
     let args1 = EvalPolicyArgs {
         path: relative_path.clone(),
-        node_id: Some(node_id),
+        node_id: Some(node_id.to_string()),
         verbose: false,
         failures_only: false,
+        dry_run: false,
     };
     assert_eq!(args1.path, relative_path);
     
@@ -93,9 +98,10 @@ async fn test_eval_policy_args_path_variations() {
     let absolute_path = PathBuf::from("/home/user/policies");
     let args2 = EvalPolicyArgs {
         path: absolute_path.clone(),
-        node_id: Some(node_id),
+        node_id: Some(node_id.to_string()),
         verbose: true,
         failures_only: true,
+        dry_run: false,
     };
     assert_eq!(args2.path, absolute_path);
 }
\ No newline at end of file
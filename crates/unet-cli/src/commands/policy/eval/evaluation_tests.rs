@@ -19,6 +19,7 @@ async fn test_evaluate_node_policies_basic_functionality() {
         node_id: None,
         verbose: false,
         failures_only: false,
+        dry_run: false,
     };
 
     let result = evaluate_node_policies(&node, &policies, &args);
@@ -41,6 +42,7 @@ async fn test_evaluate_node_policies_with_verbose() {
         node_id: None,
         verbose: true,
         failures_only: false,
+        dry_run: false,
     };
 
     let result = evaluate_node_policies(&node, &policies, &args);
@@ -63,6 +65,7 @@ async fn test_evaluate_node_policies_failures_only() {
         node_id: None,
         verbose: false,
         failures_only: true,
+        dry_run: false,
     };
 
     let result = evaluate_node_policies(&node, &policies, &args);
@@ -85,6 +88,7 @@ async fn test_evaluate_node_policies_verbose_and_failures_only() {
         node_id: None,
         verbose: true,
         failures_only: true,
+        dry_run: false,
     };
 
     let result = evaluate_node_policies(&node, &policies, &args);
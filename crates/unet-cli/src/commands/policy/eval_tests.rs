@@ -41,9 +41,10 @@ mod tests {
 
         let args = EvalPolicyArgs {
             path: temp_file.path().to_path_buf(),
-            node_id: Some(Uuid::new_v4()),
+            node_id: Some(Uuid::new_v4().to_string()),
             verbose: true,
             failures_only: false,
+            dry_run: false,
         };
         let result = eval_policy(args, &store).await;
         assert!(result.is_ok());
@@ -74,6 +75,7 @@ mod tests {
             node_id: None,
             verbose: false,
             failures_only: true,
+            dry_run: false,
         };
         let result = eval_policy(args, &store).await;
         assert!(result.is_ok());
@@ -2,7 +2,7 @@
 use anyhow::Result;
 use unet_core::config::GitConfig;
 use unet_core::datastore::DataStore;
-use unet_core::policy::{PolicyLoader, PolicyParser};
+use unet_core::policy::PolicyLoader;
 use unet_core::prelude::QueryOptions;
 
 /// Load policies from a file or directory path
@@ -27,9 +27,8 @@ pub fn load_policies_from_path(
     let mut loader = PolicyLoader::new(git_config);
 
     if path.is_file() {
-        let content = std::fs::read_to_string(path)?;
-        let rules = PolicyParser::parse_file(&content)?;
-        Ok(vec![rules])
+        // Load through the loader so INCLUDE directives resolve relative to the file
+        Ok(vec![loader.load_policy_file(path)?.rules])
     } else if path.is_dir() {
         let load_result = loader.load_policies_from_directory(path)?;
         if !load_result.errors.is_empty() {
@@ -6,7 +6,6 @@ use anyhow::Result;
 use clap::{Args, Subcommand};
 use std::path::PathBuf;
 use unet_core::datastore::DataStore;
-use uuid::Uuid;
 
 pub mod eval;
 pub mod helpers;
@@ -15,7 +14,7 @@ pub mod validate;
 
 #[derive(Subcommand, Debug)]
 pub enum PolicyCommands {
-    /// Validate policy file syntax
+    /// Validate policy file syntax, optionally with style warnings
     Validate(ValidatePolicyArgs),
     /// Evaluate policies against nodes
     Eval(EvalPolicyArgs),
@@ -36,6 +35,11 @@ pub struct ValidatePolicyArgs {
     /// Show detailed validation results
     #[arg(short, long)]
     pub verbose: bool,
+
+    /// Also warn about duplicated rules, always-true or always-false
+    /// conditions, unknown fields, and rules without an ID
+    #[arg(long)]
+    pub lint: bool,
 }
 
 #[derive(Args, Debug)]
@@ -44,9 +48,9 @@ pub struct EvalPolicyArgs {
     #[arg(short, long)]
     pub path: PathBuf,
 
-    /// Node ID to evaluate against (optional, evaluates against all nodes if not specified)
-    #[arg(short, long)]
-    pub node_id: Option<Uuid>,
+    /// Node name or ID to evaluate against (optional, evaluates against all nodes if not specified)
+    #[arg(short, long, visible_alias = "node")]
+    pub node_id: Option<String>,
 
     /// Show detailed evaluation results
     #[arg(short, long)]
@@ -55,6 +59,11 @@ pub struct EvalPolicyArgs {
     /// Only show rules that failed evaluation
     #[arg(short, long)]
     pub failures_only: bool,
+
+    /// Show the SET and APPLY actions satisfied rules would run and the
+    /// resulting field values, without changing anything
+    #[arg(long)]
+    pub dry_run: bool,
 }
 
 #[derive(Args, Debug)]
@@ -63,9 +72,9 @@ pub struct DiffPolicyArgs {
     #[arg(short, long)]
     pub path: PathBuf,
 
-    /// Node ID to check compliance for
-    #[arg(short, long)]
-    pub node_id: Uuid,
+    /// Node name or ID to check compliance for
+    #[arg(short, long, visible_alias = "node")]
+    pub node_id: String,
 
     /// Show detailed differences
     #[arg(short, long)]
@@ -1,10 +1,23 @@
 /// Policy validation functionality
 use anyhow::Result;
 use unet_core::config::GitConfig;
-use unet_core::policy::{PolicyLoader, PolicyParser};
+use unet_core::policy::{PolicyFile, PolicyLoader, PolicyRule, lint_rules};
 
 use super::ValidatePolicyArgs;
 
+/// Print the lint warnings of the rules, returning how many there were
+fn print_lint_warnings(rules: &[PolicyRule], heading: Option<&str>) -> usize {
+    let warnings = lint_rules(rules);
+    if let (Some(heading), false) = (heading, warnings.is_empty()) {
+        println!("📄 {heading}");
+    }
+    let indent = if heading.is_some() { "  " } else { "" };
+    for warning in &warnings {
+        println!("{indent}⚠️  {warning}");
+    }
+    warnings.len()
+}
+
 /// Validate a single policy file.
 ///
 /// # Errors
@@ -26,10 +39,9 @@ pub fn validate_policy(args: &ValidatePolicyArgs) -> Result<()> {
     let mut loader = PolicyLoader::new(git_config);
 
     if args.path.is_file() {
-        // Validate single file by trying to parse it directly
-        let content = std::fs::read_to_string(&args.path)?;
-        match PolicyParser::parse_file(&content) {
-            Ok(rules) => {
+        // Validate single file by loading it, which also resolves its includes
+        match loader.load_policy_file(&args.path) {
+            Ok(PolicyFile { rules, .. }) => {
                 println!("✅ Policy file is valid");
                 if args.verbose {
                     println!("Rules found: {}", rules.len());
@@ -37,6 +49,9 @@ pub fn validate_policy(args: &ValidatePolicyArgs) -> Result<()> {
                         println!("  Rule {}: {}", i + 1, rule);
                     }
                 }
+                if args.lint && print_lint_warnings(&rules, None) == 0 {
+                    println!("No lint warnings");
+                }
             }
             Err(e) => {
                 println!("❌ Policy file validation failed: {e}");
@@ -59,6 +74,19 @@ pub fn validate_policy(args: &ValidatePolicyArgs) -> Result<()> {
                     );
                 }
             }
+            if args.lint {
+                let warnings: usize = load_result
+                    .loaded
+                    .iter()
+                    .map(|policy_file| {
+                        let path = policy_file.path.display().to_string();
+                        print_lint_warnings(&policy_file.rules, Some(&path))
+                    })
+                    .sum();
+                if warnings == 0 {
+                    println!("No lint warnings");
+                }
+            }
         } else {
             println!("❌ Some policy files failed validation:");
             for (file_path, error) in &load_result.errors {
@@ -95,6 +123,7 @@ mod tests {
         let args = ValidatePolicyArgs {
             path: temp_file.path().to_path_buf(),
             verbose: false,
+            lint: false,
         };
 
         let result = validate_policy(&args);
@@ -113,6 +142,7 @@ mod tests {
         let args = ValidatePolicyArgs {
             path: temp_file.path().to_path_buf(),
             verbose: true,
+            lint: false,
         };
 
         let result = validate_policy(&args);
@@ -127,6 +157,7 @@ mod tests {
         let args = ValidatePolicyArgs {
             path: temp_file.path().to_path_buf(),
             verbose: false,
+            lint: false,
         };
 
         let result = validate_policy(&args);
@@ -147,6 +178,7 @@ mod tests {
         let args = ValidatePolicyArgs {
             path: temp_dir.path().to_path_buf(),
             verbose: false,
+            lint: false,
         };
 
         let result = validate_policy(&args);
@@ -173,6 +205,7 @@ mod tests {
         let args = ValidatePolicyArgs {
             path: temp_dir.path().to_path_buf(),
             verbose: true,
+            lint: false,
         };
 
         let result = validate_policy(&args);
@@ -186,6 +219,7 @@ mod tests {
         let args = ValidatePolicyArgs {
             path: temp_dir.path().to_path_buf(),
             verbose: false,
+            lint: false,
         };
 
         let result = validate_policy(&args);
@@ -197,6 +231,7 @@ mod tests {
         let args = ValidatePolicyArgs {
             path: std::path::Path::new("/nonexistent/path").to_path_buf(),
             verbose: false,
+            lint: false,
         };
 
         let result = validate_policy(&args);
@@ -222,12 +257,32 @@ mod tests {
         let args = ValidatePolicyArgs {
             path: temp_dir.path().to_path_buf(),
             verbose: false,
+            lint: false,
         };
 
         let result = validate_policy(&args);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_validate_policy_lint_warns_without_failing() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("dup.policy"),
+            "WHEN node.vendr == \"cisco\" THEN ASSERT node.version IS \"15.1\"\n\
+             WHEN node.vendr == \"cisco\" THEN ASSERT node.version IS \"15.1\"",
+        )
+        .unwrap();
+
+        let args = ValidatePolicyArgs {
+            path: temp_dir.path().to_path_buf(),
+            verbose: false,
+            lint: true,
+        };
+
+        assert!(validate_policy(&args).is_ok());
+    }
+
     #[test]
     fn test_validate_policy_args_creation() {
         let path = std::path::Path::new("/test/path").to_path_buf();
@@ -235,6 +290,7 @@ mod tests {
         let args = ValidatePolicyArgs {
             path: path.clone(),
             verbose: true,
+            lint: false,
         };
 
         assert_eq!(args.path, path);
@@ -243,6 +299,7 @@ mod tests {
         let args_non_verbose = ValidatePolicyArgs {
             path: path.clone(),
             verbose: false,
+            lint: false,
         };
 
         assert_eq!(args_non_verbose.path, path);
@@ -266,6 +323,7 @@ mod tests {
         let args = ValidatePolicyArgs {
             path: temp_file.path().to_path_buf(),
             verbose: true,
+            lint: false,
         };
 
         let result = validate_policy(&args);
@@ -0,0 +1,227 @@
+/// Polling pause, shard, adjustment, and context commands
+use anyhow::Result;
+use chrono::{TimeDelta, Utc};
+use clap::{Args, Subcommand};
+use unet_core::datastore::DataStore;
+use unet_core::snmp::adjustments::list_adjustments;
+use unet_core::snmp::contexts::{list_polling_contexts, polling_contexts, set_polling_contexts};
+use unet_core::snmp::pauses::{
+    PauseScope, PollingPause, active_pauses, parse_duration, pause_polling, resume_polling,
+};
+use unet_core::snmp::shards::shard_status;
+use uuid::Uuid;
+
+use crate::resolve;
+
+#[derive(Subcommand)]
+pub enum PollingCommands {
+    /// Pause polling of a node, a location, or everything
+    Pause(PauseArgs),
+    /// Remove a pause
+    Resume(ResumeArgs),
+    /// List pauses in effect
+    Pauses,
+    /// List the servers sharing polling and the shards each holds
+    Shards,
+    /// List polling settings changed by policy rules
+    Adjustments,
+    /// Show or set the logical instances, such as VRFs, polled on a node
+    Contexts(ContextsArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct PauseArgs {
+    /// Location name, path, or ID; nodes in sub-locations are paused too
+    #[arg(long, conflicts_with = "node")]
+    pub location: Option<String>,
+    /// Node name, FQDN, or ID
+    #[arg(long)]
+    pub node: Option<String>,
+    /// How long to pause, e.g. 30m, 2h, or 1d; omit to pause until resumed
+    #[arg(long, value_parser = parse_duration)]
+    pub duration: Option<TimeDelta>,
+    /// Why polling is paused
+    #[arg(long)]
+    pub reason: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct ResumeArgs {
+    /// Pause ID
+    pub id: Uuid,
+}
+
+#[derive(Args, Debug)]
+pub struct ContextsArgs {
+    /// Node name, FQDN, or ID; omit to list the contexts of every node
+    pub node: Option<String>,
+    /// Context names to poll, replacing the node's current ones
+    #[arg(
+        long,
+        value_delimiter = ',',
+        requires = "node",
+        conflicts_with = "clear"
+    )]
+    pub set: Option<Vec<String>>,
+    /// Stop polling every context, leaving only the default instance
+    #[arg(long, requires = "node")]
+    pub clear: bool,
+}
+
+/// Execute polling pause subcommands.
+///
+/// # Errors
+/// Returns an error if the location or node cannot be found, the pause does
+/// not exist, a context name is invalid, datastore operations fail, or output
+/// formatting fails.
+pub async fn execute(
+    command: PollingCommands,
+    datastore: &dyn DataStore,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    match command {
+        PollingCommands::Pause(args) => {
+            let scope = match (&args.location, &args.node) {
+                (Some(location), _) => {
+                    let id = resolve::location(datastore, location, false).await?;
+                    PauseScope::Location(datastore.get_location_required(&id).await?.id)
+                }
+                (None, Some(node)) => {
+                    let id = resolve::node(datastore, node, false).await?;
+                    PauseScope::Node(datastore.get_node_required(&id).await?.id)
+                }
+                (None, None) => PauseScope::Global,
+            };
+            let pause = PollingPause::new(scope, args.reason, args.duration)?;
+            pause_polling(datastore, &pause).await?;
+            crate::commands::print_output(&pause, output_format)
+        }
+        PollingCommands::Resume(args) => {
+            resume_polling(datastore, args.id).await?;
+            let output = serde_json::json!({
+                "message": "Polling pause removed",
+                "id": args.id,
+            });
+            crate::commands::print_output(&output, output_format)
+        }
+        PollingCommands::Pauses => {
+            let pauses = active_pauses(datastore, Utc::now()).await?;
+            crate::commands::print_output(&pauses, output_format)
+        }
+        PollingCommands::Shards => {
+            let status = shard_status(datastore, Utc::now()).await?;
+            crate::commands::print_output(&status, output_format)
+        }
+        PollingCommands::Adjustments => {
+            let adjustments = list_adjustments(datastore).await?;
+            crate::commands::print_output(&adjustments, output_format)
+        }
+        PollingCommands::Contexts(args) => contexts(args, datastore, output_format).await,
+    }
+}
+
+async fn contexts(
+    args: ContextsArgs,
+    datastore: &dyn DataStore,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    let Some(node) = &args.node else {
+        let contexts = list_polling_contexts(datastore).await?;
+        return crate::commands::print_output(&contexts, output_format);
+    };
+    let id = resolve::node(datastore, node, false).await?;
+    let node = datastore.get_node_required(&id).await?;
+    let contexts = match (args.set, args.clear) {
+        (Some(names), _) => set_polling_contexts(datastore, node.id, names).await?,
+        (None, true) => set_polling_contexts(datastore, node.id, Vec::new()).await?,
+        (None, false) => polling_contexts(datastore, node.id).await?,
+    };
+    let output = serde_json::json!({
+        "node_id": node.id,
+        "node_name": node.name,
+        "contexts": contexts,
+    });
+    crate::commands::print_output(&output, output_format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unet_core::datastore::MockDataStore;
+
+    #[tokio::test]
+    async fn test_global_pause_with_duration() {
+        let mut mock = MockDataStore::new();
+        mock.expect_put_setting()
+            .withf(|namespace, _, value| {
+                namespace == "polling_pauses"
+                    && value["scope"]["type"] == "global"
+                    && value["reason"] == "core upgrade"
+                    && !value["expires_at"].is_null()
+            })
+            .times(1)
+            .returning(|_, _, _| Box::pin(async { Ok(()) }));
+
+        let command = PollingCommands::Pause(PauseArgs {
+            location: None,
+            node: None,
+            duration: Some(TimeDelta::hours(2)),
+            reason: Some("core upgrade".to_string()),
+        });
+        let result = execute(command, &mock, crate::OutputFormat::Json).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_pause_unknown_node_fails() {
+        let mut mock = MockDataStore::new();
+        mock.expect_get_setting()
+            .returning(|_, _| Box::pin(async { Ok(None) }));
+        mock.expect_search_nodes_by_name()
+            .returning(|_| Box::pin(async { Ok(Vec::new()) }));
+        mock.expect_put_setting().times(0);
+
+        let command = PollingCommands::Pause(PauseArgs {
+            location: None,
+            node: Some("edge-9".to_string()),
+            duration: None,
+            reason: None,
+        });
+        let result = execute(command, &mock, crate::OutputFormat::Json).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_set_contexts_on_node() {
+        use unet_core::models::{DeviceRole, Node, Vendor};
+
+        let node = Node::new(
+            "edge-1".to_string(),
+            "example.com".to_string(),
+            Vendor::Cisco,
+            DeviceRole::Router,
+        );
+        let node_id = node.id;
+        let mut mock = MockDataStore::new();
+        mock.expect_get_node().returning(move |_| {
+            let node = node.clone();
+            Box::pin(async move { Ok(Some(node)) })
+        });
+        mock.expect_put_setting()
+            .withf(move |namespace, key, value| {
+                namespace == "polling_contexts"
+                    && key == node_id.to_string()
+                    && *value == serde_json::json!(["vrf-blue", "vrf-red"])
+            })
+            .times(1)
+            .returning(|_, _, _| Box::pin(async { Ok(()) }));
+
+        let command = PollingCommands::Contexts(ContextsArgs {
+            node: Some(node_id.to_string()),
+            set: Some(vec!["vrf-red".to_string(), "vrf-blue".to_string()]),
+            clear: false,
+        });
+        let result = execute(command, &mock, crate::OutputFormat::Json).await;
+        assert!(result.is_ok());
+    }
+}
@@ -0,0 +1,366 @@
+/// Fleet report commands
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use std::path::PathBuf;
+use unet_core::datastore::DataStore;
+use unet_core::reports::capacity::{DEFAULT_WINDOW_DAYS, capacity_report};
+use unet_core::reports::custom::{
+    ReportFormat, ReportTemplate, delete_template, latest_render, list_templates, run_report,
+    save_template,
+};
+use unet_core::reports::firmware::{
+    FirmwareStatus, FirmwareTarget, TargetScope, delete_target, firmware_report, list_targets,
+    save_target as save_firmware_target,
+};
+use unet_core::reports::forecast::{Method, forecast_report};
+
+use crate::confirm::{Confirmation, confirm};
+
+#[derive(Subcommand)]
+pub enum ReportCommands {
+    /// Report node OS versions against the target-version matrix
+    Firmware(FirmwareReportArgs),
+    /// Manage the firmware target-version matrix
+    #[command(subcommand)]
+    Targets(TargetCommands),
+    /// Report link capacity and utilization between pairs of sites
+    Capacity(CapacityReportArgs),
+    /// Project when each link will reach 70 and 90 percent utilization
+    Forecast(ForecastReportArgs),
+    /// Manage and render custom report templates
+    #[command(subcommand)]
+    Custom(CustomCommands),
+}
+
+#[derive(Subcommand)]
+pub enum TargetCommands {
+    /// List target versions
+    List,
+    /// Set the target version of a model or role
+    Set(SetTargetArgs),
+    /// Remove the target version of a model or role
+    Delete(DeleteTargetArgs),
+}
+
+#[derive(Subcommand)]
+pub enum CustomCommands {
+    /// List custom report templates
+    List,
+    /// Create or replace a custom report template
+    Set(SetCustomArgs),
+    /// Remove a custom report template and its latest rendering
+    Delete(DeleteCustomArgs),
+    /// Render a custom report and print it
+    Render(RenderCustomArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct FirmwareReportArgs {
+    /// Only list groups in the upgrade backlog (outdated or ahead)
+    #[arg(long)]
+    pub backlog: bool,
+    /// Only report on the current members of this node group
+    #[arg(long)]
+    pub group: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct CapacityReportArgs {
+    /// Days of utilization to summarize
+    #[arg(
+        long,
+        default_value_t = DEFAULT_WINDOW_DAYS,
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    pub days: u32,
+    /// Print the report as CSV
+    #[arg(long)]
+    pub csv: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct ForecastReportArgs {
+    /// Days of utilization to fit the projection to
+    #[arg(
+        long,
+        default_value_t = DEFAULT_WINDOW_DAYS,
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    pub days: u32,
+    /// Projection method (linear, holt-winters)
+    #[arg(long, default_value = "linear")]
+    pub method: Method,
+    /// Print the report as CSV
+    #[arg(long)]
+    pub csv: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct SetTargetArgs {
+    /// Target scope (model, role)
+    pub scope: TargetScope,
+    /// Model or role name
+    pub target: String,
+    /// Version nodes should run
+    pub version: String,
+    /// Another accepted version; repeat for several
+    #[arg(long = "allow", value_name = "VERSION")]
+    pub allowed: Vec<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct DeleteTargetArgs {
+    /// Target scope (model, role)
+    pub scope: TargetScope,
+    /// Model or role name
+    pub target: String,
+    /// Skip confirmation prompt
+    #[arg(short = 'y', long)]
+    pub yes: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct SetCustomArgs {
+    /// Report name (letters, digits, '-', '_')
+    pub name: String,
+    /// `MiniJinja` template file
+    pub file: PathBuf,
+    /// Output format (html, markdown)
+    #[arg(long, default_value = "markdown")]
+    pub format: ReportFormat,
+    /// What the report is for
+    #[arg(long)]
+    pub description: Option<String>,
+    /// Also render the report on a schedule, every this many minutes
+    #[arg(long, value_name = "MINUTES")]
+    pub every: Option<u32>,
+}
+
+#[derive(Args, Debug)]
+pub struct DeleteCustomArgs {
+    /// Report name
+    pub name: String,
+    /// Skip confirmation prompt
+    #[arg(short = 'y', long)]
+    pub yes: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct RenderCustomArgs {
+    /// Report name
+    pub name: String,
+    /// Print the latest rendering instead of rendering again
+    #[arg(long)]
+    pub latest: bool,
+}
+
+/// Execute report subcommands.
+///
+/// # Errors
+/// Returns an error if a target is invalid, datastore operations fail, or
+/// output formatting fails.
+pub async fn execute(
+    command: ReportCommands,
+    datastore: &dyn DataStore,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    match command {
+        ReportCommands::Firmware(args) => {
+            let mut report = firmware_report(datastore, args.group.as_deref()).await?;
+            if args.backlog {
+                report.groups.retain(|group| {
+                    matches!(
+                        group.status,
+                        FirmwareStatus::Outdated | FirmwareStatus::Ahead
+                    )
+                });
+            }
+            crate::commands::print_output(&report, output_format)
+        }
+        ReportCommands::Capacity(args) => {
+            let report = capacity_report(datastore, args.days, chrono::Utc::now()).await?;
+            if args.csv {
+                print!("{}", report.to_csv());
+                return Ok(());
+            }
+            crate::commands::print_output(&report, output_format)
+        }
+        ReportCommands::Forecast(args) => {
+            let report =
+                forecast_report(datastore, args.days, args.method, chrono::Utc::now()).await?;
+            if args.csv {
+                print!("{}", report.to_csv());
+                return Ok(());
+            }
+            crate::commands::print_output(&report, output_format)
+        }
+        ReportCommands::Targets(TargetCommands::List) => {
+            crate::commands::print_output(&list_targets(datastore).await?, output_format)
+        }
+        ReportCommands::Targets(TargetCommands::Set(args)) => {
+            let target =
+                FirmwareTarget::new(args.scope, &args.target, &args.version, args.allowed)?;
+            save_firmware_target(datastore, &target).await?;
+            crate::commands::print_output(&target, output_format)
+        }
+        ReportCommands::Targets(TargetCommands::Delete(args)) => {
+            let confirmation = Confirmation::new("Remove firmware target")
+                .affects(format!("{}:{}", args.scope, args.target));
+            if !confirm(args.yes, &confirmation)? {
+                return Ok(());
+            }
+            delete_target(datastore, args.scope, &args.target).await?;
+            let output = serde_json::json!({
+                "message": "Firmware target removed",
+                "scope": args.scope,
+                "target": args.target,
+            });
+            crate::commands::print_output(&output, output_format)
+        }
+        ReportCommands::Custom(command) => execute_custom(command, datastore, output_format).await,
+    }
+}
+
+async fn execute_custom(
+    command: CustomCommands,
+    datastore: &dyn DataStore,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    match command {
+        CustomCommands::List => {
+            crate::commands::print_output(&list_templates(datastore).await?, output_format)
+        }
+        CustomCommands::Set(args) => {
+            let report = ReportTemplate::new(
+                &args.name,
+                args.format,
+                std::fs::read_to_string(&args.file)?,
+                args.description,
+                args.every,
+            )?;
+            save_template(datastore, &report).await?;
+            crate::commands::print_output(&report, output_format)
+        }
+        CustomCommands::Delete(args) => {
+            let confirmation = Confirmation::new("Remove report template").affects(&args.name);
+            if !confirm(args.yes, &confirmation)? {
+                return Ok(());
+            }
+            delete_template(datastore, &args.name).await?;
+            let output = serde_json::json!({
+                "message": "Report template removed",
+                "name": args.name,
+            });
+            crate::commands::print_output(&output, output_format)
+        }
+        CustomCommands::Render(args) => {
+            let rendered = if args.latest {
+                latest_render(datastore, &args.name)
+                    .await?
+                    .ok_or_else(|| anyhow::anyhow!("Report {} has not been rendered", args.name))?
+            } else {
+                run_report(datastore, &args.name, chrono::Utc::now()).await?
+            };
+            print!("{}", rendered.content);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unet_core::datastore::{MockDataStore, PagedResult};
+
+    #[tokio::test]
+    async fn test_set_stores_normalized_target() {
+        let mut mock = MockDataStore::new();
+        mock.expect_put_setting()
+            .withf(|namespace, key, value| {
+                namespace == "firmware_targets"
+                    && key == "role:router"
+                    && value["version"] == "17.9.4"
+                    && value["allowed"][0] == "17.6.5"
+            })
+            .times(1)
+            .returning(|_, _, _| Box::pin(async { Ok(()) }));
+
+        let command = ReportCommands::Targets(TargetCommands::Set(SetTargetArgs {
+            scope: TargetScope::Role,
+            target: "Router".to_string(),
+            version: "17.9.4".to_string(),
+            allowed: vec!["17.6.5".to_string()],
+        }));
+        let result = execute(command, &mock, crate::OutputFormat::Json).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_custom_set_stores_template_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("noc.md.j2");
+        std::fs::write(&file, "# {{ report.name }}").unwrap();
+        let mut mock = MockDataStore::new();
+        mock.expect_put_setting()
+            .withf(|namespace, key, value| {
+                namespace == "report_templates"
+                    && key == "noc"
+                    && value["format"] == "markdown"
+                    && value["interval_minutes"] == 1440
+            })
+            .times(1)
+            .returning(|_, _, _| Box::pin(async { Ok(()) }));
+
+        let command = ReportCommands::Custom(CustomCommands::Set(SetCustomArgs {
+            name: "noc".to_string(),
+            file,
+            format: ReportFormat::Markdown,
+            description: None,
+            every: Some(1440),
+        }));
+        let result = execute(command, &mock, crate::OutputFormat::Json).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_capacity_reads_inventory_and_utilization() {
+        let mut mock = MockDataStore::new();
+        mock.expect_list_nodes()
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(PagedResult::new(Vec::new(), 0, None)) }));
+        mock.expect_list_locations()
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(PagedResult::new(Vec::new(), 0, None)) }));
+        mock.expect_list_links()
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(PagedResult::new(Vec::new(), 0, None)) }));
+        mock.expect_list_settings()
+            .withf(|namespace| namespace == "link_utilization")
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(Vec::new()) }));
+
+        let command = ReportCommands::Capacity(CapacityReportArgs { days: 7, csv: true });
+        let result = execute(command, &mock, crate::OutputFormat::Json).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_forecast_reads_links_and_utilization() {
+        let mut mock = MockDataStore::new();
+        mock.expect_list_links()
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(PagedResult::new(Vec::new(), 0, None)) }));
+        mock.expect_list_settings()
+            .withf(|namespace| namespace == "link_utilization")
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(Vec::new()) }));
+
+        let command = ReportCommands::Forecast(ForecastReportArgs {
+            days: 30,
+            method: Method::HoltWinters,
+            csv: false,
+        });
+        let result = execute(command, &mock, crate::OutputFormat::Json).await;
+        assert!(result.is_ok());
+    }
+}
@@ -0,0 +1,443 @@
+/// Secrets export and import for moving between configuration backends
+///
+/// Secrets are the configuration keys listed in [`SECRET_KEYS`], whether they
+/// come from the configuration file or `UNET_*` environment variables. An
+/// export always records which were configured; with `--include-values` the
+/// values are encrypted for an age recipient so the bundle can be carried to
+/// another environment and written out as environment variables there.
+///
+/// `import --from-env-prefix` and `import --from-csv` instead bootstrap device
+/// credentials, writing them to the nodes' `secrets.encrypted_fields` in the
+/// local database. `seal` encrypts a value with `secrets.master_key` for
+/// configuration keys read through the secret manager, such as
+/// `database.encryption_key`.
+use anyhow::{Context as _, Result, bail};
+use chrono::{TimeDelta, Utc};
+use clap::{ArgGroup, Args, Subcommand};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use tracing::info;
+use unet_core::config::Config;
+use unet_core::datastore::DataStore;
+use unet_core::secrets::SecretManager;
+use unet_core::snmp::pauses::parse_duration;
+
+use super::admin::SECRET_KEYS;
+
+mod bundle;
+mod seed;
+
+use bundle::{SecretEntry, SecretsBundle, parse_identity, parse_recipient};
+use seed::SeedStatus;
+
+#[derive(Subcommand)]
+pub enum SecretsCommands {
+    /// List which secrets are configured
+    List,
+    /// Write secrets metadata, and optionally encrypted values, to a bundle file
+    Export(ExportSecretsArgs),
+    /// Verify a secrets bundle and write its values as environment variables,
+    /// or import device credentials from the environment or a CSV file
+    Import(ImportSecretsArgs),
+    /// Encrypt a value read from standard input with `secrets.master_key`,
+    /// e.g. for `database.encryption_key`
+    Seal,
+}
+
+impl SecretsCommands {
+    /// Whether the command writes device credentials to nodes, and so needs
+    /// the datastore
+    pub const fn seeds_nodes(&self) -> bool {
+        matches!(self, Self::Import(args) if args.seeds_nodes())
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct ExportSecretsArgs {
+    /// Bundle file to write
+    #[arg(short, long)]
+    pub output: PathBuf,
+    /// Include secret values, encrypted for `--re-encrypt-for`
+    #[arg(long, requires = "re_encrypt_for")]
+    pub include_values: bool,
+    /// age recipient public key (`age1...`) to encrypt values for
+    #[arg(long, value_name = "PUBKEY", requires = "include_values")]
+    pub re_encrypt_for: Option<String>,
+    /// How long the exported secrets stay importable, e.g. 2h or 7d
+    #[arg(long, value_parser = parse_duration)]
+    pub expires_in: Option<TimeDelta>,
+    /// Overwrite an existing bundle file
+    #[arg(long)]
+    pub force: bool,
+}
+
+#[derive(Args, Debug)]
+#[command(group(
+    ArgGroup::new("source")
+        .required(true)
+        .args(["from", "from_env_prefix", "from_csv"])
+))]
+pub struct ImportSecretsArgs {
+    /// Bundle file to read
+    #[arg(short, long)]
+    pub from: Option<PathBuf>,
+    /// Import device credentials from variables named
+    /// `<PREFIX><NODE>__<FIELD>`, e.g. `UNET_SEED_EDGE_01__SNMP__COMMUNITY`
+    #[arg(long, value_name = "PREFIX")]
+    pub from_env_prefix: Option<String>,
+    /// Import device credentials from a CSV file of `node,field,value` rows
+    #[arg(long, value_name = "FILE")]
+    pub from_csv: Option<PathBuf>,
+    /// age identity file able to decrypt the values
+    #[arg(long, requires = "from")]
+    pub identity: Option<PathBuf>,
+    /// Environment file to write the values to as `UNET_*` variables
+    #[arg(long, requires = "identity")]
+    pub env_file: Option<PathBuf>,
+    /// Overwrite an existing environment file
+    #[arg(long)]
+    pub force: bool,
+}
+
+impl ImportSecretsArgs {
+    /// Whether device credentials are imported rather than a bundle
+    pub const fn seeds_nodes(&self) -> bool {
+        self.from_env_prefix.is_some() || self.from_csv.is_some()
+    }
+}
+
+/// Returns each secret key with its configured value, if any
+fn secret_values(config: &Config) -> Result<Vec<(&'static str, Option<String>)>> {
+    let value = serde_json::to_value(config)?;
+    Ok(SECRET_KEYS
+        .iter()
+        .map(|key| {
+            let pointer = format!("/{}", key.replace('.', "/"));
+            let secret = value
+                .pointer(&pointer)
+                .and_then(serde_json::Value::as_str)
+                .map(str::to_string);
+            (*key, secret)
+        })
+        .collect())
+}
+
+/// Environment variable the configuration reads `key` from
+fn env_var(key: &str) -> String {
+    format!("UNET_{}", key.to_uppercase().replace('.', "__"))
+}
+
+/// Quotes a value for an environment file
+fn env_value(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+fn list(config: &Config, output_format: crate::OutputFormat) -> Result<()> {
+    let secrets: Vec<SecretEntry> = secret_values(config)?
+        .into_iter()
+        .map(|(key, value)| SecretEntry {
+            key: key.to_string(),
+            configured: value.is_some(),
+            expires_at: None,
+        })
+        .collect();
+    crate::commands::print_output(&secrets, output_format)
+}
+
+fn export(
+    args: &ExportSecretsArgs,
+    config: &Config,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    if args.output.exists() && !args.force {
+        bail!(
+            "{} already exists. Use --force to overwrite",
+            args.output.display()
+        );
+    }
+    let recipient = args
+        .re_encrypt_for
+        .as_deref()
+        .map(parse_recipient)
+        .transpose()?;
+    let expires_at = args.expires_in.map(|expires_in| Utc::now() + expires_in);
+
+    let mut secrets = Vec::new();
+    let mut values = BTreeMap::new();
+    for (key, value) in secret_values(config)? {
+        secrets.push(SecretEntry {
+            key: key.to_string(),
+            configured: value.is_some(),
+            expires_at: value.as_ref().and(expires_at),
+        });
+        if let Some(value) = value.filter(|_| args.include_values) {
+            values.insert(key.to_string(), value);
+        }
+    }
+    let exported = values.len();
+    let bundle = SecretsBundle::seal(secrets, values, recipient.as_ref())?;
+    std::fs::write(&args.output, serde_json::to_string_pretty(&bundle)?)
+        .with_context(|| format!("Failed to write {}", args.output.display()))?;
+    info!("Wrote secrets bundle to {}", args.output.display());
+
+    crate::commands::print_output(
+        &serde_json::json!({
+            "bundle": args.output,
+            "secrets": bundle.secrets,
+            "values_exported": exported,
+            "recipient": bundle.recipient,
+        }),
+        output_format,
+    )
+}
+
+fn import(args: &ImportSecretsArgs, output_format: crate::OutputFormat) -> Result<()> {
+    let Some(from) = &args.from else {
+        bail!("Device credentials are imported into the local database");
+    };
+    let content = std::fs::read_to_string(from)
+        .with_context(|| format!("Failed to read {}", from.display()))?;
+    let bundle: SecretsBundle = serde_json::from_str(&content)
+        .with_context(|| format!("{} is not a secrets bundle", from.display()))?;
+    bundle.verify(Utc::now())?;
+
+    let identity = args
+        .identity
+        .as_ref()
+        .map(|path| {
+            std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))
+                .and_then(|text| parse_identity(&text))
+        })
+        .transpose()?;
+    let values = identity
+        .as_ref()
+        .map(|identity| bundle.open(identity))
+        .transpose()?
+        .unwrap_or_default();
+
+    if let Some(path) = &args.env_file {
+        if path.exists() && !args.force {
+            bail!(
+                "{} already exists. Use --force to overwrite",
+                path.display()
+            );
+        }
+        let lines: String = values
+            .iter()
+            .map(|(key, value)| format!("{}={}\n", env_var(key), env_value(value)))
+            .collect();
+        std::fs::write(path, lines)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        info!("Wrote {} secrets to {}", values.len(), path.display());
+    }
+
+    crate::commands::print_output(
+        &serde_json::json!({
+            "bundle": from,
+            "verified": true,
+            "secrets": bundle.secrets,
+            "values_decrypted": values.len(),
+            "env_file": args.env_file,
+        }),
+        output_format,
+    )
+}
+
+/// Encrypts `value` with the configured master key
+fn seal_value(config: &Config, value: &str) -> Result<String> {
+    let master_key = config
+        .secrets
+        .master_key
+        .as_deref()
+        .context("No master key configured; set secrets.master_key")?;
+    if value.is_empty() {
+        bail!("Nothing to seal; write the value to standard input");
+    }
+    let sealed = SecretManager::new(master_key)?.encrypt(&Value::String(value.to_string()))?;
+    Ok(sealed.as_str().unwrap_or_default().to_string())
+}
+
+fn seal(config: &Config, output_format: crate::OutputFormat) -> Result<()> {
+    let mut value = String::new();
+    std::io::stdin()
+        .read_line(&mut value)
+        .context("Failed to read the value from standard input")?;
+    let sealed = seal_value(config, value.trim_end_matches(['\r', '\n']))?;
+    crate::commands::print_output(&serde_json::json!({ "sealed": sealed }), output_format)
+}
+
+/// Import device credentials from `--from-env-prefix` or `--from-csv` into
+/// nodes' encrypted fields, reporting each entry
+///
+/// # Errors
+/// Returns an error if the command does not import credentials, no fields
+/// are encrypted, the source cannot be read, or any entry fails.
+pub async fn seed_nodes(
+    command: &SecretsCommands,
+    datastore: &dyn DataStore,
+    config: &Config,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    let SecretsCommands::Import(args) = command else {
+        bail!("secrets commands run before the datastore is opened");
+    };
+    let (source, entries) = if let Some(prefix) = &args.from_env_prefix {
+        (
+            format!("env:{prefix}"),
+            seed::from_env(prefix, std::env::vars()),
+        )
+    } else if let Some(path) = &args.from_csv {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let file = path.display().to_string();
+        let entries =
+            seed::parse_csv(&content, &file).with_context(|| format!("Failed to parse {file}"))?;
+        (file, entries)
+    } else {
+        bail!("secrets commands run before the datastore is opened");
+    };
+    if entries.is_empty() {
+        bail!("No device credentials found in {source}");
+    }
+    if config.secrets.encrypted_fields.is_empty() {
+        bail!(
+            "No fields are encrypted; list the credential paths in secrets.encrypted_fields \
+             and set secrets.master_key before importing"
+        );
+    }
+
+    let results = seed::seed(
+        datastore,
+        &entries,
+        &config.secrets.encrypted_fields,
+        Utc::now(),
+    )
+    .await?;
+    let count = |status| results.iter().filter(|r| r.status == status).count();
+    let (imported, unchanged, failed) = (
+        count(SeedStatus::Imported),
+        count(SeedStatus::Unchanged),
+        count(SeedStatus::Failed),
+    );
+    info!("Imported {imported} device credentials from {source}");
+
+    crate::commands::print_output(
+        &serde_json::json!({
+            "source": source,
+            "imported": imported,
+            "unchanged": unchanged,
+            "failed": failed,
+            "entries": results,
+        }),
+        output_format,
+    )?;
+    if failed > 0 {
+        bail!("Imported {imported} device credentials; {failed} failed");
+    }
+    Ok(())
+}
+
+/// Execute secrets subcommands
+///
+/// # Errors
+/// Returns an error if a bundle cannot be read, written, verified, or
+/// decrypted, a value cannot be sealed, or output formatting fails.
+pub fn execute(
+    command: &SecretsCommands,
+    config: &Config,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    match command {
+        SecretsCommands::List => list(config, output_format),
+        SecretsCommands::Export(args) => export(args, config, output_format),
+        SecretsCommands::Import(args) => import(args, output_format),
+        SecretsCommands::Seal => seal(config, output_format),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use age::secrecy::ExposeSecret as _;
+    use age::x25519::Identity;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_env_var_names_follow_config_environment() {
+        assert_eq!(
+            env_var("auth.oidc.client_secret"),
+            "UNET_AUTH__OIDC__CLIENT_SECRET"
+        );
+        assert_eq!(env_value("it's"), r"'it'\''s'");
+    }
+
+    #[test]
+    fn test_export_import_round_trip_writes_env_file() {
+        let dir = TempDir::new().unwrap();
+        let identity = Identity::generate();
+        let identity_path = dir.path().join("key.txt");
+        std::fs::write(
+            &identity_path,
+            format!("# test key\n{}\n", identity.to_string().expose_secret()),
+        )
+        .unwrap();
+        let mut config = Config::default();
+        config.snmp.community = "s3cret".to_string();
+
+        let export_args = ExportSecretsArgs {
+            output: dir.path().join("secrets.json"),
+            include_values: true,
+            re_encrypt_for: Some(identity.to_public().to_string()),
+            expires_in: Some(TimeDelta::hours(1)),
+            force: false,
+        };
+        export(&export_args, &config, crate::OutputFormat::Json).unwrap();
+        let written = std::fs::read_to_string(&export_args.output).unwrap();
+        assert!(!written.contains("s3cret"));
+
+        let import_args = ImportSecretsArgs {
+            from: Some(export_args.output),
+            from_env_prefix: None,
+            from_csv: None,
+            identity: Some(identity_path),
+            env_file: Some(dir.path().join("unet.env")),
+            force: false,
+        };
+        import(&import_args, crate::OutputFormat::Json).unwrap();
+        let env = std::fs::read_to_string(dir.path().join("unet.env")).unwrap();
+        assert!(env.contains("UNET_SNMP__COMMUNITY='s3cret'"));
+    }
+
+    #[test]
+    fn test_sealed_value_opens_as_database_key() {
+        let mut config = Config::default();
+        assert!(seal_value(&config, "change-me").is_err());
+
+        config.secrets.master_key = Some("master".to_string());
+        let sealed = seal_value(&config, "change-me").unwrap();
+        assert!(!sealed.contains("change-me"));
+        config.database.encryption_key = Some(sealed);
+        assert_eq!(
+            config.database_encryption_key().unwrap().as_deref(),
+            Some("change-me")
+        );
+    }
+
+    #[test]
+    fn test_export_refuses_to_overwrite() {
+        let existing = tempfile::NamedTempFile::new().unwrap();
+        let args = ExportSecretsArgs {
+            output: existing.path().to_path_buf(),
+            include_values: false,
+            re_encrypt_for: None,
+            expires_in: None,
+            force: false,
+        };
+
+        let error = export(&args, &Config::default(), crate::OutputFormat::Json).unwrap_err();
+
+        assert!(error.to_string().contains("already exists"));
+    }
+}
@@ -0,0 +1,223 @@
+/// Secrets bundle layout, integrity digest, and value encryption
+///
+/// Secret values are only ever written encrypted to an age X25519 recipient.
+/// The encrypted payload repeats the plaintext metadata so that expiry times
+/// cannot be edited without detection, and a SHA-256 digest over the
+/// metadata and payload catches truncated or corrupted files.
+use age::x25519::{Identity, Recipient};
+use anyhow::{Result, anyhow, bail};
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest as _, Sha256};
+use std::collections::BTreeMap;
+
+/// Identifies secrets bundle files
+const SECRETS_FORMAT: &str = "unet-secrets";
+/// Secrets bundle layout version written and read by this build
+pub const SECRETS_BUNDLE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecretEntry {
+    /// Configuration key, e.g. `snmp.community`
+    pub key: String,
+    pub configured: bool,
+    /// After this time the secret must not be imported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl SecretEntry {
+    /// Whether the secret has expired at `now`
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SecretsBundle {
+    pub format: String,
+    pub version: u32,
+    pub created_at: DateTime<Utc>,
+    pub secrets: Vec<SecretEntry>,
+    /// age recipient the values were encrypted for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recipient: Option<String>,
+    /// Base64 age ciphertext of the values and a copy of `secrets`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload: Option<String>,
+    /// Hex SHA-256 of `secrets` and `payload`
+    pub digest: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Payload {
+    secrets: Vec<SecretEntry>,
+    values: BTreeMap<String, String>,
+}
+
+/// Parses an age recipient such as `age1...`
+pub fn parse_recipient(text: &str) -> Result<Recipient> {
+    text.trim()
+        .parse()
+        .map_err(|e| anyhow!("Invalid age recipient '{text}': {e}"))
+}
+
+/// Parses the first identity in an age identity file, skipping comments
+pub fn parse_identity(text: &str) -> Result<Identity> {
+    let line = text
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))
+        .ok_or_else(|| anyhow!("Identity file contains no age identity"))?;
+    line.parse()
+        .map_err(|e| anyhow!("Invalid age identity: {e}"))
+}
+
+fn digest(secrets: &[SecretEntry], payload: Option<&str>) -> Result<String> {
+    let mut hasher = Sha256::new();
+    hasher.update(serde_json::to_vec(secrets)?);
+    hasher.update(payload.unwrap_or_default());
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+impl SecretsBundle {
+    /// Builds a bundle, encrypting `values` for `recipient` when there are any
+    pub fn seal(
+        secrets: Vec<SecretEntry>,
+        values: BTreeMap<String, String>,
+        recipient: Option<&Recipient>,
+    ) -> Result<Self> {
+        let payload = match recipient {
+            Some(recipient) if !values.is_empty() => {
+                let plaintext = serde_json::to_vec(&Payload {
+                    secrets: secrets.clone(),
+                    values,
+                })?;
+                let ciphertext = age::encrypt(recipient, &plaintext)
+                    .map_err(|e| anyhow!("Failed to encrypt secret values: {e}"))?;
+                Some(STANDARD.encode(ciphertext))
+            }
+            None if !values.is_empty() => {
+                bail!("Secret values can only be exported encrypted for a recipient")
+            }
+            _ => None,
+        };
+        Ok(Self {
+            format: SECRETS_FORMAT.to_string(),
+            version: SECRETS_BUNDLE_VERSION,
+            created_at: Utc::now(),
+            digest: digest(&secrets, payload.as_deref())?,
+            recipient: payload.as_ref().and(recipient).map(ToString::to_string),
+            secrets,
+            payload,
+        })
+    }
+
+    /// Checks the bundle's format, digest, and expiry times
+    pub fn verify(&self, now: DateTime<Utc>) -> Result<()> {
+        if self.format != SECRETS_FORMAT {
+            bail!("Unknown secrets bundle format '{}'", self.format);
+        }
+        if self.version != SECRETS_BUNDLE_VERSION {
+            bail!(
+                "Secrets bundle version {} is not supported (this build reads version {SECRETS_BUNDLE_VERSION})",
+                self.version
+            );
+        }
+        if digest(&self.secrets, self.payload.as_deref())? != self.digest {
+            bail!("Secrets bundle failed its integrity check; the file is corrupt or was modified");
+        }
+        let expired: Vec<&str> = self
+            .secrets
+            .iter()
+            .filter(|secret| secret.configured && secret.is_expired(now))
+            .map(|secret| secret.key.as_str())
+            .collect();
+        if !expired.is_empty() {
+            bail!("Refusing to import expired secrets: {}", expired.join(", "));
+        }
+        Ok(())
+    }
+
+    /// Decrypts the secret values with `identity`
+    ///
+    /// Returns no values for a metadata-only bundle. Call [`Self::verify`] first.
+    pub fn open(&self, identity: &Identity) -> Result<BTreeMap<String, String>> {
+        let Some(payload) = &self.payload else {
+            return Ok(BTreeMap::new());
+        };
+        let ciphertext = STANDARD
+            .decode(payload)
+            .map_err(|e| anyhow!("Secrets bundle payload is not valid base64: {e}"))?;
+        let plaintext = age::decrypt(identity, &ciphertext)
+            .map_err(|e| anyhow!("Failed to decrypt secret values: {e}"))?;
+        let payload: Payload = serde_json::from_slice(&plaintext)?;
+        if payload.secrets != self.secrets {
+            bail!("Secrets bundle metadata does not match its encrypted payload");
+        }
+        Ok(payload.values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+
+    fn entry(key: &str, expires_at: Option<DateTime<Utc>>) -> SecretEntry {
+        SecretEntry {
+            key: key.to_string(),
+            configured: true,
+            expires_at,
+        }
+    }
+
+    #[test]
+    fn test_seal_and_open_round_trip() {
+        let identity = Identity::generate();
+        let values = BTreeMap::from([("snmp.community".to_string(), "s3cret".to_string())]);
+
+        let bundle = SecretsBundle::seal(
+            vec![entry("snmp.community", None)],
+            values.clone(),
+            Some(&identity.to_public()),
+        )
+        .unwrap();
+
+        assert!(!bundle.payload.as_deref().unwrap().contains("s3cret"));
+        bundle.verify(Utc::now()).unwrap();
+        assert_eq!(bundle.open(&identity).unwrap(), values);
+        assert!(bundle.open(&Identity::generate()).is_err());
+    }
+
+    #[test]
+    fn test_seal_requires_recipient_for_values() {
+        let values = BTreeMap::from([("auth.token".to_string(), "t".to_string())]);
+
+        assert!(SecretsBundle::seal(vec![entry("auth.token", None)], values, None).is_err());
+    }
+
+    #[test]
+    fn test_verify_rejects_tampering_and_expired_secrets() {
+        let identity = Identity::generate();
+        let values = BTreeMap::from([("auth.token".to_string(), "t".to_string())]);
+        let expires_at = Utc::now() + TimeDelta::hours(1);
+        let mut bundle = SecretsBundle::seal(
+            vec![entry("auth.token", Some(expires_at))],
+            values,
+            Some(&identity.to_public()),
+        )
+        .unwrap();
+
+        let later = expires_at + TimeDelta::seconds(1);
+        let error = bundle.verify(later).unwrap_err();
+        assert!(error.to_string().contains("expired secrets: auth.token"));
+
+        bundle.secrets[0].expires_at = Some(later + TimeDelta::days(1));
+        assert!(bundle.verify(later).is_err());
+        bundle.digest = digest(&bundle.secrets, bundle.payload.as_deref()).unwrap();
+        bundle.verify(later).unwrap();
+        assert!(bundle.open(&identity).is_err());
+    }
+}
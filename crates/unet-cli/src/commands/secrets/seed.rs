@@ -0,0 +1,400 @@
+//! Bulk import of device credentials into nodes' encrypted fields
+//!
+//! Each entry names a node, a dotted `custom_data` path listed in
+//! `secrets.encrypted_fields`, and the value to store there. Entries come
+//! from environment variables (`UNET_SEED_EDGE_01__SNMP__COMMUNITY`) or a CSV
+//! file of `node,field,value` rows. Every value written is recorded in the
+//! node's provenance as an import, with the variable or file line it came
+//! from and when.
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use unet_core::datastore::{DataStore, QueryOptions};
+use unet_core::models::Node;
+use unet_core::provenance::{self, Source};
+use uuid::Uuid;
+
+/// Columns of a credentials file, in CSV order
+const COLUMNS: [&str; 3] = ["node", "field", "value"];
+
+/// One credential to import
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SeedEntry {
+    /// Environment variable or `file:line` the entry was read from
+    pub origin: String,
+    /// Node the credential belongs to
+    pub node: NodeRef,
+    /// Dotted `custom_data` path
+    pub field: String,
+    /// Credential value
+    pub value: String,
+}
+
+/// How an entry names its node
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum NodeRef {
+    /// ID, name, or FQDN
+    Reference(String),
+    /// Name as written in an environment variable: upper case, with `_` for
+    /// `-` and `.`
+    EnvName(String),
+}
+
+impl NodeRef {
+    fn matches(&self, node: &Node) -> bool {
+        match self {
+            Self::Reference(reference) => {
+                node.id.to_string() == *reference
+                    || node.name == *reference
+                    || node.fqdn == *reference
+            }
+            Self::EnvName(name) => env_name(&node.name) == *name,
+        }
+    }
+}
+
+impl std::fmt::Display for NodeRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Reference(name) | Self::EnvName(name) => f.write_str(name),
+        }
+    }
+}
+
+/// What happened to an entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SeedStatus {
+    /// The value was written
+    Imported,
+    /// The node already had the value
+    Unchanged,
+    /// Nothing was written; see the message
+    Failed,
+}
+
+/// Outcome of one entry; never includes the value
+#[derive(Debug, Clone, Serialize)]
+pub struct SeedResult {
+    /// Environment variable or `file:line` the entry was read from
+    pub source: String,
+    /// Node as named by the entry
+    pub node: String,
+    /// Node the value was written to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_id: Option<Uuid>,
+    /// Dotted `custom_data` path
+    pub field: String,
+    /// What happened
+    pub status: SeedStatus,
+    /// When the value was written
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub imported_at: Option<DateTime<Utc>>,
+    /// Why the entry failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// A node name as it is written in an environment variable
+fn env_name(name: &str) -> String {
+    name.to_uppercase().replace(['-', '.'], "_")
+}
+
+/// Reads entries from variables named `<prefix><NODE>__<FIELD>`, where the
+/// field's path segments are also separated by `__`
+///
+/// Variables without a field, or with an empty value, are skipped.
+pub fn from_env(prefix: &str, vars: impl IntoIterator<Item = (String, String)>) -> Vec<SeedEntry> {
+    let mut entries: Vec<SeedEntry> = vars
+        .into_iter()
+        .filter(|(_, value)| !value.is_empty())
+        .filter_map(|(name, value)| {
+            let rest = name.strip_prefix(prefix)?;
+            let (node, field) = rest.split_once("__")?;
+            if node.is_empty() || field.split("__").any(str::is_empty) {
+                return None;
+            }
+            Some(SeedEntry {
+                node: NodeRef::EnvName(node.to_string()),
+                field: field.to_lowercase().replace("__", "."),
+                origin: name,
+                value,
+            })
+        })
+        .collect();
+    entries.sort();
+    entries
+}
+
+/// Parses CSV rows of `node,field,value`, naming each entry `file:line`
+///
+/// Blank lines and lines starting with `#` are ignored, and a first row that
+/// names the columns is treated as a header. The value is everything after
+/// the second comma, so it may contain commas; a value wrapped in double
+/// quotes is unwrapped.
+///
+/// # Errors
+/// Returns an error naming the line if a row has fewer than three fields or
+/// an empty one.
+pub fn parse_csv(content: &str, file: &str) -> Result<Vec<SeedEntry>> {
+    let mut entries = Vec::new();
+    let mut first = true;
+    for (line, text) in (1..).zip(content.lines()) {
+        let text = text.trim();
+        if text.is_empty() || text.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = text
+            .splitn(3, ',')
+            .map(|field| field.trim().trim_matches('"'))
+            .collect();
+        let header = first
+            && fields
+                .iter()
+                .zip(COLUMNS)
+                .all(|(field, column)| field.eq_ignore_ascii_case(column));
+        first = false;
+        if header {
+            continue;
+        }
+        if fields.len() < COLUMNS.len() {
+            return Err(anyhow!(
+                "Line {line}: expected {} fields, found {}",
+                COLUMNS.join(","),
+                fields.len()
+            ));
+        }
+        if let Some(column) = fields.iter().position(|field| field.is_empty()) {
+            return Err(anyhow!("Line {line}: {} is empty", COLUMNS[column]));
+        }
+        entries.push(SeedEntry {
+            origin: format!("{file}:{line}"),
+            node: NodeRef::Reference(fields[0].to_string()),
+            field: fields[1].to_string(),
+            value: fields[2].to_string(),
+        });
+    }
+    Ok(entries)
+}
+
+/// Writes each entry's value to its node, continuing past failed entries
+///
+/// Only fields listed in `encrypted_fields` are written, so values are never
+/// stored in plaintext.
+///
+/// # Errors
+/// Returns an error if the nodes cannot be listed.
+pub async fn seed(
+    datastore: &dyn DataStore,
+    entries: &[SeedEntry],
+    encrypted_fields: &[String],
+    now: DateTime<Utc>,
+) -> Result<Vec<SeedResult>> {
+    let mut nodes = datastore.list_nodes(&QueryOptions::default()).await?.items;
+    let mut results = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let mut result = SeedResult {
+            source: entry.origin.clone(),
+            node: entry.node.to_string(),
+            node_id: None,
+            field: entry.field.clone(),
+            status: SeedStatus::Failed,
+            imported_at: None,
+            message: None,
+        };
+        match seed_entry(datastore, &mut nodes, entry, encrypted_fields, now).await {
+            Ok((node_id, written)) => {
+                result.node_id = Some(node_id);
+                result.status = if written {
+                    SeedStatus::Imported
+                } else {
+                    SeedStatus::Unchanged
+                };
+                result.imported_at = written.then_some(now);
+            }
+            Err(message) => result.message = Some(message),
+        }
+        results.push(result);
+    }
+    Ok(results)
+}
+
+/// Writes one entry, returning the node and whether its value changed
+///
+/// The written node replaces its copy in `nodes`, so later entries for the
+/// same node build on it.
+async fn seed_entry(
+    datastore: &dyn DataStore,
+    nodes: &mut [Node],
+    entry: &SeedEntry,
+    encrypted_fields: &[String],
+    now: DateTime<Utc>,
+) -> Result<(Uuid, bool), String> {
+    if !encrypted_fields.contains(&entry.field) {
+        return Err(format!(
+            "custom_data.{} is not listed in secrets.encrypted_fields",
+            entry.field
+        ));
+    }
+    let matches: Vec<usize> = (0..nodes.len())
+        .filter(|index| entry.node.matches(&nodes[*index]))
+        .collect();
+    let node = match matches.as_slice() {
+        [index] => &mut nodes[*index],
+        [] => return Err(format!("Node '{}' not found", entry.node)),
+        _ => return Err(format!("Node '{}' is ambiguous", entry.node)),
+    };
+
+    let mut updated = node.clone();
+    set_field(
+        &mut updated.custom_data,
+        &entry.field,
+        Value::String(entry.value.clone()),
+    );
+    if updated.custom_data == node.custom_data {
+        return Ok((node.id, false));
+    }
+    let stored = datastore
+        .update_node(&updated)
+        .await
+        .map_err(|e| format!("Failed to update node: {e}"))?;
+    let before = std::mem::replace(node, stored);
+    provenance::record(
+        datastore,
+        Some(&before),
+        node,
+        Source::Import,
+        Some(&entry.origin),
+        now,
+    )
+    .await
+    .map_err(|e| format!("Failed to record provenance: {e}"))?;
+    Ok((node.id, true))
+}
+
+/// Sets the value at a dotted path, creating the objects above it
+fn set_field(custom_data: &mut Value, path: &str, value: Value) {
+    let mut target = custom_data;
+    for segment in path.split('.') {
+        if !target.is_object() {
+            *target = Value::Object(Map::new());
+        }
+        let Value::Object(map) = target else {
+            return;
+        };
+        target = map.entry(segment).or_insert(Value::Null);
+    }
+    *target = value;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use unet_core::models::{DeviceRole, Vendor};
+
+    fn router(name: &str) -> Node {
+        Node::new(
+            name.to_string(),
+            "example.com".to_string(),
+            Vendor::Cisco,
+            DeviceRole::Router,
+        )
+    }
+
+    #[test]
+    fn test_from_env_maps_names_to_nodes_and_paths() {
+        let vars = [
+            ("UNET_SEED_EDGE_01__SNMP__COMMUNITY", "edge-ro"),
+            ("UNET_SEED_CORE1__ENABLE_SECRET", "s3cret"),
+            ("UNET_SEED_CORE1", "no field"),
+            ("UNET_SEED_CORE1__EMPTY", ""),
+            ("UNET_DATABASE__URL", "sqlite://unet.db"),
+        ]
+        .map(|(name, value)| (name.to_string(), value.to_string()));
+
+        let entries = from_env("UNET_SEED_", vars);
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].origin, "UNET_SEED_CORE1__ENABLE_SECRET");
+        assert_eq!(entries[0].field, "enable_secret");
+        assert_eq!(entries[1].node, NodeRef::EnvName("EDGE_01".to_string()));
+        assert_eq!(entries[1].field, "snmp.community");
+        assert!(entries[1].node.matches(&router("edge-01")));
+    }
+
+    #[test]
+    fn test_parse_csv_keeps_commas_in_values() {
+        let content = "node,field,value\n\
+                       # core\n\
+                       core1,snmp.community,\"ro,public\"\n\
+                       \n\
+                       edge1 , enable_secret , s3cret\n";
+
+        let entries = parse_csv(content, "secrets.csv").unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].origin, "secrets.csv:3");
+        assert_eq!(entries[0].value, "ro,public");
+        assert_eq!(entries[1].node, NodeRef::Reference("edge1".to_string()));
+
+        let error = parse_csv("core1,snmp.community\n", "secrets.csv").unwrap_err();
+        assert!(error.to_string().contains("Line 1: expected"));
+        let error = parse_csv("core1,,x\n", "secrets.csv").unwrap_err();
+        assert!(error.to_string().contains("Line 1: field is empty"));
+    }
+
+    #[tokio::test]
+    async fn test_seed_reports_each_entry_and_records_provenance() {
+        let store = test_support::sqlite::sqlite_store().await;
+        let mut edge = router("edge1");
+        edge.custom_data = json!({"snmp": {"community": "old"}, "rack": "R1"});
+        let edge = store.create_node(&edge).await.unwrap();
+        let entries = parse_csv(
+            "edge1,snmp.community,edge-ro\n\
+             edge1,snmp.community,edge-ro\n\
+             edge1,rack,R2\n\
+             missing,snmp.community,x\n",
+            "secrets.csv",
+        )
+        .unwrap();
+        let fields = vec!["snmp.community".to_string()];
+        let now = Utc::now();
+
+        let results = seed(&store, &entries, &fields, now).await.unwrap();
+
+        let statuses: Vec<SeedStatus> = results.iter().map(|r| r.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                SeedStatus::Imported,
+                SeedStatus::Unchanged,
+                SeedStatus::Failed,
+                SeedStatus::Failed
+            ]
+        );
+        assert_eq!(results[0].node_id, Some(edge.id));
+        assert_eq!(results[0].imported_at, Some(now));
+        assert!(
+            results[2]
+                .message
+                .as_deref()
+                .unwrap()
+                .contains("encrypted_fields")
+        );
+        assert!(results[3].message.as_deref().unwrap().contains("not found"));
+
+        let stored = store.get_node(&edge.id).await.unwrap().unwrap();
+        assert_eq!(stored.custom_data["snmp"]["community"], "edge-ro");
+        assert_eq!(stored.custom_data["rack"], "R1");
+        let provenance = provenance::get_provenance(&store, edge.id)
+            .await
+            .unwrap()
+            .unwrap();
+        let community = &provenance.fields["custom_data.snmp.community"];
+        assert_eq!(community.source, Source::Import);
+        assert_eq!(community.detail.as_deref(), Some("secrets.csv:1"));
+        assert!(!serde_json::to_string(&results).unwrap().contains("edge-ro"));
+    }
+}
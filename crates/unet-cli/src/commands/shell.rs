@@ -0,0 +1,304 @@
+/// Interactive shell running commands over one datastore or server connection
+///
+/// Each line takes the same grammar as `unet` without the program name, and
+/// several statements can be separated by `;`. `use node <name|id>` sets a
+/// current node: node commands such as `show`, `status`, or `nodes metrics`
+/// then apply to it when no node is given, and a node name can stand in for
+/// its ID anywhere a node ID is expected.
+use anyhow::{Result, anyhow, bail};
+use clap::{Args, CommandFactory, Parser};
+use rustyline::Editor;
+use rustyline::error::ReadlineError;
+use rustyline::history::DefaultHistory;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use unet_core::config::Config;
+use unet_core::datastore::{DataStore, QueryOptions};
+use uuid::Uuid;
+
+use crate::remote::{self, RemoteClient};
+use crate::resolve;
+use crate::{Commands, OutputFormat};
+
+mod completion;
+mod words;
+
+use completion::{Entity, ShellHelper};
+
+#[derive(Args, Debug)]
+pub struct ShellArgs {
+    /// File to keep command history in [default: ~/.unet_history]
+    #[arg(long)]
+    pub history_file: Option<PathBuf>,
+    /// Do not read or write command history
+    #[arg(long, conflicts_with = "history_file")]
+    pub no_history: bool,
+}
+
+/// Connection the shell runs commands over
+pub enum Backend<'a> {
+    /// Datastore opened by the CLI
+    Local(&'a dyn DataStore),
+    /// μNet server, when `--server` is given
+    Remote(RemoteClient),
+}
+
+/// One statement, parsed with the CLI grammar
+#[derive(Parser)]
+#[command(name = "unet", no_binary_name = true, disable_version_flag = true)]
+struct ShellLine {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+/// Whether the shell keeps reading input
+#[derive(Debug, PartialEq, Eq)]
+enum Flow {
+    Continue,
+    Exit,
+}
+
+struct Session<'a> {
+    backend: Backend<'a>,
+    config: &'a Config,
+    output: OutputFormat,
+    /// ID and name of every node
+    nodes: Vec<(Uuid, String)>,
+    /// Locations and links offered for completion
+    others: Vec<Entity>,
+    /// Node set with `use node`
+    current: Option<(Uuid, String)>,
+}
+
+/// Runs the shell until `exit` or end of input
+///
+/// # Errors
+/// Returns an error if the terminal cannot be used or history cannot be saved.
+pub async fn run(
+    args: &ShellArgs,
+    backend: Backend<'_>,
+    config: &Config,
+    output: OutputFormat,
+) -> Result<()> {
+    let mut session = Session {
+        backend,
+        config,
+        output,
+        nodes: Vec::new(),
+        others: Vec::new(),
+        current: None,
+    };
+    if let Err(e) = session.refresh().await {
+        eprintln!("Completion of entity IDs is unavailable: {e:#}");
+    }
+
+    let mut editor: Editor<ShellHelper, DefaultHistory> = Editor::new()?;
+    editor.set_helper(Some(ShellHelper::new(command_tree())));
+    let history = history_path(args);
+    if let Some(path) = &history {
+        // A missing history file is created on exit
+        let _ = editor.load_history(path);
+    }
+
+    loop {
+        if let Some(helper) = editor.helper_mut() {
+            helper.entities = session.entities();
+            helper.node_names = session.nodes.iter().map(|(_, name)| name.clone()).collect();
+        }
+        let line = match editor.readline(&session.prompt()) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(e.into()),
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        editor.add_history_entry(line.as_str())?;
+        if session.run_line(&line).await == Flow::Exit {
+            break;
+        }
+    }
+
+    if let Some(path) = &history {
+        editor.save_history(path)?;
+    }
+    Ok(())
+}
+
+fn history_path(args: &ShellArgs) -> Option<PathBuf> {
+    if args.no_history {
+        return None;
+    }
+    args.history_file
+        .clone()
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".unet_history")))
+}
+
+/// Top-level command names with their subcommand names, for completion
+fn command_tree() -> BTreeMap<String, Vec<String>> {
+    ShellLine::command()
+        .get_subcommands()
+        .map(|command| {
+            let subcommands = command
+                .get_subcommands()
+                .map(|sub| sub.get_name().to_string())
+                .collect();
+            (command.get_name().to_string(), subcommands)
+        })
+        .collect()
+}
+
+impl Session<'_> {
+    fn prompt(&self) -> String {
+        self.current.as_ref().map_or_else(
+            || "unet> ".to_string(),
+            |(_, name)| format!("unet({name})> "),
+        )
+    }
+
+    fn entities(&self) -> Vec<Entity> {
+        self.nodes
+            .iter()
+            .map(|(id, name)| Entity {
+                id: id.to_string(),
+                name: name.clone(),
+            })
+            .chain(self.others.iter().cloned())
+            .collect()
+    }
+
+    /// Reloads the entities used for names and completion
+    async fn refresh(&mut self) -> Result<()> {
+        match &self.backend {
+            Backend::Local(datastore) => {
+                let options = QueryOptions::default();
+                let nodes = datastore.list_nodes(&options).await?.items;
+                let locations = datastore.list_locations(&options).await?.items;
+                let links = datastore.list_links(&options).await?.items;
+                self.nodes = nodes.into_iter().map(|node| (node.id, node.name)).collect();
+                self.others = locations
+                    .into_iter()
+                    .map(|location| (location.id, location.name))
+                    .chain(links.into_iter().map(|link| (link.id, link.name)))
+                    .map(|(id, name)| Entity {
+                        id: id.to_string(),
+                        name,
+                    })
+                    .collect();
+            }
+            Backend::Remote(client) => self.nodes = remote::node_names(client).await?,
+        }
+
+        if let Some((id, name)) = &self.current {
+            if !self.nodes.iter().any(|(node_id, _)| node_id == id) {
+                println!("Node {name} no longer exists; cleared the current node");
+                self.current = None;
+            }
+        }
+        Ok(())
+    }
+
+    /// Finds a node by ID or name
+    fn resolve(&self, node: &str) -> Result<(Uuid, String)> {
+        let id = match resolve::parse_id(node, false)? {
+            Some(id) => id,
+            None => resolve::node_named(&self.nodes, node)?,
+        };
+        self.nodes
+            .iter()
+            .find(|(node_id, _)| *node_id == id)
+            .cloned()
+            .ok_or_else(|| anyhow!("Unknown node: {node}"))
+    }
+
+    /// Runs every statement on a line, stopping at the first error
+    async fn run_line(&mut self, line: &str) -> Flow {
+        let statements = match words::statements(line) {
+            Ok(statements) => statements,
+            Err(e) => {
+                eprintln!("Error: {e}");
+                return Flow::Continue;
+            }
+        };
+        for statement in statements {
+            // Warnings of a failed statement must not leak into the next output
+            crate::warnings::take();
+            match self.run_statement(statement).await {
+                Ok(Flow::Exit) => return Flow::Exit,
+                Ok(Flow::Continue) => {}
+                Err(e) => {
+                    eprintln!("Error: {e:#}");
+                    break;
+                }
+            }
+        }
+        Flow::Continue
+    }
+
+    async fn run_statement(&mut self, statement: Vec<String>) -> Result<Flow> {
+        let words: Vec<&str> = statement.iter().map(String::as_str).collect();
+        match words.as_slice() {
+            ["exit" | "quit"] => return Ok(Flow::Exit),
+            ["help"] => {
+                println!("{}", ShellLine::command().render_help());
+                println!("Shell commands:");
+                println!("  use node <NAME|ID>  Apply node commands to this node");
+                println!("  use none            Clear the current node");
+                println!("  exit, quit          Leave the shell");
+            }
+            ["use"] => match &self.current {
+                Some((id, name)) => println!("Current node: {name} ({id})"),
+                None => println!("No current node"),
+            },
+            ["use", "none"] => self.current = None,
+            ["use", "node", node] => {
+                self.current = Some(self.resolve(node)?);
+            }
+            ["use", ..] => bail!("Usage: use node <NAME|ID> | use none"),
+            _ => return self.execute(statement).await.map(|()| Flow::Continue),
+        }
+        Ok(Flow::Continue)
+    }
+
+    /// Parses a statement as a CLI command and runs it
+    async fn execute(&mut self, statement: Vec<String>) -> Result<()> {
+        let current = self.current.as_ref().map(|(id, _)| *id);
+        let statement = words::expand(statement, current, |name| {
+            self.resolve(name).ok().map(|(id, _)| id)
+        });
+        let changes_entities = !matches!(
+            statement.get(1).map(String::as_str),
+            Some("list" | "show" | "status" | "metrics" | "history" | "compare")
+        );
+        let line = match ShellLine::try_parse_from(statement) {
+            Ok(line) => line,
+            Err(e) => {
+                // Help and usage errors are printed like the CLI prints them
+                e.print()?;
+                return Ok(());
+            }
+        };
+
+        match (line.command, &self.backend) {
+            (Commands::Shell(_), _) => bail!("Already in the shell"),
+            (Commands::Secrets(command), _) if !command.seeds_nodes() => {
+                return crate::commands::secrets::execute(&command, self.config, self.output);
+            }
+            (command, Backend::Local(datastore)) => {
+                crate::execute_local(command, *datastore, self.config, self.output).await?;
+            }
+            (command, Backend::Remote(client)) => {
+                remote::dispatch(command, client, self.output).await?;
+            }
+        }
+
+        if changes_entities {
+            self.refresh().await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,153 @@
+//! Tab completion of commands and entity IDs
+
+use rustyline::completion::{Completer, Pair};
+use rustyline::{Context, Helper, Highlighter, Hinter, Validator};
+use std::collections::BTreeMap;
+
+use super::words::NODE_SHORTHANDS;
+
+/// Words the shell handles itself
+pub const BUILTINS: [&str; 4] = ["use", "help", "exit", "quit"];
+
+/// Entity offered for completion by ID or name
+#[derive(Debug, Clone)]
+pub struct Entity {
+    pub id: String,
+    pub name: String,
+}
+
+/// Completion state for the line editor
+#[derive(Helper, Highlighter, Hinter, Validator)]
+pub struct ShellHelper {
+    /// Top-level command names with their subcommand names
+    commands: BTreeMap<String, Vec<String>>,
+    /// Entities known from the last refresh
+    pub entities: Vec<Entity>,
+    /// Nodes, by name, accepted by `use node`
+    pub node_names: Vec<String>,
+}
+
+impl ShellHelper {
+    #[must_use]
+    pub const fn new(commands: BTreeMap<String, Vec<String>>) -> Self {
+        Self {
+            commands,
+            entities: Vec::new(),
+            node_names: Vec::new(),
+        }
+    }
+
+    /// Returns the completions for `prefix` following the statement's earlier words
+    fn candidates(&self, before: &[&str], prefix: &str) -> Vec<Pair> {
+        match before {
+            _ if prefix.starts_with('-') => Vec::new(),
+            [] => keywords(
+                self.commands
+                    .keys()
+                    .map(String::as_str)
+                    .chain(BUILTINS)
+                    .chain(NODE_SHORTHANDS),
+                prefix,
+            ),
+            ["use"] => keywords(["node", "none"].into_iter(), prefix),
+            ["use", "node"] => keywords(self.node_names.iter().map(String::as_str), prefix),
+            [command] if self.commands.contains_key(*command) => {
+                keywords(self.commands[*command].iter().map(String::as_str), prefix)
+            }
+            _ => {
+                let lowered = prefix.to_lowercase();
+                self.entities
+                    .iter()
+                    .filter(|entity| {
+                        entity.id.starts_with(prefix)
+                            || entity.name.to_lowercase().starts_with(&lowered)
+                    })
+                    .map(|entity| Pair {
+                        display: format!("{} ({})", entity.name, entity.id),
+                        replacement: entity.id.clone(),
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+fn keywords<'a>(words: impl Iterator<Item = &'a str>, prefix: &str) -> Vec<Pair> {
+    words
+        .filter(|word| word.starts_with(prefix))
+        .map(|word| Pair {
+            display: word.to_string(),
+            replacement: word.to_string(),
+        })
+        .collect()
+}
+
+impl Completer for ShellHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let line = &line[..pos];
+        let statement_start = line.rfind(';').map_or(0, |i| i + 1);
+        let word_start = line[statement_start..]
+            .rfind(char::is_whitespace)
+            .map_or(statement_start, |i| statement_start + i + 1);
+        let before: Vec<&str> = line[statement_start..word_start]
+            .split_whitespace()
+            .collect();
+        Ok((word_start, self.candidates(&before, &line[word_start..])))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustyline::history::DefaultHistory;
+
+    fn helper() -> ShellHelper {
+        let mut commands = BTreeMap::new();
+        commands.insert(
+            "nodes".to_string(),
+            vec!["list".to_string(), "show".to_string()],
+        );
+        commands.insert("links".to_string(), vec!["list".to_string()]);
+        let mut helper = ShellHelper::new(commands);
+        helper.entities = vec![Entity {
+            id: "4b1e0f0a-0000-0000-0000-000000000001".to_string(),
+            name: "core-01".to_string(),
+        }];
+        helper.node_names = vec!["core-01".to_string()];
+        helper
+    }
+
+    fn complete(line: &str) -> (usize, Vec<String>) {
+        let history = DefaultHistory::new();
+        let (start, pairs) = helper()
+            .complete(line, line.len(), &Context::new(&history))
+            .unwrap();
+        (
+            start,
+            pairs.into_iter().map(|pair| pair.replacement).collect(),
+        )
+    }
+
+    #[test]
+    fn test_completes_commands_and_subcommands() {
+        assert_eq!(complete("no"), (0, vec!["nodes".to_string()]));
+        assert_eq!(complete("nodes s"), (6, vec!["show".to_string()]));
+        assert_eq!(complete("show; li"), (6, vec!["links".to_string()]));
+        assert_eq!(complete("use node c"), (9, vec!["core-01".to_string()]));
+    }
+
+    #[test]
+    fn test_completes_entity_ids_by_name_or_id() {
+        let id = "4b1e0f0a-0000-0000-0000-000000000001".to_string();
+        assert_eq!(complete("nodes show CORE"), (11, vec![id.clone()]));
+        assert_eq!(complete("nodes show 4b1e"), (11, vec![id]));
+        assert!(complete("nodes show --o").1.is_empty());
+    }
+}
@@ -0,0 +1,79 @@
+use super::*;
+use unet_core::models::{DeviceRole, Node, Vendor};
+
+async fn session<'a>(datastore: &'a dyn DataStore, config: &'a Config) -> Session<'a> {
+    let mut session = Session {
+        backend: Backend::Local(datastore),
+        config,
+        output: OutputFormat::Json,
+        nodes: Vec::new(),
+        others: Vec::new(),
+        current: None,
+    };
+    session.refresh().await.unwrap();
+    session
+}
+
+#[tokio::test]
+async fn test_use_node_sets_current_node() {
+    let store = test_support::sqlite::sqlite_store().await;
+    let node = Node::new(
+        "core-01".to_string(),
+        "example.com".to_string(),
+        Vendor::Cisco,
+        DeviceRole::Router,
+    );
+    store.create_node(&node).await.unwrap();
+    let config = Config::default();
+    let mut session = session(&store, &config).await;
+
+    assert_eq!(
+        session.run_line("use node core-01; show").await,
+        Flow::Continue
+    );
+    assert_eq!(session.current, Some((node.id, "core-01".to_string())));
+    assert_eq!(session.prompt(), "unet(core-01)> ");
+    assert!(
+        session
+            .run_statement(vec!["status".to_string()])
+            .await
+            .is_ok()
+    );
+
+    let missing = session
+        .run_statement(vec!["use".into(), "node".into(), "edge-09".into()])
+        .await;
+    assert!(missing.is_err());
+    assert_eq!(session.run_line("use none; exit").await, Flow::Exit);
+    assert!(session.current.is_none());
+}
+
+#[tokio::test]
+async fn test_deleting_current_node_clears_it() {
+    let store = test_support::sqlite::sqlite_store().await;
+    let node = Node::new(
+        "core-01".to_string(),
+        "example.com".to_string(),
+        Vendor::Cisco,
+        DeviceRole::Router,
+    );
+    store.create_node(&node).await.unwrap();
+    let config = Config::default();
+    let mut session = session(&store, &config).await;
+    session.current = Some((node.id, node.name.clone()));
+
+    session
+        .execute(vec!["delete".into(), "--yes".into()])
+        .await
+        .unwrap();
+
+    assert!(session.current.is_none());
+    assert!(session.nodes.is_empty());
+}
+
+#[test]
+fn test_command_tree_lists_subcommands() {
+    let tree = command_tree();
+    assert!(tree["nodes"].iter().any(|sub| sub == "show"));
+    assert!(tree.contains_key("shell"));
+}
@@ -0,0 +1,219 @@
+//! Splitting shell input into statements and applying the node context
+
+use anyhow::{Result, bail};
+use uuid::Uuid;
+
+/// Node subcommands that take the node ID as their first argument
+const NODE_ID_COMMANDS: [&str; 8] = [
+    "show",
+    "update",
+    "delete",
+    "status",
+    "metrics",
+    "polling",
+    "history",
+    "test-access",
+];
+
+/// Node subcommands that may be typed without the leading `nodes`; `polling`
+/// is left out because it is also a top-level command
+pub const NODE_SHORTHANDS: [&str; 7] = [
+    "show",
+    "update",
+    "delete",
+    "status",
+    "metrics",
+    "history",
+    "test-access",
+];
+
+/// Splits a line into `;`-separated statements of words
+///
+/// Single quotes keep their contents literally, double quotes allow `\"` and
+/// `\\`, and a backslash outside quotes escapes the next character.
+///
+/// # Errors
+/// Returns an error if a quote is left open.
+pub fn statements(line: &str) -> Result<Vec<Vec<String>>> {
+    let mut statements = Vec::new();
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            ';' => {
+                words.extend(word.take());
+                statements.push(std::mem::take(&mut words));
+            }
+            c if c.is_whitespace() => words.extend(word.take()),
+            '\'' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => bail!("Unterminated single quote"),
+                    }
+                }
+            }
+            '"' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\')) => word.push(c),
+                            Some(c) => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                            None => bail!("Unterminated double quote"),
+                        },
+                        Some(c) => word.push(c),
+                        None => bail!("Unterminated double quote"),
+                    }
+                }
+            }
+            '\\' => {
+                let word = word.get_or_insert_with(String::new);
+                word.extend(chars.next());
+            }
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    words.extend(word);
+    statements.push(words);
+    statements.retain(|words| !words.is_empty());
+    Ok(statements)
+}
+
+/// Rewrites a statement for the current node
+///
+/// A node shorthand such as `show` becomes `nodes show`. For node commands
+/// taking a node ID, a node name in the ID position is replaced by its ID
+/// using `resolve`, and when the ID is missing the current node is inserted.
+#[must_use]
+pub fn expand(
+    mut words: Vec<String>,
+    current: Option<Uuid>,
+    resolve: impl Fn(&str) -> Option<Uuid>,
+) -> Vec<String> {
+    if words
+        .first()
+        .is_some_and(|word| NODE_SHORTHANDS.contains(&word.as_str()))
+    {
+        words.insert(0, "nodes".to_string());
+    }
+    let takes_node = words.first().is_some_and(|word| word == "nodes")
+        && words
+            .get(1)
+            .is_some_and(|word| NODE_ID_COMMANDS.contains(&word.as_str()));
+    if !takes_node {
+        return words;
+    }
+
+    let given = words.get(2).filter(|word| !word.starts_with('-'));
+    if given.is_some_and(|word| Uuid::parse_str(word).is_ok()) {
+        return words;
+    }
+    if let Some(id) = given.and_then(|word| resolve(word)) {
+        words[2] = id.to_string();
+    } else if let Some(id) = current {
+        words.insert(2, id.to_string());
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(items: &[&str]) -> Vec<String> {
+        items.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_statements_split_on_unquoted_semicolons() {
+        let parsed = statements("use node core-01; show ;status").unwrap();
+        assert_eq!(
+            parsed,
+            vec![
+                words(&["use", "node", "core-01"]),
+                words(&["show"]),
+                words(&["status"]),
+            ]
+        );
+        assert!(statements("  ;; ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_statements_handle_quotes_and_escapes() {
+        let parsed =
+            statements(r#"nodes update x --name 'a; b' --model "MX \"480\"" c\ d """#).unwrap();
+        assert_eq!(
+            parsed,
+            vec![words(&[
+                "nodes",
+                "update",
+                "x",
+                "--name",
+                "a; b",
+                "--model",
+                "MX \"480\"",
+                "c d",
+                "",
+            ])]
+        );
+        assert!(statements("show 'open").is_err());
+        assert!(statements("show \"open").is_err());
+    }
+
+    #[test]
+    fn test_expand_inserts_current_node() {
+        let id = Uuid::new_v4();
+        let none = |_: &str| None;
+
+        assert_eq!(
+            expand(words(&["show"]), Some(id), none),
+            words(&["nodes", "show", &id.to_string()])
+        );
+        assert_eq!(
+            expand(words(&["nodes", "status", "--live"]), Some(id), none),
+            words(&["nodes", "status", &id.to_string(), "--live"])
+        );
+        assert_eq!(
+            expand(words(&["nodes", "polling", "start"]), Some(id), none),
+            words(&["nodes", "polling", &id.to_string(), "start"])
+        );
+        assert_eq!(
+            expand(words(&["show"]), None, none),
+            words(&["nodes", "show"])
+        );
+        assert_eq!(
+            expand(words(&["nodes", "list"]), Some(id), none),
+            words(&["nodes", "list"])
+        );
+        assert_eq!(
+            expand(words(&["polling", "list"]), Some(id), none),
+            words(&["polling", "list"])
+        );
+    }
+
+    #[test]
+    fn test_expand_resolves_node_names() {
+        let current = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let resolve = |name: &str| (name == "core-02").then_some(other);
+
+        assert_eq!(
+            expand(words(&["show", "core-02"]), Some(current), resolve),
+            words(&["nodes", "show", &other.to_string()])
+        );
+        let explicit = Uuid::new_v4().to_string();
+        assert_eq!(
+            expand(words(&["nodes", "show", &explicit]), Some(current), resolve),
+            words(&["nodes", "show", &explicit])
+        );
+    }
+}
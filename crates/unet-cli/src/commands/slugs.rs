@@ -0,0 +1,122 @@
+/// Slug commands
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use unet_core::datastore::DataStore;
+use unet_core::slug::{SlugKind, backfill_slugs, change_slug, list_slugs};
+
+use crate::resolve;
+
+#[derive(Subcommand)]
+pub enum SlugCommands {
+    /// List the current slug of every node, location, or link
+    List(ListSlugArgs),
+    /// Change an entity's slug; the old slug keeps redirecting to it
+    Set(SetSlugArgs),
+    /// Give every entity without a slug one derived from its name
+    Backfill,
+}
+
+#[derive(Args, Debug)]
+pub struct ListSlugArgs {
+    /// Entity kind: node, location, or link
+    pub kind: SlugKind,
+}
+
+#[derive(Args, Debug)]
+pub struct SetSlugArgs {
+    /// Entity kind: node, location, or link
+    pub kind: SlugKind,
+    /// Entity slug, name, or ID
+    pub entity: String,
+    /// New slug
+    pub slug: String,
+}
+
+/// Execute slug subcommands.
+///
+/// # Errors
+/// Returns an error if the entity cannot be found, the slug is invalid or
+/// taken, datastore operations fail, or output formatting fails.
+pub async fn execute(
+    command: SlugCommands,
+    datastore: &dyn DataStore,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    match command {
+        SlugCommands::List(args) => {
+            let slugs = list_slugs(datastore, args.kind).await?;
+            crate::commands::print_output(&slugs, output_format)
+        }
+        SlugCommands::Set(args) => {
+            let id = match args.kind {
+                SlugKind::Node => resolve::node(datastore, &args.entity, false).await?,
+                SlugKind::Location => resolve::location(datastore, &args.entity, false).await?,
+                SlugKind::Link => resolve::link(datastore, &args.entity, false).await?,
+            };
+            change_slug(datastore, args.kind, id, &args.slug).await?;
+            let output = serde_json::json!({
+                "message": format!("{} slug changed", args.kind),
+                "kind": args.kind,
+                "id": id,
+                "slug": args.slug,
+            });
+            crate::commands::print_output(&output, output_format)
+        }
+        SlugCommands::Backfill => {
+            let assigned = backfill_slugs(datastore).await?;
+            crate::commands::print_output(&assigned, output_format)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unet_core::models::Location;
+    use unet_core::slug::{assign_slug, resolve_slug};
+
+    #[tokio::test]
+    async fn test_set_slug_by_name_keeps_old_slug() {
+        let store = test_support::sqlite::sqlite_store().await;
+        let site = Location::new_root("Slug Site".to_string(), "site".to_string());
+        store.create_location(&site).await.unwrap();
+
+        assign_slug(&store, SlugKind::Location, site.id, &site.name, None)
+            .await
+            .unwrap();
+        let command = SlugCommands::Set(SetSlugArgs {
+            kind: SlugKind::Location,
+            entity: "slug-site".to_string(),
+            slug: "slug-site-east".to_string(),
+        });
+        execute(command, &store, crate::OutputFormat::Json)
+            .await
+            .unwrap();
+
+        let old = resolve_slug(&store, SlugKind::Location, "slug-site")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(old.id, site.id);
+        assert_eq!(old.slug, "slug-site-east");
+        assert!(old.redirected);
+    }
+
+    #[tokio::test]
+    async fn test_set_slug_rejects_invalid_slug() {
+        let store = test_support::sqlite::sqlite_store().await;
+        let site = Location::new_root("slug-invalid".to_string(), "site".to_string());
+        store.create_location(&site).await.unwrap();
+
+        let command = SlugCommands::Set(SetSlugArgs {
+            kind: SlugKind::Location,
+            entity: site.id.to_string(),
+            slug: "Not A Slug".to_string(),
+        });
+        assert!(
+            execute(command, &store, crate::OutputFormat::Json)
+                .await
+                .is_err()
+        );
+    }
+}
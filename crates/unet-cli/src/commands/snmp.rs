@@ -0,0 +1,247 @@
+/// SNMP walk snapshot commands
+use anyhow::{Context, Result, anyhow};
+use clap::{Args, Subcommand};
+use serde::Serialize;
+use std::path::PathBuf;
+use unet_core::config::{Config, SnmpConfig};
+use unet_core::datastore::DataStore;
+use unet_core::enrichment::EnrichmentRegistry;
+use unet_core::models::derived::NodeStatus;
+use unet_core::snmp::{SnmpClient, format_walk, parse_walk};
+use uuid::Uuid;
+
+use crate::commands::nodes::test_access::{credential_candidates, session_config};
+use crate::resolve::{self, EntityArg};
+
+#[derive(Subcommand)]
+pub enum SnmpCommands {
+    /// Walk a node's MIB subtree and print or save it in snmpwalk format
+    Walk(WalkArgs),
+    /// Run a saved walk through the derived-state pipeline without a device
+    Replay(ReplayArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct WalkArgs {
+    #[command(flatten)]
+    pub node: EntityArg,
+    /// Subtree to walk
+    #[arg(long, default_value = "1.3.6.1.2.1")]
+    pub oid: String,
+    /// Write the walk to this file instead of printing it
+    #[arg(long, value_name = "FILE")]
+    pub save: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+pub struct ReplayArgs {
+    /// Walk file in snmpwalk format, with numeric OIDs
+    pub file: PathBuf,
+    /// Node ID recorded on the derived status
+    #[arg(long)]
+    pub node_id: Option<Uuid>,
+}
+
+/// Summary of a saved walk
+#[derive(Debug, Serialize)]
+struct SavedWalk {
+    node_name: String,
+    oid: String,
+    file: String,
+    varbinds: usize,
+}
+
+/// Derived state computed from a walk file
+#[derive(Debug, Serialize)]
+struct ReplayReport {
+    file: String,
+    varbinds: usize,
+    /// Enrichment plugins that completed, in run order; failures are in the
+    /// status's `enrichment_errors`
+    applied_plugins: Vec<String>,
+    status: NodeStatus,
+}
+
+/// Walks a subtree of a node with the first working SNMP community
+///
+/// Communities are tried in the order `nodes test-access` uses. Without
+/// `--save` the walk is printed to stdout as it would be saved.
+///
+/// # Errors
+/// Returns an error if the node cannot be resolved, has no usable management
+/// address, every community fails, the walk is empty, or the file cannot be
+/// written.
+pub async fn walk(
+    args: &WalkArgs,
+    datastore: &dyn DataStore,
+    config: &SnmpConfig,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    let id = resolve::node(datastore, &args.node.reference, args.node.by_id).await?;
+    let node = datastore.get_node_required(&id).await?;
+    let address = node
+        .management_socket_addr(161, config.address_family)
+        .map_err(|e| anyhow!("Node {} has an invalid management address: {e}", node.name))?
+        .ok_or_else(|| anyhow!("Node {} has no management IP to walk", node.name))?;
+
+    let client = SnmpClient::new(config.client_config());
+    let mut last_error = None;
+    let mut values = None;
+    for (_, community) in credential_candidates(&node, config) {
+        let session = session_config(address, community, config);
+        match client.walk(address, &args.oid, Some(session)).await {
+            Ok(result) => {
+                values = Some(result);
+                break;
+            }
+            Err(e) => last_error = Some(e),
+        }
+    }
+    let values = match (values, last_error) {
+        (Some(values), _) => values,
+        (None, Some(e)) => {
+            return Err(
+                anyhow::Error::new(e).context(format!("Failed to walk {} at {address}", node.name))
+            );
+        }
+        (None, None) => return Err(anyhow!("No SNMP community to walk {}", node.name)),
+    };
+    if values.is_empty() {
+        return Err(anyhow!("{} returned nothing under {}", node.name, args.oid));
+    }
+
+    let walk = format_walk(&values);
+    let Some(path) = &args.save else {
+        print!("{walk}");
+        return Ok(());
+    };
+    tokio::fs::write(path, walk)
+        .await
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    let saved = SavedWalk {
+        node_name: node.name,
+        oid: args.oid.clone(),
+        file: path.display().to_string(),
+        varbinds: values.len(),
+    };
+    crate::commands::print_output(&saved, output_format)
+}
+
+/// Derives node status from a walk file with the configured enrichment plugins
+///
+/// # Errors
+/// Returns an error if the file cannot be read or parsed, or an enrichment
+/// plugin is unknown or misconfigured.
+pub fn replay(
+    args: &ReplayArgs,
+    config: &Config,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    let content = std::fs::read_to_string(&args.file)
+        .with_context(|| format!("Failed to read {}", args.file.display()))?;
+    let values = parse_walk(&content)
+        .with_context(|| format!("Invalid walk file {}", args.file.display()))?;
+    let pipeline = EnrichmentRegistry::with_builtins().build(&config.server.enrichment)?;
+
+    let varbinds = values.len();
+    let mut status = NodeStatus::new(args.node_id.unwrap_or_default());
+    let outcome = pipeline.update_from_snmp(&mut status, values);
+    let report = ReplayReport {
+        file: args.file.display().to_string(),
+        varbinds,
+        applied_plugins: outcome.applied,
+        status,
+    };
+    crate::commands::print_output(&report, output_format)
+}
+
+/// Execute SNMP snapshot subcommands.
+///
+/// # Errors
+/// Returns an error if the walk or replay fails.
+pub async fn execute(
+    command: SnmpCommands,
+    datastore: &dyn DataStore,
+    config: &Config,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    match command {
+        SnmpCommands::Walk(args) => walk(&args, datastore, &config.snmp, output_format).await,
+        SnmpCommands::Replay(args) => replay(&args, config, output_format),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unet_core::datastore::{MockDataStore, testing::ready_ok};
+    use unet_core::models::{DeviceRole, NodeBuilder, Vendor};
+
+    const WALK: &str = "\
+.1.3.6.1.2.1.1.1.0 = STRING: \"Cisco IOS Software, C2960 Software, Version 15.2(7)E4\"
+.1.3.6.1.2.1.1.5.0 = STRING: \"access-01\"
+.1.3.6.1.2.1.2.2.1.2.1 = STRING: \"GigabitEthernet0/1\"
+.1.3.6.1.2.1.2.2.1.8.1 = INTEGER: up(1)
+";
+
+    #[test]
+    fn test_replay_walk_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("walk.txt");
+        std::fs::write(&file, WALK).unwrap();
+        let args = ReplayArgs {
+            file,
+            node_id: Some(Uuid::new_v4()),
+        };
+
+        replay(&args, &Config::default(), crate::OutputFormat::Json).unwrap();
+    }
+
+    #[test]
+    fn test_replay_reports_bad_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("walk.txt");
+        std::fs::write(&file, "SNMPv2-MIB::sysName.0 = STRING: r1\n").unwrap();
+        let args = ReplayArgs {
+            file,
+            node_id: None,
+        };
+
+        let error = replay(&args, &Config::default(), crate::OutputFormat::Json).unwrap_err();
+
+        assert!(format!("{error:#}").contains("line 1"));
+    }
+
+    #[tokio::test]
+    async fn test_walk_requires_management_address() {
+        let node = NodeBuilder::new()
+            .name("access-01")
+            .domain("example.com")
+            .vendor(Vendor::Cisco)
+            .model("C2960")
+            .role(DeviceRole::Switch)
+            .build()
+            .unwrap();
+        let id = node.id;
+        let mut datastore = MockDataStore::new();
+        datastore
+            .expect_get_node_required()
+            .returning(move |_| ready_ok(node.clone()));
+        let args = WalkArgs {
+            node: EntityArg::from(id),
+            oid: "1.3.6.1.2.1".to_string(),
+            save: None,
+        };
+
+        let error = walk(
+            &args,
+            &datastore,
+            &Config::default().snmp,
+            crate::OutputFormat::Json,
+        )
+        .await
+        .unwrap_err();
+
+        assert!(error.to_string().contains("no management IP"));
+    }
+}
@@ -0,0 +1,354 @@
+/// Template rendering commands
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use std::path::PathBuf;
+use unet_core::datastore::{DataStore, QueryOptions};
+use unet_core::golden::GoldenSource;
+use unet_core::models::DeviceRole;
+use unet_core::template::fixtures::{FixtureStatus, check_templates};
+use unet_core::template::vars::{VarScope, delete_vars, resolve_vars, set_vars};
+use unet_core::template::{RenderOptions, render_fleet};
+use uuid::Uuid;
+
+use crate::confirm::{Confirmation, confirm};
+use crate::resolve::{self, EntityArg};
+
+#[derive(Subcommand)]
+pub enum TemplateCommands {
+    /// Render templates for nodes and compare with their recorded renders
+    Render(RenderArgs),
+    /// Render templates against fixture variables and compare with golden outputs
+    Test(TestArgs),
+    /// Show a node's merged template variables and where each value came from
+    Vars(NodeVarsArgs),
+    /// Set the template variables of every node, a location, or a group
+    SetVars(SetVarsArgs),
+    /// Remove the template variables of every node, a location, or a group
+    ClearVars(ClearVarsArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct RenderArgs {
+    /// Node ID to render
+    #[arg(long, conflicts_with = "all", required_unless_present = "all")]
+    pub node: Option<Uuid>,
+    /// Render every node
+    #[arg(long)]
+    pub all: bool,
+    /// Only render nodes with this role
+    #[arg(long, requires = "all")]
+    pub role: Option<DeviceRole>,
+    /// Candidate `MiniJinja` template file rendered instead of each node's golden config
+    #[arg(long)]
+    pub template: Option<PathBuf>,
+    /// Template variables as a JSON object
+    #[arg(long, requires = "template")]
+    pub vars: Option<String>,
+    /// Include a unified diff against the recorded render for changed nodes
+    #[arg(long)]
+    pub diff: bool,
+    /// Record the renders as the baseline for later comparisons
+    #[arg(long)]
+    pub record: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct TestArgs {
+    /// Directory of `.j2` templates, with fixtures under `tests/<template>/`
+    #[arg(long, default_value = "templates")]
+    pub dir: PathBuf,
+    /// Write the rendered outputs as the new golden files
+    #[arg(long)]
+    pub update_golden: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct NodeVarsArgs {
+    #[command(flatten)]
+    pub node: EntityArg,
+}
+
+#[derive(Args, Debug)]
+pub struct VarScopeArgs {
+    /// Location name, path, or ID; applies to nodes in sub-locations too
+    #[arg(long, conflicts_with = "group")]
+    pub location: Option<String>,
+    /// Dynamic group name
+    #[arg(long)]
+    pub group: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct SetVarsArgs {
+    /// Template variables as a JSON object; replaces those set before
+    pub vars: String,
+    #[command(flatten)]
+    pub scope: VarScopeArgs,
+}
+
+#[derive(Args, Debug)]
+pub struct ClearVarsArgs {
+    #[command(flatten)]
+    pub scope: VarScopeArgs,
+    /// Skip confirmation prompt
+    #[arg(short = 'y', long)]
+    pub yes: bool,
+}
+
+/// Renders template fixtures and compares them with their golden files
+///
+/// Fixtures are local files, so this runs without a datastore.
+///
+/// # Errors
+/// Returns an error if the fixtures cannot be read or written, output
+/// formatting fails, or any case differs from, lacks, or cannot render its
+/// golden output.
+pub fn test_fixtures(args: &TestArgs, output_format: crate::OutputFormat) -> Result<()> {
+    let report = check_templates(&args.dir, args.update_golden)?;
+    crate::commands::print_output(&report, output_format)?;
+    if matches!(output_format, crate::OutputFormat::Table) {
+        for result in &report.results {
+            if let Some(diff) = &result.diff {
+                println!("\n{}/{}:\n{diff}", result.template, result.case);
+            }
+        }
+    }
+
+    if !report.passed() {
+        let failing = report
+            .results
+            .iter()
+            .filter(|result| {
+                !matches!(
+                    result.status,
+                    FixtureStatus::Passed | FixtureStatus::Updated
+                )
+            })
+            .count();
+        anyhow::bail!(
+            "{failing} template fixture case(s) failed; rerun with --update-golden to accept the rendered output"
+        );
+    }
+    Ok(())
+}
+
+/// Execute template subcommands.
+///
+/// # Errors
+/// Returns an error if the template or variables cannot be read, the node,
+/// location, or group name is invalid, datastore operations fail, or output
+/// formatting fails.
+pub async fn execute(
+    command: TemplateCommands,
+    datastore: &dyn DataStore,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    match command {
+        TemplateCommands::Render(args) => {
+            let nodes = match args.node {
+                Some(id) => vec![datastore.get_node_required(&id).await?],
+                None => {
+                    let mut nodes = datastore.list_nodes(&QueryOptions::default()).await?.items;
+                    if let Some(role) = args.role {
+                        nodes.retain(|node| node.role == role);
+                    }
+                    nodes
+                }
+            };
+            let options = RenderOptions {
+                template: read_template(&args)?,
+                diff: args.diff,
+                record: args.record,
+            };
+            let report = render_fleet(datastore, &nodes, &options).await?;
+            crate::commands::print_output(&report, output_format)
+        }
+        TemplateCommands::Test(args) => test_fixtures(&args, output_format),
+        TemplateCommands::Vars(args) => {
+            let id = resolve::node(datastore, &args.node.reference, args.node.by_id).await?;
+            let node = datastore.get_node_required(&id).await?;
+            crate::commands::print_output(&resolve_vars(datastore, &node).await?, output_format)
+        }
+        TemplateCommands::SetVars(args) => {
+            let scope = var_scope(datastore, &args.scope).await?;
+            let variables: serde_json::Value = serde_json::from_str(&args.vars)?;
+            set_vars(datastore, &scope, &variables).await?;
+            let output = serde_json::json!({
+                "message": "Template variables set",
+                "scope": scope.to_string(),
+                "variables": variables,
+            });
+            crate::commands::print_output(&output, output_format)
+        }
+        TemplateCommands::ClearVars(args) => {
+            let scope = var_scope(datastore, &args.scope).await?;
+            let confirmation =
+                Confirmation::new("Remove template variables").affects(scope.to_string());
+            if !confirm(args.yes, &confirmation)? {
+                return Ok(());
+            }
+            delete_vars(datastore, &scope).await?;
+            let output = serde_json::json!({
+                "message": "Template variables removed",
+                "scope": scope.to_string(),
+            });
+            crate::commands::print_output(&output, output_format)
+        }
+    }
+}
+
+/// The variable layer named by `--location` or `--group`, or the global one
+async fn var_scope(datastore: &dyn DataStore, args: &VarScopeArgs) -> Result<VarScope> {
+    Ok(match (&args.location, &args.group) {
+        (Some(location), _) => {
+            let id = resolve::location(datastore, location, false).await?;
+            VarScope::Location(datastore.get_location_required(&id).await?.id)
+        }
+        (None, Some(group)) => VarScope::group(group)?,
+        (None, None) => VarScope::Global,
+    })
+}
+
+fn read_template(args: &RenderArgs) -> Result<Option<GoldenSource>> {
+    let Some(path) = &args.template else {
+        return Ok(None);
+    };
+    let variables = args
+        .vars
+        .as_deref()
+        .map(serde_json::from_str::<serde_json::Value>)
+        .transpose()?
+        .unwrap_or_default();
+    if !variables.is_object() && !variables.is_null() {
+        anyhow::bail!("Template variables must be a JSON object");
+    }
+    Ok(Some(GoldenSource::Template {
+        template: std::fs::read_to_string(path)?,
+        variables,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unet_core::datastore::{MockDataStore, PagedResult};
+    use unet_core::models::{Node, Vendor};
+
+    fn node(name: &str, role: DeviceRole) -> Node {
+        Node::new(
+            name.to_string(),
+            "example.com".to_string(),
+            Vendor::Juniper,
+            role,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_render_all_filters_by_role_without_recording() {
+        let dir = tempfile::tempdir().unwrap();
+        let template = dir.path().join("router.j2");
+        std::fs::write(&template, "hostname {{ node.name }}\n").unwrap();
+        let router = node("edge-1", DeviceRole::Router);
+        let router_key = router.id.to_string();
+        let nodes = vec![router, node("sw-1", DeviceRole::Switch)];
+
+        let mut mock = MockDataStore::new();
+        mock.expect_list_nodes().returning(move |_| {
+            let nodes = nodes.clone();
+            Box::pin(async move { Ok(PagedResult::new(nodes, 2, None)) })
+        });
+        mock.expect_get_setting()
+            .withf(move |namespace, key| namespace == "template_renders" && key == router_key)
+            .times(1)
+            .returning(|_, _| Box::pin(async { Ok(None) }));
+        mock.expect_list_settings()
+            .withf(|namespace| namespace == "template_vars")
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(Vec::new()) }));
+        mock.expect_put_setting().times(0);
+
+        let args = RenderArgs {
+            node: None,
+            all: true,
+            role: Some(DeviceRole::Router),
+            template: Some(template),
+            vars: None,
+            diff: true,
+            record: false,
+        };
+        let result = execute(
+            TemplateCommands::Render(args),
+            &mock,
+            crate::OutputFormat::Json,
+        )
+        .await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_set_vars_for_group() {
+        let mut mock = MockDataStore::new();
+        mock.expect_put_setting()
+            .withf(|namespace, key, value| {
+                namespace == "template_vars"
+                    && key == "group:core"
+                    && value == &serde_json::json!({"ntp": "10.0.0.1"})
+            })
+            .times(1)
+            .returning(|_, _, _| Box::pin(async { Ok(()) }));
+
+        let args = SetVarsArgs {
+            vars: r#"{"ntp": "10.0.0.1"}"#.to_string(),
+            scope: VarScopeArgs {
+                location: None,
+                group: Some("core".to_string()),
+            },
+        };
+        execute(
+            TemplateCommands::SetVars(args),
+            &mock,
+            crate::OutputFormat::Json,
+        )
+        .await
+        .unwrap();
+
+        let args = SetVarsArgs {
+            vars: r#"["10.0.0.1"]"#.to_string(),
+            scope: VarScopeArgs {
+                location: None,
+                group: None,
+            },
+        };
+        let result = execute(
+            TemplateCommands::SetVars(args),
+            &MockDataStore::new(),
+            crate::OutputFormat::Json,
+        )
+        .await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_template_fixtures_fail_until_golden_is_updated() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("ntp.j2"), "ntp server {{ ntp }}\n").unwrap();
+        let cases = dir.path().join("tests").join("ntp");
+        std::fs::create_dir_all(&cases).unwrap();
+        std::fs::write(cases.join("dc1.json"), r#"{"ntp": "10.0.0.1"}"#).unwrap();
+
+        let mut args = TestArgs {
+            dir: dir.path().to_path_buf(),
+            update_golden: false,
+        };
+        assert!(test_fixtures(&args, crate::OutputFormat::Json).is_err());
+
+        args.update_golden = true;
+        test_fixtures(&args, crate::OutputFormat::Json).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(cases.join("dc1.golden")).unwrap(),
+            "ntp server 10.0.0.1\n"
+        );
+        args.update_golden = false;
+        test_fixtures(&args, crate::OutputFormat::Json).unwrap();
+    }
+}
@@ -0,0 +1,88 @@
+/// Topology analysis commands
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use unet_core::datastore::DataStore;
+use unet_core::topology::{FailureTarget, analyze_impact};
+use uuid::Uuid;
+
+#[derive(Subcommand)]
+pub enum TopologyCommands {
+    /// List everything affected if a node or link goes down
+    Impact(ImpactArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct ImpactArgs {
+    /// Node taken out of service, with all its links
+    #[arg(long, conflicts_with = "link", required_unless_present = "link")]
+    pub node: Option<Uuid>,
+    /// Link taken out of service
+    #[arg(long)]
+    pub link: Option<Uuid>,
+    /// Upstream node; repeat for several (default: internet circuit nodes)
+    #[arg(long = "root", value_name = "NODE_ID")]
+    pub roots: Vec<Uuid>,
+}
+
+/// Execute topology subcommands.
+///
+/// # Errors
+/// Returns an error if the target does not exist, the topology cannot be
+/// read, or output formatting fails.
+pub async fn execute(
+    command: TopologyCommands,
+    datastore: &dyn DataStore,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    match command {
+        TopologyCommands::Impact(args) => {
+            let target = match (args.node, args.link) {
+                (Some(node), _) => FailureTarget::Node(node),
+                (None, Some(link)) => FailureTarget::Link(link),
+                (None, None) => anyhow::bail!("Either --node or --link is required"),
+            };
+            let report = analyze_impact(datastore, target, &args.roots).await?;
+            crate::commands::print_output(&report, output_format)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unet_core::datastore::{MockDataStore, types::PagedResult};
+    use unet_core::models::{DeviceRole, Link, Node, Vendor};
+
+    #[tokio::test]
+    async fn test_impact_of_unknown_node_fails() {
+        let node = Node::new(
+            "a".to_string(),
+            "example.com".to_string(),
+            Vendor::Cisco,
+            DeviceRole::Router,
+        );
+        let mut mock = MockDataStore::new();
+        mock.expect_list_nodes().returning(move |_| {
+            let nodes = vec![node.clone()];
+            Box::pin(async move { Ok(PagedResult::new(nodes, 1, None)) })
+        });
+        mock.expect_list_links()
+            .returning(|_| Box::pin(async { Ok(PagedResult::new(Vec::<Link>::new(), 0, None)) }));
+        mock.expect_list_locations()
+            .returning(|_| Box::pin(async { Ok(PagedResult::new(Vec::new(), 0, None)) }));
+
+        let args = ImpactArgs {
+            node: Some(Uuid::new_v4()),
+            link: None,
+            roots: Vec::new(),
+        };
+        let result = execute(
+            TopologyCommands::Impact(args),
+            &mock,
+            crate::OutputFormat::Json,
+        )
+        .await;
+
+        assert!(result.unwrap_err().to_string().contains("not found"));
+    }
+}
@@ -2,6 +2,8 @@ use anyhow::Result;
 use clap::{Args, Subcommand};
 use unet_core::datastore::DataStore;
 
+use crate::confirm::{Confirmation, confirm};
+
 #[derive(Subcommand)]
 pub enum VendorCommands {
     /// Add a new vendor name
@@ -65,13 +67,9 @@ async fn delete_vendor(
     datastore: &dyn DataStore,
     output_format: crate::OutputFormat,
 ) -> Result<()> {
-    if !args.yes {
-        let stdin = std::io::stdin();
-        let mut lock = stdin.lock();
-        let mut reader = std::io::BufReader::new(&mut lock);
-        if !confirm_deletion(args.yes, &args.name, &mut reader)? {
-            return Ok(());
-        }
+    let confirmation = Confirmation::new("Delete vendor").affects(&args.name);
+    if !confirm(args.yes, &confirmation)? {
+        return Ok(());
     }
     datastore.delete_vendor(&args.name).await?;
     let output = serde_json::json!({ "message": "Vendor deleted", "name": args.name });
@@ -79,25 +77,6 @@ async fn delete_vendor(
     Ok(())
 }
 
-// Small helper to make interactive confirmation testable
-pub(crate) fn confirm_deletion(
-    yes: bool,
-    name: &str,
-    reader: &mut impl std::io::BufRead,
-) -> Result<bool> {
-    if yes {
-        return Ok(true);
-    }
-    println!("Are you sure you want to delete vendor '{name}' ? [y/N]");
-    let mut input = String::new();
-    reader.read_line(&mut input)?;
-    if !input.trim().to_lowercase().starts_with('y') {
-        println!("Cancelled.");
-        return Ok(false);
-    }
-    Ok(true)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -436,17 +415,4 @@ mod exec_tests {
         .await
         .is_ok());
     }
-
-    #[test]
-    fn test_confirm_deletion_reader_variants() {
-        // Negative
-        let mut cur = std::io::Cursor::new(b"n\n".to_vec());
-        let res = confirm_deletion(false, "cisco", &mut cur).unwrap();
-        assert!(!res);
-
-        // Positive
-        let mut cur2 = std::io::Cursor::new(b"yes\n".to_vec());
-        let res2 = confirm_deletion(false, "cisco", &mut cur2).unwrap();
-        assert!(res2);
-    }
 }
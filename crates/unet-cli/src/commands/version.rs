@@ -0,0 +1,82 @@
+/// CLI and server version reporting
+use anyhow::{Result, anyhow};
+use clap::Args;
+use reqwest::Method;
+use serde::Serialize;
+use unet_core::build_info::BuildInfo;
+
+use crate::remote::RemoteClient;
+
+#[derive(Args, Debug)]
+pub struct VersionArgs {
+    /// Also show the version of the server given with --server and compare
+    #[arg(long)]
+    pub remote: bool,
+}
+
+/// Builds of the CLI and, when asked, the server
+#[derive(Debug, Serialize)]
+struct VersionReport {
+    cli: BuildInfo,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    server: Option<BuildInfo>,
+    /// How the CLI and server builds differ
+    #[serde(skip_serializing_if = "Option::is_none")]
+    skew: Option<String>,
+}
+
+/// Prints the CLI's version, commit, and features, and with `--remote` the
+/// server's, warning on stderr when the two differ
+///
+/// # Errors
+/// Returns an error if `--remote` is given without `--server`, the server
+/// cannot be reached, or output formatting fails.
+pub async fn execute(
+    args: &VersionArgs,
+    server_url: Option<&str>,
+    token: Option<&str>,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    let cli = BuildInfo::current();
+    let server = if args.remote {
+        let server_url =
+            server_url.ok_or_else(|| anyhow!("--remote needs the server URL in --server"))?;
+        let client = RemoteClient::new(server_url, token)?;
+        let request = client.request(Method::GET, "/api/v1/system/info");
+        let server: BuildInfo = client
+            .send(request)
+            .await
+            .map_err(|e| anyhow!("Failed to read the version of {server_url}: {e}"))?;
+        Some(server)
+    } else {
+        None
+    };
+
+    let skew = server.as_ref().and_then(|server| cli.skew(server));
+    if let Some(skew) = &skew {
+        crate::warnings::warn(format!(
+            "CLI and server {skew}; upgrade one to match the other"
+        ));
+    }
+    let report = VersionReport { cli, server, skew };
+    crate::commands::print_output(&report, output_format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_local_version() {
+        let args = VersionArgs { remote: false };
+        let result = execute(&args, None, None, crate::OutputFormat::Json).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_remote_version_requires_server() {
+        let args = VersionArgs { remote: true };
+        let result = execute(&args, None, None, crate::OutputFormat::Json).await;
+        assert!(result.unwrap_err().to_string().contains("--server"));
+    }
+}
@@ -0,0 +1,168 @@
+/// VLAN and interface VLAN membership commands
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use std::collections::BTreeSet;
+use unet_core::datastore::DataStore;
+use unet_core::vlan::{
+    Vlan, VlanMembership, check_consistency, delete_vlan, interface_vlans, list_vlans,
+    parse_vlan_list, save_vlan, set_interface_vlan,
+};
+use uuid::Uuid;
+
+#[derive(Subcommand)]
+pub enum VlanCommands {
+    /// List VLAN definitions
+    List,
+    /// Define or rename a VLAN
+    Set(SetVlanArgs),
+    /// Remove a VLAN definition
+    Delete(DeleteVlanArgs),
+    /// Show a node's interface VLAN memberships
+    Interfaces(NodeVlanArgs),
+    /// Set or clear the VLAN membership of a node's interface
+    Assign(AssignVlanArgs),
+    /// List links whose ends disagree on VLANs
+    Check(CheckVlanArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct SetVlanArgs {
+    /// VLAN ID (1-4094)
+    pub id: u16,
+    /// VLAN name
+    pub name: String,
+    /// What the VLAN carries
+    #[arg(long)]
+    pub description: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct DeleteVlanArgs {
+    /// VLAN ID
+    pub id: u16,
+}
+
+#[derive(Args, Debug)]
+pub struct NodeVlanArgs {
+    /// Node ID
+    pub node_id: Uuid,
+}
+
+#[derive(Args, Debug)]
+pub struct AssignVlanArgs {
+    /// Node ID
+    pub node_id: Uuid,
+    /// Interface name
+    pub interface: String,
+    /// Make the interface an access port in this VLAN
+    #[arg(long, conflicts_with_all = ["trunk", "clear"])]
+    pub access: Option<u16>,
+    /// Make the interface a trunk allowing these VLANs, e.g. 10,20,100-110
+    #[arg(long, value_parser = parse_vlan_list, conflicts_with = "clear")]
+    pub trunk: Option<BTreeSet<u16>>,
+    /// Native VLAN of the trunk
+    #[arg(long, requires = "trunk")]
+    pub native: Option<u16>,
+    /// Remove the interface's membership
+    #[arg(long, required_unless_present_any = ["access", "trunk"])]
+    pub clear: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct CheckVlanArgs {
+    /// Only check this node's links
+    #[arg(long)]
+    pub node: Option<Uuid>,
+}
+
+/// Execute VLAN subcommands.
+///
+/// # Errors
+/// Returns an error if the VLAN or node is invalid, a membership references
+/// an undefined VLAN, datastore operations fail, or output formatting fails.
+pub async fn execute(
+    command: VlanCommands,
+    datastore: &dyn DataStore,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    match command {
+        VlanCommands::List => {
+            crate::commands::print_output(&list_vlans(datastore).await?, output_format)
+        }
+        VlanCommands::Set(args) => {
+            let vlan = Vlan::new(args.id, &args.name, args.description)?;
+            save_vlan(datastore, &vlan).await?;
+            crate::commands::print_output(&vlan, output_format)
+        }
+        VlanCommands::Delete(args) => {
+            delete_vlan(datastore, args.id).await?;
+            let output = serde_json::json!({
+                "message": "VLAN removed",
+                "id": args.id,
+            });
+            crate::commands::print_output(&output, output_format)
+        }
+        VlanCommands::Interfaces(args) => {
+            datastore.get_node_required(&args.node_id).await?;
+            let memberships = interface_vlans(datastore, args.node_id).await?;
+            crate::commands::print_output(&memberships, output_format)
+        }
+        VlanCommands::Assign(args) => {
+            datastore.get_node_required(&args.node_id).await?;
+            let membership = match (args.access, args.trunk) {
+                (Some(vlan), _) => Some(VlanMembership::Access { vlan }),
+                (None, Some(allowed)) => Some(VlanMembership::Trunk {
+                    allowed,
+                    native: args.native,
+                }),
+                (None, None) => None,
+            };
+            let memberships =
+                set_interface_vlan(datastore, args.node_id, &args.interface, membership).await?;
+            crate::commands::print_output(&memberships, output_format)
+        }
+        VlanCommands::Check(args) => {
+            let mismatches = check_consistency(datastore, args.node).await?;
+            crate::commands::print_output(&mismatches, output_format)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unet_core::datastore::MockDataStore;
+
+    #[tokio::test]
+    async fn test_set_vlan_stores_definition() {
+        let mut mock = MockDataStore::new();
+        mock.expect_put_setting()
+            .withf(|namespace, key, value| {
+                namespace == "vlans" && key == "110" && value["name"] == "users"
+            })
+            .times(1)
+            .returning(|_, _, _| Box::pin(async { Ok(()) }));
+
+        let args = SetVlanArgs {
+            id: 110,
+            name: "users".to_string(),
+            description: None,
+        };
+        let result = execute(VlanCommands::Set(args), &mock, crate::OutputFormat::Json).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_set_vlan_rejects_reserved_id() {
+        let mut mock = MockDataStore::new();
+        mock.expect_put_setting().times(0);
+
+        let args = SetVlanArgs {
+            id: 4095,
+            name: "reserved".to_string(),
+            description: None,
+        };
+        let result = execute(VlanCommands::Set(args), &mock, crate::OutputFormat::Json).await;
+        assert!(result.is_err());
+    }
+}
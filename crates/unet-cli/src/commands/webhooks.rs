@@ -0,0 +1,276 @@
+/// Webhook subscription management commands
+use anyhow::{Result, anyhow};
+use clap::{Args, Subcommand, ValueEnum};
+use std::collections::BTreeMap;
+use unet_core::datastore::DataStore;
+use unet_core::webhooks::{
+    DEFAULT_JIRA_ISSUE_TYPE, EventType, FloodControl, PayloadFormat, WebhookSubscription,
+    delete_dead_letter, delete_subscription, list_dead_letters, list_subscriptions,
+    retry_dead_letter, save_subscription,
+};
+use uuid::Uuid;
+
+use crate::confirm::{Confirmation, confirm};
+
+#[derive(Subcommand)]
+pub enum WebhookCommands {
+    /// List webhook subscriptions
+    List,
+    /// Subscribe a URL to events
+    Add(AddWebhookArgs),
+    /// Remove a webhook subscription
+    Delete(DeleteWebhookArgs),
+    /// List deliveries that failed every attempt
+    DeadLetters,
+    /// Requeue a dead letter for delivery
+    Retry(DeadLetterArgs),
+    /// Discard a dead letter
+    Discard(DeadLetterArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct AddWebhookArgs {
+    /// URL events are POSTed to
+    pub url: String,
+    /// Event type to deliver; repeat for several (default: all)
+    #[arg(long = "event", value_name = "TYPE")]
+    pub events: Vec<EventType>,
+    /// Only deliver events whose attribute matches, e.g. vendor=juniper; repeat for several
+    #[arg(long = "filter", value_name = "KEY=VALUE", value_parser = parse_filter)]
+    pub filters: Vec<(String, String)>,
+    /// Key used to sign request bodies
+    #[arg(long)]
+    pub secret: Option<String>,
+    /// Seconds to hold events so repeats with the same dedupe key are sent as one group
+    #[arg(long, value_name = "SECONDS", default_value_t = 0)]
+    pub group_window: u64,
+    /// Event attribute grouping events besides their type, e.g. location_id; repeat for several
+    #[arg(long = "dedupe-by", value_name = "ATTRIBUTE")]
+    pub dedupe_by: Vec<String>,
+    /// Most notifications per minute; later events are sent as one flood notification
+    #[arg(long, value_name = "COUNT")]
+    pub rate_limit: Option<u32>,
+    /// Shape of the request bodies
+    #[arg(long, value_enum, default_value_t = PayloadKind::Event)]
+    pub payload: PayloadKind,
+    /// Jira project key issues are created in
+    #[arg(long, value_name = "KEY", required_if_eq("payload", "jira"))]
+    pub jira_project: Option<String>,
+    /// Jira issue type
+    #[arg(long, value_name = "TYPE", default_value = DEFAULT_JIRA_ISSUE_TYPE)]
+    pub jira_issue_type: String,
+}
+
+/// Request body shapes
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum PayloadKind {
+    /// The event as is
+    Event,
+    /// An incident
+    #[value(help = "A ServiceNow incident")]
+    Servicenow,
+    /// A Jira issue
+    Jira,
+}
+
+#[derive(Args, Debug)]
+pub struct DeleteWebhookArgs {
+    /// Subscription ID
+    pub id: Uuid,
+    /// Skip confirmation prompt
+    #[arg(short = 'y', long)]
+    pub yes: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct DeadLetterArgs {
+    /// Dead letter ID
+    pub id: Uuid,
+}
+
+fn parse_filter(filter: &str) -> Result<(String, String), String> {
+    filter
+        .split_once('=')
+        .filter(|(key, _)| !key.is_empty())
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .ok_or_else(|| format!("Invalid filter {filter:?}: expected KEY=VALUE"))
+}
+
+/// Execute webhook subscription subcommands.
+///
+/// # Errors
+/// Returns an error if a subscription is invalid, the subscription or dead
+/// letter does not exist, datastore operations fail, or output formatting fails.
+pub async fn execute(
+    command: WebhookCommands,
+    datastore: &dyn DataStore,
+    output_format: crate::OutputFormat,
+) -> Result<()> {
+    match command {
+        WebhookCommands::List => {
+            let subscriptions: Vec<WebhookSubscription> = list_subscriptions(datastore)
+                .await?
+                .iter()
+                .map(WebhookSubscription::redacted)
+                .collect();
+            crate::commands::print_output(&subscriptions, output_format)
+        }
+        WebhookCommands::Add(args) => {
+            let filters: BTreeMap<String, String> = args.filters.into_iter().collect();
+            let flood_control = FloodControl {
+                group_window: args.group_window,
+                dedupe_by: args.dedupe_by,
+                rate_limit: args.rate_limit,
+            };
+            let payload_format = match args.payload {
+                PayloadKind::Event => PayloadFormat::Event,
+                PayloadKind::Servicenow => PayloadFormat::ServiceNow,
+                PayloadKind::Jira => PayloadFormat::Jira {
+                    project: args.jira_project.unwrap_or_default(),
+                    issue_type: args.jira_issue_type,
+                },
+            };
+            let subscription =
+                WebhookSubscription::new(&args.url, args.secret, args.events, filters)?
+                    .with_flood_control(flood_control)?
+                    .with_payload_format(payload_format)?;
+            save_subscription(datastore, &subscription).await?;
+            crate::commands::print_output(&subscription.redacted(), output_format)
+        }
+        WebhookCommands::Delete(args) => {
+            let subscription = list_subscriptions(datastore)
+                .await?
+                .into_iter()
+                .find(|subscription| subscription.id == args.id)
+                .ok_or_else(|| anyhow!("Webhook subscription {} not found", args.id))?;
+            let confirmation = Confirmation::new("Remove webhook subscription")
+                .affects(format!("{} ({})", subscription.url, subscription.id));
+            if !confirm(args.yes, &confirmation)? {
+                return Ok(());
+            }
+            delete_subscription(datastore, args.id).await?;
+            let output = serde_json::json!({
+                "message": "Webhook subscription removed",
+                "id": args.id,
+                "url": subscription.url,
+            });
+            crate::commands::print_output(&output, output_format)
+        }
+        WebhookCommands::DeadLetters => {
+            crate::commands::print_output(&list_dead_letters(datastore).await?, output_format)
+        }
+        WebhookCommands::Retry(args) => {
+            let delivery = retry_dead_letter(datastore, args.id).await?;
+            crate::commands::print_output(&delivery, output_format)
+        }
+        WebhookCommands::Discard(args) => {
+            delete_dead_letter(datastore, args.id).await?;
+            let output = serde_json::json!({
+                "message": "Dead letter discarded",
+                "id": args.id,
+            });
+            crate::commands::print_output(&output, output_format)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unet_core::datastore::MockDataStore;
+
+    #[test]
+    fn test_parse_filter() {
+        assert_eq!(
+            parse_filter("vendor=juniper").unwrap(),
+            ("vendor".to_string(), "juniper".to_string())
+        );
+        assert!(parse_filter("vendor").is_err());
+        assert!(parse_filter("=juniper").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_add_stores_subscription() {
+        let mut mock = MockDataStore::new();
+        mock.expect_put_setting()
+            .withf(|namespace, _, value| {
+                namespace == "webhook_subscriptions"
+                    && value["url"] == "https://hooks.example.com/unet"
+                    && value["events"][0] == "node.deleted"
+                    && value["filters"]["role"] == "router"
+            })
+            .times(1)
+            .returning(|_, _, _| Box::pin(async { Ok(()) }));
+
+        let command = WebhookCommands::Add(AddWebhookArgs {
+            url: "https://hooks.example.com/unet".to_string(),
+            events: vec![EventType::NodeDeleted],
+            filters: vec![("role".to_string(), "router".to_string())],
+            secret: None,
+            group_window: 0,
+            dedupe_by: Vec::new(),
+            rate_limit: None,
+            payload: PayloadKind::Event,
+            jira_project: None,
+            jira_issue_type: DEFAULT_JIRA_ISSUE_TYPE.to_string(),
+        });
+        let result = execute(command, &mock, crate::OutputFormat::Json).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_add_stores_flood_control() {
+        let mut mock = MockDataStore::new();
+        mock.expect_put_setting()
+            .withf(|_, _, value| {
+                value["flood_control"]["group_window"] == 300
+                    && value["flood_control"]["dedupe_by"][0] == "location_id"
+                    && value["flood_control"]["rate_limit"] == 20
+            })
+            .times(1)
+            .returning(|_, _, _| Box::pin(async { Ok(()) }));
+
+        let command = WebhookCommands::Add(AddWebhookArgs {
+            url: "https://hooks.example.com/unet".to_string(),
+            events: Vec::new(),
+            filters: Vec::new(),
+            secret: None,
+            group_window: 300,
+            dedupe_by: vec!["location_id".to_string()],
+            rate_limit: Some(20),
+            payload: PayloadKind::Event,
+            jira_project: None,
+            jira_issue_type: DEFAULT_JIRA_ISSUE_TYPE.to_string(),
+        });
+        let result = execute(command, &mock, crate::OutputFormat::Json).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_add_stores_jira_payload_format() {
+        let mut mock = MockDataStore::new();
+        mock.expect_put_setting()
+            .withf(|_, _, value| {
+                value["payload_format"]["format"] == "jira"
+                    && value["payload_format"]["project"] == "NET"
+                    && value["payload_format"]["issue_type"] == "Task"
+            })
+            .times(1)
+            .returning(|_, _, _| Box::pin(async { Ok(()) }));
+
+        let command = WebhookCommands::Add(AddWebhookArgs {
+            url: "https://example.atlassian.net/rest/api/2/issue".to_string(),
+            events: vec![EventType::PolicyFailed],
+            filters: Vec::new(),
+            secret: None,
+            group_window: 0,
+            dedupe_by: Vec::new(),
+            rate_limit: None,
+            payload: PayloadKind::Jira,
+            jira_project: Some("NET".to_string()),
+            jira_issue_type: DEFAULT_JIRA_ISSUE_TYPE.to_string(),
+        });
+        let result = execute(command, &mock, crate::OutputFormat::Json).await;
+        assert!(result.is_ok());
+    }
+}
@@ -0,0 +1,164 @@
+//! Confirmation prompts for destructive commands
+//!
+//! Destructive commands list what they are about to change and wait for a
+//! `y`/`yes` answer on stdin. `--yes` on the command, or a truthy
+//! `UNET_ASSUME_YES` in the environment, answers for the user. Without either,
+//! a command whose stdin is not a terminal fails rather than proceeding
+//! unconfirmed.
+//!
+//! `--yes` only skips prompts; `--force` is kept for overwriting existing
+//! files and never implies `--yes`.
+
+use anyhow::{Result, bail};
+use std::io::{BufRead, IsTerminal};
+
+/// Environment variable that answers yes to every confirmation prompt
+pub const ASSUME_YES_ENV: &str = "UNET_ASSUME_YES";
+
+/// A destructive action and the entities it affects
+#[derive(Debug, Clone)]
+pub struct Confirmation {
+    action: String,
+    affected: Vec<String>,
+}
+
+impl Confirmation {
+    /// Describes an action such as `Delete node`
+    #[must_use]
+    pub fn new(action: impl Into<String>) -> Self {
+        Self {
+            action: action.into(),
+            affected: Vec::new(),
+        }
+    }
+
+    /// Adds an affected entity to the listing
+    #[must_use]
+    pub fn affects(mut self, entity: impl Into<String>) -> Self {
+        self.affected.push(entity.into());
+        self
+    }
+
+    /// Adds a summary line for dependent entities, e.g. `3 links`, when `count` is non-zero
+    #[must_use]
+    pub fn also_affects(self, count: usize, singular: &str, plural: &str) -> Self {
+        match count {
+            0 => self,
+            1 => self.affects(format!("1 {singular}")),
+            n => self.affects(format!("{n} {plural}")),
+        }
+    }
+
+    /// Prompt text shown before asking
+    #[must_use]
+    pub fn prompt(&self) -> String {
+        let mut prompt = format!("{}:", self.action);
+        for entity in &self.affected {
+            prompt.push_str("\n  - ");
+            prompt.push_str(entity);
+        }
+        prompt.push_str("\nContinue? [y/N]");
+        prompt
+    }
+}
+
+/// Returns whether `UNET_ASSUME_YES` is set to a truthy value
+#[must_use]
+pub fn assume_yes_from_env() -> bool {
+    std::env::var(ASSUME_YES_ENV).is_ok_and(|value| is_truthy(&value))
+}
+
+fn is_truthy(value: &str) -> bool {
+    matches!(
+        value.trim().to_ascii_lowercase().as_str(),
+        "1" | "true" | "yes" | "y" | "on"
+    )
+}
+
+/// Asks for confirmation on stdin unless `yes` or `UNET_ASSUME_YES` is set
+///
+/// # Errors
+/// Returns an error if confirmation is needed but stdin is not a terminal, or
+/// reading the answer fails.
+pub fn confirm(yes: bool, confirmation: &Confirmation) -> Result<bool> {
+    if yes || assume_yes_from_env() {
+        return Ok(true);
+    }
+    let stdin = std::io::stdin();
+    if !stdin.is_terminal() {
+        bail!(
+            "{} needs confirmation; pass --yes or set {ASSUME_YES_ENV}=1",
+            confirmation.action
+        );
+    }
+    confirm_with(confirmation, &mut stdin.lock())
+}
+
+/// Prints the prompt and reads the answer from `reader`
+///
+/// # Errors
+/// Returns an error if reading the answer fails.
+pub fn confirm_with(confirmation: &Confirmation, reader: &mut impl BufRead) -> Result<bool> {
+    eprintln!("{}", confirmation.prompt());
+    let mut input = String::new();
+    reader.read_line(&mut input)?;
+    let input = input.trim().to_lowercase();
+    if input != "y" && input != "yes" {
+        eprintln!("Cancelled");
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_prompt_lists_affected_entities() {
+        let confirmation = Confirmation::new("Delete node")
+            .affects("edge-1")
+            .also_affects(2, "link", "links")
+            .also_affects(0, "policy result", "policy results");
+
+        assert_eq!(
+            confirmation.prompt(),
+            "Delete node:\n  - edge-1\n  - 2 links\nContinue? [y/N]"
+        );
+    }
+
+    #[test]
+    fn test_confirm_with_accepts_only_yes() {
+        let confirmation = Confirmation::new("Delete vendor").affects("cisco");
+
+        for (answer, expected) in [
+            ("y\n", true),
+            ("YES\n", true),
+            ("n\n", false),
+            ("\n", false),
+        ] {
+            let mut reader = Cursor::new(answer.as_bytes());
+            assert_eq!(
+                confirm_with(&confirmation, &mut reader).unwrap(),
+                expected,
+                "{answer:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_truthy_values() {
+        for value in ["1", "true", "YES", " on "] {
+            assert!(is_truthy(value), "{value}");
+        }
+        for value in ["", "0", "false", "no"] {
+            assert!(!is_truthy(value), "{value}");
+        }
+    }
+
+    #[test]
+    fn test_confirm_skips_prompt_with_yes() {
+        assert!(confirm(true, &Confirmation::new("Delete link")).unwrap());
+    }
+}
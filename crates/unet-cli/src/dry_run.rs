@@ -1,10 +1,14 @@
-use std::sync::Arc;
 use async_trait::async_trait;
+use std::sync::Arc;
 use tracing::info;
-use unet_core::datastore::types::{BatchResult, DataStoreError, DataStoreResult, PagedResult, QueryOptions};
+use unet_core::change_log::{ChangeEvent, EventFilter};
+use unet_core::datastore::types::{
+    BatchResult, DataStoreError, DataStoreResult, PagedResult, QueryOptions,
+};
 use unet_core::datastore::{BatchOperation, DataStore, Transaction};
 use unet_core::models::{Link, Location, Node};
 use unet_core::policy::PolicyExecutionResult;
+use unet_core::snmp::shards::ShardLease;
 use uuid::Uuid;
 
 pub struct DryRunStore {
@@ -12,23 +16,35 @@ pub struct DryRunStore {
 }
 
 impl DryRunStore {
-    pub fn new(inner: Arc<dyn DataStore>) -> Self { Self { inner } }
+    pub fn new(inner: Arc<dyn DataStore>) -> Self {
+        Self { inner }
+    }
 }
 
 #[async_trait]
 impl DataStore for DryRunStore {
-    fn name(&self) -> &'static str { "dry-run" }
+    fn name(&self) -> &'static str {
+        "dry-run"
+    }
 
-    async fn health_check(&self) -> DataStoreResult<()> { self.inner.health_check().await }
-    async fn begin_transaction(&self) -> DataStoreResult<Box<dyn Transaction>> { self.inner.begin_transaction().await }
+    async fn health_check(&self) -> DataStoreResult<()> {
+        self.inner.health_check().await
+    }
+    async fn begin_transaction(&self) -> DataStoreResult<Box<dyn Transaction>> {
+        self.inner.begin_transaction().await
+    }
 
     // Node ops
     async fn create_node(&self, node: &Node) -> DataStoreResult<Node> {
         info!("[dry-run] create_node: {}", node.name);
         Ok(node.clone())
     }
-    async fn get_node(&self, id: &Uuid) -> DataStoreResult<Option<Node>> { self.inner.get_node(id).await }
-    async fn list_nodes(&self, options: &QueryOptions) -> DataStoreResult<PagedResult<Node>> { self.inner.list_nodes(options).await }
+    async fn get_node(&self, id: &Uuid) -> DataStoreResult<Option<Node>> {
+        self.inner.get_node(id).await
+    }
+    async fn list_nodes(&self, options: &QueryOptions) -> DataStoreResult<PagedResult<Node>> {
+        self.inner.list_nodes(options).await
+    }
     async fn update_node(&self, node: &Node) -> DataStoreResult<Node> {
         info!("[dry-run] update_node: {}", node.name);
         Ok(node.clone())
@@ -37,16 +53,24 @@ impl DataStore for DryRunStore {
         info!("[dry-run] delete_node: {}", id);
         Ok(())
     }
-    async fn get_nodes_by_location(&self, location_id: &Uuid) -> DataStoreResult<Vec<Node>> { self.inner.get_nodes_by_location(location_id).await }
-    async fn search_nodes_by_name(&self, name: &str) -> DataStoreResult<Vec<Node>> { self.inner.search_nodes_by_name(name).await }
+    async fn get_nodes_by_location(&self, location_id: &Uuid) -> DataStoreResult<Vec<Node>> {
+        self.inner.get_nodes_by_location(location_id).await
+    }
+    async fn search_nodes_by_name(&self, name: &str) -> DataStoreResult<Vec<Node>> {
+        self.inner.search_nodes_by_name(name).await
+    }
 
     // Link ops
     async fn create_link(&self, link: &Link) -> DataStoreResult<Link> {
         info!("[dry-run] create_link: {}", link.name);
         Ok(link.clone())
     }
-    async fn get_link(&self, id: &Uuid) -> DataStoreResult<Option<Link>> { self.inner.get_link(id).await }
-    async fn list_links(&self, options: &QueryOptions) -> DataStoreResult<PagedResult<Link>> { self.inner.list_links(options).await }
+    async fn get_link(&self, id: &Uuid) -> DataStoreResult<Option<Link>> {
+        self.inner.get_link(id).await
+    }
+    async fn list_links(&self, options: &QueryOptions) -> DataStoreResult<PagedResult<Link>> {
+        self.inner.list_links(options).await
+    }
     async fn update_link(&self, link: &Link) -> DataStoreResult<Link> {
         info!("[dry-run] update_link: {}", link.name);
         Ok(link.clone())
@@ -55,16 +79,27 @@ impl DataStore for DryRunStore {
         info!("[dry-run] delete_link: {}", id);
         Ok(())
     }
-    async fn get_links_for_node(&self, node_id: &Uuid) -> DataStoreResult<Vec<Link>> { self.inner.get_links_for_node(node_id).await }
-    async fn get_links_between_nodes(&self, a: &Uuid, b: &Uuid) -> DataStoreResult<Vec<Link>> { self.inner.get_links_between_nodes(a, b).await }
+    async fn get_links_for_node(&self, node_id: &Uuid) -> DataStoreResult<Vec<Link>> {
+        self.inner.get_links_for_node(node_id).await
+    }
+    async fn get_links_between_nodes(&self, a: &Uuid, b: &Uuid) -> DataStoreResult<Vec<Link>> {
+        self.inner.get_links_between_nodes(a, b).await
+    }
 
     // Location ops
     async fn create_location(&self, location: &Location) -> DataStoreResult<Location> {
         info!("[dry-run] create_location: {}", location.name);
         Ok(location.clone())
     }
-    async fn get_location(&self, id: &Uuid) -> DataStoreResult<Option<Location>> { self.inner.get_location(id).await }
-    async fn list_locations(&self, options: &QueryOptions) -> DataStoreResult<PagedResult<Location>> { self.inner.list_locations(options).await }
+    async fn get_location(&self, id: &Uuid) -> DataStoreResult<Option<Location>> {
+        self.inner.get_location(id).await
+    }
+    async fn list_locations(
+        &self,
+        options: &QueryOptions,
+    ) -> DataStoreResult<PagedResult<Location>> {
+        self.inner.list_locations(options).await
+    }
     async fn update_location(&self, location: &Location) -> DataStoreResult<Location> {
         info!("[dry-run] update_location: {}", location.name);
         Ok(location.clone())
@@ -79,54 +114,226 @@ impl DataStore for DryRunStore {
         info!("[dry-run] create_vendor: {}", name);
         Ok(())
     }
-    async fn list_vendors(&self) -> DataStoreResult<Vec<String>> { self.inner.list_vendors().await }
+    async fn list_vendors(&self) -> DataStoreResult<Vec<String>> {
+        self.inner.list_vendors().await
+    }
     async fn delete_vendor(&self, name: &str) -> DataStoreResult<()> {
         info!("[dry-run] delete_vendor: {}", name);
         Ok(())
     }
 
+    // Settings
+    async fn get_setting(
+        &self,
+        namespace: &str,
+        key: &str,
+    ) -> DataStoreResult<Option<serde_json::Value>> {
+        self.inner.get_setting(namespace, key).await
+    }
+    async fn list_settings(
+        &self,
+        namespace: &str,
+    ) -> DataStoreResult<Vec<(String, serde_json::Value)>> {
+        self.inner.list_settings(namespace).await
+    }
+    async fn put_setting(
+        &self,
+        namespace: &str,
+        key: &str,
+        value: &serde_json::Value,
+    ) -> DataStoreResult<()> {
+        info!("[dry-run] put_setting: {}/{} -> {}", namespace, key, value);
+        Ok(())
+    }
+    async fn delete_setting(&self, namespace: &str, key: &str) -> DataStoreResult<()> {
+        info!("[dry-run] delete_setting: {}/{}", namespace, key);
+        Ok(())
+    }
+
+    // Change log
+    async fn append_change_event(&self, event: &ChangeEvent) -> DataStoreResult<ChangeEvent> {
+        info!(
+            "[dry-run] append_change_event: {} {}",
+            event.entity, event.entity_id
+        );
+        Ok(event.clone())
+    }
+    async fn list_change_events(&self, filter: &EventFilter) -> DataStoreResult<Vec<ChangeEvent>> {
+        self.inner.list_change_events(filter).await
+    }
+    async fn last_change_sequence(&self) -> DataStoreResult<i64> {
+        self.inner.last_change_sequence().await
+    }
+
+    // Shard leases
+    async fn acquire_shard_lease(
+        &self,
+        lease: &ShardLease,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> DataStoreResult<bool> {
+        info!(
+            "[dry-run] acquire_shard_lease: {} -> {} at {}",
+            lease.shard, lease.collector, now
+        );
+        Ok(true)
+    }
+    async fn list_shard_leases(&self) -> DataStoreResult<Vec<ShardLease>> {
+        self.inner.list_shard_leases().await
+    }
+    async fn release_shard_lease(&self, shard: u32, collector: &str) -> DataStoreResult<()> {
+        info!(
+            "[dry-run] release_shard_lease: {} from {}",
+            shard, collector
+        );
+        Ok(())
+    }
+
     // Batch
-    async fn batch_nodes(&self, operations: &[BatchOperation<Node>]) -> DataStoreResult<BatchResult> {
+    async fn batch_nodes(
+        &self,
+        operations: &[BatchOperation<Node>],
+    ) -> DataStoreResult<BatchResult> {
         info!("[dry-run] batch_nodes: {} ops", operations.len());
-        Ok(BatchResult { success_count: operations.len(), error_count: 0, errors: vec![] })
+        Ok(BatchResult {
+            success_count: operations.len(),
+            error_count: 0,
+            errors: vec![],
+        })
     }
-    async fn batch_links(&self, operations: &[BatchOperation<Link>]) -> DataStoreResult<BatchResult> {
+    async fn batch_links(
+        &self,
+        operations: &[BatchOperation<Link>],
+    ) -> DataStoreResult<BatchResult> {
         info!("[dry-run] batch_links: {} ops", operations.len());
-        Ok(BatchResult { success_count: operations.len(), error_count: 0, errors: vec![] })
+        Ok(BatchResult {
+            success_count: operations.len(),
+            error_count: 0,
+            errors: vec![],
+        })
     }
-    async fn batch_locations(&self, operations: &[BatchOperation<Location>]) -> DataStoreResult<BatchResult> {
+    async fn batch_locations(
+        &self,
+        operations: &[BatchOperation<Location>],
+    ) -> DataStoreResult<BatchResult> {
         info!("[dry-run] batch_locations: {} ops", operations.len());
-        Ok(BatchResult { success_count: operations.len(), error_count: 0, errors: vec![] })
+        Ok(BatchResult {
+            success_count: operations.len(),
+            error_count: 0,
+            errors: vec![],
+        })
     }
 
     // Stats
-    async fn get_entity_counts(&self) -> DataStoreResult<std::collections::HashMap<String, usize>> { self.inner.get_entity_counts().await }
-    async fn get_statistics(&self) -> DataStoreResult<std::collections::HashMap<String, serde_json::Value>> { self.inner.get_statistics().await }
+    async fn get_entity_counts(&self) -> DataStoreResult<std::collections::HashMap<String, usize>> {
+        self.inner.get_entity_counts().await
+    }
+    async fn get_statistics(
+        &self,
+    ) -> DataStoreResult<std::collections::HashMap<String, serde_json::Value>> {
+        self.inner.get_statistics().await
+    }
+    async fn count_orphaned_records(
+        &self,
+    ) -> DataStoreResult<std::collections::HashMap<String, usize>> {
+        self.inner.count_orphaned_records().await
+    }
 
     // Derived state
-    async fn get_node_status(&self, node_id: &Uuid) -> DataStoreResult<Option<unet_core::models::derived::NodeStatus>> { self.inner.get_node_status(node_id).await }
-    async fn get_node_interfaces(&self, node_id: &Uuid) -> DataStoreResult<Vec<unet_core::models::derived::InterfaceStatus>> { self.inner.get_node_interfaces(node_id).await }
-    async fn get_node_metrics(&self, node_id: &Uuid) -> DataStoreResult<Option<unet_core::models::derived::PerformanceMetrics>> { self.inner.get_node_metrics(node_id).await }
+    async fn get_node_status(
+        &self,
+        node_id: &Uuid,
+    ) -> DataStoreResult<Option<unet_core::models::derived::NodeStatus>> {
+        self.inner.get_node_status(node_id).await
+    }
+    async fn get_node_interfaces(
+        &self,
+        node_id: &Uuid,
+    ) -> DataStoreResult<Vec<unet_core::models::derived::InterfaceStatus>> {
+        self.inner.get_node_interfaces(node_id).await
+    }
+    async fn get_node_metrics(
+        &self,
+        node_id: &Uuid,
+    ) -> DataStoreResult<Option<unet_core::models::derived::PerformanceMetrics>> {
+        self.inner.get_node_metrics(node_id).await
+    }
+    async fn get_node_statuses(
+        &self,
+        node_ids: &[Uuid],
+    ) -> DataStoreResult<Vec<unet_core::models::derived::NodeStatus>> {
+        self.inner.get_node_statuses(node_ids).await
+    }
+    async fn record_performance_metrics(
+        &self,
+        node_id: &Uuid,
+        _recorded_at: chrono::DateTime<chrono::Utc>,
+        _metrics: &unet_core::models::derived::PerformanceMetrics,
+    ) -> DataStoreResult<()> {
+        info!("[dry-run] record_performance_metrics: {}", node_id);
+        Ok(())
+    }
+    async fn query_performance_history(
+        &self,
+        node_id: &Uuid,
+        query: &unet_core::models::derived::MetricQuery,
+    ) -> DataStoreResult<Vec<unet_core::models::derived::MetricPoint>> {
+        self.inner.query_performance_history(node_id, query).await
+    }
 
     // Policy
-    async fn store_policy_result(&self, node_id: &Uuid, rule_id: &str, result: &PolicyExecutionResult) -> DataStoreResult<()> {
-        info!("[dry-run] store_policy_result: node={} rule={} result={:?}", node_id, rule_id, result);
+    async fn store_policy_result(
+        &self,
+        node_id: &Uuid,
+        rule_id: &str,
+        result: &PolicyExecutionResult,
+    ) -> DataStoreResult<()> {
+        info!(
+            "[dry-run] store_policy_result: node={} rule={} result={:?}",
+            node_id, rule_id, result
+        );
         Ok(())
     }
-    async fn get_policy_results(&self, node_id: &Uuid) -> DataStoreResult<Vec<PolicyExecutionResult>> { self.inner.get_policy_results(node_id).await }
-    async fn get_latest_policy_results(&self, node_id: &Uuid) -> DataStoreResult<Vec<PolicyExecutionResult>> { self.inner.get_latest_policy_results(node_id).await }
-    async fn get_rule_results(&self, rule_id: &str) -> DataStoreResult<Vec<(Uuid, PolicyExecutionResult)>> { self.inner.get_rule_results(rule_id).await }
+    async fn get_policy_results(
+        &self,
+        node_id: &Uuid,
+    ) -> DataStoreResult<Vec<PolicyExecutionResult>> {
+        self.inner.get_policy_results(node_id).await
+    }
+    async fn get_latest_policy_results(
+        &self,
+        node_id: &Uuid,
+    ) -> DataStoreResult<Vec<PolicyExecutionResult>> {
+        self.inner.get_latest_policy_results(node_id).await
+    }
+    async fn get_rule_results(
+        &self,
+        rule_id: &str,
+    ) -> DataStoreResult<Vec<(Uuid, PolicyExecutionResult)>> {
+        self.inner.get_rule_results(rule_id).await
+    }
 
-    async fn update_node_custom_data(&self, node_id: &Uuid, custom_data: &serde_json::Value) -> DataStoreResult<()> {
-        info!("[dry-run] update_node_custom_data: {} -> {}", node_id, custom_data);
+    async fn update_node_custom_data(
+        &self,
+        node_id: &Uuid,
+        custom_data: &serde_json::Value,
+    ) -> DataStoreResult<()> {
+        info!(
+            "[dry-run] update_node_custom_data: {} -> {}",
+            node_id, custom_data
+        );
         // Optionally verify node exists
         if self.inner.get_node(node_id).await?.is_none() {
-            return Err(DataStoreError::NotFound { entity_type: "Node".into(), id: node_id.to_string() });
+            return Err(DataStoreError::NotFound {
+                entity_type: "Node".into(),
+                id: node_id.to_string(),
+            });
         }
         Ok(())
     }
 
-    async fn get_nodes_for_policy_evaluation(&self) -> DataStoreResult<Vec<Node>> { self.inner.get_nodes_for_policy_evaluation().await }
+    async fn get_nodes_for_policy_evaluation(&self) -> DataStoreResult<Vec<Node>> {
+        self.inner.get_nodes_for_policy_evaluation().await
+    }
 }
 
 #[cfg(test)]
@@ -138,8 +345,13 @@ mod tests {
     #[tokio::test]
     async fn test_dry_run_create_update_delete_node_ok() {
         let node = NodeBuilder::new()
-            .name("n1").domain("example.com").vendor(Vendor::Cisco).model("ISR").role(DeviceRole::Router)
-            .build().unwrap();
+            .name("n1")
+            .domain("example.com")
+            .vendor(Vendor::Cisco)
+            .model("ISR")
+            .role(DeviceRole::Router)
+            .build()
+            .unwrap();
         let mock = MockDataStore::new();
         let store = DryRunStore::new(Arc::new(mock));
         let created = store.create_node(&node).await.unwrap();
@@ -0,0 +1,320 @@
+//! Exit codes and machine-readable error output
+//!
+//! A failed command exits with the code of its [`ErrorKind`], so scripts can
+//! branch on the kind of failure without parsing messages:
+//!
+//! | Code | Kind |
+//! |------|------|
+//! | 0 | success |
+//! | 1 | any other failure |
+//! | 2 | invalid command line (reported by the argument parser) |
+//! | 3 | validation error |
+//! | 4 | not found |
+//! | 5 | conflict with stored data |
+//! | 6 | connection failure (database, server, or device) |
+//! | 7 | partial success |
+//!
+//! The kind and error code come from the first error in the chain that
+//! carries one: a `DataStoreError` or `unet_core::Error`, a server response,
+//! or a [`CommandError`] raised by the command itself. With
+//! `--error-format json` the error is written to stderr as one JSON object.
+
+use clap::ValueEnum;
+use reqwest::StatusCode;
+use serde::Serialize;
+use std::process::ExitCode;
+use unet_core::datastore::DataStoreError;
+use unet_core::error;
+
+use crate::remote::RemoteClientError;
+
+/// How failures are written to stderr
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum ErrorFormat {
+    /// The message and its causes
+    #[default]
+    Text,
+    /// One JSON object with the kind, code, and exit code
+    Json,
+}
+
+/// Kind of failure, each with a stable exit code
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// Any failure without a more specific kind
+    Failure,
+    /// Invalid input or configuration
+    Validation,
+    /// The entity does not exist
+    NotFound,
+    /// The change conflicts with stored data
+    Conflict,
+    /// The database, server, or device could not be reached
+    Connection,
+    /// The command succeeded for only some of its items
+    PartialSuccess,
+}
+
+impl ErrorKind {
+    /// Process exit code for the kind
+    #[must_use]
+    pub const fn exit_code(self) -> u8 {
+        match self {
+            Self::Failure => 1,
+            Self::Validation => 3,
+            Self::NotFound => 4,
+            Self::Conflict => 5,
+            Self::Connection => 6,
+            Self::PartialSuccess => 7,
+        }
+    }
+}
+
+/// An error raised by a command with an explicit kind
+#[derive(Debug, thiserror::Error)]
+#[error("{message}")]
+pub struct CommandError {
+    /// Kind of failure
+    pub kind: ErrorKind,
+    /// Error code from `unet_core::error`
+    pub code: &'static str,
+    /// Human-readable message
+    pub message: String,
+}
+
+impl CommandError {
+    /// Invalid input
+    #[must_use]
+    pub fn validation(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Validation, error::VALIDATION_ERROR, message)
+    }
+
+    /// An entity named on the command line does not exist
+    #[must_use]
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::NotFound, error::NOT_FOUND, message)
+    }
+
+    /// The change conflicts with stored data
+    #[must_use]
+    pub fn conflict(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Conflict, error::CONFLICT, message)
+    }
+
+    /// The command succeeded for only some of its items
+    #[must_use]
+    pub fn partial_success(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::PartialSuccess, error::PARTIAL_SUCCESS, message)
+    }
+
+    fn new(kind: ErrorKind, code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// A failure as reported with `--error-format json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ErrorReport {
+    /// Kind of failure
+    pub kind: ErrorKind,
+    /// Error code
+    pub code: String,
+    /// Process exit code
+    pub exit_code: u8,
+    /// Top-level message
+    pub message: String,
+    /// Underlying errors, outermost first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub causes: Vec<String>,
+}
+
+impl ErrorReport {
+    /// Classifies an error by the first error in its chain with a known kind
+    #[must_use]
+    pub fn new(err: &anyhow::Error) -> Self {
+        let (kind, code) = err
+            .chain()
+            .find_map(classify)
+            .unwrap_or_else(|| (ErrorKind::Failure, error::OTHER_ERROR.to_string()));
+        Self {
+            kind,
+            code,
+            exit_code: kind.exit_code(),
+            message: err.to_string(),
+            causes: err.chain().skip(1).map(ToString::to_string).collect(),
+        }
+    }
+}
+
+/// Kind and code of one error, if its type is known
+fn classify(err: &(dyn std::error::Error + 'static)) -> Option<(ErrorKind, String)> {
+    if let Some(err) = err.downcast_ref::<CommandError>() {
+        return Some((err.kind, err.code.to_string()));
+    }
+    if let Some(err) = err.downcast_ref::<DataStoreError>() {
+        let kind = match err {
+            DataStoreError::NotFound { .. } => ErrorKind::NotFound,
+            DataStoreError::ValidationError { .. } => ErrorKind::Validation,
+            DataStoreError::ConstraintViolation { .. } => ErrorKind::Conflict,
+            DataStoreError::ConnectionError { .. }
+            | DataStoreError::Timeout { .. }
+            | DataStoreError::Busy { .. } => ErrorKind::Connection,
+            DataStoreError::TransactionError { .. }
+            | DataStoreError::InternalError { .. }
+            | DataStoreError::UnsupportedOperation { .. } => ErrorKind::Failure,
+        };
+        return Some((kind, err.error_code().to_string()));
+    }
+    if let Some(err) = err.downcast_ref::<unet_core::Error>() {
+        let kind = match err {
+            unet_core::Error::Config { .. } | unet_core::Error::Validation { .. } => {
+                ErrorKind::Validation
+            }
+            unet_core::Error::Network { .. } | unet_core::Error::Snmp { .. } => {
+                ErrorKind::Connection
+            }
+            _ => ErrorKind::Failure,
+        };
+        return Some((kind, err.error_code().to_string()));
+    }
+    if let Some(err) = err.downcast_ref::<RemoteClientError>() {
+        return Some(match err {
+            RemoteClientError::Transport(_) => (
+                ErrorKind::Connection,
+                error::NET_CONNECTION_REFUSED.to_string(),
+            ),
+            RemoteClientError::Decode(_) => {
+                (ErrorKind::Failure, error::SERIAL_JSON_FAILED.to_string())
+            }
+            RemoteClientError::Api { status, code, .. } => (status_kind(*status), code.clone()),
+        });
+    }
+    if err.is::<reqwest::Error>() {
+        return Some((
+            ErrorKind::Connection,
+            error::NET_CONNECTION_REFUSED.to_string(),
+        ));
+    }
+    err.downcast_ref::<std::io::Error>()
+        .map(|err| match err.kind() {
+            std::io::ErrorKind::NotFound => {
+                (ErrorKind::NotFound, error::IO_FILE_NOT_FOUND.to_string())
+            }
+            std::io::ErrorKind::PermissionDenied => {
+                (ErrorKind::Failure, error::IO_PERMISSION_DENIED.to_string())
+            }
+            _ => (ErrorKind::Failure, error::OTHER_ERROR.to_string()),
+        })
+}
+
+/// Kind of a failed server response
+const fn status_kind(status: StatusCode) -> ErrorKind {
+    match status {
+        StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => ErrorKind::Validation,
+        StatusCode::NOT_FOUND => ErrorKind::NotFound,
+        StatusCode::CONFLICT => ErrorKind::Conflict,
+        StatusCode::BAD_GATEWAY | StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT => {
+            ErrorKind::Connection
+        }
+        _ => ErrorKind::Failure,
+    }
+}
+
+/// Writes a failure to stderr in `format` and returns its exit code
+#[must_use]
+pub fn report(err: &anyhow::Error, format: ErrorFormat) -> ExitCode {
+    let report = ErrorReport::new(err);
+    match format {
+        ErrorFormat::Text => eprintln!("Error: {err:?}"),
+        ErrorFormat::Json => match serde_json::to_string(&report) {
+            Ok(json) => eprintln!("{json}"),
+            Err(_) => eprintln!("Error: {err:?}"),
+        },
+    }
+    ExitCode::from(report.exit_code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_datastore_errors_keep_their_kind_through_context() {
+        let err = anyhow::Error::new(DataStoreError::NotFound {
+            entity_type: "Node".to_string(),
+            id: "edge-1".to_string(),
+        })
+        .context("Failed to show node");
+
+        let report = ErrorReport::new(&err);
+
+        assert_eq!(report.kind, ErrorKind::NotFound);
+        assert_eq!(report.code, error::NOT_FOUND);
+        assert_eq!(report.exit_code, 4);
+        assert_eq!(report.message, "Failed to show node");
+        assert_eq!(report.causes, vec!["Entity not found: Node with id edge-1"]);
+    }
+
+    #[test]
+    fn test_error_kinds_and_codes() {
+        let conflict = anyhow::Error::new(DataStoreError::ConstraintViolation {
+            message: "UNIQUE constraint failed: nodes.name".to_string(),
+        });
+        let invalid = anyhow::Error::new(unet_core::Error::validation("vendor", "unknown"));
+        let partial = anyhow::Error::new(CommandError::partial_success("2 of 5 failed"));
+        let remote = anyhow::Error::new(RemoteClientError::Api {
+            status: StatusCode::NOT_FOUND,
+            code: "NOT_FOUND".to_string(),
+            message: "Node not found".to_string(),
+        });
+        let missing_file: anyhow::Result<()> =
+            Err(std::io::Error::from(std::io::ErrorKind::NotFound)).context("policies.toml");
+
+        for (err, kind, code) in [
+            (
+                conflict,
+                ErrorKind::Conflict,
+                error::DB_CONSTRAINT_VIOLATION,
+            ),
+            (invalid, ErrorKind::Validation, error::VALIDATION_ERROR),
+            (partial, ErrorKind::PartialSuccess, error::PARTIAL_SUCCESS),
+            (remote, ErrorKind::NotFound, error::NOT_FOUND),
+            (
+                missing_file.unwrap_err(),
+                ErrorKind::NotFound,
+                error::IO_FILE_NOT_FOUND,
+            ),
+            (
+                anyhow::anyhow!("boom"),
+                ErrorKind::Failure,
+                error::OTHER_ERROR,
+            ),
+        ] {
+            let report = ErrorReport::new(&err);
+            assert_eq!((report.kind, report.code.as_str()), (kind, code), "{err}");
+        }
+    }
+
+    #[test]
+    fn test_json_report_shape() {
+        let err = anyhow::Error::new(CommandError::conflict("Node edge-1 already exists"));
+
+        let json = serde_json::to_value(ErrorReport::new(&err)).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "kind": "conflict",
+                "code": "CONFLICT",
+                "exit_code": 5,
+                "message": "Node edge-1 already exists"
+            })
+        );
+    }
+}
@@ -8,9 +8,13 @@ use unet_core::config::Config;
 use unet_core::prelude::*;
 
 pub mod commands;
+pub mod confirm;
 pub mod dry_run;
+pub mod errors;
 mod remote;
+pub mod resolve;
 pub mod runtime;
+pub mod warnings;
 
 pub use runtime::{AppContext, Db};
 
@@ -43,6 +47,10 @@ pub struct Cli {
     #[arg(short = 'f', long, default_value = "table")]
     pub output: OutputFormat,
 
+    /// How failures are written to stderr
+    #[arg(long, value_enum, default_value_t)]
+    pub error_format: errors::ErrorFormat,
+
     #[command(subcommand)]
     pub command: Commands,
 
@@ -82,9 +90,54 @@ pub enum Commands {
     /// Link management commands
     #[command(subcommand)]
     Links(commands::links::LinkCommands),
+    /// Human-readable slugs accepted in place of IDs
+    #[command(subcommand)]
+    Slugs(commands::slugs::SlugCommands),
     /// Vendor management commands
     #[command(subcommand)]
     Vendors(commands::vendors::VendorCommands),
+    /// Default custom data for new nodes, by role or vendor
+    #[command(subcommand)]
+    NodeDefaults(commands::node_defaults::NodeDefaultsCommands),
+    /// Golden configuration assignment and conformance scoring
+    #[command(subcommand)]
+    Golden(commands::golden::GoldenCommands),
+    /// Dynamic node groups defined by saved filters
+    #[command(subcommand)]
+    Groups(commands::groups::GroupCommands),
+    /// Collected configurations and approved change windows
+    #[command(subcommand)]
+    Changes(commands::changes::ChangeCommands),
+    /// Fleet-wide template rendering and change previews
+    #[command(subcommand)]
+    Templates(commands::templates::TemplateCommands),
+    /// VLAN definitions, interface memberships, and consistency checks
+    #[command(subcommand)]
+    Vlans(commands::vlans::VlanCommands),
+    /// Fleet-wide reports, such as firmware compliance
+    #[command(subcommand)]
+    Reports(commands::reports::ReportCommands),
+    /// Change log of nodes, links, and locations
+    #[command(subcommand)]
+    Events(commands::events::EventCommands),
+    /// Live tail of the server's logs, read through --server
+    #[command(subcommand)]
+    Logs(commands::logs::LogCommands),
+    /// Topology analysis commands
+    #[command(subcommand)]
+    Topology(commands::topology::TopologyCommands),
+    /// Save SNMP walks and replay them through the derived-state pipeline
+    #[command(subcommand)]
+    Snmp(commands::snmp::SnmpCommands),
+    /// SNMP OID profile management commands
+    #[command(subcommand)]
+    OidProfiles(commands::oid_profiles::OidProfileCommands),
+    /// Webhook subscriptions and failed deliveries
+    #[command(subcommand)]
+    Webhooks(commands::webhooks::WebhookCommands),
+    /// Pause and resume polling, e.g. during maintenance
+    #[command(subcommand)]
+    Polling(commands::polling::PollingCommands),
     /// Policy management commands
     #[command(subcommand)]
     Policy(commands::policy::PolicyCommands),
@@ -92,6 +145,24 @@ pub enum Commands {
     Import(commands::import::ImportArgs),
     /// Export data to files
     Export(commands::export::ExportArgs),
+    /// Inventory exports the server runs on a schedule
+    #[command(subcommand)]
+    ExportSchedules(commands::export_schedules::ExportScheduleCommands),
+    /// Export and import configured secrets between environments
+    #[command(subcommand)]
+    Secrets(commands::secrets::SecretsCommands),
+    /// Scoped API keys for the server
+    #[command(subcommand)]
+    ApiKeys(commands::api_keys::ApiKeyCommands),
+    /// Administrative commands
+    #[command(subcommand)]
+    Admin(commands::admin::AdminCommands),
+    /// Diagnose configuration, database, and connectivity problems
+    Doctor(commands::doctor::DoctorArgs),
+    /// Interactive shell keeping one connection open across commands
+    Shell(commands::shell::ShellArgs),
+    /// Show the CLI version and, with --remote, compare it with the server's
+    Version(commands::version::VersionArgs),
 }
 
 /// Run the CLI using parsed `Cli` and an injected runtime context.
@@ -106,18 +177,103 @@ pub async fn run_with(ctx: AppContext, cli: Cli) -> Result<()> {
     // Initialize tracing with config
     init_tracing(&config.logging)?;
 
+    // Doctor checks the server itself, so it runs locally even with --server
+    if let Commands::Doctor(args) = &cli.command {
+        let input = commands::doctor::DoctorInput {
+            config: &config,
+            database_url: &cli.database_url,
+            server_url: cli.server.as_deref(),
+            token: cli.token.as_deref(),
+        };
+        return commands::doctor::execute(args, &ctx, &input, cli.output).await;
+    }
+
+    // The version is built in; only --remote asks the server
+    if let Commands::Version(args) = &cli.command {
+        let server_url = cli.server.as_deref();
+        return commands::version::execute(args, server_url, cli.token.as_deref(), cli.output)
+            .await;
+    }
+
+    // Logs are held in the server's memory, so they are only read from a server
+    if let Commands::Logs(command) = &cli.command {
+        let server_url = cli.server.as_deref();
+        return commands::logs::execute(command, server_url, cli.token.as_deref(), cli.output)
+            .await;
+    }
+
+    // Secrets are read from the local configuration, never from a server;
+    // only device credentials being imported go to the datastore
+    if let Commands::Secrets(command) = &cli.command {
+        if !command.seeds_nodes() {
+            return commands::secrets::execute(command, &config, cli.output);
+        }
+    }
+
+    // Template fixtures are local files, tested without a datastore or server
+    if let Commands::Templates(commands::templates::TemplateCommands::Test(args)) = &cli.command {
+        return commands::templates::test_fixtures(args, cli.output);
+    }
+
+    // Walk files are replayed offline, without a datastore or server
+    if let Commands::Snmp(commands::snmp::SnmpCommands::Replay(args)) = &cli.command {
+        return commands::snmp::replay(args, &config, cli.output);
+    }
+
     if let Some(server_url) = cli.server.as_deref() {
-        return remote::dispatch(cli.command, server_url, cli.token.as_deref(), cli.output).await;
+        let client = remote::RemoteClient::new(server_url, cli.token.as_deref())?;
+        if let Commands::Shell(args) = &cli.command {
+            let backend = commands::shell::Backend::Remote(client);
+            return commands::shell::run(args, backend, &config, cli.output).await;
+        }
+        return remote::dispatch(cli.command, &client, cli.output).await;
     }
 
     // Initialize SQLite datastore via injected runtime
     let database_url = cli.database_url.clone();
     // Optionally emit debug logs controlled by config logging settings
 
-    let datastore = build_datastore(&ctx, &database_url, cli.dry_run).await?;
+    if let Commands::Admin(commands::admin::AdminCommands::EncryptDatabase(args)) = &cli.command {
+        let encryption_key = config.database_encryption_key()?;
+        return commands::admin::encrypt_database(
+            args,
+            &database_url,
+            encryption_key.as_deref(),
+            cli.output,
+        )
+        .await;
+    }
+
+    let datastore = build_datastore(&ctx, &database_url, &config, cli.dry_run).await?;
 
-    // Execute command
-    dispatch_command(cli.command, datastore.as_ref(), cli.output).await
+    if let Commands::Shell(args) = &cli.command {
+        let backend = commands::shell::Backend::Local(datastore.as_ref());
+        return commands::shell::run(args, backend, &config, cli.output).await;
+    }
+
+    execute_local(cli.command, datastore.as_ref(), &config, cli.output).await
+}
+
+/// Runs a command against an open datastore
+pub(crate) async fn execute_local(
+    command: Commands,
+    datastore: &dyn unet_core::datastore::DataStore,
+    config: &Config,
+    output: OutputFormat,
+) -> Result<()> {
+    if let Commands::Nodes(commands::nodes::NodeCommands::TestAccess(args)) = &command {
+        return commands::nodes::test_access(args, datastore, &config.snmp, output).await;
+    }
+
+    if let Commands::Nodes(commands::nodes::NodeCommands::Onboard(args)) = &command {
+        return commands::nodes::onboard(args, datastore, config, output).await;
+    }
+
+    if let Commands::Links(commands::links::LinkCommands::Measure(args)) = &command {
+        return commands::links::measure(args, datastore, config, output).await;
+    }
+
+    dispatch_command(command, datastore, config, output).await
 }
 
 fn load_config(cli: &Cli) -> Result<Config> {
@@ -136,21 +292,54 @@ fn load_config(cli: &Cli) -> Result<Config> {
 async fn build_datastore(
     ctx: &AppContext,
     database_url: &str,
+    config: &Config,
     dry_run: bool,
 ) -> Result<Box<dyn unet_core::datastore::DataStore>> {
-    let db = (ctx.connect)(database_url).await.map_err(|e| {
-        error!("Failed to connect to database: {}", e);
-        anyhow::anyhow!("Failed to connect to database: {e}")
-    })?;
+    let db = if let Some(key) = config.database_encryption_key()? {
+        // The injected connector has no key parameter, so encrypted databases
+        // are opened (and their key verified) by the store itself
+        let store = unet_core::datastore::sqlite::SqliteStore::new_encrypted(database_url, &key)
+            .await
+            .map_err(|e| {
+                error!("Failed to open encrypted database: {}", e);
+                anyhow::anyhow!("Failed to open encrypted database: {e}")
+            })?;
+        Db(store.connection().clone())
+    } else {
+        (ctx.connect)(database_url).await.map_err(|e| {
+            error!("Failed to connect to database: {}", e);
+            anyhow::Error::new(DataStoreError::ConnectionError {
+                message: e.to_string(),
+            })
+            .context("Failed to connect to database")
+        })?
+    };
 
     (ctx.migrate)(&db).await.map_err(|e| {
         error!("Failed to run migrations: {}", e);
         anyhow::anyhow!("Failed to run migrations: {e}")
     })?;
 
-    let base: Box<dyn unet_core::datastore::DataStore> = Box::new(
-        unet_core::datastore::sqlite::SqliteStore::from_connection(db.0),
+    let mut base: Box<dyn unet_core::datastore::DataStore> = Box::new(
+        unet_core::datastore::sqlite::SqliteStore::from_connection(db.0)
+            .with_change_log(config.change_log.enabled),
     );
+    if config.change_log.enabled {
+        let store = unet_core::change_log::ChangeLogStore::with_builtins(Arc::from(base))
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to open the change log: {e}"))?;
+        base = Box::new(store);
+    }
+    let field_encryption = unet_core::secrets::FieldEncryption::from_config(&config.secrets)
+        .map_err(|e| anyhow::anyhow!("Invalid secrets configuration: {e}"))?;
+    if let Some(fields) = field_encryption {
+        // Whoever holds the master key locally may read the plaintext
+        base = Box::new(unet_core::secrets::EncryptedFieldsStore::new(
+            Arc::from(base),
+            Arc::new(fields),
+            true,
+        ));
+    }
     if dry_run {
         info!("Dry-run mode enabled: no changes will be persisted");
         Ok(Box::new(crate::dry_run::DryRunStore::new(Arc::from(base))))
@@ -162,16 +351,64 @@ async fn build_datastore(
 async fn dispatch_command(
     command: Commands,
     datastore: &dyn unet_core::datastore::DataStore,
+    config: &Config,
     output: OutputFormat,
 ) -> Result<()> {
     match command {
         Commands::Nodes(cmd) => commands::nodes::execute(cmd, datastore, output).await,
         Commands::Locations(cmd) => commands::locations::execute(cmd, datastore, output).await,
         Commands::Links(cmd) => commands::links::execute(cmd, datastore, output).await,
+        Commands::Slugs(cmd) => commands::slugs::execute(cmd, datastore, output).await,
         Commands::Vendors(cmd) => commands::vendors::execute(cmd, datastore, output).await,
+        Commands::NodeDefaults(cmd) => {
+            commands::node_defaults::execute(cmd, datastore, output).await
+        }
+        Commands::Golden(cmd) => commands::golden::execute(cmd, datastore, output).await,
+        Commands::Groups(cmd) => commands::groups::execute(cmd, datastore, output).await,
+        Commands::Changes(cmd) => commands::changes::execute(cmd, datastore, output).await,
+        Commands::Templates(cmd) => commands::templates::execute(cmd, datastore, output).await,
+        Commands::Vlans(cmd) => commands::vlans::execute(cmd, datastore, output).await,
+        Commands::Reports(cmd) => commands::reports::execute(cmd, datastore, output).await,
+        Commands::Events(cmd) => commands::events::execute(cmd, datastore, output).await,
+        Commands::Logs(cmd) => commands::logs::execute(&cmd, None, None, output).await,
+        Commands::Topology(cmd) => commands::topology::execute(cmd, datastore, output).await,
+        Commands::Snmp(cmd) => commands::snmp::execute(cmd, datastore, config, output).await,
+        Commands::OidProfiles(cmd) => commands::oid_profiles::execute(cmd, datastore, output).await,
+        Commands::Webhooks(cmd) => commands::webhooks::execute(cmd, datastore, output).await,
+        Commands::Polling(cmd) => commands::polling::execute(cmd, datastore, output).await,
         Commands::Policy(cmd) => commands::policy::execute(cmd, datastore).await,
         Commands::Import(args) => commands::import::execute(args, datastore, output).await,
         Commands::Export(args) => commands::export::execute(args, datastore, output).await,
+        Commands::ExportSchedules(cmd) => {
+            commands::export_schedules::execute(cmd, datastore, output).await
+        }
+        Commands::ApiKeys(cmd) => commands::api_keys::execute(cmd, datastore, output).await,
+        Commands::Admin(cmd) => commands::admin::execute(cmd, datastore, config, output).await,
+        Commands::Doctor(_) => Err(anyhow::anyhow!(
+            "doctor runs before the datastore is opened"
+        )),
+        Commands::Secrets(cmd) => {
+            commands::secrets::seed_nodes(&cmd, datastore, config, output).await
+        }
+        Commands::Shell(_) => Err(anyhow::anyhow!("the shell cannot be started from itself")),
+        Commands::Version(args) => commands::version::execute(&args, None, None, output).await,
+    }
+}
+
+/// Parse args, run with the default runtime, and report any failure.
+///
+/// A failure is written to stderr in the `--error-format` chosen and its
+/// exit code returned, as described in [`errors`].
+pub async fn run_and_report<I, S>(args: I) -> std::process::ExitCode
+where
+    I: IntoIterator<Item = S>,
+    S: Into<std::ffi::OsString> + Clone,
+{
+    let cli = Cli::parse_from(args);
+    let error_format = cli.error_format;
+    match run_with(AppContext::default(), cli).await {
+        Ok(()) => std::process::ExitCode::SUCCESS,
+        Err(err) => errors::report(&err, error_format),
     }
 }
 
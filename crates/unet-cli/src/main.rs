@@ -1,6 +1,6 @@
-use anyhow::Result;
+use std::process::ExitCode;
 
 #[tokio::main]
-async fn main() -> Result<()> {
-    unet_cli::run(std::env::args_os()).await
+async fn main() -> ExitCode {
+    unet_cli::run_and_report(std::env::args_os()).await
 }
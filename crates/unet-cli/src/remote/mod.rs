@@ -96,20 +96,39 @@ impl RemoteClient {
 
 pub async fn dispatch(
     command: Commands,
-    server_url: &str,
-    token: Option<&str>,
+    client: &RemoteClient,
     output: OutputFormat,
 ) -> Result<()> {
-    let client = RemoteClient::new(server_url, token)?;
-
     match command {
-        Commands::Nodes(command) => nodes::dispatch(command, &client, output).await,
+        Commands::Nodes(command) => nodes::dispatch(command, client, output).await,
         _ => Err(anyhow::anyhow!(
             "Remote mode currently supports node commands backed by the server API"
         )),
     }
 }
 
+/// Lists the ID and name of every node, reading all pages
+pub async fn node_names(client: &RemoteClient) -> Result<Vec<(uuid::Uuid, String)>> {
+    let mut names = Vec::new();
+    for page in 1_u64.. {
+        let request = client
+            .request(Method::GET, "/api/v1/nodes")
+            .query(&[("page", page.to_string()), ("per_page", "100".to_string())]);
+        let response: node_api::RemotePage<node_api::RemoteNodeResponse> =
+            client.send(request).await?;
+        names.extend(
+            response
+                .data
+                .into_iter()
+                .map(|node| (node.node.id, node.node.name)),
+        );
+        if !response.has_next {
+            break;
+        }
+    }
+    Ok(names)
+}
+
 pub fn parse_json_arg(
     value: Option<String>,
 ) -> Result<Option<serde_json::Value>, serde_json::Error> {
@@ -5,12 +5,22 @@ use reqwest::Method;
 use serde_json::json;
 use unet_core::{
     datastore::PagedResult,
+    hardware::HardwareInventory,
     models::{DeviceRole, Lifecycle, Vendor},
+    notes::Note,
+    provenance::NodeProvenance,
 };
+use uuid::Uuid;
 
 use crate::{
     OutputFormat,
-    commands::nodes::{NodeCommands, types::StatusType},
+    commands::nodes::{
+        NodeCommands, fields,
+        notes::note_body,
+        types::{HardwareNodeArgs, NoteCommands, StatusType},
+    },
+    confirm::{Confirmation, confirm},
+    resolve::{self, EntityArg},
 };
 
 use super::{
@@ -22,6 +32,15 @@ use super::{
     parse_json_arg, print_remote_output,
 };
 
+/// Resolves a node argument, listing nodes from the server only for names
+async fn resolve_node(client: &RemoteClient, node: &EntityArg) -> Result<Uuid> {
+    if let Some(id) = resolve::parse_id(&node.reference, node.by_id)? {
+        return Ok(id);
+    }
+    let nodes = super::node_names(client).await?;
+    resolve::node_named(&nodes, &node.reference)
+}
+
 pub(super) async fn dispatch(
     command: NodeCommands,
     client: &RemoteClient,
@@ -35,11 +54,15 @@ pub(super) async fn dispatch(
         NodeCommands::Delete(args) => delete(args, client, output).await,
         NodeCommands::Status(args) => status(args, client, output).await,
         NodeCommands::Metrics(args) => metrics(args, client, output).await,
-        NodeCommands::Compare(_) | NodeCommands::Polling(_) | NodeCommands::History(_) => {
-            Err(anyhow::anyhow!(
-                "Remote mode does not support compare, polling, or history node commands yet"
-            ))
-        }
+        NodeCommands::Note(command) => note(command, client, output).await,
+        NodeCommands::Hardware(args) => hardware(args, client, output).await,
+        NodeCommands::Compare(_)
+        | NodeCommands::Polling(_)
+        | NodeCommands::History(_)
+        | NodeCommands::TestAccess(_)
+        | NodeCommands::Onboard(_) => Err(anyhow::anyhow!(
+            "Remote mode does not support compare, polling, history, test-access, or onboard node commands yet"
+        )),
     }
 }
 
@@ -62,19 +85,31 @@ async fn list(
     if let Some(lifecycle) = args.lifecycle {
         request = request.query(&[("lifecycle", lifecycle)]);
     }
+    if let Some(management_ip) = args.management_ip {
+        request = request.query(&[("management_ip", management_ip)]);
+    }
+
+    if let Some(fields) = fields::parse(args.fields.as_deref())? {
+        // Sparse nodes are not full `Node`s, so they are printed as returned
+        let request = request.query(&[("fields", fields.to_query())]);
+        let response: RemotePage<serde_json::Value> = client.send(request).await?;
+        return print_remote_output(&into_paged(response, std::convert::identity)?, output);
+    }
 
     let response: RemotePage<RemoteNodeResponse> = client.send(request).await?;
-    let paged = PagedResult {
-        items: response.data.into_iter().map(|node| node.node).collect(),
+    print_remote_output(&into_paged(response, |node| node.node)?, output)
+}
+
+fn into_paged<T, U>(response: RemotePage<T>, map: impl FnMut(T) -> U) -> Result<PagedResult<U>> {
+    Ok(PagedResult {
+        items: response.data.into_iter().map(map).collect(),
         total_count: usize::try_from(response.total)?,
         page_size: usize::try_from(response.per_page)?,
         page: usize::try_from(response.page)?,
         total_pages: usize::try_from(response.total_pages)?,
         has_next: response.has_next,
         has_previous: response.has_prev,
-    };
-
-    print_remote_output(&paged, output)
+    })
 }
 
 async fn add(
@@ -96,7 +131,9 @@ async fn add(
         "lifecycle": lifecycle,
         "location_id": args.location_id,
         "management_ip": args.management_ip,
+        "management_ipv6": args.management_ipv6,
         "custom_data": custom_data,
+        "slug": args.slug,
     });
 
     let response: RemoteNodeResponse = client
@@ -110,23 +147,43 @@ async fn show(
     client: &RemoteClient,
     output: OutputFormat,
 ) -> Result<()> {
-    let node = fetch_node(client, args.id).await?;
-    if !args.include_status && !args.show_interfaces && !args.show_system_info {
-        return print_remote_output(&node.node, output);
+    let fields = fields::parse(args.fields.as_deref())?;
+    let id = resolve_node(client, &args.id).await?;
+    let node = match fields {
+        Some(fields) => {
+            let request = client
+                .request(Method::GET, &format!("/api/v1/nodes/{id}"))
+                .query(&[("fields", fields.to_query())]);
+            client.send::<serde_json::Value>(request).await?
+        }
+        None => serde_json::to_value(fetch_node(client, id).await?.node)?,
+    };
+    let derived = args.include_status || args.show_interfaces || args.show_system_info;
+    if !derived && !args.provenance {
+        return print_remote_output(&node, output);
     }
 
-    let mut response = json!({ "node": node.node, "derived_state": {} });
+    let mut response = json!({ "node": node });
+    if derived {
+        response["derived_state"] = json!({});
+    }
     if args.include_status {
         response["derived_state"]["status"] =
-            serde_json::to_value(fetch_status(client, args.id).await?)?;
+            serde_json::to_value(fetch_status(client, id).await?)?;
     }
     if args.show_interfaces {
         response["derived_state"]["interfaces"] =
-            serde_json::to_value(fetch_interfaces(client, args.id).await?)?;
+            serde_json::to_value(fetch_interfaces(client, id).await?)?;
     }
     if args.show_system_info {
         response["derived_state"]["system_info"] =
-            serde_json::to_value(fetch_status(client, args.id).await?.system_info)?;
+            serde_json::to_value(fetch_status(client, id).await?.system_info)?;
+    }
+    if args.provenance {
+        let provenance: NodeProvenance = client
+            .send(client.request(Method::GET, &format!("/api/v1/nodes/{id}/provenance")))
+            .await?;
+        response["provenance"] = serde_json::to_value(provenance.fields)?;
     }
 
     print_remote_output(&response, output)
@@ -137,6 +194,7 @@ async fn update(
     client: &RemoteClient,
     output: OutputFormat,
 ) -> Result<()> {
+    let id = resolve_node(client, &args.id).await?;
     let custom_data = parse_json_arg(args.custom_data)?;
     let payload = json!({
         "name": args.name,
@@ -147,13 +205,14 @@ async fn update(
         "lifecycle": args.lifecycle,
         "location_id": args.location_id,
         "management_ip": args.management_ip,
+        "management_ipv6": args.management_ipv6,
         "custom_data": custom_data,
     });
 
     let response: RemoteNodeResponse = client
         .send(
             client
-                .request(Method::PUT, &format!("/api/v1/nodes/{}", args.id))
+                .request(Method::PUT, &format!("/api/v1/nodes/{id}"))
                 .json(&payload),
         )
         .await?;
@@ -165,24 +224,17 @@ async fn delete(
     client: &RemoteClient,
     output: OutputFormat,
 ) -> Result<()> {
-    let node = fetch_node(client, args.id).await?;
+    let id = resolve_node(client, &args.id).await?;
+    let node = fetch_node(client, id).await?;
 
-    if !args.yes {
-        eprintln!(
-            "Are you sure you want to delete node '{}' ? [y/N]",
-            node.node.name
-        );
-        let mut input = String::new();
-        std::io::stdin().read_line(&mut input)?;
-        let confirmation = input.trim().to_lowercase();
-        if confirmation != "y" && confirmation != "yes" {
-            eprintln!("Cancelled");
-            return Ok(());
-        }
+    let confirmation =
+        Confirmation::new("Delete node").affects(format!("{} ({})", node.node.name, node.node.id));
+    if !confirm(args.yes, &confirmation)? {
+        return Ok(());
     }
 
     let _: () = client
-        .send(client.request(Method::DELETE, &format!("/api/v1/nodes/{}", args.id)))
+        .send(client.request(Method::DELETE, &format!("/api/v1/nodes/{id}")))
         .await?;
 
     let result = json!({
@@ -287,3 +339,45 @@ async fn metrics(
 
     print_remote_output(&response, output)
 }
+
+async fn note(command: NoteCommands, client: &RemoteClient, output: OutputFormat) -> Result<()> {
+    match command {
+        NoteCommands::Add(args) => {
+            let body = note_body(&args)?;
+            let id = resolve_node(client, &args.id).await?;
+            let payload = json!({ "body": body, "author": args.author });
+            let note: Note = client
+                .send(
+                    client
+                        .request(Method::POST, &format!("/api/v1/nodes/{id}/notes"))
+                        .json(&payload),
+                )
+                .await?;
+            print_remote_output(&note, output)
+        }
+        NoteCommands::List(args) => {
+            let id = resolve_node(client, &args.id).await?;
+            let notes: Vec<Note> = client
+                .send(client.request(Method::GET, &format!("/api/v1/nodes/{id}/notes")))
+                .await?;
+            print_remote_output(&notes, output)
+        }
+    }
+}
+
+async fn hardware(
+    args: HardwareNodeArgs,
+    client: &RemoteClient,
+    output: OutputFormat,
+) -> Result<()> {
+    let id = resolve_node(client, &args.id).await?;
+    let mut inventory: HardwareInventory = client
+        .send(client.request(Method::GET, &format!("/api/v1/nodes/{id}/hardware")))
+        .await?;
+    if let Some(kind) = args.kind {
+        inventory
+            .components
+            .retain(|component| component.kind == kind);
+    }
+    print_remote_output(&inventory, output)
+}
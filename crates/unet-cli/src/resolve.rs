@@ -0,0 +1,272 @@
+//! Resolving nodes, locations, and links named on the command line
+//!
+//! Commands take an entity by slug, name, or ID. A value that parses as a
+//! UUID is used as the ID; anything else is looked up as a slug first, then
+//! matched exactly against names (and node FQDNs or location paths). A name
+//! shared by several entities is rejected with the matching IDs listed, and
+//! `--id` skips the slug and name lookups entirely.
+
+use anyhow::Result;
+use clap::Args;
+use unet_core::datastore::{DataStore, QueryOptions};
+use unet_core::slug::{SlugKind, resolve_slug};
+use uuid::Uuid;
+
+use crate::errors::CommandError;
+
+/// Entity argument taking a name or an ID
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct EntityArg {
+    /// Slug, name, or ID
+    #[arg(value_name = "NAME|ID")]
+    pub reference: String,
+
+    /// Only accept an ID; names are not looked up
+    #[arg(long = "id")]
+    pub by_id: bool,
+}
+
+impl From<Uuid> for EntityArg {
+    fn from(id: Uuid) -> Self {
+        Self {
+            reference: id.to_string(),
+            by_id: true,
+        }
+    }
+}
+
+impl PartialEq<Uuid> for EntityArg {
+    fn eq(&self, other: &Uuid) -> bool {
+        self.reference.parse::<Uuid>().is_ok_and(|id| id == *other)
+    }
+}
+
+/// Returns the ID in `reference`, or `None` when names should be looked up
+///
+/// # Errors
+/// Returns an error if `by_id` is set and `reference` is not a UUID.
+pub fn parse_id(reference: &str, by_id: bool) -> Result<Option<Uuid>> {
+    match reference.parse::<Uuid>() {
+        Ok(id) => Ok(Some(id)),
+        Err(_) if by_id => {
+            Err(CommandError::validation(format!("'{reference}' is not a valid ID")).into())
+        }
+        Err(_) => Ok(None),
+    }
+}
+
+/// Returns the entity a slug names, current or retired
+async fn slug(datastore: &dyn DataStore, kind: SlugKind, reference: &str) -> Result<Option<Uuid>> {
+    Ok(resolve_slug(datastore, kind, reference)
+        .await?
+        .map(|found| found.id))
+}
+
+/// Picks the only entity matching `reference`
+fn single(kind: &str, reference: &str, matches: &[(Uuid, String)]) -> Result<Uuid> {
+    match matches {
+        [(id, _)] => Ok(*id),
+        [] => Err(CommandError::not_found(format!("{kind} '{reference}' not found")).into()),
+        _ => {
+            let candidates = matches
+                .iter()
+                .map(|(id, name)| format!("{name} ({id})"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            Err(CommandError::validation(format!(
+                "{kind} name '{reference}' is ambiguous: {candidates}; use one of the IDs instead"
+            ))
+            .into())
+        }
+    }
+}
+
+/// Resolves a node by ID, slug, name, or FQDN
+///
+/// # Errors
+/// Returns an error if no node or several nodes match, or the lookup fails.
+pub async fn node(datastore: &dyn DataStore, reference: &str, by_id: bool) -> Result<Uuid> {
+    if let Some(id) = parse_id(reference, by_id)? {
+        return Ok(id);
+    }
+    if let Some(id) = slug(datastore, SlugKind::Node, reference).await? {
+        return Ok(id);
+    }
+    let matches: Vec<(Uuid, String)> = datastore
+        .search_nodes_by_name(reference)
+        .await?
+        .into_iter()
+        .filter(|node| node.name == reference || node.fqdn == reference)
+        .map(|node| (node.id, node.fqdn))
+        .collect();
+    single("Node", reference, &matches)
+}
+
+/// Resolves a location by ID, slug, name, or path
+///
+/// # Errors
+/// Returns an error if no location or several locations match, or the lookup fails.
+pub async fn location(datastore: &dyn DataStore, reference: &str, by_id: bool) -> Result<Uuid> {
+    if let Some(id) = parse_id(reference, by_id)? {
+        return Ok(id);
+    }
+    if let Some(id) = slug(datastore, SlugKind::Location, reference).await? {
+        return Ok(id);
+    }
+    let matches: Vec<(Uuid, String)> = datastore
+        .list_locations(&QueryOptions::default())
+        .await?
+        .items
+        .into_iter()
+        .filter(|location| location.name == reference || location.path == reference)
+        .map(|location| (location.id, location.path))
+        .collect();
+    single("Location", reference, &matches)
+}
+
+/// Resolves a link by ID, slug, or name
+///
+/// # Errors
+/// Returns an error if no link or several links match, or the lookup fails.
+pub async fn link(datastore: &dyn DataStore, reference: &str, by_id: bool) -> Result<Uuid> {
+    if let Some(id) = parse_id(reference, by_id)? {
+        return Ok(id);
+    }
+    if let Some(id) = slug(datastore, SlugKind::Link, reference).await? {
+        return Ok(id);
+    }
+    let matches: Vec<(Uuid, String)> = datastore
+        .list_links(&QueryOptions::default())
+        .await?
+        .items
+        .into_iter()
+        .filter(|link| link.name == reference)
+        .map(|link| (link.id, link.name))
+        .collect();
+    single("Link", reference, &matches)
+}
+
+/// Resolves a node name against the ID and name of every node, as read from
+/// a server
+///
+/// # Errors
+/// Returns an error if no node or several nodes have the name.
+pub fn node_named(nodes: &[(Uuid, String)], name: &str) -> Result<Uuid> {
+    let matches: Vec<(Uuid, String)> = nodes
+        .iter()
+        .filter(|(_, node)| node == name)
+        .cloned()
+        .collect();
+    single("Node", name, &matches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::{ErrorKind, ErrorReport};
+    use unet_core::models::{DeviceRole, Location, Node, Vendor};
+
+    fn router(name: &str, domain: &str) -> Node {
+        Node::new(
+            name.to_string(),
+            domain.to_string(),
+            Vendor::Cisco,
+            DeviceRole::Router,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_node_resolves_by_id_name_or_fqdn() {
+        let store = test_support::sqlite::sqlite_store().await;
+        let dc1 = router("edge-01", "dc1.example.com");
+        let dc2 = router("edge-01", "dc2.example.com");
+        let core = router("core-01", "dc1.example.com");
+        for router in [&dc1, &dc2, &core] {
+            store.create_node(router).await.unwrap();
+        }
+
+        assert_eq!(node(&store, "core-01", false).await.unwrap(), core.id);
+        assert_eq!(
+            node(&store, "edge-01.dc2.example.com", false)
+                .await
+                .unwrap(),
+            dc2.id
+        );
+        assert_eq!(
+            node(&store, &dc1.id.to_string(), true).await.unwrap(),
+            dc1.id
+        );
+
+        let ambiguous = node(&store, "edge-01", false)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(ambiguous.contains("is ambiguous"));
+        assert!(ambiguous.contains(&dc1.id.to_string()));
+        assert!(ambiguous.contains(&dc2.id.to_string()));
+
+        let missing = node(&store, "edge-02", false).await.unwrap_err();
+        assert_eq!(ErrorReport::new(&missing).kind, ErrorKind::NotFound);
+        assert!(node(&store, "core-01", true).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_location_resolves_by_path() {
+        let store = test_support::sqlite::sqlite_store().await;
+        let campus = Location::new_root("campus".to_string(), "campus".to_string());
+        let mut building = Location::new_child(
+            "building-a".to_string(),
+            "building".to_string(),
+            &campus.path,
+        );
+        building.parent_id = Some(campus.id);
+        let annex = Location::new_root("building-a".to_string(), "building".to_string());
+        for location in [&campus, &building, &annex] {
+            store.create_location(location).await.unwrap();
+        }
+
+        assert_eq!(
+            location(&store, "campus/building-a", false).await.unwrap(),
+            building.id
+        );
+        assert!(location(&store, "building-a", false).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_slugs_resolve_before_names() {
+        let store = test_support::sqlite::sqlite_store().await;
+        let access = router("access-01", "dc1.example.com");
+        let named = router("access", "dc2.example.com");
+        for router in [&access, &named] {
+            store.create_node(router).await.unwrap();
+        }
+        unet_core::slug::assign_slug(
+            &store,
+            SlugKind::Node,
+            access.id,
+            &access.name,
+            Some("access"),
+        )
+        .await
+        .unwrap();
+        unet_core::slug::change_slug(&store, SlugKind::Node, access.id, "access-dc1")
+            .await
+            .unwrap();
+
+        assert_eq!(node(&store, "access-dc1", false).await.unwrap(), access.id);
+        // Retired slugs still resolve, and win over names
+        assert_eq!(node(&store, "access", false).await.unwrap(), access.id);
+        assert!(node(&store, "access-dc1", true).await.is_err());
+    }
+
+    #[test]
+    fn test_node_named_matches_exact_names() {
+        let first = Uuid::new_v4();
+        let nodes = vec![
+            (first, "core-01".to_string()),
+            (Uuid::new_v4(), "core-010".to_string()),
+        ];
+        assert_eq!(node_named(&nodes, "core-01").unwrap(), first);
+        assert!(node_named(&nodes, "core").is_err());
+    }
+}
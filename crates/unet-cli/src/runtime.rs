@@ -36,20 +36,33 @@ impl DbConnector for SeaOrmConnector {
     }
 }
 
-/// Default migration runner using our Migrator.
+/// Default migration runner using our Migrator, with its startup safeguards.
 pub struct DefaultMigrator;
 
 impl MigrationRunner for DefaultMigrator {
     fn run(&self, db: &Db) -> Pin<Box<dyn Future<Output = Result<()>> + Send>> {
-        use migration::{Migrator, MigratorTrait as _};
         let conn = db.0.clone();
-        Box::pin(async move {
-            Migrator::up(&conn, None).await?;
-            Ok(())
-        })
+        Box::pin(async move { migrate(&conn).await })
     }
 }
 
+/// Applies pending migrations, logging what was applied and where the
+/// database was backed up first
+async fn migrate(conn: &sea_orm::DatabaseConnection) -> Result<()> {
+    let report = migration::migrate_safely(conn).await?;
+    if !report.applied.is_empty() {
+        tracing::info!(
+            "Applied {} migration(s): {}",
+            report.applied.len(),
+            report.applied.join(", ")
+        );
+    }
+    if let Some(backup) = &report.backup {
+        tracing::info!("Database backed up before migrating to {}", backup.display());
+    }
+    Ok(())
+}
+
 /// Application runtime context for dependency injection.
 type ConnectFn = dyn Fn(&str) -> Pin<Box<dyn Future<Output = Result<Db>> + Send>> + Send + Sync;
 type MigrateFn = dyn Fn(&Db) -> Pin<Box<dyn Future<Output = Result<()>> + Send>> + Send + Sync;
@@ -73,11 +86,8 @@ impl Default for AppContext {
         });
         let migrate = Box::new(|db: &Db| {
             let conn = db.0.clone();
-            Box::pin(async move {
-                use migration::{Migrator, MigratorTrait as _};
-                Migrator::up(&conn, None).await?;
-                Ok(())
-            }) as Pin<Box<dyn Future<Output = Result<()>> + Send>>
+            Box::pin(async move { migrate(&conn).await })
+                as Pin<Box<dyn Future<Output = Result<()>> + Send>>
         });
         Self { connect, migrate }
     }
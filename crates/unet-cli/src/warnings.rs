@@ -0,0 +1,69 @@
+//! Warnings raised while a command runs
+//!
+//! A warning is written to stderr as `Warning: <message>` when it is raised,
+//! whatever the output format. It is also kept until the command prints its
+//! result: JSON and YAML output of a command that raised warnings is wrapped
+//! as `{data, warnings}`, so scripts see them without parsing stderr, while
+//! output of commands without warnings keeps its usual shape. Nothing but the
+//! result is ever written to stdout, so piping JSON to `jq` stays safe.
+
+use serde::Serialize;
+use std::sync::Mutex;
+
+/// Warnings raised since the last result was printed
+static PENDING: Mutex<Vec<Warning>> = Mutex::new(Vec::new());
+
+/// A condition worth reporting that did not stop the command
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Warning {
+    /// What happened
+    pub message: String,
+}
+
+/// Result of a command that raised warnings, as printed in JSON and YAML
+#[derive(Debug, Serialize)]
+pub struct Envelope<'a, T: Serialize> {
+    /// The command's result
+    pub data: &'a T,
+    /// Warnings raised while producing it, oldest first
+    pub warnings: Vec<Warning>,
+}
+
+/// Writes a warning to stderr and keeps it for the command's output
+pub fn warn(message: impl Into<String>) {
+    let warning = Warning {
+        message: message.into(),
+    };
+    eprintln!("Warning: {}", warning.message);
+    PENDING
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .push(warning);
+}
+
+/// Takes the warnings raised since the last call, oldest first
+pub fn take() -> Vec<Warning> {
+    std::mem::take(
+        &mut *PENDING
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_take_returns_raised_warnings_once() {
+        warn("first skew");
+        warn("second skew");
+
+        let messages: Vec<String> = take().into_iter().map(|w| w.message).collect();
+        // Tests run in parallel and share the pending list
+        let position = |m: &str| messages.iter().position(|message| message == m);
+        assert!(position("first skew") < position("second skew"));
+        assert!(position("first skew").is_some());
+        assert!(!take().iter().any(|w| w.message == "first skew"));
+    }
+}
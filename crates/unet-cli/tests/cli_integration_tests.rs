@@ -247,6 +247,24 @@ token = "bed-24-secret"
     assert!(res.is_ok());
 }
 
+#[test]
+fn test_connection_failure_is_reported_as_json() {
+    let mut cmd = Command::cargo_bin("unet").expect("Failed to find unet binary");
+
+    cmd.args([
+        "--error-format",
+        "json",
+        "--database-url",
+        "invalid://url",
+        "nodes",
+        "list",
+    ])
+    .assert()
+    .code(6)
+    .stderr(predicate::str::contains(r#""kind":"connection""#))
+    .stderr(predicate::str::contains(r#""code":"DB_CONNECTION_FAILED""#));
+}
+
 #[test]
 fn test_unknown_subcommand() {
     let (mut cmd, _temp_dir) = create_test_command();
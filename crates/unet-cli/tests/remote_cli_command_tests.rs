@@ -181,3 +181,70 @@ async fn test_run_with_remote_nodes_show_fetches_derived_state_views() {
     assert!(run_remote(&server_url, &args).await.is_ok());
     assert_eq!(requests_rx.await.unwrap().len(), 4);
 }
+
+#[tokio::test]
+async fn test_run_with_remote_nodes_note_add_posts_body() {
+    let node_id = Uuid::new_v4();
+    let note = unet_core::notes::Note::new(
+        unet_core::notes::NoteTarget::Node(node_id),
+        "RMA open for PSU 2".to_string(),
+        Some("noc".to_string()),
+    )
+    .unwrap();
+    let (server_url, requests_rx) = spawn_test_server(1, move |_, _| {
+        json_response(200, serde_json::to_value(&note).unwrap())
+    })
+    .await;
+    let args = vec![
+        "nodes".to_string(),
+        "note".to_string(),
+        "add".to_string(),
+        node_id.to_string(),
+        "RMA open for PSU 2".to_string(),
+        "--author".to_string(),
+        "noc".to_string(),
+    ];
+
+    assert!(run_remote(&server_url, &args).await.is_ok());
+
+    let request = requests_rx.await.unwrap().remove(0);
+    assert!(request.starts_with(&format!("POST /api/v1/nodes/{node_id}/notes ")));
+    assert!(request.contains(r#""body":"RMA open for PSU 2""#));
+    assert!(request.contains(r#""author":"noc""#));
+}
+
+#[tokio::test]
+async fn test_run_with_remote_nodes_hardware_gets_inventory() {
+    let node_id = Uuid::new_v4();
+    let inventory = json!({
+        "node_id": node_id,
+        "collected_at": "2026-01-05T10:00:00Z",
+        "components": [{
+            "index": 1,
+            "kind": "chassis",
+            "name": null,
+            "description": "MX204",
+            "serial_number": "JN11F2C3AAFA",
+            "part_number": "MX204",
+            "manufacturer": "Juniper Networks",
+            "hardware_revision": null,
+            "firmware_revision": null,
+            "software_revision": "22.4R3",
+            "field_replaceable": false
+        }]
+    });
+    let (server_url, requests_rx) =
+        spawn_test_server(1, move |_, _| json_response(200, inventory.clone())).await;
+    let args = vec![
+        "nodes".to_string(),
+        "hardware".to_string(),
+        node_id.to_string(),
+        "--kind".to_string(),
+        "chassis".to_string(),
+    ];
+
+    assert!(run_remote(&server_url, &args).await.is_ok());
+
+    let request = requests_rx.await.unwrap().remove(0);
+    assert!(request.starts_with(&format!("GET /api/v1/nodes/{node_id}/hardware ")));
+}
@@ -139,6 +139,7 @@ pub fn sample_status(node_id: Uuid) -> NodeStatus {
         name: Some("edge-1".to_string()),
         location: Some("SJC1".to_string()),
         services: Some(72),
+        software: None,
     });
     status.interfaces = vec![sample_interface()];
     status.performance = Some(sample_metrics());
@@ -0,0 +1,36 @@
+//! Records the commit and time of the build for `build_info`
+
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn main() {
+    println!("cargo:rerun-if-changed=../../.git/HEAD");
+    println!("cargo:rerun-if-changed=../../.git/refs/heads");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+
+    let sha = Command::new("git")
+        .args(["rev-parse", "--short=12", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|sha| sha.trim().to_string())
+        .filter(|sha| !sha.is_empty());
+    if let Some(sha) = sha {
+        println!("cargo:rustc-env=UNET_GIT_SHA={sha}");
+    }
+
+    // Reproducible builds pin the build time through SOURCE_DATE_EPOCH
+    let timestamp = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .ok()
+                .map(|elapsed| elapsed.as_secs())
+        });
+    if let Some(timestamp) = timestamp {
+        println!("cargo:rustc-env=UNET_BUILD_TIMESTAMP={timestamp}");
+    }
+}
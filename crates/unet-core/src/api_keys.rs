@@ -0,0 +1,264 @@
+//! Scoped API keys
+//!
+//! An API key is a bearer token limited to a set of [`ApiScope`]s. Only a
+//! SHA-256 hash of each token is stored, so a token is shown once when the
+//! key is created and cannot be recovered afterwards. Tokens start with
+//! [`TOKEN_PREFIX`], which lets the server tell them apart from OIDC ID
+//! tokens without a datastore lookup.
+
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult};
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+#[cfg(feature = "secrets")]
+use sha2::{Digest as _, Sha256};
+use std::fmt;
+#[cfg(feature = "secrets")]
+use std::fmt::Write as _;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Settings namespace holding API keys keyed by token hash
+const NAMESPACE: &str = "api_keys";
+
+/// Prefix of every API key token
+pub const TOKEN_PREFIX: &str = "unet_";
+
+/// What an API key may do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ApiScope {
+    /// Read nodes and every other inventory, status, and report endpoint
+    #[serde(rename = "nodes:read")]
+    NodesRead,
+    /// Create, change, and delete nodes and other inventory
+    #[serde(rename = "nodes:write")]
+    NodesWrite,
+    /// Evaluate and validate policies and trigger policy batches
+    #[serde(rename = "policies:evaluate")]
+    PoliciesEvaluate,
+    /// Change what SNMP polls and when, such as OID profiles and polling pauses
+    #[serde(rename = "snmp:execute")]
+    SnmpExecute,
+    /// Admin-only endpoints; implies every other scope
+    #[serde(rename = "admin")]
+    Admin,
+}
+
+impl ApiScope {
+    /// Every scope
+    pub const ALL: [Self; 5] = [
+        Self::NodesRead,
+        Self::NodesWrite,
+        Self::PoliciesEvaluate,
+        Self::SnmpExecute,
+        Self::Admin,
+    ];
+
+    /// Scope string used in requests and storage
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::NodesRead => "nodes:read",
+            Self::NodesWrite => "nodes:write",
+            Self::PoliciesEvaluate => "policies:evaluate",
+            Self::SnmpExecute => "snmp:execute",
+            Self::Admin => "admin",
+        }
+    }
+}
+
+impl fmt::Display for ApiScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ApiScope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|scope| scope.as_str() == s)
+            .ok_or_else(|| {
+                let known: Vec<&str> = Self::ALL.iter().map(|scope| scope.as_str()).collect();
+                format!("Unknown scope '{s}'; expected one of {}", known.join(", "))
+            })
+    }
+}
+
+/// A stored API key; the token itself is never stored
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiKey {
+    /// Key ID
+    pub id: Uuid,
+    /// Who or what the key is for
+    pub name: String,
+    /// Granted scopes
+    pub scopes: Vec<ApiScope>,
+    /// When the key was created
+    pub created_at: DateTime<Utc>,
+    /// When the key stops being accepted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl ApiKey {
+    /// Creates a key and its token
+    ///
+    /// # Errors
+    /// Returns a validation error if the name or the scope list is empty, or
+    /// the lifetime is not positive.
+    pub fn new(
+        name: &str,
+        scopes: &[ApiScope],
+        expires_in: Option<TimeDelta>,
+    ) -> DataStoreResult<(Self, String)> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(DataStoreError::ValidationError {
+                message: "API key name must not be empty".to_string(),
+            });
+        }
+        if scopes.is_empty() {
+            return Err(DataStoreError::ValidationError {
+                message: "API key needs at least one scope".to_string(),
+            });
+        }
+        if expires_in.is_some_and(|lifetime| lifetime <= TimeDelta::zero()) {
+            return Err(DataStoreError::ValidationError {
+                message: "API key lifetime must be positive".to_string(),
+            });
+        }
+
+        let mut scopes = scopes.to_vec();
+        scopes.sort_unstable();
+        scopes.dedup();
+        let created_at = Utc::now();
+        let key = Self {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            scopes,
+            created_at,
+            expires_at: expires_in.map(|lifetime| created_at + lifetime),
+        };
+        let token = format!(
+            "{TOKEN_PREFIX}{}{}",
+            Uuid::new_v4().simple(),
+            Uuid::new_v4().simple()
+        );
+        Ok((key, token))
+    }
+
+    /// Whether the key grants `scope`; the admin scope grants every scope
+    #[must_use]
+    pub fn allows(&self, scope: ApiScope) -> bool {
+        self.scopes.contains(&scope) || self.scopes.contains(&ApiScope::Admin)
+    }
+
+    /// Whether the key has expired at `at`
+    #[must_use]
+    pub fn is_expired(&self, at: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= at)
+    }
+}
+
+/// Hashes a token for storage and lookup
+///
+/// # Errors
+/// Returns an unsupported-operation error if built without the `secrets` feature.
+#[cfg(feature = "secrets")]
+pub fn hash_token(token: &str) -> DataStoreResult<String> {
+    let mut hash = String::new();
+    for byte in Sha256::digest(token.as_bytes()) {
+        let _ = write!(hash, "{byte:02x}");
+    }
+    Ok(hash)
+}
+
+/// Hashes a token for storage and lookup
+///
+/// # Errors
+/// Returns an unsupported-operation error if built without the `secrets` feature.
+#[cfg(not(feature = "secrets"))]
+pub fn hash_token(_token: &str) -> DataStoreResult<String> {
+    Err(DataStoreError::UnsupportedOperation {
+        operation: "API keys without the `secrets` feature".to_string(),
+    })
+}
+
+fn parse(key: &str, value: Value) -> DataStoreResult<ApiKey> {
+    serde_json::from_value(value).map_err(|e| DataStoreError::InternalError {
+        message: format!("stored API key {key}: {e}"),
+    })
+}
+
+/// Creates and stores a key, returning it with its token
+///
+/// # Errors
+/// Returns an error if the key is invalid, tokens cannot be hashed, or the
+/// datastore write fails.
+pub async fn create_key(
+    datastore: &dyn DataStore,
+    name: &str,
+    scopes: &[ApiScope],
+    expires_in: Option<TimeDelta>,
+) -> DataStoreResult<(ApiKey, String)> {
+    let (key, token) = ApiKey::new(name, scopes, expires_in)?;
+    let value = serde_json::to_value(&key).map_err(|e| DataStoreError::InternalError {
+        message: format!("API key {}: {e}", key.id),
+    })?;
+    datastore
+        .put_setting(NAMESPACE, &hash_token(&token)?, &value)
+        .await?;
+    Ok((key, token))
+}
+
+/// Lists keys, oldest first
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or a stored key is malformed.
+pub async fn list_keys(datastore: &dyn DataStore) -> DataStoreResult<Vec<ApiKey>> {
+    let mut keys = datastore
+        .list_settings(NAMESPACE)
+        .await?
+        .into_iter()
+        .map(|(key, value)| parse(&key, value))
+        .collect::<DataStoreResult<Vec<_>>>()?;
+    keys.sort_by_key(|key| key.created_at);
+    Ok(keys)
+}
+
+/// Returns the key a token belongs to, if any, whether or not it has expired
+///
+/// # Errors
+/// Returns an error if the token cannot be hashed, the datastore cannot be
+/// read, or the stored key is malformed.
+pub async fn find_key(datastore: &dyn DataStore, token: &str) -> DataStoreResult<Option<ApiKey>> {
+    let hash = hash_token(token)?;
+    datastore
+        .get_setting(NAMESPACE, &hash)
+        .await?
+        .map(|value| parse(&hash, value))
+        .transpose()
+}
+
+/// Revokes a key so its token is no longer accepted
+///
+/// # Errors
+/// Returns not found if no key has the ID, or an error if the datastore
+/// cannot be read or written.
+pub async fn revoke_key(datastore: &dyn DataStore, id: Uuid) -> DataStoreResult<()> {
+    for (hash, value) in datastore.list_settings(NAMESPACE).await? {
+        if parse(&hash, value)?.id == id {
+            return datastore.delete_setting(NAMESPACE, &hash).await;
+        }
+    }
+    Err(DataStoreError::NotFound {
+        entity_type: "API key".to_string(),
+        id: id.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,64 @@
+use super::*;
+use crate::datastore::sqlite::tests::setup::settings_store;
+
+#[test]
+fn test_scope_strings_round_trip() {
+    for scope in ApiScope::ALL {
+        assert_eq!(scope.as_str().parse::<ApiScope>().unwrap(), scope);
+        assert_eq!(
+            serde_json::to_value(scope).unwrap(),
+            Value::String(scope.to_string())
+        );
+    }
+    assert!("nodes:delete".parse::<ApiScope>().is_err());
+}
+
+#[test]
+fn test_new_validates_and_admin_implies_every_scope() {
+    assert!(ApiKey::new(" ", &[ApiScope::NodesRead], None).is_err());
+    assert!(ApiKey::new("ci", &[], None).is_err());
+    assert!(ApiKey::new("ci", &[ApiScope::NodesRead], Some(TimeDelta::zero())).is_err());
+
+    let (key, token) = ApiKey::new(
+        " ci ",
+        &[
+            ApiScope::PoliciesEvaluate,
+            ApiScope::NodesRead,
+            ApiScope::NodesRead,
+        ],
+        Some(TimeDelta::days(1)),
+    )
+    .unwrap();
+    assert_eq!(key.name, "ci");
+    assert_eq!(
+        key.scopes,
+        [ApiScope::NodesRead, ApiScope::PoliciesEvaluate]
+    );
+    assert!(token.starts_with(TOKEN_PREFIX));
+    assert!(key.allows(ApiScope::NodesRead));
+    assert!(!key.allows(ApiScope::NodesWrite));
+    assert!(!key.is_expired(key.created_at));
+    assert!(key.is_expired(key.created_at + TimeDelta::days(1)));
+
+    let (admin, _) = ApiKey::new("ops", &[ApiScope::Admin], None).unwrap();
+    assert!(ApiScope::ALL.into_iter().all(|scope| admin.allows(scope)));
+    assert!(!admin.is_expired(Utc::now()));
+}
+
+#[tokio::test]
+async fn test_create_find_list_and_revoke() {
+    let store = settings_store().await;
+    let (key, token) = create_key(&store, "ci", &[ApiScope::NodesRead], None)
+        .await
+        .unwrap();
+
+    assert_eq!(find_key(&store, &token).await.unwrap(), Some(key.clone()));
+    assert!(find_key(&store, "unet_other").await.unwrap().is_none());
+    let stored = store.list_settings(NAMESPACE).await.unwrap();
+    assert!(stored.iter().all(|(hash, _)| !hash.contains(&token)));
+    assert_eq!(list_keys(&store).await.unwrap(), [key.clone()]);
+
+    revoke_key(&store, key.id).await.unwrap();
+    assert!(find_key(&store, &token).await.unwrap().is_none());
+    assert!(revoke_key(&store, key.id).await.is_err());
+}
@@ -0,0 +1,131 @@
+//! Version, commit, and features of this build
+//!
+//! The commit and build time are recorded by the build script when Git is
+//! available; builds from a source archive leave them unset. The CLI and the
+//! server compare their build info to warn when they run different releases.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Release version of the μNet crates
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Abbreviated commit the build was made from, if known
+pub const GIT_SHA: Option<&str> = option_env!("UNET_GIT_SHA");
+
+/// Unix time of the build, if known
+const BUILD_TIMESTAMP: Option<&str> = option_env!("UNET_BUILD_TIMESTAMP");
+
+/// Version, commit, and features of a build
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildInfo {
+    /// Release version
+    pub version: String,
+    /// Abbreviated commit the build was made from
+    pub git_sha: Option<String>,
+    /// When the build was made
+    pub build_date: Option<DateTime<Utc>>,
+    /// Cargo features compiled in
+    #[serde(default)]
+    pub features: Vec<String>,
+}
+
+impl BuildInfo {
+    /// Build info of this binary, listing the `unet-core` features compiled
+    /// in, sorted
+    #[must_use]
+    pub fn current() -> Self {
+        let features = [
+            ("policy", cfg!(feature = "policy")),
+            ("secrets", cfg!(feature = "secrets")),
+            ("snmp", cfg!(feature = "snmp")),
+            ("sqlcipher", cfg!(feature = "sqlcipher")),
+            ("sqlite", cfg!(feature = "sqlite")),
+        ];
+        Self {
+            version: VERSION.to_string(),
+            git_sha: GIT_SHA.map(str::to_string),
+            build_date: BUILD_TIMESTAMP
+                .and_then(|timestamp| timestamp.parse().ok())
+                .and_then(|timestamp| DateTime::from_timestamp(timestamp, 0)),
+            features: features
+                .into_iter()
+                .filter(|(_, enabled)| *enabled)
+                .map(|(name, _)| name.to_string())
+                .collect(),
+        }
+    }
+
+    /// Adds features compiled into the binary itself, keeping them sorted
+    #[must_use]
+    pub fn with_features<'a>(mut self, features: impl IntoIterator<Item = &'a str>) -> Self {
+        self.features
+            .extend(features.into_iter().map(str::to_string));
+        self.features.sort();
+        self.features.dedup();
+        self
+    }
+
+    /// How this build and `other` differ, if they may not work together
+    ///
+    /// Different versions are a skew. Builds of the same version from
+    /// different commits are too, when both commits are known. The message
+    /// completes a sentence naming both builds, e.g. "CLI and server ...".
+    #[must_use]
+    pub fn skew(&self, other: &Self) -> Option<String> {
+        if self.version != other.version {
+            return Some(format!(
+                "versions differ ({} and {})",
+                self.version, other.version
+            ));
+        }
+        match (&self.git_sha, &other.git_sha) {
+            (Some(this), Some(that)) if this != that => Some(format!(
+                "are both {} but built from different commits ({this} and {that})",
+                self.version
+            )),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(version: &str, git_sha: Option<&str>) -> BuildInfo {
+        BuildInfo {
+            version: version.to_string(),
+            git_sha: git_sha.map(str::to_string),
+            build_date: None,
+            features: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_skew_compares_version_then_commit() {
+        let here = build("0.2.0", Some("abc123"));
+
+        assert_eq!(here.skew(&build("0.2.0", Some("abc123"))), None);
+        assert_eq!(here.skew(&build("0.2.0", None)), None);
+        assert!(
+            here.skew(&build("0.3.0", Some("abc123")))
+                .unwrap()
+                .contains("(0.2.0 and 0.3.0)")
+        );
+        assert!(
+            here.skew(&build("0.2.0", Some("def456")))
+                .unwrap()
+                .contains("different commits")
+        );
+    }
+
+    #[test]
+    fn test_current_lists_features_sorted() {
+        let info = BuildInfo::current().with_features(["web-ui", "snmp"]);
+
+        assert_eq!(info.version, VERSION);
+        assert!(info.features.contains(&"web-ui".to_string()));
+        assert!(info.features.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+}
@@ -0,0 +1,257 @@
+//! Append-only log of inventory changes and projections replayed from it
+//!
+//! With `change_log.enabled`, the datastore appends a [`ChangeEvent`] for
+//! every node, link, and location that is created, updated, or deleted, in
+//! the same transaction as the write: the entity kind and ID, the change, and
+//! the entity as it was written (or, for deletions, as it was last stored).
+//! Each event takes the next sequence number of the log, which is never
+//! rewritten; retention only drops events older than the audit period.
+//!
+//! A [`Projection`] is a read model derived from the events.
+//! [`ChangeLogStore`] applies new events to the live projections after each
+//! write; [`replay`] rebuilds one from the whole log, a page at a time, so a
+//! new or changed read model covers the full history without migrating the
+//! stored data, or as much of it as retention has kept. [`EntityActivity`],
+//! [`ComplianceSummary`], and [`SearchIndex`] are built in.
+//!
+//! Each event also lists the `custom_data` fields the write changed, so the
+//! history of one field can be read back with [`field_history`] and policy
+//! rules can ask how recently a field changed.
+//!
+//! [`SearchIndex`]: crate::search::SearchIndex
+
+mod compliance;
+mod fields;
+mod projections;
+mod store;
+
+pub use compliance::{ComplianceSummary, LocationCompliance};
+pub use fields::{
+    CUSTOM_DATA_FIELD, FieldChange, custom_data_changes, field_history, last_changed,
+};
+pub use projections::{ActivityRecord, EntityActivity, Projection, builtin_projections};
+pub use store::ChangeLogStore;
+
+use crate::datastore::{DataStore, DataStoreResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Events read from the log at a time when applying them to projections
+const EVENT_PAGE: usize = 500;
+
+/// Kind of entity a change applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntityKind {
+    /// A node
+    Node,
+    /// A link
+    Link,
+    /// A location
+    Location,
+}
+
+impl Display for EntityKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Node => write!(f, "node"),
+            Self::Link => write!(f, "link"),
+            Self::Location => write!(f, "location"),
+        }
+    }
+}
+
+impl FromStr for EntityKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "node" => Ok(Self::Node),
+            "link" => Ok(Self::Link),
+            "location" => Ok(Self::Location),
+            _ => Err(format!("Invalid entity kind: {s}")),
+        }
+    }
+}
+
+/// What happened to an entity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeType {
+    /// The entity was created
+    Created,
+    /// The entity was changed
+    Updated,
+    /// The entity was deleted
+    Deleted,
+}
+
+impl Display for ChangeType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Created => write!(f, "created"),
+            Self::Updated => write!(f, "updated"),
+            Self::Deleted => write!(f, "deleted"),
+        }
+    }
+}
+
+impl FromStr for ChangeType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "created" => Ok(Self::Created),
+            "updated" => Ok(Self::Updated),
+            "deleted" => Ok(Self::Deleted),
+            _ => Err(format!("Invalid change type: {s}")),
+        }
+    }
+}
+
+/// One change to one entity
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChangeEvent {
+    /// Position in the log, assigned when the event is appended
+    #[serde(default)]
+    pub sequence: i64,
+    /// Event ID
+    pub id: Uuid,
+    /// When the change was recorded
+    pub recorded_at: DateTime<Utc>,
+    /// Kind of entity changed
+    pub entity: EntityKind,
+    /// ID of the entity changed
+    pub entity_id: Uuid,
+    /// What happened
+    pub change: ChangeType,
+    /// The entity as written; for deletions, as last stored if it was found
+    pub payload: Value,
+    /// Dotted paths of the `custom_data` leaves the write added, changed, or
+    /// removed, e.g. `custom_data.bgp.asn`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changed_fields: Vec<String>,
+}
+
+impl ChangeEvent {
+    /// Creates an event recorded now
+    #[must_use]
+    pub fn new(entity: EntityKind, entity_id: Uuid, change: ChangeType, payload: Value) -> Self {
+        Self {
+            sequence: 0,
+            id: Uuid::new_v4(),
+            recorded_at: Utc::now(),
+            entity,
+            entity_id,
+            change,
+            payload,
+            changed_fields: Vec::new(),
+        }
+    }
+
+    /// Creates the event of a write recorded now; `before` is the entity as
+    /// stored before the write, or `null`, and is diffed with `payload` for
+    /// the changed fields
+    #[must_use]
+    pub fn from_write(
+        entity: EntityKind,
+        entity_id: Uuid,
+        change: ChangeType,
+        before: &Value,
+        payload: Value,
+    ) -> Self {
+        let changed_fields = match change {
+            ChangeType::Created | ChangeType::Updated => custom_data_changes(before, &payload),
+            ChangeType::Deleted => Vec::new(),
+        };
+        Self::new(entity, entity_id, change, payload).with_changed_fields(changed_fields)
+    }
+
+    /// Records the `custom_data` fields the change touched
+    #[must_use]
+    pub fn with_changed_fields(mut self, changed_fields: Vec<String>) -> Self {
+        self.changed_fields = changed_fields;
+        self
+    }
+}
+
+/// Which events to list
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventFilter {
+    /// Only events for this kind of entity
+    #[serde(default)]
+    pub entity: Option<EntityKind>,
+    /// Only events for this entity
+    #[serde(default)]
+    pub entity_id: Option<Uuid>,
+    /// Only events recorded at or after this time
+    #[serde(default)]
+    pub since: Option<DateTime<Utc>>,
+    /// Only events that changed this `custom_data` field or a field below it
+    #[serde(default)]
+    pub field: Option<String>,
+    /// Only events after this sequence number; pass the last sequence of a
+    /// page to read the next one
+    #[serde(default)]
+    pub after: Option<i64>,
+    /// Most events to return
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// Reads the page of events following sequence number `after`; a page
+/// shorter than [`EVENT_PAGE`] ends the log
+async fn next_page(datastore: &dyn DataStore, after: i64) -> DataStoreResult<Vec<ChangeEvent>> {
+    let filter = EventFilter {
+        after: Some(after),
+        limit: Some(EVENT_PAGE),
+        ..EventFilter::default()
+    };
+    datastore.list_change_events(&filter).await
+}
+
+/// Result of rebuilding a projection
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayReport {
+    /// Projection rebuilt
+    pub projection: String,
+    /// Events applied
+    pub events: usize,
+}
+
+/// Clears a projection and rebuilds it from every event in the log, reading
+/// the log a page at a time
+///
+/// # Errors
+/// Returns an error if the log cannot be read or the projection cannot be
+/// cleared or updated.
+pub async fn replay(
+    datastore: &dyn DataStore,
+    projection: &dyn Projection,
+) -> DataStoreResult<ReplayReport> {
+    let mut page = next_page(datastore, 0).await?;
+    projection.reset(datastore).await?;
+    let mut events = 0;
+    loop {
+        for event in &page {
+            projection.apply(datastore, event).await?;
+        }
+        events += page.len();
+        let Some(last) = page.last().filter(|_| page.len() == EVENT_PAGE) else {
+            break;
+        };
+        let after = last.sequence;
+        page = next_page(datastore, after).await?;
+    }
+    Ok(ReplayReport {
+        projection: projection.name().to_string(),
+        events,
+    })
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,119 @@
+//! Compliance of the nodes at each location
+//!
+//! The projection keeps which location each node is at, from the node
+//! events; [`ComplianceSummary::summarize`] rolls the latest policy results
+//! of those nodes up by location.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use super::{ChangeEvent, ChangeType, EntityKind, Projection};
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult};
+
+/// Settings namespace holding the location of each node, keyed by node ID
+const MEMBERS_NAMESPACE: &str = "projection_compliance_summary";
+
+/// Location of a node as of its latest event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Member {
+    node_id: Uuid,
+    #[serde(default)]
+    location_id: Option<Uuid>,
+}
+
+/// Compliance of the nodes at one location
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocationCompliance {
+    /// Location; `None` for nodes without one
+    pub location_id: Option<Uuid>,
+    /// Nodes at the location
+    pub nodes: usize,
+    /// Nodes whose latest policy results have no compliance failures
+    pub compliant: usize,
+    /// Nodes with a compliance failure in their latest policy results
+    pub non_compliant: usize,
+    /// Nodes without policy results
+    pub not_evaluated: usize,
+}
+
+/// Per-location compliance, readable with [`ComplianceSummary::summarize`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ComplianceSummary;
+
+impl ComplianceSummary {
+    /// Name of the projection
+    pub const NAME: &'static str = "compliance_summary";
+
+    /// Compliance of the nodes at each location, ordered by location
+    ///
+    /// # Errors
+    /// Returns an error if the datastore cannot be read or a stored record is
+    /// malformed.
+    pub async fn summarize(datastore: &dyn DataStore) -> DataStoreResult<Vec<LocationCompliance>> {
+        let mut locations: BTreeMap<Option<Uuid>, LocationCompliance> = BTreeMap::new();
+        for (key, value) in datastore.list_settings(MEMBERS_NAMESPACE).await? {
+            let member: Member =
+                serde_json::from_value(value).map_err(|e| DataStoreError::InternalError {
+                    message: format!("stored compliance member {key}: {e}"),
+                })?;
+            let results = datastore.get_latest_policy_results(&member.node_id).await?;
+            let location =
+                locations
+                    .entry(member.location_id)
+                    .or_insert_with(|| LocationCompliance {
+                        location_id: member.location_id,
+                        ..LocationCompliance::default()
+                    });
+            location.nodes += 1;
+            if results.is_empty() {
+                location.not_evaluated += 1;
+            } else if results.iter().any(|result| result.is_compliance_failure()) {
+                location.non_compliant += 1;
+            } else {
+                location.compliant += 1;
+            }
+        }
+        Ok(locations.into_values().collect())
+    }
+}
+
+#[async_trait]
+impl Projection for ComplianceSummary {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    async fn reset(&self, datastore: &dyn DataStore) -> DataStoreResult<()> {
+        for (key, _) in datastore.list_settings(MEMBERS_NAMESPACE).await? {
+            datastore.delete_setting(MEMBERS_NAMESPACE, &key).await?;
+        }
+        Ok(())
+    }
+
+    async fn apply(&self, datastore: &dyn DataStore, event: &ChangeEvent) -> DataStoreResult<()> {
+        if event.entity != EntityKind::Node {
+            return Ok(());
+        }
+        let key = event.entity_id.to_string();
+        if event.change == ChangeType::Deleted {
+            return match datastore.delete_setting(MEMBERS_NAMESPACE, &key).await {
+                Err(DataStoreError::NotFound { .. }) => Ok(()),
+                result => result,
+            };
+        }
+        let member = Member {
+            node_id: event.entity_id,
+            location_id: event
+                .payload
+                .get("location_id")
+                .and_then(|id| id.as_str())
+                .and_then(|id| id.parse().ok()),
+        };
+        let value = serde_json::to_value(&member).map_err(|e| DataStoreError::InternalError {
+            message: format!("compliance member {key}: {e}"),
+        })?;
+        datastore.put_setting(MEMBERS_NAMESPACE, &key, &value).await
+    }
+}
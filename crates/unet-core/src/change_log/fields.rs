@@ -0,0 +1,168 @@
+//! Which `custom_data` fields each change touched
+//!
+//! Every event records the dotted paths of the `custom_data` leaves its
+//! write added, changed, or removed, diffed against the entity as stored
+//! before the write. Objects are descended into; arrays and scalars count
+//! as leaves, so `custom_data.bgp.asn` changes when the ASN does and
+//! `custom_data.ntp_servers` when any server in the list does.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use uuid::Uuid;
+
+use super::{ChangeEvent, ChangeType, EventFilter};
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult};
+
+/// Field of an entity's payload whose changes are tracked
+pub const CUSTOM_DATA_FIELD: &str = "custom_data";
+
+/// Dotted paths of the `custom_data` leaves that differ between two
+/// serialized versions of an entity, sorted
+///
+/// `before` is `null` for a created entity, making every leaf a change.
+#[must_use]
+pub fn custom_data_changes(before: &Value, after: &Value) -> Vec<String> {
+    let mut changed = BTreeSet::new();
+    diff(
+        CUSTOM_DATA_FIELD,
+        before.get(CUSTOM_DATA_FIELD),
+        after.get(CUSTOM_DATA_FIELD),
+        &mut changed,
+    );
+    changed.into_iter().collect()
+}
+
+fn diff(path: &str, before: Option<&Value>, after: Option<&Value>, changed: &mut BTreeSet<String>) {
+    if before == after {
+        return;
+    }
+    let child = |key: &str| format!("{path}.{key}");
+    match (
+        before.and_then(Value::as_object),
+        after.and_then(Value::as_object),
+    ) {
+        (Some(before), Some(after)) => {
+            for key in before.keys().chain(after.keys()) {
+                diff(&child(key), before.get(key), after.get(key), changed);
+            }
+        }
+        (before_object, after_object) => {
+            if before.is_some_and(|value| !value.is_object())
+                || after.is_some_and(|value| !value.is_object())
+            {
+                changed.insert(path.to_string());
+            }
+            for (key, value) in before_object.into_iter().flat_map(Map::iter) {
+                diff(&child(key), Some(value), None, changed);
+            }
+            for (key, value) in after_object.into_iter().flat_map(Map::iter) {
+                diff(&child(key), None, Some(value), changed);
+            }
+        }
+    }
+}
+
+/// Whether `path` is `field` or lies below it
+fn is_within(path: &str, field: &str) -> bool {
+    path.strip_prefix(field)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+}
+
+/// A change to a field of an entity, read from the change log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+    /// When the change was recorded
+    pub recorded_at: DateTime<Utc>,
+    /// Event the change was recorded in
+    pub event_id: Uuid,
+    /// Whether the entity was created or updated
+    pub change: ChangeType,
+    /// Leaves at or below the requested field that changed
+    pub fields: Vec<String>,
+    /// Value of the requested field after the change; `null` once removed
+    pub value: Value,
+}
+
+/// Lists the changes to `field` of an entity, or to anything below it,
+/// oldest first
+///
+/// # Errors
+/// Returns a validation error if `field` is not under `custom_data`, or an
+/// error if the change log cannot be read.
+pub async fn field_history(
+    datastore: &dyn DataStore,
+    entity_id: Uuid,
+    field: &str,
+) -> DataStoreResult<Vec<FieldChange>> {
+    if !is_within(field, CUSTOM_DATA_FIELD) {
+        return Err(DataStoreError::ValidationError {
+            message: format!(
+                "Only custom_data fields are tracked; expected custom_data.<key>, got {field}"
+            ),
+        });
+    }
+    let filter = EventFilter {
+        entity_id: Some(entity_id),
+        field: Some(field.to_string()),
+        ..EventFilter::default()
+    };
+    let events = datastore.list_change_events(&filter).await?;
+    Ok(events
+        .into_iter()
+        .map(|event| FieldChange {
+            recorded_at: event.recorded_at,
+            event_id: event.id,
+            change: event.change,
+            fields: event
+                .changed_fields
+                .iter()
+                .filter(|path| is_within(path, field))
+                .cloned()
+                .collect(),
+            value: value_at(&event.payload, field),
+        })
+        .collect())
+}
+
+fn value_at(payload: &Value, field: &str) -> Value {
+    field
+        .split('.')
+        .try_fold(payload, |value, key| value.get(key))
+        .cloned()
+        .unwrap_or(Value::Null)
+}
+
+/// When each tracked field of an entity last changed, by dotted path
+///
+/// Datastores without a change log have no recorded changes.
+///
+/// # Errors
+/// Returns an error if the change log cannot be read.
+pub async fn last_changed(
+    datastore: &dyn DataStore,
+    entity_id: Uuid,
+) -> DataStoreResult<BTreeMap<String, DateTime<Utc>>> {
+    let filter = EventFilter {
+        entity_id: Some(entity_id),
+        ..EventFilter::default()
+    };
+    let events = match datastore.list_change_events(&filter).await {
+        Ok(events) => events,
+        Err(DataStoreError::UnsupportedOperation { .. }) => return Ok(BTreeMap::new()),
+        Err(e) => return Err(e),
+    };
+    let mut changed = BTreeMap::new();
+    for ChangeEvent {
+        recorded_at,
+        changed_fields,
+        ..
+    } in events
+    {
+        for field in changed_fields {
+            changed.insert(field, recorded_at);
+        }
+    }
+    Ok(changed)
+}
@@ -0,0 +1,158 @@
+//! Read models derived from change events
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::{ChangeEvent, ChangeType, ComplianceSummary, EntityKind};
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult};
+use crate::search::SearchIndex;
+
+/// A read model kept up to date from change events
+#[async_trait]
+pub trait Projection: Send + Sync {
+    /// Name used by the replay tool
+    fn name(&self) -> &str;
+
+    /// Removes everything the projection has stored
+    ///
+    /// # Errors
+    /// Returns an error if the stored state cannot be removed.
+    async fn reset(&self, datastore: &dyn DataStore) -> DataStoreResult<()>;
+
+    /// Updates the stored state for one event; events arrive in log order,
+    /// and an event may arrive again when several processes catch up on the
+    /// same log
+    ///
+    /// # Errors
+    /// Returns an error if the stored state cannot be read or written.
+    async fn apply(&self, datastore: &dyn DataStore, event: &ChangeEvent) -> DataStoreResult<()>;
+}
+
+/// The projections kept by [`super::ChangeLogStore::with_builtins`]
+#[must_use]
+pub fn builtin_projections() -> Vec<Arc<dyn Projection>> {
+    vec![
+        Arc::new(EntityActivity),
+        Arc::new(ComplianceSummary),
+        Arc::new(SearchIndex),
+    ]
+}
+
+/// Settings namespace holding activity records keyed by entity ID
+const ACTIVITY_NAMESPACE: &str = "projection_entity_activity";
+
+/// When an entity was created, last changed, and deleted
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActivityRecord {
+    /// Kind of entity
+    pub entity: EntityKind,
+    /// Entity ID
+    pub entity_id: Uuid,
+    /// Latest name seen in the entity's events
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// First event recorded for the entity
+    pub first_seen: DateTime<Utc>,
+    /// Creation, if it was recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+    /// Latest event recorded for the entity
+    pub last_changed_at: DateTime<Utc>,
+    /// Deletion, if the entity is gone
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// Events recorded for the entity
+    pub changes: usize,
+    /// Sequence number of the latest event applied
+    #[serde(default)]
+    pub sequence: i64,
+}
+
+/// Per-entity change activity, readable with [`EntityActivity::get`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EntityActivity;
+
+impl EntityActivity {
+    /// Name of the projection
+    pub const NAME: &'static str = "entity_activity";
+
+    /// The activity recorded for an entity
+    ///
+    /// # Errors
+    /// Returns an error if the datastore cannot be read or the record is malformed.
+    pub async fn get(
+        datastore: &dyn DataStore,
+        entity_id: Uuid,
+    ) -> DataStoreResult<Option<ActivityRecord>> {
+        let key = entity_id.to_string();
+        datastore
+            .get_setting(ACTIVITY_NAMESPACE, &key)
+            .await?
+            .map(|value| {
+                serde_json::from_value(value).map_err(|e| DataStoreError::InternalError {
+                    message: format!("stored entity activity {key}: {e}"),
+                })
+            })
+            .transpose()
+    }
+}
+
+#[async_trait]
+impl Projection for EntityActivity {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    async fn reset(&self, datastore: &dyn DataStore) -> DataStoreResult<()> {
+        for (key, _) in datastore.list_settings(ACTIVITY_NAMESPACE).await? {
+            datastore.delete_setting(ACTIVITY_NAMESPACE, &key).await?;
+        }
+        Ok(())
+    }
+
+    async fn apply(&self, datastore: &dyn DataStore, event: &ChangeEvent) -> DataStoreResult<()> {
+        let stored = Self::get(datastore, event.entity_id).await?;
+        if stored
+            .as_ref()
+            .is_some_and(|record| record.sequence >= event.sequence)
+        {
+            return Ok(());
+        }
+        let mut record = stored.unwrap_or_else(|| ActivityRecord {
+            entity: event.entity,
+            entity_id: event.entity_id,
+            name: None,
+            first_seen: event.recorded_at,
+            created_at: None,
+            last_changed_at: event.recorded_at,
+            deleted_at: None,
+            changes: 0,
+            sequence: 0,
+        });
+        if let Some(name) = event.payload.get("name").and_then(|name| name.as_str()) {
+            record.name = Some(name.to_string());
+        }
+        match event.change {
+            ChangeType::Created => {
+                record.created_at = Some(event.recorded_at);
+                record.deleted_at = None;
+            }
+            ChangeType::Updated => {}
+            ChangeType::Deleted => record.deleted_at = Some(event.recorded_at),
+        }
+        record.last_changed_at = event.recorded_at;
+        record.changes += 1;
+        record.sequence = event.sequence;
+
+        let key = event.entity_id.to_string();
+        let value = serde_json::to_value(&record).map_err(|e| DataStoreError::InternalError {
+            message: format!("entity activity {key}: {e}"),
+        })?;
+        datastore
+            .put_setting(ACTIVITY_NAMESPACE, &key, &value)
+            .await
+    }
+}
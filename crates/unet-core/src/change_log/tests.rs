@@ -0,0 +1,451 @@
+use super::*;
+use crate::datastore::sqlite::SqliteStore;
+use crate::datastore::sqlite::tests::setup::migrated_store;
+use crate::models::{DeviceRole, Location, Node, Vendor};
+use crate::policy::{Action, Condition, EvaluationResult, FieldRef, PolicyExecutionResult};
+use crate::policy::{PolicyRule, Value as PolicyValue};
+use std::sync::Arc;
+
+async fn sqlite_store(change_log: bool) -> SqliteStore {
+    migrated_store().await.with_change_log(change_log)
+}
+
+fn event(entity: EntityKind, change: ChangeType, name: &str) -> ChangeEvent {
+    ChangeEvent::new(
+        entity,
+        Uuid::new_v4(),
+        change,
+        serde_json::json!({ "name": name }),
+    )
+}
+
+fn node(name: &str, location_id: Option<Uuid>) -> Node {
+    let mut node = Node::new(
+        name.to_string(),
+        "example.com".to_string(),
+        Vendor::Cisco,
+        DeviceRole::Router,
+    );
+    node.location_id = location_id;
+    node
+}
+
+#[test]
+fn test_entity_kind_and_change_type_parse_case_insensitively() {
+    assert_eq!("Location".parse::<EntityKind>(), Ok(EntityKind::Location));
+    assert!("vendor".parse::<EntityKind>().is_err());
+    for change in [
+        ChangeType::Created,
+        ChangeType::Updated,
+        ChangeType::Deleted,
+    ] {
+        assert_eq!(change.to_string().parse::<ChangeType>(), Ok(change));
+    }
+    assert_eq!("DELETED".parse::<ChangeType>(), Ok(ChangeType::Deleted));
+}
+
+#[tokio::test]
+async fn test_store_records_location_lifecycle() {
+    let inner: Arc<dyn DataStore> = Arc::new(sqlite_store(true).await);
+    let store = ChangeLogStore::with_builtins(inner.clone()).await.unwrap();
+
+    let mut location = Location::new_root("dc1".to_string(), "datacenter".to_string());
+    store.create_location(&location).await.unwrap();
+    location.name = "dc1-east".to_string();
+    store.update_location(&location).await.unwrap();
+    store.delete_location(&location.id).await.unwrap();
+
+    let events = inner
+        .list_change_events(&EventFilter::default())
+        .await
+        .unwrap();
+    let changes: Vec<ChangeType> = events.iter().map(|event| event.change).collect();
+    assert_eq!(
+        changes,
+        [
+            ChangeType::Created,
+            ChangeType::Updated,
+            ChangeType::Deleted
+        ]
+    );
+    let sequences: Vec<i64> = events.iter().map(|event| event.sequence).collect();
+    assert_eq!(sequences, [1, 2, 3]);
+    assert!(events.iter().all(|event| event.entity_id == location.id));
+    assert_eq!(events[2].payload["name"], "dc1-east");
+    assert_eq!(inner.last_change_sequence().await.unwrap(), 3);
+
+    let activity = EntityActivity::get(inner.as_ref(), location.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(activity.entity, EntityKind::Location);
+    assert_eq!(activity.name.as_deref(), Some("dc1-east"));
+    assert_eq!(activity.changes, 3);
+    assert_eq!(activity.sequence, 3);
+    assert!(activity.deleted_at.is_some());
+}
+
+#[tokio::test]
+async fn test_writes_append_events_only_when_enabled_and_successful() {
+    let store = sqlite_store(true).await;
+    assert!(store.delete_location(&Uuid::new_v4()).await.is_err());
+    let missing = Location::new_root("dc9".to_string(), "datacenter".to_string());
+    assert!(store.update_location(&missing).await.is_err());
+    assert_eq!(store.last_change_sequence().await.unwrap(), 0);
+
+    let store = sqlite_store(false).await;
+    store.create_node(&node("edge-1", None)).await.unwrap();
+    assert_eq!(store.last_change_sequence().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_list_filters_events_in_sql() {
+    let store = sqlite_store(true).await;
+    let mut edge = node("edge-1", None);
+    edge.custom_data = serde_json::json!({ "bgp": { "asn": 65001 } });
+    store.create_node(&edge).await.unwrap();
+    edge.custom_data = serde_json::json!({ "bgp": { "asn": 65001 }, "bgpx": 1 });
+    store.update_node(&edge).await.unwrap();
+    let site = Location::new_root("dc1".to_string(), "datacenter".to_string());
+    store.create_location(&site).await.unwrap();
+
+    let list = |filter: EventFilter| {
+        let store = &store;
+        async move {
+            store
+                .list_change_events(&filter)
+                .await
+                .unwrap()
+                .iter()
+                .map(|event| event.sequence)
+                .collect::<Vec<_>>()
+        }
+    };
+    let by_field = EventFilter {
+        field: Some("custom_data.bgp".to_string()),
+        ..EventFilter::default()
+    };
+    assert_eq!(list(by_field).await, [1]);
+    let by_kind = EventFilter {
+        entity: Some(EntityKind::Location),
+        ..EventFilter::default()
+    };
+    assert_eq!(list(by_kind).await, [3]);
+    let by_id = EventFilter {
+        entity_id: Some(edge.id),
+        ..EventFilter::default()
+    };
+    assert_eq!(list(by_id).await, [1, 2]);
+    let page = EventFilter {
+        after: Some(1),
+        limit: Some(1),
+        ..EventFilter::default()
+    };
+    assert_eq!(list(page).await, [2]);
+    let later = EventFilter {
+        since: Some(chrono::Utc::now() + chrono::Duration::seconds(1)),
+        ..EventFilter::default()
+    };
+    assert!(list(later).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_catch_up_applies_events_of_other_writers_once() {
+    let inner: Arc<dyn DataStore> = Arc::new(sqlite_store(true).await);
+    let first = ChangeLogStore::with_builtins(inner.clone()).await.unwrap();
+    let second = ChangeLogStore::with_builtins(inner.clone()).await.unwrap();
+
+    let location = Location::new_root("dc1".to_string(), "datacenter".to_string());
+    first.create_location(&location).await.unwrap();
+    // The second store catches up on the first one's event as well as its
+    // own, after the first has already applied it
+    let other = Location::new_root("dc2".to_string(), "datacenter".to_string());
+    second.create_location(&other).await.unwrap();
+
+    let activity = EntityActivity::get(inner.as_ref(), location.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(activity.changes, 1);
+    assert!(
+        EntityActivity::get(inner.as_ref(), other.id)
+            .await
+            .unwrap()
+            .is_some()
+    );
+}
+
+#[tokio::test]
+async fn test_replay_rebuilds_projection_from_log_in_pages() {
+    let store = sqlite_store(true).await;
+    let created = event(EntityKind::Node, ChangeType::Created, "edge-1");
+    store.append_change_event(&created).await.unwrap();
+    for _ in 0..EVENT_PAGE {
+        let mut renamed = created.clone();
+        renamed.id = Uuid::new_v4();
+        renamed.change = ChangeType::Updated;
+        renamed.recorded_at = created.recorded_at + chrono::Duration::seconds(1);
+        renamed.payload = serde_json::json!({ "name": "edge-01" });
+        store.append_change_event(&renamed).await.unwrap();
+    }
+
+    // Projection state left over from a stale or buggy read model
+    let stale = event(EntityKind::Node, ChangeType::Created, "ghost");
+    EntityActivity.apply(&store, &stale).await.unwrap();
+
+    let report = replay(&store, &EntityActivity).await.unwrap();
+    assert_eq!(report.projection, EntityActivity::NAME);
+    assert_eq!(report.events, EVENT_PAGE + 1);
+
+    let activity = EntityActivity::get(&store, created.entity_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(activity.name.as_deref(), Some("edge-01"));
+    assert_eq!(
+        activity.created_at.map(|at| at.timestamp_micros()),
+        Some(created.recorded_at.timestamp_micros())
+    );
+    assert_eq!(activity.changes, EVENT_PAGE + 1);
+    assert!(
+        EntityActivity::get(&store, stale.entity_id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn test_prune_drops_only_events_before_cutoff() {
+    let store = sqlite_store(true).await;
+    let mut old = event(EntityKind::Node, ChangeType::Created, "edge-1");
+    old.recorded_at -= chrono::Duration::days(10);
+    let recent = event(EntityKind::Node, ChangeType::Updated, "edge-1");
+    store.append_change_event(&old).await.unwrap();
+    let recent = store.append_change_event(&recent).await.unwrap();
+    let cutoff = recent.recorded_at - chrono::Duration::days(1);
+
+    let dry_run = store.prune_change_events(cutoff, true).await.unwrap();
+    assert_eq!(dry_run.rows, 1);
+    assert!(dry_run.bytes > 0);
+    assert_eq!(store.last_change_sequence().await.unwrap(), 2);
+
+    let pruned = store.prune_change_events(cutoff, false).await.unwrap();
+    assert_eq!(pruned, dry_run);
+    let events = store
+        .list_change_events(&EventFilter::default())
+        .await
+        .unwrap();
+    let ids: Vec<Uuid> = events.iter().map(|event| event.id).collect();
+    assert_eq!(ids, [recent.id]);
+}
+
+#[tokio::test]
+async fn test_compliance_summary_rolls_up_latest_results_by_location() {
+    let inner: Arc<dyn DataStore> = Arc::new(sqlite_store(true).await);
+    let store = ChangeLogStore::with_builtins(inner.clone()).await.unwrap();
+    let site = Location::new_root("dc1".to_string(), "datacenter".to_string());
+    store.create_location(&site).await.unwrap();
+    let compliant = store
+        .create_node(&node("edge-1", Some(site.id)))
+        .await
+        .unwrap();
+    let failing = store
+        .create_node(&node("edge-2", Some(site.id)))
+        .await
+        .unwrap();
+    let mut moved = store
+        .create_node(&node("edge-3", Some(site.id)))
+        .await
+        .unwrap();
+    moved.location_id = None;
+    store.update_node(&moved).await.unwrap();
+    let gone = store
+        .create_node(&node("edge-4", Some(site.id)))
+        .await
+        .unwrap();
+    store.delete_node(&gone.id).await.unwrap();
+
+    let rule = PolicyRule {
+        id: Some("version".to_string()),
+        condition: Condition::True,
+        action: Action::Assert {
+            field: FieldRef {
+                path: vec!["version".to_string()],
+            },
+            expected: PolicyValue::String("17.3".to_string()),
+        },
+    };
+    let passed = PolicyExecutionResult::new(rule, EvaluationResult::NotSatisfied, None);
+    let failed = PolicyExecutionResult::new_error_with_id(Some("version".to_string()), "x".into());
+    inner
+        .store_policy_result(&compliant.id, "version", &passed)
+        .await
+        .unwrap();
+    inner
+        .store_policy_result(&failing.id, "version", &failed)
+        .await
+        .unwrap();
+
+    let summary = ComplianceSummary::summarize(inner.as_ref()).await.unwrap();
+    assert_eq!(
+        summary,
+        [
+            LocationCompliance {
+                location_id: None,
+                nodes: 1,
+                not_evaluated: 1,
+                ..LocationCompliance::default()
+            },
+            LocationCompliance {
+                location_id: Some(site.id),
+                nodes: 2,
+                compliant: 1,
+                non_compliant: 1,
+                not_evaluated: 0,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_custom_data_changes_lists_changed_leaves() {
+    let before = serde_json::json!({
+        "name": "edge-1",
+        "custom_data": { "bgp": { "asn": 65001, "peers": 2 }, "rack": "r1", "old": { "a": 1 } }
+    });
+    let after = serde_json::json!({
+        "name": "edge-2",
+        "custom_data": { "bgp": { "asn": 65002, "peers": 2 }, "rack": { "row": 4 }, "ntp": [1] }
+    });
+
+    assert_eq!(
+        custom_data_changes(&before, &after),
+        [
+            "custom_data.bgp.asn",
+            "custom_data.ntp",
+            "custom_data.old.a",
+            "custom_data.rack",
+            "custom_data.rack.row",
+        ]
+    );
+    assert_eq!(
+        custom_data_changes(&serde_json::Value::Null, &before),
+        [
+            "custom_data.bgp.asn",
+            "custom_data.bgp.peers",
+            "custom_data.old.a",
+            "custom_data.rack",
+        ]
+    );
+    assert!(custom_data_changes(&before, &before).is_empty());
+}
+
+#[tokio::test]
+async fn test_store_tracks_custom_data_field_history() {
+    let inner: Arc<dyn DataStore> = Arc::new(sqlite_store(true).await);
+    let store = ChangeLogStore::with_builtins(inner.clone()).await.unwrap();
+
+    let mut location = Location::new_root("dc1".to_string(), "datacenter".to_string());
+    location.custom_data = serde_json::json!({ "bgp": { "asn": 65001 } });
+    store.create_location(&location).await.unwrap();
+    location.name = "dc1-east".to_string();
+    store.update_location(&location).await.unwrap();
+    location.custom_data = serde_json::json!({ "bgp": { "asn": 65002 }, "tier": 1 });
+    store.update_location(&location).await.unwrap();
+
+    let events = inner
+        .list_change_events(&EventFilter::default())
+        .await
+        .unwrap();
+    let changed: Vec<&[String]> = events
+        .iter()
+        .map(|event| event.changed_fields.as_slice())
+        .collect();
+    assert_eq!(
+        changed,
+        [
+            &["custom_data.bgp.asn".to_string()][..],
+            &[],
+            &[
+                "custom_data.bgp.asn".to_string(),
+                "custom_data.tier".to_string()
+            ],
+        ]
+    );
+
+    let history = field_history(inner.as_ref(), location.id, "custom_data.bgp")
+        .await
+        .unwrap();
+    let values: Vec<&serde_json::Value> = history.iter().map(|change| &change.value).collect();
+    assert_eq!(
+        values,
+        [
+            &serde_json::json!({ "asn": 65001 }),
+            &serde_json::json!({ "asn": 65002 }),
+        ]
+    );
+    assert_eq!(history[1].fields, ["custom_data.bgp.asn"]);
+
+    let last = last_changed(inner.as_ref(), location.id).await.unwrap();
+    assert_eq!(last["custom_data.bgp.asn"], events[2].recorded_at);
+    assert_eq!(last["custom_data.tier"], events[2].recorded_at);
+
+    assert!(
+        field_history(inner.as_ref(), location.id, "name")
+            .await
+            .is_err()
+    );
+}
+
+#[cfg(feature = "policy")]
+#[tokio::test]
+async fn test_policy_context_holds_seconds_since_change() {
+    use crate::policy::{
+        Action, ComparisonOperator, Condition, EvaluationContext, FieldRef, PolicyRule, Value,
+    };
+    use crate::policy_integration::add_change_ages;
+
+    let store = sqlite_store(true).await;
+    let node_id = Uuid::new_v4();
+    let mut event = ChangeEvent::new(
+        EntityKind::Node,
+        node_id,
+        ChangeType::Updated,
+        serde_json::Value::Null,
+    )
+    .with_changed_fields(vec!["custom_data.bgp.asn".to_string()]);
+    event.recorded_at -= chrono::Duration::hours(2);
+    store.append_change_event(&event).await.unwrap();
+
+    let field = |path: &str| FieldRef {
+        path: path.split('.').map(str::to_string).collect(),
+    };
+    let rule = PolicyRule {
+        id: Some("recent-asn".to_string()),
+        condition: Condition::Comparison {
+            field: field("changed.custom_data.bgp.asn"),
+            operator: ComparisonOperator::LessThan,
+            value: Value::Number(86400.0),
+        },
+        action: Action::Set {
+            field: field("custom_data.review"),
+            value: Value::Boolean(true),
+        },
+    };
+
+    let mut context = EvaluationContext::new(serde_json::json!({"node": {}}));
+    add_change_ages(&mut context, &store, Uuid::new_v4(), [&rule])
+        .await
+        .unwrap();
+    assert_eq!(context.get_field("changed"), Some(&serde_json::json!({})));
+
+    add_change_ages(&mut context, &store, node_id, [&rule])
+        .await
+        .unwrap();
+    let age = context
+        .get_field("changed.custom_data.bgp.asn")
+        .and_then(serde_json::Value::as_i64)
+        .unwrap();
+    assert!((7200..7260).contains(&age));
+}
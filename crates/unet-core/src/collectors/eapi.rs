@@ -0,0 +1,202 @@
+//! Arista EOS eAPI collector
+//!
+//! One JSON-RPC `runCmds` request runs `show interfaces`, `show ip bgp
+//! summary vrf all`, and `show lldp neighbors detail`. eAPI does not report
+//! interface indexes, so interfaces are numbered in name order.
+
+use serde_json::{Value, json};
+
+use super::{
+    HttpMethod, HttpRequest, HttpTransport, Normalized, counter, interface_type, json_body,
+    oper_status, text,
+};
+use crate::models::derived::{
+    BgpPeer, BgpPeerState, InterfaceAdminStatus, InterfaceStats, InterfaceStatus, LldpNeighbor,
+};
+
+/// Path of the eAPI endpoint
+pub const EAPI_PATH: &str = "/command-api";
+
+/// Commands run on every collection, in the order their results are read
+const COMMANDS: [&str; 3] = [
+    "show interfaces",
+    "show ip bgp summary vrf all",
+    "show lldp neighbors detail",
+];
+
+/// Builds the `runCmds` request for the device at `base`
+///
+/// # Errors
+/// Returns an error message if the request body cannot be serialized.
+pub fn request(base: &str) -> Result<HttpRequest, String> {
+    let body = json!({
+        "jsonrpc": "2.0",
+        "method": "runCmds",
+        "params": { "version": 1, "cmds": COMMANDS, "format": "json" },
+        "id": "unet",
+    });
+    Ok(HttpRequest {
+        method: HttpMethod::Post,
+        url: format!("{base}{EAPI_PATH}"),
+        headers: vec![("Content-Type", "application/json".to_string())],
+        body: Some(serde_json::to_vec(&body).map_err(|e| e.to_string())?),
+    })
+}
+
+/// Normalizes a `runCmds` response
+///
+/// # Errors
+/// Returns an error message if eAPI reports an error or the response lacks
+/// a result for every command.
+pub fn parse(response: &Value) -> Result<Normalized, String> {
+    if let Some(error) = response.get("error") {
+        let message = error
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown error");
+        return Err(format!("eAPI error: {message}"));
+    }
+    let results = response
+        .get("result")
+        .and_then(Value::as_array)
+        .filter(|results| results.len() == COMMANDS.len())
+        .ok_or_else(|| "eAPI response has no result for every command".to_string())?;
+    Ok((
+        interfaces(&results[0]),
+        bgp_peers(&results[1]),
+        lldp_neighbors(&results[2]),
+    ))
+}
+
+/// Collects from the device at `base`
+pub(super) async fn collect(
+    transport: &dyn HttpTransport,
+    base: &str,
+    username: &str,
+    password: &str,
+) -> Result<Normalized, String> {
+    let request = request(base)?;
+    let response = transport.send(&request, username, password).await?;
+    parse(&json_body(&response, &request.url)?)
+}
+
+fn interfaces(result: &Value) -> Vec<InterfaceStatus> {
+    let Some(interfaces) = result.get("interfaces").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    let mut names: Vec<&String> = interfaces.keys().collect();
+    names.sort();
+    names
+        .into_iter()
+        .zip(1..)
+        .map(|(name, index)| {
+            let interface = &interfaces[name];
+            let counters = interface.get("interfaceCounters");
+            let count = |key: &str| counter(counters.and_then(|c| c.get(key))).unwrap_or_default();
+            let admin_status = match text(interface.get("interfaceStatus")).as_deref() {
+                Some("disabled") => InterfaceAdminStatus::Down,
+                Some(_) => InterfaceAdminStatus::Up,
+                None => InterfaceAdminStatus::Unknown,
+            };
+            InterfaceStatus {
+                index,
+                name: name.clone(),
+                interface_type: interface_type(name),
+                mtu: counter(interface.get("mtu")).and_then(|mtu| u32::try_from(mtu).ok()),
+                speed: counter(interface.get("bandwidth")),
+                physical_address: text(interface.get("physicalAddress")),
+                admin_status,
+                oper_status: oper_status(
+                    &text(interface.get("lineProtocolStatus")).unwrap_or_default(),
+                ),
+                last_change: None,
+                input_stats: InterfaceStats {
+                    octets: count("inOctets"),
+                    packets: count("inUcastPkts"),
+                    errors: count("totalInErrors"),
+                    discards: count("inDiscards"),
+                    high_capacity: true,
+                    rate: None,
+                    crc_errors: counter(
+                        counters.and_then(|c| c.pointer("/inputErrorsDetail/fcsErrors")),
+                    ),
+                    error_rate: None,
+                },
+                output_stats: InterfaceStats {
+                    octets: count("outOctets"),
+                    packets: count("outUcastPkts"),
+                    errors: count("totalOutErrors"),
+                    discards: count("outDiscards"),
+                    high_capacity: true,
+                    rate: None,
+                    crc_errors: None,
+                    error_rate: None,
+                },
+            }
+        })
+        .collect()
+}
+
+fn bgp_peers(result: &Value) -> Vec<BgpPeer> {
+    let Some(vrfs) = result.get("vrfs").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    let mut peers: Vec<BgpPeer> = vrfs
+        .iter()
+        .flat_map(|(vrf, details)| {
+            details
+                .get("peers")
+                .and_then(Value::as_object)
+                .into_iter()
+                .flatten()
+                .map(move |(address, peer)| BgpPeer {
+                    address: address.clone(),
+                    vrf: vrf.clone(),
+                    remote_as: counter(peer.get("asn")).and_then(|asn| u32::try_from(asn).ok()),
+                    state: text(peer.get("peerState")).map_or(BgpPeerState::Unknown, |state| {
+                        BgpPeerState::from_name(&state)
+                    }),
+                    prefixes_received: counter(peer.get("prefixReceived")),
+                })
+        })
+        .collect();
+    peers.sort_by(|a, b| (&a.vrf, &a.address).cmp(&(&b.vrf, &b.address)));
+    peers
+}
+
+fn lldp_neighbors(result: &Value) -> Vec<LldpNeighbor> {
+    let Some(local_interfaces) = result.get("lldpNeighbors").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    let mut neighbors: Vec<LldpNeighbor> = local_interfaces
+        .iter()
+        .flat_map(|(local_interface, details)| {
+            details
+                .get("lldpNeighborInfo")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .map(move |neighbor| {
+                    let port = neighbor.get("neighborInterfaceInfo");
+                    LldpNeighbor {
+                        local_interface: local_interface.clone(),
+                        chassis_id: text(neighbor.get("chassisId")),
+                        system_name: text(neighbor.get("systemName")),
+                        // EOS quotes interface names in the port ID
+                        port_id: text(port.and_then(|port| port.get("interfaceId")))
+                            .map(|id| id.trim_matches('"').to_string()),
+                        port_description: text(
+                            port.and_then(|port| port.get("interfaceDescription")),
+                        ),
+                        management_address: neighbor
+                            .get("managementAddresses")
+                            .and_then(Value::as_array)
+                            .and_then(|addresses| addresses.first())
+                            .and_then(|address| text(address.get("address"))),
+                    }
+                })
+        })
+        .collect();
+    neighbors.sort_by(|a, b| a.local_interface.cmp(&b.local_interface));
+    neighbors
+}
@@ -0,0 +1,379 @@
+//! HTTP collectors of derived state
+//!
+//! Devices with an HTTP API can report interface, BGP, and LLDP state
+//! directly instead of through SNMP: Arista switches through eAPI and Cisco
+//! IOS-XE devices through RESTCONF. A node opts in by naming its API in
+//! `custom_data.collector`, either as a string (`"arista_eapi"` or
+//! `"restconf"`) or as an object with a `type` and an optional `port`.
+//!
+//! Responses are normalized into the derived-state models used for SNMP
+//! ([`InterfaceStatus`], [`BgpPeer`], and [`LldpNeighbor`]). The latest
+//! collection of each node is kept through the `DataStore` settings API and
+//! overlaid on the node's status with [`CollectedState::apply_to`]. Requests
+//! are made through an [`HttpTransport`], which the server implements.
+
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult};
+use crate::models::derived::{
+    BgpPeer, InterfaceOperStatus, InterfaceStatus, LldpNeighbor, NodeStatus,
+};
+use crate::models::{AddressFamilyPreference, Node};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::SystemTime;
+use uuid::Uuid;
+
+pub mod eapi;
+pub mod restconf;
+
+/// Settings namespace holding the latest collection of each node keyed by node ID
+const COLLECTED_NAMESPACE: &str = "collected_state";
+
+/// `custom_data` field naming a node's collector
+pub const COLLECTOR_FIELD: &str = "collector";
+
+/// Device API a collector talks to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CollectorKind {
+    /// Arista EOS eAPI (JSON-RPC over HTTPS)
+    AristaEapi,
+    /// RESTCONF with the Cisco IOS-XE operational models
+    Restconf,
+}
+
+/// Where and how a node's state is collected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollectorTarget {
+    /// API to collect through
+    #[serde(rename = "type")]
+    pub kind: CollectorKind,
+    /// HTTPS port of the API
+    #[serde(default = "default_port")]
+    pub port: u16,
+}
+
+const fn default_port() -> u16 {
+    443
+}
+
+impl CollectorTarget {
+    /// Reads the collector a node opted into
+    ///
+    /// Returns `Ok(None)` for nodes without `custom_data.collector`.
+    ///
+    /// # Errors
+    /// Returns an error message if the field names no known collector.
+    pub fn for_node(node: &Node) -> Result<Option<Self>, String> {
+        let Some(value) = node.custom_data.get(COLLECTOR_FIELD) else {
+            return Ok(None);
+        };
+        let value = match value {
+            Value::Null => return Ok(None),
+            Value::String(kind) => serde_json::json!({ "type": kind }),
+            other => other.clone(),
+        };
+        serde_json::from_value(value).map(Some).map_err(|e| {
+            format!(
+                "Invalid custom_data.{COLLECTOR_FIELD} on {}: {e}; expected arista_eapi or restconf",
+                node.name
+            )
+        })
+    }
+}
+
+/// HTTP method of a collector request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HttpMethod {
+    /// GET
+    Get,
+    /// POST
+    Post,
+}
+
+/// An HTTP request to a device API
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpRequest {
+    /// Request method
+    pub method: HttpMethod,
+    /// Target URL
+    pub url: String,
+    /// Headers to send, besides basic authentication
+    pub headers: Vec<(&'static str, String)>,
+    /// JSON body, if any
+    pub body: Option<Vec<u8>>,
+}
+
+/// A device API response
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    /// HTTP status code
+    pub status: u16,
+    /// Response body
+    pub body: Vec<u8>,
+}
+
+/// Interfaces, BGP peers, and LLDP neighbors read from a device
+pub type Normalized = (Vec<InterfaceStatus>, Vec<BgpPeer>, Vec<LldpNeighbor>);
+
+/// Transport making collector requests
+#[async_trait]
+pub trait HttpTransport: Send + Sync {
+    /// Sends the request with basic authentication, failing on transport errors
+    async fn send(
+        &self,
+        request: &HttpRequest,
+        username: &str,
+        password: &str,
+    ) -> Result<HttpResponse, String>;
+}
+
+/// State of a node as last reported by its collector
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CollectedState {
+    /// Node the state was collected from
+    pub node_id: Uuid,
+    /// API it was collected through
+    pub collector: CollectorKind,
+    /// When it was collected
+    pub collected_at: DateTime<Utc>,
+    /// Interfaces, with 64-bit counters
+    pub interfaces: Vec<InterfaceStatus>,
+    /// BGP peers in every VRF
+    pub bgp_peers: Vec<BgpPeer>,
+    /// LLDP neighbors
+    pub lldp_neighbors: Vec<LldpNeighbor>,
+}
+
+impl CollectedState {
+    /// Sets rates from an earlier collection of the same node
+    ///
+    /// Interfaces are matched by name, since HTTP APIs do not all report
+    /// interface indexes.
+    pub fn compute_rates(&mut self, previous: &Self) {
+        let Ok(elapsed) = (self.collected_at - previous.collected_at).to_std() else {
+            return;
+        };
+        for interface in &mut self.interfaces {
+            if let Some(earlier) = previous
+                .interfaces
+                .iter()
+                .find(|earlier| earlier.name == interface.name)
+            {
+                interface.compute_rates(earlier, elapsed);
+            }
+        }
+    }
+
+    /// Overlays the collected state on a node's status
+    ///
+    /// Collected interfaces replace polled ones when there are any, and the
+    /// node counts as reachable if the collection is newer than its last
+    /// update.
+    pub fn apply_to(&self, status: &mut NodeStatus) {
+        if !self.interfaces.is_empty() {
+            status.interfaces.clone_from(&self.interfaces);
+        }
+        status.bgp_peers.clone_from(&self.bgp_peers);
+        status.lldp_neighbors.clone_from(&self.lldp_neighbors);
+
+        let collected_at = SystemTime::from(self.collected_at);
+        if collected_at > status.last_updated {
+            status.last_updated = collected_at;
+            status.reachable = true;
+        }
+    }
+}
+
+/// Collects a node's state through its API
+///
+/// `address` is the management address and port of the API.
+///
+/// # Errors
+/// Returns an error message if a request fails or a response cannot be read.
+pub async fn collect(
+    transport: &dyn HttpTransport,
+    node_id: Uuid,
+    kind: CollectorKind,
+    address: std::net::SocketAddr,
+    username: &str,
+    password: &str,
+) -> Result<CollectedState, String> {
+    let base = format!("https://{address}");
+    let (interfaces, bgp_peers, lldp_neighbors) = match kind {
+        CollectorKind::AristaEapi => eapi::collect(transport, &base, username, password).await?,
+        CollectorKind::Restconf => restconf::collect(transport, &base, username, password).await?,
+    };
+    Ok(CollectedState {
+        node_id,
+        collector: kind,
+        collected_at: Utc::now(),
+        interfaces,
+        bgp_peers,
+        lldp_neighbors,
+    })
+}
+
+/// Collects a node's state and records it as its latest collection
+///
+/// Rates are computed from the previous collection. Returns `Ok(None)` for
+/// nodes that have not opted into a collector.
+///
+/// # Errors
+/// Returns a `ValidationError` if the node's collector or management address
+/// is invalid, a `ConnectionError` if the device cannot be collected from, or
+/// an error if the datastore cannot be read or written.
+pub async fn collect_node(
+    datastore: &dyn DataStore,
+    transport: &dyn HttpTransport,
+    node: &Node,
+    credentials: (&str, &str),
+    preference: AddressFamilyPreference,
+) -> DataStoreResult<Option<CollectedState>> {
+    let invalid = |message: String| DataStoreError::ValidationError { message };
+    let Some(target) = CollectorTarget::for_node(node).map_err(invalid)? else {
+        return Ok(None);
+    };
+    let address = node
+        .management_socket_addr(target.port, preference)
+        .map_err(invalid)?
+        .ok_or_else(|| invalid(format!("{} has no management address", node.name)))?;
+
+    let (username, password) = credentials;
+    let mut state = collect(transport, node.id, target.kind, address, username, password)
+        .await
+        .map_err(|message| DataStoreError::ConnectionError {
+            message: format!("Collecting from {} failed: {message}", node.name),
+        })?;
+    if let Some(previous) = get_collected_state(datastore, node.id).await? {
+        state.compute_rates(&previous);
+    }
+
+    let value = serde_json::to_value(&state).map_err(|e| DataStoreError::InternalError {
+        message: format!("collected state of {}: {e}", node.id),
+    })?;
+    datastore
+        .put_setting(COLLECTED_NAMESPACE, &node.id.to_string(), &value)
+        .await?;
+    Ok(Some(state))
+}
+
+/// Gets the latest collection of a node
+///
+/// Datastores without settings support have no collections.
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or the stored state is malformed.
+pub async fn get_collected_state(
+    datastore: &dyn DataStore,
+    node_id: Uuid,
+) -> DataStoreResult<Option<CollectedState>> {
+    let stored = match datastore
+        .get_setting(COLLECTED_NAMESPACE, &node_id.to_string())
+        .await
+    {
+        Ok(stored) => stored,
+        Err(DataStoreError::UnsupportedOperation { .. }) => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    stored
+        .map(|value| {
+            serde_json::from_value(value).map_err(|e| DataStoreError::InternalError {
+                message: format!("stored collected state of {node_id}: {e}"),
+            })
+        })
+        .transpose()
+}
+
+/// Gets a node's status with its latest collection overlaid
+///
+/// Nodes that are only collected from, and never polled over SNMP, get a
+/// status built from the collection alone.
+///
+/// # Errors
+/// Returns an error if the status or collection cannot be read.
+pub async fn node_status_with_collected(
+    datastore: &dyn DataStore,
+    node_id: Uuid,
+) -> DataStoreResult<Option<NodeStatus>> {
+    let status = match datastore.get_node_status(&node_id).await {
+        Ok(status) => status,
+        Err(DataStoreError::UnsupportedOperation { .. }) => None,
+        Err(e) => return Err(e),
+    };
+    let Some(collected) = get_collected_state(datastore, node_id).await? else {
+        return Ok(status);
+    };
+    let mut status = status.unwrap_or_else(|| {
+        let mut status = NodeStatus::new(node_id);
+        status.last_updated = SystemTime::UNIX_EPOCH;
+        status
+    });
+    collected.apply_to(&mut status);
+    Ok(Some(status))
+}
+
+/// Reads a JSON response body, failing on non-2xx statuses
+fn json_body(response: &HttpResponse, url: &str) -> Result<Value, String> {
+    if !(200..300).contains(&response.status) {
+        return Err(format!("{url} returned HTTP {}", response.status));
+    }
+    serde_json::from_slice(&response.body).map_err(|e| format!("{url} returned invalid JSON: {e}"))
+}
+
+/// Reads a counter that may be a JSON number or, as YANG encodes 64-bit
+/// integers, a string
+fn counter(value: Option<&Value>) -> Option<u64> {
+    match value? {
+        Value::Number(number) => number
+            .as_u64()
+            .or_else(|| number.as_f64().and_then(|n| n.to_u64())),
+        Value::String(text) => text.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Reads a non-empty string
+fn text(value: Option<&Value>) -> Option<String> {
+    value
+        .and_then(Value::as_str)
+        .filter(|text| !text.is_empty())
+        .map(str::to_string)
+}
+
+/// Reads an operational status as APIs name it, e.g. `lowerLayerDown` or
+/// `lower-layer-down`
+fn oper_status(name: &str) -> InterfaceOperStatus {
+    match name.to_ascii_lowercase().replace(['-', '_'], "").as_str() {
+        "up" => InterfaceOperStatus::Up,
+        "down" => InterfaceOperStatus::Down,
+        "testing" => InterfaceOperStatus::Testing,
+        "dormant" => InterfaceOperStatus::Dormant,
+        "notpresent" => InterfaceOperStatus::NotPresent,
+        "lowerlayerdown" => InterfaceOperStatus::LowerLayerDown,
+        _ => InterfaceOperStatus::Unknown,
+    }
+}
+
+/// IANA `ifType` guessed from an interface name
+fn interface_type(name: &str) -> u32 {
+    let name = name.to_ascii_lowercase();
+    if name.starts_with("loopback") {
+        24
+    } else if name.starts_with("port-channel") || name.starts_with("bundle") {
+        161
+    } else if name.starts_with("tunnel") {
+        131
+    } else if name.starts_with("vlan") {
+        53
+    } else if name.contains("ethernet") || name.starts_with("management") {
+        6
+    } else {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,192 @@
+//! RESTCONF collector for Cisco IOS-XE
+//!
+//! Interfaces are read from the standard `ietf-interfaces` state tree, BGP
+//! neighbors and LLDP entries from the IOS-XE operational models. A device
+//! without BGP or LLDP answers those requests with no content or 404, which
+//! yields no peers or neighbors rather than an error.
+
+use serde_json::Value;
+
+use super::{
+    HttpMethod, HttpRequest, HttpResponse, HttpTransport, Normalized, counter, interface_type,
+    json_body, oper_status, text,
+};
+use crate::models::derived::{
+    BgpPeer, BgpPeerState, InterfaceAdminStatus, InterfaceStats, InterfaceStatus, LldpNeighbor,
+};
+
+/// Interface state, RFC 8343
+pub const INTERFACES_PATH: &str = "/restconf/data/ietf-interfaces:interfaces-state";
+/// BGP neighbor state
+pub const BGP_PATH: &str = "/restconf/data/Cisco-IOS-XE-bgp-oper:bgp-state-data/neighbors";
+/// LLDP neighbor entries
+pub const LLDP_PATH: &str = "/restconf/data/Cisco-IOS-XE-lldp-oper:lldp-entries";
+
+/// Builds the GET request for a RESTCONF resource of the device at `base`
+#[must_use]
+pub fn request(base: &str, path: &str) -> HttpRequest {
+    HttpRequest {
+        method: HttpMethod::Get,
+        url: format!("{base}{path}"),
+        headers: vec![("Accept", "application/yang-data+json".to_string())],
+        body: None,
+    }
+}
+
+/// Collects from the device at `base`
+pub(super) async fn collect(
+    transport: &dyn HttpTransport,
+    base: &str,
+    username: &str,
+    password: &str,
+) -> Result<Normalized, String> {
+    let get = |path: &'static str| async move {
+        let request = request(base, path);
+        let response = transport.send(&request, username, password).await?;
+        Ok::<_, String>((request.url, response))
+    };
+
+    let (url, response) = get(INTERFACES_PATH).await?;
+    let interfaces = parse_interfaces(&json_body(&response, &url)?);
+    let (url, response) = get(BGP_PATH).await?;
+    let bgp_peers = optional_body(&response, &url)?
+        .map(|body| parse_bgp_peers(&body))
+        .unwrap_or_default();
+    let (url, response) = get(LLDP_PATH).await?;
+    let lldp_neighbors = optional_body(&response, &url)?
+        .map(|body| parse_lldp_neighbors(&body))
+        .unwrap_or_default();
+    Ok((interfaces, bgp_peers, lldp_neighbors))
+}
+
+/// Reads a body that is absent when the feature is not configured
+fn optional_body(response: &HttpResponse, url: &str) -> Result<Option<Value>, String> {
+    match response.status {
+        204 | 404 => Ok(None),
+        _ => json_body(response, url).map(Some),
+    }
+}
+
+/// Normalizes an `ietf-interfaces:interfaces-state` response
+#[must_use]
+pub fn parse_interfaces(body: &Value) -> Vec<InterfaceStatus> {
+    list(body, "ietf-interfaces:interfaces-state", "interface")
+        .iter()
+        .zip(1..)
+        .filter_map(|(interface, position)| {
+            let name = text(interface.get("name"))?;
+            let statistics = interface.get("statistics");
+            let count =
+                |key: &str| counter(statistics.and_then(|s| s.get(key))).unwrap_or_default();
+            let stats = |direction: &str| InterfaceStats {
+                octets: count(&format!("{direction}-octets")),
+                packets: count(&format!("{direction}-unicast-pkts")),
+                errors: count(&format!("{direction}-errors")),
+                discards: count(&format!("{direction}-discards")),
+                high_capacity: true,
+                rate: None,
+                // RFC 8343 statistics have no FCS counter
+                crc_errors: None,
+                error_rate: None,
+            };
+            let admin_status = match text(interface.get("admin-status")).as_deref() {
+                Some("up") => InterfaceAdminStatus::Up,
+                Some("down") => InterfaceAdminStatus::Down,
+                Some("testing") => InterfaceAdminStatus::Testing,
+                _ => InterfaceAdminStatus::Unknown,
+            };
+            Some(InterfaceStatus {
+                index: counter(interface.get("if-index"))
+                    .and_then(|index| u32::try_from(index).ok())
+                    .unwrap_or(position),
+                interface_type: text(interface.get("type"))
+                    .and_then(|iana| iana_type(&iana))
+                    .unwrap_or_else(|| interface_type(&name)),
+                mtu: None,
+                speed: counter(interface.get("speed")),
+                physical_address: text(interface.get("phys-address")),
+                admin_status,
+                oper_status: oper_status(&text(interface.get("oper-status")).unwrap_or_default()),
+                last_change: None,
+                input_stats: stats("in"),
+                output_stats: stats("out"),
+                name,
+            })
+        })
+        .collect()
+}
+
+/// Normalizes a `Cisco-IOS-XE-bgp-oper` neighbors response
+///
+/// Neighbors are listed once per address family; the first entry of each
+/// peer is kept.
+#[must_use]
+pub fn parse_bgp_peers(body: &Value) -> Vec<BgpPeer> {
+    let mut peers: Vec<BgpPeer> = Vec::new();
+    for neighbor in list(body, "Cisco-IOS-XE-bgp-oper:neighbors", "neighbor") {
+        let Some(address) = text(neighbor.get("neighbor-id")) else {
+            continue;
+        };
+        let vrf = text(neighbor.get("vrf-name")).unwrap_or_else(|| "default".to_string());
+        if peers
+            .iter()
+            .any(|peer| peer.address == address && peer.vrf == vrf)
+        {
+            continue;
+        }
+        peers.push(BgpPeer {
+            address,
+            vrf,
+            remote_as: counter(neighbor.get("as")).and_then(|asn| u32::try_from(asn).ok()),
+            state: text(neighbor.pointer("/connection/state"))
+                .map_or(BgpPeerState::Unknown, |state| {
+                    BgpPeerState::from_name(&state)
+                }),
+            prefixes_received: counter(
+                neighbor.pointer("/prefix-activity/received/total-prefixes"),
+            ),
+        });
+    }
+    peers
+}
+
+/// Normalizes a `Cisco-IOS-XE-lldp-oper` entries response
+#[must_use]
+pub fn parse_lldp_neighbors(body: &Value) -> Vec<LldpNeighbor> {
+    list(body, "Cisco-IOS-XE-lldp-oper:lldp-entries", "lldp-entry")
+        .iter()
+        .filter_map(|entry| {
+            Some(LldpNeighbor {
+                local_interface: text(entry.get("local-interface"))?,
+                chassis_id: None,
+                system_name: text(entry.get("device-id")),
+                port_id: text(entry.get("connecting-interface")),
+                port_description: None,
+                management_address: None,
+            })
+        })
+        .collect()
+}
+
+/// Entries of a YANG list under a top-level container
+fn list<'a>(body: &'a Value, container: &str, name: &str) -> &'a [Value] {
+    body.get(container)
+        .and_then(|container| container.get(name))
+        .and_then(Value::as_array)
+        .map_or(&[], Vec::as_slice)
+}
+
+/// IANA `ifType` number of an `iana-if-type` identity
+fn iana_type(identity: &str) -> Option<u32> {
+    let name = identity.rsplit(':').next()?;
+    match name {
+        "other" => Some(1),
+        "ethernetCsmacd" => Some(6),
+        "softwareLoopback" => Some(24),
+        "propVirtual" => Some(53),
+        "tunnel" => Some(131),
+        "l3ipvlan" => Some(136),
+        "ieee8023adLag" => Some(161),
+        _ => None,
+    }
+}
@@ -0,0 +1,363 @@
+use super::*;
+use crate::datastore::sqlite::tests::setup::migrated_store;
+use crate::models::derived::{BgpPeerState, InterfaceAdminStatus, InterfaceOperStatus};
+use crate::models::{DeviceRole, Vendor};
+use chrono::TimeDelta;
+use serde_json::json;
+use std::collections::HashMap;
+
+/// Answers requests by URL; unknown URLs get a 404
+struct FakeTransport {
+    responses: HashMap<String, (u16, Value)>,
+}
+
+impl FakeTransport {
+    fn new(responses: impl IntoIterator<Item = (String, u16, Value)>) -> Self {
+        Self {
+            responses: responses
+                .into_iter()
+                .map(|(url, status, body)| (url, (status, body)))
+                .collect(),
+        }
+    }
+}
+
+#[async_trait]
+impl HttpTransport for FakeTransport {
+    async fn send(
+        &self,
+        request: &HttpRequest,
+        username: &str,
+        _password: &str,
+    ) -> Result<HttpResponse, String> {
+        if username != "unet" {
+            return Ok(HttpResponse {
+                status: 401,
+                body: Vec::new(),
+            });
+        }
+        let (status, body) = self
+            .responses
+            .get(&request.url)
+            .cloned()
+            .unwrap_or((404, Value::Null));
+        Ok(HttpResponse {
+            status,
+            body: serde_json::to_vec(&body).unwrap(),
+        })
+    }
+}
+
+fn node(collector: Value) -> Node {
+    let mut node = Node::new(
+        "leaf-01".to_string(),
+        "example.com".to_string(),
+        Vendor::Arista,
+        DeviceRole::Switch,
+    );
+    node.management_ip = Some("192.0.2.10".parse().unwrap());
+    node.custom_data = json!({ "collector": collector });
+    node
+}
+
+fn eapi_response(in_octets: u64) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": "unet",
+        "result": [
+            {
+                "interfaces": {
+                    "Ethernet2": {
+                        "name": "Ethernet2",
+                        "interfaceStatus": "disabled",
+                        "lineProtocolStatus": "down",
+                        "mtu": 9214,
+                        "bandwidth": 0,
+                    },
+                    "Ethernet1": {
+                        "name": "Ethernet1",
+                        "interfaceStatus": "connected",
+                        "lineProtocolStatus": "up",
+                        "mtu": 9214,
+                        "bandwidth": 10_000_000_000_u64,
+                        "physicalAddress": "00:1c:73:00:00:01",
+                        "interfaceCounters": {
+                            "inOctets": in_octets,
+                            "inUcastPkts": 1000,
+                            "totalInErrors": 2,
+                            "inputErrorsDetail": { "fcsErrors": 1 },
+                            "outOctets": 5000,
+                            "outUcastPkts": 50,
+                        },
+                    },
+                },
+            },
+            {
+                "vrfs": {
+                    "default": {
+                        "peers": {
+                            "10.0.0.1": {
+                                "peerState": "Established",
+                                "asn": "65001",
+                                "prefixReceived": 12,
+                            },
+                        },
+                    },
+                    "MGMT": {
+                        "peers": {
+                            "10.9.0.1": { "peerState": "Active", "asn": 65009 },
+                        },
+                    },
+                },
+            },
+            {
+                "lldpNeighbors": {
+                    "Ethernet1": {
+                        "lldpNeighborInfo": [{
+                            "chassisId": "001c.7300.0002",
+                            "systemName": "spine-01",
+                            "neighborInterfaceInfo": {
+                                "interfaceId": "\"Ethernet7\"",
+                                "interfaceDescription": "to leaf-01",
+                            },
+                            "managementAddresses": [{ "address": "192.0.2.1" }],
+                        }],
+                    },
+                },
+            },
+        ],
+    })
+}
+
+#[test]
+fn test_target_for_node() {
+    assert_eq!(
+        CollectorTarget::for_node(&node(json!("arista_eapi"))).unwrap(),
+        Some(CollectorTarget {
+            kind: CollectorKind::AristaEapi,
+            port: 443,
+        })
+    );
+    assert_eq!(
+        CollectorTarget::for_node(&node(json!({ "type": "restconf", "port": 8443 }))).unwrap(),
+        Some(CollectorTarget {
+            kind: CollectorKind::Restconf,
+            port: 8443,
+        })
+    );
+    assert_eq!(CollectorTarget::for_node(&node(Value::Null)).unwrap(), None);
+    let error = CollectorTarget::for_node(&node(json!("netconf"))).unwrap_err();
+    assert!(error.contains("leaf-01"));
+}
+
+#[test]
+fn test_eapi_response_normalized() {
+    let (interfaces, bgp_peers, lldp_neighbors) = eapi::parse(&eapi_response(4096)).unwrap();
+
+    let names: Vec<&str> = interfaces.iter().map(|i| i.name.as_str()).collect();
+    assert_eq!(names, ["Ethernet1", "Ethernet2"]);
+    let ethernet1 = &interfaces[0];
+    assert_eq!(ethernet1.index, 1);
+    assert_eq!(ethernet1.interface_type, 6);
+    assert_eq!(ethernet1.speed, Some(10_000_000_000));
+    assert_eq!(ethernet1.oper_status, InterfaceOperStatus::Up);
+    assert_eq!(ethernet1.input_stats.octets, 4096);
+    assert_eq!(ethernet1.input_stats.errors, 2);
+    assert_eq!(ethernet1.input_stats.crc_errors, Some(1));
+    assert!(ethernet1.input_stats.high_capacity);
+    assert_eq!(interfaces[1].admin_status, InterfaceAdminStatus::Down);
+
+    assert_eq!(bgp_peers.len(), 2);
+    assert_eq!(bgp_peers[0].vrf, "MGMT");
+    assert_eq!(bgp_peers[0].state, BgpPeerState::Active);
+    assert_eq!(bgp_peers[1].remote_as, Some(65001));
+    assert_eq!(bgp_peers[1].state, BgpPeerState::Established);
+    assert_eq!(bgp_peers[1].prefixes_received, Some(12));
+
+    assert_eq!(lldp_neighbors.len(), 1);
+    assert_eq!(lldp_neighbors[0].system_name.as_deref(), Some("spine-01"));
+    assert_eq!(lldp_neighbors[0].port_id.as_deref(), Some("Ethernet7"));
+    assert_eq!(
+        lldp_neighbors[0].management_address.as_deref(),
+        Some("192.0.2.1")
+    );
+
+    let error = eapi::parse(&json!({ "error": { "code": 1002, "message": "invalid command" } }))
+        .unwrap_err();
+    assert!(error.contains("invalid command"));
+}
+
+#[tokio::test]
+async fn test_restconf_without_bgp() {
+    let base = "https://192.0.2.10:443";
+    let interfaces = json!({
+        "ietf-interfaces:interfaces-state": {
+            "interface": [{
+                "name": "GigabitEthernet1",
+                "type": "iana-if-type:ethernetCsmacd",
+                "admin-status": "up",
+                "oper-status": "lower-layer-down",
+                "if-index": 7,
+                "speed": "1000000000",
+                "statistics": { "in-octets": "18446744073709551615", "out-errors": 3 },
+            }],
+        },
+    });
+    let lldp = json!({
+        "Cisco-IOS-XE-lldp-oper:lldp-entries": {
+            "lldp-entry": [{
+                "device-id": "core-01",
+                "local-interface": "GigabitEthernet1",
+                "connecting-interface": "Gi0/0/1",
+            }],
+        },
+    });
+    let transport = FakeTransport::new([
+        (
+            format!("{base}{}", restconf::INTERFACES_PATH),
+            200,
+            interfaces,
+        ),
+        (format!("{base}{}", restconf::LLDP_PATH), 200, lldp),
+    ]);
+
+    let state = collect(
+        &transport,
+        Uuid::new_v4(),
+        CollectorKind::Restconf,
+        "192.0.2.10:443".parse().unwrap(),
+        "unet",
+        "secret",
+    )
+    .await
+    .unwrap();
+
+    let interface = &state.interfaces[0];
+    assert_eq!(interface.index, 7);
+    assert_eq!(interface.oper_status, InterfaceOperStatus::LowerLayerDown);
+    assert_eq!(interface.speed, Some(1_000_000_000));
+    assert_eq!(interface.input_stats.octets, u64::MAX);
+    assert_eq!(interface.output_stats.errors, 3);
+    assert!(state.bgp_peers.is_empty());
+    assert_eq!(state.lldp_neighbors[0].port_id.as_deref(), Some("Gi0/0/1"));
+
+    let error = collect(
+        &transport,
+        Uuid::new_v4(),
+        CollectorKind::Restconf,
+        "192.0.2.10:443".parse().unwrap(),
+        "admin",
+        "wrong",
+    )
+    .await
+    .unwrap_err();
+    assert!(error.contains("HTTP 401"));
+}
+
+#[test]
+fn test_restconf_bgp_peers_listed_once() {
+    let neighbor = |afi: &str| {
+        json!({
+            "afi-safi": afi,
+            "vrf-name": "default",
+            "neighbor-id": "10.0.0.1",
+            "as": 65001,
+            "connection": { "state": "fsm-established" },
+            "prefix-activity": { "received": { "total-prefixes": "40" } },
+        })
+    };
+    let body = json!({
+        "Cisco-IOS-XE-bgp-oper:neighbors": {
+            "neighbor": [neighbor("ipv4-unicast"), neighbor("vpnv4-unicast")],
+        },
+    });
+
+    let peers = restconf::parse_bgp_peers(&body);
+
+    assert_eq!(peers.len(), 1);
+    assert_eq!(peers[0].state, BgpPeerState::Established);
+    assert_eq!(peers[0].prefixes_received, Some(40));
+}
+
+#[test]
+fn test_rates_from_previous_collection() {
+    let (interfaces, _, _) = eapi::parse(&eapi_response(0)).unwrap();
+    let previous = CollectedState {
+        node_id: Uuid::new_v4(),
+        collector: CollectorKind::AristaEapi,
+        collected_at: Utc::now(),
+        interfaces,
+        bgp_peers: Vec::new(),
+        lldp_neighbors: Vec::new(),
+    };
+    let (interfaces, _, _) = eapi::parse(&eapi_response(1_250_000)).unwrap();
+    let mut current = CollectedState {
+        collected_at: previous.collected_at + TimeDelta::seconds(10),
+        interfaces,
+        ..previous.clone()
+    };
+
+    current.compute_rates(&previous);
+
+    let rate = current.interfaces[0].input_stats.rate.unwrap();
+    assert!((rate.bps - 1_000_000.0).abs() < f64::EPSILON);
+}
+
+#[tokio::test]
+async fn test_collected_state_overlays_status() {
+    let store = migrated_store().await;
+    let node = node(json!("arista_eapi"));
+    let transport = FakeTransport::new([(
+        format!("https://192.0.2.10:443{}", eapi::EAPI_PATH),
+        200,
+        eapi_response(4096),
+    )]);
+
+    assert!(
+        node_status_with_collected(&store, node.id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+    let state = collect_node(
+        &store,
+        &transport,
+        &node,
+        ("unet", "secret"),
+        AddressFamilyPreference::default(),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    assert_eq!(
+        get_collected_state(&store, node.id).await.unwrap(),
+        Some(state)
+    );
+
+    let status = node_status_with_collected(&store, node.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(status.reachable);
+    assert_eq!(status.interfaces.len(), 2);
+    assert_eq!(status.bgp_peers.len(), 2);
+    assert_eq!(status.lldp_neighbors.len(), 1);
+
+    // Nodes without a collector are skipped
+    let plain = Node::new(
+        "edge-01".to_string(),
+        "example.com".to_string(),
+        Vendor::Cisco,
+        DeviceRole::Router,
+    );
+    let skipped = collect_node(
+        &store,
+        &transport,
+        &plain,
+        ("unet", "secret"),
+        AddressFamilyPreference::default(),
+    )
+    .await
+    .unwrap();
+    assert!(skipped.is_none());
+}
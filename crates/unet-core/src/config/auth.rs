@@ -0,0 +1,86 @@
+//! API authentication configuration
+
+use serde::{Deserialize, Serialize};
+
+/// Authentication configuration for API bearer-token enforcement.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthConfig {
+    /// Authentication enabled
+    pub enabled: bool,
+    /// Static bearer token accepted by the server when auth is enabled
+    pub token: Option<String>,
+    /// Bearer token that also grants the admin role; admin endpoints are
+    /// refused when auth is enabled and this is unset
+    #[serde(default)]
+    pub admin_token: Option<String>,
+    /// `OpenID` Connect login; ID tokens from the issuer are accepted as bearer tokens
+    #[serde(default)]
+    pub oidc: Option<OidcConfig>,
+    /// LDAP bind verification for HTTP Basic credentials
+    #[serde(default)]
+    pub ldap: Option<LdapConfig>,
+    /// Maps OIDC and LDAP groups to API roles
+    #[serde(default)]
+    pub group_roles: GroupRoleConfig,
+}
+
+/// `OpenID` Connect authorization code flow settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OidcConfig {
+    /// Issuer URL; its discovery document supplies the endpoints and JWKS
+    pub issuer: String,
+    /// Client ID registered with the issuer, also the expected token audience
+    pub client_id: String,
+    /// Client secret used when exchanging authorization codes
+    #[serde(default)]
+    pub client_secret: Option<String>,
+    /// Callback URL registered with the issuer
+    pub redirect_url: String,
+    /// Scopes requested at login
+    #[serde(default = "default_oidc_scopes")]
+    pub scopes: Vec<String>,
+    /// ID token claim listing the user's groups
+    #[serde(default = "default_oidc_groups_claim")]
+    pub groups_claim: String,
+}
+
+/// LDAP bind verification settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LdapConfig {
+    /// Server URL, e.g. `ldaps://ldap.example.com`
+    pub url: String,
+    /// DN bound as the user; `{username}` is replaced with the escaped username
+    pub bind_dn: String,
+    /// Attribute of the user entry listing their groups
+    #[serde(default = "default_ldap_group_attribute")]
+    pub group_attribute: String,
+}
+
+/// Groups granting each API role
+///
+/// Entries match a group by its exact name or full DN, ignoring case, so
+/// LDAP groups are listed by DN. Admin wins when a user is in groups for
+/// both roles.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GroupRoleConfig {
+    /// Groups granting the admin role
+    pub admin: Vec<String>,
+    /// Groups granting the operator role
+    pub operator: Vec<String>,
+}
+
+fn default_oidc_scopes() -> Vec<String> {
+    crate::config::defaults::auth::DEFAULT_OIDC_SCOPES
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+fn default_oidc_groups_claim() -> String {
+    crate::config::defaults::auth::DEFAULT_OIDC_GROUPS_CLAIM.to_string()
+}
+
+fn default_ldap_group_attribute() -> String {
+    crate::config::defaults::auth::DEFAULT_LDAP_GROUP_ATTRIBUTE.to_string()
+}
@@ -1,13 +1,23 @@
 //! Core configuration structure and implementations
 
 use crate::error::{Error, Result};
+use crate::models::AddressFamilyPreference;
+use crate::secrets::{ENCRYPTED_PREFIX, SecretManager};
+use crate::snmp::OidAccessList;
+use crate::snmp::config::{DEFAULT_MAX_MESSAGE_SIZE, MIN_MAX_MESSAGE_SIZE};
 use config::{Config as ConfigBuilder, File};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
 use std::net::SocketAddr;
 use std::path::Path;
 
+use super::auth::{AuthConfig, GroupRoleConfig};
+use super::server::ServerConfig;
 use super::types::{
-    AuthConfig, DatabaseConfig, DomainConfig, GitConfig, LoggingConfig, ServerConfig, SnmpConfig,
+    ChangeLogConfig, CollectorsConfig, DatabaseConfig, DomainConfig, GitConfig, IntegrityConfig,
+    LoggingConfig, MeasurementConfig, OnboardingConfig, PolicyConfig, RetentionConfig,
+    SecretsConfig, ShardingConfig, SnmpConfig, SnmpPollingConfig,
 };
 use super::{defaults, env};
 
@@ -28,6 +38,30 @@ pub struct Config {
     pub domain: DomainConfig,
     /// Authentication configuration settings
     pub auth: AuthConfig,
+    /// Link measurement configuration settings
+    #[serde(default)]
+    pub measurement: MeasurementConfig,
+    /// HTTP derived-state collector configuration settings
+    #[serde(default)]
+    pub collectors: CollectorsConfig,
+    /// Secrets configuration settings
+    #[serde(default)]
+    pub secrets: SecretsConfig,
+    /// Change log configuration settings
+    #[serde(default)]
+    pub change_log: ChangeLogConfig,
+    /// Node onboarding configuration settings
+    #[serde(default)]
+    pub onboarding: OnboardingConfig,
+    /// Data retention configuration settings
+    #[serde(default)]
+    pub retention: RetentionConfig,
+    /// Integrity check configuration settings
+    #[serde(default)]
+    pub integrity: IntegrityConfig,
+    /// Policy evaluation configuration settings
+    #[serde(default)]
+    pub policy: PolicyConfig,
 }
 
 impl Config {
@@ -131,6 +165,11 @@ impl Config {
         self.validate_server()?;
         self.validate_git()?;
         self.validate_auth()?;
+        self.validate_secrets()?;
+        self.validate_retention()?;
+        self.validate_integrity()?;
+        self.validate_collectors()?;
+        self.validate_snmp()?;
         Ok(())
     }
 
@@ -140,6 +179,43 @@ impl Config {
         &self.database.url
     }
 
+    /// Returns the `SQLCipher` key of the database, if one is configured
+    ///
+    /// A key sealed with `unet secrets seal` is decrypted with
+    /// `secrets.master_key`; a plaintext key is only returned when
+    /// `database.allow_plaintext_key` is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key is in plaintext without
+    /// `allow_plaintext_key`, or cannot be decrypted with the master key.
+    pub fn database_encryption_key(&self) -> Result<Option<String>> {
+        let Some(key) = &self.database.encryption_key else {
+            return Ok(None);
+        };
+        if !key.starts_with(ENCRYPTED_PREFIX) {
+            if self.database.allow_plaintext_key {
+                return Ok(Some(key.clone()));
+            }
+            return Err(Error::config(
+                "Database encryption_key must be sealed with `unet secrets seal`, \
+                 or database.allow_plaintext_key set",
+            ));
+        }
+        let master_key = self.secrets.master_key.as_deref().ok_or_else(|| {
+            Error::config("A sealed database encryption_key requires secrets.master_key")
+        })?;
+        let opened = SecretManager::new(master_key)
+            .and_then(|manager| manager.decrypt(&Value::String(key.clone())))
+            .map_err(|e| Error::config(format!("Database encryption_key: {e}")))?;
+        match opened {
+            Value::String(key) if !key.trim().is_empty() => Ok(Some(key)),
+            _ => Err(Error::config(
+                "Database encryption_key must seal a non-empty string",
+            )),
+        }
+    }
+
     /// Returns the server socket address
     ///
     /// # Errors
@@ -167,6 +243,27 @@ impl Config {
                 return Err(Error::config("Database timeout must be greater than 0"));
             }
         }
+        if let Some(key) = &self.database.encryption_key {
+            if key.trim().is_empty() {
+                return Err(Error::config("Database encryption_key cannot be empty"));
+            }
+            if !self.database.url.starts_with("sqlite:") {
+                return Err(Error::config(
+                    "Database encryption_key is only supported for SQLite URLs",
+                ));
+            }
+            if !key.starts_with(ENCRYPTED_PREFIX) && !self.database.allow_plaintext_key {
+                return Err(Error::config(
+                    "Database encryption_key must be sealed with `unet secrets seal`, \
+                     or database.allow_plaintext_key set",
+                ));
+            }
+            if key.starts_with(ENCRYPTED_PREFIX) && self.secrets.master_key.is_none() {
+                return Err(Error::config(
+                    "A sealed database encryption_key requires secrets.master_key",
+                ));
+            }
+        }
         Ok(())
     }
 
@@ -182,6 +279,17 @@ impl Config {
                 "Server max_request_size must be greater than 0",
             ));
         }
+        let classes = self.server.body_limits.classes();
+        if let Some((class, _)) = classes.iter().find(|(_, limit)| *limit == Some(0)) {
+            return Err(Error::config(format!(
+                "Server body_limits.{class} must be greater than 0"
+            )));
+        }
+        if self.server.attachments.max_size == 0 {
+            return Err(Error::config(
+                "Server attachments.max_size must be greater than 0",
+            ));
+        }
         self.socket_addr()?;
         Ok(())
     }
@@ -198,6 +306,8 @@ impl Config {
 
     fn validate_auth(&self) -> Result<()> {
         if self.auth.enabled
+            && self.auth.oidc.is_none()
+            && self.auth.ldap.is_none()
             && self
                 .auth
                 .token
@@ -206,6 +316,120 @@ impl Config {
         {
             return Err(Error::config("Auth token must be set when auth is enabled"));
         }
+        if let Some(admin_token) = self.auth.admin_token.as_deref() {
+            if admin_token.trim().is_empty() {
+                return Err(Error::config("Auth admin_token must not be empty"));
+            }
+            if self.auth.token.as_deref() == Some(admin_token) {
+                return Err(Error::config(
+                    "Auth admin_token must differ from the regular token",
+                ));
+            }
+        }
+        if let Some(oidc) = &self.auth.oidc {
+            if oidc.issuer.trim().is_empty() || oidc.client_id.trim().is_empty() {
+                return Err(Error::config("Auth oidc issuer and client_id must be set"));
+            }
+        }
+        if let Some(ldap) = &self.auth.ldap {
+            if !ldap.bind_dn.contains("{username}") {
+                return Err(Error::config(
+                    "Auth ldap bind_dn must contain the {username} placeholder",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_secrets(&self) -> Result<()> {
+        if self
+            .secrets
+            .master_key
+            .as_deref()
+            .is_some_and(|key| key.trim().is_empty())
+        {
+            return Err(Error::config("Secrets master_key cannot be empty"));
+        }
+        if !self.secrets.encrypted_fields.is_empty() && self.secrets.master_key.is_none() {
+            return Err(Error::config(
+                "Secrets encrypted_fields requires secrets.master_key",
+            ));
+        }
+        if let Some(path) = self
+            .secrets
+            .encrypted_fields
+            .iter()
+            .find(|path| path.split('.').any(str::is_empty))
+        {
+            return Err(Error::config(format!(
+                "Secrets encrypted_fields entry '{path}' is not a dotted custom_data path"
+            )));
+        }
+        Ok(())
+    }
+
+    fn validate_retention(&self) -> Result<()> {
+        let retention = &self.retention;
+        if retention.interval == 0 {
+            return Err(Error::config("Retention interval must be greater than 0"));
+        }
+        let days = [
+            ("raw_metrics_days", retention.raw_metrics_days),
+            ("rollups_days", retention.rollups_days),
+            ("audit_days", retention.audit_days),
+        ];
+        if let Some((class, _)) = days.iter().find(|(_, days)| *days == 0) {
+            return Err(Error::config(format!(
+                "Retention {class} must be greater than 0"
+            )));
+        }
+        if retention.config_snapshots == 0 {
+            return Err(Error::config(
+                "Retention config_snapshots must be greater than 0",
+            ));
+        }
+        Ok(())
+    }
+
+    fn validate_integrity(&self) -> Result<()> {
+        if self.integrity.interval == 0 {
+            return Err(Error::config("Integrity interval must be greater than 0"));
+        }
+        Ok(())
+    }
+
+    fn validate_collectors(&self) -> Result<()> {
+        let collectors = &self.collectors;
+        if collectors.interval == 0 {
+            return Err(Error::config("Collectors interval must be greater than 0"));
+        }
+        if collectors.timeout == 0 {
+            return Err(Error::config("Collectors timeout must be greater than 0"));
+        }
+        if collectors.max_concurrent == 0 {
+            return Err(Error::config(
+                "Collectors max_concurrent must be greater than 0",
+            ));
+        }
+        if collectors.enabled && collectors.username.is_none() {
+            return Err(Error::config(
+                "Collectors username must be set when collectors are enabled",
+            ));
+        }
+        Ok(())
+    }
+
+    fn validate_snmp(&self) -> Result<()> {
+        if let Some(prefix) = self.snmp.oid_access.invalid_prefix() {
+            return Err(Error::config(format!(
+                "SNMP oid_access prefix '{prefix}' must be a dotted numeric OID"
+            )));
+        }
+        if self.snmp.max_message_size < MIN_MAX_MESSAGE_SIZE {
+            return Err(Error::config(format!(
+                "SNMP max_message_size must be at least {MIN_MAX_MESSAGE_SIZE} bytes"
+            )));
+        }
         Ok(())
     }
 }
@@ -217,6 +441,8 @@ impl Default for Config {
                 url: defaults::database::DEFAULT_DATABASE_URL.to_string(),
                 max_connections: Some(defaults::database::DEFAULT_DB_MAX_CONNECTIONS),
                 timeout: Some(defaults::database::DEFAULT_DB_TIMEOUT_SECONDS),
+                encryption_key: None,
+                allow_plaintext_key: false,
             },
             logging: LoggingConfig {
                 level: defaults::logging::DEFAULT_LOG_LEVEL.to_string(),
@@ -227,6 +453,12 @@ impl Default for Config {
                 community: defaults::snmp::DEFAULT_SNMP_COMMUNITY.to_string(),
                 timeout: defaults::snmp::DEFAULT_SNMP_TIMEOUT_SECONDS,
                 retries: defaults::snmp::DEFAULT_SNMP_RETRIES,
+                address_family: AddressFamilyPreference::default(),
+                profiles: BTreeMap::new(),
+                polling: SnmpPollingConfig::default(),
+                sharding: ShardingConfig::default(),
+                oid_access: OidAccessList::default(),
+                max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
             },
             server: ServerConfig::default(),
             git: GitConfig {
@@ -245,7 +477,19 @@ impl Default for Config {
             auth: AuthConfig {
                 enabled: false,
                 token: None,
+                admin_token: None,
+                oidc: None,
+                ldap: None,
+                group_roles: GroupRoleConfig::default(),
             },
+            measurement: MeasurementConfig::default(),
+            collectors: CollectorsConfig::default(),
+            secrets: SecretsConfig::default(),
+            change_log: ChangeLogConfig::default(),
+            onboarding: OnboardingConfig::default(),
+            retention: RetentionConfig::default(),
+            integrity: IntegrityConfig::default(),
+            policy: PolicyConfig::default(),
         }
     }
 }
@@ -140,6 +140,26 @@ fn test_collect_env_vars_includes_auth_token() {
     );
 }
 
+#[test]
+fn test_collect_env_vars_includes_database_encryption_key() {
+    let mut env_vars = HashMap::new();
+    env_vars.insert("UNET_DATABASE__ENCRYPTION_KEY", "db-secret");
+
+    let env_source = |key: &str| {
+        env_vars
+            .get(key)
+            .map(|v| (*v).to_string())
+            .ok_or(env::VarError::NotPresent)
+    };
+
+    let vars = collect_env_vars(&env_source);
+    let vars_map: HashMap<String, String> = vars.into_iter().collect();
+    assert_eq!(
+        vars_map.get("database.encryption_key"),
+        Some(&"db-secret".to_string())
+    );
+}
+
 #[test]
 fn test_config_from_env_with_cors_lists() {
     let mut env_vars = HashMap::new();
@@ -1,6 +1,7 @@
 //! Tests for configuration file loading and saving
 
 use super::super::core::Config;
+use crate::models::AddressFamilyPreference;
 use tempfile::NamedTempFile;
 
 #[test]
@@ -27,6 +28,7 @@ cors_headers = ["authorization", "content-type"]
 community = "test"
 timeout = 10
 retries = 5
+address_family = "prefer-ipv6"
 
 [git]
 branch = "develop"
@@ -71,6 +73,10 @@ token = "bed-24-secret"
     assert_eq!(config.snmp.community, "test");
     assert_eq!(config.snmp.timeout, 10);
     assert_eq!(config.snmp.retries, 5);
+    assert_eq!(
+        config.snmp.address_family,
+        AddressFamilyPreference::PreferIpv6
+    );
 
     assert_eq!(config.git.branch, "develop");
     assert_eq!(config.git.sync_interval, 600);
@@ -1,6 +1,7 @@
 //! Tests for configuration validation
 
 use super::super::core::Config;
+use super::super::types::LdapConfig;
 
 #[test]
 fn test_config_validate_valid_config() {
@@ -49,6 +50,71 @@ fn test_config_validate_zero_database_timeout() {
     );
 }
 
+#[test]
+fn test_config_validate_empty_database_encryption_key() {
+    let mut config = Config::default();
+    config.database.encryption_key = Some("  ".to_string());
+
+    let error = config.validate().unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains("Database encryption_key cannot be empty")
+    );
+}
+
+#[test]
+fn test_config_validate_encryption_key_requires_sqlite() {
+    let mut config = Config::default();
+    config.database.url = "postgres://localhost/unet".to_string();
+    config.database.encryption_key = Some("secret".to_string());
+
+    let error = config.validate().unwrap_err();
+    assert!(error.to_string().contains("only supported for SQLite"));
+}
+
+#[test]
+fn test_config_validate_plaintext_encryption_key_requires_opt_in() {
+    let mut config = Config::default();
+    config.database.encryption_key = Some("change-me".to_string());
+
+    let error = config.validate().unwrap_err();
+    assert!(error.to_string().contains("unet secrets seal"));
+    assert!(config.database_encryption_key().is_err());
+
+    config.database.allow_plaintext_key = true;
+    assert!(config.validate().is_ok());
+    assert_eq!(
+        config.database_encryption_key().unwrap().as_deref(),
+        Some("change-me")
+    );
+}
+
+#[cfg(feature = "secrets")]
+#[test]
+fn test_database_encryption_key_opens_sealed_key() {
+    use crate::secrets::SecretManager;
+
+    let manager = SecretManager::new("master").unwrap();
+    let sealed = manager
+        .encrypt(&serde_json::Value::String("change-me".to_string()))
+        .unwrap();
+    let mut config = Config::default();
+    config.database.encryption_key = sealed.as_str().map(str::to_string);
+    // The master key is needed to open it
+    assert!(config.validate().is_err());
+    assert!(config.database_encryption_key().is_err());
+
+    config.secrets.master_key = Some("master".to_string());
+    assert!(config.validate().is_ok());
+    assert_eq!(
+        config.database_encryption_key().unwrap().as_deref(),
+        Some("change-me")
+    );
+    config.secrets.master_key = Some("other".to_string());
+    assert!(config.database_encryption_key().is_err());
+}
+
 #[test]
 fn test_config_validate_empty_server_host() {
     let mut config = Config::default();
@@ -90,6 +156,34 @@ fn test_config_validate_zero_max_request_size() {
     );
 }
 
+#[test]
+fn test_config_validate_zero_body_limit() {
+    let mut config = Config::default();
+    config.server.body_limits.nodes = Some(4 * 1024 * 1024);
+    assert!(config.validate().is_ok());
+
+    config.server.body_limits.policies = Some(0);
+    let error = config.validate().unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains("Server body_limits.policies must be greater than 0")
+    );
+}
+
+#[test]
+fn test_config_validate_zero_attachment_size() {
+    let mut config = Config::default();
+    config.server.attachments.max_size = 0;
+
+    let error = config.validate().unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains("Server attachments.max_size must be greater than 0")
+    );
+}
+
 #[test]
 fn test_config_validate_invalid_server_address() {
     let mut config = Config::default();
@@ -141,3 +235,132 @@ fn test_config_validate_auth_enabled_without_token() {
             .contains("Auth token must be set when auth is enabled")
     );
 }
+
+#[test]
+fn test_config_validate_auth_admin_token() {
+    let mut config = Config::default();
+    config.auth.enabled = true;
+    config.auth.token = Some("operator".to_string());
+    config.auth.admin_token = Some("admin".to_string());
+    assert!(config.validate().is_ok());
+
+    config.auth.admin_token = Some("  ".to_string());
+    let error = config.validate().unwrap_err();
+    assert!(error.to_string().contains("admin_token must not be empty"));
+
+    config.auth.admin_token = Some("operator".to_string());
+    let error = config.validate().unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains("admin_token must differ from the regular token")
+    );
+}
+
+#[test]
+fn test_config_validate_auth_providers() {
+    let mut config = Config::default();
+    config.auth.enabled = true;
+    config.auth.ldap = Some(LdapConfig {
+        url: "ldaps://ldap.example.com".to_string(),
+        bind_dn: "uid={username},ou=people,dc=example,dc=com".to_string(),
+        group_attribute: "memberOf".to_string(),
+    });
+    assert!(config.validate().is_ok());
+
+    config.auth.ldap.as_mut().unwrap().bind_dn = "ou=people,dc=example,dc=com".to_string();
+    let error = config.validate().unwrap_err();
+    assert!(error.to_string().contains("{username} placeholder"));
+}
+
+#[test]
+fn test_config_validate_secrets() {
+    let mut config = Config::default();
+    config.secrets.encrypted_fields = vec!["snmp.community".to_string()];
+    let error = config.validate().unwrap_err();
+    assert!(error.to_string().contains("requires secrets.master_key"));
+
+    config.secrets.master_key = Some("master".to_string());
+    assert!(config.validate().is_ok());
+
+    config
+        .secrets
+        .encrypted_fields
+        .push("snmp..community".to_string());
+    let error = config.validate().unwrap_err();
+    assert!(error.to_string().contains("'snmp..community'"));
+}
+
+#[test]
+fn test_config_validate_retention() {
+    let mut config = Config::default();
+    assert_eq!(config.retention.raw_metrics_days, 7);
+    assert_eq!(config.retention.config_snapshots, 50);
+
+    config.retention.audit_days = 0;
+    let error = config.validate().unwrap_err();
+    assert!(error.to_string().contains("Retention audit_days"));
+
+    config.retention.audit_days = 30;
+    config.retention.config_snapshots = 0;
+    let error = config.validate().unwrap_err();
+    assert!(error.to_string().contains("config_snapshots"));
+}
+
+#[test]
+fn test_config_validate_integrity() {
+    let mut config = Config::default();
+    assert!(!config.integrity.enabled);
+    assert!(config.integrity.repair.is_empty());
+
+    config.integrity.interval = 0;
+    let error = config.validate().unwrap_err();
+    assert!(error.to_string().contains("Integrity interval"));
+}
+
+#[test]
+fn test_config_validate_collectors() {
+    let mut config = Config::default();
+    config.collectors.enabled = true;
+    let error = config.validate().unwrap_err();
+    assert!(error.to_string().contains("Collectors username"));
+
+    config.collectors.username = Some("unet".to_string());
+    assert!(config.validate().is_ok());
+
+    config.collectors.timeout = 0;
+    let error = config.validate().unwrap_err();
+    assert!(error.to_string().contains("Collectors timeout"));
+
+    config.collectors.timeout = 10;
+    config.collectors.max_concurrent = 0;
+    let error = config.validate().unwrap_err();
+    assert!(error.to_string().contains("Collectors max_concurrent"));
+}
+
+#[test]
+fn test_config_validate_snmp_oid_access() {
+    let mut config = Config::default();
+    config.snmp.oid_access.deny = vec!["1.3.6.1.4.1.9.9.96".to_string()];
+    assert!(config.validate().is_ok());
+
+    config.snmp.oid_access.allow = vec!["mib-2".to_string()];
+    let error = config.validate().unwrap_err();
+    assert!(error.to_string().contains("'mib-2'"));
+}
+
+#[test]
+fn test_config_validate_snmp_max_message_size() {
+    let mut config = Config::default();
+    config.snmp.max_message_size = 484;
+    assert!(config.validate().is_ok());
+    assert_eq!(
+        config.snmp.client_config().default_session.max_message_size,
+        484
+    );
+    assert_eq!(config.snmp.session_config().max_message_size, 484);
+
+    config.snmp.max_message_size = 483;
+    let error = config.validate().unwrap_err();
+    assert!(error.to_string().contains("max_message_size"));
+}
@@ -42,6 +42,8 @@ pub mod snmp {
     pub const MAX_SNMP_TIMEOUT_SECONDS: u64 = 60;
     /// Maximum allowed SNMP retries
     pub const MAX_SNMP_RETRIES: u8 = 10;
+    /// Default seconds between rebuilding polling tasks from OID profiles
+    pub const DEFAULT_POLLING_SYNC_INTERVAL_SECONDS: u64 = 300;
 }
 
 /// Server configuration constants
@@ -90,6 +92,30 @@ pub mod server {
             .map(ToString::to_string)
             .collect()
     }
+
+    /// Responses are compressed unless disabled.
+    #[must_use]
+    pub const fn default_compression() -> bool {
+        true
+    }
+
+    /// Responses smaller than 1KB are sent uncompressed.
+    #[must_use]
+    pub const fn default_compression_min_size() -> u16 {
+        1024
+    }
+
+    /// Enrichment plugins listed in the configuration run unless disabled.
+    #[must_use]
+    pub const fn default_enrichment_enabled() -> bool {
+        true
+    }
+
+    /// The topology cache is reloaded every 5 minutes.
+    #[must_use]
+    pub const fn default_topology_cache_max_age() -> u64 {
+        super::cache::DEFAULT_CACHE_TTL_SECONDS
+    }
 }
 
 /// Performance tuning constants
@@ -129,3 +155,99 @@ pub mod git {
     /// Default sync interval in seconds (5 minutes)
     pub const DEFAULT_SYNC_INTERVAL_SECONDS: u64 = 300;
 }
+
+/// Link measurement configuration constants
+pub mod measurement {
+    /// Default interval between measurement runs in seconds
+    pub const DEFAULT_MEASUREMENT_INTERVAL_SECONDS: u64 = 300;
+    /// Default number of probes sent to each link endpoint per run
+    pub const DEFAULT_PROBE_COUNT: u32 = 5;
+    /// Default number of measurements kept per link
+    pub const DEFAULT_HISTORY_SAMPLES: usize = 288;
+}
+
+/// HTTP collector configuration constants
+pub mod collectors {
+    /// Default interval between collection runs in seconds
+    pub const DEFAULT_COLLECTION_INTERVAL_SECONDS: u64 = 300;
+    /// Default timeout for a single device request in seconds
+    pub const DEFAULT_REQUEST_TIMEOUT_SECONDS: u64 = 10;
+    /// Default number of on-demand polls collecting at once
+    pub const DEFAULT_MAX_CONCURRENT: usize = 4;
+}
+
+/// Attachment configuration constants
+pub mod attachments {
+    /// Default largest attachment in bytes (1 MiB)
+    pub const DEFAULT_MAX_ATTACHMENT_SIZE: usize = 1024 * 1024;
+    /// Default media types attachments may have
+    pub const DEFAULT_ALLOWED_TYPES: &[&str] = &[
+        "text/plain",
+        "text/markdown",
+        "text/csv",
+        "application/json",
+        "application/pdf",
+        "image/png",
+        "image/jpeg",
+    ];
+
+    /// Attachments up to 1 MiB are accepted.
+    #[must_use]
+    pub const fn default_max_size() -> usize {
+        DEFAULT_MAX_ATTACHMENT_SIZE
+    }
+
+    /// Default allowed attachment media types.
+    #[must_use]
+    pub fn default_allowed_types() -> Vec<String> {
+        DEFAULT_ALLOWED_TYPES
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+}
+
+/// Polling shard configuration constants
+pub mod sharding {
+    /// Default number of shards nodes are split into
+    pub const DEFAULT_SHARD_COUNT: u32 = 16;
+    /// Default lease lifetime in seconds
+    pub const DEFAULT_LEASE_TTL_SECONDS: u64 = 60;
+}
+
+/// Data retention configuration constants
+pub mod retention {
+    /// Default interval between pruning runs in seconds (1 hour)
+    pub const DEFAULT_PRUNE_INTERVAL_SECONDS: u64 = 3600;
+    /// Default days raw performance samples are kept
+    pub const DEFAULT_RAW_METRICS_DAYS: u32 = 7;
+    /// Default days hourly performance rollups are kept
+    pub const DEFAULT_ROLLUPS_DAYS: u32 = 365;
+    /// Default days change log events are kept (3 years)
+    pub const DEFAULT_AUDIT_DAYS: u32 = 1095;
+    /// Default configuration snapshots kept per node
+    pub const DEFAULT_CONFIG_SNAPSHOTS: usize = 50;
+}
+
+/// Integrity check configuration constants
+pub mod integrity {
+    /// Default interval between integrity checks in seconds (1 day)
+    pub const DEFAULT_CHECK_INTERVAL_SECONDS: u64 = 86_400;
+}
+
+/// Policy evaluation configuration constants
+pub mod policy {
+    /// Default wait after a node changes before evaluating its policies in
+    /// milliseconds
+    pub const DEFAULT_DEBOUNCE_MS: u64 = 2_000;
+}
+
+/// Authentication provider configuration constants
+pub mod auth {
+    /// Default ID token claim listing the user's groups
+    pub const DEFAULT_OIDC_GROUPS_CLAIM: &str = "groups";
+    /// Default scopes requested during OIDC login
+    pub const DEFAULT_OIDC_SCOPES: [&str; 3] = ["openid", "profile", "email"];
+    /// Default LDAP attribute listing the user's groups
+    pub const DEFAULT_LDAP_GROUP_ATTRIBUTE: &str = "memberOf";
+}
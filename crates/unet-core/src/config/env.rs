@@ -3,19 +3,33 @@
 use crate::error::{Error, Result};
 use config::builder::{ConfigBuilder, DefaultState};
 
-const SCALAR_ENV_VARS: [(&str, &str); 20] = [
+const SCALAR_ENV_VARS: [(&str, &str); 38] = [
     ("UNET_DATABASE__URL", "database.url"),
     ("UNET_DATABASE__MAX_CONNECTIONS", "database.max_connections"),
     ("UNET_DATABASE__TIMEOUT", "database.timeout"),
+    ("UNET_DATABASE__ENCRYPTION_KEY", "database.encryption_key"),
+    (
+        "UNET_DATABASE__ALLOW_PLAINTEXT_KEY",
+        "database.allow_plaintext_key",
+    ),
     ("UNET_LOGGING__LEVEL", "logging.level"),
     ("UNET_LOGGING__FORMAT", "logging.format"),
     ("UNET_LOGGING__FILE", "logging.file"),
     ("UNET_SNMP__COMMUNITY", "snmp.community"),
     ("UNET_SNMP__TIMEOUT", "snmp.timeout"),
     ("UNET_SNMP__RETRIES", "snmp.retries"),
+    ("UNET_SNMP__ADDRESS_FAMILY", "snmp.address_family"),
+    ("UNET_SNMP__MAX_MESSAGE_SIZE", "snmp.max_message_size"),
+    ("UNET_SNMP__SHARDING__ENABLED", "snmp.sharding.enabled"),
+    (
+        "UNET_SNMP__SHARDING__COLLECTOR_ID",
+        "snmp.sharding.collector_id",
+    ),
     ("UNET_SERVER__HOST", "server.host"),
     ("UNET_SERVER__PORT", "server.port"),
     ("UNET_SERVER__MAX_REQUEST_SIZE", "server.max_request_size"),
+    ("UNET_SERVER__COMPRESSION", "server.compression"),
+    ("UNET_SERVER__UI", "server.ui"),
     ("UNET_GIT__REPOSITORY_URL", "git.repository_url"),
     ("UNET_GIT__LOCAL_DIRECTORY", "git.local_directory"),
     ("UNET_GIT__BRANCH", "git.branch"),
@@ -24,13 +38,27 @@ const SCALAR_ENV_VARS: [(&str, &str); 20] = [
     ("UNET_DOMAIN__DEFAULT_DOMAIN", "domain.default_domain"),
     ("UNET_AUTH__ENABLED", "auth.enabled"),
     ("UNET_AUTH__TOKEN", "auth.token"),
+    ("UNET_AUTH__ADMIN_TOKEN", "auth.admin_token"),
+    ("UNET_SECRETS__MASTER_KEY", "secrets.master_key"),
+    ("UNET_CHANGE_LOG__ENABLED", "change_log.enabled"),
+    ("UNET_RETENTION__ENABLED", "retention.enabled"),
+    ("UNET_INTEGRITY__ENABLED", "integrity.enabled"),
+    (
+        "UNET_POLICY__EVALUATE_ON_CHANGE",
+        "policy.evaluate_on_change",
+    ),
+    ("UNET_POLICY__DEBOUNCE_MS", "policy.debounce_ms"),
+    ("UNET_COLLECTORS__ENABLED", "collectors.enabled"),
+    ("UNET_COLLECTORS__USERNAME", "collectors.username"),
+    ("UNET_COLLECTORS__PASSWORD", "collectors.password"),
 ];
 
-const LIST_ENV_VARS: [(&str, &str); 4] = [
+const LIST_ENV_VARS: [(&str, &str); 5] = [
     ("UNET_DOMAIN__SEARCH_DOMAINS", "domain.search_domains"),
     ("UNET_SERVER__CORS_ORIGINS", "server.cors_origins"),
     ("UNET_SERVER__CORS_METHODS", "server.cors_methods"),
     ("UNET_SERVER__CORS_HEADERS", "server.cors_headers"),
+    ("UNET_SECRETS__ENCRYPTED_FIELDS", "secrets.encrypted_fields"),
 ];
 
 pub fn apply_env_overrides<F>(
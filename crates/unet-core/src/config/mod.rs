@@ -4,9 +4,11 @@
 //! configuration support and environment variable overrides.
 
 // Re-export submodules
+pub mod auth;
 pub mod core;
 pub mod defaults;
 pub mod network;
+pub mod server;
 pub mod types;
 pub mod utils;
 
@@ -15,9 +17,11 @@ mod core_tests;
 mod env;
 
 // Re-export the main Config struct and commonly used items
+pub use auth::*;
 pub use core::Config;
 pub use defaults::*;
 pub use network::*;
+pub use server::*;
 pub use types::*;
 
 // Re-export specific constants for backward compatibility
@@ -1,12 +1,16 @@
 //! Network configuration utilities
 
 use crate::error::{Error, Result};
+use crate::models::ScopedIpAddr;
+use crate::models::node::management::socket_addr;
 use std::net::{IpAddr, SocketAddr};
 
 /// Parse a socket address with a default port if none is specified
 ///
 /// This function provides better error handling than the standard library's
 /// `parse().unwrap()` pattern and supports both IPv4 and IPv6 addresses.
+/// IPv6 addresses may be bare (`2001:db8::1`) or bracketed, and link-local
+/// addresses take a zone (`fe80::1%eth0`) that becomes the scope ID.
 ///
 /// # Arguments
 /// * `addr_str` - The address string to parse (e.g., "127.0.0.1:22" or "127.0.0.1")
@@ -33,24 +37,26 @@ pub fn parse_socket_addr_with_default_port(
     addr_str: &str,
     default_port: u16,
 ) -> Result<SocketAddr> {
-    // Check if address already contains a port
-    if addr_str.contains(':') && !addr_str.starts_with('[') {
-        // For IPv4 with port, or IPv6 with brackets and port
-        addr_str
-            .parse()
-            .map_err(|e| Error::config(format!("Invalid socket address '{addr_str}': {e}")))
-    } else if addr_str.starts_with('[') && addr_str.contains("]:") {
-        // IPv6 with brackets and port
-        addr_str
-            .parse()
-            .map_err(|e| Error::config(format!("Invalid IPv6 socket address '{addr_str}': {e}")))
-    } else {
-        // Address without port - add default port
-        let ip: IpAddr = addr_str
+    // "192.0.2.1:22" and "[2001:db8::1]:22" carry their own port
+    if let Ok(addr) = addr_str.parse::<SocketAddr>() {
+        return Ok(addr);
+    }
+    if addr_str.starts_with('[') && addr_str.contains("]:") {
+        // Bracketed IPv6 with a zone and a port, such as "[fe80::1%eth0]:22"
+        let (host, port) = addr_str.rsplit_once(':').unwrap_or((addr_str, ""));
+        let port = port
             .parse()
-            .map_err(|e| Error::config(format!("Invalid IP address '{addr_str}': {e}")))?;
-        Ok(SocketAddr::new(ip, default_port))
+            .map_err(|e| Error::config(format!("Invalid port in '{addr_str}': {e}")))?;
+        return scoped_socket_addr(host, port);
     }
+    // Bare address, including unbracketed IPv6 such as "2001:db8::1"
+    scoped_socket_addr(addr_str, default_port)
+}
+
+fn scoped_socket_addr(host: &str, port: u16) -> Result<SocketAddr> {
+    let scoped: ScopedIpAddr = host.parse().map_err(Error::config)?;
+    socket_addr(scoped.ip, scoped.zone.as_deref(), port)
+        .map_err(|e| Error::config(format!("Invalid socket address '{host}': {e}")))
 }
 
 /// Parse an IP address string
@@ -111,6 +117,25 @@ mod tests {
         assert!(parse_socket_addr_with_default_port("invalid", 161).is_err());
     }
 
+    #[test]
+    fn test_parse_socket_addr_with_default_port_ipv6() {
+        let addr = parse_socket_addr_with_default_port("2001:db8::1", 161).unwrap();
+        assert_eq!(addr.to_string(), "[2001:db8::1]:161");
+
+        let addr = parse_socket_addr_with_default_port("[2001:db8::1]:22", 161).unwrap();
+        assert_eq!(addr.port(), 22);
+
+        let addr = parse_socket_addr_with_default_port("fe80::1%3", 161).unwrap();
+        assert_eq!(addr.to_string(), "[fe80::1%3]:161");
+
+        let addr = parse_socket_addr_with_default_port("[fe80::1%3]:22", 161).unwrap();
+        assert_eq!(addr.to_string(), "[fe80::1%3]:22");
+
+        // Link-local addresses are ambiguous without a zone
+        assert!(parse_socket_addr_with_default_port("fe80::1", 161).is_err());
+        assert!(parse_socket_addr_with_default_port("192.0.2.1%eth0", 161).is_err());
+    }
+
     #[test]
     fn test_parse_ip_addr() {
         // Valid IPv4 address should parse correctly
@@ -0,0 +1,132 @@
+//! HTTP server configuration
+
+use serde::{Deserialize, Serialize};
+
+/// Server configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerConfig {
+    /// Server host address
+    pub host: String,
+    /// Server port
+    pub port: u16,
+    /// Maximum request size in bytes
+    pub max_request_size: usize,
+    /// Allowed CORS origins
+    #[serde(default = "crate::config::defaults::server::default_cors_origins")]
+    pub cors_origins: Vec<String>,
+    /// Allowed CORS methods
+    #[serde(default = "crate::config::defaults::server::default_cors_methods")]
+    pub cors_methods: Vec<String>,
+    /// Allowed CORS headers
+    #[serde(default = "crate::config::defaults::server::default_cors_headers")]
+    pub cors_headers: Vec<String>,
+    /// Request body limits for route classes that differ from `max_request_size`
+    #[serde(default)]
+    pub body_limits: BodyLimitsConfig,
+    /// Compress responses with gzip or brotli when the client accepts it
+    #[serde(default = "crate::config::defaults::server::default_compression")]
+    pub compression: bool,
+    /// Smallest response body in bytes that is compressed
+    #[serde(default = "crate::config::defaults::server::default_compression_min_size")]
+    pub compression_min_size: u16,
+    /// Derived-state enrichment plugins, run in this order after each poll
+    #[serde(default)]
+    pub enrichment: Vec<EnrichmentPluginConfig>,
+    /// Serve the bundled web UI under `/ui`; needs the server's `web-ui` feature
+    #[serde(default)]
+    pub ui: bool,
+    /// Seconds before the in-memory topology is reloaded even though change
+    /// events keep it current; 0 reloads it for every query
+    #[serde(default = "crate::config::defaults::server::default_topology_cache_max_age")]
+    pub topology_cache_max_age: u64,
+    /// Limits and storage of files attached to nodes, links, and locations
+    #[serde(default)]
+    pub attachments: AttachmentsConfig,
+}
+
+/// Request body limits in bytes per route class; unset classes use
+/// `server.max_request_size`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BodyLimitsConfig {
+    /// Node routes, whose bodies carry `custom_data`
+    #[serde(default)]
+    pub nodes: Option<usize>,
+    /// Policy routes, whose bodies carry rule text
+    #[serde(default)]
+    pub policies: Option<usize>,
+    /// Administration routes
+    #[serde(default)]
+    pub admin: Option<usize>,
+}
+
+impl BodyLimitsConfig {
+    /// Route class names with their configured limits
+    #[must_use]
+    pub const fn classes(&self) -> [(&'static str, Option<usize>); 3] {
+        [
+            ("nodes", self.nodes),
+            ("policies", self.policies),
+            ("admin", self.admin),
+        ]
+    }
+}
+
+/// Attachment limits and storage
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AttachmentsConfig {
+    /// Largest attachment in bytes
+    #[serde(default = "crate::config::defaults::attachments::default_max_size")]
+    pub max_size: usize,
+    /// Media types attachments may have, e.g. `image/png`
+    #[serde(default = "crate::config::defaults::attachments::default_allowed_types")]
+    pub allowed_types: Vec<String>,
+    /// Directory attachment contents are written to; unset keeps them in the
+    /// datastore
+    #[serde(default)]
+    pub blob_dir: Option<String>,
+}
+
+impl Default for AttachmentsConfig {
+    fn default() -> Self {
+        use crate::config::defaults::attachments;
+        Self {
+            max_size: attachments::DEFAULT_MAX_ATTACHMENT_SIZE,
+            allowed_types: attachments::default_allowed_types(),
+            blob_dir: None,
+        }
+    }
+}
+
+/// An enrichment plugin entry in the server configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnrichmentPluginConfig {
+    /// Name the plugin is registered under
+    pub name: String,
+    /// Whether the plugin runs
+    #[serde(default = "crate::config::defaults::server::default_enrichment_enabled")]
+    pub enabled: bool,
+    /// Plugin-specific options
+    #[serde(default)]
+    pub options: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            host: crate::config::defaults::server::DEFAULT_SERVER_HOST.to_string(),
+            port: crate::config::defaults::network::DEFAULT_SERVER_PORT,
+            max_request_size: crate::config::defaults::server::DEFAULT_MAX_REQUEST_SIZE,
+            cors_origins: crate::config::defaults::server::default_cors_origins(),
+            cors_methods: crate::config::defaults::server::default_cors_methods(),
+            cors_headers: crate::config::defaults::server::default_cors_headers(),
+            body_limits: BodyLimitsConfig::default(),
+            compression: crate::config::defaults::server::default_compression(),
+            compression_min_size: crate::config::defaults::server::default_compression_min_size(),
+            enrichment: Vec::new(),
+            ui: false,
+            topology_cache_max_age: crate::config::defaults::server::default_topology_cache_max_age(
+            ),
+            attachments: AttachmentsConfig::default(),
+        }
+    }
+}
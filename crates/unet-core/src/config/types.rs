@@ -1,6 +1,12 @@
 //! Configuration type definitions
 
+use crate::integrity::IssueKind;
+use crate::models::AddressFamilyPreference;
+use crate::snmp::config::default_max_message_size;
+use crate::snmp::{OidAccessList, SessionConfig, SnmpClientConfig, SnmpCredentials};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
 
 /// Database configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,6 +17,13 @@ pub struct DatabaseConfig {
     pub max_connections: Option<u32>,
     /// Database connection timeout in seconds
     pub timeout: Option<u64>,
+    /// `SQLCipher` key sealed with the secrets master key (`unet secrets
+    /// seal`); when set, the `SQLite` database is opened encrypted
+    #[serde(default)]
+    pub encryption_key: Option<String>,
+    /// Accept a plaintext `encryption_key` instead of a sealed one
+    #[serde(default)]
+    pub allow_plaintext_key: bool,
 }
 
 /// Logging configuration
@@ -33,37 +46,295 @@ pub struct SnmpConfig {
     pub timeout: u64,
     /// Number of retries for SNMP operations
     pub retries: u8,
+    /// Management address family to poll dual-stack nodes over
+    #[serde(default)]
+    pub address_family: AddressFamilyPreference,
+    /// Named credentials for reaching devices not yet in the inventory
+    #[serde(default)]
+    pub profiles: BTreeMap<String, SnmpCredentialProfile>,
+    /// Background polling of the inventory
+    #[serde(default)]
+    pub polling: SnmpPollingConfig,
+    /// Splitting polling between several collectors
+    #[serde(default)]
+    pub sharding: ShardingConfig,
+    /// OID prefixes that may and may not be requested from devices
+    #[serde(default)]
+    pub oid_access: OidAccessList,
+    /// Largest SNMP message, in bytes, a response is expected to fit; GETs
+    /// are split into requests whose estimated responses stay within it
+    #[serde(default = "default_max_message_size")]
+    pub max_message_size: usize,
 }
 
-/// Server configuration
+/// Background SNMP polling of every node with a management address
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ServerConfig {
-    /// Server host address
-    pub host: String,
-    /// Server port
-    pub port: u16,
-    /// Maximum request size in bytes
-    pub max_request_size: usize,
-    /// Allowed CORS origins
-    #[serde(default = "crate::config::defaults::server::default_cors_origins")]
-    pub cors_origins: Vec<String>,
-    /// Allowed CORS methods
-    #[serde(default = "crate::config::defaults::server::default_cors_methods")]
-    pub cors_methods: Vec<String>,
-    /// Allowed CORS headers
-    #[serde(default = "crate::config::defaults::server::default_cors_headers")]
-    pub cors_headers: Vec<String>,
-}
-
-impl Default for ServerConfig {
+#[serde(default)]
+pub struct SnmpPollingConfig {
+    /// Whether the server polls nodes over SNMP in the background
+    pub enabled: bool,
+    /// Seconds between rebuilding each node's polling tasks from its OID
+    /// profile
+    pub sync_interval: u64,
+}
+
+impl Default for SnmpPollingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            sync_interval: crate::config::defaults::snmp::DEFAULT_POLLING_SYNC_INTERVAL_SECONDS,
+        }
+    }
+}
+
+/// Polling shards claimed by collectors sharing the database
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShardingConfig {
+    /// Whether this server polls only the shards it holds a lease on
+    pub enabled: bool,
+    /// Number of shards nodes are split into; the same on every collector
+    pub shards: u32,
+    /// Seconds a lease lasts without renewal before another collector may
+    /// take it, at most a day
+    pub lease_ttl: u64,
+    /// Name of this collector; a random one is chosen at startup if unset
+    pub collector_id: Option<String>,
+}
+
+impl Default for ShardingConfig {
+    fn default() -> Self {
+        use crate::config::defaults::sharding;
+        Self {
+            enabled: false,
+            shards: sharding::DEFAULT_SHARD_COUNT,
+            lease_ttl: sharding::DEFAULT_LEASE_TTL_SECONDS,
+            collector_id: None,
+        }
+    }
+}
+
+/// SNMP credentials selected by name, e.g. with `--credential-profile`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnmpCredentialProfile {
+    /// SNMP community string
+    pub community: String,
+}
+
+impl SnmpConfig {
+    /// Community of a credential profile
+    ///
+    /// `default` resolves to `community` unless a profile of that name is
+    /// configured.
+    #[must_use]
+    pub fn profile_community(&self, name: &str) -> Option<&str> {
+        match self.profiles.get(name) {
+            Some(profile) => Some(profile.community.as_str()),
+            None if name == "default" => Some(self.community.as_str()),
+            None => None,
+        }
+    }
+
+    /// Client settings enforcing the configured OID access list and message
+    /// size
+    #[must_use]
+    pub fn client_config(&self) -> SnmpClientConfig {
+        let mut config = SnmpClientConfig {
+            oid_access: self.oid_access.clone(),
+            ..SnmpClientConfig::default()
+        };
+        config.default_session.max_message_size = self.max_message_size;
+        config
+    }
+
+    /// Session with the configured community, timeout, retries, and message
+    /// size
+    #[must_use]
+    pub fn session_config(&self) -> SessionConfig {
+        SessionConfig {
+            credentials: SnmpCredentials::Community {
+                community: self.community.clone(),
+            },
+            timeout: Duration::from_secs(self.timeout),
+            retries: u32::from(self.retries),
+            max_message_size: self.max_message_size,
+            ..SessionConfig::default()
+        }
+    }
+}
+
+/// Active link measurement configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MeasurementConfig {
+    /// Whether the server measures links in the background
+    pub enabled: bool,
+    /// Interval between measurement runs in seconds
+    pub interval: u64,
+    /// Number of probes sent to each link endpoint per run
+    pub probes: u32,
+    /// Number of measurements kept per link
+    pub history: usize,
+}
+
+impl Default for MeasurementConfig {
     fn default() -> Self {
+        use crate::config::defaults::measurement;
         Self {
-            host: crate::config::defaults::server::DEFAULT_SERVER_HOST.to_string(),
-            port: crate::config::defaults::network::DEFAULT_SERVER_PORT,
-            max_request_size: crate::config::defaults::server::DEFAULT_MAX_REQUEST_SIZE,
-            cors_origins: crate::config::defaults::server::default_cors_origins(),
-            cors_methods: crate::config::defaults::server::default_cors_methods(),
-            cors_headers: crate::config::defaults::server::default_cors_headers(),
+            enabled: false,
+            interval: measurement::DEFAULT_MEASUREMENT_INTERVAL_SECONDS,
+            probes: measurement::DEFAULT_PROBE_COUNT,
+            history: measurement::DEFAULT_HISTORY_SAMPLES,
+        }
+    }
+}
+
+/// HTTP derived-state collector configuration
+///
+/// Nodes opt in with `custom_data.collector`, see [`crate::collectors`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CollectorsConfig {
+    /// Whether the server runs HTTP collectors in the background
+    pub enabled: bool,
+    /// Interval between collection runs in seconds
+    pub interval: u64,
+    /// Timeout for a single device request in seconds
+    pub timeout: u64,
+    /// Most on-demand polls collecting at once; further polls wait for one
+    /// to finish
+    pub max_concurrent: usize,
+    /// Username for eAPI and RESTCONF requests
+    pub username: Option<String>,
+    /// Password for eAPI and RESTCONF requests
+    pub password: Option<String>,
+    /// Whether device TLS certificates are verified; disable only for
+    /// devices with self-signed certificates
+    pub verify_tls: bool,
+}
+
+impl Default for CollectorsConfig {
+    fn default() -> Self {
+        use crate::config::defaults::collectors;
+        Self {
+            enabled: false,
+            interval: collectors::DEFAULT_COLLECTION_INTERVAL_SECONDS,
+            timeout: collectors::DEFAULT_REQUEST_TIMEOUT_SECONDS,
+            max_concurrent: collectors::DEFAULT_MAX_CONCURRENT,
+            username: None,
+            password: None,
+            verify_tls: true,
+        }
+    }
+}
+
+/// Secrets configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SecretsConfig {
+    /// Master key `custom_data` fields are encrypted with; use a long random string
+    pub master_key: Option<String>,
+    /// Dotted `custom_data` paths stored encrypted, e.g. `snmp.community`
+    pub encrypted_fields: Vec<String>,
+}
+
+/// Node onboarding configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OnboardingConfig {
+    /// Rules mapping a device's `sysLocation` to a location, tried in order
+    pub location_rules: Vec<LocationRule>,
+}
+
+/// Places onboarded devices whose `sysLocation` matches `pattern`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocationRule {
+    /// Regular expression matched against `sysLocation`
+    pub pattern: String,
+    /// Location slug, name, path, or ID
+    pub location: String,
+}
+
+/// Change log configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChangeLogConfig {
+    /// Append an event to the change log for every node, link, and location change
+    pub enabled: bool,
+}
+
+/// How long each class of collected data is kept
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionConfig {
+    /// Whether the server prunes data past its retention in the background
+    pub enabled: bool,
+    /// Interval between pruning runs in seconds
+    pub interval: u64,
+    /// Days raw performance samples are kept before being compacted into
+    /// hourly rollups
+    pub raw_metrics_days: u32,
+    /// Days hourly performance rollups are kept
+    pub rollups_days: u32,
+    /// Days change log events are kept
+    pub audit_days: u32,
+    /// Configuration snapshots kept per node
+    pub config_snapshots: usize,
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        use crate::config::defaults::retention;
+        Self {
+            enabled: false,
+            interval: retention::DEFAULT_PRUNE_INTERVAL_SECONDS,
+            raw_metrics_days: retention::DEFAULT_RAW_METRICS_DAYS,
+            rollups_days: retention::DEFAULT_ROLLUPS_DAYS,
+            audit_days: retention::DEFAULT_AUDIT_DAYS,
+            config_snapshots: retention::DEFAULT_CONFIG_SNAPSHOTS,
+        }
+    }
+}
+
+/// Scheduled checks for references to data that no longer exists
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IntegrityConfig {
+    /// Whether the server checks integrity in the background
+    pub enabled: bool,
+    /// Interval between checks in seconds
+    pub interval: u64,
+    /// Kinds of issue scheduled checks repair; the rest are only reported
+    pub repair: Vec<IssueKind>,
+}
+
+impl Default for IntegrityConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval: crate::config::defaults::integrity::DEFAULT_CHECK_INTERVAL_SECONDS,
+            repair: Vec::new(),
+        }
+    }
+}
+
+/// Policy evaluation of single nodes as they change
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PolicyConfig {
+    /// Whether the server evaluates a node's policies shortly after the node
+    /// is created or updated through the API, besides the periodic evaluation
+    pub evaluate_on_change: bool,
+    /// Milliseconds to wait after a node's last change before evaluating it,
+    /// so a burst of updates is evaluated once
+    pub debounce_ms: u64,
+}
+
+impl Default for PolicyConfig {
+    fn default() -> Self {
+        Self {
+            evaluate_on_change: true,
+            debounce_ms: crate::config::defaults::policy::DEFAULT_DEBOUNCE_MS,
         }
     }
 }
@@ -95,12 +366,3 @@ pub struct DomainConfig {
     /// Search domains list
     pub search_domains: Vec<String>,
 }
-
-/// Authentication configuration for API bearer-token enforcement.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AuthConfig {
-    /// Authentication enabled
-    pub enabled: bool,
-    /// Static bearer token accepted by the server when auth is enabled
-    pub token: Option<String>,
-}
@@ -42,7 +42,8 @@ impl Config {
 
 #[cfg(test)]
 mod tests {
-    use super::super::types::{DatabaseConfig, ServerConfig};
+    use super::super::server::ServerConfig;
+    use super::super::types::DatabaseConfig;
     use super::Config;
     use std::sync::Mutex;
 
@@ -55,6 +56,8 @@ mod tests {
                 url: "sqlite:test.db".to_string(),
                 max_connections: Some(10),
                 timeout: Some(30),
+                encryption_key: None,
+                allow_plaintext_key: false,
             },
             server: ServerConfig {
                 host: "127.0.0.1".to_string(),
@@ -0,0 +1,381 @@
+//! Collected configuration snapshots and unauthorized change alerts
+//!
+//! Every collected configuration is kept in its node's snapshot history, and
+//! the most recent one as the node's snapshot; retention trims the history
+//! to the newest snapshots of each node. A newly collected configuration that
+//! differs from the latest snapshot is a change, and a change is authorized
+//! when a change window covering the node is open. Change windows are approved periods for one node, every node in a
+//! location and its sub-locations, or every node, optionally recording the
+//! change request that approved them.
+//!
+//! An unauthorized change queues a `config.unauthorized_change` event with a
+//! unified diff from the previous snapshot for webhook subscribers (see
+//! [`crate::webhooks`]). The first snapshot of a node is its baseline and is
+//! never reported.
+
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult, PruneStats, QueryOptions};
+use crate::models::{Location, Node};
+use crate::webhooks::{WebhookEvent, record_event};
+use chrono::{DateTime, Utc};
+use config_slicer::diff::{diff_stats, unified_diff};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+/// Settings namespace holding the latest snapshot of each node keyed by node ID
+const SNAPSHOTS_NAMESPACE: &str = "config_snapshots";
+/// Settings namespace holding every snapshot keyed by node ID and collection time
+const HISTORY_NAMESPACE: &str = "config_snapshot_history";
+/// Settings namespace holding change windows keyed by ID
+const WINDOWS_NAMESPACE: &str = "change_windows";
+
+/// What a change window applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "id", rename_all = "snake_case")]
+pub enum ChangeScope {
+    /// Every node
+    Global,
+    /// Nodes in the location or any of its sub-locations
+    Location(Uuid),
+    /// A single node
+    Node(Uuid),
+}
+
+/// A period in which configuration changes are approved
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangeWindow {
+    /// Window ID
+    pub id: Uuid,
+    /// What the window applies to
+    pub scope: ChangeScope,
+    /// When changes start being approved
+    pub starts_at: DateTime<Utc>,
+    /// When changes stop being approved
+    pub ends_at: DateTime<Utc>,
+    /// Change request that approved the window, such as a ticket number
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub change_request: Option<String>,
+    /// What the change is
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl ChangeWindow {
+    /// Creates a change window
+    ///
+    /// # Errors
+    /// Returns a validation error if the window does not end after it starts
+    /// or the change request is empty.
+    pub fn new(
+        scope: ChangeScope,
+        starts_at: DateTime<Utc>,
+        ends_at: DateTime<Utc>,
+        change_request: Option<String>,
+        description: Option<String>,
+    ) -> DataStoreResult<Self> {
+        if ends_at <= starts_at {
+            return Err(DataStoreError::ValidationError {
+                message: "Change window must end after it starts".to_string(),
+            });
+        }
+        if change_request
+            .as_ref()
+            .is_some_and(|cr| cr.trim().is_empty())
+        {
+            return Err(DataStoreError::ValidationError {
+                message: "Change request must not be empty".to_string(),
+            });
+        }
+        Ok(Self {
+            id: Uuid::new_v4(),
+            scope,
+            starts_at,
+            ends_at,
+            change_request: change_request.map(|cr| cr.trim().to_string()),
+            description,
+        })
+    }
+
+    /// Whether the window is open at `at`
+    #[must_use]
+    pub fn is_open(&self, at: DateTime<Utc>) -> bool {
+        self.starts_at <= at && at < self.ends_at
+    }
+
+    /// Whether the window applies to `node`, given every location
+    #[must_use]
+    pub fn covers(&self, node: &Node, locations: &[Location]) -> bool {
+        match self.scope {
+            ChangeScope::Global => true,
+            ChangeScope::Node(id) => node.id == id,
+            ChangeScope::Location(id) => {
+                let Some(location) = node
+                    .location_id
+                    .and_then(|location_id| locations.iter().find(|l| l.id == location_id))
+                else {
+                    return false;
+                };
+                location.id == id
+                    || location
+                        .get_ancestors(locations)
+                        .iter()
+                        .any(|ancestor| ancestor.id == id)
+            }
+        }
+    }
+}
+
+/// A collected configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigSnapshot {
+    /// Node the configuration was collected from
+    pub node_id: Uuid,
+    /// When the configuration was collected
+    pub collected_at: DateTime<Utc>,
+    /// Configuration text
+    pub config: String,
+}
+
+/// How a new snapshot compares with the previous one
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotComparison {
+    /// Node the configuration was collected from
+    pub node_id: Uuid,
+    /// When the configuration was collected
+    pub collected_at: DateTime<Utc>,
+    /// When the previous snapshot was collected; `None` for a baseline
+    pub previous_collected_at: Option<DateTime<Utc>>,
+    /// Lines added since the previous snapshot
+    pub added: usize,
+    /// Lines removed since the previous snapshot
+    pub removed: usize,
+    /// Unified diff from the previous snapshot, if the configuration changed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
+    /// Open change window the change fell in, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window: Option<ChangeWindow>,
+    /// Whether the configuration changed outside every change window
+    pub unauthorized: bool,
+}
+
+fn parse<T: serde::de::DeserializeOwned>(key: &str, value: Value) -> DataStoreResult<T> {
+    serde_json::from_value(value).map_err(|e| DataStoreError::InternalError {
+        message: format!("stored config change data {key}: {e}"),
+    })
+}
+
+fn to_value<T: Serialize>(key: &str, value: &T) -> DataStoreResult<Value> {
+    serde_json::to_value(value).map_err(|e| DataStoreError::InternalError {
+        message: format!("config change data {key}: {e}"),
+    })
+}
+
+/// Lists change windows, earliest start first
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or a stored window is malformed.
+pub async fn list_windows(datastore: &dyn DataStore) -> DataStoreResult<Vec<ChangeWindow>> {
+    let mut windows = datastore
+        .list_settings(WINDOWS_NAMESPACE)
+        .await?
+        .into_iter()
+        .map(|(key, value)| parse::<ChangeWindow>(&key, value))
+        .collect::<DataStoreResult<Vec<_>>>()?;
+    windows.sort_by_key(|window| window.starts_at);
+    Ok(windows)
+}
+
+/// Stores a change window
+///
+/// # Errors
+/// Returns an error if the datastore write fails.
+pub async fn save_window(datastore: &dyn DataStore, window: &ChangeWindow) -> DataStoreResult<()> {
+    let key = window.id.to_string();
+    datastore
+        .put_setting(WINDOWS_NAMESPACE, &key, &to_value(&key, window)?)
+        .await
+}
+
+/// Removes a change window
+///
+/// # Errors
+/// Returns an error if the window does not exist or the datastore write fails.
+pub async fn delete_window(datastore: &dyn DataStore, id: Uuid) -> DataStoreResult<()> {
+    datastore
+        .delete_setting(WINDOWS_NAMESPACE, &id.to_string())
+        .await
+}
+
+/// Returns the latest snapshot of a node, if one has been collected
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or the snapshot is malformed.
+pub async fn latest_snapshot(
+    datastore: &dyn DataStore,
+    node_id: Uuid,
+) -> DataStoreResult<Option<ConfigSnapshot>> {
+    let key = node_id.to_string();
+    datastore
+        .get_setting(SNAPSHOTS_NAMESPACE, &key)
+        .await?
+        .map(|value| parse(&key, value))
+        .transpose()
+}
+
+/// Returns a node's snapshot history, oldest first
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or a snapshot is malformed.
+pub async fn snapshot_history(
+    datastore: &dyn DataStore,
+    node_id: Uuid,
+) -> DataStoreResult<Vec<ConfigSnapshot>> {
+    let prefix = format!("{node_id}/");
+    let mut history = datastore.list_settings(HISTORY_NAMESPACE).await?;
+    history.retain(|(key, _)| key.starts_with(&prefix));
+    history.sort_by(|(a, _), (b, _)| a.cmp(b));
+    history
+        .into_iter()
+        .map(|(key, value)| parse(&key, value))
+        .collect()
+}
+
+/// Deletes all but the newest `keep` snapshots of each node from the
+/// history; with `dry_run`, only measures them
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or written.
+pub async fn prune_snapshot_history(
+    datastore: &dyn DataStore,
+    keep: usize,
+    dry_run: bool,
+) -> DataStoreResult<PruneStats> {
+    let mut history = datastore.list_settings(HISTORY_NAMESPACE).await?;
+    // Newest first within each node, so everything after `keep` goes
+    history.sort_by(|(a, _), (b, _)| b.cmp(a));
+    let mut stats = PruneStats::default();
+    let mut node = "";
+    let mut kept = 0;
+    for (key, value) in &history {
+        let key_node = key.split_once('/').map_or(key.as_str(), |(node, _)| node);
+        if key_node != node {
+            node = key_node;
+            kept = 0;
+        }
+        if kept < keep {
+            kept += 1;
+            continue;
+        }
+        if !dry_run {
+            match datastore.delete_setting(HISTORY_NAMESPACE, key).await {
+                Ok(()) | Err(DataStoreError::NotFound { .. }) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        stats.add_setting(key, value);
+    }
+    Ok(stats)
+}
+
+fn history_key(snapshot: &ConfigSnapshot) -> String {
+    format!(
+        "{}/{:020}",
+        snapshot.node_id,
+        snapshot.collected_at.timestamp_micros().max(0)
+    )
+}
+
+/// Stores a newly collected configuration and compares it with the previous one
+///
+/// A change outside every open change window queues a
+/// `config.unauthorized_change` event for webhook subscribers.
+///
+/// # Errors
+/// Returns an error if the previous snapshot, change windows, or locations
+/// cannot be read, or the snapshot or event cannot be written.
+pub async fn record_snapshot(
+    datastore: &dyn DataStore,
+    node: &Node,
+    config: String,
+    collected_at: DateTime<Utc>,
+) -> DataStoreResult<SnapshotComparison> {
+    let previous = latest_snapshot(datastore, node.id).await?;
+    let snapshot = ConfigSnapshot {
+        node_id: node.id,
+        collected_at,
+        config,
+    };
+    let key = node.id.to_string();
+    let value = to_value(&key, &snapshot)?;
+    datastore
+        .put_setting(HISTORY_NAMESPACE, &history_key(&snapshot), &value)
+        .await?;
+    datastore
+        .put_setting(SNAPSHOTS_NAMESPACE, &key, &value)
+        .await?;
+
+    let mut comparison = SnapshotComparison {
+        node_id: node.id,
+        collected_at,
+        previous_collected_at: previous.as_ref().map(|previous| previous.collected_at),
+        added: 0,
+        removed: 0,
+        diff: None,
+        window: None,
+        unauthorized: false,
+    };
+    let Some(previous) = previous else {
+        return Ok(comparison);
+    };
+    let stats = diff_stats(&previous.config, &snapshot.config);
+    if stats.is_empty() {
+        return Ok(comparison);
+    }
+
+    let diff = unified_diff(
+        &previous.config,
+        &snapshot.config,
+        &previous.collected_at.to_rfc3339(),
+        &collected_at.to_rfc3339(),
+    );
+    comparison.added = stats.added;
+    comparison.removed = stats.removed;
+    comparison.window = open_window(datastore, node, collected_at).await?;
+    comparison.unauthorized = comparison.window.is_none();
+    if comparison.unauthorized {
+        let event = WebhookEvent::unauthorized_change(node, &previous, &snapshot, &diff, stats);
+        record_event(datastore, &event).await?;
+    }
+    comparison.diff = Some(diff);
+    Ok(comparison)
+}
+
+async fn open_window(
+    datastore: &dyn DataStore,
+    node: &Node,
+    at: DateTime<Utc>,
+) -> DataStoreResult<Option<ChangeWindow>> {
+    let open: Vec<ChangeWindow> = list_windows(datastore)
+        .await?
+        .into_iter()
+        .filter(|window| window.is_open(at))
+        .collect();
+    let needs_locations = open
+        .iter()
+        .any(|window| matches!(window.scope, ChangeScope::Location(_)));
+    let locations = if needs_locations {
+        datastore
+            .list_locations(&QueryOptions::default())
+            .await?
+            .items
+    } else {
+        Vec::new()
+    };
+    Ok(open
+        .into_iter()
+        .find(|window| window.covers(node, &locations)))
+}
+
+#[cfg(test)]
+mod tests;
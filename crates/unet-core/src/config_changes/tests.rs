@@ -0,0 +1,185 @@
+use super::*;
+use crate::datastore::sqlite::SqliteStore;
+use crate::datastore::sqlite::tests::setup::migrated_store;
+use crate::models::{DeviceRole, Vendor};
+use crate::webhooks::{EventType, WebhookSubscription, save_subscription};
+use chrono::TimeDelta;
+use std::collections::BTreeMap;
+
+fn node(location_id: Option<Uuid>) -> Node {
+    let mut node = Node::new(
+        "edge-1".to_string(),
+        "example.com".to_string(),
+        Vendor::Juniper,
+        DeviceRole::Router,
+    );
+    node.location_id = location_id;
+    node
+}
+
+async fn queued_events(store: &SqliteStore) -> usize {
+    store.list_settings("webhook_outbox").await.unwrap().len()
+}
+
+#[test]
+fn test_window_validation_and_opening_hours() {
+    let now = Utc::now();
+    assert!(ChangeWindow::new(ChangeScope::Global, now, now, None, None).is_err());
+    assert!(
+        ChangeWindow::new(
+            ChangeScope::Global,
+            now,
+            now + TimeDelta::hours(1),
+            Some(" ".to_string()),
+            None
+        )
+        .is_err()
+    );
+
+    let window = ChangeWindow::new(
+        ChangeScope::Global,
+        now,
+        now + TimeDelta::hours(1),
+        Some(" CHG0042 ".to_string()),
+        None,
+    )
+    .unwrap();
+    assert_eq!(window.change_request.as_deref(), Some("CHG0042"));
+    assert!(window.is_open(now));
+    assert!(!window.is_open(now - TimeDelta::seconds(1)));
+    assert!(!window.is_open(now + TimeDelta::hours(1)));
+}
+
+#[test]
+fn test_location_window_covers_sub_locations() {
+    let site = Location::new_root("dc1".to_string(), "datacenter".to_string());
+    let mut rack = Location::new_child("rack1".to_string(), "rack".to_string(), &site.path);
+    rack.parent_id = Some(site.id);
+    let locations = vec![site.clone(), rack.clone()];
+    let now = Utc::now();
+    let window =
+        |scope| ChangeWindow::new(scope, now, now + TimeDelta::hours(1), None, None).unwrap();
+
+    assert!(window(ChangeScope::Location(site.id)).covers(&node(Some(rack.id)), &locations));
+    assert!(!window(ChangeScope::Location(rack.id)).covers(&node(None), &locations));
+    assert!(!window(ChangeScope::Node(Uuid::new_v4())).covers(&node(None), &locations));
+    assert!(window(ChangeScope::Global).covers(&node(None), &locations));
+}
+
+#[tokio::test]
+async fn test_change_outside_window_queues_event_with_diff() {
+    let store = migrated_store().await;
+    let subscription = WebhookSubscription::new(
+        "https://hooks.example.com/unet",
+        None,
+        vec![EventType::ConfigUnauthorizedChange],
+        BTreeMap::new(),
+    )
+    .unwrap();
+    save_subscription(&store, &subscription).await.unwrap();
+    let node = node(None);
+    let start = Utc::now();
+
+    let baseline = record_snapshot(&store, &node, "hostname edge-1\n".to_string(), start)
+        .await
+        .unwrap();
+    assert!(baseline.previous_collected_at.is_none());
+    assert!(!baseline.unauthorized);
+
+    let unchanged = record_snapshot(
+        &store,
+        &node,
+        "hostname edge-1\n".to_string(),
+        start + TimeDelta::hours(1),
+    )
+    .await
+    .unwrap();
+    assert!(unchanged.diff.is_none());
+    assert_eq!(queued_events(&store).await, 0);
+
+    let changed = record_snapshot(
+        &store,
+        &node,
+        "hostname edge-1\nsnmp-server community public\n".to_string(),
+        start + TimeDelta::hours(2),
+    )
+    .await
+    .unwrap();
+    assert!(changed.unauthorized);
+    assert_eq!((changed.added, changed.removed), (1, 0));
+    assert!(
+        changed
+            .diff
+            .unwrap()
+            .contains("+snmp-server community public")
+    );
+    assert_eq!(queued_events(&store).await, 1);
+
+    let latest = latest_snapshot(&store, node.id).await.unwrap().unwrap();
+    assert_eq!(latest.collected_at, start + TimeDelta::hours(2));
+}
+
+#[tokio::test]
+async fn test_change_inside_window_is_authorized() {
+    let store = migrated_store().await;
+    let node = node(None);
+    let start = Utc::now();
+    let window = ChangeWindow::new(
+        ChangeScope::Node(node.id),
+        start + TimeDelta::hours(1),
+        start + TimeDelta::hours(3),
+        Some("CHG0042".to_string()),
+        Some("Rotate SNMP community".to_string()),
+    )
+    .unwrap();
+    save_window(&store, &window).await.unwrap();
+
+    record_snapshot(&store, &node, "a\n".to_string(), start)
+        .await
+        .unwrap();
+    let changed = record_snapshot(
+        &store,
+        &node,
+        "b\n".to_string(),
+        start + TimeDelta::hours(2),
+    )
+    .await
+    .unwrap();
+
+    assert!(!changed.unauthorized);
+    assert_eq!(changed.window.unwrap().id, window.id);
+    assert_eq!(list_windows(&store).await.unwrap().len(), 1);
+    delete_window(&store, window.id).await.unwrap();
+    assert!(list_windows(&store).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_snapshot_history_is_pruned_to_newest_per_node() {
+    let store = migrated_store().await;
+    let first = node(None);
+    let second = node(None);
+    let start = Utc::now();
+    for hour in 0..3 {
+        let collected_at = start + TimeDelta::hours(hour);
+        record_snapshot(&store, &first, format!("v{hour}\n"), collected_at)
+            .await
+            .unwrap();
+    }
+    record_snapshot(&store, &second, "v0\n".to_string(), start)
+        .await
+        .unwrap();
+
+    let dry_run = prune_snapshot_history(&store, 2, true).await.unwrap();
+    assert_eq!(dry_run.rows, 1);
+    assert!(dry_run.bytes > 0);
+    assert_eq!(snapshot_history(&store, first.id).await.unwrap().len(), 3);
+
+    let pruned = prune_snapshot_history(&store, 2, false).await.unwrap();
+    assert_eq!(pruned, dry_run);
+    let history = snapshot_history(&store, first.id).await.unwrap();
+    let configs: Vec<&str> = history.iter().map(|s| s.config.as_str()).collect();
+    assert_eq!(configs, ["v1\n", "v2\n"]);
+    assert_eq!(snapshot_history(&store, second.id).await.unwrap().len(), 1);
+    let latest = latest_snapshot(&store, first.id).await.unwrap().unwrap();
+    assert_eq!(latest.config, "v2\n");
+}
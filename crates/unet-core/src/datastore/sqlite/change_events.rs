@@ -0,0 +1,235 @@
+//! Change log operations for `SQLite` datastore
+//!
+//! Node, link, and location writes run in a [`LoggedWrite`], which appends
+//! the write's event in the same transaction when the change log is enabled,
+//! so an event is stored exactly when its write is. The database assigns
+//! each event the next sequence number.
+
+use super::super::types::{DataStoreError, DataStoreResult, PruneStats};
+use super::SqliteStore;
+use super::errors::{query_error, transaction_error};
+use crate::change_log::{ChangeEvent, ChangeType, EntityKind, EventFilter};
+use crate::entities::change_events;
+use chrono::{DateTime, Utc};
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseBackend, DatabaseTransaction, EntityTrait, NotSet,
+    QueryFilter, QueryOrder, QuerySelect, Set, Statement, TransactionTrait, Value,
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Estimated stored size of an event row; the sequence and time are 8 bytes
+/// each
+const EVENT_BYTES: &str = "length(id) + length(entity) + length(entity_id) + \
+                           length(change_type) + length(payload) + length(changed_fields) + 16";
+
+/// A write to a node, link, or location and the event recording it
+pub(super) struct LoggedWrite {
+    txn: DatabaseTransaction,
+    change_log: bool,
+}
+
+impl LoggedWrite {
+    /// Begins the transaction of a write
+    pub(super) async fn begin(store: &SqliteStore) -> DataStoreResult<Self> {
+        let txn = store
+            .db
+            .begin()
+            .await
+            .map_err(|e| transaction_error("Failed to begin transaction", &e))?;
+        Ok(Self {
+            txn,
+            change_log: store.change_log,
+        })
+    }
+
+    /// Connection to run the write on
+    pub(super) const fn db(&self) -> &DatabaseTransaction {
+        &self.txn
+    }
+
+    /// Appends the event of the write, if the change log is enabled, and
+    /// commits; `before` is the entity as stored before an update
+    pub(super) async fn commit<T: Serialize + Sync>(
+        self,
+        entity: EntityKind,
+        entity_id: Uuid,
+        change: ChangeType,
+        before: Option<&T>,
+        payload: &T,
+    ) -> DataStoreResult<()> {
+        if self.change_log {
+            let event = ChangeEvent::from_write(
+                entity,
+                entity_id,
+                change,
+                &to_json(&before)?,
+                to_json(payload)?,
+            );
+            insert_event(&self.txn, &event).await?;
+        }
+        self.txn
+            .commit()
+            .await
+            .map_err(|e| transaction_error("Failed to commit transaction", &e))
+    }
+}
+
+fn to_json<T: Serialize>(value: &T) -> DataStoreResult<serde_json::Value> {
+    serde_json::to_value(value).map_err(|e| DataStoreError::InternalError {
+        message: format!("Failed to serialize change event payload: {e}"),
+    })
+}
+
+/// Stores an event and returns it with the sequence number it was given
+async fn insert_event<C: ConnectionTrait>(
+    db: &C,
+    event: &ChangeEvent,
+) -> DataStoreResult<ChangeEvent> {
+    let model = change_events::ActiveModel {
+        sequence: NotSet,
+        id: Set(event.id.to_string()),
+        recorded_at: Set(event.recorded_at.timestamp_micros()),
+        entity: Set(event.entity.to_string()),
+        entity_id: Set(event.entity_id.to_string()),
+        change_type: Set(event.change.to_string()),
+        payload: Set(event.payload.to_string()),
+        changed_fields: Set(serde_json::json!(event.changed_fields).to_string()),
+    };
+    let inserted = change_events::Entity::insert(model)
+        .exec(db)
+        .await
+        .map_err(|e| query_error("Failed to append change event", &e))?;
+    Ok(ChangeEvent {
+        sequence: inserted.last_insert_id,
+        ..event.clone()
+    })
+}
+
+fn model_to_event(model: change_events::Model) -> DataStoreResult<ChangeEvent> {
+    let sequence = model.sequence;
+    let invalid = |e: String| DataStoreError::InternalError {
+        message: format!("Invalid change event {sequence}: {e}"),
+    };
+    Ok(ChangeEvent {
+        sequence,
+        id: Uuid::parse_str(&model.id).map_err(|e| invalid(e.to_string()))?,
+        recorded_at: DateTime::from_timestamp_micros(model.recorded_at)
+            .ok_or_else(|| invalid(format!("time {} out of range", model.recorded_at)))?,
+        entity: model.entity.parse().map_err(invalid)?,
+        entity_id: Uuid::parse_str(&model.entity_id).map_err(|e| invalid(e.to_string()))?,
+        change: model.change_type.parse().map_err(invalid)?,
+        payload: serde_json::from_str(&model.payload).map_err(|e| invalid(e.to_string()))?,
+        changed_fields: serde_json::from_str(&model.changed_fields)
+            .map_err(|e| invalid(e.to_string()))?,
+    })
+}
+
+/// Appends an event outside of an entity write
+pub async fn append_change_event(
+    store: &SqliteStore,
+    event: &ChangeEvent,
+) -> DataStoreResult<ChangeEvent> {
+    insert_event(&store.db, event).await
+}
+
+/// Lists the events passing a filter, in log order
+pub async fn list_change_events(
+    store: &SqliteStore,
+    filter: &EventFilter,
+) -> DataStoreResult<Vec<ChangeEvent>> {
+    let mut query = change_events::Entity::find().order_by_asc(change_events::Column::Sequence);
+    if let Some(after) = filter.after {
+        query = query.filter(change_events::Column::Sequence.gt(after));
+    }
+    if let Some(entity) = filter.entity {
+        query = query.filter(change_events::Column::Entity.eq(entity.to_string()));
+    }
+    if let Some(entity_id) = filter.entity_id {
+        query = query.filter(change_events::Column::EntityId.eq(entity_id.to_string()));
+    }
+    if let Some(since) = filter.since {
+        query = query.filter(change_events::Column::RecordedAt.gte(since.timestamp_micros()));
+    }
+    if let Some(field) = &filter.field {
+        // The field itself or any path below it
+        let prefix = format!("{field}.");
+        let prefix_chars = i64::try_from(prefix.chars().count()).unwrap_or(i64::MAX);
+        query = query.filter(Expr::cust_with_values(
+            "EXISTS (SELECT 1 FROM json_each(change_event.changed_fields) \
+             WHERE json_each.value = ? OR substr(json_each.value, 1, ?) = ?)",
+            [
+                Value::from(field.clone()),
+                Value::from(prefix_chars),
+                Value::from(prefix),
+            ],
+        ));
+    }
+    if let Some(limit) = filter.limit {
+        query = query.limit(u64::try_from(limit).unwrap_or(u64::MAX));
+    }
+    query
+        .all(&store.db)
+        .await
+        .map_err(|e| query_error("Failed to list change events", &e))?
+        .into_iter()
+        .map(model_to_event)
+        .collect()
+}
+
+/// Sequence number of the latest event; 0 when the log is empty
+pub async fn last_change_sequence(store: &SqliteStore) -> DataStoreResult<i64> {
+    let last = change_events::Entity::find()
+        .select_only()
+        .column(change_events::Column::Sequence)
+        .order_by_desc(change_events::Column::Sequence)
+        .into_tuple::<i64>()
+        .one(&store.db)
+        .await
+        .map_err(|e| query_error("Failed to read change log position", &e))?;
+    Ok(last.unwrap_or(0))
+}
+
+/// Deletes events recorded before `cutoff`
+pub async fn prune_change_events(
+    store: &SqliteStore,
+    cutoff: DateTime<Utc>,
+    dry_run: bool,
+) -> DataStoreResult<PruneStats> {
+    let cutoff = cutoff.timestamp_micros();
+    let failed = |e: String| DataStoreError::InternalError {
+        message: format!("Failed to measure change events: {e}"),
+    };
+    let row = store
+        .db
+        .query_one(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            format!(
+                "SELECT COUNT(*) AS row_count, COALESCE(SUM({EVENT_BYTES}), 0) AS byte_count \
+                 FROM change_event WHERE recorded_at < ?"
+            ),
+            [Value::from(cutoff)],
+        ))
+        .await
+        .map_err(|e| failed(e.to_string()))?
+        .ok_or_else(|| failed("no result row".to_string()))?;
+    let rows: i64 = row
+        .try_get("", "row_count")
+        .map_err(|e| failed(e.to_string()))?;
+    let bytes: i64 = row
+        .try_get("", "byte_count")
+        .map_err(|e| failed(e.to_string()))?;
+    let stats = PruneStats {
+        rows: usize::try_from(rows).map_err(|e| failed(e.to_string()))?,
+        bytes: u64::try_from(bytes).map_err(|e| failed(e.to_string()))?,
+    };
+    if !dry_run && stats.rows > 0 {
+        change_events::Entity::delete_many()
+            .filter(change_events::Column::RecordedAt.lt(cutoff))
+            .exec(&store.db)
+            .await
+            .map_err(|e| query_error("Failed to delete change events", &e))?;
+    }
+    Ok(stats)
+}
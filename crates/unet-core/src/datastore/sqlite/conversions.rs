@@ -5,9 +5,11 @@ use crate::entities::{interface_status, links, locations, node_status, nodes};
 use crate::models::derived::{
     InterfaceAdminStatus, InterfaceOperStatus, InterfaceStatus, NodeStatus,
 };
-use crate::models::{DeviceRole, Lifecycle, Link, Location, Node, Vendor};
-use chrono::{DateTime, Utc};
+use crate::models::location::Coordinates;
+use crate::models::{DeviceRole, Lifecycle, Link, Location, Node, ProvisioningState, Vendor};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::de::DeserializeOwned;
+use std::collections::{BTreeMap, HashMap};
 use std::time::SystemTime;
 use uuid::Uuid;
 
@@ -40,6 +42,27 @@ pub fn entity_to_link(entity: links::Model) -> DataStoreResult<Link> {
         None
     };
 
+    let management_ipv6 = entity
+        .management_ipv6
+        .map(|ip_str| {
+            ip_str.parse().map_err(|e| DataStoreError::ValidationError {
+                message: format!("Invalid IPv6 management address: {e}"),
+            })
+        })
+        .transpose()?;
+
+    let provisioning_state = entity
+        .provisioning_state
+        .map(|state| {
+            state
+                .parse::<ProvisioningState>()
+                .map_err(|message| DataStoreError::ValidationError { message })
+        })
+        .transpose()?
+        .unwrap_or_default();
+    let ordered_on = parse_date(entity.ordered_on, "order")?;
+    let delivered_on = parse_date(entity.delivered_on, "delivery")?;
+
     let custom_data = entity
         .custom_data
         .as_ref()
@@ -58,10 +81,26 @@ pub fn entity_to_link(entity: links::Model) -> DataStoreResult<Link> {
         bandwidth: entity.capacity.map(|c| u64::try_from(c).unwrap_or(0)),
         link_type: None, // Not stored in entity yet
         is_internet_circuit: entity.is_internet_circuit != 0,
+        provider: entity.provider,
+        circuit_id: entity.circuit_id,
+        ordered_on,
+        delivered_on,
+        provisioning_state,
         custom_data,
     })
 }
 
+/// Parses a stored `YYYY-MM-DD` date
+fn parse_date(value: Option<String>, kind: &str) -> DataStoreResult<Option<NaiveDate>> {
+    value
+        .map(|date| {
+            date.parse().map_err(|e| DataStoreError::ValidationError {
+                message: format!("Invalid {kind} date {date:?}: {e}"),
+            })
+        })
+        .transpose()
+}
+
 /// Helper function to convert `SeaORM` location entity to our Location model
 pub fn entity_to_location(entity: locations::Model) -> DataStoreResult<Location> {
     let id = entity
@@ -90,6 +129,15 @@ pub fn entity_to_location(entity: locations::Model) -> DataStoreResult<Location>
             serde_json::from_str(data_str).unwrap_or_default()
         });
 
+    let coordinates = entity
+        .coordinates
+        .as_deref()
+        .map(str::parse::<Coordinates>)
+        .transpose()
+        .map_err(|e| DataStoreError::ValidationError {
+            message: format!("Invalid coordinates: {e}"),
+        })?;
+
     Ok(Location {
         id,
         name: entity.name,
@@ -98,6 +146,9 @@ pub fn entity_to_location(entity: locations::Model) -> DataStoreResult<Location>
         path: entity.path,
         description: entity.description,
         address: entity.address,
+        postal_address: parse_optional_json(entity.postal_address, "postal_address")?,
+        coordinates,
+        timezone: entity.timezone,
         custom_data,
     })
 }
@@ -192,6 +243,8 @@ pub fn entity_to_node(entity: nodes::Model) -> DataStoreResult<Node> {
         purchase_date: None,    // Not stored in entity yet
         warranty_expires: None, // Not stored in entity yet
         custom_data,
+        management_ipv6,
+        management_zone: entity.management_zone,
     })
 }
 
@@ -240,6 +293,11 @@ pub fn entity_to_node_status(
                 message: format!("Invalid consecutive failure count: {e}"),
             }
         })?,
+        enrichments: HashMap::new(),
+        enrichment_errors: HashMap::new(),
+        bgp_peers: Vec::new(),
+        lldp_neighbors: Vec::new(),
+        contexts: BTreeMap::new(),
     })
 }
 
@@ -21,6 +21,8 @@ fn test_node_entity(domain: Option<&str>, fqdn: Option<&str>) -> nodes::Model {
         custom_data: None,
         created_at: "2026-04-07T01:02:03Z".to_string(),
         updated_at: "2026-04-07T01:02:03Z".to_string(),
+        management_ipv6: None,
+        management_zone: None,
     }
 }
 
@@ -94,6 +96,9 @@ fn test_entity_to_link_tolerates_invalid_optional_values() {
         custom_data: Some("{invalid-json".to_string()),
         created_at: "2026-04-07T01:02:03Z".to_string(),
         updated_at: "2026-04-07T01:02:03Z".to_string(),
+        provisioning_state: None,
+        ordered_on: None,
+        delivered_on: None,
     };
 
     let link = entity_to_link(entity).unwrap();
@@ -103,6 +108,7 @@ fn test_entity_to_link_tolerates_invalid_optional_values() {
     assert_eq!(link.bandwidth, Some(0));
     assert_eq!(link.custom_data, serde_json::Value::Null);
     assert!(link.is_internet_circuit);
+    assert_eq!(link.provisioning_state, ProvisioningState::Live);
 }
 
 #[test]
@@ -119,6 +125,8 @@ fn test_entity_to_location_rejects_invalid_parent_uuid() {
         custom_data: None,
         created_at: "2026-04-07T01:02:03Z".to_string(),
         updated_at: "2026-04-07T01:02:03Z".to_string(),
+        postal_address: None,
+        timezone: None,
     };
 
     let error = entity_to_location(entity).unwrap_err();
@@ -6,8 +6,12 @@ use crate::datastore::types::{DataStoreError, DataStoreResult};
 use crate::entities::{interface_status, node_status};
 use crate::models::derived::{InterfaceStatus, NodeStatus, PerformanceMetrics};
 use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+use std::collections::HashMap;
 use uuid::Uuid;
 
+/// IDs bound per `IN` query, well under `SQLite`'s variable limit
+const IN_CHUNK_SIZE: usize = 500;
+
 pub async fn get_node_status(
     store: &SqliteStore,
     node_id: &Uuid,
@@ -27,6 +31,50 @@ pub async fn get_node_status(
     entity_to_node_status(status_entity, interfaces).map(Some)
 }
 
+pub async fn get_node_statuses(
+    store: &SqliteStore,
+    node_ids: &[Uuid],
+) -> DataStoreResult<Vec<NodeStatus>> {
+    let mut entities = Vec::new();
+    for chunk in node_ids.chunks(IN_CHUNK_SIZE) {
+        let found = node_status::Entity::find()
+            .filter(node_status::Column::NodeId.is_in(chunk.iter().map(Uuid::to_string)))
+            .all(&store.db)
+            .await
+            .map_err(|e| DataStoreError::InternalError {
+                message: format!("Failed to query node_status: {e}"),
+            })?;
+        entities.extend(found);
+    }
+
+    let status_ids: Vec<String> = entities.iter().map(|entity| entity.id.clone()).collect();
+    let mut interfaces: HashMap<String, Vec<interface_status::Model>> = HashMap::new();
+    for chunk in status_ids.chunks(IN_CHUNK_SIZE) {
+        let found = interface_status::Entity::find()
+            .filter(interface_status::Column::NodeStatusId.is_in(chunk.iter().cloned()))
+            .order_by_asc(interface_status::Column::Index)
+            .all(&store.db)
+            .await
+            .map_err(|e| DataStoreError::InternalError {
+                message: format!("Failed to query interface status: {e}"),
+            })?;
+        for interface in found {
+            interfaces
+                .entry(interface.node_status_id.clone())
+                .or_default()
+                .push(interface);
+        }
+    }
+
+    entities
+        .into_iter()
+        .map(|entity| {
+            let node_interfaces = interfaces.remove(&entity.id).unwrap_or_default();
+            entity_to_node_status(entity, node_interfaces)
+        })
+        .collect()
+}
+
 pub async fn get_node_interfaces(
     store: &SqliteStore,
     node_id: &Uuid,
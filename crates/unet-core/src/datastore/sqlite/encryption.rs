@@ -0,0 +1,175 @@
+//! `SQLCipher` support for encrypted `SQLite` databases
+//!
+//! Encryption requires an `SQLite` library built with `SQLCipher`, which is
+//! linked when the crate is built with the `sqlcipher` feature. Support is
+//! detected at runtime, so a configured key against a plain `SQLite` build is
+//! reported as an error instead of silently writing plaintext.
+
+use sea_orm::{ConnectOptions, ConnectionTrait, Database, DatabaseConnection, Statement};
+use std::path::Path;
+
+use super::super::types::{DataStoreError, DataStoreResult};
+
+/// Registers the `SQLCipher` key so it is applied first on every pooled connection
+pub(crate) fn apply_key(options: &mut ConnectOptions, key: &str) {
+    let pragma = quote_literal(key);
+    options.map_sqlx_sqlite_opts(move |opts| opts.pragma("key", pragma.clone()));
+}
+
+/// Confirms that `SQLCipher` is available and that the key opens the database
+///
+/// # Errors
+/// Returns a `ConnectionError` if the linked `SQLite` lacks `SQLCipher`
+/// support or the key does not decrypt the database.
+pub async fn verify_encryption_key(db: &DatabaseConnection) -> DataStoreResult<()> {
+    ensure_sqlcipher(db).await?;
+
+    // SQLCipher only decrypts on first page access, so a wrong key surfaces here
+    db.query_one(sqlite_statement("SELECT count(*) FROM sqlite_master"))
+        .await
+        .map_err(|e| DataStoreError::ConnectionError {
+            message: format!(
+                "Database encryption key is incorrect or the database is not encrypted: {e}"
+            ),
+        })?;
+    Ok(())
+}
+
+/// Writes an encrypted copy of a plaintext `SQLite` database
+///
+/// The source is left untouched; point the configuration at `destination`
+/// once the copy has been verified.
+///
+/// # Errors
+/// Returns an error if `destination` already exists, `SQLCipher` is not
+/// available, or the export fails.
+pub async fn encrypt_database(
+    source_url: &str,
+    destination: &Path,
+    key: &str,
+) -> DataStoreResult<()> {
+    if destination.exists() {
+        return Err(DataStoreError::ValidationError {
+            message: format!("Destination {} already exists", destination.display()),
+        });
+    }
+
+    // ATTACH is per connection, so the export must run on a single one
+    let mut options = ConnectOptions::new(source_url);
+    options.max_connections(1).sqlx_logging(false);
+    let db = Database::connect(options)
+        .await
+        .map_err(|e| DataStoreError::ConnectionError {
+            message: format!("Failed to open source database: {e}"),
+        })?;
+
+    ensure_sqlcipher(&db).await?;
+
+    let export = format!(
+        "ATTACH DATABASE {} AS encrypted KEY {}; \
+         SELECT sqlcipher_export('encrypted'); \
+         DETACH DATABASE encrypted;",
+        quote_literal(&destination.to_string_lossy()),
+        quote_literal(key)
+    );
+    db.execute_unprepared(&export)
+        .await
+        .map_err(|e| DataStoreError::InternalError {
+            message: format!("Failed to export encrypted database: {e}"),
+        })?;
+    Ok(())
+}
+
+async fn ensure_sqlcipher(db: &DatabaseConnection) -> DataStoreResult<()> {
+    // Plain SQLite ignores unknown pragmas and returns no rows
+    let version = db
+        .query_one(sqlite_statement("PRAGMA cipher_version"))
+        .await
+        .map_err(|e| DataStoreError::ConnectionError {
+            message: format!("Failed to query SQLCipher version: {e}"),
+        })?;
+
+    if version.is_none() {
+        return Err(DataStoreError::ConnectionError {
+            message: "Database encryption requires SQLCipher; rebuild with the `sqlcipher` feature"
+                .to_string(),
+        });
+    }
+    Ok(())
+}
+
+fn sqlite_statement(sql: &str) -> Statement {
+    Statement::from_string(sea_orm::DatabaseBackend::Sqlite, sql)
+}
+
+/// Quotes a value as an SQL string literal
+fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote_literal_escapes_single_quotes() {
+        assert_eq!(quote_literal("secret"), "'secret'");
+        assert_eq!(quote_literal("it's"), "'it''s'");
+    }
+
+    #[tokio::test]
+    async fn test_encrypt_database_rejects_existing_destination() {
+        let destination = tempfile::NamedTempFile::new().unwrap();
+
+        let result = encrypt_database("sqlite::memory:", destination.path(), "key").await;
+
+        assert!(matches!(
+            result,
+            Err(DataStoreError::ValidationError { .. })
+        ));
+    }
+
+    #[cfg(not(feature = "sqlcipher"))]
+    #[tokio::test]
+    async fn test_verify_encryption_key_requires_sqlcipher() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+
+        let error = verify_encryption_key(&db).await.unwrap_err();
+
+        assert!(error.to_string().contains("requires SQLCipher"));
+    }
+
+    #[cfg(feature = "sqlcipher")]
+    #[tokio::test]
+    async fn test_encrypt_database_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("plain.db");
+        let destination = dir.path().join("encrypted.db");
+        let source_url = format!("sqlite://{}?mode=rwc", source.display());
+
+        let plain = Database::connect(&source_url).await.unwrap();
+        plain
+            .execute_unprepared("CREATE TABLE sample (id INTEGER PRIMARY KEY)")
+            .await
+            .unwrap();
+        drop(plain);
+
+        encrypt_database(&source_url, &destination, "correct")
+            .await
+            .unwrap();
+
+        let url = format!("sqlite://{}", destination.display());
+        let mut good = ConnectOptions::new(&url);
+        apply_key(&mut good, "correct");
+        let db = Database::connect(good).await.unwrap();
+        assert!(verify_encryption_key(&db).await.is_ok());
+
+        let mut bad = ConnectOptions::new(&url);
+        apply_key(&mut bad, "wrong");
+        let opened = match Database::connect(bad).await {
+            Ok(db) => verify_encryption_key(&db).await.is_ok(),
+            Err(_) => false,
+        };
+        assert!(!opened);
+    }
+}
@@ -0,0 +1,94 @@
+//! Mapping of database driver errors to `DataStoreError`
+//!
+//! Failures that may succeed if tried again are reported as
+//! [`DataStoreError::Busy`], judged by the driver's error kind and `SQLite`
+//! result code rather than by the message.
+
+use super::super::types::DataStoreError;
+use sea_orm::{DbErr, RuntimeErr, SqlxError};
+
+/// Primary result code of a database another connection is writing to
+const SQLITE_BUSY: i32 = 5;
+/// Primary result code of a table locked by another statement
+const SQLITE_LOCKED: i32 = 6;
+
+/// Whether the database was busy or locked, or the connection to it dropped
+pub(super) fn is_transient(e: &DbErr) -> bool {
+    match e {
+        DbErr::ConnectionAcquire(_) => true,
+        DbErr::Conn(RuntimeErr::SqlxError(e))
+        | DbErr::Exec(RuntimeErr::SqlxError(e))
+        | DbErr::Query(RuntimeErr::SqlxError(e)) => match e {
+            SqlxError::PoolTimedOut | SqlxError::Io(_) => true,
+            // Extended result codes carry the primary code in the low byte
+            SqlxError::Database(e) => e
+                .code()
+                .and_then(|code| code.parse::<i32>().ok())
+                .is_some_and(|code| matches!(code & 0xff, SQLITE_BUSY | SQLITE_LOCKED)),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Maps a failure to open the database
+pub(super) fn connection_error(context: &str, e: &DbErr) -> DataStoreError {
+    let message = format!("{context}: {e}");
+    if is_transient(e) {
+        DataStoreError::Busy { message }
+    } else {
+        DataStoreError::ConnectionError { message }
+    }
+}
+
+/// Maps a failed query or write
+pub(super) fn query_error(context: &str, e: &DbErr) -> DataStoreError {
+    let message = format!("{context}: {e}");
+    if is_transient(e) {
+        DataStoreError::Busy { message }
+    } else {
+        DataStoreError::InternalError { message }
+    }
+}
+
+/// Maps a failure to begin or finish a transaction
+pub(super) fn transaction_error(context: &str, e: &DbErr) -> DataStoreError {
+    let message = format!("{context}: {e}");
+    if is_transient(e) {
+        DataStoreError::Busy { message }
+    } else {
+        DataStoreError::TransactionError { message }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::ConnAcquireErr;
+
+    #[test]
+    fn test_transient_errors_are_busy() {
+        let busy = [
+            DbErr::ConnectionAcquire(ConnAcquireErr::Timeout),
+            DbErr::Exec(RuntimeErr::SqlxError(SqlxError::PoolTimedOut)),
+            DbErr::Query(RuntimeErr::SqlxError(SqlxError::Io(
+                std::io::ErrorKind::ConnectionReset.into(),
+            ))),
+        ];
+        for e in &busy {
+            let error = query_error("Failed to create node", e);
+            assert!(matches!(error, DataStoreError::Busy { .. }), "{e}");
+            assert!(error.is_retryable());
+        }
+
+        // The message alone does not make an error transient
+        let error = query_error(
+            "Failed to create node",
+            &DbErr::Custom("database is locked".to_string()),
+        );
+        assert!(matches!(error, DataStoreError::InternalError { .. }));
+        let error = transaction_error("Failed to commit transaction", &DbErr::RecordNotUpdated);
+        assert!(matches!(error, DataStoreError::TransactionError { .. }));
+        assert!(!error.is_retryable());
+    }
+}
@@ -13,10 +13,11 @@ mod test_helpers;
 mod tests;
 
 use super::super::types::{
-    DataStoreError, DataStoreResult, Filter, FilterValue, Sort, SortDirection,
+    DataStoreError, DataStoreResult, Filter, FilterOperation, FilterValue, Sort, SortDirection,
 };
 use crate::entities::{links, locations, nodes};
-use sea_orm::{ColumnTrait, QueryFilter, QueryOrder};
+use crate::models::{ProvisioningState, ScopedIpAddr};
+use sea_orm::{ColumnTrait, Condition, QueryFilter, QueryOrder};
 
 /// Apply filters to a node query
 pub fn apply_node_filters(
@@ -65,6 +66,25 @@ pub fn apply_node_filters(
                     });
                 }
             },
+            "management_ip" => match &filter.value {
+                FilterValue::String(s) => {
+                    // Match the canonical form so `2001:DB8::1` finds `2001:db8::1`
+                    let address = s
+                        .parse::<ScopedIpAddr>()
+                        .map_err(|message| DataStoreError::ValidationError { message })?;
+                    let ip = address.ip.to_string();
+                    query = query.filter(
+                        Condition::any()
+                            .add(nodes::Column::ManagementIp.eq(ip.as_str()))
+                            .add(nodes::Column::ManagementIpv6.eq(ip.as_str())),
+                    );
+                }
+                _ => {
+                    return Err(DataStoreError::ValidationError {
+                        message: "Management IP filter must be a string".to_string(),
+                    });
+                }
+            },
             _ => {
                 return Err(DataStoreError::ValidationError {
                     message: format!("Unsupported filter field: {}", filter.field),
@@ -234,6 +254,51 @@ pub fn apply_link_filters(
                     });
                 }
             },
+            "provider" => match &filter.value {
+                FilterValue::String(s) => {
+                    query = query.filter(links::Column::Provider.eq(s));
+                }
+                _ => {
+                    return Err(DataStoreError::ValidationError {
+                        message: "Provider filter must be a string".to_string(),
+                    });
+                }
+            },
+            "circuit_id" => match &filter.value {
+                FilterValue::String(s) => {
+                    query = query.filter(links::Column::CircuitId.contains(s));
+                }
+                _ => {
+                    return Err(DataStoreError::ValidationError {
+                        message: "Circuit ID filter must be a string".to_string(),
+                    });
+                }
+            },
+            "provisioning_state" => match &filter.value {
+                FilterValue::String(s) => {
+                    let state = s
+                        .parse::<ProvisioningState>()
+                        .map_err(|message| DataStoreError::ValidationError { message })?;
+                    let mut condition = Condition::any()
+                        .add(links::Column::ProvisioningState.eq(state.to_string()));
+                    // Links stored before provisioning states existed are live
+                    if state == ProvisioningState::Live {
+                        condition = condition.add(links::Column::ProvisioningState.is_null());
+                    }
+                    query = query.filter(condition);
+                }
+                _ => {
+                    return Err(DataStoreError::ValidationError {
+                        message: "Provisioning state filter must be a string".to_string(),
+                    });
+                }
+            },
+            "ordered_on" => {
+                query = query.filter(date_condition(links::Column::OrderedOn, filter)?);
+            }
+            "delivered_on" => {
+                query = query.filter(date_condition(links::Column::DeliveredOn, filter)?);
+            }
             _ => {
                 return Err(DataStoreError::ValidationError {
                     message: format!("Unsupported filter field: {}", filter.field),
@@ -244,6 +309,34 @@ pub fn apply_link_filters(
     Ok(query)
 }
 
+/// Compares a `YYYY-MM-DD` date column with a date filter
+fn date_condition(column: links::Column, filter: &Filter) -> DataStoreResult<Condition> {
+    let FilterValue::String(date) = &filter.value else {
+        return Err(DataStoreError::ValidationError {
+            message: format!("{} filter must be a date string", filter.field),
+        });
+    };
+    let date = date
+        .parse::<chrono::NaiveDate>()
+        .map_err(|e| DataStoreError::ValidationError {
+            message: format!("Invalid {} date {date:?}: {e}", filter.field),
+        })?
+        .to_string();
+    let expr = match filter.operation {
+        FilterOperation::Equals => column.eq(date),
+        FilterOperation::LessThan => column.lt(date),
+        FilterOperation::LessThanOrEqual => column.lte(date),
+        FilterOperation::GreaterThan => column.gt(date),
+        FilterOperation::GreaterThanOrEqual => column.gte(date),
+        _ => {
+            return Err(DataStoreError::ValidationError {
+                message: format!("Unsupported {} filter operation", filter.field),
+            });
+        }
+    };
+    Ok(Condition::all().add(expr))
+}
+
 /// Apply sorting to a link query
 pub fn apply_link_sorting(
     mut query: sea_orm::Select<links::Entity>,
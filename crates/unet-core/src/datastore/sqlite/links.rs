@@ -4,13 +4,17 @@ use super::super::types::{
     BatchOperation, BatchResult, DataStoreError, DataStoreResult, PagedResult, QueryOptions,
 };
 use super::SqliteStore;
+use super::change_events::LoggedWrite;
 use super::conversions::entity_to_link;
+use super::errors::query_error;
 use super::filters::{apply_link_filters, apply_link_sorting};
+use crate::change_log::{ChangeType, EntityKind};
 use crate::entities::links;
 use crate::models::Link;
 use chrono::Utc;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QuerySelect, Set,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, PaginatorTrait, QueryFilter,
+    QuerySelect, Set,
 };
 use uuid::Uuid;
 
@@ -26,40 +30,50 @@ pub async fn create_link(store: &SqliteStore, link: &Link) -> DataStoreResult<Li
         capacity: Set(link.bandwidth.map(|b| b.try_into().unwrap_or(i64::MAX))),
         utilization: Set(None), // Not in Link model yet
         is_internet_circuit: Set(i32::from(link.is_internet_circuit)),
-        circuit_id: Set(None), // Not in Link model yet
-        provider: Set(None),   // Not in Link model yet
+        circuit_id: Set(link.circuit_id.clone()),
+        provider: Set(link.provider.clone()),
         description: Set(link.description.clone()),
         custom_data: Set(Some(
             serde_json::to_string(&link.custom_data).unwrap_or_default(),
         )),
         created_at: Set(Utc::now().to_rfc3339()),
         updated_at: Set(Utc::now().to_rfc3339()),
+        provisioning_state: Set(Some(link.provisioning_state.to_string())),
+        ordered_on: Set(link.ordered_on.map(|date| date.to_string())),
+        delivered_on: Set(link.delivered_on.map(|date| date.to_string())),
     };
 
+    let write = LoggedWrite::begin(store).await?;
     active_link
-        .insert(&store.db)
+        .insert(write.db())
         .await
-        .map_err(|e| DataStoreError::InternalError {
-            message: format!("Failed to create link: {e}"),
-        })?;
+        .map_err(|e| query_error("Failed to create link", &e))?;
 
     // Convert back to Link model
-    get_link(store, &link.id)
-        .await?
-        .ok_or_else(|| DataStoreError::NotFound {
-            entity_type: "Link".to_string(),
-            id: link.id.to_string(),
-        })
+    let created = find_link_required(write.db(), &link.id).await?;
+    write
+        .commit(
+            EntityKind::Link,
+            link.id,
+            ChangeType::Created,
+            None,
+            &created,
+        )
+        .await?;
+    Ok(created)
 }
 
 /// Gets a link by ID
 pub async fn get_link(store: &SqliteStore, id: &Uuid) -> DataStoreResult<Option<Link>> {
+    find_link(&store.db, id).await
+}
+
+/// Gets a link by ID on a connection or transaction
+async fn find_link<C: ConnectionTrait>(db: &C, id: &Uuid) -> DataStoreResult<Option<Link>> {
     let entity = links::Entity::find_by_id(id.to_string())
-        .one(&store.db)
+        .one(db)
         .await
-        .map_err(|e| DataStoreError::InternalError {
-            message: format!("Failed to query link: {e}"),
-        })?;
+        .map_err(|e| query_error("Failed to query link", &e))?;
 
     match entity {
         Some(e) => Ok(Some(entity_to_link(e)?)),
@@ -67,6 +81,15 @@ pub async fn get_link(store: &SqliteStore, id: &Uuid) -> DataStoreResult<Option<
     }
 }
 
+async fn find_link_required<C: ConnectionTrait>(db: &C, id: &Uuid) -> DataStoreResult<Link> {
+    find_link(db, id)
+        .await?
+        .ok_or_else(|| DataStoreError::NotFound {
+            entity_type: "Link".to_string(),
+            id: id.to_string(),
+        })
+}
+
 /// Lists links with filtering, sorting, and pagination
 pub async fn list_links(
     store: &SqliteStore,
@@ -79,14 +102,11 @@ pub async fn list_links(
     query = apply_link_sorting(query, &options.sort)?;
 
     // Get total count
-    let total_count =
-        query
-            .clone()
-            .count(&store.db)
-            .await
-            .map_err(|e| DataStoreError::InternalError {
-                message: format!("Failed to count links: {e}"),
-            })?;
+    let total_count = query
+        .clone()
+        .count(&store.db)
+        .await
+        .map_err(|e| query_error("Failed to count links", &e))?;
 
     // Apply pagination
     if let Some(pagination) = &options.pagination {
@@ -99,9 +119,7 @@ pub async fn list_links(
     let entities = query
         .all(&store.db)
         .await
-        .map_err(|e| DataStoreError::InternalError {
-            message: format!("Failed to query links: {e}"),
-        })?;
+        .map_err(|e| query_error("Failed to query links", &e))?;
 
     // Convert entities to Link models
     let links = entities
@@ -118,6 +136,8 @@ pub async fn list_links(
 
 /// Updates an existing link
 pub async fn update_link(store: &SqliteStore, link: &Link) -> DataStoreResult<Link> {
+    let write = LoggedWrite::begin(store).await?;
+    let before = find_link_required(write.db(), &link.id).await?;
     let active_link = links::ActiveModel {
         id: Set(link.id.to_string()),
         name: Set(link.name.clone()),
@@ -128,49 +148,49 @@ pub async fn update_link(store: &SqliteStore, link: &Link) -> DataStoreResult<Li
         capacity: Set(link.bandwidth.map(|b| b.try_into().unwrap_or(i64::MAX))),
         utilization: Set(None), // Not in Link model yet
         is_internet_circuit: Set(i32::from(link.is_internet_circuit)),
-        circuit_id: Set(None), // Not in Link model yet
-        provider: Set(None),   // Not in Link model yet
+        circuit_id: Set(link.circuit_id.clone()),
+        provider: Set(link.provider.clone()),
         description: Set(link.description.clone()),
         custom_data: Set(Some(
             serde_json::to_string(&link.custom_data).unwrap_or_default(),
         )),
         created_at: Set(Utc::now().to_rfc3339()),
         updated_at: Set(Utc::now().to_rfc3339()),
+        provisioning_state: Set(Some(link.provisioning_state.to_string())),
+        ordered_on: Set(link.ordered_on.map(|date| date.to_string())),
+        delivered_on: Set(link.delivered_on.map(|date| date.to_string())),
     };
 
     active_link
-        .update(&store.db)
+        .update(write.db())
         .await
-        .map_err(|e| DataStoreError::InternalError {
-            message: format!("Failed to update link: {e}"),
-        })?;
+        .map_err(|e| query_error("Failed to update link", &e))?;
 
     // Convert back to Link model
-    get_link(store, &link.id)
-        .await?
-        .ok_or_else(|| DataStoreError::NotFound {
-            entity_type: "Link".to_string(),
-            id: link.id.to_string(),
-        })
+    let updated = find_link_required(write.db(), &link.id).await?;
+    write
+        .commit(
+            EntityKind::Link,
+            link.id,
+            ChangeType::Updated,
+            Some(&before),
+            &updated,
+        )
+        .await?;
+    Ok(updated)
 }
 
 /// Deletes a link by ID
 pub async fn delete_link(store: &SqliteStore, id: &Uuid) -> DataStoreResult<()> {
-    let result = links::Entity::delete_by_id(id.to_string())
-        .exec(&store.db)
+    let write = LoggedWrite::begin(store).await?;
+    let stored = find_link_required(write.db(), id).await?;
+    links::Entity::delete_by_id(id.to_string())
+        .exec(write.db())
+        .await
+        .map_err(|e| query_error("Failed to delete link", &e))?;
+    write
+        .commit(EntityKind::Link, *id, ChangeType::Deleted, None, &stored)
         .await
-        .map_err(|e| DataStoreError::InternalError {
-            message: format!("Failed to delete link: {e}"),
-        })?;
-
-    if result.rows_affected == 0 {
-        return Err(DataStoreError::NotFound {
-            entity_type: "Link".to_string(),
-            id: id.to_string(),
-        });
-    }
-
-    Ok(())
 }
 
 /// Gets links that involve a specific node
@@ -183,9 +203,7 @@ pub async fn get_links_for_node(store: &SqliteStore, node_id: &Uuid) -> DataStor
         )
         .all(&store.db)
         .await
-        .map_err(|e| DataStoreError::InternalError {
-            message: format!("Failed to query links for node: {e}"),
-        })?;
+        .map_err(|e| query_error("Failed to query links for node", &e))?;
 
     entities
         .into_iter()
@@ -210,9 +228,7 @@ pub async fn get_links_between_nodes(
         )
         .all(&store.db)
         .await
-        .map_err(|e| DataStoreError::InternalError {
-            message: format!("Failed to query links between nodes: {e}"),
-        })?;
+        .map_err(|e| query_error("Failed to query links between nodes", &e))?;
 
     entities
         .into_iter()
@@ -4,11 +4,14 @@
 
 use super::super::super::types::{DataStoreError, DataStoreResult};
 use super::super::SqliteStore;
+use super::super::change_events::LoggedWrite;
 use super::super::conversions::entity_to_location;
+use super::super::errors::query_error;
+use crate::change_log::{ChangeType, EntityKind};
 use crate::entities::locations;
 use crate::models::Location;
 use chrono::Utc;
-use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+use sea_orm::{ActiveModelTrait, ConnectionTrait, DbErr, EntityTrait, Set};
 use uuid::Uuid;
 
 /// Creates a new location
@@ -24,38 +27,50 @@ pub async fn create_location(
         parent_id: Set(location.parent_id.map(|id| id.to_string())),
         description: Set(location.description.clone()),
         address: Set(location.address.clone()),
-        coordinates: Set(None), // Not in Location model yet
+        coordinates: Set(location.coordinates.map(|c| c.to_string())),
         custom_data: Set(Some(
             serde_json::to_string(&location.custom_data).unwrap_or_default(),
         )),
         created_at: Set(Utc::now().to_rfc3339()),
         updated_at: Set(Utc::now().to_rfc3339()),
+        postal_address: Set(location
+            .postal_address
+            .as_ref()
+            .map(|address| serde_json::to_string(address).unwrap_or_default())),
+        timezone: Set(location.timezone.clone()),
     };
 
+    let write = LoggedWrite::begin(store).await?;
     active_location
-        .insert(&store.db)
+        .insert(write.db())
         .await
-        .map_err(|e| DataStoreError::InternalError {
-            message: format!("Failed to create location: {e}"),
-        })?;
+        .map_err(|e| query_error("Failed to create location", &e))?;
 
     // Convert back to Location model
-    get_location(store, &location.id)
-        .await?
-        .ok_or_else(|| DataStoreError::NotFound {
-            entity_type: "Location".to_string(),
-            id: location.id.to_string(),
-        })
+    let created = find_location_required(write.db(), &location.id).await?;
+    write
+        .commit(
+            EntityKind::Location,
+            location.id,
+            ChangeType::Created,
+            None,
+            &created,
+        )
+        .await?;
+    Ok(created)
 }
 
 /// Gets a location by ID
 pub async fn get_location(store: &SqliteStore, id: &Uuid) -> DataStoreResult<Option<Location>> {
+    find_location(&store.db, id).await
+}
+
+/// Gets a location by ID on a connection or transaction
+async fn find_location<C: ConnectionTrait>(db: &C, id: &Uuid) -> DataStoreResult<Option<Location>> {
     let entity = locations::Entity::find_by_id(id.to_string())
-        .one(&store.db)
+        .one(db)
         .await
-        .map_err(|e| DataStoreError::InternalError {
-            message: format!("Failed to query location: {e}"),
-        })?;
+        .map_err(|e| query_error("Failed to query location", &e))?;
 
     match entity {
         Some(e) => Ok(Some(entity_to_location(e)?)),
@@ -63,11 +78,25 @@ pub async fn get_location(store: &SqliteStore, id: &Uuid) -> DataStoreResult<Opt
     }
 }
 
+async fn find_location_required<C: ConnectionTrait>(
+    db: &C,
+    id: &Uuid,
+) -> DataStoreResult<Location> {
+    find_location(db, id)
+        .await?
+        .ok_or_else(|| DataStoreError::NotFound {
+            entity_type: "Location".to_string(),
+            id: id.to_string(),
+        })
+}
+
 /// Updates an existing location
 pub async fn update_location(
     store: &SqliteStore,
     location: &Location,
 ) -> DataStoreResult<Location> {
+    let write = LoggedWrite::begin(store).await?;
+    let before = find_location_required(write.db(), &location.id).await?;
     let active_location = locations::ActiveModel {
         id: Set(location.id.to_string()),
         name: Set(location.name.clone()),
@@ -76,52 +105,59 @@ pub async fn update_location(
         parent_id: Set(location.parent_id.map(|id| id.to_string())),
         description: Set(location.description.clone()),
         address: Set(location.address.clone()),
-        coordinates: Set(None), // Not in Location model yet
+        coordinates: Set(location.coordinates.map(|c| c.to_string())),
         custom_data: Set(Some(
             serde_json::to_string(&location.custom_data).unwrap_or_default(),
         )),
         created_at: Set(Utc::now().to_rfc3339()),
         updated_at: Set(Utc::now().to_rfc3339()),
+        postal_address: Set(location
+            .postal_address
+            .as_ref()
+            .map(|address| serde_json::to_string(address).unwrap_or_default())),
+        timezone: Set(location.timezone.clone()),
     };
 
-    active_location.update(&store.db).await.map_err(|e| {
-        let error_msg = e.to_string();
-        if error_msg.contains("None of the records are updated") {
-            DataStoreError::NotFound {
+    active_location
+        .update(write.db())
+        .await
+        .map_err(|e| match e {
+            DbErr::RecordNotUpdated => DataStoreError::NotFound {
                 entity_type: "Location".to_string(),
                 id: location.id.to_string(),
-            }
-        } else {
-            DataStoreError::InternalError {
-                message: format!("Failed to update location: {e}"),
-            }
-        }
-    })?;
+            },
+            e => query_error("Failed to update location", &e),
+        })?;
 
     // Convert back to Location model
-    get_location(store, &location.id)
-        .await?
-        .ok_or_else(|| DataStoreError::NotFound {
-            entity_type: "Location".to_string(),
-            id: location.id.to_string(),
-        })
+    let updated = find_location_required(write.db(), &location.id).await?;
+    write
+        .commit(
+            EntityKind::Location,
+            location.id,
+            ChangeType::Updated,
+            Some(&before),
+            &updated,
+        )
+        .await?;
+    Ok(updated)
 }
 
 /// Deletes a location by ID
 pub async fn delete_location(store: &SqliteStore, id: &Uuid) -> DataStoreResult<()> {
-    let result = locations::Entity::delete_by_id(id.to_string())
-        .exec(&store.db)
+    let write = LoggedWrite::begin(store).await?;
+    let stored = find_location_required(write.db(), id).await?;
+    locations::Entity::delete_by_id(id.to_string())
+        .exec(write.db())
+        .await
+        .map_err(|e| query_error("Failed to delete location", &e))?;
+    write
+        .commit(
+            EntityKind::Location,
+            *id,
+            ChangeType::Deleted,
+            None,
+            &stored,
+        )
         .await
-        .map_err(|e| DataStoreError::InternalError {
-            message: format!("Failed to delete location: {e}"),
-        })?;
-
-    if result.rows_affected == 0 {
-        return Err(DataStoreError::NotFound {
-            entity_type: "Location".to_string(),
-            id: id.to_string(),
-        });
-    }
-
-    Ok(())
 }
@@ -16,7 +16,7 @@ mod location_tests {
         QueryOptions, Sort, SortDirection,
     };
     use crate::models::Location;
-    use crate::models::location::LocationBuilder;
+    use crate::models::location::{Coordinates, LocationBuilder, PostalAddress};
     use serde_json::json;
     use uuid::Uuid;
 
@@ -55,6 +55,34 @@ mod location_tests {
         assert_eq!(found.unwrap().id, location.id);
     }
 
+    #[tokio::test]
+    async fn test_location_address_coordinates_and_timezone_round_trip() {
+        let test_db = setup_test_db().await;
+        let location = LocationBuilder::new()
+            .name("Frankfurt DC")
+            .location_type("datacenter")
+            .postal_address(PostalAddress {
+                street: Some("Hanauer Landstrasse 300".to_string()),
+                city: Some("Frankfurt".to_string()),
+                country: Some("DE".to_string()),
+                ..PostalAddress::default()
+            })
+            .coordinates(Coordinates::new(50.1187, 8.7195).unwrap())
+            .timezone("Europe/Berlin")
+            .build()
+            .unwrap();
+
+        let created = create_location(&test_db.store, &location).await.unwrap();
+        assert_eq!(created, location);
+
+        let mut moved = created;
+        moved.postal_address = None;
+        moved.coordinates = None;
+        moved.timezone = Some("Europe/Amsterdam".to_string());
+        let updated = update_location(&test_db.store, &moved).await.unwrap();
+        assert_eq!(updated, moved);
+    }
+
     #[tokio::test]
     async fn test_get_location_not_found() {
         let test_db = setup_test_db().await;
@@ -0,0 +1,255 @@
+//! Maintenance operations for the `SQLite` datastore
+
+use super::SqliteStore;
+use crate::datastore::types::{DataStoreError, DataStoreResult};
+use crate::entities::{
+    interface_status, node_status, nodes, performance_rollups, performance_samples, policy_results,
+    polling_tasks,
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+use sea_orm::sea_query::Query;
+use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, PaginatorTrait, QueryFilter};
+use std::collections::HashMap;
+
+pub async fn vacuum(store: &SqliteStore) -> DataStoreResult<()> {
+    execute(store, "VACUUM").await
+}
+
+pub async fn analyze(store: &SqliteStore) -> DataStoreResult<()> {
+    execute(store, "ANALYZE").await
+}
+
+pub async fn cleanup_orphaned_records(
+    store: &SqliteStore,
+) -> DataStoreResult<HashMap<String, usize>> {
+    let node_ids = || {
+        Query::select()
+            .column(nodes::Column::Id)
+            .from(nodes::Entity)
+            .to_owned()
+    };
+    let mut deleted = HashMap::new();
+
+    let result = node_status::Entity::delete_many()
+        .filter(node_status::Column::NodeId.not_in_subquery(node_ids()))
+        .exec(&store.db)
+        .await;
+    deleted.insert(
+        "node_status".to_string(),
+        rows_affected(result, "node_status")?,
+    );
+
+    // Runs after the node_status cleanup so it also catches their interfaces
+    let result = interface_status::Entity::delete_many()
+        .filter(
+            interface_status::Column::NodeStatusId.not_in_subquery(
+                Query::select()
+                    .column(node_status::Column::Id)
+                    .from(node_status::Entity)
+                    .to_owned(),
+            ),
+        )
+        .exec(&store.db)
+        .await;
+    deleted.insert(
+        "interface_status".to_string(),
+        rows_affected(result, "interface_status")?,
+    );
+
+    let result = polling_tasks::Entity::delete_many()
+        .filter(polling_tasks::Column::NodeId.not_in_subquery(node_ids()))
+        .exec(&store.db)
+        .await;
+    deleted.insert(
+        "polling_tasks".to_string(),
+        rows_affected(result, "polling_tasks")?,
+    );
+
+    let result = policy_results::Entity::delete_many()
+        .filter(policy_results::Column::NodeId.not_in_subquery(node_ids()))
+        .exec(&store.db)
+        .await;
+    deleted.insert(
+        "policy_result".to_string(),
+        rows_affected(result, "policy_result")?,
+    );
+
+    let result = performance_samples::Entity::delete_many()
+        .filter(performance_samples::Column::NodeId.not_in_subquery(node_ids()))
+        .exec(&store.db)
+        .await;
+    deleted.insert(
+        "performance_sample".to_string(),
+        rows_affected(result, "performance_sample")?,
+    );
+
+    let result = performance_rollups::Entity::delete_many()
+        .filter(performance_rollups::Column::NodeId.not_in_subquery(node_ids()))
+        .exec(&store.db)
+        .await;
+    deleted.insert(
+        "performance_rollup".to_string(),
+        rows_affected(result, "performance_rollup")?,
+    );
+
+    Ok(deleted)
+}
+
+/// Counts the rows [`cleanup_orphaned_records`] would delete, under the same
+/// table names
+pub async fn count_orphaned_records(
+    store: &SqliteStore,
+) -> DataStoreResult<HashMap<String, usize>> {
+    let node_ids = || {
+        Query::select()
+            .column(nodes::Column::Id)
+            .from(nodes::Entity)
+            .to_owned()
+    };
+    let mut counts = HashMap::new();
+
+    let result = node_status::Entity::find()
+        .filter(node_status::Column::NodeId.not_in_subquery(node_ids()))
+        .count(&store.db)
+        .await;
+    counts.insert("node_status".to_string(), row_count(result, "node_status")?);
+
+    // Interfaces of orphaned statuses count too, since cleanup deletes them
+    let result = interface_status::Entity::find()
+        .filter(
+            interface_status::Column::NodeStatusId.not_in_subquery(
+                Query::select()
+                    .column(node_status::Column::Id)
+                    .from(node_status::Entity)
+                    .and_where(node_status::Column::NodeId.in_subquery(node_ids()))
+                    .to_owned(),
+            ),
+        )
+        .count(&store.db)
+        .await;
+    counts.insert(
+        "interface_status".to_string(),
+        row_count(result, "interface_status")?,
+    );
+
+    let result = polling_tasks::Entity::find()
+        .filter(polling_tasks::Column::NodeId.not_in_subquery(node_ids()))
+        .count(&store.db)
+        .await;
+    counts.insert(
+        "polling_tasks".to_string(),
+        row_count(result, "polling_tasks")?,
+    );
+
+    let result = policy_results::Entity::find()
+        .filter(policy_results::Column::NodeId.not_in_subquery(node_ids()))
+        .count(&store.db)
+        .await;
+    counts.insert(
+        "policy_result".to_string(),
+        row_count(result, "policy_result")?,
+    );
+
+    let result = performance_samples::Entity::find()
+        .filter(performance_samples::Column::NodeId.not_in_subquery(node_ids()))
+        .count(&store.db)
+        .await;
+    counts.insert(
+        "performance_sample".to_string(),
+        row_count(result, "performance_sample")?,
+    );
+
+    let result = performance_rollups::Entity::find()
+        .filter(performance_rollups::Column::NodeId.not_in_subquery(node_ids()))
+        .count(&store.db)
+        .await;
+    counts.insert(
+        "performance_rollup".to_string(),
+        row_count(result, "performance_rollup")?,
+    );
+
+    Ok(counts)
+}
+
+pub async fn prune_derived_state(
+    store: &SqliteStore,
+    cutoff: DateTime<Utc>,
+) -> DataStoreResult<HashMap<String, usize>> {
+    let cutoff_seconds = cutoff.timestamp();
+    let cutoff = cutoff.to_rfc3339_opts(SecondsFormat::Secs, true);
+    let mut deleted = HashMap::new();
+
+    let result = interface_status::Entity::delete_many()
+        .filter(
+            interface_status::Column::NodeStatusId.in_subquery(
+                Query::select()
+                    .column(node_status::Column::Id)
+                    .from(node_status::Entity)
+                    .and_where(node_status::Column::LastUpdated.lt(cutoff.clone()))
+                    .to_owned(),
+            ),
+        )
+        .exec(&store.db)
+        .await;
+    deleted.insert(
+        "interface_status".to_string(),
+        rows_affected(result, "interface_status")?,
+    );
+
+    let result = node_status::Entity::delete_many()
+        .filter(node_status::Column::LastUpdated.lt(cutoff))
+        .exec(&store.db)
+        .await;
+    deleted.insert(
+        "node_status".to_string(),
+        rows_affected(result, "node_status")?,
+    );
+
+    let result = performance_samples::Entity::delete_many()
+        .filter(performance_samples::Column::RecordedAt.lt(cutoff_seconds))
+        .exec(&store.db)
+        .await;
+    deleted.insert(
+        "performance_sample".to_string(),
+        rows_affected(result, "performance_sample")?,
+    );
+
+    Ok(deleted)
+}
+
+async fn execute(store: &SqliteStore, sql: &str) -> DataStoreResult<()> {
+    store
+        .db
+        .execute_unprepared(sql)
+        .await
+        .map(|_| ())
+        .map_err(|e| DataStoreError::InternalError {
+            message: format!("Failed to run {sql}: {e}"),
+        })
+}
+
+fn rows_affected(
+    result: Result<sea_orm::DeleteResult, sea_orm::DbErr>,
+    label: &str,
+) -> DataStoreResult<usize> {
+    result
+        .map_err(|e| DataStoreError::InternalError {
+            message: format!("Failed to delete {label} rows: {e}"),
+        })?
+        .rows_affected
+        .try_into()
+        .map_err(|e| DataStoreError::InternalError {
+            message: format!("Failed to convert deleted row count for {label}: {e}"),
+        })
+}
+
+fn row_count(result: Result<u64, sea_orm::DbErr>, label: &str) -> DataStoreResult<usize> {
+    result
+        .map_err(|e| DataStoreError::InternalError {
+            message: format!("Failed to count orphaned {label} rows: {e}"),
+        })?
+        .try_into()
+        .map_err(|e| DataStoreError::InternalError {
+            message: format!("Failed to convert orphaned row count for {label}: {e}"),
+        })
+}
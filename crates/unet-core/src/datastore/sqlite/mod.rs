@@ -1,18 +1,27 @@
 //! SQLite-based `DataStore` implementation using `SeaORM`
 
+pub use encryption::{encrypt_database, verify_encryption_key};
 pub use store::SqliteStore;
 pub use transaction::SqliteTransaction;
 
+mod change_events;
 mod conversions;
 mod derived_state;
+mod encryption;
+mod errors;
 mod filters;
 mod links;
 mod locations;
+mod maintenance;
 mod metadata;
 mod nodes;
+mod performance_history;
+mod policy_results;
+mod settings;
+mod shard_leases;
 mod store;
 mod transaction;
 mod vendors;
 
 #[cfg(test)]
-mod tests;
+pub(crate) mod tests;
@@ -4,13 +4,17 @@ use super::super::types::{
     BatchOperation, BatchResult, DataStoreError, DataStoreResult, PagedResult, QueryOptions,
 };
 use super::SqliteStore;
+use super::change_events::LoggedWrite;
 use super::conversions::entity_to_node;
+use super::errors::query_error;
 use super::filters::{apply_node_filters, apply_node_sorting};
+use crate::change_log::{ChangeType, EntityKind};
 use crate::entities::nodes;
 use crate::models::Node;
 use chrono::Utc;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QuerySelect, Set,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, PaginatorTrait,
+    QueryFilter, QuerySelect, Set,
 };
 use uuid::Uuid;
 
@@ -35,32 +39,41 @@ pub async fn create_node(store: &SqliteStore, node: &Node) -> DataStoreResult<No
         )),
         created_at: Set(Utc::now().to_rfc3339()),
         updated_at: Set(Utc::now().to_rfc3339()),
+        management_ipv6: Set(node.management_ipv6.map(|ip| ip.to_string())),
+        management_zone: Set(node.management_zone.clone()),
     };
 
+    let write = LoggedWrite::begin(store).await?;
     active_node
-        .insert(&store.db)
+        .insert(write.db())
         .await
-        .map_err(|e| DataStoreError::InternalError {
-            message: format!("Failed to create node: {e}"),
-        })?;
+        .map_err(|e| query_error("Failed to create node", &e))?;
 
     // Convert back to Node model
-    get_node(store, &node.id)
-        .await?
-        .ok_or_else(|| DataStoreError::NotFound {
-            entity_type: "Node".to_string(),
-            id: node.id.to_string(),
-        })
+    let created = find_node_required(write.db(), &node.id).await?;
+    write
+        .commit(
+            EntityKind::Node,
+            node.id,
+            ChangeType::Created,
+            None,
+            &created,
+        )
+        .await?;
+    Ok(created)
 }
 
 /// Gets a node by ID
 pub async fn get_node(store: &SqliteStore, id: &Uuid) -> DataStoreResult<Option<Node>> {
+    find_node(&store.db, id).await
+}
+
+/// Gets a node by ID on a connection or transaction
+async fn find_node<C: ConnectionTrait>(db: &C, id: &Uuid) -> DataStoreResult<Option<Node>> {
     let entity = nodes::Entity::find_by_id(id.to_string())
-        .one(&store.db)
+        .one(db)
         .await
-        .map_err(|e| DataStoreError::InternalError {
-            message: format!("Failed to query node: {e}"),
-        })?;
+        .map_err(|e| query_error("Failed to query node", &e))?;
 
     match entity {
         Some(e) => Ok(Some(entity_to_node(e)?)),
@@ -68,6 +81,15 @@ pub async fn get_node(store: &SqliteStore, id: &Uuid) -> DataStoreResult<Option<
     }
 }
 
+async fn find_node_required<C: ConnectionTrait>(db: &C, id: &Uuid) -> DataStoreResult<Node> {
+    find_node(db, id)
+        .await?
+        .ok_or_else(|| DataStoreError::NotFound {
+            entity_type: "Node".to_string(),
+            id: id.to_string(),
+        })
+}
+
 /// Lists nodes with filtering, sorting, and pagination
 pub async fn list_nodes(
     store: &SqliteStore,
@@ -80,14 +102,11 @@ pub async fn list_nodes(
     query = apply_node_sorting(query, &options.sort)?;
 
     // Get total count
-    let total_count =
-        query
-            .clone()
-            .count(&store.db)
-            .await
-            .map_err(|e| DataStoreError::InternalError {
-                message: format!("Failed to count nodes: {e}"),
-            })?;
+    let total_count = query
+        .clone()
+        .count(&store.db)
+        .await
+        .map_err(|e| query_error("Failed to count nodes", &e))?;
 
     // Apply pagination
     if let Some(pagination) = &options.pagination {
@@ -100,9 +119,7 @@ pub async fn list_nodes(
     let entities = query
         .all(&store.db)
         .await
-        .map_err(|e| DataStoreError::InternalError {
-            message: format!("Failed to query nodes: {e}"),
-        })?;
+        .map_err(|e| query_error("Failed to query nodes", &e))?;
 
     // Convert entities to Node models
     let nodes = entities
@@ -119,6 +136,8 @@ pub async fn list_nodes(
 
 /// Updates an existing node
 pub async fn update_node(store: &SqliteStore, node: &Node) -> DataStoreResult<Node> {
+    let write = LoggedWrite::begin(store).await?;
+    let before = find_node_required(write.db(), &node.id).await?;
     let active_node = nodes::ActiveModel {
         id: Set(node.id.to_string()),
         name: Set(node.name.clone()),
@@ -138,52 +157,43 @@ pub async fn update_node(store: &SqliteStore, node: &Node) -> DataStoreResult<No
         )),
         created_at: Set(Utc::now().to_rfc3339()),
         updated_at: Set(Utc::now().to_rfc3339()),
+        management_ipv6: Set(node.management_ipv6.map(|ip| ip.to_string())),
+        management_zone: Set(node.management_zone.clone()),
     };
 
-    let update_result = active_node.update(&store.db).await;
-    match update_result {
-        Ok(_) => {}
-        Err(e) => {
-            // Check if the error indicates no records were updated
-            let error_msg = e.to_string();
-            if error_msg.contains("None of the records are updated") {
-                return Err(DataStoreError::NotFound {
-                    entity_type: "Node".to_string(),
-                    id: node.id.to_string(),
-                });
-            }
-            return Err(DataStoreError::InternalError {
-                message: format!("Failed to update node: {e}"),
-            });
-        }
-    }
-
-    // Convert back to Node model
-    get_node(store, &node.id)
-        .await?
-        .ok_or_else(|| DataStoreError::NotFound {
+    active_node.update(write.db()).await.map_err(|e| match e {
+        DbErr::RecordNotUpdated => DataStoreError::NotFound {
             entity_type: "Node".to_string(),
             id: node.id.to_string(),
-        })
+        },
+        e => query_error("Failed to update node", &e),
+    })?;
+
+    // Convert back to Node model
+    let updated = find_node_required(write.db(), &node.id).await?;
+    write
+        .commit(
+            EntityKind::Node,
+            node.id,
+            ChangeType::Updated,
+            Some(&before),
+            &updated,
+        )
+        .await?;
+    Ok(updated)
 }
 
 /// Deletes a node by ID
 pub async fn delete_node(store: &SqliteStore, id: &Uuid) -> DataStoreResult<()> {
-    let result = nodes::Entity::delete_by_id(id.to_string())
-        .exec(&store.db)
+    let write = LoggedWrite::begin(store).await?;
+    let stored = find_node_required(write.db(), id).await?;
+    nodes::Entity::delete_by_id(id.to_string())
+        .exec(write.db())
+        .await
+        .map_err(|e| query_error("Failed to delete node", &e))?;
+    write
+        .commit(EntityKind::Node, *id, ChangeType::Deleted, None, &stored)
         .await
-        .map_err(|e| DataStoreError::InternalError {
-            message: format!("Failed to delete node: {e}"),
-        })?;
-
-    if result.rows_affected == 0 {
-        return Err(DataStoreError::NotFound {
-            entity_type: "Node".to_string(),
-            id: id.to_string(),
-        });
-    }
-
-    Ok(())
 }
 
 /// Gets nodes by location ID
@@ -195,9 +205,7 @@ pub async fn get_nodes_by_location(
         .filter(nodes::Column::LocationId.eq(location_id.to_string()))
         .all(&store.db)
         .await
-        .map_err(|e| DataStoreError::InternalError {
-            message: format!("Failed to query nodes by location: {e}"),
-        })?;
+        .map_err(|e| query_error("Failed to query nodes by location", &e))?;
 
     entities
         .into_iter()
@@ -217,9 +225,7 @@ pub async fn search_nodes_by_name(store: &SqliteStore, name: &str) -> DataStoreR
         .filter(nodes::Column::Name.contains(&escaped_name))
         .all(&store.db)
         .await
-        .map_err(|e| DataStoreError::InternalError {
-            message: format!("Failed to search nodes: {e}"),
-        })?;
+        .map_err(|e| query_error("Failed to search nodes", &e))?;
 
     entities
         .into_iter()
@@ -0,0 +1,402 @@
+//! Performance history operations for `SQLite` datastore
+//!
+//! Range queries are downsampled in SQL: samples are grouped into buckets of
+//! `step` seconds, aligned to the Unix epoch, and aggregated per bucket, so
+//! only one row per bucket leaves the database. Samples past their retention
+//! are compacted into hourly rollups, which are read alongside them.
+
+use super::super::types::{DataStoreError, DataStoreResult, PruneStats};
+use super::SqliteStore;
+use crate::entities::performance_samples;
+use crate::models::derived::{MetricPoint, MetricQuery, PerformanceMetrics};
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ConnectionTrait, DatabaseBackend, EntityTrait, Set, Statement, TransactionTrait, Value,
+};
+use uuid::Uuid;
+
+/// Width of a rollup bucket in seconds
+const ROLLUP_SECONDS: i64 = 3600;
+
+/// Estimated stored size of a sample row; the value and time are 8 bytes each
+const SAMPLE_BYTES: &str = "length(id) + length(node_id) + length(metric) + 16";
+
+/// Estimated stored size of a rollup row; the four numbers are 8 bytes each,
+/// the ID 32 hex digits
+const ROLLUP_BYTES: &str = "32 + length(node_id) + length(metric) + 32";
+
+/// Stores one sample per metric that is set
+pub async fn record_performance_metrics(
+    store: &SqliteStore,
+    node_id: &Uuid,
+    recorded_at: DateTime<Utc>,
+    metrics: &PerformanceMetrics,
+) -> DataStoreResult<()> {
+    let samples: Vec<_> = metrics
+        .history_samples()
+        .into_iter()
+        .map(|(metric, value)| performance_samples::ActiveModel {
+            id: Set(Uuid::new_v4().to_string()),
+            node_id: Set(node_id.to_string()),
+            metric: Set(metric.as_str().to_string()),
+            value: Set(value),
+            recorded_at: Set(recorded_at.timestamp()),
+        })
+        .collect();
+    if samples.is_empty() {
+        return Ok(());
+    }
+
+    performance_samples::Entity::insert_many(samples)
+        .exec(&store.db)
+        .await
+        .map_err(|e| DataStoreError::InternalError {
+            message: format!("Failed to record performance metrics for node {node_id}: {e}"),
+        })?;
+    Ok(())
+}
+
+/// Aggregates one metric of a node per bucket, oldest bucket first
+pub async fn query_performance_history(
+    store: &SqliteStore,
+    node_id: &Uuid,
+    query: &MetricQuery,
+) -> DataStoreResult<Vec<MetricPoint>> {
+    query
+        .validate()
+        .map_err(|message| DataStoreError::ValidationError { message })?;
+
+    let step = query.step.num_seconds();
+    let sql = format!(
+        "SELECT (recorded_at / ?) * ? AS bucket, CAST({} AS REAL) AS value FROM ( \
+         SELECT recorded_at, value AS total, 1 AS samples, value AS low, value AS high \
+         FROM performance_sample \
+         WHERE node_id = ? AND metric = ? AND recorded_at >= ? AND recorded_at <= ? \
+         UNION ALL \
+         SELECT bucket_start, total, samples, low, high FROM performance_rollup \
+         WHERE node_id = ? AND metric = ? AND bucket_start >= ? AND bucket_start <= ?) \
+         GROUP BY bucket ORDER BY bucket",
+        query.aggregation.sql_expression()
+    );
+    let range: [Value; 4] = [
+        node_id.to_string().into(),
+        query.metric.as_str().into(),
+        query.from.timestamp().into(),
+        query.to.timestamp().into(),
+    ];
+    let statement = Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        sql,
+        [step.into(), step.into()]
+            .into_iter()
+            .chain(range.clone())
+            .chain(range),
+    );
+    let rows = store
+        .db
+        .query_all(statement)
+        .await
+        .map_err(|e| DataStoreError::InternalError {
+            message: format!("Failed to query performance history for node {node_id}: {e}"),
+        })?;
+
+    let invalid_row = |e: sea_orm::DbErr| DataStoreError::InternalError {
+        message: format!("Invalid performance history row: {e}"),
+    };
+    rows.iter()
+        .map(|row| {
+            let bucket: i64 = row.try_get("", "bucket").map_err(invalid_row)?;
+            let value: f64 = row.try_get("", "value").map_err(invalid_row)?;
+            let timestamp = DateTime::from_timestamp(bucket, 0).ok_or_else(|| {
+                DataStoreError::InternalError {
+                    message: format!("Performance history bucket {bucket} is out of range"),
+                }
+            })?;
+            Ok(MetricPoint { timestamp, value })
+        })
+        .collect()
+}
+
+/// Compacts samples recorded before `cutoff`, rounded down to the hour, into
+/// hourly rollups and deletes them
+pub async fn prune_performance_samples(
+    store: &SqliteStore,
+    cutoff: DateTime<Utc>,
+    dry_run: bool,
+) -> DataStoreResult<PruneStats> {
+    let cutoff = cutoff.timestamp().div_euclid(ROLLUP_SECONDS) * ROLLUP_SECONDS;
+    let (rows, removed) = measure(
+        store,
+        format!(
+            "SELECT COUNT(*) AS row_count, COALESCE(SUM({SAMPLE_BYTES}), 0) AS byte_count \
+             FROM performance_sample WHERE recorded_at < ?"
+        ),
+        cutoff,
+    )
+    .await?;
+    let (_, added) = measure(
+        store,
+        format!(
+            "SELECT COUNT(*) AS row_count, COALESCE(SUM({ROLLUP_BYTES}), 0) AS byte_count \
+             FROM (SELECT node_id, metric FROM performance_sample WHERE recorded_at < ? \
+             GROUP BY node_id, metric, recorded_at / {ROLLUP_SECONDS})"
+        ),
+        cutoff,
+    )
+    .await?;
+    let stats = PruneStats {
+        rows,
+        bytes: removed.saturating_sub(added),
+    };
+    if dry_run || rows == 0 {
+        return Ok(stats);
+    }
+
+    let failed = |e: sea_orm::DbErr| DataStoreError::InternalError {
+        message: format!("Failed to roll up performance samples: {e}"),
+    };
+    let txn = store.db.begin().await.map_err(failed)?;
+    // Aggregated in SQL so the samples never leave the database
+    txn.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        format!(
+            "INSERT INTO performance_rollup \
+             (id, node_id, metric, bucket_start, total, samples, low, high) \
+             SELECT lower(hex(randomblob(16))), node_id, metric, \
+             (recorded_at / {ROLLUP_SECONDS}) * {ROLLUP_SECONDS} AS bucket, \
+             SUM(value), COUNT(*), MIN(value), MAX(value) \
+             FROM performance_sample WHERE recorded_at < ? \
+             GROUP BY node_id, metric, bucket"
+        ),
+        [cutoff.into()],
+    ))
+    .await
+    .map_err(failed)?;
+    txn.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "DELETE FROM performance_sample WHERE recorded_at < ?",
+        [cutoff.into()],
+    ))
+    .await
+    .map_err(failed)?;
+    txn.commit().await.map_err(failed)?;
+    Ok(stats)
+}
+
+/// Deletes rollups of hours that started before `cutoff`
+pub async fn prune_performance_rollups(
+    store: &SqliteStore,
+    cutoff: DateTime<Utc>,
+    dry_run: bool,
+) -> DataStoreResult<PruneStats> {
+    let cutoff = cutoff.timestamp();
+    let (rows, bytes) = measure(
+        store,
+        format!(
+            "SELECT COUNT(*) AS row_count, COALESCE(SUM({ROLLUP_BYTES}), 0) AS byte_count \
+             FROM performance_rollup WHERE bucket_start < ?"
+        ),
+        cutoff,
+    )
+    .await?;
+    if !dry_run && rows > 0 {
+        store
+            .db
+            .execute(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                "DELETE FROM performance_rollup WHERE bucket_start < ?",
+                [cutoff.into()],
+            ))
+            .await
+            .map_err(|e| DataStoreError::InternalError {
+                message: format!("Failed to delete performance rollups: {e}"),
+            })?;
+    }
+    Ok(PruneStats { rows, bytes })
+}
+
+/// Runs a query returning one `row_count` and `byte_count` row
+async fn measure(store: &SqliteStore, sql: String, cutoff: i64) -> DataStoreResult<(usize, u64)> {
+    let failed = |e: String| DataStoreError::InternalError {
+        message: format!("Failed to measure performance history: {e}"),
+    };
+    let row = store
+        .db
+        .query_one(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            sql,
+            [Value::from(cutoff)],
+        ))
+        .await
+        .map_err(|e| failed(e.to_string()))?
+        .ok_or_else(|| failed("no result row".to_string()))?;
+    let rows: i64 = row
+        .try_get("", "row_count")
+        .map_err(|e| failed(e.to_string()))?;
+    let bytes: i64 = row
+        .try_get("", "byte_count")
+        .map_err(|e| failed(e.to_string()))?;
+    Ok((
+        usize::try_from(rows).map_err(|e| failed(e.to_string()))?,
+        u64::try_from(bytes).map_err(|e| failed(e.to_string()))?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::derived::{Aggregation, HistoryMetric};
+    use chrono::TimeDelta;
+    use sea_orm::{Database, Schema};
+
+    async fn store() -> SqliteStore {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        let schema = Schema::new(DatabaseBackend::Sqlite);
+        for stmt in [
+            schema.create_table_from_entity(performance_samples::Entity),
+            schema.create_table_from_entity(crate::entities::performance_rollups::Entity),
+        ] {
+            db.execute(db.get_database_backend().build(&stmt))
+                .await
+                .unwrap();
+        }
+        SqliteStore::from_connection(db)
+    }
+
+    fn cpu(percent: u8) -> PerformanceMetrics {
+        PerformanceMetrics {
+            cpu_utilization: Some(percent),
+            memory_utilization: None,
+            total_memory: None,
+            used_memory: None,
+            load_average: None,
+        }
+    }
+
+    fn at(seconds: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(seconds, 0).unwrap()
+    }
+
+    fn query(aggregation: Aggregation) -> MetricQuery {
+        MetricQuery {
+            metric: HistoryMetric::Cpu,
+            from: at(0),
+            to: at(900),
+            step: TimeDelta::minutes(5),
+            aggregation,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_query_downsamples_per_step() {
+        let store = store().await;
+        let node_id = Uuid::new_v4();
+        for (seconds, percent) in [(0, 10), (60, 20), (120, 60), (600, 40)] {
+            record_performance_metrics(&store, &node_id, at(seconds), &cpu(percent))
+                .await
+                .unwrap();
+        }
+        // Another node's samples are not included
+        record_performance_metrics(&store, &Uuid::new_v4(), at(0), &cpu(99))
+            .await
+            .unwrap();
+
+        let avg = query_performance_history(&store, &node_id, &query(Aggregation::Avg))
+            .await
+            .unwrap();
+        assert_eq!(
+            avg,
+            vec![
+                MetricPoint {
+                    timestamp: at(0),
+                    value: 30.0
+                },
+                MetricPoint {
+                    timestamp: at(600),
+                    value: 40.0
+                },
+            ]
+        );
+
+        let count = query_performance_history(&store, &node_id, &query(Aggregation::Count))
+            .await
+            .unwrap();
+        assert!((count[0].value - 3.0).abs() < f64::EPSILON);
+        let max = query_performance_history(&store, &node_id, &query(Aggregation::Max))
+            .await
+            .unwrap();
+        assert!((max[0].value - 60.0).abs() < f64::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn test_pruned_samples_are_answered_from_rollups() {
+        let store = store().await;
+        let node_id = Uuid::new_v4();
+        for (seconds, percent) in [(0, 10), (60, 20), (3600, 60), (7200, 40)] {
+            record_performance_metrics(&store, &node_id, at(seconds), &cpu(percent))
+                .await
+                .unwrap();
+        }
+        let hourly = |aggregation| MetricQuery {
+            to: at(7200),
+            step: TimeDelta::hours(2),
+            ..query(aggregation)
+        };
+        let before = query_performance_history(&store, &node_id, &hourly(Aggregation::Avg))
+            .await
+            .unwrap();
+
+        // Rounded down to the hour, so the sample at 7200 is kept
+        let dry_run = prune_performance_samples(&store, at(7300), true)
+            .await
+            .unwrap();
+        assert_eq!(dry_run.rows, 3);
+        let pruned = prune_performance_samples(&store, at(7300), false)
+            .await
+            .unwrap();
+        assert_eq!(pruned, dry_run);
+        assert_eq!(
+            prune_performance_samples(&store, at(7300), true)
+                .await
+                .unwrap()
+                .rows,
+            0
+        );
+
+        let after = query_performance_history(&store, &node_id, &hourly(Aggregation::Avg))
+            .await
+            .unwrap();
+        assert_eq!(after, before);
+        let count = query_performance_history(&store, &node_id, &hourly(Aggregation::Count))
+            .await
+            .unwrap();
+        assert!((count[0].value - 3.0).abs() < f64::EPSILON);
+        let min = query_performance_history(&store, &node_id, &hourly(Aggregation::Min))
+            .await
+            .unwrap();
+        assert!((min[0].value - 10.0).abs() < f64::EPSILON);
+
+        let rollups = prune_performance_rollups(&store, at(3600), false)
+            .await
+            .unwrap();
+        assert_eq!(rollups.rows, 1);
+        let count = query_performance_history(&store, &node_id, &hourly(Aggregation::Count))
+            .await
+            .unwrap();
+        assert!((count[0].value - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn test_query_rejects_invalid_range() {
+        let store = store().await;
+        let mut invalid = query(Aggregation::Avg);
+        invalid.to = at(-60);
+
+        let result = query_performance_history(&store, &Uuid::new_v4(), &invalid).await;
+
+        assert!(matches!(
+            result,
+            Err(DataStoreError::ValidationError { .. })
+        ));
+    }
+}
@@ -0,0 +1,152 @@
+//! Policy result operations for `SQLite` datastore
+//!
+//! The outcome of each evaluation is stored in plain columns (`status`,
+//! `severity`, `message`) so compliance summaries can be filtered and
+//! counted in SQL. The full result is kept alongside in `detail` to rebuild
+//! [`PolicyExecutionResult`] values.
+
+use super::super::types::{DataStoreError, DataStoreResult};
+use super::SqliteStore;
+use crate::entities::policy_results;
+use crate::policy::{ActionResult, EvaluationResult, PolicyExecutionResult};
+use chrono::{SecondsFormat, Utc};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder, Set};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// Rule ID stored for rules without one
+const UNKNOWN_RULE: &str = "unknown";
+
+/// Returns the `status`, `severity`, and `message` columns for a result
+fn outcome(result: &PolicyExecutionResult) -> (&'static str, &'static str, Option<String>) {
+    if let Some(exemption) = &result.exemption {
+        return ("exempted", "info", Some(exemption.reason.clone()));
+    }
+    match (&result.evaluation_result, &result.action_result) {
+        (EvaluationResult::Error { message }, _) => ("error", "error", Some(message.clone())),
+        (EvaluationResult::Aborted { reason }, _) => ("aborted", "error", Some(reason.to_string())),
+        (EvaluationResult::NotSatisfied, _) => ("not_satisfied", "info", None),
+        (EvaluationResult::Satisfied { .. }, None) => ("satisfied", "info", None),
+        (EvaluationResult::Satisfied { .. }, Some(action)) => match &action.result {
+            ActionResult::Success { message } => ("satisfied", "info", Some(message.clone())),
+            ActionResult::ComplianceFailure {
+                field,
+                expected,
+                actual,
+            } => (
+                "compliance_failure",
+                "warning",
+                Some(format!("{field}: expected {expected}, found {actual}")),
+            ),
+            ActionResult::Error { message } => ("error", "error", Some(message.clone())),
+        },
+    }
+}
+
+fn parse_detail(model: &policy_results::Model) -> DataStoreResult<PolicyExecutionResult> {
+    serde_json::from_str(&model.detail).map_err(|e| DataStoreError::InternalError {
+        message: format!("Invalid policy result {}: {e}", model.id),
+    })
+}
+
+async fn query(
+    store: &SqliteStore,
+    filter: sea_orm::sea_query::SimpleExpr,
+) -> DataStoreResult<Vec<policy_results::Model>> {
+    policy_results::Entity::find()
+        .filter(filter)
+        .order_by_desc(policy_results::Column::EvaluatedAt)
+        .order_by_asc(policy_results::Column::RuleId)
+        .all(&store.db)
+        .await
+        .map_err(|e| DataStoreError::InternalError {
+            message: format!("Failed to query policy results: {e}"),
+        })
+}
+
+/// Stores the result of evaluating a rule against a node
+pub async fn store_policy_result(
+    store: &SqliteStore,
+    node_id: &Uuid,
+    rule_id: &str,
+    result: &PolicyExecutionResult,
+) -> DataStoreResult<()> {
+    let detail = serde_json::to_string(result).map_err(|e| DataStoreError::InternalError {
+        message: format!("Failed to serialize policy result: {e}"),
+    })?;
+    let (status, severity, message) = outcome(result);
+    let rule_id = if rule_id.is_empty() {
+        UNKNOWN_RULE
+    } else {
+        rule_id
+    };
+
+    let active = policy_results::ActiveModel {
+        id: Set(Uuid::new_v4().to_string()),
+        rule_id: Set(rule_id.to_owned()),
+        node_id: Set(node_id.to_string()),
+        status: Set(status.to_owned()),
+        severity: Set(severity.to_owned()),
+        message: Set(message),
+        evaluated_at: Set(Utc::now().to_rfc3339_opts(SecondsFormat::Micros, true)),
+        detail: Set(detail),
+    };
+
+    policy_results::Entity::insert(active)
+        .exec(&store.db)
+        .await
+        .map_err(|e| DataStoreError::InternalError {
+            message: format!("Failed to store policy result: {e}"),
+        })?;
+    Ok(())
+}
+
+/// Gets every stored result for a node, newest first
+pub async fn get_policy_results(
+    store: &SqliteStore,
+    node_id: &Uuid,
+) -> DataStoreResult<Vec<PolicyExecutionResult>> {
+    let models = query(
+        store,
+        policy_results::Column::NodeId.eq(node_id.to_string()),
+    )
+    .await?;
+    models.iter().map(parse_detail).collect()
+}
+
+/// Gets the newest result of each rule for a node
+pub async fn get_latest_policy_results(
+    store: &SqliteStore,
+    node_id: &Uuid,
+) -> DataStoreResult<Vec<PolicyExecutionResult>> {
+    let models = query(
+        store,
+        policy_results::Column::NodeId.eq(node_id.to_string()),
+    )
+    .await?;
+    let mut seen = HashSet::new();
+    models
+        .iter()
+        .filter(|model| seen.insert(model.rule_id.as_str()))
+        .map(parse_detail)
+        .collect()
+}
+
+/// Gets every stored result of a rule with the node it was evaluated
+/// against, newest first
+pub async fn get_rule_results(
+    store: &SqliteStore,
+    rule_id: &str,
+) -> DataStoreResult<Vec<(Uuid, PolicyExecutionResult)>> {
+    let models = query(store, policy_results::Column::RuleId.eq(rule_id)).await?;
+    models
+        .iter()
+        .map(|model| {
+            let node_id =
+                Uuid::parse_str(&model.node_id).map_err(|e| DataStoreError::InternalError {
+                    message: format!("Invalid node ID in policy result {}: {e}", model.id),
+                })?;
+            Ok((node_id, parse_detail(model)?))
+        })
+        .collect()
+}
@@ -0,0 +1,97 @@
+//! Settings operations for `SQLite` datastore
+
+use super::super::types::{DataStoreError, DataStoreResult};
+use super::SqliteStore;
+use super::errors::query_error;
+use crate::entities::settings;
+use chrono::Utc;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder, Set};
+
+fn setting_id(namespace: &str, key: &str) -> String {
+    format!("{namespace}/{key}")
+}
+
+fn parse_value(model: &settings::Model) -> DataStoreResult<serde_json::Value> {
+    serde_json::from_str(&model.value).map_err(|e| DataStoreError::InternalError {
+        message: format!("Invalid JSON in setting {}: {e}", model.id),
+    })
+}
+
+/// Gets a settings document by namespace and key
+pub async fn get_setting(
+    store: &SqliteStore,
+    namespace: &str,
+    key: &str,
+) -> DataStoreResult<Option<serde_json::Value>> {
+    let model = settings::Entity::find_by_id(setting_id(namespace, key))
+        .one(&store.db)
+        .await
+        .map_err(|e| query_error("Failed to query setting", &e))?;
+    model.as_ref().map(parse_value).transpose()
+}
+
+/// Lists all settings documents in a namespace, ordered by key
+pub async fn list_settings(
+    store: &SqliteStore,
+    namespace: &str,
+) -> DataStoreResult<Vec<(String, serde_json::Value)>> {
+    let models = settings::Entity::find()
+        .filter(settings::Column::Namespace.eq(namespace))
+        .order_by_asc(settings::Column::Key)
+        .all(&store.db)
+        .await
+        .map_err(|e| query_error("Failed to query settings", &e))?;
+
+    models
+        .iter()
+        .map(|model| Ok((model.key.clone(), parse_value(model)?)))
+        .collect()
+}
+
+/// Creates or replaces a settings document
+pub async fn put_setting(
+    store: &SqliteStore,
+    namespace: &str,
+    key: &str,
+    value: &serde_json::Value,
+) -> DataStoreResult<()> {
+    let active = settings::ActiveModel {
+        id: Set(setting_id(namespace, key)),
+        namespace: Set(namespace.to_owned()),
+        key: Set(key.to_owned()),
+        value: Set(value.to_string()),
+        updated_at: Set(Utc::now().to_rfc3339()),
+    };
+
+    settings::Entity::insert(active)
+        .on_conflict(
+            OnConflict::column(settings::Column::Id)
+                .update_columns([settings::Column::Value, settings::Column::UpdatedAt])
+                .to_owned(),
+        )
+        .exec(&store.db)
+        .await
+        .map_err(|e| query_error("Failed to write setting", &e))?;
+    Ok(())
+}
+
+/// Deletes a settings document
+pub async fn delete_setting(
+    store: &SqliteStore,
+    namespace: &str,
+    key: &str,
+) -> DataStoreResult<()> {
+    let id = setting_id(namespace, key);
+    let result = settings::Entity::delete_by_id(id.clone())
+        .exec(&store.db)
+        .await
+        .map_err(|e| query_error("Failed to delete setting", &e))?;
+    if result.rows_affected == 0 {
+        return Err(DataStoreError::NotFound {
+            entity_type: "Setting".to_owned(),
+            id,
+        });
+    }
+    Ok(())
+}
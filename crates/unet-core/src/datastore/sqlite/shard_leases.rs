@@ -0,0 +1,92 @@
+//! Shard lease operations for `SQLite` datastore
+//!
+//! A lease is claimed with a single upsert whose update only applies when
+//! the stored lease has expired or belongs to the same collector, so two
+//! collectors racing for a shard cannot both hold it.
+
+use super::super::types::{DataStoreError, DataStoreResult};
+use super::SqliteStore;
+use super::errors::query_error;
+use crate::entities::shard_leases;
+use crate::snmp::shards::ShardLease;
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseBackend, EntityTrait, QueryFilter, QueryOrder, Statement,
+    Value,
+};
+
+/// Inserts a lease, or takes over the stored one if it has expired or is
+/// already the collector's
+const ACQUIRE_SQL: &str = "INSERT INTO shard_lease (shard, collector, acquired_at, expires_at) \
+     VALUES (?, ?, ?, ?) \
+     ON CONFLICT (shard) DO UPDATE SET collector = excluded.collector, \
+     acquired_at = excluded.acquired_at, expires_at = excluded.expires_at \
+     WHERE shard_lease.collector = excluded.collector OR shard_lease.expires_at <= ?";
+
+fn model_to_lease(model: shard_leases::Model) -> DataStoreResult<ShardLease> {
+    let shard = model.shard;
+    let invalid = |e: String| DataStoreError::InternalError {
+        message: format!("Invalid lease on shard {shard}: {e}"),
+    };
+    let time = |micros: i64| {
+        DateTime::from_timestamp_micros(micros)
+            .ok_or_else(|| invalid(format!("time {micros} out of range")))
+    };
+    Ok(ShardLease {
+        shard: u32::try_from(shard).map_err(|e| invalid(e.to_string()))?,
+        collector: model.collector,
+        acquired_at: time(model.acquired_at)?,
+        expires_at: time(model.expires_at)?,
+    })
+}
+
+/// Stores a lease unless another collector holds the shard at `now`
+pub async fn acquire_shard_lease(
+    store: &SqliteStore,
+    lease: &ShardLease,
+    now: DateTime<Utc>,
+) -> DataStoreResult<bool> {
+    let result = store
+        .db
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            ACQUIRE_SQL,
+            [
+                Value::from(i64::from(lease.shard)),
+                Value::from(lease.collector.clone()),
+                Value::from(lease.acquired_at.timestamp_micros()),
+                Value::from(lease.expires_at.timestamp_micros()),
+                Value::from(now.timestamp_micros()),
+            ],
+        ))
+        .await
+        .map_err(|e| query_error("Failed to acquire shard lease", &e))?;
+    Ok(result.rows_affected() == 1)
+}
+
+/// Lists every lease by shard number
+pub async fn list_shard_leases(store: &SqliteStore) -> DataStoreResult<Vec<ShardLease>> {
+    shard_leases::Entity::find()
+        .order_by_asc(shard_leases::Column::Shard)
+        .all(&store.db)
+        .await
+        .map_err(|e| query_error("Failed to list shard leases", &e))?
+        .into_iter()
+        .map(model_to_lease)
+        .collect()
+}
+
+/// Removes the lease on a shard if the collector holds it
+pub async fn release_shard_lease(
+    store: &SqliteStore,
+    shard: u32,
+    collector: &str,
+) -> DataStoreResult<()> {
+    shard_leases::Entity::delete_many()
+        .filter(shard_leases::Column::Shard.eq(i64::from(shard)))
+        .filter(shard_leases::Column::Collector.eq(collector))
+        .exec(&store.db)
+        .await
+        .map_err(|e| query_error("Failed to release shard lease", &e))?;
+    Ok(())
+}
@@ -1,16 +1,26 @@
 //! Main `SQLite` store implementation
 
-use super::{derived_state, links, locations, metadata, nodes, vendors};
+use super::{
+    change_events, derived_state, encryption, links, locations, maintenance, metadata, nodes,
+    performance_history, policy_results, settings, shard_leases, vendors,
+};
 
-use super::super::DataStore;
 use super::super::types::{
-    BatchOperation, BatchResult, DataStoreError, DataStoreResult, PagedResult, QueryOptions,
-    Transaction,
+    BatchOperation, BatchResult, DataStoreError, DataStoreResult, PagedResult, PruneStats,
+    QueryOptions, Transaction,
 };
+use super::super::{DEFAULT_MAX_RETRIES, DataStore, retry_operation};
+use super::errors::{connection_error, transaction_error};
 use super::transaction::SqliteTransaction;
-use crate::models::derived::{InterfaceStatus, NodeStatus, PerformanceMetrics};
+use crate::change_log::{ChangeEvent, EventFilter};
+use crate::models::derived::{
+    InterfaceStatus, MetricPoint, MetricQuery, NodeStatus, PerformanceMetrics,
+};
 use crate::models::{Link, Location, Node};
+use crate::policy::PolicyExecutionResult;
+use crate::snmp::shards::ShardLease;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use sea_orm::{ConnectOptions, Database, DatabaseConnection, TransactionTrait};
 use std::collections::HashMap;
 use std::time::Duration;
@@ -21,6 +31,8 @@ use uuid::Uuid;
 pub struct SqliteStore {
     /// Database connection
     pub(crate) db: DatabaseConnection,
+    /// Whether node, link, and location writes append change events
+    pub(crate) change_log: bool,
 }
 
 impl SqliteStore {
@@ -29,6 +41,34 @@ impl SqliteStore {
     /// # Errors
     /// Returns an error if the database connection cannot be established
     pub async fn new(database_url: &str) -> DataStoreResult<Self> {
+        let db = Self::connect(Self::connect_options(database_url)).await?;
+        Ok(Self {
+            db,
+            change_log: false,
+        })
+    }
+
+    /// Creates a new `SQLite` store backed by an `SQLCipher`-encrypted database
+    ///
+    /// The key is verified before the store is returned, so a wrong key fails
+    /// at startup rather than on the first query.
+    ///
+    /// # Errors
+    /// Returns an error if the connection cannot be established, `SQLCipher`
+    /// is not available, or the key does not decrypt the database
+    pub async fn new_encrypted(database_url: &str, key: &str) -> DataStoreResult<Self> {
+        let mut opt = Self::connect_options(database_url);
+        encryption::apply_key(&mut opt, key);
+
+        let db = Self::connect(opt).await?;
+        encryption::verify_encryption_key(&db).await?;
+        Ok(Self {
+            db,
+            change_log: false,
+        })
+    }
+
+    fn connect_options(database_url: &str) -> ConnectOptions {
         let mut opt = ConnectOptions::new(database_url);
         opt.max_connections(100)
             .min_connections(5)
@@ -37,14 +77,13 @@ impl SqliteStore {
             .idle_timeout(Duration::from_secs(8))
             .max_lifetime(Duration::from_secs(8))
             .sqlx_logging(false);
+        opt
+    }
 
-        let db = Database::connect(opt)
+    async fn connect(opt: ConnectOptions) -> DataStoreResult<DatabaseConnection> {
+        Database::connect(opt)
             .await
-            .map_err(|e| DataStoreError::ConnectionError {
-                message: format!("Failed to connect to database: {e}"),
-            })?;
-
-        Ok(Self { db })
+            .map_err(|e| connection_error("Failed to connect to database", &e))
     }
 
     /// Create a new `SqliteStore` from an existing database connection
@@ -53,7 +92,18 @@ impl SqliteStore {
     /// that already has the schema set up.
     #[must_use]
     pub const fn from_connection(db: DatabaseConnection) -> Self {
-        Self { db }
+        Self {
+            db,
+            change_log: false,
+        }
+    }
+
+    /// Appends a change event with each node, link, and location write when
+    /// `enabled`
+    #[must_use]
+    pub const fn with_change_log(mut self, enabled: bool) -> Self {
+        self.change_log = enabled;
+        self
     }
 
     /// Get the database connection for testing
@@ -83,9 +133,7 @@ impl DataStore for SqliteStore {
             .db
             .begin()
             .await
-            .map_err(|e| DataStoreError::TransactionError {
-                message: format!("Failed to begin transaction: {e}"),
-            })?;
+            .map_err(|e| transaction_error("Failed to begin transaction", &e))?;
 
         Ok(Box::new(SqliteTransaction { txn }))
     }
@@ -202,6 +250,42 @@ impl DataStore for SqliteStore {
         vendors::delete_vendor(self, name).await
     }
 
+    async fn get_setting(
+        &self,
+        namespace: &str,
+        key: &str,
+    ) -> DataStoreResult<Option<serde_json::Value>> {
+        settings::get_setting(self, namespace, key).await
+    }
+
+    async fn list_settings(
+        &self,
+        namespace: &str,
+    ) -> DataStoreResult<Vec<(String, serde_json::Value)>> {
+        settings::list_settings(self, namespace).await
+    }
+
+    async fn put_setting(
+        &self,
+        namespace: &str,
+        key: &str,
+        value: &serde_json::Value,
+    ) -> DataStoreResult<()> {
+        // Settings are written from many modules, so busy databases are
+        // retried here rather than by every caller
+        retry_operation(DEFAULT_MAX_RETRIES, || {
+            settings::put_setting(self, namespace, key, value)
+        })
+        .await
+    }
+
+    async fn delete_setting(&self, namespace: &str, key: &str) -> DataStoreResult<()> {
+        retry_operation(DEFAULT_MAX_RETRIES, || {
+            settings::delete_setting(self, namespace, key)
+        })
+        .await
+    }
+
     async fn batch_locations(
         &self,
         operations: &[BatchOperation<Location>],
@@ -209,6 +293,44 @@ impl DataStore for SqliteStore {
         locations::batch_locations(self, operations).await
     }
 
+    // Change log operations - delegate to change_events module
+    async fn append_change_event(&self, event: &ChangeEvent) -> DataStoreResult<ChangeEvent> {
+        change_events::append_change_event(self, event).await
+    }
+
+    async fn list_change_events(&self, filter: &EventFilter) -> DataStoreResult<Vec<ChangeEvent>> {
+        change_events::list_change_events(self, filter).await
+    }
+
+    async fn last_change_sequence(&self) -> DataStoreResult<i64> {
+        change_events::last_change_sequence(self).await
+    }
+
+    async fn prune_change_events(
+        &self,
+        cutoff: DateTime<Utc>,
+        dry_run: bool,
+    ) -> DataStoreResult<PruneStats> {
+        change_events::prune_change_events(self, cutoff, dry_run).await
+    }
+
+    // Shard lease operations - delegate to shard_leases module
+    async fn acquire_shard_lease(
+        &self,
+        lease: &ShardLease,
+        now: DateTime<Utc>,
+    ) -> DataStoreResult<bool> {
+        shard_leases::acquire_shard_lease(self, lease, now).await
+    }
+
+    async fn list_shard_leases(&self) -> DataStoreResult<Vec<ShardLease>> {
+        shard_leases::list_shard_leases(self).await
+    }
+
+    async fn release_shard_lease(&self, shard: u32, collector: &str) -> DataStoreResult<()> {
+        shard_leases::release_shard_lease(self, shard, collector).await
+    }
+
     // Statistics operations
     async fn get_entity_counts(&self) -> DataStoreResult<HashMap<String, usize>> {
         metadata::get_entity_counts(self).await
@@ -218,6 +340,45 @@ impl DataStore for SqliteStore {
         metadata::get_statistics(self).await
     }
 
+    async fn vacuum(&self) -> DataStoreResult<()> {
+        maintenance::vacuum(self).await
+    }
+
+    async fn analyze(&self) -> DataStoreResult<()> {
+        maintenance::analyze(self).await
+    }
+
+    async fn cleanup_orphaned_records(&self) -> DataStoreResult<HashMap<String, usize>> {
+        maintenance::cleanup_orphaned_records(self).await
+    }
+
+    async fn count_orphaned_records(&self) -> DataStoreResult<HashMap<String, usize>> {
+        maintenance::count_orphaned_records(self).await
+    }
+
+    async fn prune_derived_state(
+        &self,
+        cutoff: DateTime<Utc>,
+    ) -> DataStoreResult<HashMap<String, usize>> {
+        maintenance::prune_derived_state(self, cutoff).await
+    }
+
+    async fn prune_performance_samples(
+        &self,
+        cutoff: DateTime<Utc>,
+        dry_run: bool,
+    ) -> DataStoreResult<PruneStats> {
+        performance_history::prune_performance_samples(self, cutoff, dry_run).await
+    }
+
+    async fn prune_performance_rollups(
+        &self,
+        cutoff: DateTime<Utc>,
+        dry_run: bool,
+    ) -> DataStoreResult<PruneStats> {
+        performance_history::prune_performance_rollups(self, cutoff, dry_run).await
+    }
+
     async fn get_node_status(&self, node_id: &Uuid) -> DataStoreResult<Option<NodeStatus>> {
         derived_state::get_node_status(self, node_id).await
     }
@@ -226,12 +387,63 @@ impl DataStore for SqliteStore {
         derived_state::get_node_interfaces(self, node_id).await
     }
 
+    async fn get_node_statuses(&self, node_ids: &[Uuid]) -> DataStoreResult<Vec<NodeStatus>> {
+        derived_state::get_node_statuses(self, node_ids).await
+    }
+
     async fn get_node_metrics(
         &self,
         node_id: &Uuid,
     ) -> DataStoreResult<Option<PerformanceMetrics>> {
         derived_state::get_node_metrics(self, node_id).await
     }
+
+    async fn record_performance_metrics(
+        &self,
+        node_id: &Uuid,
+        recorded_at: DateTime<Utc>,
+        metrics: &PerformanceMetrics,
+    ) -> DataStoreResult<()> {
+        performance_history::record_performance_metrics(self, node_id, recorded_at, metrics).await
+    }
+
+    async fn query_performance_history(
+        &self,
+        node_id: &Uuid,
+        query: &MetricQuery,
+    ) -> DataStoreResult<Vec<MetricPoint>> {
+        performance_history::query_performance_history(self, node_id, query).await
+    }
+
+    async fn store_policy_result(
+        &self,
+        node_id: &Uuid,
+        rule_id: &str,
+        result: &PolicyExecutionResult,
+    ) -> DataStoreResult<()> {
+        policy_results::store_policy_result(self, node_id, rule_id, result).await
+    }
+
+    async fn get_policy_results(
+        &self,
+        node_id: &Uuid,
+    ) -> DataStoreResult<Vec<PolicyExecutionResult>> {
+        policy_results::get_policy_results(self, node_id).await
+    }
+
+    async fn get_latest_policy_results(
+        &self,
+        node_id: &Uuid,
+    ) -> DataStoreResult<Vec<PolicyExecutionResult>> {
+        policy_results::get_latest_policy_results(self, node_id).await
+    }
+
+    async fn get_rule_results(
+        &self,
+        rule_id: &str,
+    ) -> DataStoreResult<Vec<(Uuid, PolicyExecutionResult)>> {
+        policy_results::get_rule_results(self, rule_id).await
+    }
 }
 
 #[cfg(test)]
@@ -243,5 +455,14 @@ mod comprehensive_store_tests;
 #[cfg(test)]
 mod derived_state_tests;
 
+#[cfg(test)]
+mod maintenance_tests;
+
 #[cfg(test)]
 mod metadata_tests;
+
+#[cfg(test)]
+mod policy_results_tests;
+
+#[cfg(test)]
+mod settings_tests;
@@ -3,7 +3,7 @@
 use super::super::SqliteStore;
 use crate::datastore::DataStore;
 use crate::datastore::types::{BatchOperation, Pagination, QueryOptions, Sort, SortDirection};
-use crate::models::{DeviceRole, Lifecycle, Link, Location, Node, Vendor};
+use crate::models::{DeviceRole, Lifecycle, Link, Location, Node, ProvisioningState, Vendor};
 use sea_orm::Database;
 use serde_json::Value;
 use std::net::IpAddr;
@@ -212,6 +212,8 @@ async fn test_crud_operations_with_mock_entities() {
         purchase_date: None,
         warranty_expires: None,
         custom_data: Value::Null,
+        management_ipv6: None,
+        management_zone: None,
     };
 
     // Test create_node delegate
@@ -242,6 +244,11 @@ async fn test_link_crud_operations_with_mock_entities() {
         bandwidth: None,
         link_type: Some("ethernet".to_string()),
         is_internet_circuit: false,
+        provider: None,
+        circuit_id: None,
+        ordered_on: None,
+        delivered_on: None,
+        provisioning_state: ProvisioningState::Live,
         custom_data: Value::Null,
     };
 
@@ -270,6 +277,9 @@ async fn test_location_crud_operations_with_mock_entities() {
         path: "test_location".to_string(),
         description: None,
         address: None,
+        postal_address: None,
+        coordinates: None,
+        timezone: None,
         custom_data: Value::Null,
     };
 
@@ -201,3 +201,62 @@ async fn test_get_node_metrics_returns_none_without_persisted_status() {
     let metrics = store.get_node_metrics(&node.id).await.unwrap();
     assert!(metrics.is_none());
 }
+
+#[tokio::test]
+async fn test_get_node_statuses_reads_statuses_with_interfaces() {
+    let store = setup_schema_store().await;
+    let polled = test_node();
+    let mut unpolled = test_node();
+    unpolled.name = "unpolled-node".to_string();
+    for node in [&polled, &unpolled] {
+        store.create_node(node).await.unwrap();
+    }
+
+    crate::entities::node_status::ActiveModel {
+        id: Set("status-derived-bulk".to_string()),
+        node_id: Set(polled.id.to_string()),
+        last_updated: Set("2026-04-07T01:02:03Z".to_string()),
+        reachable: Set(false),
+        system_info: Set(None),
+        performance: Set(None),
+        environmental: Set(None),
+        vendor_metrics: Set(None),
+        raw_snmp_data: Set(None),
+        last_snmp_success: Set(None),
+        last_error: Set(Some("timeout".to_string())),
+        consecutive_failures: Set(3),
+    }
+    .insert(store.connection())
+    .await
+    .unwrap();
+    crate::entities::interface_status::ActiveModel {
+        id: Set("iface-bulk".to_string()),
+        node_status_id: Set("status-derived-bulk".to_string()),
+        index: Set(1),
+        name: Set("Ethernet1".to_string()),
+        interface_type: Set(6),
+        mtu: Set(None),
+        speed: Set(None),
+        physical_address: Set(None),
+        admin_status: Set("up".to_string()),
+        oper_status: Set("up".to_string()),
+        last_change: Set(None),
+        input_stats: Set(r#"{"octets":0,"packets":0,"errors":0,"discards":0}"#.to_string()),
+        output_stats: Set(r#"{"octets":0,"packets":0,"errors":0,"discards":0}"#.to_string()),
+    }
+    .insert(store.connection())
+    .await
+    .unwrap();
+
+    let statuses = store
+        .get_node_statuses(&[polled.id, unpolled.id])
+        .await
+        .unwrap();
+    assert_eq!(statuses.len(), 1);
+    assert_eq!(statuses[0].node_id, polled.id);
+    assert!(!statuses[0].reachable);
+    assert_eq!(statuses[0].interfaces.len(), 1);
+    assert_eq!(statuses[0].interfaces[0].name, "Ethernet1");
+
+    assert!(store.get_node_statuses(&[]).await.unwrap().is_empty());
+}
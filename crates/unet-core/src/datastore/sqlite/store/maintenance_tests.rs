@@ -0,0 +1,199 @@
+use super::super::SqliteStore;
+use crate::datastore::DataStore;
+use crate::entities;
+use crate::models::derived::PerformanceMetrics;
+use crate::models::{DeviceRole, Node, Vendor};
+use chrono::{TimeZone, Utc};
+use sea_orm::{ActiveModelTrait, ConnectionTrait, Database, DatabaseBackend, Schema, Set};
+
+const STATS: &str = r#"{"octets":0,"packets":0,"errors":0,"discards":0}"#;
+
+async fn setup_schema_store() -> SqliteStore {
+    let db = Database::connect("sqlite::memory:")
+        .await
+        .expect("Failed to create in-memory database");
+    let schema = Schema::new(DatabaseBackend::Sqlite);
+
+    for stmt in [
+        schema.create_table_from_entity(entities::locations::Entity),
+        schema.create_table_from_entity(entities::nodes::Entity),
+        schema.create_table_from_entity(entities::node_status::Entity),
+        schema.create_table_from_entity(entities::interface_status::Entity),
+        schema.create_table_from_entity(entities::polling_tasks::Entity),
+        schema.create_table_from_entity(entities::policy_results::Entity),
+        schema.create_table_from_entity(entities::performance_samples::Entity),
+        schema.create_table_from_entity(entities::performance_rollups::Entity),
+    ] {
+        db.execute(db.get_database_backend().build(&stmt))
+            .await
+            .unwrap();
+    }
+    // Orphans only exist in databases written without foreign key enforcement
+    db.execute_unprepared("PRAGMA foreign_keys = OFF")
+        .await
+        .unwrap();
+    SqliteStore::from_connection(db)
+}
+
+async fn insert_status(store: &SqliteStore, id: &str, node_id: &str, last_updated: &str) {
+    entities::node_status::ActiveModel {
+        id: Set(id.to_string()),
+        node_id: Set(node_id.to_string()),
+        last_updated: Set(last_updated.to_string()),
+        reachable: Set(true),
+        system_info: Set(None),
+        performance: Set(None),
+        environmental: Set(None),
+        vendor_metrics: Set(None),
+        raw_snmp_data: Set(None),
+        last_snmp_success: Set(None),
+        last_error: Set(None),
+        consecutive_failures: Set(0),
+    }
+    .insert(store.connection())
+    .await
+    .unwrap();
+}
+
+async fn insert_interface(store: &SqliteStore, id: &str, node_status_id: &str) {
+    entities::interface_status::ActiveModel {
+        id: Set(id.to_string()),
+        node_status_id: Set(node_status_id.to_string()),
+        index: Set(1),
+        name: Set("GigabitEthernet0/1".to_string()),
+        interface_type: Set(6),
+        mtu: Set(None),
+        speed: Set(None),
+        physical_address: Set(None),
+        admin_status: Set("up".to_string()),
+        oper_status: Set("up".to_string()),
+        last_change: Set(None),
+        input_stats: Set(STATS.to_string()),
+        output_stats: Set(STATS.to_string()),
+    }
+    .insert(store.connection())
+    .await
+    .unwrap();
+}
+
+async fn create_node(store: &SqliteStore, name: &str) -> Node {
+    let mut node = Node::new(
+        name.to_string(),
+        "example.com".to_string(),
+        Vendor::Cisco,
+        DeviceRole::Router,
+    );
+    node.model = "ASR1000".to_string();
+    store.create_node(&node).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_cleanup_orphaned_records_keeps_rows_of_existing_nodes() {
+    let store = setup_schema_store().await;
+    let node = create_node(&store, "kept").await;
+    let missing = uuid::Uuid::new_v4().to_string();
+    insert_status(
+        &store,
+        "status-kept",
+        &node.id.to_string(),
+        "2026-04-07T00:00:00Z",
+    )
+    .await;
+    insert_status(&store, "status-orphan", &missing, "2026-04-07T00:00:00Z").await;
+    insert_interface(&store, "iface-kept", "status-kept").await;
+    insert_interface(&store, "iface-orphan", "status-orphan").await;
+
+    let deleted = store.cleanup_orphaned_records().await.unwrap();
+
+    assert_eq!(deleted.get("node_status"), Some(&1));
+    assert_eq!(deleted.get("interface_status"), Some(&1));
+    assert_eq!(deleted.get("polling_tasks"), Some(&0));
+    assert_eq!(deleted.get("policy_result"), Some(&0));
+    assert_eq!(deleted.get("performance_rollup"), Some(&0));
+    let counts = store.get_entity_counts().await.unwrap();
+    assert_eq!(counts.get("node_status"), Some(&1));
+    assert_eq!(counts.get("interface_status"), Some(&1));
+}
+
+#[tokio::test]
+async fn test_count_orphaned_records_counts_what_cleanup_deletes() {
+    let store = setup_schema_store().await;
+    let node = create_node(&store, "kept").await;
+    let missing = uuid::Uuid::new_v4().to_string();
+    insert_status(
+        &store,
+        "status-kept",
+        &node.id.to_string(),
+        "2026-04-07T00:00:00Z",
+    )
+    .await;
+    insert_status(&store, "status-orphan", &missing, "2026-04-07T00:00:00Z").await;
+    insert_interface(&store, "iface-kept", "status-kept").await;
+    insert_interface(&store, "iface-orphan", "status-orphan").await;
+
+    let counted = store.count_orphaned_records().await.unwrap();
+    let counts = store.get_entity_counts().await.unwrap();
+    assert_eq!(
+        counts.get("node_status"),
+        Some(&2),
+        "counting deletes nothing"
+    );
+
+    let deleted = store.cleanup_orphaned_records().await.unwrap();
+    assert_eq!(counted, deleted);
+    assert_eq!(counted.get("interface_status"), Some(&1));
+}
+
+#[tokio::test]
+async fn test_prune_derived_state_removes_stale_status_and_interfaces() {
+    let store = setup_schema_store().await;
+    let fresh = create_node(&store, "fresh").await;
+    let stale = create_node(&store, "stale").await;
+    insert_status(
+        &store,
+        "status-fresh",
+        &fresh.id.to_string(),
+        "2026-04-07T00:00:00Z",
+    )
+    .await;
+    insert_status(
+        &store,
+        "status-stale",
+        &stale.id.to_string(),
+        "2026-01-01T00:00:00Z",
+    )
+    .await;
+    insert_interface(&store, "iface-fresh", "status-fresh").await;
+    insert_interface(&store, "iface-stale", "status-stale").await;
+    let metrics = PerformanceMetrics {
+        cpu_utilization: Some(12),
+        memory_utilization: Some(40),
+        total_memory: None,
+        used_memory: None,
+        load_average: None,
+    };
+    for day in [1, 7] {
+        let recorded_at = Utc.with_ymd_and_hms(2026, 2, day, 0, 0, 0).unwrap();
+        store
+            .record_performance_metrics(&fresh.id, recorded_at, &metrics)
+            .await
+            .unwrap();
+    }
+
+    let cutoff = Utc.with_ymd_and_hms(2026, 2, 5, 0, 0, 0).unwrap();
+    let deleted = store.prune_derived_state(cutoff).await.unwrap();
+
+    assert_eq!(deleted.get("node_status"), Some(&1));
+    assert_eq!(deleted.get("interface_status"), Some(&1));
+    assert_eq!(deleted.get("performance_sample"), Some(&2));
+    assert!(store.get_node_status(&fresh.id).await.unwrap().is_some());
+    assert!(store.get_node_status(&stale.id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_vacuum_and_analyze_succeed() {
+    let store = setup_schema_store().await;
+
+    store.vacuum().await.unwrap();
+    store.analyze().await.unwrap();
+}
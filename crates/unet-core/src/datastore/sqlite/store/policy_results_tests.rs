@@ -0,0 +1,164 @@
+use super::super::SqliteStore;
+use crate::datastore::DataStore;
+use crate::entities;
+use crate::policy::{
+    Action, ActionExecutionResult, ActionResult, AppliedExemption, Condition, EvaluationResult,
+    FieldRef, PolicyExecutionResult, PolicyRule, Value,
+};
+use sea_orm::{ConnectionTrait, Database, DatabaseBackend, EntityTrait, Schema};
+use serde_json::json;
+use std::time::Duration;
+use uuid::Uuid;
+
+async fn setup_policy_results_store() -> SqliteStore {
+    let db = Database::connect("sqlite::memory:")
+        .await
+        .expect("Failed to create in-memory database");
+    let schema = Schema::new(DatabaseBackend::Sqlite);
+    let stmt = schema.create_table_from_entity(entities::policy_results::Entity);
+    db.execute(db.get_database_backend().build(&stmt))
+        .await
+        .unwrap();
+    SqliteStore::from_connection(db)
+}
+
+fn rule(id: &str) -> PolicyRule {
+    PolicyRule {
+        id: Some(id.to_string()),
+        condition: Condition::True,
+        action: Action::Assert {
+            field: FieldRef {
+                path: vec!["version".to_string()],
+            },
+            expected: Value::String("17.3".to_string()),
+        },
+    }
+}
+
+fn compliance_failure(id: &str) -> PolicyExecutionResult {
+    let rule = rule(id);
+    PolicyExecutionResult::new(
+        rule.clone(),
+        EvaluationResult::Satisfied {
+            action: rule.action,
+        },
+        Some(ActionExecutionResult {
+            result: ActionResult::ComplianceFailure {
+                field: "version".to_string(),
+                expected: json!("17.3"),
+                actual: json!("16.9"),
+            },
+            rollback_data: None,
+        }),
+    )
+}
+
+#[tokio::test]
+async fn test_store_policy_result_writes_normalized_columns() {
+    let store = setup_policy_results_store().await;
+    let node_id = Uuid::new_v4();
+
+    store
+        .store_policy_result(&node_id, "version", &compliance_failure("version"))
+        .await
+        .unwrap();
+
+    let rows = entities::policy_results::Entity::find()
+        .all(store.connection())
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].node_id, node_id.to_string());
+    assert_eq!(rows[0].rule_id, "version");
+    assert_eq!(rows[0].status, "compliance_failure");
+    assert_eq!(rows[0].severity, "warning");
+    assert_eq!(
+        rows[0].message.as_deref(),
+        Some(r#"version: expected "17.3", found "16.9""#)
+    );
+}
+
+#[tokio::test]
+async fn test_store_policy_result_records_exemption() {
+    let store = setup_policy_results_store().await;
+    let node_id = Uuid::new_v4();
+    let mut exempted = compliance_failure("version");
+    exempted.exemption = Some(AppliedExemption {
+        location_id: Uuid::new_v4(),
+        reason: "upgrade scheduled".to_string(),
+        expires_at: chrono::Utc::now(),
+    });
+
+    store
+        .store_policy_result(&node_id, "version", &exempted)
+        .await
+        .unwrap();
+
+    let rows = entities::policy_results::Entity::find()
+        .all(store.connection())
+        .await
+        .unwrap();
+    assert_eq!(rows[0].status, "exempted");
+    assert_eq!(rows[0].severity, "info");
+    assert_eq!(rows[0].message.as_deref(), Some("upgrade scheduled"));
+    let results = store.get_policy_results(&node_id).await.unwrap();
+    assert!(results[0].is_exempted());
+    assert!(!results[0].is_compliance_failure());
+}
+
+#[tokio::test]
+async fn test_get_policy_results_round_trips_newest_first() {
+    let store = setup_policy_results_store().await;
+    let node_id = Uuid::new_v4();
+
+    store
+        .store_policy_result(&node_id, "version", &compliance_failure("version"))
+        .await
+        .unwrap();
+    // Timestamps have microsecond precision; keep the two evaluations apart
+    tokio::time::sleep(Duration::from_millis(2)).await;
+    let error = PolicyExecutionResult::new_error(rule("version"), "no data".to_string());
+    store
+        .store_policy_result(&node_id, "version", &error)
+        .await
+        .unwrap();
+    store
+        .store_policy_result(&Uuid::new_v4(), "version", &error)
+        .await
+        .unwrap();
+
+    let results = store.get_policy_results(&node_id).await.unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].get_error_message(), Some("no data"));
+    assert!(results[1].is_compliance_failure());
+
+    let latest = store.get_latest_policy_results(&node_id).await.unwrap();
+    assert_eq!(latest.len(), 1);
+    assert!(latest[0].is_error());
+}
+
+#[tokio::test]
+async fn test_get_rule_results_spans_nodes() {
+    let store = setup_policy_results_store().await;
+    let first = Uuid::new_v4();
+    let second = Uuid::new_v4();
+
+    for node_id in [first, second] {
+        store
+            .store_policy_result(&node_id, "version", &compliance_failure("version"))
+            .await
+            .unwrap();
+    }
+    store
+        .store_policy_result(&first, "ntp", &compliance_failure("ntp"))
+        .await
+        .unwrap();
+
+    let results = store.get_rule_results("version").await.unwrap();
+    let mut nodes: Vec<Uuid> = results.iter().map(|(node_id, _)| *node_id).collect();
+    nodes.sort();
+    let mut expected = vec![first, second];
+    expected.sort();
+    assert_eq!(nodes, expected);
+    assert!(store.get_rule_results("missing").await.unwrap().is_empty());
+}
@@ -0,0 +1,90 @@
+use super::super::SqliteStore;
+use crate::datastore::{DataStore, DataStoreError};
+use crate::entities;
+use sea_orm::{ConnectionTrait, Database, DatabaseBackend, Schema};
+use serde_json::json;
+
+async fn setup_settings_store() -> SqliteStore {
+    let db = Database::connect("sqlite::memory:")
+        .await
+        .expect("Failed to create in-memory database");
+    let schema = Schema::new(DatabaseBackend::Sqlite);
+    let stmt = schema.create_table_from_entity(entities::settings::Entity);
+    db.execute(db.get_database_backend().build(&stmt))
+        .await
+        .unwrap();
+    SqliteStore::from_connection(db)
+}
+
+#[tokio::test]
+async fn test_put_and_get_setting_round_trip() {
+    let store = setup_settings_store().await;
+    let value = json!({ "interval": 60 });
+
+    store.put_setting("profiles", "core", &value).await.unwrap();
+
+    let loaded = store.get_setting("profiles", "core").await.unwrap();
+    assert_eq!(loaded, Some(value));
+    assert!(
+        store
+            .get_setting("profiles", "missing")
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn test_put_setting_replaces_existing_value() {
+    let store = setup_settings_store().await;
+
+    store
+        .put_setting("profiles", "core", &json!(1))
+        .await
+        .unwrap();
+    store
+        .put_setting("profiles", "core", &json!(2))
+        .await
+        .unwrap();
+
+    let all = store.list_settings("profiles").await.unwrap();
+    assert_eq!(all, vec![("core".to_string(), json!(2))]);
+}
+
+#[tokio::test]
+async fn test_list_settings_is_scoped_and_ordered() {
+    let store = setup_settings_store().await;
+
+    store
+        .put_setting("profiles", "b", &json!("b"))
+        .await
+        .unwrap();
+    store
+        .put_setting("profiles", "a", &json!("a"))
+        .await
+        .unwrap();
+    store.put_setting("other", "c", &json!("c")).await.unwrap();
+
+    let keys: Vec<_> = store
+        .list_settings("profiles")
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|(key, _)| key)
+        .collect();
+    assert_eq!(keys, vec!["a", "b"]);
+}
+
+#[tokio::test]
+async fn test_delete_setting_reports_missing_key() {
+    let store = setup_settings_store().await;
+    store
+        .put_setting("profiles", "core", &json!({}))
+        .await
+        .unwrap();
+
+    store.delete_setting("profiles", "core").await.unwrap();
+    let result = store.delete_setting("profiles", "core").await;
+
+    assert!(matches!(result, Err(DataStoreError::NotFound { .. })));
+}
@@ -1,6 +1,6 @@
 //! Helper functions for link tests
 
-use crate::models::Link;
+use crate::models::{Link, ProvisioningState};
 use serde_json::json;
 use uuid::Uuid;
 
@@ -17,6 +17,11 @@ pub fn create_test_link(name: &str, source_node_id: Uuid, dest_node_id: Option<U
         bandwidth: Some(1_000_000_000), // 1 Gbps
         link_type: Some("ethernet".to_string()),
         is_internet_circuit: false,
+        provider: None,
+        circuit_id: None,
+        ordered_on: None,
+        delivered_on: None,
+        provisioning_state: ProvisioningState::Live,
         custom_data: json!({"test": "data"}),
     }
 }
@@ -34,6 +39,11 @@ pub fn create_internet_circuit_link(name: &str, source_node_id: Uuid) -> Link {
         bandwidth: Some(100_000_000), // 100 Mbps
         link_type: Some("fiber".to_string()),
         is_internet_circuit: true,
+        provider: Some("ISP1".to_string()),
+        circuit_id: None,
+        ordered_on: None,
+        delivered_on: None,
+        provisioning_state: ProvisioningState::Live,
         custom_data: json!({"provider": "ISP1"}),
     }
 }
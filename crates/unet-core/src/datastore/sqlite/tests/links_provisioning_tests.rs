@@ -0,0 +1,108 @@
+//! Tests for circuit metadata and provisioning states in `SQLite` link storage
+
+use super::setup::setup_test_db;
+use crate::datastore::DataStore;
+use crate::datastore::types::{DataStoreError, Filter, FilterOperation, FilterValue, QueryOptions};
+use crate::models::{Link, LinkBuilder, ProvisioningState};
+use uuid::Uuid;
+
+fn filter(field: &str, operation: FilterOperation, value: &str) -> QueryOptions {
+    QueryOptions {
+        filters: vec![Filter {
+            field: field.to_string(),
+            operation,
+            value: FilterValue::String(value.to_string()),
+        }],
+        ..QueryOptions::default()
+    }
+}
+
+fn circuit(name: &str, provider: &str, state: ProvisioningState, ordered_on: &str) -> Link {
+    LinkBuilder::new()
+        .name(name)
+        .source_node_id(Uuid::new_v4())
+        .node_a_interface("xe-0/0/0")
+        .is_internet_circuit(true)
+        .provider(provider)
+        .circuit_id(format!("{provider}-{name}"))
+        .ordered_on(ordered_on.parse().unwrap())
+        .provisioning_state(state)
+        .build()
+        .unwrap()
+}
+
+async fn names(store: &dyn DataStore, options: &QueryOptions) -> Vec<String> {
+    let mut names: Vec<String> = store
+        .list_links(options)
+        .await
+        .unwrap()
+        .items
+        .into_iter()
+        .map(|link| link.name)
+        .collect();
+    names.sort();
+    names
+}
+
+#[tokio::test]
+async fn test_circuit_metadata_round_trip() {
+    let test_db = setup_test_db().await;
+    let mut link = circuit(
+        "transit-1",
+        "lumen",
+        ProvisioningState::Delivered,
+        "2026-01-05",
+    );
+    link.delivered_on = Some("2026-02-01".parse().unwrap());
+
+    let created = test_db.store.create_link(&link).await.unwrap();
+
+    assert_eq!(created.provider.as_deref(), Some("lumen"));
+    assert_eq!(created.circuit_id.as_deref(), Some("lumen-transit-1"));
+    assert_eq!(created.ordered_on, link.ordered_on);
+    assert_eq!(created.delivered_on, link.delivered_on);
+    assert_eq!(created.provisioning_state, ProvisioningState::Delivered);
+}
+
+#[tokio::test]
+async fn test_filter_links_by_circuit_metadata() {
+    let test_db = setup_test_db().await;
+    let store = &test_db.store;
+    for link in [
+        circuit(
+            "transit-1",
+            "lumen",
+            ProvisioningState::Ordered,
+            "2026-01-05",
+        ),
+        circuit("transit-2", "lumen", ProvisioningState::Live, "2025-06-01"),
+        circuit("transit-3", "zayo", ProvisioningState::Tested, "2026-02-10"),
+    ] {
+        store.create_link(&link).await.unwrap();
+    }
+
+    let by_provider = filter("provider", FilterOperation::Equals, "lumen");
+    assert_eq!(names(store, &by_provider).await, ["transit-1", "transit-2"]);
+
+    let by_circuit = filter("circuit_id", FilterOperation::Contains, "zayo");
+    assert_eq!(names(store, &by_circuit).await, ["transit-3"]);
+
+    let live = filter("provisioning_state", FilterOperation::Equals, "live");
+    assert_eq!(names(store, &live).await, ["transit-2"]);
+
+    let ordered_this_year = filter(
+        "ordered_on",
+        FilterOperation::GreaterThanOrEqual,
+        "2026-01-01",
+    );
+    assert_eq!(
+        names(store, &ordered_this_year).await,
+        ["transit-1", "transit-3"]
+    );
+
+    let bad_state = filter("provisioning_state", FilterOperation::Equals, "active");
+    assert!(matches!(
+        store.list_links(&bad_state).await,
+        Err(DataStoreError::ValidationError { .. })
+    ));
+}
@@ -1,6 +1,8 @@
 //! Tests for `SQLite` datastore implementations
 
 pub mod links;
+mod links_provisioning_tests;
 mod links_tests;
 mod nodes_error_tests;
+mod nodes_ipv6_tests;
 pub mod setup;
@@ -35,6 +35,8 @@ async fn test_batch_nodes_mixed_operations() {
         purchase_date: None,
         warranty_expires: None,
         custom_data: json!({}),
+        management_ipv6: None,
+        management_zone: None,
     };
 
     let updated_node = Node {
@@ -55,6 +57,8 @@ async fn test_batch_nodes_mixed_operations() {
         purchase_date: None,
         warranty_expires: None,
         custom_data: json!({}),
+        management_ipv6: None,
+        management_zone: None,
     };
 
     let non_existent_id = Uuid::new_v4();
@@ -114,6 +118,8 @@ async fn test_batch_nodes_all_failures() {
         purchase_date: None,
         warranty_expires: None,
         custom_data: json!({}),
+        management_ipv6: None,
+        management_zone: None,
     };
 
     let operations = vec![
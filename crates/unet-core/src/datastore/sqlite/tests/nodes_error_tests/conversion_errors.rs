@@ -29,6 +29,8 @@ async fn test_node_conversion_edge_cases() {
         purchase_date: None,
         warranty_expires: None,
         custom_data: json!(null), // Test null JSON
+        management_ipv6: None,
+        management_zone: None,
     };
 
     let result = create_node(&test_db.store, &minimal_node).await;
@@ -40,6 +40,8 @@ async fn test_create_node_with_invalid_json_in_custom_data() {
                 }
             }
         }),
+        management_ipv6: None,
+        management_zone: None,
     };
 
     // This should work despite complex JSON
@@ -77,6 +79,8 @@ async fn test_create_node_duplicate_id() {
         purchase_date: None,
         warranty_expires: None,
         custom_data: json!({}),
+        management_ipv6: None,
+        management_zone: None,
     };
 
     // Create the node once - should succeed
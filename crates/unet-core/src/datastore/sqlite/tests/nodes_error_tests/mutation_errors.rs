@@ -30,6 +30,8 @@ async fn test_update_node_not_found() {
         purchase_date: None,
         warranty_expires: None,
         custom_data: serde_json::json!({}),
+        management_ipv6: None,
+        management_zone: None,
     };
 
     let result = update_node(&test_db.store, &non_existent_node).await;
@@ -0,0 +1,68 @@
+//! Tests for IPv6 management addressing in `SQLite` node storage
+
+use super::setup::setup_test_db;
+use crate::datastore::DataStore;
+use crate::datastore::types::{DataStoreError, Filter, FilterOperation, FilterValue, QueryOptions};
+use crate::models::{DeviceRole, NodeBuilder, Vendor};
+
+fn management_ip_filter(value: &str) -> QueryOptions {
+    QueryOptions {
+        filters: vec![Filter {
+            field: "management_ip".to_string(),
+            operation: FilterOperation::Equals,
+            value: FilterValue::String(value.to_string()),
+        }],
+        ..QueryOptions::default()
+    }
+}
+
+#[tokio::test]
+async fn test_dual_stack_node_round_trip_and_filter() {
+    let test_db = setup_test_db().await;
+    let node = NodeBuilder::new()
+        .name("edge1")
+        .domain("example.com")
+        .vendor(Vendor::Juniper)
+        .model("MX204")
+        .role(DeviceRole::Router)
+        .management_ip("192.0.2.1".parse().unwrap())
+        .management_ipv6("fe80::1".parse().unwrap())
+        .management_zone("eth0")
+        .build()
+        .unwrap();
+
+    let created = test_db.store.create_node(&node).await.unwrap();
+    assert_eq!(created.management_ipv6, node.management_ipv6);
+    assert_eq!(created.management_zone.as_deref(), Some("eth0"));
+
+    // Either family matches, in any textual form
+    for query in ["192.0.2.1", "FE80:0::1", "fe80::1%eth0"] {
+        let found = test_db
+            .store
+            .list_nodes(&management_ip_filter(query))
+            .await
+            .unwrap();
+        assert_eq!(found.items.len(), 1, "{query}");
+    }
+    let missing = test_db
+        .store
+        .list_nodes(&management_ip_filter("2001:db8::1"))
+        .await
+        .unwrap();
+    assert!(missing.items.is_empty());
+}
+
+#[tokio::test]
+async fn test_management_ip_filter_rejects_invalid_address() {
+    let test_db = setup_test_db().await;
+
+    let result = test_db
+        .store
+        .list_nodes(&management_ip_filter("not-an-ip"))
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(DataStoreError::ValidationError { .. })
+    ));
+}
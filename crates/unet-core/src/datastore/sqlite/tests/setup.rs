@@ -3,7 +3,9 @@
 use crate::datastore::sqlite::SqliteStore;
 use crate::models::{DeviceRole, Lifecycle, Node, Vendor};
 use chrono::Utc;
+use migration::Migrator;
 use sea_orm::{ActiveModelTrait, Set};
+use sea_orm_migration::MigratorTrait;
 use serde_json::json;
 use std::net::IpAddr;
 use uuid::Uuid;
@@ -57,6 +59,34 @@ pub async fn setup_test_db() -> TestDb {
     TestDb::new().await.expect("Failed to create test database")
 }
 
+/// Creates a store with every migration applied
+pub async fn migrated_store() -> SqliteStore {
+    let store = SqliteStore::new("sqlite::memory:")
+        .await
+        .expect("Failed to create test database");
+    Migrator::up(store.connection(), None)
+        .await
+        .expect("Failed to apply migrations");
+    store
+}
+
+/// Creates a store holding only the settings table, for code that keeps its
+/// documents in settings
+pub async fn settings_store() -> SqliteStore {
+    use sea_orm::{ConnectionTrait, Database, DatabaseBackend, Schema};
+
+    let connection = Database::connect("sqlite::memory:")
+        .await
+        .expect("Failed to create test database");
+    let schema = Schema::new(DatabaseBackend::Sqlite);
+    let stmt = schema.create_table_from_entity(crate::entities::settings::Entity);
+    connection
+        .execute(connection.get_database_backend().build(&stmt))
+        .await
+        .expect("Failed to create settings table");
+    SqliteStore::from_connection(connection)
+}
+
 /// Create a test node in the database
 pub async fn create_test_node(
     store: &SqliteStore,
@@ -81,6 +111,8 @@ pub async fn create_test_node(
         purchase_date: None,
         warranty_expires: None,
         custom_data: json!({}),
+        management_ipv6: None,
+        management_zone: None,
     };
 
     let active_node = crate::entities::nodes::ActiveModel {
@@ -100,6 +132,8 @@ pub async fn create_test_node(
         custom_data: Set(Some(serde_json::to_string(&node.custom_data)?)),
         created_at: Set(Utc::now().to_rfc3339()),
         updated_at: Set(Utc::now().to_rfc3339()),
+        management_ipv6: Set(None),
+        management_zone: Set(None),
     };
 
     active_node.insert(store.connection()).await?;
@@ -1,6 +1,7 @@
 //! Transaction implementation for `SQLite`
 
-use super::super::types::{DataStoreError, DataStoreResult, Transaction};
+use super::super::types::{DataStoreResult, Transaction};
+use super::errors::transaction_error;
 use async_trait::async_trait;
 use sea_orm::DatabaseTransaction;
 
@@ -24,18 +25,14 @@ impl Transaction for SqliteTransaction {
         self.txn
             .commit()
             .await
-            .map_err(|e| DataStoreError::TransactionError {
-                message: format!("Failed to commit transaction: {e}"),
-            })
+            .map_err(|e| transaction_error("Failed to commit transaction", &e))
     }
 
     async fn rollback(self: Box<Self>) -> DataStoreResult<()> {
         self.txn
             .rollback()
             .await
-            .map_err(|e| DataStoreError::TransactionError {
-                message: format!("Failed to rollback transaction: {e}"),
-            })
+            .map_err(|e| transaction_error("Failed to rollback transaction", &e))
     }
 }
 
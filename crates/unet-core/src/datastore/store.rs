@@ -0,0 +1,487 @@
+//! The `DataStore` trait implemented by every backend
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::change_log::{ChangeEvent, EventFilter};
+use crate::models::derived::{
+    InterfaceStatus, MetricPoint, MetricQuery, NodeStatus, PerformanceMetrics,
+};
+use crate::models::{Link, Location, Node};
+use crate::policy::PolicyExecutionResult;
+use crate::snmp::shards::ShardLease;
+
+use super::types::{
+    BatchOperation, BatchResult, DataStoreError, DataStoreResult, PagedResult, PruneStats,
+    QueryOptions, Transaction,
+};
+
+/// Main `DataStore` trait for abstracting data access
+#[async_trait]
+#[cfg_attr(any(test, feature = "test-utils"), mockall::automock)]
+pub trait DataStore: Send + Sync {
+    /// Returns the name/type of this datastore implementation
+    fn name(&self) -> &'static str;
+
+    /// Checks if the datastore is healthy and can serve requests
+    ///
+    /// # Errors
+    /// Returns an error if the datastore is unhealthy or unreachable
+    async fn health_check(&self) -> DataStoreResult<()>;
+
+    /// Begins a new transaction
+    ///
+    /// # Errors
+    /// Returns an error if the transaction cannot be started
+    async fn begin_transaction(&self) -> DataStoreResult<Box<dyn Transaction>>;
+
+    // Node operations
+    /// Creates a new node
+    ///
+    /// # Errors
+    /// Returns an error if the node cannot be created or validation fails
+    async fn create_node(&self, node: &Node) -> DataStoreResult<Node>;
+
+    /// Gets a node by ID
+    ///
+    /// # Errors
+    /// Returns an error if the database query fails
+    async fn get_node(&self, id: &Uuid) -> DataStoreResult<Option<Node>>;
+
+    /// Gets a node by ID, returning an error if not found
+    async fn get_node_required(&self, id: &Uuid) -> DataStoreResult<Node> {
+        self.get_node(id)
+            .await?
+            .ok_or_else(|| DataStoreError::not_found("Node", id))
+    }
+
+    /// Lists nodes with optional filtering, sorting, and pagination
+    ///
+    /// # Errors
+    /// Returns an error if the database query fails
+    async fn list_nodes(&self, options: &QueryOptions) -> DataStoreResult<PagedResult<Node>>;
+
+    /// Updates an existing node
+    ///
+    /// # Errors
+    /// Returns an error if the node cannot be updated or validation fails
+    async fn update_node(&self, node: &Node) -> DataStoreResult<Node>;
+
+    /// Deletes a node by ID
+    ///
+    /// # Errors
+    /// Returns an error if the node cannot be deleted or doesn't exist
+    async fn delete_node(&self, id: &Uuid) -> DataStoreResult<()>;
+
+    /// Gets nodes by location ID
+    ///
+    /// # Errors
+    /// Returns an error if the database query fails
+    async fn get_nodes_by_location(&self, location_id: &Uuid) -> DataStoreResult<Vec<Node>>;
+
+    /// Searches nodes by name (case-insensitive partial match)
+    ///
+    /// # Errors
+    /// Returns an error if the database query fails
+    async fn search_nodes_by_name(&self, name: &str) -> DataStoreResult<Vec<Node>>;
+
+    // Link operations
+    /// Creates a new link
+    ///
+    /// # Errors
+    /// Returns an error if the link cannot be created or validation fails
+    async fn create_link(&self, link: &Link) -> DataStoreResult<Link>;
+
+    /// Gets a link by ID
+    ///
+    /// # Errors
+    /// Returns an error if the database query fails
+    async fn get_link(&self, id: &Uuid) -> DataStoreResult<Option<Link>>;
+
+    /// Gets a link by ID, returning an error if not found
+    async fn get_link_required(&self, id: &Uuid) -> DataStoreResult<Link> {
+        self.get_link(id)
+            .await?
+            .ok_or_else(|| DataStoreError::not_found("Link", id))
+    }
+
+    /// Lists links with optional filtering, sorting, and pagination
+    ///
+    /// # Errors
+    /// Returns an error if the database query fails
+    async fn list_links(&self, options: &QueryOptions) -> DataStoreResult<PagedResult<Link>>;
+
+    /// Updates an existing link
+    ///
+    /// # Errors
+    /// Returns an error if the link cannot be updated or validation fails
+    async fn update_link(&self, link: &Link) -> DataStoreResult<Link>;
+
+    /// Deletes a link by ID
+    ///
+    /// # Errors
+    /// Returns an error if the link cannot be deleted or doesn't exist
+    async fn delete_link(&self, id: &Uuid) -> DataStoreResult<()>;
+
+    /// Gets links involving a specific node
+    ///
+    /// # Errors
+    /// Returns an error if the database query fails
+    async fn get_links_for_node(&self, node_id: &Uuid) -> DataStoreResult<Vec<Link>>;
+
+    /// Gets links between two specific nodes
+    ///
+    /// # Errors
+    /// Returns an error if the database query fails
+    async fn get_links_between_nodes(
+        &self,
+        first_node_id: &Uuid,
+        second_node_id: &Uuid,
+    ) -> DataStoreResult<Vec<Link>>;
+
+    // Location operations
+    /// Creates a new location
+    ///
+    /// # Errors
+    /// Returns an error if the location cannot be created or validation fails
+    async fn create_location(&self, location: &Location) -> DataStoreResult<Location>;
+
+    /// Gets a location by ID
+    ///
+    /// # Errors
+    /// Returns an error if the database query fails
+    async fn get_location(&self, id: &Uuid) -> DataStoreResult<Option<Location>>;
+
+    /// Gets a location by ID, returning an error if not found
+    async fn get_location_required(&self, id: &Uuid) -> DataStoreResult<Location> {
+        self.get_location(id)
+            .await?
+            .ok_or_else(|| DataStoreError::not_found("Location", id))
+    }
+
+    /// Lists locations with optional filtering, sorting, and pagination
+    ///
+    /// # Errors
+    /// Returns an error if the database query fails
+    async fn list_locations(
+        &self,
+        options: &QueryOptions,
+    ) -> DataStoreResult<PagedResult<Location>>;
+
+    /// Updates an existing location
+    ///
+    /// # Errors
+    /// Returns an error if the location cannot be updated or validation fails
+    async fn update_location(&self, location: &Location) -> DataStoreResult<Location>;
+
+    /// Deletes a location by ID
+    ///
+    /// # Errors
+    /// Returns an error if the location cannot be deleted or doesn't exist
+    async fn delete_location(&self, id: &Uuid) -> DataStoreResult<()>;
+
+    // Vendor operations
+    /// Creates a new vendor record
+    async fn create_vendor(&self, name: &str) -> DataStoreResult<()>;
+
+    /// Lists all vendor names
+    async fn list_vendors(&self) -> DataStoreResult<Vec<String>>;
+
+    /// Deletes a vendor record by name
+    async fn delete_vendor(&self, name: &str) -> DataStoreResult<()>;
+
+    // Settings operations (namespaced JSON documents)
+    /// Gets a settings document by namespace and key
+    async fn get_setting(
+        &self,
+        _namespace: &str,
+        _key: &str,
+    ) -> DataStoreResult<Option<serde_json::Value>> {
+        Err(DataStoreError::unsupported("get_setting"))
+    }
+
+    /// Lists all settings documents in a namespace, ordered by key
+    async fn list_settings(
+        &self,
+        _namespace: &str,
+    ) -> DataStoreResult<Vec<(String, serde_json::Value)>> {
+        Err(DataStoreError::unsupported("list_settings"))
+    }
+
+    /// Creates or replaces a settings document
+    async fn put_setting(
+        &self,
+        _namespace: &str,
+        _key: &str,
+        _value: &serde_json::Value,
+    ) -> DataStoreResult<()> {
+        Err(DataStoreError::unsupported("put_setting"))
+    }
+
+    /// Deletes a settings document, returning `NotFound` if it does not exist
+    async fn delete_setting(&self, _namespace: &str, _key: &str) -> DataStoreResult<()> {
+        Err(DataStoreError::unsupported("delete_setting"))
+    }
+
+    // Change log operations
+    /// Appends an event to the change log and returns it with its sequence
+    /// number
+    async fn append_change_event(&self, _event: &ChangeEvent) -> DataStoreResult<ChangeEvent> {
+        Err(DataStoreError::unsupported("append_change_event"))
+    }
+
+    /// Lists the events passing `filter`, in log order
+    async fn list_change_events(&self, _filter: &EventFilter) -> DataStoreResult<Vec<ChangeEvent>> {
+        Err(DataStoreError::unsupported("list_change_events"))
+    }
+
+    /// Sequence number of the latest event in the change log; 0 when empty
+    async fn last_change_sequence(&self) -> DataStoreResult<i64> {
+        Err(DataStoreError::unsupported("last_change_sequence"))
+    }
+
+    /// Deletes change events recorded before `cutoff`; with `dry_run`, only
+    /// measures them
+    async fn prune_change_events(
+        &self,
+        _cutoff: DateTime<Utc>,
+        _dry_run: bool,
+    ) -> DataStoreResult<PruneStats> {
+        Err(DataStoreError::unsupported("prune_change_events"))
+    }
+
+    // Shard lease operations
+    /// Stores `lease` unless another collector holds the shard at `now`, in
+    /// one conditional write; returns whether the lease was stored
+    async fn acquire_shard_lease(
+        &self,
+        _lease: &ShardLease,
+        _now: DateTime<Utc>,
+    ) -> DataStoreResult<bool> {
+        Err(DataStoreError::unsupported("acquire_shard_lease"))
+    }
+
+    /// Lists every shard lease, expired or not, by shard number
+    async fn list_shard_leases(&self) -> DataStoreResult<Vec<ShardLease>> {
+        Err(DataStoreError::unsupported("list_shard_leases"))
+    }
+
+    /// Removes the lease on `shard` if `collector` holds it
+    async fn release_shard_lease(&self, _shard: u32, _collector: &str) -> DataStoreResult<()> {
+        Err(DataStoreError::unsupported("release_shard_lease"))
+    }
+
+    // Batch operations
+    /// Performs batch operations on nodes
+    ///
+    /// # Errors
+    /// Returns an error if any batch operation fails
+    async fn batch_nodes(
+        &self,
+        operations: &[BatchOperation<Node>],
+    ) -> DataStoreResult<BatchResult>;
+
+    /// Performs batch operations on links
+    ///
+    /// # Errors
+    /// Returns an error if any batch operation fails
+    async fn batch_links(
+        &self,
+        operations: &[BatchOperation<Link>],
+    ) -> DataStoreResult<BatchResult>;
+
+    /// Performs batch operations on locations
+    ///
+    /// # Errors
+    /// Returns an error if any batch operation fails
+    async fn batch_locations(
+        &self,
+        operations: &[BatchOperation<Location>],
+    ) -> DataStoreResult<BatchResult>;
+
+    // Statistics and metadata
+    /// Gets count of all entities
+    ///
+    /// # Errors
+    /// Returns an error if the database query fails
+    async fn get_entity_counts(&self) -> DataStoreResult<HashMap<String, usize>>;
+
+    /// Gets datastore statistics (implementation-specific)
+    ///
+    /// # Errors
+    /// Returns an error if the statistics cannot be collected
+    async fn get_statistics(&self) -> DataStoreResult<HashMap<String, serde_json::Value>>;
+
+    // Maintenance operations
+    /// Rebuilds the database file to reclaim space left by deleted rows
+    async fn vacuum(&self) -> DataStoreResult<()> {
+        Err(DataStoreError::unsupported("vacuum"))
+    }
+
+    /// Refreshes the statistics the query planner uses
+    async fn analyze(&self) -> DataStoreResult<()> {
+        Err(DataStoreError::unsupported("analyze"))
+    }
+
+    /// Deletes derived-state and polling rows whose node no longer exists
+    ///
+    /// Returns the number of deleted rows per table.
+    async fn cleanup_orphaned_records(&self) -> DataStoreResult<HashMap<String, usize>> {
+        Err(DataStoreError::unsupported("cleanup_orphaned_records"))
+    }
+
+    /// Counts the rows [`cleanup_orphaned_records`](Self::cleanup_orphaned_records)
+    /// would delete, without deleting them
+    ///
+    /// Returns the number of orphaned rows per table.
+    async fn count_orphaned_records(&self) -> DataStoreResult<HashMap<String, usize>> {
+        Err(DataStoreError::unsupported("count_orphaned_records"))
+    }
+
+    /// Deletes node status, with its interfaces, last updated before `cutoff`,
+    /// and performance history recorded before it
+    ///
+    /// Returns the number of deleted rows per table.
+    async fn prune_derived_state(
+        &self,
+        _cutoff: DateTime<Utc>,
+    ) -> DataStoreResult<HashMap<String, usize>> {
+        Err(DataStoreError::unsupported("prune_derived_state"))
+    }
+
+    /// Compacts performance samples recorded before `cutoff` into hourly
+    /// rollups and deletes them; with `dry_run`, only measures them
+    ///
+    /// `cutoff` is rounded down to the hour so no hour is split between
+    /// samples and rollups. The bytes reported are net of the rollups written.
+    async fn prune_performance_samples(
+        &self,
+        _cutoff: DateTime<Utc>,
+        _dry_run: bool,
+    ) -> DataStoreResult<PruneStats> {
+        Err(DataStoreError::unsupported("prune_performance_samples"))
+    }
+
+    /// Deletes performance rollups of hours that started before `cutoff`;
+    /// with `dry_run`, only measures them
+    async fn prune_performance_rollups(
+        &self,
+        _cutoff: DateTime<Utc>,
+        _dry_run: bool,
+    ) -> DataStoreResult<PruneStats> {
+        Err(DataStoreError::unsupported("prune_performance_rollups"))
+    }
+
+    // Derived state operations (basic implementation)
+    /// Gets node status (derived state) by node ID
+    async fn get_node_status(&self, _node_id: &Uuid) -> DataStoreResult<Option<NodeStatus>> {
+        Err(DataStoreError::unsupported("get_node_status"))
+    }
+
+    /// Gets interface status for a specific node
+    async fn get_node_interfaces(&self, _node_id: &Uuid) -> DataStoreResult<Vec<InterfaceStatus>> {
+        Err(DataStoreError::unsupported("get_node_interfaces"))
+    }
+
+    /// Gets performance metrics for a specific node
+    async fn get_node_metrics(
+        &self,
+        _node_id: &Uuid,
+    ) -> DataStoreResult<Option<PerformanceMetrics>> {
+        Err(DataStoreError::unsupported("get_node_metrics"))
+    }
+
+    /// Gets the status of every node in `node_ids` that has one
+    ///
+    /// The default implementation reads one node at a time; stores should
+    /// override it to read all statuses together.
+    async fn get_node_statuses(&self, node_ids: &[Uuid]) -> DataStoreResult<Vec<NodeStatus>> {
+        let mut statuses = Vec::with_capacity(node_ids.len());
+        for node_id in node_ids {
+            if let Some(status) = self.get_node_status(node_id).await? {
+                statuses.push(status);
+            }
+        }
+        Ok(statuses)
+    }
+
+    /// Records polled performance metrics of a node in its history
+    async fn record_performance_metrics(
+        &self,
+        _node_id: &Uuid,
+        _recorded_at: DateTime<Utc>,
+        _metrics: &PerformanceMetrics,
+    ) -> DataStoreResult<()> {
+        Err(DataStoreError::unsupported("record_performance_metrics"))
+    }
+
+    /// Reads one metric of a node's history, aggregated per `query.step`
+    ///
+    /// Buckets without samples are left out.
+    async fn query_performance_history(
+        &self,
+        _node_id: &Uuid,
+        _query: &MetricQuery,
+    ) -> DataStoreResult<Vec<MetricPoint>> {
+        Err(DataStoreError::unsupported("query_performance_history"))
+    }
+
+    // Policy-related operations
+    /// Stores a policy execution result
+    async fn store_policy_result(
+        &self,
+        _node_id: &Uuid,
+        _rule_id: &str,
+        _result: &PolicyExecutionResult,
+    ) -> DataStoreResult<()> {
+        Err(DataStoreError::unsupported("store_policy_result"))
+    }
+
+    /// Gets policy execution results for a node
+    async fn get_policy_results(
+        &self,
+        _node_id: &Uuid,
+    ) -> DataStoreResult<Vec<PolicyExecutionResult>> {
+        Err(DataStoreError::unsupported("get_policy_results"))
+    }
+
+    /// Gets the latest policy execution results for a node
+    async fn get_latest_policy_results(
+        &self,
+        node_id: &Uuid,
+    ) -> DataStoreResult<Vec<PolicyExecutionResult>> {
+        // Default implementation delegates to get_policy_results
+        self.get_policy_results(node_id).await
+    }
+
+    /// Gets policy execution results for a specific rule across all nodes
+    async fn get_rule_results(
+        &self,
+        _rule_id: &str,
+    ) -> DataStoreResult<Vec<(Uuid, PolicyExecutionResult)>> {
+        Err(DataStoreError::unsupported("get_rule_results"))
+    }
+
+    /// Updates `custom_data` field for a node (used by SET actions)
+    async fn update_node_custom_data(
+        &self,
+        node_id: &Uuid,
+        custom_data: &serde_json::Value,
+    ) -> DataStoreResult<()> {
+        // Default implementation: get node, update custom_data, save node
+        let mut node = self.get_node_required(node_id).await?;
+        node.custom_data = custom_data.clone();
+        self.update_node(&node).await?;
+        Ok(())
+    }
+
+    /// Gets all nodes for policy evaluation
+    async fn get_nodes_for_policy_evaluation(&self) -> DataStoreResult<Vec<Node>> {
+        // Default implementation: get all nodes using list_nodes with no filters
+        let options = QueryOptions::default();
+        let result = self.list_nodes(&options).await?;
+        Ok(result.items)
+    }
+}
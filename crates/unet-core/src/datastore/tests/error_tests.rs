@@ -30,3 +30,41 @@ fn test_datastore_error_display() {
     };
     assert!(error.to_string().contains("Constraint violation"));
 }
+
+#[test]
+fn test_datastore_error_is_retryable() {
+    assert!(DataStoreError::Timeout { seconds: 5 }.is_retryable());
+    assert!(
+        DataStoreError::Busy {
+            message: "database is locked".to_string(),
+        }
+        .is_retryable()
+    );
+
+    // Messages are not inspected; only the driver's codes make an error busy
+    assert!(
+        !DataStoreError::InternalError {
+            message: "Failed to create node: database is locked".to_string(),
+        }
+        .is_retryable()
+    );
+    assert!(
+        !DataStoreError::ConnectionError {
+            message: "Connection reset by peer".to_string(),
+        }
+        .is_retryable()
+    );
+    assert!(
+        !DataStoreError::ValidationError {
+            message: "database is locked".to_string(),
+        }
+        .is_retryable()
+    );
+    assert!(
+        !DataStoreError::NotFound {
+            entity_type: "Node".to_string(),
+            id: "123".to_string(),
+        }
+        .is_retryable()
+    );
+}
@@ -2,7 +2,17 @@
 
 use super::super::DataStore;
 use super::super::types::{DataStoreError, DataStoreResult, Transaction};
+use std::collections::hash_map::RandomState;
 use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// Retry count for datastore calls made on behalf of a CLI or API request
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+/// Upper bound of the delay before the first retry
+pub const RETRY_BASE_DELAY: Duration = Duration::from_millis(50);
+/// Upper bound of the delay before any retry
+pub const RETRY_MAX_DELAY: Duration = Duration::from_secs(2);
 
 /// Execute a function within a transaction with automatic commit/rollback
 ///
@@ -126,8 +136,9 @@ where
 
 /// Retry a transaction operation up to a specified number of times
 ///
-/// This is useful for handling transient failures like deadlocks or temporary
-/// resource unavailability.
+/// Each attempt runs in its own transaction. Only retryable errors such as a
+/// locked database are retried (see [`retry_operation`]); any other error is
+/// returned immediately.
 ///
 /// # Arguments
 /// * `datastore` - The datastore to create transactions on
@@ -136,10 +147,11 @@ where
 ///
 /// # Returns
 /// * `Ok(T)` - The result if any attempt succeeds
-/// * `Err(DataStoreError)` - If all attempts fail (returns the last error)
+/// * `Err(DataStoreError)` - The first permanent error, or the last error once
+///   all attempts fail
 ///
 /// # Errors
-/// Returns an error if all retry attempts fail
+/// Returns an error if the operation fails permanently or all retry attempts fail
 pub async fn retry_transaction<T, F, Fut>(
     datastore: &dyn DataStore,
     max_retries: u32,
@@ -150,27 +162,60 @@ where
     Fut: Future<Output = DataStoreResult<T>>,
     T: Send,
 {
-    let mut last_error = None;
-
-    for attempt in 0..=max_retries {
+    let mut attempt = 0;
+    loop {
         match with_transaction(datastore, |_tx| operation()).await {
-            Ok(result) => return Ok(result),
-            Err(error) => {
-                last_error = Some(error);
-                if attempt < max_retries {
-                    // Optional: add exponential backoff here
-                    tokio::time::sleep(tokio::time::Duration::from_millis(
-                        100 * u64::from(attempt + 1),
-                    ))
-                    .await;
-                }
+            Err(error) if error.is_retryable() && attempt < max_retries => {
+                backoff(attempt, &error).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Retry an operation that fails with a retryable error
+///
+/// See [`DataStoreError::is_retryable`] for which errors are retried.
+///
+/// Waits between attempts with exponential backoff and full jitter, starting
+/// at [`RETRY_BASE_DELAY`] and capped at [`RETRY_MAX_DELAY`], so concurrent
+/// writers contending for a locked database do not retry in lockstep.
+///
+/// # Errors
+/// Returns the first permanent error, or the last error once `max_retries`
+/// retries have failed
+pub async fn retry_operation<T, F, Fut>(max_retries: u32, mut operation: F) -> DataStoreResult<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = DataStoreResult<T>>,
+{
+    let mut attempt = 0;
+    loop {
+        match operation().await {
+            Err(error) if error.is_retryable() && attempt < max_retries => {
+                backoff(attempt, &error).await;
+                attempt += 1;
             }
+            result => return result,
         }
     }
+}
+
+/// Sleeps before retry number `attempt + 1`
+async fn backoff(attempt: u32, error: &DataStoreError) {
+    let delay = backoff_delay(attempt);
+    tracing::debug!(attempt, ?delay, %error, "Retrying datastore operation");
+    tokio::time::sleep(delay).await;
+}
 
-    Err(
-        last_error.unwrap_or_else(|| DataStoreError::TransactionError {
-            message: "Unknown error in retry_transaction".to_string(),
-        }),
-    )
+/// Random delay before retry number `attempt + 1`
+fn backoff_delay(attempt: u32) -> Duration {
+    let ceiling = RETRY_BASE_DELAY
+        .saturating_mul(2_u32.saturating_pow(attempt))
+        .min(RETRY_MAX_DELAY);
+    let ceiling_ms = u64::try_from(ceiling.as_millis()).unwrap_or(u64::MAX);
+    // No RNG dependency: a fresh `RandomState` is randomly keyed
+    let random = RandomState::new().build_hasher().finish();
+    Duration::from_millis(random % (ceiling_ms + 1))
 }
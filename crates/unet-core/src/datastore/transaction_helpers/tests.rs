@@ -142,15 +142,37 @@ mod transaction_helper_tests {
     #[tokio::test]
     async fn test_retry_transaction_all_failures() {
         let datastore = mock_datastore(false);
+        let attempts = std::sync::atomic::AtomicU32::new(0);
 
-        let result = retry_transaction(&datastore, 2, || async {
-            Err::<i32, DataStoreError>(DataStoreError::ValidationError {
-                message: "persistent error".to_string(),
-            })
+        let result = retry_transaction(&datastore, 2, || {
+            attempts.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            async { Err::<i32, DataStoreError>(DataStoreError::Timeout { seconds: 1 }) }
         })
         .await;
 
-        assert!(result.is_err());
+        assert!(matches!(result, Err(DataStoreError::Timeout { .. })));
+        assert_eq!(attempts.into_inner(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_operation_stops_at_permanent_error() {
+        let attempts = std::sync::atomic::AtomicU32::new(0);
+
+        let result = retry_operation(3, || {
+            attempts.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            async {
+                Err::<i32, DataStoreError>(DataStoreError::ValidationError {
+                    message: "persistent error".to_string(),
+                })
+            }
+        })
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(DataStoreError::ValidationError { .. })
+        ));
+        assert_eq!(attempts.into_inner(), 1);
     }
 
     #[tokio::test]
@@ -165,8 +187,8 @@ mod transaction_helper_tests {
                 let mut count = counter.lock().expect("lock retry attempt count");
                 *count += 1;
                 if *count < 3 {
-                    Err::<i32, DataStoreError>(DataStoreError::ValidationError {
-                        message: "temporary error".to_string(),
+                    Err::<i32, DataStoreError>(DataStoreError::Busy {
+                        message: "database is locked".to_string(),
                     })
                 } else {
                     Ok(42)
@@ -4,6 +4,8 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::error;
+
 /// Errors that can occur during datastore operations
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum DataStoreError {
@@ -64,6 +66,59 @@ pub enum DataStoreError {
         /// The name of the unsupported operation
         operation: String,
     },
+
+    /// The database was busy or locked, or the connection to it dropped
+    #[error("Database busy: {message}")]
+    Busy {
+        /// The driver's error message
+        message: String,
+    },
+}
+
+impl DataStoreError {
+    /// Error for a missing entity
+    #[must_use]
+    pub fn not_found(entity_type: &str, id: &impl std::fmt::Display) -> Self {
+        Self::NotFound {
+            entity_type: entity_type.to_string(),
+            id: id.to_string(),
+        }
+    }
+
+    /// Error for an operation the datastore does not implement
+    #[must_use]
+    pub fn unsupported(operation: &str) -> Self {
+        Self::UnsupportedOperation {
+            operation: operation.to_string(),
+        }
+    }
+
+    /// Whether the operation may succeed if retried unchanged
+    ///
+    /// Only timeouts and [`Busy`](Self::Busy) errors, which datastores report
+    /// from the driver's error codes, are transient; everything else
+    /// (missing entities, invalid input, constraint violations, failed
+    /// queries) fails the same way on every attempt.
+    #[must_use]
+    pub const fn is_retryable(&self) -> bool {
+        matches!(self, Self::Timeout { .. } | Self::Busy { .. })
+    }
+
+    /// Stable code identifying the kind of failure, from [`crate::error`]
+    #[must_use]
+    pub const fn error_code(&self) -> &'static str {
+        match self {
+            Self::NotFound { .. } => error::NOT_FOUND,
+            Self::ValidationError { .. } => error::VALIDATION_ERROR,
+            Self::ConstraintViolation { .. } => error::DB_CONSTRAINT_VIOLATION,
+            Self::TransactionError { .. } => error::DB_TRANSACTION_FAILED,
+            Self::ConnectionError { .. } => error::DB_CONNECTION_FAILED,
+            Self::InternalError { .. } => error::DB_QUERY_FAILED,
+            Self::Timeout { .. } => error::DB_TIMEOUT,
+            Self::Busy { .. } => error::DB_BUSY,
+            Self::UnsupportedOperation { .. } => error::UNSUPPORTED_OPERATION,
+        }
+    }
 }
 
 /// Result type for datastore operations
@@ -285,5 +340,25 @@ pub struct BatchResult {
     pub errors: Vec<(usize, DataStoreError)>,
 }
 
+/// Rows removed, or that would be removed, by a pruning operation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PruneStats {
+    /// Rows removed
+    pub rows: usize,
+    /// Estimated bytes of data removed
+    pub bytes: u64,
+}
+
+impl PruneStats {
+    /// Counts one settings entry, estimating its size from its key and JSON
+    pub fn add_setting(&mut self, key: &str, value: &serde_json::Value) {
+        let size = key.len() + value.to_string().len();
+        self.rows += 1;
+        self.bytes = self
+            .bytes
+            .saturating_add(u64::try_from(size).unwrap_or(u64::MAX));
+    }
+}
+
 #[cfg(test)]
 mod tests;
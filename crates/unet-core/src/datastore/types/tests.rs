@@ -56,6 +56,33 @@ mod datastore_types_tests {
         assert!(err.to_string().contains("Transaction aborted"));
     }
 
+    #[test]
+    fn test_datastore_error_codes() {
+        let not_found = DataStoreError::NotFound {
+            entity_type: "Node".to_string(),
+            id: "test-id".to_string(),
+        };
+        let duplicate = DataStoreError::ConstraintViolation {
+            message: "Duplicate name".to_string(),
+        };
+        assert_eq!(not_found.error_code(), crate::error::NOT_FOUND);
+        assert_eq!(
+            duplicate.error_code(),
+            crate::error::DB_CONSTRAINT_VIOLATION
+        );
+        assert_eq!(
+            DataStoreError::Timeout { seconds: 30 }.error_code(),
+            crate::error::DB_TIMEOUT
+        );
+        assert_eq!(
+            DataStoreError::Busy {
+                message: "database is locked".to_string(),
+            }
+            .error_code(),
+            crate::error::DB_BUSY
+        );
+    }
+
     #[test]
     fn test_batch_result_new() {
         let batch = BatchResult {
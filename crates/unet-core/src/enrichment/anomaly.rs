@@ -0,0 +1,332 @@
+//! Anomaly detection on polled metrics
+
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::{Mutex, PoisonError};
+use std::time::SystemTime;
+use tracing::warn;
+use uuid::Uuid;
+
+use super::{Enricher, EnricherOptions, EnrichmentError, parse_options};
+use crate::models::derived::NodeStatus;
+
+/// Registered plugin name
+pub(super) const NAME: &str = "anomaly_detection";
+
+/// Smallest standard deviation used for scoring, so a flat series does not
+/// alarm on small changes
+const MIN_DEVIATION: f64 = 1.0;
+
+/// Metric watched for anomalies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnomalyMetric {
+    /// CPU utilization in percent
+    Cpu,
+    /// Memory utilization in percent
+    Memory,
+    /// Input and output errors on all interfaces since the previous poll
+    InterfaceErrors,
+}
+
+impl AnomalyMetric {
+    const ALL: [Self; 3] = [Self::Cpu, Self::Memory, Self::InterfaceErrors];
+
+    /// Name used in options and output
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Cpu => "cpu",
+            Self::Memory => "memory",
+            Self::InterfaceErrors => "interface_errors",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+enum Method {
+    /// Mean and standard deviation of the last `window` samples
+    #[serde(rename = "zscore")]
+    ZScore,
+    /// Exponentially weighted mean and variance with weight `alpha`
+    #[serde(rename = "ewma")]
+    Ewma,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct MetricOptions {
+    method: Method,
+    window: usize,
+    alpha: f64,
+    threshold: f64,
+    min_samples: usize,
+}
+
+impl Default for MetricOptions {
+    fn default() -> Self {
+        Self {
+            method: Method::ZScore,
+            window: 30,
+            alpha: 0.3,
+            threshold: 3.0,
+            min_samples: 10,
+        }
+    }
+}
+
+impl MetricOptions {
+    fn validate(&self, metric: AnomalyMetric) -> Result<(), EnrichmentError> {
+        let message = if self.threshold <= 0.0 {
+            "threshold must be positive"
+        } else if self.min_samples < 2 {
+            "min_samples must be at least 2"
+        } else if self.method == Method::ZScore && self.window < self.min_samples {
+            "window must be at least min_samples"
+        } else if self.method == Method::Ewma && (self.alpha <= 0.0 || self.alpha > 1.0) {
+            "alpha must be greater than 0 and at most 1"
+        } else {
+            return Ok(());
+        };
+        Err(EnrichmentError::InvalidOptions {
+            plugin: NAME.to_string(),
+            message: format!("{}: {message}", metric.as_str()),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Options {
+    metrics: BTreeMap<AnomalyMetric, MetricOptions>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            metrics: AnomalyMetric::ALL
+                .into_iter()
+                .map(|metric| (metric, MetricOptions::default()))
+                .collect(),
+        }
+    }
+}
+
+/// A polled value far from the metric's baseline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Anomaly {
+    /// Metric that deviated
+    pub metric: AnomalyMetric,
+    /// Polled value
+    pub value: f64,
+    /// Baseline mean before this poll
+    pub expected: f64,
+    /// Distance from the baseline in standard deviations; negative below it
+    pub deviation: f64,
+    /// Configured threshold in standard deviations
+    pub threshold: f64,
+}
+
+/// Anomalies written to `enrichments.anomaly_detection`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AnomalyReport {
+    /// Metrics of the latest poll that deviated from their baseline
+    pub anomalies: Vec<Anomaly>,
+}
+
+/// Recent samples of one metric on one node
+#[derive(Debug)]
+enum Baseline {
+    Window(VecDeque<f64>),
+    Ewma {
+        samples: usize,
+        mean: f64,
+        variance: f64,
+    },
+}
+
+impl Baseline {
+    const fn new(method: Method) -> Self {
+        match method {
+            Method::ZScore => Self::Window(VecDeque::new()),
+            Method::Ewma => Self::Ewma {
+                samples: 0,
+                mean: 0.0,
+                variance: 0.0,
+            },
+        }
+    }
+
+    /// Scores `value` against the baseline, returning the expected value and
+    /// the deviation once `min_samples` are known, then adds it to the baseline
+    fn observe(&mut self, value: f64, options: &MetricOptions) -> Option<(f64, f64)> {
+        match self {
+            Self::Window(samples) => {
+                let score = samples
+                    .len()
+                    .to_f64()
+                    .filter(|_| samples.len() >= options.min_samples)
+                    .map(|count| {
+                        let mean = samples.iter().sum::<f64>() / count;
+                        let variance =
+                            samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / count;
+                        (mean, (value - mean) / variance.sqrt().max(MIN_DEVIATION))
+                    });
+                samples.push_back(value);
+                while samples.len() > options.window {
+                    samples.pop_front();
+                }
+                score
+            }
+            Self::Ewma {
+                samples,
+                mean,
+                variance,
+            } => {
+                let score = (*samples >= options.min_samples)
+                    .then(|| (*mean, (value - *mean) / variance.sqrt().max(MIN_DEVIATION)));
+                if *samples == 0 {
+                    *mean = value;
+                } else {
+                    let diff = value - *mean;
+                    *mean = options.alpha.mul_add(diff, *mean);
+                    *variance =
+                        (1.0 - options.alpha) * options.alpha.mul_add(diff * diff, *variance);
+                }
+                *samples += 1;
+                score
+            }
+        }
+    }
+}
+
+/// Baselines and latest report for one node
+#[derive(Debug, Default)]
+struct NodeBaselines {
+    /// Poll the baselines were last updated from
+    last_updated: Option<SystemTime>,
+    baselines: HashMap<AnomalyMetric, Baseline>,
+    /// Interface error total at the previous poll
+    error_total: Option<u64>,
+    report: AnomalyReport,
+}
+
+impl NodeBaselines {
+    fn read(&mut self, metric: AnomalyMetric, status: &NodeStatus) -> Option<f64> {
+        match metric {
+            AnomalyMetric::Cpu => status.performance.as_ref()?.cpu_utilization.map(f64::from),
+            AnomalyMetric::Memory => status
+                .performance
+                .as_ref()?
+                .memory_utilization
+                .map(f64::from),
+            AnomalyMetric::InterfaceErrors => {
+                if status.interfaces.is_empty() {
+                    return None;
+                }
+                let total = status.interfaces.iter().fold(0_u64, |total, interface| {
+                    total
+                        .saturating_add(interface.input_stats.errors)
+                        .saturating_add(interface.output_stats.errors)
+                });
+                let previous = self.error_total.replace(total)?;
+                total
+                    .checked_sub(previous)
+                    .and_then(|increase| increase.to_f64())
+            }
+        }
+    }
+}
+
+/// Flags polled metrics that deviate sharply from their recent history
+///
+/// Each configured metric keeps a per-node baseline: the mean and standard
+/// deviation of the last `window` samples (`zscore`, the default), or an
+/// exponentially weighted mean and variance (`ewma`). Once `min_samples`
+/// polls are known, a value `threshold` or more standard deviations above or
+/// below the baseline is reported in `enrichments.anomaly_detection` and
+/// logged on the `alarm` tracing target. Baselines live in memory and are
+/// updated once per poll, however often the status is read.
+#[derive(Debug)]
+pub struct AnomalyDetection {
+    metrics: BTreeMap<AnomalyMetric, MetricOptions>,
+    nodes: Mutex<HashMap<Uuid, NodeBaselines>>,
+}
+
+impl AnomalyDetection {
+    /// Builds the enricher from configured options
+    ///
+    /// # Errors
+    /// Returns an error for unknown options or out-of-range values.
+    pub fn from_options(options: &EnricherOptions) -> Result<Self, EnrichmentError> {
+        let options: Options = parse_options(NAME, options)?;
+        for (metric, metric_options) in &options.metrics {
+            metric_options.validate(*metric)?;
+        }
+        Ok(Self {
+            metrics: options.metrics,
+            nodes: Mutex::new(HashMap::new()),
+        })
+    }
+
+    fn observe(&self, node: &mut NodeBaselines, status: &NodeStatus) -> AnomalyReport {
+        let mut anomalies = Vec::new();
+        for (metric, options) in &self.metrics {
+            let Some(value) = node.read(*metric, status) else {
+                continue;
+            };
+            let baseline = node
+                .baselines
+                .entry(*metric)
+                .or_insert_with(|| Baseline::new(options.method));
+            if let Some((expected, deviation)) = baseline.observe(value, options) {
+                if deviation.abs() >= options.threshold {
+                    anomalies.push(Anomaly {
+                        metric: *metric,
+                        value,
+                        expected,
+                        deviation,
+                        threshold: options.threshold,
+                    });
+                }
+            }
+        }
+        AnomalyReport { anomalies }
+    }
+}
+
+impl Enricher for AnomalyDetection {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn enrich(&self, status: &mut NodeStatus) -> Result<(), EnrichmentError> {
+        let mut nodes = self.nodes.lock().unwrap_or_else(PoisonError::into_inner);
+        let node = nodes.entry(status.node_id).or_default();
+        if node.last_updated != Some(status.last_updated) {
+            node.last_updated = Some(status.last_updated);
+            node.report = if status.reachable {
+                self.observe(node, status)
+            } else {
+                AnomalyReport::default()
+            };
+            for anomaly in &node.report.anomalies {
+                warn!(
+                    target: "alarm",
+                    node_id = %status.node_id,
+                    metric = anomaly.metric.as_str(),
+                    value = anomaly.value,
+                    expected = anomaly.expected,
+                    deviation = anomaly.deviation,
+                    "Polled metric deviates from its baseline"
+                );
+            }
+        }
+
+        let report = serde_json::to_value(&node.report)
+            .map_err(|e| EnrichmentError::Failed(e.to_string()))?;
+        status.enrichments.insert(NAME.to_string(), report);
+        Ok(())
+    }
+}
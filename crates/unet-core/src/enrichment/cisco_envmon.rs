@@ -0,0 +1,198 @@
+//! Cisco ENVMON (CISCO-ENVMON-MIB) normalization
+
+use num_traits::ToPrimitive;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+
+use super::{Enricher, EnricherOptions, EnrichmentError, parse_options};
+use crate::models::derived::{
+    EnvironmentalMetrics, FanSensor, FanStatus, NodeStatus, PowerSupply, PowerSupplyStatus,
+    TemperatureSensor,
+};
+use crate::snmp::SnmpValue;
+
+/// Registered plugin name
+pub(super) const NAME: &str = "cisco_envmon";
+
+/// ciscoEnvMonTemperatureStatusEntry
+const TEMPERATURE_ENTRY: &str = "1.3.6.1.4.1.9.9.13.1.3.1";
+/// ciscoEnvMonFanStatusEntry
+const FAN_ENTRY: &str = "1.3.6.1.4.1.9.9.13.1.4.1";
+/// ciscoEnvMonSupplyStatusEntry
+const SUPPLY_ENTRY: &str = "1.3.6.1.4.1.9.9.13.1.5.1";
+
+/// Column numbers shared by the ENVMON tables
+const DESCR: u32 = 2;
+const STATE: u32 = 3;
+const TEMPERATURE_VALUE: u32 = 3;
+const TEMPERATURE_THRESHOLD: u32 = 4;
+const TEMPERATURE_STATE: u32 = 6;
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Options {
+    warning_margin: f32,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            warning_margin: 10.0,
+        }
+    }
+}
+
+/// Replaces environmental metrics with named sensors from CISCO-ENVMON-MIB
+///
+/// Temperature sensors take their name from the description column and their
+/// critical threshold from the shutdown threshold; the warning threshold is
+/// `warning_margin` degrees below it (default 10). Fan and supply states map
+/// `normal` to `Normal`, `notPresent` to `NotPresent`, and `warning`,
+/// `critical`, `shutdown`, and `notFunctioning` to `Failed`.
+///
+/// Nodes without ENVMON data are left unchanged.
+#[derive(Debug, Clone)]
+pub struct CiscoEnvMon {
+    warning_margin: f32,
+}
+
+impl CiscoEnvMon {
+    /// Builds the enricher from configured options
+    ///
+    /// # Errors
+    /// Returns an error for unknown options or a negative `warning_margin`.
+    pub fn from_options(options: &EnricherOptions) -> Result<Self, EnrichmentError> {
+        let options: Options = parse_options(NAME, options)?;
+        if options.warning_margin < 0.0 {
+            return Err(EnrichmentError::InvalidOptions {
+                plugin: NAME.to_string(),
+                message: "warning_margin must not be negative".to_string(),
+            });
+        }
+        Ok(Self {
+            warning_margin: options.warning_margin,
+        })
+    }
+
+    fn temperatures(
+        &self,
+        rows: &BTreeMap<u32, HashMap<u32, &SnmpValue>>,
+    ) -> Result<Vec<TemperatureSensor>, EnrichmentError> {
+        let mut sensors = Vec::new();
+        for (index, columns) in rows {
+            if columns.get(&TEMPERATURE_STATE).copied().and_then(integer) == Some(5) {
+                continue; // notPresent
+            }
+            let Some(value) = columns.get(&TEMPERATURE_VALUE) else {
+                continue;
+            };
+            let temperature = integer(value)
+                .and_then(|celsius| celsius.to_f32())
+                .ok_or_else(|| {
+                    EnrichmentError::Failed(format!(
+                        "ciscoEnvMonTemperatureStatusValue.{index} is not numeric: {value}"
+                    ))
+                })?;
+            let critical = columns
+                .get(&TEMPERATURE_THRESHOLD)
+                .copied()
+                .and_then(integer)
+                .and_then(|threshold| threshold.to_f32());
+            sensors.push(TemperatureSensor {
+                name: description(columns, "Temperature Sensor", *index),
+                temperature,
+                critical_threshold: critical,
+                warning_threshold: critical.map(|critical| critical - self.warning_margin),
+            });
+        }
+        Ok(sensors)
+    }
+}
+
+impl Enricher for CiscoEnvMon {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn enrich(&self, status: &mut NodeStatus) -> Result<(), EnrichmentError> {
+        let temperature_rows = table(&status.raw_snmp_data, TEMPERATURE_ENTRY);
+        let fan_rows = table(&status.raw_snmp_data, FAN_ENTRY);
+        let supply_rows = table(&status.raw_snmp_data, SUPPLY_ENTRY);
+        if temperature_rows.is_empty() && fan_rows.is_empty() && supply_rows.is_empty() {
+            return Ok(());
+        }
+
+        let temperatures = self.temperatures(&temperature_rows)?;
+        let fans = fan_rows
+            .iter()
+            .map(|(index, columns)| FanSensor {
+                name: description(columns, "Fan", *index),
+                speed_rpm: None,
+                status: match columns.get(&STATE).copied().and_then(integer) {
+                    Some(1) => FanStatus::Normal,
+                    Some(2 | 3 | 4 | 6) => FanStatus::Failed,
+                    Some(5) => FanStatus::NotPresent,
+                    _ => FanStatus::Unknown,
+                },
+            })
+            .collect();
+        let power_supplies = supply_rows
+            .iter()
+            .map(|(index, columns)| PowerSupply {
+                name: description(columns, "Power Supply", *index),
+                status: match columns.get(&STATE).copied().and_then(integer) {
+                    Some(1) => PowerSupplyStatus::Normal,
+                    Some(2 | 3 | 4 | 6) => PowerSupplyStatus::Failed,
+                    Some(5) => PowerSupplyStatus::NotPresent,
+                    _ => PowerSupplyStatus::Unknown,
+                },
+                power_output: None,
+            })
+            .collect();
+
+        status.environmental = Some(EnvironmentalMetrics {
+            temperatures,
+            fans,
+            power_supplies,
+        });
+        Ok(())
+    }
+}
+
+/// Groups `<entry>.<column>.<index>` values by index, then column
+fn table<'a>(
+    snmp_data: &'a HashMap<String, SnmpValue>,
+    entry: &str,
+) -> BTreeMap<u32, HashMap<u32, &'a SnmpValue>> {
+    let mut rows: BTreeMap<u32, HashMap<u32, &SnmpValue>> = BTreeMap::new();
+    for (oid, value) in snmp_data {
+        let Some(rest) = oid
+            .strip_prefix(entry)
+            .and_then(|rest| rest.strip_prefix('.'))
+        else {
+            continue;
+        };
+        let Some((column, index)) = rest.split_once('.') else {
+            continue;
+        };
+        if let (Ok(column), Ok(index)) = (column.parse(), index.parse()) {
+            rows.entry(index).or_default().insert(column, value);
+        }
+    }
+    rows
+}
+
+fn description(columns: &HashMap<u32, &SnmpValue>, fallback: &str, index: u32) -> String {
+    match columns.get(&DESCR) {
+        Some(SnmpValue::String(descr)) if !descr.trim().is_empty() => descr.trim().to_string(),
+        _ => format!("{fallback} {index}"),
+    }
+}
+
+fn integer(value: &SnmpValue) -> Option<i64> {
+    match value {
+        SnmpValue::Integer(value) => Some(*value),
+        SnmpValue::Gauge32(value) => Some(i64::from(*value)),
+        _ => None,
+    }
+}
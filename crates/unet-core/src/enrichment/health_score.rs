@@ -0,0 +1,163 @@
+//! Node health score computed from derived state
+
+use serde::{Deserialize, Serialize};
+
+use super::{Enricher, EnricherOptions, EnrichmentError, parse_options};
+use crate::models::derived::{
+    FanStatus, InterfaceAdminStatus, InterfaceOperStatus, NodeStatus, PowerSupplyStatus,
+};
+
+/// Registered plugin name
+pub(super) const NAME: &str = "health_score";
+
+/// Maximum deduction for interfaces that are up administratively but down
+const INTERFACE_DOWN_CAP: u32 = 25;
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Options {
+    cpu_warning: u8,
+    memory_warning: u8,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            cpu_warning: 80,
+            memory_warning: 90,
+        }
+    }
+}
+
+/// Score written to `enrichments.health_score`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HealthReport {
+    /// 0 (unhealthy) to 100 (healthy)
+    pub score: u32,
+    /// One entry per deduction, in evaluation order
+    pub factors: Vec<String>,
+}
+
+/// Scores node health from 0 to 100
+///
+/// An unreachable node scores 0. Otherwise the score starts at 100 and is
+/// reduced by 20 for CPU or memory utilization at or above the configured
+/// warning level, 30 per temperature sensor at or above its critical
+/// threshold (10 at or above its warning threshold), 15 per failed fan, 20
+/// per failed power supply, and 5 per interface that is administratively up
+/// but operationally down (25 at most). Run it after plugins that fill in
+/// environmental data, such as `cisco_envmon`.
+#[derive(Debug, Clone)]
+pub struct HealthScore {
+    cpu_warning: u8,
+    memory_warning: u8,
+}
+
+impl HealthScore {
+    /// Builds the enricher from configured options
+    ///
+    /// # Errors
+    /// Returns an error for unknown options or percentages above 100.
+    pub fn from_options(options: &EnricherOptions) -> Result<Self, EnrichmentError> {
+        let options: Options = parse_options(NAME, options)?;
+        if options.cpu_warning > 100 || options.memory_warning > 100 {
+            return Err(EnrichmentError::InvalidOptions {
+                plugin: NAME.to_string(),
+                message: "cpu_warning and memory_warning are percentages (0-100)".to_string(),
+            });
+        }
+        Ok(Self {
+            cpu_warning: options.cpu_warning,
+            memory_warning: options.memory_warning,
+        })
+    }
+
+    /// Computes the health report for a status
+    #[must_use]
+    pub fn score(&self, status: &NodeStatus) -> HealthReport {
+        if !status.reachable {
+            return HealthReport {
+                score: 0,
+                factors: vec!["node is unreachable".to_string()],
+            };
+        }
+
+        let mut deductions: Vec<(u32, String)> = Vec::new();
+        if let Some(performance) = &status.performance {
+            if let Some(cpu) = performance
+                .cpu_utilization
+                .filter(|cpu| *cpu >= self.cpu_warning)
+            {
+                deductions.push((20, format!("CPU utilization {cpu}%")));
+            }
+            if let Some(memory) = performance
+                .memory_utilization
+                .filter(|memory| *memory >= self.memory_warning)
+            {
+                deductions.push((20, format!("memory utilization {memory}%")));
+            }
+        }
+
+        if let Some(environmental) = &status.environmental {
+            for sensor in &environmental.temperatures {
+                if sensor
+                    .critical_threshold
+                    .is_some_and(|critical| sensor.temperature >= critical)
+                {
+                    deductions.push((30, format!("{} above critical temperature", sensor.name)));
+                } else if sensor
+                    .warning_threshold
+                    .is_some_and(|warning| sensor.temperature >= warning)
+                {
+                    deductions.push((10, format!("{} above warning temperature", sensor.name)));
+                }
+            }
+            for fan in &environmental.fans {
+                if fan.status == FanStatus::Failed {
+                    deductions.push((15, format!("{} failed", fan.name)));
+                }
+            }
+            for supply in &environmental.power_supplies {
+                if supply.status == PowerSupplyStatus::Failed {
+                    deductions.push((20, format!("{} failed", supply.name)));
+                }
+            }
+        }
+
+        let down: Vec<&str> = status
+            .interfaces
+            .iter()
+            .filter(|interface| {
+                interface.admin_status == InterfaceAdminStatus::Up
+                    && interface.oper_status == InterfaceOperStatus::Down
+            })
+            .map(|interface| interface.name.as_str())
+            .collect();
+        if !down.is_empty() {
+            let count = u32::try_from(down.len()).unwrap_or(u32::MAX);
+            deductions.push((
+                count.saturating_mul(5).min(INTERFACE_DOWN_CAP),
+                format!("interfaces down: {}", down.join(", ")),
+            ));
+        }
+
+        let total: u32 = deductions.iter().map(|(points, _)| points).sum();
+        HealthReport {
+            score: 100_u32.saturating_sub(total),
+            factors: deductions.into_iter().map(|(_, factor)| factor).collect(),
+        }
+    }
+}
+
+impl Enricher for HealthScore {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn enrich(&self, status: &mut NodeStatus) -> Result<(), EnrichmentError> {
+        let report = serde_json::to_value(self.score(status))
+            .map_err(|e| EnrichmentError::Failed(e.to_string()))?;
+        status.enrichments.insert(NAME.to_string(), report);
+        Ok(())
+    }
+}
@@ -0,0 +1,270 @@
+//! Derived-state enrichment plugins
+//!
+//! Enrichers transform a [`NodeStatus`] after it has been updated from a poll:
+//! normalizing vendor MIB data into the common models, or computing values
+//! such as health scores. An [`EnrichmentRegistry`] maps plugin names to
+//! factories, and the `server.enrichment` configuration list selects which
+//! plugins run, in which order, and with which options.
+//!
+//! Plugins are isolated from each other: if one returns an error or panics,
+//! its changes to the status are discarded, the error is recorded in
+//! [`NodeStatus::enrichment_errors`], and the remaining plugins still run.
+//!
+//! The pipeline runs where poll results are recorded, and its output is
+//! stored with [`enrich_and_store`]; reads overlay the stored output.
+
+mod anomaly;
+mod cisco_envmon;
+mod health_score;
+mod store;
+
+pub use anomaly::{Anomaly, AnomalyDetection, AnomalyMetric, AnomalyReport};
+pub use cisco_envmon::CiscoEnvMon;
+pub use health_score::{HealthReport, HealthScore};
+pub use store::{
+    EnrichedState, enrich_and_store, enrich_node, enriched_node_status, list_enriched_states,
+};
+
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::warn;
+
+use crate::config::EnrichmentPluginConfig;
+use crate::models::derived::NodeStatus;
+use crate::snmp::SnmpValue;
+
+/// Plugin options from the configuration
+pub type EnricherOptions = serde_json::Map<String, serde_json::Value>;
+
+/// Builds an enricher from its configured options
+pub type EnricherFactory =
+    Box<dyn Fn(&EnricherOptions) -> Result<Arc<dyn Enricher>, EnrichmentError> + Send + Sync>;
+
+/// A transform applied to node status after each poll
+pub trait Enricher: Send + Sync {
+    /// Name used in configuration and as the key for output and errors
+    fn name(&self) -> &str;
+
+    /// Updates the status in place
+    ///
+    /// # Errors
+    /// Returns an error if the status cannot be enriched; the pipeline
+    /// discards any partial changes.
+    fn enrich(&self, status: &mut NodeStatus) -> Result<(), EnrichmentError>;
+}
+
+/// Errors from building or running enrichment plugins
+#[derive(Error, Debug)]
+pub enum EnrichmentError {
+    /// No plugin is registered under the configured name
+    #[error("Unknown enrichment plugin '{name}' (available: {available})")]
+    UnknownPlugin {
+        /// Configured name
+        name: String,
+        /// Comma-separated registered names
+        available: String,
+    },
+
+    /// The configured options do not match what the plugin accepts
+    #[error("Invalid options for enrichment plugin '{plugin}': {message}")]
+    InvalidOptions {
+        /// Plugin name
+        plugin: String,
+        /// Parse error
+        message: String,
+    },
+
+    /// The plugin could not enrich the status
+    #[error("{0}")]
+    Failed(String),
+}
+
+/// Parses plugin options into a typed struct
+///
+/// # Errors
+/// Returns [`EnrichmentError::InvalidOptions`] if the options do not
+/// deserialize into `T`.
+pub fn parse_options<T: DeserializeOwned>(
+    plugin: &str,
+    options: &EnricherOptions,
+) -> Result<T, EnrichmentError> {
+    serde_json::from_value(serde_json::Value::Object(options.clone())).map_err(|e| {
+        EnrichmentError::InvalidOptions {
+            plugin: plugin.to_string(),
+            message: e.to_string(),
+        }
+    })
+}
+
+/// Named enricher factories available to the configuration
+#[derive(Default)]
+pub struct EnrichmentRegistry {
+    factories: HashMap<String, EnricherFactory>,
+}
+
+impl EnrichmentRegistry {
+    /// Creates an empty registry
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a registry with the plugins shipped with μNet
+    #[must_use]
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::new();
+        registry.register(cisco_envmon::NAME, |options| {
+            Ok(Arc::new(CiscoEnvMon::from_options(options)?) as Arc<dyn Enricher>)
+        });
+        registry.register(health_score::NAME, |options| {
+            Ok(Arc::new(HealthScore::from_options(options)?) as Arc<dyn Enricher>)
+        });
+        registry.register(anomaly::NAME, |options| {
+            Ok(Arc::new(AnomalyDetection::from_options(options)?) as Arc<dyn Enricher>)
+        });
+        registry
+    }
+
+    /// Registers a factory, replacing any existing factory with the same name
+    pub fn register<F>(&mut self, name: impl Into<String>, factory: F)
+    where
+        F: Fn(&EnricherOptions) -> Result<Arc<dyn Enricher>, EnrichmentError>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.factories.insert(name.into(), Box::new(factory));
+    }
+
+    /// Registered plugin names in alphabetical order
+    #[must_use]
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.factories.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Builds the pipeline for the enabled plugins, in configuration order
+    ///
+    /// # Errors
+    /// Returns an error if a plugin is not registered or rejects its options.
+    pub fn build(
+        &self,
+        plugins: &[EnrichmentPluginConfig],
+    ) -> Result<EnrichmentPipeline, EnrichmentError> {
+        let enrichers = plugins
+            .iter()
+            .filter(|plugin| plugin.enabled)
+            .map(|plugin| {
+                let factory = self.factories.get(&plugin.name).ok_or_else(|| {
+                    EnrichmentError::UnknownPlugin {
+                        name: plugin.name.clone(),
+                        available: self.names().join(", "),
+                    }
+                })?;
+                factory(&plugin.options)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(EnrichmentPipeline::new(enrichers))
+    }
+}
+
+/// Outcome of running the pipeline on one status
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EnrichmentReport {
+    /// Plugins that completed, in run order
+    pub applied: Vec<String>,
+    /// Plugins that failed, with their error, in run order
+    pub failed: Vec<(String, String)>,
+}
+
+/// Ordered list of enrichers applied to node status
+#[derive(Clone, Default)]
+pub struct EnrichmentPipeline {
+    enrichers: Vec<Arc<dyn Enricher>>,
+}
+
+impl EnrichmentPipeline {
+    /// Creates a pipeline that runs the enrichers in the given order
+    #[must_use]
+    pub const fn new(enrichers: Vec<Arc<dyn Enricher>>) -> Self {
+        Self { enrichers }
+    }
+
+    /// Names of the enrichers in run order
+    #[must_use]
+    pub fn names(&self) -> Vec<&str> {
+        self.enrichers
+            .iter()
+            .map(|enricher| enricher.name())
+            .collect()
+    }
+
+    /// Returns true if no enrichers are configured
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.enrichers.is_empty()
+    }
+
+    /// Updates the status from a poll and then runs the enrichers
+    pub fn update_from_snmp(
+        &self,
+        status: &mut NodeStatus,
+        snmp_data: HashMap<String, SnmpValue>,
+    ) -> EnrichmentReport {
+        status.update_from_snmp(snmp_data);
+        self.apply(status)
+    }
+
+    /// Runs each enricher in order, isolating failures
+    ///
+    /// Output from a previous run is cleared first, so removing a plugin
+    /// from the configuration also removes its output.
+    pub fn apply(&self, status: &mut NodeStatus) -> EnrichmentReport {
+        status.enrichments.clear();
+        status.enrichment_errors.clear();
+
+        let mut report = EnrichmentReport::default();
+        for enricher in &self.enrichers {
+            let name = enricher.name().to_string();
+            let snapshot = status.clone();
+            let outcome = panic::catch_unwind(AssertUnwindSafe(|| enricher.enrich(status)));
+            let error = match outcome {
+                Ok(Ok(())) => {
+                    report.applied.push(name);
+                    continue;
+                }
+                Ok(Err(e)) => e.to_string(),
+                Err(payload) => panic_message(payload.as_ref()),
+            };
+
+            warn!(
+                plugin = %name,
+                node_id = %status.node_id,
+                error = %error,
+                "Enrichment plugin failed"
+            );
+            *status = snapshot;
+            status.enrichment_errors.insert(name.clone(), error.clone());
+            report.failed.push((name, error));
+        }
+        report
+    }
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| (*message).to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .map_or_else(
+            || "plugin panicked".to_string(),
+            |message| format!("plugin panicked: {message}"),
+        )
+}
+
+#[cfg(test)]
+mod tests;
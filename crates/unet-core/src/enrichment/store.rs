@@ -0,0 +1,166 @@
+//! Storage of enrichment output between polls
+//!
+//! Polls run the pipeline once and store its output under the
+//! `enriched_state` settings namespace, keyed by node ID. Reads of node
+//! status overlay the stored output instead of running the plugins again, so
+//! stateful plugins such as anomaly detection see each poll exactly once.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use super::{EnrichmentPipeline, EnrichmentReport};
+use crate::collectors::node_status_with_collected;
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult};
+use crate::models::derived::{EnvironmentalMetrics, NodeStatus};
+
+/// Settings namespace of stored enrichment output
+const ENRICHED_NAMESPACE: &str = "enriched_state";
+
+/// Output of the enrichment pipeline from a node's latest poll
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnrichedState {
+    /// Node the status belongs to
+    pub node_id: Uuid,
+    /// When the pipeline ran
+    pub enriched_at: DateTime<Utc>,
+    /// Output of each plugin, keyed by plugin name
+    pub enrichments: HashMap<String, serde_json::Value>,
+    /// Errors of the plugins that failed, keyed by plugin name
+    pub enrichment_errors: HashMap<String, String>,
+    /// Environmental data after plugins normalized vendor MIBs into it
+    pub environmental: Option<EnvironmentalMetrics>,
+}
+
+impl EnrichedState {
+    /// Captures the enrichment output of a status the pipeline has run on
+    #[must_use]
+    pub fn from_status(status: &NodeStatus, enriched_at: DateTime<Utc>) -> Self {
+        Self {
+            node_id: status.node_id,
+            enriched_at,
+            enrichments: status.enrichments.clone(),
+            enrichment_errors: status.enrichment_errors.clone(),
+            environmental: status.environmental.clone(),
+        }
+    }
+
+    /// Overlays the stored output on a status
+    pub fn apply_to(&self, status: &mut NodeStatus) {
+        status.enrichments.clone_from(&self.enrichments);
+        status.enrichment_errors.clone_from(&self.enrichment_errors);
+        if self.environmental.is_some() {
+            status.environmental.clone_from(&self.environmental);
+        }
+    }
+}
+
+/// Runs the pipeline on a status updated by a poll and stores its output
+///
+/// With no plugins configured, any previously stored output is removed
+/// instead. Datastores without settings support keep no output.
+///
+/// # Errors
+/// Returns an error if the output cannot be stored.
+pub async fn enrich_and_store(
+    datastore: &dyn DataStore,
+    pipeline: &EnrichmentPipeline,
+    status: &mut NodeStatus,
+) -> DataStoreResult<EnrichmentReport> {
+    let report = pipeline.apply(status);
+    let key = status.node_id.to_string();
+    let stored = if pipeline.is_empty() {
+        match datastore.delete_setting(ENRICHED_NAMESPACE, &key).await {
+            Err(DataStoreError::NotFound { .. }) => Ok(()),
+            result => result,
+        }
+    } else {
+        let state = EnrichedState::from_status(status, Utc::now());
+        let value = serde_json::to_value(&state).map_err(|e| DataStoreError::InternalError {
+            message: format!("enriched state of {key}: {e}"),
+        })?;
+        datastore
+            .put_setting(ENRICHED_NAMESPACE, &key, &value)
+            .await
+    };
+    match stored {
+        Ok(()) | Err(DataStoreError::UnsupportedOperation { .. }) => Ok(report),
+        Err(e) => Err(e),
+    }
+}
+
+/// Runs the pipeline on a node's stored status, with its latest collection,
+/// and stores the output
+///
+/// Returns `Ok(None)` if the node has no status yet.
+///
+/// # Errors
+/// Returns an error if the status cannot be read or the output stored.
+pub async fn enrich_node(
+    datastore: &dyn DataStore,
+    pipeline: &EnrichmentPipeline,
+    node_id: Uuid,
+) -> DataStoreResult<Option<EnrichmentReport>> {
+    let Some(mut status) = node_status_with_collected(datastore, node_id).await? else {
+        return Ok(None);
+    };
+    enrich_and_store(datastore, pipeline, &mut status)
+        .await
+        .map(Some)
+}
+
+/// Gets the stored enrichment output of every node
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or an entry is malformed.
+pub async fn list_enriched_states(
+    datastore: &dyn DataStore,
+) -> DataStoreResult<HashMap<Uuid, EnrichedState>> {
+    let stored = match datastore.list_settings(ENRICHED_NAMESPACE).await {
+        Ok(stored) => stored,
+        Err(DataStoreError::UnsupportedOperation { .. }) => return Ok(HashMap::new()),
+        Err(e) => return Err(e),
+    };
+    stored
+        .into_iter()
+        .map(|(key, value)| {
+            serde_json::from_value::<EnrichedState>(value)
+                .map(|state| (state.node_id, state))
+                .map_err(|e| DataStoreError::InternalError {
+                    message: format!("stored enriched state of {key}: {e}"),
+                })
+        })
+        .collect()
+}
+
+/// Gets a node's status, with its latest collection and the stored output
+/// of the enrichment pipeline
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or the stored output is
+/// malformed.
+pub async fn enriched_node_status(
+    datastore: &dyn DataStore,
+    node_id: Uuid,
+) -> DataStoreResult<Option<NodeStatus>> {
+    let Some(mut status) = node_status_with_collected(datastore, node_id).await? else {
+        return Ok(None);
+    };
+    let stored = match datastore
+        .get_setting(ENRICHED_NAMESPACE, &node_id.to_string())
+        .await
+    {
+        Ok(stored) => stored,
+        Err(DataStoreError::UnsupportedOperation { .. }) => None,
+        Err(e) => return Err(e),
+    };
+    if let Some(value) = stored {
+        let state: EnrichedState =
+            serde_json::from_value(value).map_err(|e| DataStoreError::InternalError {
+                message: format!("stored enriched state of {node_id}: {e}"),
+            })?;
+        state.apply_to(&mut status);
+    }
+    Ok(Some(status))
+}
@@ -0,0 +1,320 @@
+use super::*;
+use crate::models::derived::{FanStatus, PerformanceMetrics, PowerSupplyStatus};
+use uuid::Uuid;
+
+/// Appends its name to `enrichments.trace` so tests can observe run order
+struct Tracer(&'static str);
+
+impl Enricher for Tracer {
+    fn name(&self) -> &str {
+        self.0
+    }
+
+    fn enrich(&self, status: &mut NodeStatus) -> Result<(), EnrichmentError> {
+        let trace = status
+            .enrichments
+            .entry("trace".to_string())
+            .or_insert_with(|| serde_json::json!([]));
+        trace.as_array_mut().unwrap().push(self.0.into());
+        Ok(())
+    }
+}
+
+/// Modifies the status and then fails, or panics
+struct Faulty {
+    panic: bool,
+}
+
+impl Enricher for Faulty {
+    fn name(&self) -> &str {
+        if self.panic { "panics" } else { "fails" }
+    }
+
+    fn enrich(&self, status: &mut NodeStatus) -> Result<(), EnrichmentError> {
+        status.reachable = false;
+        assert!(!self.panic, "sensor table corrupt");
+        Err(EnrichmentError::Failed("missing data".to_string()))
+    }
+}
+
+fn plugin(name: &str, options: serde_json::Value) -> EnrichmentPluginConfig {
+    EnrichmentPluginConfig {
+        name: name.to_string(),
+        enabled: true,
+        options: options.as_object().cloned().unwrap_or_default(),
+    }
+}
+
+fn reachable_status() -> NodeStatus {
+    let mut status = NodeStatus::new(Uuid::new_v4());
+    status.reachable = true;
+    status
+}
+
+fn envmon_data() -> HashMap<String, SnmpValue> {
+    HashMap::from([
+        (
+            "1.3.6.1.4.1.9.9.13.1.3.1.2.1".to_string(),
+            SnmpValue::String("Inlet".to_string()),
+        ),
+        (
+            "1.3.6.1.4.1.9.9.13.1.3.1.3.1".to_string(),
+            SnmpValue::Gauge32(72),
+        ),
+        (
+            "1.3.6.1.4.1.9.9.13.1.3.1.4.1".to_string(),
+            SnmpValue::Integer(75),
+        ),
+        (
+            "1.3.6.1.4.1.9.9.13.1.3.1.3.2".to_string(),
+            SnmpValue::Gauge32(40),
+        ),
+        (
+            "1.3.6.1.4.1.9.9.13.1.4.1.2.1".to_string(),
+            SnmpValue::String("Fan 1".to_string()),
+        ),
+        (
+            "1.3.6.1.4.1.9.9.13.1.4.1.3.1".to_string(),
+            SnmpValue::Integer(6),
+        ),
+        (
+            "1.3.6.1.4.1.9.9.13.1.5.1.3.1".to_string(),
+            SnmpValue::Integer(1),
+        ),
+    ])
+}
+
+#[test]
+fn test_pipeline_runs_enrichers_in_order() {
+    let pipeline = EnrichmentPipeline::new(vec![Arc::new(Tracer("b")), Arc::new(Tracer("a"))]);
+    let mut status = reachable_status();
+
+    let report = pipeline.apply(&mut status);
+
+    assert_eq!(report.applied, ["b", "a"]);
+    assert_eq!(status.enrichments["trace"], serde_json::json!(["b", "a"]));
+}
+
+#[test]
+fn test_pipeline_isolates_errors_and_panics() {
+    let pipeline = EnrichmentPipeline::new(vec![
+        Arc::new(Faulty { panic: false }),
+        Arc::new(Faulty { panic: true }),
+        Arc::new(Tracer("after")),
+    ]);
+    let mut status = reachable_status();
+
+    let report = pipeline.apply(&mut status);
+
+    assert_eq!(report.applied, ["after"]);
+    assert_eq!(report.failed.len(), 2);
+    assert!(
+        status.reachable,
+        "changes from failed plugins are discarded"
+    );
+    assert_eq!(status.enrichment_errors["fails"], "missing data");
+    assert!(status.enrichment_errors["panics"].contains("sensor table corrupt"));
+}
+
+#[test]
+fn test_registry_builds_enabled_plugins_in_config_order() {
+    let mut disabled = plugin("cisco_envmon", serde_json::json!({}));
+    disabled.enabled = false;
+    let plugins = vec![
+        plugin("health_score", serde_json::json!({ "cpu_warning": 70 })),
+        disabled,
+        plugin("cisco_envmon", serde_json::json!({})),
+    ];
+
+    let pipeline = EnrichmentRegistry::with_builtins().build(&plugins).unwrap();
+
+    assert_eq!(pipeline.names(), ["health_score", "cisco_envmon"]);
+}
+
+#[test]
+fn test_registry_rejects_unknown_plugins_and_options() {
+    let registry = EnrichmentRegistry::with_builtins();
+
+    let unknown = registry.build(&[plugin("envmon", serde_json::json!({}))]);
+    let Err(EnrichmentError::UnknownPlugin { available, .. }) = unknown else {
+        panic!("expected an unknown plugin error");
+    };
+    assert_eq!(available, "anomaly_detection, cisco_envmon, health_score");
+
+    let typo = registry.build(&[plugin("health_score", serde_json::json!({ "cpu": 70 }))]);
+    assert!(matches!(typo, Err(EnrichmentError::InvalidOptions { .. })));
+}
+
+#[test]
+fn test_cisco_envmon_normalizes_sensors() {
+    let mut status = reachable_status();
+    status.update_from_snmp(envmon_data());
+    let enricher = CiscoEnvMon::from_options(&EnricherOptions::new()).unwrap();
+
+    enricher.enrich(&mut status).unwrap();
+
+    let environmental = status.environmental.unwrap();
+    let inlet = &environmental.temperatures[0];
+    assert_eq!(inlet.name, "Inlet");
+    assert_eq!(inlet.critical_threshold, Some(75.0));
+    assert_eq!(inlet.warning_threshold, Some(65.0));
+    assert_eq!(environmental.temperatures[1].name, "Temperature Sensor 2");
+    assert_eq!(environmental.fans[0].status, FanStatus::Failed);
+    assert_eq!(
+        environmental.power_supplies[0].status,
+        PowerSupplyStatus::Normal
+    );
+}
+
+#[test]
+fn test_cisco_envmon_rejects_non_numeric_temperature() {
+    let mut status = reachable_status();
+    status.raw_snmp_data.insert(
+        "1.3.6.1.4.1.9.9.13.1.3.1.3.1".to_string(),
+        SnmpValue::String("n/a".to_string()),
+    );
+    let enricher = CiscoEnvMon::from_options(&EnricherOptions::new()).unwrap();
+
+    assert!(enricher.enrich(&mut status).is_err());
+}
+
+#[test]
+fn test_health_score_uses_normalized_environment() {
+    let plugins = [
+        plugin("cisco_envmon", serde_json::json!({})),
+        plugin("health_score", serde_json::json!({})),
+    ];
+    let pipeline = EnrichmentRegistry::with_builtins().build(&plugins).unwrap();
+    let mut status = NodeStatus::new(Uuid::new_v4());
+
+    let report = pipeline.update_from_snmp(&mut status, envmon_data());
+    status.performance = Some(PerformanceMetrics {
+        cpu_utilization: Some(95),
+        memory_utilization: None,
+        total_memory: None,
+        used_memory: None,
+        load_average: None,
+    });
+
+    assert!(report.failed.is_empty());
+    let health: HealthReport =
+        serde_json::from_value(status.enrichments["health_score"].clone()).unwrap();
+    // Inlet above warning (-10), fan failed (-15)
+    assert_eq!(health.score, 75);
+    assert_eq!(health.factors.len(), 2);
+
+    let rescored = HealthScore::from_options(&EnricherOptions::new())
+        .unwrap()
+        .score(&status);
+    assert_eq!(rescored.score, 55);
+}
+
+#[test]
+fn test_health_score_is_zero_when_unreachable() {
+    let status = NodeStatus::new(Uuid::new_v4());
+
+    let report = HealthScore::from_options(&EnricherOptions::new())
+        .unwrap()
+        .score(&status);
+
+    assert_eq!(report.score, 0);
+}
+
+/// Status of the `poll`th poll of a node
+fn polled(node_id: Uuid, poll: u64, cpu: u8) -> NodeStatus {
+    let mut status = NodeStatus::new(node_id);
+    status.last_updated = std::time::UNIX_EPOCH + std::time::Duration::from_secs(poll);
+    status.reachable = true;
+    status.performance = Some(PerformanceMetrics {
+        cpu_utilization: Some(cpu),
+        memory_utilization: None,
+        total_memory: None,
+        used_memory: None,
+        load_average: None,
+    });
+    status
+}
+
+fn anomalies(detector: &AnomalyDetection, status: &mut NodeStatus) -> Vec<Anomaly> {
+    detector.enrich(status).unwrap();
+    let report: AnomalyReport =
+        serde_json::from_value(status.enrichments["anomaly_detection"].clone()).unwrap();
+    report.anomalies
+}
+
+#[test]
+fn test_anomaly_detection_flags_sharp_deviation_after_warmup() {
+    let options = serde_json::json!({ "metrics": { "cpu": { "min_samples": 5 } } });
+    let detector = AnomalyDetection::from_options(options.as_object().unwrap()).unwrap();
+    let node_id = Uuid::new_v4();
+
+    for (poll, cpu) in (0..).zip([20, 22, 21, 19, 20]) {
+        assert!(anomalies(&detector, &mut polled(node_id, poll, cpu)).is_empty());
+    }
+    let mut spike = polled(node_id, 5, 90);
+    let found = anomalies(&detector, &mut spike);
+
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].metric, AnomalyMetric::Cpu);
+    assert!(found[0].deviation > 3.0);
+    assert!((found[0].expected - 20.4).abs() < 1e-9);
+    // Reading the same poll again reports it without adding a sample
+    assert_eq!(anomalies(&detector, &mut spike).len(), 1);
+    assert!(anomalies(&detector, &mut polled(node_id, 6, 21)).is_empty());
+}
+
+#[test]
+fn test_anomaly_detection_ewma_and_option_validation() {
+    let options = serde_json::json!({
+        "metrics": { "cpu": { "method": "ewma", "alpha": 0.5, "min_samples": 3, "threshold": 4.0 } }
+    });
+    let detector = AnomalyDetection::from_options(options.as_object().unwrap()).unwrap();
+    let node_id = Uuid::new_v4();
+    for (poll, cpu) in (0..).zip([50, 50, 50, 53]) {
+        assert!(anomalies(&detector, &mut polled(node_id, poll, cpu)).is_empty());
+    }
+    assert_eq!(anomalies(&detector, &mut polled(node_id, 4, 5)).len(), 1);
+
+    for invalid in [
+        serde_json::json!({ "metrics": { "cpu": { "threshold": 0.0 } } }),
+        serde_json::json!({ "metrics": { "cpu": { "window": 5 } } }),
+        serde_json::json!({ "metrics": { "cpu": { "method": "ewma", "alpha": 1.5 } } }),
+        serde_json::json!({ "metrics": { "disk": {} } }),
+    ] {
+        assert!(AnomalyDetection::from_options(invalid.as_object().unwrap()).is_err());
+    }
+}
+
+#[tokio::test]
+async fn test_enrich_and_store_keeps_output_for_reads() {
+    use crate::datastore::sqlite::tests::setup::migrated_store;
+
+    let store = migrated_store().await;
+    let pipeline = EnrichmentPipeline::new(vec![Arc::new(Tracer("a"))]);
+    let mut status = NodeStatus::new(Uuid::new_v4());
+
+    let report = enrich_and_store(&store, &pipeline, &mut status)
+        .await
+        .unwrap();
+    assert_eq!(report.applied, ["a"]);
+    let stored = list_enriched_states(&store).await.unwrap();
+    let state = &stored[&status.node_id];
+    assert_eq!(state.enrichments["trace"], serde_json::json!(["a"]));
+
+    // Reads overlay the stored output without running the plugins
+    let mut read = NodeStatus::new(status.node_id);
+    state.apply_to(&mut read);
+    assert_eq!(read.enrichments, status.enrichments);
+
+    // Without plugins the stored output is removed
+    enrich_and_store(&store, &EnrichmentPipeline::default(), &mut status)
+        .await
+        .unwrap();
+    assert!(list_enriched_states(&store).await.unwrap().is_empty());
+    assert!(
+        enriched_node_status(&store, status.node_id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
@@ -0,0 +1,36 @@
+//! `SeaORM` Entity for the change event table
+
+use sea_orm::entity::prelude::*;
+
+/// One change to one node, link, or location, in the append-only change log
+///
+/// The sequence orders the log and is assigned by the database when the
+/// event is appended. The time is Unix microseconds; the payload and changed
+/// fields are JSON.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "change_event")]
+pub struct Model {
+    /// Position of the event in the log
+    #[sea_orm(primary_key)]
+    pub sequence: i64,
+    /// Event ID
+    pub id: String,
+    /// Unix time the change was recorded, in microseconds
+    pub recorded_at: i64,
+    /// `node`, `link`, or `location`
+    pub entity: String,
+    /// ID of the entity changed
+    pub entity_id: String,
+    /// `created`, `updated`, or `deleted`
+    pub change_type: String,
+    /// JSON-encoded entity as written, or as last stored for deletions
+    pub payload: String,
+    /// JSON array of the `custom_data` paths the write changed
+    pub changed_fields: String,
+}
+
+/// Database relations for the change event entity
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
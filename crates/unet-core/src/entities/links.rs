@@ -38,6 +38,12 @@ pub struct Model {
     pub created_at: String,
     /// Timestamp when record was last updated
     pub updated_at: String,
+    /// Provisioning state (ordered, delivered, tested, live); unset means live
+    pub provisioning_state: Option<String>,
+    /// Date the circuit was ordered (`YYYY-MM-DD`)
+    pub ordered_on: Option<String>,
+    /// Date the circuit was delivered (`YYYY-MM-DD`)
+    pub delivered_on: Option<String>,
 }
 
 /// Database relations for link entity
@@ -30,6 +30,10 @@ pub struct Model {
     pub created_at: String,
     /// Timestamp when record was last updated
     pub updated_at: String,
+    /// JSON string for the structured postal address
+    pub postal_address: Option<String>,
+    /// IANA timezone name
+    pub timezone: Option<String>,
 }
 
 /// Database relations for location entity
@@ -67,6 +71,8 @@ mod tests {
             custom_data: Some(r#"{"zone": "A"}"#.to_string()),
             created_at: "2023-01-01T00:00:00Z".to_string(),
             updated_at: "2023-01-01T00:00:00Z".to_string(),
+            postal_address: None,
+            timezone: None,
         };
 
         assert_eq!(location.id, "loc-123");
@@ -94,6 +100,8 @@ mod tests {
             custom_data: None,
             created_at: "2023-01-01T00:00:00Z".to_string(),
             updated_at: "2023-01-01T00:00:00Z".to_string(),
+            postal_address: None,
+            timezone: None,
         };
 
         assert_eq!(location.id, "loc-minimal");
@@ -121,6 +129,8 @@ mod tests {
             custom_data: None,
             created_at: "2023-01-01T00:00:00Z".to_string(),
             updated_at: "2023-01-01T00:00:00Z".to_string(),
+            postal_address: None,
+            timezone: None,
         };
 
         let debug_str = format!("{location:?}");
@@ -143,6 +153,8 @@ mod tests {
             custom_data: None,
             created_at: "2023-01-01T00:00:00Z".to_string(),
             updated_at: "2023-01-01T00:00:00Z".to_string(),
+            postal_address: None,
+            timezone: None,
         };
 
         // Test equality with itself
@@ -160,6 +172,8 @@ mod tests {
             custom_data: None,
             created_at: "2023-01-01T00:00:00Z".to_string(),
             updated_at: "2023-01-01T00:00:00Z".to_string(),
+            postal_address: None,
+            timezone: None,
         };
         assert_ne!(location1, location3);
     }
@@ -178,6 +192,8 @@ mod tests {
             custom_data: Some(r#"{"test": true}"#.to_string()),
             created_at: "2023-01-01T00:00:00Z".to_string(),
             updated_at: "2023-01-01T00:00:00Z".to_string(),
+            postal_address: None,
+            timezone: None,
         };
 
         let serialized = serde_json::to_string(&location).expect("Failed to serialize");
@@ -1,19 +1,31 @@
 //! `SeaORM` Entities for μNet Core Database Tables
 
+pub mod change_events;
 pub mod interface_status;
 pub mod links;
 pub mod locations;
 pub mod node_status;
 pub mod nodes;
+pub mod performance_rollups;
+pub mod performance_samples;
+pub mod policy_results;
 pub mod polling_tasks;
+pub mod settings;
+pub mod shard_leases;
 pub mod vendors;
 
+pub use change_events::Entity as ChangeEvents;
 pub use interface_status::Entity as InterfaceStatus;
 pub use links::Entity as Links;
 pub use locations::Entity as Locations;
 pub use node_status::Entity as NodeStatus;
 pub use nodes::Entity as Nodes;
+pub use performance_rollups::Entity as PerformanceRollups;
+pub use performance_samples::Entity as PerformanceSamples;
+pub use policy_results::Entity as PolicyResults;
 pub use polling_tasks::Entity as PollingTasks;
+pub use settings::Entity as Settings;
+pub use shard_leases::Entity as ShardLeases;
 pub use vendors::Entity as Vendors;
 
 #[cfg(test)]
@@ -40,6 +40,10 @@ pub struct Model {
     pub created_at: String,
     /// Timestamp when record was last updated
     pub updated_at: String,
+    /// IPv6 management address of a dual-stack node
+    pub management_ipv6: Option<String>,
+    /// Zone of a link-local IPv6 management address
+    pub management_zone: Option<String>,
 }
 
 /// Database relations for node entity
@@ -0,0 +1,36 @@
+//! `SeaORM` Entity for the performance rollup table
+
+use sea_orm::entity::prelude::*;
+
+/// Hourly aggregate of one performance metric of a node
+///
+/// Raw samples past their retention are compacted into rollups, which keep
+/// enough to answer every aggregation of a range query at hourly or coarser
+/// steps.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "performance_rollup")]
+pub struct Model {
+    /// Unique identifier for the rollup
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    /// Node the samples were polled from
+    pub node_id: String,
+    /// Metric name, e.g. `cpu` or `load_average`
+    pub metric: String,
+    /// Unix time of the start of the hour, in seconds
+    pub bucket_start: i64,
+    /// Sum of the samples
+    pub total: f64,
+    /// Number of samples
+    pub samples: i64,
+    /// Smallest sample
+    pub low: f64,
+    /// Largest sample
+    pub high: f64,
+}
+
+/// Database relations for the performance rollup entity
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
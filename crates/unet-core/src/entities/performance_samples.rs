@@ -0,0 +1,29 @@
+//! `SeaORM` Entity for the performance sample table
+
+use sea_orm::entity::prelude::*;
+
+/// One polled value of one performance metric of a node
+///
+/// Samples are kept one metric per row, with the time as Unix seconds, so a
+/// range can be bucketed and aggregated in SQL.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "performance_sample")]
+pub struct Model {
+    /// Unique identifier for the sample
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    /// Node the sample was polled from
+    pub node_id: String,
+    /// Metric name, e.g. `cpu` or `load_average`
+    pub metric: String,
+    /// Polled value
+    pub value: f64,
+    /// Unix time of the poll, in seconds
+    pub recorded_at: i64,
+}
+
+/// Database relations for the performance sample entity
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
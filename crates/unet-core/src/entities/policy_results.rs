@@ -0,0 +1,35 @@
+//! `SeaORM` Entity for the policy results table
+
+use sea_orm::entity::prelude::*;
+
+/// One policy rule evaluated against one node
+///
+/// The outcome is kept in plain columns so results can be filtered and
+/// aggregated in SQL; `detail` holds the full serialized result.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "policy_result")]
+pub struct Model {
+    /// Unique identifier for the result
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    /// Rule ID, or `unknown` for rules without one
+    pub rule_id: String,
+    /// Node the rule was evaluated against
+    pub node_id: String,
+    /// `satisfied`, `not_satisfied`, `compliance_failure`, or `error`
+    pub status: String,
+    /// `info`, `warning`, or `error`
+    pub severity: String,
+    /// Error, compliance failure, or action message
+    pub message: Option<String>,
+    /// RFC 3339 timestamp of the evaluation
+    pub evaluated_at: String,
+    /// JSON-encoded `PolicyExecutionResult`
+    pub detail: String,
+}
+
+/// Database relations for the policy result entity
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
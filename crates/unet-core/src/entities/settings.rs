@@ -0,0 +1,26 @@
+//! `SeaORM` Entity for the settings table
+
+use sea_orm::entity::prelude::*;
+
+/// Namespaced JSON document used for runtime-managed configuration
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "setting")]
+pub struct Model {
+    /// Composite identifier in the form `namespace/key`
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    /// Namespace grouping related settings (e.g., `oid_profiles`)
+    pub namespace: String,
+    /// Key unique within the namespace
+    pub key: String,
+    /// JSON-encoded value
+    pub value: String,
+    /// Timestamp of the last write
+    pub updated_at: String,
+}
+
+/// Database relations for the setting entity
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,27 @@
+//! `SeaORM` Entity for the shard lease table
+
+use sea_orm::entity::prelude::*;
+
+/// A collector's claim on a polling shard
+///
+/// Times are Unix microseconds. A row whose expiry has passed is free to be
+/// claimed by any collector.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "shard_lease")]
+pub struct Model {
+    /// Shard number
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub shard: i64,
+    /// Collector holding the lease
+    pub collector: String,
+    /// Unix time the collector first claimed the shard, in microseconds
+    pub acquired_at: i64,
+    /// Unix time the lease lapses unless renewed, in microseconds
+    pub expires_at: i64,
+}
+
+/// Database relations for the shard lease entity
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,22 @@
+//! Tests for `change_events` entity
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::change_events::*;
+
+    #[test]
+    fn test_change_event_model_creation() {
+        let event = Model {
+            sequence: 1,
+            id: "0b5e2f9e-3c4a-4d61-8a55-9a0e4c1f7d21".to_string(),
+            recorded_at: 1_734_739_200_000_000,
+            entity: "node".to_string(),
+            entity_id: "5f0c8d7a-1e2b-4c3d-9f8e-7a6b5c4d3e2f".to_string(),
+            change_type: "updated".to_string(),
+            payload: "{}".to_string(),
+            changed_fields: "[\"custom_data.bgp.asn\"]".to_string(),
+        };
+        assert_eq!(event.entity, "node");
+        assert_eq!(event.change_type, "updated");
+    }
+}
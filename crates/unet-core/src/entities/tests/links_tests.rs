@@ -24,6 +24,9 @@ mod tests {
             custom_data: None,
             created_at: "2023-01-01T00:00:00Z".to_string(),
             updated_at: "2023-01-01T00:00:00Z".to_string(),
+            provisioning_state: None,
+            ordered_on: None,
+            delivered_on: None,
         };
 
         assert_eq!(link.id, "link-001");
@@ -54,6 +57,9 @@ mod tests {
             custom_data: Some(r#"{"sla": "99.9%"}"#.to_string()),
             created_at: "2023-01-01T00:00:00Z".to_string(),
             updated_at: "2023-01-01T00:00:00Z".to_string(),
+            provisioning_state: None,
+            ordered_on: None,
+            delivered_on: None,
         };
 
         assert_eq!(link.is_internet_circuit, 1);
@@ -80,6 +86,9 @@ mod tests {
             custom_data: None,
             created_at: "2023-01-01T00:00:00Z".to_string(),
             updated_at: "2023-01-01T00:00:00Z".to_string(),
+            provisioning_state: None,
+            ordered_on: None,
+            delivered_on: None,
         };
 
         let json = serde_json::to_string(&link).unwrap();
@@ -1,9 +1,13 @@
 //! Tests for `SeaORM` entities
 
+mod change_events_tests;
 mod interface_status_tests;
 mod links_tests;
 mod locations_tests;
 mod node_status_tests;
 mod nodes_tests;
+mod policy_results_tests;
 mod polling_tasks_tests;
+mod settings_tests;
+mod shard_leases_tests;
 mod vendors_tests;
@@ -25,6 +25,8 @@ mod tests {
             custom_data: Some(r#"{"key": "value"}"#.to_string()),
             created_at: "2023-01-01T00:00:00Z".to_string(),
             updated_at: "2023-01-01T00:00:00Z".to_string(),
+            management_ipv6: None,
+            management_zone: None,
         };
 
         assert_eq!(node.id, "test-node-id");
@@ -55,6 +57,8 @@ mod tests {
             custom_data: None,
             created_at: "2023-01-01T00:00:00Z".to_string(),
             updated_at: "2023-01-01T00:00:00Z".to_string(),
+            management_ipv6: None,
+            management_zone: None,
         };
 
         let json = serde_json::to_string(&node).unwrap();
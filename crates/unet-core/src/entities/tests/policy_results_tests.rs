@@ -0,0 +1,23 @@
+//! Tests for `policy_results` entity
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::policy_results::*;
+
+    #[test]
+    fn test_policy_result_model_creation() {
+        let result = Model {
+            id: "0b5e2f9e-3c4a-4d61-8a55-9a0e4c1f7d21".to_string(),
+            rule_id: "ntp-servers".to_string(),
+            node_id: "5f0c8d7a-1e2b-4c3d-9f8e-7a6b5c4d3e2f".to_string(),
+            status: "compliance_failure".to_string(),
+            severity: "warning".to_string(),
+            message: Some("ntp.server: expected \"10.0.0.1\", found null".to_string()),
+            evaluated_at: "2024-12-21T00:00:00.000000Z".to_string(),
+            detail: "{}".to_string(),
+        };
+        assert_eq!(result.status, "compliance_failure");
+        assert_eq!(result.severity, "warning");
+        assert!(result.message.is_some());
+    }
+}
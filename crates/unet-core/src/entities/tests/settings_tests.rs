@@ -0,0 +1,19 @@
+//! Tests for `settings` entity
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::settings::*;
+
+    #[test]
+    fn test_setting_model_creation() {
+        let setting = Model {
+            id: "oid_profiles/router-core".to_string(),
+            namespace: "oid_profiles".to_string(),
+            key: "router-core".to_string(),
+            value: "{}".to_string(),
+            updated_at: "2024-12-21T00:00:00Z".to_string(),
+        };
+        assert_eq!(setting.namespace, "oid_profiles");
+        assert_eq!(setting.key, "router-core");
+    }
+}
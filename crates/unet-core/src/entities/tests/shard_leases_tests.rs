@@ -0,0 +1,18 @@
+//! Tests for `shard_leases` entity
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::shard_leases::*;
+
+    #[test]
+    fn test_shard_lease_model_creation() {
+        let lease = Model {
+            shard: 3,
+            collector: "collector-a".to_string(),
+            acquired_at: 1_734_739_200_000_000,
+            expires_at: 1_734_739_260_000_000,
+        };
+        assert_eq!(lease.shard, 3);
+        assert!(lease.acquired_at < lease.expires_at);
+    }
+}
@@ -21,6 +21,8 @@ pub const DB_TRANSACTION_FAILED: &str = "DB_TRANSACTION_FAILED";
 pub const DB_CONSTRAINT_VIOLATION: &str = "DB_CONSTRAINT_VIOLATION";
 /// Error code for database operation timeouts
 pub const DB_TIMEOUT: &str = "DB_TIMEOUT";
+/// Error code for a busy or locked database, or a dropped connection
+pub const DB_BUSY: &str = "DB_BUSY";
 
 // Policy errors (POLICY_*)
 /// Error code for policy parsing failures
@@ -81,3 +83,17 @@ pub const SERIAL_JSON_FAILED: &str = "SERIAL_JSON_FAILED";
 pub const SERIAL_TOML_FAILED: &str = "SERIAL_TOML_FAILED";
 /// Error code for YAML serialization failures
 pub const SERIAL_YAML_FAILED: &str = "SERIAL_YAML_FAILED";
+
+// Outcome errors shared by every subsystem
+/// Error code for invalid input
+pub const VALIDATION_ERROR: &str = "VALIDATION_ERROR";
+/// Error code for entities that do not exist
+pub const NOT_FOUND: &str = "NOT_FOUND";
+/// Error code for writes that conflict with stored data
+pub const CONFLICT: &str = "CONFLICT";
+/// Error code for operations that succeeded for only some of their items
+pub const PARTIAL_SUCCESS: &str = "PARTIAL_SUCCESS";
+/// Error code for operations a backend does not support
+pub const UNSUPPORTED_OPERATION: &str = "UNSUPPORTED_OPERATION";
+/// Error code for failures no other code describes
+pub const OTHER_ERROR: &str = "OTHER_ERROR";
@@ -87,6 +87,7 @@ mod tests {
             "DB_CONSTRAINT_VIOLATION"
         );
         assert_eq!(constants::DB_TIMEOUT, "DB_TIMEOUT");
+        assert_eq!(constants::DB_BUSY, "DB_BUSY");
     }
 
     #[test]
@@ -128,11 +128,11 @@ impl Error {
             Self::Policy { .. } => "POLICY_ERROR",
             Self::Template { .. } => "TEMPLATE_ERROR",
             Self::Snmp { .. } => "SNMP_ERROR",
-            Self::Validation { .. } => "VALIDATION_ERROR",
+            Self::Validation { .. } => super::VALIDATION_ERROR,
             Self::Network { .. } => "NETWORK_ERROR",
             Self::Io { .. } => "IO_ERROR",
             Self::Serialization { .. } => "SERIALIZATION_ERROR",
-            Self::Other { .. } => "OTHER_ERROR",
+            Self::Other { .. } => super::OTHER_ERROR,
         }
     }
 
@@ -0,0 +1,258 @@
+//! Scheduled inventory exports
+//!
+//! An export schedule writes the inventory to a [`Destination`] every
+//! `interval_minutes`, so nightly dumps land in a data lake or on a file
+//! server without cron and scp glue. Each run writes `locations.json`,
+//! `nodes.json`, and `links.json`, as `unet export` does, to a directory
+//! named after the time the run started, e.g. `20261016T020000Z/nodes.json`.
+//!
+//! Destinations are a directory on the server, an S3 bucket or S3-compatible
+//! store, a Google Cloud Storage bucket, or a directory on an SFTP server.
+//! The server's export task runs the schedules that are due (see
+//! [`run_due`]) and hands every file to an [`ExportUploader`]. Object store
+//! requests are signed with AWS Signature Version 4 (see [`object_store`]),
+//! which Cloud Storage accepts for HMAC keys. Credentials never enter the
+//! datastore; the server reads them from its environment (see
+//! [`S3_CREDENTIALS`] and [`GCS_CREDENTIALS`]), and SFTP uses the server
+//! user's SSH keys.
+
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult, QueryOptions};
+use async_trait::async_trait;
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+
+mod destination;
+#[cfg(feature = "secrets")]
+pub mod object_store;
+mod schedules;
+
+pub use destination::Destination;
+use schedules::save_run;
+pub use schedules::{delete_schedule, get_schedule, latest_run, list_schedules, save_schedule};
+
+/// Minutes after a failed run before the schedule is tried again, unless its
+/// interval is shorter
+const RETRY_MINUTES: u32 = 15;
+
+/// Environment variables holding the access key ID, secret access key, and
+/// optional session token for S3 destinations
+pub const S3_CREDENTIALS: [&str; 3] = [
+    "AWS_ACCESS_KEY_ID",
+    "AWS_SECRET_ACCESS_KEY",
+    "AWS_SESSION_TOKEN",
+];
+
+/// Environment variables holding the HMAC key ID and secret for Cloud
+/// Storage destinations
+pub const GCS_CREDENTIALS: [&str; 2] = ["GCS_HMAC_ACCESS_ID", "GCS_HMAC_SECRET"];
+
+/// An inventory export run on a schedule
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportSchedule {
+    /// Schedule name, made of letters, digits, `-`, and `_`
+    pub name: String,
+    /// Where the inventory is written
+    pub destination: Destination,
+    /// Minutes between runs
+    pub interval_minutes: u32,
+    /// What the export is for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl ExportSchedule {
+    /// Creates an export schedule
+    ///
+    /// # Errors
+    /// Returns a validation error if the name is empty or has other
+    /// characters than letters, digits, `-`, and `_`, or the interval is zero.
+    pub fn new(
+        name: &str,
+        destination: Destination,
+        interval_minutes: u32,
+        description: Option<String>,
+    ) -> DataStoreResult<Self> {
+        let name = name.trim();
+        let message = if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            Some(format!(
+                "Invalid export name {name:?}: use letters, digits, '-', and '_'"
+            ))
+        } else if interval_minutes == 0 {
+            Some(format!(
+                "Export {name} interval must be at least one minute"
+            ))
+        } else {
+            None
+        };
+        if let Some(message) = message {
+            return Err(DataStoreError::ValidationError { message });
+        }
+        Ok(Self {
+            name: name.to_string(),
+            destination,
+            interval_minutes,
+            description: description.filter(|d| !d.trim().is_empty()),
+        })
+    }
+
+    /// Whether a run is due, given the latest one
+    ///
+    /// A failed run is retried after 15 minutes, or the interval if shorter.
+    #[must_use]
+    pub fn is_due(&self, latest: Option<&ExportRun>, now: DateTime<Utc>) -> bool {
+        latest.is_none_or(|run| {
+            let minutes = if run.error.is_some() {
+                self.interval_minutes.min(RETRY_MINUTES)
+            } else {
+                self.interval_minutes
+            };
+            now - run.started_at >= TimeDelta::minutes(i64::from(minutes))
+        })
+    }
+}
+
+/// One run of an export schedule
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportRun {
+    /// Schedule name
+    pub schedule: String,
+    /// When the run started
+    pub started_at: DateTime<Utc>,
+    /// Directory below the destination the files were written to
+    pub directory: String,
+    /// Files written, in order
+    pub files: Vec<String>,
+    /// Why the run stopped, if a file could not be written
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Outcome of running the schedules that were due
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScheduleRun {
+    /// Schedules whose files were all written
+    pub exported: Vec<String>,
+    /// Schedules that failed, with the error
+    pub failed: Vec<(String, String)>,
+}
+
+/// A file of an export
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportFile {
+    /// File name
+    pub name: String,
+    /// File contents
+    pub content: String,
+}
+
+/// Writes export files to destinations
+#[async_trait]
+pub trait ExportUploader: Send + Sync {
+    /// Writes `content` to `path`, relative to the destination
+    ///
+    /// # Errors
+    /// Returns a description of the failure if the file was not written.
+    async fn upload(
+        &self,
+        destination: &Destination,
+        path: &str,
+        content: &[u8],
+    ) -> Result<(), String>;
+}
+
+fn json_file<T: Serialize>(name: &str, items: &[T]) -> DataStoreResult<ExportFile> {
+    let content =
+        serde_json::to_string_pretty(items).map_err(|e| DataStoreError::InternalError {
+            message: format!("export file {name}: {e}"),
+        })?;
+    Ok(ExportFile {
+        name: name.to_string(),
+        content,
+    })
+}
+
+/// The inventory files of an export
+///
+/// # Errors
+/// Returns an error if the inventory cannot be read.
+pub async fn inventory_files(datastore: &dyn DataStore) -> DataStoreResult<Vec<ExportFile>> {
+    let options = QueryOptions::default();
+    Ok(vec![
+        json_file(
+            "locations.json",
+            &datastore.list_locations(&options).await?.items,
+        )?,
+        json_file("nodes.json", &datastore.list_nodes(&options).await?.items)?,
+        json_file("links.json", &datastore.list_links(&options).await?.items)?,
+    ])
+}
+
+/// Writes the inventory to a schedule's destination and keeps the run as
+/// its latest
+///
+/// The run stops at the first file that cannot be written; the failure is
+/// recorded in the returned run rather than returned as an error.
+///
+/// # Errors
+/// Returns an error if the inventory cannot be read or the run cannot be
+/// recorded.
+pub async fn run_export(
+    datastore: &dyn DataStore,
+    uploader: &dyn ExportUploader,
+    schedule: &ExportSchedule,
+    now: DateTime<Utc>,
+) -> DataStoreResult<ExportRun> {
+    let mut run = ExportRun {
+        schedule: schedule.name.clone(),
+        started_at: now,
+        directory: now.format("%Y%m%dT%H%M%SZ").to_string(),
+        files: Vec::new(),
+        error: None,
+    };
+    for file in inventory_files(datastore).await? {
+        let path = format!("{}/{}", run.directory, file.name);
+        if let Err(e) = uploader
+            .upload(&schedule.destination, &path, file.content.as_bytes())
+            .await
+        {
+            run.error = Some(format!("{path}: {e}"));
+            break;
+        }
+        run.files.push(file.name);
+    }
+    save_run(datastore, &run).await?;
+    Ok(run)
+}
+
+/// Runs every export schedule that is due
+///
+/// # Errors
+/// Returns an error if the schedules or their latest runs cannot be read.
+pub async fn run_due(
+    datastore: &dyn DataStore,
+    uploader: &dyn ExportUploader,
+    now: DateTime<Utc>,
+) -> DataStoreResult<ScheduleRun> {
+    let mut outcome = ScheduleRun::default();
+    for schedule in list_schedules(datastore).await? {
+        let latest = latest_run(datastore, &schedule.name).await?;
+        if !schedule.is_due(latest.as_ref(), now) {
+            continue;
+        }
+        match run_export(datastore, uploader, &schedule, now).await {
+            Ok(ExportRun { error: None, .. }) => outcome.exported.push(schedule.name),
+            Ok(ExportRun {
+                error: Some(error), ..
+            }) => outcome.failed.push((schedule.name, error)),
+            Err(e) => outcome.failed.push((schedule.name, e.to_string())),
+        }
+    }
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,165 @@
+//! Export destinations and their URLs
+
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+
+/// Default SFTP port
+const SFTP_PORT: u16 = 22;
+
+/// Where an export is written
+///
+/// Written and parsed as a URL: `file:///var/exports` (or a plain path),
+/// `s3://bucket/prefix?region=eu-west-1`, `gs://bucket/prefix`, or
+/// `sftp://user@host:22/var/exports`. An S3 URL may add
+/// `&endpoint=http://minio:9000` for an S3-compatible store.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Destination {
+    /// A directory on the server
+    Local {
+        /// Directory path
+        path: String,
+    },
+    /// An S3 bucket
+    S3 {
+        /// Bucket name
+        bucket: String,
+        /// Key prefix the run directories are written under
+        #[serde(default)]
+        prefix: String,
+        /// Bucket region, e.g. `eu-west-1`
+        region: String,
+        /// Endpoint of an S3-compatible store, addressed path-style;
+        /// AWS if unset
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        endpoint: Option<String>,
+    },
+    /// A Google Cloud Storage bucket
+    Gcs {
+        /// Bucket name
+        bucket: String,
+        /// Object name prefix the run directories are written under
+        #[serde(default)]
+        prefix: String,
+    },
+    /// A directory on an SFTP server
+    Sftp {
+        /// Server host name or address
+        host: String,
+        /// Server port
+        port: u16,
+        /// User to log in as
+        user: String,
+        /// Absolute directory path, without `"` or control characters
+        path: String,
+    },
+}
+
+impl Display for Destination {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Local { path } if path.starts_with('/') => write!(f, "file://{path}"),
+            Self::Local { path } => write!(f, "{path}"),
+            Self::S3 {
+                bucket,
+                prefix,
+                region,
+                endpoint,
+            } => {
+                write!(f, "s3://{bucket}/{prefix}?region={region}")?;
+                match endpoint {
+                    Some(endpoint) => write!(f, "&endpoint={endpoint}"),
+                    None => Ok(()),
+                }
+            }
+            Self::Gcs { bucket, prefix } => write!(f, "gs://{bucket}/{prefix}"),
+            Self::Sftp {
+                host,
+                port,
+                user,
+                path,
+            } => write!(f, "sftp://{user}@{host}:{port}{path}"),
+        }
+    }
+}
+
+impl FromStr for Destination {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| format!("Invalid export destination {s:?}: {reason}");
+        let Some((scheme, rest)) = s.split_once("://") else {
+            return if s.trim().is_empty() {
+                Err(invalid("no path given"))
+            } else {
+                Ok(Self::Local {
+                    path: s.to_string(),
+                })
+            };
+        };
+        let (location, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let params = query.split('&').filter_map(|pair| pair.split_once('='));
+        let param = |name: &str| {
+            params
+                .clone()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        };
+        let (bucket, prefix) = location.split_once('/').unwrap_or((location, ""));
+        let prefix = prefix.trim_matches('/').to_string();
+        if matches!(scheme, "s3" | "gs") && bucket.is_empty() {
+            return Err(invalid("no bucket given"));
+        }
+        Ok(match scheme {
+            "file" if location.starts_with('/') => Self::Local {
+                path: location.to_string(),
+            },
+            "file" => return Err(invalid("file URLs need an absolute path")),
+            "s3" => Self::S3 {
+                bucket: bucket.to_string(),
+                prefix,
+                region: param("region").ok_or_else(|| invalid("S3 URLs need ?region="))?,
+                endpoint: param("endpoint"),
+            },
+            "gs" => Self::Gcs {
+                bucket: bucket.to_string(),
+                prefix,
+            },
+            "sftp" => {
+                let (server, path) = location
+                    .split_once('/')
+                    .ok_or_else(|| invalid("SFTP URLs need a directory path"))?;
+                let (user, address) = server
+                    .split_once('@')
+                    .ok_or_else(|| invalid("SFTP URLs need a user, as user@host"))?;
+                let (host, port) = match address.rsplit_once(':') {
+                    Some((host, port)) => (
+                        host,
+                        port.parse()
+                            .map_err(|_| invalid("the port is not a number"))?,
+                    ),
+                    None => (address, SFTP_PORT),
+                };
+                if user.is_empty() || host.is_empty() {
+                    return Err(invalid("SFTP URLs need a user and host"));
+                }
+                // The path is quoted in sftp batch commands
+                if path.chars().any(|c| c == '"' || c.is_control()) {
+                    return Err(invalid(
+                        "SFTP paths must not contain '\"' or control characters",
+                    ));
+                }
+                Self::Sftp {
+                    host: host.to_string(),
+                    port,
+                    user: user.to_string(),
+                    path: format!("/{}", path.trim_end_matches('/')),
+                }
+            }
+            _ => {
+                return Err(invalid("use a path, file://, s3://, gs://, or sftp:// URL"));
+            }
+        })
+    }
+}
@@ -0,0 +1,234 @@
+//! Signed uploads to S3 and Cloud Storage
+//!
+//! Both stores accept `PUT` requests signed with AWS Signature Version 4;
+//! Cloud Storage does so through its XML API with HMAC keys, in region
+//! `auto`. Exports are small enough to upload in a single request, so
+//! multipart uploads are not needed.
+
+use super::{Destination, GCS_CREDENTIALS, S3_CREDENTIALS};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use std::fmt::Write;
+
+/// Host of the Cloud Storage XML API
+const GCS_HOST: &str = "storage.googleapis.com";
+
+/// Region Cloud Storage expects in signatures
+const GCS_REGION: &str = "auto";
+
+/// Signing algorithm named in the authorization header
+const ALGORITHM: &str = "AWS4-HMAC-SHA256";
+
+/// Keys signing object store requests
+#[derive(Clone, PartialEq, Eq)]
+pub struct Credentials {
+    /// Access key ID, or Cloud Storage HMAC key ID
+    pub access_key_id: String,
+    /// Secret access key, or Cloud Storage HMAC secret
+    pub secret_access_key: String,
+    /// Session token of temporary S3 credentials
+    pub session_token: Option<String>,
+}
+
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("access_key_id", &self.access_key_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Credentials {
+    /// Reads the credentials for a destination from the environment
+    ///
+    /// Returns `None` for destinations that are not object stores or when
+    /// the key ID or secret is not set.
+    #[must_use]
+    pub fn from_env(destination: &Destination) -> Option<Self> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        let (id, secret, token) = match destination {
+            Destination::S3 { .. } => {
+                (S3_CREDENTIALS[0], S3_CREDENTIALS[1], var(S3_CREDENTIALS[2]))
+            }
+            Destination::Gcs { .. } => (GCS_CREDENTIALS[0], GCS_CREDENTIALS[1], None),
+            Destination::Local { .. } | Destination::Sftp { .. } => return None,
+        };
+        Some(Self {
+            access_key_id: var(id)?,
+            secret_access_key: var(secret)?,
+            session_token: token,
+        })
+    }
+}
+
+/// A signed upload request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedPut {
+    /// Object URL
+    pub url: String,
+    /// Headers to send, including `authorization`
+    pub headers: Vec<(String, String)>,
+}
+
+/// Signs a `PUT` of `content` to `path` below an object store destination
+///
+/// Returns `None` for destinations that are not object stores.
+#[must_use]
+pub fn sign_put(
+    destination: &Destination,
+    path: &str,
+    content: &[u8],
+    credentials: &Credentials,
+    now: DateTime<Utc>,
+) -> Option<SignedPut> {
+    let (base, host, uri, region) = match destination {
+        Destination::S3 {
+            bucket,
+            prefix,
+            region,
+            endpoint: Some(endpoint),
+        } => {
+            // S3-compatible stores are addressed path-style
+            let base = endpoint.trim_end_matches('/').to_string();
+            let host = base
+                .split_once("://")
+                .map_or(base.as_str(), |(_, host)| host)
+                .to_string();
+            let uri = format!("/{bucket}/{}", encode_key(&object_key(prefix, path)));
+            (base, host, uri, region.as_str())
+        }
+        Destination::S3 {
+            bucket,
+            prefix,
+            region,
+            endpoint: None,
+        } => {
+            let host = format!("{bucket}.s3.{region}.amazonaws.com");
+            let uri = format!("/{}", encode_key(&object_key(prefix, path)));
+            (format!("https://{host}"), host, uri, region.as_str())
+        }
+        Destination::Gcs { bucket, prefix } => {
+            let uri = format!("/{bucket}/{}", encode_key(&object_key(prefix, path)));
+            (
+                format!("https://{GCS_HOST}"),
+                GCS_HOST.to_string(),
+                uri,
+                GCS_REGION,
+            )
+        }
+        Destination::Local { .. } | Destination::Sftp { .. } => return None,
+    };
+
+    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let payload_hash = hex(&Sha256::digest(content));
+    let mut headers = vec![
+        ("host".to_string(), host),
+        ("x-amz-content-sha256".to_string(), payload_hash.clone()),
+        ("x-amz-date".to_string(), amz_date.clone()),
+    ];
+    if let Some(token) = &credentials.session_token {
+        headers.push(("x-amz-security-token".to_string(), token.clone()));
+    }
+    let authorization = authorization(
+        "PUT",
+        &uri,
+        &headers,
+        &payload_hash,
+        credentials,
+        &amz_date,
+        region,
+    );
+    // The HTTP client sets the host header from the URL
+    headers.retain(|(name, _)| name != "host");
+    headers.push(("authorization".to_string(), authorization));
+    Some(SignedPut {
+        url: format!("{base}{uri}"),
+        headers,
+    })
+}
+
+/// Object key of `path` below `prefix`
+fn object_key(prefix: &str, path: &str) -> String {
+    let prefix = prefix.trim_matches('/');
+    if prefix.is_empty() {
+        path.to_string()
+    } else {
+        format!("{prefix}/{path}")
+    }
+}
+
+/// Percent-encodes an object key, keeping unreserved characters and `/`
+fn encode_key(key: &str) -> String {
+    let mut encoded = String::with_capacity(key.len());
+    for byte in key.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~/".contains(&byte) {
+            encoded.push(char::from(byte));
+        } else {
+            let _ = write!(encoded, "%{byte:02X}");
+        }
+    }
+    encoded
+}
+
+/// The `authorization` header of a request
+///
+/// `headers` are the signed headers with lowercase names, sorted by name.
+fn authorization(
+    method: &str,
+    uri: &str,
+    headers: &[(String, String)],
+    payload_hash: &str,
+    credentials: &Credentials,
+    amz_date: &str,
+    region: &str,
+) -> String {
+    let date = amz_date.get(..8).unwrap_or(amz_date);
+    let scope = format!("{date}/{region}/s3/aws4_request");
+    let canonical_headers: String = headers
+        .iter()
+        .map(|(name, value)| format!("{name}:{}\n", value.trim()))
+        .collect();
+    let signed_headers = headers
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>()
+        .join(";");
+    let canonical_request =
+        format!("{method}\n{uri}\n\n{canonical_headers}\n{signed_headers}\n{payload_hash}");
+    let string_to_sign = format!(
+        "{ALGORITHM}\n{amz_date}\n{scope}\n{}",
+        hex(&Sha256::digest(canonical_request.as_bytes()))
+    );
+
+    let secret = format!("AWS4{}", credentials.secret_access_key);
+    let mut key = hmac(secret.as_bytes(), date.as_bytes());
+    for part in [region, "s3", "aws4_request"] {
+        key = hmac(&key, part.as_bytes());
+    }
+    let signature = hex(&hmac(&key, string_to_sign.as_bytes()));
+    format!(
+        "{ALGORITHM} Credential={}/{scope}, SignedHeaders={signed_headers}, Signature={signature}",
+        credentials.access_key_id
+    )
+}
+
+fn hmac(key: &[u8], message: &[u8]) -> Vec<u8> {
+    // HMAC takes keys of any length
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(key) else {
+        return Vec::new();
+    };
+    mac.update(message);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        let _ = write!(hex, "{byte:02x}");
+    }
+    hex
+}
+
+#[cfg(test)]
+mod tests;
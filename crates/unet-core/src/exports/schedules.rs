@@ -0,0 +1,115 @@
+//! Storage of export schedules and their runs
+//!
+//! Schedules and the latest run of each are kept through the `DataStore`
+//! settings API keyed by schedule name.
+
+use super::{ExportRun, ExportSchedule};
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult};
+use serde::Serialize;
+use serde_json::Value;
+
+/// Settings namespace holding export schedules keyed by name
+const SCHEDULES_NAMESPACE: &str = "export_schedules";
+
+/// Settings namespace holding the latest run of each schedule keyed by name
+const RUNS_NAMESPACE: &str = "export_runs";
+
+fn parse<T: serde::de::DeserializeOwned>(key: &str, value: Value) -> DataStoreResult<T> {
+    serde_json::from_value(value).map_err(|e| DataStoreError::InternalError {
+        message: format!("stored export data {key}: {e}"),
+    })
+}
+
+fn to_value<T: Serialize>(key: &str, value: &T) -> DataStoreResult<Value> {
+    serde_json::to_value(value).map_err(|e| DataStoreError::InternalError {
+        message: format!("export data {key}: {e}"),
+    })
+}
+
+/// Lists all export schedules, ordered by name
+///
+/// # Errors
+/// Returns an error if the schedules cannot be read or one does not parse.
+pub async fn list_schedules(datastore: &dyn DataStore) -> DataStoreResult<Vec<ExportSchedule>> {
+    datastore
+        .list_settings(SCHEDULES_NAMESPACE)
+        .await?
+        .into_iter()
+        .map(|(key, value)| parse(&key, value))
+        .collect()
+}
+
+/// Returns the export schedule called `name`
+///
+/// # Errors
+/// Returns a not-found error if there is none, or an error if the datastore
+/// cannot be read.
+pub async fn get_schedule(
+    datastore: &dyn DataStore,
+    name: &str,
+) -> DataStoreResult<ExportSchedule> {
+    datastore
+        .get_setting(SCHEDULES_NAMESPACE, name)
+        .await?
+        .ok_or_else(|| DataStoreError::NotFound {
+            entity_type: "Export schedule".to_string(),
+            id: name.to_string(),
+        })
+        .and_then(|value| parse(name, value))
+}
+
+/// Stores an export schedule, replacing any with the same name
+///
+/// # Errors
+/// Returns an error if the datastore write fails.
+pub async fn save_schedule(
+    datastore: &dyn DataStore,
+    schedule: &ExportSchedule,
+) -> DataStoreResult<()> {
+    datastore
+        .put_setting(
+            SCHEDULES_NAMESPACE,
+            &schedule.name,
+            &to_value(&schedule.name, schedule)?,
+        )
+        .await
+}
+
+/// Removes an export schedule and its latest run
+///
+/// # Errors
+/// Returns a not-found error if there is no such schedule, or an error if
+/// the datastore write fails.
+pub async fn delete_schedule(datastore: &dyn DataStore, name: &str) -> DataStoreResult<()> {
+    datastore.delete_setting(SCHEDULES_NAMESPACE, name).await?;
+    match datastore.delete_setting(RUNS_NAMESPACE, name).await {
+        Ok(()) | Err(DataStoreError::NotFound { .. }) => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Returns the latest run of a schedule, if it has run
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or the stored run is malformed.
+pub async fn latest_run(
+    datastore: &dyn DataStore,
+    name: &str,
+) -> DataStoreResult<Option<ExportRun>> {
+    datastore
+        .get_setting(RUNS_NAMESPACE, name)
+        .await?
+        .map(|value| parse(name, value))
+        .transpose()
+}
+
+/// Keeps a run as the latest of its schedule
+pub(super) async fn save_run(datastore: &dyn DataStore, run: &ExportRun) -> DataStoreResult<()> {
+    datastore
+        .put_setting(
+            RUNS_NAMESPACE,
+            &run.schedule,
+            &to_value(&run.schedule, run)?,
+        )
+        .await
+}
@@ -0,0 +1,163 @@
+use super::*;
+use crate::datastore::sqlite::tests::setup::migrated_store;
+use crate::models::{DeviceRole, Node, Vendor};
+use chrono::TimeZone;
+use std::sync::Mutex;
+
+/// Records uploads and fails those of files named `failing`
+#[derive(Default)]
+struct RecordingUploader {
+    failing: Option<&'static str>,
+    uploads: Mutex<Vec<(Destination, String, Vec<u8>)>>,
+}
+
+#[async_trait]
+impl ExportUploader for RecordingUploader {
+    async fn upload(
+        &self,
+        destination: &Destination,
+        path: &str,
+        content: &[u8],
+    ) -> Result<(), String> {
+        if self.failing.is_some_and(|name| path.ends_with(name)) {
+            return Err("connection refused".to_string());
+        }
+        self.uploads.lock().unwrap().push((
+            destination.clone(),
+            path.to_string(),
+            content.to_vec(),
+        ));
+        Ok(())
+    }
+}
+
+#[test]
+fn test_destination_urls_round_trip() {
+    for url in [
+        "file:///var/exports",
+        "s3://lake/unet/inventory?region=eu-west-1",
+        "s3://lake/?region=us-east-1&endpoint=http://minio:9000",
+        "gs://lake/unet",
+        "sftp://backup@files.example.com:2222/srv/unet",
+    ] {
+        let destination: Destination = url.parse().unwrap();
+        assert_eq!(destination.to_string(), url);
+    }
+
+    assert_eq!(
+        "exports".parse::<Destination>().unwrap(),
+        Destination::Local {
+            path: "exports".to_string()
+        }
+    );
+    assert_eq!(
+        "sftp://backup@files/srv/unet/"
+            .parse::<Destination>()
+            .unwrap(),
+        Destination::Sftp {
+            host: "files".to_string(),
+            port: 22,
+            user: "backup".to_string(),
+            path: "/srv/unet".to_string(),
+        }
+    );
+    for invalid in [
+        "s3://lake/unet",
+        "gs:///unet",
+        "sftp://files/srv",
+        "ftp://files/srv",
+        "file://exports",
+        "sftp://backup@files/srv/\"unet",
+        "sftp://backup@files/srv/unet\nput /etc/passwd",
+    ] {
+        assert!(invalid.parse::<Destination>().is_err(), "{invalid}");
+    }
+}
+
+#[test]
+fn test_schedule_validation_and_due() {
+    let destination: Destination = "gs://lake".parse().unwrap();
+    assert!(ExportSchedule::new("nightly dump", destination.clone(), 60, None).is_err());
+    assert!(ExportSchedule::new("nightly", destination.clone(), 0, None).is_err());
+
+    let schedule = ExportSchedule::new("nightly", destination, 1440, None).unwrap();
+    let started_at = Utc.with_ymd_and_hms(2026, 10, 16, 2, 0, 0).unwrap();
+    let mut run = ExportRun {
+        schedule: "nightly".to_string(),
+        started_at,
+        directory: "20261016T020000Z".to_string(),
+        files: Vec::new(),
+        error: None,
+    };
+    assert!(schedule.is_due(None, started_at));
+    assert!(!schedule.is_due(Some(&run), started_at + TimeDelta::hours(23)));
+    assert!(schedule.is_due(Some(&run), started_at + TimeDelta::hours(24)));
+
+    // Failed runs are retried sooner
+    run.error = Some("nodes.json: timed out".to_string());
+    assert!(!schedule.is_due(Some(&run), started_at + TimeDelta::minutes(10)));
+    assert!(schedule.is_due(Some(&run), started_at + TimeDelta::minutes(15)));
+}
+
+#[tokio::test]
+async fn test_run_due_writes_inventory_and_records_runs() {
+    let store = migrated_store().await;
+    let node = Node::new(
+        "edge-1".to_string(),
+        "example.com".to_string(),
+        Vendor::Juniper,
+        DeviceRole::Router,
+    );
+    store.create_node(&node).await.unwrap();
+    for (name, url) in [
+        ("lake", "s3://lake/unet?region=eu-west-1"),
+        ("backup", "sftp://backup@files/srv"),
+    ] {
+        let schedule = ExportSchedule::new(name, url.parse().unwrap(), 60, None).unwrap();
+        save_schedule(&store, &schedule).await.unwrap();
+    }
+    let uploader = RecordingUploader {
+        failing: Some("nodes.json"),
+        ..RecordingUploader::default()
+    };
+    let now = Utc.with_ymd_and_hms(2026, 10, 16, 2, 0, 0).unwrap();
+
+    let outcome = run_due(&store, &uploader, now).await.unwrap();
+    assert!(outcome.exported.is_empty());
+    assert_eq!(outcome.failed.len(), 2);
+    assert_eq!(
+        outcome.failed[0],
+        (
+            "backup".to_string(),
+            "20261016T020000Z/nodes.json: connection refused".to_string()
+        )
+    );
+    let run = latest_run(&store, "lake").await.unwrap().unwrap();
+    assert_eq!(run.files, ["locations.json"]);
+
+    let uploader = RecordingUploader::default();
+    let later = now + TimeDelta::minutes(15);
+    let outcome = run_due(&store, &uploader, later).await.unwrap();
+    assert_eq!(outcome.exported, ["backup", "lake"]);
+    let uploads = uploader.uploads.lock().unwrap();
+    assert_eq!(uploads.len(), 6);
+    let (destination, path, content) = &uploads[1];
+    assert_eq!(destination.to_string(), "sftp://backup@files:22/srv");
+    assert_eq!(path, "20261016T021500Z/nodes.json");
+    let nodes: Value = serde_json::from_slice(content).unwrap();
+    assert_eq!(nodes[0]["name"], "edge-1");
+    drop(uploads);
+
+    // Not due again until the interval has passed
+    let outcome = run_due(&store, &uploader, later + TimeDelta::minutes(30))
+        .await
+        .unwrap();
+    assert_eq!(outcome, ScheduleRun::default());
+
+    delete_schedule(&store, "lake").await.unwrap();
+    assert!(latest_run(&store, "lake").await.unwrap().is_none());
+    assert!(matches!(
+        get_schedule(&store, "lake").await,
+        Err(DataStoreError::NotFound { .. })
+    ));
+}
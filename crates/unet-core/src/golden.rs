@@ -0,0 +1,334 @@
+//! Golden configurations and conformance scoring
+//!
+//! A golden configuration is the configuration a node is expected to carry:
+//! either a stored snapshot or a template rendered with variables and the
+//! node itself. It is assigned per node or per device role; a node
+//! assignment wins over its role's.
+//!
+//! Drift detection scores a node's running configuration by the share of the
+//! golden configuration's top-level sections it contains unchanged (see
+//! [`config_slicer::conformance`]). Both sides are first restricted to the
+//! sections the node owns (see [`crate::ownership`]). Every check is appended
+//! to a bounded per-node history so score trends can be reported.
+
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult};
+use crate::models::{DeviceRole, Node};
+use crate::ownership::{owned_config, owned_patterns};
+use crate::template::vars::resolve_vars;
+use chrono::{DateTime, Utc};
+use config_slicer::conformance::{Conformance, conformance};
+use config_slicer::parser;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Settings namespace holding golden configurations keyed by `scope:target`
+const GOLDEN_NAMESPACE: &str = "golden_configs";
+/// Settings namespace holding conformance history keyed by node ID
+const HISTORY_NAMESPACE: &str = "golden_conformance";
+
+/// Conformance checks kept per node
+pub const HISTORY_LIMIT: usize = 100;
+
+/// What a golden configuration applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GoldenScope {
+    /// A single node
+    Node,
+    /// All nodes with a device role
+    Role,
+}
+
+impl Display for GoldenScope {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Node => write!(f, "node"),
+            Self::Role => write!(f, "role"),
+        }
+    }
+}
+
+impl FromStr for GoldenScope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "node" => Ok(Self::Node),
+            "role" => Ok(Self::Role),
+            _ => Err(format!("Invalid golden config scope: {s}")),
+        }
+    }
+}
+
+/// Where the expected configuration comes from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum GoldenSource {
+    /// A stored configuration used as is
+    Snapshot {
+        /// Configuration text
+        config: String,
+    },
+    /// A `MiniJinja` template rendered per node
+    ///
+    /// The template sees each variable by name and the node as `node`.
+    Template {
+        /// Template text
+        template: String,
+        /// JSON object of template variables
+        #[serde(default)]
+        variables: Value,
+    },
+}
+
+/// A golden configuration assigned to a node or role
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GoldenConfig {
+    /// Assignment scope
+    pub scope: GoldenScope,
+    /// Node ID or role name depending on scope
+    pub target: String,
+    /// Expected configuration
+    pub source: GoldenSource,
+}
+
+impl GoldenConfig {
+    /// Creates an assignment, normalizing the target for its scope
+    ///
+    /// # Errors
+    /// Returns a validation error if the target is not a node ID or role, or
+    /// template variables are not a JSON object.
+    pub fn new(scope: GoldenScope, target: &str, source: GoldenSource) -> DataStoreResult<Self> {
+        let target = match scope {
+            GoldenScope::Node => Uuid::parse_str(target)
+                .map(|id| id.to_string())
+                .map_err(|e| format!("Invalid node ID {target}: {e}")),
+            GoldenScope::Role => DeviceRole::from_str(target).map(|role| role.to_string()),
+        }
+        .map_err(|message| DataStoreError::ValidationError { message })?;
+        if let GoldenSource::Template { variables, .. } = &source {
+            if !variables.is_object() && !variables.is_null() {
+                return Err(DataStoreError::ValidationError {
+                    message: format!(
+                        "Template variables for {scope} {target} must be a JSON object"
+                    ),
+                });
+            }
+        }
+        Ok(Self {
+            scope,
+            target,
+            source,
+        })
+    }
+
+    /// Storage key identifying the scope and target
+    #[must_use]
+    pub fn key(&self) -> String {
+        format!("{}:{}", self.scope, self.target)
+    }
+
+    /// Returns the expected configuration for `node`
+    ///
+    /// # Errors
+    /// Returns a validation error if the template fails to render.
+    pub fn render(&self, node: &Node) -> DataStoreResult<String> {
+        self.render_with(node, &Value::Null)
+    }
+
+    /// Returns the expected configuration for `node` with its layered
+    /// template variables (see [`crate::template::vars`])
+    ///
+    /// Layered variables override the template's own variables key by key.
+    ///
+    /// # Errors
+    /// Returns a validation error if the template fails to render.
+    pub fn render_with(&self, node: &Node, vars: &Value) -> DataStoreResult<String> {
+        match &self.source {
+            GoldenSource::Snapshot { config } => Ok(config.clone()),
+            GoldenSource::Template {
+                template,
+                variables,
+            } => {
+                let mut context = variables.as_object().cloned().unwrap_or_default();
+                if let Value::Object(vars) = vars {
+                    context.extend(vars.clone());
+                }
+                context.insert("node".to_string(), to_value(&self.key(), node)?);
+                minijinja::Environment::new()
+                    .render_str(template, Value::Object(context))
+                    .map_err(|e| DataStoreError::ValidationError {
+                        message: format!("Golden template {} failed to render: {e}", self.key()),
+                    })
+            }
+        }
+    }
+}
+
+/// One scored drift check
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConformanceRecord {
+    /// When the check ran
+    pub checked_at: DateTime<Utc>,
+    /// Key of the golden configuration checked against
+    pub golden: String,
+    /// Score and unmatched sections
+    pub conformance: Conformance,
+    /// Owned-section patterns the check was restricted to, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owned_sections: Option<Vec<String>>,
+}
+
+/// Result of a drift check with the change since the previous check
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConformanceCheck {
+    /// Checked node
+    pub node_id: Uuid,
+    /// The recorded check
+    pub record: ConformanceRecord,
+    /// Score of the previous check, if any
+    pub previous_score: Option<f64>,
+}
+
+fn parse<T: serde::de::DeserializeOwned>(key: &str, value: Value) -> DataStoreResult<T> {
+    serde_json::from_value(value).map_err(|e| DataStoreError::InternalError {
+        message: format!("stored golden config data {key}: {e}"),
+    })
+}
+
+fn to_value<T: Serialize>(key: &str, value: &T) -> DataStoreResult<Value> {
+    serde_json::to_value(value).map_err(|e| DataStoreError::InternalError {
+        message: format!("golden config data {key}: {e}"),
+    })
+}
+
+/// Lists all golden configuration assignments, ordered by scope and target
+///
+/// # Errors
+/// Returns an error if the assignments cannot be read or one does not parse.
+pub async fn list_golden(datastore: &dyn DataStore) -> DataStoreResult<Vec<GoldenConfig>> {
+    datastore
+        .list_settings(GOLDEN_NAMESPACE)
+        .await?
+        .into_iter()
+        .map(|(key, value)| parse(&key, value))
+        .collect()
+}
+
+/// Stores an assignment, replacing any for the same scope and target
+///
+/// # Errors
+/// Returns an error if the datastore write fails.
+pub async fn save_golden(datastore: &dyn DataStore, golden: &GoldenConfig) -> DataStoreResult<()> {
+    let key = golden.key();
+    datastore
+        .put_setting(GOLDEN_NAMESPACE, &key, &to_value(&key, golden)?)
+        .await
+}
+
+/// Removes the assignment for a scope and target
+///
+/// # Errors
+/// Returns an error if the target is invalid, nothing is assigned, or the
+/// datastore write fails.
+pub async fn delete_golden(
+    datastore: &dyn DataStore,
+    scope: GoldenScope,
+    target: &str,
+) -> DataStoreResult<()> {
+    let placeholder = GoldenSource::Snapshot {
+        config: String::new(),
+    };
+    let key = GoldenConfig::new(scope, target, placeholder)?.key();
+    datastore.delete_setting(GOLDEN_NAMESPACE, &key).await
+}
+
+/// Returns the golden configuration for a node: its own, else its role's
+///
+/// # Errors
+/// Returns an error if the node or role assignment cannot be read or the
+/// first one found does not parse.
+pub async fn resolve_golden(
+    datastore: &dyn DataStore,
+    node: &Node,
+) -> DataStoreResult<Option<GoldenConfig>> {
+    let keys = [
+        format!("{}:{}", GoldenScope::Node, node.id),
+        format!("{}:{}", GoldenScope::Role, node.role),
+    ];
+    for key in keys {
+        if let Some(value) = datastore.get_setting(GOLDEN_NAMESPACE, &key).await? {
+            return parse(&key, value).map(Some);
+        }
+    }
+    Ok(None)
+}
+
+/// Returns a node's conformance checks, oldest first
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or the history is malformed.
+pub async fn conformance_history(
+    datastore: &dyn DataStore,
+    node_id: Uuid,
+) -> DataStoreResult<Vec<ConformanceRecord>> {
+    let key = node_id.to_string();
+    datastore
+        .get_setting(HISTORY_NAMESPACE, &key)
+        .await?
+        .map_or_else(|| Ok(Vec::new()), |value| parse(&key, value))
+}
+
+/// Scores a running configuration against the node's golden configuration and records it
+///
+/// # Errors
+/// Returns a not-found error if no golden configuration applies to the node,
+/// a validation error if its template fails to render or its owned-section
+/// patterns are malformed, or an error if the history cannot be read or
+/// written.
+pub async fn check_conformance(
+    datastore: &dyn DataStore,
+    node: &Node,
+    running_config: &str,
+) -> DataStoreResult<ConformanceCheck> {
+    let golden =
+        resolve_golden(datastore, node)
+            .await?
+            .ok_or_else(|| DataStoreError::NotFound {
+                entity_type: "Golden config".to_string(),
+                id: node.name.clone(),
+            })?;
+    let vars = resolve_vars(datastore, node).await?;
+    let expected = owned_config(
+        node,
+        &parser::parse(&golden.render_with(node, &vars.variables)?),
+    )?;
+    let running = owned_config(node, &parser::parse(running_config))?;
+    let record = ConformanceRecord {
+        checked_at: Utc::now(),
+        golden: golden.key(),
+        conformance: conformance(&expected, &running),
+        owned_sections: owned_patterns(node)?,
+    };
+
+    let key = node.id.to_string();
+    let mut history = conformance_history(datastore, node.id).await?;
+    let previous_score = history.last().map(|last| last.conformance.score);
+    history.push(record.clone());
+    history.drain(..history.len().saturating_sub(HISTORY_LIMIT));
+    datastore
+        .put_setting(HISTORY_NAMESPACE, &key, &to_value(&key, &history)?)
+        .await?;
+
+    Ok(ConformanceCheck {
+        node_id: node.id,
+        record,
+        previous_score,
+    })
+}
+
+#[cfg(test)]
+mod tests;
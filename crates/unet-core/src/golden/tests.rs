@@ -0,0 +1,138 @@
+use super::*;
+use crate::datastore::sqlite::tests::setup::settings_store;
+use crate::models::Vendor;
+use serde_json::json;
+
+fn router() -> Node {
+    Node::new(
+        "edge-1".to_string(),
+        "example.com".to_string(),
+        Vendor::Juniper,
+        DeviceRole::Router,
+    )
+}
+
+fn snapshot(config: &str) -> GoldenSource {
+    GoldenSource::Snapshot {
+        config: config.to_string(),
+    }
+}
+
+#[test]
+fn test_new_normalizes_target_and_validates_variables() {
+    let golden = GoldenConfig::new(GoldenScope::Role, "Router", snapshot("")).unwrap();
+    assert_eq!(golden.key(), "role:router");
+
+    assert!(GoldenConfig::new(GoldenScope::Node, "edge-1", snapshot("")).is_err());
+    let template = GoldenSource::Template {
+        template: String::new(),
+        variables: json!([1]),
+    };
+    assert!(GoldenConfig::new(GoldenScope::Role, "router", template).is_err());
+}
+
+#[test]
+fn test_render_template_with_variables_and_node() {
+    let node = router();
+    let template = GoldenSource::Template {
+        template: "hostname {{ node.name }}\nntp server {{ ntp }}\n".to_string(),
+        variables: json!({"ntp": "10.0.0.1"}),
+    };
+    let golden = GoldenConfig::new(GoldenScope::Role, "router", template).unwrap();
+
+    assert_eq!(
+        golden.render(&node).unwrap(),
+        "hostname edge-1\nntp server 10.0.0.1\n"
+    );
+}
+
+#[tokio::test]
+async fn test_node_assignment_wins_over_role() {
+    let store = settings_store().await;
+    let node = router();
+    let role = GoldenConfig::new(GoldenScope::Role, "router", snapshot("a\n")).unwrap();
+    let own = GoldenConfig::new(GoldenScope::Node, &node.id.to_string(), snapshot("b\n")).unwrap();
+    save_golden(&store, &role).await.unwrap();
+    assert_eq!(resolve_golden(&store, &node).await.unwrap(), Some(role));
+
+    save_golden(&store, &own).await.unwrap();
+    assert_eq!(resolve_golden(&store, &node).await.unwrap(), Some(own));
+    assert_eq!(list_golden(&store).await.unwrap().len(), 2);
+
+    delete_golden(&store, GoldenScope::Node, &node.id.to_string())
+        .await
+        .unwrap();
+    assert!(
+        delete_golden(&store, GoldenScope::Node, &node.id.to_string())
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_check_conformance_records_trend() {
+    let store = settings_store().await;
+    let node = router();
+    let golden = snapshot("ntp server 10.0.0.1\nsnmp-server community x\n");
+    save_golden(
+        &store,
+        &GoldenConfig::new(GoldenScope::Role, "router", golden).unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let first = check_conformance(&store, &node, "ntp server 10.0.0.1\n")
+        .await
+        .unwrap();
+    let second = check_conformance(
+        &store,
+        &node,
+        "ntp server 10.0.0.1\nsnmp-server community x\n",
+    )
+    .await
+    .unwrap();
+
+    assert!(first.previous_score.is_none());
+    assert!((first.record.conformance.score - 50.0).abs() < 1e-9);
+    assert!((second.previous_score.unwrap() - 50.0).abs() < 1e-9);
+    assert!((second.record.conformance.score - 100.0).abs() < 1e-9);
+    assert_eq!(conformance_history(&store, node.id).await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_check_conformance_without_golden_is_not_found() {
+    let store = settings_store().await;
+
+    let result = check_conformance(&store, &router(), "hostname edge-1\n").await;
+
+    assert!(matches!(result, Err(DataStoreError::NotFound { .. })));
+}
+
+#[tokio::test]
+async fn test_check_conformance_ignores_unowned_sections() {
+    let store = settings_store().await;
+    let mut node = router();
+    crate::ownership::set_owned_patterns(&mut node, Some(vec!["ntp .*".to_string()])).unwrap();
+    let golden = snapshot("ntp server 10.0.0.1\nsnmp-server community x\n");
+    save_golden(
+        &store,
+        &GoldenConfig::new(GoldenScope::Role, "router", golden).unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let check = check_conformance(
+        &store,
+        &node,
+        "ntp server 10.0.0.1\nsnmp-server community y\n",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(check.record.conformance.total, 1);
+    assert!((check.record.conformance.score - 100.0).abs() < 1e-9);
+    assert_eq!(
+        check.record.owned_sections,
+        Some(vec!["ntp .*".to_string()])
+    );
+}
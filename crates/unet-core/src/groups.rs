@@ -0,0 +1,212 @@
+//! Dynamic node groups defined by saved filters
+//!
+//! A group is a named policy condition such as
+//! `node.vendor == "juniper" AND node.custom_data.tier == "core"`, evaluated
+//! against each node with the node's fields under `node.`, as in policy
+//! rules. Membership is never stored: every lookup evaluates the filter
+//! against the current inventory, so nodes join and leave groups as their
+//! attributes change. A node missing a field its group's filter compares is
+//! not a member.
+//!
+//! Groups can be named wherever a set of nodes is selected: OID profile
+//! assignments (see [`crate::snmp::profiles`]), policy evaluation batches,
+//! the firmware report, and webhook subscription filters.
+//!
+//! Filters are written in the policy DSL, so saving a group needs the
+//! `policy` feature.
+
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult, QueryOptions};
+use crate::models::Node;
+use crate::policy::{Condition, EvaluationContext, PolicyEvaluator};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// Settings namespace holding group definitions keyed by name
+const GROUPS_NAMESPACE: &str = "node_groups";
+
+/// A named filter selecting nodes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeGroup {
+    /// Group name, made of letters, digits, `-`, and `_`
+    pub name: String,
+    /// What the group is for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Policy condition a node must satisfy to be a member
+    pub filter: String,
+}
+
+impl NodeGroup {
+    /// Creates a group, checking that the filter parses
+    ///
+    /// # Errors
+    /// Returns a validation error if the name is empty or has other
+    /// characters than letters, digits, `-`, and `_`, or the filter is not a
+    /// valid policy condition.
+    pub fn new(name: &str, filter: &str, description: Option<String>) -> DataStoreResult<Self> {
+        let group = Self {
+            name: group_name(name)?,
+            description: description.filter(|d| !d.trim().is_empty()),
+            filter: filter.trim().to_string(),
+        };
+        group.condition()?;
+        Ok(group)
+    }
+
+    /// Whether the node is currently a member
+    ///
+    /// # Errors
+    /// Returns a validation error if the filter does not parse.
+    pub fn matches(&self, node: &Node) -> DataStoreResult<bool> {
+        Ok(is_member(&self.condition()?, node))
+    }
+
+    /// Selects the members among `nodes`, keeping their order
+    ///
+    /// # Errors
+    /// Returns a validation error if the filter does not parse.
+    pub fn select<'a>(&self, nodes: &'a [Node]) -> DataStoreResult<Vec<&'a Node>> {
+        let condition = self.condition()?;
+        Ok(nodes
+            .iter()
+            .filter(|node| is_member(&condition, node))
+            .collect())
+    }
+
+    fn condition(&self) -> DataStoreResult<Condition> {
+        parse_filter(&self.filter).map_err(|message| DataStoreError::ValidationError {
+            message: format!("Invalid filter for group {}: {message}", self.name),
+        })
+    }
+}
+
+/// Trims and checks a group name
+///
+/// # Errors
+/// Returns a validation error if the name is empty or has other characters
+/// than letters, digits, `-`, and `_`.
+pub fn group_name(name: &str) -> DataStoreResult<String> {
+    let name = name.trim();
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(DataStoreError::ValidationError {
+            message: format!("Invalid group name {name:?}: use letters, digits, '-', and '_'"),
+        });
+    }
+    Ok(name.to_string())
+}
+
+#[cfg(feature = "policy")]
+fn parse_filter(filter: &str) -> Result<Condition, String> {
+    crate::policy::PolicyParser::parse_condition(filter).map_err(|e| e.message)
+}
+
+#[cfg(not(feature = "policy"))]
+fn parse_filter(_filter: &str) -> Result<Condition, String> {
+    Err("group filters require the `policy` feature".to_string())
+}
+
+fn is_member(condition: &Condition, node: &Node) -> bool {
+    let Ok(node_data) = serde_json::to_value(node) else {
+        return false;
+    };
+    let context = EvaluationContext::new(json!({ "node": node_data }));
+    PolicyEvaluator::evaluate_condition(condition, &context).unwrap_or(false)
+}
+
+/// Names of the groups among `groups` the node is a member of
+///
+/// Groups whose filter does not parse have no members.
+#[must_use]
+pub fn memberships(groups: &[NodeGroup], node: &Node) -> Vec<String> {
+    groups
+        .iter()
+        .filter(|group| group.matches(node).unwrap_or(false))
+        .map(|group| group.name.clone())
+        .collect()
+}
+
+/// Lists all groups, ordered by name
+///
+/// Datastores without settings support have no groups.
+///
+/// # Errors
+/// Returns an error if the groups cannot be read or a stored group does not
+/// parse.
+pub async fn list_groups(datastore: &dyn DataStore) -> DataStoreResult<Vec<NodeGroup>> {
+    let stored = match datastore.list_settings(GROUPS_NAMESPACE).await {
+        Ok(stored) => stored,
+        Err(DataStoreError::UnsupportedOperation { .. }) => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    stored
+        .into_iter()
+        .map(|(name, value)| decode(&name, value))
+        .collect()
+}
+
+/// Gets a group by name
+///
+/// # Errors
+/// Returns a not-found error if there is no such group, or an error if the
+/// datastore cannot be read or the stored document is malformed.
+pub async fn get_group(datastore: &dyn DataStore, name: &str) -> DataStoreResult<NodeGroup> {
+    let value = datastore
+        .get_setting(GROUPS_NAMESPACE, name)
+        .await?
+        .ok_or_else(|| DataStoreError::NotFound {
+            entity_type: "node group".to_string(),
+            id: name.to_string(),
+        })?;
+    decode(name, value)
+}
+
+/// Stores a group, replacing any group with the same name
+///
+/// # Errors
+/// Returns an error if the datastore write fails.
+pub async fn save_group(datastore: &dyn DataStore, group: &NodeGroup) -> DataStoreResult<()> {
+    let value = serde_json::to_value(group).map_err(|e| DataStoreError::InternalError {
+        message: format!("node group {}: {e}", group.name),
+    })?;
+    datastore
+        .put_setting(GROUPS_NAMESPACE, &group.name, &value)
+        .await
+}
+
+/// Deletes a group
+///
+/// Profile assignments, batches, and subscriptions still naming the group
+/// match no nodes until it is recreated.
+///
+/// # Errors
+/// Returns a not-found error if there is no such group, or an error if the
+/// datastore write fails.
+pub async fn delete_group(datastore: &dyn DataStore, name: &str) -> DataStoreResult<()> {
+    datastore.delete_setting(GROUPS_NAMESPACE, name).await
+}
+
+/// Evaluates a group against every node, returning the members ordered by name
+///
+/// # Errors
+/// Returns an error if the group does not exist, its filter does not parse,
+/// or nodes cannot be read.
+pub async fn group_members(datastore: &dyn DataStore, name: &str) -> DataStoreResult<Vec<Node>> {
+    let group = get_group(datastore, name).await?;
+    let nodes = datastore.list_nodes(&QueryOptions::default()).await?.items;
+    let mut members: Vec<Node> = group.select(&nodes)?.into_iter().cloned().collect();
+    members.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(members)
+}
+
+fn decode(name: &str, value: serde_json::Value) -> DataStoreResult<NodeGroup> {
+    serde_json::from_value(value).map_err(|e| DataStoreError::InternalError {
+        message: format!("stored node group {name}: {e}"),
+    })
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,108 @@
+use super::*;
+use crate::datastore::sqlite::tests::setup::migrated_store;
+use crate::models::{DeviceRole, Vendor};
+use serde_json::json;
+
+fn node(name: &str, vendor: Vendor, tier: Option<&str>) -> Node {
+    let mut node = Node::new(
+        name.to_string(),
+        "example.com".to_string(),
+        vendor,
+        DeviceRole::Router,
+    );
+    if let Some(tier) = tier {
+        node.custom_data = json!({ "tier": tier });
+    }
+    node
+}
+
+#[test]
+fn test_new_validates_name_and_filter() {
+    let group =
+        NodeGroup::new(" core ", r#" node.vendor == "juniper" "#, Some(" ".into())).unwrap();
+    assert_eq!(group.name, "core");
+    assert_eq!(group.filter, r#"node.vendor == "juniper""#);
+    assert_eq!(group.description, None);
+
+    assert!(NodeGroup::new("core routers", "node.vendor == \"juniper\"", None).is_err());
+    assert!(NodeGroup::new("core", "node.vendor ==", None).is_err());
+}
+
+#[test]
+fn test_membership_follows_node_attributes() {
+    let group = NodeGroup::new(
+        "juniper-core",
+        r#"node.vendor == "juniper" AND node.custom_data.tier == "core""#,
+        None,
+    )
+    .unwrap();
+    let mut core = node("core-1", Vendor::Juniper, Some("core"));
+    let edge = node("edge-1", Vendor::Juniper, Some("edge"));
+    let untagged = node("lab-1", Vendor::Juniper, None);
+
+    let nodes = [core.clone(), edge, untagged];
+    let selected: Vec<&str> = group
+        .select(&nodes)
+        .unwrap()
+        .iter()
+        .map(|node| node.name.as_str())
+        .collect();
+    assert_eq!(selected, ["core-1"]);
+
+    core.custom_data = json!({ "tier": "edge" });
+    assert!(!group.matches(&core).unwrap());
+}
+
+#[test]
+fn test_memberships_names_matching_groups() {
+    let groups = [
+        NodeGroup::new("cisco", r#"node.vendor == "cisco""#, None).unwrap(),
+        NodeGroup::new("routers", r#"node.role == "router""#, None).unwrap(),
+        NodeGroup {
+            name: "broken".to_string(),
+            description: None,
+            filter: "node.vendor ==".to_string(),
+        },
+    ];
+
+    assert_eq!(
+        memberships(&groups, &node("r1", Vendor::Juniper, None)),
+        ["routers"]
+    );
+}
+
+#[tokio::test]
+async fn test_group_members_are_evaluated_on_lookup() {
+    let store = migrated_store().await;
+    let group = NodeGroup::new("core", r#"node.custom_data.tier == "core""#, None).unwrap();
+    save_group(&store, &group).await.unwrap();
+    store
+        .create_node(&node("core-2", Vendor::Arista, Some("core")))
+        .await
+        .unwrap();
+    store
+        .create_node(&node("edge-1", Vendor::Arista, Some("edge")))
+        .await
+        .unwrap();
+
+    let members = group_members(&store, "core").await.unwrap();
+    assert_eq!(members.len(), 1);
+    store
+        .create_node(&node("core-1", Vendor::Arista, Some("core")))
+        .await
+        .unwrap();
+    let names: Vec<String> = group_members(&store, "core")
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|node| node.name)
+        .collect();
+    assert_eq!(names, ["core-1", "core-2"]);
+
+    assert_eq!(list_groups(&store).await.unwrap(), [group]);
+    delete_group(&store, "core").await.unwrap();
+    assert!(matches!(
+        get_group(&store, "core").await,
+        Err(DataStoreError::NotFound { .. })
+    ));
+}
@@ -0,0 +1,135 @@
+//! Hardware inventory of nodes
+//!
+//! The poller walks each device's ENTITY-MIB `entPhysicalTable` once per
+//! `inventory_interval`, and the chassis, modules, transceivers, and power
+//! supplies found are recorded as the node's inventory, with serial numbers,
+//! part numbers, and firmware revisions. The latest inventory of each node
+//! is kept through the `DataStore` settings API and feeds asset and
+//! end-of-life reports.
+
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult};
+use crate::models::derived::HardwareComponent;
+use crate::snmp::{SnmpValue, StandardOid};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Settings namespace holding the latest inventory of each node keyed by node ID
+const HARDWARE_NAMESPACE: &str = "hardware_inventory";
+
+/// Hardware of a node as last walked
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HardwareInventory {
+    /// Node the inventory was walked from
+    pub node_id: Uuid,
+    /// When it was walked
+    pub collected_at: DateTime<Utc>,
+    /// Chassis, modules, transceivers, and power supplies, by index
+    pub components: Vec<HardwareComponent>,
+}
+
+impl HardwareInventory {
+    /// Builds the inventory from polled values
+    ///
+    /// Returns `None` when the values hold no `entPhysicalTable` rows, as
+    /// for polls that did not walk the inventory.
+    #[must_use]
+    pub fn from_snmp(
+        node_id: Uuid,
+        snmp_data: &HashMap<String, SnmpValue>,
+        collected_at: DateTime<Utc>,
+    ) -> Option<Self> {
+        let table = format!("{}.", StandardOid::EntPhysicalTable.oid());
+        if !snmp_data.keys().any(|oid| oid.starts_with(&table)) {
+            return None;
+        }
+        Some(Self {
+            node_id,
+            collected_at,
+            components: HardwareComponent::from_snmp(snmp_data),
+        })
+    }
+}
+
+fn parse(node_id: &str, value: serde_json::Value) -> DataStoreResult<HardwareInventory> {
+    serde_json::from_value(value).map_err(|e| DataStoreError::InternalError {
+        message: format!("stored hardware inventory of {node_id}: {e}"),
+    })
+}
+
+/// Records the inventory in a node's polled values as its latest
+///
+/// Returns `Ok(None)`, leaving the stored inventory alone, when the values
+/// hold no `entPhysicalTable` rows.
+///
+/// # Errors
+/// Returns an error if the datastore write fails.
+pub async fn record_inventory(
+    datastore: &dyn DataStore,
+    node_id: Uuid,
+    snmp_data: &HashMap<String, SnmpValue>,
+) -> DataStoreResult<Option<HardwareInventory>> {
+    let Some(inventory) = HardwareInventory::from_snmp(node_id, snmp_data, Utc::now()) else {
+        return Ok(None);
+    };
+    save_inventory(datastore, &inventory).await?;
+    Ok(Some(inventory))
+}
+
+/// Stores an inventory as its node's latest
+///
+/// # Errors
+/// Returns an error if the datastore write fails.
+pub async fn save_inventory(
+    datastore: &dyn DataStore,
+    inventory: &HardwareInventory,
+) -> DataStoreResult<()> {
+    let value = serde_json::to_value(inventory).map_err(|e| DataStoreError::InternalError {
+        message: format!("hardware inventory of {}: {e}", inventory.node_id),
+    })?;
+    datastore
+        .put_setting(HARDWARE_NAMESPACE, &inventory.node_id.to_string(), &value)
+        .await
+}
+
+/// Gets the latest inventory of a node
+///
+/// Datastores without settings support have no inventories.
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or the stored inventory
+/// is malformed.
+pub async fn get_inventory(
+    datastore: &dyn DataStore,
+    node_id: Uuid,
+) -> DataStoreResult<Option<HardwareInventory>> {
+    let key = node_id.to_string();
+    match datastore.get_setting(HARDWARE_NAMESPACE, &key).await {
+        Ok(stored) => stored.map(|value| parse(&key, value)).transpose(),
+        Err(DataStoreError::UnsupportedOperation { .. }) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Lists the latest inventory of every node that has one
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or a stored inventory
+/// is malformed.
+pub async fn list_inventories(
+    datastore: &dyn DataStore,
+) -> DataStoreResult<Vec<HardwareInventory>> {
+    let stored = match datastore.list_settings(HARDWARE_NAMESPACE).await {
+        Ok(stored) => stored,
+        Err(DataStoreError::UnsupportedOperation { .. }) => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    stored
+        .into_iter()
+        .map(|(key, value)| parse(&key, value))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,70 @@
+use super::*;
+use crate::datastore::sqlite::tests::setup::settings_store;
+use crate::models::derived::HardwareKind;
+
+fn walk(rows: &[(u32, u32, SnmpValue)]) -> HashMap<String, SnmpValue> {
+    let table = StandardOid::EntPhysicalTable.oid();
+    rows.iter()
+        .map(|(column, index, value)| (format!("{table}.{column}.{index}"), value.clone()))
+        .collect()
+}
+
+fn text(value: &str) -> SnmpValue {
+    SnmpValue::String(value.to_string())
+}
+
+#[tokio::test]
+async fn test_record_inventory_replaces_latest() {
+    let store = settings_store().await;
+    let node_id = Uuid::new_v4();
+    let first = walk(&[
+        (5, 1, SnmpValue::Integer(3)),
+        (11, 1, text("JN11F2C3AAFA")),
+        (13, 1, text("MX204")),
+        (5, 9, SnmpValue::Integer(6)),
+        (7, 9, text("PEM 0")),
+    ]);
+
+    let recorded = record_inventory(&store, node_id, &first)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(recorded.components.len(), 2);
+    assert_eq!(recorded.components[0].kind, HardwareKind::Chassis);
+    assert_eq!(
+        recorded.components[0].serial_number.as_deref(),
+        Some("JN11F2C3AAFA")
+    );
+    assert_eq!(recorded.components[1].kind, HardwareKind::PowerSupply);
+
+    let second = walk(&[(5, 1, SnmpValue::Integer(3)), (13, 1, text("MX204"))]);
+    record_inventory(&store, node_id, &second).await.unwrap();
+    let stored = get_inventory(&store, node_id).await.unwrap().unwrap();
+    assert_eq!(stored.components.len(), 1);
+    assert_eq!(list_inventories(&store).await.unwrap(), [stored]);
+}
+
+#[tokio::test]
+async fn test_poll_without_inventory_keeps_stored() {
+    let store = settings_store().await;
+    let node_id = Uuid::new_v4();
+    record_inventory(&store, node_id, &walk(&[(5, 1, SnmpValue::Integer(3))]))
+        .await
+        .unwrap();
+
+    let poll = HashMap::from([("1.3.6.1.2.1.1.5.0".to_string(), text("edge-01"))]);
+    assert_eq!(
+        record_inventory(&store, node_id, &poll).await.unwrap(),
+        None
+    );
+    assert_eq!(
+        get_inventory(&store, node_id)
+            .await
+            .unwrap()
+            .unwrap()
+            .components
+            .len(),
+        1
+    );
+    assert_eq!(get_inventory(&store, Uuid::new_v4()).await.unwrap(), None);
+}
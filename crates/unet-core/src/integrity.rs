@@ -0,0 +1,365 @@
+//! Checks for references to data that no longer exists
+//!
+//! Links, locations, and nodes refer to each other by ID, and derived state,
+//! policy results, and per-node settings documents refer to nodes. Not every
+//! path that deletes an entity takes what refers to it along (databases
+//! written without foreign key enforcement, imports, direct edits), so
+//! [`check`] looks for [`IssueKind`]s of dangling reference and repairs the
+//! kinds it is asked to:
+//!
+//! - links whose source or destination node is gone are deleted
+//! - locations whose parent is gone become roots, with the paths below them
+//!   rewritten
+//! - nodes whose location is gone are left without a location
+//! - derived state, polling tasks, performance history, and policy results of
+//!   deleted nodes are deleted
+//! - settings documents kept per node (interface VLANs, hardware inventory,
+//!   configuration snapshots, collected state, template renders, attribute
+//!   provenance) of deleted nodes are deleted
+//!
+//! The report of every run is kept, so the result of the server's scheduled
+//! check can be read back with [`last_report`].
+
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult, QueryOptions};
+use crate::models::Location;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Settings namespace holding the last report
+const NAMESPACE: &str = "integrity";
+/// Key of the last [`IntegrityReport`] in [`NAMESPACE`]
+const REPORT_KEY: &str = "last_report";
+
+/// Settings namespaces whose keys start with a node ID
+const NODE_KEYED_NAMESPACES: [&str; 7] = [
+    "interface_vlans",
+    "hardware_inventory",
+    "config_snapshots",
+    "config_snapshot_history",
+    "collected_state",
+    "template_renders",
+    "node_provenance",
+];
+
+/// Kind of dangling reference
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueKind {
+    /// Link whose source or destination node does not exist
+    OrphanedLink,
+    /// Location whose parent does not exist
+    MissingParent,
+    /// Node whose location does not exist
+    MissingLocation,
+    /// Derived state, polling, performance, or policy result rows of a node
+    /// that does not exist
+    OrphanedState,
+    /// Settings document kept for a node that does not exist
+    OrphanedSetting,
+}
+
+impl IssueKind {
+    /// Every kind, in the order they are checked
+    pub const ALL: [Self; 5] = [
+        Self::OrphanedLink,
+        Self::MissingParent,
+        Self::MissingLocation,
+        Self::OrphanedState,
+        Self::OrphanedSetting,
+    ];
+
+    /// The kind's name, as used in configuration and on the command line
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::OrphanedLink => "orphaned_link",
+            Self::MissingParent => "missing_parent",
+            Self::MissingLocation => "missing_location",
+            Self::OrphanedState => "orphaned_state",
+            Self::OrphanedSetting => "orphaned_setting",
+        }
+    }
+}
+
+impl fmt::Display for IssueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl FromStr for IssueKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.replace('-', "_");
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.as_str() == name)
+            .ok_or_else(|| {
+                let names: Vec<&str> = Self::ALL.iter().map(|kind| kind.as_str()).collect();
+                format!(
+                    "Invalid issue kind {s:?}: expected one of {}",
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+/// One dangling reference
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Issue {
+    /// What kind of reference dangles
+    pub kind: IssueKind,
+    /// The link, location, or node ID, table name, or settings
+    /// `namespace/key` holding the reference
+    pub subject: String,
+    /// What is wrong
+    pub detail: String,
+    /// Whether the run repaired it
+    pub repaired: bool,
+}
+
+/// Outcome of one check
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntegrityReport {
+    /// When the check ran
+    pub checked_at: DateTime<Utc>,
+    /// Kinds the run was allowed to repair
+    pub repair: Vec<IssueKind>,
+    /// Every dangling reference found
+    pub issues: Vec<Issue>,
+    /// Number of issues found per kind
+    pub counts: BTreeMap<IssueKind, usize>,
+    /// Number of issues repaired
+    pub repaired: usize,
+}
+
+impl IntegrityReport {
+    fn push(&mut self, kind: IssueKind, subject: String, detail: String) -> bool {
+        let repair = self.repair.contains(&kind);
+        *self.counts.entry(kind).or_default() += 1;
+        self.issues.push(Issue {
+            kind,
+            subject,
+            detail,
+            repaired: false,
+        });
+        repair
+    }
+
+    fn mark_repaired(&mut self) {
+        if let Some(issue) = self.issues.last_mut() {
+            issue.repaired = true;
+            self.repaired += 1;
+        }
+    }
+}
+
+/// Looks for dangling references and repairs those of the `repair` kinds
+///
+/// The report is kept as the last report, whether or not anything was found.
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read, a repair fails, or the
+/// report cannot be stored. Repairs made before the failure stay made.
+pub async fn check(
+    datastore: &dyn DataStore,
+    repair: &[IssueKind],
+    now: DateTime<Utc>,
+) -> DataStoreResult<IntegrityReport> {
+    let query_options = QueryOptions::default();
+    let locations = datastore.list_locations(&query_options).await?.items;
+    let nodes = datastore.list_nodes(&query_options).await?.items;
+    let links = datastore.list_links(&query_options).await?.items;
+    let location_ids: HashSet<Uuid> = locations.iter().map(|location| location.id).collect();
+    let node_ids: HashSet<Uuid> = nodes.iter().map(|node| node.id).collect();
+
+    let mut report = IntegrityReport {
+        checked_at: now,
+        repair: repair.to_vec(),
+        issues: Vec::new(),
+        counts: BTreeMap::new(),
+        repaired: 0,
+    };
+
+    for link in &links {
+        let missing: Vec<Uuid> = std::iter::once(link.source_node_id)
+            .chain(link.dest_node_id)
+            .filter(|id| !node_ids.contains(id))
+            .collect();
+        if missing.is_empty() {
+            continue;
+        }
+        let detail = format!(
+            "link {} connects missing node {}",
+            link.name,
+            join(&missing)
+        );
+        if report.push(IssueKind::OrphanedLink, link.id.to_string(), detail) {
+            datastore.delete_link(&link.id).await?;
+            report.mark_repaired();
+        }
+    }
+
+    for location in &locations {
+        let Some(parent_id) = location.parent_id.filter(|id| !location_ids.contains(id)) else {
+            continue;
+        };
+        let detail = format!("location {} has missing parent {parent_id}", location.path);
+        if report.push(IssueKind::MissingParent, location.id.to_string(), detail) {
+            detach(datastore, location, &locations).await?;
+            report.mark_repaired();
+        }
+    }
+
+    for node in &nodes {
+        let Some(location_id) = node.location_id.filter(|id| !location_ids.contains(id)) else {
+            continue;
+        };
+        let detail = format!("node {} is in missing location {location_id}", node.name);
+        if report.push(IssueKind::MissingLocation, node.id.to_string(), detail) {
+            let mut node = node.clone();
+            node.location_id = None;
+            datastore.update_node(&node).await?;
+            report.mark_repaired();
+        }
+    }
+
+    check_state(datastore, &mut report).await?;
+    check_settings(datastore, &node_ids, &mut report).await?;
+
+    save_report(datastore, &report).await?;
+    Ok(report)
+}
+
+/// Makes `location` a root and rewrites the paths of its subtree
+async fn detach(
+    datastore: &dyn DataStore,
+    location: &Location,
+    locations: &[Location],
+) -> DataStoreResult<()> {
+    let mut root = location.clone();
+    root.parent_id = None;
+    root.path.clone_from(&root.name);
+    datastore.update_location(&root).await?;
+
+    let mut parents = vec![root];
+    while let Some(parent) = parents.pop() {
+        for child in locations
+            .iter()
+            .filter(|child| child.parent_id == Some(parent.id))
+        {
+            let mut child = child.clone();
+            child.path = format!("{}/{}", parent.path, child.name);
+            datastore.update_location(&child).await?;
+            parents.push(child);
+        }
+    }
+    Ok(())
+}
+
+/// Reports orphaned rows per table; a store that cannot count them is skipped
+async fn check_state(
+    datastore: &dyn DataStore,
+    report: &mut IntegrityReport,
+) -> DataStoreResult<()> {
+    let counts = match datastore.count_orphaned_records().await {
+        Ok(counts) => counts,
+        Err(DataStoreError::UnsupportedOperation { .. }) => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    let tables: BTreeMap<String, usize> =
+        counts.into_iter().filter(|(_, rows)| *rows > 0).collect();
+    for (table, rows) in &tables {
+        let detail = format!("{rows} {table} rows refer to deleted nodes");
+        report.push(IssueKind::OrphanedState, table.clone(), detail);
+    }
+    // One cleanup removes the rows of every table
+    if !tables.is_empty() && report.repair.contains(&IssueKind::OrphanedState) {
+        datastore.cleanup_orphaned_records().await?;
+        for issue in report
+            .issues
+            .iter_mut()
+            .filter(|issue| issue.kind == IssueKind::OrphanedState)
+        {
+            issue.repaired = true;
+        }
+        report.repaired += tables.len();
+    }
+    Ok(())
+}
+
+/// Reports settings documents whose key names a deleted node
+async fn check_settings(
+    datastore: &dyn DataStore,
+    node_ids: &HashSet<Uuid>,
+    report: &mut IntegrityReport,
+) -> DataStoreResult<()> {
+    for namespace in NODE_KEYED_NAMESPACES {
+        let settings = match datastore.list_settings(namespace).await {
+            Ok(settings) => settings,
+            Err(DataStoreError::UnsupportedOperation { .. }) => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        for (key, _) in settings {
+            let Some(node_id) = key
+                .split('/')
+                .next()
+                .and_then(|id| id.parse::<Uuid>().ok())
+                .filter(|id| !node_ids.contains(id))
+            else {
+                continue;
+            };
+            let detail = format!("{namespace} entry kept for deleted node {node_id}");
+            if report.push(
+                IssueKind::OrphanedSetting,
+                format!("{namespace}/{key}"),
+                detail,
+            ) {
+                datastore.delete_setting(namespace, &key).await?;
+                report.mark_repaired();
+            }
+        }
+    }
+    Ok(())
+}
+
+fn join(ids: &[Uuid]) -> String {
+    ids.iter()
+        .map(Uuid::to_string)
+        .collect::<Vec<_>>()
+        .join(" and ")
+}
+
+/// Returns the report of the last check, if any has run
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or the report is malformed.
+pub async fn last_report(datastore: &dyn DataStore) -> DataStoreResult<Option<IntegrityReport>> {
+    datastore
+        .get_setting(NAMESPACE, REPORT_KEY)
+        .await?
+        .map(|value| {
+            serde_json::from_value(value).map_err(|e| DataStoreError::InternalError {
+                message: format!("stored integrity report: {e}"),
+            })
+        })
+        .transpose()
+}
+
+async fn save_report(datastore: &dyn DataStore, report: &IntegrityReport) -> DataStoreResult<()> {
+    let value = serde_json::to_value(report).map_err(|e| DataStoreError::InternalError {
+        message: format!("integrity report: {e}"),
+    })?;
+    match datastore.put_setting(NAMESPACE, REPORT_KEY, &value).await {
+        Ok(()) | Err(DataStoreError::UnsupportedOperation { .. }) => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,144 @@
+use super::*;
+use crate::datastore::sqlite::SqliteStore;
+use crate::datastore::sqlite::tests::setup;
+use crate::models::{DeviceRole, Link, Node, Vendor};
+use sea_orm::ConnectionTrait;
+use serde_json::json;
+
+async fn migrated_store() -> SqliteStore {
+    let store = setup::migrated_store().await;
+    // Dangling references only exist in databases written without foreign
+    // key enforcement
+    store
+        .connection()
+        .execute_unprepared("PRAGMA foreign_keys = OFF")
+        .await
+        .unwrap();
+    store
+}
+
+fn node(name: &str) -> Node {
+    Node::new(
+        name.to_string(),
+        "example.com".to_string(),
+        Vendor::Juniper,
+        DeviceRole::Router,
+    )
+}
+
+/// A site with a node, plus one dangling reference of every kind but
+/// orphaned state; returns the site's orphaned child location
+async fn seed(store: &SqliteStore) -> Location {
+    let ghost = Uuid::new_v4();
+    let site = Location::new_root("dc-west".to_string(), "site".to_string());
+    store.create_location(&site).await.unwrap();
+    let mut orphan = Location::new_child("Hall A".to_string(), "room".to_string(), "gone");
+    orphan.parent_id = Some(Uuid::new_v4());
+    store.create_location(&orphan).await.unwrap();
+    let mut rack = Location::new_child("Rack 1".to_string(), "rack".to_string(), &orphan.path);
+    rack.parent_id = Some(orphan.id);
+    store.create_location(&rack).await.unwrap();
+
+    let mut placed = node("edge-1");
+    placed.location_id = Some(site.id);
+    store.create_node(&placed).await.unwrap();
+    let mut unplaced = node("edge-2");
+    unplaced.location_id = Some(Uuid::new_v4());
+    store.create_node(&unplaced).await.unwrap();
+
+    let link = Link::new(
+        "edge-1-ghost".to_string(),
+        placed.id,
+        "xe-0/0/0".to_string(),
+        ghost,
+        "xe-0/0/0".to_string(),
+    );
+    store.create_link(&link).await.unwrap();
+    store
+        .put_setting(
+            "interface_vlans",
+            &ghost.to_string(),
+            &json!({ "ge-0/0/0": { "mode": "access", "vlan": 10 } }),
+        )
+        .await
+        .unwrap();
+    store
+        .put_setting(
+            "interface_vlans",
+            &placed.id.to_string(),
+            &json!({ "ge-0/0/0": { "mode": "access", "vlan": 10 } }),
+        )
+        .await
+        .unwrap();
+    orphan
+}
+
+#[tokio::test]
+async fn test_check_reports_dangling_references_without_repairing() {
+    let store = migrated_store().await;
+    seed(&store).await;
+
+    let report = check(&store, &[], Utc::now()).await.unwrap();
+
+    let kinds: Vec<IssueKind> = report.issues.iter().map(|issue| issue.kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            IssueKind::OrphanedLink,
+            IssueKind::MissingParent,
+            IssueKind::MissingLocation,
+            IssueKind::OrphanedSetting,
+        ]
+    );
+    assert_eq!(report.repaired, 0);
+    assert_eq!(
+        store
+            .list_links(&QueryOptions::default())
+            .await
+            .unwrap()
+            .items
+            .len(),
+        1
+    );
+    assert_eq!(last_report(&store).await.unwrap(), Some(report));
+}
+
+#[tokio::test]
+async fn test_check_repairs_requested_kinds() {
+    let store = migrated_store().await;
+    let orphan = seed(&store).await;
+
+    let report = check(&store, &IssueKind::ALL, Utc::now()).await.unwrap();
+    assert_eq!(report.repaired, 4);
+    assert!(report.issues.iter().all(|issue| issue.repaired));
+
+    let hall = store.get_location_required(&orphan.id).await.unwrap();
+    assert_eq!(hall.parent_id, None);
+    assert_eq!(hall.path, "Hall A");
+    let paths: Vec<String> = store
+        .list_locations(&QueryOptions::default())
+        .await
+        .unwrap()
+        .items
+        .into_iter()
+        .map(|location| location.path)
+        .collect();
+    assert!(paths.contains(&"Hall A/Rack 1".to_string()));
+    assert_eq!(
+        store.list_settings("interface_vlans").await.unwrap().len(),
+        1
+    );
+
+    let again = check(&store, &IssueKind::ALL, Utc::now()).await.unwrap();
+    assert!(again.issues.is_empty());
+}
+
+#[test]
+fn test_issue_kind_parses_names() {
+    assert_eq!(
+        "orphaned-link".parse::<IssueKind>(),
+        Ok(IssueKind::OrphanedLink)
+    );
+    assert_eq!(IssueKind::MissingParent.to_string(), "missing_parent");
+    assert!("broken".parse::<IssueKind>().is_err());
+}
@@ -24,27 +24,93 @@
 //!
 //! The library is organized into several modules:
 //!
+//! - [`api_keys`] - Scoped API keys for the server's bearer authentication
+//! - [`build_info`] - Version, commit, and features of this build
+//! - [`change_log`] - Append-only change log and projections replayed from it
+//! - [`collectors`] - Interface, BGP, and LLDP state from Arista eAPI and RESTCONF
+//! - [`config_changes`] - Collected configuration snapshots and unauthorized change alerts
+//! - [`measurement`] - Active link latency, jitter, and loss measurement
 //! - [`models`] - Core data models (Node, Link, Location)
+//! - [`node_defaults`] - Default `custom_data` for new nodes by role or vendor
+//! - [`notes`] - Markdown notes and file attachments on nodes, links, and locations
+//! - [`onboarding`] - Node fields from the facts a live device reports over SNMP
+//! - [`ownership`] - Configuration sections μNet manages on shared nodes
 //! - [`datastore`] - Storage abstraction layer with multiple backends
+//! - [`enrichment`] - Plugins that enrich derived state after each poll
 //! - [`error`] - Unified error types and handling
+//! - [`exports`] - Scheduled inventory exports to local, S3, GCS, and SFTP destinations
+//! - [`golden`] - Golden configuration assignment and conformance scoring
+//! - [`groups`] - Dynamic node groups defined by saved filters
+//! - [`hardware`] - Chassis, module, transceiver, and PSU inventory from ENTITY-MIB
 //! - [`config`] - Configuration management (Milestone 1.3.3)
 //! - [`policy`] - Policy engine (Milestone 3)
+//! - [`reports`] - Fleet-wide reports such as firmware compliance
+//! - [`retention`] - Per-class data retention and the pruning that enforces it
+//! - [`search`] - Ranked search across nodes, links, locations, policies, and configs
+//! - [`secrets`] - Field-level encryption of sensitive `custom_data` values
+//! - [`seed`] - Deterministic synthetic inventory generation
 //! - [`snmp`] - SNMP integration (Milestone 2)
-//! - [`template`] - Template rendering (Milestone 4)
+//! - [`template`] - Fleet-wide template rendering, change previews, and fixture tests
+//! - [`topology`] - Link endpoint verification and topology audits
+//! - [`vlan`] - VLAN definitions, interface membership, and link consistency checks
+//! - [`webhooks`] - Outbound webhook subscriptions and signed event delivery
+//!
+//! # Features
+//!
+//! Everything is enabled by default. Tools that only need the models and the
+//! `DataStore` abstraction can turn off default features and pick from:
+//!
+//! - `snmp` - SNMP sessions, the polling scheduler, and `SnmpProber`
+//!   (models, OIDs, and OID profiles are always available)
+//! - `secrets` - HMAC signing of webhook requests, `custom_data` field encryption,
+//!   API key hashing, and signing of object store export uploads
+//! - `policy` - Policy DSL parser, policy file loader, and `policy_integration`
+//!   (the AST and evaluator are always available)
+//! - `sqlite` - The `SQLite` datastore backend and its `entities`
+//! - `sqlcipher` - `SQLCipher` encryption for the `SQLite` backend
 
 #![warn(missing_docs)]
 
 // Public modules
+pub mod api_keys;
+pub mod build_info;
+pub mod change_log;
+pub mod collectors;
 pub mod config;
+pub mod config_changes;
 pub mod datastore;
+pub mod enrichment;
+#[cfg(feature = "sqlite")]
 pub mod entities;
 pub mod error;
+pub mod exports;
+pub mod golden;
+pub mod groups;
+pub mod hardware;
+pub mod integrity;
+pub mod location_status;
 pub mod logging;
+pub mod measurement;
 pub mod models;
+pub mod node_defaults;
+pub mod notes;
+pub mod onboarding;
+pub mod ownership;
 pub mod policy;
+#[cfg(feature = "policy")]
 pub mod policy_integration;
+pub mod provenance;
+pub mod reports;
+pub mod retention;
+pub mod search;
+pub mod secrets;
+pub mod seed;
+pub mod slug;
 pub mod snmp;
 pub mod template;
+pub mod topology;
+pub mod vlan;
+pub mod webhooks;
 
 // Re-exports for convenience
 pub use error::{Error, Result};
@@ -62,7 +128,7 @@ pub mod prelude {
     // Data models
     pub use crate::models::{
         DeviceRole, Lifecycle, Link, LinkBuilder, Location, LocationBuilder, Node, NodeBuilder,
-        Vendor,
+        ProvisioningState, Vendor,
     };
 
     // Derived state models
@@ -102,12 +168,16 @@ pub mod prelude {
 
     // SNMP types
     pub use crate::snmp::{
-        OidMap, PollingConfig, PollingHandle, PollingResult, PollingScheduler, PollingTask,
-        SessionConfig, SnmpClient, SnmpClientConfig, SnmpCredentials, SnmpError, SnmpResult,
-        SnmpValue, StandardOid, VendorOid,
+        OidMap, SessionConfig, SnmpClientConfig, SnmpCredentials, SnmpError, SnmpResult, SnmpValue,
+        StandardOid, VendorOid,
+    };
+    #[cfg(feature = "snmp")]
+    pub use crate::snmp::{
+        PollingConfig, PollingHandle, PollingResult, PollingScheduler, PollingTask, SnmpClient,
     };
 
     // Policy integration types
+    #[cfg(feature = "policy")]
     pub use crate::policy_integration::{
         DefaultPolicyEvaluationEngine, PolicyEvaluationEngine, PolicyService,
     };
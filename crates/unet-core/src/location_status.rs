@@ -0,0 +1,259 @@
+//! Site-level rollup of derived node state
+//!
+//! Summarizes the polled state of every node in a location and the
+//! locations below it: reachability, active alarms, average CPU and memory
+//! utilization, and the most utilized interface. Node statuses are read
+//! with one [`DataStore::get_node_statuses`] call, so a dashboard can show a
+//! site without a request per node.
+
+use crate::datastore::{DataStore, DataStoreResult, QueryOptions};
+use crate::enrichment::{AnomalyReport, list_enriched_states};
+use crate::models::derived::{FanStatus, NodeStatus, PowerSupplyStatus};
+use crate::models::{Location, Node};
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// Enrichment key holding anomaly detection output
+const ANOMALY_ENRICHMENT: &str = "anomaly_detection";
+
+/// A condition on a node that needs attention
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocationAlarm {
+    /// Node raising the alarm
+    pub node_id: Uuid,
+    /// Node name
+    pub node: String,
+    /// What is wrong
+    pub message: String,
+}
+
+/// The most utilized interface in a location
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InterfaceUtilization {
+    /// Node owning the interface
+    pub node_id: Uuid,
+    /// Node name
+    pub node: String,
+    /// Interface name
+    pub interface: String,
+    /// Higher of input and output utilization, in percent
+    pub utilization: f64,
+}
+
+/// Rolled-up state of a location subtree
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LocationStatus {
+    /// Location the rollup starts from
+    pub location_id: Uuid,
+    /// Location path
+    pub path: String,
+    /// Locations in the subtree, including this one
+    pub locations: usize,
+    /// Nodes in the subtree
+    pub nodes: usize,
+    /// Nodes with polled state
+    pub polled: usize,
+    /// Polled nodes that answered their last poll
+    pub reachable: usize,
+    /// Polled nodes that did not answer their last poll
+    pub unreachable: usize,
+    /// Average CPU utilization over nodes reporting it, in percent
+    pub avg_cpu_utilization: Option<f64>,
+    /// Average memory utilization over nodes reporting it, in percent
+    pub avg_memory_utilization: Option<f64>,
+    /// Interface with the highest utilization, if any reports one
+    pub worst_interface: Option<InterfaceUtilization>,
+    /// Active alarms, grouped by node
+    pub alarms: Vec<LocationAlarm>,
+}
+
+/// Returns the alarms raised by one node's status
+fn node_alarms(status: &NodeStatus) -> Vec<String> {
+    if !status.reachable {
+        let error = status.last_error.as_deref().unwrap_or("no response");
+        return vec![format!(
+            "unreachable after {} failed polls: {error}",
+            status.consecutive_failures
+        )];
+    }
+
+    let mut alarms = Vec::new();
+    if let Some(environmental) = &status.environmental {
+        for sensor in &environmental.temperatures {
+            if sensor
+                .critical_threshold
+                .is_some_and(|critical| sensor.temperature >= critical)
+            {
+                alarms.push(format!("{} above critical temperature", sensor.name));
+            }
+        }
+        for fan in environmental
+            .fans
+            .iter()
+            .filter(|fan| fan.status == FanStatus::Failed)
+        {
+            alarms.push(format!("{} failed", fan.name));
+        }
+        for supply in environmental
+            .power_supplies
+            .iter()
+            .filter(|supply| supply.status == PowerSupplyStatus::Failed)
+        {
+            alarms.push(format!("{} failed", supply.name));
+        }
+    }
+    let anomalies = status
+        .enrichments
+        .get(ANOMALY_ENRICHMENT)
+        .and_then(|report| serde_json::from_value::<AnomalyReport>(report.clone()).ok())
+        .map(|report| report.anomalies)
+        .unwrap_or_default();
+    for anomaly in anomalies {
+        alarms.push(format!(
+            "{} anomaly: {:.1} against a baseline of {:.1}",
+            anomaly.metric.as_str(),
+            anomaly.value,
+            anomaly.expected
+        ));
+    }
+    alarms
+}
+
+fn average(values: &[u8]) -> Option<f64> {
+    let count = values.len().to_f64().filter(|count| *count > 0.0)?;
+    Some(values.iter().map(|value| f64::from(*value)).sum::<f64>() / count)
+}
+
+/// Summarizes the statuses of the nodes in a location subtree
+///
+/// `locations` is the number of locations in the subtree and `nodes` the
+/// nodes in it; statuses of other nodes are ignored.
+#[must_use]
+pub fn summarize(
+    location: &Location,
+    locations: usize,
+    nodes: &[Node],
+    statuses: &[NodeStatus],
+) -> LocationStatus {
+    let names: HashMap<Uuid, &str> = nodes
+        .iter()
+        .map(|node| (node.id, node.name.as_str()))
+        .collect();
+    let mut summary = LocationStatus {
+        location_id: location.id,
+        path: location.path.clone(),
+        locations,
+        nodes: nodes.len(),
+        polled: 0,
+        reachable: 0,
+        unreachable: 0,
+        avg_cpu_utilization: None,
+        avg_memory_utilization: None,
+        worst_interface: None,
+        alarms: Vec::new(),
+    };
+    let mut cpu = Vec::new();
+    let mut memory = Vec::new();
+
+    for status in statuses {
+        let Some(node) = names.get(&status.node_id) else {
+            continue;
+        };
+        summary.polled += 1;
+        if status.reachable {
+            summary.reachable += 1;
+        } else {
+            summary.unreachable += 1;
+        }
+        summary.alarms.extend(
+            node_alarms(status)
+                .into_iter()
+                .map(|message| LocationAlarm {
+                    node_id: status.node_id,
+                    node: (*node).to_string(),
+                    message,
+                }),
+        );
+        if !status.reachable {
+            continue;
+        }
+
+        if let Some(performance) = &status.performance {
+            cpu.extend(performance.cpu_utilization);
+            memory.extend(performance.memory_utilization);
+        }
+        for interface in &status.interfaces {
+            let utilization = [&interface.input_stats, &interface.output_stats]
+                .into_iter()
+                .filter_map(|stats| stats.rate.and_then(|rate| rate.utilization))
+                .reduce(f64::max);
+            let Some(utilization) = utilization else {
+                continue;
+            };
+            if summary
+                .worst_interface
+                .as_ref()
+                .is_none_or(|worst| utilization > worst.utilization)
+            {
+                summary.worst_interface = Some(InterfaceUtilization {
+                    node_id: status.node_id,
+                    node: (*node).to_string(),
+                    interface: interface.name.clone(),
+                    utilization,
+                });
+            }
+        }
+    }
+
+    summary.avg_cpu_utilization = average(&cpu);
+    summary.avg_memory_utilization = average(&memory);
+    summary
+}
+
+/// Loads a location subtree and its nodes' statuses and summarizes them
+///
+/// Each status carries the stored output of the enrichment plugins from its
+/// latest poll.
+///
+/// # Errors
+/// Returns a not-found error if the location does not exist, or an error if
+/// locations, nodes, or statuses cannot be read.
+pub async fn location_status(
+    datastore: &dyn DataStore,
+    location_id: Uuid,
+) -> DataStoreResult<LocationStatus> {
+    let location = datastore.get_location_required(&location_id).await?;
+    let options = QueryOptions::default();
+    let all_locations = datastore.list_locations(&options).await?.items;
+    let subtree: HashSet<Uuid> = std::iter::once(location.id)
+        .chain(
+            location
+                .get_descendants(&all_locations)
+                .into_iter()
+                .map(|descendant| descendant.id),
+        )
+        .collect();
+
+    let nodes: Vec<Node> = datastore
+        .list_nodes(&options)
+        .await?
+        .items
+        .into_iter()
+        .filter(|node| node.location_id.is_some_and(|id| subtree.contains(&id)))
+        .collect();
+    let node_ids: Vec<Uuid> = nodes.iter().map(|node| node.id).collect();
+    let mut statuses = datastore.get_node_statuses(&node_ids).await?;
+    let enriched = list_enriched_states(datastore).await?;
+    for status in &mut statuses {
+        if let Some(state) = enriched.get(&status.node_id) {
+            state.apply_to(status);
+        }
+    }
+
+    Ok(summarize(&location, subtree.len(), &nodes, &statuses))
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,133 @@
+use super::*;
+use crate::models::derived::{
+    EnvironmentalMetrics, FanSensor, InterfaceAdminStatus, InterfaceOperStatus, InterfaceStats,
+    InterfaceStatus, PerformanceMetrics, TrafficRate,
+};
+use crate::models::{DeviceRole, Vendor};
+use serde_json::json;
+
+fn node(name: &str) -> Node {
+    Node::new(
+        name.to_string(),
+        "example.com".to_string(),
+        Vendor::Arista,
+        DeviceRole::Switch,
+    )
+}
+
+fn polled(node: &Node, cpu: u8, memory: u8) -> NodeStatus {
+    let mut status = NodeStatus::new(node.id);
+    status.reachable = true;
+    status.performance = Some(PerformanceMetrics {
+        cpu_utilization: Some(cpu),
+        memory_utilization: Some(memory),
+        total_memory: None,
+        used_memory: None,
+        load_average: None,
+    });
+    status
+}
+
+fn interface(name: &str, utilization: f64) -> InterfaceStatus {
+    InterfaceStatus {
+        index: 1,
+        name: name.to_string(),
+        interface_type: 6,
+        mtu: None,
+        speed: Some(1_000_000_000),
+        physical_address: None,
+        admin_status: InterfaceAdminStatus::Up,
+        oper_status: InterfaceOperStatus::Up,
+        last_change: None,
+        input_stats: InterfaceStats::default(),
+        output_stats: InterfaceStats {
+            rate: Some(TrafficRate {
+                bps: utilization * 10_000_000.0,
+                pps: 0.0,
+                utilization: Some(utilization),
+                interval_seconds: 60.0,
+            }),
+            ..InterfaceStats::default()
+        },
+    }
+}
+
+#[test]
+fn test_summarize_rolls_up_reachability_metrics_and_interfaces() {
+    let site = Location::new_root("dc1".to_string(), "site".to_string());
+    let (a, b, c) = (node("leaf-1"), node("leaf-2"), node("leaf-3"));
+    let other = node("elsewhere");
+
+    let mut first = polled(&a, 40, 50);
+    first.interfaces = vec![interface("Ethernet1", 35.0), interface("Ethernet2", 92.5)];
+    let second = polled(&b, 60, 70);
+    let mut down = NodeStatus::new(c.id);
+    down.last_error = Some("timeout".to_string());
+    down.consecutive_failures = 3;
+    let mut ignored = polled(&other, 100, 100);
+    ignored.interfaces = vec![interface("Ethernet9", 100.0)];
+
+    let summary = summarize(
+        &site,
+        3,
+        &[a.clone(), b, c.clone()],
+        &[first, second, down, ignored],
+    );
+
+    assert_eq!(summary.locations, 3);
+    assert_eq!(summary.nodes, 3);
+    assert_eq!(summary.polled, 3);
+    assert_eq!((summary.reachable, summary.unreachable), (2, 1));
+    assert!((summary.avg_cpu_utilization.unwrap() - 50.0).abs() < f64::EPSILON);
+    assert!((summary.avg_memory_utilization.unwrap() - 60.0).abs() < f64::EPSILON);
+    let worst = summary.worst_interface.unwrap();
+    assert_eq!(
+        (worst.node_id, worst.interface.as_str()),
+        (a.id, "Ethernet2")
+    );
+    assert_eq!(summary.alarms.len(), 1);
+    assert_eq!(summary.alarms[0].node_id, c.id);
+    assert!(summary.alarms[0].message.contains("timeout"));
+}
+
+#[test]
+fn test_summarize_reports_environment_and_anomaly_alarms() {
+    let site = Location::new_root("dc1".to_string(), "site".to_string());
+    let leaf = node("leaf-1");
+    let mut status = polled(&leaf, 10, 10);
+    status.environmental = Some(EnvironmentalMetrics {
+        temperatures: Vec::new(),
+        fans: vec![FanSensor {
+            name: "Fan 2".to_string(),
+            speed_rpm: None,
+            status: FanStatus::Failed,
+        }],
+        power_supplies: Vec::new(),
+    });
+    status.enrichments.insert(
+        ANOMALY_ENRICHMENT.to_string(),
+        json!({"anomalies": [{
+            "metric": "cpu",
+            "value": 95.0,
+            "expected": 12.0,
+            "deviation": 8.3,
+            "threshold": 3.0
+        }]}),
+    );
+
+    let summary = summarize(&site, 1, &[leaf], &[status]);
+
+    let messages: Vec<&str> = summary
+        .alarms
+        .iter()
+        .map(|alarm| alarm.message.as_str())
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            "Fan 2 failed",
+            "cpu anomaly: 95.0 against a baseline of 12.0"
+        ]
+    );
+    assert!(summary.worst_interface.is_none());
+}
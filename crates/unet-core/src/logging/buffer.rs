@@ -0,0 +1,325 @@
+//! In-memory buffer of recent log records
+//!
+//! Every event the tracing subscriber lets through is also kept in a
+//! process-wide ring buffer holding the last [`LOG_BUFFER_CAPACITY`]
+//! records, so the server can hand its recent logs to `unet logs tail`
+//! without anyone needing shell access to the host. Records are numbered in
+//! order; a client polls for the records after the last one it has seen.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::VecDeque;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Mutex, PoisonError};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+/// Number of records kept before the oldest are dropped
+pub const LOG_BUFFER_CAPACITY: usize = 2000;
+
+static LOG_BUFFER: LogBuffer = LogBuffer::new(LOG_BUFFER_CAPACITY);
+
+/// The process-wide buffer fed by [`init_tracing`](super::init_tracing)
+#[must_use]
+pub fn log_buffer() -> &'static LogBuffer {
+    &LOG_BUFFER
+}
+
+/// Severity of a log record, from least to most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    /// Very detailed tracing
+    Trace,
+    /// Debugging detail
+    Debug,
+    /// Normal operation
+    Info,
+    /// Something unexpected that was handled
+    Warn,
+    /// A failure
+    Error,
+}
+
+impl From<tracing::Level> for LogLevel {
+    fn from(level: tracing::Level) -> Self {
+        match level {
+            tracing::Level::TRACE => Self::Trace,
+            tracing::Level::DEBUG => Self::Debug,
+            tracing::Level::INFO => Self::Info,
+            tracing::Level::WARN => Self::Warn,
+            tracing::Level::ERROR => Self::Error,
+        }
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = match self {
+            Self::Trace => "TRACE",
+            Self::Debug => "DEBUG",
+            Self::Info => "INFO",
+            Self::Warn => "WARN",
+            Self::Error => "ERROR",
+        };
+        f.pad(level)
+    }
+}
+
+impl FromStr for LogLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "trace" => Ok(Self::Trace),
+            "debug" => Ok(Self::Debug),
+            "info" => Ok(Self::Info),
+            "warn" | "warning" => Ok(Self::Warn),
+            "error" => Ok(Self::Error),
+            _ => Err(format!("Invalid log level: {s}")),
+        }
+    }
+}
+
+/// One logged event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogRecord {
+    /// Position in the buffer's sequence, increasing by one per record
+    pub seq: u64,
+    /// When the event was logged
+    pub timestamp: DateTime<Utc>,
+    /// Severity
+    pub level: LogLevel,
+    /// Module that logged the event, e.g. `unet_core::snmp::poller`
+    pub target: String,
+    /// The event's message
+    pub message: String,
+    /// The event's other fields, such as `node_id`
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub fields: Map<String, Value>,
+}
+
+/// Which records to return from the buffer
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogFilter {
+    /// Only records numbered after this one
+    pub after: Option<u64>,
+    /// Only records at least this severe
+    pub level: Option<LogLevel>,
+    /// Only records from this component: a module in the record's target,
+    /// such as `poller` for `unet_core::snmp::poller`, or the whole target
+    pub component: Option<String>,
+    /// Only records whose `node_id` or `node` field is this value
+    pub node: Option<String>,
+    /// At most this many records, the most recent ones
+    pub limit: Option<usize>,
+}
+
+impl LogFilter {
+    /// Whether a record passes the filter, ignoring `limit`
+    #[must_use]
+    pub fn matches(&self, record: &LogRecord) -> bool {
+        self.after.is_none_or(|after| record.seq > after)
+            && self.level.is_none_or(|level| record.level >= level)
+            && self.component.as_deref().is_none_or(|component| {
+                record.target == component || record.target.split("::").any(|m| m == component)
+            })
+            && self.node.as_deref().is_none_or(|node| {
+                ["node_id", "node"].iter().any(|key| {
+                    record.fields.get(*key).is_some_and(|value| match value {
+                        Value::String(value) => value == node,
+                        other => other.to_string() == node,
+                    })
+                })
+            })
+    }
+}
+
+/// Ring buffer of the most recent log records
+#[derive(Debug)]
+pub struct LogBuffer {
+    capacity: usize,
+    inner: Mutex<Records>,
+}
+
+#[derive(Debug)]
+struct Records {
+    records: VecDeque<LogRecord>,
+    next_seq: u64,
+}
+
+impl LogBuffer {
+    /// Creates an empty buffer keeping up to `capacity` records
+    #[must_use]
+    pub const fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            inner: Mutex::new(Records {
+                records: VecDeque::new(),
+                next_seq: 1,
+            }),
+        }
+    }
+
+    /// Appends a record, numbering it and dropping the oldest record when
+    /// the buffer is full
+    pub fn push(&self, mut record: LogRecord) {
+        let mut inner = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        record.seq = inner.next_seq;
+        inner.next_seq += 1;
+        if inner.records.len() >= self.capacity {
+            inner.records.pop_front();
+        }
+        if self.capacity > 0 {
+            inner.records.push_back(record);
+        }
+    }
+
+    /// Records passing the filter, oldest first
+    #[must_use]
+    pub fn query(&self, filter: &LogFilter) -> Vec<LogRecord> {
+        let inner = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut records: Vec<LogRecord> = inner
+            .records
+            .iter()
+            .filter(|record| filter.matches(record))
+            .cloned()
+            .collect();
+        if let Some(limit) = filter.limit {
+            records.drain(..records.len().saturating_sub(limit));
+        }
+        records
+    }
+
+    /// Layer feeding this buffer from a tracing subscriber
+    #[must_use]
+    pub const fn layer(&'static self) -> LogBufferLayer {
+        LogBufferLayer { buffer: self }
+    }
+}
+
+/// Tracing layer copying events into a [`LogBuffer`]
+#[derive(Debug, Clone, Copy)]
+pub struct LogBufferLayer {
+    buffer: &'static LogBuffer,
+}
+
+impl<S: Subscriber> Layer<S> for LogBufferLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        self.buffer.push(LogRecord {
+            seq: 0,
+            timestamp: Utc::now(),
+            level: (*metadata.level()).into(),
+            target: metadata.target().to_string(),
+            message: visitor.message,
+            fields: visitor.fields,
+        });
+    }
+}
+
+#[derive(Default)]
+struct FieldVisitor {
+    message: String,
+    fields: Map<String, Value>,
+}
+
+impl FieldVisitor {
+    fn insert(&mut self, field: &Field, value: Value) {
+        if field.name() == "message" {
+            self.message = match value {
+                Value::String(message) => message,
+                other => other.to_string(),
+            };
+        } else {
+            self.fields.insert(field.name().to_string(), value);
+        }
+    }
+}
+
+impl Visit for FieldVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.insert(field, Value::from(format!("{value:?}")));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.insert(field, Value::from(value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    static BUFFER: LogBuffer = LogBuffer::new(3);
+
+    #[test]
+    fn test_layer_records_events_and_filter_selects_them() {
+        let subscriber = tracing_subscriber::registry().with(BUFFER.layer());
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(target: "unet_server::background::webhook_task", "delivered");
+            tracing::warn!(
+                target: "unet_core::snmp::poller",
+                node_id = "n1",
+                attempts = 3,
+                "poll timed out"
+            );
+            tracing::error!(target: "unet_core::snmp::poller", node = "edge-1", "poll failed");
+            tracing::debug!(target: "unet_core::snmp::poller", "poll started");
+        });
+
+        let all = BUFFER.query(&LogFilter::default());
+        let seqs: Vec<u64> = all.iter().map(|record| record.seq).collect();
+        assert_eq!(seqs, [2, 3, 4], "oldest record is dropped at capacity");
+        assert_eq!(all[0].message, "poll timed out");
+        assert_eq!(all[0].fields["attempts"], 3);
+
+        let filter = LogFilter {
+            level: Some(LogLevel::Warn),
+            component: Some("poller".to_string()),
+            ..LogFilter::default()
+        };
+        assert_eq!(BUFFER.query(&filter).len(), 2);
+        let filter = LogFilter {
+            node: Some("edge-1".to_string()),
+            ..LogFilter::default()
+        };
+        assert_eq!(BUFFER.query(&filter)[0].level, LogLevel::Error);
+        let filter = LogFilter {
+            after: Some(2),
+            limit: Some(1),
+            ..LogFilter::default()
+        };
+        assert_eq!(BUFFER.query(&filter)[0].seq, 4);
+    }
+
+    #[test]
+    fn test_log_level_parses_and_orders_by_severity() {
+        assert_eq!("WARN".parse::<LogLevel>(), Ok(LogLevel::Warn));
+        assert!("loud".parse::<LogLevel>().is_err());
+        assert!(LogLevel::Error > LogLevel::Warn);
+        assert_eq!(LogLevel::from(tracing::Level::DEBUG), LogLevel::Debug);
+    }
+}
@@ -3,13 +3,21 @@
 //! This module provides structured logging and tracing capabilities using the `tracing`
 //! ecosystem with support for multiple output formats, log levels, and file output.
 
+use super::buffer::log_buffer;
 use crate::config::LoggingConfig;
 use crate::error::{Error, Result};
 use std::path::Path;
 use tracing_subscriber::EnvFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
 
 /// Initializes the global tracing subscriber based on configuration
 ///
+/// Console output goes to stderr, leaving stdout to command results.
+///
+/// Events that pass the level filter are also kept in the in-memory
+/// [`log_buffer`](super::log_buffer).
+///
 /// # Errors
 /// Returns an error if the log level is invalid or if tracing initialization fails
 pub fn init_tracing(config: &LoggingConfig) -> Result<()> {
@@ -35,6 +43,7 @@ fn init_tracing_console_only(config: &LoggingConfig, env_filter: EnvFilter) {
         ref f if f == "json" => {
             tracing_subscriber::fmt()
                 .json()
+                .with_writer(std::io::stderr)
                 .with_env_filter(env_filter)
                 .with_current_span(true)
                 .with_target(true)
@@ -42,17 +51,22 @@ fn init_tracing_console_only(config: &LoggingConfig, env_filter: EnvFilter) {
                 .with_thread_names(true)
                 .with_file(true)
                 .with_line_number(true)
+                .finish()
+                .with(log_buffer().layer())
                 .init();
         }
         _ => {
             tracing_subscriber::fmt()
                 .pretty()
+                .with_writer(std::io::stderr)
                 .with_env_filter(env_filter)
                 .with_target(true)
                 .with_thread_ids(true)
                 .with_thread_names(true)
                 .with_file(true)
                 .with_line_number(true)
+                .finish()
+                .with(log_buffer().layer())
                 .init();
         }
     }
@@ -97,6 +111,7 @@ fn init_tracing_with_file(
         ref f if f == "json" => {
             tracing_subscriber::fmt()
                 .json()
+                .with_writer(std::io::stderr)
                 .with_env_filter(env_filter)
                 .with_current_span(true)
                 .with_target(true)
@@ -104,17 +119,22 @@ fn init_tracing_with_file(
                 .with_thread_names(true)
                 .with_file(true)
                 .with_line_number(true)
+                .finish()
+                .with(log_buffer().layer())
                 .init();
         }
         _ => {
             tracing_subscriber::fmt()
                 .pretty()
+                .with_writer(std::io::stderr)
                 .with_env_filter(env_filter)
                 .with_target(true)
                 .with_thread_ids(true)
                 .with_thread_names(true)
                 .with_file(true)
                 .with_line_number(true)
+                .finish()
+                .with(log_buffer().layer())
                 .init();
         }
     }
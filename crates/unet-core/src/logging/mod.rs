@@ -1,7 +1,9 @@
 //! Logging and tracing infrastructure for μNet Core
 
+mod buffer;
 mod core;
 
+pub use buffer::*;
 pub use core::*;
 
 #[cfg(test)]
@@ -0,0 +1,192 @@
+//! Active link latency, jitter, and loss measurement
+//!
+//! The server probes the management addresses of both link endpoints and
+//! stores the results per link as derived state. Each probe is a timed SNMP
+//! GET of `sysUpTime`: a single UDP round trip that, unlike ICMP, needs no
+//! raw-socket privileges. A probe that gets no answer counts as lost.
+//!
+//! Probes run from the server, so they measure the path to each endpoint
+//! rather than the link itself; a link reports the worse of its two
+//! endpoints. Measurements are kept as a bounded history per link, and each
+//! new measurement is checked against the link's [`LinkThresholds`]. Breaches
+//! are logged on the `alarm` tracing target and returned to the caller.
+
+mod probe;
+#[cfg(feature = "snmp")]
+mod snmp_prober;
+mod store;
+
+pub use probe::{Prober, measure_link};
+#[cfg(feature = "snmp")]
+pub use snmp_prober::SnmpProber;
+pub use store::{
+    DEFAULT_THRESHOLDS_KEY, get_thresholds, measurement_history, record_measurement, set_thresholds,
+};
+
+use chrono::{DateTime, Utc};
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Probe results for one link endpoint
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProbeSummary {
+    /// Node at this end of the link
+    pub node_id: Uuid,
+    /// Address that was probed
+    pub address: IpAddr,
+    /// Probes sent
+    pub sent: u32,
+    /// Probes answered
+    pub received: u32,
+    /// Mean round-trip time of answered probes
+    pub latency_ms: Option<f64>,
+    /// Mean difference between consecutive round-trip times (RFC 3550 style)
+    pub jitter_ms: Option<f64>,
+    /// Share of probes that got no answer, in percent
+    pub loss_percent: f64,
+}
+
+impl ProbeSummary {
+    /// Summarizes round-trip times, where `None` is a lost probe
+    #[must_use]
+    pub fn from_samples(node_id: Uuid, address: IpAddr, samples: &[Option<Duration>]) -> Self {
+        let rtts: Vec<f64> = samples
+            .iter()
+            .flatten()
+            .map(|rtt| rtt.as_secs_f64() * 1000.0)
+            .collect();
+        let sent = u32::try_from(samples.len()).unwrap_or(u32::MAX);
+        let received = u32::try_from(rtts.len()).unwrap_or(u32::MAX);
+
+        let latency_ms = rtts
+            .len()
+            .to_f64()
+            .filter(|count| *count > 0.0)
+            .map(|count| rtts.iter().sum::<f64>() / count);
+        let jitter_ms = rtts
+            .len()
+            .checked_sub(1)
+            .and_then(|intervals| intervals.to_f64())
+            .filter(|intervals| *intervals > 0.0)
+            .map(|intervals| {
+                let deltas = rtts.windows(2).map(|pair| (pair[1] - pair[0]).abs());
+                deltas.sum::<f64>() / intervals
+            });
+        let loss_percent = if sent == 0 {
+            0.0
+        } else {
+            f64::from(sent - received) * 100.0 / f64::from(sent)
+        };
+
+        Self {
+            node_id,
+            address,
+            sent,
+            received,
+            latency_ms,
+            jitter_ms,
+            loss_percent,
+        }
+    }
+}
+
+/// One measurement of a link
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LinkMeasurement {
+    /// Measured link
+    pub link_id: Uuid,
+    /// When the probes finished
+    pub measured_at: DateTime<Utc>,
+    /// Highest endpoint latency
+    pub latency_ms: Option<f64>,
+    /// Highest endpoint jitter
+    pub jitter_ms: Option<f64>,
+    /// Highest endpoint loss
+    pub loss_percent: f64,
+    /// Per-endpoint results
+    pub endpoints: Vec<ProbeSummary>,
+}
+
+impl LinkMeasurement {
+    /// Combines endpoint results, taking the worse value of each metric
+    #[must_use]
+    pub fn from_endpoints(link_id: Uuid, endpoints: Vec<ProbeSummary>) -> Self {
+        let worst = |metric: fn(&ProbeSummary) -> Option<f64>| {
+            endpoints.iter().filter_map(metric).reduce(f64::max)
+        };
+        Self {
+            link_id,
+            measured_at: Utc::now(),
+            latency_ms: worst(|e| e.latency_ms),
+            jitter_ms: worst(|e| e.jitter_ms),
+            loss_percent: worst(|e| Some(e.loss_percent)).unwrap_or(0.0),
+            endpoints,
+        }
+    }
+}
+
+/// A measurement with the thresholds it breached
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MeasurementOutcome {
+    /// The recorded measurement
+    pub measurement: LinkMeasurement,
+    /// Thresholds the measurement exceeded
+    pub breaches: Vec<ThresholdBreach>,
+}
+
+/// Alarm thresholds for link measurements; unset limits are not checked
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LinkThresholds {
+    /// Highest acceptable latency in milliseconds
+    #[serde(default)]
+    pub max_latency_ms: Option<f64>,
+    /// Highest acceptable jitter in milliseconds
+    #[serde(default)]
+    pub max_jitter_ms: Option<f64>,
+    /// Highest acceptable loss in percent
+    #[serde(default)]
+    pub max_loss_percent: Option<f64>,
+}
+
+/// A measurement exceeding a threshold
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThresholdBreach {
+    /// Metric name: `latency_ms`, `jitter_ms`, or `loss_percent`
+    pub metric: String,
+    /// Measured value
+    pub value: f64,
+    /// Configured limit
+    pub threshold: f64,
+}
+
+impl LinkThresholds {
+    /// Returns every metric of `measurement` above its limit
+    #[must_use]
+    pub fn evaluate(&self, measurement: &LinkMeasurement) -> Vec<ThresholdBreach> {
+        [
+            ("latency_ms", measurement.latency_ms, self.max_latency_ms),
+            ("jitter_ms", measurement.jitter_ms, self.max_jitter_ms),
+            (
+                "loss_percent",
+                Some(measurement.loss_percent),
+                self.max_loss_percent,
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(metric, value, threshold)| {
+            let (value, threshold) = (value?, threshold?);
+            (value > threshold).then(|| ThresholdBreach {
+                metric: metric.to_string(),
+                value,
+                threshold,
+            })
+        })
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,89 @@
+//! Probing link endpoints
+
+use super::{
+    LinkMeasurement, MeasurementOutcome, ProbeSummary, get_thresholds, record_measurement,
+};
+use crate::config::MeasurementConfig;
+use crate::config::defaults::network::SNMP_DEFAULT_PORT;
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult};
+use crate::models::{AddressFamilyPreference, Link};
+use async_trait::async_trait;
+use futures_util::future::join_all;
+use std::net::SocketAddr;
+use std::time::Duration;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+/// Sends a single probe and times the answer
+#[async_trait]
+pub trait Prober: Send + Sync {
+    /// Returns the round-trip time, or `None` if the probe was lost
+    async fn probe(&self, target: SocketAddr) -> Option<Duration>;
+}
+
+/// Probes both endpoints of a link, records the measurement, and checks thresholds
+///
+/// Each endpoint with a management address gets `config.probes` probes, one
+/// after another; the two endpoints are probed concurrently. The newest
+/// `config.history` measurements are kept, and breaches of the link's
+/// thresholds are logged on the `alarm` target.
+///
+/// # Errors
+/// Returns an error if neither endpoint has a usable management address or
+/// datastore operations fail.
+pub async fn measure_link(
+    datastore: &dyn DataStore,
+    prober: &dyn Prober,
+    link: &Link,
+    config: &MeasurementConfig,
+    preference: AddressFamilyPreference,
+) -> DataStoreResult<MeasurementOutcome> {
+    let mut targets: Vec<(Uuid, SocketAddr)> = Vec::new();
+    for node_id in std::iter::once(link.source_node_id).chain(link.dest_node_id) {
+        let node = datastore.get_node_required(&node_id).await?;
+        match node.management_socket_addr(SNMP_DEFAULT_PORT, preference) {
+            Ok(Some(address)) => targets.push((node_id, address)),
+            Ok(None) => debug!(node = %node.name, "Skipping endpoint without management address"),
+            Err(e) => debug!(node = %node.name, error = %e, "Skipping endpoint"),
+        }
+    }
+    if targets.is_empty() {
+        return Err(DataStoreError::ValidationError {
+            message: format!(
+                "Link {} has no endpoint with a usable management address",
+                link.name
+            ),
+        });
+    }
+
+    let endpoints = join_all(targets.into_iter().map(|(node_id, address)| async move {
+        let mut samples = Vec::new();
+        for _ in 0..config.probes.max(1) {
+            samples.push(prober.probe(address).await);
+        }
+        ProbeSummary::from_samples(node_id, address.ip(), &samples)
+    }))
+    .await;
+
+    let measurement = LinkMeasurement::from_endpoints(link.id, endpoints);
+    record_measurement(datastore, &measurement, config.history).await?;
+    let breaches = get_thresholds(datastore, link.id)
+        .await?
+        .evaluate(&measurement);
+    for breach in &breaches {
+        warn!(
+            target: "alarm",
+            link_id = %link.id,
+            link = %link.name,
+            metric = %breach.metric,
+            value = breach.value,
+            threshold = breach.threshold,
+            "Link measurement above threshold"
+        );
+    }
+
+    Ok(MeasurementOutcome {
+        measurement,
+        breaches,
+    })
+}
@@ -0,0 +1,60 @@
+//! SNMP probes of link endpoints
+
+use super::Prober;
+use crate::config::SnmpConfig;
+use crate::snmp::{SessionConfig, SnmpClient, SnmpCredentials, SnmpValue, StandardOid};
+use async_trait::async_trait;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// Probes with an SNMP GET of `sysUpTime`
+pub struct SnmpProber {
+    client: SnmpClient,
+    session: SessionConfig,
+}
+
+impl SnmpProber {
+    /// Creates a prober using the configured community, timeout, and OID
+    /// access list
+    ///
+    /// Retries are disabled so that every lost datagram counts as loss.
+    #[must_use]
+    pub fn new(config: &SnmpConfig) -> Self {
+        let session = SessionConfig {
+            credentials: SnmpCredentials::Community {
+                community: config.community.clone(),
+            },
+            timeout: Duration::from_secs(config.timeout),
+            retries: 0,
+            pipeline_window: 1,
+            ..SessionConfig::default()
+        };
+        Self {
+            client: SnmpClient::new(config.client_config()),
+            session,
+        }
+    }
+}
+
+#[async_trait]
+impl Prober for SnmpProber {
+    async fn probe(&self, target: SocketAddr) -> Option<Duration> {
+        let session = SessionConfig {
+            address: target,
+            ..self.session.clone()
+        };
+        let started = Instant::now();
+        let response = self
+            .client
+            .get(target, &[StandardOid::SysUpTime.oid()], Some(session))
+            .await
+            .map_err(|e| debug!(target = %target, error = %e, "Probe failed"))
+            .ok()?;
+        let rtt = started.elapsed();
+        response
+            .values()
+            .any(|value| !matches!(value, SnmpValue::NoSuchObject))
+            .then_some(rtt)
+    }
+}
@@ -0,0 +1,103 @@
+//! Persistence of link measurements and thresholds through the `DataStore` settings API
+
+use super::{LinkMeasurement, LinkThresholds};
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult};
+use serde::de::DeserializeOwned;
+use uuid::Uuid;
+
+/// Settings namespace holding each link's measurement history keyed by link ID
+const HISTORY_NAMESPACE: &str = "link_measurements";
+/// Settings namespace holding thresholds keyed by link ID or [`DEFAULT_THRESHOLDS_KEY`]
+const THRESHOLDS_NAMESPACE: &str = "link_thresholds";
+
+/// Thresholds key applying to links without their own thresholds
+pub const DEFAULT_THRESHOLDS_KEY: &str = "default";
+
+fn parse<T: DeserializeOwned>(key: &str, value: serde_json::Value) -> DataStoreResult<T> {
+    serde_json::from_value(value).map_err(|e| DataStoreError::InternalError {
+        message: format!("stored link measurement data {key}: {e}"),
+    })
+}
+
+fn to_value<T: serde::Serialize>(key: &str, value: &T) -> DataStoreResult<serde_json::Value> {
+    serde_json::to_value(value).map_err(|e| DataStoreError::InternalError {
+        message: format!("link measurement data {key}: {e}"),
+    })
+}
+
+/// Returns a link's measurements, oldest first
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or the history is malformed.
+pub async fn measurement_history(
+    datastore: &dyn DataStore,
+    link_id: Uuid,
+) -> DataStoreResult<Vec<LinkMeasurement>> {
+    let key = link_id.to_string();
+    datastore
+        .get_setting(HISTORY_NAMESPACE, &key)
+        .await?
+        .map_or_else(|| Ok(Vec::new()), |value| parse(&key, value))
+}
+
+/// Appends a measurement to its link's history, keeping the newest `limit`
+///
+/// # Errors
+/// Returns an error if the history cannot be read or written.
+pub async fn record_measurement(
+    datastore: &dyn DataStore,
+    measurement: &LinkMeasurement,
+    limit: usize,
+) -> DataStoreResult<()> {
+    let key = measurement.link_id.to_string();
+    let mut history = measurement_history(datastore, measurement.link_id).await?;
+    history.push(measurement.clone());
+    let excess = history.len().saturating_sub(limit.max(1));
+    history.drain(..excess);
+    datastore
+        .put_setting(HISTORY_NAMESPACE, &key, &to_value(&key, &history)?)
+        .await
+}
+
+/// Returns a link's thresholds, falling back to the default thresholds
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or stored thresholds are malformed.
+pub async fn get_thresholds(
+    datastore: &dyn DataStore,
+    link_id: Uuid,
+) -> DataStoreResult<LinkThresholds> {
+    for key in [link_id.to_string(), DEFAULT_THRESHOLDS_KEY.to_string()] {
+        if let Some(value) = datastore.get_setting(THRESHOLDS_NAMESPACE, &key).await? {
+            return parse(&key, value);
+        }
+    }
+    Ok(LinkThresholds::default())
+}
+
+/// Stores thresholds for a link, or the defaults when `link_id` is `None`
+///
+/// # Errors
+/// Returns a validation error if a limit is negative or not a number, or an
+/// error if the datastore write fails.
+pub async fn set_thresholds(
+    datastore: &dyn DataStore,
+    link_id: Option<Uuid>,
+    thresholds: &LinkThresholds,
+) -> DataStoreResult<()> {
+    for (name, limit) in [
+        ("max_latency_ms", thresholds.max_latency_ms),
+        ("max_jitter_ms", thresholds.max_jitter_ms),
+        ("max_loss_percent", thresholds.max_loss_percent),
+    ] {
+        if limit.is_some_and(|limit| limit.is_nan() || limit < 0.0) {
+            return Err(DataStoreError::ValidationError {
+                message: format!("{name} must be a non-negative number"),
+            });
+        }
+    }
+    let key = link_id.map_or_else(|| DEFAULT_THRESHOLDS_KEY.to_string(), |id| id.to_string());
+    datastore
+        .put_setting(THRESHOLDS_NAMESPACE, &key, &to_value(&key, thresholds)?)
+        .await
+}
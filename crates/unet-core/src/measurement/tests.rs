@@ -0,0 +1,163 @@
+use super::*;
+use crate::config::MeasurementConfig;
+use crate::datastore::sqlite::tests::setup::migrated_store;
+use crate::datastore::{DataStore, DataStoreError, sqlite::SqliteStore};
+use crate::models::{AddressFamilyPreference, DeviceRole, Link, Node, Vendor};
+use async_trait::async_trait;
+use std::net::{Ipv4Addr, SocketAddr};
+
+/// Answers probes to `answering` after a fixed delay and drops all others
+struct ScriptedProber {
+    answering: IpAddr,
+    rtt: Duration,
+}
+
+#[async_trait]
+impl Prober for ScriptedProber {
+    async fn probe(&self, target: SocketAddr) -> Option<Duration> {
+        (target.ip() == self.answering).then_some(self.rtt)
+    }
+}
+
+fn ms(millis: u64) -> Option<Duration> {
+    Some(Duration::from_millis(millis))
+}
+
+fn summary(latency_ms: f64, loss_percent: f64) -> ProbeSummary {
+    ProbeSummary {
+        node_id: Uuid::new_v4(),
+        address: IpAddr::V4(Ipv4Addr::LOCALHOST),
+        sent: 4,
+        received: 4,
+        latency_ms: Some(latency_ms),
+        jitter_ms: None,
+        loss_percent,
+    }
+}
+
+async fn create_node(store: &SqliteStore, name: &str, ip: &str) -> Node {
+    let mut node = Node::new(
+        name.to_string(),
+        "example.com".to_string(),
+        Vendor::Cisco,
+        DeviceRole::Router,
+    );
+    node.management_ip = Some(ip.parse().unwrap());
+    store.create_node(&node).await.unwrap()
+}
+
+#[test]
+fn test_probe_summary_computes_latency_jitter_and_loss() {
+    let address = IpAddr::V4(Ipv4Addr::LOCALHOST);
+    let summary =
+        ProbeSummary::from_samples(Uuid::new_v4(), address, &[ms(10), None, ms(14), ms(12)]);
+
+    assert_eq!(summary.sent, 4);
+    assert_eq!(summary.received, 3);
+    assert!((summary.latency_ms.unwrap() - 12.0).abs() < 1e-9);
+    assert!((summary.jitter_ms.unwrap() - 3.0).abs() < 1e-9);
+    assert!((summary.loss_percent - 25.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_probe_summary_with_all_probes_lost() {
+    let summary =
+        ProbeSummary::from_samples(Uuid::new_v4(), IpAddr::V4(Ipv4Addr::LOCALHOST), &[None; 3]);
+
+    assert!(summary.latency_ms.is_none());
+    assert!(summary.jitter_ms.is_none());
+    assert!((summary.loss_percent - 100.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_link_measurement_takes_worse_endpoint() {
+    let measurement = LinkMeasurement::from_endpoints(
+        Uuid::new_v4(),
+        vec![summary(5.0, 20.0), summary(9.0, 0.0)],
+    );
+
+    assert!((measurement.latency_ms.unwrap() - 9.0).abs() < 1e-9);
+    assert!(measurement.jitter_ms.is_none());
+    assert!((measurement.loss_percent - 20.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_thresholds_report_only_exceeded_limits() {
+    let measurement = LinkMeasurement::from_endpoints(Uuid::new_v4(), vec![summary(30.0, 0.0)]);
+    let thresholds = LinkThresholds {
+        max_latency_ms: Some(20.0),
+        max_jitter_ms: Some(1.0),
+        max_loss_percent: Some(0.0),
+    };
+
+    let breaches = thresholds.evaluate(&measurement);
+
+    assert_eq!(breaches.len(), 1);
+    assert_eq!(breaches[0].metric, "latency_ms");
+    assert!(LinkThresholds::default().evaluate(&measurement).is_empty());
+}
+
+#[tokio::test]
+async fn test_measure_link_records_history_and_breaches() {
+    let store = migrated_store().await;
+    let a = create_node(&store, "measure-a", "192.0.2.1").await;
+    let z = create_node(&store, "measure-z", "192.0.2.2").await;
+    let link = store
+        .create_link(&Link::new(
+            "measure-a-z".to_string(),
+            a.id,
+            "ge-0/0/0".to_string(),
+            z.id,
+            "ge-0/0/1".to_string(),
+        ))
+        .await
+        .unwrap();
+    set_thresholds(
+        &store,
+        Some(link.id),
+        &LinkThresholds {
+            max_loss_percent: Some(10.0),
+            ..LinkThresholds::default()
+        },
+    )
+    .await
+    .unwrap();
+    let prober = ScriptedProber {
+        answering: "192.0.2.1".parse().unwrap(),
+        rtt: Duration::from_millis(4),
+    };
+    let config = MeasurementConfig {
+        probes: 2,
+        history: 2,
+        ..MeasurementConfig::default()
+    };
+
+    for _ in 0..3 {
+        let outcome = measure_link(
+            &store,
+            &prober,
+            &link,
+            &config,
+            AddressFamilyPreference::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(outcome.measurement.endpoints.len(), 2);
+        assert!((outcome.measurement.loss_percent - 100.0).abs() < 1e-9);
+        assert_eq!(outcome.breaches.len(), 1);
+    }
+
+    assert_eq!(measurement_history(&store, link.id).await.unwrap().len(), 2);
+    assert_eq!(
+        get_thresholds(&store, Uuid::new_v4()).await.unwrap(),
+        LinkThresholds::default()
+    );
+    let negative = LinkThresholds {
+        max_latency_ms: Some(-1.0),
+        ..LinkThresholds::default()
+    };
+    assert!(matches!(
+        set_thresholds(&store, None, &negative).await,
+        Err(DataStoreError::ValidationError { .. })
+    ));
+}
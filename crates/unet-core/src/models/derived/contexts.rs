@@ -0,0 +1,82 @@
+//! Derived state of logical instances within a device
+//!
+//! Devices with VRF-aware SNMP agents or virtual contexts answer for each
+//! logical instance separately, through an `SNMPv3` `contextName` or an
+//! indexed community. Each instance is polled on its own and its state is
+//! kept under its context name, apart from the node-level state.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+use super::{InterfaceStatus, polled_interfaces};
+use crate::snmp::SnmpValue;
+
+/// Status of one logical instance of a node
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContextStatus {
+    /// Timestamp when this status was last updated
+    pub last_updated: SystemTime,
+    /// Whether the instance answered its last polls
+    pub reachable: bool,
+    /// Interfaces the instance reports
+    pub interfaces: Vec<InterfaceStatus>,
+    /// Raw SNMP data from the instance's last successful poll
+    pub raw_snmp_data: HashMap<String, SnmpValue>,
+    /// Timestamp of last successful SNMP poll
+    pub last_snmp_success: Option<SystemTime>,
+    /// Error message from last failed poll attempt
+    pub last_error: Option<String>,
+    /// Number of consecutive polling failures
+    pub consecutive_failures: u32,
+}
+
+impl ContextStatus {
+    /// Status of an instance not yet polled
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            last_updated: SystemTime::now(),
+            reachable: false,
+            interfaces: Vec::new(),
+            raw_snmp_data: HashMap::new(),
+            last_snmp_success: None,
+            last_error: None,
+            consecutive_failures: 0,
+        }
+    }
+
+    /// Update status with successful SNMP poll result
+    pub fn update_from_snmp(&mut self, snmp_data: HashMap<String, SnmpValue>) {
+        let now = SystemTime::now();
+        let elapsed = self
+            .last_snmp_success
+            .and_then(|previous| now.duration_since(previous).ok());
+
+        self.last_updated = now;
+        self.last_snmp_success = Some(now);
+        self.reachable = true;
+        self.consecutive_failures = 0;
+        self.last_error = None;
+        self.interfaces = polled_interfaces(&snmp_data, &self.interfaces, elapsed);
+        self.raw_snmp_data = snmp_data;
+    }
+
+    /// Mark polling failure
+    pub fn mark_polling_failure(&mut self, error: String) {
+        self.last_updated = SystemTime::now();
+        self.consecutive_failures += 1;
+        self.last_error = Some(error);
+
+        // Mark as unreachable after multiple failures, as for the node
+        if self.consecutive_failures >= 3 {
+            self.reachable = false;
+        }
+    }
+}
+
+impl Default for ContextStatus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
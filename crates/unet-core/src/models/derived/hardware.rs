@@ -0,0 +1,311 @@
+//! Hardware inventory read from the ENTITY-MIB `entPhysicalTable`
+//!
+//! Devices list their physical parts in `entPhysicalTable` (RFC 6933), one
+//! row per chassis, slot, card, port, fan, and sensor. Only the parts an
+//! asset inventory tracks are kept: chassis, modules, transceivers, and power
+//! supplies. ENTITY-MIB has no class for transceivers, so vendors report
+//! them as ports or modules; they are told apart by their description.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+
+use crate::snmp::SnmpValue;
+
+/// `entPhysicalEntry`; columns are `<entry>.<column>.<entPhysicalIndex>`
+const ENTRY: &str = "1.3.6.1.2.1.47.1.1.1.1";
+
+const DESCR: u32 = 2;
+const CONTAINED_IN: u32 = 4;
+const CLASS: u32 = 5;
+const NAME: u32 = 7;
+const HARDWARE_REV: u32 = 8;
+const FIRMWARE_REV: u32 = 9;
+const SOFTWARE_REV: u32 = 10;
+const SERIAL_NUM: u32 = 11;
+const MFG_NAME: u32 = 12;
+const MODEL_NAME: u32 = 13;
+const IS_FRU: u32 = 16;
+
+/// Words vendors use in the description of a pluggable optic
+const TRANSCEIVER_MARKERS: [&str; 8] = [
+    "sfp",
+    "qsfp",
+    "xfp",
+    "cfp",
+    "gbic",
+    "osfp",
+    "transceiver",
+    "optic",
+];
+
+/// Kind of a tracked hardware component
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HardwareKind {
+    /// The device enclosure (`entPhysicalClass` chassis)
+    Chassis,
+    /// A line card, supervisor, or other module
+    Module,
+    /// A pluggable optic or copper transceiver
+    Transceiver,
+    /// A power supply
+    PowerSupply,
+}
+
+impl Display for HardwareKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Chassis => write!(f, "chassis"),
+            Self::Module => write!(f, "module"),
+            Self::Transceiver => write!(f, "transceiver"),
+            Self::PowerSupply => write!(f, "power_supply"),
+        }
+    }
+}
+
+impl FromStr for HardwareKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('-', "_").as_str() {
+            "chassis" => Ok(Self::Chassis),
+            "module" => Ok(Self::Module),
+            "transceiver" => Ok(Self::Transceiver),
+            "power_supply" | "psu" => Ok(Self::PowerSupply),
+            _ => Err(format!("Invalid hardware kind: {s}")),
+        }
+    }
+}
+
+impl HardwareKind {
+    /// Kind of an entity given its `entPhysicalClass` and the text it is
+    /// described by, or `None` for parts that are not tracked
+    #[must_use]
+    pub fn classify(class: i64, text: &str) -> Option<Self> {
+        let text = text.to_ascii_lowercase();
+        let transceiver = TRANSCEIVER_MARKERS
+            .iter()
+            .any(|marker| text.contains(marker));
+        match class {
+            3 => Some(Self::Chassis),
+            6 => Some(Self::PowerSupply),
+            1 | 9 | 10 if transceiver => Some(Self::Transceiver),
+            9 => Some(Self::Module),
+            _ => None,
+        }
+    }
+}
+
+/// A chassis, module, transceiver, or power supply of a device
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HardwareComponent {
+    /// `entPhysicalIndex`
+    pub index: u32,
+    /// What the component is
+    pub kind: HardwareKind,
+    /// Name, e.g. `Slot 1` or `Power Supply 2`
+    pub name: Option<String>,
+    /// Description, e.g. `1000BaseSX SFP`
+    pub description: Option<String>,
+    /// Index of the component this one sits in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_index: Option<u32>,
+    /// Serial number
+    pub serial_number: Option<String>,
+    /// Orderable part number (`entPhysicalModelName`), e.g. `SFP-10G-SR`
+    pub part_number: Option<String>,
+    /// Manufacturer
+    pub manufacturer: Option<String>,
+    /// Hardware revision
+    pub hardware_revision: Option<String>,
+    /// Firmware revision
+    pub firmware_revision: Option<String>,
+    /// Software revision
+    pub software_revision: Option<String>,
+    /// Whether the component can be replaced in the field
+    pub field_replaceable: Option<bool>,
+}
+
+impl HardwareComponent {
+    /// Extract tracked components from a walk of `entPhysicalTable`, ordered
+    /// by index
+    #[must_use]
+    pub fn from_snmp(snmp_data: &HashMap<String, SnmpValue>) -> Vec<Self> {
+        let prefix = format!("{ENTRY}.{CLASS}.");
+        let indexes: BTreeSet<u32> = snmp_data
+            .keys()
+            .filter_map(|oid| oid.strip_prefix(&prefix)?.parse().ok())
+            .collect();
+
+        indexes
+            .into_iter()
+            .filter_map(|index| {
+                let value = |column: u32| snmp_data.get(&format!("{ENTRY}.{column}.{index}"));
+                let text = |column: u32| {
+                    value(column)
+                        .and_then(SnmpValue::as_str)
+                        .map(str::trim)
+                        .filter(|text| !text.is_empty())
+                        .map(str::to_string)
+                };
+
+                let class = value(CLASS)?.as_i64()?;
+                let description = text(DESCR);
+                let name = text(NAME);
+                let part_number = text(MODEL_NAME);
+                let words = [&description, &name, &part_number]
+                    .into_iter()
+                    .flatten()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                let kind = HardwareKind::classify(class, &words)?;
+
+                Some(Self {
+                    index,
+                    kind,
+                    name,
+                    description,
+                    parent_index: value(CONTAINED_IN)
+                        .and_then(SnmpValue::as_u64)
+                        .and_then(|parent| u32::try_from(parent).ok())
+                        .filter(|parent| *parent != 0),
+                    serial_number: text(SERIAL_NUM),
+                    part_number,
+                    manufacturer: text(MFG_NAME),
+                    hardware_revision: text(HARDWARE_REV),
+                    firmware_revision: text(FIRMWARE_REV),
+                    software_revision: text(SOFTWARE_REV),
+                    field_replaceable: value(IS_FRU).and_then(SnmpValue::as_i64).and_then(
+                        |truth| match truth {
+                            1 => Some(true),
+                            2 => Some(false),
+                            _ => None,
+                        },
+                    ),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entity(
+        data: &mut HashMap<String, SnmpValue>,
+        index: u32,
+        class: i64,
+        columns: &[(u32, &str)],
+    ) {
+        data.insert(
+            format!("{ENTRY}.{CLASS}.{index}"),
+            SnmpValue::Integer(class),
+        );
+        for (column, text) in columns {
+            data.insert(
+                format!("{ENTRY}.{column}.{index}"),
+                SnmpValue::String((*text).to_string()),
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_snmp_keeps_tracked_parts() {
+        let mut data = HashMap::new();
+        entity(
+            &mut data,
+            1,
+            3,
+            &[
+                (DESCR, "Cisco Catalyst 9300 Chassis"),
+                (SERIAL_NUM, "FOC2233X0AB"),
+                (MODEL_NAME, "C9300-48P"),
+                (SOFTWARE_REV, "17.3.4"),
+            ],
+        );
+        entity(&mut data, 2, 5, &[(DESCR, "Slot 1")]);
+        entity(
+            &mut data,
+            1000,
+            10,
+            &[
+                (DESCR, "SFP-10GBase-SR"),
+                (SERIAL_NUM, "AVD1234ABCD"),
+                (MODEL_NAME, "SFP-10G-SR"),
+            ],
+        );
+        entity(&mut data, 1001, 10, &[(DESCR, "GigabitEthernet1/0/1")]);
+        entity(
+            &mut data,
+            1010,
+            6,
+            &[(NAME, "Power Supply 1"), (SERIAL_NUM, " ")],
+        );
+        entity(&mut data, 1020, 9, &[(DESCR, "C9300 8x10G Uplink Module")]);
+        data.insert(
+            format!("{ENTRY}.{CONTAINED_IN}.1000"),
+            SnmpValue::Integer(2),
+        );
+        data.insert(format!("{ENTRY}.{CONTAINED_IN}.1"), SnmpValue::Integer(0));
+        data.insert(format!("{ENTRY}.{IS_FRU}.1010"), SnmpValue::Integer(1));
+
+        let components = HardwareComponent::from_snmp(&data);
+
+        let kinds: Vec<(u32, HardwareKind)> = components
+            .iter()
+            .map(|component| (component.index, component.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                (1, HardwareKind::Chassis),
+                (1000, HardwareKind::Transceiver),
+                (1010, HardwareKind::PowerSupply),
+                (1020, HardwareKind::Module),
+            ]
+        );
+        assert_eq!(components[0].parent_index, None);
+        assert_eq!(components[0].part_number.as_deref(), Some("C9300-48P"));
+        assert_eq!(components[0].software_revision.as_deref(), Some("17.3.4"));
+        assert_eq!(components[1].parent_index, Some(2));
+        assert_eq!(components[1].serial_number.as_deref(), Some("AVD1234ABCD"));
+        assert_eq!(components[2].serial_number, None);
+        assert_eq!(components[2].field_replaceable, Some(true));
+    }
+
+    #[test]
+    fn test_classify_transceivers_by_description() {
+        assert_eq!(
+            HardwareKind::classify(9, "QSFP-100G-LR4"),
+            Some(HardwareKind::Transceiver)
+        );
+        assert_eq!(
+            HardwareKind::classify(1, "Xcvr 10GBASE-LR Optics"),
+            Some(HardwareKind::Transceiver)
+        );
+        assert_eq!(
+            HardwareKind::classify(9, "FPC @ 0/*/*"),
+            Some(HardwareKind::Module)
+        );
+        assert_eq!(HardwareKind::classify(7, "Fan Tray"), None);
+        assert_eq!(HardwareKind::classify(10, "xe-0/0/0"), None);
+    }
+
+    #[test]
+    fn test_kind_round_trips_through_its_name() {
+        for kind in [
+            HardwareKind::Chassis,
+            HardwareKind::Module,
+            HardwareKind::Transceiver,
+            HardwareKind::PowerSupply,
+        ] {
+            assert_eq!(kind.to_string().parse::<HardwareKind>(), Ok(kind));
+        }
+        assert_eq!("PSU".parse(), Ok(HardwareKind::PowerSupply));
+        assert!("fan".parse::<HardwareKind>().is_err());
+    }
+}
@@ -0,0 +1,243 @@
+//! Performance history and range queries over it
+//!
+//! Polled performance metrics are kept as one sample per metric so a range
+//! can be downsampled by the datastore: a [`MetricQuery`] splits `from..=to`
+//! into `step`-wide buckets, aligned to the Unix epoch, and aggregates the
+//! samples in each bucket. Samples past their retention are kept as hourly
+//! rollups, which answer the same queries at hourly or coarser steps.
+
+use chrono::{DateTime, TimeDelta, Utc};
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use super::PerformanceMetrics;
+
+/// Most buckets one query may return
+pub const MAX_QUERY_POINTS: i64 = 11_000;
+
+/// Performance metric kept in history
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryMetric {
+    /// CPU utilization in percent
+    Cpu,
+    /// Memory utilization in percent
+    Memory,
+    /// Used memory in bytes
+    MemoryUsed,
+    /// Load average
+    LoadAverage,
+}
+
+impl HistoryMetric {
+    /// Every metric kept in history
+    pub const ALL: [Self; 4] = [Self::Cpu, Self::Memory, Self::MemoryUsed, Self::LoadAverage];
+
+    /// Name used in queries and storage
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Cpu => "cpu",
+            Self::Memory => "memory",
+            Self::MemoryUsed => "memory_used",
+            Self::LoadAverage => "load_average",
+        }
+    }
+}
+
+impl fmt::Display for HistoryMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for HistoryMetric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|metric| metric.as_str() == s)
+            .ok_or_else(|| {
+                let names: Vec<_> = Self::ALL.into_iter().map(Self::as_str).collect();
+                format!("Unknown metric {s:?}; expected one of {}", names.join(", "))
+            })
+    }
+}
+
+/// Function applied to the samples in each bucket
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Aggregation {
+    /// Mean of the samples
+    #[default]
+    Avg,
+    /// Smallest sample
+    Min,
+    /// Largest sample
+    Max,
+    /// Sum of the samples
+    Sum,
+    /// Number of samples
+    Count,
+}
+
+impl Aggregation {
+    const ALL: [Self; 5] = [Self::Avg, Self::Min, Self::Max, Self::Sum, Self::Count];
+
+    /// Name used in queries
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Avg => "avg",
+            Self::Min => "min",
+            Self::Max => "max",
+            Self::Sum => "sum",
+            Self::Count => "count",
+        }
+    }
+
+    /// SQL aggregate over `total`, `samples`, `low`, and `high` columns
+    ///
+    /// A raw sample is one row with `samples` of 1 and the value in the
+    /// other three columns, so samples and hourly rollups aggregate together.
+    #[must_use]
+    pub const fn sql_expression(self) -> &'static str {
+        match self {
+            Self::Avg => "SUM(total) / SUM(samples)",
+            Self::Min => "MIN(low)",
+            Self::Max => "MAX(high)",
+            Self::Sum => "SUM(total)",
+            Self::Count => "SUM(samples)",
+        }
+    }
+}
+
+impl FromStr for Aggregation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|function| function.as_str() == s)
+            .ok_or_else(|| format!("Unknown function {s:?}; expected avg, min, max, sum, or count"))
+    }
+}
+
+/// Range query over one metric of a node
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetricQuery {
+    /// Metric to read
+    pub metric: HistoryMetric,
+    /// Start of the range, inclusive
+    pub from: DateTime<Utc>,
+    /// End of the range, inclusive
+    pub to: DateTime<Utc>,
+    /// Bucket width, in whole seconds
+    pub step: TimeDelta,
+    /// Function applied to each bucket
+    pub aggregation: Aggregation,
+}
+
+impl MetricQuery {
+    /// Checks the range and step
+    ///
+    /// # Errors
+    /// Returns an error if `to` is before `from`, `step` is under a second or
+    /// not whole seconds, or the range has more than [`MAX_QUERY_POINTS`]
+    /// buckets.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.to < self.from {
+            return Err(format!(
+                "Query ends at {} before it starts at {}",
+                self.to, self.from
+            ));
+        }
+        if self.step < TimeDelta::seconds(1) || self.step.subsec_nanos() != 0 {
+            return Err("Step must be a whole number of seconds".to_string());
+        }
+        let points = (self.to - self.from).num_seconds() / self.step.num_seconds() + 1;
+        if points > MAX_QUERY_POINTS {
+            return Err(format!(
+                "Query would return {points} points; the limit is {MAX_QUERY_POINTS}, use a larger step"
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Aggregated value of one bucket
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MetricPoint {
+    /// Start of the bucket
+    pub timestamp: DateTime<Utc>,
+    /// Aggregated value
+    pub value: f64,
+}
+
+/// Series in the shape Grafana's JSON datasource expects
+///
+/// Each datapoint is `[value, unix_milliseconds]`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricSeries {
+    /// Series name
+    pub target: String,
+    /// `[value, unix_milliseconds]` pairs, oldest first
+    pub datapoints: Vec<(f64, i64)>,
+}
+
+impl MetricSeries {
+    /// Builds a series from aggregated points
+    #[must_use]
+    pub fn new(target: impl Into<String>, points: &[MetricPoint]) -> Self {
+        Self {
+            target: target.into(),
+            datapoints: points
+                .iter()
+                .map(|point| (point.value, point.timestamp.timestamp_millis()))
+                .collect(),
+        }
+    }
+}
+
+impl PerformanceMetrics {
+    /// Samples to record in history for the metrics that are set
+    #[must_use]
+    pub fn history_samples(&self) -> Vec<(HistoryMetric, f64)> {
+        [
+            (HistoryMetric::Cpu, self.cpu_utilization.map(f64::from)),
+            (
+                HistoryMetric::Memory,
+                self.memory_utilization.map(f64::from),
+            ),
+            (
+                HistoryMetric::MemoryUsed,
+                self.used_memory.and_then(|bytes| bytes.to_f64()),
+            ),
+            (HistoryMetric::LoadAverage, self.load_average.map(f64::from)),
+        ]
+        .into_iter()
+        .filter_map(|(metric, value)| value.map(|value| (metric, value)))
+        .collect()
+    }
+}
+
+/// Parses a query time given as Unix milliseconds or RFC 3339
+///
+/// # Errors
+/// Returns an error if the text is neither.
+pub fn parse_query_time(text: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(millis) = text.parse::<i64>() {
+        return DateTime::from_timestamp_millis(millis)
+            .ok_or_else(|| format!("Time {text} is out of range"));
+    }
+    DateTime::parse_from_rfc3339(text)
+        .map(|time| time.with_timezone(&Utc))
+        .map_err(|e| format!("Invalid time {text:?}: expected Unix milliseconds or RFC 3339 ({e})"))
+}
+
+#[cfg(test)]
+#[path = "history_tests.rs"]
+mod history_tests;
@@ -0,0 +1,90 @@
+use super::*;
+
+fn query(from: &str, to: &str, step: TimeDelta) -> MetricQuery {
+    MetricQuery {
+        metric: HistoryMetric::Cpu,
+        from: parse_query_time(from).unwrap(),
+        to: parse_query_time(to).unwrap(),
+        step,
+        aggregation: Aggregation::Avg,
+    }
+}
+
+#[test]
+fn test_parse_query_time_accepts_millis_and_rfc3339() {
+    let millis = parse_query_time("1700000000000").unwrap();
+    let rfc3339 = parse_query_time("2023-11-14T22:13:20Z").unwrap();
+    assert_eq!(millis, rfc3339);
+    assert!(parse_query_time("yesterday").is_err());
+}
+
+#[test]
+fn test_metric_and_function_names_round_trip() {
+    for metric in HistoryMetric::ALL {
+        assert_eq!(metric.as_str().parse::<HistoryMetric>(), Ok(metric));
+    }
+    assert_eq!("max".parse::<Aggregation>(), Ok(Aggregation::Max));
+    assert!("median".parse::<Aggregation>().is_err());
+    assert!("disk".parse::<HistoryMetric>().unwrap_err().contains("cpu"));
+}
+
+#[test]
+fn test_query_validation() {
+    let from = "2024-01-01T00:00:00Z";
+    assert!(
+        query(from, "2024-01-01T01:00:00Z", TimeDelta::minutes(5))
+            .validate()
+            .is_ok()
+    );
+    assert!(
+        query("2024-01-01T01:00:00Z", from, TimeDelta::minutes(5))
+            .validate()
+            .is_err()
+    );
+    assert!(
+        query(from, "2024-01-01T01:00:00Z", TimeDelta::milliseconds(1500))
+            .validate()
+            .is_err()
+    );
+    // A year at one-second resolution is far over the point limit
+    let error = query(from, "2025-01-01T00:00:00Z", TimeDelta::seconds(1))
+        .validate()
+        .unwrap_err();
+    assert!(error.contains("larger step"));
+}
+
+#[test]
+fn test_history_samples_skip_unset_metrics() {
+    let metrics = PerformanceMetrics {
+        cpu_utilization: Some(42),
+        memory_utilization: None,
+        total_memory: Some(8_000),
+        used_memory: Some(2_000),
+        load_average: None,
+    };
+
+    assert_eq!(
+        metrics.history_samples(),
+        vec![
+            (HistoryMetric::Cpu, 42.0),
+            (HistoryMetric::MemoryUsed, 2_000.0)
+        ]
+    );
+}
+
+#[test]
+fn test_series_uses_grafana_datapoint_order() {
+    let timestamp = parse_query_time("1700000000000").unwrap();
+    let series = MetricSeries::new(
+        "edge-01.cpu",
+        &[MetricPoint {
+            timestamp,
+            value: 12.5,
+        }],
+    );
+
+    assert_eq!(
+        serde_json::to_value(&series).unwrap(),
+        serde_json::json!({"target": "edge-01.cpu", "datapoints": [[12.5, 1_700_000_000_000_i64]]})
+    );
+}
@@ -3,10 +3,11 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use crate::snmp::SnmpValue;
+use super::{ErrorRate, TrafficRate};
+use crate::snmp::{SnmpValue, StandardOid};
 
 /// Status of a network interface
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct InterfaceStatus {
     /// Interface index
     pub index: u32,
@@ -66,97 +67,56 @@ impl InterfaceStatus {
                 output_stats: InterfaceStats::default(),
             };
 
+            let value = |column: u32| snmp_data.get(&format!("1.3.6.1.2.1.2.2.1.{column}.{index}"));
+            let number = |column: u32| value(column).and_then(SnmpValue::as_u64);
+
             // Extract interface description
-            if let Some(SnmpValue::String(desc)) =
-                snmp_data.get(&format!("1.3.6.1.2.1.2.2.1.2.{index}"))
-            {
-                interface.name.clone_from(desc);
+            if let Some(desc) = value(2).and_then(SnmpValue::as_str) {
+                desc.clone_into(&mut interface.name);
             }
 
             // Extract interface type
-            if let Some(SnmpValue::Integer(iface_type)) =
-                snmp_data.get(&format!("1.3.6.1.2.1.2.2.1.3.{index}"))
-            {
-                if let Ok(iface_type_u32) = u32::try_from(*iface_type) {
-                    interface.interface_type = iface_type_u32;
-                }
+            if let Some(iface_type) = number(3).and_then(|n| u32::try_from(n).ok()) {
+                interface.interface_type = iface_type;
             }
 
             // Extract MTU
-            if let Some(SnmpValue::Integer(mtu)) =
-                snmp_data.get(&format!("1.3.6.1.2.1.2.2.1.4.{index}"))
-            {
-                if let Ok(mtu_u32) = u32::try_from(*mtu) {
-                    interface.mtu = Some(mtu_u32);
-                }
-            }
+            interface.mtu = number(4).and_then(|n| u32::try_from(n).ok());
 
             // Extract speed
-            if let Some(SnmpValue::Gauge32(speed)) =
-                snmp_data.get(&format!("1.3.6.1.2.1.2.2.1.5.{index}"))
-            {
-                interface.speed = Some(u64::from(*speed));
-            }
+            interface.speed = number(5);
+
+            // Extract physical address
+            interface.physical_address = value(6).and_then(SnmpValue::as_mac);
 
             // Extract administrative status
-            if let Some(SnmpValue::Integer(status)) =
-                snmp_data.get(&format!("1.3.6.1.2.1.2.2.1.7.{index}"))
-            {
-                if let Ok(status_u8) = u8::try_from(*status) {
-                    interface.admin_status = InterfaceAdminStatus::from(status_u8);
-                }
+            if let Some(status) = number(7).and_then(|n| u8::try_from(n).ok()) {
+                interface.admin_status = InterfaceAdminStatus::from(status);
             }
 
             // Extract operational status
-            if let Some(SnmpValue::Integer(status)) =
-                snmp_data.get(&format!("1.3.6.1.2.1.2.2.1.8.{index}"))
-            {
-                if let Ok(status_u8) = u8::try_from(*status) {
-                    interface.oper_status = InterfaceOperStatus::from(status_u8);
-                }
-            }
-
-            // Extract input octets
-            if let Some(SnmpValue::Counter32(octets)) =
-                snmp_data.get(&format!("1.3.6.1.2.1.2.2.1.10.{index}"))
-            {
-                interface.input_stats.octets = u64::from(*octets);
-            }
-
-            // Extract input packets
-            if let Some(SnmpValue::Counter32(packets)) =
-                snmp_data.get(&format!("1.3.6.1.2.1.2.2.1.11.{index}"))
-            {
-                interface.input_stats.packets = u64::from(*packets);
-            }
-
-            // Extract input errors
-            if let Some(SnmpValue::Counter32(errors)) =
-                snmp_data.get(&format!("1.3.6.1.2.1.2.2.1.14.{index}"))
-            {
-                interface.input_stats.errors = u64::from(*errors);
-            }
-
-            // Extract output octets
-            if let Some(SnmpValue::Counter32(octets)) =
-                snmp_data.get(&format!("1.3.6.1.2.1.2.2.1.16.{index}"))
-            {
-                interface.output_stats.octets = u64::from(*octets);
-            }
-
-            // Extract output packets
-            if let Some(SnmpValue::Counter32(packets)) =
-                snmp_data.get(&format!("1.3.6.1.2.1.2.2.1.17.{index}"))
-            {
-                interface.output_stats.packets = u64::from(*packets);
+            if let Some(status) = number(8).and_then(|n| u8::try_from(n).ok()) {
+                interface.oper_status = InterfaceOperStatus::from(status);
             }
 
-            // Extract output errors
-            if let Some(SnmpValue::Counter32(errors)) =
-                snmp_data.get(&format!("1.3.6.1.2.1.2.2.1.20.{index}"))
-            {
-                interface.output_stats.errors = u64::from(*errors);
-            }
+            // Extract last change
+            interface.last_change = number(9).and_then(|n| u32::try_from(n).ok());
+
+            // Extract input and output counters
+            interface.input_stats.octets = number(10).unwrap_or_default();
+            interface.input_stats.packets = number(11).unwrap_or_default();
+            interface.input_stats.errors = number(14).unwrap_or_default();
+            interface.output_stats.octets = number(16).unwrap_or_default();
+            interface.output_stats.packets = number(17).unwrap_or_default();
+            interface.output_stats.errors = number(20).unwrap_or_default();
+            interface.input_stats.crc_errors = snmp_data
+                .get(&format!(
+                    "{}.{index}",
+                    StandardOid::Dot3StatsFcsErrors.oid()
+                ))
+                .and_then(SnmpValue::as_u64);
+
+            super::rates::apply_high_capacity_counters(&mut interface, snmp_data);
 
             interfaces.push(interface);
         }
@@ -223,7 +183,7 @@ impl From<u8> for InterfaceOperStatus {
 }
 
 /// Interface traffic statistics
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct InterfaceStats {
     /// Number of octets (bytes)
     pub octets: u64,
@@ -233,6 +193,19 @@ pub struct InterfaceStats {
     pub errors: u64,
     /// Number of discarded packets
     pub discards: u64,
+    /// Whether octets and packets come from 64-bit ifXTable counters
+    #[serde(default)]
+    pub high_capacity: bool,
+    /// Rate since the previous poll
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate: Option<TrafficRate>,
+    /// Frame check sequence (CRC) errors, reported for the input direction
+    /// of Ethernet interfaces
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crc_errors: Option<u64>,
+    /// Error rates since the previous poll
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_rate: Option<ErrorRate>,
 }
 
 #[cfg(test)]
@@ -264,6 +237,43 @@ mod tests {
         assert_eq!(interface.oper_status, InterfaceOperStatus::Up);
     }
 
+    #[test]
+    fn test_interface_status_coerces_value_encodings() {
+        let mut snmp_data = HashMap::new();
+        snmp_data.insert("1.3.6.1.2.1.2.2.1.1.2".to_string(), SnmpValue::Integer(2));
+        snmp_data.insert(
+            "1.3.6.1.2.1.2.2.1.5.2".to_string(),
+            SnmpValue::Integer(1_000_000_000),
+        );
+        snmp_data.insert(
+            "1.3.6.1.2.1.2.2.1.6.2".to_string(),
+            SnmpValue::String("0x001a2b3c4dff".to_string()),
+        );
+        snmp_data.insert(
+            "1.3.6.1.2.1.2.2.1.9.2".to_string(),
+            SnmpValue::TimeTicks(4_200),
+        );
+        snmp_data.insert(
+            "1.3.6.1.2.1.2.2.1.10.2".to_string(),
+            SnmpValue::Counter32(1_500),
+        );
+        snmp_data.insert(
+            "1.3.6.1.2.1.10.7.2.1.3.2".to_string(),
+            SnmpValue::Counter32(7),
+        );
+
+        let interface = &InterfaceStatus::from_snmp(&snmp_data)[0];
+        assert_eq!(interface.speed, Some(1_000_000_000));
+        assert_eq!(
+            interface.physical_address.as_deref(),
+            Some("00:1a:2b:3c:4d:ff")
+        );
+        assert_eq!(interface.last_change, Some(4_200));
+        assert_eq!(interface.input_stats.octets, 1_500);
+        assert_eq!(interface.input_stats.crc_errors, Some(7));
+        assert_eq!(interface.output_stats.crc_errors, None);
+    }
+
     #[test]
     fn test_interface_admin_status_conversion() {
         assert_eq!(InterfaceAdminStatus::from(1), InterfaceAdminStatus::Up);
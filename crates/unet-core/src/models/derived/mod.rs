@@ -5,19 +5,31 @@
 //! actual state of network devices as discovered through monitoring.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::time::SystemTime;
 use uuid::Uuid;
 
 use crate::snmp::SnmpValue;
 
 // Re-export all public types for backward compatibility
+pub use self::contexts::*;
+pub use self::hardware::*;
+pub use self::history::*;
 pub use self::interfaces::*;
 pub use self::metrics::*;
+pub use self::neighbors::*;
+pub use self::rates::*;
+pub use self::software::*;
 pub use self::system::*;
 
+mod contexts;
+mod hardware;
+mod history;
 mod interfaces;
 mod metrics;
+mod neighbors;
+mod rates;
+mod software;
 mod system;
 
 /// Current status and derived state for a network node
@@ -47,6 +59,43 @@ pub struct NodeStatus {
     pub last_error: Option<String>,
     /// Number of consecutive polling failures
     pub consecutive_failures: u32,
+    /// Output of enrichment plugins, keyed by plugin name
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub enrichments: HashMap<String, serde_json::Value>,
+    /// Errors from enrichment plugins that failed on the last run, keyed by plugin name
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub enrichment_errors: HashMap<String, String>,
+    /// BGP peers reported by an HTTP collector
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bgp_peers: Vec<BgpPeer>,
+    /// LLDP neighbors reported by an HTTP collector
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub lldp_neighbors: Vec<LldpNeighbor>,
+    /// State of logical instances polled separately, such as VRFs or
+    /// virtual contexts, keyed by context name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub contexts: BTreeMap<String, ContextStatus>,
+}
+
+/// Interfaces in `snmp_data`, with rates computed against the same
+/// interfaces of the previous poll `elapsed` earlier
+fn polled_interfaces(
+    snmp_data: &HashMap<String, SnmpValue>,
+    previous_interfaces: &[InterfaceStatus],
+    elapsed: Option<std::time::Duration>,
+) -> Vec<InterfaceStatus> {
+    let mut interfaces = InterfaceStatus::from_snmp(snmp_data);
+    if let Some(elapsed) = elapsed {
+        for interface in &mut interfaces {
+            if let Some(previous) = previous_interfaces
+                .iter()
+                .find(|previous| previous.index == interface.index)
+            {
+                interface.compute_rates(previous, elapsed);
+            }
+        }
+    }
+    interfaces
 }
 
 impl NodeStatus {
@@ -66,13 +115,24 @@ impl NodeStatus {
             last_snmp_success: None,
             last_error: None,
             consecutive_failures: 0,
+            enrichments: HashMap::new(),
+            enrichment_errors: HashMap::new(),
+            bgp_peers: Vec::new(),
+            lldp_neighbors: Vec::new(),
+            contexts: BTreeMap::new(),
         }
     }
 
     /// Update status with successful SNMP poll result
     pub fn update_from_snmp(&mut self, snmp_data: HashMap<String, SnmpValue>) {
-        self.last_updated = SystemTime::now();
-        self.last_snmp_success = Some(SystemTime::now());
+        let now = SystemTime::now();
+        let elapsed = self
+            .last_snmp_success
+            .and_then(|previous| now.duration_since(previous).ok());
+        let previous_interfaces = std::mem::take(&mut self.interfaces);
+
+        self.last_updated = now;
+        self.last_snmp_success = Some(now);
         self.reachable = true;
         self.consecutive_failures = 0;
         self.last_error = None;
@@ -83,8 +143,8 @@ impl NodeStatus {
         // Extract system information
         self.system_info = SystemInfo::from_snmp(&snmp_data);
 
-        // Extract interface information
-        self.interfaces = InterfaceStatus::from_snmp(&snmp_data);
+        // Extract interface information and rates since the previous poll
+        self.interfaces = polled_interfaces(&snmp_data, &previous_interfaces, elapsed);
 
         // Extract performance metrics
         self.performance = PerformanceMetrics::from_snmp(&snmp_data);
@@ -111,6 +171,27 @@ impl NodeStatus {
         }
     }
 
+    /// Update the status of logical instance `context` with a successful
+    /// SNMP poll of it, leaving the node-level state alone
+    pub fn update_context_from_snmp(
+        &mut self,
+        context: &str,
+        snmp_data: HashMap<String, SnmpValue>,
+    ) {
+        self.contexts
+            .entry(context.to_string())
+            .or_insert_with(ContextStatus::new)
+            .update_from_snmp(snmp_data);
+    }
+
+    /// Mark a polling failure of logical instance `context`
+    pub fn mark_context_failure(&mut self, context: &str, error: String) {
+        self.contexts
+            .entry(context.to_string())
+            .or_insert_with(ContextStatus::new)
+            .mark_polling_failure(error);
+    }
+
     /// Check if node status is stale (hasn't been updated recently)
     #[must_use]
     pub fn is_stale(&self, max_age: std::time::Duration) -> bool {
@@ -231,6 +312,7 @@ mod tests {
             name: None,
             location: None,
             services: None,
+            software: None,
         });
 
         assert_eq!(status.uptime_seconds(), Some(1234));
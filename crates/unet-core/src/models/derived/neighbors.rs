@@ -0,0 +1,94 @@
+//! Routing and discovery neighbors of network devices
+
+use serde::{Deserialize, Serialize};
+
+/// State of a BGP session (RFC 4271 finite state machine)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BgpPeerState {
+    /// No session is being attempted
+    Idle,
+    /// Waiting for the TCP connection to complete
+    Connect,
+    /// Retrying the TCP connection
+    Active,
+    /// OPEN sent, waiting for the peer's
+    OpenSent,
+    /// OPEN received, waiting for a KEEPALIVE
+    OpenConfirm,
+    /// Session is up and exchanging routes
+    Established,
+    /// State the device reported is not recognized
+    Unknown,
+}
+
+impl BgpPeerState {
+    /// Reads a state as devices name it, e.g. `Established` or `fsm-established`
+    #[must_use]
+    pub fn from_name(name: &str) -> Self {
+        let name = name.to_ascii_lowercase().replace(['-', '_'], "");
+        match name.strip_prefix("fsm").unwrap_or(&name) {
+            "idle" => Self::Idle,
+            "connect" => Self::Connect,
+            "active" => Self::Active,
+            "opensent" => Self::OpenSent,
+            "openconfirm" => Self::OpenConfirm,
+            "established" => Self::Established,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// A BGP peer of a device
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BgpPeer {
+    /// Peer address
+    pub address: String,
+    /// VRF the session belongs to
+    pub vrf: String,
+    /// Peer autonomous system number
+    pub remote_as: Option<u32>,
+    /// Session state
+    pub state: BgpPeerState,
+    /// Prefixes received from the peer
+    pub prefixes_received: Option<u64>,
+}
+
+/// A device seen over LLDP on a local interface
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LldpNeighbor {
+    /// Local interface the neighbor was seen on
+    pub local_interface: String,
+    /// Neighbor chassis ID
+    pub chassis_id: Option<String>,
+    /// Neighbor system name
+    pub system_name: Option<String>,
+    /// Neighbor port ID, usually its interface name
+    pub port_id: Option<String>,
+    /// Neighbor port description
+    pub port_description: Option<String>,
+    /// Neighbor management address
+    pub management_address: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bgp_peer_state_from_name() {
+        assert_eq!(
+            BgpPeerState::from_name("Established"),
+            BgpPeerState::Established
+        );
+        assert_eq!(
+            BgpPeerState::from_name("fsm-opensent"),
+            BgpPeerState::OpenSent
+        );
+        assert_eq!(
+            BgpPeerState::from_name("OpenConfirm"),
+            BgpPeerState::OpenConfirm
+        );
+        assert_eq!(BgpPeerState::from_name("bogus"), BgpPeerState::Unknown);
+    }
+}
@@ -0,0 +1,352 @@
+//! Interface counter deltas and traffic rates
+//!
+//! SNMP interface counters are cumulative, so rates are computed from the
+//! difference between two polls. 32-bit counters wrap at 2^32 and are
+//! corrected for a single wrap; 64-bit (HC) counters from the ifXTable are
+//! preferred when the device reports them. A 64-bit counter that decreases
+//! is treated as a reset and produces no rate for that interval.
+//!
+//! Error counters (ifInErrors, ifOutErrors, and the EtherLike-MIB CRC
+//! counter) are always 32 bits wide over SNMP; their rates are reported per
+//! second and as a percentage of the packets seen in the same interval.
+
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+use super::{InterfaceStats, InterfaceStatus};
+use crate::snmp::{SnmpUnit, SnmpValue};
+
+/// ifHCInOctets (IF-MIB ifXTable)
+const IF_HC_IN_OCTETS: &str = "1.3.6.1.2.1.31.1.1.1.6";
+/// ifHCInUcastPkts
+const IF_HC_IN_UCAST_PKTS: &str = "1.3.6.1.2.1.31.1.1.1.7";
+/// ifHCOutOctets
+const IF_HC_OUT_OCTETS: &str = "1.3.6.1.2.1.31.1.1.1.10";
+/// ifHCOutUcastPkts
+const IF_HC_OUT_UCAST_PKTS: &str = "1.3.6.1.2.1.31.1.1.1.11";
+/// ifHighSpeed, in units of 1,000,000 bits per second
+const IF_HIGH_SPEED: &str = "1.3.6.1.2.1.31.1.1.1.15";
+
+/// Traffic rate over the interval between two polls
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TrafficRate {
+    /// Bits per second
+    pub bps: f64,
+    /// Packets per second
+    pub pps: f64,
+    /// Percentage of interface speed, if the speed is known
+    pub utilization: Option<f64>,
+    /// Seconds between the two polls
+    pub interval_seconds: f64,
+}
+
+/// Error rates over the interval between two polls
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ErrorRate {
+    /// Errored packets per second
+    pub errors_per_second: f64,
+    /// Errored packets as a percentage of all packets received or sent,
+    /// if any were
+    pub error_percent: Option<f64>,
+    /// CRC errors per second, if the interface reports them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crc_per_second: Option<f64>,
+}
+
+/// Value at which a 32-bit counter wraps to zero
+const COUNTER32_WRAP: u64 = 1 << 32;
+
+/// Computes the increase of a counter between two polls
+///
+/// Returns `None` for a decreasing 64-bit counter, which indicates a
+/// counter reset rather than a wrap.
+#[must_use]
+pub const fn counter_delta(previous: u64, current: u64, high_capacity: bool) -> Option<u64> {
+    if current >= previous {
+        Some(current - previous)
+    } else if high_capacity || previous >= COUNTER32_WRAP {
+        None
+    } else {
+        Some(COUNTER32_WRAP - previous + current)
+    }
+}
+
+impl InterfaceStats {
+    /// Computes the rate from a previous sample of the same counters
+    ///
+    /// Returns `None` if the interval is zero, the counter width changed, or
+    /// a counter was reset.
+    #[must_use]
+    pub fn rate_since(
+        &self,
+        previous: &Self,
+        elapsed: Duration,
+        speed: Option<u64>,
+    ) -> Option<TrafficRate> {
+        let seconds = elapsed.as_secs_f64();
+        if seconds <= 0.0 || self.high_capacity != previous.high_capacity {
+            return None;
+        }
+        let octets = counter_delta(previous.octets, self.octets, self.high_capacity)?;
+        let packets = counter_delta(previous.packets, self.packets, self.high_capacity)?;
+
+        let bps = octets.to_f64()? * 8.0 / seconds;
+        Some(TrafficRate {
+            bps,
+            pps: packets.to_f64()? / seconds,
+            utilization: speed
+                .filter(|speed| *speed > 0)
+                .and_then(|speed| speed.to_f64())
+                .map(|speed| (bps / speed * 100.0).min(100.0)),
+            interval_seconds: seconds,
+        })
+    }
+
+    /// Computes error rates from a previous sample of the same counters
+    ///
+    /// Returns `None` if the interval is zero or the error counter was
+    /// reset. The percentage is left out if the packet counter width
+    /// changed or was reset, and the CRC rate if either sample lacks it.
+    #[must_use]
+    pub fn error_rate_since(&self, previous: &Self, elapsed: Duration) -> Option<ErrorRate> {
+        let seconds = elapsed.as_secs_f64();
+        if seconds <= 0.0 {
+            return None;
+        }
+        let errors = counter_delta(previous.errors, self.errors, false)?;
+        let packets = (self.high_capacity == previous.high_capacity)
+            .then(|| counter_delta(previous.packets, self.packets, self.high_capacity))
+            .flatten();
+        let crc = match (previous.crc_errors, self.crc_errors) {
+            (Some(previous), Some(current)) => counter_delta(previous, current, false),
+            _ => None,
+        };
+
+        let error_count = errors.to_f64()?;
+        Some(ErrorRate {
+            errors_per_second: error_count / seconds,
+            error_percent: packets
+                .map(|packets| packets + errors)
+                .filter(|total| *total > 0)
+                .and_then(|total| total.to_f64())
+                .map(|total| error_count / total * 100.0),
+            crc_per_second: crc.and_then(|crc| crc.to_f64()).map(|crc| crc / seconds),
+        })
+    }
+}
+
+impl InterfaceStatus {
+    /// Sets input and output traffic and error rates from the previous poll
+    /// of this interface
+    pub fn compute_rates(&mut self, previous: &Self, elapsed: Duration) {
+        self.input_stats.rate =
+            self.input_stats
+                .rate_since(&previous.input_stats, elapsed, self.speed);
+        self.output_stats.rate =
+            self.output_stats
+                .rate_since(&previous.output_stats, elapsed, self.speed);
+        self.input_stats.error_rate = self
+            .input_stats
+            .error_rate_since(&previous.input_stats, elapsed);
+        self.output_stats.error_rate = self
+            .output_stats
+            .error_rate_since(&previous.output_stats, elapsed);
+    }
+}
+
+/// Replaces 32-bit counters and speed with ifXTable values when present
+pub(super) fn apply_high_capacity_counters(
+    interface: &mut InterfaceStatus,
+    snmp_data: &HashMap<String, SnmpValue>,
+) {
+    let counter = |base: &str| match snmp_data.get(&format!("{base}.{}", interface.index)) {
+        Some(SnmpValue::Counter64(value)) => Some(*value),
+        _ => None,
+    };
+
+    let in_octets = counter(IF_HC_IN_OCTETS);
+    let in_packets = counter(IF_HC_IN_UCAST_PKTS);
+    let out_octets = counter(IF_HC_OUT_OCTETS);
+    let out_packets = counter(IF_HC_OUT_UCAST_PKTS);
+
+    // Mixing widths within one direction would corrupt the deltas
+    if let (Some(octets), Some(packets)) = (in_octets, in_packets) {
+        set_counters(&mut interface.input_stats, octets, packets);
+    }
+    if let (Some(octets), Some(packets)) = (out_octets, out_packets) {
+        set_counters(&mut interface.output_stats, octets, packets);
+    }
+
+    // ifSpeed saturates at 4.29 Gbps; ifHighSpeed covers faster links
+    if let Some(bps) = snmp_data
+        .get(&format!("{IF_HIGH_SPEED}.{}", interface.index))
+        .and_then(|value| value.scaled(SnmpUnit::MegabitsPerSecond))
+        .and_then(|bps| bps.to_u64())
+    {
+        if bps > 0
+            && interface
+                .speed
+                .is_none_or(|speed| speed >= u64::from(u32::MAX))
+        {
+            interface.speed = Some(bps);
+        }
+    }
+}
+
+const fn set_counters(stats: &mut InterfaceStats, octets: u64, packets: u64) {
+    stats.octets = octets;
+    stats.packets = packets;
+    stats.high_capacity = true;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::derived::NodeStatus;
+    use std::time::SystemTime;
+
+    fn stats(octets: u64, packets: u64, high_capacity: bool) -> InterfaceStats {
+        InterfaceStats {
+            octets,
+            packets,
+            high_capacity,
+            ..InterfaceStats::default()
+        }
+    }
+
+    #[test]
+    fn test_counter_delta_handles_32_bit_wrap() {
+        assert_eq!(counter_delta(100, 250, false), Some(150));
+        assert_eq!(counter_delta(u64::from(u32::MAX) - 9, 10, false), Some(20));
+    }
+
+    #[test]
+    fn test_counter_delta_treats_64_bit_decrease_as_reset() {
+        assert_eq!(counter_delta(5_000_000_000, 10, true), None);
+        assert_eq!(counter_delta(5_000_000_000, 10, false), None);
+    }
+
+    #[test]
+    fn test_rate_since_computes_bps_pps_and_utilization() {
+        let previous = stats(1_000, 10, false);
+        let current = stats(1_000 + 125_000_000, 10 + 100_000, false);
+
+        let rate = current
+            .rate_since(&previous, Duration::from_secs(10), Some(1_000_000_000))
+            .unwrap();
+
+        assert!((rate.bps - 100_000_000.0).abs() < f64::EPSILON);
+        assert!((rate.pps - 10_000.0).abs() < f64::EPSILON);
+        assert!((rate.utilization.unwrap() - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rate_since_rejects_width_change_and_zero_interval() {
+        let previous = stats(1_000, 10, false);
+
+        assert!(
+            stats(2_000, 20, true)
+                .rate_since(&previous, Duration::from_secs(10), None)
+                .is_none()
+        );
+        assert!(
+            stats(2_000, 20, false)
+                .rate_since(&previous, Duration::ZERO, None)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_error_rate_since_computes_rates_and_percent() {
+        let previous = InterfaceStats {
+            errors: 100,
+            crc_errors: Some(40),
+            ..stats(0, 9_900, false)
+        };
+        let current = InterfaceStats {
+            errors: 150,
+            crc_errors: Some(60),
+            ..stats(0, 14_850, false)
+        };
+
+        let rate = current
+            .error_rate_since(&previous, Duration::from_secs(10))
+            .unwrap();
+
+        assert!((rate.errors_per_second - 5.0).abs() < f64::EPSILON);
+        assert!((rate.error_percent.unwrap() - 1.0).abs() < 1e-9);
+        assert!((rate.crc_per_second.unwrap() - 2.0).abs() < f64::EPSILON);
+
+        let without_crc = InterfaceStats {
+            crc_errors: None,
+            ..previous
+        };
+        let rate = current
+            .error_rate_since(&without_crc, Duration::from_secs(10))
+            .unwrap();
+        assert_eq!(rate.crc_per_second, None);
+    }
+
+    #[test]
+    fn test_high_capacity_counters_and_speed_are_preferred() {
+        let mut snmp_data = HashMap::new();
+        snmp_data.insert("1.3.6.1.2.1.2.2.1.1.3".to_string(), SnmpValue::Integer(3));
+        snmp_data.insert(
+            "1.3.6.1.2.1.2.2.1.5.3".to_string(),
+            SnmpValue::Gauge32(u32::MAX),
+        );
+        snmp_data.insert(
+            "1.3.6.1.2.1.2.2.1.10.3".to_string(),
+            SnmpValue::Counter32(42),
+        );
+        snmp_data.insert(
+            format!("{IF_HC_IN_OCTETS}.3"),
+            SnmpValue::Counter64(9_000_000_000),
+        );
+        snmp_data.insert(format!("{IF_HC_IN_UCAST_PKTS}.3"), SnmpValue::Counter64(7));
+        snmp_data.insert(format!("{IF_HIGH_SPEED}.3"), SnmpValue::Gauge32(100_000));
+
+        let interfaces = InterfaceStatus::from_snmp(&snmp_data);
+
+        let interface = &interfaces[0];
+        assert_eq!(interface.input_stats.octets, 9_000_000_000);
+        assert!(interface.input_stats.high_capacity);
+        assert!(!interface.output_stats.high_capacity);
+        assert_eq!(interface.speed, Some(100_000_000_000));
+    }
+
+    #[test]
+    fn test_update_from_snmp_computes_rates_across_wrap() {
+        let poll = |octets: u32, packets: u32| {
+            HashMap::from([
+                ("1.3.6.1.2.1.2.2.1.1.1".to_string(), SnmpValue::Integer(1)),
+                (
+                    "1.3.6.1.2.1.2.2.1.5.1".to_string(),
+                    SnmpValue::Gauge32(1_000_000),
+                ),
+                (
+                    "1.3.6.1.2.1.2.2.1.10.1".to_string(),
+                    SnmpValue::Counter32(octets),
+                ),
+                (
+                    "1.3.6.1.2.1.2.2.1.11.1".to_string(),
+                    SnmpValue::Counter32(packets),
+                ),
+            ])
+        };
+        let mut status = NodeStatus::new(uuid::Uuid::new_v4());
+
+        status.update_from_snmp(poll(u32::MAX - 999, 10));
+        assert!(status.interfaces[0].input_stats.rate.is_none());
+
+        status.last_snmp_success = Some(SystemTime::now() - Duration::from_secs(10));
+        status.update_from_snmp(poll(1_000, 30));
+
+        let rate = status.interfaces[0].input_stats.rate.unwrap();
+        assert!((rate.bps - 1_600.0).abs() < 1.0);
+        assert!((rate.pps - 2.0).abs() < 0.01);
+        assert!((rate.utilization.unwrap() - 0.16).abs() < 0.001);
+    }
+}
@@ -0,0 +1,228 @@
+//! Operating system details parsed from `sysDescr`
+//!
+//! Vendors put the OS name and version in `sysDescr` in their own formats.
+//! Each parser below recognizes one OS family and pulls out the version and,
+//! where the vendor has one, the release train, so versions can be compared
+//! across a fleet without knowing every vendor's wording.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+
+/// Operating system name, version, and release train of a device
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SoftwareVersion {
+    /// Operating system, e.g. `IOS-XE`, `NX-OS`, `Junos`, `EOS`, or `RouterOS`
+    pub os: String,
+    /// Version as the device reports it, e.g. `17.3.4` or `18.4R3-S4.2`
+    pub version: String,
+    /// Release train: the IOS train (`M`, `SE`), the IOS-XE release name
+    /// (`Amsterdam`), the `RouterOS` channel, or else the major.minor release
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub train: Option<String>,
+}
+
+/// Compiles one of the fixed patterns below
+fn pattern(source: &str) -> Regex {
+    Regex::new(source).expect("built-in sysDescr pattern is valid")
+}
+
+/// `Version 17.3.4,` as Cisco prints it
+static CISCO_VERSION: LazyLock<Regex> = LazyLock::new(|| pattern(r"Version\s+(\d[^\s,]*)"));
+static NXOS: LazyLock<Regex> = LazyLock::new(|| pattern(r"(?i)\bNX-OS\b"));
+static IOS_XE: LazyLock<Regex> = LazyLock::new(|| pattern(r"(?i)IOS[-_ ]?XE\b"));
+static IOS: LazyLock<Regex> = LazyLock::new(|| pattern(r"(?i)\bCisco IOS\b"));
+/// IOS-XE 16 and later name releases, e.g. `[Amsterdam]`
+static IOS_XE_RELEASE: LazyLock<Regex> = LazyLock::new(|| pattern(r"\[([A-Za-z]+)\]"));
+/// Classic IOS trains follow the maintenance release, e.g. `15.2(4)M7`
+static IOS_TRAIN: LazyLock<Regex> = LazyLock::new(|| pattern(r"^\d+\.\d+\(\d+\)([A-Z]+)"));
+static JUNOS: LazyLock<Regex> = LazyLock::new(|| pattern(r"(?i)\bJUNOS(?:\s+OS)?\s+(\d[^\s,\]]*)"));
+static EOS: LazyLock<Regex> = LazyLock::new(|| pattern(r"(?i)\bEOS\s+version\s+(\d[^\s,]*)"));
+static ROUTEROS: LazyLock<Regex> = LazyLock::new(|| {
+    pattern(r"(?i)\bRouterOS\s+v?(\d+\.\d+(?:\.\d+)*\S*)(?:\s+\(([a-z][a-z-]*)\))?")
+});
+static MAJOR_MINOR: LazyLock<Regex> = LazyLock::new(|| pattern(r"^(\d+\.\d+)"));
+
+/// The `major.minor` prefix of a version
+fn major_minor(version: &str) -> Option<String> {
+    MAJOR_MINOR
+        .captures(version)
+        .map(|captures| captures[1].to_string())
+}
+
+/// The first capture of `regex` in `descr`
+fn capture(regex: &Regex, descr: &str) -> Option<String> {
+    regex
+        .captures(descr)
+        .and_then(|captures| captures.get(1))
+        .map(|version| version.as_str().to_string())
+}
+
+fn nxos(descr: &str) -> Option<SoftwareVersion> {
+    if !NXOS.is_match(descr) {
+        return None;
+    }
+    let version = capture(&CISCO_VERSION, descr)?;
+    Some(SoftwareVersion {
+        os: "NX-OS".to_string(),
+        train: major_minor(&version),
+        version,
+    })
+}
+
+/// IOS-XE says so, or names its release the way only IOS-XE does
+fn ios_xe(descr: &str) -> Option<SoftwareVersion> {
+    if !IOS_XE.is_match(descr) && !(IOS.is_match(descr) && IOS_XE_RELEASE.is_match(descr)) {
+        return None;
+    }
+    let version = capture(&CISCO_VERSION, descr)?;
+    Some(SoftwareVersion {
+        os: "IOS-XE".to_string(),
+        train: capture(&IOS_XE_RELEASE, descr).or_else(|| major_minor(&version)),
+        version,
+    })
+}
+
+fn ios(descr: &str) -> Option<SoftwareVersion> {
+    if !IOS.is_match(descr) {
+        return None;
+    }
+    let version = capture(&CISCO_VERSION, descr)?;
+    Some(SoftwareVersion {
+        os: "IOS".to_string(),
+        train: capture(&IOS_TRAIN, &version),
+        version,
+    })
+}
+
+fn junos(descr: &str) -> Option<SoftwareVersion> {
+    let version = capture(&JUNOS, descr)?;
+    Some(SoftwareVersion {
+        os: "Junos".to_string(),
+        train: major_minor(&version),
+        version,
+    })
+}
+
+fn eos(descr: &str) -> Option<SoftwareVersion> {
+    let version = capture(&EOS, descr)?;
+    Some(SoftwareVersion {
+        os: "EOS".to_string(),
+        train: major_minor(&version),
+        version,
+    })
+}
+
+fn routeros(descr: &str) -> Option<SoftwareVersion> {
+    let captures = ROUTEROS.captures(descr)?;
+    let version = captures[1].to_string();
+    Some(SoftwareVersion {
+        os: "RouterOS".to_string(),
+        train: captures
+            .get(2)
+            .map(|channel| channel.as_str().to_lowercase())
+            .or_else(|| major_minor(&version)),
+        version,
+    })
+}
+
+/// Parsers in the order they are tried; IOS-XE descriptions also mention
+/// "Cisco IOS", so IOS comes after it
+const PARSERS: [fn(&str) -> Option<SoftwareVersion>; 6] = [nxos, ios_xe, ios, junos, eos, routeros];
+
+impl SoftwareVersion {
+    /// Parses the OS and version out of a `sysDescr` value
+    ///
+    /// Returns `None` for unrecognized operating systems, and for recognized
+    /// ones whose description carries no version (`RouterOS` usually names
+    /// only the board).
+    #[must_use]
+    pub fn from_sys_descr(descr: &str) -> Option<Self> {
+        PARSERS.iter().find_map(|parse| parse(descr))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parsed(descr: &str) -> (String, String, Option<String>) {
+        let software = SoftwareVersion::from_sys_descr(descr).expect("sysDescr should parse");
+        (software.os, software.version, software.train)
+    }
+
+    fn expect(descr: &str, os: &str, version: &str, train: Option<&str>) {
+        assert_eq!(
+            parsed(descr),
+            (
+                os.to_string(),
+                version.to_string(),
+                train.map(ToString::to_string)
+            ),
+            "{descr}"
+        );
+    }
+
+    #[test]
+    fn test_cisco_descriptions() {
+        expect(
+            "Cisco IOS Software [Amsterdam], Catalyst L3 Switch Software (CAT9K_IOSXE), Version 17.3.4, RELEASE SOFTWARE (fc3)",
+            "IOS-XE",
+            "17.3.4",
+            Some("Amsterdam"),
+        );
+        expect(
+            "Cisco IOS Software [Fuji], ASR1000 Software (X86_64_LINUX_IOSD-UNIVERSALK9-M), Version 16.9.4, RELEASE SOFTWARE (fc2)",
+            "IOS-XE",
+            "16.9.4",
+            Some("Fuji"),
+        );
+        expect(
+            "Cisco IOS Software, IOS-XE Software, Catalyst 4500 L3 Switch Software (cat4500e-UNIVERSALK9-M), Version 03.06.07.E RELEASE SOFTWARE (fc3)",
+            "IOS-XE",
+            "03.06.07.E",
+            Some("03.06"),
+        );
+        expect(
+            "Cisco IOS Software, C2960X Software (C2960X-UNIVERSALK9-M), Version 15.2(4)E10, RELEASE SOFTWARE (fc2)",
+            "IOS",
+            "15.2(4)E10",
+            Some("E"),
+        );
+        expect(
+            "Cisco NX-OS(tm) n9000, Software (n9000-dk9), Version 9.3(8), RELEASE SOFTWARE Copyright (c) 2002-2021 by Cisco Systems, Inc.",
+            "NX-OS",
+            "9.3(8)",
+            Some("9.3"),
+        );
+    }
+
+    #[test]
+    fn test_other_vendor_descriptions() {
+        expect(
+            "Juniper Networks, Inc. mx480 internet router, kernel JUNOS 18.4R3-S4.2, Build date: 2020-08-11 22:01:57 UTC",
+            "Junos",
+            "18.4R3-S4.2",
+            Some("18.4"),
+        );
+        expect(
+            "Arista Networks EOS version 4.25.4M running on an Arista Networks DCS-7280SR-48C6",
+            "EOS",
+            "4.25.4M",
+            Some("4.25"),
+        );
+        expect(
+            "RouterOS 6.49.7 (long-term) on CCR1036-8G-2S+",
+            "RouterOS",
+            "6.49.7",
+            Some("long-term"),
+        );
+    }
+
+    #[test]
+    fn test_unrecognized_descriptions() {
+        assert!(SoftwareVersion::from_sys_descr("RouterOS CCR1036-8G-2S+").is_none());
+        assert!(SoftwareVersion::from_sys_descr("Linux edge 5.15.0-91-generic").is_none());
+        assert!(SoftwareVersion::from_sys_descr("").is_none());
+    }
+}
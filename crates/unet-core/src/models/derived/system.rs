@@ -3,6 +3,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use super::SoftwareVersion;
 use crate::snmp::SnmpValue;
 
 /// System information derived from SNMP system group
@@ -22,6 +23,9 @@ pub struct SystemInfo {
     pub location: Option<String>,
     /// System services (sysServices)
     pub services: Option<u32>,
+    /// Operating system and version parsed from `description`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub software: Option<SoftwareVersion>,
 }
 
 impl SystemInfo {
@@ -36,11 +40,13 @@ impl SystemInfo {
             name: None,
             location: None,
             services: None,
+            software: None,
         };
 
         // Extract system description
         if let Some(SnmpValue::String(desc)) = snmp_data.get("1.3.6.1.2.1.1.1.0") {
             system_info.description = Some(desc.clone());
+            system_info.software = SoftwareVersion::from_sys_descr(desc);
         }
 
         // Extract system object ID
@@ -49,9 +55,10 @@ impl SystemInfo {
         }
 
         // Extract system uptime
-        if let Some(SnmpValue::TimeTicks(ticks)) = snmp_data.get("1.3.6.1.2.1.1.3.0") {
-            system_info.uptime_ticks = Some(*ticks);
-        }
+        system_info.uptime_ticks = snmp_data
+            .get("1.3.6.1.2.1.1.3.0")
+            .and_then(SnmpValue::as_u64)
+            .and_then(|ticks| u32::try_from(ticks).ok());
 
         // Extract system contact
         if let Some(SnmpValue::String(contact)) = snmp_data.get("1.3.6.1.2.1.1.4.0") {
@@ -69,11 +76,10 @@ impl SystemInfo {
         }
 
         // Extract system services
-        if let Some(SnmpValue::Integer(services)) = snmp_data.get("1.3.6.1.2.1.1.7.0") {
-            if let Ok(services_u32) = u32::try_from(*services) {
-                system_info.services = Some(services_u32);
-            }
-        }
+        system_info.services = snmp_data
+            .get("1.3.6.1.2.1.1.7.0")
+            .and_then(SnmpValue::as_u64)
+            .and_then(|services| u32::try_from(services).ok());
 
         if system_info.description.is_some()
             || system_info.object_id.is_some()
@@ -120,6 +126,23 @@ mod tests {
         assert_eq!(info.name, Some("test-router".to_string()));
     }
 
+    #[test]
+    fn test_system_info_parses_software_version() {
+        let mut snmp_data = HashMap::new();
+        snmp_data.insert(
+            "1.3.6.1.2.1.1.1.0".to_string(),
+            SnmpValue::String(
+                "Arista Networks EOS version 4.28.3M running on an Arista Networks DCS-7050SX3-48YC8"
+                    .to_string(),
+            ),
+        );
+
+        let software = SystemInfo::from_snmp(&snmp_data).unwrap().software.unwrap();
+        assert_eq!(software.os, "EOS");
+        assert_eq!(software.version, "4.28.3M");
+        assert_eq!(software.train.as_deref(), Some("4.28"));
+    }
+
     #[test]
     fn test_system_info_from_snmp_with_services_only() {
         let mut snmp_data = HashMap::new();
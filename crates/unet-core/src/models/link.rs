@@ -1,12 +1,87 @@
 //! Link model and implementation
 //!
 //! Contains the core `Link` struct representing network connections between devices
-//! and the `LinkBuilder` for creating links with validation.
+//! and the `ProvisioningState` of circuits.
 
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
 use uuid::Uuid;
 
+/// Where a link is in provisioning
+///
+/// Circuits move forward one state at a time (ordered → delivered → tested →
+/// live) and may return to any earlier state, e.g. when acceptance testing
+/// fails. Links without a recorded state are live.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ProvisioningState {
+    /// Ordered from the provider
+    Ordered,
+    /// Delivered by the provider
+    Delivered,
+    /// Tested end to end
+    Tested,
+    /// Carrying traffic
+    #[default]
+    Live,
+}
+
+impl ProvisioningState {
+    /// Every state, in provisioning order
+    pub const ALL: [Self; 4] = [Self::Ordered, Self::Delivered, Self::Tested, Self::Live];
+
+    /// The state that follows this one, if any
+    #[must_use]
+    pub const fn next(self) -> Option<Self> {
+        match self {
+            Self::Ordered => Some(Self::Delivered),
+            Self::Delivered => Some(Self::Tested),
+            Self::Tested => Some(Self::Live),
+            Self::Live => None,
+        }
+    }
+
+    /// Checks that a link may move from this state to `target`
+    ///
+    /// # Errors
+    /// Returns an error if `target` skips a state.
+    pub fn transition_to(self, target: Self) -> Result<Self, String> {
+        match self.next() {
+            Some(next) if target > next => Err(format!(
+                "Cannot move link from {self} to {target}; next is {next}"
+            )),
+            _ => Ok(target),
+        }
+    }
+}
+
+impl Display for ProvisioningState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Ordered => write!(f, "ordered"),
+            Self::Delivered => write!(f, "delivered"),
+            Self::Tested => write!(f, "tested"),
+            Self::Live => write!(f, "live"),
+        }
+    }
+}
+
+impl FromStr for ProvisioningState {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|state| state.to_string() == s.trim().to_lowercase())
+            .ok_or_else(|| format!("Invalid provisioning state: {s}"))
+    }
+}
+
 /// Network link/connection between nodes
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Link {
@@ -30,6 +105,17 @@ pub struct Link {
     pub link_type: Option<String>,
     /// Whether this is an internet circuit
     pub is_internet_circuit: bool,
+    /// Service provider of the circuit
+    pub provider: Option<String>,
+    /// Provider circuit identifier
+    pub circuit_id: Option<String>,
+    /// Date the circuit was ordered
+    pub ordered_on: Option<NaiveDate>,
+    /// Date the provider delivered the circuit
+    pub delivered_on: Option<NaiveDate>,
+    /// Where the link is in provisioning
+    #[serde(default)]
+    pub provisioning_state: ProvisioningState,
     /// Extended/custom data as JSON
     pub custom_data: Value,
 }
@@ -55,6 +141,11 @@ impl Link {
             bandwidth: None,
             link_type: None,
             is_internet_circuit: false,
+            provider: None,
+            circuit_id: None,
+            ordered_on: None,
+            delivered_on: None,
+            provisioning_state: ProvisioningState::Live,
             custom_data: Value::Null,
         }
     }
@@ -77,6 +168,11 @@ impl Link {
             bandwidth: None,
             link_type: None,
             is_internet_circuit: true,
+            provider: None,
+            circuit_id: None,
+            ordered_on: None,
+            delivered_on: None,
+            provisioning_state: ProvisioningState::Live,
             custom_data: Value::Null,
         }
     }
@@ -85,13 +181,37 @@ impl Link {
     ///
     /// # Errors
     /// Returns an error if the link name is empty, node interfaces are empty,
-    /// or if interface names have invalid format.
+    /// interface names have invalid format, the provider or circuit ID is
+    /// blank, or the circuit was delivered before it was ordered.
     pub fn validate(&self) -> Result<(), String> {
         // Validate name
         if self.name.is_empty() {
             return Err("Link name cannot be empty".to_string());
         }
 
+        // Validate circuit metadata
+        if self
+            .provider
+            .as_deref()
+            .is_some_and(|p| p.trim().is_empty())
+        {
+            return Err("Provider cannot be empty".to_string());
+        }
+        if self
+            .circuit_id
+            .as_deref()
+            .is_some_and(|c| c.trim().is_empty())
+        {
+            return Err("Circuit ID cannot be empty".to_string());
+        }
+        if let (Some(ordered), Some(delivered)) = (self.ordered_on, self.delivered_on) {
+            if delivered < ordered {
+                return Err(format!(
+                    "Circuit cannot be delivered ({delivered}) before it was ordered ({ordered})"
+                ));
+            }
+        }
+
         // Validate node A interface
         if self.node_a_interface.is_empty() {
             return Err("Node A interface cannot be empty".to_string());
@@ -234,146 +354,3 @@ impl Link {
         Ok(())
     }
 }
-
-/// Builder pattern for Link creation with validation
-#[derive(Debug, Default)]
-pub struct LinkBuilder {
-    /// Link ID (optional, will generate UUID if not provided)
-    id: Option<Uuid>,
-    /// Link name (required)
-    name: Option<String>,
-    /// Source node ID (required)
-    source_node_id: Option<Uuid>,
-    /// Node A interface (required)
-    node_a_interface: Option<String>,
-    /// Destination node ID (optional for internet circuits)
-    dest_node_id: Option<Uuid>,
-    /// Node Z interface (optional for internet circuits)
-    node_z_interface: Option<String>,
-    /// Link description (optional)
-    description: Option<String>,
-    /// Link bandwidth (optional)
-    bandwidth: Option<u64>,
-    /// Link type (optional)
-    link_type: Option<String>,
-    /// Whether this is an internet circuit (optional)
-    is_internet_circuit: Option<bool>,
-    /// Custom data (optional)
-    custom_data: Option<Value>,
-}
-
-impl LinkBuilder {
-    /// Creates a new link builder
-    #[must_use]
-    pub fn new() -> Self {
-        Self::default()
-    }
-
-    /// Sets the link ID (optional, will generate UUID if not provided)
-    #[must_use]
-    pub const fn id(mut self, id: Uuid) -> Self {
-        self.id = Some(id);
-        self
-    }
-
-    /// Sets the link name (required)
-    #[must_use]
-    pub fn name<S: Into<String>>(mut self, name: S) -> Self {
-        self.name = Some(name.into());
-        self
-    }
-
-    /// Sets node A ID (required)
-    #[must_use]
-    pub const fn source_node_id(mut self, source_node_id: Uuid) -> Self {
-        self.source_node_id = Some(source_node_id);
-        self
-    }
-
-    /// Sets node A interface (required)
-    #[must_use]
-    pub fn node_a_interface<S: Into<String>>(mut self, interface: S) -> Self {
-        self.node_a_interface = Some(interface.into());
-        self
-    }
-
-    /// Sets node Z ID (optional for internet circuits)
-    #[must_use]
-    pub const fn dest_node_id(mut self, dest_node_id: Uuid) -> Self {
-        self.dest_node_id = Some(dest_node_id);
-        self
-    }
-
-    /// Sets node Z interface (optional for internet circuits)
-    #[must_use]
-    pub fn node_z_interface<S: Into<String>>(mut self, interface: S) -> Self {
-        self.node_z_interface = Some(interface.into());
-        self
-    }
-
-    /// Sets the description (optional)
-    #[must_use]
-    pub fn description<S: Into<String>>(mut self, description: S) -> Self {
-        self.description = Some(description.into());
-        self
-    }
-
-    /// Sets the bandwidth (optional)
-    #[must_use]
-    pub const fn bandwidth(mut self, bandwidth: u64) -> Self {
-        self.bandwidth = Some(bandwidth);
-        self
-    }
-
-    /// Sets the link type (optional)
-    #[must_use]
-    pub fn link_type<S: Into<String>>(mut self, link_type: S) -> Self {
-        self.link_type = Some(link_type.into());
-        self
-    }
-
-    /// Sets whether this is an internet circuit (optional, defaults to false)
-    #[must_use]
-    pub const fn is_internet_circuit(mut self, is_internet_circuit: bool) -> Self {
-        self.is_internet_circuit = Some(is_internet_circuit);
-        self
-    }
-
-    /// Sets custom data (optional)
-    #[must_use]
-    pub fn custom_data(mut self, custom_data: Value) -> Self {
-        self.custom_data = Some(custom_data);
-        self
-    }
-
-    /// Builds the link with validation
-    ///
-    /// # Errors
-    /// Returns an error if required fields (name, `source_node_id`, `node_a_interface`) are missing,
-    /// or if the created link fails validation.
-    pub fn build(self) -> Result<Link, String> {
-        let name = self.name.ok_or("Name is required")?;
-        let source_node_id = self.source_node_id.ok_or("Node A ID is required")?;
-        let node_a_interface = self
-            .node_a_interface
-            .ok_or("Node A interface is required")?;
-        let is_internet_circuit = self.is_internet_circuit.unwrap_or(false);
-
-        let link = Link {
-            id: self.id.unwrap_or_else(Uuid::new_v4),
-            name,
-            source_node_id,
-            node_a_interface,
-            dest_node_id: self.dest_node_id,
-            node_z_interface: self.node_z_interface,
-            description: self.description,
-            bandwidth: self.bandwidth,
-            link_type: self.link_type,
-            is_internet_circuit,
-            custom_data: self.custom_data.unwrap_or(Value::Null),
-        };
-
-        link.validate()?;
-        Ok(link)
-    }
-}
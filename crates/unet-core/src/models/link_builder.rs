@@ -0,0 +1,202 @@
+//! Link builder for creating links with validation
+//!
+//! Provides a builder pattern for constructing `Link` instances with proper
+//! validation and error handling.
+
+use crate::models::{Link, ProvisioningState};
+use chrono::NaiveDate;
+use serde_json::Value;
+use uuid::Uuid;
+
+/// Builder pattern for Link creation with validation
+#[derive(Debug, Default)]
+pub struct LinkBuilder {
+    /// Link ID (optional, will generate UUID if not provided)
+    id: Option<Uuid>,
+    /// Link name (required)
+    name: Option<String>,
+    /// Source node ID (required)
+    source_node_id: Option<Uuid>,
+    /// Node A interface (required)
+    node_a_interface: Option<String>,
+    /// Destination node ID (optional for internet circuits)
+    dest_node_id: Option<Uuid>,
+    /// Node Z interface (optional for internet circuits)
+    node_z_interface: Option<String>,
+    /// Link description (optional)
+    description: Option<String>,
+    /// Link bandwidth (optional)
+    bandwidth: Option<u64>,
+    /// Link type (optional)
+    link_type: Option<String>,
+    /// Whether this is an internet circuit (optional)
+    is_internet_circuit: Option<bool>,
+    /// Service provider (optional)
+    provider: Option<String>,
+    /// Provider circuit identifier (optional)
+    circuit_id: Option<String>,
+    /// Order date (optional)
+    ordered_on: Option<NaiveDate>,
+    /// Delivery date (optional)
+    delivered_on: Option<NaiveDate>,
+    /// Provisioning state (optional, defaults to live)
+    provisioning_state: Option<ProvisioningState>,
+    /// Custom data (optional)
+    custom_data: Option<Value>,
+}
+
+impl LinkBuilder {
+    /// Creates a new link builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the link ID (optional, will generate UUID if not provided)
+    #[must_use]
+    pub const fn id(mut self, id: Uuid) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Sets the link name (required)
+    #[must_use]
+    pub fn name<S: Into<String>>(mut self, name: S) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets node A ID (required)
+    #[must_use]
+    pub const fn source_node_id(mut self, source_node_id: Uuid) -> Self {
+        self.source_node_id = Some(source_node_id);
+        self
+    }
+
+    /// Sets node A interface (required)
+    #[must_use]
+    pub fn node_a_interface<S: Into<String>>(mut self, interface: S) -> Self {
+        self.node_a_interface = Some(interface.into());
+        self
+    }
+
+    /// Sets node Z ID (optional for internet circuits)
+    #[must_use]
+    pub const fn dest_node_id(mut self, dest_node_id: Uuid) -> Self {
+        self.dest_node_id = Some(dest_node_id);
+        self
+    }
+
+    /// Sets node Z interface (optional for internet circuits)
+    #[must_use]
+    pub fn node_z_interface<S: Into<String>>(mut self, interface: S) -> Self {
+        self.node_z_interface = Some(interface.into());
+        self
+    }
+
+    /// Sets the description (optional)
+    #[must_use]
+    pub fn description<S: Into<String>>(mut self, description: S) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets the bandwidth (optional)
+    #[must_use]
+    pub const fn bandwidth(mut self, bandwidth: u64) -> Self {
+        self.bandwidth = Some(bandwidth);
+        self
+    }
+
+    /// Sets the link type (optional)
+    #[must_use]
+    pub fn link_type<S: Into<String>>(mut self, link_type: S) -> Self {
+        self.link_type = Some(link_type.into());
+        self
+    }
+
+    /// Sets whether this is an internet circuit (optional, defaults to false)
+    #[must_use]
+    pub const fn is_internet_circuit(mut self, is_internet_circuit: bool) -> Self {
+        self.is_internet_circuit = Some(is_internet_circuit);
+        self
+    }
+
+    /// Sets the service provider (optional)
+    #[must_use]
+    pub fn provider<S: Into<String>>(mut self, provider: S) -> Self {
+        self.provider = Some(provider.into());
+        self
+    }
+
+    /// Sets the provider circuit identifier (optional)
+    #[must_use]
+    pub fn circuit_id<S: Into<String>>(mut self, circuit_id: S) -> Self {
+        self.circuit_id = Some(circuit_id.into());
+        self
+    }
+
+    /// Sets the order date (optional)
+    #[must_use]
+    pub const fn ordered_on(mut self, ordered_on: NaiveDate) -> Self {
+        self.ordered_on = Some(ordered_on);
+        self
+    }
+
+    /// Sets the delivery date (optional)
+    #[must_use]
+    pub const fn delivered_on(mut self, delivered_on: NaiveDate) -> Self {
+        self.delivered_on = Some(delivered_on);
+        self
+    }
+
+    /// Sets the provisioning state (optional, defaults to live)
+    #[must_use]
+    pub const fn provisioning_state(mut self, state: ProvisioningState) -> Self {
+        self.provisioning_state = Some(state);
+        self
+    }
+
+    /// Sets custom data (optional)
+    #[must_use]
+    pub fn custom_data(mut self, custom_data: Value) -> Self {
+        self.custom_data = Some(custom_data);
+        self
+    }
+
+    /// Builds the link with validation
+    ///
+    /// # Errors
+    /// Returns an error if required fields (name, `source_node_id`, `node_a_interface`) are missing,
+    /// or if the created link fails validation.
+    pub fn build(self) -> Result<Link, String> {
+        let name = self.name.ok_or("Name is required")?;
+        let source_node_id = self.source_node_id.ok_or("Node A ID is required")?;
+        let node_a_interface = self
+            .node_a_interface
+            .ok_or("Node A interface is required")?;
+        let is_internet_circuit = self.is_internet_circuit.unwrap_or(false);
+
+        let link = Link {
+            id: self.id.unwrap_or_else(Uuid::new_v4),
+            name,
+            source_node_id,
+            node_a_interface,
+            dest_node_id: self.dest_node_id,
+            node_z_interface: self.node_z_interface,
+            description: self.description,
+            bandwidth: self.bandwidth,
+            link_type: self.link_type,
+            is_internet_circuit,
+            provider: self.provider,
+            circuit_id: self.circuit_id,
+            ordered_on: self.ordered_on,
+            delivered_on: self.delivered_on,
+            provisioning_state: self.provisioning_state.unwrap_or_default(),
+            custom_data: self.custom_data.unwrap_or(Value::Null),
+        };
+
+        link.validate()?;
+        Ok(link)
+    }
+}
@@ -0,0 +1,145 @@
+//! Structured postal addresses and geographic coordinates for locations
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Postal address split into its components
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PostalAddress {
+    /// Street and number (e.g., "1 Infinite Loop")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub street: Option<String>,
+    /// City or locality
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    /// State, province, or region
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+    /// Postal or ZIP code
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub postal_code: Option<String>,
+    /// Country name or ISO 3166 code
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+}
+
+impl PostalAddress {
+    /// Returns true if no component is set
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.street.is_none()
+            && self.city.is_none()
+            && self.region.is_none()
+            && self.postal_code.is_none()
+            && self.country.is_none()
+    }
+
+    /// Copies the components that are set in `other` into this address
+    pub fn merge(&mut self, other: Self) {
+        let Self {
+            street,
+            city,
+            region,
+            postal_code,
+            country,
+        } = other;
+        self.street = street.or_else(|| self.street.take());
+        self.city = city.or_else(|| self.city.take());
+        self.region = region.or_else(|| self.region.take());
+        self.postal_code = postal_code.or_else(|| self.postal_code.take());
+        self.country = country.or_else(|| self.country.take());
+    }
+
+    /// Formats the address on one line, as sent to geocoding providers
+    #[must_use]
+    pub fn to_single_line(&self) -> String {
+        let region = match (&self.region, &self.postal_code) {
+            (Some(region), Some(postal_code)) => Some(format!("{region} {postal_code}")),
+            (Some(part), None) | (None, Some(part)) => Some(part.clone()),
+            (None, None) => None,
+        };
+        [
+            self.street.clone(),
+            self.city.clone(),
+            region,
+            self.country.clone(),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|part| !part.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join(", ")
+    }
+}
+
+/// WGS 84 latitude and longitude in decimal degrees
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Coordinates {
+    /// Latitude, -90 to 90
+    pub latitude: f64,
+    /// Longitude, -180 to 180
+    pub longitude: f64,
+}
+
+impl Coordinates {
+    /// Creates coordinates after checking their range
+    ///
+    /// # Errors
+    /// Returns an error if the latitude or longitude is out of range or not finite.
+    pub fn new(latitude: f64, longitude: f64) -> Result<Self, String> {
+        let coordinates = Self {
+            latitude,
+            longitude,
+        };
+        coordinates.validate()?;
+        Ok(coordinates)
+    }
+
+    /// Checks that the latitude and longitude are in range
+    ///
+    /// # Errors
+    /// Returns an error if the latitude or longitude is out of range or not finite.
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.latitude.is_finite() || !(-90.0..=90.0).contains(&self.latitude) {
+            return Err(format!(
+                "Latitude must be between -90 and 90, got {}",
+                self.latitude
+            ));
+        }
+        if !self.longitude.is_finite() || !(-180.0..=180.0).contains(&self.longitude) {
+            return Err(format!(
+                "Longitude must be between -180 and 180, got {}",
+                self.longitude
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Formats as `latitude,longitude`, the format stored in the database
+impl fmt::Display for Coordinates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.latitude, self.longitude)
+    }
+}
+
+/// Parses `latitude,longitude`
+impl FromStr for Coordinates {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (latitude, longitude) = s
+            .split_once(',')
+            .ok_or_else(|| format!("Coordinates must be 'latitude,longitude', got '{s}'"))?;
+        let latitude = latitude
+            .trim()
+            .parse()
+            .map_err(|e| format!("Invalid latitude '{latitude}': {e}"))?;
+        let longitude = longitude
+            .trim()
+            .parse()
+            .map_err(|e| format!("Invalid longitude '{longitude}': {e}"))?;
+        Self::new(latitude, longitude)
+    }
+}
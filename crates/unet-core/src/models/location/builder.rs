@@ -1,5 +1,6 @@
 //! `LocationBuilder` implementation for creating locations with validation
 
+use super::address::{Coordinates, PostalAddress};
 use super::model::Location;
 use serde_json::Value;
 use uuid::Uuid;
@@ -21,6 +22,12 @@ pub struct LocationBuilder {
     description: Option<String>,
     /// Address (optional)
     address: Option<String>,
+    /// Structured postal address (optional)
+    postal_address: Option<PostalAddress>,
+    /// Coordinates (optional)
+    coordinates: Option<Coordinates>,
+    /// IANA timezone (optional)
+    timezone: Option<String>,
     /// Custom data (optional)
     custom_data: Option<Value>,
 }
@@ -81,6 +88,27 @@ impl LocationBuilder {
         self
     }
 
+    /// Sets the structured postal address (optional)
+    #[must_use]
+    pub fn postal_address(mut self, postal_address: PostalAddress) -> Self {
+        self.postal_address = Some(postal_address);
+        self
+    }
+
+    /// Sets the coordinates (optional)
+    #[must_use]
+    pub const fn coordinates(mut self, coordinates: Coordinates) -> Self {
+        self.coordinates = Some(coordinates);
+        self
+    }
+
+    /// Sets the IANA timezone (optional)
+    #[must_use]
+    pub fn timezone<S: Into<String>>(mut self, timezone: S) -> Self {
+        self.timezone = Some(timezone.into());
+        self
+    }
+
     /// Sets custom data (optional)
     #[must_use]
     pub fn custom_data(mut self, custom_data: Value) -> Self {
@@ -112,6 +140,9 @@ impl LocationBuilder {
             path,
             description: self.description,
             address: self.address,
+            postal_address: self.postal_address,
+            coordinates: self.coordinates,
+            timezone: self.timezone,
             custom_data: self.custom_data.unwrap_or(Value::Null),
         };
 
@@ -0,0 +1,91 @@
+//! Pluggable geocoding of location addresses
+//!
+//! μNet does not ship a geocoding service. Deployments implement
+//! [`Geocoder`] for the provider they use (an internal GIS, Nominatim, a
+//! commercial API) and call [`geocode_location`] when a location is created
+//! or its address changes.
+
+use async_trait::async_trait;
+use thiserror::Error;
+
+use super::address::{Coordinates, PostalAddress};
+use super::model::Location;
+
+/// Errors from geocoding a location
+#[derive(Error, Debug)]
+pub enum GeocodingError {
+    /// The location has no structured address to look up
+    #[error("Location '{0}' has no postal address to geocode")]
+    NoAddress(String),
+
+    /// The provider returned coordinates outside the valid range
+    #[error("Geocoder '{provider}' returned invalid coordinates: {message}")]
+    InvalidResult {
+        /// Provider name
+        provider: String,
+        /// Validation error
+        message: String,
+    },
+
+    /// The provider could not be reached or rejected the request
+    #[error("Geocoder '{provider}' failed: {message}")]
+    Provider {
+        /// Provider name
+        provider: String,
+        /// Provider error
+        message: String,
+    },
+}
+
+/// Resolves postal addresses to coordinates
+#[async_trait]
+pub trait Geocoder: Send + Sync {
+    /// Provider name used in errors and logs
+    fn name(&self) -> &str;
+
+    /// Looks up an address
+    ///
+    /// Returns `Ok(None)` if the provider has no match for the address.
+    ///
+    /// # Errors
+    /// Returns [`GeocodingError::Provider`] if the lookup fails.
+    async fn geocode(&self, address: &PostalAddress)
+    -> Result<Option<Coordinates>, GeocodingError>;
+}
+
+/// Fills in a location's coordinates from its postal address
+///
+/// Existing coordinates are kept unless `overwrite` is true. Returns true if
+/// the coordinates were changed.
+///
+/// # Errors
+/// Returns an error if the location has no postal address, the provider
+/// fails, or the provider returns out-of-range coordinates.
+pub async fn geocode_location(
+    geocoder: &dyn Geocoder,
+    location: &mut Location,
+    overwrite: bool,
+) -> Result<bool, GeocodingError> {
+    if location.coordinates.is_some() && !overwrite {
+        return Ok(false);
+    }
+    let address = location
+        .postal_address
+        .as_ref()
+        .filter(|address| !address.is_empty())
+        .ok_or_else(|| GeocodingError::NoAddress(location.name.clone()))?;
+
+    let Some(coordinates) = geocoder.geocode(address).await? else {
+        return Ok(false);
+    };
+    coordinates
+        .validate()
+        .map_err(|message| GeocodingError::InvalidResult {
+            provider: geocoder.name().to_string(),
+            message,
+        })?;
+
+    let changed = location.coordinates != Some(coordinates);
+    location.coordinates = Some(coordinates);
+    Ok(changed)
+}
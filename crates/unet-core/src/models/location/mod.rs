@@ -1,11 +1,18 @@
 //! Location model and implementation
 //!
 //! Contains the core `Location` struct representing hierarchical locations
-//! and the `LocationBuilder` for creating locations with validation.
+//! and the `LocationBuilder` for creating locations with validation, along
+//! with structured addresses, geocoding, and site timezones.
 
+pub mod address;
 pub mod builder;
+pub mod geocoding;
 pub mod model;
+pub mod timezone;
 
 // Re-export the main types for backward compatibility
+pub use address::{Coordinates, PostalAddress};
 pub use builder::LocationBuilder;
+pub use geocoding::{Geocoder, GeocodingError, geocode_location};
 pub use model::Location;
+pub use timezone::{local_to_utc, next_local_occurrence, parse_timezone};
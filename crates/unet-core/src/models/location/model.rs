@@ -4,8 +4,11 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use uuid::Uuid;
 
+use super::address::{Coordinates, PostalAddress};
+use super::timezone::parse_timezone;
+
 /// Physical or logical location in a hierarchy
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Location {
     /// Unique identifier for the location
     pub id: Uuid,
@@ -19,8 +22,17 @@ pub struct Location {
     pub path: String,
     /// Description of the location
     pub description: Option<String>,
-    /// Address or coordinates
+    /// Free-form address
     pub address: Option<String>,
+    /// Structured postal address
+    #[serde(default)]
+    pub postal_address: Option<PostalAddress>,
+    /// Latitude and longitude, entered or filled in by a geocoder
+    #[serde(default)]
+    pub coordinates: Option<Coordinates>,
+    /// IANA timezone (e.g., "America/Chicago") used for local-time schedules
+    #[serde(default)]
+    pub timezone: Option<String>,
     /// Extended/custom data as JSON
     pub custom_data: Value,
 }
@@ -37,6 +49,9 @@ impl Location {
             path: name,
             description: None,
             address: None,
+            postal_address: None,
+            coordinates: None,
+            timezone: None,
             custom_data: Value::Null,
         }
     }
@@ -58,6 +73,9 @@ impl Location {
             path,
             description: None,
             address: None,
+            postal_address: None,
+            coordinates: None,
+            timezone: None,
             custom_data: Value::Null,
         }
     }
@@ -66,7 +84,8 @@ impl Location {
     ///
     /// # Errors
     /// Returns an error if the location name or type is empty, path is inconsistent,
-    /// or hierarchy constraints are violated.
+    /// hierarchy constraints are violated, coordinates are out of range, or the
+    /// timezone is unknown.
     pub fn validate(&self) -> Result<(), String> {
         // Validate name
         if self.name.is_empty() {
@@ -93,6 +112,14 @@ impl Location {
             return Err("Location path must end with location name".to_string());
         }
 
+        if let Some(coordinates) = &self.coordinates {
+            coordinates.validate()?;
+        }
+
+        if let Some(timezone) = &self.timezone {
+            parse_timezone(timezone)?;
+        }
+
         Ok(())
     }
 
@@ -0,0 +1,75 @@
+//! Site-local time for locations
+//!
+//! Schedules such as maintenance windows and reports are expressed in the
+//! wall-clock time of the site ("02:00 local"). These helpers resolve a
+//! location's IANA timezone and convert local times to UTC, accounting for
+//! daylight saving transitions.
+
+use chrono::{DateTime, Duration, NaiveDateTime, NaiveTime, Offset, TimeZone, Utc};
+use chrono_tz::Tz;
+
+use super::model::Location;
+
+/// Parses an IANA timezone name such as `Europe/Berlin`
+///
+/// # Errors
+/// Returns an error if the name is not in the IANA timezone database.
+pub fn parse_timezone(name: &str) -> Result<Tz, String> {
+    name.parse::<Tz>().map_err(|_| {
+        format!("Unknown timezone '{name}' (expected an IANA name such as 'America/New_York')")
+    })
+}
+
+/// Converts a wall-clock time at a site to UTC
+///
+/// A time repeated when clocks go back resolves to its first occurrence. A
+/// time skipped when clocks go forward is shifted forward by the length of
+/// the gap, so 02:30 on the night clocks jump from 02:00 to 03:00 becomes
+/// 03:30.
+#[must_use]
+pub fn local_to_utc(tz: Tz, local: NaiveDateTime) -> DateTime<Utc> {
+    if let Some(resolved) = tz.from_local_datetime(&local).earliest() {
+        return resolved.with_timezone(&Utc);
+    }
+    // Transitions are months apart, so the offset a day earlier is the one
+    // in effect before the gap
+    let before = tz
+        .offset_from_utc_datetime(&(local - Duration::days(1)))
+        .fix()
+        .local_minus_utc();
+    Utc.from_utc_datetime(&(local - Duration::seconds(i64::from(before))))
+}
+
+/// Returns the first UTC instant after `after` whose local time is `time`
+#[must_use]
+pub fn next_local_occurrence(tz: Tz, time: NaiveTime, after: DateTime<Utc>) -> DateTime<Utc> {
+    let mut date = after.with_timezone(&tz).date_naive();
+    loop {
+        let candidate = local_to_utc(tz, date.and_time(time));
+        match date.succ_opt() {
+            Some(next) if candidate <= after => date = next,
+            _ => return candidate,
+        }
+    }
+}
+
+impl Location {
+    /// Parses this location's own timezone
+    ///
+    /// # Errors
+    /// Returns an error if the timezone is set but unknown.
+    pub fn tz(&self) -> Result<Option<Tz>, String> {
+        self.timezone.as_deref().map(parse_timezone).transpose()
+    }
+
+    /// Returns the timezone of this location or of its nearest ancestor
+    ///
+    /// Sites usually set the timezone once on a building or campus; floors
+    /// and racks inherit it. Unknown timezone names are skipped.
+    #[must_use]
+    pub fn effective_timezone(&self, all_locations: &[Self]) -> Option<Tz> {
+        std::iter::once(self)
+            .chain(self.get_ancestors(all_locations))
+            .find_map(|location| location.tz().ok().flatten())
+    }
+}
@@ -5,6 +5,7 @@
 
 pub mod derived;
 pub mod link;
+pub mod link_builder;
 pub mod location;
 pub mod node;
 pub mod node_builder;
@@ -16,9 +17,10 @@ use std::fmt::{Display, Formatter, Result as FmtResult};
 use std::str::FromStr;
 
 // Re-export all public types for backward compatibility
-pub use link::{Link, LinkBuilder};
+pub use link::{Link, ProvisioningState};
+pub use link_builder::LinkBuilder;
 pub use location::{Location, LocationBuilder};
-pub use node::Node;
+pub use node::{AddressFamilyPreference, Node, NodeFields, ScopedIpAddr};
 pub use node_builder::NodeBuilder;
 pub use validation::*;
 
@@ -6,7 +6,7 @@
 use crate::models::{DeviceRole, Lifecycle, Vendor};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::net::IpAddr;
+use std::net::{IpAddr, Ipv6Addr};
 use uuid::Uuid;
 
 /// Network node/device representation
@@ -46,6 +46,12 @@ pub struct Node {
     pub warranty_expires: Option<String>,
     /// Extended/custom data as JSON
     pub custom_data: Value,
+    /// IPv6 management address of a dual-stack node whose `management_ip` is IPv4
+    #[serde(default)]
+    pub management_ipv6: Option<Ipv6Addr>,
+    /// Zone (interface name or index) of a link-local IPv6 management address
+    #[serde(default)]
+    pub management_zone: Option<String>,
 }
 
 impl Node {
@@ -76,6 +82,8 @@ impl Node {
             purchase_date: None,
             warranty_expires: None,
             custom_data: Value::Null,
+            management_ipv6: None,
+            management_zone: None,
         }
     }
 
@@ -132,7 +140,7 @@ impl Node {
             return Err("Node model cannot be empty".to_string());
         }
 
-        Ok(())
+        self.validate_management()
     }
 
     /// Updates the FQDN based on current name and domain
@@ -0,0 +1,143 @@
+//! Sparse fieldsets for node responses
+//!
+//! Clients that only need a few node fields can ask for them by name
+//! (`name,vendor,lifecycle`) instead of receiving every field, including
+//! potentially large `custom_data`. The `id` field is always kept.
+
+use serde_json::Value;
+
+/// Top-level fields of a serialized [`Node`](super::Node)
+pub const NODE_FIELDS: [&str; 19] = [
+    "id",
+    "name",
+    "domain",
+    "fqdn",
+    "vendor",
+    "model",
+    "role",
+    "lifecycle",
+    "management_ip",
+    "location_id",
+    "platform",
+    "version",
+    "serial_number",
+    "asset_tag",
+    "purchase_date",
+    "warranty_expires",
+    "custom_data",
+    "management_ipv6",
+    "management_zone",
+];
+
+/// A validated selection of top-level node fields
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeFields {
+    fields: Vec<String>,
+}
+
+impl NodeFields {
+    /// Parses a comma-separated field list such as `name,vendor,lifecycle`
+    ///
+    /// `extra` lists fields that a response adds next to the node fields,
+    /// such as `status`.
+    ///
+    /// # Errors
+    /// Returns an error if the list is empty or names an unknown field.
+    pub fn parse(spec: &str, extra: &[&str]) -> Result<Self, String> {
+        let mut fields: Vec<String> = Vec::new();
+        for field in spec.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+            if !NODE_FIELDS.contains(&field) && !extra.contains(&field) {
+                return Err(format!(
+                    "Unknown field '{field}'; expected one of: {}",
+                    NODE_FIELDS
+                        .iter()
+                        .chain(extra)
+                        .copied()
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+            if !fields.iter().any(|existing| existing == field) {
+                fields.push(field.to_string());
+            }
+        }
+        if fields.is_empty() {
+            return Err("fields must name at least one field".to_string());
+        }
+        Ok(Self { fields })
+    }
+
+    /// Returns whether `field` is selected
+    #[must_use]
+    pub fn contains(&self, field: &str) -> bool {
+        field == "id" || self.fields.iter().any(|selected| selected == field)
+    }
+
+    /// Removes unselected fields from a serialized node object
+    pub fn project(&self, value: &mut Value) {
+        if let Value::Object(object) = value {
+            object.retain(|key, _| self.contains(key));
+        }
+    }
+
+    /// Returns the selection as a comma-separated list
+    #[must_use]
+    pub fn to_query(&self) -> String {
+        self.fields.join(",")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DeviceRole, Node, Vendor};
+    use serde_json::json;
+
+    #[test]
+    fn test_node_fields_match_serialized_node() {
+        let node = Node::new(
+            "edge-1".to_string(),
+            "example.com".to_string(),
+            Vendor::Cisco,
+            DeviceRole::Router,
+        );
+        let value = serde_json::to_value(&node).unwrap();
+        let mut keys: Vec<&str> = value
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        let mut expected = NODE_FIELDS.to_vec();
+        keys.sort_unstable();
+        expected.sort_unstable();
+
+        assert_eq!(keys, expected);
+    }
+
+    #[test]
+    fn test_project_keeps_id_and_selected_fields() {
+        let fields = NodeFields::parse("name, vendor,name", &[]).unwrap();
+        let mut value = json!({"id": "1", "name": "edge-1", "vendor": "cisco", "custom_data": {}});
+
+        fields.project(&mut value);
+
+        assert_eq!(
+            value,
+            json!({"id": "1", "name": "edge-1", "vendor": "cisco"})
+        );
+        assert_eq!(fields.to_query(), "name,vendor");
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_and_empty_fields() {
+        assert!(
+            NodeFields::parse("name,owner", &[])
+                .unwrap_err()
+                .contains("Unknown field 'owner'")
+        );
+        assert!(NodeFields::parse(" , ", &[]).is_err());
+        assert!(NodeFields::parse("status", &[]).is_err());
+        assert!(NodeFields::parse("status", &["status"]).is_ok());
+    }
+}
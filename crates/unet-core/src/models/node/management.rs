@@ -0,0 +1,274 @@
+//! Management addressing for nodes
+//!
+//! A node is managed through `management_ip`, which may be IPv4 or IPv6.
+//! Dual-stack nodes also carry `management_ipv6`, and an
+//! [`AddressFamilyPreference`] decides which address is used. IPv6 link-local
+//! addresses are only reachable through one interface, so they carry a zone
+//! (`fe80::1%eth0`) that becomes the scope ID of the socket address.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::net::{IpAddr, SocketAddr, SocketAddrV6};
+use std::str::FromStr;
+
+use super::core::Node;
+
+/// Address family to use for nodes with both IPv4 and IPv6 management addresses
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AddressFamilyPreference {
+    /// Use IPv4 when available, otherwise IPv6
+    #[default]
+    PreferIpv4,
+    /// Use IPv6 when available, otherwise IPv4
+    PreferIpv6,
+    /// Never use IPv6
+    Ipv4Only,
+    /// Never use IPv4
+    Ipv6Only,
+}
+
+impl AddressFamilyPreference {
+    /// Orders addresses by preference and drops families that are not allowed
+    #[must_use]
+    pub fn order(self, addresses: &[IpAddr]) -> Vec<IpAddr> {
+        let (v4, v6): (Vec<IpAddr>, Vec<IpAddr>) = addresses.iter().partition(|ip| ip.is_ipv4());
+        match self {
+            Self::PreferIpv4 => v4.into_iter().chain(v6).collect(),
+            Self::PreferIpv6 => v6.into_iter().chain(v4).collect(),
+            Self::Ipv4Only => v4,
+            Self::Ipv6Only => v6,
+        }
+    }
+}
+
+/// An IP address with an optional IPv6 zone, written `fe80::1%eth0`
+///
+/// Brackets are accepted around the address (`[fe80::1%2]`). The zone is an
+/// interface name or a numeric interface index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScopedIpAddr {
+    /// The address without its zone
+    pub ip: IpAddr,
+    /// Interface name or index for link-local IPv6 addresses
+    pub zone: Option<String>,
+}
+
+impl FromStr for ScopedIpAddr {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        let unbracketed = trimmed
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+            .unwrap_or(trimmed);
+        let (address, zone) = match unbracketed.split_once('%') {
+            Some((address, zone)) => (address, Some(zone)),
+            None => (unbracketed, None),
+        };
+
+        let ip: IpAddr = address
+            .parse()
+            .map_err(|_| format!("Invalid IP address '{s}'"))?;
+        if let Some(zone) = zone {
+            if ip.is_ipv4() {
+                return Err(format!("Zone in '{s}' is only valid on IPv6 addresses"));
+            }
+            validate_zone(zone)?;
+        }
+
+        Ok(Self {
+            ip,
+            zone: zone.map(str::to_string),
+        })
+    }
+}
+
+impl fmt::Display for ScopedIpAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.zone {
+            Some(zone) => write!(f, "{}%{zone}", self.ip),
+            None => write!(f, "{}", self.ip),
+        }
+    }
+}
+
+/// Checks that a zone is a plausible interface name or index
+///
+/// # Errors
+/// Returns an error if the zone is empty, longer than an interface name can
+/// be, or contains characters interface names cannot contain.
+pub fn validate_zone(zone: &str) -> Result<(), String> {
+    let valid = !zone.is_empty()
+        && zone.len() <= 15
+        && !zone.starts_with('.')
+        && zone
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | ':' | '@'));
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid zone '{zone}' (expected an interface name such as eth0 or an index)"
+        ))
+    }
+}
+
+/// Resolves a zone to the interface index used as a socket scope ID
+///
+/// Numeric zones are used as-is; interface names are looked up on the host.
+///
+/// # Errors
+/// Returns an error if the zone names an interface that does not exist.
+pub fn zone_index(zone: &str) -> Result<u32, String> {
+    if let Ok(index) = zone.parse::<u32>() {
+        return Ok(index);
+    }
+    validate_zone(zone)?;
+    interface_index(zone)
+}
+
+#[cfg(target_os = "linux")]
+fn interface_index(name: &str) -> Result<u32, String> {
+    std::fs::read_to_string(format!("/sys/class/net/{name}/ifindex"))
+        .ok()
+        .and_then(|index| index.trim().parse().ok())
+        .ok_or_else(|| format!("Unknown network interface '{name}'"))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn interface_index(name: &str) -> Result<u32, String> {
+    Err(format!(
+        "Interface names cannot be resolved on this platform; use the index of '{name}'"
+    ))
+}
+
+/// Builds the socket address for reaching `ip` on `port`
+///
+/// Link-local IPv6 addresses need a zone, which becomes the scope ID; zones
+/// on other addresses are ignored.
+///
+/// # Errors
+/// Returns an error if a link-local address has no zone or its zone cannot
+/// be resolved.
+pub fn socket_addr(ip: IpAddr, zone: Option<&str>, port: u16) -> Result<SocketAddr, String> {
+    match ip {
+        IpAddr::V6(v6) if v6.is_unicast_link_local() => {
+            let zone = zone.ok_or_else(|| {
+                format!("Link-local address {v6} needs a zone, such as {v6}%eth0")
+            })?;
+            Ok(SocketAddr::V6(SocketAddrV6::new(
+                v6,
+                port,
+                0,
+                zone_index(zone)?,
+            )))
+        }
+        _ => Ok(SocketAddr::new(ip, port)),
+    }
+}
+
+impl Node {
+    /// Returns `management_ip` followed by `management_ipv6`, if set
+    #[must_use]
+    pub fn management_addresses(&self) -> Vec<IpAddr> {
+        let mut addresses: Vec<IpAddr> = self.management_ip.into_iter().collect();
+        if let Some(v6) = self.management_ipv6.map(IpAddr::V6) {
+            if !addresses.contains(&v6) {
+                addresses.push(v6);
+            }
+        }
+        addresses
+    }
+
+    /// Returns the management address to use under the given preference
+    #[must_use]
+    pub fn preferred_management_ip(&self, preference: AddressFamilyPreference) -> Option<IpAddr> {
+        preference
+            .order(&self.management_addresses())
+            .into_iter()
+            .next()
+    }
+
+    /// Returns the socket address of the node's management plane on `port`
+    ///
+    /// Returns `Ok(None)` if the node has no management address of an allowed
+    /// family.
+    ///
+    /// # Errors
+    /// Returns an error if the chosen address is link-local and
+    /// `management_zone` is missing or unknown.
+    pub fn management_socket_addr(
+        &self,
+        port: u16,
+        preference: AddressFamilyPreference,
+    ) -> Result<Option<SocketAddr>, String> {
+        self.preferred_management_ip(preference)
+            .map(|ip| socket_addr(ip, self.management_zone.as_deref(), port))
+            .transpose()
+    }
+
+    /// Sets management addresses given as text, as from a CLI flag or API field
+    ///
+    /// `ip` replaces `management_ip` and `ipv6` replaces `management_ipv6`;
+    /// either may carry a zone (`fe80::1%eth0`), which replaces
+    /// `management_zone`. An existing zone is dropped once no link-local
+    /// address remains.
+    ///
+    /// # Errors
+    /// Returns an error if an address cannot be parsed, `ipv6` is not an IPv6
+    /// address, or the resulting addressing is invalid.
+    pub fn set_management_addresses(
+        &mut self,
+        ip: Option<&str>,
+        ipv6: Option<&str>,
+    ) -> Result<(), String> {
+        let mut zone = None;
+        if let Some(ip) = ip {
+            let scoped: ScopedIpAddr = ip.parse()?;
+            self.management_ip = Some(scoped.ip);
+            zone = scoped.zone.or(zone);
+        }
+        if let Some(ipv6) = ipv6 {
+            let scoped: ScopedIpAddr = ipv6.parse()?;
+            let IpAddr::V6(v6) = scoped.ip else {
+                return Err(format!("'{ipv6}' is not an IPv6 address"));
+            };
+            self.management_ipv6 = Some(v6);
+            zone = scoped.zone.or(zone);
+        }
+        if zone.is_some() {
+            self.management_zone = zone;
+        } else if !self.has_link_local_management() {
+            self.management_zone = None;
+        }
+        self.validate_management()
+    }
+
+    fn has_link_local_management(&self) -> bool {
+        self.management_addresses().iter().any(|ip| match ip {
+            IpAddr::V6(v6) => v6.is_unicast_link_local(),
+            IpAddr::V4(_) => false,
+        })
+    }
+
+    /// Checks the management addressing fields
+    pub(super) fn validate_management(&self) -> Result<(), String> {
+        if self.management_ipv6.is_some() && self.management_ip.is_some_and(|ip| ip.is_ipv6()) {
+            return Err(
+                "management_ipv6 is for dual-stack nodes; management_ip must then be IPv4"
+                    .to_string(),
+            );
+        }
+        if let Some(zone) = &self.management_zone {
+            validate_zone(zone)?;
+            if !self.has_link_local_management() {
+                return Err(format!(
+                    "Management zone '{zone}' requires a link-local IPv6 management address"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,131 @@
+//! Tests for management addressing
+
+use super::Node;
+use super::management::{AddressFamilyPreference, ScopedIpAddr, socket_addr};
+use crate::models::{DeviceRole, Vendor};
+use std::net::{IpAddr, SocketAddr};
+
+fn dual_stack_node() -> Node {
+    let mut node = Node::new(
+        "edge1".to_string(),
+        "example.com".to_string(),
+        Vendor::Cisco,
+        DeviceRole::Router,
+    );
+    node.model = "ASR1000".to_string();
+    node.management_ip = Some("192.0.2.1".parse().unwrap());
+    node.management_ipv6 = Some("2001:db8::1".parse().unwrap());
+    node
+}
+
+#[test]
+fn test_scoped_ip_addr_parsing() {
+    let scoped: ScopedIpAddr = "fe80::1%eth0".parse().unwrap();
+    assert_eq!(scoped.ip, "fe80::1".parse::<IpAddr>().unwrap());
+    assert_eq!(scoped.zone.as_deref(), Some("eth0"));
+    assert_eq!(scoped.to_string(), "fe80::1%eth0");
+
+    let bracketed: ScopedIpAddr = "[fe80::1%2]".parse().unwrap();
+    assert_eq!(bracketed.zone.as_deref(), Some("2"));
+
+    let plain: ScopedIpAddr = "192.0.2.1".parse().unwrap();
+    assert!(plain.zone.is_none());
+
+    assert!("192.0.2.1%eth0".parse::<ScopedIpAddr>().is_err());
+    assert!("fe80::1%".parse::<ScopedIpAddr>().is_err());
+    assert!("fe80::1%eth0/1".parse::<ScopedIpAddr>().is_err());
+    assert!("not-an-ip".parse::<ScopedIpAddr>().is_err());
+}
+
+#[test]
+fn test_socket_addr_uses_zone_as_scope_id() {
+    let ip: IpAddr = "fe80::1".parse().unwrap();
+    match socket_addr(ip, Some("2"), 161).unwrap() {
+        SocketAddr::V6(addr) => {
+            assert_eq!(addr.scope_id(), 2);
+            assert_eq!(addr.port(), 161);
+        }
+        SocketAddr::V4(_) => panic!("expected an IPv6 socket address"),
+    }
+
+    assert!(socket_addr(ip, None, 161).is_err());
+
+    let global: IpAddr = "2001:db8::1".parse().unwrap();
+    assert_eq!(
+        socket_addr(global, None, 161).unwrap().to_string(),
+        "[2001:db8::1]:161"
+    );
+}
+
+#[test]
+fn test_preferred_management_ip() {
+    let node = dual_stack_node();
+    let v4: IpAddr = "192.0.2.1".parse().unwrap();
+    let v6: IpAddr = "2001:db8::1".parse().unwrap();
+
+    assert_eq!(
+        node.preferred_management_ip(AddressFamilyPreference::PreferIpv4),
+        Some(v4)
+    );
+    assert_eq!(
+        node.preferred_management_ip(AddressFamilyPreference::PreferIpv6),
+        Some(v6)
+    );
+
+    let mut v4_only = dual_stack_node();
+    v4_only.management_ipv6 = None;
+    assert_eq!(
+        v4_only.preferred_management_ip(AddressFamilyPreference::PreferIpv6),
+        Some(v4)
+    );
+    assert_eq!(
+        v4_only.preferred_management_ip(AddressFamilyPreference::Ipv6Only),
+        None
+    );
+    assert_eq!(
+        v4_only
+            .management_socket_addr(161, AddressFamilyPreference::Ipv6Only)
+            .unwrap(),
+        None
+    );
+}
+
+#[test]
+fn test_set_management_addresses() {
+    let mut node = dual_stack_node();
+    node.set_management_addresses(None, Some("fe80::1%3"))
+        .unwrap();
+    assert_eq!(node.management_ipv6, Some("fe80::1".parse().unwrap()));
+    assert_eq!(node.management_zone.as_deref(), Some("3"));
+
+    // Replacing the IPv4 address keeps the zone of the link-local IPv6 one
+    node.set_management_addresses(Some("192.0.2.2"), None)
+        .unwrap();
+    assert_eq!(node.management_zone.as_deref(), Some("3"));
+
+    // The zone goes away with the last link-local address
+    node.set_management_addresses(None, Some("2001:db8::2"))
+        .unwrap();
+    assert!(node.management_zone.is_none());
+
+    assert!(
+        node.set_management_addresses(None, Some("192.0.2.3"))
+            .is_err()
+    );
+    assert!(
+        node.set_management_addresses(Some("2001:db8::3"), None)
+            .is_err()
+    );
+}
+
+#[test]
+fn test_validate_management() {
+    let mut node = dual_stack_node();
+    assert!(node.validate().is_ok());
+
+    node.management_zone = Some("eth0".to_string());
+    assert!(node.validate().is_err());
+
+    node.management_ipv6 = Some("fe80::1".parse().unwrap());
+    assert!(node.validate().is_ok());
+}
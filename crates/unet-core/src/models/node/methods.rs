@@ -4,6 +4,7 @@
 //! for the Node struct.
 
 use super::core::Node;
+use crate::models::derived::SoftwareVersion;
 use serde_json::Value;
 
 impl Node {
@@ -88,4 +89,22 @@ impl Node {
 
         Ok(())
     }
+
+    /// Fills in `platform` and `version` from polled software details
+    ///
+    /// Values already on the node are kept unless `overwrite` is set.
+    /// Returns whether the node changed.
+    pub fn backfill_software(&mut self, software: &SoftwareVersion, overwrite: bool) -> bool {
+        let mut changed = false;
+        for (field, value) in [
+            (&mut self.platform, &software.os),
+            (&mut self.version, &software.version),
+        ] {
+            if (overwrite || field.is_none()) && field.as_ref() != Some(value) {
+                *field = Some(value.clone());
+                changed = true;
+            }
+        }
+        changed
+    }
 }
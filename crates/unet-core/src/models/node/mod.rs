@@ -2,14 +2,22 @@
 //!
 //! This module has been reorganized into focused submodules:
 //! - `core`: Core Node struct definition and basic operations
+//! - `fields`: Sparse fieldsets selecting which node fields to return
 //! - `methods`: Utility methods and custom data manipulation
+//! - `management`: IPv4/IPv6 management addressing and socket resolution
 //! - `tests`: Comprehensive test suite
 
 pub mod core;
+pub mod fields;
+pub mod management;
 pub mod methods;
 
+#[cfg(test)]
+mod management_tests;
 #[cfg(test)]
 mod tests;
 
 // Re-export the main struct for backward compatibility
 pub use core::Node;
+pub use fields::{NODE_FIELDS, NodeFields};
+pub use management::{AddressFamilyPreference, ScopedIpAddr};
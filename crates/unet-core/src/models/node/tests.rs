@@ -335,4 +335,24 @@ mod node_tests {
         assert_eq!(node.purchase_date.unwrap(), "2023-01-15");
         assert_eq!(node.warranty_expires.unwrap(), "2026-01-15");
     }
+
+    #[test]
+    fn test_backfill_software_keeps_existing_values() {
+        let software = crate::models::derived::SoftwareVersion {
+            os: "IOS-XE".to_string(),
+            version: "17.9.4".to_string(),
+            train: Some("Cupertino".to_string()),
+        };
+        let mut node = create_test_node();
+        node.version = Some("17.6.5".to_string());
+
+        assert!(node.backfill_software(&software, false));
+        assert_eq!(node.platform.as_deref(), Some("IOS-XE"));
+        assert_eq!(node.version.as_deref(), Some("17.6.5"));
+        assert!(!node.backfill_software(&software, false));
+
+        assert!(node.backfill_software(&software, true));
+        assert_eq!(node.version.as_deref(), Some("17.9.4"));
+        assert!(!node.backfill_software(&software, true));
+    }
 }
@@ -5,7 +5,7 @@
 
 use crate::models::{DeviceRole, Lifecycle, Node, Vendor};
 use serde_json::Value;
-use std::net::IpAddr;
+use std::net::{IpAddr, Ipv6Addr};
 use uuid::Uuid;
 
 /// Builder pattern for Node creation with validation
@@ -27,6 +27,8 @@ pub struct NodeBuilder {
     lifecycle: Option<Lifecycle>,
     /// Management IP address (optional)
     management_ip: Option<IpAddr>,
+    management_ipv6: Option<Ipv6Addr>,
+    management_zone: Option<String>,
     /// Location ID (optional)
     location_id: Option<Uuid>,
     /// Platform/OS information (optional)
@@ -108,6 +110,20 @@ impl NodeBuilder {
         self
     }
 
+    /// Sets the IPv6 management address of a dual-stack node (optional)
+    #[must_use]
+    pub const fn management_ipv6(mut self, ip: Ipv6Addr) -> Self {
+        self.management_ipv6 = Some(ip);
+        self
+    }
+
+    /// Sets the zone of a link-local IPv6 management address (optional)
+    #[must_use]
+    pub fn management_zone<S: Into<String>>(mut self, zone: S) -> Self {
+        self.management_zone = Some(zone.into());
+        self
+    }
+
     /// Sets the location ID (optional)
     #[must_use]
     pub const fn location_id(mut self, location_id: Uuid) -> Self {
@@ -200,6 +216,8 @@ impl NodeBuilder {
             purchase_date: self.purchase_date,
             warranty_expires: self.warranty_expires,
             custom_data: self.custom_data.unwrap_or(Value::Null),
+            management_ipv6: self.management_ipv6,
+            management_zone: self.management_zone,
         };
 
         node.validate()?;
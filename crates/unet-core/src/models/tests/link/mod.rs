@@ -2,3 +2,4 @@
 
 mod basic_operations;
 mod builder_and_serialization;
+mod provisioning;
@@ -0,0 +1,71 @@
+//! Provisioning state and circuit metadata tests for `Link` model
+
+use crate::models::*;
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+fn date(s: &str) -> NaiveDate {
+    s.parse().unwrap()
+}
+
+#[test]
+fn test_provisioning_state_transitions() {
+    use ProvisioningState::{Delivered, Live, Ordered, Tested};
+
+    assert_eq!(Ordered.transition_to(Delivered), Ok(Delivered));
+    assert_eq!(Tested.transition_to(Live), Ok(Live));
+    // Failed acceptance tests send a circuit back
+    assert_eq!(Tested.transition_to(Delivered), Ok(Delivered));
+    assert_eq!(Live.transition_to(Live), Ok(Live));
+
+    let skipped = Ordered.transition_to(Live).unwrap_err();
+    assert!(skipped.contains("next is delivered"));
+    assert_eq!("Tested".parse::<ProvisioningState>(), Ok(Tested));
+    assert!("active".parse::<ProvisioningState>().is_err());
+}
+
+#[test]
+fn test_link_builder_circuit_metadata() {
+    let link = LinkBuilder::new()
+        .name("transit-1")
+        .source_node_id(Uuid::new_v4())
+        .node_a_interface("xe-0/0/0")
+        .is_internet_circuit(true)
+        .provider("Lumen")
+        .circuit_id("CID-1234")
+        .ordered_on(date("2026-01-05"))
+        .provisioning_state(ProvisioningState::Ordered)
+        .build()
+        .unwrap();
+
+    assert_eq!(link.provider.as_deref(), Some("Lumen"));
+    assert_eq!(link.circuit_id.as_deref(), Some("CID-1234"));
+    assert_eq!(link.ordered_on, Some(date("2026-01-05")));
+    assert_eq!(link.provisioning_state, ProvisioningState::Ordered);
+
+    let mut delivered_early = link;
+    delivered_early.delivered_on = Some(date("2026-01-01"));
+    assert!(
+        delivered_early
+            .validate()
+            .unwrap_err()
+            .contains("before it was ordered")
+    );
+}
+
+#[test]
+fn test_link_without_provisioning_state_is_live() {
+    let link = Link::new(
+        "core".to_string(),
+        Uuid::new_v4(),
+        "eth0".to_string(),
+        Uuid::new_v4(),
+        "eth1".to_string(),
+    );
+    let mut json = serde_json::to_value(&link).unwrap();
+    json.as_object_mut().unwrap().remove("provisioning_state");
+
+    let deserialized: Link = serde_json::from_value(json).unwrap();
+
+    assert_eq!(deserialized.provisioning_state, ProvisioningState::Live);
+}
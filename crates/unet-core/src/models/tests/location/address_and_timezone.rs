@@ -0,0 +1,205 @@
+//! Structured address, geocoding, and timezone tests for `Location` model
+
+use crate::models::location::{
+    Coordinates, Geocoder, GeocodingError, PostalAddress, geocode_location, local_to_utc,
+    next_local_occurrence, parse_timezone,
+};
+use crate::models::*;
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+
+struct FixedGeocoder(Option<Coordinates>);
+
+#[async_trait]
+impl Geocoder for FixedGeocoder {
+    fn name(&self) -> &str {
+        "fixed"
+    }
+
+    async fn geocode(
+        &self,
+        _address: &PostalAddress,
+    ) -> Result<Option<Coordinates>, GeocodingError> {
+        Ok(self.0)
+    }
+}
+
+fn utc(s: &str) -> DateTime<Utc> {
+    s.parse().unwrap()
+}
+
+fn local(date: (i32, u32, u32), time: (u32, u32)) -> chrono::NaiveDateTime {
+    NaiveDate::from_ymd_opt(date.0, date.1, date.2)
+        .unwrap()
+        .and_hms_opt(time.0, time.1, 0)
+        .unwrap()
+}
+
+fn site_with_address() -> Location {
+    LocationBuilder::new()
+        .name("Chicago POP")
+        .location_type("building")
+        .postal_address(PostalAddress {
+            street: Some("350 E Cermak Rd".to_string()),
+            city: Some("Chicago".to_string()),
+            region: Some("IL".to_string()),
+            postal_code: Some("60616".to_string()),
+            country: Some("US".to_string()),
+        })
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_coordinates_parse_and_display_round_trip() {
+    let coordinates: Coordinates = "41.8531, -87.6181".parse().unwrap();
+
+    assert_eq!(coordinates, Coordinates::new(41.8531, -87.6181).unwrap());
+    assert_eq!(coordinates.to_string(), "41.8531,-87.6181");
+    assert!("91,0".parse::<Coordinates>().is_err());
+    assert!("0,-181".parse::<Coordinates>().is_err());
+    assert!("41.8531".parse::<Coordinates>().is_err());
+}
+
+#[test]
+fn test_postal_address_single_line_and_merge() {
+    let mut address = site_with_address().postal_address.unwrap();
+    assert_eq!(
+        address.to_single_line(),
+        "350 E Cermak Rd, Chicago, IL 60616, US"
+    );
+
+    address.merge(PostalAddress {
+        street: Some("427 S LaSalle St".to_string()),
+        ..PostalAddress::default()
+    });
+    assert_eq!(address.street.as_deref(), Some("427 S LaSalle St"));
+    assert_eq!(address.city.as_deref(), Some("Chicago"));
+    assert!(PostalAddress::default().is_empty());
+}
+
+#[test]
+fn test_location_validation_rejects_unknown_timezone() {
+    let result = LocationBuilder::new()
+        .name("Site")
+        .location_type("site")
+        .timezone("Central Time")
+        .build();
+
+    assert!(result.unwrap_err().contains("Unknown timezone"));
+}
+
+#[test]
+fn test_local_to_utc_handles_dst_transitions() {
+    let tz = parse_timezone("America/New_York").unwrap();
+
+    // Standard time and daylight time
+    assert_eq!(
+        local_to_utc(tz, local((2024, 1, 15), (2, 0))),
+        utc("2024-01-15T07:00:00Z")
+    );
+    assert_eq!(
+        local_to_utc(tz, local((2024, 7, 1), (2, 0))),
+        utc("2024-07-01T06:00:00Z")
+    );
+    // 02:30 does not exist on 2024-03-10; it is shifted to 03:30 EDT
+    assert_eq!(
+        local_to_utc(tz, local((2024, 3, 10), (2, 30))),
+        utc("2024-03-10T07:30:00Z")
+    );
+    // 01:30 occurs twice on 2024-11-03; the first (EDT) is used
+    assert_eq!(
+        local_to_utc(tz, local((2024, 11, 3), (1, 30))),
+        utc("2024-11-03T05:30:00Z")
+    );
+}
+
+#[test]
+fn test_next_local_occurrence() {
+    let tz = parse_timezone("Europe/Berlin").unwrap();
+    let two_am = NaiveTime::from_hms_opt(2, 0, 0).unwrap();
+
+    assert_eq!(
+        next_local_occurrence(tz, two_am, utc("2024-01-15T00:30:00Z")),
+        utc("2024-01-15T01:00:00Z")
+    );
+    assert_eq!(
+        next_local_occurrence(tz, two_am, utc("2024-01-15T01:00:00Z")),
+        utc("2024-01-16T01:00:00Z")
+    );
+    assert_eq!(
+        next_local_occurrence(tz, two_am, utc("2024-07-15T12:00:00Z")),
+        utc("2024-07-16T00:00:00Z")
+    );
+}
+
+#[test]
+fn test_effective_timezone_is_inherited_from_ancestors() {
+    let mut campus = Location::new_root("Campus".to_string(), "campus".to_string());
+    campus.timezone = Some("Asia/Tokyo".to_string());
+    let mut building = Location::new_child("B1".to_string(), "building".to_string(), "Campus");
+    building.parent_id = Some(campus.id);
+    let mut floor = Location::new_child("F2".to_string(), "floor".to_string(), "Campus/B1");
+    floor.parent_id = Some(building.id);
+    let all = vec![campus, building, floor.clone()];
+
+    assert_eq!(
+        floor.effective_timezone(&all),
+        Some(parse_timezone("Asia/Tokyo").unwrap())
+    );
+
+    floor.timezone = Some("UTC".to_string());
+    assert_eq!(floor.effective_timezone(&all), Some(chrono_tz::UTC));
+
+    let orphan = Location::new_root("Orphan".to_string(), "site".to_string());
+    assert_eq!(orphan.effective_timezone(&all), None);
+}
+
+#[tokio::test]
+async fn test_geocode_location_fills_missing_coordinates() {
+    let found = Coordinates::new(41.8531, -87.6181).unwrap();
+    let geocoder = FixedGeocoder(Some(found));
+    let mut location = site_with_address();
+
+    assert!(
+        geocode_location(&geocoder, &mut location, false)
+            .await
+            .unwrap()
+    );
+    assert_eq!(location.coordinates, Some(found));
+
+    let other = FixedGeocoder(Some(Coordinates::new(0.0, 0.0).unwrap()));
+    assert!(
+        !geocode_location(&other, &mut location, false)
+            .await
+            .unwrap()
+    );
+    assert_eq!(location.coordinates, Some(found));
+}
+
+#[tokio::test]
+async fn test_geocode_location_errors() {
+    let mut location = Location::new_root("Site".to_string(), "site".to_string());
+    let geocoder = FixedGeocoder(None);
+    assert!(matches!(
+        geocode_location(&geocoder, &mut location, false).await,
+        Err(GeocodingError::NoAddress(_))
+    ));
+
+    let mut location = site_with_address();
+    assert!(
+        !geocode_location(&geocoder, &mut location, false)
+            .await
+            .unwrap()
+    );
+
+    let invalid = FixedGeocoder(Some(Coordinates {
+        latitude: 120.0,
+        longitude: 0.0,
+    }));
+    assert!(matches!(
+        geocode_location(&invalid, &mut location, true).await,
+        Err(GeocodingError::InvalidResult { .. })
+    ));
+    assert_eq!(location.coordinates, None);
+}
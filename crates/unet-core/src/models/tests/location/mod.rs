@@ -1,5 +1,6 @@
 //! Tests for `Location` model and `LocationBuilder`
 
+mod address_and_timezone;
 mod basic_operations;
 mod builder_and_serialization;
 mod hierarchy_operations;
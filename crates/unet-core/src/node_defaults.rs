@@ -0,0 +1,196 @@
+//! Default `custom_data` skeletons applied when nodes are created
+//!
+//! Admins register a JSON object per device role or vendor, such as
+//! `{"bgp": {"asn": null}}` for routers. New nodes are created with those
+//! keys filled in wherever their own `custom_data` leaves them out, so
+//! templates and policies can rely on the keys existing.
+
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult};
+use crate::models::{DeviceRole, Node, Vendor};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+
+/// Settings namespace holding defaults keyed by `scope:target`
+const DEFAULTS_NAMESPACE: &str = "custom_data_defaults";
+
+/// What a set of `custom_data` defaults applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DefaultsScope {
+    /// All nodes with a device role
+    Role,
+    /// All nodes from a vendor
+    Vendor,
+}
+
+impl Display for DefaultsScope {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Role => write!(f, "role"),
+            Self::Vendor => write!(f, "vendor"),
+        }
+    }
+}
+
+impl FromStr for DefaultsScope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "role" => Ok(Self::Role),
+            "vendor" => Ok(Self::Vendor),
+            _ => Err(format!("Invalid defaults scope: {s}")),
+        }
+    }
+}
+
+/// A `custom_data` skeleton registered for a role or vendor
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomDataDefaults {
+    /// Defaults scope
+    pub scope: DefaultsScope,
+    /// Role or vendor name depending on scope
+    pub target: String,
+    /// JSON object merged into new nodes' `custom_data`
+    pub data: Value,
+}
+
+impl CustomDataDefaults {
+    /// Creates defaults, normalizing the target for its scope
+    ///
+    /// # Errors
+    /// Returns a validation error if the target is not a valid role or
+    /// vendor, or `data` is not a JSON object.
+    pub fn new(scope: DefaultsScope, target: &str, data: Value) -> DataStoreResult<Self> {
+        let target = match scope {
+            DefaultsScope::Role => DeviceRole::from_str(target).map(|role| role.to_string()),
+            DefaultsScope::Vendor => Vendor::from_str(target).map(|vendor| vendor.to_string()),
+        }
+        .map_err(|message| DataStoreError::ValidationError { message })?;
+        if !data.is_object() {
+            return Err(DataStoreError::ValidationError {
+                message: format!("custom_data defaults for {scope} {target} must be a JSON object"),
+            });
+        }
+        Ok(Self {
+            scope,
+            target,
+            data,
+        })
+    }
+
+    /// Storage key identifying the scope and target
+    #[must_use]
+    pub fn key(&self) -> String {
+        format!("{}:{}", self.scope, self.target)
+    }
+}
+
+/// Fills keys from `defaults` that are missing in `custom_data`
+///
+/// Nested objects are merged key by key; any value already present in
+/// `custom_data` wins. A `null` `custom_data` is treated as an empty object,
+/// and non-object `custom_data` is left unchanged.
+pub fn merge_defaults(custom_data: &mut Value, defaults: &Value) {
+    let Value::Object(defaults) = defaults else {
+        return;
+    };
+    if custom_data.is_null() {
+        *custom_data = Value::Object(Map::new());
+    }
+    let Value::Object(existing) = custom_data else {
+        return;
+    };
+    for (key, default) in defaults {
+        match existing.get_mut(key) {
+            Some(value) => merge_defaults(value, default),
+            None => {
+                existing.insert(key.clone(), default.clone());
+            }
+        }
+    }
+}
+
+/// Lists all registered defaults, ordered by scope and target
+///
+/// # Errors
+/// Returns an error if the defaults cannot be read or a stored entry does not
+/// parse.
+pub async fn list_defaults(datastore: &dyn DataStore) -> DataStoreResult<Vec<CustomDataDefaults>> {
+    datastore
+        .list_settings(DEFAULTS_NAMESPACE)
+        .await?
+        .into_iter()
+        .map(|(key, value)| {
+            serde_json::from_value(value).map_err(|e| DataStoreError::InternalError {
+                message: format!("stored custom_data defaults {key}: {e}"),
+            })
+        })
+        .collect()
+}
+
+/// Stores defaults, replacing any defaults for the same scope and target
+///
+/// # Errors
+/// Returns an error if the datastore write fails.
+pub async fn save_defaults(
+    datastore: &dyn DataStore,
+    defaults: &CustomDataDefaults,
+) -> DataStoreResult<()> {
+    let value = serde_json::to_value(defaults).map_err(|e| DataStoreError::InternalError {
+        message: format!("custom_data defaults {}: {e}", defaults.key()),
+    })?;
+    datastore
+        .put_setting(DEFAULTS_NAMESPACE, &defaults.key(), &value)
+        .await
+}
+
+/// Removes the defaults for a scope and target
+///
+/// # Errors
+/// Returns an error if the target is invalid, no defaults are registered,
+/// or the datastore write fails.
+pub async fn delete_defaults(
+    datastore: &dyn DataStore,
+    scope: DefaultsScope,
+    target: &str,
+) -> DataStoreResult<()> {
+    let key = CustomDataDefaults::new(scope, target, Value::Object(Map::new()))?.key();
+    datastore.delete_setting(DEFAULTS_NAMESPACE, &key).await
+}
+
+/// Merges the role and vendor defaults into a new node's `custom_data`
+///
+/// Role defaults are applied before vendor defaults, so a key registered for
+/// both takes the role's value. Datastores without settings support have no
+/// defaults and leave the node unchanged.
+///
+/// # Errors
+/// Returns an error if the role or vendor defaults cannot be read or do not
+/// parse.
+pub async fn apply_defaults(datastore: &dyn DataStore, node: &mut Node) -> DataStoreResult<()> {
+    let keys = [
+        format!("{}:{}", DefaultsScope::Role, node.role),
+        format!("{}:{}", DefaultsScope::Vendor, node.vendor),
+    ];
+    for key in keys {
+        let stored = match datastore.get_setting(DEFAULTS_NAMESPACE, &key).await {
+            Ok(stored) => stored,
+            Err(DataStoreError::UnsupportedOperation { .. }) => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        if let Some(value) = stored {
+            let defaults: CustomDataDefaults =
+                serde_json::from_value(value).map_err(|e| DataStoreError::InternalError {
+                    message: format!("stored custom_data defaults {key}: {e}"),
+                })?;
+            merge_defaults(&mut node.custom_data, &defaults.data);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,90 @@
+use super::*;
+use crate::datastore::sqlite::tests::setup::settings_store;
+use serde_json::json;
+
+fn router() -> Node {
+    Node::new(
+        "edge-1".to_string(),
+        "example.com".to_string(),
+        Vendor::Juniper,
+        DeviceRole::Router,
+    )
+}
+
+#[test]
+fn test_new_normalizes_target_and_requires_object() {
+    let defaults = CustomDataDefaults::new(DefaultsScope::Role, "Router", json!({})).unwrap();
+    assert_eq!(defaults.key(), "role:router");
+
+    assert!(CustomDataDefaults::new(DefaultsScope::Vendor, "acme", json!({})).is_err());
+    assert!(CustomDataDefaults::new(DefaultsScope::Role, "router", json!([1])).is_err());
+}
+
+#[test]
+fn test_merge_defaults_keeps_existing_values() {
+    let mut custom_data = json!({"bgp": {"asn": 65001}, "site": "lab"});
+
+    merge_defaults(
+        &mut custom_data,
+        &json!({"bgp": {"asn": null, "peers": []}, "site": null, "owner": "netops"}),
+    );
+
+    assert_eq!(
+        custom_data,
+        json!({"bgp": {"asn": 65001, "peers": []}, "site": "lab", "owner": "netops"})
+    );
+}
+
+#[test]
+fn test_merge_defaults_fills_null_custom_data() {
+    let mut custom_data = Value::Null;
+
+    merge_defaults(&mut custom_data, &json!({"bgp": {"asn": null}}));
+
+    assert_eq!(custom_data, json!({"bgp": {"asn": null}}));
+}
+
+#[tokio::test]
+async fn test_apply_defaults_merges_role_before_vendor() {
+    let store = settings_store().await;
+    for defaults in [
+        CustomDataDefaults::new(DefaultsScope::Role, "router", json!({"bgp": {"asn": null}}))
+            .unwrap(),
+        CustomDataDefaults::new(
+            DefaultsScope::Vendor,
+            "juniper",
+            json!({"bgp": {"asn": 0, "group": "ext"}, "os": "junos"}),
+        )
+        .unwrap(),
+        CustomDataDefaults::new(DefaultsScope::Role, "switch", json!({"vlans": []})).unwrap(),
+    ] {
+        save_defaults(&store, &defaults).await.unwrap();
+    }
+    let mut node = router();
+
+    apply_defaults(&store, &mut node).await.unwrap();
+
+    assert_eq!(
+        node.custom_data,
+        json!({"bgp": {"asn": null, "group": "ext"}, "os": "junos"})
+    );
+    assert_eq!(list_defaults(&store).await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_delete_defaults_removes_stored_defaults() {
+    let store = settings_store().await;
+    let defaults =
+        CustomDataDefaults::new(DefaultsScope::Role, "router", json!({"bgp": {}})).unwrap();
+    save_defaults(&store, &defaults).await.unwrap();
+
+    delete_defaults(&store, DefaultsScope::Role, "router")
+        .await
+        .unwrap();
+
+    assert!(list_defaults(&store).await.unwrap().is_empty());
+    assert!(matches!(
+        delete_defaults(&store, DefaultsScope::Role, "router").await,
+        Err(DataStoreError::NotFound { .. })
+    ));
+}
@@ -0,0 +1,237 @@
+//! Notes and file attachments on nodes, links, and locations
+//!
+//! Operators keep context that does not fit a structured field next to the
+//! entity it is about: markdown notes such as "RMA open, replace PSU 2", and
+//! small files such as rack photos or circuit handover documents. Notes and
+//! attachment metadata are kept through the `DataStore` settings API;
+//! attachment contents are kept there too unless a blob directory is
+//! configured (see [`attachments`]).
+
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::fmt;
+use uuid::Uuid;
+
+pub mod attachments;
+
+pub use attachments::{
+    Attachment, AttachmentStorage, add_attachment, attachment_content, delete_attachment,
+    get_attachment, list_attachments,
+};
+
+/// Settings namespace holding notes keyed by ID
+const NOTES_NAMESPACE: &str = "notes";
+
+/// Longest note body in bytes
+pub const MAX_NOTE_LENGTH: usize = 64 * 1024;
+
+/// Entity a note or attachment belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "id", rename_all = "snake_case")]
+pub enum NoteTarget {
+    /// A node
+    Node(Uuid),
+    /// A link
+    Link(Uuid),
+    /// A location
+    Location(Uuid),
+}
+
+impl NoteTarget {
+    /// Fails unless the entity exists
+    ///
+    /// # Errors
+    /// Returns a not-found error if the entity does not exist, or an error if
+    /// the datastore cannot be read.
+    pub async fn ensure_exists(self, datastore: &dyn DataStore) -> DataStoreResult<()> {
+        match self {
+            Self::Node(id) => datastore.get_node_required(&id).await.map(drop),
+            Self::Link(id) => datastore.get_link_required(&id).await.map(drop),
+            Self::Location(id) => datastore.get_location_required(&id).await.map(drop),
+        }
+    }
+}
+
+impl fmt::Display for NoteTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Node(id) => write!(f, "node {id}"),
+            Self::Link(id) => write!(f, "link {id}"),
+            Self::Location(id) => write!(f, "location {id}"),
+        }
+    }
+}
+
+/// A markdown note on an entity
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Note {
+    /// Note ID
+    pub id: Uuid,
+    /// Entity the note is about
+    pub target: NoteTarget,
+    /// Markdown text
+    pub body: String,
+    /// Who wrote the note
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// When the note was written
+    pub created_at: DateTime<Utc>,
+    /// When the note was last edited
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Note {
+    /// Creates a note written now
+    ///
+    /// # Errors
+    /// Returns a validation error if the body is blank or longer than
+    /// [`MAX_NOTE_LENGTH`].
+    pub fn new(target: NoteTarget, body: String, author: Option<String>) -> DataStoreResult<Self> {
+        validate_body(&body)?;
+        let now = Utc::now();
+        Ok(Self {
+            id: Uuid::new_v4(),
+            target,
+            body,
+            author,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+}
+
+fn validate_body(body: &str) -> DataStoreResult<()> {
+    let message = if body.trim().is_empty() {
+        "Note body cannot be empty".to_string()
+    } else if body.len() > MAX_NOTE_LENGTH {
+        format!("Note body is longer than {MAX_NOTE_LENGTH} bytes")
+    } else {
+        return Ok(());
+    };
+    Err(DataStoreError::ValidationError { message })
+}
+
+fn parse<T: DeserializeOwned>(
+    kind: &str,
+    key: &str,
+    value: serde_json::Value,
+) -> DataStoreResult<T> {
+    serde_json::from_value(value).map_err(|e| DataStoreError::InternalError {
+        message: format!("stored {kind} {key}: {e}"),
+    })
+}
+
+async fn put<T: Serialize>(
+    datastore: &dyn DataStore,
+    namespace: &str,
+    id: Uuid,
+    value: &T,
+) -> DataStoreResult<()> {
+    let key = id.to_string();
+    let value = serde_json::to_value(value).map_err(|e| DataStoreError::InternalError {
+        message: format!("{namespace} {key}: {e}"),
+    })?;
+    datastore.put_setting(namespace, &key, &value).await
+}
+
+/// Reads a document by ID, failing with a not-found error naming `kind`
+async fn get<T: DeserializeOwned>(
+    datastore: &dyn DataStore,
+    namespace: &str,
+    kind: &str,
+    id: Uuid,
+) -> DataStoreResult<T> {
+    let key = id.to_string();
+    let value = datastore
+        .get_setting(namespace, &key)
+        .await?
+        .ok_or_else(|| DataStoreError::NotFound {
+            entity_type: kind.to_string(),
+            id: key.clone(),
+        })?;
+    parse(kind, &key, value)
+}
+
+/// Lists the documents of a namespace; datastores without settings support
+/// have none
+async fn list<T: DeserializeOwned>(
+    datastore: &dyn DataStore,
+    namespace: &str,
+    kind: &str,
+) -> DataStoreResult<Vec<T>> {
+    let stored = match datastore.list_settings(namespace).await {
+        Ok(stored) => stored,
+        Err(DataStoreError::UnsupportedOperation { .. }) => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    stored
+        .into_iter()
+        .map(|(key, value)| parse(kind, &key, value))
+        .collect()
+}
+
+/// Stores a new note
+///
+/// # Errors
+/// Returns a not-found error if the note's entity does not exist, or an error
+/// if the datastore cannot be read or written.
+pub async fn add_note(datastore: &dyn DataStore, note: &Note) -> DataStoreResult<()> {
+    note.target.ensure_exists(datastore).await?;
+    put(datastore, NOTES_NAMESPACE, note.id, note).await
+}
+
+/// Gets a note
+///
+/// # Errors
+/// Returns a not-found error if there is no such note, or an error if the
+/// datastore cannot be read.
+pub async fn get_note(datastore: &dyn DataStore, id: Uuid) -> DataStoreResult<Note> {
+    get(datastore, NOTES_NAMESPACE, "Note", id).await
+}
+
+/// Lists the notes on an entity, oldest first
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or a stored note is malformed.
+pub async fn list_notes(
+    datastore: &dyn DataStore,
+    target: NoteTarget,
+) -> DataStoreResult<Vec<Note>> {
+    let mut notes: Vec<Note> = list(datastore, NOTES_NAMESPACE, "note").await?;
+    notes.retain(|note| note.target == target);
+    notes.sort_by_key(|note| note.created_at);
+    Ok(notes)
+}
+
+/// Replaces the body of a note
+///
+/// # Errors
+/// Returns a validation error if the body is invalid, a not-found error if
+/// there is no such note, or an error if the datastore cannot be read or
+/// written.
+pub async fn update_note(
+    datastore: &dyn DataStore,
+    id: Uuid,
+    body: String,
+) -> DataStoreResult<Note> {
+    validate_body(&body)?;
+    let mut note = get_note(datastore, id).await?;
+    note.body = body;
+    note.updated_at = Utc::now();
+    put(datastore, NOTES_NAMESPACE, id, &note).await?;
+    Ok(note)
+}
+
+/// Removes a note
+///
+/// # Errors
+/// Returns an error if the note does not exist or the datastore write fails.
+pub async fn delete_note(datastore: &dyn DataStore, id: Uuid) -> DataStoreResult<()> {
+    datastore
+        .delete_setting(NOTES_NAMESPACE, &id.to_string())
+        .await
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,257 @@
+//! Small files attached to nodes, links, and locations
+//!
+//! Attachments are limited in size and media type by `server.attachments`.
+//! Their contents are written to `blob_dir`, one file per attachment named by
+//! its ID, or kept base64-encoded in the datastore when no directory is
+//! configured. Each attachment records where its contents went, so changing
+//! `blob_dir` later does not strand older attachments kept in the datastore.
+
+use super::{NoteTarget, get, list, put};
+use crate::config::AttachmentsConfig;
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult};
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Settings namespace holding attachment metadata keyed by ID
+const ATTACHMENTS_NAMESPACE: &str = "attachments";
+/// Settings namespace holding contents of attachments kept in the datastore
+const CONTENTS_NAMESPACE: &str = "attachment_contents";
+
+/// Longest attachment file name
+const MAX_FILENAME_LENGTH: usize = 255;
+
+/// Where an attachment's contents are kept
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttachmentStorage {
+    /// In the datastore
+    Datastore,
+    /// In the configured blob directory
+    BlobDir,
+}
+
+/// A file attached to an entity
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attachment {
+    /// Attachment ID
+    pub id: Uuid,
+    /// Entity the file is attached to
+    pub target: NoteTarget,
+    /// File name as uploaded
+    pub filename: String,
+    /// Media type, e.g. `image/png`
+    pub content_type: String,
+    /// Size in bytes
+    pub size: usize,
+    /// Where the contents are kept
+    pub storage: AttachmentStorage,
+    /// When the file was attached
+    pub created_at: DateTime<Utc>,
+}
+
+/// Media type without parameters, lowercased: `Text/Plain; charset=utf-8`
+/// is `text/plain`
+fn media_type(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+fn validate(
+    config: &AttachmentsConfig,
+    filename: &str,
+    content_type: &str,
+    size: usize,
+) -> DataStoreResult<()> {
+    let message = if filename.trim().is_empty() {
+        "Attachment file name cannot be empty".to_string()
+    } else if filename.len() > MAX_FILENAME_LENGTH || filename.contains(['/', '\\']) {
+        format!("Invalid attachment file name {filename:?}")
+    } else if !config
+        .allowed_types
+        .iter()
+        .any(|allowed| media_type(allowed) == content_type)
+    {
+        format!(
+            "Attachments of type {content_type:?} are not allowed; allowed types: {}",
+            config.allowed_types.join(", ")
+        )
+    } else if size == 0 {
+        "Attachment is empty".to_string()
+    } else if size > config.max_size {
+        format!(
+            "Attachment is {size} bytes; the limit is {} bytes",
+            config.max_size
+        )
+    } else {
+        return Ok(());
+    };
+    Err(DataStoreError::ValidationError { message })
+}
+
+fn blob_path(config: &AttachmentsConfig, id: Uuid) -> DataStoreResult<PathBuf> {
+    config
+        .blob_dir
+        .as_deref()
+        .map(|dir| Path::new(dir).join(id.to_string()))
+        .ok_or_else(|| DataStoreError::InternalError {
+            message: format!("Attachment {id} is kept in a blob directory, but none is configured"),
+        })
+}
+
+fn io_error(path: &Path, e: &std::io::Error) -> DataStoreError {
+    DataStoreError::ConnectionError {
+        message: format!("Attachment file {}: {e}", path.display()),
+    }
+}
+
+/// Attaches a file to an entity
+///
+/// # Errors
+/// Returns a validation error if the file name, media type, or size is not
+/// allowed, a not-found error if the entity does not exist, or an error if
+/// the contents or metadata cannot be written.
+pub async fn add_attachment(
+    datastore: &dyn DataStore,
+    config: &AttachmentsConfig,
+    target: NoteTarget,
+    filename: &str,
+    content_type: &str,
+    content: &[u8],
+) -> DataStoreResult<Attachment> {
+    let content_type = media_type(content_type);
+    validate(config, filename, &content_type, content.len())?;
+    target.ensure_exists(datastore).await?;
+
+    let id = Uuid::new_v4();
+    let storage = if config.blob_dir.is_some() {
+        let path = blob_path(config, id)?;
+        if let Some(dir) = path.parent() {
+            tokio::fs::create_dir_all(dir)
+                .await
+                .map_err(|e| io_error(dir, &e))?;
+        }
+        tokio::fs::write(&path, content)
+            .await
+            .map_err(|e| io_error(&path, &e))?;
+        AttachmentStorage::BlobDir
+    } else {
+        let encoded = serde_json::Value::String(STANDARD.encode(content));
+        datastore
+            .put_setting(CONTENTS_NAMESPACE, &id.to_string(), &encoded)
+            .await?;
+        AttachmentStorage::Datastore
+    };
+
+    let attachment = Attachment {
+        id,
+        target,
+        filename: filename.trim().to_string(),
+        content_type,
+        size: content.len(),
+        storage,
+        created_at: Utc::now(),
+    };
+    put(datastore, ATTACHMENTS_NAMESPACE, id, &attachment).await?;
+    Ok(attachment)
+}
+
+/// Gets an attachment's metadata
+///
+/// # Errors
+/// Returns a not-found error if there is no such attachment, or an error if
+/// the datastore cannot be read.
+pub async fn get_attachment(datastore: &dyn DataStore, id: Uuid) -> DataStoreResult<Attachment> {
+    get(datastore, ATTACHMENTS_NAMESPACE, "Attachment", id).await
+}
+
+/// Lists the files attached to an entity, oldest first
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or stored metadata is malformed.
+pub async fn list_attachments(
+    datastore: &dyn DataStore,
+    target: NoteTarget,
+) -> DataStoreResult<Vec<Attachment>> {
+    let mut attachments: Vec<Attachment> =
+        list(datastore, ATTACHMENTS_NAMESPACE, "attachment").await?;
+    attachments.retain(|attachment| attachment.target == target);
+    attachments.sort_by_key(|attachment| attachment.created_at);
+    Ok(attachments)
+}
+
+/// Reads an attachment's contents
+///
+/// # Errors
+/// Returns an error if the contents are missing or cannot be read.
+pub async fn attachment_content(
+    datastore: &dyn DataStore,
+    config: &AttachmentsConfig,
+    attachment: &Attachment,
+) -> DataStoreResult<Vec<u8>> {
+    match attachment.storage {
+        AttachmentStorage::BlobDir => {
+            let path = blob_path(config, attachment.id)?;
+            tokio::fs::read(&path)
+                .await
+                .map_err(|e| io_error(&path, &e))
+        }
+        AttachmentStorage::Datastore => {
+            let key = attachment.id.to_string();
+            let stored = datastore
+                .get_setting(CONTENTS_NAMESPACE, &key)
+                .await?
+                .ok_or_else(|| DataStoreError::NotFound {
+                    entity_type: "Attachment contents".to_string(),
+                    id: key.clone(),
+                })?;
+            stored
+                .as_str()
+                .and_then(|encoded| STANDARD.decode(encoded).ok())
+                .ok_or_else(|| DataStoreError::InternalError {
+                    message: format!("stored attachment contents {key} are not base64"),
+                })
+        }
+    }
+}
+
+/// Removes an attachment and its contents
+///
+/// # Errors
+/// Returns a not-found error if there is no such attachment, or an error if
+/// the datastore cannot be written. Contents already gone are ignored.
+pub async fn delete_attachment(
+    datastore: &dyn DataStore,
+    config: &AttachmentsConfig,
+    id: Uuid,
+) -> DataStoreResult<()> {
+    let attachment = get_attachment(datastore, id).await?;
+    datastore
+        .delete_setting(ATTACHMENTS_NAMESPACE, &id.to_string())
+        .await?;
+    match attachment.storage {
+        AttachmentStorage::BlobDir => {
+            let path = blob_path(config, id)?;
+            match tokio::fs::remove_file(&path).await {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(io_error(&path, &e)),
+                _ => Ok(()),
+            }
+        }
+        AttachmentStorage::Datastore => {
+            match datastore
+                .delete_setting(CONTENTS_NAMESPACE, &id.to_string())
+                .await
+            {
+                Err(DataStoreError::NotFound { .. }) | Ok(()) => Ok(()),
+                Err(e) => Err(e),
+            }
+        }
+    }
+}
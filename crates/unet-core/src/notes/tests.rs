@@ -0,0 +1,162 @@
+use super::*;
+use crate::config::AttachmentsConfig;
+use crate::datastore::sqlite::SqliteStore;
+use crate::datastore::sqlite::tests::setup::migrated_store;
+use crate::models::{DeviceRole, Node, Vendor};
+
+async fn create_node(store: &SqliteStore, name: &str) -> Node {
+    let node = Node::new(
+        name.to_string(),
+        "example.com".to_string(),
+        Vendor::Cisco,
+        DeviceRole::Router,
+    );
+    store.create_node(&node).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_notes_listed_per_entity() {
+    let store = migrated_store().await;
+    let core = NoteTarget::Node(create_node(&store, "core-01").await.id);
+    let edge = NoteTarget::Node(create_node(&store, "edge-01").await.id);
+
+    let first = Note::new(core, "RMA open for PSU 2".to_string(), None).unwrap();
+    add_note(&store, &first).await.unwrap();
+    let other = Note::new(edge, "Spare optics in rack B".to_string(), None).unwrap();
+    add_note(&store, &other).await.unwrap();
+    let second = Note::new(core, "PSU replaced".to_string(), Some("noc".to_string())).unwrap();
+    add_note(&store, &second).await.unwrap();
+
+    let notes = list_notes(&store, core).await.unwrap();
+    assert_eq!(notes, [first.clone(), second]);
+
+    let updated = update_note(&store, first.id, "RMA closed".to_string())
+        .await
+        .unwrap();
+    assert_eq!(updated.body, "RMA closed");
+    assert!(updated.updated_at >= first.updated_at);
+
+    delete_note(&store, first.id).await.unwrap();
+    assert_eq!(list_notes(&store, core).await.unwrap().len(), 1);
+    assert!(matches!(
+        get_note(&store, first.id).await,
+        Err(DataStoreError::NotFound { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_note_validation() {
+    let store = migrated_store().await;
+    let missing = NoteTarget::Location(Uuid::new_v4());
+
+    assert!(Note::new(missing, "  ".to_string(), None).is_err());
+    assert!(Note::new(missing, "x".repeat(MAX_NOTE_LENGTH + 1), None).is_err());
+    let note = Note::new(missing, "Cabinet moved".to_string(), None).unwrap();
+    assert!(matches!(
+        add_note(&store, &note).await,
+        Err(DataStoreError::NotFound { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_attachment_kept_in_datastore() {
+    let store = migrated_store().await;
+    let target = NoteTarget::Node(create_node(&store, "core-01").await.id);
+    let config = AttachmentsConfig::default();
+
+    let attachment = add_attachment(
+        &store,
+        &config,
+        target,
+        "rack.png",
+        "image/png",
+        &[0x89, b'P', b'N', b'G'],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(attachment.storage, AttachmentStorage::Datastore);
+    assert_eq!(attachment.size, 4);
+    assert_eq!(
+        list_attachments(&store, target).await.unwrap(),
+        [attachment.clone()]
+    );
+    assert_eq!(
+        attachment_content(&store, &config, &attachment)
+            .await
+            .unwrap(),
+        [0x89, b'P', b'N', b'G']
+    );
+
+    delete_attachment(&store, &config, attachment.id)
+        .await
+        .unwrap();
+    assert!(list_attachments(&store, target).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_attachment_kept_in_blob_dir() {
+    let store = migrated_store().await;
+    let target = NoteTarget::Node(create_node(&store, "core-01").await.id);
+    let dir = tempfile::tempdir().unwrap();
+    let config = AttachmentsConfig {
+        blob_dir: Some(dir.path().join("blobs").display().to_string()),
+        ..AttachmentsConfig::default()
+    };
+
+    let attachment = add_attachment(
+        &store,
+        &config,
+        target,
+        "handover.txt",
+        "text/plain; charset=utf-8",
+        b"circuit 42",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(attachment.storage, AttachmentStorage::BlobDir);
+    assert_eq!(attachment.content_type, "text/plain");
+    let path = dir.path().join("blobs").join(attachment.id.to_string());
+    assert_eq!(std::fs::read(&path).unwrap(), b"circuit 42");
+    assert_eq!(
+        attachment_content(&store, &config, &attachment)
+            .await
+            .unwrap(),
+        b"circuit 42"
+    );
+
+    delete_attachment(&store, &config, attachment.id)
+        .await
+        .unwrap();
+    assert!(!path.exists());
+}
+
+#[tokio::test]
+async fn test_attachment_limits() {
+    let store = migrated_store().await;
+    let target = NoteTarget::Node(create_node(&store, "core-01").await.id);
+    let config = AttachmentsConfig {
+        max_size: 8,
+        ..AttachmentsConfig::default()
+    };
+
+    let cases = [
+        (
+            "big.txt",
+            "text/plain",
+            b"too many bytes".as_slice(),
+            "limit is 8",
+        ),
+        ("tool.exe", "application/octet-stream", b"MZ", "not allowed"),
+        ("../etc/passwd", "text/plain", b"x", "file name"),
+        ("empty.txt", "text/plain", b"", "empty"),
+    ];
+    for (filename, content_type, content, expected) in cases {
+        let error = add_attachment(&store, &config, target, filename, content_type, content)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains(expected), "{filename}: {error}");
+    }
+    assert!(list_attachments(&store, target).await.unwrap().is_empty());
+}
@@ -0,0 +1,357 @@
+//! Facts read from a live device for adding it to the inventory
+//!
+//! Onboarding polls a device that is not in the inventory yet for `sysDescr`,
+//! `sysName`, `sysLocation`, and the interface table. The functions here turn
+//! those values into node fields: the vendor and model named in `sysDescr`,
+//! the name and domain in `sysName`, the interfaces, and the location picked
+//! by the first configured rule matching `sysLocation`.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::LazyLock;
+
+use crate::config::LocationRule;
+use crate::error::{Error, Result};
+use crate::models::Vendor;
+use crate::snmp::{SnmpValue, StandardOid};
+
+/// An interface from the device's `ifTable`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiscoveredInterface {
+    /// `ifIndex`
+    pub index: u32,
+    /// `ifDescr`, e.g. `GigabitEthernet0/0/1`
+    pub name: String,
+    /// IANA `ifType`, e.g. 6 for Ethernet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub if_type: Option<i64>,
+    /// MTU in bytes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mtu: Option<u64>,
+    /// Speed in bits per second
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speed: Option<u64>,
+    /// Physical address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mac_address: Option<String>,
+    /// Whether the interface is administratively up
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub admin_up: Option<bool>,
+}
+
+/// What a device reported about itself
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeviceFacts {
+    /// `sysDescr`
+    pub sys_descr: Option<String>,
+    /// `sysName`
+    pub sys_name: Option<String>,
+    /// `sysLocation`
+    pub sys_location: Option<String>,
+    /// Interfaces ordered by index
+    pub interfaces: Vec<DiscoveredInterface>,
+}
+
+impl DeviceFacts {
+    /// Reads the system group values out of a GET response
+    #[must_use]
+    pub fn from_system(values: &HashMap<String, SnmpValue>) -> Self {
+        let text = |oid: StandardOid| {
+            values
+                .get(oid.oid())
+                .and_then(SnmpValue::as_str)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(ToString::to_string)
+        };
+        Self {
+            sys_descr: text(StandardOid::SysDescr),
+            sys_name: text(StandardOid::SysName),
+            sys_location: text(StandardOid::SysLocation),
+            interfaces: Vec::new(),
+        }
+    }
+
+    /// Vendor named in `sysDescr`, or [`Vendor::Generic`]
+    #[must_use]
+    pub fn vendor(&self) -> Vendor {
+        self.sys_descr
+            .as_deref()
+            .map_or(Vendor::Generic, detect_vendor)
+    }
+
+    /// Model named in `sysDescr`, if the vendor names one there
+    #[must_use]
+    pub fn model(&self) -> Option<String> {
+        self.sys_descr.as_deref().and_then(detect_model)
+    }
+
+    /// Host name and domain from `sysName`
+    #[must_use]
+    pub fn name_and_domain(&self) -> Option<(String, Option<String>)> {
+        let sys_name = self.sys_name.as_deref()?.trim_end_matches('.');
+        match sys_name.split_once('.') {
+            Some((name, domain)) if !name.is_empty() => {
+                Some((name.to_string(), Some(domain.to_string())))
+            }
+            _ => Some((sys_name.to_string(), None)),
+        }
+    }
+
+    /// Interfaces as stored in the node's `custom_data.interfaces`
+    #[must_use]
+    pub fn interfaces_value(&self) -> Value {
+        serde_json::to_value(&self.interfaces).unwrap_or(Value::Null)
+    }
+}
+
+/// Vendors and the words in `sysDescr` that identify them, tried in order
+const VENDOR_MARKERS: [(Vendor, &[&str]); 10] = [
+    (Vendor::Cisco, &["cisco"]),
+    (Vendor::Juniper, &["juniper", "junos"]),
+    (Vendor::Arista, &["arista"]),
+    (Vendor::PaloAlto, &["palo alto", "pan-os"]),
+    (Vendor::Fortinet, &["fortinet", "fortigate"]),
+    (Vendor::Hpe, &["hewlett", "hpe", "aruba", "procurve"]),
+    (Vendor::Dell, &["dell"]),
+    (Vendor::Extreme, &["extreme"]),
+    (Vendor::Mikrotik, &["mikrotik", "routeros"]),
+    (Vendor::Ubiquiti, &["ubiquiti", "edgeos", "unifi"]),
+];
+
+/// Vendor named in a `sysDescr` value, or [`Vendor::Generic`]
+#[must_use]
+pub fn detect_vendor(sys_descr: &str) -> Vendor {
+    let descr = sys_descr.to_lowercase();
+    VENDOR_MARKERS
+        .iter()
+        .find(|(_, markers)| markers.iter().any(|marker| descr.contains(marker)))
+        .map_or(Vendor::Generic, |(vendor, _)| *vendor)
+}
+
+/// Compiles one of the fixed patterns below
+fn pattern(source: &str) -> Regex {
+    Regex::new(source).expect("built-in sysDescr pattern is valid")
+}
+
+/// `Cisco IOS Software, C2960X Software (...)`; IOS-XE descriptions name a
+/// product family in several words instead and are not matched
+static CISCO_IOS_MODEL: LazyLock<Regex> =
+    LazyLock::new(|| pattern(r"Cisco IOS Software(?: \[\w+\])?, (\S+) Software \("));
+/// `Cisco NX-OS(tm) n9000, Software (...)`
+static CISCO_NXOS_MODEL: LazyLock<Regex> = LazyLock::new(|| pattern(r"NX-OS\(tm\) ([^\s,]+),"));
+/// `Juniper Networks, Inc. mx204 internet router, ...`
+static JUNIPER_MODEL: LazyLock<Regex> =
+    LazyLock::new(|| pattern(r"Juniper Networks, Inc\. ([^\s,]+)"));
+/// `... running on an Arista Networks DCS-7050SX3-48YC8`
+static ARISTA_MODEL: LazyLock<Regex> =
+    LazyLock::new(|| pattern(r"running on an Arista Networks (\S+)"));
+/// `RouterOS CCR1036-12G-4S`; the board name, not a version
+static ROUTEROS_MODEL: LazyLock<Regex> = LazyLock::new(|| pattern(r"RouterOS ([A-Za-z][^\s,]*)"));
+
+/// Model named in a `sysDescr` value, upper-cased
+///
+/// Only some vendors name the model there; Cisco IOS-XE, for one, names a
+/// product family, so `None` is common.
+#[must_use]
+pub fn detect_model(sys_descr: &str) -> Option<String> {
+    [
+        &CISCO_IOS_MODEL,
+        &CISCO_NXOS_MODEL,
+        &JUNIPER_MODEL,
+        &ARISTA_MODEL,
+        &ROUTEROS_MODEL,
+    ]
+    .iter()
+    .find_map(|regex| regex.captures(sys_descr))
+    .map(|captures| captures[1].to_uppercase())
+}
+
+/// Builds interfaces from an `ifTable` walk, ordered by index
+///
+/// Rows without an `ifDescr` are left out.
+#[must_use]
+pub fn parse_if_table(walk: &HashMap<String, SnmpValue>) -> Vec<DiscoveredInterface> {
+    let table = StandardOid::IfTable.oid();
+    let mut rows: BTreeMap<u32, HashMap<u32, &SnmpValue>> = BTreeMap::new();
+    for (oid, value) in walk {
+        let Some(suffix) = oid
+            .trim_start_matches('.')
+            .strip_prefix(table)
+            .and_then(|rest| rest.strip_prefix('.'))
+        else {
+            continue;
+        };
+        let Some((column, index)) = suffix.split_once('.') else {
+            continue;
+        };
+        if let (Ok(column), Ok(index)) = (column.parse(), index.parse()) {
+            rows.entry(index).or_default().insert(column, value);
+        }
+    }
+
+    rows.into_iter()
+        .filter_map(|(index, columns)| {
+            let column = |number: u32| columns.get(&number).copied();
+            Some(DiscoveredInterface {
+                index,
+                name: column(2)?.as_str()?.trim().to_string(),
+                if_type: column(3).and_then(SnmpValue::as_i64),
+                mtu: column(4).and_then(SnmpValue::as_u64),
+                speed: column(5).and_then(SnmpValue::as_u64),
+                mac_address: column(6).and_then(SnmpValue::as_mac),
+                // ifAdminStatus: up(1), down(2), testing(3)
+                admin_up: column(7)
+                    .and_then(SnmpValue::as_i64)
+                    .map(|status| status == 1),
+            })
+        })
+        .collect()
+}
+
+/// First rule whose pattern matches `sys_location`
+///
+/// # Errors
+/// Returns an error if a rule tried before the match has an invalid pattern.
+pub fn match_location_rule<'a>(
+    rules: &'a [LocationRule],
+    sys_location: &str,
+) -> Result<Option<&'a LocationRule>> {
+    for rule in rules {
+        let regex = Regex::new(&rule.pattern).map_err(|e| {
+            Error::validation_with_value(
+                "onboarding.location_rules",
+                format!("invalid pattern: {e}"),
+                &rule.pattern,
+            )
+        })?;
+        if regex.is_match(sys_location) {
+            return Ok(Some(rule));
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(pattern: &str, location: &str) -> LocationRule {
+        LocationRule {
+            pattern: pattern.to_string(),
+            location: location.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_detect_vendor_and_model() {
+        let cases = [
+            (
+                "Cisco IOS Software, C2960X Software (C2960X-UNIVERSALK9-M), Version 15.2(4)E10, RELEASE SOFTWARE (fc2)",
+                Vendor::Cisco,
+                Some("C2960X"),
+            ),
+            (
+                "Cisco IOS Software [Amsterdam], Catalyst L3 Switch Software (CAT9K_IOSXE), Version 17.3.4, RELEASE SOFTWARE (fc3)",
+                Vendor::Cisco,
+                None,
+            ),
+            (
+                "Juniper Networks, Inc. mx204 internet router, kernel JUNOS 21.4R3-S2.3, Build date: 2022-09-01",
+                Vendor::Juniper,
+                Some("MX204"),
+            ),
+            (
+                "Arista Networks EOS version 4.28.3M running on an Arista Networks DCS-7050SX3-48YC8",
+                Vendor::Arista,
+                Some("DCS-7050SX3-48YC8"),
+            ),
+            (
+                "RouterOS CCR1036-12G-4S",
+                Vendor::Mikrotik,
+                Some("CCR1036-12G-4S"),
+            ),
+            ("Linux fw01 5.15.0-91-generic", Vendor::Generic, None),
+        ];
+        for (descr, vendor, model) in cases {
+            assert_eq!(detect_vendor(descr), vendor, "{descr}");
+            assert_eq!(detect_model(descr).as_deref(), model, "{descr}");
+        }
+    }
+
+    #[test]
+    fn test_name_and_domain_split_sys_name() {
+        let facts = |sys_name: &str| DeviceFacts {
+            sys_name: Some(sys_name.to_string()),
+            ..DeviceFacts::default()
+        };
+        assert_eq!(
+            facts("edge-01.dc1.example.com").name_and_domain(),
+            Some(("edge-01".to_string(), Some("dc1.example.com".to_string())))
+        );
+        assert_eq!(
+            facts("edge-01").name_and_domain(),
+            Some(("edge-01".to_string(), None))
+        );
+        assert_eq!(DeviceFacts::default().name_and_domain(), None);
+    }
+
+    #[test]
+    fn test_parse_if_table_groups_columns_by_index() {
+        let table = StandardOid::IfTable.oid();
+        let walk = HashMap::from([
+            (
+                format!("{table}.2.2"),
+                SnmpValue::String("Gi0/0/2".to_string()),
+            ),
+            (
+                format!("{table}.2.1"),
+                SnmpValue::String("Gi0/0/1".to_string()),
+            ),
+            (format!("{table}.3.1"), SnmpValue::Integer(6)),
+            (format!("{table}.4.1"), SnmpValue::Integer(1500)),
+            (format!("{table}.5.1"), SnmpValue::Gauge32(1_000_000_000)),
+            (
+                format!("{table}.6.1"),
+                SnmpValue::Opaque(vec![0x00, 0x1b, 0x54, 0xaa, 0xbb, 0xcc]),
+            ),
+            (format!("{table}.7.1"), SnmpValue::Integer(1)),
+            (format!("{table}.7.2"), SnmpValue::Integer(2)),
+            // A row without ifDescr
+            (format!("{table}.7.3"), SnmpValue::Integer(1)),
+        ]);
+
+        let interfaces = parse_if_table(&walk);
+
+        assert_eq!(interfaces.len(), 2);
+        assert_eq!(interfaces[0].index, 1);
+        assert_eq!(interfaces[0].name, "Gi0/0/1");
+        assert_eq!(interfaces[0].if_type, Some(6));
+        assert_eq!(interfaces[0].mtu, Some(1500));
+        assert_eq!(interfaces[0].speed, Some(1_000_000_000));
+        assert!(interfaces[0].mac_address.is_some());
+        assert_eq!(interfaces[0].admin_up, Some(true));
+        assert_eq!(interfaces[1].name, "Gi0/0/2");
+        assert_eq!(interfaces[1].admin_up, Some(false));
+    }
+
+    #[test]
+    fn test_match_location_rule_takes_first_match() {
+        let rules = [
+            rule(r"(?i)^dc1\b", "us-east/dc1"),
+            rule(r"(?i)ashburn", "us-east"),
+        ];
+        let matched = match_location_rule(&rules, "DC1, Row 4, Rack 12").unwrap();
+        assert_eq!(
+            matched.map(|rule| rule.location.as_str()),
+            Some("us-east/dc1")
+        );
+        let matched = match_location_rule(&rules, "Ashburn VA").unwrap();
+        assert_eq!(matched.map(|rule| rule.location.as_str()), Some("us-east"));
+        assert!(match_location_rule(&rules, "Lab").unwrap().is_none());
+
+        assert!(match_location_rule(&[rule("(", "dc1")], "Lab").is_err());
+    }
+}
@@ -0,0 +1,144 @@
+//! Configuration sections μNet manages on a node
+//!
+//! Nodes shared with other automation list the sections μNet owns as slice
+//! patterns (see [`config_slicer::slicer`]) under the `owned_sections` key of
+//! their `custom_data`. Drift detection only compares owned sections, so
+//! whatever other tools manage elsewhere in the configuration never shows up
+//! as drift. A node without the key owns its whole configuration.
+//!
+//! Role or vendor `custom_data` defaults (see [`crate::node_defaults`]) can
+//! set the key for every new node of a role.
+
+use crate::datastore::{DataStoreError, DataStoreResult};
+use crate::models::Node;
+use config_slicer::parser::ConfigNode;
+use config_slicer::slicer::{MatchSpec, slice_any};
+use serde_json::{Map, Value};
+
+/// `custom_data` key holding the owned-section patterns
+pub const OWNED_SECTIONS_KEY: &str = "owned_sections";
+
+/// Returns the node's owned-section patterns, or `None` when it owns its
+/// whole configuration
+///
+/// # Errors
+/// Returns a validation error if the key does not hold a list of strings.
+pub fn owned_patterns(node: &Node) -> DataStoreResult<Option<Vec<String>>> {
+    match node.custom_data.get(OWNED_SECTIONS_KEY) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => serde_json::from_value(value.clone())
+            .map(Some)
+            .map_err(|e| DataStoreError::ValidationError {
+                message: format!(
+                    "custom_data.{OWNED_SECTIONS_KEY} of {} must be a list of slice patterns: {e}",
+                    node.name
+                ),
+            }),
+    }
+}
+
+/// Parses slice patterns
+///
+/// # Errors
+/// Returns a validation error naming the first invalid pattern.
+pub fn parse_patterns(patterns: &[String]) -> DataStoreResult<Vec<MatchSpec>> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            pattern
+                .parse()
+                .map_err(|e| DataStoreError::ValidationError {
+                    message: format!("Invalid owned section pattern {pattern:?}: {e}"),
+                })
+        })
+        .collect()
+}
+
+/// Restricts a parsed configuration to the sections the node owns
+///
+/// # Errors
+/// Returns a validation error if the node's patterns are malformed.
+pub fn owned_config(node: &Node, config: &[ConfigNode]) -> DataStoreResult<Vec<ConfigNode>> {
+    match owned_patterns(node)? {
+        None => Ok(config.to_vec()),
+        Some(patterns) => Ok(slice_any(&parse_patterns(&patterns)?, config)),
+    }
+}
+
+/// Sets the node's owned-section patterns, or removes them with `None`
+///
+/// Only updates the node in memory; the caller saves it.
+///
+/// # Errors
+/// Returns a validation error if a pattern is invalid or the node's
+/// `custom_data` is not an object.
+pub fn set_owned_patterns(node: &mut Node, patterns: Option<Vec<String>>) -> DataStoreResult<()> {
+    if let Some(patterns) = &patterns {
+        parse_patterns(patterns)?;
+    }
+    if node.custom_data.is_null() {
+        node.custom_data = Value::Object(Map::new());
+    }
+    let Value::Object(custom_data) = &mut node.custom_data else {
+        return Err(DataStoreError::ValidationError {
+            message: format!("custom_data of {} is not a JSON object", node.name),
+        });
+    };
+    match patterns {
+        Some(patterns) => {
+            custom_data.insert(OWNED_SECTIONS_KEY.to_string(), Value::from(patterns));
+        }
+        None => {
+            custom_data.remove(OWNED_SECTIONS_KEY);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DeviceRole, Vendor};
+    use config_slicer::parser::{parse, render};
+    use serde_json::json;
+
+    fn node() -> Node {
+        Node::new(
+            "edge-1".to_string(),
+            "example.com".to_string(),
+            Vendor::Cisco,
+            DeviceRole::Router,
+        )
+    }
+
+    #[test]
+    fn test_unannotated_node_owns_everything() {
+        let config = parse("hostname r1\nntp server 10.0.0.1\n");
+
+        assert_eq!(owned_config(&node(), &config).unwrap(), config);
+    }
+
+    #[test]
+    fn test_owned_config_keeps_only_owned_sections() {
+        let mut node = node();
+        set_owned_patterns(&mut node, Some(vec!["ntp .*".to_string()])).unwrap();
+
+        let config = parse("hostname r1\nntp server 10.0.0.1\nsnmp-server community x\n");
+
+        assert_eq!(
+            render(&owned_config(&node, &config).unwrap()),
+            "ntp server 10.0.0.1\n"
+        );
+        set_owned_patterns(&mut node, None).unwrap();
+        assert_eq!(node.custom_data, json!({}));
+    }
+
+    #[test]
+    fn test_invalid_annotations_are_rejected() {
+        let mut node = node();
+        assert!(set_owned_patterns(&mut node, Some(vec!["ntp (".to_string()])).is_err());
+
+        node.custom_data = json!({ "owned_sections": "ntp .*" });
+        assert!(owned_patterns(&node).is_err());
+    }
+}
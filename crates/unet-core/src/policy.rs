@@ -26,11 +26,18 @@
 //! // Evaluate the rule
 //! let result = PolicyEvaluator::evaluate_rule(&rule, &context).unwrap();
 //! ```
+//!
+//! The parser and the policy file loader need the `policy` feature; rules
+//! built directly from the AST can be evaluated without it.
 
 mod ast;
 mod evaluator;
+#[cfg(feature = "policy")]
 mod grammar;
+mod lint;
+#[cfg(feature = "policy")]
 mod loader;
+#[cfg(feature = "policy")]
 mod parser;
 
 #[cfg(test)]
@@ -39,22 +46,29 @@ mod tests;
 #[cfg(test)]
 mod ast_comprehensive_tests;
 
-pub use ast::{Action, ComparisonOperator, Condition, FieldRef, PolicyRule, Value};
+pub use ast::{
+    Action, ComparisonOperator, Condition, FieldRef, PolicyRule, PolicyStatement, Value,
+};
 pub use evaluator::{
-    ActionExecutionResult, ActionResult, AggregatedResult, EvaluationBatch, EvaluationContext,
-    EvaluationResult, OrchestrationConfig, OrchestrationRule, PolicyEvaluator,
-    PolicyExecutionContext, PolicyExecutionResult, PolicyOrchestrator, PolicyPriority,
-    PolicyTransaction, RollbackData, RollbackResult,
+    ActionExecutionResult, ActionResult, AggregatedResult, AppliedExemption, CacheMetrics,
+    EvaluationBatch, EvaluationContext, EvaluationLimits, EvaluationResult, OrchestrationConfig,
+    OrchestrationRule, PolicyEvaluator, PolicyExecutionContext, PolicyExecutionResult,
+    PolicyOrchestrator, PolicyPriority, PolicyTransaction, PredictedChange, RollbackData,
+    RollbackResult, RuleAbort, rule_fields,
 };
+pub use lint::{LintKind, LintWarning, lint_rules};
+#[cfg(feature = "policy")]
 pub use loader::{
     CacheStats, LoadResult, PolicyFile, PolicyLoader, ValidationError, ValidationResult,
 };
+#[cfg(feature = "policy")]
 pub use parser::{ParseError, PolicyParser};
 
 /// Policy engine errors
 #[derive(Debug, thiserror::Error)]
 pub enum PolicyError {
     /// Parse error from policy parsing
+    #[cfg(feature = "policy")]
     #[error("Parse error: {0}")]
     Parse(#[from] ParseError),
 
@@ -120,9 +134,20 @@ pub enum PolicyError {
         feature: String,
     },
 
+    #[error("No policies source configured (neither local directory nor Git repository)")]
+    /// Neither a local policies directory nor a Git repository is configured
+    NoSource,
+
     #[error("Evaluation error: {0}")]
     /// General evaluation error
     EvaluationError(String),
+
+    #[error("Rule aborted: {reason}")]
+    /// Rule evaluation was abandoned for exceeding an evaluation limit
+    RuleAborted {
+        /// The limit the rule exceeded
+        reason: RuleAbort,
+    },
 }
 
 /// Policy engine result type
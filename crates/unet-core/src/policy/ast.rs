@@ -121,6 +121,24 @@ pub enum Value {
     Object(HashMap<String, Self>),
 }
 
+/// A top-level statement of a policy file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PolicyStatement {
+    /// A policy rule
+    Rule(PolicyRule),
+    /// `INCLUDE "path"`: rules and groups of another file, relative to this one
+    Include(String),
+    /// `GROUP name { ... }`: rules that apply only where the group is used
+    Group {
+        /// Group name
+        name: String,
+        /// Rules in the group
+        rules: Vec<PolicyRule>,
+    },
+    /// `USE name`: applies the rules of a group
+    Use(String),
+}
+
 impl fmt::Display for FieldRef {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.path.join("."))
@@ -4,10 +4,13 @@
 //! with proper rollback support for transactional policy evaluation.
 
 use super::context::{
-    ActionExecutionResult, ActionResult, EvaluationContext, PolicyExecutionContext, RollbackData,
+    ActionExecutionResult, ActionResult, EvaluationContext, PolicyExecutionContext,
+    PredictedChange, RollbackData,
 };
 use crate::policy::PolicyError;
 use crate::policy::ast::{Action, FieldRef, Value};
+use crate::snmp::adjustments::PollingSetting;
+use crate::snmp::profiles::load_catalog;
 use serde_json::Value as JsonValue;
 
 /// Action executor for policy actions
@@ -80,10 +83,22 @@ impl ActionExecutor {
             });
         }
 
-        // For SET actions, we only support setting values in custom_data
+        // Polling settings are applied when results are stored, not on the node
+        if let Some(setting) = PollingSetting::from_action(field, value) {
+            let setting = setting.map_err(|message| PolicyError::ValidationError { message })?;
+            Self::check_polling_setting(&setting, exec_ctx).await?;
+            return Ok(ActionExecutionResult {
+                result: ActionResult::Success {
+                    message: format!("Polling adjusted: {field} set to {value}"),
+                },
+                rollback_data: None,
+            });
+        }
+
+        // Otherwise, we only support setting values in custom_data
         if field.path[0] != "custom_data" {
             return Err(PolicyError::ValidationError {
-                message: "Only custom_data fields can be modified".to_string(),
+                message: "Only custom_data and polling fields can be modified".to_string(),
             });
         }
 
@@ -130,6 +145,28 @@ impl ActionExecutor {
         })
     }
 
+    /// Checks that a profile named by a polling adjustment exists
+    async fn check_polling_setting(
+        setting: &PollingSetting,
+        exec_ctx: &PolicyExecutionContext<'_>,
+    ) -> Result<(), PolicyError> {
+        let PollingSetting::Profile(name) = setting else {
+            return Ok(());
+        };
+        let catalog =
+            load_catalog(exec_ctx.datastore)
+                .await
+                .map_err(|e| PolicyError::DataStoreError {
+                    message: e.to_string(),
+                })?;
+        if catalog.profile(name).is_none() {
+            return Err(PolicyError::ValidationError {
+                message: format!("Unknown OID profile: {name}"),
+            });
+        }
+        Ok(())
+    }
+
     /// Execute APPLY action with rollback support - assigns template to node for configuration generation
     ///
     /// # Errors
@@ -197,6 +234,75 @@ impl ActionExecutor {
         })
     }
 
+    /// Predict the change a SET or APPLY action would make, without writing
+    /// anything
+    ///
+    /// `custom_data` is the node's `custom_data` as the changes predicted
+    /// before left it, and is updated with this one, as executing the actions
+    /// in order would. ASSERT actions change nothing and return `None`.
+    /// Polling adjustments apply to stored results rather than the node, so
+    /// they have no previous value.
+    ///
+    /// # Errors
+    /// Returns a validation error for the actions execution would reject,
+    /// except that OID profile names are not checked against the catalog
+    pub fn predict_action(
+        action: &Action,
+        context: &EvaluationContext,
+        custom_data: &mut JsonValue,
+    ) -> Result<Option<PredictedChange>, PolicyError> {
+        match action {
+            Action::Assert { .. } => Ok(None),
+            Action::Set { field, value } => {
+                if field.path.is_empty() {
+                    return Err(PolicyError::ValidationError {
+                        message: "Field path cannot be empty".to_string(),
+                    });
+                }
+                if let Some(setting) = PollingSetting::from_action(field, value) {
+                    setting.map_err(|message| PolicyError::ValidationError { message })?;
+                    return Ok(Some(PredictedChange {
+                        field: field.to_string(),
+                        previous: None,
+                        value: Self::resolve_value(value, context)?,
+                    }));
+                }
+                if field.path[0] != "custom_data" {
+                    return Err(PolicyError::ValidationError {
+                        message: "Only custom_data and polling fields can be modified".to_string(),
+                    });
+                }
+                let previous = Self::get_nested_field(custom_data, &field.path[1..]);
+                let new_value = Self::resolve_value(value, context)?;
+                Self::set_nested_field(custom_data, &field.path[1..], new_value.clone())?;
+                Ok(Some(PredictedChange {
+                    field: field.to_string(),
+                    previous,
+                    value: new_value,
+                }))
+            }
+            Action::ApplyTemplate { template_path } => {
+                let JsonValue::Object(map) = custom_data else {
+                    return Err(PolicyError::ValidationError {
+                        message: "custom_data must be an object".to_string(),
+                    });
+                };
+                if map.contains_key("template") {
+                    return Err(PolicyError::ValidationError {
+                        message: "Template already assigned to node".to_string(),
+                    });
+                }
+                let value = JsonValue::String(template_path.clone());
+                map.insert("template".to_string(), value.clone());
+                Ok(Some(PredictedChange {
+                    field: "custom_data.template".to_string(),
+                    previous: None,
+                    value,
+                }))
+            }
+        }
+    }
+
     /// Helper function to set a nested field in JSON data
     fn set_nested_field(
         data: &mut JsonValue,
@@ -14,6 +14,10 @@ mod set_action_tests;
 #[path = "apply_template_tests.rs"]
 mod apply_template_tests;
 
+#[cfg(test)]
+#[path = "predict_action_tests.rs"]
+mod predict_action_tests;
+
 #[cfg(test)]
 #[path = "resolve_value_tests.rs"]
 mod resolve_value_tests;
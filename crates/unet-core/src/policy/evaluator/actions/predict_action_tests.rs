@@ -0,0 +1,119 @@
+//! Tests for predicting the changes of actions without executing them
+
+use crate::policy::PolicyError;
+use crate::policy::ast::{Action, FieldRef, Value};
+use crate::policy::evaluator::actions::ActionExecutor;
+use crate::policy::evaluator::context::{EvaluationContext, PredictedChange};
+use serde_json::json;
+
+fn field(path: &str) -> FieldRef {
+    FieldRef {
+        path: path.split('.').map(str::to_string).collect(),
+    }
+}
+
+fn set(path: &str, value: Value) -> Action {
+    Action::Set {
+        field: field(path),
+        value,
+    }
+}
+
+#[test]
+fn test_predict_set_tracks_previous_values_in_order() {
+    let context = EvaluationContext::new(json!({ "vendor": "cisco" }));
+    let mut custom_data = json!({ "ntp": { "primary": "10.0.0.1" } });
+
+    let change = ActionExecutor::predict_action(
+        &set(
+            "custom_data.ntp.primary",
+            Value::String("10.0.0.2".to_string()),
+        ),
+        &context,
+        &mut custom_data,
+    )
+    .unwrap();
+    assert_eq!(
+        change,
+        Some(PredictedChange {
+            field: "custom_data.ntp.primary".to_string(),
+            previous: Some(json!("10.0.0.1")),
+            value: json!("10.0.0.2"),
+        })
+    );
+
+    // A later action sees the earlier one's change
+    let change = ActionExecutor::predict_action(
+        &set(
+            "custom_data.ntp.primary",
+            Value::String("10.0.0.3".to_string()),
+        ),
+        &context,
+        &mut custom_data,
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(change.previous, Some(json!("10.0.0.2")));
+
+    let change = ActionExecutor::predict_action(
+        &set("custom_data.snmp.enabled", Value::Boolean(true)),
+        &context,
+        &mut custom_data,
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(change.previous, None);
+    assert_eq!(
+        custom_data,
+        json!({ "ntp": { "primary": "10.0.0.3" }, "snmp": { "enabled": true } })
+    );
+}
+
+#[test]
+fn test_predict_apply_assert_and_polling() {
+    let context = EvaluationContext::new(json!({}));
+    let mut custom_data = json!({});
+
+    let apply = Action::ApplyTemplate {
+        template_path: "templates/edge.j2".to_string(),
+    };
+    let change = ActionExecutor::predict_action(&apply, &context, &mut custom_data)
+        .unwrap()
+        .unwrap();
+    assert_eq!(change.field, "custom_data.template");
+    assert_eq!(custom_data["template"], "templates/edge.j2");
+    // Applying a second template fails as execution would
+    assert!(matches!(
+        ActionExecutor::predict_action(&apply, &context, &mut custom_data),
+        Err(PolicyError::ValidationError { .. })
+    ));
+
+    let assert = Action::Assert {
+        field: field("vendor"),
+        expected: Value::String("cisco".to_string()),
+    };
+    assert_eq!(
+        ActionExecutor::predict_action(&assert, &context, &mut custom_data).unwrap(),
+        None
+    );
+
+    let change = ActionExecutor::predict_action(
+        &set("polling.interval", Value::String("30s".to_string())),
+        &context,
+        &mut custom_data,
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(change.field, "polling.interval");
+    assert_eq!(change.value, json!("30s"));
+    assert_eq!(custom_data, json!({ "template": "templates/edge.j2" }));
+
+    assert!(
+        ActionExecutor::predict_action(
+            &set("vendor", Value::String("juniper".to_string())),
+            &context,
+            &mut custom_data,
+        )
+        .is_err()
+    );
+}
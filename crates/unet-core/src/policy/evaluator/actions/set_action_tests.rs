@@ -71,7 +71,7 @@ async fn test_execute_set_action_with_non_custom_data_field() {
     assert!(result.is_err());
     match result.unwrap_err() {
         PolicyError::ValidationError { message } => {
-            assert!(message.contains("Only custom_data fields can be modified"));
+            assert!(message.contains("Only custom_data and polling fields can be modified"));
         }
         other => panic!("Expected ValidationError, got {other:?}"),
     }
@@ -116,3 +116,44 @@ async fn test_execute_set_action_with_nonexistent_node() {
     // The exact error type depends on datastore implementation
     // but it should be an error since the node doesn't exist
 }
+
+#[tokio::test]
+async fn test_execute_set_action_on_polling_fields() {
+    let datastore = setup_test_datastore().await;
+    let node = create_test_node(&datastore).await;
+    let context = create_test_context();
+    let exec_ctx = PolicyExecutionContext::new(&context, &datastore, &node.id);
+    let interval = FieldRef {
+        path: vec!["polling".to_string(), "interval".to_string()],
+    };
+    let profile = FieldRef {
+        path: vec!["polling".to_string(), "profile".to_string()],
+    };
+
+    let result = ActionExecutor::execute_set_action_with_rollback(
+        &interval,
+        &Value::String("60s".to_string()),
+        &exec_ctx,
+    )
+    .await
+    .unwrap();
+    assert!(result.rollback_data.is_none());
+    let stored = datastore.get_node(&node.id).await.unwrap().unwrap();
+    assert_eq!(stored.custom_data, node.custom_data);
+
+    let unknown = ActionExecutor::execute_set_action_with_rollback(
+        &profile,
+        &Value::String("missing".to_string()),
+        &exec_ctx,
+    )
+    .await;
+    assert!(matches!(unknown, Err(PolicyError::ValidationError { .. })));
+
+    let known = ActionExecutor::execute_set_action_with_rollback(
+        &profile,
+        &Value::String("base".to_string()),
+        &exec_ctx,
+    )
+    .await;
+    assert!(known.is_ok());
+}
@@ -1,11 +1,13 @@
 //! JSON value comparison operations
 //!
 //! Contains functions for comparing JSON values, including numeric comparisons,
-//! string operations, and regex matching.
+//! string operations, and regex matching. Regexes are capped in compiled
+//! size so a huge pattern aborts its rule instead of stalling evaluation.
 
+use super::limits::{MAX_REGEX_SIZE, RuleAbort};
 use crate::policy::PolicyError;
 use dashmap::DashMap;
-use regex::Regex;
+use regex::{Regex, RegexBuilder};
 use serde_json::Value as JsonValue;
 use std::sync::LazyLock;
 
@@ -67,9 +69,17 @@ pub fn evaluate_regex_match_json(
             let regex = if let Some(existing) = REGEX_CACHE.get(regex_str) {
                 existing.clone()
             } else {
-                let compiled = Regex::new(regex_str).map_err(|_| PolicyError::InvalidRegex {
-                    pattern: regex_str.clone(),
-                })?;
+                let compiled = RegexBuilder::new(regex_str)
+                    .size_limit(MAX_REGEX_SIZE)
+                    .build()
+                    .map_err(|e| match e {
+                        regex::Error::CompiledTooBig(limit) => PolicyError::RuleAborted {
+                            reason: RuleAbort::RegexTooLarge { limit },
+                        },
+                        _ => PolicyError::InvalidRegex {
+                            pattern: regex_str.clone(),
+                        },
+                    })?;
                 REGEX_CACHE.insert(regex_str.clone(), compiled.clone());
                 compiled
             };
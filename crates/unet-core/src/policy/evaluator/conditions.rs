@@ -4,6 +4,7 @@
 //! evaluation contexts, including field resolution and existence checks.
 
 use super::context::EvaluationContext;
+use super::limits::Deadline;
 use crate::policy::PolicyError;
 use crate::policy::ast::{ComparisonOperator, Condition, FieldRef, Value};
 use serde_json::Value as JsonValue;
@@ -13,19 +14,34 @@ pub fn evaluate_condition(
     condition: &Condition,
     context: &EvaluationContext,
 ) -> Result<bool, PolicyError> {
+    evaluate_condition_until(condition, context, None)
+}
+
+/// Evaluate a condition, giving up once the deadline passes
+///
+/// The deadline is checked before each step of the condition, so a single
+/// comparison is never interrupted.
+pub fn evaluate_condition_until(
+    condition: &Condition,
+    context: &EvaluationContext,
+    deadline: Option<&Deadline>,
+) -> Result<bool, PolicyError> {
+    if let Some(deadline) = deadline {
+        deadline.check()?;
+    }
     match condition {
         Condition::And(left, right) => {
-            let left_result = evaluate_condition(left, context)?;
-            let right_result = evaluate_condition(right, context)?;
+            let left_result = evaluate_condition_until(left, context, deadline)?;
+            let right_result = evaluate_condition_until(right, context, deadline)?;
             Ok(left_result && right_result)
         }
         Condition::Or(left, right) => {
-            let left_result = evaluate_condition(left, context)?;
-            let right_result = evaluate_condition(right, context)?;
+            let left_result = evaluate_condition_until(left, context, deadline)?;
+            let right_result = evaluate_condition_until(right, context, deadline)?;
             Ok(left_result || right_result)
         }
         Condition::Not(condition) => {
-            let result = evaluate_condition(condition, context)?;
+            let result = evaluate_condition_until(condition, context, deadline)?;
             Ok(!result)
         }
         Condition::Comparison {
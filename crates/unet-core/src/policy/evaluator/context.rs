@@ -3,8 +3,10 @@
 //! Contains the core context structures used during policy evaluation,
 //! including evaluation context, execution context, and various result types.
 
+use super::limits::RuleAbort;
 use crate::datastore::DataStore;
 use crate::policy::ast::{Action, FieldRef, PolicyRule};
+use chrono::{DateTime, Utc};
 use serde_json::Value as JsonValue;
 use std::time::Instant;
 use uuid::Uuid;
@@ -118,6 +120,11 @@ pub enum EvaluationResult {
         /// Error message describing the failure
         message: String,
     },
+    /// Policy evaluation was abandoned for exceeding an evaluation limit
+    Aborted {
+        /// The limit the rule exceeded
+        reason: RuleAbort,
+    },
 }
 
 /// Result of action execution
@@ -172,6 +179,17 @@ pub struct ActionExecutionResult {
     pub rollback_data: Option<RollbackData>,
 }
 
+/// Change a SET or APPLY action would make, predicted without writing anything
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PredictedChange {
+    /// Field the action sets, e.g. `custom_data.ntp.primary`
+    pub field: String,
+    /// Value before the action, if the field was set
+    pub previous: Option<JsonValue>,
+    /// Value after the action
+    pub value: JsonValue,
+}
+
 /// Transaction context for policy execution with rollback support
 #[derive(Debug, Clone)]
 pub struct PolicyTransaction {
@@ -211,6 +229,17 @@ impl PolicyTransaction {
     }
 }
 
+/// Location exemption that waived a compliance failure
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct AppliedExemption {
+    /// Location the exemption is attached to
+    pub location_id: Uuid,
+    /// Why the rule is waived
+    pub reason: String,
+    /// When the exemption stops applying
+    pub expires_at: DateTime<Utc>,
+}
+
 /// Complete result of policy rule execution (evaluation + action)
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct PolicyExecutionResult {
@@ -220,6 +249,9 @@ pub struct PolicyExecutionResult {
     pub evaluation_result: EvaluationResult,
     /// Result of action execution (if condition was satisfied)
     pub action_result: Option<ActionExecutionResult>,
+    /// Exemption that waived a compliance failure, if one applied
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exemption: Option<AppliedExemption>,
 }
 
 impl PolicyExecutionResult {
@@ -234,6 +266,7 @@ impl PolicyExecutionResult {
             rule,
             evaluation_result,
             action_result,
+            exemption: None,
         }
     }
 
@@ -246,7 +279,7 @@ impl PolicyExecutionResult {
                 .as_ref()
                 .is_some_and(|ar| matches!(ar.result, ActionResult::Success { .. })),
             EvaluationResult::NotSatisfied => true, // Not satisfied but no error
-            EvaluationResult::Error { .. } => false,
+            EvaluationResult::Error { .. } | EvaluationResult::Aborted { .. } => false,
         }
     }
 
@@ -260,7 +293,7 @@ impl PolicyExecutionResult {
     #[must_use]
     pub fn is_error(&self) -> bool {
         match &self.evaluation_result {
-            EvaluationResult::Error { .. } => true,
+            EvaluationResult::Error { .. } | EvaluationResult::Aborted { .. } => true,
             EvaluationResult::Satisfied { .. } => self
                 .action_result
                 .as_ref()
@@ -270,8 +303,22 @@ impl PolicyExecutionResult {
     }
 
     /// Check if the policy execution resulted in a compliance failure
+    ///
+    /// Failures waived by an exemption do not count.
     #[must_use]
     pub fn is_compliance_failure(&self) -> bool {
+        self.exemption.is_none() && self.fails_compliance_check()
+    }
+
+    /// Check if a compliance failure was waived by an exemption
+    #[must_use]
+    pub const fn is_exempted(&self) -> bool {
+        self.exemption.is_some()
+    }
+
+    /// Check if the action failed its compliance check, exempted or not
+    #[must_use]
+    pub fn fails_compliance_check(&self) -> bool {
         self.action_result
             .as_ref()
             .is_some_and(|ar| matches!(ar.result, ActionResult::ComplianceFailure { .. }))
@@ -282,6 +329,7 @@ impl PolicyExecutionResult {
     pub fn get_error_message(&self) -> Option<&str> {
         match &self.evaluation_result {
             EvaluationResult::Error { message } => Some(message),
+            EvaluationResult::Aborted { reason } => Some(reason.description()),
             EvaluationResult::Satisfied { .. } => {
                 self.action_result
                     .as_ref()
@@ -304,6 +352,7 @@ impl PolicyExecutionResult {
                 message: error_message,
             },
             action_result: None,
+            exemption: None,
         }
     }
 
@@ -324,6 +373,18 @@ impl PolicyExecutionResult {
         Self::new_error(rule, error_message)
     }
 
+    /// Create a new policy execution result for a rule abandoned at a limit
+    #[must_use]
+    pub const fn new_aborted(rule: PolicyRule, reason: RuleAbort) -> Self {
+        Self::new(rule, EvaluationResult::Aborted { reason }, None)
+    }
+
+    /// Check if the rule was abandoned for exceeding an evaluation limit
+    #[must_use]
+    pub const fn is_aborted(&self) -> bool {
+        matches!(self.evaluation_result, EvaluationResult::Aborted { .. })
+    }
+
     /// Get the rule ID (name) if available
     #[must_use]
     pub fn rule_id(&self) -> Option<&str> {
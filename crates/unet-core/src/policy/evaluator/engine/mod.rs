@@ -9,16 +9,30 @@ mod tests;
 use super::actions::ActionExecutor;
 use super::context::{
     EvaluationContext, EvaluationResult, PolicyExecutionContext, PolicyExecutionResult,
-    PolicyTransaction,
+    PolicyTransaction, PredictedChange,
 };
+use super::limits::{Deadline, EvaluationLimits, RuleAbort};
 use crate::policy::PolicyError;
-use crate::policy::ast::PolicyRule;
+use crate::policy::ast::{Action, Condition, PolicyRule};
 use std::time::Instant;
+use uuid::Uuid;
 
 /// Policy evaluation engine
 pub struct PolicyEvaluator;
 
 impl PolicyEvaluator {
+    /// Evaluate a condition on its own against the given context
+    ///
+    /// # Errors
+    /// Returns an error if the condition compares a field missing from the
+    /// context or values that cannot be compared
+    pub fn evaluate_condition(
+        condition: &Condition,
+        context: &EvaluationContext,
+    ) -> Result<bool, PolicyError> {
+        super::conditions::evaluate_condition(condition, context)
+    }
+
     /// Evaluate a single policy rule against the given context
     ///
     /// # Errors
@@ -36,6 +50,22 @@ impl PolicyEvaluator {
         }
     }
 
+    /// Predict the change a satisfied rule's action would make, without
+    /// writing anything
+    ///
+    /// `custom_data` is the node's `custom_data` with the changes predicted
+    /// so far, and is updated with this one.
+    ///
+    /// # Errors
+    /// Returns a validation error for actions execution would reject
+    pub fn predict_action(
+        action: &Action,
+        context: &EvaluationContext,
+        custom_data: &mut serde_json::Value,
+    ) -> Result<Option<PredictedChange>, PolicyError> {
+        ActionExecutor::predict_action(action, context, custom_data)
+    }
+
     /// Execute a single policy rule (evaluate condition and execute action if satisfied)
     ///
     /// # Errors
@@ -60,6 +90,90 @@ impl PolicyEvaluator {
         ))
     }
 
+    /// Checks a node's context against the payload cap, once for all of the
+    /// node's rules
+    ///
+    /// Returns the reason each rule of the node is aborted with when the
+    /// context is over the cap; such rules are not evaluated.
+    #[must_use]
+    pub fn payload_abort(
+        context: &EvaluationContext,
+        node_id: &Uuid,
+        limits: &EvaluationLimits,
+    ) -> Option<RuleAbort> {
+        match limits.check_payload(context) {
+            Err(PolicyError::RuleAborted { reason }) => {
+                tracing::warn!(node_id = %node_id, %reason, "Aborted policy rules of node");
+                Some(reason)
+            }
+            _ => None,
+        }
+    }
+
+    /// Execute a single policy rule within the given time and regex limits
+    ///
+    /// A rule whose regex is too large, or whose condition runs past the
+    /// time limit, gets an [`EvaluationResult::Aborted`] result instead of
+    /// holding up the caller. The deadline is checked once more before the
+    /// action starts; an action that has started runs to completion, so its
+    /// writes are never cut off halfway. The payload cap is checked by
+    /// [`Self::payload_abort`].
+    ///
+    /// # Errors
+    /// Returns an error if condition evaluation or action execution fails
+    pub async fn execute_rule_with_limits(
+        rule: &PolicyRule,
+        exec_ctx: &PolicyExecutionContext<'_>,
+        limits: &EvaluationLimits,
+    ) -> Result<PolicyExecutionResult, PolicyError> {
+        match Self::execute_rule_within(rule, exec_ctx, limits).await {
+            Err(PolicyError::RuleAborted { reason }) => {
+                tracing::warn!(
+                    rule_id = rule.id.as_deref().unwrap_or("unknown"),
+                    node_id = %exec_ctx.node_id,
+                    %reason,
+                    "Aborted policy rule"
+                );
+                Ok(PolicyExecutionResult::new_aborted(rule.clone(), reason))
+            }
+            result => result,
+        }
+    }
+
+    async fn execute_rule_within(
+        rule: &PolicyRule,
+        exec_ctx: &PolicyExecutionContext<'_>,
+        limits: &EvaluationLimits,
+    ) -> Result<PolicyExecutionResult, PolicyError> {
+        let deadline = Deadline::after(limits.rule_timeout);
+
+        let evaluation_result = if super::conditions::evaluate_condition_until(
+            &rule.condition,
+            exec_ctx.context,
+            Some(&deadline),
+        )? {
+            EvaluationResult::Satisfied {
+                action: rule.action.clone(),
+            }
+        } else {
+            EvaluationResult::NotSatisfied
+        };
+
+        let action_result = match &evaluation_result {
+            EvaluationResult::Satisfied { action } => {
+                deadline.check()?;
+                Some(ActionExecutor::execute_action_with_rollback(action, exec_ctx).await?)
+            }
+            _ => None,
+        };
+
+        Ok(PolicyExecutionResult::new(
+            rule.clone(),
+            evaluation_result,
+            action_result,
+        ))
+    }
+
     /// Execute multiple policy rules against the given context
     ///
     /// # Errors
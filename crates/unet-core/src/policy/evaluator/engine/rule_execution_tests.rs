@@ -4,10 +4,14 @@ use crate::datastore::{DataStore, sqlite::SqliteStore};
 use crate::models::{DeviceRole, Node, Vendor};
 use crate::policy::ast::*;
 use crate::policy::evaluator::PolicyEvaluator;
-use crate::policy::evaluator::context::{EvaluationContext, PolicyExecutionContext};
+use crate::policy::evaluator::context::{
+    EvaluationContext, EvaluationResult, PolicyExecutionContext,
+};
+use crate::policy::evaluator::limits::{EvaluationLimits, RuleAbort};
 use migration::Migrator;
 use sea_orm_migration::MigratorTrait;
 use serde_json::json;
+use std::time::Duration;
 use uuid::Uuid;
 
 /// Set up a test datastore for testing
@@ -205,3 +209,101 @@ async fn test_execute_rules_with_transaction_node_not_found_fails() {
     assert!(result.is_err());
     // Should fail because node doesn't exist
 }
+
+#[test]
+fn test_payload_abort_checks_context_size() {
+    let context = create_test_context();
+    let node_id = Uuid::new_v4();
+    let limits = EvaluationLimits {
+        max_payload_bytes: 16,
+        ..EvaluationLimits::default()
+    };
+
+    assert!(matches!(
+        PolicyEvaluator::payload_abort(&context, &node_id, &limits),
+        Some(RuleAbort::PayloadTooLarge { limit: 16, .. })
+    ));
+    assert_eq!(
+        PolicyEvaluator::payload_abort(&context, &node_id, &EvaluationLimits::default()),
+        None
+    );
+}
+
+#[tokio::test]
+async fn test_execute_rule_with_limits_aborts_at_time_limit() {
+    let datastore = setup_test_datastore().await;
+    let node = create_test_node(&datastore).await;
+    let context = create_test_context();
+    let exec_ctx = PolicyExecutionContext::new(&context, &datastore, &node.id);
+    let limits = EvaluationLimits {
+        rule_timeout: Duration::ZERO,
+        ..EvaluationLimits::default()
+    };
+
+    let result =
+        PolicyEvaluator::execute_rule_with_limits(&create_always_true_rule(), &exec_ctx, &limits)
+            .await
+            .unwrap();
+
+    assert_eq!(
+        result.evaluation_result,
+        EvaluationResult::Aborted {
+            reason: RuleAbort::Timeout { limit_ms: 0 }
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_execute_rule_with_limits_aborts_huge_regex() {
+    let datastore = setup_test_datastore().await;
+    let node = create_test_node(&datastore).await;
+    let context = create_test_context();
+    let exec_ctx = PolicyExecutionContext::new(&context, &datastore, &node.id);
+    let rule = PolicyRule {
+        id: Some("huge_regex".to_string()),
+        condition: Condition::Comparison {
+            field: FieldRef {
+                path: vec!["vendor".to_string()],
+            },
+            operator: ComparisonOperator::Matches,
+            value: Value::Regex(r"(?:\w{1000}){1000}".to_string()),
+        },
+        action: create_always_true_rule().action,
+    };
+
+    let result =
+        PolicyEvaluator::execute_rule_with_limits(&rule, &exec_ctx, &EvaluationLimits::default())
+            .await
+            .unwrap();
+
+    assert!(matches!(
+        result.evaluation_result,
+        EvaluationResult::Aborted {
+            reason: RuleAbort::RegexTooLarge { .. }
+        }
+    ));
+    assert!(
+        PolicyEvaluator::execute_rule(&rule, &exec_ctx)
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_execute_rule_with_limits_matches_execute_rule_within_limits() {
+    let datastore = setup_test_datastore().await;
+    let node = create_test_node(&datastore).await;
+    let context = create_test_context();
+    let exec_ctx = PolicyExecutionContext::new(&context, &datastore, &node.id);
+
+    let result = PolicyEvaluator::execute_rule_with_limits(
+        &create_always_true_rule(),
+        &exec_ctx,
+        &EvaluationLimits::default(),
+    )
+    .await
+    .unwrap();
+
+    assert!(result.is_satisfied());
+    assert!(!result.is_aborted());
+}
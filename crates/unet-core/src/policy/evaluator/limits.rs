@@ -0,0 +1,219 @@
+//! Per-rule evaluation limits
+//!
+//! A badly written rule, such as one with a huge regex or one comparing
+//! fields of a very large `custom_data` document, must not stall the
+//! evaluation loop for every other rule and node. [`EvaluationLimits`] caps
+//! how long a single rule may run and how large the context it is evaluated
+//! against may be; regexes are also capped in compiled size. A rule over a
+//! limit is abandoned with an [`EvaluationResult::Aborted`] result naming the
+//! limit, and evaluation carries on with the next rule.
+//!
+//! The time limit is checked between the steps of a condition and once more
+//! before the rule's action starts; a started action is not interrupted, so
+//! its datastore writes are not cut off halfway. The payload cap is checked
+//! once per node, since every rule of a node shares its context.
+//!
+//! [`EvaluationResult::Aborted`]: super::EvaluationResult::Aborted
+
+use super::context::EvaluationContext;
+use crate::policy::PolicyError;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Default time a rule may take before its action starts
+pub const DEFAULT_RULE_TIMEOUT: Duration = Duration::from_secs(1);
+/// Default cap on the JSON size of the context a rule is evaluated against
+pub const DEFAULT_MAX_PAYLOAD_BYTES: usize = 1024 * 1024;
+/// Cap on the compiled size of a regex used in a `MATCHES` comparison
+pub const MAX_REGEX_SIZE: usize = 1024 * 1024;
+
+/// Limits applied to each rule evaluation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EvaluationLimits {
+    /// Time a rule may take before its action starts
+    pub rule_timeout: Duration,
+    /// Largest JSON size in bytes of the node and derived data a rule is
+    /// evaluated against
+    pub max_payload_bytes: usize,
+}
+
+impl Default for EvaluationLimits {
+    fn default() -> Self {
+        Self {
+            rule_timeout: DEFAULT_RULE_TIMEOUT,
+            max_payload_bytes: DEFAULT_MAX_PAYLOAD_BYTES,
+        }
+    }
+}
+
+impl EvaluationLimits {
+    /// Checks the context against the payload cap
+    ///
+    /// # Errors
+    /// Returns [`PolicyError::RuleAborted`] if the context is over the cap.
+    pub fn check_payload(&self, context: &EvaluationContext) -> Result<(), PolicyError> {
+        let size = payload_size(context);
+        if size > self.max_payload_bytes {
+            return Err(PolicyError::RuleAborted {
+                reason: RuleAbort::PayloadTooLarge {
+                    size,
+                    limit: self.max_payload_bytes,
+                },
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Why a rule was abandoned before finishing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "limit", rename_all = "snake_case")]
+pub enum RuleAbort {
+    /// The rule ran past the per-rule time limit
+    Timeout {
+        /// The time limit in milliseconds
+        limit_ms: u64,
+    },
+    /// The evaluation context is larger than the payload cap
+    PayloadTooLarge {
+        /// JSON size of the context in bytes
+        size: usize,
+        /// The payload cap in bytes
+        limit: usize,
+    },
+    /// A regex in the rule compiles to more than [`MAX_REGEX_SIZE`]
+    RegexTooLarge {
+        /// The compiled size cap in bytes
+        limit: usize,
+    },
+}
+
+impl RuleAbort {
+    /// Short description of the limit, without the measured values
+    #[must_use]
+    pub const fn description(&self) -> &'static str {
+        match self {
+            Self::Timeout { .. } => "Rule aborted: time limit exceeded",
+            Self::PayloadTooLarge { .. } => "Rule aborted: evaluation context too large",
+            Self::RegexTooLarge { .. } => "Rule aborted: regex too large",
+        }
+    }
+}
+
+impl std::fmt::Display for RuleAbort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Timeout { limit_ms } => write!(f, "rule ran longer than {limit_ms}ms"),
+            Self::PayloadTooLarge { size, limit } => write!(
+                f,
+                "evaluation context is {size} bytes, over the {limit} byte cap"
+            ),
+            Self::RegexTooLarge { limit } => {
+                write!(f, "regex compiles to more than {limit} bytes")
+            }
+        }
+    }
+}
+
+/// Point in time after which a rule is abandoned
+#[derive(Debug, Clone, Copy)]
+pub struct Deadline {
+    at: Instant,
+    limit: Duration,
+}
+
+impl Deadline {
+    /// Starts a deadline `limit` from now
+    #[must_use]
+    pub fn after(limit: Duration) -> Self {
+        Self {
+            at: Instant::now() + limit,
+            limit,
+        }
+    }
+
+    /// Checks that the deadline has not passed
+    ///
+    /// # Errors
+    /// Returns [`PolicyError::RuleAborted`] once the deadline has passed.
+    pub fn check(&self) -> Result<(), PolicyError> {
+        if Instant::now() >= self.at {
+            return Err(self.exceeded());
+        }
+        Ok(())
+    }
+
+    /// The error reported when the deadline passes
+    #[must_use]
+    pub fn exceeded(&self) -> PolicyError {
+        PolicyError::RuleAborted {
+            reason: RuleAbort::Timeout {
+                limit_ms: u64::try_from(self.limit.as_millis()).unwrap_or(u64::MAX),
+            },
+        }
+    }
+}
+
+/// JSON size in bytes of the node and derived data, without buffering it
+fn payload_size(context: &EvaluationContext) -> usize {
+    let mut counter = ByteCounter(0);
+    // Writing to a counter cannot fail and `Value` always serializes
+    let _ = serde_json::to_writer(&mut counter, &context.node_data);
+    if let Some(derived) = &context.derived_data {
+        let _ = serde_json::to_writer(&mut counter, derived);
+    }
+    counter.0
+}
+
+struct ByteCounter(usize);
+
+impl std::io::Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_check_payload_reports_size_and_cap() {
+        let context = EvaluationContext::new(json!({ "node": { "name": "r1" } }));
+        let limits = EvaluationLimits {
+            max_payload_bytes: 10,
+            ..EvaluationLimits::default()
+        };
+
+        let Err(PolicyError::RuleAborted { reason }) = limits.check_payload(&context) else {
+            panic!("expected the payload cap to abort the rule");
+        };
+        assert_eq!(
+            reason,
+            RuleAbort::PayloadTooLarge {
+                size: r#"{"node":{"name":"r1"}}"#.len(),
+                limit: 10
+            }
+        );
+        assert!(EvaluationLimits::default().check_payload(&context).is_ok());
+    }
+
+    #[test]
+    fn test_deadline_reports_timeout() {
+        let deadline = Deadline::after(Duration::ZERO);
+
+        assert!(matches!(
+            deadline.check(),
+            Err(PolicyError::RuleAborted {
+                reason: RuleAbort::Timeout { limit_ms: 0 }
+            })
+        ));
+        assert!(Deadline::after(Duration::from_secs(60)).check().is_ok());
+    }
+}
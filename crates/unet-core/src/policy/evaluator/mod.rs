@@ -8,18 +8,22 @@ pub mod comparisons;
 pub mod conditions;
 pub mod context;
 pub mod engine;
+pub mod limits;
 pub mod orchestration;
 pub mod results;
 pub mod rollback;
 
 // Re-export commonly used types
 pub use context::{
-    ActionExecutionResult, ActionResult, EvaluationContext, EvaluationResult,
-    PolicyExecutionContext, PolicyExecutionResult, PolicyTransaction, RollbackData,
+    ActionExecutionResult, ActionResult, AppliedExemption, EvaluationContext, EvaluationResult,
+    PolicyExecutionContext, PolicyExecutionResult, PolicyTransaction, PredictedChange,
+    RollbackData,
 };
 pub use engine::PolicyEvaluator;
+pub use limits::{EvaluationLimits, RuleAbort};
 pub use orchestration::{
-    EvaluationBatch, OrchestrationConfig, OrchestrationRule, PolicyOrchestrator,
+    CacheMetrics, EvaluationBatch, OrchestrationConfig, OrchestrationRule, PolicyOrchestrator,
+    rule_fields,
 };
 pub use results::{AggregatedResult, PolicyPriority};
 pub use rollback::RollbackResult;
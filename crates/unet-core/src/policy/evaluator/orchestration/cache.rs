@@ -0,0 +1,228 @@
+//! Evaluation result cache keyed on node state
+//!
+//! A cached result is reused only while both the rule set and the node's
+//! policy-relevant state are unchanged. The state is the value of every field
+//! the rules read or write, so changes to unrelated node data (timestamps,
+//! counters no rule mentions) do not cause re-evaluation.
+
+use super::super::context::EvaluationContext;
+use super::super::results::AggregatedResult;
+use super::core::{CacheEntry, EvaluationBatch, OrchestrationRule};
+use crate::policy::ast::{Action, Condition, PolicyRule, Value};
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap, hash_map::DefaultHasher};
+use std::hash::{Hash, Hasher};
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Cache effectiveness counters since the orchestrator was created
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct CacheMetrics {
+    /// Evaluations skipped because a cached result matched
+    pub hits: usize,
+    /// Evaluations with no usable cached result
+    pub misses: usize,
+    /// Misses caused by a change in the node's policy-relevant state
+    pub state_changes: usize,
+    /// Evaluations that bypassed the cache on request
+    pub forced: usize,
+}
+
+/// Identifies the inputs of a batch evaluation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct BatchFingerprint {
+    /// Node and rule set the result belongs to
+    key: (Uuid, u64),
+    /// Hash of the fields referenced by the rules
+    state_hash: u64,
+}
+
+impl BatchFingerprint {
+    fn of(batch: &EvaluationBatch) -> Self {
+        Self {
+            key: (batch.node_id, rule_set_hash(&batch.rules)),
+            state_hash: state_hash(&batch.context, &referenced_fields(&batch.rules)),
+        }
+    }
+}
+
+/// Cached evaluation results with hit and miss accounting
+#[derive(Debug, Clone, Default)]
+pub struct EvaluationCache {
+    entries: HashMap<(Uuid, u64), CacheEntry>,
+    metrics: CacheMetrics,
+}
+
+impl EvaluationCache {
+    /// Returns the cached result for the batch if its state is unchanged
+    ///
+    /// The returned result carries the batch ID of the new batch. A forced
+    /// lookup always misses.
+    pub fn lookup(&mut self, batch: &EvaluationBatch) -> Option<AggregatedResult> {
+        if batch.force {
+            self.metrics.forced += 1;
+            return None;
+        }
+        let fingerprint = BatchFingerprint::of(batch);
+
+        match self.entries.get(&fingerprint.key) {
+            Some(entry) if !entry.is_expired() && entry.state_hash == fingerprint.state_hash => {
+                self.metrics.hits += 1;
+                Some(AggregatedResult {
+                    batch_id: batch.batch_id.clone(),
+                    ..entry.result.clone()
+                })
+            }
+            Some(entry) => {
+                if !entry.is_expired() {
+                    self.metrics.state_changes += 1;
+                }
+                self.metrics.misses += 1;
+                None
+            }
+            None => {
+                self.metrics.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Stores the result of evaluating a batch
+    pub fn store(&mut self, batch: &EvaluationBatch, result: &AggregatedResult, ttl: Duration) {
+        let fingerprint = BatchFingerprint::of(batch);
+        self.entries.insert(
+            fingerprint.key,
+            CacheEntry::new(result.clone(), fingerprint.state_hash, ttl),
+        );
+    }
+
+    /// Drops expired entries
+    pub fn clean_expired(&mut self) {
+        self.entries.retain(|_, entry| !entry.is_expired());
+    }
+
+    /// Drops all entries; metrics are kept
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Number of cached results, including expired ones
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if nothing is cached
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Number of expired entries not yet cleaned
+    #[must_use]
+    pub fn expired_count(&self) -> usize {
+        self.entries
+            .values()
+            .filter(|entry| entry.is_expired())
+            .count()
+    }
+
+    /// Current cache counters
+    #[must_use]
+    pub const fn metrics(&self) -> CacheMetrics {
+        self.metrics
+    }
+}
+
+/// Hashes the rules together with their priority and order
+#[must_use]
+pub fn rule_set_hash(rules: &[OrchestrationRule]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for rule in rules {
+        format!("{:?}", rule.rule).hash(&mut hasher);
+        rule.priority.hash(&mut hasher);
+        rule.order.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Collects the dotted paths of every field the rules read or write
+#[must_use]
+pub fn referenced_fields(rules: &[OrchestrationRule]) -> BTreeSet<String> {
+    rules
+        .iter()
+        .flat_map(|rule| rule_fields(&rule.rule))
+        .collect()
+}
+
+/// Collects the dotted paths of every field a rule reads or writes
+#[must_use]
+pub fn rule_fields(rule: &PolicyRule) -> BTreeSet<String> {
+    let mut fields = BTreeSet::new();
+    collect_condition(&rule.condition, &mut fields);
+    match &rule.action {
+        Action::Assert {
+            field,
+            expected: value,
+        }
+        | Action::Set { field, value } => {
+            fields.insert(field.path.join("."));
+            collect_value(value, &mut fields);
+        }
+        Action::ApplyTemplate { .. } => {}
+    }
+    fields
+}
+
+/// Hashes the values of the given fields in the context
+///
+/// Missing fields hash differently from fields set to `null`.
+#[must_use]
+pub fn state_hash(context: &EvaluationContext, fields: &BTreeSet<String>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for field in fields {
+        field.hash(&mut hasher);
+        context
+            .get_field(field)
+            .map(ToString::to_string)
+            .hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+fn collect_condition(condition: &Condition, fields: &mut BTreeSet<String>) {
+    match condition {
+        Condition::And(left, right) | Condition::Or(left, right) => {
+            collect_condition(left, fields);
+            collect_condition(right, fields);
+        }
+        Condition::Not(inner) => collect_condition(inner, fields),
+        Condition::Comparison { field, value, .. } => {
+            fields.insert(field.path.join("."));
+            collect_value(value, fields);
+        }
+        Condition::Existence { field, .. } => {
+            fields.insert(field.path.join("."));
+        }
+        Condition::True | Condition::False => {}
+    }
+}
+
+fn collect_value(value: &Value, fields: &mut BTreeSet<String>) {
+    match value {
+        Value::FieldRef(field) => {
+            fields.insert(field.path.join("."));
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_value(item, fields);
+            }
+        }
+        Value::Object(entries) => {
+            for item in entries.values() {
+                collect_value(item, fields);
+            }
+        }
+        _ => {}
+    }
+}
@@ -1,6 +1,10 @@
 //! Caching functionality tests for orchestrator
 
+use super::super::cache::referenced_fields;
 use super::super::*;
+use crate::policy::PolicyParser;
+use crate::policy::evaluator::context::EvaluationContext;
+use serde_json::json;
 use std::time::Duration;
 use uuid::Uuid;
 
@@ -154,6 +158,104 @@ async fn test_cache_cleanup_basic() {
     assert!(orchestrator.cache_size() > 0);
 }
 
+#[tokio::test]
+async fn test_cache_skips_nodes_with_unchanged_relevant_state() {
+    let mut orchestrator = PolicyOrchestrator::default();
+    let datastore = setup_test_datastore().await;
+    let node_id = Uuid::new_v4();
+    let rule = OrchestrationRule::new(create_test_rule());
+    let context =
+        |model: &str| EvaluationContext::new(json!({"node": {"vendor": "cisco", "model": model}}));
+
+    let first = orchestrator
+        .evaluate_node_policies(node_id, context("2960"), vec![rule.clone()], &datastore)
+        .await
+        .unwrap();
+    // `node.model` is not referenced by the rule, so the cached result is reused
+    let second = orchestrator
+        .evaluate_node_policies(node_id, context("3850"), vec![rule], &datastore)
+        .await
+        .unwrap();
+
+    assert_eq!(second.total_rules, first.total_rules);
+    assert_eq!(orchestrator.cache_metrics().misses, 1);
+    assert_eq!(orchestrator.cache_metrics().hits, 1);
+}
+
+#[tokio::test]
+async fn test_cache_reevaluates_when_referenced_field_changes() {
+    let mut orchestrator = PolicyOrchestrator::default();
+    let datastore = setup_test_datastore().await;
+    let node_id = Uuid::new_v4();
+    let rule = OrchestrationRule::new(create_test_rule());
+    let juniper = EvaluationContext::new(json!({"node": {"vendor": "juniper"}}));
+
+    let _ = orchestrator
+        .evaluate_node_policies(
+            node_id,
+            create_test_context(),
+            vec![rule.clone()],
+            &datastore,
+        )
+        .await
+        .unwrap();
+    let _ = orchestrator
+        .evaluate_node_policies(node_id, juniper, vec![rule], &datastore)
+        .await
+        .unwrap();
+
+    let metrics = orchestrator.cache_metrics();
+    assert_eq!(metrics.hits, 0);
+    assert_eq!(metrics.misses, 2);
+    assert_eq!(metrics.state_changes, 1);
+    assert_eq!(orchestrator.cache_size(), 1);
+}
+
+#[tokio::test]
+async fn test_forced_evaluation_bypasses_cache() {
+    let mut orchestrator = PolicyOrchestrator::default();
+    let datastore = setup_test_datastore().await;
+    let node_id = Uuid::new_v4();
+    let rule = OrchestrationRule::new(create_test_rule());
+
+    let _ = orchestrator
+        .evaluate_node_policies(
+            node_id,
+            create_test_context(),
+            vec![rule.clone()],
+            &datastore,
+        )
+        .await
+        .unwrap();
+    let _ = orchestrator
+        .force_evaluate_node_policies(node_id, create_test_context(), vec![rule], &datastore)
+        .await
+        .unwrap();
+
+    let stats = orchestrator.cache_stats();
+    assert_eq!(stats.get("hits"), Some(&0));
+    assert_eq!(stats.get("misses"), Some(&1));
+    assert_eq!(stats.get("forced_evaluations"), Some(&1));
+}
+
+#[test]
+fn test_referenced_fields_cover_conditions_and_actions() {
+    let rule = PolicyParser::parse_rule(
+        r#"WHEN node.vendor == "cisco" AND derived.uptime > 0 THEN SET custom_data.owner TO node.site"#,
+    )
+    .unwrap();
+
+    let fields = referenced_fields(&[OrchestrationRule::new(rule)]);
+
+    let expected = [
+        "custom_data.owner",
+        "derived.uptime",
+        "node.site",
+        "node.vendor",
+    ];
+    assert!(fields.iter().eq(expected.iter()));
+}
+
 // Tests for basic orchestration features
 
 #[test]
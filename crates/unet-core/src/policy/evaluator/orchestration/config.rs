@@ -1,8 +1,9 @@
 //! Orchestration configuration types
 //!
 //! Contains configuration structures for policy orchestration settings
-//! including concurrency, caching, and timeout parameters.
+//! including concurrency, caching, timeout, and per-rule limit parameters.
 
+use super::super::limits::EvaluationLimits;
 use std::time::Duration;
 
 /// Configuration for policy orchestration engine
@@ -16,6 +17,8 @@ pub struct OrchestrationConfig {
     pub batch_timeout: Duration,
     /// Enable result caching
     pub enable_caching: bool,
+    /// Time and payload limits applied to each rule
+    pub limits: EvaluationLimits,
 }
 
 impl Default for OrchestrationConfig {
@@ -25,6 +28,7 @@ impl Default for OrchestrationConfig {
             cache_ttl: Duration::from_secs(300),
             batch_timeout: Duration::from_secs(30),
             enable_caching: true,
+            limits: EvaluationLimits::default(),
         }
     }
 }
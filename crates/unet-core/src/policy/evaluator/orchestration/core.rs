@@ -94,6 +94,8 @@ pub struct EvaluationBatch {
     pub batch_id: String,
     /// Timestamp when the batch was created
     pub created_at: Instant,
+    /// Evaluate even if a cached result for the same state exists
+    pub force: bool,
 }
 
 impl EvaluationBatch {
@@ -111,6 +113,7 @@ impl EvaluationBatch {
             rules,
             batch_id,
             created_at: Instant::now(),
+            force: false,
         }
     }
 
@@ -147,6 +150,8 @@ impl EvaluationBatch {
 pub struct CacheEntry {
     /// The cached result
     pub result: AggregatedResult,
+    /// Hash of the policy-relevant node state the result was computed from
+    pub state_hash: u64,
     /// When this cache entry expires
     pub expires_at: Instant,
 }
@@ -154,9 +159,10 @@ pub struct CacheEntry {
 impl CacheEntry {
     /// Create a new cache entry with TTL
     #[must_use]
-    pub fn new(result: AggregatedResult, ttl: Duration) -> Self {
+    pub fn new(result: AggregatedResult, state_hash: u64, ttl: Duration) -> Self {
         Self {
             result,
+            state_hash,
             expires_at: Instant::now() + ttl,
         }
     }
@@ -3,6 +3,7 @@
 //! Contains the orchestration engine for managing complex policy evaluation
 //! workflows including batching, caching, and scheduling.
 
+pub mod cache;
 pub mod config;
 pub mod core;
 pub mod orchestrator;
@@ -11,6 +12,7 @@ pub mod orchestrator;
 mod orchestrator_tests;
 
 // Re-export commonly used types
+pub use cache::{CacheMetrics, rule_fields};
 pub use config::OrchestrationConfig;
 pub use core::{EvaluationBatch, OrchestrationRule};
 pub use orchestrator::PolicyOrchestrator;
@@ -69,7 +71,7 @@ mod tests {
             Duration::from_millis(100),
         );
 
-        let cache_entry = CacheEntry::new(result, Duration::from_secs(60));
+        let cache_entry = CacheEntry::new(result, 0, Duration::from_secs(60));
         assert!(!cache_entry.is_expired());
     }
 
@@ -3,15 +3,15 @@
 //! Contains the main `PolicyOrchestrator` implementation for managing
 //! complex policy evaluation workflows including batching and scheduling.
 
-use super::super::context::{EvaluationContext, PolicyExecutionContext};
+use super::super::context::{EvaluationContext, PolicyExecutionContext, PolicyExecutionResult};
 use super::super::results::{AggregatedResult, BatchStatistics};
+use super::cache::{CacheMetrics, EvaluationCache};
 use super::config::OrchestrationConfig;
 use super::core::{EvaluationBatch, OrchestrationRule};
 use crate::datastore::DataStore;
 use crate::policy::PolicyError;
 use crate::policy::evaluator::PolicyEvaluator;
-use std::collections::{HashMap, hash_map::DefaultHasher};
-use std::hash::{Hash, Hasher};
+use std::collections::HashMap;
 use std::time::{Duration, Instant};
 use tokio::time::interval;
 use tracing::debug;
@@ -22,8 +22,8 @@ use uuid::Uuid;
 pub struct PolicyOrchestrator {
     /// Orchestration configuration
     config: OrchestrationConfig,
-    /// Cache for evaluation results
-    cache: HashMap<String, super::core::CacheEntry>,
+    /// Cache for evaluation results keyed on node state
+    cache: EvaluationCache,
     /// Pending evaluation batches
     pending_batches: HashMap<Uuid, EvaluationBatch>,
 }
@@ -40,7 +40,7 @@ impl PolicyOrchestrator {
     pub fn new(config: OrchestrationConfig) -> Self {
         Self {
             config,
-            cache: HashMap::new(),
+            cache: EvaluationCache::default(),
             pending_batches: HashMap::new(),
         }
     }
@@ -52,6 +52,27 @@ impl PolicyOrchestrator {
         node_id: Uuid,
         context: EvaluationContext,
         rules: Vec<OrchestrationRule>,
+    ) -> String {
+        self.schedule(node_id, context, rules, false)
+    }
+
+    /// Add a batch of policy rules that is evaluated even if cached
+    #[must_use]
+    pub fn schedule_forced_evaluation(
+        &mut self,
+        node_id: Uuid,
+        context: EvaluationContext,
+        rules: Vec<OrchestrationRule>,
+    ) -> String {
+        self.schedule(node_id, context, rules, true)
+    }
+
+    fn schedule(
+        &mut self,
+        node_id: Uuid,
+        context: EvaluationContext,
+        rules: Vec<OrchestrationRule>,
+        force: bool,
     ) -> String {
         let batch_id = format!(
             "batch_{}_{}",
@@ -59,12 +80,13 @@ impl PolicyOrchestrator {
             Instant::now().elapsed().as_millis()
         );
 
-        let batch = EvaluationBatch::new(
+        let mut batch = EvaluationBatch::new(
             node_id,
             context,
             Self::sort_rules_by_priority(rules),
             batch_id.clone(),
         );
+        batch.force = force;
 
         self.pending_batches.insert(node_id, batch);
         batch_id
@@ -82,10 +104,9 @@ impl PolicyOrchestrator {
         let batches: Vec<_> = self.pending_batches.drain().collect();
 
         for (_node_id, batch) in batches {
-            // Check cache first if enabled
+            // Skip evaluation if the node's policy-relevant state is unchanged
             if self.config.enable_caching {
-                let cache_key = Self::create_cache_key(&batch);
-                if let Some(cached_result) = self.get_cached_result(&cache_key) {
+                if let Some(cached_result) = self.cache.lookup(&batch) {
                     results.push(cached_result);
                     continue;
                 }
@@ -93,10 +114,8 @@ impl PolicyOrchestrator {
 
             let result = self.execute_batch(&batch, datastore).await?;
 
-            // Cache the result if enabled
             if self.config.enable_caching {
-                let cache_key = Self::create_cache_key(&batch);
-                self.cache_result(cache_key, &result);
+                self.cache.store(&batch, &result, self.config.cache_ttl);
             }
 
             results.push(result);
@@ -128,11 +147,20 @@ impl PolicyOrchestrator {
     ) -> Result<AggregatedResult, PolicyError> {
         let start_time = Instant::now();
         let mut results = Vec::new();
+        let exec_ctx = PolicyExecutionContext::new(&batch.context, datastore, &batch.node_id);
+        let oversized =
+            PolicyEvaluator::payload_abort(&batch.context, &batch.node_id, &self.config.limits);
 
         // Execute rules in priority order
         for orchestration_rule in &batch.rules {
-            let exec_ctx = PolicyExecutionContext::new(&batch.context, datastore, &batch.node_id);
-            let result = PolicyEvaluator::execute_rule(&orchestration_rule.rule, &exec_ctx).await?;
+            let rule = &orchestration_rule.rule;
+            let result = match oversized {
+                Some(reason) => PolicyExecutionResult::new_aborted(rule.clone(), reason),
+                None => {
+                    PolicyEvaluator::execute_rule_with_limits(rule, &exec_ctx, &self.config.limits)
+                        .await?
+                }
+            };
             results.push(result);
         }
 
@@ -157,7 +185,34 @@ impl PolicyOrchestrator {
         rules: Vec<OrchestrationRule>,
         datastore: &dyn DataStore,
     ) -> Result<AggregatedResult, PolicyError> {
-        let batch_id = self.schedule_evaluation(node_id, context, rules);
+        self.evaluate(node_id, context, rules, false, datastore)
+            .await
+    }
+
+    /// Execute policies for a single node, ignoring any cached result
+    ///
+    /// # Errors
+    /// Returns an error if batch scheduling or execution fails
+    pub async fn force_evaluate_node_policies(
+        &mut self,
+        node_id: Uuid,
+        context: EvaluationContext,
+        rules: Vec<OrchestrationRule>,
+        datastore: &dyn DataStore,
+    ) -> Result<AggregatedResult, PolicyError> {
+        self.evaluate(node_id, context, rules, true, datastore)
+            .await
+    }
+
+    async fn evaluate(
+        &mut self,
+        node_id: Uuid,
+        context: EvaluationContext,
+        rules: Vec<OrchestrationRule>,
+        force: bool,
+        datastore: &dyn DataStore,
+    ) -> Result<AggregatedResult, PolicyError> {
+        let batch_id = self.schedule(node_id, context, rules, force);
         let results = self.execute_pending_batches(datastore).await?;
 
         results
@@ -181,7 +236,7 @@ impl PolicyOrchestrator {
             interval_timer.tick().await;
 
             // Clean expired cache entries
-            self.clean_expired_cache();
+            self.cache.clean_expired();
 
             // Check for batches that have timed out and execute them if any exist
             let has_timed_out_batches = self
@@ -208,58 +263,25 @@ impl PolicyOrchestrator {
         rules
     }
 
-    /// Create a cache key for a batch
-    fn create_cache_key(batch: &EvaluationBatch) -> String {
-        let mut hasher = DefaultHasher::new();
-        batch.node_id.hash(&mut hasher);
-
-        // Hash rule content for cache invalidation
-        for rule in &batch.rules {
-            format!("{:?}", rule.rule).hash(&mut hasher);
-            rule.priority.hash(&mut hasher);
-            rule.order.hash(&mut hasher);
-        }
-
-        format!("cache_{:x}", hasher.finish())
-    }
-
-    /// Get cached result if available and not expired
-    fn get_cached_result(&self, cache_key: &str) -> Option<AggregatedResult> {
-        self.cache.get(cache_key).and_then(|entry| {
-            if entry.is_expired() {
-                None
-            } else {
-                Some(entry.result.clone())
-            }
-        })
-    }
-
-    /// Cache an evaluation result
-    fn cache_result(&mut self, cache_key: String, result: &AggregatedResult) {
-        let entry = super::core::CacheEntry::new(result.clone(), self.config.cache_ttl);
-        self.cache.insert(cache_key, entry);
-    }
-
-    /// Clean expired cache entries
-    fn clean_expired_cache(&mut self) {
-        self.cache.retain(|_, entry| !entry.is_expired());
-    }
-
     /// Get current cache statistics
     #[must_use]
     pub fn cache_stats(&self) -> HashMap<String, usize> {
-        let mut stats = HashMap::new();
-        stats.insert("total_entries".to_string(), self.cache.len());
-        stats.insert("pending_batches".to_string(), self.pending_batches.len());
-
-        let expired_count = self
-            .cache
-            .values()
-            .filter(|entry| entry.is_expired())
-            .count();
-        stats.insert("expired_entries".to_string(), expired_count);
-
-        stats
+        let metrics = self.cache.metrics();
+        HashMap::from([
+            ("total_entries".to_string(), self.cache.len()),
+            ("pending_batches".to_string(), self.pending_batches.len()),
+            ("expired_entries".to_string(), self.cache.expired_count()),
+            ("hits".to_string(), metrics.hits),
+            ("misses".to_string(), metrics.misses),
+            ("state_changes".to_string(), metrics.state_changes),
+            ("forced_evaluations".to_string(), metrics.forced),
+        ])
+    }
+
+    /// Get cache hit, miss, and forced evaluation counters
+    #[must_use]
+    pub const fn cache_metrics(&self) -> CacheMetrics {
+        self.cache.metrics()
     }
 
     /// Clear all cached results
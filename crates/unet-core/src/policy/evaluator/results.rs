@@ -85,8 +85,11 @@ impl AggregatedResult {
                     if let Some(action_result) = &result.action_result {
                         match &action_result.result {
                             ActionResult::ComplianceFailure { .. } => {
-                                compliance_failures += 1;
-                                failed_rules += 1;
+                                // Exempted failures are waived, not counted
+                                if !result.is_exempted() {
+                                    compliance_failures += 1;
+                                    failed_rules += 1;
+                                }
                             }
                             ActionResult::Error { .. } => {
                                 error_rules += 1;
@@ -100,7 +103,7 @@ impl AggregatedResult {
                 EvaluationResult::NotSatisfied => {
                     // Rule condition not met, but this is not an error
                 }
-                EvaluationResult::Error { .. } => {
+                EvaluationResult::Error { .. } | EvaluationResult::Aborted { .. } => {
                     error_rules += 1;
                 }
             }
@@ -213,7 +216,7 @@ impl AggregatedResult {
     pub fn get_compliance_failures(&self) -> Vec<ComplianceFailureDetail> {
         let mut failures = Vec::new();
 
-        for result in &self.results {
+        for result in self.results.iter().filter(|result| !result.is_exempted()) {
             if let Some(action_result) = &result.action_result {
                 if let ActionResult::ComplianceFailure {
                     field,
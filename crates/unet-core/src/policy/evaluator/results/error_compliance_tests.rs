@@ -3,7 +3,7 @@
 use super::test_helpers::*;
 use crate::policy::ast::{Action, FieldRef, Value};
 use crate::policy::evaluator::context::{
-    ActionExecutionResult, ActionResult, EvaluationResult, RollbackData,
+    ActionExecutionResult, ActionResult, AppliedExemption, EvaluationResult, RollbackData,
 };
 use crate::policy::evaluator::results::AggregatedResult;
 use serde_json::json;
@@ -163,3 +163,44 @@ fn test_get_compliance_failures() {
     assert_eq!(second_failure.expected, json!("active"));
     assert_eq!(second_failure.actual, json!("inactive"));
 }
+
+#[test]
+fn test_exempted_compliance_failures_are_not_counted() {
+    let mut exempted = create_test_execution_result(
+        create_test_rule(Some("version".to_string())),
+        EvaluationResult::Satisfied {
+            action: Action::Assert {
+                field: FieldRef {
+                    path: vec!["version".to_string()],
+                },
+                expected: Value::String("2.0".to_string()),
+            },
+        },
+        Some(ActionExecutionResult {
+            result: ActionResult::ComplianceFailure {
+                field: "version".to_string(),
+                expected: json!("2.0"),
+                actual: json!("1.0"),
+            },
+            rollback_data: Some(RollbackData::AssertRollback),
+        }),
+    );
+    exempted.exemption = Some(AppliedExemption {
+        location_id: Uuid::new_v4(),
+        reason: "upgrade scheduled".to_string(),
+        expires_at: chrono::Utc::now(),
+    });
+    assert!(exempted.fails_compliance_check());
+    assert!(!exempted.is_compliance_failure());
+
+    let aggregated = AggregatedResult::from_results(
+        Uuid::new_v4(),
+        "test".to_string(),
+        vec![exempted],
+        Duration::from_millis(10),
+    );
+
+    assert_eq!(aggregated.compliance_failures, 0);
+    assert_eq!(aggregated.failed_rules, 0);
+    assert!(aggregated.get_compliance_failures().is_empty());
+}
@@ -0,0 +1,239 @@
+//! Style warnings for policy rules
+//!
+//! Rules that parse can still be wrong: the same rule written twice, a
+//! condition no node can ever meet, or a field name with a typo that makes
+//! the rule silently never match. The linter flags these, along with rules
+//! without an ID, whose results cannot be told apart or put under canary.
+//! Warnings never stop rules from loading.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+use super::ast::{ComparisonOperator, Condition, PolicyRule, Value};
+use super::evaluator::rule_fields;
+use crate::models::node::NODE_FIELDS;
+use crate::snmp::adjustments::POLLING_FIELD;
+
+/// Fields the policy engine adds to the serialized node
+const COMPUTED_NODE_FIELDS: [&str; 2] = ["has_management_ip", "has_location"];
+
+/// Top-level context keys whose contents are not fixed by the node schema
+const OPEN_ROOTS: [&str; 3] = ["custom_data", "vlans", "changed"];
+
+/// Settings a rule can adjust under `polling`
+const POLLING_SETTINGS: [&str; 2] = ["interval", "profile"];
+
+/// What a lint warning is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintKind {
+    /// The rule repeats an earlier rule
+    DuplicateRule,
+    /// The condition holds for every node
+    AlwaysTrue,
+    /// The condition holds for no node
+    AlwaysFalse,
+    /// The rule reads or writes a field the node schema does not have
+    UnknownField,
+    /// The rule has no `RULE` ID
+    MissingId,
+}
+
+impl fmt::Display for LintKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicateRule => write!(f, "duplicate_rule"),
+            Self::AlwaysTrue => write!(f, "always_true"),
+            Self::AlwaysFalse => write!(f, "always_false"),
+            Self::UnknownField => write!(f, "unknown_field"),
+            Self::MissingId => write!(f, "missing_id"),
+        }
+    }
+}
+
+/// A style problem found in a rule
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintWarning {
+    /// Position of the rule in the linted list, from 0
+    pub index: usize,
+    /// ID of the rule, if it has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule_id: Option<String>,
+    /// What the warning is about
+    pub kind: LintKind,
+    /// Human-readable explanation
+    pub message: String,
+}
+
+impl fmt::Display for LintWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rule {}", self.index + 1)?;
+        if let Some(id) = &self.rule_id {
+            write!(f, " ({id})")?;
+        }
+        write!(f, ": {} [{}]", self.message, self.kind)
+    }
+}
+
+/// Lints rules, returning warnings ordered by rule
+#[must_use]
+pub fn lint_rules(rules: &[PolicyRule]) -> Vec<LintWarning> {
+    let mut warnings = Vec::new();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+
+    for (index, rule) in rules.iter().enumerate() {
+        let mut warn = |kind, message: String| {
+            warnings.push(LintWarning {
+                index,
+                rule_id: rule.id.clone(),
+                kind,
+                message,
+            });
+        };
+
+        if rule.id.is_none() {
+            warn(
+                LintKind::MissingId,
+                "Rule has no ID; start it with RULE <id>".to_string(),
+            );
+        }
+
+        let key = format!("{} THEN {}", rule.condition, rule.action);
+        if let Some(first) = seen.get(&key) {
+            warn(
+                LintKind::DuplicateRule,
+                format!("Rule repeats rule {}", first + 1),
+            );
+        } else {
+            seen.insert(key, index);
+        }
+
+        match truth(&rule.condition) {
+            Some(true) => warn(
+                LintKind::AlwaysTrue,
+                format!("Condition {} is always true", rule.condition),
+            ),
+            Some(false) => warn(
+                LintKind::AlwaysFalse,
+                format!(
+                    "Condition {} is always false, so the rule never applies",
+                    rule.condition
+                ),
+            ),
+            None => {}
+        }
+
+        for field in rule_fields(rule) {
+            if let Some(problem) = unknown_field(&field) {
+                warn(LintKind::UnknownField, problem);
+            }
+        }
+    }
+
+    warnings
+}
+
+/// Why a dotted field path is not in the node schema, or `None` if it is
+fn unknown_field(field: &str) -> Option<String> {
+    let path: Vec<&str> = field.split('.').collect();
+    match path.as_slice() {
+        ["node", name, ..] if NODE_FIELDS.contains(name) || COMPUTED_NODE_FIELDS.contains(name) => {
+            None
+        }
+        ["node", name, ..] => Some(format!("node has no field '{name}' (in {field})")),
+        [root, ..] if OPEN_ROOTS.contains(root) => None,
+        [root, setting] if *root == POLLING_FIELD && POLLING_SETTINGS.contains(setting) => None,
+        [root, ..] if *root == POLLING_FIELD => Some(format!(
+            "Unknown polling setting {field}; expected polling.interval or polling.profile"
+        )),
+        [name, ..] if NODE_FIELDS.contains(name) => {
+            Some(format!("Unknown field {field}; did you mean node.{field}?"))
+        }
+        _ => Some(format!("Unknown field {field}")),
+    }
+}
+
+/// Whether a condition holds for every node (`Some(true)`), for none
+/// (`Some(false)`), or depends on the node
+fn truth(condition: &Condition) -> Option<bool> {
+    match condition {
+        Condition::True => Some(true),
+        Condition::False => Some(false),
+        Condition::Not(inner) => truth(inner).map(|value| !value),
+        Condition::And(left, right) => match (truth(left), truth(right)) {
+            (Some(false), _) | (_, Some(false)) => Some(false),
+            (Some(true), Some(true)) => Some(true),
+            _ if contradicts(left, right) => Some(false),
+            _ => None,
+        },
+        Condition::Or(left, right) => match (truth(left), truth(right)) {
+            (Some(true), _) | (_, Some(true)) => Some(true),
+            (Some(false), Some(false)) => Some(false),
+            _ if negates(left, right) => Some(true),
+            _ => None,
+        },
+        Condition::Comparison {
+            field,
+            operator,
+            value: Value::FieldRef(other),
+        } if field == other => match operator {
+            ComparisonOperator::Equal
+            | ComparisonOperator::LessThanOrEqual
+            | ComparisonOperator::GreaterThanOrEqual => Some(true),
+            ComparisonOperator::NotEqual
+            | ComparisonOperator::LessThan
+            | ComparisonOperator::GreaterThan => Some(false),
+            ComparisonOperator::Contains | ComparisonOperator::Matches => None,
+        },
+        Condition::Comparison { .. } | Condition::Existence { .. } => None,
+    }
+}
+
+/// Whether exactly one of the conditions holds for any node
+fn negates(left: &Condition, right: &Condition) -> bool {
+    match (left, right) {
+        (Condition::Not(inner), other) | (other, Condition::Not(inner)) => **inner == *other,
+        (
+            Condition::Existence {
+                field: left,
+                is_null: left_null,
+            },
+            Condition::Existence {
+                field: right,
+                is_null: right_null,
+            },
+        ) => left == right && left_null != right_null,
+        _ => false,
+    }
+}
+
+/// Whether the conditions can never both hold
+fn contradicts(left: &Condition, right: &Condition) -> bool {
+    if negates(left, right) {
+        return true;
+    }
+    match (left, right) {
+        (
+            Condition::Comparison {
+                field: left_field,
+                operator: ComparisonOperator::Equal,
+                value: left_value,
+            },
+            Condition::Comparison {
+                field: right_field,
+                operator: ComparisonOperator::Equal,
+                value: right_value,
+            },
+        ) => {
+            left_field == right_field
+                && left_value != right_value
+                && !matches!(left_value, Value::FieldRef(_))
+                && !matches!(right_value, Value::FieldRef(_))
+        }
+        _ => false,
+    }
+}
+
+#[cfg(all(test, feature = "policy"))]
+mod tests;
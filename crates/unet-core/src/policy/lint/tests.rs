@@ -0,0 +1,100 @@
+use super::*;
+use crate::policy::PolicyParser;
+
+fn lint(input: &str) -> Vec<LintWarning> {
+    lint_rules(&PolicyParser::parse_file(input).unwrap())
+}
+
+fn kinds(warnings: &[LintWarning]) -> Vec<(usize, LintKind)> {
+    warnings
+        .iter()
+        .map(|warning| (warning.index, warning.kind))
+        .collect()
+}
+
+#[test]
+fn test_clean_rules_have_no_warnings() {
+    let warnings = lint(
+        r#"
+        RULE ios_version WHEN node.vendor == "cisco" THEN ASSERT node.version IS "17.3"
+        RULE "poll-degraded" WHEN custom_data.degraded == true THEN SET polling.interval TO 60s
+        RULE vlans WHEN node.has_location == true THEN ASSERT vlans.consistent IS true
+    "#,
+    );
+
+    assert_eq!(warnings, []);
+}
+
+#[test]
+fn test_flags_missing_ids_and_duplicates() {
+    let warnings = lint(
+        r#"
+        RULE a WHEN node.vendor == "cisco" THEN ASSERT node.version IS "17.3"
+        RULE b WHEN node.vendor == "cisco" THEN ASSERT node.version IS "17.3"
+        WHEN node.role == "core" THEN SET custom_data.tier TO 1
+    "#,
+    );
+
+    assert_eq!(
+        kinds(&warnings),
+        [(1, LintKind::DuplicateRule), (2, LintKind::MissingId)]
+    );
+    assert_eq!(warnings[0].rule_id.as_deref(), Some("b"));
+    assert_eq!(
+        warnings[0].to_string(),
+        "rule 2 (b): Rule repeats rule 1 [duplicate_rule]"
+    );
+}
+
+#[test]
+fn test_flags_constant_conditions() {
+    let warnings = lint(
+        r#"
+        RULE a WHEN node.vendor == "cisco" AND node.vendor == "juniper" THEN SET custom_data.x TO 1
+        RULE b WHEN node.name IS NULL OR node.name IS NOT NULL THEN SET custom_data.x TO 2
+        RULE c WHEN node.model == node.model THEN SET custom_data.x TO 3
+        RULE d WHEN node.role == "core" AND NOT node.role == "core" THEN SET custom_data.x TO 4
+        RULE e WHEN node.role == "core" OR node.role == "edge" THEN SET custom_data.x TO 5
+    "#,
+    );
+
+    assert_eq!(
+        kinds(&warnings),
+        [
+            (0, LintKind::AlwaysFalse),
+            (1, LintKind::AlwaysTrue),
+            (2, LintKind::AlwaysTrue),
+            (3, LintKind::AlwaysFalse),
+        ]
+    );
+}
+
+#[test]
+fn test_flags_fields_outside_the_node_schema() {
+    let warnings = lint(
+        r#"
+        RULE a WHEN node.vendr == "cisco" THEN ASSERT node.version IS "17.3"
+        RULE b WHEN vendor == "cisco" THEN SET polling.timeout TO 5
+        RULE c WHEN node.role == Router THEN SET custom_data.core TO true
+    "#,
+    );
+
+    let messages: Vec<&str> = warnings
+        .iter()
+        .map(|warning| warning.message.as_str())
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            "node has no field 'vendr' (in node.vendr)",
+            "Unknown polling setting polling.timeout; expected polling.interval or polling.profile",
+            "Unknown field vendor; did you mean node.vendor?",
+            "Unknown field Router",
+        ]
+    );
+    assert!(
+        warnings
+            .iter()
+            .all(|warning| warning.kind == LintKind::UnknownField)
+    );
+}
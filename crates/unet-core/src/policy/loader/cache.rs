@@ -14,6 +14,8 @@ pub struct CachedPolicy {
     pub mtime: SystemTime,
     /// Cache timestamp
     pub cached_at: SystemTime,
+    /// Included files and their modification times when cached
+    pub includes: Vec<(PathBuf, SystemTime)>,
 }
 
 /// Statistics about the policy cache state
@@ -35,9 +37,17 @@ impl CachedPolicy {
             rules,
             mtime,
             cached_at: SystemTime::now(),
+            includes: Vec::new(),
         }
     }
 
+    /// Records the included files, so the entry expires when one changes
+    #[must_use]
+    pub fn with_includes(mut self, includes: Vec<(PathBuf, SystemTime)>) -> Self {
+        self.includes = includes;
+        self
+    }
+
     /// Check if the cached policy is still valid based on TTL and file modification time
     #[must_use]
     pub fn is_valid(&self, ttl: std::time::Duration, current_mtime: SystemTime) -> bool {
@@ -46,8 +56,9 @@ impl CachedPolicy {
             .duration_since(self.cached_at)
             .map_or(true, |age| age > ttl);
 
-        // Check if file has been modified
-        let file_modified = current_mtime != self.mtime;
+        // Check if the file or anything it includes has been modified
+        let file_modified =
+            current_mtime != self.mtime || !super::includes::includes_unchanged(&self.includes);
 
         !cache_expired && !file_modified
     }
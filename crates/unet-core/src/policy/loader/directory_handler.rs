@@ -52,10 +52,7 @@ impl DirectoryHandler {
             });
         }
 
-        Err(PolicyError::Evaluation {
-            message: "No policies source configured (neither local directory nor Git repository)"
-                .to_string(),
-        })
+        Err(PolicyError::NoSource)
     }
 }
 
@@ -139,12 +136,6 @@ mod tests {
         let handler = DirectoryHandler::new(create_git_config());
         let result = handler.get_policies_directory();
 
-        assert!(result.is_err());
-        match result.unwrap_err() {
-            PolicyError::Evaluation { message } => {
-                assert!(message.contains("No policies source configured"));
-            }
-            other => panic!("Expected Evaluation error, got {other:?}"),
-        }
+        assert!(matches!(result, Err(PolicyError::NoSource)));
     }
 }
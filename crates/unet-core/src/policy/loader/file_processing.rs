@@ -8,7 +8,7 @@ use tracing::{debug, info, warn};
 use walkdir::WalkDir;
 
 use super::cache::CachedPolicy;
-use super::validation::PolicyValidator;
+use super::includes;
 
 /// Policy file metadata
 #[derive(Debug, Clone)]
@@ -227,11 +227,13 @@ impl FileProcessor {
         debug!("Loading policy file from disk: {}", path.display());
         let content = std::fs::read_to_string(path).map_err(PolicyError::Io)?;
 
-        // Validate and parse policy content
-        let rules = PolicyValidator::validate_and_parse(&content)?;
+        // Validate and parse policy content, resolving includes
+        let resolved = includes::resolve(path, &content)?;
+        let rules = resolved.rules;
 
         // Cache the parsed policy
-        let cached_policy = CachedPolicy::new(rules.clone(), mtime);
+        let cached_policy =
+            CachedPolicy::new(rules.clone(), mtime).with_includes(resolved.includes);
         policy_cache.insert(path.to_path_buf(), cached_policy);
 
         Ok(PolicyFile {
@@ -278,11 +280,14 @@ impl FileProcessor {
             .await
             .map_err(PolicyError::Io)?;
 
-        // Validate and parse policy content
-        let rules = PolicyValidator::validate_and_parse(&content)?;
+        // Validate and parse policy content, resolving includes (read synchronously;
+        // included rule files are small)
+        let resolved = includes::resolve(path, &content)?;
+        let rules = resolved.rules;
 
         // Cache the parsed policy
-        let cached_policy = CachedPolicy::new(rules.clone(), mtime);
+        let cached_policy =
+            CachedPolicy::new(rules.clone(), mtime).with_includes(resolved.includes);
         policy_cache.insert(path.to_path_buf(), cached_policy);
 
         Ok(PolicyFile {
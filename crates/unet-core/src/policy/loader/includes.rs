@@ -0,0 +1,147 @@
+//! Resolution of `INCLUDE` directives and rule groups across policy files
+//!
+//! Include paths are relative to the file containing the directive. Each file
+//! is read at most once per policy file, so shared files included through
+//! several paths contribute their rules once. Groups defined in a file are
+//! visible to everything after the point where the file is included.
+
+use crate::policy::{PolicyError, PolicyResult, PolicyRule, PolicyStatement};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use super::validation::PolicyValidator;
+
+/// A policy file with its includes resolved
+#[derive(Debug, Clone)]
+pub struct ResolvedPolicy {
+    /// Rules in evaluation order, with includes and groups expanded
+    pub rules: Vec<PolicyRule>,
+    /// Included files and their modification times when read
+    pub includes: Vec<(PathBuf, SystemTime)>,
+}
+
+/// Resolves the statements of `content`, read from `path`
+///
+/// # Errors
+///
+/// Returns `PolicyError` if a file is invalid or cannot be read, includes
+/// form a cycle, a group is defined twice, or an unknown group is used.
+pub fn resolve(path: &Path, content: &str) -> PolicyResult<ResolvedPolicy> {
+    let root = path.canonicalize().map_err(PolicyError::Io)?;
+    let mut resolver = Resolver {
+        stack: vec![root.clone()],
+        includes: Vec::new(),
+        groups: HashMap::new(),
+    };
+    let statements = PolicyValidator::validate_and_parse_statements(content)?;
+    let rules = resolver.resolve_statements(statements, &root)?;
+
+    Ok(ResolvedPolicy {
+        rules,
+        includes: resolver.includes,
+    })
+}
+
+/// Returns whether every included file still has the recorded modification time
+#[must_use]
+pub fn includes_unchanged(includes: &[(PathBuf, SystemTime)]) -> bool {
+    includes.iter().all(|(path, mtime)| {
+        std::fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .is_ok_and(|current| current == *mtime)
+    })
+}
+
+struct Resolver {
+    /// Files being resolved, outermost first
+    stack: Vec<PathBuf>,
+    /// Files read so far
+    includes: Vec<(PathBuf, SystemTime)>,
+    /// Groups by name, with the file defining them
+    groups: HashMap<String, (PathBuf, Vec<PolicyRule>)>,
+}
+
+impl Resolver {
+    fn resolve_statements(
+        &mut self,
+        statements: Vec<PolicyStatement>,
+        file: &Path,
+    ) -> PolicyResult<Vec<PolicyRule>> {
+        let mut rules = Vec::new();
+
+        for statement in statements {
+            match statement {
+                PolicyStatement::Rule(rule) => rules.push(rule),
+                PolicyStatement::Include(target) => {
+                    let dir = file.parent().unwrap_or_else(|| Path::new("."));
+                    rules.extend(self.include(&dir.join(&target), &target, file)?);
+                }
+                PolicyStatement::Group { name, rules: group } => {
+                    if let Some((defined_in, _)) = self.groups.get(&name) {
+                        return Err(validation_error(format!(
+                            "Rule group '{name}' in {} is already defined in {}",
+                            file.display(),
+                            defined_in.display()
+                        )));
+                    }
+                    self.groups.insert(name, (file.to_path_buf(), group));
+                }
+                PolicyStatement::Use(name) => {
+                    let (_, group) = self.groups.get(&name).ok_or_else(|| {
+                        validation_error(format!(
+                            "Unknown rule group '{name}' used in {}",
+                            file.display()
+                        ))
+                    })?;
+                    rules.extend(group.iter().cloned());
+                }
+            }
+        }
+
+        Ok(rules)
+    }
+
+    fn include(&mut self, path: &Path, target: &str, from: &Path) -> PolicyResult<Vec<PolicyRule>> {
+        let path = path.canonicalize().map_err(|e| {
+            validation_error(format!(
+                "Cannot include \"{target}\" from {}: {e}",
+                from.display()
+            ))
+        })?;
+
+        if self.stack.contains(&path) {
+            let chain: Vec<String> = self
+                .stack
+                .iter()
+                .skip_while(|open| **open != path)
+                .chain(std::iter::once(&path))
+                .map(|file| file.display().to_string())
+                .collect();
+            return Err(validation_error(format!(
+                "Include cycle: {}",
+                chain.join(" -> ")
+            )));
+        }
+        if self.includes.iter().any(|(seen, _)| *seen == path) {
+            return Ok(Vec::new());
+        }
+
+        let mtime = std::fs::metadata(&path)
+            .and_then(|metadata| metadata.modified())
+            .map_err(PolicyError::Io)?;
+        let content = std::fs::read_to_string(&path).map_err(PolicyError::Io)?;
+        self.includes.push((path.clone(), mtime));
+
+        let statements = PolicyValidator::validate_and_parse_statements(&content)
+            .map_err(|e| validation_error(format!("In included file {}: {e}", path.display())))?;
+        self.stack.push(path.clone());
+        let rules = self.resolve_statements(statements, &path);
+        self.stack.pop();
+        rules
+    }
+}
+
+const fn validation_error(message: String) -> PolicyError {
+    PolicyError::ValidationError { message }
+}
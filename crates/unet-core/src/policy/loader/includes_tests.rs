@@ -0,0 +1,137 @@
+//! Tests for includes and rule groups across policy files
+
+use super::*;
+use crate::config::GitConfig;
+use std::fs;
+use tempfile::TempDir;
+
+const NAMING_RULES: &str = r#"# Shared naming assertions
+WHEN node.role == "router" THEN ASSERT node.name CONTAINS "rtr"
+GROUP naming {
+    WHEN node.vendor == "cisco" THEN SET custom_data.os TO "ios"
+    WHEN node.vendor == "juniper" THEN SET custom_data.os TO "junos"
+}
+"#;
+
+fn loader() -> PolicyLoader {
+    PolicyLoader::new(GitConfig {
+        repository_url: None,
+        local_directory: None,
+        branch: "main".to_string(),
+        auth_token: None,
+        sync_interval: 300,
+        policies_repo: None,
+        templates_repo: None,
+    })
+}
+
+fn write(dir: &Path, name: &str, content: &str) -> PathBuf {
+    let path = dir.join(name);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, content).unwrap();
+    path
+}
+
+#[test]
+fn test_include_resolves_relative_to_including_file() {
+    let temp_dir = TempDir::new().unwrap();
+    write(temp_dir.path(), "common/naming.rules", NAMING_RULES);
+    let policy = write(
+        temp_dir.path(),
+        "sites/lab.policy",
+        r#"INCLUDE "../common/naming.rules"
+USE naming
+WHEN node.lifecycle == "live" THEN SET custom_data.monitored TO true
+"#,
+    );
+
+    let loaded = loader().load_policy_file(&policy).unwrap();
+
+    assert_eq!(loaded.rules.len(), 4);
+    assert_eq!(
+        loaded.rules[1].to_string(),
+        r#"WHEN node.vendor == "cisco" THEN SET custom_data.os TO "ios""#
+    );
+}
+
+#[test]
+fn test_shared_include_contributes_rules_once() {
+    let temp_dir = TempDir::new().unwrap();
+    write(temp_dir.path(), "naming.rules", NAMING_RULES);
+    write(temp_dir.path(), "core.rules", "INCLUDE \"naming.rules\"\n");
+    let policy = write(
+        temp_dir.path(),
+        "site.policy",
+        "INCLUDE \"naming.rules\"\nINCLUDE \"core.rules\"\nUSE naming\n",
+    );
+
+    let loaded = loader().load_policy_file(&policy).unwrap();
+
+    assert_eq!(loaded.rules.len(), 3);
+}
+
+#[test]
+fn test_include_cycle_is_rejected() {
+    let temp_dir = TempDir::new().unwrap();
+    write(temp_dir.path(), "a.rules", "INCLUDE \"b.rules\"\n");
+    write(temp_dir.path(), "b.rules", "INCLUDE \"a.rules\"\n");
+    let policy = write(temp_dir.path(), "site.policy", "INCLUDE \"a.rules\"\n");
+
+    let error = loader().load_policy_file(&policy).unwrap_err().to_string();
+
+    assert!(error.contains("Include cycle"), "{error}");
+    assert!(error.contains("a.rules -> "), "{error}");
+}
+
+#[test]
+fn test_unknown_group_and_missing_include_are_rejected() {
+    let temp_dir = TempDir::new().unwrap();
+    let unknown = write(temp_dir.path(), "unknown.policy", "USE naming\n");
+    let missing = write(
+        temp_dir.path(),
+        "missing.policy",
+        "INCLUDE \"common/none.rules\"\n",
+    );
+
+    let error = loader().load_policy_file(&unknown).unwrap_err().to_string();
+    assert!(error.contains("Unknown rule group 'naming'"), "{error}");
+
+    let error = loader().load_policy_file(&missing).unwrap_err().to_string();
+    assert!(
+        error.contains("Cannot include \"common/none.rules\""),
+        "{error}"
+    );
+}
+
+#[test]
+fn test_changed_include_invalidates_cached_policy() {
+    let temp_dir = TempDir::new().unwrap();
+    let shared = write(temp_dir.path(), "naming.rules", NAMING_RULES);
+    let policy = write(temp_dir.path(), "site.policy", "INCLUDE \"naming.rules\"\n");
+    let mut loader = loader().with_cache_ttl(Duration::from_secs(60));
+
+    assert_eq!(loader.load_policy_file(&policy).unwrap().rules.len(), 1);
+
+    std::thread::sleep(Duration::from_millis(10)); // Ensure different mtime
+    fs::write(&shared, format!("{NAMING_RULES}USE naming\n")).unwrap();
+
+    assert_eq!(loader.load_policy_file(&policy).unwrap().rules.len(), 3);
+}
+
+#[test]
+fn test_directory_load_skips_included_rule_files() {
+    let temp_dir = TempDir::new().unwrap();
+    write(temp_dir.path(), "common/naming.rules", NAMING_RULES);
+    write(
+        temp_dir.path(),
+        "site.policy",
+        "INCLUDE \"common/naming.rules\"\nUSE naming\n",
+    );
+
+    let result = loader()
+        .load_policies_from_directory(temp_dir.path())
+        .unwrap();
+
+    assert_eq!(result.total_files, 1);
+    assert_eq!(result.loaded[0].rules.len(), 3);
+}
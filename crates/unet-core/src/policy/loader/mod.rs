@@ -14,6 +14,7 @@ use tracing::{debug, info};
 pub use self::cache::{CacheManager, CacheStats, CachedPolicy};
 pub use self::directory_handler::DirectoryHandler;
 pub use self::file_processing::{FileProcessor, LoadResult, PolicyFile};
+pub use self::includes::ResolvedPolicy;
 // Git integration is not yet implemented - exports removed to avoid dead code warnings
 pub use self::validation::{PolicyValidator, ValidationError, ValidationResult};
 
@@ -21,10 +22,13 @@ mod cache;
 mod directory_handler;
 mod file_processing;
 mod git;
+mod includes;
 mod validation;
 
 #[cfg(test)]
 mod async_file_tests;
+#[cfg(test)]
+mod includes_tests;
 
 /// Policy file loader with Git integration and caching
 #[derive(Debug, Clone)]
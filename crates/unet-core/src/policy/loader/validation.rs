@@ -1,6 +1,8 @@
 //! Policy file validation logic
 
-use crate::policy::{PolicyError, PolicyParser, PolicyResult, PolicyRule};
+use crate::policy::grammar::{PolicyGrammar, Rule};
+use crate::policy::{PolicyError, PolicyParser, PolicyResult, PolicyRule, PolicyStatement};
+use pest::Parser;
 
 /// Policy file validation result
 #[derive(Debug, Clone)]
@@ -75,6 +77,9 @@ impl PolicyValidator {
     }
 
     /// Validate policy file content
+    ///
+    /// Every line must be a rule, a comment, or a directive (`INCLUDE`,
+    /// `GROUP name {`, `}`, or `USE`).
     pub fn validate_policy_file(content: &str) -> ValidationResult {
         let mut result = ValidationResult::new();
 
@@ -87,8 +92,11 @@ impl PolicyValidator {
             if trimmed.is_empty() || trimmed.starts_with('#') {
                 continue;
             }
+            if PolicyGrammar::parse(Rule::directive_line, trimmed).is_ok() {
+                continue;
+            }
 
-            match PolicyParser::parse_rule(line) {
+            match PolicyParser::parse_rule(trimmed) {
                 Ok(_rule) => {
                     result.valid_rules += 1;
                 }
@@ -103,10 +111,30 @@ impl PolicyValidator {
 
     /// Validate and parse policy content into rules
     ///
+    /// Rule groups are expanded where they are used; includes are rejected
+    /// because they need the file's location.
+    ///
     /// # Errors
     ///
     /// Returns `PolicyError` if the content cannot be parsed into valid rules
     pub fn validate_and_parse(content: &str) -> PolicyResult<Vec<PolicyRule>> {
+        Self::validate(content)?;
+        Ok(PolicyParser::parse_file(&without_comment_lines(content))?)
+    }
+
+    /// Validate and parse policy content into unresolved statements
+    ///
+    /// # Errors
+    ///
+    /// Returns `PolicyError` if any line is invalid
+    pub fn validate_and_parse_statements(content: &str) -> PolicyResult<Vec<PolicyStatement>> {
+        Self::validate(content)?;
+        Ok(PolicyParser::parse_statements(&without_comment_lines(
+            content,
+        ))?)
+    }
+
+    fn validate(content: &str) -> PolicyResult<()> {
         let validation_result = Self::validate_policy_file(content);
 
         if !validation_result.is_valid() {
@@ -117,21 +145,23 @@ impl PolicyValidator {
                 ),
             });
         }
+        Ok(())
+    }
+}
 
-        // Parse all rules if validation passed
-        let mut rules = Vec::new();
-        for line in content.lines() {
-            let trimmed = line.trim();
-            if trimmed.is_empty() || trimmed.starts_with('#') {
-                continue;
+/// Blanks `#` comment lines, which the grammar does not know, keeping line numbers
+fn without_comment_lines(content: &str) -> String {
+    content
+        .lines()
+        .map(|line| {
+            if line.trim_start().starts_with('#') {
+                ""
+            } else {
+                line
             }
-
-            let rule = PolicyParser::parse_rule(line)?;
-            rules.push(rule);
-        }
-
-        Ok(rules)
-    }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 impl Default for PolicyValidator {
@@ -5,10 +5,11 @@
 //! - `condition_parsing`: Condition parsing logic (or, and, not, comparison)
 //! - `value_parsing`: Value, field ref, and operator parsing
 //! - `action_parsing`: Action parsing logic (assert, set, apply template)
+//! - `statement_parsing`: Includes, rule groups, and group expansion
 
 use super::super::error::ParseError;
 use super::entry_points;
-use crate::policy::ast::PolicyRule;
+use crate::policy::ast::{Condition, PolicyRule, PolicyStatement};
 
 /// Parser for policy rules
 pub struct PolicyParser;
@@ -26,15 +27,39 @@ impl PolicyParser {
         entry_points::parse_rule_from_input(input)
     }
 
+    /// Parse a condition on its own, such as `node.vendor == "cisco"`
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError` if the input is not exactly one condition.
+    pub fn parse_condition(input: &str) -> Result<Condition, ParseError> {
+        entry_points::parse_condition_from_input(input)
+    }
+
     /// Parse multiple policy rules from a policy file
     ///
+    /// Rule groups are expanded where they are used.
+    ///
     /// # Errors
     ///
     /// Returns `ParseError` if:
     /// - The file contains invalid syntax
     /// - Any rule structure is malformed
     /// - Required components are missing
+    /// - A group is used before it is defined, or defined twice
+    /// - The file contains an `INCLUDE`, which only `PolicyLoader` resolves
     pub fn parse_file(input: &str) -> Result<Vec<PolicyRule>, ParseError> {
         entry_points::parse_file_from_input(input)
     }
+
+    /// Parse the statements of a policy file, leaving includes and groups
+    /// unresolved
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError` if the file contains invalid syntax or a
+    /// malformed rule.
+    pub fn parse_statements(input: &str) -> Result<Vec<PolicyStatement>, ParseError> {
+        entry_points::parse_statements_from_input(input)
+    }
 }
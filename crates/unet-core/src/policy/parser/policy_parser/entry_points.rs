@@ -1,8 +1,9 @@
 //! Main parsing entry points for policy rules and files
 
 use super::super::error::ParseError;
-use super::{action_parsing, condition_parsing};
-use crate::policy::ast::PolicyRule;
+use super::super::utils::next_pair;
+use super::{action_parsing, condition_parsing, statement_parsing, value_parsing};
+use crate::policy::ast::{Condition, PolicyRule, PolicyStatement, Value};
 use crate::policy::grammar::{PolicyGrammar, Rule};
 use pest::{Parser, iterators::Pair};
 
@@ -21,34 +22,61 @@ pub fn parse_rule_from_input(input: &str) -> Result<PolicyRule, ParseError> {
     parse_rule_pair(rule_pair)
 }
 
+/// Parse a condition written without a rule around it
+pub fn parse_condition_from_input(input: &str) -> Result<Condition, ParseError> {
+    let pairs = PolicyGrammar::parse(Rule::condition_line, input).map_err(|e| ParseError {
+        message: e.to_string(),
+        location: None,
+    })?;
+
+    let line = next_pair(pairs, "condition line")?;
+    condition_parsing::parse_condition(next_pair(line.into_inner(), "condition")?)
+}
+
 /// Parse multiple policy rules from a policy file
 pub fn parse_file_from_input(input: &str) -> Result<Vec<PolicyRule>, ParseError> {
+    statement_parsing::expand_statements(parse_statements_from_input(input)?)
+}
+
+/// Parse the statements of a policy file without resolving them
+pub fn parse_statements_from_input(input: &str) -> Result<Vec<PolicyStatement>, ParseError> {
     let pairs = PolicyGrammar::parse(Rule::policy_file, input).map_err(|e| ParseError {
         message: e.to_string(),
         location: None,
     })?;
 
-    let mut rules = Vec::new();
+    let mut statements = Vec::new();
     for pair in pairs {
         if pair.as_rule() == Rule::policy_file {
             for inner_pair in pair.into_inner() {
-                if inner_pair.as_rule() == Rule::rule {
-                    rules.push(parse_rule_pair(inner_pair)?);
+                if inner_pair.as_rule() != Rule::EOI {
+                    statements.push(statement_parsing::parse_statement(inner_pair)?);
                 }
             }
         }
     }
 
-    Ok(rules)
+    Ok(statements)
 }
 
 /// Parse a rule pair into a `PolicyRule`
 pub fn parse_rule_pair(pair: Pair<Rule>) -> Result<PolicyRule, ParseError> {
+    let mut id = None;
     let mut condition = None;
     let mut action = None;
 
     for inner_pair in pair.into_inner() {
         match inner_pair.as_rule() {
+            Rule::rule_id => {
+                let id_pair = next_pair(inner_pair.into_inner(), "rule ID")?;
+                id = Some(match id_pair.as_rule() {
+                    Rule::string_literal => match value_parsing::parse_value(id_pair)? {
+                        Value::String(text) => text,
+                        other => other.to_string(),
+                    },
+                    _ => id_pair.as_str().to_string(),
+                });
+            }
             Rule::condition => {
                 condition = Some(condition_parsing::parse_condition(inner_pair)?);
             }
@@ -60,7 +88,7 @@ pub fn parse_rule_pair(pair: Pair<Rule>) -> Result<PolicyRule, ParseError> {
     }
 
     Ok(PolicyRule {
-        id: None, // Parser doesn't set IDs - they can be set by loader
+        id,
         condition: condition.ok_or_else(|| ParseError {
             message: "Missing condition in rule".to_string(),
             location: None,
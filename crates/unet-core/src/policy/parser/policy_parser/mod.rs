@@ -6,6 +6,7 @@ mod condition_parsing;
 mod condition_parsing_tests;
 pub mod core;
 mod entry_points;
+mod statement_parsing;
 mod tests;
 mod value_parsing;
 
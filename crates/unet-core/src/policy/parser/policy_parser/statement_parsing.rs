@@ -0,0 +1,86 @@
+//! Statement parsing logic for includes and rule groups
+
+use super::super::error::ParseError;
+use super::super::utils::next_pair;
+use super::{entry_points, value_parsing};
+use crate::policy::ast::{PolicyRule, PolicyStatement, Value};
+use crate::policy::grammar::Rule;
+use pest::iterators::Pair;
+use std::collections::HashMap;
+
+/// Parse a top-level statement pair of a policy file
+pub fn parse_statement(pair: Pair<Rule>) -> Result<PolicyStatement, ParseError> {
+    match pair.as_rule() {
+        Rule::rule => entry_points::parse_rule_pair(pair).map(PolicyStatement::Rule),
+        Rule::include_directive => {
+            let path_pair = next_pair(pair.into_inner(), "include path")?;
+            match value_parsing::parse_value(path_pair)? {
+                Value::String(path) => Ok(PolicyStatement::Include(path)),
+                _ => Err(ParseError {
+                    message: "Include path must be a string".to_string(),
+                    location: None,
+                }),
+            }
+        }
+        Rule::rule_group => {
+            let mut inner = pair.into_inner();
+            let open = next_pair(&mut inner, "group header")?;
+            let name = next_pair(open.into_inner(), "group name")?
+                .as_str()
+                .to_string();
+            let rules = inner
+                .map(entry_points::parse_rule_pair)
+                .collect::<Result<_, _>>()?;
+            Ok(PolicyStatement::Group { name, rules })
+        }
+        Rule::use_directive => {
+            let name = next_pair(pair.into_inner(), "group name")?;
+            Ok(PolicyStatement::Use(name.as_str().to_string()))
+        }
+        _ => Err(ParseError {
+            message: format!("Unexpected statement rule: {:?}", pair.as_rule()),
+            location: None,
+        }),
+    }
+}
+
+/// Expand groups used within one file into a flat rule list
+///
+/// Includes cannot be resolved without knowing where the file lives, so they
+/// are rejected; `PolicyLoader` resolves them when loading files.
+pub fn expand_statements(statements: Vec<PolicyStatement>) -> Result<Vec<PolicyRule>, ParseError> {
+    let mut groups: HashMap<String, Vec<PolicyRule>> = HashMap::new();
+    let mut rules = Vec::new();
+
+    for statement in statements {
+        match statement {
+            PolicyStatement::Rule(rule) => rules.push(rule),
+            PolicyStatement::Group { name, rules: group } => {
+                if groups.contains_key(&name) {
+                    return Err(ParseError {
+                        message: format!("Rule group '{name}' is defined more than once"),
+                        location: None,
+                    });
+                }
+                groups.insert(name, group);
+            }
+            PolicyStatement::Use(name) => {
+                let group = groups.get(&name).ok_or_else(|| ParseError {
+                    message: format!("Unknown rule group '{name}'"),
+                    location: None,
+                })?;
+                rules.extend(group.iter().cloned());
+            }
+            PolicyStatement::Include(path) => {
+                return Err(ParseError {
+                    message: format!(
+                        "INCLUDE \"{path}\" can only be resolved when loading a policy file"
+                    ),
+                    location: None,
+                });
+            }
+        }
+    }
+
+    Ok(rules)
+}
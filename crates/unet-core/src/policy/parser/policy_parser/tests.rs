@@ -3,7 +3,9 @@
 #[cfg(test)]
 mod policy_parser_tests {
     use crate::policy::PolicyParser;
-    use crate::policy::ast::{ComparisonOperator, Condition, Value};
+    use crate::policy::ast::{
+        Action, ComparisonOperator, Condition, FieldRef, PolicyStatement, Value,
+    };
 
     #[test]
     fn test_parse_simple_rule() {
@@ -46,6 +48,17 @@ mod policy_parser_tests {
         );
     }
 
+    #[test]
+    fn test_parse_standalone_condition() {
+        let condition =
+            PolicyParser::parse_condition(r#"node.vendor == "cisco" AND node.role == "router""#)
+                .unwrap();
+        assert!(matches!(condition, Condition::And(_, _)));
+
+        assert!(PolicyParser::parse_condition("").is_err());
+        assert!(PolicyParser::parse_condition(r#"node.vendor == "cisco" THEN"#).is_err());
+    }
+
     #[test]
     fn test_parse_null_check() {
         let input = r"WHEN custom_data.location IS NOT NULL THEN SET node.location_id TO custom_data.location";
@@ -53,6 +66,21 @@ mod policy_parser_tests {
         assert!(result.is_ok(), "Failed to parse null check: {result:?}");
     }
 
+    #[test]
+    fn test_parse_polling_duration() {
+        let input = "WHEN custom_data.degraded == true THEN SET polling.interval TO 60s";
+        let rule = PolicyParser::parse_rule(input).unwrap();
+        assert_eq!(
+            rule.action,
+            Action::Set {
+                field: FieldRef {
+                    path: vec!["polling".to_string(), "interval".to_string()],
+                },
+                value: Value::String("60s".to_string()),
+            }
+        );
+    }
+
     #[test]
     fn test_parse_regex_literal() {
         let input = r#"WHEN node.hostname MATCHES /^dist-\d+$/ THEN APPLY "dist-template.jinja""#;
@@ -136,4 +164,71 @@ mod policy_parser_tests {
         let rules = result.unwrap();
         assert_eq!(rules.len(), 0);
     }
+
+    #[test]
+    fn test_parse_statements_with_includes_and_groups() {
+        let input = r#"
+            INCLUDE "common/naming.rules"
+            GROUP naming {
+                WHEN node.name MATCHES /^[a-z]+-\d+$/ THEN SET custom_data.named TO true
+                WHEN node.role == "router" THEN ASSERT node.name CONTAINS "rtr"
+            }
+            USE naming
+        "#;
+
+        let statements = PolicyParser::parse_statements(input).unwrap();
+
+        assert_eq!(statements.len(), 3);
+        assert_eq!(
+            statements[0],
+            PolicyStatement::Include("common/naming.rules".to_string())
+        );
+        match &statements[1] {
+            PolicyStatement::Group { name, rules } => {
+                assert_eq!(name, "naming");
+                assert_eq!(rules.len(), 2);
+            }
+            other => panic!("expected a group, got {other:?}"),
+        }
+        assert_eq!(statements[2], PolicyStatement::Use("naming".to_string()));
+    }
+
+    #[test]
+    fn test_parse_file_expands_groups() {
+        let input = r#"
+            GROUP vendor { WHEN node.vendor == "cisco" THEN SET custom_data.os TO "ios" }
+            USE vendor
+            WHEN node.role == "router" THEN SET custom_data.priority TO "high"
+            USE vendor
+        "#;
+
+        let rules = PolicyParser::parse_file(input).unwrap();
+
+        assert_eq!(rules.len(), 3);
+        assert_eq!(rules[0], rules[2]);
+    }
+
+    #[test]
+    fn test_parse_file_rejects_unresolvable_statements() {
+        let unknown = PolicyParser::parse_file("USE missing").unwrap_err();
+        assert!(unknown.message.contains("Unknown rule group 'missing'"));
+
+        let include = PolicyParser::parse_file(r#"INCLUDE "common.rules""#).unwrap_err();
+        assert!(include.message.contains("when loading a policy file"));
+    }
+
+    #[test]
+    fn test_parse_rule_ids() {
+        let rules = PolicyParser::parse_file(
+            r#"
+            RULE ntp_servers WHEN node.vendor == "cisco" THEN SET custom_data.ntp TO "10.0.0.1"
+            RULE "core-snmp" WHEN node.role == "core" THEN ASSERT node.version IS "17.3"
+            WHEN node.role == "access" THEN SET custom_data.tier TO 3
+        "#,
+        )
+        .unwrap();
+
+        let ids: Vec<Option<&str>> = rules.iter().map(|rule| rule.id.as_deref()).collect();
+        assert_eq!(ids, [Some("ntp_servers"), Some("core-snmp"), None]);
+    }
 }
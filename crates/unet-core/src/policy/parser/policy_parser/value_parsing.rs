@@ -46,7 +46,7 @@ pub fn parse_field_ref(pair: &Pair<Rule>) -> FieldRef {
     FieldRef { path }
 }
 
-/// Parse a value (string, number, boolean, null, regex, or field reference)
+/// Parse a value (string, duration, number, boolean, null, regex, or field reference)
 pub fn parse_value(pair: Pair<Rule>) -> Result<Value, ParseError> {
     match pair.as_rule() {
         Rule::value => {
@@ -90,6 +90,7 @@ pub fn parse_value(pair: Pair<Rule>) -> Result<Value, ParseError> {
                     })
             }
         }
+        Rule::duration_literal => Ok(Value::String(pair.as_str().to_string())),
         Rule::boolean_literal => match pair.as_str() {
             "true" => Ok(Value::Boolean(true)),
             "false" => Ok(Value::Boolean(false)),
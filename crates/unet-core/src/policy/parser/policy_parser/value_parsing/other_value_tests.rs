@@ -80,3 +80,15 @@ fn test_parse_value_nested_value_rule() {
     assert!(result.is_ok());
     assert_eq!(result.unwrap(), Value::String("nested string".to_string()));
 }
+
+#[test]
+fn test_parse_value_duration() {
+    let pair = create_test_pair(Rule::value, "60s");
+    assert_eq!(parse_value(pair).unwrap(), Value::String("60s".to_string()));
+
+    let pair = create_test_pair(Rule::value, "1h30m");
+    assert_eq!(
+        parse_value(pair).unwrap(),
+        Value::String("1h30m".to_string())
+    );
+}
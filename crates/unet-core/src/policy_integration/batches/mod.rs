@@ -0,0 +1,192 @@
+//! Named policy evaluation batches run through the `PolicyOrchestrator`
+//!
+//! A batch definition selects nodes with a [`NodeFilter`], which can name a
+//! dynamic node group (see [`crate::groups`]), and rules by tag, and assigns
+//! its priority to every selected rule. Definitions are stored through the
+//! `DataStore` settings API; runs and their results are kept in memory by
+//! [`BatchRuns`].
+//!
+//! Rules are tagged with the stem of the policy file they were loaded from
+//! (`security.policy` yields `security`) and with their rule ID, if any.
+
+mod runs;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult};
+use crate::groups::get_group;
+use crate::models::{DeviceRole, Lifecycle, Node, Vendor};
+use crate::policy::{OrchestrationRule, PolicyPriority};
+
+pub use runs::{BatchRun, BatchRunState, BatchRuns};
+
+/// Settings namespace holding batch definitions keyed by name
+const BATCH_NAMESPACE: &str = "policy_batches";
+
+/// Node selection criteria; empty criteria match every node
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeFilter {
+    /// Only these nodes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub node_ids: Vec<Uuid>,
+    /// Only nodes from this vendor
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vendor: Option<Vendor>,
+    /// Only nodes with this role
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<DeviceRole>,
+    /// Only nodes in this lifecycle state
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lifecycle: Option<Lifecycle>,
+    /// Only current members of this node group
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+}
+
+impl NodeFilter {
+    /// Returns true if the node satisfies every criterion except group
+    /// membership, which [`NodeFilter::select`] checks
+    #[must_use]
+    pub fn matches(&self, node: &Node) -> bool {
+        (self.node_ids.is_empty() || self.node_ids.contains(&node.id))
+            && self.vendor.is_none_or(|vendor| node.vendor == vendor)
+            && self.role.is_none_or(|role| node.role == role)
+            && self
+                .lifecycle
+                .is_none_or(|lifecycle| node.lifecycle == lifecycle)
+    }
+
+    /// Keeps the nodes satisfying every criterion, evaluating the group's
+    /// filter against them
+    ///
+    /// # Errors
+    /// Returns an error if the group does not exist or its filter does not parse.
+    pub async fn select(
+        &self,
+        datastore: &dyn DataStore,
+        nodes: Vec<Node>,
+    ) -> DataStoreResult<Vec<Node>> {
+        let group = match &self.group {
+            Some(name) => Some(get_group(datastore, name).await?),
+            None => None,
+        };
+        let mut selected = Vec::new();
+        for node in nodes {
+            if self.matches(&node) && group.as_ref().map_or(Ok(true), |g| g.matches(&node))? {
+                selected.push(node);
+            }
+        }
+        Ok(selected)
+    }
+}
+
+/// Stored definition of an evaluation batch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchDefinition {
+    /// Unique batch name
+    pub name: String,
+    /// Nodes to evaluate
+    #[serde(default)]
+    pub node_filter: NodeFilter,
+    /// Rules carrying any of these tags are evaluated; empty selects all rules
+    #[serde(default)]
+    pub rule_tags: Vec<String>,
+    /// Priority assigned to the selected rules
+    #[serde(default)]
+    pub priority: PolicyPriority,
+}
+
+impl BatchDefinition {
+    /// Checks that the definition can be stored
+    ///
+    /// # Errors
+    /// Returns a validation error if the name is empty or contains `/`.
+    pub fn validate(&self) -> DataStoreResult<()> {
+        if self.name.trim().is_empty() || self.name.contains('/') {
+            return Err(DataStoreError::ValidationError {
+                message: format!("Invalid batch name: {:?}", self.name),
+            });
+        }
+        Ok(())
+    }
+
+    /// Selects the rules matching the batch tags and applies the batch priority
+    #[must_use]
+    pub fn select_rules(&self, rules: &[OrchestrationRule]) -> Vec<OrchestrationRule> {
+        rules
+            .iter()
+            .filter(|rule| {
+                self.rule_tags.is_empty() || self.rule_tags.iter().any(|t| rule.has_tag(t))
+            })
+            .map(|rule| OrchestrationRule {
+                priority: self.priority,
+                ..rule.clone()
+            })
+            .collect()
+    }
+}
+
+/// Lists stored batch definitions ordered by name
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or a definition is malformed.
+pub async fn list_batch_definitions(
+    datastore: &dyn DataStore,
+) -> DataStoreResult<Vec<BatchDefinition>> {
+    datastore
+        .list_settings(BATCH_NAMESPACE)
+        .await?
+        .into_iter()
+        .map(|(name, value)| decode(&name, value))
+        .collect()
+}
+
+/// Gets a stored batch definition by name
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or the definition is malformed.
+pub async fn get_batch_definition(
+    datastore: &dyn DataStore,
+    name: &str,
+) -> DataStoreResult<Option<BatchDefinition>> {
+    datastore
+        .get_setting(BATCH_NAMESPACE, name)
+        .await?
+        .map(|value| decode(name, value))
+        .transpose()
+}
+
+/// Validates and stores a batch definition, replacing one with the same name
+///
+/// # Errors
+/// Returns an error if the definition is invalid or the datastore write fails.
+pub async fn save_batch_definition(
+    datastore: &dyn DataStore,
+    definition: &BatchDefinition,
+) -> DataStoreResult<()> {
+    definition.validate()?;
+    let value = serde_json::to_value(definition).map_err(|e| DataStoreError::InternalError {
+        message: format!("Failed to serialize batch {}: {e}", definition.name),
+    })?;
+    datastore
+        .put_setting(BATCH_NAMESPACE, &definition.name, &value)
+        .await
+}
+
+/// Deletes a stored batch definition
+///
+/// # Errors
+/// Returns `NotFound` if no batch has this name.
+pub async fn delete_batch_definition(datastore: &dyn DataStore, name: &str) -> DataStoreResult<()> {
+    datastore.delete_setting(BATCH_NAMESPACE, name).await
+}
+
+fn decode(name: &str, value: serde_json::Value) -> DataStoreResult<BatchDefinition> {
+    serde_json::from_value(value).map_err(|e| DataStoreError::InternalError {
+        message: format!("Stored batch {name} is malformed: {e}"),
+    })
+}
+
+#[cfg(test)]
+mod tests;
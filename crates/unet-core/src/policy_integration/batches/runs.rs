@@ -0,0 +1,223 @@
+//! Execution and progress tracking of batch runs
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, PoisonError, RwLock};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use super::BatchDefinition;
+use crate::datastore::DataStore;
+use crate::policy::{AggregatedResult, OrchestrationRule, PolicyOrchestrator};
+use crate::policy_integration::{
+    PolicyEvaluationEngine, add_change_ages, add_interface_errors, add_vlan_view,
+};
+
+/// Number of runs kept in memory; the oldest finished runs are dropped first
+const MAX_RETAINED_RUNS: usize = 100;
+
+/// Lifecycle of a batch run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchRunState {
+    /// Accepted but not started
+    Pending,
+    /// Evaluating nodes
+    Running,
+    /// All selected nodes were evaluated
+    Completed,
+    /// The run could not select nodes
+    Failed,
+}
+
+/// Progress and results of one triggered batch
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchRun {
+    /// Run identifier
+    pub id: Uuid,
+    /// Name of the batch definition
+    pub batch: String,
+    /// Current state
+    pub state: BatchRunState,
+    /// Nodes selected by the batch filter
+    pub nodes_total: usize,
+    /// Nodes evaluated so far
+    pub nodes_completed: usize,
+    /// Rules selected by the batch tags
+    pub rules_selected: usize,
+    /// True if cached results were ignored and every node was re-evaluated
+    pub forced: bool,
+    /// When the run was triggered
+    pub started_at: DateTime<Utc>,
+    /// When the run finished
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Per-node orchestrator results
+    pub results: Vec<AggregatedResult>,
+    /// Per-node or run-level failures
+    pub errors: Vec<String>,
+}
+
+impl BatchRun {
+    const fn is_finished(&self) -> bool {
+        matches!(self.state, BatchRunState::Completed | BatchRunState::Failed)
+    }
+}
+
+/// In-memory registry of batch runs shared between clones
+#[derive(Debug, Clone, Default)]
+pub struct BatchRuns {
+    runs: Arc<RwLock<HashMap<Uuid, BatchRun>>>,
+}
+
+impl BatchRuns {
+    /// Registers a pending run for the named batch
+    ///
+    /// A forced run re-evaluates every node even if its policy-relevant
+    /// state is unchanged since a cached evaluation.
+    #[must_use]
+    pub fn start(&self, batch: &str, forced: bool) -> BatchRun {
+        let run = BatchRun {
+            id: Uuid::new_v4(),
+            batch: batch.to_string(),
+            state: BatchRunState::Pending,
+            nodes_total: 0,
+            nodes_completed: 0,
+            rules_selected: 0,
+            forced,
+            started_at: Utc::now(),
+            finished_at: None,
+            results: Vec::new(),
+            errors: Vec::new(),
+        };
+
+        let mut runs = self.runs.write().unwrap_or_else(PoisonError::into_inner);
+        if runs.len() >= MAX_RETAINED_RUNS {
+            let oldest = runs
+                .values()
+                .filter(|run| run.is_finished())
+                .min_by_key(|run| run.started_at)
+                .map(|run| run.id);
+            if let Some(id) = oldest {
+                runs.remove(&id);
+            }
+        }
+        runs.insert(run.id, run.clone());
+        run
+    }
+
+    /// Returns a snapshot of a run
+    #[must_use]
+    pub fn get(&self, id: &Uuid) -> Option<BatchRun> {
+        self.runs
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(id)
+            .cloned()
+    }
+
+    /// Returns snapshots of all retained runs, newest first
+    #[must_use]
+    pub fn list(&self) -> Vec<BatchRun> {
+        let mut runs: Vec<BatchRun> = self
+            .runs
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .values()
+            .cloned()
+            .collect();
+        runs.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+        runs
+    }
+
+    /// Evaluates the batch and records progress on the run after each node
+    pub async fn execute(
+        &self,
+        run_id: Uuid,
+        definition: &BatchDefinition,
+        rules: &[OrchestrationRule],
+        orchestrator: &Mutex<PolicyOrchestrator>,
+        engine: &dyn PolicyEvaluationEngine,
+        datastore: &dyn DataStore,
+    ) {
+        let nodes = match datastore.get_nodes_for_policy_evaluation().await {
+            Ok(nodes) => definition.node_filter.select(datastore, nodes).await,
+            Err(e) => Err(e),
+        };
+        let nodes = match nodes {
+            Ok(nodes) => nodes,
+            Err(e) => {
+                self.update(run_id, |run| {
+                    run.state = BatchRunState::Failed;
+                    run.errors.push(format!("Failed to load nodes: {e}"));
+                    run.finished_at = Some(Utc::now());
+                });
+                return;
+            }
+        };
+        let rules = definition.select_rules(rules);
+        let forced = self.get(&run_id).is_some_and(|run| run.forced);
+
+        self.update(run_id, |run| {
+            run.state = BatchRunState::Running;
+            run.nodes_total = nodes.len();
+            run.rules_selected = rules.len();
+        });
+
+        for node in &nodes {
+            let context = match engine.create_evaluation_context(node) {
+                Ok(mut context) => {
+                    let selected = || rules.iter().map(|r| &r.rule);
+                    async {
+                        add_vlan_view(&mut context, datastore, node.id, selected()).await?;
+                        add_change_ages(&mut context, datastore, node.id, selected()).await?;
+                        add_interface_errors(&mut context, datastore, node.id, selected()).await
+                    }
+                    .await
+                    .map(|()| context)
+                }
+                Err(e) => Err(e),
+            };
+            let outcome = match context {
+                Ok(context) => {
+                    let mut orchestrator = orchestrator.lock().await;
+                    if forced {
+                        orchestrator
+                            .force_evaluate_node_policies(
+                                node.id,
+                                context,
+                                rules.clone(),
+                                datastore,
+                            )
+                            .await
+                    } else {
+                        orchestrator
+                            .evaluate_node_policies(node.id, context, rules.clone(), datastore)
+                            .await
+                    }
+                }
+                Err(e) => Err(e),
+            };
+
+            self.update(run_id, |run| {
+                run.nodes_completed += 1;
+                match outcome {
+                    Ok(result) => run.results.push(result),
+                    Err(e) => run.errors.push(format!("Node {}: {e}", node.id)),
+                }
+            });
+        }
+
+        self.update(run_id, |run| {
+            run.state = BatchRunState::Completed;
+            run.finished_at = Some(Utc::now());
+        });
+    }
+
+    fn update(&self, id: Uuid, apply: impl FnOnce(&mut BatchRun)) {
+        let mut runs = self.runs.write().unwrap_or_else(PoisonError::into_inner);
+        if let Some(run) = runs.get_mut(&id) {
+            apply(run);
+        }
+    }
+}
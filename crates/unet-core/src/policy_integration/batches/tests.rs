@@ -0,0 +1,227 @@
+use super::*;
+use crate::datastore::MockDataStore;
+use crate::policy::{OrchestrationConfig, PolicyOrchestrator, PolicyParser};
+use crate::policy_integration::DefaultPolicyEvaluationEngine;
+use serde_json::json;
+use tokio::sync::Mutex;
+
+fn node(name: &str, vendor: Vendor, role: DeviceRole) -> Node {
+    Node::new(name.to_string(), "example.com".to_string(), vendor, role)
+}
+
+fn tagged_rule(tag: &str) -> OrchestrationRule {
+    let rule = PolicyParser::parse_rule(
+        r#"WHEN node.vendor == "cisco" THEN ASSERT node.vendor IS "cisco""#,
+    )
+    .unwrap();
+    OrchestrationRule::new(rule).with_tag(tag.to_string())
+}
+
+fn definition(name: &str) -> BatchDefinition {
+    BatchDefinition {
+        name: name.to_string(),
+        node_filter: NodeFilter::default(),
+        rule_tags: Vec::new(),
+        priority: PolicyPriority::Medium,
+    }
+}
+
+#[test]
+fn test_node_filter_matches_all_criteria() {
+    let router = node("r1", Vendor::Cisco, DeviceRole::Router);
+    let switch = node("s1", Vendor::Juniper, DeviceRole::Switch);
+
+    assert!(NodeFilter::default().matches(&router));
+
+    let filter = NodeFilter {
+        vendor: Some(Vendor::Cisco),
+        role: Some(DeviceRole::Router),
+        ..NodeFilter::default()
+    };
+    assert!(filter.matches(&router));
+    assert!(!filter.matches(&switch));
+
+    let by_id = NodeFilter {
+        node_ids: vec![switch.id],
+        ..NodeFilter::default()
+    };
+    assert!(by_id.matches(&switch));
+    assert!(!by_id.matches(&router));
+}
+
+#[tokio::test]
+async fn test_node_filter_selects_group_members() {
+    let group = crate::groups::NodeGroup::new("cisco", r#"node.vendor == "cisco""#, None).unwrap();
+    let mut mock = MockDataStore::new();
+    mock.expect_get_setting()
+        .withf(|namespace, key| namespace == "node_groups" && key == "cisco")
+        .returning(move |_, _| {
+            let value = serde_json::to_value(&group).unwrap();
+            Box::pin(async move { Ok(Some(value)) })
+        });
+    let nodes = vec![
+        node("r1", Vendor::Cisco, DeviceRole::Router),
+        node("s1", Vendor::Cisco, DeviceRole::Switch),
+        node("r2", Vendor::Juniper, DeviceRole::Router),
+    ];
+
+    let filter = NodeFilter {
+        role: Some(DeviceRole::Router),
+        group: Some("cisco".to_string()),
+        ..NodeFilter::default()
+    };
+    let selected = filter.select(&mock, nodes).await.unwrap();
+
+    assert_eq!(selected.len(), 1);
+    assert_eq!(selected[0].name, "r1");
+}
+
+#[test]
+fn test_select_rules_filters_tags_and_applies_priority() {
+    let rules = vec![tagged_rule("security"), tagged_rule("naming")];
+
+    let mut batch = definition("security");
+    batch.rule_tags = vec!["security".to_string()];
+    batch.priority = PolicyPriority::Critical;
+
+    let selected = batch.select_rules(&rules);
+    assert_eq!(selected.len(), 1);
+    assert!(selected[0].has_tag("security"));
+    assert_eq!(selected[0].priority, PolicyPriority::Critical);
+
+    assert_eq!(definition("all").select_rules(&rules).len(), 2);
+}
+
+#[test]
+fn test_definition_rejects_invalid_names() {
+    assert!(definition(" ").validate().is_err());
+    assert!(definition("a/b").validate().is_err());
+    assert!(definition("nightly").validate().is_ok());
+}
+
+#[tokio::test]
+async fn test_save_and_list_batch_definitions() {
+    let mut mock = MockDataStore::new();
+    mock.expect_put_setting()
+        .withf(|namespace, key, value| {
+            namespace == BATCH_NAMESPACE && key == "nightly" && value["name"] == "nightly"
+        })
+        .times(1)
+        .returning(|_, _, _| Box::pin(async { Ok(()) }));
+    mock.expect_list_settings().returning(|_| {
+        Box::pin(async {
+            Ok(vec![(
+                "nightly".to_string(),
+                json!({ "name": "nightly", "rule_tags": ["security"] }),
+            )])
+        })
+    });
+
+    save_batch_definition(&mock, &definition("nightly"))
+        .await
+        .unwrap();
+    let definitions = list_batch_definitions(&mock).await.unwrap();
+
+    assert_eq!(definitions.len(), 1);
+    assert_eq!(definitions[0].rule_tags, vec!["security".to_string()]);
+    assert_eq!(definitions[0].priority, PolicyPriority::Medium);
+}
+
+#[tokio::test]
+async fn test_execute_records_progress_and_results() {
+    let nodes = vec![
+        node("r1", Vendor::Cisco, DeviceRole::Router),
+        node("r2", Vendor::Cisco, DeviceRole::Router),
+        node("s1", Vendor::Juniper, DeviceRole::Switch),
+    ];
+    let mut mock = MockDataStore::new();
+    mock.expect_get_nodes_for_policy_evaluation()
+        .returning(move || {
+            let nodes = nodes.clone();
+            Box::pin(async move { Ok(nodes) })
+        });
+
+    let mut batch = definition("routers");
+    batch.node_filter.role = Some(DeviceRole::Router);
+    let runs = BatchRuns::default();
+    let run = runs.start(&batch.name, false);
+    assert_eq!(run.state, BatchRunState::Pending);
+
+    let orchestrator = Mutex::new(PolicyOrchestrator::new(OrchestrationConfig::default()));
+    runs.execute(
+        run.id,
+        &batch,
+        &[tagged_rule("security")],
+        &orchestrator,
+        &DefaultPolicyEvaluationEngine::new(),
+        &mock,
+    )
+    .await;
+
+    let finished = runs.get(&run.id).unwrap();
+    assert_eq!(finished.state, BatchRunState::Completed);
+    assert_eq!(finished.nodes_total, 2);
+    assert_eq!(finished.nodes_completed, 2);
+    assert_eq!(finished.rules_selected, 1);
+    assert_eq!(finished.results.len(), 2);
+    assert!(finished.finished_at.is_some());
+    assert_eq!(orchestrator.lock().await.cache_size(), 2);
+}
+
+#[tokio::test]
+async fn test_execute_marks_run_failed_when_nodes_unavailable() {
+    let mut mock = MockDataStore::new();
+    mock.expect_get_nodes_for_policy_evaluation().returning(|| {
+        Box::pin(async {
+            Err(DataStoreError::ConnectionError {
+                message: "down".to_string(),
+            })
+        })
+    });
+
+    let runs = BatchRuns::default();
+    let run = runs.start("nightly", false);
+    let orchestrator = Mutex::new(PolicyOrchestrator::default());
+    runs.execute(
+        run.id,
+        &definition("nightly"),
+        &[],
+        &orchestrator,
+        &DefaultPolicyEvaluationEngine::new(),
+        &mock,
+    )
+    .await;
+
+    let failed = runs.get(&run.id).unwrap();
+    assert_eq!(failed.state, BatchRunState::Failed);
+    assert_eq!(failed.errors.len(), 1);
+    assert_eq!(runs.list().len(), 1);
+}
+
+#[tokio::test]
+async fn test_forced_run_skips_cached_results() {
+    let nodes = vec![node("r1", Vendor::Cisco, DeviceRole::Router)];
+    let mut mock = MockDataStore::new();
+    mock.expect_get_nodes_for_policy_evaluation()
+        .returning(move || {
+            let nodes = nodes.clone();
+            Box::pin(async move { Ok(nodes) })
+        });
+
+    let batch = definition("nightly");
+    let rules = [tagged_rule("security")];
+    let runs = BatchRuns::default();
+    let orchestrator = Mutex::new(PolicyOrchestrator::default());
+    let engine = DefaultPolicyEvaluationEngine::new();
+    for forced in [false, false, true] {
+        let run = runs.start(&batch.name, forced);
+        runs.execute(run.id, &batch, &rules, &orchestrator, &engine, &mock)
+            .await;
+        assert_eq!(runs.get(&run.id).unwrap().results.len(), 1);
+    }
+
+    let metrics = orchestrator.lock().await.cache_metrics();
+    assert_eq!(metrics.misses, 1);
+    assert_eq!(metrics.hits, 1);
+    assert_eq!(metrics.forced, 1);
+}
@@ -0,0 +1,194 @@
+//! Canary rollout of new policy rules
+//!
+//! A canary definition names a rule tag (a rule ID or policy file stem, as
+//! assigned by `PolicyService::load_orchestration_rules_async`) and the nodes
+//! that evaluate it: a percentage of the fleet, nodes carrying a tag in
+//! `custom_data.tags`, or both. Other nodes skip the rule, so its results are
+//! only recorded for the canary nodes until the definition is deleted.
+//! Definitions are stored through the `DataStore` settings API.
+//!
+//! Percentage selection hashes the rule tag with the node ID, so a node stays
+//! in or out of a canary across evaluations and raising the percentage only
+//! adds nodes.
+
+mod report;
+
+use serde::{Deserialize, Serialize};
+
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult};
+use crate::models::Node;
+use crate::policy::{OrchestrationRule, PolicyError, PolicyResult, PolicyRule};
+
+pub use report::{CanaryCohort, CanaryReport};
+
+/// Settings namespace holding canary definitions keyed by rule tag
+const CANARY_NAMESPACE: &str = "policy_canaries";
+
+/// Stored definition of a canary rollout
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CanaryDefinition {
+    /// Rule ID or policy file stem of the rules under canary
+    pub rule: String,
+    /// Share of nodes, 0 to 100, that evaluate the rules
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub percent: Option<u8>,
+    /// Nodes with this tag in `custom_data.tags` evaluate the rules
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_tag: Option<String>,
+}
+
+impl CanaryDefinition {
+    /// Checks that the definition can be stored
+    ///
+    /// # Errors
+    /// Returns a validation error if the rule tag is empty or contains `/`,
+    /// the percentage exceeds 100, or no nodes are selected.
+    pub fn validate(&self) -> DataStoreResult<()> {
+        let message = if self.rule.trim().is_empty() || self.rule.contains('/') {
+            format!("Invalid canary rule tag: {:?}", self.rule)
+        } else if self.percent.is_some_and(|percent| percent > 100) {
+            format!("Canary percentage must be at most 100 for {}", self.rule)
+        } else if self.percent.is_none() && self.node_tag.is_none() {
+            format!("Canary {} must set a percentage or a node tag", self.rule)
+        } else {
+            return Ok(());
+        };
+        Err(DataStoreError::ValidationError { message })
+    }
+
+    /// Returns true if the rules under canary apply to `node`
+    #[must_use]
+    pub fn includes(&self, node: &Node) -> bool {
+        let by_percent = self
+            .percent
+            .is_some_and(|percent| bucket(&self.rule, node) < u64::from(percent));
+        let by_tag = self.node_tag.as_deref().is_some_and(|tag| {
+            node.custom_data
+                .get("tags")
+                .and_then(serde_json::Value::as_array)
+                .is_some_and(|tags| tags.iter().any(|t| t.as_str() == Some(tag)))
+        });
+        by_percent || by_tag
+    }
+}
+
+/// Places a node in one of 100 buckets for a rule tag using FNV-1a, which
+/// unlike the standard library hasher is stable across releases
+fn bucket(rule: &str, node: &Node) -> u64 {
+    let hash = rule
+        .bytes()
+        .chain(node.id.as_bytes().iter().copied())
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    hash % 100
+}
+
+/// Returns the rules `node` evaluates: every rule not under canary, and
+/// canary rules whose canary includes the node
+#[must_use]
+pub fn rules_for_node(
+    canaries: &[CanaryDefinition],
+    rules: &[OrchestrationRule],
+    node: &Node,
+) -> Vec<OrchestrationRule> {
+    rules
+        .iter()
+        .filter(|rule| {
+            let mut matching = canaries
+                .iter()
+                .filter(|canary| rule.has_tag(&canary.rule))
+                .peekable();
+            matching.peek().is_none() || matching.any(|canary| canary.includes(node))
+        })
+        .cloned()
+        .collect()
+}
+
+/// Returns the policy rules `node` evaluates once canaries are applied
+pub(super) fn policies_for_node(
+    canaries: &[CanaryDefinition],
+    rules: &[OrchestrationRule],
+    node: &Node,
+) -> Vec<PolicyRule> {
+    rules_for_node(canaries, rules, node)
+        .into_iter()
+        .map(|rule| rule.rule)
+        .collect()
+}
+
+/// Loads canary definitions for evaluation; a datastore without settings
+/// support has none
+pub(super) async fn active_canaries(
+    datastore: &dyn DataStore,
+) -> PolicyResult<Vec<CanaryDefinition>> {
+    match list_canaries(datastore).await {
+        Ok(canaries) => Ok(canaries),
+        Err(DataStoreError::UnsupportedOperation { .. }) => Ok(Vec::new()),
+        Err(e) => Err(PolicyError::DataStoreError {
+            message: e.to_string(),
+        }),
+    }
+}
+
+/// Lists stored canary definitions ordered by rule tag
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or a definition is malformed.
+pub async fn list_canaries(datastore: &dyn DataStore) -> DataStoreResult<Vec<CanaryDefinition>> {
+    datastore
+        .list_settings(CANARY_NAMESPACE)
+        .await?
+        .into_iter()
+        .map(|(rule, value)| decode(&rule, value))
+        .collect()
+}
+
+/// Gets the canary definition for a rule tag
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or the definition is malformed.
+pub async fn get_canary(
+    datastore: &dyn DataStore,
+    rule: &str,
+) -> DataStoreResult<Option<CanaryDefinition>> {
+    datastore
+        .get_setting(CANARY_NAMESPACE, rule)
+        .await?
+        .map(|value| decode(rule, value))
+        .transpose()
+}
+
+/// Validates and stores a canary definition, replacing one for the same rule tag
+///
+/// # Errors
+/// Returns an error if the definition is invalid or the datastore write fails.
+pub async fn save_canary(
+    datastore: &dyn DataStore,
+    definition: &CanaryDefinition,
+) -> DataStoreResult<()> {
+    definition.validate()?;
+    let value = serde_json::to_value(definition).map_err(|e| DataStoreError::InternalError {
+        message: format!("Failed to serialize canary {}: {e}", definition.rule),
+    })?;
+    datastore
+        .put_setting(CANARY_NAMESPACE, &definition.rule, &value)
+        .await
+}
+
+/// Deletes a canary definition, enforcing its rules on every node
+///
+/// # Errors
+/// Returns `NotFound` if no canary exists for the rule tag.
+pub async fn delete_canary(datastore: &dyn DataStore, rule: &str) -> DataStoreResult<()> {
+    datastore.delete_setting(CANARY_NAMESPACE, rule).await
+}
+
+fn decode(rule: &str, value: serde_json::Value) -> DataStoreResult<CanaryDefinition> {
+    serde_json::from_value(value).map_err(|e| DataStoreError::InternalError {
+        message: format!("Stored canary {rule} is malformed: {e}"),
+    })
+}
+
+#[cfg(test)]
+mod tests;
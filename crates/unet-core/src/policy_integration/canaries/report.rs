@@ -0,0 +1,132 @@
+//! Canary versus fleet comparison
+
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::CanaryDefinition;
+use crate::datastore::DataStore;
+use crate::policy::{
+    OrchestrationRule, PolicyError, PolicyExecutionResult, PolicyResult, PolicyRule,
+};
+use crate::policy_integration::PolicyEvaluationEngine;
+
+/// Outcome counts for one group of nodes
+///
+/// Each node is counted once: as an error if any rule failed to evaluate,
+/// otherwise as failing if any rule found a compliance failure.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CanaryCohort {
+    /// Nodes evaluated
+    pub nodes: usize,
+    /// Nodes that satisfied every rule
+    pub passing: usize,
+    /// Nodes with a compliance failure
+    pub failing: usize,
+    /// Nodes where a rule failed to evaluate
+    pub errors: usize,
+    /// Share of nodes failing or in error, from 0 to 1
+    pub failure_rate: f64,
+}
+
+impl CanaryCohort {
+    /// Counts a node's results; returns true if the node did not pass
+    fn record(&mut self, results: &[PolicyExecutionResult]) -> bool {
+        self.nodes += 1;
+        let passed = if results.iter().any(PolicyExecutionResult::is_error) {
+            self.errors += 1;
+            false
+        } else if results
+            .iter()
+            .any(PolicyExecutionResult::is_compliance_failure)
+        {
+            self.failing += 1;
+            false
+        } else {
+            self.passing += 1;
+            true
+        };
+        if let (Some(failed), Some(nodes)) =
+            ((self.failing + self.errors).to_f64(), self.nodes.to_f64())
+        {
+            self.failure_rate = failed / nodes;
+        }
+        !passed
+    }
+}
+
+/// Results of rules under canary on the canary nodes and on the rest of the fleet
+///
+/// Fleet results come from a dry run and are not recorded, showing what
+/// enforcing the rules everywhere would report.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CanaryReport {
+    /// Rule tag of the canary
+    pub rule: String,
+    /// Rules carrying the tag
+    pub rules_evaluated: usize,
+    /// Nodes in the canary
+    pub canary: CanaryCohort,
+    /// Nodes outside the canary
+    pub fleet: CanaryCohort,
+    /// Fleet nodes that would fail or error once the rules are enforced
+    pub fleet_failures: Vec<Uuid>,
+}
+
+impl CanaryReport {
+    /// Creates an empty report for a canary rule tag
+    #[must_use]
+    pub fn new(rule: String, rules_evaluated: usize) -> Self {
+        Self {
+            rule,
+            rules_evaluated,
+            canary: CanaryCohort::default(),
+            fleet: CanaryCohort::default(),
+            fleet_failures: Vec::new(),
+        }
+    }
+
+    /// Evaluates the rules tagged with the canary's rule tag on every node
+    ///
+    /// # Errors
+    /// Returns `PolicyError` if nodes cannot be read or evaluation fails.
+    pub(crate) async fn evaluate(
+        canary: &CanaryDefinition,
+        rules: &[OrchestrationRule],
+        engine: &dyn PolicyEvaluationEngine,
+        datastore: &dyn DataStore,
+    ) -> PolicyResult<Self> {
+        let policies: Vec<PolicyRule> = rules
+            .iter()
+            .filter(|rule| rule.has_tag(&canary.rule))
+            .map(|rule| rule.rule.clone())
+            .collect();
+        let mut report = Self::new(canary.rule.clone(), policies.len());
+        if policies.is_empty() {
+            return Ok(report);
+        }
+
+        let nodes = datastore
+            .get_nodes_for_policy_evaluation()
+            .await
+            .map_err(|e| PolicyError::DataStoreError {
+                message: e.to_string(),
+            })?;
+        for node in &nodes {
+            let results = engine
+                .evaluate_node_policies(datastore, node, &policies)
+                .await?;
+            report.record(node.id, canary.includes(node), &results);
+        }
+        Ok(report)
+    }
+
+    /// Adds a node's results to the canary or fleet counts
+    pub fn record(&mut self, node_id: Uuid, in_canary: bool, results: &[PolicyExecutionResult]) {
+        if in_canary {
+            self.canary.record(results);
+        } else if self.fleet.record(results) {
+            self.fleet_failures.push(node_id);
+        }
+    }
+}
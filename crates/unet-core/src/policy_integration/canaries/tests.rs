@@ -0,0 +1,159 @@
+use super::*;
+use crate::datastore::MockDataStore;
+use crate::models::{DeviceRole, Vendor};
+use crate::policy::PolicyParser;
+use crate::policy_integration::DefaultPolicyEvaluationEngine;
+use serde_json::json;
+
+fn node(name: &str, vendor: Vendor, version: &str, tags: &[&str]) -> Node {
+    let mut node = Node::new(
+        name.to_string(),
+        "example.com".to_string(),
+        vendor,
+        DeviceRole::Router,
+    );
+    node.version = Some(version.to_string());
+    node.custom_data = json!({ "tags": tags });
+    node
+}
+
+fn tagged_rule(tag: &str) -> OrchestrationRule {
+    let rule = PolicyParser::parse_rule(
+        r#"WHEN node.vendor == "cisco" THEN ASSERT node.version IS "17.3""#,
+    )
+    .unwrap();
+    OrchestrationRule::new(rule).with_tag(tag.to_string())
+}
+
+fn canary(rule: &str) -> CanaryDefinition {
+    CanaryDefinition {
+        rule: rule.to_string(),
+        percent: None,
+        node_tag: Some("canary".to_string()),
+    }
+}
+
+#[test]
+fn test_definition_validation() {
+    assert!(canary("upgrade").validate().is_ok());
+    assert!(canary("a/b").validate().is_err());
+
+    let mut definition = canary("upgrade");
+    definition.node_tag = None;
+    assert!(definition.validate().is_err());
+    definition.percent = Some(101);
+    assert!(definition.validate().is_err());
+    definition.percent = Some(100);
+    assert!(definition.validate().is_ok());
+}
+
+#[test]
+fn test_includes_by_tag_and_stable_percentage() {
+    let tagged = node("r1", Vendor::Cisco, "17.3", &["canary"]);
+    let untagged = node("r2", Vendor::Cisco, "17.3", &[]);
+    assert!(canary("upgrade").includes(&tagged));
+    assert!(!canary("upgrade").includes(&untagged));
+
+    let by_percent = |percent| CanaryDefinition {
+        rule: "upgrade".to_string(),
+        percent: Some(percent),
+        node_tag: None,
+    };
+    assert!(!by_percent(0).includes(&untagged));
+    assert!(by_percent(100).includes(&untagged));
+
+    let nodes: Vec<Node> = (0..200)
+        .map(|i| node(&format!("n{i}"), Vendor::Cisco, "17.3", &[]))
+        .collect();
+    let at_twenty: Vec<bool> = nodes.iter().map(|n| by_percent(20).includes(n)).collect();
+    let selected = at_twenty.iter().filter(|included| **included).count();
+    assert!((10..=70).contains(&selected), "selected {selected} of 200");
+    // Raising the percentage keeps every node already in the canary
+    assert!(
+        nodes
+            .iter()
+            .zip(&at_twenty)
+            .all(|(n, included)| !included || by_percent(50).includes(n))
+    );
+}
+
+#[test]
+fn test_rules_for_node_skips_canary_rules_outside_canary() {
+    let rules = vec![tagged_rule("upgrade"), tagged_rule("naming")];
+    let canaries = vec![canary("upgrade")];
+
+    let inside = rules_for_node(
+        &canaries,
+        &rules,
+        &node("r1", Vendor::Cisco, "17.3", &["canary"]),
+    );
+    let outside = rules_for_node(&canaries, &rules, &node("r2", Vendor::Cisco, "17.3", &[]));
+
+    assert_eq!(inside.len(), 2);
+    assert_eq!(outside.len(), 1);
+    assert!(outside[0].has_tag("naming"));
+    assert_eq!(
+        rules_for_node(&[], &rules, &node("r3", Vendor::Cisco, "17.3", &[])).len(),
+        2
+    );
+}
+
+#[tokio::test]
+async fn test_save_and_list_canaries() {
+    let mut mock = MockDataStore::new();
+    mock.expect_put_setting()
+        .withf(|namespace, key, value| {
+            namespace == CANARY_NAMESPACE && key == "upgrade" && value["node_tag"] == "canary"
+        })
+        .times(1)
+        .returning(|_, _, _| Box::pin(async { Ok(()) }));
+    mock.expect_list_settings().returning(|_| {
+        Box::pin(async {
+            Ok(vec![(
+                "upgrade".to_string(),
+                json!({ "rule": "upgrade", "percent": 10 }),
+            )])
+        })
+    });
+
+    save_canary(&mock, &canary("upgrade")).await.unwrap();
+    let canaries = list_canaries(&mock).await.unwrap();
+
+    assert_eq!(canaries.len(), 1);
+    assert_eq!(canaries[0].percent, Some(10));
+    assert!(canaries[0].node_tag.is_none());
+}
+
+#[tokio::test]
+async fn test_report_compares_canary_with_fleet() {
+    let outdated = node("r2", Vendor::Cisco, "16.9", &[]);
+    let outdated_id = outdated.id;
+    let nodes = vec![
+        node("r1", Vendor::Cisco, "17.3", &["canary"]),
+        outdated,
+        node("s1", Vendor::Juniper, "22.4", &[]),
+    ];
+    let mut mock = MockDataStore::new();
+    mock.expect_get_nodes_for_policy_evaluation()
+        .returning(move || {
+            let nodes = nodes.clone();
+            Box::pin(async move { Ok(nodes) })
+        });
+
+    let report = CanaryReport::evaluate(
+        &canary("upgrade"),
+        &[tagged_rule("upgrade"), tagged_rule("naming")],
+        &DefaultPolicyEvaluationEngine::new(),
+        &mock,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(report.rules_evaluated, 1);
+    assert_eq!(report.canary.nodes, 1);
+    assert_eq!(report.canary.passing, 1);
+    assert_eq!(report.fleet.nodes, 2);
+    assert_eq!(report.fleet.failing, 1);
+    assert!((report.fleet.failure_rate - 0.5).abs() < f64::EPSILON);
+    assert_eq!(report.fleet_failures, vec![outdated_id]);
+}
@@ -0,0 +1,75 @@
+//! Recently changed fields in policy evaluation contexts
+
+use chrono::Utc;
+use serde_json::{Map, Value};
+use uuid::Uuid;
+
+use crate::change_log::last_changed;
+use crate::datastore::DataStore;
+use crate::policy::{EvaluationContext, PolicyError, PolicyResult, PolicyRule, rule_fields};
+
+/// Top-level context key holding how long ago each tracked field changed
+pub const CHANGED_FIELD: &str = "changed";
+
+/// Adds `changed`, holding the seconds since each `custom_data` field of
+/// the node last changed according to the change log, when a rule reads it
+///
+/// A rule such as `changed.custom_data.bgp.asn < 86400` then matches nodes
+/// whose ASN changed in the last day. Fields with no recorded change are
+/// absent, so they compare as `null`. Contexts for rules that never mention
+/// `changed` are left untouched and the change log is not read for them.
+///
+/// # Errors
+/// Returns `PolicyError` if the change log cannot be read.
+pub async fn add_change_ages<'a>(
+    context: &mut EvaluationContext,
+    datastore: &dyn DataStore,
+    node_id: Uuid,
+    rules: impl IntoIterator<Item = &'a PolicyRule>,
+) -> PolicyResult<()> {
+    let prefix = format!("{CHANGED_FIELD}.");
+    let reads_changes = rules.into_iter().any(|rule| {
+        rule_fields(rule)
+            .iter()
+            .any(|field| field == CHANGED_FIELD || field.starts_with(&prefix))
+    });
+    if !reads_changes {
+        return Ok(());
+    }
+    let Value::Object(data) = &mut context.node_data else {
+        return Ok(());
+    };
+    let changed =
+        last_changed(datastore, node_id)
+            .await
+            .map_err(|e| PolicyError::DataStoreError {
+                message: e.to_string(),
+            })?;
+
+    let now = Utc::now();
+    let mut ages = Map::new();
+    for (field, changed_at) in changed {
+        let seconds = (now - changed_at).num_seconds().max(0);
+        insert_path(&mut ages, &field, Value::from(seconds));
+    }
+    data.insert(CHANGED_FIELD.to_string(), Value::Object(ages));
+    Ok(())
+}
+
+/// Sets the dotted `path` under `map`, replacing values in the way
+fn insert_path(map: &mut Map<String, Value>, path: &str, value: Value) {
+    match path.split_once('.') {
+        None => {
+            map.insert(path.to_string(), value);
+        }
+        Some((key, rest)) => {
+            let child = map.entry(key).or_insert_with(|| Value::Object(Map::new()));
+            if !child.is_object() {
+                *child = Value::Object(Map::new());
+            }
+            if let Value::Object(child) = child {
+                insert_path(child, rest, value);
+            }
+        }
+    }
+}
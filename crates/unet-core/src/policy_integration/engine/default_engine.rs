@@ -1,6 +1,7 @@
 //! Default implementation of `PolicyEvaluationEngine`
 
 use async_trait::async_trait;
+use chrono::Utc;
 use serde_json::Value;
 use std::collections::HashMap;
 use uuid::Uuid;
@@ -8,13 +9,20 @@ use uuid::Uuid;
 use crate::datastore::{DataStore, DataStoreResult};
 use crate::models::Node;
 use crate::policy::{
-    EvaluationContext, PolicyEvaluator, PolicyExecutionContext, PolicyExecutionResult,
-    PolicyResult, PolicyRule,
+    EvaluationContext, EvaluationLimits, PolicyEvaluator, PolicyExecutionContext,
+    PolicyExecutionResult, PolicyResult, PolicyRule,
 };
+use crate::snmp::adjustments::reconcile_adjustments;
 
 use super::trait_definition::PolicyEvaluationEngine;
+use crate::policy_integration::exemptions::exempt_results;
+use crate::policy_integration::{add_change_ages, add_interface_errors, add_vlan_view};
 
 /// Default implementation of `PolicyEvaluationEngine`
+///
+/// Each rule runs within the default [`EvaluationLimits`], so a rule that is
+/// too slow or is given too large a node is reported as aborted and the
+/// remaining rules still run.
 pub struct DefaultPolicyEvaluationEngine;
 
 impl DefaultPolicyEvaluationEngine {
@@ -39,12 +47,21 @@ impl PolicyEvaluationEngine for DefaultPolicyEvaluationEngine {
         node: &Node,
         policies: &[PolicyRule],
     ) -> PolicyResult<Vec<PolicyExecutionResult>> {
-        let context = self.create_evaluation_context(node)?;
+        let mut context = self.create_evaluation_context(node)?;
+        add_vlan_view(&mut context, datastore, node.id, policies).await?;
+        add_change_ages(&mut context, datastore, node.id, policies).await?;
+        add_interface_errors(&mut context, datastore, node.id, policies).await?;
+        let limits = EvaluationLimits::default();
+        let exec_ctx = PolicyExecutionContext::new(&context, datastore, &node.id);
+        let oversized = PolicyEvaluator::payload_abort(&context, &node.id, &limits);
         let mut results = Vec::new();
 
         for policy in policies {
-            let exec_ctx = PolicyExecutionContext::new(&context, datastore, &node.id);
-            match PolicyEvaluator::execute_rule(policy, &exec_ctx).await {
+            if let Some(reason) = oversized {
+                results.push(PolicyExecutionResult::new_aborted(policy.clone(), reason));
+                continue;
+            }
+            match PolicyEvaluator::execute_rule_with_limits(policy, &exec_ctx, &limits).await {
                 Ok(result) => results.push(result),
                 Err(e) => {
                     // Log error but continue with other policies
@@ -63,6 +80,7 @@ impl PolicyEvaluationEngine for DefaultPolicyEvaluationEngine {
             }
         }
 
+        exempt_results(datastore, node, &mut results).await?;
         Ok(results)
     }
 
@@ -122,7 +140,7 @@ impl PolicyEvaluationEngine for DefaultPolicyEvaluationEngine {
             obj.insert("fqdn".to_string(), Value::String(node.fqdn.clone()));
             obj.insert(
                 "has_management_ip".to_string(),
-                Value::Bool(node.management_ip.is_some()),
+                Value::Bool(!node.management_addresses().is_empty()),
             );
             obj.insert(
                 "has_location".to_string(),
@@ -149,6 +167,7 @@ impl PolicyEvaluationEngine for DefaultPolicyEvaluationEngine {
                 .store_policy_result(node_id, result.rule_id().map_or("unknown", |v| v), result)
                 .await?;
         }
-        Ok(())
+        // Polling adjustments follow the stored outcome, reverting cleared rules
+        reconcile_adjustments(datastore, *node_id, results, Utc::now()).await
     }
 }
@@ -27,6 +27,8 @@ pub fn create_test_node() -> Node {
         purchase_date: None,
         warranty_expires: None,
         custom_data: serde_json::json!({"compliance": "pending"}),
+        management_ipv6: None,
+        management_zone: None,
     }
 }
 
@@ -33,7 +33,8 @@ pub trait PolicyEvaluationEngine: Send + Sync {
     /// Returns `PolicyError` if context creation fails due to invalid node data
     fn create_evaluation_context(&self, node: &Node) -> PolicyResult<EvaluationContext>;
 
-    /// Stores policy execution results
+    /// Stores policy execution results and applies or reverts the polling
+    /// adjustments they call for
     async fn store_results(
         &self,
         datastore: &dyn DataStore,
@@ -0,0 +1,243 @@
+//! Policy exemptions inherited through the location hierarchy
+//!
+//! An exemption waives one rule, named by its rule ID, for every node at a
+//! location and at any location below it, until the exemption expires. The
+//! engine applies exemptions after evaluating a node: a compliance failure of
+//! an exempted rule is kept in the results but marked with the exemption, so
+//! it is reported as exempted rather than failing. Results that pass or error
+//! are never changed.
+//!
+//! When the node's location and one of its ancestors both exempt a rule, the
+//! exemption of the nearest location applies. Expired exemptions are ignored
+//! and remain stored until deleted. Exemptions are stored through the
+//! `DataStore` settings API.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult};
+use crate::models::Node;
+use crate::policy::{AppliedExemption, PolicyError, PolicyExecutionResult, PolicyResult};
+
+/// Settings namespace holding exemptions keyed by location ID and rule ID
+const EXEMPTION_NAMESPACE: &str = "policy_exemptions";
+
+/// Stored exemption of a location, and the locations below it, from a rule
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyExemption {
+    /// Location whose nodes, including those of child locations, are exempt
+    pub location_id: Uuid,
+    /// ID of the exempted rule
+    pub rule: String,
+    /// Why the nodes are exempt
+    pub reason: String,
+    /// When the exemption stops applying
+    pub expires_at: DateTime<Utc>,
+}
+
+impl PolicyExemption {
+    /// Storage key; one exemption per location and rule
+    #[must_use]
+    pub fn key(&self) -> String {
+        exemption_key(self.location_id, &self.rule)
+    }
+
+    /// Checks that the exemption can be stored
+    ///
+    /// # Errors
+    /// Returns a validation error if the rule ID or reason is empty.
+    pub fn validate(&self) -> DataStoreResult<()> {
+        let message = if self.rule.trim().is_empty() {
+            format!("Invalid exemption rule ID: {:?}", self.rule)
+        } else if self.reason.trim().is_empty() {
+            format!("Exemption from {} must give a reason", self.rule)
+        } else {
+            return Ok(());
+        };
+        Err(DataStoreError::ValidationError { message })
+    }
+
+    /// Returns true if the exemption has not expired at `now`
+    #[must_use]
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at > now
+    }
+
+    /// The exemption as recorded on an exempted result
+    #[must_use]
+    pub fn applied(&self) -> AppliedExemption {
+        AppliedExemption {
+            location_id: self.location_id,
+            reason: self.reason.clone(),
+            expires_at: self.expires_at,
+        }
+    }
+}
+
+fn exemption_key(location_id: Uuid, rule: &str) -> String {
+    format!("{location_id}:{rule}")
+}
+
+/// Returns the exemption that applies to `rule` for a node whose location
+/// ancestry, nearest first, is `ancestry`
+#[must_use]
+pub fn exemption_for<'a>(
+    exemptions: &'a [PolicyExemption],
+    ancestry: &[Uuid],
+    rule: &str,
+    now: DateTime<Utc>,
+) -> Option<&'a PolicyExemption> {
+    ancestry.iter().find_map(|location_id| {
+        exemptions.iter().find(|exemption| {
+            exemption.location_id == *location_id
+                && exemption.rule == rule
+                && exemption.is_active(now)
+        })
+    })
+}
+
+/// Marks each compliance failure whose rule is exempted at `ancestry` with
+/// the applicable exemption
+pub fn apply_exemptions(
+    results: &mut [PolicyExecutionResult],
+    exemptions: &[PolicyExemption],
+    ancestry: &[Uuid],
+    now: DateTime<Utc>,
+) {
+    for result in results
+        .iter_mut()
+        .filter(|result| result.fails_compliance_check())
+    {
+        result.exemption = result
+            .rule_id()
+            .and_then(|rule| exemption_for(exemptions, ancestry, rule, now))
+            .map(PolicyExemption::applied);
+    }
+}
+
+/// Returns `location_id` followed by its ancestors, nearest first
+///
+/// # Errors
+/// Returns an error if a location cannot be read.
+pub async fn location_ancestry(
+    datastore: &dyn DataStore,
+    location_id: Uuid,
+) -> DataStoreResult<Vec<Uuid>> {
+    let mut ancestry = vec![location_id];
+    let mut seen = HashSet::from([location_id]);
+    let mut current = datastore.get_location(&location_id).await?;
+    while let Some(parent_id) = current.and_then(|location| location.parent_id) {
+        // A corrupt hierarchy must not loop forever
+        if !seen.insert(parent_id) {
+            break;
+        }
+        ancestry.push(parent_id);
+        current = datastore.get_location(&parent_id).await?;
+    }
+    Ok(ancestry)
+}
+
+/// Applies the exemptions inherited by `node` to its results; a node without
+/// a location or a datastore without settings support has none
+pub(super) async fn exempt_results(
+    datastore: &dyn DataStore,
+    node: &Node,
+    results: &mut [PolicyExecutionResult],
+) -> PolicyResult<()> {
+    let Some(location_id) = node.location_id else {
+        return Ok(());
+    };
+    if !results
+        .iter()
+        .any(PolicyExecutionResult::fails_compliance_check)
+    {
+        return Ok(());
+    }
+    let exemptions = match list_exemptions(datastore).await {
+        Ok(exemptions) if exemptions.is_empty() => return Ok(()),
+        Ok(exemptions) => exemptions,
+        Err(DataStoreError::UnsupportedOperation { .. }) => return Ok(()),
+        Err(e) => {
+            return Err(PolicyError::DataStoreError {
+                message: e.to_string(),
+            });
+        }
+    };
+    let ancestry = location_ancestry(datastore, location_id)
+        .await
+        .map_err(|e| PolicyError::DataStoreError {
+            message: e.to_string(),
+        })?;
+    apply_exemptions(results, &exemptions, &ancestry, Utc::now());
+    Ok(())
+}
+
+/// Lists stored exemptions, including expired ones, ordered by location ID
+/// and rule ID
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or an exemption is malformed.
+pub async fn list_exemptions(datastore: &dyn DataStore) -> DataStoreResult<Vec<PolicyExemption>> {
+    datastore
+        .list_settings(EXEMPTION_NAMESPACE)
+        .await?
+        .into_iter()
+        .map(|(key, value)| decode(&key, value))
+        .collect()
+}
+
+/// Validates and stores an exemption, replacing one for the same location
+/// and rule
+///
+/// # Errors
+/// Returns an error if the exemption is invalid or already expired, the
+/// location does not exist, or the datastore write fails.
+pub async fn save_exemption(
+    datastore: &dyn DataStore,
+    exemption: &PolicyExemption,
+) -> DataStoreResult<()> {
+    exemption.validate()?;
+    if !exemption.is_active(Utc::now()) {
+        return Err(DataStoreError::ValidationError {
+            message: format!(
+                "Exemption from {} expired at {}",
+                exemption.rule, exemption.expires_at
+            ),
+        });
+    }
+    datastore
+        .get_location_required(&exemption.location_id)
+        .await?;
+    let key = exemption.key();
+    let value = serde_json::to_value(exemption).map_err(|e| DataStoreError::InternalError {
+        message: format!("Failed to serialize exemption {key}: {e}"),
+    })?;
+    datastore
+        .put_setting(EXEMPTION_NAMESPACE, &key, &value)
+        .await
+}
+
+/// Deletes the exemption of a location from a rule
+///
+/// # Errors
+/// Returns `NotFound` if the location has no exemption from the rule.
+pub async fn delete_exemption(
+    datastore: &dyn DataStore,
+    location_id: Uuid,
+    rule: &str,
+) -> DataStoreResult<()> {
+    datastore
+        .delete_setting(EXEMPTION_NAMESPACE, &exemption_key(location_id, rule))
+        .await
+}
+
+fn decode(key: &str, value: serde_json::Value) -> DataStoreResult<PolicyExemption> {
+    serde_json::from_value(value).map_err(|e| DataStoreError::InternalError {
+        message: format!("Stored exemption {key} is malformed: {e}"),
+    })
+}
+
+#[cfg(test)]
+mod tests;
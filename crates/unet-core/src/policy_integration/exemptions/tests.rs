@@ -0,0 +1,163 @@
+use super::*;
+use crate::datastore::sqlite::SqliteStore;
+use crate::models::{DeviceRole, Location, Vendor};
+use crate::policy::{PolicyParser, PolicyRule};
+use crate::policy_integration::{DefaultPolicyEvaluationEngine, PolicyEvaluationEngine};
+use chrono::Duration;
+use sea_orm::{ConnectionTrait, Database, DatabaseBackend, Schema};
+
+async fn sqlite_store() -> SqliteStore {
+    let db = Database::connect("sqlite::memory:").await.unwrap();
+    let schema = Schema::new(DatabaseBackend::Sqlite);
+    for stmt in [
+        schema.create_table_from_entity(crate::entities::settings::Entity),
+        schema.create_table_from_entity(crate::entities::locations::Entity),
+    ] {
+        db.execute(db.get_database_backend().build(&stmt))
+            .await
+            .unwrap();
+    }
+    SqliteStore::from_connection(db)
+}
+
+/// Creates `dc1` and its child `row4`, returning their IDs
+async fn hierarchy(store: &SqliteStore) -> (Uuid, Uuid) {
+    let site = Location::new_root("dc1".to_string(), "datacenter".to_string());
+    let mut row = Location::new_child("row4".to_string(), "row".to_string(), &site.path);
+    row.parent_id = Some(site.id);
+    store.create_location(&site).await.unwrap();
+    store.create_location(&row).await.unwrap();
+    (site.id, row.id)
+}
+
+fn version_rule() -> PolicyRule {
+    let mut rule = PolicyParser::parse_rule(
+        r#"WHEN node.vendor == "cisco" THEN ASSERT node.version IS "17.3""#,
+    )
+    .unwrap();
+    rule.id = Some("version".to_string());
+    rule
+}
+
+fn outdated_node(location_id: Option<Uuid>) -> Node {
+    let mut node = Node::new(
+        "r1".to_string(),
+        "example.com".to_string(),
+        Vendor::Cisco,
+        DeviceRole::Router,
+    );
+    node.version = Some("16.9".to_string());
+    node.location_id = location_id;
+    node
+}
+
+fn exemption(location_id: Uuid, reason: &str, expires_in: Duration) -> PolicyExemption {
+    PolicyExemption {
+        location_id,
+        rule: "version".to_string(),
+        reason: reason.to_string(),
+        expires_at: Utc::now() + expires_in,
+    }
+}
+
+#[test]
+fn test_exemption_validation() {
+    let location_id = Uuid::new_v4();
+    assert!(
+        exemption(location_id, "vendor bug", Duration::days(1))
+            .validate()
+            .is_ok()
+    );
+    assert!(
+        exemption(location_id, " ", Duration::days(1))
+            .validate()
+            .is_err()
+    );
+
+    let mut unnamed = exemption(location_id, "vendor bug", Duration::days(1));
+    unnamed.rule = String::new();
+    assert!(unnamed.validate().is_err());
+}
+
+#[test]
+fn test_nearest_active_exemption_wins() {
+    let (site, row) = (Uuid::new_v4(), Uuid::new_v4());
+    let ancestry = [row, site];
+    let now = Utc::now();
+    let exemptions = vec![
+        exemption(site, "site freeze", Duration::days(7)),
+        exemption(row, "row migration", Duration::days(1)),
+    ];
+
+    let applied = exemption_for(&exemptions, &ancestry, "version", now).unwrap();
+    assert_eq!(applied.reason, "row migration");
+    assert!(exemption_for(&exemptions, &ancestry, "naming", now).is_none());
+
+    // Once the row's exemption expires the site's applies again
+    let later = now + Duration::days(2);
+    let applied = exemption_for(&exemptions, &ancestry, "version", later).unwrap();
+    assert_eq!(applied.reason, "site freeze");
+    assert!(exemption_for(&exemptions, &ancestry, "version", now + Duration::days(8)).is_none());
+}
+
+#[tokio::test]
+async fn test_save_rejects_expired_exemption_and_unknown_location() {
+    let store = sqlite_store().await;
+    let (site, _) = hierarchy(&store).await;
+
+    let expired = exemption(site, "vendor bug", -Duration::hours(1));
+    assert!(matches!(
+        save_exemption(&store, &expired).await,
+        Err(DataStoreError::ValidationError { .. })
+    ));
+    let unknown = exemption(Uuid::new_v4(), "vendor bug", Duration::days(1));
+    assert!(matches!(
+        save_exemption(&store, &unknown).await,
+        Err(DataStoreError::NotFound { .. })
+    ));
+    assert!(list_exemptions(&store).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_child_location_inherits_exemption() {
+    let store = sqlite_store().await;
+    let (site, row) = hierarchy(&store).await;
+    assert_eq!(
+        location_ancestry(&store, row).await.unwrap(),
+        vec![row, site]
+    );
+
+    let engine = DefaultPolicyEvaluationEngine::new();
+    let node = outdated_node(Some(row));
+    let results = engine
+        .evaluate_node_policies(&store, &node, &[version_rule()])
+        .await
+        .unwrap();
+    assert!(results[0].is_compliance_failure());
+
+    save_exemption(&store, &exemption(site, "site freeze", Duration::days(7)))
+        .await
+        .unwrap();
+    let results = engine
+        .evaluate_node_policies(&store, &node, &[version_rule()])
+        .await
+        .unwrap();
+    assert!(results[0].fails_compliance_check());
+    assert!(!results[0].is_compliance_failure());
+    let applied = results[0].exemption.as_ref().unwrap();
+    assert_eq!(applied.location_id, site);
+    assert_eq!(applied.reason, "site freeze");
+
+    // Nodes outside the hierarchy are not exempt
+    let results = engine
+        .evaluate_node_policies(&store, &outdated_node(None), &[version_rule()])
+        .await
+        .unwrap();
+    assert!(results[0].is_compliance_failure());
+
+    delete_exemption(&store, site, "version").await.unwrap();
+    assert!(matches!(
+        delete_exemption(&store, site, "version").await,
+        Err(DataStoreError::NotFound { .. })
+    ));
+}
@@ -0,0 +1,204 @@
+//! Interface error rates in policy evaluation contexts
+
+use serde_json::{Map, Value};
+use uuid::Uuid;
+
+use crate::datastore::{DataStore, DataStoreError};
+use crate::models::derived::InterfaceStatus;
+use crate::policy::{EvaluationContext, PolicyError, PolicyResult, PolicyRule, rule_fields};
+
+/// Top-level context key holding the node's interface error rates
+const INTERFACES_FIELD: &str = "interfaces";
+
+/// Adds `interfaces`, holding the node's highest interface error rates over
+/// the last polling interval, when a rule reads it
+///
+/// The rates are `input_errors_per_second`, `output_errors_per_second`,
+/// `crc_errors_per_second`, and `error_percent` (errored packets as a
+/// percentage of all packets, input or output), each the highest among the
+/// node's interfaces. `erroring` lists the names of the interfaces that saw
+/// any errors. A rule such as `interfaces.crc_errors_per_second > 1` then
+/// matches nodes with a failing link. Rates not yet computed, such as
+/// before the second poll, are absent and compare as `null`.
+///
+/// Contexts for rules that never mention `interfaces` are left untouched
+/// and no interface data is loaded for them.
+///
+/// # Errors
+/// Returns `PolicyError` if the interfaces cannot be loaded.
+pub async fn add_interface_errors<'a>(
+    context: &mut EvaluationContext,
+    datastore: &dyn DataStore,
+    node_id: Uuid,
+    rules: impl IntoIterator<Item = &'a PolicyRule>,
+) -> PolicyResult<()> {
+    let prefix = format!("{INTERFACES_FIELD}.");
+    let reads_interfaces = rules.into_iter().any(|rule| {
+        rule_fields(rule)
+            .iter()
+            .any(|field| field == INTERFACES_FIELD || field.starts_with(&prefix))
+    });
+    if !reads_interfaces {
+        return Ok(());
+    }
+    let Value::Object(data) = &mut context.node_data else {
+        return Ok(());
+    };
+    let interfaces = match datastore.get_node_interfaces(&node_id).await {
+        Ok(interfaces) => interfaces,
+        Err(DataStoreError::UnsupportedOperation { .. }) => Vec::new(),
+        Err(e) => {
+            return Err(PolicyError::DataStoreError {
+                message: e.to_string(),
+            });
+        }
+    };
+    data.insert(INTERFACES_FIELD.to_string(), error_summary(&interfaces));
+    Ok(())
+}
+
+/// Highest error rates across the interfaces, and the names of those with
+/// errors in the last interval
+fn error_summary(interfaces: &[InterfaceStatus]) -> Value {
+    let mut summary = Map::new();
+    let mut erroring = Vec::new();
+    let mut keep_highest = |key: &str, value: Option<f64>| {
+        let Some(value) = value else {
+            return;
+        };
+        let highest = summary.entry(key).or_insert_with(|| Value::from(value));
+        if highest.as_f64().is_some_and(|highest| value > highest) {
+            *highest = Value::from(value);
+        }
+    };
+
+    for interface in interfaces {
+        let input = interface.input_stats.error_rate;
+        let output = interface.output_stats.error_rate;
+        keep_highest(
+            "input_errors_per_second",
+            input.map(|rate| rate.errors_per_second),
+        );
+        keep_highest(
+            "output_errors_per_second",
+            output.map(|rate| rate.errors_per_second),
+        );
+        keep_highest(
+            "crc_errors_per_second",
+            input.and_then(|rate| rate.crc_per_second),
+        );
+        keep_highest("error_percent", input.and_then(|rate| rate.error_percent));
+        keep_highest("error_percent", output.and_then(|rate| rate.error_percent));
+
+        let has_errors = input.into_iter().chain(output).any(|rate| {
+            rate.errors_per_second > 0.0 || rate.crc_per_second.is_some_and(|crc| crc > 0.0)
+        });
+        if has_errors {
+            erroring.push(Value::from(interface.name.clone()));
+        }
+    }
+
+    summary.insert("erroring".to_string(), Value::Array(erroring));
+    Value::Object(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datastore::MockDataStore;
+    use crate::models::derived::{
+        ErrorRate, InterfaceAdminStatus, InterfaceOperStatus, InterfaceStats,
+    };
+    use crate::policy::{Action, ComparisonOperator, Condition, FieldRef, Value as PolicyValue};
+    use serde_json::json;
+
+    fn interface(name: &str, input: Option<ErrorRate>) -> InterfaceStatus {
+        InterfaceStatus {
+            index: 1,
+            name: name.to_string(),
+            interface_type: 6,
+            mtu: None,
+            speed: None,
+            physical_address: None,
+            admin_status: InterfaceAdminStatus::Up,
+            oper_status: InterfaceOperStatus::Up,
+            last_change: None,
+            input_stats: InterfaceStats {
+                error_rate: input,
+                ..InterfaceStats::default()
+            },
+            output_stats: InterfaceStats::default(),
+        }
+    }
+
+    fn rule(path: &str) -> PolicyRule {
+        PolicyRule {
+            id: Some("crc".to_string()),
+            condition: Condition::Comparison {
+                field: FieldRef {
+                    path: path.split('.').map(str::to_string).collect(),
+                },
+                operator: ComparisonOperator::GreaterThan,
+                value: PolicyValue::Number(1.0),
+            },
+            action: Action::Assert {
+                field: FieldRef {
+                    path: vec!["node".to_string(), "name".to_string()],
+                },
+                expected: PolicyValue::Null,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_interface_errors_hold_highest_rates() {
+        let mut mock = MockDataStore::new();
+        mock.expect_get_node_interfaces().times(1).returning(|_| {
+            let rate = |errors: f64, crc: f64| ErrorRate {
+                errors_per_second: errors,
+                error_percent: Some(errors / 10.0),
+                crc_per_second: Some(crc),
+            };
+            Box::pin(async move {
+                Ok(vec![
+                    interface("et-0/0/1", Some(rate(4.0, 3.0))),
+                    interface("et-0/0/2", Some(rate(0.0, 0.0))),
+                    interface("et-0/0/3", None),
+                ])
+            })
+        });
+
+        let mut context = EvaluationContext::new(json!({ "node": {} }));
+        add_interface_errors(
+            &mut context,
+            &mock,
+            Uuid::new_v4(),
+            [&rule("interfaces.crc_errors_per_second")],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            context.get_field("interfaces"),
+            Some(&json!({
+                "input_errors_per_second": 4.0,
+                "crc_errors_per_second": 3.0,
+                "error_percent": 0.4,
+                "erroring": ["et-0/0/1"],
+            }))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_interfaces_not_loaded_for_other_rules() {
+        let mut mock = MockDataStore::new();
+        mock.expect_get_node_interfaces().never();
+
+        let mut context = EvaluationContext::new(json!({ "node": {} }));
+        add_interface_errors(&mut context, &mock, Uuid::new_v4(), [&rule("node.mtu")])
+            .await
+            .unwrap();
+
+        assert_eq!(context.get_field("interfaces"), None);
+    }
+}
@@ -3,11 +3,20 @@
 //! This module provides integration between the policy engine and the data layer,
 //! enabling policy evaluation against live network data and storage of policy results.
 
+pub use changes::add_change_ages;
 pub use engine::{DefaultPolicyEvaluationEngine, PolicyEvaluationEngine};
+pub use interfaces::add_interface_errors;
 pub use service::PolicyService;
+pub use vlans::add_vlan_view;
 
+pub mod batches;
+pub mod canaries;
+mod changes;
 mod engine;
+pub mod exemptions;
+mod interfaces;
 mod service;
+mod vlans;
 
 #[cfg(test)]
 mod service_tests;
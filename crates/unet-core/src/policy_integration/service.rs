@@ -3,16 +3,19 @@
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 use std::time::SystemTime;
+use tokio::sync::Mutex;
 use uuid::Uuid;
 
 use crate::config::GitConfig;
 use crate::datastore::{DataStore, DataStoreResult};
 use crate::models::Node;
 use crate::policy::{
-    OrchestrationConfig, PolicyExecutionResult, PolicyLoader, PolicyOrchestrator, PolicyResult,
-    PolicyRule,
+    OrchestrationConfig, OrchestrationRule, PolicyError, PolicyExecutionResult, PolicyLoader,
+    PolicyOrchestrator, PolicyResult, PolicyRule,
 };
 
+use super::batches::{BatchDefinition, BatchRuns};
+use super::canaries::{CanaryDefinition, CanaryReport, active_canaries, policies_for_node};
 use super::engine::{DefaultPolicyEvaluationEngine, PolicyEvaluationEngine};
 
 /// Policy service that orchestrates policy loading, evaluation, and result storage
@@ -20,7 +23,8 @@ use super::engine::{DefaultPolicyEvaluationEngine, PolicyEvaluationEngine};
 pub struct PolicyService {
     loader: PolicyLoader,
     engine: Arc<dyn PolicyEvaluationEngine>,
-    orchestrator: PolicyOrchestrator,
+    orchestrator: Arc<Mutex<PolicyOrchestrator>>,
+    batch_runs: BatchRuns,
     runtime_status: Arc<PolicyRuntimeStatus>,
 }
 
@@ -46,12 +50,15 @@ impl PolicyService {
         let runtime_status = Arc::new(PolicyRuntimeStatus::new(git_config.sync_interval));
         let loader = PolicyLoader::new(git_config);
         let engine = Arc::new(DefaultPolicyEvaluationEngine::new());
-        let orchestrator = PolicyOrchestrator::new(OrchestrationConfig::default());
+        let orchestrator = Arc::new(Mutex::new(PolicyOrchestrator::new(
+            OrchestrationConfig::default(),
+        )));
 
         Self {
             loader,
             engine,
             orchestrator,
+            batch_runs: BatchRuns::default(),
             runtime_status,
         }
     }
@@ -70,13 +77,16 @@ impl PolicyService {
         };
         let loader = PolicyLoader::new(git_config).with_local_dir(policies_directory);
         let engine = Arc::new(DefaultPolicyEvaluationEngine::new());
-        let orchestrator = PolicyOrchestrator::new(OrchestrationConfig::default());
+        let orchestrator = Arc::new(Mutex::new(PolicyOrchestrator::new(
+            OrchestrationConfig::default(),
+        )));
         let runtime_status = Arc::new(PolicyRuntimeStatus::new(300));
 
         Self {
             loader,
             engine,
             orchestrator,
+            batch_runs: BatchRuns::default(),
             runtime_status,
         }
     }
@@ -85,12 +95,15 @@ impl PolicyService {
     pub fn with_engine(git_config: GitConfig, engine: Arc<dyn PolicyEvaluationEngine>) -> Self {
         let runtime_status = Arc::new(PolicyRuntimeStatus::new(git_config.sync_interval));
         let loader = PolicyLoader::new(git_config);
-        let orchestrator = PolicyOrchestrator::new(OrchestrationConfig::default());
+        let orchestrator = Arc::new(Mutex::new(PolicyOrchestrator::new(
+            OrchestrationConfig::default(),
+        )));
 
         Self {
             loader,
             engine,
             orchestrator,
+            batch_runs: BatchRuns::default(),
             runtime_status,
         }
     }
@@ -125,6 +138,27 @@ impl PolicyService {
             .collect())
     }
 
+    /// Loads policies as orchestration rules tagged with their file stem and rule ID
+    ///
+    /// # Errors
+    ///
+    /// Returns `PolicyError` if policies cannot be loaded or parsed
+    pub async fn load_orchestration_rules_async(&mut self) -> PolicyResult<Vec<OrchestrationRule>> {
+        let result = self.loader.load_policies_async().await?;
+        let mut rules = Vec::new();
+        for file in result.loaded {
+            let stem = file
+                .path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned());
+            for rule in file.rules {
+                let tags = stem.iter().chain(rule.id.as_ref()).cloned().collect();
+                rules.push(OrchestrationRule::new(rule).with_tags(tags));
+            }
+        }
+        Ok(rules)
+    }
+
     /// Evaluates policies against a single node
     ///
     /// # Errors
@@ -135,7 +169,9 @@ impl PolicyService {
         datastore: &dyn DataStore,
         node: &Node,
     ) -> PolicyResult<Vec<PolicyExecutionResult>> {
-        let policies = self.load_policies_async().await?;
+        let rules = self.load_orchestration_rules_async().await?;
+        let canaries = active_canaries(datastore).await?;
+        let policies = policies_for_node(&canaries, &rules, node);
         self.engine
             .evaluate_node_policies(datastore, node, &policies)
             .await
@@ -150,10 +186,53 @@ impl PolicyService {
         &mut self,
         datastore: &dyn DataStore,
     ) -> PolicyResult<HashMap<Uuid, Vec<PolicyExecutionResult>>> {
-        let policies = self.load_policies_async().await?;
-        self.engine
-            .evaluate_all_policies(datastore, &policies)
+        let rules = self.load_orchestration_rules_async().await?;
+        let canaries = active_canaries(datastore).await?;
+        if canaries.is_empty() {
+            let policies: Vec<PolicyRule> = rules.into_iter().map(|rule| rule.rule).collect();
+            return self
+                .engine
+                .evaluate_all_policies(datastore, &policies)
+                .await;
+        }
+
+        let nodes = datastore
+            .get_nodes_for_policy_evaluation()
             .await
+            .map_err(|e| PolicyError::DataStoreError {
+                message: e.to_string(),
+            })?;
+        let mut all_results = HashMap::new();
+        for node in nodes {
+            let policies = policies_for_node(&canaries, &rules, &node);
+            let results = self
+                .engine
+                .evaluate_node_policies(datastore, &node, &policies)
+                .await
+                .unwrap_or_else(|e| {
+                    vec![PolicyExecutionResult::new_error_with_id(
+                        Some("evaluation".to_string()),
+                        e.to_string(),
+                    )]
+                });
+            all_results.insert(node.id, results);
+        }
+        Ok(all_results)
+    }
+
+    /// Compares the rules tagged for a canary on its nodes with a dry run on
+    /// the rest of the fleet; no results are stored
+    ///
+    /// # Errors
+    ///
+    /// Returns `PolicyError` if nodes cannot be read or evaluation fails
+    pub async fn canary_report(
+        &self,
+        canary: &CanaryDefinition,
+        rules: &[OrchestrationRule],
+        datastore: &dyn DataStore,
+    ) -> PolicyResult<CanaryReport> {
+        CanaryReport::evaluate(canary, rules, self.engine.as_ref(), datastore).await
     }
 
     /// Evaluates policies with orchestration (priority, batching, etc.)
@@ -170,11 +249,12 @@ impl PolicyService {
         self.evaluate_all_nodes(datastore).await
     }
 
-    /// Stores evaluation results for a node
+    /// Stores evaluation results for a node, applying or reverting the polling
+    /// adjustments they call for
     ///
     /// # Errors
     ///
-    /// Returns `DataStoreError` if results cannot be stored
+    /// Returns `DataStoreError` if results or polling adjustments cannot be stored
     pub async fn store_results(
         &self,
         datastore: &dyn DataStore,
@@ -190,12 +270,38 @@ impl PolicyService {
         &self.loader
     }
 
-    /// Gets the policy orchestrator (for configuration, etc.)
+    /// Gets the policy orchestrator shared by all clones of this service
     #[must_use]
-    pub const fn orchestrator(&self) -> &PolicyOrchestrator {
+    pub const fn orchestrator(&self) -> &Arc<Mutex<PolicyOrchestrator>> {
         &self.orchestrator
     }
 
+    /// Gets the registry of batch runs shared by all clones of this service
+    #[must_use]
+    pub const fn batch_runs(&self) -> &BatchRuns {
+        &self.batch_runs
+    }
+
+    /// Executes a previously started batch run through the shared orchestrator
+    pub async fn run_batch(
+        &self,
+        run_id: Uuid,
+        definition: &BatchDefinition,
+        rules: &[OrchestrationRule],
+        datastore: &dyn DataStore,
+    ) {
+        self.batch_runs
+            .execute(
+                run_id,
+                definition,
+                rules,
+                &self.orchestrator,
+                self.engine.as_ref(),
+                datastore,
+            )
+            .await;
+    }
+
     /// Records the completion of a policy evaluation cycle.
     pub fn record_evaluation_run(&self) {
         let mut last_evaluation = self
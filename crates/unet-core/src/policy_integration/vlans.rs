@@ -0,0 +1,46 @@
+//! VLAN data in policy evaluation contexts
+
+use uuid::Uuid;
+
+use crate::datastore::DataStore;
+use crate::policy::{EvaluationContext, PolicyError, PolicyResult, PolicyRule, rule_fields};
+use crate::vlan::node_vlan_view;
+
+/// Top-level context key holding the node's VLAN view
+const VLANS_FIELD: &str = "vlans";
+
+/// Adds the node's VLAN view (see [`node_vlan_view`]) as `vlans` when a rule
+/// reads it, so rules such as `vlans.consistent == false` can be asserted on
+///
+/// Contexts for rules that never mention `vlans` are left untouched and no
+/// VLAN data is loaded for them.
+///
+/// # Errors
+/// Returns `PolicyError` if the VLAN view cannot be loaded.
+pub async fn add_vlan_view<'a>(
+    context: &mut EvaluationContext,
+    datastore: &dyn DataStore,
+    node_id: Uuid,
+    rules: impl IntoIterator<Item = &'a PolicyRule>,
+) -> PolicyResult<()> {
+    let prefix = format!("{VLANS_FIELD}.");
+    let reads_vlans = rules.into_iter().any(|rule| {
+        rule_fields(rule)
+            .iter()
+            .any(|field| field == VLANS_FIELD || field.starts_with(&prefix))
+    });
+    if !reads_vlans {
+        return Ok(());
+    }
+    let serde_json::Value::Object(data) = &mut context.node_data else {
+        return Ok(());
+    };
+    let view =
+        node_vlan_view(datastore, node_id)
+            .await
+            .map_err(|e| PolicyError::DataStoreError {
+                message: e.to_string(),
+            })?;
+    data.insert(VLANS_FIELD.to_string(), view);
+    Ok(())
+}
@@ -0,0 +1,318 @@
+//! Where each node attribute came from
+//!
+//! Writes that set node attributes record, for every attribute they change,
+//! the [`Source`] of the value and when it was set: an operator through the
+//! CLI or API, an import file, a `NetBox` sync, or SNMP discovery. Node
+//! fields are tracked by name and `custom_data` by the dotted path of each
+//! leaf, as in the change log (`custom_data.bgp.asn`). The record of each
+//! node is kept through the `DataStore` settings API keyed by node ID.
+//!
+//! When several automations write the same nodes, the record shows which one
+//! set a value last. A sync job calls [`keep_manual`] before writing a node
+//! so that fields an operator set by hand are not overwritten.
+
+use crate::change_log::{CUSTOM_DATA_FIELD, custom_data_changes};
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult};
+use crate::models::Node;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Settings namespace holding the record of each node keyed by node ID
+const PROVENANCE_NAMESPACE: &str = "node_provenance";
+
+/// Node fields whose source is not tracked: the ID never changes and the
+/// FQDN is derived from the name and domain
+const UNTRACKED_FIELDS: [&str; 2] = ["id", "fqdn"];
+
+/// What set a node attribute
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Source {
+    /// An operator, through the CLI or API
+    Manual,
+    /// An import file
+    Import,
+    /// A `NetBox` sync
+    Netbox,
+    /// SNMP discovery of the device
+    SnmpDiscovery,
+}
+
+impl Source {
+    /// The source's name, as shown and stored
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Manual => "manual",
+            Self::Import => "import",
+            Self::Netbox => "netbox",
+            Self::SnmpDiscovery => "snmp_discovery",
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl FromStr for Source {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('-', "_").as_str() {
+            "manual" => Ok(Self::Manual),
+            "import" => Ok(Self::Import),
+            "netbox" => Ok(Self::Netbox),
+            "snmp_discovery" => Ok(Self::SnmpDiscovery),
+            _ => Err(format!("Invalid provenance source: {s}")),
+        }
+    }
+}
+
+/// Who last set one attribute, and when
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldSource {
+    /// What set the value
+    pub source: Source,
+    /// The import file, polled address, or API the value came through
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// When the value was set
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Sources of a node's attributes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeProvenance {
+    /// Node the attributes belong to
+    pub node_id: Uuid,
+    /// Source of each attribute set since tracking began, by field name or
+    /// `custom_data` path
+    pub fields: BTreeMap<String, FieldSource>,
+}
+
+impl NodeProvenance {
+    /// Creates an empty record for a node
+    #[must_use]
+    pub const fn new(node_id: Uuid) -> Self {
+        Self {
+            node_id,
+            fields: BTreeMap::new(),
+        }
+    }
+
+    /// Fields whose value was last set by hand
+    #[must_use]
+    pub fn manual_fields(&self) -> Vec<&str> {
+        self.fields
+            .iter()
+            .filter(|(_, field)| field.source == Source::Manual)
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    fn set(&mut self, field: String, source: FieldSource) {
+        // A leaf replacing an object, or the reverse, makes the old paths stale
+        self.fields
+            .retain(|name, _| !is_within(name, &field) && !is_within(&field, name));
+        self.fields.insert(field, source);
+    }
+}
+
+/// Whether `path` is `field` or lies below it
+fn is_within(path: &str, field: &str) -> bool {
+    path.strip_prefix(field)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+}
+
+/// Attributes that differ between two versions of a node, sorted
+///
+/// `before` is `None` for a created node, making every attribute with a
+/// value a change.
+#[must_use]
+pub fn changed_fields(before: Option<&Node>, after: &Node) -> Vec<String> {
+    let before = before.map_or(Value::Null, to_value);
+    let after = to_value(after);
+    let mut fields: Vec<String> = after
+        .as_object()
+        .into_iter()
+        .flat_map(Map::iter)
+        .filter(|(name, _)| {
+            !UNTRACKED_FIELDS.contains(&name.as_str()) && name.as_str() != CUSTOM_DATA_FIELD
+        })
+        .filter(|(name, value)| match before.get(name.as_str()) {
+            Some(old) => old != *value,
+            None => !value.is_null(),
+        })
+        .map(|(name, _)| name.clone())
+        .collect();
+    let custom_data = custom_data_changes(&before, &after);
+    // An object replaced by a scalar changes every leaf below it too; the
+    // object's own path stands for them
+    fields.extend(
+        custom_data
+            .iter()
+            .filter(|path| {
+                !custom_data
+                    .iter()
+                    .any(|other| other != *path && is_within(path, other))
+            })
+            .cloned(),
+    );
+    fields.sort();
+    fields
+}
+
+fn to_value(node: &Node) -> Value {
+    serde_json::to_value(node).unwrap_or(Value::Null)
+}
+
+fn parse(node_id: Uuid, value: Value) -> DataStoreResult<NodeProvenance> {
+    serde_json::from_value(value).map_err(|e| DataStoreError::InternalError {
+        message: format!("stored provenance of node {node_id}: {e}"),
+    })
+}
+
+/// Records `source` as the origin of every attribute the write from
+/// `before` to `after` changed, returning the fields recorded
+///
+/// Datastores without settings support record nothing.
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or written or the
+/// stored record is malformed.
+pub async fn record(
+    datastore: &dyn DataStore,
+    before: Option<&Node>,
+    after: &Node,
+    source: Source,
+    detail: Option<&str>,
+    now: DateTime<Utc>,
+) -> DataStoreResult<Vec<String>> {
+    let fields = changed_fields(before, after);
+    if fields.is_empty() {
+        return Ok(fields);
+    }
+    let key = after.id.to_string();
+    let mut provenance = match datastore.get_setting(PROVENANCE_NAMESPACE, &key).await {
+        Ok(Some(value)) => parse(after.id, value)?,
+        Ok(None) => NodeProvenance::new(after.id),
+        Err(DataStoreError::UnsupportedOperation { .. }) => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    for field in &fields {
+        provenance.set(
+            field.clone(),
+            FieldSource {
+                source,
+                detail: detail.map(ToString::to_string),
+                updated_at: now,
+            },
+        );
+    }
+    let value = serde_json::to_value(&provenance).map_err(|e| DataStoreError::InternalError {
+        message: format!("provenance of node {}: {e}", after.id),
+    })?;
+    datastore
+        .put_setting(PROVENANCE_NAMESPACE, &key, &value)
+        .await?;
+    Ok(fields)
+}
+
+/// Gets the sources of a node's attributes
+///
+/// Datastores without settings support have no records.
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or the stored record is
+/// malformed.
+pub async fn get_provenance(
+    datastore: &dyn DataStore,
+    node_id: Uuid,
+) -> DataStoreResult<Option<NodeProvenance>> {
+    match datastore
+        .get_setting(PROVENANCE_NAMESPACE, &node_id.to_string())
+        .await
+    {
+        Ok(stored) => stored.map(|value| parse(node_id, value)).transpose(),
+        Err(DataStoreError::UnsupportedOperation { .. }) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Puts back, in `incoming`, the value `current` has for every field last
+/// set by hand, returning the fields kept
+///
+/// Only updates the node in memory; the caller saves it.
+///
+/// # Errors
+/// Returns a validation error if a kept value no longer fits the node.
+pub fn keep_manual(
+    provenance: &NodeProvenance,
+    current: &Node,
+    incoming: &mut Node,
+) -> DataStoreResult<Vec<String>> {
+    let current = to_value(current);
+    let mut merged = to_value(incoming);
+    let mut kept = Vec::new();
+    for field in provenance.manual_fields() {
+        let pointer = format!("/{}", field.replace('.', "/"));
+        let value = current.pointer(&pointer);
+        if merged.pointer(&pointer) != value {
+            set_path(&mut merged, field, value.cloned());
+            kept.push(field.to_string());
+        }
+    }
+    if !kept.is_empty() {
+        *incoming =
+            serde_json::from_value(merged).map_err(|e| DataStoreError::ValidationError {
+                message: format!(
+                    "Keeping manually set {} of {}: {e}",
+                    kept.join(", "),
+                    incoming.name
+                ),
+            })?;
+    }
+    Ok(kept)
+}
+
+/// Sets, or with `None` removes, the value at a dotted path, creating the
+/// objects above it
+fn set_path(target: &mut Value, path: &str, value: Option<Value>) {
+    let (parents, last) = path
+        .rsplit_once('.')
+        .map_or((None, path), |(parents, last)| (Some(parents), last));
+    let mut object = target;
+    for segment in parents.into_iter().flat_map(|parents| parents.split('.')) {
+        if !object.is_object() {
+            *object = Value::Object(Map::new());
+        }
+        let Value::Object(map) = object else {
+            return;
+        };
+        object = map.entry(segment).or_insert(Value::Null);
+    }
+    if !object.is_object() {
+        *object = Value::Object(Map::new());
+    }
+    if let Value::Object(map) = object {
+        match value {
+            Some(value) => {
+                map.insert(last.to_string(), value);
+            }
+            None => {
+                map.remove(last);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,134 @@
+use super::*;
+use crate::datastore::sqlite::tests::setup::settings_store;
+use crate::models::{DeviceRole, Vendor};
+use chrono::TimeDelta;
+use serde_json::json;
+
+fn node() -> Node {
+    let mut node = Node::new(
+        "edge-01".to_string(),
+        "example.com".to_string(),
+        Vendor::Juniper,
+        DeviceRole::Router,
+    );
+    node.custom_data = json!({ "bgp": { "asn": 65001 }, "rack": "R1" });
+    node
+}
+
+#[test]
+fn test_changed_fields_tracks_fields_and_custom_data_leaves() {
+    let before = node();
+    let created = changed_fields(None, &before);
+    assert!(created.contains(&"name".to_string()));
+    assert!(created.contains(&"custom_data.bgp.asn".to_string()));
+    assert!(!created.contains(&"fqdn".to_string()));
+    assert!(
+        !created.contains(&"location_id".to_string()),
+        "unset fields have no source"
+    );
+
+    let mut after = before.clone();
+    after.model = "MX204".to_string();
+    after.custom_data["bgp"]["asn"] = json!(65002);
+    assert_eq!(
+        changed_fields(Some(&before), &after),
+        ["custom_data.bgp.asn", "model"]
+    );
+    assert!(changed_fields(Some(&before), &before).is_empty());
+}
+
+#[tokio::test]
+async fn test_record_keeps_latest_source_per_field() {
+    let store = settings_store().await;
+    let discovered = node();
+    let now = Utc::now();
+    record(
+        &store,
+        None,
+        &discovered,
+        Source::SnmpDiscovery,
+        Some("192.0.2.10"),
+        now,
+    )
+    .await
+    .unwrap();
+
+    let mut edited = discovered.clone();
+    edited.model = "MX204".to_string();
+    edited.custom_data["bgp"] = json!(null);
+    let later = now + TimeDelta::minutes(5);
+    let recorded = record(
+        &store,
+        Some(&discovered),
+        &edited,
+        Source::Manual,
+        None,
+        later,
+    )
+    .await
+    .unwrap();
+    assert_eq!(recorded, ["custom_data.bgp", "model"]);
+
+    let provenance = get_provenance(&store, discovered.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(provenance.fields["name"].source, Source::SnmpDiscovery);
+    assert_eq!(
+        provenance.fields["name"].detail.as_deref(),
+        Some("192.0.2.10")
+    );
+    assert_eq!(provenance.fields["model"].source, Source::Manual);
+    assert_eq!(provenance.fields["model"].updated_at, later);
+    assert!(
+        !provenance.fields.contains_key("custom_data.bgp.asn"),
+        "paths below a replaced value are dropped"
+    );
+    assert_eq!(provenance.manual_fields(), ["custom_data.bgp", "model"]);
+    assert!(
+        get_provenance(&store, Uuid::new_v4())
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+fn test_keep_manual_restores_hand_set_values() {
+    let current = node();
+    let mut provenance = NodeProvenance::new(current.id);
+    let set_by = |source| FieldSource {
+        source,
+        detail: None,
+        updated_at: Utc::now(),
+    };
+    provenance.set("model".to_string(), set_by(Source::Manual));
+    provenance.set("custom_data.bgp.asn".to_string(), set_by(Source::Manual));
+    provenance.set("custom_data.rack".to_string(), set_by(Source::Netbox));
+
+    let mut incoming = current.clone();
+    incoming.model = "MX480".to_string();
+    incoming.custom_data = json!({ "rack": "R7" });
+    let kept = keep_manual(&provenance, &current, &mut incoming).unwrap();
+
+    assert_eq!(kept, ["custom_data.bgp.asn", "model"]);
+    assert_eq!(incoming.model, current.model);
+    assert_eq!(
+        incoming.custom_data,
+        json!({ "bgp": { "asn": 65001 }, "rack": "R7" })
+    );
+}
+
+#[test]
+fn test_source_round_trips_through_strings() {
+    for source in [
+        Source::Manual,
+        Source::Import,
+        Source::Netbox,
+        Source::SnmpDiscovery,
+    ] {
+        assert_eq!(source.as_str().parse::<Source>(), Ok(source));
+    }
+    assert_eq!("snmp-discovery".parse(), Ok(Source::SnmpDiscovery));
+    assert!("ldap".parse::<Source>().is_err());
+}
@@ -0,0 +1,16 @@
+//! Fleet-wide reports
+//!
+//! Reports read the whole inventory and summarize it along one concern, with
+//! totals for the fleet and a breakdown per site. They are computed on
+//! demand and not stored, except that custom reports keep their latest
+//! rendering.
+//!
+//! - [`firmware`] - OS versions against the declared target-version matrix
+//! - [`capacity`] - Link capacity and utilization between pairs of sites
+//! - [`forecast`] - When each link's utilization will reach 70 and 90 percent
+//! - [`custom`] - User-authored templates rendered to HTML or Markdown
+
+pub mod capacity;
+pub mod custom;
+pub mod firmware;
+pub mod forecast;
@@ -0,0 +1,454 @@
+//! Site-to-site capacity planning
+//!
+//! Links between sites are grouped by the pair of sites they join. A node's
+//! site is the nearest location of type `site` at or above its own location,
+//! or its own location if none is; links inside one site, or with an end
+//! that has no location, are left out.
+//!
+//! The utilization of each link is sampled from the polled interface rates
+//! of both of its ends, taking the busiest direction, and kept as hourly
+//! rollups through the `DataStore` settings API. The report combines the
+//! hourly means of a site pair's links, weighted by their bandwidth, into
+//! one utilization per hour and summarizes the window: aggregate capacity,
+//! 95th percentile utilization, the headroom left above it, and the trend
+//! as a least-squares slope, with when that line reaches 70 and 90 percent
+//! (see [`forecast`](super::forecast)). Links without a bandwidth count
+//! toward the pair but not its capacity.
+
+use super::forecast::{hours_between, linear_fit, project_linear, to_float};
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult, QueryOptions};
+use crate::models::derived::NodeStatus;
+use crate::models::{Link, Location, Node};
+use chrono::{DateTime, DurationRound, TimeDelta, Utc};
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use uuid::Uuid;
+
+/// Settings namespace holding hourly utilization rollups keyed by link ID
+const UTILIZATION_NAMESPACE: &str = "link_utilization";
+
+/// Hourly rollups kept per link, 90 days
+pub const MAX_UTILIZATION_HOURS: usize = 24 * 90;
+
+/// Days of utilization a report covers unless told otherwise
+pub const DEFAULT_WINDOW_DAYS: u32 = 30;
+
+/// Location type marking a site
+const SITE_TYPE: &str = "site";
+
+/// Percentile reported as a pair's planning utilization
+const PERCENTILE: f64 = 95.0;
+
+/// Utilization of a link over one hour
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct UtilizationRollup {
+    /// Start of the hour
+    pub hour: DateTime<Utc>,
+    /// Samples taken in the hour
+    pub samples: u32,
+    /// Mean utilization, in percent
+    pub mean: f64,
+    /// Highest sampled utilization, in percent
+    pub peak: f64,
+}
+
+/// Capacity of the links between two sites
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SitePairCapacity {
+    /// One site, the first of the two by path
+    pub site_a: String,
+    /// The other site
+    pub site_z: String,
+    /// Links between the sites
+    pub links: usize,
+    /// Links without a bandwidth, left out of the capacity
+    pub unrated_links: usize,
+    /// Sum of the link bandwidths, in bits per second
+    pub capacity_bps: u64,
+    /// Hours in the window with utilization samples
+    pub hours: usize,
+    /// 95th percentile of the hourly utilization, in percent
+    pub p95_utilization: Option<f64>,
+    /// Highest hourly peak of any link, in percent
+    pub peak_utilization: Option<f64>,
+    /// Capacity left above the 95th percentile, in bits per second
+    pub headroom_bps: Option<u64>,
+    /// Change of the hourly utilization, in percentage points per week
+    pub trend_per_week: Option<f64>,
+    /// When the trend reaches 70 percent utilization; now if it has
+    pub reaches_70_at: Option<DateTime<Utc>>,
+    /// When the trend reaches 90 percent utilization; now if it has
+    pub reaches_90_at: Option<DateTime<Utc>>,
+}
+
+/// Capacity of every site pair
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CapacityReport {
+    /// Start of the window the utilization is read from
+    pub since: DateTime<Utc>,
+    /// Site pairs, busiest first; pairs without samples last, by site
+    pub pairs: Vec<SitePairCapacity>,
+}
+
+impl CapacityReport {
+    /// The report as CSV, one row per site pair with a header row
+    ///
+    /// Values without data are left empty.
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "site_a,site_z,links,unrated_links,capacity_bps,hours,p95_utilization,\
+             peak_utilization,headroom_bps,trend_per_week,reaches_70_at,reaches_90_at\n",
+        );
+        let number = |value: Option<f64>| value.map(|value| format!("{value:.2}"));
+        let time = |at: Option<DateTime<Utc>>| at.map(|at| at.to_rfc3339()).unwrap_or_default();
+        for pair in &self.pairs {
+            let fields = [
+                csv_field(&pair.site_a),
+                csv_field(&pair.site_z),
+                pair.links.to_string(),
+                pair.unrated_links.to_string(),
+                pair.capacity_bps.to_string(),
+                pair.hours.to_string(),
+                number(pair.p95_utilization).unwrap_or_default(),
+                number(pair.peak_utilization).unwrap_or_default(),
+                pair.headroom_bps
+                    .map(|bps| bps.to_string())
+                    .unwrap_or_default(),
+                number(pair.trend_per_week).unwrap_or_default(),
+                time(pair.reaches_70_at),
+                time(pair.reaches_90_at),
+            ];
+            let _ = writeln!(csv, "{}", fields.join(","));
+        }
+        csv
+    }
+}
+
+/// Quotes a CSV field holding a comma, quote, or line break
+pub(super) fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Utilization of a link from its ends' polled interface rates: the higher
+/// of input and output on either end, in percent
+///
+/// Returns `None` if neither end reports a utilization for the link's
+/// interface.
+#[must_use]
+pub fn link_utilization(link: &Link, statuses: &HashMap<Uuid, &NodeStatus>) -> Option<f64> {
+    let ends = std::iter::once((link.source_node_id, Some(&link.node_a_interface))).chain(
+        link.dest_node_id
+            .map(|node_id| (node_id, link.node_z_interface.as_ref())),
+    );
+    ends.filter_map(|(node_id, interface)| Some((statuses.get(&node_id)?, interface?)))
+        .flat_map(|(status, interface)| {
+            status
+                .interfaces
+                .iter()
+                .filter(move |candidate| candidate.name == *interface)
+        })
+        .flat_map(|interface| [&interface.input_stats, &interface.output_stats])
+        .filter_map(|stats| stats.rate.and_then(|rate| rate.utilization))
+        .reduce(f64::max)
+}
+
+fn parse(link_id: &str, value: serde_json::Value) -> DataStoreResult<Vec<UtilizationRollup>> {
+    serde_json::from_value(value).map_err(|e| DataStoreError::InternalError {
+        message: format!("stored utilization of link {link_id}: {e}"),
+    })
+}
+
+/// Gets the hourly utilization rollups of a link, oldest first
+///
+/// Datastores without settings support have no rollups.
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or the stored rollups
+/// are malformed.
+pub async fn utilization_history(
+    datastore: &dyn DataStore,
+    link_id: Uuid,
+) -> DataStoreResult<Vec<UtilizationRollup>> {
+    let key = link_id.to_string();
+    match datastore.get_setting(UTILIZATION_NAMESPACE, &key).await {
+        Ok(stored) => Ok(stored
+            .map(|value| parse(&key, value))
+            .transpose()?
+            .unwrap_or_default()),
+        Err(DataStoreError::UnsupportedOperation { .. }) => Ok(Vec::new()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Gets the hourly utilization rollups of every link that has them
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or stored rollups are
+/// malformed.
+pub async fn list_utilization(
+    datastore: &dyn DataStore,
+) -> DataStoreResult<HashMap<Uuid, Vec<UtilizationRollup>>> {
+    let stored = match datastore.list_settings(UTILIZATION_NAMESPACE).await {
+        Ok(stored) => stored,
+        Err(DataStoreError::UnsupportedOperation { .. }) => return Ok(HashMap::new()),
+        Err(e) => return Err(e),
+    };
+    stored
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let link_id = key.parse().ok()?;
+            Some(parse(&key, value).map(|rollups| (link_id, rollups)))
+        })
+        .collect()
+}
+
+/// Adds a utilization sample of a link to the rollup of its hour, keeping
+/// the newest [`MAX_UTILIZATION_HOURS`] rollups
+///
+/// # Errors
+/// Returns an error if the stored rollups cannot be read or the datastore
+/// write fails.
+pub async fn record_utilization(
+    datastore: &dyn DataStore,
+    link_id: Uuid,
+    recorded_at: DateTime<Utc>,
+    utilization: f64,
+) -> DataStoreResult<()> {
+    let hour = recorded_at
+        .duration_trunc(TimeDelta::hours(1))
+        .unwrap_or(recorded_at);
+    let mut rollups = utilization_history(datastore, link_id).await?;
+    match rollups.iter_mut().find(|rollup| rollup.hour == hour) {
+        Some(rollup) => {
+            let samples = f64::from(rollup.samples);
+            rollup.mean = rollup.mean.mul_add(samples, utilization) / (samples + 1.0);
+            rollup.peak = rollup.peak.max(utilization);
+            rollup.samples += 1;
+        }
+        None => {
+            rollups.push(UtilizationRollup {
+                hour,
+                samples: 1,
+                mean: utilization,
+                peak: utilization,
+            });
+            rollups.sort_by_key(|rollup| rollup.hour);
+        }
+    }
+    let excess = rollups.len().saturating_sub(MAX_UTILIZATION_HOURS);
+    rollups.drain(..excess);
+
+    let value = serde_json::to_value(&rollups).map_err(|e| DataStoreError::InternalError {
+        message: format!("utilization of link {link_id}: {e}"),
+    })?;
+    datastore
+        .put_setting(UTILIZATION_NAMESPACE, &link_id.to_string(), &value)
+        .await
+}
+
+/// Samples the utilization of `links` from their ends' latest statuses
+///
+/// Returns the number of links sampled; links neither end of which reports
+/// a utilization are skipped.
+///
+/// # Errors
+/// Returns an error if statuses cannot be read or a sample cannot be stored.
+pub async fn sample_utilization(
+    datastore: &dyn DataStore,
+    links: &[Link],
+    recorded_at: DateTime<Utc>,
+) -> DataStoreResult<usize> {
+    let mut node_ids: Vec<Uuid> = links
+        .iter()
+        .flat_map(|link| std::iter::once(link.source_node_id).chain(link.dest_node_id))
+        .collect();
+    node_ids.sort_unstable();
+    node_ids.dedup();
+    let statuses = datastore.get_node_statuses(&node_ids).await?;
+    let statuses: HashMap<Uuid, &NodeStatus> = statuses
+        .iter()
+        .map(|status| (status.node_id, status))
+        .collect();
+
+    let mut sampled = 0;
+    for link in links {
+        if let Some(utilization) = link_utilization(link, &statuses) {
+            record_utilization(datastore, link.id, recorded_at, utilization).await?;
+            sampled += 1;
+        }
+    }
+    Ok(sampled)
+}
+
+/// Path of the site a location belongs to
+fn site_of<'a>(location: &'a Location, locations: &'a [Location]) -> &'a str {
+    std::iter::once(location)
+        .chain(location.get_ancestors(locations))
+        .find(|candidate| candidate.location_type.eq_ignore_ascii_case(SITE_TYPE))
+        .unwrap_or(location)
+        .path
+        .as_str()
+}
+
+/// Nearest-rank percentile of unsorted values
+fn percentile(values: &[f64], percent: f64) -> Option<f64> {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let rank = ((percent / 100.0) * to_float(sorted.len()))
+        .ceil()
+        .max(1.0)
+        .to_usize()?;
+    sorted.get(rank - 1).copied()
+}
+
+/// Summarizes the links of one site pair from their rollups since `since`,
+/// projecting the trend from `now`
+fn summarize(
+    (site_a, site_z): (String, String),
+    links: &[&Link],
+    histories: &HashMap<Uuid, Vec<UtilizationRollup>>,
+    since: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> SitePairCapacity {
+    let capacity_bps: u64 = links.iter().filter_map(|link| link.bandwidth).sum();
+    // Per hour: traffic in bits per second, and capacity of the links sampled
+    let mut hourly: BTreeMap<DateTime<Utc>, (f64, f64)> = BTreeMap::new();
+    let mut peak_utilization: Option<f64> = None;
+    for link in links {
+        let Some(bandwidth) = link.bandwidth else {
+            continue;
+        };
+        let rollups = histories.get(&link.id).map_or(&[][..], Vec::as_slice);
+        for rollup in rollups.iter().filter(|rollup| rollup.hour >= since) {
+            let (traffic, sampled) = hourly.entry(rollup.hour).or_default();
+            // Bandwidths fit an f64 closely enough for capacity planning
+            *traffic += rollup.mean / 100.0 * to_float(bandwidth);
+            *sampled += to_float(bandwidth);
+            peak_utilization =
+                Some(peak_utilization.map_or(rollup.peak, |peak| peak.max(rollup.peak)));
+        }
+    }
+    let utilization: Vec<(f64, f64)> = hourly
+        .iter()
+        .filter(|(_, (_, sampled))| *sampled > 0.0)
+        .map(|(hour, (traffic, sampled))| (hours_between(since, *hour), traffic / sampled * 100.0))
+        .collect();
+    let values: Vec<f64> = utilization.iter().map(|(_, value)| *value).collect();
+    let p95_utilization = percentile(&values, PERCENTILE);
+    let projection = project_linear(&utilization, since, now);
+
+    SitePairCapacity {
+        site_a,
+        site_z,
+        links: links.len(),
+        unrated_links: links.iter().filter(|link| link.bandwidth.is_none()).count(),
+        capacity_bps,
+        hours: values.len(),
+        p95_utilization,
+        peak_utilization,
+        headroom_bps: p95_utilization.and_then(|p95| {
+            (to_float(capacity_bps) * (100.0 - p95).max(0.0) / 100.0)
+                .round()
+                .to_u64()
+        }),
+        trend_per_week: linear_fit(&utilization).map(|(_, per_hour)| per_hour * 24.0 * 7.0),
+        reaches_70_at: projection.and_then(|projection| projection.reaches_70_at),
+        reaches_90_at: projection.and_then(|projection| projection.reaches_90_at),
+    }
+}
+
+/// Builds the report for `links` between the sites of `nodes`, reading
+/// each link's utilization since `since` from `histories` and projecting
+/// trends from `now`
+#[must_use]
+pub fn build_report(
+    nodes: &[Node],
+    locations: &[Location],
+    links: &[Link],
+    histories: &HashMap<Uuid, Vec<UtilizationRollup>>,
+    since: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> CapacityReport {
+    let by_id: HashMap<Uuid, &Location> = locations
+        .iter()
+        .map(|location| (location.id, location))
+        .collect();
+    let sites: HashMap<Uuid, &str> = nodes
+        .iter()
+        .filter_map(|node| {
+            let location = by_id.get(&node.location_id?)?;
+            Some((node.id, site_of(location, locations)))
+        })
+        .collect();
+
+    let mut pairs: BTreeMap<(String, String), Vec<&Link>> = BTreeMap::new();
+    for link in links {
+        let Some(dest) = link.dest_node_id else {
+            continue;
+        };
+        let (Some(a), Some(z)) = (sites.get(&link.source_node_id), sites.get(&dest)) else {
+            continue;
+        };
+        if a == z {
+            continue;
+        }
+        let key = if a < z { (a, z) } else { (z, a) };
+        pairs
+            .entry(((*key.0).to_string(), (*key.1).to_string()))
+            .or_default()
+            .push(link);
+    }
+
+    let mut pairs: Vec<SitePairCapacity> = pairs
+        .into_iter()
+        .map(|(sites, links)| summarize(sites, &links, histories, since, now))
+        .collect();
+    pairs.sort_by(|a, b| match (a.p95_utilization, b.p95_utilization) {
+        (Some(a), Some(b)) => b.total_cmp(&a),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+    CapacityReport { since, pairs }
+}
+
+/// Start of a window of `days` days ending at `now`
+#[must_use]
+pub fn window_start(now: DateTime<Utc>, days: u32) -> DateTime<Utc> {
+    now - TimeDelta::days(i64::from(days))
+}
+
+/// Reports the capacity of every site pair over the last `days` days
+///
+/// # Errors
+/// Returns an error if nodes, locations, links, or utilization rollups
+/// cannot be read.
+pub async fn capacity_report(
+    datastore: &dyn DataStore,
+    days: u32,
+    now: DateTime<Utc>,
+) -> DataStoreResult<CapacityReport> {
+    let options = QueryOptions::default();
+    let nodes = datastore.list_nodes(&options).await?.items;
+    let locations = datastore.list_locations(&options).await?.items;
+    let links = datastore.list_links(&options).await?.items;
+    let histories = list_utilization(datastore).await?;
+    Ok(build_report(
+        &nodes,
+        &locations,
+        &links,
+        &histories,
+        window_start(now, days),
+        now,
+    ))
+}
+
+#[cfg(test)]
+mod tests;
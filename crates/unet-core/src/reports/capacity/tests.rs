@@ -0,0 +1,169 @@
+use super::*;
+use crate::datastore::sqlite::tests::setup::settings_store;
+use crate::models::{DeviceRole, Vendor};
+
+fn at(hours: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp(1_790_000_000 - 1_790_000_000 % 3600, 0).unwrap()
+        + TimeDelta::hours(hours)
+}
+
+fn node(name: &str, location: &Location) -> Node {
+    let mut node = Node::new(
+        name.to_string(),
+        "example.com".to_string(),
+        Vendor::Arista,
+        DeviceRole::Router,
+    );
+    node.location_id = Some(location.id);
+    node
+}
+
+fn link(a: &Node, z: &Node, bandwidth: Option<u64>) -> Link {
+    let mut link = Link::new(
+        format!("{}-{}", a.name, z.name),
+        a.id,
+        "Ethernet1".to_string(),
+        z.id,
+        "Ethernet1".to_string(),
+    );
+    link.bandwidth = bandwidth;
+    link
+}
+
+fn rollups(means: &[f64]) -> Vec<UtilizationRollup> {
+    means
+        .iter()
+        .zip(0..)
+        .map(|(mean, hour)| UtilizationRollup {
+            hour: at(hour),
+            samples: 1,
+            mean: *mean,
+            peak: mean + 5.0,
+        })
+        .collect()
+}
+
+#[test]
+fn test_build_report_groups_links_by_site_pair() {
+    let sfo = Location::new_root("sfo".to_string(), "site".to_string());
+    let mut rack = Location::new_child("rack1".to_string(), "rack".to_string(), &sfo.path);
+    rack.parent_id = Some(sfo.id);
+    let nyc = Location::new_root("nyc".to_string(), "Site".to_string());
+    let locations = [sfo.clone(), rack.clone(), nyc.clone()];
+
+    let (sfo_1, sfo_2, nyc_1) = (
+        node("sfo-1", &rack),
+        node("sfo-2", &sfo),
+        node("nyc-1", &nyc),
+    );
+    let fast = link(&nyc_1, &sfo_1, Some(10_000_000_000));
+    let slow = link(&sfo_2, &nyc_1, Some(1_000_000_000));
+    let unrated = link(&sfo_2, &nyc_1, None);
+    let local = link(&sfo_1, &sfo_2, Some(10_000_000_000));
+    let histories = HashMap::from([
+        (fast.id, rollups(&[10.0, 20.0, 30.0, 40.0])),
+        (slow.id, rollups(&[50.0, 50.0, 50.0, 50.0])),
+        (local.id, rollups(&[90.0])),
+    ]);
+
+    let report = build_report(
+        &[sfo_1, sfo_2, nyc_1],
+        &locations,
+        &[fast, slow, unrated, local],
+        &histories,
+        at(0),
+        at(3),
+    );
+
+    assert_eq!(report.pairs.len(), 1);
+    let pair = &report.pairs[0];
+    assert_eq!((pair.site_a.as_str(), pair.site_z.as_str()), ("nyc", "sfo"));
+    assert_eq!((pair.links, pair.unrated_links), (3, 1));
+    assert_eq!(pair.capacity_bps, 11_000_000_000);
+    assert_eq!(pair.hours, 4);
+    // Hourly means weighted by bandwidth: (10 * mean + 50) / 11
+    let p95 = pair.p95_utilization.unwrap();
+    assert!((p95 - 450.0 / 11.0).abs() < 1e-9);
+    assert_eq!(pair.peak_utilization, Some(55.0));
+    assert_eq!(pair.headroom_bps, Some(6_500_000_000));
+    let trend = pair.trend_per_week.unwrap();
+    assert!((trend - 100.0 / 11.0 * 168.0).abs() < 1e-6);
+    // 450 / 11 percent at hour 3, rising 100 / 11 points an hour
+    assert_eq!(pair.reaches_70_at, Some(at(3) + TimeDelta::seconds(11_520)));
+    assert_eq!(pair.reaches_90_at, Some(at(3) + TimeDelta::seconds(19_440)));
+}
+
+#[test]
+fn test_build_report_skips_samples_before_window() {
+    let sfo = Location::new_root("sfo".to_string(), "site".to_string());
+    let nyc = Location::new_root("nyc".to_string(), "site".to_string());
+    let (a, z) = (node("sfo-1", &sfo), node("nyc-1", &nyc));
+    let quiet = link(&a, &z, Some(1_000_000_000));
+    let histories = HashMap::from([(quiet.id, rollups(&[70.0, 80.0]))]);
+
+    let report = build_report(&[a, z], &[sfo, nyc], &[quiet], &histories, at(2), at(2));
+
+    let pair = &report.pairs[0];
+    assert_eq!(pair.hours, 0);
+    assert_eq!(pair.p95_utilization, None);
+    assert_eq!(pair.headroom_bps, None);
+    assert_eq!(pair.trend_per_week, None);
+    assert_eq!(pair.reaches_70_at, None);
+    assert_eq!(
+        report.to_csv(),
+        "site_a,site_z,links,unrated_links,capacity_bps,hours,p95_utilization,\
+         peak_utilization,headroom_bps,trend_per_week,reaches_70_at,reaches_90_at\n\
+         nyc,sfo,1,0,1000000000,0,,,,,,\n"
+    );
+}
+
+#[test]
+fn test_percentile_uses_nearest_rank() {
+    let values: Vec<f64> = (1..=20).map(f64::from).collect();
+    assert_eq!(percentile(&values, 95.0), Some(19.0));
+    assert_eq!(percentile(&[3.0], 95.0), Some(3.0));
+    assert_eq!(percentile(&[], 95.0), None);
+    assert_eq!(csv_field("dc, east"), "\"dc, east\"");
+}
+
+#[tokio::test]
+async fn test_record_utilization_rolls_up_by_hour() {
+    let store = settings_store().await;
+    let link_id = Uuid::new_v4();
+    for (minutes, utilization) in [(5, 20.0), (35, 40.0), (65, 10.0)] {
+        record_utilization(
+            &store,
+            link_id,
+            at(0) + TimeDelta::minutes(minutes),
+            utilization,
+        )
+        .await
+        .unwrap();
+    }
+
+    let history = utilization_history(&store, link_id).await.unwrap();
+    assert_eq!(
+        history,
+        [
+            UtilizationRollup {
+                hour: at(0),
+                samples: 2,
+                mean: 30.0,
+                peak: 40.0,
+            },
+            UtilizationRollup {
+                hour: at(1),
+                samples: 1,
+                mean: 10.0,
+                peak: 10.0,
+            },
+        ]
+    );
+    assert_eq!(list_utilization(&store).await.unwrap()[&link_id], history);
+    assert!(
+        utilization_history(&store, Uuid::new_v4())
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
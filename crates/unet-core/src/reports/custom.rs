@@ -0,0 +1,423 @@
+//! Custom reports rendered from user-authored templates
+//!
+//! A report template is `MiniJinja` text producing HTML or Markdown, so a
+//! report for a particular audience needs no code change. Templates do not
+//! reach the datastore; they see a fixed set of datasets:
+//!
+//! - `nodes`, `links`, `locations` - the inventory
+//! - `firmware` - the [`firmware`](super::firmware) report
+//! - `compliance` - failed policy rules per node from its latest evaluation
+//! - `conformance` - the latest golden configuration score per node
+//! - `metrics` - reachability and CPU, memory, and load per polled node
+//! - `hardware` - the latest [`hardware`](crate::hardware) inventory per
+//!   node, for asset and end-of-life reports
+//! - `capacity` - the [`capacity`](super::capacity) report over its default
+//!   window
+//! - `report` - the template's name and description
+//! - `generated_at` - when the report was rendered, in RFC 3339
+//!
+//! Only the datasets a template names are loaded. Templates cannot include or
+//! import other templates, and HTML reports escape every value they print.
+//!
+//! Reports are rendered on demand, or on a schedule when their template has
+//! an interval. The latest rendering of each report is kept.
+
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult, QueryOptions};
+use crate::reports::capacity::{self, DEFAULT_WINDOW_DAYS, list_utilization, window_start};
+use crate::reports::firmware::{build_report, list_targets};
+use chrono::{DateTime, TimeDelta, Utc};
+use minijinja::{AutoEscape, Environment};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+
+mod datasets;
+
+pub use datasets::{NodeCompliance, NodeConformance, NodeHardware, NodeMetrics};
+use datasets::{compliance, conformance, hardware, metrics};
+
+/// Settings namespace holding report templates keyed by name
+const TEMPLATES_NAMESPACE: &str = "report_templates";
+/// Settings namespace holding the latest rendering of each report by name
+const RENDERS_NAMESPACE: &str = "report_renders";
+
+/// Datasets a template can name, in the order they are loaded
+pub const DATASETS: [&str; 9] = [
+    "nodes",
+    "links",
+    "locations",
+    "firmware",
+    "compliance",
+    "conformance",
+    "metrics",
+    "hardware",
+    "capacity",
+];
+
+/// Output format of a report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    /// HTML, with every printed value escaped
+    Html,
+    /// Markdown, printed as is
+    Markdown,
+}
+
+impl Display for ReportFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Html => write!(f, "html"),
+            Self::Markdown => write!(f, "markdown"),
+        }
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "html" => Ok(Self::Html),
+            "markdown" | "md" => Ok(Self::Markdown),
+            _ => Err(format!("Invalid report format: {s}")),
+        }
+    }
+}
+
+/// A user-authored report
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportTemplate {
+    /// Report name, made of letters, digits, `-`, and `_`
+    pub name: String,
+    /// What the report is for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Output format
+    pub format: ReportFormat,
+    /// `MiniJinja` template text
+    pub template: String,
+    /// Minutes between scheduled renderings; rendered only on demand if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interval_minutes: Option<u32>,
+}
+
+impl ReportTemplate {
+    /// Creates a report template, checking that the template compiles
+    ///
+    /// # Errors
+    /// Returns a validation error if the name is empty or has other
+    /// characters than letters, digits, `-`, and `_`, the interval is zero, or
+    /// the template has a syntax error.
+    pub fn new(
+        name: &str,
+        format: ReportFormat,
+        template: String,
+        description: Option<String>,
+        interval_minutes: Option<u32>,
+    ) -> DataStoreResult<Self> {
+        let name = name.trim();
+        let message = if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            Some(format!(
+                "Invalid report name {name:?}: use letters, digits, '-', and '_'"
+            ))
+        } else if interval_minutes == Some(0) {
+            Some(format!(
+                "Report {name} interval must be at least one minute"
+            ))
+        } else {
+            None
+        };
+        if let Some(message) = message {
+            return Err(DataStoreError::ValidationError { message });
+        }
+        let report = Self {
+            name: name.to_string(),
+            description: description.filter(|d| !d.trim().is_empty()),
+            format,
+            template,
+            interval_minutes,
+        };
+        report.datasets()?;
+        Ok(report)
+    }
+
+    /// Datasets the template names, in [`DATASETS`] order
+    ///
+    /// # Errors
+    /// Returns a validation error if the template has a syntax error.
+    pub fn datasets(&self) -> DataStoreResult<Vec<&'static str>> {
+        let env = environment(self.format);
+        let compiled = env
+            .template_from_str(&self.template)
+            .map_err(|e| self.render_error(&e))?;
+        let names = compiled.undeclared_variables(false);
+        Ok(DATASETS
+            .into_iter()
+            .filter(|dataset| names.contains(*dataset))
+            .collect())
+    }
+
+    /// Whether a scheduled rendering is due, given the latest one
+    #[must_use]
+    pub fn is_due(&self, latest: Option<DateTime<Utc>>, now: DateTime<Utc>) -> bool {
+        self.interval_minutes.is_some_and(|minutes| {
+            latest.is_none_or(|at| now - at >= TimeDelta::minutes(i64::from(minutes)))
+        })
+    }
+
+    fn render_error(&self, error: &minijinja::Error) -> DataStoreError {
+        DataStoreError::ValidationError {
+            message: format!("Report template {} failed to render: {error:#}", self.name),
+        }
+    }
+}
+
+/// A rendered report
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RenderedReport {
+    /// Report name
+    pub name: String,
+    /// Output format
+    pub format: ReportFormat,
+    /// When the report was rendered
+    pub rendered_at: DateTime<Utc>,
+    /// Rendered text
+    pub content: String,
+}
+
+/// Outcome of rendering the reports that were due
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScheduleRun {
+    /// Reports rendered
+    pub rendered: Vec<String>,
+    /// Reports that failed, with the error
+    pub failed: Vec<(String, String)>,
+}
+
+/// Template environment without a loader, so nothing can be included
+fn environment(format: ReportFormat) -> Environment<'static> {
+    let mut env = Environment::new();
+    env.set_auto_escape_callback(move |_| match format {
+        ReportFormat::Html => AutoEscape::Html,
+        ReportFormat::Markdown => AutoEscape::None,
+    });
+    env
+}
+
+fn parse<T: serde::de::DeserializeOwned>(key: &str, value: Value) -> DataStoreResult<T> {
+    serde_json::from_value(value).map_err(|e| DataStoreError::InternalError {
+        message: format!("stored report data {key}: {e}"),
+    })
+}
+
+fn to_value<T: Serialize>(key: &str, value: &T) -> DataStoreResult<Value> {
+    serde_json::to_value(value).map_err(|e| DataStoreError::InternalError {
+        message: format!("report data {key}: {e}"),
+    })
+}
+
+/// Renders a report from the datasets its template names
+///
+/// # Errors
+/// Returns a validation error if the template fails to render, or an error
+/// if a dataset cannot be loaded.
+pub async fn render_report(
+    datastore: &dyn DataStore,
+    report: &ReportTemplate,
+    now: DateTime<Utc>,
+) -> DataStoreResult<RenderedReport> {
+    let datasets = report.datasets()?;
+    let wants = |names: &[&str]| datasets.iter().any(|dataset| names.contains(dataset));
+    let options = QueryOptions::default();
+    let nodes = if wants(&[
+        "nodes",
+        "firmware",
+        "compliance",
+        "conformance",
+        "metrics",
+        "hardware",
+        "capacity",
+    ]) {
+        datastore.list_nodes(&options).await?.items
+    } else {
+        Vec::new()
+    };
+    let locations = if wants(&["locations", "firmware", "capacity"]) {
+        datastore.list_locations(&options).await?.items
+    } else {
+        Vec::new()
+    };
+
+    let mut context = Map::new();
+    context.insert(
+        "report".to_string(),
+        serde_json::json!({"name": report.name, "description": report.description}),
+    );
+    context.insert("generated_at".to_string(), Value::String(now.to_rfc3339()));
+    for dataset in datasets {
+        let value = match dataset {
+            "nodes" => to_value(dataset, &nodes)?,
+            "links" => to_value(dataset, &datastore.list_links(&options).await?.items)?,
+            "locations" => to_value(dataset, &locations)?,
+            "firmware" => {
+                let targets = list_targets(datastore).await?;
+                to_value(dataset, &build_report(&nodes, &locations, &targets))?
+            }
+            "compliance" => to_value(dataset, &compliance(datastore, &nodes).await?)?,
+            "conformance" => to_value(dataset, &conformance(datastore, &nodes).await?)?,
+            "metrics" => to_value(dataset, &metrics(datastore, &nodes).await?)?,
+            "hardware" => to_value(dataset, &hardware(datastore, &nodes).await?)?,
+            "capacity" => {
+                let links = datastore.list_links(&options).await?.items;
+                let histories = list_utilization(datastore).await?;
+                let since = window_start(now, DEFAULT_WINDOW_DAYS);
+                let report =
+                    capacity::build_report(&nodes, &locations, &links, &histories, since, now);
+                to_value(dataset, &report)?
+            }
+            _ => continue,
+        };
+        context.insert(dataset.to_string(), value);
+    }
+
+    let env = environment(report.format);
+    let content = env
+        .render_str(&report.template, Value::Object(context))
+        .map_err(|e| report.render_error(&e))?;
+    Ok(RenderedReport {
+        name: report.name.clone(),
+        format: report.format,
+        rendered_at: now,
+        content,
+    })
+}
+
+/// Lists all report templates, ordered by name
+///
+/// # Errors
+/// Returns an error if the templates cannot be read or one does not parse.
+pub async fn list_templates(datastore: &dyn DataStore) -> DataStoreResult<Vec<ReportTemplate>> {
+    datastore
+        .list_settings(TEMPLATES_NAMESPACE)
+        .await?
+        .into_iter()
+        .map(|(key, value)| parse(&key, value))
+        .collect()
+}
+
+/// Returns the report template called `name`
+///
+/// # Errors
+/// Returns a not-found error if there is none, or an error if the datastore
+/// cannot be read.
+pub async fn get_template(
+    datastore: &dyn DataStore,
+    name: &str,
+) -> DataStoreResult<ReportTemplate> {
+    datastore
+        .get_setting(TEMPLATES_NAMESPACE, name)
+        .await?
+        .ok_or_else(|| DataStoreError::NotFound {
+            entity_type: "Report template".to_string(),
+            id: name.to_string(),
+        })
+        .and_then(|value| parse(name, value))
+}
+
+/// Stores a report template, replacing any with the same name
+///
+/// # Errors
+/// Returns an error if the datastore write fails.
+pub async fn save_template(
+    datastore: &dyn DataStore,
+    report: &ReportTemplate,
+) -> DataStoreResult<()> {
+    datastore
+        .put_setting(
+            TEMPLATES_NAMESPACE,
+            &report.name,
+            &to_value(&report.name, report)?,
+        )
+        .await
+}
+
+/// Removes a report template and its latest rendering
+///
+/// # Errors
+/// Returns a not-found error if there is no such template, or an error if
+/// the datastore write fails.
+pub async fn delete_template(datastore: &dyn DataStore, name: &str) -> DataStoreResult<()> {
+    datastore.delete_setting(TEMPLATES_NAMESPACE, name).await?;
+    match datastore.delete_setting(RENDERS_NAMESPACE, name).await {
+        Ok(()) | Err(DataStoreError::NotFound { .. }) => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Returns the latest rendering of a report, if it has been rendered
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or the stored rendering is malformed.
+pub async fn latest_render(
+    datastore: &dyn DataStore,
+    name: &str,
+) -> DataStoreResult<Option<RenderedReport>> {
+    datastore
+        .get_setting(RENDERS_NAMESPACE, name)
+        .await?
+        .map(|value| parse(name, value))
+        .transpose()
+}
+
+/// Renders a stored report and keeps the result as its latest rendering
+///
+/// # Errors
+/// Returns a not-found error if there is no such template, a validation error
+/// if it fails to render, or an error if the datastore cannot be read or
+/// written.
+pub async fn run_report(
+    datastore: &dyn DataStore,
+    name: &str,
+    now: DateTime<Utc>,
+) -> DataStoreResult<RenderedReport> {
+    let report = get_template(datastore, name).await?;
+    let rendered = render_report(datastore, &report, now).await?;
+    datastore
+        .put_setting(RENDERS_NAMESPACE, name, &to_value(name, &rendered)?)
+        .await?;
+    Ok(rendered)
+}
+
+/// Renders every scheduled report whose interval has passed
+///
+/// A report that fails is recorded in the outcome and tried again on the
+/// next run.
+///
+/// # Errors
+/// Returns an error if the templates or their latest renderings cannot be read.
+pub async fn render_due(
+    datastore: &dyn DataStore,
+    now: DateTime<Utc>,
+) -> DataStoreResult<ScheduleRun> {
+    let mut run = ScheduleRun::default();
+    for report in list_templates(datastore).await? {
+        let latest = latest_render(datastore, &report.name).await?;
+        if !report.is_due(latest.map(|latest| latest.rendered_at), now) {
+            continue;
+        }
+        match run_report(datastore, &report.name, now).await {
+            Ok(_) => run.rendered.push(report.name),
+            Err(e) => run.failed.push((report.name, e.to_string())),
+        }
+    }
+    Ok(run)
+}
+
+#[cfg(test)]
+mod tests;
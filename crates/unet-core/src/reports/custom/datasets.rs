@@ -0,0 +1,171 @@
+//! Datasets of per-node state that templates can name
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::datastore::{DataStore, DataStoreResult};
+use crate::golden::conformance_history;
+use crate::hardware::list_inventories;
+use crate::models::Node;
+use crate::models::derived::HardwareComponent;
+
+/// Failed policy rules of a node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeCompliance {
+    /// Node name
+    pub node: String,
+    /// Node ID
+    pub node_id: Uuid,
+    /// Rules in the latest evaluation
+    pub evaluated: usize,
+    /// Rules that failed, by ID or by their text when they have none
+    pub failed: Vec<String>,
+}
+
+/// Latest golden configuration score of a node
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeConformance {
+    /// Node name
+    pub node: String,
+    /// Node ID
+    pub node_id: Uuid,
+    /// Matched sections in percent
+    pub score: f64,
+    /// Key of the golden configuration checked against
+    pub golden: String,
+    /// When the check ran
+    pub checked_at: DateTime<Utc>,
+}
+
+/// Polled health of a node
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeMetrics {
+    /// Node name
+    pub node: String,
+    /// Node ID
+    pub node_id: Uuid,
+    /// Whether the last poll reached the node
+    pub reachable: bool,
+    /// Failed polls in a row
+    pub consecutive_failures: u32,
+    /// CPU utilization in percent
+    pub cpu_utilization: Option<u8>,
+    /// Memory utilization in percent
+    pub memory_utilization: Option<u8>,
+    /// System load average
+    pub load_average: Option<f32>,
+}
+
+/// Latest hardware inventory of a node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeHardware {
+    /// Node name
+    pub node: String,
+    /// Node ID
+    pub node_id: Uuid,
+    /// When the inventory was walked
+    pub collected_at: DateTime<Utc>,
+    /// Chassis, modules, transceivers, and power supplies
+    pub components: Vec<HardwareComponent>,
+}
+
+pub(super) async fn compliance(
+    datastore: &dyn DataStore,
+    nodes: &[Node],
+) -> DataStoreResult<Vec<NodeCompliance>> {
+    let mut compliance = Vec::with_capacity(nodes.len());
+    for node in nodes {
+        let results = datastore.get_latest_policy_results(&node.id).await?;
+        compliance.push(NodeCompliance {
+            node: node.name.clone(),
+            node_id: node.id,
+            evaluated: results.len(),
+            failed: results
+                .iter()
+                .filter(|result| result.is_compliance_failure())
+                .map(|result| {
+                    result
+                        .rule
+                        .id
+                        .clone()
+                        .unwrap_or_else(|| result.rule.to_string())
+                })
+                .collect(),
+        });
+    }
+    Ok(compliance)
+}
+
+pub(super) async fn conformance(
+    datastore: &dyn DataStore,
+    nodes: &[Node],
+) -> DataStoreResult<Vec<NodeConformance>> {
+    let mut conformance = Vec::new();
+    for node in nodes {
+        if let Some(record) = conformance_history(datastore, node.id).await?.pop() {
+            conformance.push(NodeConformance {
+                node: node.name.clone(),
+                node_id: node.id,
+                score: record.conformance.score,
+                golden: record.golden,
+                checked_at: record.checked_at,
+            });
+        }
+    }
+    Ok(conformance)
+}
+
+pub(super) async fn metrics(
+    datastore: &dyn DataStore,
+    nodes: &[Node],
+) -> DataStoreResult<Vec<NodeMetrics>> {
+    let ids: Vec<Uuid> = nodes.iter().map(|node| node.id).collect();
+    let names: HashMap<Uuid, &str> = nodes
+        .iter()
+        .map(|node| (node.id, node.name.as_str()))
+        .collect();
+    Ok(datastore
+        .get_node_statuses(&ids)
+        .await?
+        .into_iter()
+        .filter_map(|status| {
+            let node = (*names.get(&status.node_id)?).to_string();
+            let performance = status.performance.as_ref();
+            Some(NodeMetrics {
+                node,
+                node_id: status.node_id,
+                reachable: status.reachable,
+                consecutive_failures: status.consecutive_failures,
+                cpu_utilization: performance.and_then(|p| p.cpu_utilization),
+                memory_utilization: performance.and_then(|p| p.memory_utilization),
+                load_average: performance.and_then(|p| p.load_average),
+            })
+        })
+        .collect())
+}
+
+pub(super) async fn hardware(
+    datastore: &dyn DataStore,
+    nodes: &[Node],
+) -> DataStoreResult<Vec<NodeHardware>> {
+    let names: HashMap<Uuid, &str> = nodes
+        .iter()
+        .map(|node| (node.id, node.name.as_str()))
+        .collect();
+    let mut hardware: Vec<NodeHardware> = list_inventories(datastore)
+        .await?
+        .into_iter()
+        .filter_map(|inventory| {
+            Some(NodeHardware {
+                node: (*names.get(&inventory.node_id)?).to_string(),
+                node_id: inventory.node_id,
+                collected_at: inventory.collected_at,
+                components: inventory.components,
+            })
+        })
+        .collect();
+    hardware.sort_by(|a, b| a.node.cmp(&b.node));
+    Ok(hardware)
+}
@@ -0,0 +1,184 @@
+use super::*;
+use crate::datastore::sqlite::tests::setup::settings_store;
+use crate::datastore::{MockDataStore, PagedResult, testing::ready_ok};
+use crate::models::derived::HardwareComponent;
+use crate::models::{DeviceRole, Node, Vendor};
+
+fn report(format: ReportFormat, template: &str) -> ReportTemplate {
+    ReportTemplate::new("weekly", format, template.to_string(), None, Some(60)).unwrap()
+}
+
+fn node(name: &str) -> Node {
+    Node::new(
+        name.to_string(),
+        "example.com".to_string(),
+        Vendor::Cisco,
+        DeviceRole::Router,
+    )
+}
+
+#[test]
+fn test_new_validates_name_interval_and_syntax() {
+    let new = |name: &str, template: &str, interval| {
+        ReportTemplate::new(
+            name,
+            ReportFormat::Markdown,
+            template.to_string(),
+            None,
+            interval,
+        )
+    };
+
+    assert_eq!(new(" noc-weekly ", "", None).unwrap().name, "noc-weekly");
+    assert!(new("", "", None).is_err());
+    assert!(new("noc weekly", "", None).is_err());
+    assert!(new("noc", "", Some(0)).is_err());
+    assert!(new("noc", "{% for node in nodes %}", None).is_err());
+}
+
+#[test]
+fn test_datasets_are_the_named_ones() {
+    let report = report(
+        ReportFormat::Markdown,
+        "{% for row in metrics %}{{ row.node }}{% endfor %}{{ nodes | length }} {{ other }}",
+    );
+
+    assert_eq!(report.datasets().unwrap(), ["nodes", "metrics"]);
+}
+
+#[test]
+fn test_is_due_after_interval() {
+    let now = Utc::now();
+    let report = report(ReportFormat::Markdown, "");
+
+    assert!(report.is_due(None, now));
+    assert!(!report.is_due(Some(now - TimeDelta::minutes(59)), now));
+    assert!(report.is_due(Some(now - TimeDelta::minutes(60)), now));
+
+    let on_demand = ReportTemplate {
+        interval_minutes: None,
+        ..report
+    };
+    assert!(!on_demand.is_due(None, now));
+}
+
+#[tokio::test]
+async fn test_render_loads_only_named_datasets_and_escapes_html() {
+    let nodes = vec![node("<edge>")];
+    let mut datastore = MockDataStore::new();
+    datastore
+        .expect_list_nodes()
+        .times(1)
+        .returning(move |_| ready_ok(PagedResult::new(nodes.clone(), nodes.len(), None)));
+    let report = report(
+        ReportFormat::Html,
+        "<h1>{{ report.name }}</h1>{% for node in nodes %}<p>{{ node.name }}</p>{% endfor %}",
+    );
+
+    let rendered = render_report(&datastore, &report, Utc::now())
+        .await
+        .unwrap();
+
+    assert_eq!(rendered.content, "<h1>weekly</h1><p>&lt;edge&gt;</p>");
+    assert_eq!(rendered.format, ReportFormat::Html);
+}
+
+#[tokio::test]
+async fn test_render_hardware_dataset() {
+    let edge = node("edge-01");
+    let inventory = crate::hardware::HardwareInventory {
+        node_id: edge.id,
+        collected_at: Utc::now(),
+        components: vec![HardwareComponent {
+            index: 1,
+            kind: crate::models::derived::HardwareKind::Chassis,
+            name: None,
+            description: None,
+            parent_index: None,
+            serial_number: Some("FOC2233X0AB".to_string()),
+            part_number: Some("C9300-48P".to_string()),
+            manufacturer: None,
+            hardware_revision: None,
+            firmware_revision: None,
+            software_revision: None,
+            field_replaceable: Some(false),
+        }],
+    };
+    let nodes = vec![edge];
+    let mut datastore = MockDataStore::new();
+    datastore
+        .expect_list_nodes()
+        .returning(move |_| ready_ok(PagedResult::new(nodes.clone(), nodes.len(), None)));
+    datastore.expect_list_settings().returning(move |_| {
+        ready_ok(vec![(
+            inventory.node_id.to_string(),
+            serde_json::to_value(&inventory).unwrap(),
+        )])
+    });
+    let report = report(
+        ReportFormat::Markdown,
+        "{% for row in hardware %}{% for part in row.components %}\
+         {{ row.node }} {{ part.part_number }} {{ part.serial_number }}{% endfor %}{% endfor %}",
+    );
+
+    let rendered = render_report(&datastore, &report, Utc::now())
+        .await
+        .unwrap();
+
+    assert_eq!(rendered.content, "edge-01 C9300-48P FOC2233X0AB");
+}
+
+#[tokio::test]
+async fn test_templates_cannot_include_others() {
+    let report = report(ReportFormat::Markdown, "{% include \"secrets.txt\" %}");
+
+    let error = render_report(&MockDataStore::new(), &report, Utc::now())
+        .await
+        .unwrap_err();
+
+    assert!(error.to_string().contains("weekly"));
+}
+
+#[tokio::test]
+async fn test_save_run_and_render_due() {
+    let store = settings_store().await;
+    let now = Utc::now();
+    save_template(
+        &store,
+        &report(ReportFormat::Markdown, "# {{ report.name }}"),
+    )
+    .await
+    .unwrap();
+    let on_demand = ReportTemplate::new(
+        "adhoc",
+        ReportFormat::Markdown,
+        "{{ generated_at }}".to_string(),
+        Some("Ad hoc".to_string()),
+        None,
+    )
+    .unwrap();
+    save_template(&store, &on_demand).await.unwrap();
+
+    let names: Vec<String> = list_templates(&store)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|report| report.name)
+        .collect();
+    assert_eq!(names, ["adhoc", "weekly"]);
+
+    let run = render_due(&store, now).await.unwrap();
+    assert_eq!(run.rendered, ["weekly"]);
+    assert!(run.failed.is_empty());
+    let latest = latest_render(&store, "weekly").await.unwrap().unwrap();
+    assert_eq!(latest.content, "# weekly");
+    assert!(render_due(&store, now).await.unwrap().rendered.is_empty());
+
+    let rendered = run_report(&store, "adhoc", now).await.unwrap();
+    assert_eq!(rendered.content, now.to_rfc3339());
+
+    delete_template(&store, "weekly").await.unwrap();
+    assert!(latest_render(&store, "weekly").await.unwrap().is_none());
+    assert!(delete_template(&store, "weekly").await.is_err());
+    delete_template(&store, "adhoc").await.unwrap();
+}
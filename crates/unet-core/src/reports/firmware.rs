@@ -0,0 +1,449 @@
+//! Firmware/OS compliance against a target-version matrix
+//!
+//! Admins declare the OS version each device model or role should run. A
+//! model target wins over the role's, so a role can set the fleet standard
+//! while individual platforms pin their own release. Targets may list other
+//! versions that are still accepted, such as the previous approved release.
+//!
+//! The report compares every node's `version` (filled in from polling with
+//! `nodes test-access --backfill-version`) with its target. Nodes are grouped
+//! by vendor, model, and version, and counted per site; nodes below or above
+//! their target make up the upgrade backlog. Decommissioned nodes are left
+//! out. The report can be limited to a dynamic node group (see
+//! [`crate::groups`]).
+
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult, QueryOptions};
+use crate::groups::get_group;
+use crate::models::{DeviceRole, Lifecycle, Location, Node, Vendor};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Settings namespace holding target versions keyed by `scope:target`
+const TARGETS_NAMESPACE: &str = "firmware_targets";
+
+/// What a target version applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TargetScope {
+    /// All nodes of a device model
+    Model,
+    /// All nodes with a device role
+    Role,
+}
+
+impl Display for TargetScope {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Model => write!(f, "model"),
+            Self::Role => write!(f, "role"),
+        }
+    }
+}
+
+impl FromStr for TargetScope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "model" => Ok(Self::Model),
+            "role" => Ok(Self::Role),
+            _ => Err(format!("Invalid firmware target scope: {s}")),
+        }
+    }
+}
+
+/// The OS version a model or role should run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FirmwareTarget {
+    /// Target scope
+    pub scope: TargetScope,
+    /// Model name, matched exactly, or role name depending on scope
+    pub target: String,
+    /// Version nodes should run
+    pub version: String,
+    /// Other versions that are accepted
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed: Vec<String>,
+}
+
+impl FirmwareTarget {
+    /// Creates a target, normalizing role names
+    ///
+    /// # Errors
+    /// Returns a validation error if the role is invalid, or the model or a
+    /// version is empty.
+    pub fn new(
+        scope: TargetScope,
+        target: &str,
+        version: &str,
+        allowed: Vec<String>,
+    ) -> DataStoreResult<Self> {
+        let target = match scope {
+            TargetScope::Model => Ok(target.trim().to_string()),
+            TargetScope::Role => DeviceRole::from_str(target).map(|role| role.to_string()),
+        }
+        .map_err(|message| DataStoreError::ValidationError { message })?;
+        if target.is_empty() {
+            return Err(DataStoreError::ValidationError {
+                message: "Firmware target model must not be empty".to_string(),
+            });
+        }
+        let version = version.trim().to_string();
+        let allowed: Vec<String> = allowed.into_iter().map(|v| v.trim().to_string()).collect();
+        if version.is_empty() || allowed.iter().any(String::is_empty) {
+            return Err(DataStoreError::ValidationError {
+                message: format!("Firmware target versions for {scope} {target} must not be empty"),
+            });
+        }
+        Ok(Self {
+            scope,
+            target,
+            version,
+            allowed,
+        })
+    }
+
+    /// Storage key identifying the scope and target
+    #[must_use]
+    pub fn key(&self) -> String {
+        format!("{}:{}", self.scope, self.target)
+    }
+
+    /// How `version` stands against this target
+    #[must_use]
+    pub fn status(&self, version: &str) -> FirmwareStatus {
+        if std::iter::once(&self.version)
+            .chain(&self.allowed)
+            .any(|accepted| compare_versions(version, accepted) == Ordering::Equal)
+        {
+            return FirmwareStatus::Compliant;
+        }
+        match compare_versions(version, &self.version) {
+            Ordering::Less => FirmwareStatus::Outdated,
+            _ => FirmwareStatus::Ahead,
+        }
+    }
+}
+
+/// Compares vendor version strings such as `17.3.4`, `15.2(4)M7`, or
+/// `18.4R3-S4.2`
+///
+/// Versions are split into runs of digits and runs of letters; punctuation
+/// only separates runs. Digit runs compare as numbers and letter runs
+/// case-insensitively, a letter run sorts before a digit run, and a version
+/// that continues past the other's end is the newer one.
+#[must_use]
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let (a, b) = (version_runs(a), version_runs(b));
+    for (left, right) in a.iter().zip(&b) {
+        let left_numeric = left.starts_with(|c: char| c.is_ascii_digit());
+        let right_numeric = right.starts_with(|c: char| c.is_ascii_digit());
+        let ordering = match (left_numeric, right_numeric) {
+            (true, true) => {
+                let (left, right) = (left.trim_start_matches('0'), right.trim_start_matches('0'));
+                left.len().cmp(&right.len()).then_with(|| left.cmp(right))
+            }
+            (false, false) => left.to_lowercase().cmp(&right.to_lowercase()),
+            (left_numeric, _) => left_numeric.cmp(&right_numeric),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    a.len().cmp(&b.len())
+}
+
+/// Runs of ASCII digits and runs of letters in a version
+fn version_runs(version: &str) -> Vec<&str> {
+    let mut runs = Vec::new();
+    let mut start = None;
+    let mut numeric = false;
+    for (index, c) in version.char_indices() {
+        let kind = if c.is_ascii_digit() {
+            Some(true)
+        } else if c.is_alphabetic() {
+            Some(false)
+        } else {
+            None
+        };
+        if let Some(run_start) = start {
+            if kind != Some(numeric) {
+                runs.push(&version[run_start..index]);
+                start = None;
+            }
+        }
+        if let (None, Some(kind)) = (start, kind) {
+            start = Some(index);
+            numeric = kind;
+        }
+    }
+    if let Some(run_start) = start {
+        runs.push(&version[run_start..]);
+    }
+    runs
+}
+
+/// How a node's version stands against its target
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FirmwareStatus {
+    /// Runs the target or an allowed version
+    Compliant,
+    /// Runs an older version than the target
+    Outdated,
+    /// Runs a newer version than the target that is not allowed
+    Ahead,
+    /// Has a target but no recorded version
+    Unknown,
+    /// No target applies to the node
+    Untargeted,
+}
+
+impl Display for FirmwareStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Compliant => write!(f, "compliant"),
+            Self::Outdated => write!(f, "outdated"),
+            Self::Ahead => write!(f, "ahead"),
+            Self::Unknown => write!(f, "unknown"),
+            Self::Untargeted => write!(f, "untargeted"),
+        }
+    }
+}
+
+/// Node counts by firmware status
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FirmwareCounts {
+    /// Nodes counted
+    pub nodes: usize,
+    /// Nodes on their target or an allowed version
+    pub compliant: usize,
+    /// Nodes below their target
+    pub outdated: usize,
+    /// Nodes above their target
+    pub ahead: usize,
+    /// Nodes with a target but no recorded version
+    pub unknown: usize,
+    /// Nodes without a target
+    pub untargeted: usize,
+    /// Nodes to move to their target version: outdated plus ahead
+    pub backlog: usize,
+}
+
+impl FirmwareCounts {
+    fn count(&mut self, status: FirmwareStatus) {
+        self.nodes += 1;
+        match status {
+            FirmwareStatus::Compliant => self.compliant += 1,
+            FirmwareStatus::Outdated => self.outdated += 1,
+            FirmwareStatus::Ahead => self.ahead += 1,
+            FirmwareStatus::Unknown => self.unknown += 1,
+            FirmwareStatus::Untargeted => self.untargeted += 1,
+        }
+        self.backlog = self.outdated + self.ahead;
+    }
+}
+
+/// Nodes of one model running one version
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FirmwareGroup {
+    /// Vendor
+    pub vendor: Vendor,
+    /// Device model
+    pub model: String,
+    /// Version the nodes run, if recorded
+    pub version: Option<String>,
+    /// Key of the target that applies, such as `model:ASR1001-X`
+    pub target: Option<String>,
+    /// Target version
+    pub target_version: Option<String>,
+    /// How the version stands against the target
+    pub status: FirmwareStatus,
+    /// Names of the nodes, sorted
+    pub nodes: Vec<String>,
+}
+
+/// Firmware status counts for one site
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SiteFirmware {
+    /// Location the nodes are assigned to; `None` for unassigned nodes
+    pub location_id: Option<Uuid>,
+    /// Location path
+    pub path: Option<String>,
+    /// Counts for the nodes directly in this location
+    pub counts: FirmwareCounts,
+}
+
+/// Fleet-wide firmware compliance
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FirmwareReport {
+    /// Totals over the fleet
+    pub summary: FirmwareCounts,
+    /// Nodes grouped by vendor, model, version, and target
+    pub groups: Vec<FirmwareGroup>,
+    /// Counts per location, by path, with unassigned nodes last
+    pub sites: Vec<SiteFirmware>,
+}
+
+/// Builds the report for `nodes` from `targets`, naming sites from `locations`
+#[must_use]
+pub fn build_report(
+    nodes: &[Node],
+    locations: &[Location],
+    targets: &[FirmwareTarget],
+) -> FirmwareReport {
+    let targets: HashMap<String, &FirmwareTarget> = targets
+        .iter()
+        .map(|target| (target.key(), target))
+        .collect();
+    let paths: HashMap<Uuid, &str> = locations
+        .iter()
+        .map(|location| (location.id, location.path.as_str()))
+        .collect();
+
+    let mut report = FirmwareReport::default();
+    let mut groups: BTreeMap<_, FirmwareGroup> = BTreeMap::new();
+    let mut sites: BTreeMap<_, SiteFirmware> = BTreeMap::new();
+    for node in nodes {
+        if node.lifecycle == Lifecycle::Decommissioned {
+            continue;
+        }
+        let target = [
+            format!("{}:{}", TargetScope::Model, node.model),
+            format!("{}:{}", TargetScope::Role, node.role),
+        ]
+        .iter()
+        .find_map(|key| targets.get(key).copied());
+        let version = node
+            .version
+            .as_deref()
+            .map(str::trim)
+            .filter(|version| !version.is_empty());
+        let status = match (target, version) {
+            (None, _) => FirmwareStatus::Untargeted,
+            (Some(_), None) => FirmwareStatus::Unknown,
+            (Some(target), Some(version)) => target.status(version),
+        };
+
+        report.summary.count(status);
+        let path = node.location_id.map(|id| {
+            paths
+                .get(&id)
+                .map_or_else(|| id.to_string(), ToString::to_string)
+        });
+        sites
+            .entry((path.is_none(), path.clone()))
+            .or_insert_with(|| SiteFirmware {
+                location_id: node.location_id,
+                path,
+                counts: FirmwareCounts::default(),
+            })
+            .counts
+            .count(status);
+
+        let key = (
+            node.vendor.to_string(),
+            node.model.clone(),
+            version.map(ToString::to_string),
+            target.map(FirmwareTarget::key),
+        );
+        groups
+            .entry(key)
+            .or_insert_with(|| FirmwareGroup {
+                vendor: node.vendor,
+                model: node.model.clone(),
+                version: version.map(ToString::to_string),
+                target: target.map(FirmwareTarget::key),
+                target_version: target.map(|target| target.version.clone()),
+                status,
+                nodes: Vec::new(),
+            })
+            .nodes
+            .push(node.name.clone());
+    }
+
+    report.groups = groups
+        .into_values()
+        .map(|mut group| {
+            group.nodes.sort();
+            group
+        })
+        .collect();
+    report.sites = sites.into_values().collect();
+    report
+}
+
+/// Reports every node's OS version against the stored targets, or only the
+/// current members of a node `group`
+///
+/// # Errors
+/// Returns an error if nodes, locations, or targets cannot be read, or the
+/// group does not exist or its filter does not parse.
+pub async fn firmware_report(
+    datastore: &dyn DataStore,
+    group: Option<&str>,
+) -> DataStoreResult<FirmwareReport> {
+    let options = QueryOptions::default();
+    let mut nodes = datastore.list_nodes(&options).await?.items;
+    if let Some(name) = group {
+        let group = get_group(datastore, name).await?;
+        nodes = group.select(&nodes)?.into_iter().cloned().collect();
+    }
+    let locations = datastore.list_locations(&options).await?.items;
+    let targets = list_targets(datastore).await?;
+    Ok(build_report(&nodes, &locations, &targets))
+}
+
+/// Lists all target versions, ordered by scope and target
+///
+/// # Errors
+/// Returns an error if the targets cannot be read or one does not parse.
+pub async fn list_targets(datastore: &dyn DataStore) -> DataStoreResult<Vec<FirmwareTarget>> {
+    datastore
+        .list_settings(TARGETS_NAMESPACE)
+        .await?
+        .into_iter()
+        .map(|(key, value)| {
+            serde_json::from_value(value).map_err(|e| DataStoreError::InternalError {
+                message: format!("stored firmware target {key}: {e}"),
+            })
+        })
+        .collect()
+}
+
+/// Stores a target, replacing any for the same scope and target
+///
+/// # Errors
+/// Returns an error if the datastore write fails.
+pub async fn save_target(
+    datastore: &dyn DataStore,
+    target: &FirmwareTarget,
+) -> DataStoreResult<()> {
+    let value = serde_json::to_value(target).map_err(|e| DataStoreError::InternalError {
+        message: format!("firmware target {}: {e}", target.key()),
+    })?;
+    datastore
+        .put_setting(TARGETS_NAMESPACE, &target.key(), &value)
+        .await
+}
+
+/// Removes the target for a scope and target
+///
+/// # Errors
+/// Returns an error if the target is invalid, none is stored, or the
+/// datastore write fails.
+pub async fn delete_target(
+    datastore: &dyn DataStore,
+    scope: TargetScope,
+    target: &str,
+) -> DataStoreResult<()> {
+    let key = FirmwareTarget::new(scope, target, "0", Vec::new())?.key();
+    datastore.delete_setting(TARGETS_NAMESPACE, &key).await
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,141 @@
+use super::*;
+use crate::datastore::sqlite::tests::setup::settings_store;
+
+fn node(name: &str, model: &str, role: DeviceRole, version: Option<&str>) -> Node {
+    let mut node = Node::new(
+        name.to_string(),
+        "example.com".to_string(),
+        Vendor::Cisco,
+        role,
+    );
+    node.model = model.to_string();
+    node.version = version.map(ToString::to_string);
+    node
+}
+
+fn target(scope: TargetScope, target: &str, version: &str) -> FirmwareTarget {
+    FirmwareTarget::new(scope, target, version, Vec::new()).unwrap()
+}
+
+#[test]
+fn test_compare_versions_orders_vendor_formats() {
+    assert_eq!(compare_versions("17.3.4", "17.3.4"), Ordering::Equal);
+    assert_eq!(compare_versions("17.03.04", "17.3.4"), Ordering::Equal);
+    assert_eq!(compare_versions("17.3.4", "17.10.1"), Ordering::Less);
+    assert_eq!(compare_versions("17.3.4a", "17.3.4"), Ordering::Greater);
+    assert_eq!(compare_versions("15.2(4)M7", "15.2(4)M10"), Ordering::Less);
+    assert_eq!(compare_versions("18.4R3-S4.2", "18.4R3"), Ordering::Greater);
+    assert_eq!(compare_versions("18.4R3", "18.4.1"), Ordering::Less);
+}
+
+#[test]
+fn test_new_normalizes_role_and_rejects_empty_versions() {
+    let role = target(TargetScope::Role, "Router", "17.9.4");
+    assert_eq!(role.key(), "role:router");
+    let model = target(TargetScope::Model, " ASR1001-X ", "17.9.4");
+    assert_eq!(model.key(), "model:ASR1001-X");
+
+    assert!(FirmwareTarget::new(TargetScope::Role, "toaster", "1", Vec::new()).is_err());
+    assert!(FirmwareTarget::new(TargetScope::Model, " ", "1", Vec::new()).is_err());
+    assert!(FirmwareTarget::new(TargetScope::Model, "MX204", " ", Vec::new()).is_err());
+    assert!(FirmwareTarget::new(TargetScope::Model, "MX204", "1", vec![String::new()]).is_err());
+}
+
+#[test]
+fn test_status_accepts_allowed_versions() {
+    let target = FirmwareTarget::new(
+        TargetScope::Role,
+        "router",
+        "17.9.4",
+        vec!["17.6.5".to_string()],
+    )
+    .unwrap();
+
+    assert_eq!(target.status("17.9.4"), FirmwareStatus::Compliant);
+    assert_eq!(target.status("17.6.5"), FirmwareStatus::Compliant);
+    assert_eq!(target.status("17.3.4"), FirmwareStatus::Outdated);
+    assert_eq!(target.status("17.12.1"), FirmwareStatus::Ahead);
+}
+
+#[test]
+fn test_build_report_groups_nodes_and_counts_sites() {
+    let dc1 = Location::new_root("dc1".to_string(), "site".to_string());
+    let mut nodes = vec![
+        node("edge-1", "ASR1001-X", DeviceRole::Router, Some("17.9.4")),
+        node("edge-2", "ASR1001-X", DeviceRole::Router, Some("17.3.4")),
+        node("edge-3", "ASR1001-X", DeviceRole::Router, Some("17.3.4")),
+        node("core-1", "N9K-C93180", DeviceRole::Router, Some("10.2(5)")),
+        node("access-1", "C9300-48P", DeviceRole::Switch, None),
+        node("fw-1", "FPR-2110", DeviceRole::Firewall, Some("7.0.1")),
+        node("old-1", "ASR1001-X", DeviceRole::Router, Some("16.9.1")),
+    ];
+    for node in &mut nodes[..3] {
+        node.location_id = Some(dc1.id);
+    }
+    nodes[6].lifecycle = Lifecycle::Decommissioned;
+    let targets = [
+        target(TargetScope::Role, "router", "10.3(1)"),
+        target(TargetScope::Model, "ASR1001-X", "17.9.4"),
+        target(TargetScope::Role, "switch", "17.9.4"),
+    ];
+
+    let report = build_report(&nodes, &[dc1.clone()], &targets);
+
+    assert_eq!(report.summary.nodes, 6);
+    assert_eq!(report.summary.compliant, 1);
+    assert_eq!(report.summary.outdated, 3);
+    assert_eq!(report.summary.unknown, 1);
+    assert_eq!(report.summary.untargeted, 1);
+    assert_eq!(report.summary.backlog, 3);
+
+    let outdated = report
+        .groups
+        .iter()
+        .find(|group| group.version.as_deref() == Some("17.3.4"))
+        .unwrap();
+    assert_eq!(outdated.target.as_deref(), Some("model:ASR1001-X"));
+    assert_eq!(outdated.status, FirmwareStatus::Outdated);
+    assert_eq!(outdated.nodes, ["edge-2", "edge-3"]);
+    let core = report
+        .groups
+        .iter()
+        .find(|group| group.model == "N9K-C93180")
+        .unwrap();
+    assert_eq!(core.target_version.as_deref(), Some("10.3(1)"));
+
+    assert_eq!(report.sites.len(), 2);
+    assert_eq!(report.sites[0].path.as_deref(), Some("dc1"));
+    assert_eq!(report.sites[0].counts.nodes, 3);
+    assert_eq!(report.sites[0].counts.backlog, 2);
+    assert_eq!(report.sites[1].location_id, None);
+    assert_eq!(report.sites[1].counts.nodes, 3);
+}
+
+#[tokio::test]
+async fn test_save_list_and_delete_targets() {
+    let store = settings_store().await;
+
+    save_target(&store, &target(TargetScope::Model, "MX204", "22.4R3"))
+        .await
+        .unwrap();
+    save_target(&store, &target(TargetScope::Role, "router", "21.4R3"))
+        .await
+        .unwrap();
+    let keys: Vec<String> = list_targets(&store)
+        .await
+        .unwrap()
+        .iter()
+        .map(FirmwareTarget::key)
+        .collect();
+    assert_eq!(keys, ["model:MX204", "role:router"]);
+
+    delete_target(&store, TargetScope::Role, "Router")
+        .await
+        .unwrap();
+    assert_eq!(list_targets(&store).await.unwrap().len(), 1);
+    assert!(
+        delete_target(&store, TargetScope::Role, "router")
+            .await
+            .is_err()
+    );
+}
@@ -0,0 +1,411 @@
+//! Link utilization forecasting
+//!
+//! Projects, from the hourly utilization rollups kept for capacity planning,
+//! when each link will reach [`WARNING_UTILIZATION`] and
+//! [`CRITICAL_UTILIZATION`] percent, so upgrades can be ordered before they
+//! are needed. Two methods are offered:
+//!
+//! - [`Method::Linear`] fits a least-squares line to the hourly means and
+//!   extends it.
+//! - [`Method::HoltWinters`] smooths the level, trend, and daily cycle of the
+//!   hourly means (additive Holt-Winters) and extends them, so a link is
+//!   projected to cross a threshold when its busy hours do. It needs two full
+//!   days of rollups; links with fewer are fitted linearly instead.
+//!
+//! Hours missing between rollups repeat the previous hour's mean. Crossings
+//! more than [`HORIZON_DAYS`] ahead are not reported.
+
+use super::capacity::{UtilizationRollup, csv_field, list_utilization, window_start};
+use crate::datastore::{DataStore, DataStoreResult, QueryOptions};
+use crate::models::Link;
+use chrono::{DateTime, TimeDelta, Utc};
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt::{self, Write as _};
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Utilization, in percent, at which an upgrade should be planned
+pub const WARNING_UTILIZATION: f64 = 70.0;
+
+/// Utilization, in percent, at which a link is considered full
+pub const CRITICAL_UTILIZATION: f64 = 90.0;
+
+/// Days ahead a crossing is projected
+pub const HORIZON_DAYS: i64 = 365;
+
+/// Hours in the cycle Holt-Winters smooths
+const SEASON_HOURS: usize = 24;
+
+/// Weight of the newest hour in the smoothed level
+const LEVEL_SMOOTHING: f64 = 0.3;
+
+/// Weight of the newest hour in the smoothed trend
+const TREND_SMOOTHING: f64 = 0.05;
+
+/// Weight of the newest hour in the smoothed daily cycle
+const SEASON_SMOOTHING: f64 = 0.2;
+
+/// How utilization is projected
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Method {
+    /// A least-squares line through the hourly means
+    #[default]
+    Linear,
+    /// Additive Holt-Winters with a daily cycle
+    HoltWinters,
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Linear => write!(f, "linear"),
+            Self::HoltWinters => write!(f, "holt_winters"),
+        }
+    }
+}
+
+impl FromStr for Method {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('-', "_").as_str() {
+            "linear" => Ok(Self::Linear),
+            "holt_winters" => Ok(Self::HoltWinters),
+            _ => Err(format!("Invalid forecast method: {s}")),
+        }
+    }
+}
+
+/// Projected utilization of a link or site pair
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Forecast {
+    /// Method the projection was made with
+    pub method: Method,
+    /// Projected utilization now, in percent
+    pub utilization: f64,
+    /// Projected change of the utilization, in percentage points per week
+    pub trend_per_week: f64,
+    /// When the utilization is projected to reach 70 percent; now if it has
+    pub reaches_70_at: Option<DateTime<Utc>>,
+    /// When the utilization is projected to reach 90 percent; now if it has
+    pub reaches_90_at: Option<DateTime<Utc>>,
+}
+
+/// Projected utilization of one link
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LinkForecast {
+    /// Link the projection is for
+    pub link_id: Uuid,
+    /// Link name
+    pub link: String,
+    /// Link bandwidth, in bits per second
+    pub bandwidth_bps: Option<u64>,
+    /// Hours in the window with utilization samples
+    pub hours: usize,
+    /// The projection; `None` with fewer than two hours of samples
+    pub forecast: Option<Forecast>,
+}
+
+/// Projected utilization of every link with samples
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForecastReport {
+    /// Start of the window the projections are fitted to
+    pub since: DateTime<Utc>,
+    /// Method asked for
+    pub method: Method,
+    /// Links, soonest to reach 90 then 70 percent first; links that reach
+    /// neither, then links without a projection, by name
+    pub links: Vec<LinkForecast>,
+}
+
+impl ForecastReport {
+    /// The report as CSV, one row per link with a header row
+    ///
+    /// Values without data are left empty.
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "link,bandwidth_bps,hours,method,utilization,trend_per_week,reaches_70_at,\
+             reaches_90_at\n",
+        );
+        let time = |at: Option<DateTime<Utc>>| at.map(|at| at.to_rfc3339()).unwrap_or_default();
+        for link in &self.links {
+            let forecast = link.forecast.as_ref();
+            let fields = [
+                csv_field(&link.link),
+                link.bandwidth_bps
+                    .map(|bps| bps.to_string())
+                    .unwrap_or_default(),
+                link.hours.to_string(),
+                forecast.map(|f| f.method.to_string()).unwrap_or_default(),
+                forecast
+                    .map(|f| format!("{:.2}", f.utilization))
+                    .unwrap_or_default(),
+                forecast
+                    .map(|f| format!("{:.2}", f.trend_per_week))
+                    .unwrap_or_default(),
+                time(forecast.and_then(|f| f.reaches_70_at)),
+                time(forecast.and_then(|f| f.reaches_90_at)),
+            ];
+            let _ = writeln!(csv, "{}", fields.join(","));
+        }
+        csv
+    }
+}
+
+/// An integer as a float, rounded to the nearest one above 2^53
+pub(super) fn to_float(value: impl ToPrimitive + Copy) -> f64 {
+    // Every integer has a nearest float, so this never falls back
+    value.to_f64().unwrap_or_default()
+}
+
+/// Least-squares intercept and slope of `(x, y)` points
+pub(super) fn linear_fit(points: &[(f64, f64)]) -> Option<(f64, f64)> {
+    if points.len() < 2 {
+        return None;
+    }
+    let count = to_float(points.len());
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / count;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / count;
+    let (covariance, variance) = points.iter().fold((0.0, 0.0), |(cov, var), (x, y)| {
+        let dx = x - mean_x;
+        (dx.mul_add(y - mean_y, cov), dx.mul_add(dx, var))
+    });
+    (variance > 0.0).then(|| {
+        let slope = covariance / variance;
+        (slope.mul_add(-mean_x, mean_y), slope)
+    })
+}
+
+/// Hours from `from` to `to`
+pub(super) fn hours_between(from: DateTime<Utc>, to: DateTime<Utc>) -> f64 {
+    to_float((to - from).num_seconds()) / 3600.0
+}
+
+/// Time `hours` after `from`, to the second; `None` if out of range
+fn after_hours(from: DateTime<Utc>, hours: f64) -> Option<DateTime<Utc>> {
+    let seconds = (hours * 3600.0).round().to_i64()?;
+    from.checked_add_signed(TimeDelta::try_seconds(seconds)?)
+}
+
+/// Extends a least-squares line through `(hours after origin, utilization)`
+/// points
+///
+/// Returns `None` with fewer than two distinct hours.
+#[must_use]
+pub(super) fn project_linear(
+    points: &[(f64, f64)],
+    origin: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Option<Forecast> {
+    let (intercept, slope) = linear_fit(points)?;
+    let utilization = slope.mul_add(hours_between(origin, now), intercept);
+    let horizon = hours_between(now, now + TimeDelta::days(HORIZON_DAYS));
+    let reaches = |threshold: f64| {
+        if utilization >= threshold {
+            return Some(now);
+        }
+        let hours = (threshold - utilization) / slope;
+        if slope > 0.0 && hours <= horizon {
+            after_hours(now, hours)
+        } else {
+            None
+        }
+    };
+    Some(Forecast {
+        method: Method::Linear,
+        utilization,
+        trend_per_week: slope * 24.0 * 7.0,
+        reaches_70_at: reaches(WARNING_UTILIZATION),
+        reaches_90_at: reaches(CRITICAL_UTILIZATION),
+    })
+}
+
+/// Hourly means from the first rollup to the last, repeating the previous
+/// mean for hours without one
+fn hourly_series(rollups: &[UtilizationRollup]) -> Vec<f64> {
+    let mut series: Vec<f64> = Vec::new();
+    let Some(first) = rollups.first() else {
+        return series;
+    };
+    for rollup in rollups {
+        let index = usize::try_from((rollup.hour - first.hour).num_hours()).unwrap_or(0);
+        if index > series.len() {
+            let previous = series.last().copied().unwrap_or(rollup.mean);
+            series.resize(index, previous);
+        }
+        series.push(rollup.mean);
+    }
+    series
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / to_float(values.len())
+}
+
+/// Extends the smoothed level, trend, and daily cycle of the rollups
+fn project_holt_winters(rollups: &[UtilizationRollup], now: DateTime<Utc>) -> Option<Forecast> {
+    let series = hourly_series(rollups);
+    let last = rollups.last()?.hour;
+    if series.len() < 2 * SEASON_HOURS {
+        return project_linear(&points(rollups), rollups.first()?.hour, now);
+    }
+
+    let mut level = mean(&series[..SEASON_HOURS]);
+    let mut trend = (mean(&series[SEASON_HOURS..2 * SEASON_HOURS]) - level) / 24.0;
+    let mut season: Vec<f64> = series[..SEASON_HOURS]
+        .iter()
+        .map(|value| value - level)
+        .collect();
+    for (index, value) in series.iter().enumerate() {
+        let slot = index % SEASON_HOURS;
+        let previous = level;
+        level = LEVEL_SMOOTHING.mul_add(
+            value - season[slot],
+            (1.0 - LEVEL_SMOOTHING) * (level + trend),
+        );
+        trend = TREND_SMOOTHING.mul_add(level - previous, (1.0 - TREND_SMOOTHING) * trend);
+        season[slot] =
+            SEASON_SMOOTHING.mul_add(value - level, (1.0 - SEASON_SMOOTHING) * season[slot]);
+    }
+
+    let last_index = series.len() - 1;
+    let project = |steps: usize| {
+        trend.mul_add(to_float(steps), level) + season[(last_index + steps) % SEASON_HOURS]
+    };
+    let start = usize::try_from((now - last).num_hours()).unwrap_or(0);
+    let horizon = usize::try_from(HORIZON_DAYS * 24).unwrap_or(usize::MAX);
+    let reaches = |threshold: f64| {
+        (start..=start + horizon)
+            .find(|steps| project(*steps) >= threshold)
+            .map(|steps| {
+                if steps == start {
+                    now
+                } else {
+                    last + TimeDelta::hours(i64::try_from(steps).unwrap_or(i64::MAX))
+                }
+            })
+    };
+    Some(Forecast {
+        method: Method::HoltWinters,
+        utilization: project(start),
+        trend_per_week: trend * 24.0 * 7.0,
+        reaches_70_at: reaches(WARNING_UTILIZATION),
+        reaches_90_at: reaches(CRITICAL_UTILIZATION),
+    })
+}
+
+/// `(hours after the first rollup, mean)` of each rollup
+fn points(rollups: &[UtilizationRollup]) -> Vec<(f64, f64)> {
+    let Some(first) = rollups.first() else {
+        return Vec::new();
+    };
+    rollups
+        .iter()
+        .map(|rollup| (hours_between(first.hour, rollup.hour), rollup.mean))
+        .collect()
+}
+
+/// Projects the utilization of a link from its rollups, oldest first
+///
+/// Returns `None` with fewer than two rollups.
+#[must_use]
+pub fn forecast(
+    rollups: &[UtilizationRollup],
+    method: Method,
+    now: DateTime<Utc>,
+) -> Option<Forecast> {
+    match method {
+        Method::Linear => project_linear(&points(rollups), rollups.first()?.hour, now),
+        Method::HoltWinters => project_holt_winters(rollups, now),
+    }
+}
+
+/// Orders projections soonest crossing first, `None` last
+fn soonest(a: Option<DateTime<Utc>>, b: Option<DateTime<Utc>>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// Builds the report for `links`, fitting each to its rollups since `since`
+/// in `histories`
+#[must_use]
+pub fn build_report(
+    links: &[Link],
+    histories: &HashMap<Uuid, Vec<UtilizationRollup>>,
+    method: Method,
+    since: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> ForecastReport {
+    let mut forecasts: Vec<LinkForecast> = links
+        .iter()
+        .filter_map(|link| {
+            let rollups: Vec<UtilizationRollup> = histories
+                .get(&link.id)?
+                .iter()
+                .filter(|rollup| rollup.hour >= since)
+                .copied()
+                .collect();
+            (!rollups.is_empty()).then(|| LinkForecast {
+                link_id: link.id,
+                link: link.name.clone(),
+                bandwidth_bps: link.bandwidth,
+                hours: rollups.len(),
+                forecast: forecast(&rollups, method, now),
+            })
+        })
+        .collect();
+    forecasts.sort_by(|a, b| {
+        let (a_forecast, b_forecast) = (a.forecast.as_ref(), b.forecast.as_ref());
+        soonest(
+            a_forecast.and_then(|f| f.reaches_90_at),
+            b_forecast.and_then(|f| f.reaches_90_at),
+        )
+        .then_with(|| {
+            soonest(
+                a_forecast.and_then(|f| f.reaches_70_at),
+                b_forecast.and_then(|f| f.reaches_70_at),
+            )
+        })
+        .then_with(|| b_forecast.is_some().cmp(&a_forecast.is_some()))
+        .then_with(|| a.link.cmp(&b.link))
+    });
+    ForecastReport {
+        since,
+        method,
+        links: forecasts,
+    }
+}
+
+/// Projects the utilization of every link from its last `days` days of
+/// rollups
+///
+/// # Errors
+/// Returns an error if links or utilization rollups cannot be read.
+pub async fn forecast_report(
+    datastore: &dyn DataStore,
+    days: u32,
+    method: Method,
+    now: DateTime<Utc>,
+) -> DataStoreResult<ForecastReport> {
+    let links = datastore.list_links(&QueryOptions::default()).await?.items;
+    let histories = list_utilization(datastore).await?;
+    Ok(build_report(
+        &links,
+        &histories,
+        method,
+        window_start(now, days),
+        now,
+    ))
+}
+
+#[cfg(test)]
+mod tests;
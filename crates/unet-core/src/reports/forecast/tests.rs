@@ -0,0 +1,138 @@
+use super::*;
+
+fn at(hours: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp(1_790_000_000 - 1_790_000_000 % 3600, 0).unwrap()
+        + TimeDelta::hours(hours)
+}
+
+fn rollups(means: &[f64]) -> Vec<UtilizationRollup> {
+    means
+        .iter()
+        .zip(0..)
+        .map(|(mean, hour)| UtilizationRollup {
+            hour: at(hour),
+            samples: 1,
+            mean: *mean,
+            peak: *mean,
+        })
+        .collect()
+}
+
+fn link(name: &str) -> Link {
+    Link::new(
+        name.to_string(),
+        Uuid::new_v4(),
+        "Ethernet1".to_string(),
+        Uuid::new_v4(),
+        "Ethernet1".to_string(),
+    )
+}
+
+/// Three days rising 0.05 points an hour, 20 points busier from noon to 6pm
+fn daily_cycle() -> Vec<UtilizationRollup> {
+    let means: Vec<f64> = (0..72)
+        .map(|hour| {
+            let busy = if (12..18).contains(&(hour % 24)) {
+                20.0
+            } else {
+                0.0
+            };
+            0.05f64.mul_add(f64::from(hour), 40.0) + busy
+        })
+        .collect();
+    rollups(&means)
+}
+
+#[test]
+fn test_linear_forecast_extends_trend() {
+    let rising = forecast(&rollups(&[40.0, 41.0, 42.0, 43.0]), Method::Linear, at(3)).unwrap();
+    assert_eq!(rising.method, Method::Linear);
+    assert!((rising.utilization - 43.0).abs() < 1e-9);
+    assert!((rising.trend_per_week - 168.0).abs() < 1e-9);
+    assert_eq!(rising.reaches_70_at, Some(at(30)));
+    assert_eq!(rising.reaches_90_at, Some(at(50)));
+
+    let full = forecast(&rollups(&[95.0, 92.0]), Method::Linear, at(1)).unwrap();
+    assert_eq!(full.reaches_70_at, Some(at(1)));
+    assert_eq!(full.reaches_90_at, Some(at(1)));
+
+    let falling = forecast(&rollups(&[50.0, 40.0]), Method::Linear, at(1)).unwrap();
+    assert_eq!(falling.reaches_70_at, None);
+
+    // Too slow to cross within the horizon
+    let flat = forecast(&rollups(&[10.0, 10.001]), Method::Linear, at(1)).unwrap();
+    assert_eq!(flat.reaches_70_at, None);
+    assert!(forecast(&rollups(&[10.0]), Method::Linear, at(1)).is_none());
+}
+
+#[test]
+fn test_holt_winters_projects_busy_hours() {
+    let history = daily_cycle();
+
+    let seasonal = forecast(&history, Method::HoltWinters, at(71)).unwrap();
+    let linear = forecast(&history, Method::Linear, at(71)).unwrap();
+
+    assert_eq!(seasonal.method, Method::HoltWinters);
+    assert!(seasonal.trend_per_week > 0.0);
+    let busy = seasonal.reaches_70_at.unwrap();
+    assert!(busy > at(71));
+    assert!(busy < linear.reaches_70_at.unwrap());
+    assert!(seasonal.reaches_90_at.unwrap() > busy);
+
+    // Fewer than two days are fitted linearly
+    let short = forecast(&history[..30], Method::HoltWinters, at(29)).unwrap();
+    assert_eq!(short.method, Method::Linear);
+}
+
+#[test]
+fn test_hourly_series_repeats_previous_mean() {
+    let mut history = rollups(&[10.0, 20.0]);
+    history[1].hour = at(3);
+
+    assert_eq!(hourly_series(&history), [10.0, 10.0, 10.0, 20.0]);
+    assert!(hourly_series(&[]).is_empty());
+}
+
+#[test]
+fn test_build_report_orders_links_by_crossing() {
+    let (soon, later, quiet, single, unsampled) = (
+        link("soon"),
+        link("later"),
+        link("quiet"),
+        link("single"),
+        link("unsampled"),
+    );
+    let mut recent = rollups(&[40.0]);
+    recent[0].hour = at(2);
+    let histories = HashMap::from([
+        (soon.id, rollups(&[0.0, 80.0, 85.0])),
+        (later.id, rollups(&[0.0, 50.0, 51.0])),
+        (quiet.id, rollups(&[0.0, 20.0, 5.0])),
+        (single.id, recent),
+    ]);
+
+    let report = build_report(
+        &[unsampled, single, quiet, later, soon],
+        &histories,
+        Method::Linear,
+        at(1),
+        at(2),
+    );
+
+    let names: Vec<&str> = report.links.iter().map(|l| l.link.as_str()).collect();
+    assert_eq!(names, ["soon", "later", "quiet", "single"]);
+    // Hours before the window are left out
+    assert_eq!(report.links[0].hours, 2);
+    let soon = report.links[0].forecast.unwrap();
+    assert_eq!(soon.reaches_70_at, Some(at(2)));
+    assert_eq!(soon.reaches_90_at, Some(at(3)));
+    assert!(report.links[3].forecast.is_none());
+    let csv = report.to_csv();
+    assert!(csv.starts_with("link,bandwidth_bps,hours,method,"));
+    assert!(csv.contains(&format!(
+        "\nlater,,2,linear,51.00,168.00,{},{}\n",
+        at(21).to_rfc3339(),
+        at(41).to_rfc3339()
+    )));
+    assert!(csv.ends_with("\nsingle,,1,,,,,\n"));
+}
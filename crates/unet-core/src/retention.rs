@@ -0,0 +1,219 @@
+//! Per-class data retention and the pruning that enforces it
+//!
+//! [`RetentionConfig`] says how long each [`DataClass`] is kept: raw
+//! performance samples and their hourly rollups, change log (audit) events,
+//! and each node's configuration snapshot history. [`enforce`] prunes
+//! everything past its retention or, as a dry run, reports what it would
+//! prune. Raw samples are not lost outright: they are compacted into hourly
+//! rollups first.
+//!
+//! Every run that deletes data updates a [`RetentionStatus`] with the run's
+//! report and running totals of the rows and estimated bytes reclaimed. The
+//! estimate is the size of the deleted data; the database file itself only
+//! shrinks after a vacuum.
+
+use crate::config::RetentionConfig;
+use crate::config_changes;
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult, PruneStats};
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Settings namespace holding the enforced policy and the pruning totals
+const NAMESPACE: &str = "retention";
+/// Key of the [`RetentionStatus`] in [`NAMESPACE`]
+const STATUS_KEY: &str = "status";
+
+/// Class of data with its own retention
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DataClass {
+    /// Raw performance samples, compacted into rollups when they expire
+    RawMetrics,
+    /// Hourly performance rollups
+    Rollups,
+    /// Change log events
+    Audit,
+    /// Configuration snapshot history
+    ConfigSnapshots,
+}
+
+/// What one data class lost, or would lose, in a run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClassReport {
+    /// Data class pruned
+    pub class: DataClass,
+    /// Data older than this is pruned; `None` for count-based retention
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cutoff: Option<DateTime<Utc>>,
+    /// Entries kept per node; `None` for time-based retention
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keep: Option<usize>,
+    /// Rows or entries removed
+    pub rows: usize,
+    /// Estimated bytes removed
+    pub bytes: u64,
+}
+
+/// Outcome of one pruning run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetentionReport {
+    /// Whether nothing was deleted and the counts are what would be
+    pub dry_run: bool,
+    /// When the run started
+    pub ran_at: DateTime<Utc>,
+    /// Per-class results, in pruning order
+    pub classes: Vec<ClassReport>,
+    /// Rows or entries removed across every class
+    pub total_rows: usize,
+    /// Estimated bytes removed across every class
+    pub total_bytes: u64,
+}
+
+/// Enforced policy and what pruning has reclaimed so far
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RetentionStatus {
+    /// Policy the server enforces, recorded when it starts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy: Option<RetentionConfig>,
+    /// Most recent run that deleted data
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_run: Option<RetentionReport>,
+    /// Runs that deleted data
+    #[serde(default)]
+    pub runs: u64,
+    /// Rows or entries removed by every run
+    #[serde(default)]
+    pub reclaimed_rows: u64,
+    /// Estimated bytes removed by every run
+    #[serde(default)]
+    pub reclaimed_bytes: u64,
+}
+
+fn days(days: u32) -> TimeDelta {
+    TimeDelta::days(i64::from(days))
+}
+
+const fn class_report(
+    class: DataClass,
+    cutoff: Option<DateTime<Utc>>,
+    keep: Option<usize>,
+    stats: PruneStats,
+) -> ClassReport {
+    ClassReport {
+        class,
+        cutoff,
+        keep,
+        rows: stats.rows,
+        bytes: stats.bytes,
+    }
+}
+
+/// Prunes every data class past its retention at `now`; with `dry_run`,
+/// only reports what would be pruned
+///
+/// A run that deletes data is added to the stored [`RetentionStatus`].
+///
+/// # Errors
+/// Returns an error if any class cannot be measured or pruned, or the status
+/// cannot be updated. Classes pruned before the failure stay pruned.
+pub async fn enforce(
+    datastore: &dyn DataStore,
+    policy: &RetentionConfig,
+    now: DateTime<Utc>,
+    dry_run: bool,
+) -> DataStoreResult<RetentionReport> {
+    let raw_cutoff = now - days(policy.raw_metrics_days);
+    let rollup_cutoff = now - days(policy.rollups_days);
+    let audit_cutoff = now - days(policy.audit_days);
+
+    let classes = vec![
+        class_report(
+            DataClass::RawMetrics,
+            Some(raw_cutoff),
+            None,
+            datastore
+                .prune_performance_samples(raw_cutoff, dry_run)
+                .await?,
+        ),
+        class_report(
+            DataClass::Rollups,
+            Some(rollup_cutoff),
+            None,
+            datastore
+                .prune_performance_rollups(rollup_cutoff, dry_run)
+                .await?,
+        ),
+        class_report(
+            DataClass::Audit,
+            Some(audit_cutoff),
+            None,
+            datastore.prune_change_events(audit_cutoff, dry_run).await?,
+        ),
+        class_report(
+            DataClass::ConfigSnapshots,
+            None,
+            Some(policy.config_snapshots),
+            config_changes::prune_snapshot_history(datastore, policy.config_snapshots, dry_run)
+                .await?,
+        ),
+    ];
+    let report = RetentionReport {
+        dry_run,
+        ran_at: now,
+        total_rows: classes.iter().map(|class| class.rows).sum(),
+        total_bytes: classes.iter().map(|class| class.bytes).sum(),
+        classes,
+    };
+
+    if !dry_run && report.total_rows > 0 {
+        let mut status = retention_status(datastore).await?;
+        status.runs += 1;
+        status.reclaimed_rows = status
+            .reclaimed_rows
+            .saturating_add(u64::try_from(report.total_rows).unwrap_or(u64::MAX));
+        status.reclaimed_bytes = status.reclaimed_bytes.saturating_add(report.total_bytes);
+        status.last_run = Some(report.clone());
+        save_status(datastore, &status).await?;
+    }
+    Ok(report)
+}
+
+/// Returns the enforced policy and what pruning has reclaimed so far
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or the status is malformed.
+pub async fn retention_status(datastore: &dyn DataStore) -> DataStoreResult<RetentionStatus> {
+    datastore
+        .get_setting(NAMESPACE, STATUS_KEY)
+        .await?
+        .map(|value| {
+            serde_json::from_value(value).map_err(|e| DataStoreError::InternalError {
+                message: format!("stored retention status: {e}"),
+            })
+        })
+        .transpose()
+        .map(Option::unwrap_or_default)
+}
+
+/// Records the policy a server enforces, so dry runs elsewhere can use it
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or written.
+pub async fn record_policy(
+    datastore: &dyn DataStore,
+    policy: &RetentionConfig,
+) -> DataStoreResult<()> {
+    let mut status = retention_status(datastore).await?;
+    status.policy = Some(policy.clone());
+    save_status(datastore, &status).await
+}
+
+async fn save_status(datastore: &dyn DataStore, status: &RetentionStatus) -> DataStoreResult<()> {
+    let value = serde_json::to_value(status).map_err(|e| DataStoreError::InternalError {
+        message: format!("retention status: {e}"),
+    })?;
+    datastore.put_setting(NAMESPACE, STATUS_KEY, &value).await
+}
+
+#[cfg(test)]
+mod tests;
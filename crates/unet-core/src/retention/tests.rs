@@ -0,0 +1,112 @@
+use super::*;
+use crate::change_log::{ChangeEvent, ChangeType, EntityKind};
+use crate::datastore::sqlite::SqliteStore;
+use crate::datastore::sqlite::tests::setup::migrated_store;
+use crate::models::derived::PerformanceMetrics;
+use crate::models::{DeviceRole, Node, Vendor};
+
+fn cpu() -> PerformanceMetrics {
+    PerformanceMetrics {
+        cpu_utilization: Some(25),
+        memory_utilization: None,
+        total_memory: None,
+        used_memory: None,
+        load_average: None,
+    }
+}
+
+async fn seed(store: &SqliteStore, now: DateTime<Utc>) {
+    let node = Node::new(
+        "edge-1".to_string(),
+        "example.com".to_string(),
+        Vendor::Juniper,
+        DeviceRole::Router,
+    );
+    for age in [
+        TimeDelta::days(400),
+        TimeDelta::days(30),
+        TimeDelta::hours(1),
+    ] {
+        store
+            .record_performance_metrics(&node.id, now - age, &cpu())
+            .await
+            .unwrap();
+    }
+    for age in [TimeDelta::days(2000), TimeDelta::days(1)] {
+        let mut event = ChangeEvent::new(
+            EntityKind::Node,
+            node.id,
+            ChangeType::Updated,
+            serde_json::json!({ "name": "edge-1" }),
+        );
+        event.recorded_at = now - age;
+        store.append_change_event(&event).await.unwrap();
+    }
+    for hour in 0..3 {
+        let collected_at = now - TimeDelta::hours(3 - hour);
+        config_changes::record_snapshot(store, &node, format!("v{hour}\n"), collected_at)
+            .await
+            .unwrap();
+    }
+}
+
+fn policy() -> RetentionConfig {
+    RetentionConfig {
+        config_snapshots: 2,
+        ..RetentionConfig::default()
+    }
+}
+
+fn rows(report: &RetentionReport, class: DataClass) -> usize {
+    report
+        .classes
+        .iter()
+        .find(|report| report.class == class)
+        .unwrap()
+        .rows
+}
+
+#[tokio::test]
+async fn test_dry_run_reports_without_deleting() {
+    let store = migrated_store().await;
+    let now = Utc::now();
+    seed(&store, now).await;
+
+    let report = enforce(&store, &policy(), now, true).await.unwrap();
+
+    assert!(report.dry_run);
+    assert_eq!(rows(&report, DataClass::RawMetrics), 2);
+    assert_eq!(rows(&report, DataClass::Rollups), 0);
+    assert_eq!(rows(&report, DataClass::Audit), 1);
+    assert_eq!(rows(&report, DataClass::ConfigSnapshots), 1);
+    assert_eq!(report.total_rows, 4);
+    assert!(report.total_bytes > 0);
+    let again = enforce(&store, &policy(), now, true).await.unwrap();
+    assert_eq!(again, report);
+    assert_eq!(retention_status(&store).await.unwrap().runs, 0);
+}
+
+#[tokio::test]
+async fn test_enforce_prunes_and_totals_reclaimed_space() {
+    let store = migrated_store().await;
+    let now = Utc::now();
+    seed(&store, now).await;
+    record_policy(&store, &policy()).await.unwrap();
+
+    let report = enforce(&store, &policy(), now, false).await.unwrap();
+    assert_eq!(report.total_rows, 4);
+
+    // The expired samples became rollups, and the oldest of those expires next
+    let report = enforce(&store, &policy(), now, true).await.unwrap();
+    assert_eq!(rows(&report, DataClass::RawMetrics), 0);
+    assert_eq!(rows(&report, DataClass::Rollups), 1);
+    assert_eq!(rows(&report, DataClass::Audit), 0);
+    assert_eq!(rows(&report, DataClass::ConfigSnapshots), 0);
+
+    let status = retention_status(&store).await.unwrap();
+    assert_eq!(status.runs, 1);
+    assert_eq!(status.reclaimed_rows, 4);
+    assert!(status.reclaimed_bytes > 0);
+    assert_eq!(status.policy.unwrap().config_snapshots, 2);
+    assert!(!status.last_run.unwrap().dry_run);
+}
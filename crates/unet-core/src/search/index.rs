@@ -0,0 +1,208 @@
+//! Searchable documents of nodes, links, and locations
+//!
+//! [`SearchIndex`] keeps one document per entity from the change events, so
+//! indexed searches read the documents instead of listing every entity.
+
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use super::SearchEntity;
+use crate::change_log::{ChangeEvent, ChangeType, EntityKind, Projection};
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult};
+use crate::models::{Link, Location, Node};
+
+/// Settings namespace holding documents keyed by entity ID
+const INDEX_NAMESPACE: &str = "projection_search_index";
+
+/// A searchable field of an entity
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexedField {
+    /// Field name reported on a match
+    pub name: String,
+    /// Field value; empty for unset fields
+    pub value: String,
+    /// Whether the field identifies the entity; matches in other fields rank
+    /// lower
+    pub primary: bool,
+}
+
+impl IndexedField {
+    /// Creates a field identifying the entity
+    #[must_use]
+    pub fn primary(name: &str, value: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            value: value.into(),
+            primary: true,
+        }
+    }
+
+    /// Creates a secondary field
+    #[must_use]
+    pub fn secondary(name: &str, value: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            value: value.into(),
+            primary: false,
+        }
+    }
+}
+
+/// The searchable fields of one entity
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchDocument {
+    /// Kind of entity
+    pub entity_type: SearchEntity,
+    /// ID to open the entity with
+    pub id: String,
+    /// Display name of the entity
+    pub title: String,
+    /// Fields matched against queries
+    pub fields: Vec<IndexedField>,
+}
+
+impl SearchDocument {
+    /// The document of a node
+    #[must_use]
+    pub fn node(node: &Node) -> Self {
+        let id = node.id.to_string();
+        Self {
+            entity_type: SearchEntity::Node,
+            title: node.name.clone(),
+            fields: vec![
+                IndexedField::primary("name", node.name.as_str()),
+                IndexedField::primary("fqdn", node.fqdn.as_str()),
+                IndexedField::primary("id", id.as_str()),
+                IndexedField::secondary(
+                    "management_ip",
+                    node.management_ip
+                        .map(|ip| ip.to_string())
+                        .unwrap_or_default(),
+                ),
+                IndexedField::secondary("model", node.model.as_str()),
+                IndexedField::secondary(
+                    "serial_number",
+                    node.serial_number.clone().unwrap_or_default(),
+                ),
+                IndexedField::secondary("asset_tag", node.asset_tag.clone().unwrap_or_default()),
+            ],
+            id,
+        }
+    }
+
+    /// The document of a link
+    #[must_use]
+    pub fn link(link: &Link) -> Self {
+        let id = link.id.to_string();
+        Self {
+            entity_type: SearchEntity::Link,
+            title: link.name.clone(),
+            fields: vec![
+                IndexedField::primary("name", link.name.as_str()),
+                IndexedField::primary("id", id.as_str()),
+                IndexedField::primary("circuit_id", link.circuit_id.clone().unwrap_or_default()),
+                IndexedField::secondary("provider", link.provider.clone().unwrap_or_default()),
+                IndexedField::secondary("node_a_interface", link.node_a_interface.as_str()),
+                IndexedField::secondary(
+                    "node_z_interface",
+                    link.node_z_interface.clone().unwrap_or_default(),
+                ),
+                IndexedField::secondary(
+                    "description",
+                    link.description.clone().unwrap_or_default(),
+                ),
+            ],
+            id,
+        }
+    }
+
+    /// The document of a location
+    #[must_use]
+    pub fn location(location: &Location) -> Self {
+        let id = location.id.to_string();
+        Self {
+            entity_type: SearchEntity::Location,
+            title: location.name.clone(),
+            fields: vec![
+                IndexedField::primary("name", location.name.as_str()),
+                IndexedField::primary("path", location.path.as_str()),
+                IndexedField::primary("id", id.as_str()),
+                IndexedField::secondary(
+                    "description",
+                    location.description.clone().unwrap_or_default(),
+                ),
+                IndexedField::secondary("address", location.address.clone().unwrap_or_default()),
+            ],
+            id,
+        }
+    }
+}
+
+/// Search documents of nodes, links, and locations, readable with
+/// [`SearchIndex::documents`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SearchIndex;
+
+impl SearchIndex {
+    /// Name of the projection
+    pub const NAME: &'static str = "search_index";
+
+    /// Every stored document
+    ///
+    /// # Errors
+    /// Returns an error if the datastore cannot be read or a document is
+    /// malformed.
+    pub async fn documents(datastore: &dyn DataStore) -> DataStoreResult<Vec<SearchDocument>> {
+        datastore
+            .list_settings(INDEX_NAMESPACE)
+            .await?
+            .into_iter()
+            .map(|(key, value)| {
+                serde_json::from_value(value).map_err(|e| DataStoreError::InternalError {
+                    message: format!("stored search document {key}: {e}"),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Reads the entity an event was written with
+fn payload<T: DeserializeOwned>(event: &ChangeEvent) -> DataStoreResult<T> {
+    serde_json::from_value(event.payload.clone()).map_err(|e| DataStoreError::InternalError {
+        message: format!("payload of change event {}: {e}", event.id),
+    })
+}
+
+#[async_trait]
+impl Projection for SearchIndex {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    async fn reset(&self, datastore: &dyn DataStore) -> DataStoreResult<()> {
+        for (key, _) in datastore.list_settings(INDEX_NAMESPACE).await? {
+            datastore.delete_setting(INDEX_NAMESPACE, &key).await?;
+        }
+        Ok(())
+    }
+
+    async fn apply(&self, datastore: &dyn DataStore, event: &ChangeEvent) -> DataStoreResult<()> {
+        let key = event.entity_id.to_string();
+        if event.change == ChangeType::Deleted {
+            return match datastore.delete_setting(INDEX_NAMESPACE, &key).await {
+                Err(DataStoreError::NotFound { .. }) => Ok(()),
+                result => result,
+            };
+        }
+        let document = match event.entity {
+            EntityKind::Node => SearchDocument::node(&payload(event)?),
+            EntityKind::Link => SearchDocument::link(&payload(event)?),
+            EntityKind::Location => SearchDocument::location(&payload(event)?),
+        };
+        let value = serde_json::to_value(&document).map_err(|e| DataStoreError::InternalError {
+            message: format!("search document {key}: {e}"),
+        })?;
+        datastore.put_setting(INDEX_NAMESPACE, &key, &value).await
+    }
+}
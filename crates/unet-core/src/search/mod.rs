@@ -0,0 +1,236 @@
+//! Search across entity types
+//!
+//! Matches a query case-insensitively against the identifying fields of
+//! nodes, links, locations, policy rules, and golden configuration snapshots
+//! and templates. Each entity is scored by its best-matching field: an exact
+//! match ranks above a prefix, which ranks above a match at a word start,
+//! which ranks above any other substring. Matches in an entity's name or ID
+//! rank above matches in secondary fields such as descriptions.
+//!
+//! Indexed queries read nodes, links, and locations from the [`SearchIndex`]
+//! projection of the change log instead of listing every entity.
+
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult, QueryOptions};
+use crate::golden::{GoldenSource, list_golden};
+use crate::policy::PolicyRule;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+
+mod index;
+mod scoring;
+
+pub use index::{IndexedField, SearchDocument, SearchIndex};
+use scoring::best_match;
+
+/// Results returned when no limit is given
+pub const DEFAULT_LIMIT: usize = 20;
+/// Largest number of results returned
+pub const MAX_LIMIT: usize = 100;
+
+/// Kind of entity a result refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchEntity {
+    /// A node
+    Node,
+    /// A link
+    Link,
+    /// A location
+    Location,
+    /// A policy rule
+    Policy,
+    /// A golden configuration snapshot or template
+    ConfigSnapshot,
+}
+
+impl SearchEntity {
+    /// Every entity type, in result tie-break order
+    pub const ALL: [Self; 5] = [
+        Self::Node,
+        Self::Link,
+        Self::Location,
+        Self::Policy,
+        Self::ConfigSnapshot,
+    ];
+}
+
+impl Display for SearchEntity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Node => write!(f, "node"),
+            Self::Link => write!(f, "link"),
+            Self::Location => write!(f, "location"),
+            Self::Policy => write!(f, "policy"),
+            Self::ConfigSnapshot => write!(f, "config_snapshot"),
+        }
+    }
+}
+
+impl FromStr for SearchEntity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|entity| entity.to_string() == s.trim().to_lowercase())
+            .ok_or_else(|| format!("Invalid search entity type: {s}"))
+    }
+}
+
+/// A search request
+#[derive(Debug, Clone)]
+pub struct SearchQuery {
+    /// Text to find
+    pub text: String,
+    /// Entity types to search; empty searches all
+    pub entities: Vec<SearchEntity>,
+    /// Results to return, at most [`MAX_LIMIT`]
+    pub limit: usize,
+    /// Read nodes, links, and locations from the [`SearchIndex`]
+    pub indexed: bool,
+}
+
+impl SearchQuery {
+    /// Creates a query over all entity types with the default limit
+    #[must_use]
+    pub fn new(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            entities: Vec::new(),
+            limit: DEFAULT_LIMIT,
+            indexed: false,
+        }
+    }
+
+    fn includes(&self, entity: SearchEntity) -> bool {
+        self.entities.is_empty() || self.entities.contains(&entity)
+    }
+}
+
+/// One matching entity
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchHit {
+    /// Kind of entity
+    pub entity_type: SearchEntity,
+    /// ID to open the entity with: a UUID, a policy rule ID, or a golden
+    /// configuration key such as `role:router`
+    pub id: String,
+    /// Display name of the entity
+    pub title: String,
+    /// Field that matched
+    pub field: String,
+    /// The matching value, or the matching line of longer text
+    pub context: String,
+    /// Rank; higher is better
+    pub score: u32,
+}
+
+/// Searches nodes, links, locations, `policies`, and golden configurations
+///
+/// Results are ordered by score, then entity type, then title.
+///
+/// # Errors
+/// Returns a validation error if the query is blank, or an error if entities
+/// cannot be read.
+pub async fn search(
+    datastore: &dyn DataStore,
+    policies: &[PolicyRule],
+    query: &SearchQuery,
+) -> DataStoreResult<Vec<SearchHit>> {
+    let needle = query.text.trim().to_lowercase();
+    if needle.is_empty() {
+        return Err(DataStoreError::ValidationError {
+            message: "Search query must not be empty".to_string(),
+        });
+    }
+    let mut hits: Vec<SearchHit> = inventory_documents(datastore, query)
+        .await?
+        .iter()
+        .filter_map(|document| best_match(document, &needle))
+        .collect();
+
+    if query.includes(SearchEntity::Policy) {
+        for (index, rule) in policies.iter().enumerate() {
+            let id = rule
+                .id
+                .clone()
+                .unwrap_or_else(|| format!("rule-{}", index + 1));
+            let document = SearchDocument {
+                entity_type: SearchEntity::Policy,
+                title: id.clone(),
+                fields: vec![
+                    IndexedField::primary("id", id.as_str()),
+                    IndexedField::secondary("rule", rule.to_string()),
+                ],
+                id,
+            };
+            hits.extend(best_match(&document, &needle));
+        }
+    }
+
+    if query.includes(SearchEntity::ConfigSnapshot) {
+        let golden = match list_golden(datastore).await {
+            Ok(golden) => golden,
+            Err(DataStoreError::UnsupportedOperation { .. }) => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        for config in golden {
+            let key = config.key();
+            let (field, text) = match &config.source {
+                GoldenSource::Snapshot { config } => ("config", config),
+                GoldenSource::Template { template, .. } => ("template", template),
+            };
+            let document = SearchDocument {
+                entity_type: SearchEntity::ConfigSnapshot,
+                title: key.clone(),
+                fields: vec![
+                    IndexedField::primary("key", key.as_str()),
+                    IndexedField::secondary(field, text.as_str()),
+                ],
+                id: key,
+            };
+            hits.extend(best_match(&document, &needle));
+        }
+    }
+
+    hits.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then(a.entity_type.cmp(&b.entity_type))
+            .then_with(|| a.title.cmp(&b.title))
+    });
+    hits.truncate(query.limit.min(MAX_LIMIT));
+    Ok(hits)
+}
+
+/// Documents of the nodes, links, and locations the query includes, from
+/// the index or from the entities themselves
+async fn inventory_documents(
+    datastore: &dyn DataStore,
+    query: &SearchQuery,
+) -> DataStoreResult<Vec<SearchDocument>> {
+    if query.indexed {
+        let mut documents = SearchIndex::documents(datastore).await?;
+        documents.retain(|document| query.includes(document.entity_type));
+        return Ok(documents);
+    }
+    let options = QueryOptions::default();
+    let mut documents = Vec::new();
+    if query.includes(SearchEntity::Node) {
+        let nodes = datastore.list_nodes(&options).await?.items;
+        documents.extend(nodes.iter().map(SearchDocument::node));
+    }
+    if query.includes(SearchEntity::Link) {
+        let links = datastore.list_links(&options).await?.items;
+        documents.extend(links.iter().map(SearchDocument::link));
+    }
+    if query.includes(SearchEntity::Location) {
+        let locations = datastore.list_locations(&options).await?.items;
+        documents.extend(locations.iter().map(SearchDocument::location));
+    }
+    Ok(documents)
+}
+
+#[cfg(test)]
+mod tests;
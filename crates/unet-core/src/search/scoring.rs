@@ -0,0 +1,65 @@
+//! Match scoring and context snippets for search results
+
+use super::SearchHit;
+use super::index::SearchDocument;
+
+/// Longest context snippet, in characters
+const CONTEXT_CHARS: usize = 120;
+/// Score lost by matches in secondary fields
+const SECONDARY_PENALTY: u32 = 20;
+
+/// Scores how well `value` matches the lowercase `needle`
+pub(super) fn score_text(value: &str, needle: &str) -> Option<u32> {
+    let value = value.to_lowercase();
+    if value == needle {
+        return Some(100);
+    }
+    if value.starts_with(needle) {
+        return Some(80);
+    }
+    let position = value.find(needle)?;
+    let at_word_start = value[..position]
+        .chars()
+        .next_back()
+        .is_none_or(|c| !c.is_alphanumeric());
+    Some(if at_word_start { 60 } else { 40 })
+}
+
+/// Shortens text to the context snippet length
+fn snippet(text: &str) -> String {
+    let text = text.trim();
+    if text.chars().count() <= CONTEXT_CHARS {
+        return text.to_string();
+    }
+    let mut short: String = text.chars().take(CONTEXT_CHARS - 1).collect();
+    short.push('…');
+    short
+}
+
+/// Scores an entity by its best field; multi-line values match per line
+pub(super) fn best_match(document: &SearchDocument, needle: &str) -> Option<SearchHit> {
+    let mut best: Option<(u32, &str, &str)> = None;
+    for field in &document.fields {
+        for line in field.value.lines() {
+            let Some(score) = score_text(line.trim(), needle) else {
+                continue;
+            };
+            let score = if field.primary {
+                score
+            } else {
+                score.saturating_sub(SECONDARY_PENALTY)
+            };
+            if best.is_none_or(|(best_score, _, _)| score > best_score) {
+                best = Some((score, &field.name, line));
+            }
+        }
+    }
+    best.map(|(score, field, line)| SearchHit {
+        entity_type: document.entity_type,
+        id: document.id.clone(),
+        title: document.title.clone(),
+        field: field.to_string(),
+        context: snippet(line),
+        score,
+    })
+}
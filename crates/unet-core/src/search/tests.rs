@@ -0,0 +1,132 @@
+use super::scoring::score_text;
+use super::*;
+use crate::change_log::ChangeLogStore;
+use crate::datastore::sqlite::tests::setup::migrated_store;
+use crate::golden::{GoldenConfig, GoldenScope, save_golden};
+use crate::models::{DeviceRole, Link, Location, Node, Vendor};
+use crate::policy::{Action, Condition, FieldRef, Value};
+use std::sync::Arc;
+
+async fn create_node(store: &dyn DataStore, name: &str) -> Node {
+    let node = Node::new(
+        name.to_string(),
+        "example.com".to_string(),
+        Vendor::Arista,
+        DeviceRole::Switch,
+    );
+    store.create_node(&node).await.unwrap()
+}
+
+fn rule(id: &str, node_name: &str) -> PolicyRule {
+    PolicyRule {
+        id: Some(id.to_string()),
+        condition: Condition::Comparison {
+            field: FieldRef {
+                path: vec!["node".to_string(), "name".to_string()],
+            },
+            operator: crate::policy::ComparisonOperator::Equal,
+            value: Value::String(node_name.to_string()),
+        },
+        action: Action::Assert {
+            field: FieldRef {
+                path: vec!["vlans".to_string(), "consistent".to_string()],
+            },
+            expected: Value::Boolean(true),
+        },
+    }
+}
+
+#[test]
+fn test_score_prefers_exact_then_prefix_then_word_start() {
+    assert_eq!(score_text("core-sw-01", "core-sw-01"), Some(100));
+    assert_eq!(score_text("Core-SW-01.example.com", "core-sw-01"), Some(80));
+    assert_eq!(score_text("uplink to core-sw-01", "core-sw-01"), Some(60));
+    assert_eq!(score_text("score-sw-01", "core-sw-01"), Some(40));
+    assert_eq!(score_text("edge-1", "core"), None);
+}
+
+#[test]
+fn test_entity_type_round_trips() {
+    for entity in SearchEntity::ALL {
+        assert_eq!(entity.to_string().parse::<SearchEntity>(), Ok(entity));
+    }
+    assert!("switch".parse::<SearchEntity>().is_err());
+}
+
+#[tokio::test]
+async fn test_search_ranks_across_entity_types() {
+    let store = migrated_store().await;
+    let core = create_node(&store, "core-sw-01").await;
+    let edge = create_node(&store, "edge-sw-01").await;
+    let mut link = Link::new(
+        "edge-uplink".to_string(),
+        edge.id,
+        "Ethernet1".to_string(),
+        core.id,
+        "Ethernet9".to_string(),
+    );
+    link.description = Some("uplink to core-sw-01".to_string());
+    store.create_link(&link).await.unwrap();
+    let site = Location::new_root("core-room".to_string(), "room".to_string());
+    store.create_location(&site).await.unwrap();
+    let snapshot = GoldenSource::Snapshot {
+        config: "hostname core-sw-01\nntp server 10.0.0.1\n".to_string(),
+    };
+    let golden = GoldenConfig::new(GoldenScope::Node, &core.id.to_string(), snapshot).unwrap();
+    save_golden(&store, &golden).await.unwrap();
+    let policies = [rule("core-vlans", "core-sw-01")];
+
+    let hits = search(&store, &policies, &SearchQuery::new("core-sw-01"))
+        .await
+        .unwrap();
+    assert_eq!(hits[0].entity_type, SearchEntity::Node);
+    assert_eq!(hits[0].id, core.id.to_string());
+    assert_eq!(hits[0].score, 100);
+    let types: Vec<SearchEntity> = hits.iter().map(|hit| hit.entity_type).collect();
+    assert!(types.contains(&SearchEntity::Link));
+    assert!(types.contains(&SearchEntity::Policy));
+    assert!(!types.contains(&SearchEntity::Location));
+    let config = hits
+        .iter()
+        .find(|hit| hit.entity_type == SearchEntity::ConfigSnapshot)
+        .unwrap();
+    assert_eq!(config.id, golden.key());
+    assert_eq!(config.context, "hostname core-sw-01");
+
+    let query = SearchQuery {
+        entities: vec![SearchEntity::Location],
+        ..SearchQuery::new("core")
+    };
+    let hits = search(&store, &policies, &query).await.unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].title, "core-room");
+
+    assert!(
+        search(&store, &policies, &SearchQuery::new("  "))
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_indexed_search_follows_change_events() {
+    let store = migrated_store().await.with_change_log(true);
+    let store = ChangeLogStore::with_builtins(Arc::new(store))
+        .await
+        .unwrap();
+    let core = create_node(&store, "core-sw-01").await;
+    let edge = create_node(&store, "edge-sw-01").await;
+    let mut renamed = core.clone();
+    renamed.name = "core-sw-02".to_string();
+    store.update_node(&renamed).await.unwrap();
+    store.delete_node(&edge.id).await.unwrap();
+
+    let query = SearchQuery {
+        indexed: true,
+        ..SearchQuery::new("sw-0")
+    };
+    let hits = search(&store, &[], &query).await.unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].id, core.id.to_string());
+    assert_eq!(hits[0].title, "core-sw-02");
+}
@@ -0,0 +1,281 @@
+//! Field-level encryption of sensitive `custom_data` values
+//!
+//! `secrets.encrypted_fields` lists dotted `custom_data` paths, such as
+//! `snmp.community`, whose values are encrypted with the [`SecretManager`]
+//! master key (`secrets.master_key`) before they reach the database. An
+//! encrypted value is stored as a string: [`ENCRYPTED_PREFIX`] followed by the
+//! base64 nonce and ChaCha20-Poly1305 ciphertext of the value's JSON.
+//!
+//! [`EncryptedFieldsStore`] wraps a `DataStore` to seal the fields on every
+//! node write. Reads are decrypted for trusted callers, such as the poller
+//! and the local CLI, and left encrypted otherwise; the server only hands
+//! plaintext to the admin role. Values written before a path was listed stay
+//! in plaintext until the node is next saved. Encryption needs the `secrets`
+//! feature.
+
+mod store;
+
+pub use store::EncryptedFieldsStore;
+
+use crate::config::SecretsConfig;
+use crate::datastore::{DataStoreError, DataStoreResult};
+#[cfg(feature = "secrets")]
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+#[cfg(feature = "secrets")]
+use chacha20poly1305::{
+    ChaCha20Poly1305, KeyInit as _, Nonce,
+    aead::{Aead as _, AeadCore as _, OsRng},
+};
+use serde_json::Value;
+#[cfg(feature = "secrets")]
+use sha2::{Digest as _, Sha256};
+use std::collections::BTreeMap;
+
+/// Marks a `custom_data` string as an encrypted value
+pub const ENCRYPTED_PREFIX: &str = "enc:v1:";
+
+/// Length of the nonce stored in front of each ciphertext
+#[cfg(feature = "secrets")]
+const NONCE_LEN: usize = 12;
+
+/// Encrypts and decrypts values with the configured master key
+///
+/// The cipher key is the SHA-256 digest of the master key, so the master key
+/// should be a long random string rather than a memorable password.
+pub struct SecretManager {
+    #[cfg(feature = "secrets")]
+    cipher: ChaCha20Poly1305,
+}
+
+impl std::fmt::Debug for SecretManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SecretManager").finish_non_exhaustive()
+    }
+}
+
+impl SecretManager {
+    /// Creates a manager for a master key
+    ///
+    /// # Errors
+    /// Returns a validation error if the key is empty.
+    #[cfg(feature = "secrets")]
+    pub fn new(master_key: &str) -> DataStoreResult<Self> {
+        if master_key.trim().is_empty() {
+            return Err(DataStoreError::ValidationError {
+                message: "Secrets master key must not be empty".to_string(),
+            });
+        }
+        let key = Sha256::digest(master_key.as_bytes());
+        Ok(Self {
+            cipher: ChaCha20Poly1305::new(&key),
+        })
+    }
+
+    /// Creates a manager for a master key
+    ///
+    /// # Errors
+    /// Returns an unsupported-operation error if built without the `secrets` feature.
+    #[cfg(not(feature = "secrets"))]
+    pub fn new(_master_key: &str) -> DataStoreResult<Self> {
+        Err(DataStoreError::UnsupportedOperation {
+            operation: "field encryption without the `secrets` feature".to_string(),
+        })
+    }
+
+    /// Whether a value was produced by [`SecretManager::encrypt`]
+    #[must_use]
+    pub fn is_encrypted(value: &Value) -> bool {
+        value
+            .as_str()
+            .is_some_and(|text| text.starts_with(ENCRYPTED_PREFIX))
+    }
+
+    /// Encrypts a JSON value into an [`ENCRYPTED_PREFIX`] string
+    ///
+    /// # Errors
+    /// Returns an internal error if encryption fails.
+    #[cfg(feature = "secrets")]
+    pub fn encrypt(&self, value: &Value) -> DataStoreResult<Value> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let plaintext = serde_json::to_vec(value).map_err(|e| DataStoreError::InternalError {
+            message: format!("Failed to serialize value for encryption: {e}"),
+        })?;
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, plaintext.as_slice())
+            .map_err(|_| DataStoreError::InternalError {
+                message: "Failed to encrypt value".to_string(),
+            })?;
+        let mut sealed = nonce.to_vec();
+        sealed.extend_from_slice(&ciphertext);
+        Ok(Value::String(format!(
+            "{ENCRYPTED_PREFIX}{}",
+            STANDARD.encode(sealed)
+        )))
+    }
+
+    /// Encrypts a JSON value into an [`ENCRYPTED_PREFIX`] string
+    ///
+    /// # Errors
+    /// Returns an unsupported-operation error if built without the `secrets` feature.
+    #[cfg(not(feature = "secrets"))]
+    pub fn encrypt(&self, _value: &Value) -> DataStoreResult<Value> {
+        Err(DataStoreError::UnsupportedOperation {
+            operation: "field encryption without the `secrets` feature".to_string(),
+        })
+    }
+
+    /// Decrypts a value produced by [`SecretManager::encrypt`]
+    ///
+    /// # Errors
+    /// Returns an internal error if the value is not encrypted, was encrypted
+    /// with another master key, or has been altered.
+    #[cfg(feature = "secrets")]
+    pub fn decrypt(&self, value: &Value) -> DataStoreResult<Value> {
+        let failed = |reason: &str| DataStoreError::InternalError {
+            message: format!("Failed to decrypt value: {reason}"),
+        };
+        let encoded = value
+            .as_str()
+            .and_then(|text| text.strip_prefix(ENCRYPTED_PREFIX))
+            .ok_or_else(|| failed("not an encrypted value"))?;
+        let sealed = STANDARD
+            .decode(encoded)
+            .map_err(|_| failed("invalid encoding"))?;
+        if sealed.len() < NONCE_LEN {
+            return Err(failed("truncated value"));
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+        let plaintext = self
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| failed("wrong master key or altered value"))?;
+        serde_json::from_slice(&plaintext).map_err(|_| failed("invalid plaintext"))
+    }
+
+    /// Decrypts a value produced by [`SecretManager::encrypt`]
+    ///
+    /// # Errors
+    /// Returns an unsupported-operation error if built without the `secrets` feature.
+    #[cfg(not(feature = "secrets"))]
+    pub fn decrypt(&self, _value: &Value) -> DataStoreResult<Value> {
+        Err(DataStoreError::UnsupportedOperation {
+            operation: "field encryption without the `secrets` feature".to_string(),
+        })
+    }
+}
+
+/// The `custom_data` paths to encrypt and the manager encrypting them
+#[derive(Debug)]
+pub struct FieldEncryption {
+    secrets: SecretManager,
+    paths: Vec<String>,
+}
+
+impl FieldEncryption {
+    /// Encrypts `paths` (dotted `custom_data` paths) with `secrets`
+    #[must_use]
+    pub const fn new(secrets: SecretManager, paths: Vec<String>) -> Self {
+        Self { secrets, paths }
+    }
+
+    /// Builds field encryption from `secrets`, or `None` when no fields are listed
+    ///
+    /// # Errors
+    /// Returns a validation error if fields are listed without a master key,
+    /// or an unsupported-operation error without the `secrets` feature.
+    pub fn from_config(config: &SecretsConfig) -> DataStoreResult<Option<Self>> {
+        if config.encrypted_fields.is_empty() {
+            return Ok(None);
+        }
+        let master_key =
+            config
+                .master_key
+                .as_deref()
+                .ok_or_else(|| DataStoreError::ValidationError {
+                    message: "secrets.encrypted_fields requires secrets.master_key".to_string(),
+                })?;
+        Ok(Some(Self::new(
+            SecretManager::new(master_key)?,
+            config.encrypted_fields.clone(),
+        )))
+    }
+
+    /// Dotted `custom_data` paths that are encrypted
+    #[must_use]
+    pub fn paths(&self) -> &[String] {
+        &self.paths
+    }
+
+    /// Encrypts the listed fields of `custom_data` that are still plaintext
+    ///
+    /// Fields that already look encrypted are kept only if they decrypt with
+    /// the master key, so a client cannot store a value that no one can read
+    /// back.
+    ///
+    /// # Errors
+    /// Returns a validation error if an encrypted-looking field does not
+    /// decrypt with the master key, or an error if encryption fails.
+    pub fn seal(&self, custom_data: &mut Value) -> DataStoreResult<()> {
+        for path in &self.paths {
+            if let Some(field) = custom_data.pointer_mut(&pointer(path)) {
+                if SecretManager::is_encrypted(field) {
+                    self.secrets
+                        .decrypt(field)
+                        .map_err(|e| DataStoreError::ValidationError {
+                            message: format!("custom_data.{path}: {e}"),
+                        })?;
+                } else if !field.is_null() {
+                    *field = self.secrets.encrypt(field)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Decrypts the listed fields of `custom_data`; plaintext values are kept
+    ///
+    /// # Errors
+    /// Returns an error if a field cannot be decrypted.
+    pub fn open(&self, custom_data: &mut Value) -> DataStoreResult<()> {
+        for path in &self.paths {
+            if let Some(field) = custom_data.pointer_mut(&pointer(path)) {
+                if SecretManager::is_encrypted(field) {
+                    *field =
+                        self.secrets
+                            .decrypt(field)
+                            .map_err(|e| DataStoreError::InternalError {
+                                message: format!("custom_data.{path}: {e}"),
+                            })?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Decrypted values of the listed fields present in `custom_data`, by path
+    ///
+    /// # Errors
+    /// Returns an error if a field cannot be decrypted.
+    pub fn opened(&self, custom_data: &Value) -> DataStoreResult<BTreeMap<String, Value>> {
+        let mut opened = custom_data.clone();
+        self.open(&mut opened)?;
+        Ok(self
+            .paths
+            .iter()
+            .filter_map(|path| {
+                opened
+                    .pointer(&pointer(path))
+                    .map(|value| (path.clone(), value.clone()))
+            })
+            .collect())
+    }
+}
+
+/// JSON pointer for a dotted `custom_data` path
+fn pointer(path: &str) -> String {
+    format!("/{}", path.replace('.', "/"))
+}
+
+#[cfg(all(test, feature = "secrets"))]
+mod tests;
@@ -0,0 +1,412 @@
+//! `DataStore` wrapper encrypting `custom_data` fields of nodes
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::FieldEncryption;
+use crate::change_log::{ChangeEvent, EventFilter};
+use crate::datastore::{
+    BatchOperation, BatchResult, DataStore, DataStoreResult, PagedResult, PruneStats, QueryOptions,
+    Transaction,
+};
+use crate::models::derived::{
+    InterfaceStatus, MetricPoint, MetricQuery, NodeStatus, PerformanceMetrics,
+};
+use crate::models::{Link, Location, Node};
+use crate::policy::PolicyExecutionResult;
+use crate::snmp::shards::ShardLease;
+
+/// Encrypts the configured `custom_data` fields of every node written
+///
+/// Nodes read back are decrypted when the store was created with
+/// `decrypt_reads`, and keep their encrypted values otherwise. Writes made
+/// through [`DataStore::begin_transaction`] bypass the wrapper.
+pub struct EncryptedFieldsStore {
+    inner: Arc<dyn DataStore>,
+    fields: Arc<FieldEncryption>,
+    decrypt_reads: bool,
+}
+
+impl EncryptedFieldsStore {
+    /// Wraps `inner`, decrypting nodes on read if `decrypt_reads` is set
+    #[must_use]
+    pub fn new(
+        inner: Arc<dyn DataStore>,
+        fields: Arc<FieldEncryption>,
+        decrypt_reads: bool,
+    ) -> Self {
+        Self {
+            inner,
+            fields,
+            decrypt_reads,
+        }
+    }
+
+    fn seal(&self, node: &Node) -> DataStoreResult<Node> {
+        let mut sealed = node.clone();
+        self.fields.seal(&mut sealed.custom_data)?;
+        Ok(sealed)
+    }
+
+    fn open(&self, mut node: Node) -> DataStoreResult<Node> {
+        if self.decrypt_reads {
+            self.fields.open(&mut node.custom_data)?;
+        }
+        Ok(node)
+    }
+
+    fn open_all(&self, nodes: Vec<Node>) -> DataStoreResult<Vec<Node>> {
+        nodes.into_iter().map(|node| self.open(node)).collect()
+    }
+}
+
+#[async_trait]
+impl DataStore for EncryptedFieldsStore {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    async fn health_check(&self) -> DataStoreResult<()> {
+        self.inner.health_check().await
+    }
+
+    async fn begin_transaction(&self) -> DataStoreResult<Box<dyn Transaction>> {
+        self.inner.begin_transaction().await
+    }
+
+    async fn create_node(&self, node: &Node) -> DataStoreResult<Node> {
+        let created = self.inner.create_node(&self.seal(node)?).await?;
+        self.open(created)
+    }
+
+    async fn get_node(&self, id: &Uuid) -> DataStoreResult<Option<Node>> {
+        self.inner
+            .get_node(id)
+            .await?
+            .map(|node| self.open(node))
+            .transpose()
+    }
+
+    async fn list_nodes(&self, options: &QueryOptions) -> DataStoreResult<PagedResult<Node>> {
+        let mut page = self.inner.list_nodes(options).await?;
+        page.items = self.open_all(page.items)?;
+        Ok(page)
+    }
+
+    async fn update_node(&self, node: &Node) -> DataStoreResult<Node> {
+        let updated = self.inner.update_node(&self.seal(node)?).await?;
+        self.open(updated)
+    }
+
+    async fn delete_node(&self, id: &Uuid) -> DataStoreResult<()> {
+        self.inner.delete_node(id).await
+    }
+
+    async fn get_nodes_by_location(&self, location_id: &Uuid) -> DataStoreResult<Vec<Node>> {
+        let nodes = self.inner.get_nodes_by_location(location_id).await?;
+        self.open_all(nodes)
+    }
+
+    async fn search_nodes_by_name(&self, name: &str) -> DataStoreResult<Vec<Node>> {
+        let nodes = self.inner.search_nodes_by_name(name).await?;
+        self.open_all(nodes)
+    }
+
+    async fn create_link(&self, link: &Link) -> DataStoreResult<Link> {
+        self.inner.create_link(link).await
+    }
+
+    async fn get_link(&self, id: &Uuid) -> DataStoreResult<Option<Link>> {
+        self.inner.get_link(id).await
+    }
+
+    async fn list_links(&self, options: &QueryOptions) -> DataStoreResult<PagedResult<Link>> {
+        self.inner.list_links(options).await
+    }
+
+    async fn update_link(&self, link: &Link) -> DataStoreResult<Link> {
+        self.inner.update_link(link).await
+    }
+
+    async fn delete_link(&self, id: &Uuid) -> DataStoreResult<()> {
+        self.inner.delete_link(id).await
+    }
+
+    async fn get_links_for_node(&self, node_id: &Uuid) -> DataStoreResult<Vec<Link>> {
+        self.inner.get_links_for_node(node_id).await
+    }
+
+    async fn get_links_between_nodes(
+        &self,
+        first_node_id: &Uuid,
+        second_node_id: &Uuid,
+    ) -> DataStoreResult<Vec<Link>> {
+        self.inner
+            .get_links_between_nodes(first_node_id, second_node_id)
+            .await
+    }
+
+    async fn create_location(&self, location: &Location) -> DataStoreResult<Location> {
+        self.inner.create_location(location).await
+    }
+
+    async fn get_location(&self, id: &Uuid) -> DataStoreResult<Option<Location>> {
+        self.inner.get_location(id).await
+    }
+
+    async fn list_locations(
+        &self,
+        options: &QueryOptions,
+    ) -> DataStoreResult<PagedResult<Location>> {
+        self.inner.list_locations(options).await
+    }
+
+    async fn update_location(&self, location: &Location) -> DataStoreResult<Location> {
+        self.inner.update_location(location).await
+    }
+
+    async fn delete_location(&self, id: &Uuid) -> DataStoreResult<()> {
+        self.inner.delete_location(id).await
+    }
+
+    async fn create_vendor(&self, name: &str) -> DataStoreResult<()> {
+        self.inner.create_vendor(name).await
+    }
+
+    async fn list_vendors(&self) -> DataStoreResult<Vec<String>> {
+        self.inner.list_vendors().await
+    }
+
+    async fn delete_vendor(&self, name: &str) -> DataStoreResult<()> {
+        self.inner.delete_vendor(name).await
+    }
+
+    async fn get_setting(
+        &self,
+        namespace: &str,
+        key: &str,
+    ) -> DataStoreResult<Option<serde_json::Value>> {
+        self.inner.get_setting(namespace, key).await
+    }
+
+    async fn list_settings(
+        &self,
+        namespace: &str,
+    ) -> DataStoreResult<Vec<(String, serde_json::Value)>> {
+        self.inner.list_settings(namespace).await
+    }
+
+    async fn put_setting(
+        &self,
+        namespace: &str,
+        key: &str,
+        value: &serde_json::Value,
+    ) -> DataStoreResult<()> {
+        self.inner.put_setting(namespace, key, value).await
+    }
+
+    async fn delete_setting(&self, namespace: &str, key: &str) -> DataStoreResult<()> {
+        self.inner.delete_setting(namespace, key).await
+    }
+
+    async fn batch_nodes(
+        &self,
+        operations: &[BatchOperation<Node>],
+    ) -> DataStoreResult<BatchResult> {
+        let sealed = operations
+            .iter()
+            .map(|operation| {
+                Ok(match operation {
+                    BatchOperation::Insert(node) => BatchOperation::Insert(self.seal(node)?),
+                    BatchOperation::Update(node) => BatchOperation::Update(self.seal(node)?),
+                    BatchOperation::Delete(id) => BatchOperation::Delete(*id),
+                })
+            })
+            .collect::<DataStoreResult<Vec<_>>>()?;
+        self.inner.batch_nodes(&sealed).await
+    }
+
+    async fn batch_links(
+        &self,
+        operations: &[BatchOperation<Link>],
+    ) -> DataStoreResult<BatchResult> {
+        self.inner.batch_links(operations).await
+    }
+
+    async fn batch_locations(
+        &self,
+        operations: &[BatchOperation<Location>],
+    ) -> DataStoreResult<BatchResult> {
+        self.inner.batch_locations(operations).await
+    }
+
+    async fn get_entity_counts(&self) -> DataStoreResult<HashMap<String, usize>> {
+        self.inner.get_entity_counts().await
+    }
+
+    async fn get_statistics(&self) -> DataStoreResult<HashMap<String, serde_json::Value>> {
+        self.inner.get_statistics().await
+    }
+
+    async fn vacuum(&self) -> DataStoreResult<()> {
+        self.inner.vacuum().await
+    }
+
+    async fn analyze(&self) -> DataStoreResult<()> {
+        self.inner.analyze().await
+    }
+
+    async fn cleanup_orphaned_records(&self) -> DataStoreResult<HashMap<String, usize>> {
+        self.inner.cleanup_orphaned_records().await
+    }
+
+    async fn count_orphaned_records(&self) -> DataStoreResult<HashMap<String, usize>> {
+        self.inner.count_orphaned_records().await
+    }
+
+    async fn prune_derived_state(
+        &self,
+        cutoff: chrono::DateTime<chrono::Utc>,
+    ) -> DataStoreResult<HashMap<String, usize>> {
+        self.inner.prune_derived_state(cutoff).await
+    }
+
+    async fn prune_performance_samples(
+        &self,
+        cutoff: chrono::DateTime<chrono::Utc>,
+        dry_run: bool,
+    ) -> DataStoreResult<PruneStats> {
+        self.inner.prune_performance_samples(cutoff, dry_run).await
+    }
+
+    async fn prune_performance_rollups(
+        &self,
+        cutoff: chrono::DateTime<chrono::Utc>,
+        dry_run: bool,
+    ) -> DataStoreResult<PruneStats> {
+        self.inner.prune_performance_rollups(cutoff, dry_run).await
+    }
+
+    async fn append_change_event(&self, event: &ChangeEvent) -> DataStoreResult<ChangeEvent> {
+        self.inner.append_change_event(event).await
+    }
+
+    async fn list_change_events(&self, filter: &EventFilter) -> DataStoreResult<Vec<ChangeEvent>> {
+        self.inner.list_change_events(filter).await
+    }
+
+    async fn last_change_sequence(&self) -> DataStoreResult<i64> {
+        self.inner.last_change_sequence().await
+    }
+
+    async fn prune_change_events(
+        &self,
+        cutoff: chrono::DateTime<chrono::Utc>,
+        dry_run: bool,
+    ) -> DataStoreResult<PruneStats> {
+        self.inner.prune_change_events(cutoff, dry_run).await
+    }
+
+    async fn acquire_shard_lease(
+        &self,
+        lease: &ShardLease,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> DataStoreResult<bool> {
+        self.inner.acquire_shard_lease(lease, now).await
+    }
+
+    async fn list_shard_leases(&self) -> DataStoreResult<Vec<ShardLease>> {
+        self.inner.list_shard_leases().await
+    }
+
+    async fn release_shard_lease(&self, shard: u32, collector: &str) -> DataStoreResult<()> {
+        self.inner.release_shard_lease(shard, collector).await
+    }
+
+    async fn get_node_status(&self, node_id: &Uuid) -> DataStoreResult<Option<NodeStatus>> {
+        self.inner.get_node_status(node_id).await
+    }
+
+    async fn get_node_interfaces(&self, node_id: &Uuid) -> DataStoreResult<Vec<InterfaceStatus>> {
+        self.inner.get_node_interfaces(node_id).await
+    }
+
+    async fn get_node_metrics(
+        &self,
+        node_id: &Uuid,
+    ) -> DataStoreResult<Option<PerformanceMetrics>> {
+        self.inner.get_node_metrics(node_id).await
+    }
+
+    async fn get_node_statuses(&self, node_ids: &[Uuid]) -> DataStoreResult<Vec<NodeStatus>> {
+        self.inner.get_node_statuses(node_ids).await
+    }
+
+    async fn record_performance_metrics(
+        &self,
+        node_id: &Uuid,
+        recorded_at: chrono::DateTime<chrono::Utc>,
+        metrics: &PerformanceMetrics,
+    ) -> DataStoreResult<()> {
+        self.inner
+            .record_performance_metrics(node_id, recorded_at, metrics)
+            .await
+    }
+
+    async fn query_performance_history(
+        &self,
+        node_id: &Uuid,
+        query: &MetricQuery,
+    ) -> DataStoreResult<Vec<MetricPoint>> {
+        self.inner.query_performance_history(node_id, query).await
+    }
+
+    async fn store_policy_result(
+        &self,
+        node_id: &Uuid,
+        rule_id: &str,
+        result: &PolicyExecutionResult,
+    ) -> DataStoreResult<()> {
+        self.inner
+            .store_policy_result(node_id, rule_id, result)
+            .await
+    }
+
+    async fn get_policy_results(
+        &self,
+        node_id: &Uuid,
+    ) -> DataStoreResult<Vec<PolicyExecutionResult>> {
+        self.inner.get_policy_results(node_id).await
+    }
+
+    async fn get_latest_policy_results(
+        &self,
+        node_id: &Uuid,
+    ) -> DataStoreResult<Vec<PolicyExecutionResult>> {
+        self.inner.get_latest_policy_results(node_id).await
+    }
+
+    async fn get_rule_results(
+        &self,
+        rule_id: &str,
+    ) -> DataStoreResult<Vec<(Uuid, PolicyExecutionResult)>> {
+        self.inner.get_rule_results(rule_id).await
+    }
+
+    async fn update_node_custom_data(
+        &self,
+        node_id: &Uuid,
+        custom_data: &serde_json::Value,
+    ) -> DataStoreResult<()> {
+        let mut sealed = custom_data.clone();
+        self.fields.seal(&mut sealed)?;
+        self.inner.update_node_custom_data(node_id, &sealed).await
+    }
+
+    async fn get_nodes_for_policy_evaluation(&self) -> DataStoreResult<Vec<Node>> {
+        let nodes = self.inner.get_nodes_for_policy_evaluation().await?;
+        self.open_all(nodes)
+    }
+}
@@ -0,0 +1,118 @@
+use super::*;
+use crate::datastore::{DataStore, MockDataStore};
+use crate::models::{DeviceRole, Node, Vendor};
+use serde_json::json;
+use std::sync::Arc;
+
+fn fields(master_key: &str) -> FieldEncryption {
+    FieldEncryption::new(
+        SecretManager::new(master_key).unwrap(),
+        vec![
+            "snmp.community".to_string(),
+            "local_admin_password".to_string(),
+        ],
+    )
+}
+
+fn foreign_ciphertext() -> Value {
+    SecretManager::new("another")
+        .unwrap()
+        .encrypt(&json!("edge-ro"))
+        .unwrap()
+}
+
+#[test]
+fn test_secret_manager_round_trip_and_wrong_key() {
+    let secrets = SecretManager::new("master").unwrap();
+    let value = json!({"user": "admin", "port": 22});
+
+    let encrypted = secrets.encrypt(&value).unwrap();
+    assert!(SecretManager::is_encrypted(&encrypted));
+    assert_ne!(encrypted, secrets.encrypt(&value).unwrap());
+    assert_eq!(secrets.decrypt(&encrypted).unwrap(), value);
+
+    let other = SecretManager::new("another").unwrap();
+    assert!(other.decrypt(&encrypted).is_err());
+    assert!(secrets.decrypt(&json!("plain")).is_err());
+    assert!(SecretManager::new(" ").is_err());
+}
+
+#[test]
+fn test_seal_and_open_only_listed_paths() {
+    let fields = fields("master");
+    let mut custom_data = json!({
+        "snmp": {"community": "edge-ro", "version": "2c"},
+        "local_admin_password": null,
+        "site": "dc1",
+    });
+
+    fields.seal(&mut custom_data).unwrap();
+    assert!(SecretManager::is_encrypted(
+        &custom_data["snmp"]["community"]
+    ));
+    assert_eq!(custom_data["snmp"]["version"], "2c");
+    assert!(custom_data["local_admin_password"].is_null());
+
+    // Sealing again keeps the ciphertext rather than encrypting it twice
+    let sealed = custom_data.clone();
+    fields.seal(&mut custom_data).unwrap();
+    assert_eq!(custom_data, sealed);
+
+    // Ciphertext from another master key, or forged, is rejected
+    for forged in [
+        foreign_ciphertext(),
+        json!(format!("{ENCRYPTED_PREFIX}not-base64")),
+    ] {
+        let mut forged_data = json!({"snmp": {"community": forged}});
+        assert!(matches!(
+            fields.seal(&mut forged_data),
+            Err(DataStoreError::ValidationError { .. })
+        ));
+    }
+
+    let opened = fields.opened(&custom_data).unwrap();
+    assert_eq!(opened["snmp.community"], "edge-ro");
+    fields.open(&mut custom_data).unwrap();
+    assert_eq!(custom_data["snmp"]["community"], "edge-ro");
+}
+
+#[test]
+fn test_from_config_needs_fields_and_key() {
+    let mut config = SecretsConfig::default();
+    assert!(FieldEncryption::from_config(&config).unwrap().is_none());
+
+    config.encrypted_fields = vec!["snmp.community".to_string()];
+    assert!(FieldEncryption::from_config(&config).is_err());
+
+    config.master_key = Some("master".to_string());
+    let fields = FieldEncryption::from_config(&config).unwrap().unwrap();
+    assert_eq!(fields.paths(), ["snmp.community"]);
+}
+
+#[tokio::test]
+async fn test_store_seals_writes_and_decrypts_reads_when_trusted() {
+    let mut node = Node::new(
+        "secret-edge".to_string(),
+        "example.com".to_string(),
+        Vendor::Cisco,
+        DeviceRole::Router,
+    );
+    node.custom_data = json!({"snmp": {"community": "edge-ro"}});
+
+    let mut mock = MockDataStore::new();
+    mock.expect_create_node()
+        .withf(|node| SecretManager::is_encrypted(&node.custom_data["snmp"]["community"]))
+        .returning(|node| Ok(node.clone()));
+    let inner: Arc<dyn DataStore> = Arc::new(mock);
+    let fields = Arc::new(fields("master"));
+
+    let trusted = EncryptedFieldsStore::new(inner.clone(), fields.clone(), true);
+    let created = trusted.create_node(&node).await.unwrap();
+    assert_eq!(created.custom_data["snmp"]["community"], "edge-ro");
+
+    let sealed = EncryptedFieldsStore::new(inner, fields, false);
+    let created = sealed.create_node(&node).await.unwrap();
+    assert!(SecretManager::is_encrypted(
+        &created.custom_data["snmp"]["community"]
+    ));
+}
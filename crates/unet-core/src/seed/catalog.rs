@@ -0,0 +1,230 @@
+//! Static catalog of plausible sites, vendors, and hardware models
+
+use crate::models::{DeviceRole, Vendor};
+
+/// A metro/site code with the region and country it belongs to
+pub struct SiteTemplate {
+    /// Short site code used in node and location names
+    pub code: &'static str,
+    /// City name used for the site address
+    pub city: &'static str,
+    /// Country the site is located in
+    pub country: &'static str,
+    /// Region grouping used as the root of the location tree
+    pub region: &'static str,
+}
+
+/// Site catalog cycled through when generating locations
+pub const SITES: &[SiteTemplate] = &[
+    SiteTemplate {
+        code: "nyc",
+        city: "New York",
+        country: "US",
+        region: "us-east",
+    },
+    SiteTemplate {
+        code: "iad",
+        city: "Ashburn",
+        country: "US",
+        region: "us-east",
+    },
+    SiteTemplate {
+        code: "atl",
+        city: "Atlanta",
+        country: "US",
+        region: "us-east",
+    },
+    SiteTemplate {
+        code: "chi",
+        city: "Chicago",
+        country: "US",
+        region: "us-central",
+    },
+    SiteTemplate {
+        code: "dfw",
+        city: "Dallas",
+        country: "US",
+        region: "us-central",
+    },
+    SiteTemplate {
+        code: "sfo",
+        city: "San Francisco",
+        country: "US",
+        region: "us-west",
+    },
+    SiteTemplate {
+        code: "sea",
+        city: "Seattle",
+        country: "US",
+        region: "us-west",
+    },
+    SiteTemplate {
+        code: "lax",
+        city: "Los Angeles",
+        country: "US",
+        region: "us-west",
+    },
+    SiteTemplate {
+        code: "lon",
+        city: "London",
+        country: "GB",
+        region: "eu-west",
+    },
+    SiteTemplate {
+        code: "ams",
+        city: "Amsterdam",
+        country: "NL",
+        region: "eu-west",
+    },
+    SiteTemplate {
+        code: "fra",
+        city: "Frankfurt",
+        country: "DE",
+        region: "eu-central",
+    },
+    SiteTemplate {
+        code: "par",
+        city: "Paris",
+        country: "FR",
+        region: "eu-west",
+    },
+    SiteTemplate {
+        code: "sin",
+        city: "Singapore",
+        country: "SG",
+        region: "ap-southeast",
+    },
+    SiteTemplate {
+        code: "syd",
+        city: "Sydney",
+        country: "AU",
+        region: "ap-southeast",
+    },
+    SiteTemplate {
+        code: "tyo",
+        city: "Tokyo",
+        country: "JP",
+        region: "ap-northeast",
+    },
+    SiteTemplate {
+        code: "bom",
+        city: "Mumbai",
+        country: "IN",
+        region: "ap-south",
+    },
+];
+
+/// Hardware options available for a device role
+pub struct HardwareOption {
+    /// Device vendor
+    pub vendor: Vendor,
+    /// Hardware model
+    pub model: &'static str,
+    /// Operating system/platform
+    pub platform: &'static str,
+    /// Software versions seen in the field for this platform
+    pub versions: &'static [&'static str],
+}
+
+const ROUTERS: &[HardwareOption] = &[
+    HardwareOption {
+        vendor: Vendor::Cisco,
+        model: "ASR1001-X",
+        platform: "IOS-XE",
+        versions: &["17.6.5", "17.9.4a", "17.12.2"],
+    },
+    HardwareOption {
+        vendor: Vendor::Juniper,
+        model: "MX204",
+        platform: "Junos",
+        versions: &["21.4R3-S5", "22.4R2", "23.2R1"],
+    },
+    HardwareOption {
+        vendor: Vendor::Arista,
+        model: "7280R3",
+        platform: "EOS",
+        versions: &["4.29.2F", "4.30.1F", "4.31.0F"],
+    },
+];
+
+const SWITCHES: &[HardwareOption] = &[
+    HardwareOption {
+        vendor: Vendor::Cisco,
+        model: "C9300-48P",
+        platform: "IOS-XE",
+        versions: &["17.6.5", "17.9.4a"],
+    },
+    HardwareOption {
+        vendor: Vendor::Arista,
+        model: "7050SX3-48YC8",
+        platform: "EOS",
+        versions: &["4.29.2F", "4.30.1F"],
+    },
+    HardwareOption {
+        vendor: Vendor::Juniper,
+        model: "EX4300-48P",
+        platform: "Junos",
+        versions: &["21.4R3-S5", "22.2R3"],
+    },
+    HardwareOption {
+        vendor: Vendor::Hpe,
+        model: "Aruba-6300M",
+        platform: "AOS-CX",
+        versions: &["10.11.1010", "10.12.0006"],
+    },
+];
+
+const FIREWALLS: &[HardwareOption] = &[
+    HardwareOption {
+        vendor: Vendor::PaloAlto,
+        model: "PA-3220",
+        platform: "PAN-OS",
+        versions: &["10.2.7", "11.0.3"],
+    },
+    HardwareOption {
+        vendor: Vendor::Fortinet,
+        model: "FG-600E",
+        platform: "FortiOS",
+        versions: &["7.2.6", "7.4.2"],
+    },
+];
+
+const ACCESS_POINTS: &[HardwareOption] = &[
+    HardwareOption {
+        vendor: Vendor::Ubiquiti,
+        model: "U6-Pro",
+        platform: "UniFi",
+        versions: &["6.5.62", "6.6.55"],
+    },
+    HardwareOption {
+        vendor: Vendor::Cisco,
+        model: "C9120AXI",
+        platform: "AireOS",
+        versions: &["8.10.185.0"],
+    },
+];
+
+/// Returns the hardware catalog entries for a device role
+#[must_use]
+pub const fn hardware_for_role(role: DeviceRole) -> &'static [HardwareOption] {
+    match role {
+        DeviceRole::Router => ROUTERS,
+        DeviceRole::Firewall => FIREWALLS,
+        DeviceRole::AccessPoint => ACCESS_POINTS,
+        _ => SWITCHES,
+    }
+}
+
+/// Returns a vendor-style interface name for the given port index
+#[must_use]
+pub fn interface_name(vendor: Vendor, port: usize) -> String {
+    match vendor {
+        Vendor::Cisco => format!("TenGigabitEthernet0/0/{port}"),
+        Vendor::Juniper => format!("xe-0/0/{port}"),
+        Vendor::Arista => format!("Ethernet{port}"),
+        Vendor::PaloAlto => format!("ethernet1/{port}"),
+        Vendor::Fortinet => format!("port{port}"),
+        Vendor::Hpe => format!("1/1/{port}"),
+        _ => format!("eth{port}"),
+    }
+}
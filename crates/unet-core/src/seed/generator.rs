@@ -0,0 +1,296 @@
+//! Inventory generation driven by [`SeedOptions`]
+
+use serde_json::json;
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr};
+use uuid::Uuid;
+
+use super::catalog::{SITES, SiteTemplate, hardware_for_role, interface_name};
+use super::{SeedDataset, SeedOptions, SeedRng};
+use crate::models::{DeviceRole, Lifecycle, LinkBuilder, LocationBuilder, NodeBuilder, Vendor};
+
+/// Core routers generated per site; every other node uplinks to each of them
+const CORE_ROUTERS_PER_SITE: usize = 2;
+/// Bandwidth of links inside a site (10 Gbps)
+const SITE_BANDWIDTH_BPS: u64 = 10_000_000_000;
+/// Bandwidth of inter-site backbone links (100 Gbps)
+const BACKBONE_BANDWIDTH_BPS: u64 = 100_000_000_000;
+/// Highest site index addressable with the `10.x.y.0/24` management scheme
+const MAX_SITES: usize = 1 << 16;
+
+/// Generates a complete synthetic inventory
+///
+/// # Errors
+/// Returns an error if `node_count` is zero, the requested size exceeds the
+/// management addressing scheme, or a generated entity fails model validation.
+pub fn generate(options: &SeedOptions) -> Result<SeedDataset, String> {
+    if options.node_count == 0 {
+        return Err("Node count must be greater than zero".to_string());
+    }
+
+    let per_site = options.profile.nodes_per_site();
+    let site_count = options.node_count.div_ceil(per_site);
+    if site_count > MAX_SITES {
+        return Err(format!(
+            "Node count {} requires {site_count} sites; at most {MAX_SITES} are supported",
+            options.node_count
+        ));
+    }
+
+    let mut generator = Generator {
+        rng: SeedRng::new(options.seed),
+        options,
+        dataset: SeedDataset::default(),
+        regions: HashMap::new(),
+        ports: HashMap::new(),
+    };
+
+    let mut backbone = Vec::with_capacity(site_count);
+    for site_index in 0..site_count {
+        let remaining = options.node_count - site_index * per_site;
+        backbone.push(generator.generate_site(site_index, remaining.min(per_site))?);
+    }
+    generator.connect_backbone(&backbone)?;
+
+    Ok(generator.dataset)
+}
+
+/// Minimal node details needed to wire links
+#[derive(Debug, Clone)]
+struct Endpoint {
+    id: Uuid,
+    name: String,
+    vendor: Vendor,
+}
+
+struct Generator<'a> {
+    rng: SeedRng,
+    options: &'a SeedOptions,
+    dataset: SeedDataset,
+    regions: HashMap<&'static str, (Uuid, String)>,
+    ports: HashMap<Uuid, usize>,
+}
+
+impl Generator<'_> {
+    /// Generates one site's locations, nodes, and intra-site links, returning its core routers
+    fn generate_site(
+        &mut self,
+        site_index: usize,
+        node_count: usize,
+    ) -> Result<Vec<Endpoint>, String> {
+        let template = &SITES[site_index % SITES.len()];
+        let site_name = format!("{}{}", template.code, site_index / SITES.len() + 1);
+        let building_id = self.site_locations(template, &site_name)?;
+
+        let mut role_counters: HashMap<&'static str, usize> = HashMap::new();
+        let mut cores: Vec<Endpoint> = Vec::with_capacity(CORE_ROUTERS_PER_SITE);
+
+        for node_index in 0..node_count {
+            let role = role_for_index(node_index);
+            let prefix = name_prefix(role);
+            let counter = role_counters.entry(prefix).or_insert(0);
+            *counter += 1;
+            let name = format!("{site_name}-{prefix}{counter:02}");
+
+            let endpoint = self.generate_node(site_index, node_index, name, building_id, role)?;
+
+            if node_index < CORE_ROUTERS_PER_SITE {
+                if let Some(peer) = cores.last() {
+                    self.connect(peer, &endpoint, SITE_BANDWIDTH_BPS, "fiber")?;
+                }
+                cores.push(endpoint);
+            } else {
+                for core in &cores {
+                    self.connect(&endpoint, core, SITE_BANDWIDTH_BPS, "ethernet")?;
+                }
+            }
+        }
+
+        Ok(cores)
+    }
+
+    /// Creates the region (once), site, and building locations and returns the building ID
+    fn site_locations(&mut self, template: &SiteTemplate, site_name: &str) -> Result<Uuid, String> {
+        let (region_id, region_path) = self.region(template)?;
+
+        let site = LocationBuilder::new()
+            .id(self.rng.uuid())
+            .name(site_name)
+            .location_type("site")
+            .parent_id(region_id)
+            .parent_path(region_path)
+            .address(format!("{}, {}", template.city, template.country))
+            .custom_data(json!({ "city": template.city, "country": template.country }))
+            .build()?;
+
+        let building = LocationBuilder::new()
+            .id(self.rng.uuid())
+            .name("bldg-1")
+            .location_type("building")
+            .parent_id(site.id)
+            .parent_path(site.path.clone())
+            .build()?;
+
+        let building_id = building.id;
+        self.dataset.locations.push(site);
+        self.dataset.locations.push(building);
+        Ok(building_id)
+    }
+
+    fn region(&mut self, template: &SiteTemplate) -> Result<(Uuid, String), String> {
+        if let Some(existing) = self.regions.get(template.region) {
+            return Ok(existing.clone());
+        }
+
+        let region = LocationBuilder::new()
+            .id(self.rng.uuid())
+            .name(template.region)
+            .location_type("region")
+            .build()?;
+
+        let entry = (region.id, region.path.clone());
+        self.regions.insert(template.region, entry.clone());
+        self.dataset.locations.push(region);
+        Ok(entry)
+    }
+
+    fn generate_node(
+        &mut self,
+        site_index: usize,
+        node_index: usize,
+        name: String,
+        location_id: Uuid,
+        role: DeviceRole,
+    ) -> Result<Endpoint, String> {
+        let hardware = self.rng.pick(hardware_for_role(role));
+        let version = *self.rng.pick(hardware.versions);
+        let lifecycle = match self.rng.below(100) {
+            0..=4 => Lifecycle::Planned,
+            5..=7 => Lifecycle::Implementing,
+            _ => Lifecycle::Live,
+        };
+        let serial = format!(
+            "{}{:010X}",
+            hardware
+                .model
+                .chars()
+                .take(2)
+                .collect::<String>()
+                .to_uppercase(),
+            self.rng.next_u64() & 0xFF_FFFF_FFFF
+        );
+        let rack = self.rng.below(20) + 1;
+
+        let node = NodeBuilder::new()
+            .id(self.rng.uuid())
+            .name(name)
+            .domain(self.options.domain.clone())
+            .vendor(hardware.vendor)
+            .model(hardware.model)
+            .role(role)
+            .lifecycle(lifecycle)
+            .management_ip(management_ip(site_index, node_index)?)
+            .location_id(location_id)
+            .platform(hardware.platform)
+            .version(version)
+            .serial_number(serial)
+            .custom_data(json!({
+                "rack": format!("R{rack:02}"),
+                "criticality": criticality(role),
+                "owner": "netops",
+                "seed": { "profile": self.options.profile.to_string(), "seed": self.options.seed },
+            }))
+            .build()?;
+
+        let endpoint = Endpoint {
+            id: node.id,
+            name: node.name.clone(),
+            vendor: node.vendor,
+        };
+        self.dataset.nodes.push(node);
+        Ok(endpoint)
+    }
+
+    /// Connects the core routers of neighbouring sites in a ring
+    fn connect_backbone(&mut self, sites: &[Vec<Endpoint>]) -> Result<(), String> {
+        let site_count = sites.len();
+        if site_count < 2 {
+            return Ok(());
+        }
+
+        // A two-site ring would otherwise link the same pair twice
+        let ring_edges = if site_count == 2 { 1 } else { site_count };
+        for index in 0..ring_edges {
+            let next = (index + 1) % site_count;
+            for (a, z) in sites[index].iter().zip(&sites[next]) {
+                self.connect(a, z, BACKBONE_BANDWIDTH_BPS, "fiber")?;
+            }
+        }
+        Ok(())
+    }
+
+    fn connect(
+        &mut self,
+        a: &Endpoint,
+        z: &Endpoint,
+        bandwidth: u64,
+        link_type: &str,
+    ) -> Result<(), String> {
+        let a_interface = self.next_interface(a);
+        let z_interface = self.next_interface(z);
+
+        let link = LinkBuilder::new()
+            .id(self.rng.uuid())
+            .name(format!("{}-{}", a.name, z.name))
+            .source_node_id(a.id)
+            .node_a_interface(a_interface)
+            .dest_node_id(z.id)
+            .node_z_interface(z_interface)
+            .bandwidth(bandwidth)
+            .link_type(link_type)
+            .build()?;
+
+        self.dataset.links.push(link);
+        Ok(())
+    }
+
+    fn next_interface(&mut self, endpoint: &Endpoint) -> String {
+        let port = self.ports.entry(endpoint.id).or_insert(0);
+        *port += 1;
+        interface_name(endpoint.vendor, *port)
+    }
+}
+
+const fn role_for_index(node_index: usize) -> DeviceRole {
+    match node_index {
+        0 | 1 => DeviceRole::Router,
+        2 => DeviceRole::Firewall,
+        n if n % 8 == 7 => DeviceRole::AccessPoint,
+        _ => DeviceRole::Switch,
+    }
+}
+
+const fn name_prefix(role: DeviceRole) -> &'static str {
+    match role {
+        DeviceRole::Router => "cr",
+        DeviceRole::Firewall => "fw",
+        DeviceRole::AccessPoint => "ap",
+        _ => "asw",
+    }
+}
+
+const fn criticality(role: DeviceRole) -> &'static str {
+    match role {
+        DeviceRole::Router | DeviceRole::Firewall => "high",
+        DeviceRole::Switch => "medium",
+        _ => "low",
+    }
+}
+
+fn management_ip(site_index: usize, node_index: usize) -> Result<IpAddr, String> {
+    let overflow = |_| format!("Site {site_index} node {node_index} exceeds the management range");
+    let high = u8::try_from(site_index >> 8).map_err(overflow)?;
+    let low = u8::try_from(site_index & 0xFF).map_err(overflow)?;
+    let host = u8::try_from(node_index + 1).map_err(overflow)?;
+    Ok(IpAddr::V4(Ipv4Addr::new(10, high, low, host)))
+}
@@ -0,0 +1,51 @@
+//! Deterministic pseudo-random source for synthetic data generation
+//!
+//! Uses the `SplitMix64` algorithm so that a given seed always yields the same
+//! sequence on every platform without pulling in an external RNG dependency.
+
+use uuid::{Builder, Uuid};
+
+/// Small deterministic PRNG used by the seed generator
+#[derive(Debug, Clone)]
+pub struct SeedRng {
+    state: u64,
+}
+
+impl SeedRng {
+    /// Creates a generator from a seed value
+    #[must_use]
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Returns the next 64-bit value in the sequence
+    pub const fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a value in `0..upper` (returns 0 when `upper` is 0)
+    pub fn below(&mut self, upper: usize) -> usize {
+        if upper == 0 {
+            return 0;
+        }
+        let upper = u64::try_from(upper).unwrap_or(u64::MAX);
+        usize::try_from(self.next_u64() % upper).unwrap_or(0)
+    }
+
+    /// Picks an element from a non-empty slice
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    /// Generates a version 4 UUID from the deterministic stream
+    pub fn uuid(&mut self) -> Uuid {
+        let mut bytes = [0_u8; 16];
+        bytes[..8].copy_from_slice(&self.next_u64().to_le_bytes());
+        bytes[8..].copy_from_slice(&self.next_u64().to_le_bytes());
+        Builder::from_random_bytes(bytes).into_uuid()
+    }
+}
@@ -0,0 +1,111 @@
+use super::*;
+use std::collections::HashSet;
+
+fn demo(node_count: usize) -> SeedDataset {
+    generate(&SeedOptions::new(SeedProfile::Demo).with_node_count(node_count)).unwrap()
+}
+
+#[test]
+fn test_generate_produces_requested_node_count() {
+    let dataset = demo(50);
+    assert_eq!(dataset.nodes.len(), 50);
+}
+
+#[test]
+fn test_generate_is_deterministic_for_same_seed() {
+    let first = demo(30);
+    let second = demo(30);
+
+    assert_eq!(first.nodes, second.nodes);
+    assert_eq!(first.links, second.links);
+    assert_eq!(first.locations, second.locations);
+}
+
+#[test]
+fn test_generate_differs_between_seeds() {
+    let options = SeedOptions::new(SeedProfile::Demo).with_node_count(30);
+    let first = generate(&options.clone().with_seed(1)).unwrap();
+    let second = generate(&options.with_seed(2)).unwrap();
+
+    assert_ne!(first.nodes[0].id, second.nodes[0].id);
+}
+
+#[test]
+fn test_generate_rejects_zero_nodes() {
+    let result = generate(&SeedOptions::new(SeedProfile::Demo).with_node_count(0));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_generated_entities_pass_validation() {
+    let dataset = demo(40);
+
+    assert!(dataset.locations.iter().all(|l| l.validate().is_ok()));
+    assert!(dataset.nodes.iter().all(|n| n.validate().is_ok()));
+    assert!(dataset.links.iter().all(|l| l.validate().is_ok()));
+}
+
+#[test]
+fn test_generated_locations_precede_children() {
+    let dataset = demo(40);
+    let mut seen = HashSet::new();
+
+    for location in &dataset.locations {
+        if let Some(parent_id) = location.parent_id {
+            assert!(
+                seen.contains(&parent_id),
+                "parent of {} not emitted first",
+                location.path
+            );
+        }
+        seen.insert(location.id);
+    }
+}
+
+#[test]
+fn test_generated_links_reference_generated_nodes() {
+    let dataset = demo(40);
+    let node_ids: HashSet<_> = dataset.nodes.iter().map(|n| n.id).collect();
+
+    for link in &dataset.links {
+        assert!(node_ids.contains(&link.source_node_id));
+        assert!(link.dest_node_id.is_some_and(|id| node_ids.contains(&id)));
+    }
+}
+
+#[test]
+fn test_generated_interfaces_are_unique_per_node() {
+    let dataset = demo(40);
+    let mut endpoints = HashSet::new();
+
+    for link in &dataset.links {
+        assert!(endpoints.insert((link.source_node_id, link.node_a_interface.clone())));
+        let z = (
+            link.dest_node_id.unwrap(),
+            link.node_z_interface.clone().unwrap(),
+        );
+        assert!(endpoints.insert(z));
+    }
+}
+
+#[test]
+fn test_generated_node_names_are_unique() {
+    let dataset = demo(60);
+    let names: HashSet<_> = dataset.nodes.iter().map(|n| n.name.as_str()).collect();
+    assert_eq!(names.len(), dataset.nodes.len());
+}
+
+#[test]
+fn test_seed_profile_parsing() {
+    assert_eq!("demo".parse::<SeedProfile>().unwrap(), SeedProfile::Demo);
+    assert_eq!("LARGE".parse::<SeedProfile>().unwrap(), SeedProfile::Large);
+    assert!("huge".parse::<SeedProfile>().is_err());
+}
+
+#[test]
+fn test_seed_rng_is_repeatable() {
+    let mut first = SeedRng::new(7);
+    let mut second = SeedRng::new(7);
+    assert_eq!(first.next_u64(), second.next_u64());
+    assert_eq!(first.uuid(), second.uuid());
+}
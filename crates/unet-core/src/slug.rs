@@ -0,0 +1,447 @@
+//! Human-readable slugs for nodes, locations, and links
+//!
+//! Every entity can carry a unique slug such as `edge-01` or `dc1-rack-4`,
+//! generated from its name or given when it is created, and accepted
+//! wherever its UUID is. A slug does not follow later renames of the entity,
+//! so URLs and scripts that use it keep working. Changing a slug explicitly
+//! keeps the old one as a redirect to the new one.
+//!
+//! Slugs are kept through the `DataStore` settings API: one document per
+//! slug, current or retired, and one per entity naming its current slug.
+//! Datastores without settings support have no slugs.
+
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult, QueryOptions};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Settings namespace mapping `kind:slug` to a [`SlugEntry`]
+const SLUGS_NAMESPACE: &str = "slugs";
+/// Settings namespace mapping `kind:id` to the entity's current slug
+const ENTITY_SLUGS_NAMESPACE: &str = "entity_slugs";
+/// Longest accepted slug
+pub const MAX_SLUG_LEN: usize = 63;
+
+/// Kind of entity a slug names; slugs are unique per kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SlugKind {
+    /// Nodes
+    Node,
+    /// Locations
+    Location,
+    /// Links
+    Link,
+}
+
+impl Display for SlugKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Node => write!(f, "node"),
+            Self::Location => write!(f, "location"),
+            Self::Link => write!(f, "link"),
+        }
+    }
+}
+
+impl FromStr for SlugKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "node" | "nodes" => Ok(Self::Node),
+            "location" | "locations" => Ok(Self::Location),
+            "link" | "links" => Ok(Self::Link),
+            _ => Err(format!("Invalid slug kind: {s}")),
+        }
+    }
+}
+
+/// Stored slug: the entity it names and, once retired, its replacement
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct SlugEntry {
+    id: Uuid,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    replaced_by: Option<String>,
+}
+
+/// Entity found for a slug
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlugMatch {
+    /// Entity ID
+    pub id: Uuid,
+    /// The entity's current slug
+    pub slug: String,
+    /// Whether the slug looked up was retired in favor of `slug`
+    pub redirected: bool,
+}
+
+/// A slug and the entity it currently names
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlugAssignment {
+    /// Entity kind
+    pub kind: SlugKind,
+    /// Entity ID
+    pub id: Uuid,
+    /// Current slug
+    pub slug: String,
+}
+
+/// Derives a slug from a name
+///
+/// Letters and digits are lowercased, and every other run of characters
+/// becomes a single `-`. The result is cut to [`MAX_SLUG_LEN`] and may be
+/// empty.
+#[must_use]
+pub fn slugify(name: &str) -> String {
+    let mut slug = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.truncate(MAX_SLUG_LEN);
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Checks that `slug` is usable as a slug
+///
+/// # Errors
+/// Returns a validation error unless the slug is 1 to 63 lowercase letters,
+/// digits, and inner hyphens and does not parse as a UUID.
+pub fn validate_slug(slug: &str) -> DataStoreResult<()> {
+    let valid = (1..=MAX_SLUG_LEN).contains(&slug.len())
+        && slug
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        && !slug.starts_with('-')
+        && !slug.ends_with('-');
+    if !valid {
+        return Err(DataStoreError::ValidationError {
+            message: format!(
+                "Invalid slug '{slug}': use 1-{MAX_SLUG_LEN} lowercase letters, digits, and inner hyphens"
+            ),
+        });
+    }
+    if slug.parse::<Uuid>().is_ok() {
+        return Err(DataStoreError::ValidationError {
+            message: format!("Invalid slug '{slug}': slugs cannot be UUIDs"),
+        });
+    }
+    Ok(())
+}
+
+fn parse<T: serde::de::DeserializeOwned>(key: &str, value: Value) -> DataStoreResult<T> {
+    serde_json::from_value(value).map_err(|e| DataStoreError::InternalError {
+        message: format!("stored slug {key}: {e}"),
+    })
+}
+
+fn to_value<T: Serialize>(key: &str, value: &T) -> DataStoreResult<Value> {
+    serde_json::to_value(value).map_err(|e| DataStoreError::InternalError {
+        message: format!("slug {key}: {e}"),
+    })
+}
+
+async fn get<T: serde::de::DeserializeOwned>(
+    datastore: &dyn DataStore,
+    namespace: &str,
+    key: &str,
+) -> DataStoreResult<Option<T>> {
+    match datastore.get_setting(namespace, key).await {
+        Ok(Some(value)) => parse(key, value).map(Some),
+        Ok(None) | Err(DataStoreError::UnsupportedOperation { .. }) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+async fn put_entry(
+    datastore: &dyn DataStore,
+    kind: SlugKind,
+    slug: &str,
+    entry: &SlugEntry,
+) -> DataStoreResult<()> {
+    let key = format!("{kind}:{slug}");
+    let value = to_value(&key, entry)?;
+    datastore.put_setting(SLUGS_NAMESPACE, &key, &value).await
+}
+
+/// Every slug entry of one kind, current and retired
+async fn entries(
+    datastore: &dyn DataStore,
+    kind: SlugKind,
+) -> DataStoreResult<Vec<(String, SlugEntry)>> {
+    let prefix = format!("{kind}:");
+    let settings = match datastore.list_settings(SLUGS_NAMESPACE).await {
+        Ok(settings) => settings,
+        Err(DataStoreError::UnsupportedOperation { .. }) => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    settings
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let slug = key.strip_prefix(&prefix)?.to_string();
+            Some(parse(&key, value).map(|entry| (slug, entry)))
+        })
+        .collect()
+}
+
+/// Returns the current slug of an entity, if it has one
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read.
+pub async fn slug_for(
+    datastore: &dyn DataStore,
+    kind: SlugKind,
+    id: Uuid,
+) -> DataStoreResult<Option<String>> {
+    get(datastore, ENTITY_SLUGS_NAMESPACE, &format!("{kind}:{id}")).await
+}
+
+/// Looks up the entity a slug names, following a retired slug to its
+/// replacement
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read.
+pub async fn resolve_slug(
+    datastore: &dyn DataStore,
+    kind: SlugKind,
+    slug: &str,
+) -> DataStoreResult<Option<SlugMatch>> {
+    let entry: Option<SlugEntry> =
+        get(datastore, SLUGS_NAMESPACE, &format!("{kind}:{slug}")).await?;
+    Ok(entry.map(|entry| SlugMatch {
+        id: entry.id,
+        redirected: entry.replaced_by.is_some(),
+        slug: entry.replaced_by.unwrap_or_else(|| slug.to_string()),
+    }))
+}
+
+/// Records `slug` as the entity's current slug
+async fn record(
+    datastore: &dyn DataStore,
+    kind: SlugKind,
+    id: Uuid,
+    slug: &str,
+) -> DataStoreResult<()> {
+    put_entry(
+        datastore,
+        kind,
+        slug,
+        &SlugEntry {
+            id,
+            replaced_by: None,
+        },
+    )
+    .await?;
+    let key = format!("{kind}:{id}");
+    datastore
+        .put_setting(
+            ENTITY_SLUGS_NAMESPACE,
+            &key,
+            &Value::String(slug.to_string()),
+        )
+        .await
+}
+
+/// Fails unless `slug` is free or already names entity `id`
+async fn ensure_available(
+    datastore: &dyn DataStore,
+    kind: SlugKind,
+    id: Uuid,
+    slug: &str,
+) -> DataStoreResult<()> {
+    match resolve_slug(datastore, kind, slug).await? {
+        Some(existing) if existing.id != id => Err(DataStoreError::ConstraintViolation {
+            message: format!("{kind} slug '{slug}' is already in use"),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Checks that `slug` is valid and free, before creating the entity it is
+/// requested for
+///
+/// # Errors
+/// Returns a validation error for an invalid slug, a constraint violation if
+/// it is taken, or an error if the datastore cannot be read.
+pub async fn check_slug(
+    datastore: &dyn DataStore,
+    kind: SlugKind,
+    slug: &str,
+) -> DataStoreResult<()> {
+    validate_slug(slug)?;
+    ensure_available(datastore, kind, Uuid::nil(), slug).await
+}
+
+/// Gives an entity its slug, unless it already has one
+///
+/// A `requested` slug is used as given; otherwise one is derived from
+/// `name`, with `-2`, `-3`, ... appended until it is unique. Slugs do not
+/// change once assigned; see [`change_slug`].
+///
+/// # Errors
+/// Returns a validation error for an invalid requested slug or one that
+/// differs from the entity's existing slug, a constraint violation if the
+/// requested slug is taken, or an error if the datastore fails.
+pub async fn assign_slug(
+    datastore: &dyn DataStore,
+    kind: SlugKind,
+    id: Uuid,
+    name: &str,
+    requested: Option<&str>,
+) -> DataStoreResult<String> {
+    if let Some(existing) = slug_for(datastore, kind, id).await? {
+        return match requested {
+            Some(requested) if requested != existing => Err(DataStoreError::ValidationError {
+                message: format!("{kind} {id} already has slug '{existing}'"),
+            }),
+            _ => Ok(existing),
+        };
+    }
+
+    let slug = if let Some(requested) = requested {
+        validate_slug(requested)?;
+        ensure_available(datastore, kind, id, requested).await?;
+        requested.to_string()
+    } else {
+        let base = match slugify(name) {
+            base if base.is_empty() || validate_slug(&base).is_err() => kind.to_string(),
+            base => base,
+        };
+        let mut candidate = base.clone();
+        let mut suffix = 2;
+        while resolve_slug(datastore, kind, &candidate).await?.is_some() {
+            let tail = format!("-{suffix}");
+            let stem = &base[..base.len().min(MAX_SLUG_LEN - tail.len())];
+            candidate = format!("{}{tail}", stem.trim_end_matches('-'));
+            suffix += 1;
+        }
+        candidate
+    };
+    record(datastore, kind, id, &slug).await?;
+    Ok(slug)
+}
+
+/// Changes an entity's slug, keeping the old slugs as redirects to it
+///
+/// # Errors
+/// Returns a validation error for an invalid slug, a constraint violation if
+/// the slug names another entity, or an error if the datastore fails.
+pub async fn change_slug(
+    datastore: &dyn DataStore,
+    kind: SlugKind,
+    id: Uuid,
+    slug: &str,
+) -> DataStoreResult<()> {
+    validate_slug(slug)?;
+    ensure_available(datastore, kind, id, slug).await?;
+    for (old, mut entry) in entries(datastore, kind).await? {
+        if entry.id == id && old != slug {
+            entry.replaced_by = Some(slug.to_string());
+            put_entry(datastore, kind, &old, &entry).await?;
+        }
+    }
+    record(datastore, kind, id, slug).await
+}
+
+/// Removes every slug of a deleted entity, current and retired
+///
+/// # Errors
+/// Returns an error if the datastore fails.
+pub async fn remove_slugs(
+    datastore: &dyn DataStore,
+    kind: SlugKind,
+    id: Uuid,
+) -> DataStoreResult<()> {
+    for (slug, entry) in entries(datastore, kind).await? {
+        if entry.id == id {
+            datastore
+                .delete_setting(SLUGS_NAMESPACE, &format!("{kind}:{slug}"))
+                .await?;
+        }
+    }
+    match datastore
+        .delete_setting(ENTITY_SLUGS_NAMESPACE, &format!("{kind}:{id}"))
+        .await
+    {
+        Ok(())
+        | Err(DataStoreError::NotFound { .. } | DataStoreError::UnsupportedOperation { .. }) => {
+            Ok(())
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Lists the current slug of every entity of a kind
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read.
+pub async fn list_slugs(
+    datastore: &dyn DataStore,
+    kind: SlugKind,
+) -> DataStoreResult<Vec<SlugAssignment>> {
+    let mut slugs: Vec<SlugAssignment> = entries(datastore, kind)
+        .await?
+        .into_iter()
+        .filter(|(_, entry)| entry.replaced_by.is_none())
+        .map(|(slug, entry)| SlugAssignment {
+            kind,
+            id: entry.id,
+            slug,
+        })
+        .collect();
+    slugs.sort_by(|a, b| a.slug.cmp(&b.slug));
+    Ok(slugs)
+}
+
+/// Assigns slugs derived from their names to all entities without one
+///
+/// Returns the slugs assigned.
+///
+/// # Errors
+/// Returns an error if entities cannot be listed or slugs cannot be stored.
+pub async fn backfill_slugs(datastore: &dyn DataStore) -> DataStoreResult<Vec<SlugAssignment>> {
+    let options = QueryOptions::default();
+    let mut named: Vec<(SlugKind, Uuid, String)> = Vec::new();
+    named.extend(
+        datastore
+            .list_nodes(&options)
+            .await?
+            .items
+            .into_iter()
+            .map(|node| (SlugKind::Node, node.id, node.name)),
+    );
+    named.extend(
+        datastore
+            .list_locations(&options)
+            .await?
+            .items
+            .into_iter()
+            .map(|location| (SlugKind::Location, location.id, location.name)),
+    );
+    named.extend(
+        datastore
+            .list_links(&options)
+            .await?
+            .items
+            .into_iter()
+            .map(|link| (SlugKind::Link, link.id, link.name)),
+    );
+
+    let mut assigned = Vec::new();
+    for (kind, id, name) in named {
+        if slug_for(datastore, kind, id).await?.is_none() {
+            let slug = assign_slug(datastore, kind, id, &name, None).await?;
+            assigned.push(SlugAssignment { kind, id, slug });
+        }
+    }
+    Ok(assigned)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,164 @@
+use super::*;
+use crate::datastore::sqlite::SqliteStore;
+use crate::models::{DeviceRole, Node, Vendor};
+use sea_orm::{ConnectionTrait, Database, DatabaseBackend, Schema};
+
+async fn slug_store() -> SqliteStore {
+    let db = Database::connect("sqlite::memory:").await.unwrap();
+    let schema = Schema::new(DatabaseBackend::Sqlite);
+    for stmt in [
+        schema.create_table_from_entity(crate::entities::vendors::Entity),
+        schema.create_table_from_entity(crate::entities::locations::Entity),
+        schema.create_table_from_entity(crate::entities::nodes::Entity),
+        schema.create_table_from_entity(crate::entities::links::Entity),
+        schema.create_table_from_entity(crate::entities::settings::Entity),
+    ] {
+        db.execute(db.get_database_backend().build(&stmt))
+            .await
+            .unwrap();
+    }
+    SqliteStore::from_connection(db)
+}
+
+#[test]
+fn test_slugify_and_validate() {
+    assert_eq!(slugify("Edge Router 01"), "edge-router-01");
+    assert_eq!(slugify("  DC1 / Rack #4 "), "dc1-rack-4");
+    assert_eq!(slugify("***"), "");
+    assert_eq!(slugify(&"a".repeat(80)).len(), MAX_SLUG_LEN);
+
+    assert!(validate_slug("dc1-rack-4").is_ok());
+    assert!(validate_slug("Edge").is_err());
+    assert!(validate_slug("-edge").is_err());
+    assert!(validate_slug("").is_err());
+    assert!(validate_slug(&Uuid::new_v4().to_string()).is_err());
+}
+
+#[tokio::test]
+async fn test_assign_slug_generates_unique_immutable_slugs() {
+    let store = slug_store().await;
+    let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+
+    let slug = assign_slug(&store, SlugKind::Node, first, "Edge 01", None)
+        .await
+        .unwrap();
+    assert_eq!(slug, "edge-01");
+    let slug = assign_slug(&store, SlugKind::Node, second, "edge-01", None)
+        .await
+        .unwrap();
+    assert_eq!(slug, "edge-01-2");
+    // Same name, different kind
+    let slug = assign_slug(&store, SlugKind::Link, first, "edge-01", None)
+        .await
+        .unwrap();
+    assert_eq!(slug, "edge-01");
+
+    // Assigning again keeps the slug, whatever the name is now
+    let slug = assign_slug(&store, SlugKind::Node, first, "renamed", None)
+        .await
+        .unwrap();
+    assert_eq!(slug, "edge-01");
+    assert!(
+        assign_slug(&store, SlugKind::Node, first, "renamed", Some("renamed"))
+            .await
+            .is_err()
+    );
+
+    assert!(check_slug(&store, SlugKind::Node, "edge-02").await.is_ok());
+    assert!(check_slug(&store, SlugKind::Node, "edge-01").await.is_err());
+    let taken = assign_slug(&store, SlugKind::Node, Uuid::new_v4(), "x", Some("edge-01")).await;
+    assert!(matches!(
+        taken,
+        Err(DataStoreError::ConstraintViolation { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_change_slug_redirects_old_slugs() {
+    let store = slug_store().await;
+    let id = Uuid::new_v4();
+    assign_slug(&store, SlugKind::Location, id, "dc1", None)
+        .await
+        .unwrap();
+
+    change_slug(&store, SlugKind::Location, id, "dc-east")
+        .await
+        .unwrap();
+    change_slug(&store, SlugKind::Location, id, "dc-east-1")
+        .await
+        .unwrap();
+
+    let current = resolve_slug(&store, SlugKind::Location, "dc-east-1")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!((current.id, current.redirected), (id, false));
+    for old in ["dc1", "dc-east"] {
+        let found = resolve_slug(&store, SlugKind::Location, old)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            (found.id, found.slug.as_str(), found.redirected),
+            (id, "dc-east-1", true)
+        );
+    }
+    assert_eq!(
+        slug_for(&store, SlugKind::Location, id).await.unwrap(),
+        Some("dc-east-1".to_string())
+    );
+    assert_eq!(
+        list_slugs(&store, SlugKind::Location).await.unwrap().len(),
+        1
+    );
+
+    // Retired slugs stay reserved for their entity
+    assert!(
+        change_slug(&store, SlugKind::Location, Uuid::new_v4(), "dc1")
+            .await
+            .is_err()
+    );
+
+    remove_slugs(&store, SlugKind::Location, id).await.unwrap();
+    assert!(
+        resolve_slug(&store, SlugKind::Location, "dc1")
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(
+        slug_for(&store, SlugKind::Location, id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn test_backfill_slugs_skips_entities_with_slugs() {
+    let store = slug_store().await;
+    let node = Node::new(
+        "core-01".to_string(),
+        "example.com".to_string(),
+        Vendor::Cisco,
+        DeviceRole::Router,
+    );
+    let other = Node::new(
+        "Core 01".to_string(),
+        "example.com".to_string(),
+        Vendor::Cisco,
+        DeviceRole::Router,
+    );
+    store.create_node(&node).await.unwrap();
+    store.create_node(&other).await.unwrap();
+    assign_slug(&store, SlugKind::Node, node.id, &node.name, Some("core"))
+        .await
+        .unwrap();
+
+    let assigned = backfill_slugs(&store).await.unwrap();
+
+    assert_eq!(assigned.len(), 1);
+    assert_eq!(assigned[0].id, other.id);
+    assert_eq!(assigned[0].slug, "core-01");
+    assert!(backfill_slugs(&store).await.unwrap().is_empty());
+}
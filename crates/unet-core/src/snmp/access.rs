@@ -0,0 +1,115 @@
+//! OID access lists
+//!
+//! Operators can restrict which parts of the MIB tree μNet may read, so
+//! sensitive areas (such as vendor configuration-copy tables or MIBs with
+//! write-capable objects) are never requested, whatever a profile, a policy,
+//! or an ad-hoc walk asks for. The [`SnmpClient`](super::SnmpClient) checks
+//! every GET and walk against the list before anything is sent.
+//!
+//! ```toml
+//! [snmp.oid_access]
+//! allow = ["1.3.6.1.2.1", "1.3.6.1.4.1.2636"]
+//! deny = ["1.3.6.1.4.1.9.9.96"]
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// Prefixes of OIDs that may and may not be requested
+///
+/// An OID is permitted when it is under none of the `deny` prefixes and,
+/// if `allow` is not empty, under one of the `allow` prefixes. Prefixes match
+/// whole arcs: `1.3.6.1.2.1.1` covers `1.3.6.1.2.1.1.3.0` but not
+/// `1.3.6.1.2.1.10`. The default list permits everything.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OidAccessList {
+    /// Subtrees that may be requested; empty permits every subtree
+    pub allow: Vec<String>,
+    /// Subtrees that are never requested, even under an allowed prefix
+    pub deny: Vec<String>,
+}
+
+impl OidAccessList {
+    /// Whether a GET of `oid` is permitted
+    #[must_use]
+    pub fn permits(&self, oid: &str) -> bool {
+        let oid = trim(oid);
+        !self.deny.iter().any(|prefix| covers(prefix, oid))
+            && (self.allow.is_empty() || self.allow.iter().any(|prefix| covers(prefix, oid)))
+    }
+
+    /// Whether a walk starting at `oid` is permitted
+    ///
+    /// Besides the start OID itself being permitted, no denied subtree may
+    /// lie below it, since the walk would read it.
+    #[must_use]
+    pub fn permits_walk(&self, oid: &str) -> bool {
+        self.permits(oid) && !self.deny.iter().any(|prefix| covers(oid, trim(prefix)))
+    }
+
+    /// The first prefix that is not a dotted numeric OID, if any
+    #[must_use]
+    pub fn invalid_prefix(&self) -> Option<&str> {
+        self.allow
+            .iter()
+            .chain(&self.deny)
+            .map(String::as_str)
+            .find(|prefix| {
+                trim(prefix)
+                    .split('.')
+                    .any(|arc| arc.is_empty() || !arc.bytes().all(|b| b.is_ascii_digit()))
+            })
+    }
+}
+
+fn trim(oid: &str) -> &str {
+    oid.trim().trim_start_matches('.')
+}
+
+/// Whether `oid` is `prefix` or lies below it
+fn covers(prefix: &str, oid: &str) -> bool {
+    let prefix = trim(prefix);
+    oid.strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list() -> OidAccessList {
+        OidAccessList {
+            allow: vec!["1.3.6.1.2.1".to_string(), ".1.3.6.1.4.1.9".to_string()],
+            deny: vec!["1.3.6.1.4.1.9.9.96".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_permits_allowed_subtrees_except_denied_ones() {
+        let list = list();
+        assert!(list.permits("1.3.6.1.2.1.1.3.0"));
+        assert!(list.permits(".1.3.6.1.4.1.9.2.1"));
+        assert!(!list.permits("1.3.6.1.4.1.9.9.96.1.1.1.1.2"));
+        assert!(!list.permits("1.3.6.1.4.1.2636.3.1"));
+        assert!(!list.permits("1.3.6.1.2.10"), "prefixes match whole arcs");
+        assert!(OidAccessList::default().permits("1.3.6.1.4.1.9.9.96.1"));
+    }
+
+    #[test]
+    fn test_walk_must_not_cover_a_denied_subtree() {
+        let list = list();
+        assert!(list.permits_walk("1.3.6.1.4.1.9.2"));
+        assert!(!list.permits_walk("1.3.6.1.4.1.9"));
+        assert!(!list.permits_walk("1.3.6.1.4.1.9.9.96.1"));
+    }
+
+    #[test]
+    fn test_invalid_prefix_finds_non_numeric_arcs() {
+        assert_eq!(list().invalid_prefix(), None);
+        let list = OidAccessList {
+            allow: vec![],
+            deny: vec!["1.3.6.1..4".to_string(), "ifTable".to_string()],
+        };
+        assert_eq!(list.invalid_prefix(), Some("1.3.6.1..4"));
+    }
+}
@@ -0,0 +1,226 @@
+//! Policy-driven adjustments of SNMP polling
+//!
+//! A policy rule can change how a node is polled while its condition holds,
+//! e.g. `SET polling.interval TO 60s` or `SET polling.profile TO "intensive"`
+//! for non-compliant or degraded devices. Adjustments are recorded when
+//! evaluation results are stored and reverted once the rule no longer matches
+//! the node or is no longer loaded. They are kept through the `DataStore`
+//! settings API, keyed by node and rule.
+//!
+//! An interval adjustment only ever shortens the interval of an OID group.
+//! When several rules adjust a node, the shortest interval applies and the
+//! most recently applied profile replaces the assigned one.
+
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult};
+use crate::policy::{Action, EvaluationResult, FieldRef, PolicyExecutionResult, PolicyRule, Value};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use tracing::info;
+use uuid::Uuid;
+
+use super::pauses::parse_duration;
+
+/// Settings namespace holding adjustments keyed by `node_id/rule`
+const ADJUSTMENTS_NAMESPACE: &str = "polling_adjustments";
+
+/// First segment of the policy fields that adjust polling
+pub const POLLING_FIELD: &str = "polling";
+
+/// A polling setting a policy rule can change
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PollingSetting {
+    /// Poll every OID group at least this often, in seconds
+    Interval(u64),
+    /// Poll with this OID profile instead of the assigned one
+    Profile(String),
+}
+
+impl PollingSetting {
+    /// Reads the setting targeted by `SET field TO value`
+    ///
+    /// Returns `None` for fields outside `polling`. Intervals are durations
+    /// such as `60s` or `5m`, or a number of seconds.
+    ///
+    /// # Errors
+    /// Returns an error message if the field is not `polling.interval` or
+    /// `polling.profile`, or the value does not suit it.
+    #[must_use]
+    pub fn from_action(field: &FieldRef, value: &Value) -> Option<Result<Self, String>> {
+        let (first, rest) = field.path.split_first()?;
+        if first != POLLING_FIELD {
+            return None;
+        }
+        Some(match (rest, value) {
+            ([setting], Value::String(text)) if setting == "interval" => interval(text),
+            ([setting], Value::Number(seconds)) if setting == "interval" => {
+                interval(&format!("{seconds}s"))
+            }
+            ([setting], Value::String(name)) if setting == "profile" && !name.is_empty() => {
+                Ok(Self::Profile(name.clone()))
+            }
+            ([setting], _) if setting == "interval" => {
+                Err(format!("{field} takes a duration such as 60s, not {value}"))
+            }
+            ([setting], _) if setting == "profile" => {
+                Err(format!("{field} takes an OID profile name, not {value}"))
+            }
+            _ => Err(format!(
+                "Unknown polling field {field}: expected polling.interval or polling.profile"
+            )),
+        })
+    }
+}
+
+fn interval(text: &str) -> Result<PollingSetting, String> {
+    let duration = parse_duration(text)?;
+    u64::try_from(duration.num_seconds())
+        .ok()
+        .filter(|seconds| *seconds > 0)
+        .map(PollingSetting::Interval)
+        .ok_or_else(|| format!("Polling interval {text:?} is shorter than a second"))
+}
+
+/// A polling setting applied to a node by a policy rule
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PollingAdjustment {
+    /// Node polled differently
+    pub node_id: Uuid,
+    /// Rule ID, or the rule text for rules without one
+    pub rule: String,
+    /// Setting the rule applies
+    pub setting: PollingSetting,
+    /// When the rule first matched
+    pub applied_at: DateTime<Utc>,
+}
+
+impl PollingAdjustment {
+    fn key(&self) -> String {
+        format!("{}/{}", self.node_id, self.rule)
+    }
+}
+
+/// Names a rule in adjustments: its ID, else its text
+#[must_use]
+pub fn rule_key(rule: &PolicyRule) -> String {
+    rule.id.clone().unwrap_or_else(|| rule.to_string())
+}
+
+/// Lists every adjustment in effect, by node and then oldest first
+///
+/// Datastores without settings support have no adjustments.
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or a stored adjustment is malformed.
+pub async fn list_adjustments(
+    datastore: &dyn DataStore,
+) -> DataStoreResult<Vec<PollingAdjustment>> {
+    let stored = match datastore.list_settings(ADJUSTMENTS_NAMESPACE).await {
+        Ok(stored) => stored,
+        Err(DataStoreError::UnsupportedOperation { .. }) => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut adjustments = stored
+        .into_iter()
+        .map(|(key, value)| {
+            serde_json::from_value(value).map_err(|e| DataStoreError::InternalError {
+                message: format!("stored polling adjustment {key}: {e}"),
+            })
+        })
+        .collect::<DataStoreResult<Vec<PollingAdjustment>>>()?;
+    adjustments.sort_by_key(|adjustment| (adjustment.node_id, adjustment.applied_at));
+    Ok(adjustments)
+}
+
+/// Lists the adjustments in effect for a node, oldest first
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or a stored adjustment is malformed.
+pub async fn node_adjustments(
+    datastore: &dyn DataStore,
+    node_id: Uuid,
+) -> DataStoreResult<Vec<PollingAdjustment>> {
+    let mut adjustments = list_adjustments(datastore).await?;
+    adjustments.retain(|adjustment| adjustment.node_id == node_id);
+    Ok(adjustments)
+}
+
+/// Brings a node's adjustments in line with its latest evaluation
+///
+/// Rules whose condition holds and that `SET` a polling field are applied;
+/// adjustments of rules that no longer match or were not evaluated are
+/// reverted. Adjustments of rules that failed to evaluate are kept until the
+/// rule evaluates again.
+///
+/// # Errors
+/// Returns an error if adjustments cannot be read or written.
+pub async fn reconcile_adjustments(
+    datastore: &dyn DataStore,
+    node_id: Uuid,
+    results: &[PolicyExecutionResult],
+    now: DateTime<Utc>,
+) -> DataStoreResult<()> {
+    let mut wanted: HashMap<String, PollingSetting> = HashMap::new();
+    let mut failed: HashSet<String> = HashSet::new();
+    for result in results {
+        match &result.evaluation_result {
+            EvaluationResult::Satisfied {
+                action: Action::Set { field, value },
+            } => {
+                if let Some(Ok(setting)) = PollingSetting::from_action(field, value) {
+                    wanted.insert(rule_key(&result.rule), setting);
+                }
+            }
+            EvaluationResult::Error { .. } | EvaluationResult::Aborted { .. } => {
+                failed.insert(rule_key(&result.rule));
+            }
+            _ => {}
+        }
+    }
+
+    for adjustment in node_adjustments(datastore, node_id).await? {
+        if wanted.get(&adjustment.rule) == Some(&adjustment.setting) {
+            wanted.remove(&adjustment.rule);
+        } else if !wanted.contains_key(&adjustment.rule) && !failed.contains(&adjustment.rule) {
+            match datastore
+                .delete_setting(ADJUSTMENTS_NAMESPACE, &adjustment.key())
+                .await
+            {
+                Ok(()) | Err(DataStoreError::NotFound { .. }) => {}
+                Err(e) => return Err(e),
+            }
+            info!(
+                node_id = %node_id,
+                rule = %adjustment.rule,
+                "Reverted policy polling adjustment"
+            );
+        }
+    }
+
+    for (rule, setting) in wanted {
+        let adjustment = PollingAdjustment {
+            node_id,
+            rule,
+            setting,
+            applied_at: now,
+        };
+        let value =
+            serde_json::to_value(&adjustment).map_err(|e| DataStoreError::InternalError {
+                message: format!("polling adjustment {}: {e}", adjustment.key()),
+            })?;
+        datastore
+            .put_setting(ADJUSTMENTS_NAMESPACE, &adjustment.key(), &value)
+            .await?;
+        info!(
+            node_id = %node_id,
+            rule = %adjustment.rule,
+            setting = ?adjustment.setting,
+            "Applied policy polling adjustment"
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,125 @@
+use super::*;
+use crate::datastore::sqlite::tests::setup::migrated_store;
+use crate::policy::Condition;
+
+fn field(path: &str) -> FieldRef {
+    FieldRef {
+        path: path.split('.').map(String::from).collect(),
+    }
+}
+
+fn rule(id: &str, path: &str, value: Value) -> PolicyRule {
+    PolicyRule {
+        id: Some(id.to_string()),
+        condition: Condition::True,
+        action: Action::Set {
+            field: field(path),
+            value,
+        },
+    }
+}
+
+fn satisfied(rule: &PolicyRule) -> PolicyExecutionResult {
+    PolicyExecutionResult::new(
+        rule.clone(),
+        EvaluationResult::Satisfied {
+            action: rule.action.clone(),
+        },
+        None,
+    )
+}
+
+fn not_satisfied(rule: &PolicyRule) -> PolicyExecutionResult {
+    PolicyExecutionResult::new(rule.clone(), EvaluationResult::NotSatisfied, None)
+}
+
+#[test]
+fn test_setting_from_action() {
+    let interval = field("polling.interval");
+    assert_eq!(
+        PollingSetting::from_action(&interval, &Value::String("1m".to_string())),
+        Some(Ok(PollingSetting::Interval(60)))
+    );
+    assert_eq!(
+        PollingSetting::from_action(&interval, &Value::Number(30.0)),
+        Some(Ok(PollingSetting::Interval(30)))
+    );
+    assert!(matches!(
+        PollingSetting::from_action(&interval, &Value::Number(0.5)),
+        Some(Err(_))
+    ));
+    assert_eq!(
+        PollingSetting::from_action(
+            &field("polling.profile"),
+            &Value::String("intensive".to_string())
+        ),
+        Some(Ok(PollingSetting::Profile("intensive".to_string())))
+    );
+    assert!(matches!(
+        PollingSetting::from_action(&field("polling.timeout"), &Value::Number(5.0)),
+        Some(Err(_))
+    ));
+    assert_eq!(
+        PollingSetting::from_action(&field("custom_data.polling"), &Value::Null),
+        None
+    );
+}
+
+#[tokio::test]
+async fn test_adjustment_reverts_when_condition_clears() {
+    let store = migrated_store().await;
+    let node_id = Uuid::new_v4();
+    let faster = rule(
+        "degraded",
+        "polling.interval",
+        Value::String("60s".to_string()),
+    );
+    let intensive = rule(
+        "non-compliant",
+        "polling.profile",
+        Value::String("intensive".to_string()),
+    );
+    let applied_at = Utc::now();
+
+    let results = [satisfied(&faster), satisfied(&intensive)];
+    reconcile_adjustments(&store, node_id, &results, applied_at)
+        .await
+        .unwrap();
+    let adjustments = node_adjustments(&store, node_id).await.unwrap();
+    assert_eq!(adjustments.len(), 2);
+
+    // Still matching: kept as first applied
+    let later = applied_at + chrono::TimeDelta::minutes(5);
+    reconcile_adjustments(&store, node_id, &results, later)
+        .await
+        .unwrap();
+    assert_eq!(
+        node_adjustments(&store, node_id).await.unwrap(),
+        adjustments
+    );
+
+    // The interval rule clears and the profile rule is no longer loaded
+    reconcile_adjustments(&store, node_id, &[not_satisfied(&faster)], later)
+        .await
+        .unwrap();
+    assert!(node_adjustments(&store, node_id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_failed_evaluation_keeps_adjustment() {
+    let store = migrated_store().await;
+    let node_id = Uuid::new_v4();
+    let faster = rule("degraded", "polling.interval", Value::Number(60.0));
+
+    reconcile_adjustments(&store, node_id, &[satisfied(&faster)], Utc::now())
+        .await
+        .unwrap();
+    let failed = PolicyExecutionResult::new_error(faster, "context unavailable".to_string());
+    reconcile_adjustments(&store, node_id, &[failed], Utc::now())
+        .await
+        .unwrap();
+
+    let adjustments = list_adjustments(&store).await.unwrap();
+    assert_eq!(adjustments.len(), 1);
+    assert_eq!(adjustments[0].setting, PollingSetting::Interval(60));
+}
@@ -3,12 +3,15 @@ use super::super::session_management::SessionManager;
 use super::ClientOperations;
 use crate::snmp::SnmpError;
 use crate::snmp::config::SessionConfig;
-use crate::snmp::values::SnmpValue;
-use std::collections::HashMap;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::sync::Arc;
 use std::time::Duration;
 
+#[path = "client_operations_mocks.rs"]
+mod mocks;
+
+use mocks::{MockClientOperations, MockSessionManager};
+
 #[test]
 fn test_client_operations_creation() {
     let config = SnmpClientConfig::default();
@@ -47,112 +50,9 @@ fn create_test_session_config() -> SessionConfig {
         timeout: Duration::from_secs(5),
         retries: 3,
         max_vars_per_request: 10,
-    }
-}
-
-// Mock session manager that returns controlled errors for testing
-struct MockSessionManager {
-    should_fail: bool,
-}
-
-impl MockSessionManager {
-    fn new(_config: SessionConfig, _max_connections: usize, should_fail: bool) -> Self {
-        Self { should_fail }
-    }
-
-    // Mock get_session_mut that returns a quick error instead of doing network calls
-    fn get_session_mut(
-        &self,
-        _address: SocketAddr,
-        _config: Option<SessionConfig>,
-    ) -> Result<MockSnmpSession, SnmpError> {
-        if self.should_fail {
-            Err(SnmpError::Network {
-                message: "Mock connection failure".to_string(),
-            })
-        } else {
-            Ok(MockSnmpSession::new())
-        }
-    }
-}
-
-// Mock SNMP session that fails fast for testing
-struct MockSnmpSession;
-
-impl MockSnmpSession {
-    fn new() -> Self {
-        Self
-    }
-
-    fn get(_oids: &[&str]) -> Result<HashMap<String, SnmpValue>, SnmpError> {
-        Err(SnmpError::Network {
-            message: "Mock SNMP operation failure".to_string(),
-        })
-    }
-
-    fn get_next(_start_oid: &str) -> Result<HashMap<String, SnmpValue>, SnmpError> {
-        Err(SnmpError::Network {
-            message: "Mock SNMP operation failure".to_string(),
-        })
-    }
-}
-
-// Mock client operations for testing semaphore behavior without network calls
-struct MockClientOperations {
-    connection_semaphore: tokio::sync::Semaphore,
-    session_manager: MockSessionManager,
-    max_connections: usize,
-}
-
-impl MockClientOperations {
-    fn new(session_manager: MockSessionManager, max_connections: usize) -> Self {
-        Self {
-            connection_semaphore: tokio::sync::Semaphore::new(max_connections),
-            session_manager,
-            max_connections,
-        }
-    }
-
-    fn available_permits(&self) -> usize {
-        self.connection_semaphore.available_permits()
-    }
-
-    async fn get(
-        &self,
-        address: SocketAddr,
-        oids: &[&str],
-        config: Option<SessionConfig>,
-    ) -> Result<HashMap<String, SnmpValue>, SnmpError> {
-        // Acquire connection permit - must be held for duration of function to maintain semaphore limit
-        let _permit =
-            self.connection_semaphore
-                .acquire()
-                .await
-                .map_err(|_| SnmpError::PoolExhausted {
-                    max_connections: self.max_connections,
-                })?;
-
-        let _session = self.session_manager.get_session_mut(address, config)?;
-        MockSnmpSession::get(oids)
-    }
-
-    async fn walk(
-        &self,
-        address: SocketAddr,
-        start_oid: &str,
-        config: Option<SessionConfig>,
-    ) -> Result<HashMap<String, SnmpValue>, SnmpError> {
-        // Acquire connection permit - must be held for duration of function to maintain semaphore limit
-        let _permit =
-            self.connection_semaphore
-                .acquire()
-                .await
-                .map_err(|_| SnmpError::PoolExhausted {
-                    max_connections: self.max_connections,
-                })?;
-
-        let _session = self.session_manager.get_session_mut(address, config)?;
-        MockSnmpSession::get_next(start_oid)
+        pipeline_window: 4,
+        max_message_size: 1472,
+        context: None,
     }
 }
 
@@ -329,6 +229,9 @@ async fn test_get_method_with_custom_session_config() {
         timeout: Duration::from_secs(10),
         retries: 5,
         max_vars_per_request: 20,
+        pipeline_window: 4,
+        max_message_size: 1472,
+        context: None,
     };
 
     // This should pass custom config through to mock session manager and fail quickly
@@ -363,6 +266,9 @@ async fn test_walk_method_with_custom_session_config() {
         timeout: Duration::from_secs(10),
         retries: 5,
         max_vars_per_request: 20,
+        pipeline_window: 4,
+        max_message_size: 1472,
+        context: None,
     };
 
     // This should pass custom config through to mock session manager and fail quickly
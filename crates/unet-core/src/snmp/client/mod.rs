@@ -7,14 +7,16 @@ mod session_management;
 // Re-export public types
 pub use client_stats::SnmpClientStats;
 
-use super::SnmpResult;
+use super::access::OidAccessList;
 use super::config::{SessionConfig, SnmpClientConfig};
 use super::values::SnmpValue;
+use super::{SnmpError, SnmpResult};
 use client_operations::ClientOperations;
 use session_management::SessionManager;
 use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::time::Duration;
+use tracing::warn;
 
 #[cfg(test)]
 use async_trait::async_trait;
@@ -28,6 +30,8 @@ pub struct SnmpClient {
     session_manager: SessionManager,
     /// Maximum number of concurrent connections
     max_connections: usize,
+    /// OIDs that may be requested
+    oid_access: OidAccessList,
 }
 
 impl SnmpClient {
@@ -44,6 +48,7 @@ impl SnmpClient {
             operations,
             session_manager: session_manager_for_client,
             max_connections: config.max_connections,
+            oid_access: config.oid_access,
         }
     }
 
@@ -55,6 +60,7 @@ impl SnmpClient {
     /// - Connection to target fails
     /// - SNMP request times out
     /// - Invalid OIDs are provided
+    /// - An OID is not permitted by the OID access list
     /// - Authentication fails
     pub async fn get(
         &self,
@@ -62,6 +68,9 @@ impl SnmpClient {
         oids: &[&str],
         config: Option<SessionConfig>,
     ) -> SnmpResult<HashMap<String, SnmpValue>> {
+        if let Some(oid) = oids.iter().find(|oid| !self.oid_access.permits(oid)) {
+            return Err(refused("get", address, oid));
+        }
         self.operations.get(address, oids, config).await
     }
 
@@ -73,6 +82,8 @@ impl SnmpClient {
     /// - Connection to target fails
     /// - SNMP request times out
     /// - Invalid start OID is provided
+    /// - The start OID, or a subtree below it, is not permitted by the OID
+    ///   access list
     /// - Authentication fails
     pub async fn walk(
         &self,
@@ -80,6 +91,9 @@ impl SnmpClient {
         start_oid: &str,
         config: Option<SessionConfig>,
     ) -> SnmpResult<HashMap<String, SnmpValue>> {
+        if !self.oid_access.permits_walk(start_oid) {
+            return Err(refused("walk", address, start_oid));
+        }
         self.operations.walk(address, start_oid, config).await
     }
 
@@ -130,6 +144,20 @@ impl SnmpClient {
     }
 }
 
+/// Records a request refused by the OID access list in the audit log
+fn refused(operation: &str, address: SocketAddr, oid: &str) -> SnmpError {
+    warn!(
+        target: "audit",
+        operation,
+        %address,
+        oid,
+        "SNMP request refused by the OID access list"
+    );
+    SnmpError::OidNotPermitted {
+        oid: oid.to_string(),
+    }
+}
+
 #[cfg(test)]
 #[async_trait]
 impl crate::snmp::testing::SnmpOperations for SnmpClient {
@@ -237,6 +265,33 @@ mod tests {
         assert_eq!(stats.active_sessions, 0);
     }
 
+    #[tokio::test]
+    async fn test_requests_outside_oid_access_list_are_refused() {
+        let client = SnmpClient::new(SnmpClientConfig {
+            oid_access: OidAccessList {
+                allow: vec!["1.3.6.1.2.1".to_string()],
+                deny: vec!["1.3.6.1.2.1.4.21".to_string()],
+            },
+            ..Default::default()
+        });
+        let address = "127.0.0.1:161".parse().unwrap();
+
+        let result = client
+            .get(
+                address,
+                &["1.3.6.1.2.1.1.3.0", "1.3.6.1.4.1.9.9.96.1"],
+                None,
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(SnmpError::OidNotPermitted { oid }) if oid == "1.3.6.1.4.1.9.9.96.1"
+        ));
+        let result = client.walk(address, "1.3.6.1.2.1.4", None).await;
+        assert!(matches!(result, Err(SnmpError::OidNotPermitted { .. })));
+        assert_eq!(client.stats().await.active_sessions, 0);
+    }
+
     #[tokio::test]
     async fn test_snmp_client_cleanup_sessions() {
         let config = SnmpClientConfig::default();
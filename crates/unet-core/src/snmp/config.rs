@@ -1,5 +1,6 @@
 //! SNMP configuration types
 
+use super::access::OidAccessList;
 use crate::config::{defaults, network};
 use serde::{Deserialize, Serialize};
 use std::net::SocketAddr;
@@ -47,6 +48,40 @@ pub struct SessionConfig {
     pub retries: u32,
     /// Maximum number of variables per request
     pub max_vars_per_request: usize,
+    /// Maximum number of GET requests kept outstanding at once
+    #[serde(default = "default_pipeline_window")]
+    pub pipeline_window: usize,
+    /// Largest message, in bytes, a response is expected to fit; GETs are
+    /// split so their estimated response stays within it
+    #[serde(default = "default_max_message_size")]
+    pub max_message_size: usize,
+    /// Logical instance to poll, e.g. a VRF or virtual context
+    ///
+    /// Sent as the `SNMPv3` `contextName`, or as an indexed community
+    /// (`community@context`) for v1/v2c. `None` polls the default instance.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+}
+
+/// Default number of outstanding GET requests per session
+pub const DEFAULT_PIPELINE_WINDOW: usize = 4;
+
+/// Upper bound on the pipeline window, to keep socket usage per session small
+pub const MAX_PIPELINE_WINDOW: usize = 32;
+
+const fn default_pipeline_window() -> usize {
+    DEFAULT_PIPELINE_WINDOW
+}
+
+/// Default largest response, the payload of one UDP datagram on an Ethernet
+/// link without IP fragmentation
+pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 1472;
+
+/// Smallest message every SNMP agent must accept (RFC 3417)
+pub const MIN_MAX_MESSAGE_SIZE: usize = 484;
+
+pub(crate) const fn default_max_message_size() -> usize {
+    DEFAULT_MAX_MESSAGE_SIZE
 }
 
 impl Default for SessionConfig {
@@ -59,6 +94,34 @@ impl Default for SessionConfig {
             timeout: Duration::from_secs(5),
             retries: 3,
             max_vars_per_request: 10,
+            pipeline_window: DEFAULT_PIPELINE_WINDOW,
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            context: None,
+        }
+    }
+}
+
+impl SessionConfig {
+    /// The same session for the logical instance `context`
+    #[must_use]
+    pub fn for_context(&self, context: &str) -> Self {
+        Self {
+            context: Some(context.to_string()),
+            ..self.clone()
+        }
+    }
+
+    /// Community sent on the wire, indexed by the context if there is one
+    ///
+    /// `None` for `SNMPv3` user-based credentials.
+    #[must_use]
+    pub fn community(&self) -> Option<String> {
+        match &self.credentials {
+            SnmpCredentials::Community { community } => Some(match &self.context {
+                Some(context) => format!("{community}@{context}"),
+                None => community.clone(),
+            }),
+            SnmpCredentials::UserBased { .. } => None,
         }
     }
 }
@@ -74,6 +137,9 @@ pub struct SnmpClientConfig {
     pub health_check_interval: Duration,
     /// Session timeout (unused sessions are cleaned up)
     pub session_timeout: Duration,
+    /// OIDs the client may request
+    #[serde(default)]
+    pub oid_access: OidAccessList,
 }
 
 impl Default for SnmpClientConfig {
@@ -83,6 +149,7 @@ impl Default for SnmpClientConfig {
             default_session: SessionConfig::default(),
             health_check_interval: Duration::from_secs(60),
             session_timeout: Duration::from_secs(300),
+            oid_access: OidAccessList::default(),
         }
     }
 }
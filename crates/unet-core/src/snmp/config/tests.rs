@@ -2,7 +2,8 @@
 
 #[cfg(test)]
 mod snmp_config_tests {
-    use super::super::{SessionConfig, SnmpClientConfig, SnmpCredentials};
+    use super::super::{DEFAULT_PIPELINE_WINDOW, SessionConfig, SnmpClientConfig, SnmpCredentials};
+    use crate::snmp::OidAccessList;
     use std::net::SocketAddr;
     use std::str::FromStr;
     use std::time::Duration;
@@ -92,6 +93,7 @@ mod snmp_config_tests {
         assert_eq!(config.timeout, Duration::from_secs(5));
         assert_eq!(config.retries, 3);
         assert_eq!(config.max_vars_per_request, 10);
+        assert_eq!(config.pipeline_window, DEFAULT_PIPELINE_WINDOW);
 
         // Test default credentials
         match config.credentials {
@@ -115,6 +117,9 @@ mod snmp_config_tests {
             timeout: Duration::from_secs(10),
             retries: 5,
             max_vars_per_request: 20,
+            pipeline_window: 4,
+            max_message_size: 1472,
+            context: None,
         };
 
         assert_eq!(config.version, 3);
@@ -125,6 +130,47 @@ mod snmp_config_tests {
         assert_eq!(config.address.port(), 161);
     }
 
+    #[test]
+    fn test_session_config_without_pipeline_window_uses_default() {
+        let mut value = serde_json::to_value(SessionConfig {
+            pipeline_window: 1,
+            ..SessionConfig::default()
+        })
+        .unwrap();
+        value.as_object_mut().unwrap().remove("pipeline_window");
+
+        let config: SessionConfig = serde_json::from_value(value).unwrap();
+
+        assert_eq!(config.pipeline_window, DEFAULT_PIPELINE_WINDOW);
+    }
+
+    #[test]
+    fn test_session_config_context_indexes_community() {
+        let config = SessionConfig::default();
+        let vrf = config.for_context("vrf-blue");
+
+        assert_eq!(config.community().as_deref(), Some("public"));
+        assert_eq!(vrf.community().as_deref(), Some("public@vrf-blue"));
+        assert_eq!(vrf.context.as_deref(), Some("vrf-blue"));
+        assert!(
+            !serde_json::to_value(&config)
+                .unwrap()
+                .as_object()
+                .unwrap()
+                .contains_key("context")
+        );
+
+        let v3 = SessionConfig {
+            credentials: SnmpCredentials::UserBased {
+                username: "monitor".to_string(),
+                auth: None,
+                privacy: None,
+            },
+            ..vrf
+        };
+        assert_eq!(v3.community(), None);
+    }
+
     #[test]
     fn test_snmp_client_config_default() {
         let config = SnmpClientConfig::default();
@@ -149,6 +195,9 @@ mod snmp_config_tests {
             timeout: Duration::from_secs(15),
             retries: 2,
             max_vars_per_request: 5,
+            pipeline_window: 4,
+            max_message_size: 1472,
+            context: None,
         };
 
         let config = SnmpClientConfig {
@@ -156,6 +205,7 @@ mod snmp_config_tests {
             default_session: custom_session,
             health_check_interval: Duration::from_secs(30),
             session_timeout: Duration::from_secs(600),
+            oid_access: OidAccessList::default(),
         };
 
         assert_eq!(config.max_connections, 50);
@@ -0,0 +1,127 @@
+//! Logical instances polled separately on a device
+//!
+//! VRF-aware SNMP agents and devices with virtual contexts only answer for
+//! one logical instance per request: the instance is picked by the `SNMPv3`
+//! `contextName` or, for v1/v2c, by an indexed community such as
+//! `public@vrf-blue`. A node can list the contexts it has; each is polled
+//! alongside the default instance and its results are kept in the node's
+//! status under the context name. Contexts are kept through the `DataStore`
+//! settings API, keyed by node.
+
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+/// Settings namespace holding context names keyed by node ID
+const CONTEXTS_NAMESPACE: &str = "polling_contexts";
+
+/// Longest context name `SNMPv3` allows, in bytes
+pub const MAX_CONTEXT_LEN: usize = 32;
+
+/// Checks that `name` can be sent as a context name
+///
+/// # Errors
+/// Returns a validation error if the name is empty, longer than
+/// [`MAX_CONTEXT_LEN`] bytes, or contains whitespace or `@`, which would make
+/// the indexed community ambiguous.
+pub fn validate_context(name: &str) -> DataStoreResult<()> {
+    let problem = if name.is_empty() {
+        "must not be empty"
+    } else if name.len() > MAX_CONTEXT_LEN {
+        "must be at most 32 bytes long"
+    } else if name.chars().any(|c| c.is_whitespace() || c == '@') {
+        "must not contain whitespace or '@'"
+    } else {
+        return Ok(());
+    };
+    Err(DataStoreError::ValidationError {
+        message: format!("Context name '{name}' {problem}"),
+    })
+}
+
+/// Sets the contexts polled on a node, replacing any it had
+///
+/// Names are sorted and duplicates dropped; an empty list clears them, so
+/// only the default instance is polled. Returns the names stored.
+///
+/// # Errors
+/// Returns a validation error if a name is invalid, or an error if the
+/// datastore write fails.
+pub async fn set_polling_contexts(
+    datastore: &dyn DataStore,
+    node_id: Uuid,
+    mut contexts: Vec<String>,
+) -> DataStoreResult<Vec<String>> {
+    for name in &contexts {
+        validate_context(name)?;
+    }
+    contexts.sort();
+    contexts.dedup();
+
+    let key = node_id.to_string();
+    if contexts.is_empty() {
+        match datastore.delete_setting(CONTEXTS_NAMESPACE, &key).await {
+            Ok(()) | Err(DataStoreError::NotFound { .. }) => {}
+            Err(e) => return Err(e),
+        }
+    } else {
+        datastore
+            .put_setting(CONTEXTS_NAMESPACE, &key, &serde_json::json!(contexts))
+            .await?;
+    }
+    Ok(contexts)
+}
+
+/// Lists the contexts polled on a node, sorted
+///
+/// Datastores without settings support have no contexts.
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or the stored list is malformed.
+pub async fn polling_contexts(
+    datastore: &dyn DataStore,
+    node_id: Uuid,
+) -> DataStoreResult<Vec<String>> {
+    let key = node_id.to_string();
+    match datastore.get_setting(CONTEXTS_NAMESPACE, &key).await {
+        Ok(Some(value)) => parse_contexts(&key, value),
+        Ok(None) | Err(DataStoreError::UnsupportedOperation { .. }) => Ok(Vec::new()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Lists the contexts of every node that has any, by node ID
+///
+/// Datastores without settings support have no contexts.
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or a stored list is malformed.
+pub async fn list_polling_contexts(
+    datastore: &dyn DataStore,
+) -> DataStoreResult<BTreeMap<Uuid, Vec<String>>> {
+    let stored = match datastore.list_settings(CONTEXTS_NAMESPACE).await {
+        Ok(stored) => stored,
+        Err(DataStoreError::UnsupportedOperation { .. }) => return Ok(BTreeMap::new()),
+        Err(e) => return Err(e),
+    };
+    stored
+        .into_iter()
+        .map(|(key, value)| {
+            let node_id = key
+                .parse::<Uuid>()
+                .map_err(|e| DataStoreError::InternalError {
+                    message: format!("stored polling contexts key {key}: {e}"),
+                })?;
+            Ok((node_id, parse_contexts(&key, value)?))
+        })
+        .collect()
+}
+
+fn parse_contexts(key: &str, value: serde_json::Value) -> DataStoreResult<Vec<String>> {
+    serde_json::from_value(value).map_err(|e| DataStoreError::InternalError {
+        message: format!("stored polling contexts of {key}: {e}"),
+    })
+}
+
+#[cfg(test)]
+mod tests;
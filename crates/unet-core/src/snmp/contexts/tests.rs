@@ -0,0 +1,103 @@
+use super::*;
+use crate::datastore::sqlite::tests::setup::migrated_store;
+
+#[test]
+fn test_validate_context() {
+    assert!(validate_context("vrf-blue").is_ok());
+    assert!(validate_context("").is_err());
+    assert!(validate_context("vrf blue").is_err());
+    assert!(validate_context("public@vrf").is_err());
+    assert!(validate_context(&"v".repeat(MAX_CONTEXT_LEN + 1)).is_err());
+}
+
+#[tokio::test]
+async fn test_set_polling_contexts_sorts_and_clears() {
+    let store = migrated_store().await;
+    let node_id = Uuid::new_v4();
+
+    let stored = set_polling_contexts(
+        &store,
+        node_id,
+        vec![
+            "vrf-red".to_string(),
+            "vrf-blue".to_string(),
+            "vrf-red".to_string(),
+        ],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(stored, ["vrf-blue", "vrf-red"]);
+    assert_eq!(polling_contexts(&store, node_id).await.unwrap(), stored);
+    assert_eq!(
+        list_polling_contexts(&store).await.unwrap()[&node_id],
+        stored
+    );
+    assert!(
+        polling_contexts(&store, Uuid::new_v4())
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    assert!(
+        set_polling_contexts(&store, node_id, vec!["bad name".to_string()])
+            .await
+            .is_err()
+    );
+    assert_eq!(polling_contexts(&store, node_id).await.unwrap(), stored);
+
+    set_polling_contexts(&store, node_id, Vec::new())
+        .await
+        .unwrap();
+    assert!(list_polling_contexts(&store).await.unwrap().is_empty());
+}
+
+#[cfg(feature = "snmp")]
+#[test]
+fn test_context_results_merge_into_namespaced_status() {
+    use crate::models::derived::NodeStatus;
+    use crate::snmp::{PollingResult, SnmpValue};
+    use std::collections::HashMap;
+    use std::time::{Duration, SystemTime};
+
+    let node_id = Uuid::new_v4();
+    let result = |context: Option<&str>, success: bool| PollingResult {
+        task_id: Uuid::new_v4(),
+        node_id,
+        target: "192.0.2.1:161".parse().unwrap(),
+        timestamp: SystemTime::now(),
+        success,
+        values: HashMap::from([
+            ("1.3.6.1.2.1.2.2.1.1.7".to_string(), SnmpValue::Integer(7)),
+            (
+                "1.3.6.1.2.1.2.2.1.2.7".to_string(),
+                SnmpValue::String("Vlan7".to_string()),
+            ),
+            ("1.3.6.1.2.1.2.2.1.8.7".to_string(), SnmpValue::Integer(1)),
+        ]),
+        error: (!success).then(|| "timeout".to_string()),
+        duration: Duration::from_millis(20),
+        reachable: true,
+        context: context.map(String::from),
+    };
+
+    let mut status = NodeStatus::new(node_id);
+    result(Some("vrf-blue"), true).apply_to(&mut status);
+    result(Some("vrf-red"), false).apply_to(&mut status);
+
+    assert!(!status.reachable);
+    assert!(status.interfaces.is_empty());
+    let blue = &status.contexts["vrf-blue"];
+    assert!(blue.reachable);
+    assert_eq!(blue.interfaces.len(), 1);
+    assert_eq!(blue.interfaces[0].name, "Vlan7");
+    let red = &status.contexts["vrf-red"];
+    assert_eq!(red.consecutive_failures, 1);
+    assert_eq!(red.last_error.as_deref(), Some("timeout"));
+
+    result(None, true).apply_to(&mut status);
+    assert!(status.reachable);
+    assert_eq!(status.interfaces.len(), 1);
+    assert_eq!(status.contexts.len(), 2);
+}
@@ -4,36 +4,67 @@
 //! derived state information. It supports `SNMPv2c` and `SNMPv3` protocols with
 //! connection pooling and error handling.
 //!
+//! Sessions, the client, and the poller need the `snmp` feature; values,
+//! OIDs, configuration, and OID profiles are always available.
+//!
 //! # Architecture
 //!
+//! - [`access`] - Allow- and deny-lists of OID prefixes enforced by the client
+//! - [`adjustments`] - Polling settings changed by policy rules
 //! - [`client`] - SNMP client wrapper with connection pooling
+//! - [`contexts`] - Logical instances, such as VRFs, polled separately
 //! - [`oids`] - Standard and vendor-specific OID definitions
+//! - [`pauses`] - Scoped pauses of polling, e.g. during maintenance
+//! - [`profiles`] - Role-aware OID profiles and their assignments
 //! - [`session`] - SNMP session management
 //! - [`poller`] - Background polling implementation
+//! - [`shards`] - Polling shards split between collectors through leases
 //! - [`types`] - SNMP-specific data types
+//! - [`walk`] - Walk snapshots in `snmpwalk` format
 
 use std::time::Duration;
 use thiserror::Error;
 
+pub mod access;
+pub mod adjustments;
+#[cfg(feature = "snmp")]
 pub mod client;
 pub mod config;
+pub mod contexts;
 pub mod oids;
+pub mod pauses;
+#[cfg(feature = "snmp")]
 pub mod poller;
+pub mod profiles;
+#[cfg(feature = "snmp")]
 pub mod session;
+pub mod shards;
 pub mod types;
 pub mod values;
+pub mod walk;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "snmp"))]
 pub mod testing;
 
 // Re-export main types for backward compatibility
+pub use access::OidAccessList;
+pub use adjustments::{PollingAdjustment, PollingSetting};
+#[cfg(feature = "snmp")]
 pub use client::{SnmpClient, SnmpClientStats};
 pub use config::{SessionConfig, SnmpClientConfig, SnmpCredentials};
 pub use oids::{OidMap, StandardOid, VendorOid};
-pub use poller::{PollingConfig, PollingHandle, PollingResult, PollingScheduler, PollingTask};
+pub use pauses::{PauseScope, PausedNodes, PollingPause};
+#[cfg(feature = "snmp")]
+pub use poller::{
+    PollingConfig, PollingHandle, PollingResult, PollingScheduler, PollingTask, Precheck,
+    PrecheckMethod,
+};
+#[cfg(feature = "snmp")]
 pub use session::SnmpSession;
+pub use shards::ShardSet;
 pub use types::SnmpType;
-pub use values::SnmpValue;
+pub use values::{SnmpUnit, SnmpValue};
+pub use walk::{WalkParseError, format_walk, parse_walk};
 
 /// SNMP error types
 #[derive(Error, Debug, Clone)]
@@ -83,6 +114,13 @@ pub enum SnmpError {
         /// The maximum number of connections in the pool
         max_connections: usize,
     },
+
+    /// OID refused by the configured OID access list
+    #[error("OID {oid} is not permitted by the SNMP OID access list")]
+    OidNotPermitted {
+        /// The refused OID
+        oid: String,
+    },
 }
 
 impl From<std::io::Error> for SnmpError {
@@ -181,6 +219,9 @@ mod tests {
             timeout: Duration::from_secs(5),
             retries: 3,
             max_vars_per_request: 10,
+            pipeline_window: 4,
+            max_message_size: 1472,
+            context: None,
         };
 
         let session = SnmpSession::new(config);
@@ -1,6 +1,7 @@
 //! OID mapping utility for organizing and accessing all types of OIDs
 
 use super::{StandardOid, VendorOid};
+use crate::snmp::values::{SnmpUnit, SnmpValue};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -13,14 +14,32 @@ pub struct OidMap {
     vendor: HashMap<String, VendorOid>,
     /// Custom OIDs defined by user
     custom: HashMap<String, String>,
+    /// Units of numeric OIDs, keyed by OID or table column prefix
+    #[serde(default)]
+    units: HashMap<String, SnmpUnit>,
 }
 
+/// IF-MIB ifXTable columns reported in a known unit
+const IF_X_TABLE_UNITS: [(&str, SnmpUnit); 5] = [
+    // ifHCInOctets
+    ("1.3.6.1.2.1.31.1.1.1.6", SnmpUnit::Octets),
+    // ifHCInUcastPkts
+    ("1.3.6.1.2.1.31.1.1.1.7", SnmpUnit::Count),
+    // ifHCOutOctets
+    ("1.3.6.1.2.1.31.1.1.1.10", SnmpUnit::Octets),
+    // ifHCOutUcastPkts
+    ("1.3.6.1.2.1.31.1.1.1.11", SnmpUnit::Count),
+    // ifHighSpeed
+    ("1.3.6.1.2.1.31.1.1.1.15", SnmpUnit::MegabitsPerSecond),
+];
+
 impl Default for OidMap {
     fn default() -> Self {
         let mut map = Self {
             standard: HashMap::new(),
             vendor: HashMap::new(),
             custom: HashMap::new(),
+            units: HashMap::new(),
         };
 
         // Populate with all standard OIDs
@@ -30,6 +49,14 @@ impl Default for OidMap {
         for oid in StandardOid::interface_oids() {
             map.standard.insert(format!("{oid:?}"), oid);
         }
+        for oid in map.standard.values() {
+            if let Some(unit) = oid.unit() {
+                map.units.insert(oid.oid().to_string(), unit);
+            }
+        }
+        for (oid, unit) in IF_X_TABLE_UNITS {
+            map.units.insert(oid.to_string(), unit);
+        }
 
         // Populate with common vendor OIDs
         for oid in VendorOid::cisco_common() {
@@ -98,6 +125,33 @@ impl OidMap {
             .or_else(|| self.get_custom(name).map(str::to_string))
     }
 
+    /// Set the unit numeric values of an OID, or of every instance of a
+    /// table column, are reported in
+    pub fn set_unit(&mut self, oid: String, unit: SnmpUnit) {
+        self.units.insert(oid, unit);
+    }
+
+    /// Get the unit of an OID from its longest registered prefix
+    #[must_use]
+    pub fn unit(&self, oid: &str) -> Option<SnmpUnit> {
+        self.units
+            .iter()
+            .filter(|(prefix, _)| {
+                oid.strip_prefix(prefix.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+            })
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, unit)| *unit)
+    }
+
+    /// Convert a polled value to the base unit of its OID
+    ///
+    /// Returns `None` if the OID has no known unit or the value is not numeric.
+    #[must_use]
+    pub fn normalize(&self, oid: &str, value: &SnmpValue) -> Option<f64> {
+        value.scaled(self.unit(oid)?)
+    }
+
     /// Get all OID names in the map
     #[must_use]
     pub fn list_names(&self) -> Vec<String> {
@@ -175,6 +229,26 @@ mod tests {
         assert_eq!(all_names.len(), standard_names.len() + vendor_names.len());
     }
 
+    #[test]
+    fn test_oid_map_units_match_column_instances() {
+        let mut map = OidMap::default();
+
+        assert_eq!(map.unit("1.3.6.1.2.1.1.3.0"), Some(SnmpUnit::Hundredths));
+        assert_eq!(
+            map.unit("1.3.6.1.2.1.31.1.1.1.15.3"),
+            Some(SnmpUnit::MegabitsPerSecond)
+        );
+        assert_eq!(map.unit("1.3.6.1.2.1.2.2.1.50"), None);
+        let bps = map.normalize("1.3.6.1.2.1.31.1.1.1.15.3", &SnmpValue::Gauge32(40));
+        assert!((bps.unwrap() - 40_000_000.0).abs() < f64::EPSILON);
+
+        map.set_unit("1.3.6.1.4.1.9.9.13.1.3.1.3".to_string(), SnmpUnit::Count);
+        assert_eq!(
+            map.unit("1.3.6.1.4.1.9.9.13.1.3.1.3.1"),
+            Some(SnmpUnit::Count)
+        );
+    }
+
     #[test]
     fn test_add_standard_and_vendor_methods() {
         let mut map = OidMap::new();
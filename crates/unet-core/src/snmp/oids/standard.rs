@@ -1,5 +1,6 @@
 //! Standard SNMP OID definitions for RFC-compliant network monitoring
 
+use crate::snmp::values::SnmpUnit;
 use serde::{Deserialize, Serialize};
 
 /// Standard SNMP OIDs commonly used for network device monitoring
@@ -53,6 +54,11 @@ pub enum StandardOid {
     IfOutUcastPkts,
     /// Interface output errors (1.3.6.1.2.1.2.2.1.20)
     IfOutErrors,
+    /// Ethernet frame check sequence (CRC) errors, indexed by ifIndex
+    /// (EtherLike-MIB, 1.3.6.1.2.1.10.7.2.1.3)
+    Dot3StatsFcsErrors,
+    /// ENTITY-MIB physical entity table base (1.3.6.1.2.1.47.1.1.1.1)
+    EntPhysicalTable,
 }
 
 impl StandardOid {
@@ -84,6 +90,8 @@ impl StandardOid {
             Self::IfOutOctets => "1.3.6.1.2.1.2.2.1.16",
             Self::IfOutUcastPkts => "1.3.6.1.2.1.2.2.1.17",
             Self::IfOutErrors => "1.3.6.1.2.1.2.2.1.20",
+            Self::Dot3StatsFcsErrors => "1.3.6.1.2.1.10.7.2.1.3",
+            Self::EntPhysicalTable => "1.3.6.1.2.1.47.1.1.1.1",
         }
     }
 
@@ -115,6 +123,24 @@ impl StandardOid {
             Self::IfOutOctets => "Interface output octets",
             Self::IfOutUcastPkts => "Interface output unicast packets",
             Self::IfOutErrors => "Interface output errors",
+            Self::Dot3StatsFcsErrors => "Ethernet frame check sequence (CRC) errors",
+            Self::EntPhysicalTable => "Physical entity (hardware inventory) table",
+        }
+    }
+
+    /// Get the unit numeric values of this OID are reported in
+    #[must_use]
+    pub const fn unit(&self) -> Option<SnmpUnit> {
+        match self {
+            Self::SysUpTime | Self::IfLastChange => Some(SnmpUnit::Hundredths),
+            Self::IfSpeed => Some(SnmpUnit::BitsPerSecond),
+            Self::IfInOctets | Self::IfOutOctets => Some(SnmpUnit::Octets),
+            Self::IfInUcastPkts
+            | Self::IfInErrors
+            | Self::IfOutUcastPkts
+            | Self::IfOutErrors
+            | Self::Dot3StatsFcsErrors => Some(SnmpUnit::Count),
+            _ => None,
         }
     }
 
@@ -152,6 +178,7 @@ impl StandardOid {
             Self::IfOutOctets,
             Self::IfOutUcastPkts,
             Self::IfOutErrors,
+            Self::Dot3StatsFcsErrors,
         ]
     }
 }
@@ -192,6 +219,22 @@ mod tests {
         assert_eq!(StandardOid::IfOutOctets.oid(), "1.3.6.1.2.1.2.2.1.16");
         assert_eq!(StandardOid::IfOutUcastPkts.oid(), "1.3.6.1.2.1.2.2.1.17");
         assert_eq!(StandardOid::IfOutErrors.oid(), "1.3.6.1.2.1.2.2.1.20");
+        assert_eq!(
+            StandardOid::Dot3StatsFcsErrors.oid(),
+            "1.3.6.1.2.1.10.7.2.1.3"
+        );
+        assert_eq!(
+            StandardOid::EntPhysicalTable.oid(),
+            "1.3.6.1.2.1.47.1.1.1.1"
+        );
+    }
+
+    #[test]
+    fn test_standard_oid_units() {
+        assert_eq!(StandardOid::SysUpTime.unit(), Some(SnmpUnit::Hundredths));
+        assert_eq!(StandardOid::IfSpeed.unit(), Some(SnmpUnit::BitsPerSecond));
+        assert_eq!(StandardOid::IfOutOctets.unit(), Some(SnmpUnit::Octets));
+        assert_eq!(StandardOid::SysName.unit(), None);
     }
 
     #[test]
@@ -278,8 +321,9 @@ mod tests {
         assert!(system_oids.contains(&StandardOid::SysServices));
 
         let interface_oids = StandardOid::interface_oids();
-        assert_eq!(interface_oids.len(), 16);
+        assert_eq!(interface_oids.len(), 17);
         assert!(interface_oids.contains(&StandardOid::IfNumber));
         assert!(interface_oids.contains(&StandardOid::IfOutErrors));
+        assert!(interface_oids.contains(&StandardOid::Dot3StatsFcsErrors));
     }
 }
@@ -0,0 +1,258 @@
+//! Scoped pauses of SNMP polling and link probes
+//!
+//! A pause stops polling of one node, every node in a location and its
+//! sub-locations, or every node at once, e.g. for the length of a maintenance
+//! window. Pauses are kept through the `DataStore` settings API. A pause with
+//! an expiry resumes on its own: once expired it is no longer active and is
+//! removed the next time active pauses are read.
+
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult, QueryOptions};
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// Settings namespace holding pauses keyed by ID
+const PAUSES_NAMESPACE: &str = "polling_pauses";
+
+/// What a pause applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "id", rename_all = "snake_case")]
+pub enum PauseScope {
+    /// Every node
+    Global,
+    /// Nodes in the location or any of its sub-locations
+    Location(Uuid),
+    /// A single node
+    Node(Uuid),
+}
+
+/// A pause of polling
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PollingPause {
+    /// Pause ID
+    pub id: Uuid,
+    /// What the pause applies to
+    pub scope: PauseScope,
+    /// Why polling is paused
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// When the pause was created
+    pub created_at: DateTime<Utc>,
+    /// When polling resumes; `None` pauses until resumed by hand
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl PollingPause {
+    /// Creates a pause starting now, lasting `duration` if one is given
+    ///
+    /// # Errors
+    /// Returns a validation error if the duration is not positive.
+    pub fn new(
+        scope: PauseScope,
+        reason: Option<String>,
+        duration: Option<TimeDelta>,
+    ) -> DataStoreResult<Self> {
+        if duration.is_some_and(|duration| duration <= TimeDelta::zero()) {
+            return Err(DataStoreError::ValidationError {
+                message: "Pause duration must be positive".to_string(),
+            });
+        }
+        let created_at = Utc::now();
+        Ok(Self {
+            id: Uuid::new_v4(),
+            scope,
+            reason,
+            created_at,
+            expires_at: duration.map(|duration| created_at + duration),
+        })
+    }
+
+    /// Whether the pause is still in effect at `now`
+    #[must_use]
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_none_or(|expires_at| now < expires_at)
+    }
+}
+
+/// Nodes that must not be polled
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PausedNodes {
+    /// A global pause is active
+    all: bool,
+    /// Nodes paused individually or through their location
+    nodes: HashSet<Uuid>,
+}
+
+impl PausedNodes {
+    /// Every node paused
+    #[must_use]
+    pub fn all() -> Self {
+        Self {
+            all: true,
+            nodes: HashSet::new(),
+        }
+    }
+
+    /// Whether polling of the node is paused
+    #[must_use]
+    pub fn contains(&self, node_id: &Uuid) -> bool {
+        self.all || self.nodes.contains(node_id)
+    }
+
+    /// Whether nothing is paused
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        !self.all && self.nodes.is_empty()
+    }
+}
+
+impl FromIterator<Uuid> for PausedNodes {
+    fn from_iter<I: IntoIterator<Item = Uuid>>(iter: I) -> Self {
+        Self {
+            all: false,
+            nodes: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// Parses durations such as `90m`, `2h`, `1d`, or `1h30m`
+///
+/// # Errors
+/// Returns an error if the text is not a sequence of numbers each followed by
+/// `s`, `m`, `h`, or `d`, or adds up to zero.
+pub fn parse_duration(text: &str) -> Result<TimeDelta, String> {
+    let invalid = || format!("Invalid duration {text:?}: expected e.g. 30m, 2h, or 1d12h");
+    let mut total = TimeDelta::zero();
+    let mut digits = String::new();
+    for c in text.trim().chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let value: i64 = digits.parse().map_err(|_| invalid())?;
+        digits.clear();
+        let part = match c {
+            's' => TimeDelta::try_seconds(value),
+            'm' => TimeDelta::try_minutes(value),
+            'h' => TimeDelta::try_hours(value),
+            'd' => TimeDelta::try_days(value),
+            _ => None,
+        };
+        total = part
+            .and_then(|part| total.checked_add(&part))
+            .ok_or_else(invalid)?;
+    }
+    if !digits.is_empty() || total <= TimeDelta::zero() {
+        return Err(invalid());
+    }
+    Ok(total)
+}
+
+/// Stores a pause
+///
+/// # Errors
+/// Returns an error if the datastore write fails.
+pub async fn pause_polling(datastore: &dyn DataStore, pause: &PollingPause) -> DataStoreResult<()> {
+    let value = serde_json::to_value(pause).map_err(|e| DataStoreError::InternalError {
+        message: format!("polling pause {}: {e}", pause.id),
+    })?;
+    datastore
+        .put_setting(PAUSES_NAMESPACE, &pause.id.to_string(), &value)
+        .await
+}
+
+/// Removes a pause, resuming what it paused unless another pause covers it
+///
+/// # Errors
+/// Returns an error if the pause does not exist or the datastore write fails.
+pub async fn resume_polling(datastore: &dyn DataStore, id: Uuid) -> DataStoreResult<()> {
+    datastore
+        .delete_setting(PAUSES_NAMESPACE, &id.to_string())
+        .await
+}
+
+/// Lists pauses in effect at `now`, oldest first, removing expired ones
+///
+/// Datastores without settings support have no pauses.
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or a stored pause is malformed.
+pub async fn active_pauses(
+    datastore: &dyn DataStore,
+    now: DateTime<Utc>,
+) -> DataStoreResult<Vec<PollingPause>> {
+    let stored = match datastore.list_settings(PAUSES_NAMESPACE).await {
+        Ok(stored) => stored,
+        Err(DataStoreError::UnsupportedOperation { .. }) => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut pauses = Vec::new();
+    for (key, value) in stored {
+        let pause: PollingPause =
+            serde_json::from_value(value).map_err(|e| DataStoreError::InternalError {
+                message: format!("stored polling pause {key}: {e}"),
+            })?;
+        if pause.is_active(now) {
+            pauses.push(pause);
+        } else {
+            // Another reader may have removed it first
+            match datastore.delete_setting(PAUSES_NAMESPACE, &key).await {
+                Ok(()) | Err(DataStoreError::NotFound { .. }) => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+    pauses.sort_by_key(|pause| pause.created_at);
+    Ok(pauses)
+}
+
+/// Resolves the pauses in effect at `now` to the nodes they cover
+///
+/// # Errors
+/// Returns an error if pauses, locations, or nodes cannot be read.
+pub async fn paused_nodes(
+    datastore: &dyn DataStore,
+    now: DateTime<Utc>,
+) -> DataStoreResult<PausedNodes> {
+    let mut paused = PausedNodes::default();
+    let mut location_ids = HashSet::new();
+    for pause in active_pauses(datastore, now).await? {
+        match pause.scope {
+            PauseScope::Global => return Ok(PausedNodes::all()),
+            PauseScope::Location(id) => {
+                location_ids.insert(id);
+            }
+            PauseScope::Node(id) => {
+                paused.nodes.insert(id);
+            }
+        }
+    }
+    if location_ids.is_empty() {
+        return Ok(paused);
+    }
+
+    let options = QueryOptions::default();
+    let locations = datastore.list_locations(&options).await?.items;
+    let descendants: Vec<Uuid> = locations
+        .iter()
+        .filter(|location| location_ids.contains(&location.id))
+        .flat_map(|location| location.get_descendants(&locations))
+        .map(|location| location.id)
+        .collect();
+    location_ids.extend(descendants);
+    let nodes = datastore.list_nodes(&options).await?.items;
+    paused.nodes.extend(
+        nodes
+            .iter()
+            .filter(|node| {
+                node.location_id
+                    .is_some_and(|id| location_ids.contains(&id))
+            })
+            .map(|node| node.id),
+    );
+    Ok(paused)
+}
+
+#[cfg(test)]
+mod tests;
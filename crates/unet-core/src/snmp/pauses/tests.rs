@@ -0,0 +1,82 @@
+use super::*;
+use crate::datastore::sqlite::tests::setup::migrated_store;
+use crate::models::{DeviceRole, Location, Node, Vendor};
+
+fn node(name: &str, location_id: Option<Uuid>) -> Node {
+    let mut node = Node::new(
+        name.to_string(),
+        "example.com".to_string(),
+        Vendor::Juniper,
+        DeviceRole::Router,
+    );
+    node.location_id = location_id;
+    node
+}
+
+#[test]
+fn test_parse_duration() {
+    assert_eq!(parse_duration("2h"), Ok(TimeDelta::hours(2)));
+    assert_eq!(parse_duration("1h30m"), Ok(TimeDelta::minutes(90)));
+    assert_eq!(parse_duration("1d"), Ok(TimeDelta::days(1)));
+    assert!(parse_duration("2").is_err());
+    assert!(parse_duration("h").is_err());
+    assert!(parse_duration("0m").is_err());
+    assert!(parse_duration("2w").is_err());
+}
+
+#[test]
+fn test_pause_expires() {
+    let pause = PollingPause::new(PauseScope::Global, None, Some(TimeDelta::hours(2))).unwrap();
+    assert!(pause.is_active(pause.created_at + TimeDelta::minutes(119)));
+    assert!(!pause.is_active(pause.created_at + TimeDelta::hours(2)));
+
+    let open_ended = PollingPause::new(PauseScope::Global, None, None).unwrap();
+    assert!(open_ended.is_active(open_ended.created_at + TimeDelta::days(365)));
+
+    assert!(PollingPause::new(PauseScope::Global, None, Some(TimeDelta::zero())).is_err());
+}
+
+#[tokio::test]
+async fn test_location_pause_covers_sub_locations() {
+    let store = migrated_store().await;
+    let site = Location::new_root("dc1".to_string(), "datacenter".to_string());
+    let mut rack = Location::new_child("rack1".to_string(), "rack".to_string(), &site.path);
+    rack.parent_id = Some(site.id);
+    store.create_location(&site).await.unwrap();
+    store.create_location(&rack).await.unwrap();
+    let in_rack = store
+        .create_node(&node("edge-1", Some(rack.id)))
+        .await
+        .unwrap();
+    let elsewhere = store.create_node(&node("edge-2", None)).await.unwrap();
+
+    let pause = PollingPause::new(PauseScope::Location(site.id), None, None).unwrap();
+    pause_polling(&store, &pause).await.unwrap();
+
+    let paused = paused_nodes(&store, Utc::now()).await.unwrap();
+    assert!(paused.contains(&in_rack.id));
+    assert!(!paused.contains(&elsewhere.id));
+
+    resume_polling(&store, pause.id).await.unwrap();
+    assert!(paused_nodes(&store, Utc::now()).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_expired_pauses_are_removed() {
+    let store = migrated_store().await;
+    let pause = PollingPause::new(
+        PauseScope::Node(Uuid::new_v4()),
+        Some("maintenance".to_string()),
+        Some(TimeDelta::hours(1)),
+    )
+    .unwrap();
+    pause_polling(&store, &pause).await.unwrap();
+
+    assert_eq!(
+        active_pauses(&store, Utc::now()).await.unwrap(),
+        vec![pause.clone()]
+    );
+    let later = Utc::now() + TimeDelta::hours(2);
+    assert!(active_pauses(&store, later).await.unwrap().is_empty());
+    assert!(resume_polling(&store, pause.id).await.is_err());
+}
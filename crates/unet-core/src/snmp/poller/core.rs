@@ -1,7 +1,7 @@
 //! Core polling scheduler implementation
 
 use super::{PollingConfig, PollingHandle, PollingMessage, PollingResult, PollingTask};
-use crate::snmp::{SnmpClient, SnmpClientConfig};
+use crate::snmp::{PausedNodes, ShardSet, SnmpClient, SnmpClientConfig};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
@@ -18,6 +18,10 @@ pub struct PollingScheduler {
     pub(super) snmp_client: Arc<SnmpClient>,
     /// Active polling tasks
     pub(super) tasks: Arc<RwLock<HashMap<Uuid, PollingTask>>>,
+    /// Nodes whose tasks are skipped while paused
+    pub(super) paused: Arc<RwLock<PausedNodes>>,
+    /// Shards whose nodes this collector polls; every node unless sharded
+    pub(super) shards: Arc<RwLock<ShardSet>>,
     /// Channel for receiving control messages
     pub(super) message_rx: mpsc::UnboundedReceiver<PollingMessage>,
     /// Channel for sending polling results
@@ -44,6 +48,8 @@ impl PollingScheduler {
             config,
             snmp_client,
             tasks,
+            paused: Arc::new(RwLock::new(PausedNodes::default())),
+            shards: Arc::new(RwLock::new(ShardSet::unsharded())),
             message_rx,
             result_tx,
             shutdown,
@@ -145,6 +151,8 @@ impl PollingScheduler {
             config,
             snmp_client,
             tasks,
+            paused: Arc::new(RwLock::new(PausedNodes::default())),
+            shards: Arc::new(RwLock::new(ShardSet::unsharded())),
             message_rx,
             result_tx,
             shutdown,
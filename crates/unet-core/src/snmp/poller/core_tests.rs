@@ -13,6 +13,8 @@ fn create_test_config() -> PollingConfig {
         max_retries: 2,
         retry_backoff_multiplier: 2.0,
         health_check_interval: Duration::from_millis(100),
+        precheck: None,
+        inventory_interval: None,
     }
 }
 
@@ -59,6 +61,8 @@ async fn test_scheduler_configuration_values() {
         max_retries: 3,
         retry_backoff_multiplier: 1.5,
         health_check_interval: Duration::from_secs(60),
+        precheck: None,
+        inventory_interval: None,
     };
     let snmp_config = SnmpClientConfig {
         max_connections: 50,
@@ -106,6 +110,8 @@ fn test_polling_config_creation() {
         max_retries: 5,
         retry_backoff_multiplier: 2.5,
         health_check_interval: Duration::from_millis(2000),
+        precheck: None,
+        inventory_interval: None,
     };
 
     assert_eq!(config.default_interval, Duration::from_millis(500));
@@ -1,24 +1,32 @@
 /// Task execution and polling logic for SNMP scheduler
 use super::core::PollingScheduler;
-use super::{PollingResult, PollingTask};
-use crate::snmp::{SnmpClient, SnmpValue};
+use super::{PollingResult, PollingTask, Precheck};
+use crate::snmp::{SnmpClient, SnmpValue, StandardOid};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, SystemTime};
-use tokio::sync::mpsc;
+use tokio::sync::{RwLock, mpsc};
 use tokio::time::Instant;
-use tracing::{debug, error, warn};
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
 
 /// Check tasks and poll those that are due
 pub async fn check_and_poll_tasks(scheduler: &PollingScheduler) {
     let now = Instant::now();
     let mut tasks_to_poll = Vec::new();
 
-    // Collect tasks that need polling
+    // Collect tasks that need polling, skipping paused nodes and nodes in
+    // shards other collectors hold
+    let paused = scheduler.paused.read().await.clone();
+    let shards = scheduler.shards.read().await.clone();
     {
         let tasks = scheduler.tasks.read().await;
         for task in tasks.values() {
-            if task.enabled && now >= task.next_poll_time() {
+            if task.enabled
+                && !paused.contains(&task.node_id)
+                && shards.contains(&task.node_id)
+                && now >= task.next_poll_time()
+            {
                 tasks_to_poll.push(task.clone());
             }
         }
@@ -37,9 +45,21 @@ pub async fn check_and_poll_tasks(scheduler: &PollingScheduler) {
             let snmp_client = Arc::clone(&scheduler.snmp_client);
             let result_tx = scheduler.result_tx.clone();
             let poll_timeout = scheduler.config.poll_timeout;
+            let precheck = scheduler.config.precheck;
+            let inventory_interval = scheduler.config.inventory_interval;
+            let tasks = Arc::clone(&scheduler.tasks);
 
             let handle = tokio::spawn(async move {
-                poll_task(task, snmp_client, result_tx, poll_timeout).await;
+                let polled = poll_checked(
+                    task,
+                    precheck,
+                    inventory_interval,
+                    snmp_client,
+                    result_tx,
+                    poll_timeout,
+                )
+                .await;
+                record_task_state(&tasks, &polled).await;
             });
 
             poll_handles.push(handle);
@@ -54,13 +74,83 @@ pub async fn check_and_poll_tasks(scheduler: &PollingScheduler) {
     }
 }
 
+/// Poll a task, first running the pre-check when one is configured
+///
+/// A device failing the pre-check is not polled over SNMP: the task records
+/// a failure and is marked unreachable. Once the device answers again its
+/// failures are cleared, so it is polled at its normal interval with the
+/// session's full SNMP retries.
+async fn poll_checked(
+    mut task: PollingTask,
+    precheck: Option<Precheck>,
+    inventory_interval: Option<Duration>,
+    snmp_client: Arc<SnmpClient>,
+    result_tx: mpsc::UnboundedSender<PollingResult>,
+    timeout: Duration,
+) -> PollingTask {
+    let Some(precheck) = precheck else {
+        return poll_task(task, inventory_interval, snmp_client, result_tx, timeout).await;
+    };
+
+    let start_time = Instant::now();
+    let poll_start = SystemTime::now();
+    if precheck.is_reachable(task.target.ip()).await {
+        if !task.reachable {
+            info!(
+                task_id = %task.id,
+                target = %task.target,
+                "Device reachable again, resuming SNMP polling"
+            );
+            task.reachable = true;
+            task.consecutive_failures = 0;
+        }
+        return poll_task(task, inventory_interval, snmp_client, result_tx, timeout).await;
+    }
+
+    if task.reachable {
+        warn!(
+            task_id = %task.id,
+            target = %task.target,
+            method = %precheck.method,
+            "Device failed reachability pre-check, skipping SNMP polls until it answers"
+        );
+    }
+    let error = format!("Device unreachable by {} pre-check", precheck.method);
+    task.reachable = false;
+    task.consecutive_failures += 1;
+    task.last_error = Some(error.clone());
+    let mut result = create_polling_result(
+        &task,
+        poll_start,
+        false,
+        HashMap::new(),
+        Some(error),
+        start_time.elapsed(),
+    );
+    result.reachable = false;
+    send_result(result, &result_tx);
+    task
+}
+
+/// Copies the outcome of a poll to the scheduler's copy of the task
+async fn record_task_state(tasks: &RwLock<HashMap<Uuid, PollingTask>>, polled: &PollingTask) {
+    if let Some(task) = tasks.write().await.get_mut(&polled.id) {
+        task.last_success = polled.last_success;
+        task.last_error.clone_from(&polled.last_error);
+        task.consecutive_failures = polled.consecutive_failures;
+        task.reachable = polled.reachable;
+        task.last_inventory = polled.last_inventory;
+    }
+}
+
 /// Poll a single task
 async fn poll_task(
     mut task: PollingTask,
+    inventory_interval: Option<Duration>,
     snmp_client: Arc<SnmpClient>,
     result_tx: mpsc::UnboundedSender<PollingResult>,
     timeout: Duration,
-) {
+) -> PollingTask {
     let start_time = Instant::now();
     let poll_start = SystemTime::now();
 
@@ -72,12 +162,23 @@ async fn poll_task(
     );
 
     let poll_result = execute_snmp_poll(&task, &snmp_client, timeout).await;
+    let (success, mut values, error) = process_poll_result(poll_result, &mut task, timeout);
+    if success {
+        walk_inventory(
+            &mut task,
+            snmp_client.as_ref(),
+            inventory_interval,
+            timeout,
+            &mut values,
+        )
+        .await;
+    }
     let duration = start_time.elapsed();
-    let (success, values, error) = process_poll_result(poll_result, &mut task, timeout);
     let result = create_polling_result(&task, poll_start, success, values, error, duration);
 
     send_result(result, &result_tx);
     log_poll_completion(&task, success, duration);
+    task
 }
 
 pub async fn execute_snmp_poll(
@@ -123,6 +224,81 @@ pub async fn execute_snmp_poll_with_mock<T: crate::snmp::testing::SnmpOperations
     .await
 }
 
+/// Walks the device's `entPhysicalTable` when its hardware inventory is
+/// due, adding the rows to the poll's values
+///
+/// Inventory changes rarely, so it is walked once per `interval` rather
+/// than on every poll; `None` never walks it. A failed walk leaves the
+/// inventory due for the next poll and does not fail this one.
+pub async fn walk_inventory(
+    task: &mut PollingTask,
+    snmp_client: &SnmpClient,
+    interval: Option<Duration>,
+    timeout: Duration,
+    values: &mut HashMap<String, SnmpValue>,
+) {
+    let now = SystemTime::now();
+    if !interval.is_some_and(|interval| task.inventory_due(interval, now)) {
+        return;
+    }
+    let walk = snmp_client.walk(
+        task.target,
+        StandardOid::EntPhysicalTable.oid(),
+        Some(task.session_config.clone()),
+    );
+    let walk_result = tokio::time::timeout(timeout, walk).await;
+    process_inventory_walk(walk_result, task, now, values);
+}
+
+#[cfg(test)]
+pub async fn walk_inventory_with_mock<T: crate::snmp::testing::SnmpOperations>(
+    task: &mut PollingTask,
+    snmp_operations: &T,
+    interval: Option<Duration>,
+    timeout: Duration,
+    values: &mut HashMap<String, SnmpValue>,
+) {
+    let now = SystemTime::now();
+    if !interval.is_some_and(|interval| task.inventory_due(interval, now)) {
+        return;
+    }
+    let walk = snmp_operations.walk(
+        task.target,
+        StandardOid::EntPhysicalTable.oid(),
+        Some(task.session_config.clone()),
+    );
+    let walk_result = tokio::time::timeout(timeout, walk).await;
+    process_inventory_walk(walk_result, task, now, values);
+}
+
+pub fn process_inventory_walk(
+    walk_result: Result<
+        Result<HashMap<String, SnmpValue>, crate::snmp::SnmpError>,
+        tokio::time::error::Elapsed,
+    >,
+    task: &mut PollingTask,
+    walked_at: SystemTime,
+    values: &mut HashMap<String, SnmpValue>,
+) {
+    match walk_result {
+        Ok(Ok(rows)) => {
+            values.extend(rows);
+            task.last_inventory = Some(walked_at);
+        }
+        Ok(Err(e)) => debug!(
+            task_id = %task.id,
+            target = %task.target,
+            error = %e,
+            "Hardware inventory walk failed"
+        ),
+        Err(_) => debug!(
+            task_id = %task.id,
+            target = %task.target,
+            "Hardware inventory walk timed out"
+        ),
+    }
+}
+
 pub fn process_poll_result(
     poll_result: Result<
         Result<HashMap<String, SnmpValue>, crate::snmp::SnmpError>,
@@ -152,7 +328,7 @@ pub fn process_poll_result(
     }
 }
 
-pub const fn create_polling_result(
+pub fn create_polling_result(
     task: &PollingTask,
     poll_start: SystemTime,
     success: bool,
@@ -169,6 +345,8 @@ pub const fn create_polling_result(
         values,
         error,
         duration,
+        reachable: true,
+        context: task.session_config.context.clone(),
     }
 }
 
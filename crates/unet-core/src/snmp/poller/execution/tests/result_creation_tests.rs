@@ -42,6 +42,7 @@ fn test_create_polling_result() {
     assert_eq!(result.values, values);
     assert_eq!(result.error, None);
     assert_eq!(result.duration, duration);
+    assert_eq!(result.context, None);
 }
 
 #[test]
@@ -60,6 +61,23 @@ fn test_create_polling_result_with_error() {
     assert_eq!(result.error, error);
 }
 
+#[test]
+fn test_create_polling_result_carries_context() {
+    let mut task = create_test_task();
+    task.session_config = task.session_config.for_context("vrf-blue");
+
+    let result = create_polling_result(
+        &task,
+        SystemTime::now(),
+        true,
+        HashMap::new(),
+        None,
+        Duration::ZERO,
+    );
+
+    assert_eq!(result.context.as_deref(), Some("vrf-blue"));
+}
+
 #[test]
 fn test_create_polling_result_with_values() {
     let task = create_test_task();
@@ -68,7 +68,7 @@ async fn test_poll_task_with_mock_client() {
     let timeout = Duration::from_millis(100); // Short timeout to ensure it fails quickly
 
     // This will test the full poll_task function including error handling
-    poll_task(task.clone(), snmp_client, tx, timeout).await;
+    poll_task(task.clone(), None, snmp_client, tx, timeout).await;
 
     // Should receive a polling result
     let result = rx.recv().await;
@@ -81,3 +81,30 @@ async fn test_poll_task_with_mock_client() {
     assert!(!polling_result.success);
     assert!(polling_result.error.is_some());
 }
+
+#[tokio::test]
+async fn test_walk_inventory_once_per_interval() {
+    use crate::snmp::testing::MockSnmpClient;
+    use crate::snmp::{SnmpValue, StandardOid};
+    use std::collections::HashMap;
+
+    let mut task = create_test_task();
+    let class = format!("{}.5.1", StandardOid::EntPhysicalTable.oid());
+    let rows = HashMap::from([(class.clone(), SnmpValue::Integer(3))]);
+    let mock_client = MockSnmpClient::new().with_walk_response(
+        task.target,
+        StandardOid::EntPhysicalTable.oid(),
+        Ok(rows),
+    );
+    let interval = Some(Duration::from_secs(3600));
+    let timeout = Duration::from_millis(100);
+
+    let mut values = HashMap::new();
+    walk_inventory_with_mock(&mut task, &mock_client, interval, timeout, &mut values).await;
+    assert_eq!(values.get(&class), Some(&SnmpValue::Integer(3)));
+    assert!(task.last_inventory.is_some());
+
+    let mut values = HashMap::new();
+    walk_inventory_with_mock(&mut task, &mock_client, interval, timeout, &mut values).await;
+    assert!(values.is_empty());
+}
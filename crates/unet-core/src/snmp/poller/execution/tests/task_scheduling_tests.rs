@@ -29,6 +29,8 @@ fn create_test_scheduler() -> (PollingScheduler, PollingHandle) {
         max_retries: 2,
         retry_backoff_multiplier: 2.0,
         health_check_interval: Duration::from_secs(30),
+        precheck: None,
+        inventory_interval: None,
     };
     let snmp_config = crate::snmp::SnmpClientConfig::default();
 
@@ -1,6 +1,7 @@
 //! Polling handle for controlling the scheduler
 
 use super::{PollingMessage, PollingResult, PollingTask};
+use crate::snmp::{PausedNodes, ShardSet};
 use tokio::sync::mpsc;
 use uuid::Uuid;
 
@@ -64,6 +65,20 @@ impl PollingHandle {
             .map_err(|e| format!("Failed to send message: {e}"))
     }
 
+    /// Replace the tasks of a node with `tasks`
+    ///
+    /// Tasks polling the same target and OIDs at the same interval and with
+    /// the same session as one of `tasks` are kept, so their schedule and
+    /// health survive; an empty list stops polling the node.
+    ///
+    /// # Errors
+    /// Returns an error if the message channel is closed
+    pub fn sync_node(&self, node_id: Uuid, tasks: Vec<PollingTask>) -> Result<(), String> {
+        self.message_tx
+            .send(PollingMessage::SyncNode(node_id, tasks))
+            .map_err(|e| format!("Failed to send message: {e}"))
+    }
+
     /// Get status of a specific task
     ///
     /// # Errors
@@ -94,6 +109,26 @@ impl PollingHandle {
             .map_err(|e| format!("Failed to receive response: {e}"))
     }
 
+    /// Skip tasks of the given nodes until replaced by another set
+    ///
+    /// # Errors
+    /// Returns an error if the message channel is closed
+    pub fn set_paused(&self, paused: PausedNodes) -> Result<(), String> {
+        self.message_tx
+            .send(PollingMessage::SetPaused(paused))
+            .map_err(|e| format!("Failed to send message: {e}"))
+    }
+
+    /// Poll only the nodes of the given shards until replaced by another set
+    ///
+    /// # Errors
+    /// Returns an error if the message channel is closed
+    pub fn set_shards(&self, shards: ShardSet) -> Result<(), String> {
+        self.message_tx
+            .send(PollingMessage::SetShards(shards))
+            .map_err(|e| format!("Failed to send message: {e}"))
+    }
+
     /// Shutdown the scheduler
     ///
     /// # Errors
@@ -126,6 +161,8 @@ mod tests {
             last_success: None,
             last_error: None,
             consecutive_failures: 0,
+            reachable: true,
+            last_inventory: None,
         }
     }
 
@@ -2,6 +2,7 @@
 
 use super::core::PollingScheduler;
 use super::{PollingMessage, PollingTask};
+use crate::snmp::{PausedNodes, ShardSet};
 use tokio::sync::oneshot;
 use tracing::info;
 use uuid::Uuid;
@@ -21,12 +22,21 @@ pub async fn handle_message(scheduler: &PollingScheduler, message: PollingMessag
         PollingMessage::EnableTask(task_id, enabled) => {
             handle_enable_task(scheduler, task_id, enabled).await;
         }
+        PollingMessage::SyncNode(node_id, tasks) => {
+            handle_sync_node(scheduler, node_id, tasks).await;
+        }
         PollingMessage::GetTaskStatus(task_id, response_tx) => {
             handle_get_task_status(scheduler, task_id, response_tx).await;
         }
         PollingMessage::ListTasks(response_tx) => {
             handle_list_tasks(scheduler, response_tx).await;
         }
+        PollingMessage::SetPaused(paused) => {
+            handle_set_paused(scheduler, paused).await;
+        }
+        PollingMessage::SetShards(shards) => {
+            handle_set_shards(scheduler, shards).await;
+        }
         PollingMessage::Shutdown => {
             info!("Shutdown requested");
             return true;
@@ -61,6 +71,39 @@ async fn handle_enable_task(scheduler: &PollingScheduler, task_id: Uuid, enabled
     }
 }
 
+async fn handle_sync_node(scheduler: &PollingScheduler, node_id: Uuid, tasks: Vec<PollingTask>) {
+    let same = |a: &PollingTask, b: &PollingTask| {
+        a.target == b.target
+            && a.oids == b.oids
+            && a.interval == b.interval
+            && a.session_config == b.session_config
+    };
+    let mut current = scheduler.tasks.write().await;
+    let before = current
+        .values()
+        .filter(|task| task.node_id == node_id)
+        .count();
+    current.retain(|_, task| task.node_id != node_id || tasks.iter().any(|new| same(task, new)));
+    let kept: Vec<PollingTask> = current
+        .values()
+        .filter(|task| task.node_id == node_id)
+        .cloned()
+        .collect();
+    for task in tasks {
+        if !kept.iter().any(|old| same(old, &task)) {
+            current.insert(task.id, task);
+        }
+    }
+    let after = current
+        .values()
+        .filter(|task| task.node_id == node_id)
+        .count();
+    drop(current);
+    if before != kept.len() || after != kept.len() {
+        info!(node_id = %node_id, tasks = after, "Synchronized polling tasks");
+    }
+}
+
 async fn handle_get_task_status(
     scheduler: &PollingScheduler,
     task_id: Uuid,
@@ -80,6 +123,22 @@ async fn handle_list_tasks(
     let _ = response_tx.send(task_list);
 }
 
+async fn handle_set_paused(scheduler: &PollingScheduler, paused: PausedNodes) {
+    let mut current = scheduler.paused.write().await;
+    if *current != paused {
+        info!(nothing_paused = paused.is_empty(), "Updating paused nodes");
+        *current = paused;
+    }
+}
+
+async fn handle_set_shards(scheduler: &PollingScheduler, shards: ShardSet) {
+    let mut current = scheduler.shards.write().await;
+    if *current != shards {
+        info!(shards = ?shards.owned(), "Updating owned polling shards");
+        *current = shards;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -98,6 +157,8 @@ mod tests {
             max_retries: 2,
             retry_backoff_multiplier: 2.0,
             health_check_interval: Duration::from_millis(100),
+            precheck: None,
+            inventory_interval: None,
         }
     }
 
@@ -129,6 +190,64 @@ mod tests {
         assert_eq!(scheduler.task_count().await, 0);
     }
 
+    #[tokio::test]
+    async fn test_sync_node_keeps_unchanged_tasks() {
+        let (scheduler, _handle) =
+            PollingScheduler::new(create_test_config(), SnmpClientConfig::default());
+        let mut kept = create_test_task();
+        kept.consecutive_failures = 2;
+        let node_id = kept.node_id;
+        let mut dropped = create_test_task();
+        dropped.node_id = node_id;
+        dropped.oids = vec!["1.3.6.1.2.1.1.3.0".to_string()];
+        let other = create_test_task();
+        for task in [kept.clone(), dropped.clone(), other.clone()] {
+            handle_add_task(&scheduler, task).await;
+        }
+
+        let mut same = create_test_task();
+        same.node_id = node_id;
+        let mut added = create_test_task();
+        added.node_id = node_id;
+        added.interval = Duration::from_secs(300);
+        handle_sync_node(&scheduler, node_id, vec![same, added.clone()]).await;
+
+        let tasks = scheduler.tasks.read().await;
+        assert_eq!(tasks.len(), 3);
+        assert_eq!(tasks[&kept.id].consecutive_failures, 2);
+        assert!(!tasks.contains_key(&dropped.id));
+        assert!(tasks.contains_key(&added.id));
+        assert!(tasks.contains_key(&other.id));
+        drop(tasks);
+
+        handle_sync_node(&scheduler, node_id, Vec::new()).await;
+        assert_eq!(scheduler.task_count().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_set_paused() {
+        let (scheduler, _handle) =
+            PollingScheduler::new(create_test_config(), SnmpClientConfig::default());
+        let node_id = Uuid::new_v4();
+
+        handle_set_paused(&scheduler, std::iter::once(node_id).collect()).await;
+        assert!(scheduler.paused.read().await.contains(&node_id));
+
+        handle_set_paused(&scheduler, PausedNodes::default()).await;
+        assert!(scheduler.paused.read().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_set_shards() {
+        let (scheduler, _handle) =
+            PollingScheduler::new(create_test_config(), SnmpClientConfig::default());
+        let node_id = Uuid::new_v4();
+        assert!(scheduler.shards.read().await.contains(&node_id));
+
+        handle_set_shards(&scheduler, ShardSet::new(4, [])).await;
+        assert!(!scheduler.shards.read().await.contains(&node_id));
+    }
+
     #[tokio::test]
     async fn test_enable_disable_task() {
         let config = create_test_config();
@@ -7,16 +7,19 @@ use std::time::{Duration, SystemTime};
 use tokio::time::Instant;
 use uuid::Uuid;
 
-use super::{SessionConfig, SnmpValue};
+use super::{PausedNodes, SessionConfig, ShardSet, SnmpValue};
+use crate::models::derived::NodeStatus;
 
 // Re-export all public types
 pub use self::core::PollingScheduler;
 pub use self::handle::PollingHandle;
+pub use self::precheck::{Precheck, PrecheckMethod};
 
 mod core;
 mod execution;
 mod handle;
 mod management;
+mod precheck;
 mod scheduler;
 
 /// Configuration for polling scheduler
@@ -34,6 +37,13 @@ pub struct PollingConfig {
     pub retry_backoff_multiplier: f64,
     /// Health check interval for cleaning up failed tasks
     pub health_check_interval: Duration,
+    /// Reachability check run before each poll; `None` polls over SNMP directly
+    #[serde(default)]
+    pub precheck: Option<Precheck>,
+    /// How often each device's ENTITY-MIB hardware inventory is walked
+    /// along with a poll; `None` never walks it
+    #[serde(default)]
+    pub inventory_interval: Option<Duration>,
 }
 
 impl Default for PollingConfig {
@@ -45,6 +55,8 @@ impl Default for PollingConfig {
             max_retries: 3,
             retry_backoff_multiplier: 2.0,
             health_check_interval: Duration::from_secs(60),
+            precheck: None,
+            inventory_interval: Some(Duration::from_secs(24 * 60 * 60)), // 1 day
         }
     }
 }
@@ -76,6 +88,10 @@ pub struct PollingTask {
     pub last_error: Option<String>,
     /// Number of consecutive failures
     pub consecutive_failures: u32,
+    /// Whether the device passed its last reachability pre-check
+    pub reachable: bool,
+    /// When the device's hardware inventory was last walked
+    pub last_inventory: Option<SystemTime>,
 }
 
 impl PollingTask {
@@ -101,6 +117,8 @@ impl PollingTask {
             last_success: None,
             last_error: None,
             consecutive_failures: 0,
+            reachable: true,
+            last_inventory: None,
         }
     }
 
@@ -115,6 +133,16 @@ impl PollingTask {
             })
     }
 
+    /// Whether the hardware inventory was never walked or was walked at
+    /// least `interval` before `now`
+    #[must_use]
+    pub fn inventory_due(&self, interval: Duration, now: SystemTime) -> bool {
+        self.last_inventory.is_none_or(|last| {
+            now.duration_since(last)
+                .is_ok_and(|elapsed| elapsed >= interval)
+        })
+    }
+
     /// Calculate next poll time based on interval and failures
     #[must_use]
     pub fn next_poll_time(&self) -> Instant {
@@ -152,6 +180,28 @@ pub struct PollingResult {
     pub error: Option<String>,
     /// Duration of the polling operation
     pub duration: Duration,
+    /// Whether the device was reachable; `false` when the pre-check failed
+    /// and no SNMP request was sent
+    pub reachable: bool,
+    /// Logical instance that was polled; `None` for the default instance
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+}
+
+impl PollingResult {
+    /// Merges the result into the node's derived state
+    ///
+    /// A result for a context updates only that context's entry in
+    /// `contexts`; one for the default instance updates the node itself.
+    pub fn apply_to(self, status: &mut NodeStatus) {
+        let error = self.error.unwrap_or_else(|| "SNMP poll failed".to_string());
+        match (self.context, self.success) {
+            (Some(context), true) => status.update_context_from_snmp(&context, self.values),
+            (Some(context), false) => status.mark_context_failure(&context, error),
+            (None, true) => status.update_from_snmp(self.values),
+            (None, false) => status.mark_polling_failure(error),
+        }
+    }
 }
 
 /// Message types for the polling scheduler
@@ -165,10 +215,16 @@ pub enum PollingMessage {
     UpdateTask(PollingTask),
     /// Enable/disable a task
     EnableTask(Uuid, bool),
+    /// Replace the tasks of a node, keeping unchanged ones and their state
+    SyncNode(Uuid, Vec<PollingTask>),
     /// Get current task status
     GetTaskStatus(Uuid, tokio::sync::oneshot::Sender<Option<PollingTask>>),
     /// List all tasks
     ListTasks(tokio::sync::oneshot::Sender<Vec<PollingTask>>),
+    /// Replace the set of nodes whose tasks are skipped
+    SetPaused(PausedNodes),
+    /// Replace the shards whose nodes this collector polls
+    SetShards(ShardSet),
     /// Shutdown the scheduler
     Shutdown,
 }
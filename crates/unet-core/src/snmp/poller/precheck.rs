@@ -0,0 +1,117 @@
+//! Reachability checks run before SNMP polls
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::ErrorKind;
+use std::net::{IpAddr, SocketAddr};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio::process::Command;
+use tracing::warn;
+
+/// How a device is checked for reachability
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrecheckMethod {
+    /// One ICMP echo request, sent with the system `ping`
+    Icmp,
+    /// A TCP connection to `port`, e.g. 22 or 161; a refused connection
+    /// still shows the device is up
+    Tcp {
+        /// Port to connect to
+        port: u16,
+    },
+}
+
+impl fmt::Display for PrecheckMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Icmp => write!(f, "ICMP"),
+            Self::Tcp { port } => write!(f, "TCP/{port}"),
+        }
+    }
+}
+
+/// Fast reachability check run before each SNMP poll
+///
+/// A device that fails the check is not polled, so a site outage costs one
+/// short check per device rather than an SNMP timeout with all its retries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Precheck {
+    /// Check to run
+    pub method: PrecheckMethod,
+    /// How long to wait for an answer
+    pub timeout: Duration,
+}
+
+impl Precheck {
+    /// Returns whether the device at `address` answered within the timeout
+    pub async fn is_reachable(&self, address: IpAddr) -> bool {
+        match self.method {
+            PrecheckMethod::Icmp => ping(address, self.timeout).await,
+            PrecheckMethod::Tcp { port } => {
+                connects(SocketAddr::new(address, port), self.timeout).await
+            }
+        }
+    }
+}
+
+async fn connects(target: SocketAddr, timeout: Duration) -> bool {
+    match tokio::time::timeout(timeout, TcpStream::connect(target)).await {
+        Ok(Ok(_)) => true,
+        Ok(Err(e)) => e.kind() == ErrorKind::ConnectionRefused,
+        Err(_) => false,
+    }
+}
+
+async fn ping(address: IpAddr, timeout: Duration) -> bool {
+    // `ping -W` takes whole seconds
+    let wait = timeout.as_secs().max(1).to_string();
+    let status = Command::new("ping")
+        .args(["-n", "-q", "-c", "1", "-W", &wait])
+        .arg(address.to_string())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .status();
+    match tokio::time::timeout(timeout + Duration::from_secs(1), status).await {
+        Ok(Ok(status)) => status.success(),
+        Ok(Err(e)) => {
+            // Without a usable `ping` every device would look down, so poll anyway
+            warn!(error = %e, "Could not run ping for the reachability pre-check");
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+    use tokio::net::TcpListener;
+
+    fn tcp(port: u16) -> Precheck {
+        Precheck {
+            method: PrecheckMethod::Tcp { port },
+            timeout: Duration::from_millis(500),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tcp_precheck_counts_listening_and_refusing_hosts_as_up() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        assert!(tcp(port).is_reachable(Ipv4Addr::LOCALHOST.into()).await);
+
+        drop(listener);
+        assert!(tcp(port).is_reachable(Ipv4Addr::LOCALHOST.into()).await);
+    }
+
+    #[test]
+    fn test_precheck_method_display() {
+        assert_eq!(PrecheckMethod::Icmp.to_string(), "ICMP");
+        assert_eq!(PrecheckMethod::Tcp { port: 22 }.to_string(), "TCP/22");
+    }
+}
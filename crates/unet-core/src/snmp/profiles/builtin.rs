@@ -0,0 +1,130 @@
+//! Built-in OID profiles and their default role assignments
+
+use super::{AssignmentScope, OidGroup, OidProfile, OidProfileAssignment};
+use crate::snmp::StandardOid;
+
+fn group(name: &str, oids: &[StandardOid], interval_seconds: u64) -> OidGroup {
+    OidGroup {
+        name: name.to_string(),
+        oids: oids.iter().map(|oid| oid.oid().to_string()).collect(),
+        interval_seconds,
+    }
+}
+
+fn profile(name: &str, description: &str, groups: Vec<OidGroup>) -> OidProfile {
+    OidProfile {
+        name: name.to_string(),
+        description: Some(description.to_string()),
+        parent: (name != super::DEFAULT_PROFILE).then(|| super::DEFAULT_PROFILE.to_string()),
+        groups,
+    }
+}
+
+/// Returns the profiles shipped with μNet
+///
+/// `base` polls system identity; the role profiles inherit from it and add
+/// interface tables at role-appropriate intervals.
+#[must_use]
+pub fn builtin_profiles() -> Vec<OidProfile> {
+    let inventory = [
+        StandardOid::IfDescr,
+        StandardOid::IfType,
+        StandardOid::IfMtu,
+        StandardOid::IfSpeed,
+    ];
+
+    vec![
+        profile(
+            "base",
+            "System identity and uptime",
+            vec![group(
+                "system",
+                &[
+                    StandardOid::SysDescr,
+                    StandardOid::SysObjectId,
+                    StandardOid::SysUpTime,
+                    StandardOid::SysName,
+                ],
+                300,
+            )],
+        ),
+        profile(
+            "router-core",
+            "Core routers: fast interface counters",
+            vec![
+                group(
+                    "interfaces",
+                    &[
+                        StandardOid::IfOperStatus,
+                        StandardOid::IfInOctets,
+                        StandardOid::IfOutOctets,
+                        StandardOid::IfInErrors,
+                        StandardOid::IfOutErrors,
+                        StandardOid::Dot3StatsFcsErrors,
+                    ],
+                    60,
+                ),
+                group("interface-inventory", &inventory, 900),
+            ],
+        ),
+        profile(
+            "access-switch",
+            "Access switches: port state and errors",
+            vec![
+                group(
+                    "interfaces",
+                    &[
+                        StandardOid::IfAdminStatus,
+                        StandardOid::IfOperStatus,
+                        StandardOid::IfInErrors,
+                        StandardOid::IfOutErrors,
+                        StandardOid::Dot3StatsFcsErrors,
+                    ],
+                    300,
+                ),
+                group("interface-inventory", &inventory, 3600),
+            ],
+        ),
+        profile(
+            "firewall",
+            "Firewalls: uptime and interface throughput",
+            vec![
+                group(
+                    "system",
+                    &[
+                        StandardOid::SysDescr,
+                        StandardOid::SysUpTime,
+                        StandardOid::SysName,
+                    ],
+                    60,
+                ),
+                group(
+                    "interfaces",
+                    &[
+                        StandardOid::IfOperStatus,
+                        StandardOid::IfInOctets,
+                        StandardOid::IfOutOctets,
+                    ],
+                    120,
+                ),
+            ],
+        ),
+    ]
+}
+
+/// Returns the role assignments applied when none are stored
+#[must_use]
+pub fn builtin_assignments() -> Vec<OidProfileAssignment> {
+    [
+        ("router", "router-core"),
+        ("switch", "access-switch"),
+        ("firewall", "firewall"),
+    ]
+    .into_iter()
+    .map(|(role, profile)| OidProfileAssignment {
+        scope: AssignmentScope::Role,
+        target: role.to_string(),
+        profile: profile.to_string(),
+    })
+    .collect()
+}
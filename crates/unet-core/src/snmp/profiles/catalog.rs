@@ -0,0 +1,274 @@
+//! Profile lookup, inheritance flattening, and per-node resolution
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+#[cfg(feature = "snmp")]
+use std::net::SocketAddr;
+#[cfg(feature = "snmp")]
+use std::time::Duration;
+#[cfg(feature = "snmp")]
+use uuid::Uuid;
+
+use super::builtin::{builtin_assignments, builtin_profiles};
+use super::{
+    AssignmentScope, DEFAULT_PROFILE, OidGroup, OidProfile, OidProfileAssignment, OidProfileError,
+};
+use crate::groups::NodeGroup;
+use crate::models::Node;
+use crate::snmp::adjustments::{PollingAdjustment, PollingSetting};
+#[cfg(feature = "snmp")]
+use crate::snmp::{PollingTask, SessionConfig};
+
+/// Profile resolved for a node, with inheritance flattened
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ResolvedOidProfile {
+    /// Selected profile name
+    pub profile: String,
+    /// Assignment that selected the profile (e.g., `role:router` or `default`)
+    pub source: String,
+    /// Inheritance chain from the selected profile up to its root
+    pub chain: Vec<String>,
+    /// Effective groups after applying overrides
+    pub groups: Vec<OidGroup>,
+    /// Policy rules adjusting the profile or its intervals
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub adjusted_by: Vec<String>,
+}
+
+impl ResolvedOidProfile {
+    /// Builds one polling task per distinct interval
+    #[cfg(feature = "snmp")]
+    #[must_use]
+    pub fn polling_tasks(
+        &self,
+        target: SocketAddr,
+        node_id: Uuid,
+        session_config: &SessionConfig,
+    ) -> Vec<PollingTask> {
+        let mut by_interval: BTreeMap<u64, Vec<String>> = BTreeMap::new();
+        for group in &self.groups {
+            let oids = by_interval.entry(group.interval_seconds).or_default();
+            for oid in &group.oids {
+                if !oids.contains(oid) {
+                    oids.push(oid.clone());
+                }
+            }
+        }
+
+        by_interval
+            .into_iter()
+            .map(|(interval, oids)| {
+                PollingTask::new(
+                    target,
+                    node_id,
+                    oids,
+                    Duration::from_secs(interval),
+                    session_config.clone(),
+                )
+            })
+            .collect()
+    }
+
+    /// Builds the polling tasks of the default instance and then the same
+    /// tasks again for each logical instance in `contexts`
+    #[cfg(feature = "snmp")]
+    #[must_use]
+    pub fn context_polling_tasks(
+        &self,
+        target: SocketAddr,
+        node_id: Uuid,
+        session_config: &SessionConfig,
+        contexts: &[String],
+    ) -> Vec<PollingTask> {
+        let mut tasks = self.polling_tasks(target, node_id, session_config);
+        for context in contexts {
+            let session_config = session_config.for_context(context);
+            tasks.extend(self.polling_tasks(target, node_id, &session_config));
+        }
+        tasks
+    }
+}
+
+/// Profiles and assignments used to resolve what each node polls
+#[derive(Debug, Clone, Default)]
+pub struct OidProfileCatalog {
+    profiles: BTreeMap<String, OidProfile>,
+    assignments: Vec<OidProfileAssignment>,
+    node_groups: BTreeMap<String, NodeGroup>,
+}
+
+impl OidProfileCatalog {
+    /// Creates a catalog containing only the built-in profiles and role assignments
+    #[must_use]
+    pub fn builtin() -> Self {
+        let mut catalog = Self::default();
+        for profile in builtin_profiles() {
+            catalog.insert_profile(profile);
+        }
+        catalog.assignments = builtin_assignments();
+        catalog
+    }
+
+    /// Adds or replaces a profile
+    pub fn insert_profile(&mut self, profile: OidProfile) {
+        self.profiles.insert(profile.name.clone(), profile);
+    }
+
+    /// Adds or replaces the assignment for a scope and target
+    pub fn insert_assignment(&mut self, assignment: OidProfileAssignment) {
+        self.assignments
+            .retain(|a| a.scope != assignment.scope || a.target != assignment.target);
+        self.assignments.push(assignment);
+    }
+
+    /// Adds or replaces a node group that group assignments can match
+    pub fn insert_node_group(&mut self, group: NodeGroup) {
+        self.node_groups.insert(group.name.clone(), group);
+    }
+
+    /// Removes the assignment with the given `scope:target` key
+    pub fn remove_assignment(&mut self, key: &str) {
+        self.assignments.retain(|a| a.key() != key);
+    }
+
+    /// Looks up a profile by name
+    #[must_use]
+    pub fn profile(&self, name: &str) -> Option<&OidProfile> {
+        self.profiles.get(name)
+    }
+
+    /// Returns all profiles ordered by name
+    pub fn profiles(&self) -> impl Iterator<Item = &OidProfile> {
+        self.profiles.values()
+    }
+
+    /// Returns all assignments ordered by scope and target
+    #[must_use]
+    pub fn assignments(&self) -> Vec<&OidProfileAssignment> {
+        let mut assignments: Vec<_> = self.assignments.iter().collect();
+        assignments.sort_by(|a, b| (a.scope, &a.target).cmp(&(b.scope, &b.target)));
+        assignments
+    }
+
+    /// Flattens a profile's inheritance chain into its effective groups
+    ///
+    /// # Errors
+    /// Returns an error if the profile or one of its ancestors is unknown, or
+    /// if the inheritance chain contains a cycle.
+    pub fn resolve(&self, name: &str) -> Result<ResolvedOidProfile, OidProfileError> {
+        let mut chain = Vec::new();
+        let mut current = Some(name);
+        while let Some(profile_name) = current {
+            if chain.iter().any(|seen| seen == profile_name) {
+                chain.push(profile_name.to_string());
+                return Err(OidProfileError::InheritanceCycle(chain.join(" -> ")));
+            }
+            let profile = self
+                .profiles
+                .get(profile_name)
+                .ok_or_else(|| OidProfileError::UnknownProfile(profile_name.to_string()))?;
+            chain.push(profile_name.to_string());
+            current = profile.parent.as_deref();
+        }
+
+        let mut groups: Vec<OidGroup> = Vec::new();
+        for profile_name in chain.iter().rev() {
+            for group in &self.profiles[profile_name].groups {
+                match groups.iter_mut().find(|g| g.name == group.name) {
+                    Some(existing) => *existing = group.clone(),
+                    None => groups.push(group.clone()),
+                }
+            }
+        }
+
+        Ok(ResolvedOidProfile {
+            profile: name.to_string(),
+            source: String::new(),
+            chain,
+            groups,
+            adjusted_by: Vec::new(),
+        })
+    }
+
+    /// Resolves the profile for a node using node, group, vendor, then role
+    /// assignments
+    ///
+    /// A node in several assigned groups takes the assignment of the group
+    /// whose name sorts first. Assignments to groups missing from the catalog
+    /// match no nodes.
+    ///
+    /// # Errors
+    /// Returns an error if the assigned profile cannot be resolved.
+    pub fn resolve_for_node(&self, node: &Node) -> Result<ResolvedOidProfile, OidProfileError> {
+        let find = |scope: AssignmentScope, target: &str| {
+            self.assignments
+                .iter()
+                .find(|a| a.scope == scope && a.target == target)
+        };
+        let group = || {
+            self.node_groups
+                .values()
+                .filter(|group| group.matches(node).unwrap_or(false))
+                .find_map(|group| find(AssignmentScope::Group, &group.name))
+        };
+
+        let (profile, source) = find(AssignmentScope::Node, &node.id.to_string())
+            .or_else(group)
+            .or_else(|| find(AssignmentScope::Vendor, &node.vendor.to_string()))
+            .or_else(|| find(AssignmentScope::Role, &node.role.to_string()))
+            .map_or_else(
+                || (DEFAULT_PROFILE, "default".to_string()),
+                |a| (a.profile.as_str(), a.key()),
+            );
+
+        let mut resolved = self.resolve(profile)?;
+        resolved.source = source;
+        Ok(resolved)
+    }
+
+    /// Resolves the profile for a node with policy adjustments applied
+    ///
+    /// The most recently applied profile adjustment replaces the assigned
+    /// profile, and the shortest interval adjustment caps every group's
+    /// interval.
+    ///
+    /// # Errors
+    /// Returns an error if the assigned or adjusted profile cannot be resolved.
+    pub fn resolve_adjusted(
+        &self,
+        node: &Node,
+        adjustments: &[PollingAdjustment],
+    ) -> Result<ResolvedOidProfile, OidProfileError> {
+        let profile = adjustments
+            .iter()
+            .filter_map(|adjustment| match &adjustment.setting {
+                PollingSetting::Profile(name) => Some((adjustment, name)),
+                PollingSetting::Interval(_) => None,
+            })
+            .max_by_key(|(adjustment, _)| adjustment.applied_at);
+        let interval = adjustments
+            .iter()
+            .filter_map(|adjustment| match adjustment.setting {
+                PollingSetting::Interval(seconds) => Some((adjustment, seconds)),
+                PollingSetting::Profile(_) => None,
+            })
+            .min_by_key(|(_, seconds)| *seconds);
+
+        let mut resolved = match profile {
+            Some((adjustment, name)) => {
+                let mut resolved = self.resolve(name)?;
+                resolved.source = format!("policy:{}", adjustment.rule);
+                resolved.adjusted_by.push(adjustment.rule.clone());
+                resolved
+            }
+            None => self.resolve_for_node(node)?,
+        };
+        if let Some((adjustment, seconds)) = interval {
+            for group in &mut resolved.groups {
+                group.interval_seconds = group.interval_seconds.min(seconds);
+            }
+            resolved.adjusted_by.push(adjustment.rule.clone());
+        }
+        Ok(resolved)
+    }
+}
@@ -0,0 +1,58 @@
+//! OID profiles describing what to poll on a device and how often
+//!
+//! A profile is a named set of [`OidGroup`]s, each polled at its own interval.
+//! Profiles may inherit from a parent; a child group replaces a parent group
+//! with the same name. Profiles are assigned to nodes by node ID, dynamic
+//! node group (see [`crate::groups`]), vendor, or role, in that order of
+//! precedence. Nodes without any assignment use [`DEFAULT_PROFILE`]. Policy
+//! rules can swap a node's profile or shorten its intervals for as long as
+//! they match; see [`crate::snmp::adjustments`].
+
+mod builtin;
+mod catalog;
+mod store;
+mod types;
+
+use thiserror::Error;
+
+use crate::datastore::DataStoreError;
+
+pub use builtin::builtin_profiles;
+pub use catalog::{OidProfileCatalog, ResolvedOidProfile};
+#[cfg(feature = "snmp")]
+pub use store::node_polling_tasks;
+pub use store::{
+    assign_profile, delete_profile, load_catalog, resolve_node_profile, save_profile,
+    unassign_profile,
+};
+pub use types::{AssignmentScope, OidGroup, OidProfile, OidProfileAssignment};
+
+/// Profile used for nodes with no matching assignment
+pub const DEFAULT_PROFILE: &str = "base";
+
+/// Errors raised while managing or resolving OID profiles
+#[derive(Error, Debug)]
+pub enum OidProfileError {
+    /// Referenced profile does not exist
+    #[error("Unknown OID profile: {0}")]
+    UnknownProfile(String),
+
+    /// Profile inheritance loops back on itself
+    #[error("OID profile inheritance cycle: {0}")]
+    InheritanceCycle(String),
+
+    /// Profile or assignment failed validation
+    #[error("Invalid OID profile: {0}")]
+    Invalid(String),
+
+    /// Built-in profiles cannot be deleted
+    #[error("OID profile {0} is built in and cannot be deleted")]
+    BuiltIn(String),
+
+    /// Underlying datastore failure
+    #[error(transparent)]
+    DataStore(#[from] DataStoreError),
+}
+
+#[cfg(test)]
+mod tests;
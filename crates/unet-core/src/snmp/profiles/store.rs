@@ -0,0 +1,206 @@
+//! Persistence of OID profiles and assignments through the `DataStore` settings API
+
+use super::builtin::builtin_assignments;
+use super::{
+    AssignmentScope, OidProfile, OidProfileAssignment, OidProfileCatalog, OidProfileError,
+    ResolvedOidProfile, builtin_profiles,
+};
+use crate::datastore::DataStore;
+use crate::groups::list_groups;
+use crate::models::Node;
+use crate::snmp::adjustments::node_adjustments;
+#[cfg(feature = "snmp")]
+use crate::snmp::{PollingTask, SessionConfig, contexts::polling_contexts};
+#[cfg(feature = "snmp")]
+use std::net::SocketAddr;
+
+/// Settings namespace holding user-defined profiles keyed by name
+const PROFILE_NAMESPACE: &str = "oid_profiles";
+/// Settings namespace holding assignments keyed by `scope:target`
+const ASSIGNMENT_NAMESPACE: &str = "oid_profile_assignments";
+
+/// Loads built-in profiles overlaid with stored profiles and assignments,
+/// along with the node groups that group assignments match
+///
+/// A stored assignment whose value is `null` suppresses the built-in
+/// assignment with the same key.
+///
+/// # Errors
+/// Returns an error if the stored profiles, assignments, or node groups
+/// cannot be read, or a stored profile or assignment does not parse.
+pub async fn load_catalog(datastore: &dyn DataStore) -> Result<OidProfileCatalog, OidProfileError> {
+    let mut catalog = OidProfileCatalog::builtin();
+
+    for (name, value) in datastore.list_settings(PROFILE_NAMESPACE).await? {
+        let profile: OidProfile = serde_json::from_value(value)
+            .map_err(|e| OidProfileError::Invalid(format!("stored profile {name}: {e}")))?;
+        catalog.insert_profile(profile);
+    }
+
+    for group in list_groups(datastore).await? {
+        catalog.insert_node_group(group);
+    }
+
+    for (key, value) in datastore.list_settings(ASSIGNMENT_NAMESPACE).await? {
+        if value.is_null() {
+            catalog.remove_assignment(&key);
+            continue;
+        }
+        let assignment: OidProfileAssignment = serde_json::from_value(value)
+            .map_err(|e| OidProfileError::Invalid(format!("stored assignment {key}: {e}")))?;
+        catalog.insert_assignment(assignment);
+    }
+
+    Ok(catalog)
+}
+
+/// Resolves the profile a node is polled with, including policy adjustments
+///
+/// # Errors
+/// Returns an error if the catalog or adjustments cannot be read, or the
+/// profile cannot be resolved.
+pub async fn resolve_node_profile(
+    datastore: &dyn DataStore,
+    node: &Node,
+) -> Result<ResolvedOidProfile, OidProfileError> {
+    let catalog = load_catalog(datastore).await?;
+    let adjustments = node_adjustments(datastore, node.id).await?;
+    catalog.resolve_adjusted(node, &adjustments)
+}
+
+/// Builds a node's polling tasks from its resolved profile, for the default
+/// instance and then each of its polling contexts
+///
+/// The catalog is passed in so it is loaded once for a whole inventory.
+///
+/// # Errors
+/// Returns an error if the node's adjustments or contexts cannot be read, or
+/// its profile cannot be resolved.
+#[cfg(feature = "snmp")]
+pub async fn node_polling_tasks(
+    datastore: &dyn DataStore,
+    catalog: &OidProfileCatalog,
+    node: &Node,
+    target: SocketAddr,
+    session_config: &SessionConfig,
+) -> Result<Vec<PollingTask>, OidProfileError> {
+    let adjustments = node_adjustments(datastore, node.id).await?;
+    let contexts = polling_contexts(datastore, node.id).await?;
+    Ok(catalog
+        .resolve_adjusted(node, &adjustments)?
+        .context_polling_tasks(target, node.id, session_config, &contexts))
+}
+
+/// Validates and stores a profile, replacing any profile with the same name
+///
+/// # Errors
+/// Returns an error if the profile is invalid, its parent chain cannot be
+/// resolved, or the datastore write fails.
+pub async fn save_profile(
+    datastore: &dyn DataStore,
+    profile: &OidProfile,
+) -> Result<(), OidProfileError> {
+    profile.validate()?;
+
+    let mut catalog = load_catalog(datastore).await?;
+    catalog.insert_profile(profile.clone());
+    catalog.resolve(&profile.name)?;
+
+    let value = serde_json::to_value(profile)
+        .map_err(|e| OidProfileError::Invalid(format!("profile {}: {e}", profile.name)))?;
+    datastore
+        .put_setting(PROFILE_NAMESPACE, &profile.name, &value)
+        .await?;
+    Ok(())
+}
+
+/// Deletes a stored profile
+///
+/// Deleting a stored override of a built-in profile restores the built-in
+/// definition. Profiles still used as a parent or by an assignment are kept.
+///
+/// # Errors
+/// Returns an error if the profile is built in and not overridden, is still
+/// referenced, or does not exist.
+pub async fn delete_profile(datastore: &dyn DataStore, name: &str) -> Result<(), OidProfileError> {
+    let is_builtin = builtin_profiles().iter().any(|p| p.name == name);
+    let stored = datastore.get_setting(PROFILE_NAMESPACE, name).await?;
+    if stored.is_none() {
+        return Err(if is_builtin {
+            OidProfileError::BuiltIn(name.to_string())
+        } else {
+            OidProfileError::UnknownProfile(name.to_string())
+        });
+    }
+
+    if !is_builtin {
+        let catalog = load_catalog(datastore).await?;
+        let child = catalog
+            .profiles()
+            .find(|p| p.parent.as_deref() == Some(name));
+        if let Some(child) = child {
+            return Err(OidProfileError::Invalid(format!(
+                "{name} is the parent of {}",
+                child.name
+            )));
+        }
+        if let Some(assignment) = catalog
+            .assignments()
+            .into_iter()
+            .find(|a| a.profile == name)
+        {
+            return Err(OidProfileError::Invalid(format!(
+                "{name} is assigned to {}",
+                assignment.key()
+            )));
+        }
+    }
+
+    datastore.delete_setting(PROFILE_NAMESPACE, name).await?;
+    Ok(())
+}
+
+/// Stores an assignment, replacing any assignment for the same scope and target
+///
+/// # Errors
+/// Returns an error if the profile does not exist or the datastore write fails.
+pub async fn assign_profile(
+    datastore: &dyn DataStore,
+    assignment: &OidProfileAssignment,
+) -> Result<(), OidProfileError> {
+    let catalog = load_catalog(datastore).await?;
+    if catalog.profile(&assignment.profile).is_none() {
+        return Err(OidProfileError::UnknownProfile(assignment.profile.clone()));
+    }
+
+    let value = serde_json::to_value(assignment)
+        .map_err(|e| OidProfileError::Invalid(format!("assignment {}: {e}", assignment.key())))?;
+    datastore
+        .put_setting(ASSIGNMENT_NAMESPACE, &assignment.key(), &value)
+        .await?;
+    Ok(())
+}
+
+/// Removes the assignment for a scope and target
+///
+/// # Errors
+/// Returns an error if the target is invalid for the scope, no assignment
+/// exists, or the datastore write fails.
+pub async fn unassign_profile(
+    datastore: &dyn DataStore,
+    scope: AssignmentScope,
+    target: &str,
+) -> Result<(), OidProfileError> {
+    let key = OidProfileAssignment::new(scope, target, String::new())?.key();
+    let is_builtin = builtin_assignments().iter().any(|a| a.key() == key);
+
+    if is_builtin {
+        // Record a tombstone so the built-in assignment stays removed
+        datastore
+            .put_setting(ASSIGNMENT_NAMESPACE, &key, &serde_json::Value::Null)
+            .await?;
+    } else {
+        datastore.delete_setting(ASSIGNMENT_NAMESPACE, &key).await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,384 @@
+use super::*;
+use crate::datastore::DataStore;
+use crate::datastore::sqlite::tests::setup::settings_store;
+use crate::groups::NodeGroup;
+use crate::models::{DeviceRole, Node, Vendor};
+use crate::snmp::adjustments::{PollingAdjustment, PollingSetting};
+use crate::snmp::{SessionConfig, StandardOid};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+fn node(vendor: Vendor, role: DeviceRole) -> Node {
+    Node::new("node1".to_string(), "example.com".to_string(), vendor, role)
+}
+
+fn custom(name: &str, parent: Option<&str>, groups: Vec<OidGroup>) -> OidProfile {
+    OidProfile {
+        name: name.to_string(),
+        description: None,
+        parent: parent.map(str::to_string),
+        groups,
+    }
+}
+
+fn group(name: &str, oid: &str, interval_seconds: u64) -> OidGroup {
+    OidGroup {
+        name: name.to_string(),
+        oids: vec![oid.to_string()],
+        interval_seconds,
+    }
+}
+
+#[test]
+fn test_builtin_profiles_are_valid_and_resolvable() {
+    let catalog = OidProfileCatalog::builtin();
+    for profile in builtin_profiles() {
+        assert!(profile.validate().is_ok(), "{} is invalid", profile.name);
+        assert!(catalog.resolve(&profile.name).is_ok());
+    }
+}
+
+#[test]
+fn test_child_group_overrides_parent_group() {
+    let catalog = OidProfileCatalog::builtin();
+    let resolved = catalog.resolve("firewall").unwrap();
+
+    assert_eq!(resolved.chain, vec!["firewall", "base"]);
+    let system = resolved.groups.iter().find(|g| g.name == "system").unwrap();
+    assert_eq!(system.interval_seconds, 60);
+    assert_eq!(
+        resolved
+            .groups
+            .iter()
+            .filter(|g| g.name == "system")
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn test_resolve_detects_inheritance_cycle() {
+    let mut catalog = OidProfileCatalog::default();
+    catalog.insert_profile(custom("a", Some("b"), vec![]));
+    catalog.insert_profile(custom("b", Some("a"), vec![]));
+
+    assert!(matches!(
+        catalog.resolve("a"),
+        Err(OidProfileError::InheritanceCycle(_))
+    ));
+}
+
+#[test]
+fn test_resolve_reports_unknown_parent() {
+    let mut catalog = OidProfileCatalog::default();
+    catalog.insert_profile(custom("orphan", Some("missing"), vec![]));
+
+    assert!(matches!(
+        catalog.resolve("orphan"),
+        Err(OidProfileError::UnknownProfile(name)) if name == "missing"
+    ));
+}
+
+#[test]
+fn test_node_assignment_precedence() {
+    let mut catalog = OidProfileCatalog::builtin();
+    catalog.insert_profile(custom("juniper-core", Some("router-core"), vec![]));
+    catalog.insert_profile(custom("pinned", Some("base"), vec![]));
+    let router = node(Vendor::Juniper, DeviceRole::Router);
+
+    let resolved = catalog.resolve_for_node(&router).unwrap();
+    assert_eq!(resolved.profile, "router-core");
+    assert_eq!(resolved.source, "role:router");
+
+    catalog.insert_assignment(
+        OidProfileAssignment::new(AssignmentScope::Vendor, "Juniper", "juniper-core").unwrap(),
+    );
+    let resolved = catalog.resolve_for_node(&router).unwrap();
+    assert_eq!(resolved.profile, "juniper-core");
+
+    catalog.insert_assignment(
+        OidProfileAssignment::new(AssignmentScope::Node, &router.id.to_string(), "pinned").unwrap(),
+    );
+    let resolved = catalog.resolve_for_node(&router).unwrap();
+    assert_eq!(resolved.profile, "pinned");
+    assert_eq!(resolved.source, format!("node:{}", router.id));
+}
+
+#[test]
+fn test_group_assignment_ranks_between_node_and_vendor() {
+    let mut catalog = OidProfileCatalog::builtin();
+    catalog.insert_profile(custom("core", Some("router-core"), vec![]));
+    catalog.insert_profile(custom("vendor", Some("base"), vec![]));
+    catalog.insert_node_group(NodeGroup::new("core", r#"node.name == "node1""#, None).unwrap());
+    catalog.insert_assignment(
+        OidProfileAssignment::new(AssignmentScope::Vendor, "juniper", "vendor").unwrap(),
+    );
+    catalog.insert_assignment(
+        OidProfileAssignment::new(AssignmentScope::Group, "core", "core").unwrap(),
+    );
+    catalog.insert_assignment(
+        OidProfileAssignment::new(AssignmentScope::Group, "missing", "base").unwrap(),
+    );
+    let router = node(Vendor::Juniper, DeviceRole::Router);
+
+    let resolved = catalog.resolve_for_node(&router).unwrap();
+    assert_eq!(resolved.profile, "core");
+    assert_eq!(resolved.source, "group:core");
+
+    let mut other = router.clone();
+    other.name = "node2".to_string();
+    assert_eq!(catalog.resolve_for_node(&other).unwrap().profile, "vendor");
+    assert!(OidProfileAssignment::new(AssignmentScope::Group, "core routers", "base").is_err());
+}
+
+#[test]
+fn test_unassigned_node_uses_default_profile() {
+    let catalog = OidProfileCatalog::builtin();
+    let resolved = catalog
+        .resolve_for_node(&node(Vendor::Generic, DeviceRole::Server))
+        .unwrap();
+
+    assert_eq!(resolved.profile, DEFAULT_PROFILE);
+    assert_eq!(resolved.source, "default");
+}
+
+#[test]
+fn test_policy_adjustments_swap_profile_and_cap_intervals() {
+    let mut catalog = OidProfileCatalog::default();
+    catalog.insert_profile(custom(
+        "base",
+        None,
+        vec![group("a", "1.3.6.1.2.1.1.1.0", 300)],
+    ));
+    catalog.insert_profile(custom(
+        "intensive",
+        None,
+        vec![
+            group("a", "1.3.6.1.2.1.1.1.0", 120),
+            group("b", "1.3.6.1.2.1.1.3.0", 30),
+        ],
+    ));
+    let router = node(Vendor::Cisco, DeviceRole::Router);
+    let adjustment = |rule: &str, setting| PollingAdjustment {
+        node_id: router.id,
+        rule: rule.to_string(),
+        setting,
+        applied_at: chrono::Utc::now(),
+    };
+
+    let resolved = catalog
+        .resolve_adjusted(
+            &router,
+            &[adjustment("degraded", PollingSetting::Interval(60))],
+        )
+        .unwrap();
+    assert_eq!(resolved.profile, DEFAULT_PROFILE);
+    assert_eq!(resolved.groups[0].interval_seconds, 60);
+    assert_eq!(resolved.adjusted_by, ["degraded"]);
+
+    let resolved = catalog
+        .resolve_adjusted(
+            &router,
+            &[
+                adjustment("degraded", PollingSetting::Interval(60)),
+                adjustment(
+                    "non-compliant",
+                    PollingSetting::Profile("intensive".to_string()),
+                ),
+            ],
+        )
+        .unwrap();
+    assert_eq!(resolved.profile, "intensive");
+    assert_eq!(resolved.source, "policy:non-compliant");
+    let intervals: Vec<u64> = resolved.groups.iter().map(|g| g.interval_seconds).collect();
+    assert_eq!(intervals, [60, 30]);
+}
+
+#[test]
+fn test_polling_tasks_grouped_by_interval() {
+    let mut catalog = OidProfileCatalog::default();
+    catalog.insert_profile(custom(
+        "p",
+        None,
+        vec![
+            group("a", "1.3.6.1.2.1.1.1.0", 60),
+            group("b", "1.3.6.1.2.1.1.3.0", 60),
+            group("c", "1.3.6.1.2.1.1.5.0", 300),
+        ],
+    ));
+    let resolved = catalog.resolve("p").unwrap();
+    let target = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 161);
+
+    let tasks = resolved.polling_tasks(target, uuid::Uuid::new_v4(), &SessionConfig::default());
+
+    assert_eq!(tasks.len(), 2);
+    assert_eq!(tasks[0].interval.as_secs(), 60);
+    assert_eq!(tasks[0].oids.len(), 2);
+    assert_eq!(tasks[1].oids, vec![StandardOid::SysName.oid().to_string()]);
+}
+
+#[test]
+fn test_context_polling_tasks_repeat_tasks_per_context() {
+    let mut catalog = OidProfileCatalog::default();
+    catalog.insert_profile(custom(
+        "p",
+        None,
+        vec![
+            group("a", "1.3.6.1.2.1.1.1.0", 60),
+            group("c", "1.3.6.1.2.1.1.5.0", 300),
+        ],
+    ));
+    let resolved = catalog.resolve("p").unwrap();
+    let target = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 161);
+    let contexts = ["vrf-blue".to_string(), "vrf-red".to_string()];
+
+    let tasks = resolved.context_polling_tasks(
+        target,
+        uuid::Uuid::new_v4(),
+        &SessionConfig::default(),
+        &contexts,
+    );
+
+    let polled: Vec<_> = tasks
+        .iter()
+        .map(|task| {
+            (
+                task.session_config.context.as_deref(),
+                task.interval.as_secs(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        polled,
+        [
+            (None, 60),
+            (None, 300),
+            (Some("vrf-blue"), 60),
+            (Some("vrf-blue"), 300),
+            (Some("vrf-red"), 60),
+            (Some("vrf-red"), 300),
+        ]
+    );
+}
+
+#[test]
+fn test_validate_rejects_malformed_profiles() {
+    assert!(custom("Bad Name", None, vec![]).validate().is_err());
+    assert!(custom("p", Some("p"), vec![]).validate().is_err());
+    assert!(
+        custom("p", None, vec![group("a", "1.3.x", 60)])
+            .validate()
+            .is_err()
+    );
+    assert!(
+        custom("p", None, vec![group("a", "1.3.6", 0)])
+            .validate()
+            .is_err()
+    );
+}
+
+#[test]
+fn test_assignment_normalizes_target() {
+    let assignment = OidProfileAssignment::new(AssignmentScope::Role, "Router", "base").unwrap();
+    assert_eq!(assignment.key(), "role:router");
+    assert!(OidProfileAssignment::new(AssignmentScope::Node, "not-a-uuid", "base").is_err());
+}
+
+#[tokio::test]
+async fn test_saved_profiles_and_assignments_round_trip() {
+    let store = settings_store().await;
+    let profile = custom(
+        "edge",
+        Some("router-core"),
+        vec![group("bgp", "1.3.6.1.2.1.15.3.1.2", 60)],
+    );
+
+    save_profile(&store, &profile).await.unwrap();
+    let assignment = OidProfileAssignment::new(AssignmentScope::Vendor, "cisco", "edge").unwrap();
+    assign_profile(&store, &assignment).await.unwrap();
+
+    let catalog = load_catalog(&store).await.unwrap();
+    assert_eq!(catalog.profile("edge"), Some(&profile));
+    let resolved = catalog
+        .resolve_for_node(&node(Vendor::Cisco, DeviceRole::Router))
+        .unwrap();
+    assert_eq!(resolved.chain, vec!["edge", "router-core", "base"]);
+}
+
+#[tokio::test]
+async fn test_save_profile_rejects_unknown_parent() {
+    let store = settings_store().await;
+    let result = save_profile(&store, &custom("edge", Some("missing"), vec![])).await;
+
+    assert!(matches!(result, Err(OidProfileError::UnknownProfile(_))));
+    assert!(
+        store
+            .list_settings("oid_profiles")
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn test_delete_profile_protects_builtins_and_references() {
+    let store = settings_store().await;
+    save_profile(&store, &custom("edge", Some("base"), vec![]))
+        .await
+        .unwrap();
+    save_profile(&store, &custom("edge-lab", Some("edge"), vec![]))
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        delete_profile(&store, "base").await,
+        Err(OidProfileError::BuiltIn(_))
+    ));
+    assert!(delete_profile(&store, "edge").await.is_err());
+    delete_profile(&store, "edge-lab").await.unwrap();
+    delete_profile(&store, "edge").await.unwrap();
+}
+
+#[tokio::test]
+async fn test_unassign_builtin_role_assignment() {
+    let store = settings_store().await;
+    unassign_profile(&store, AssignmentScope::Role, "router")
+        .await
+        .unwrap();
+
+    let catalog = load_catalog(&store).await.unwrap();
+    let resolved = catalog
+        .resolve_for_node(&node(Vendor::Cisco, DeviceRole::Router))
+        .unwrap();
+    assert_eq!(resolved.profile, DEFAULT_PROFILE);
+}
+
+#[tokio::test]
+async fn test_node_polling_tasks_follow_assigned_profile_and_contexts() {
+    let store = settings_store().await;
+    let node = node(Vendor::Cisco, DeviceRole::Router);
+    save_profile(
+        &store,
+        &custom("edge", None, vec![group("sys", "1.3.6.1.2.1.1.5.0", 120)]),
+    )
+    .await
+    .unwrap();
+    let assignment =
+        OidProfileAssignment::new(AssignmentScope::Node, &node.id.to_string(), "edge").unwrap();
+    assign_profile(&store, &assignment).await.unwrap();
+    crate::snmp::contexts::set_polling_contexts(&store, node.id, vec!["vrf-blue".to_string()])
+        .await
+        .unwrap();
+    let catalog = load_catalog(&store).await.unwrap();
+    let target = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 161);
+
+    let tasks = node_polling_tasks(&store, &catalog, &node, target, &SessionConfig::default())
+        .await
+        .unwrap();
+
+    assert_eq!(tasks.len(), 2);
+    assert!(tasks.iter().all(|task| task.node_id == node.id
+        && task.target == target
+        && task.oids == [StandardOid::SysName.oid().to_string()]
+        && task.interval.as_secs() == 120));
+    assert_eq!(tasks[1].session_config.context.as_deref(), Some("vrf-blue"));
+}
@@ -0,0 +1,186 @@
+//! OID profile and assignment definitions
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+use uuid::Uuid;
+
+use super::OidProfileError;
+use crate::groups::group_name;
+use crate::models::{DeviceRole, Vendor};
+
+/// A named set of OIDs polled together at one interval
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OidGroup {
+    /// Group name, unique within a profile (e.g., `interfaces`)
+    pub name: String,
+    /// Numeric OIDs or table bases to poll
+    pub oids: Vec<String>,
+    /// Polling interval in seconds
+    pub interval_seconds: u64,
+}
+
+/// A named, optionally inherited, collection of OID groups
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OidProfile {
+    /// Profile name (lowercase letters, digits, and dashes)
+    pub name: String,
+    /// Human-readable description
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Parent profile whose groups are inherited
+    #[serde(default)]
+    pub parent: Option<String>,
+    /// Groups defined or overridden by this profile
+    #[serde(default)]
+    pub groups: Vec<OidGroup>,
+}
+
+impl OidProfile {
+    /// Validates names, intervals, and OID syntax
+    ///
+    /// # Errors
+    /// Returns [`OidProfileError::Invalid`] describing the first problem found.
+    pub fn validate(&self) -> Result<(), OidProfileError> {
+        validate_name(&self.name)?;
+        if self.parent.as_deref() == Some(self.name.as_str()) {
+            return Err(OidProfileError::InheritanceCycle(self.name.clone()));
+        }
+
+        let mut seen = HashSet::new();
+        for group in &self.groups {
+            if group.name.is_empty() || !seen.insert(group.name.as_str()) {
+                return Err(OidProfileError::Invalid(format!(
+                    "group names in {} must be non-empty and unique",
+                    self.name
+                )));
+            }
+            if group.interval_seconds == 0 {
+                return Err(OidProfileError::Invalid(format!(
+                    "group {} must have a non-zero interval",
+                    group.name
+                )));
+            }
+            if group.oids.is_empty() {
+                return Err(OidProfileError::Invalid(format!(
+                    "group {} must list at least one OID",
+                    group.name
+                )));
+            }
+            if let Some(oid) = group.oids.iter().find(|oid| !is_numeric_oid(oid)) {
+                return Err(OidProfileError::Invalid(format!(
+                    "group {} contains malformed OID {oid}",
+                    group.name
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// What an assignment matches against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AssignmentScope {
+    /// A single node by ID
+    Node,
+    /// All members of a dynamic node group
+    Group,
+    /// All nodes from a vendor
+    Vendor,
+    /// All nodes with a device role
+    Role,
+}
+
+impl Display for AssignmentScope {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Node => write!(f, "node"),
+            Self::Group => write!(f, "group"),
+            Self::Vendor => write!(f, "vendor"),
+            Self::Role => write!(f, "role"),
+        }
+    }
+}
+
+impl FromStr for AssignmentScope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "node" => Ok(Self::Node),
+            "group" => Ok(Self::Group),
+            "vendor" => Ok(Self::Vendor),
+            "role" => Ok(Self::Role),
+            _ => Err(format!("Invalid assignment scope: {s}")),
+        }
+    }
+}
+
+/// Binds a profile to a node, group, vendor, or role
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OidProfileAssignment {
+    /// Assignment scope
+    pub scope: AssignmentScope,
+    /// Node ID, group name, vendor name, or role name depending on scope
+    pub target: String,
+    /// Assigned profile name
+    pub profile: String,
+}
+
+impl OidProfileAssignment {
+    /// Creates an assignment, normalizing the target for its scope
+    ///
+    /// # Errors
+    /// Returns [`OidProfileError::Invalid`] if the target is not a valid node
+    /// ID, group name, vendor, or role for the scope.
+    pub fn new(
+        scope: AssignmentScope,
+        target: &str,
+        profile: impl Into<String>,
+    ) -> Result<Self, OidProfileError> {
+        let target = match scope {
+            AssignmentScope::Node => Uuid::parse_str(target)
+                .map(|id| id.to_string())
+                .map_err(|e| OidProfileError::Invalid(format!("node target {target}: {e}")))?,
+            AssignmentScope::Group => group_name(target)?,
+            AssignmentScope::Vendor => Vendor::from_str(target)
+                .map_err(OidProfileError::Invalid)?
+                .to_string(),
+            AssignmentScope::Role => DeviceRole::from_str(target)
+                .map_err(OidProfileError::Invalid)?
+                .to_string(),
+        };
+        Ok(Self {
+            scope,
+            target,
+            profile: profile.into(),
+        })
+    }
+
+    /// Storage key identifying the scope and target
+    #[must_use]
+    pub fn key(&self) -> String {
+        format!("{}:{}", self.scope, self.target)
+    }
+}
+
+fn validate_name(name: &str) -> Result<(), OidProfileError> {
+    let valid = !name.is_empty()
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-');
+    if valid {
+        Ok(())
+    } else {
+        Err(OidProfileError::Invalid(format!(
+            "profile name {name:?} must use lowercase letters, digits, and dashes"
+        )))
+    }
+}
+
+fn is_numeric_oid(oid: &str) -> bool {
+    oid.split('.')
+        .all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()))
+}
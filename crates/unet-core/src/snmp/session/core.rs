@@ -1,6 +1,6 @@
 //! Core SNMP session management
 
-use super::super::config::{SessionConfig, SnmpCredentials};
+use super::super::config::{MAX_PIPELINE_WINDOW, SessionConfig};
 use super::super::{SnmpError, SnmpResult};
 use csnmp::Snmp2cClient;
 use std::time::{Duration, SystemTime};
@@ -19,8 +19,14 @@ pub struct SnmpSession {
     pub(super) connection_attempts: RwLock<u32>,
     /// Underlying SNMP client
     pub(super) client: Option<Snmp2cClient>,
+    /// Additional clients for pipelined requests, one per extra window slot
+    pub(super) pipeline_clients: Vec<Snmp2cClient>,
 }
 
+/// Spacing between the starting request IDs of pipeline clients, so each
+/// slot's request IDs stay distinct on the wire
+const REQUEST_ID_STRIDE: i32 = 1 << 24;
+
 impl SnmpSession {
     /// Create new SNMP session
     #[must_use]
@@ -33,19 +39,28 @@ impl SnmpSession {
             last_success: RwLock::new(None),
             connection_attempts: RwLock::new(0),
             client: None,
+            pipeline_clients: Vec::new(),
         }
     }
 
     /// Create a new SNMP client
     pub(super) async fn create_client(config: &SessionConfig) -> SnmpResult<Snmp2cClient> {
-        match &config.credentials {
-            SnmpCredentials::Community { community } => {
+        Self::create_client_with_request_id(config, 0).await
+    }
+
+    /// Create a new SNMP client whose request IDs start at `request_id`
+    async fn create_client_with_request_id(
+        config: &SessionConfig,
+        request_id: i32,
+    ) -> SnmpResult<Snmp2cClient> {
+        match config.community() {
+            Some(community) => {
                 let client = Snmp2cClient::new(
                     config.address,
-                    community.as_bytes().to_vec(),
+                    community.into_bytes(),
                     None, // Use default local address
                     None, // Use default timeout
-                    0,    // Default request_id
+                    request_id,
                 )
                 .await
                 .map_err(|e| SnmpError::Protocol {
@@ -54,7 +69,7 @@ impl SnmpSession {
 
                 Ok(client)
             }
-            SnmpCredentials::UserBased { .. } => Err(SnmpError::Protocol {
+            None => Err(SnmpError::Protocol {
                 message: "SNMPv3 user-based security not supported by csnmp".to_string(),
             }),
         }
@@ -69,6 +84,33 @@ impl SnmpSession {
         self.client_ref()
     }
 
+    /// Get or create one client per pipeline window slot, for up to
+    /// `requests` outstanding requests
+    ///
+    /// csnmp matches a response to its request on the client's own socket,
+    /// so every outstanding request needs its own client.
+    pub(super) async fn get_pipeline_clients(
+        &mut self,
+        requests: usize,
+    ) -> SnmpResult<Vec<&Snmp2cClient>> {
+        let window = self
+            .config
+            .pipeline_window
+            .clamp(1, MAX_PIPELINE_WINDOW)
+            .min(requests.max(1));
+        self.get_client().await?;
+        while self.pipeline_clients.len() + 1 < window {
+            let slot = i32::try_from(self.pipeline_clients.len() + 1).unwrap_or(i32::MAX);
+            let request_id = slot.saturating_mul(REQUEST_ID_STRIDE);
+            let client = Self::create_client_with_request_id(&self.config, request_id).await?;
+            self.pipeline_clients.push(client);
+        }
+
+        let mut clients = vec![self.client_ref()?];
+        clients.extend(self.pipeline_clients.iter().take(window - 1));
+        Ok(clients)
+    }
+
     fn client_ref(&self) -> SnmpResult<&Snmp2cClient> {
         self.client.as_ref().ok_or_else(|| SnmpError::Protocol {
             message: "SNMP client not initialized".to_string(),
@@ -130,6 +172,7 @@ impl std::fmt::Debug for SnmpSession {
             .field("last_success", &"<RwLock<Option<SystemTime>>>")
             .field("connection_attempts", &"<RwLock<u32>>")
             .field("client", &"<Option<Snmp2cClient>>")
+            .field("pipeline_clients", &self.pipeline_clients.len())
             .finish()
     }
 }
@@ -12,6 +12,9 @@ fn create_test_config() -> SessionConfig {
         timeout: Duration::from_secs(5),
         retries: 3,
         max_vars_per_request: 50,
+        pipeline_window: 4,
+        max_message_size: 1472,
+        context: None,
     }
 }
 
@@ -103,6 +106,9 @@ async fn test_create_client_community() {
         timeout: Duration::from_secs(5),
         retries: 3,
         max_vars_per_request: 50,
+        pipeline_window: 4,
+        max_message_size: 1472,
+        context: None,
     };
 
     let result = SnmpSession::create_client(&config).await;
@@ -125,6 +131,9 @@ async fn test_create_client_user_based() {
         timeout: Duration::from_secs(5),
         retries: 3,
         max_vars_per_request: 50,
+        pipeline_window: 4,
+        max_message_size: 1472,
+        context: None,
     };
 
     let result = SnmpSession::create_client(&config).await;
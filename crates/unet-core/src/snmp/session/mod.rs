@@ -4,4 +4,5 @@ pub use core::SnmpSession;
 
 pub mod core;
 pub mod operations;
+pub mod pipeline;
 pub mod utils;
@@ -3,6 +3,7 @@
 use super::super::values::SnmpValue;
 use super::super::{SnmpError, SnmpResult};
 use super::core::SnmpSession;
+use super::pipeline::{execute_pipelined_get, vars_per_message};
 use super::utils::{convert_object_value_to_snmp_value, parse_oids};
 use csnmp::ObjectIdentifier;
 use std::collections::HashMap;
@@ -22,9 +23,17 @@ impl SnmpSession {
         let oid_objects = parse_oids(oids)?;
         let session_id = self.session_id;
         let target_address = self.config.address;
-
-        let client = self.get_client().await?;
-        let result = execute_get_requests(client, &oid_objects, oids, session_id).await;
+        let max_vars = self
+            .config
+            .max_vars_per_request
+            .min(vars_per_message(oids, self.config.max_message_size))
+            .max(1);
+
+        let clients = self
+            .get_pipeline_clients(oids.len().div_ceil(max_vars))
+            .await?;
+        let result =
+            execute_pipelined_get(&clients, &oid_objects, oids, max_vars, session_id).await;
 
         self.update_success_timestamp(!result.is_empty()).await;
         log_operation_completion(session_id, target_address, "GET", &result);
@@ -90,37 +99,6 @@ impl SnmpSession {
     }
 }
 
-/// Execute GET requests for all OIDs
-async fn execute_get_requests(
-    client: &csnmp::Snmp2cClient,
-    oid_objects: &[ObjectIdentifier],
-    oids: &[&str],
-    session_id: Uuid,
-) -> HashMap<String, SnmpValue> {
-    let mut result = HashMap::new();
-
-    for (i, oid) in oid_objects.iter().enumerate() {
-        match client.get(*oid).await {
-            Ok(value) => {
-                let oid_str = oid.to_string();
-                let snmp_value = convert_object_value_to_snmp_value(&value);
-                result.insert(oid_str, snmp_value);
-            }
-            Err(e) => {
-                result.insert(oids[i].to_string(), SnmpValue::NoSuchObject);
-                debug!(
-                    session_id = %session_id,
-                    oid = oids[i],
-                    error = %e,
-                    "Failed to get OID value"
-                );
-            }
-        }
-    }
-
-    result
-}
-
 /// Log the completion of an SNMP operation
 fn log_operation_completion(
     session_id: Uuid,
@@ -154,6 +132,9 @@ mod tests {
             timeout: Duration::from_secs(5),
             retries: 3,
             max_vars_per_request: 50,
+            pipeline_window: 4,
+            max_message_size: 1472,
+            context: None,
         }
     }
 
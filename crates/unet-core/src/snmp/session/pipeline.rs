@@ -0,0 +1,259 @@
+//! Pipelined SNMP GET requests
+//!
+//! A GET of many OIDs is split into requests of at most
+//! `max_vars_per_request` variables. Rather than waiting for each response
+//! before sending the next request, up to `pipeline_window` requests are kept
+//! outstanding at once, one per pipeline client. Responses may complete in
+//! any order; values are merged by OID, so their order does not matter.
+//!
+//! Requests are also kept small enough for their responses to fit in
+//! `max_message_size` bytes, estimated from the OIDs asked for. An agent
+//! whose response still does not fit answers `tooBig`; the request is then
+//! split in half and each half sent again. Requests are not retried over
+//! TCP, where larger responses would fit.
+
+use super::super::config::MIN_MAX_MESSAGE_SIZE;
+use super::super::values::SnmpValue;
+use super::utils::convert_object_value_to_snmp_value;
+use csnmp::{ObjectIdentifier, Snmp2cClient, SnmpClientError};
+use futures_util::future::join_all;
+use std::collections::HashMap;
+use std::ops::Range;
+use tracing::debug;
+use uuid::Uuid;
+
+/// Bytes of a response outside its variable bindings: message and PDU
+/// headers, request ID, error fields, and a community of up to 32 bytes
+const MESSAGE_OVERHEAD: usize = 64;
+
+/// Bytes of a variable binding outside its OID: sequence, OID, and value
+/// headers
+const VARBIND_HEADERS: usize = 6;
+
+/// Bytes a value is assumed to take, enough for counters, addresses, and
+/// short strings
+const VALUE_ALLOWANCE: usize = 64;
+
+/// Estimated size of the variable binding returned for `oid`
+fn varbind_size(oid: &str) -> usize {
+    // The first two arcs are encoded together in one byte
+    let encoded: usize = oid
+        .trim_start_matches('.')
+        .split('.')
+        .skip(2)
+        .map(|arc| {
+            let mut rest = arc.parse::<u64>().unwrap_or(0) >> 7;
+            let mut bytes = 1;
+            while rest > 0 {
+                bytes += 1;
+                rest >>= 7;
+            }
+            bytes
+        })
+        .sum();
+    VARBIND_HEADERS + 1 + encoded + VALUE_ALLOWANCE
+}
+
+/// Most variables a GET of `oids` can ask for at once with every response
+/// estimated to fit in `max_message_size` bytes
+///
+/// Sizes at or below the 484 bytes every agent must accept are raised to it.
+/// At least one variable is always allowed.
+#[must_use]
+pub fn vars_per_message(oids: &[&str], max_message_size: usize) -> usize {
+    let budget = max_message_size.max(MIN_MAX_MESSAGE_SIZE) - MESSAGE_OVERHEAD;
+    let largest = oids.iter().map(|oid| varbind_size(oid)).max().unwrap_or(1);
+    (budget / largest).max(1)
+}
+
+/// Whether a failed request was answered with the `tooBig` error status
+///
+/// The client does not report the error status of a response. An agent
+/// answering `tooBig` returns no variable bindings (RFC 3416, 4.2.1), which
+/// the client reports as a binding count mismatch with none obtained; other
+/// error statuses return the bindings of the request.
+#[must_use]
+pub fn is_too_big(error: &SnmpClientError) -> bool {
+    matches!(error, SnmpClientError::BindingCount { obtained, .. } if obtained.is_empty())
+}
+
+/// Assigns request chunks to pipeline slots
+///
+/// OIDs are split into chunks of at most `max_vars` variables, and chunk
+/// `i` is sent by slot `i % window`. Each slot sends its chunks in order,
+/// so at most `window` requests are outstanding at any time.
+#[must_use]
+pub fn plan_requests(oid_count: usize, max_vars: usize, window: usize) -> Vec<Vec<Range<usize>>> {
+    let max_vars = max_vars.max(1);
+    let chunk_count = oid_count.div_ceil(max_vars);
+    let mut slots = vec![Vec::new(); window.clamp(1, chunk_count.max(1))];
+    let slot_count = slots.len();
+    for chunk in 0..chunk_count {
+        let start = chunk * max_vars;
+        slots[chunk % slot_count].push(start..(start + max_vars).min(oid_count));
+    }
+    slots
+}
+
+/// Execute GET requests for all OIDs over the given pipeline clients
+pub(super) async fn execute_pipelined_get(
+    clients: &[&Snmp2cClient],
+    oid_objects: &[ObjectIdentifier],
+    oids: &[&str],
+    max_vars: usize,
+    session_id: Uuid,
+) -> HashMap<String, SnmpValue> {
+    let plan = plan_requests(oid_objects.len(), max_vars, clients.len());
+    let slots = plan.iter().zip(clients).map(|(chunks, client)| async move {
+        let mut values = Vec::new();
+        for range in chunks {
+            values.extend(
+                get_chunk(
+                    client,
+                    &oid_objects[range.clone()],
+                    &oids[range.clone()],
+                    session_id,
+                )
+                .await,
+            );
+        }
+        values
+    });
+
+    join_all(slots).await.into_iter().flatten().collect()
+}
+
+/// Send one multi-variable GET, splitting it while responses are too big
+/// and falling back to single-OID requests on other failures
+///
+/// An agent rejects the whole request when any variable fails, so the
+/// fallback keeps the other OIDs of the chunk and reports the failing ones
+/// as `NoSuchObject`.
+async fn get_chunk(
+    client: &Snmp2cClient,
+    oid_objects: &[ObjectIdentifier],
+    oids: &[&str],
+    session_id: Uuid,
+) -> Vec<(String, SnmpValue)> {
+    let mut values = Vec::with_capacity(oid_objects.len());
+    // Requests still to send; halves of a too big request are sent in order
+    let mut pending = vec![0..oid_objects.len()];
+    while let Some(range) = pending.pop() {
+        let error = match client
+            .get_multiple(oid_objects[range.clone()].iter().copied())
+            .await
+        {
+            Ok(response) => {
+                values.extend(response.iter().map(|(oid, value)| {
+                    (oid.to_string(), convert_object_value_to_snmp_value(value))
+                }));
+                continue;
+            }
+            Err(e) => e,
+        };
+        if range.len() > 1 && is_too_big(&error) {
+            debug!(
+                session_id = %session_id,
+                oid_count = range.len(),
+                error = %error,
+                "Response too big for one message, splitting the request"
+            );
+            let middle = range.start + range.len() / 2;
+            pending.push(middle..range.end);
+            pending.push(range.start..middle);
+            continue;
+        }
+        debug!(
+            session_id = %session_id,
+            oid_count = range.len(),
+            error = %error,
+            "Multi-variable GET failed, retrying OIDs individually"
+        );
+        for (oid, oid_str) in oid_objects[range.clone()].iter().zip(&oids[range]) {
+            match client.get(*oid).await {
+                Ok(value) => {
+                    values.push((oid.to_string(), convert_object_value_to_snmp_value(&value)));
+                }
+                Err(e) => {
+                    values.push(((*oid_str).to_string(), SnmpValue::NoSuchObject));
+                    debug!(
+                        session_id = %session_id,
+                        oid = *oid_str,
+                        error = %e,
+                        "Failed to get OID value"
+                    );
+                }
+            }
+        }
+    }
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_requests_round_robins_chunks_across_slots() {
+        let plan = plan_requests(25, 10, 4);
+
+        assert_eq!(plan, vec![vec![0..10], vec![10..20], vec![20..25]]);
+    }
+
+    #[test]
+    fn test_plan_requests_queues_extra_chunks_per_slot() {
+        let plan = plan_requests(50, 10, 2);
+
+        assert_eq!(
+            plan,
+            vec![vec![0..10, 20..30, 40..50], vec![10..20, 30..40]]
+        );
+    }
+
+    #[test]
+    fn test_plan_requests_without_pipelining_is_sequential() {
+        let plan = plan_requests(5, 2, 1);
+
+        assert_eq!(plan, vec![vec![0..2, 2..4, 4..5]]);
+    }
+
+    #[test]
+    fn test_vars_per_message_fits_estimated_response() {
+        let oids = ["1.3.6.1.2.1.2.2.1.10.1", "1.3.6.1.2.1.2.2.1.10.1000"];
+
+        // 1408 bytes for variables of up to 81 bytes each
+        assert_eq!(vars_per_message(&oids, 1472), 17);
+        assert_eq!(vars_per_message(&oids, 65_507), 807);
+        // Raised to the size every agent accepts
+        assert_eq!(vars_per_message(&oids, 100), 5);
+        assert_eq!(vars_per_message(&[], 1472), 1408);
+    }
+
+    #[test]
+    fn test_is_too_big_matches_empty_responses_only() {
+        use csnmp::message::{BindingValue, VariableBinding};
+
+        assert!(is_too_big(&SnmpClientError::BindingCount {
+            expected: 2,
+            obtained: Vec::new(),
+        }));
+        let echoed = VariableBinding {
+            name: "1.3.6.1.2.1.1.1.0".parse().unwrap(),
+            value: BindingValue::Unspecified,
+        };
+        assert!(!is_too_big(&SnmpClientError::BindingCount {
+            expected: 2,
+            obtained: vec![echoed.clone()],
+        }));
+        assert!(!is_too_big(&SnmpClientError::FailedBinding {
+            binding: echoed
+        }));
+        assert!(!is_too_big(&SnmpClientError::TimedOut));
+    }
+
+    #[test]
+    fn test_plan_requests_handles_empty_and_zero_limits() {
+        assert_eq!(plan_requests(0, 10, 4), vec![Vec::<Range<usize>>::new()]);
+        assert_eq!(plan_requests(2, 0, 4), vec![vec![0..1], vec![1..2]]);
+    }
+}
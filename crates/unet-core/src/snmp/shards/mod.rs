@@ -0,0 +1,287 @@
+//! Polling shards claimed by collectors through datastore leases
+//!
+//! With sharding enabled, nodes are split into a fixed number of shards by
+//! node ID and each collector, a server sharing the database with the others,
+//! polls only the nodes of the shards it holds a lease on. Collectors renew
+//! their leases every round; a lease that is not renewed within its time to
+//! live expires and its shard is claimed by another collector, so the nodes
+//! of a collector that stops are polled again within one lease lifetime.
+//!
+//! Each collector aims for an equal share of the shards among the collectors
+//! seen within the lease lifetime and releases shards above its share, so a
+//! collector that joins picks them up on its next round. Leases are claimed
+//! and renewed with [`DataStore::acquire_shard_lease`], which takes a shard
+//! only if it is free, expired, or already the collector's, in one
+//! conditional write, so no two collectors hold a shard at once.
+
+use crate::config::ShardingConfig;
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult};
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use uuid::Uuid;
+
+/// Settings namespace holding the last round of each collector keyed by ID
+const COLLECTORS_NAMESPACE: &str = "poller_collectors";
+
+/// Shard a node belongs to when nodes are split into `shards` shards
+#[must_use]
+pub fn shard_of(node_id: &Uuid, shards: u32) -> u32 {
+    // Node IDs are random, so they spread evenly over the shards
+    u32::try_from(node_id.as_u128() % u128::from(shards.max(1))).unwrap_or_default()
+}
+
+/// Nodes a collector polls
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ShardSet {
+    /// Number of shards nodes are split into; `0` when not sharded
+    shards: u32,
+    /// Shards this collector holds a lease on
+    owned: BTreeSet<u32>,
+}
+
+impl ShardSet {
+    /// Every node, as when sharding is disabled
+    #[must_use]
+    pub fn unsharded() -> Self {
+        Self::default()
+    }
+
+    /// The nodes of the `owned` shards out of `shards`
+    #[must_use]
+    pub fn new(shards: u32, owned: impl IntoIterator<Item = u32>) -> Self {
+        Self {
+            shards: shards.max(1),
+            owned: owned.into_iter().collect(),
+        }
+    }
+
+    /// Whether this collector polls the node
+    #[must_use]
+    pub fn contains(&self, node_id: &Uuid) -> bool {
+        self.shards == 0 || self.owned.contains(&shard_of(node_id, self.shards))
+    }
+
+    /// Shards this collector holds a lease on; empty when not sharded
+    #[must_use]
+    pub const fn owned(&self) -> &BTreeSet<u32> {
+        &self.owned
+    }
+}
+
+/// A collector's claim on a shard
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShardLease {
+    /// Shard number
+    pub shard: u32,
+    /// Collector holding the lease
+    pub collector: String,
+    /// When the collector first claimed the shard
+    pub acquired_at: DateTime<Utc>,
+    /// When the lease lapses unless renewed
+    pub expires_at: DateTime<Utc>,
+}
+
+impl ShardLease {
+    /// Whether the lease is still held at `now`
+    #[must_use]
+    pub fn is_held(&self, now: DateTime<Utc>) -> bool {
+        now < self.expires_at
+    }
+}
+
+/// A collector and its most recent round
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Collector {
+    /// Collector ID
+    pub id: String,
+    /// When the collector last claimed or renewed its shards
+    pub last_seen: DateTime<Utc>,
+}
+
+/// Collectors and shard leases, for status displays
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShardStatus {
+    /// Collectors, with when each last claimed or renewed its shards
+    pub collectors: Vec<Collector>,
+    /// Leases still held, by shard number
+    pub leases: Vec<ShardLease>,
+}
+
+fn parse<T: serde::de::DeserializeOwned>(key: &str, value: Value) -> DataStoreResult<T> {
+    serde_json::from_value(value).map_err(|e| DataStoreError::InternalError {
+        message: format!("stored polling shard data {key}: {e}"),
+    })
+}
+
+fn to_value<T: Serialize>(key: &str, value: &T) -> DataStoreResult<Value> {
+    serde_json::to_value(value).map_err(|e| DataStoreError::InternalError {
+        message: format!("polling shard data {key}: {e}"),
+    })
+}
+
+/// Longest lease lifetime honoured, one day
+const MAX_LEASE_TTL_SECONDS: u64 = 86_400;
+
+fn lease_ttl(config: &ShardingConfig) -> TimeDelta {
+    let seconds = config.lease_ttl.clamp(1, MAX_LEASE_TTL_SECONDS);
+    TimeDelta::seconds(i64::try_from(seconds).unwrap_or_default())
+}
+
+async fn delete_ignoring_missing(
+    datastore: &dyn DataStore,
+    namespace: &str,
+    key: &str,
+) -> DataStoreResult<()> {
+    // Another collector may have removed it first
+    match datastore.delete_setting(namespace, key).await {
+        Ok(()) | Err(DataStoreError::NotFound { .. }) => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+async fn live_collectors(
+    datastore: &dyn DataStore,
+    since: DateTime<Utc>,
+) -> DataStoreResult<Vec<Collector>> {
+    let mut live = Vec::new();
+    for (key, value) in datastore.list_settings(COLLECTORS_NAMESPACE).await? {
+        let collector: Collector = parse(&key, value)?;
+        if collector.last_seen >= since {
+            live.push(collector);
+        } else {
+            delete_ignoring_missing(datastore, COLLECTORS_NAMESPACE, &key).await?;
+        }
+    }
+    live.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(live)
+}
+
+async fn held_leases(
+    datastore: &dyn DataStore,
+    now: DateTime<Utc>,
+) -> DataStoreResult<BTreeMap<u32, ShardLease>> {
+    Ok(datastore
+        .list_shard_leases()
+        .await?
+        .into_iter()
+        .filter(|lease| lease.is_held(now))
+        .map(|lease| (lease.shard, lease))
+        .collect())
+}
+
+/// Runs one round for `collector`: renews its leases, claims free shards up
+/// to its share, and releases shards above it
+///
+/// Returns the shards the collector holds after the round.
+///
+/// # Errors
+/// Returns an error if collectors or leases cannot be read or written.
+pub async fn claim_shards(
+    datastore: &dyn DataStore,
+    collector: &str,
+    config: &ShardingConfig,
+    now: DateTime<Utc>,
+) -> DataStoreResult<ShardSet> {
+    let shards = config.shards.max(1);
+    let ttl = lease_ttl(config);
+    let me = Collector {
+        id: collector.to_string(),
+        last_seen: now,
+    };
+    datastore
+        .put_setting(COLLECTORS_NAMESPACE, collector, &to_value(collector, &me)?)
+        .await?;
+
+    let live = live_collectors(datastore, now - ttl).await?;
+    let live_count = u32::try_from(live.len().max(1)).unwrap_or(u32::MAX);
+    let share = usize::try_from(shards.div_ceil(live_count)).unwrap_or(usize::MAX);
+    let leases = held_leases(datastore, now).await?;
+
+    let mut mine: Vec<ShardLease> = leases
+        .values()
+        .filter(|lease| lease.shard < shards && lease.collector == collector)
+        .cloned()
+        .collect();
+    for surplus in mine.split_off(share.min(mine.len())) {
+        datastore
+            .release_shard_lease(surplus.shard, collector)
+            .await?;
+    }
+
+    // A renewal fails if the lease expired and another collector took it
+    let mut owned = BTreeSet::new();
+    for lease in mine {
+        let renewed = ShardLease {
+            expires_at: now + ttl,
+            ..lease
+        };
+        if datastore.acquire_shard_lease(&renewed, now).await? {
+            owned.insert(renewed.shard);
+        }
+    }
+
+    let free: Vec<u32> = (0..shards)
+        .filter(|shard| !leases.contains_key(shard))
+        .collect();
+    for shard in free {
+        if owned.len() >= share {
+            break;
+        }
+        let lease = ShardLease {
+            shard,
+            collector: collector.to_string(),
+            acquired_at: now,
+            expires_at: now + ttl,
+        };
+        if datastore.acquire_shard_lease(&lease, now).await? {
+            owned.insert(shard);
+        }
+    }
+
+    Ok(ShardSet::new(shards, owned))
+}
+
+/// Releases every lease `collector` holds, e.g. when it shuts down, so other
+/// collectors take its shards without waiting for the leases to expire
+///
+/// # Errors
+/// Returns an error if leases cannot be read or removed.
+pub async fn release_shards(datastore: &dyn DataStore, collector: &str) -> DataStoreResult<()> {
+    for lease in datastore.list_shard_leases().await? {
+        if lease.collector == collector {
+            datastore
+                .release_shard_lease(lease.shard, collector)
+                .await?;
+        }
+    }
+    delete_ignoring_missing(datastore, COLLECTORS_NAMESPACE, collector).await
+}
+
+/// Lists collectors by their last round and the leases held at `now`
+///
+/// Collectors that stopped are listed until a running collector's next round
+/// notices they have not been seen for a lease lifetime.
+///
+/// # Errors
+/// Returns an error if collectors or leases cannot be read.
+pub async fn shard_status(
+    datastore: &dyn DataStore,
+    now: DateTime<Utc>,
+) -> DataStoreResult<ShardStatus> {
+    let mut collectors = datastore
+        .list_settings(COLLECTORS_NAMESPACE)
+        .await?
+        .into_iter()
+        .map(|(key, value)| parse::<Collector>(&key, value))
+        .collect::<DataStoreResult<Vec<_>>>()?;
+    collectors.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(ShardStatus {
+        collectors,
+        leases: held_leases(datastore, now).await?.into_values().collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,147 @@
+use super::*;
+use crate::datastore::sqlite::SqliteStore;
+use sea_orm::{ConnectionTrait, Database, DatabaseBackend, Schema};
+
+async fn lease_store() -> SqliteStore {
+    let db = Database::connect("sqlite::memory:").await.unwrap();
+    let schema = Schema::new(DatabaseBackend::Sqlite);
+    for stmt in [
+        schema.create_table_from_entity(crate::entities::settings::Entity),
+        schema.create_table_from_entity(crate::entities::shard_leases::Entity),
+    ] {
+        db.execute(db.get_database_backend().build(&stmt))
+            .await
+            .unwrap();
+    }
+    SqliteStore::from_connection(db)
+}
+
+fn config(shards: u32) -> ShardingConfig {
+    ShardingConfig {
+        enabled: true,
+        shards,
+        lease_ttl: 60,
+        collector_id: None,
+    }
+}
+
+fn owned(set: &ShardSet) -> Vec<u32> {
+    set.owned().iter().copied().collect()
+}
+
+#[test]
+fn test_shard_set_membership() {
+    let node = Uuid::new_v4();
+    let shard = shard_of(&node, 8);
+    assert!(shard < 8);
+    assert_eq!(shard_of(&node, 0), 0);
+
+    assert!(ShardSet::unsharded().contains(&node));
+    assert!(ShardSet::new(8, [shard]).contains(&node));
+    assert!(!ShardSet::new(8, (0..8).filter(|s| *s != shard)).contains(&node));
+    assert!(!ShardSet::new(8, []).contains(&node));
+}
+
+#[tokio::test]
+async fn test_collectors_rebalance_and_fail_over() {
+    let store = lease_store().await;
+    let config = config(4);
+    let start = Utc::now();
+    let at = |seconds| start + TimeDelta::seconds(seconds);
+
+    let alone = claim_shards(&store, "a", &config, at(0)).await.unwrap();
+    assert_eq!(owned(&alone), [0, 1, 2, 3]);
+
+    // b joins while a holds every shard, so a gives up half on its next round
+    let joined = claim_shards(&store, "b", &config, at(10)).await.unwrap();
+    assert!(owned(&joined).is_empty());
+    let halved = claim_shards(&store, "a", &config, at(20)).await.unwrap();
+    assert_eq!(owned(&halved), [0, 1]);
+    let balanced = claim_shards(&store, "b", &config, at(30)).await.unwrap();
+    assert_eq!(owned(&balanced), [2, 3]);
+
+    let status = shard_status(&store, at(30)).await.unwrap();
+    assert_eq!(status.collectors.len(), 2);
+    assert_eq!(status.leases.len(), 4);
+
+    // a stops; once its leases and heartbeat lapse, b takes everything
+    let failed_over = claim_shards(&store, "b", &config, at(81)).await.unwrap();
+    assert_eq!(owned(&failed_over), [0, 1, 2, 3]);
+    let status = shard_status(&store, at(81)).await.unwrap();
+    assert_eq!(status.collectors.len(), 1);
+}
+
+#[tokio::test]
+async fn test_release_frees_shards_immediately() {
+    let store = lease_store().await;
+    let config = config(2);
+    let now = Utc::now();
+
+    claim_shards(&store, "a", &config, now).await.unwrap();
+    release_shards(&store, "a").await.unwrap();
+
+    let taken = claim_shards(&store, "b", &config, now).await.unwrap();
+    assert_eq!(owned(&taken), [0, 1]);
+}
+
+#[tokio::test]
+async fn test_lease_is_acquired_only_when_free_expired_or_own() {
+    let store = lease_store().await;
+    // Whole seconds, as leases are stored in microseconds
+    let now = DateTime::from_timestamp(1_790_000_000, 0).unwrap();
+    let lease = |collector: &str, expires_in| ShardLease {
+        shard: 0,
+        collector: collector.to_string(),
+        acquired_at: now,
+        expires_at: now + TimeDelta::seconds(expires_in),
+    };
+
+    assert!(
+        store
+            .acquire_shard_lease(&lease("a", 60), now)
+            .await
+            .unwrap()
+    );
+    assert!(
+        !store
+            .acquire_shard_lease(&lease("b", 60), now)
+            .await
+            .unwrap()
+    );
+    assert!(
+        store
+            .acquire_shard_lease(&lease("a", 120), now)
+            .await
+            .unwrap()
+    );
+
+    let later = now + TimeDelta::seconds(120);
+    assert!(
+        store
+            .acquire_shard_lease(&lease("b", 180), later)
+            .await
+            .unwrap()
+    );
+    let leases = store.list_shard_leases().await.unwrap();
+    assert_eq!(leases, [lease("b", 180)]);
+
+    // Releasing a lease held by another collector leaves it in place
+    store.release_shard_lease(0, "a").await.unwrap();
+    assert_eq!(store.list_shard_leases().await.unwrap().len(), 1);
+    store.release_shard_lease(0, "b").await.unwrap();
+    assert!(store.list_shard_leases().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_racing_collectors_never_share_a_shard() {
+    let store = lease_store().await;
+    let config = config(4);
+    let now = Utc::now();
+
+    let (a, b) = tokio::join!(
+        claim_shards(&store, "a", &config, now),
+        claim_shards(&store, "b", &config, now),
+    );
+    let (a, b) = (a.unwrap(), b.unwrap());
+    assert!(a.owned().is_disjoint(b.owned()));
+}
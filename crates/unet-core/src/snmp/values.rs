@@ -3,6 +3,10 @@
 use serde::{Deserialize, Serialize};
 use std::net::IpAddr;
 
+mod coerce;
+
+pub use coerce::SnmpUnit;
+
 /// SNMP value types
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SnmpValue {
@@ -0,0 +1,270 @@
+//! Typed coercions and unit normalization for SNMP values
+//!
+//! Devices report the same quantity with different SMI types: a speed as
+//! `Gauge32` or `Integer`, a MAC address as raw octets or as the hex string
+//! the session layer produces for non-UTF-8 octet strings. These helpers
+//! accept every reasonable encoding so consumers match on the meaning of a
+//! value rather than its wire type. [`SnmpUnit`] describes the unit a numeric
+//! OID is reported in; [`SnmpValue::scaled`] converts it to the base unit.
+
+use super::SnmpValue;
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::time::Duration;
+
+/// Unit a numeric SNMP value is reported in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnmpUnit {
+    /// Hundredths of a second, as in `TimeTicks`; normalized to seconds
+    Hundredths,
+    /// Seconds
+    Seconds,
+    /// Bits per second
+    BitsPerSecond,
+    /// Thousands of bits per second; normalized to bits per second
+    KilobitsPerSecond,
+    /// Millions of bits per second, as in ifHighSpeed; normalized to bits per second
+    MegabitsPerSecond,
+    /// Octets
+    Octets,
+    /// Plain count such as packets or errors
+    Count,
+}
+
+impl SnmpUnit {
+    /// Name of the unit values are normalized to
+    #[must_use]
+    pub const fn base(self) -> &'static str {
+        match self {
+            Self::Hundredths | Self::Seconds => "seconds",
+            Self::BitsPerSecond | Self::KilobitsPerSecond | Self::MegabitsPerSecond => {
+                "bits_per_second"
+            }
+            Self::Octets => "octets",
+            Self::Count => "count",
+        }
+    }
+
+    /// Converts a value in this unit to the base unit
+    #[must_use]
+    pub const fn normalize(self, value: f64) -> f64 {
+        match self {
+            Self::Hundredths => value / 100.0,
+            Self::KilobitsPerSecond => value * 1_000.0,
+            Self::MegabitsPerSecond => value * 1_000_000.0,
+            Self::Seconds | Self::BitsPerSecond | Self::Octets | Self::Count => value,
+        }
+    }
+}
+
+/// Decodes the `0x`-prefixed hex form of a non-UTF-8 octet string
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    let hex = text.strip_prefix("0x")?;
+    if hex.is_empty() || hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Parses a MAC address written as six hex pairs separated by `:` or `-`
+fn parse_mac(text: &str) -> Option<[u8; 6]> {
+    let parts: Vec<&str> = text.split([':', '-']).collect();
+    if parts.len() != 6 {
+        return None;
+    }
+    let mut mac = [0; 6];
+    for (byte, part) in mac.iter_mut().zip(parts) {
+        if part.is_empty() || part.len() > 2 {
+            return None;
+        }
+        *byte = u8::from_str_radix(part, 16).ok()?;
+    }
+    Some(mac)
+}
+
+impl SnmpValue {
+    /// Returns the text of a string value
+    #[must_use]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Returns an unsigned integer from any numeric type
+    ///
+    /// Negative integers yield `None`.
+    #[must_use]
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Self::Integer(value) => u64::try_from(*value).ok(),
+            Self::Counter32(value) | Self::Gauge32(value) | Self::TimeTicks(value) => {
+                Some(u64::from(*value))
+            }
+            Self::Counter64(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns a signed integer from any numeric type
+    ///
+    /// 64-bit counters above `i64::MAX` yield `None`.
+    #[must_use]
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Self::Integer(value) => Some(*value),
+            _ => self.as_u64().and_then(|value| i64::try_from(value).ok()),
+        }
+    }
+
+    /// Returns the duration of a `TimeTicks` value
+    #[must_use]
+    pub fn as_duration(&self) -> Option<Duration> {
+        match self {
+            Self::TimeTicks(ticks) => Some(Duration::from_millis(u64::from(*ticks) * 10)),
+            _ => None,
+        }
+    }
+
+    /// Returns the raw bytes of an octet string or opaque value
+    ///
+    /// Strings in the `0x`-prefixed hex form are decoded.
+    #[must_use]
+    pub fn as_octets(&self) -> Option<Vec<u8>> {
+        match self {
+            Self::Opaque(bytes) => Some(bytes.clone()),
+            Self::String(text) => {
+                Some(decode_hex(text).unwrap_or_else(|| text.as_bytes().to_vec()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns a MAC address formatted as `aa:bb:cc:dd:ee:ff`
+    ///
+    /// Accepts six raw octets, their hex form, or an already formatted address.
+    #[must_use]
+    pub fn as_mac(&self) -> Option<String> {
+        let mac = self
+            .as_str()
+            .and_then(parse_mac)
+            .or_else(|| self.as_octets()?.try_into().ok())?;
+        Some(
+            mac.iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect::<Vec<_>>()
+                .join(":"),
+        )
+    }
+
+    /// Returns an IP address from an `IpAddress` value, its text form, or
+    /// 4 or 16 raw octets
+    #[must_use]
+    pub fn as_ip(&self) -> Option<IpAddr> {
+        if let Self::IpAddress(ip) = self {
+            return Some(*ip);
+        }
+        if let Some(ip) = self.as_str().and_then(|text| text.parse().ok()) {
+            return Some(ip);
+        }
+        let octets = self.as_octets()?;
+        if let Ok(v4) = <[u8; 4]>::try_from(octets.as_slice()) {
+            Some(IpAddr::V4(Ipv4Addr::from(v4)))
+        } else {
+            <[u8; 16]>::try_from(octets.as_slice())
+                .ok()
+                .map(|v6| IpAddr::V6(Ipv6Addr::from(v6)))
+        }
+    }
+
+    /// Returns a numeric value converted from `unit` to its base unit
+    ///
+    /// For example a `TimeTicks` value with [`SnmpUnit::Hundredths`] becomes
+    /// seconds and an ifHighSpeed value with [`SnmpUnit::MegabitsPerSecond`]
+    /// becomes bits per second.
+    #[must_use]
+    pub fn scaled(&self, unit: SnmpUnit) -> Option<f64> {
+        let value = match self {
+            Self::Integer(value) => value.to_f64()?,
+            _ => self.as_u64()?.to_f64()?,
+        };
+        Some(unit.normalize(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numeric_coercions() {
+        assert_eq!(SnmpValue::Counter32(7).as_u64(), Some(7));
+        assert_eq!(SnmpValue::Counter64(u64::MAX).as_u64(), Some(u64::MAX));
+        assert_eq!(SnmpValue::Integer(-1).as_u64(), None);
+        assert_eq!(SnmpValue::Integer(-1).as_i64(), Some(-1));
+        assert_eq!(SnmpValue::Counter64(u64::MAX).as_i64(), None);
+        assert_eq!(SnmpValue::String("7".to_string()).as_u64(), None);
+    }
+
+    #[test]
+    fn test_as_duration_reads_hundredths() {
+        assert_eq!(
+            SnmpValue::TimeTicks(12_345).as_duration(),
+            Some(Duration::from_millis(123_450))
+        );
+        assert_eq!(SnmpValue::Integer(12_345).as_duration(), None);
+    }
+
+    #[test]
+    fn test_as_mac_accepts_octets_hex_and_text() {
+        let expected = Some("00:1a:2b:3c:4d:ff".to_string());
+        assert_eq!(
+            SnmpValue::Opaque(vec![0x00, 0x1a, 0x2b, 0x3c, 0x4d, 0xff]).as_mac(),
+            expected
+        );
+        assert_eq!(
+            SnmpValue::String("0x001a2b3c4dff".to_string()).as_mac(),
+            expected
+        );
+        assert_eq!(
+            SnmpValue::String("00-1A-2B-3C-4D-FF".to_string()).as_mac(),
+            expected
+        );
+        assert_eq!(SnmpValue::String(String::new()).as_mac(), None);
+        assert_eq!(SnmpValue::Opaque(vec![1, 2, 3]).as_mac(), None);
+    }
+
+    #[test]
+    fn test_as_ip_accepts_address_text_and_octets() {
+        let ip: IpAddr = "192.0.2.1".parse().unwrap();
+        assert_eq!(SnmpValue::IpAddress(ip).as_ip(), Some(ip));
+        assert_eq!(SnmpValue::String("192.0.2.1".to_string()).as_ip(), Some(ip));
+        assert_eq!(SnmpValue::Opaque(vec![192, 0, 2, 1]).as_ip(), Some(ip));
+        assert_eq!(
+            SnmpValue::Opaque(vec![
+                0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1
+            ])
+            .as_ip(),
+            Some("2001:db8::1".parse().unwrap())
+        );
+        assert_eq!(SnmpValue::Counter32(1).as_ip(), None);
+    }
+
+    #[test]
+    fn test_scaled_normalizes_to_base_unit() {
+        let seconds = SnmpValue::TimeTicks(250).scaled(SnmpUnit::Hundredths);
+        assert!((seconds.unwrap() - 2.5).abs() < f64::EPSILON);
+
+        let bps = SnmpValue::Gauge32(10_000).scaled(SnmpUnit::MegabitsPerSecond);
+        assert!((bps.unwrap() - 1e10).abs() < f64::EPSILON);
+
+        assert_eq!(SnmpUnit::KilobitsPerSecond.base(), "bits_per_second");
+        assert_eq!(SnmpValue::Null.scaled(SnmpUnit::Count), None);
+    }
+}
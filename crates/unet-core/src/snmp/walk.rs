@@ -0,0 +1,393 @@
+//! SNMP walk snapshots in `snmpwalk` format
+//!
+//! Walk results are written the way `snmpwalk -On` prints them, one varbind
+//! per line with numeric OIDs:
+//!
+//! ```text
+//! .1.3.6.1.2.1.1.1.0 = STRING: "Cisco IOS Software, C2960 Software"
+//! .1.3.6.1.2.1.1.3.0 = Timeticks: (8640000) 1 day, 0:00:00.00
+//! .1.3.6.1.2.1.2.2.1.8.1 = INTEGER: 1
+//! ```
+//!
+//! Files captured with `snmpwalk` itself can be read back as well, as long as
+//! their OIDs are numeric (`-On`, or the `iso.3.6...` form printed without
+//! MIBs loaded). Enumerated integers such as `up(1)`, `Hex-STRING` values, and
+//! strings spanning several lines are understood. Octet strings that are not
+//! valid UTF-8 become `0x`-prefixed hex strings, as they do when polled.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::net::IpAddr;
+
+use thiserror::Error;
+
+use super::SnmpValue;
+
+/// A line of a walk file that cannot be read
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("line {line}: {message}")]
+pub struct WalkParseError {
+    /// 1-based line number
+    pub line: usize,
+    /// What is wrong with the line
+    pub message: String,
+}
+
+const NO_SUCH_OBJECT: &str = "No Such Object available on this agent at this OID";
+const NO_SUCH_INSTANCE: &str = "No Such Instance currently exists at this OID";
+const END_OF_MIB_VIEW: &str =
+    "No more variables left in this MIB View (It is past the end of the MIB tree)";
+
+/// Formats walk results as `snmpwalk -On` output, ordered by OID
+#[must_use]
+pub fn format_walk(values: &HashMap<String, SnmpValue>) -> String {
+    let mut entries: Vec<(&String, &SnmpValue)> = values.iter().collect();
+    entries.sort_by_cached_key(|(oid, _)| oid_sort_key(oid));
+
+    let mut output = String::new();
+    for (oid, value) in entries {
+        let _ = writeln!(
+            output,
+            ".{} = {}",
+            oid.trim_start_matches('.'),
+            format_value(value)
+        );
+    }
+    output
+}
+
+/// Parses `snmpwalk` output into walk results keyed by OID without a leading dot
+///
+/// Blank lines are skipped. When an OID appears twice, the later value wins.
+///
+/// # Errors
+/// Returns an error naming the line of the first varbind that cannot be read,
+/// such as one with a symbolic OID or an unknown value type.
+pub fn parse_walk(input: &str) -> Result<HashMap<String, SnmpValue>, WalkParseError> {
+    let mut values = HashMap::new();
+    let mut lines = input.lines().enumerate();
+    while let Some((index, line)) = lines.next() {
+        let line_number = index + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let error = |message: String| WalkParseError {
+            line: line_number,
+            message,
+        };
+
+        let (oid, value) = line
+            .split_once(" = ")
+            .ok_or_else(|| error(format!("expected '<oid> = <value>', found {line:?}")))?;
+        let oid = numeric_oid(oid.trim()).ok_or_else(|| {
+            error(format!(
+                "OID {:?} is not numeric; capture the walk with snmpwalk -On",
+                oid.trim()
+            ))
+        })?;
+
+        let mut value = value.to_string();
+        // A quoted string continues on the following lines until it is closed
+        if value.starts_with("STRING: \"") {
+            while !is_closed_string(&value["STRING: ".len()..]) {
+                let (_, next) = lines
+                    .next()
+                    .ok_or_else(|| error("unterminated string".to_string()))?;
+                value.push('\n');
+                value.push_str(next);
+            }
+        }
+
+        values.insert(oid, parse_value(&value).map_err(error)?);
+    }
+    Ok(values)
+}
+
+fn format_value(value: &SnmpValue) -> String {
+    match value {
+        SnmpValue::Integer(i) => format!("INTEGER: {i}"),
+        SnmpValue::String(s) => format!("STRING: {}", quote(s)),
+        SnmpValue::Oid(oid) => match numeric_oid(oid) {
+            Some(oid) => format!("OID: .{oid}"),
+            None => format!("OID: {oid}"),
+        },
+        SnmpValue::IpAddress(ip) => format!("IpAddress: {ip}"),
+        SnmpValue::Counter32(c) => format!("Counter32: {c}"),
+        SnmpValue::Counter64(c) => format!("Counter64: {c}"),
+        SnmpValue::Gauge32(g) => format!("Gauge32: {g}"),
+        SnmpValue::TimeTicks(t) => format!("Timeticks: ({t}) {}", format_ticks(*t)),
+        SnmpValue::Opaque(data) => format!("Opaque: {}", hex(data)),
+        SnmpValue::Null => "NULL".to_string(),
+        SnmpValue::NoSuchObject => NO_SUCH_OBJECT.to_string(),
+        SnmpValue::NoSuchInstance => NO_SUCH_INSTANCE.to_string(),
+        SnmpValue::EndOfMibView => END_OF_MIB_VIEW.to_string(),
+    }
+}
+
+fn parse_value(value: &str) -> Result<SnmpValue, String> {
+    match value.trim_end() {
+        "NULL" => return Ok(SnmpValue::Null),
+        "\"\"" => return Ok(SnmpValue::String(String::new())),
+        NO_SUCH_OBJECT => return Ok(SnmpValue::NoSuchObject),
+        NO_SUCH_INSTANCE => return Ok(SnmpValue::NoSuchInstance),
+        END_OF_MIB_VIEW => return Ok(SnmpValue::EndOfMibView),
+        _ => {}
+    }
+    let (kind, data) = value
+        .split_once(':')
+        .ok_or_else(|| format!("expected '<type>: <value>', found {value:?}"))?;
+    let data = data.strip_prefix(' ').unwrap_or(data);
+    let invalid = |e: &dyn std::fmt::Display| format!("invalid {kind} value {data:?}: {e}");
+
+    let parsed = match kind {
+        "INTEGER" => SnmpValue::Integer(enum_number(data).parse().map_err(|e| invalid(&e))?),
+        "STRING" => SnmpValue::String(unquote(data)),
+        "Hex-STRING" => octet_string(parse_hex(data).map_err(|e| invalid(&e))?),
+        "OID" => {
+            SnmpValue::Oid(numeric_oid(data.trim()).unwrap_or_else(|| data.trim().to_string()))
+        }
+        "IpAddress" => {
+            SnmpValue::IpAddress(data.trim().parse::<IpAddr>().map_err(|e| invalid(&e))?)
+        }
+        "Counter32" => SnmpValue::Counter32(first_word(data).parse().map_err(|e| invalid(&e))?),
+        "Counter64" => SnmpValue::Counter64(first_word(data).parse().map_err(|e| invalid(&e))?),
+        "Gauge32" | "Unsigned32" => {
+            SnmpValue::Gauge32(first_word(data).parse().map_err(|e| invalid(&e))?)
+        }
+        "Timeticks" => {
+            let ticks = data
+                .strip_prefix('(')
+                .and_then(|rest| rest.split_once(')'))
+                .map_or_else(|| first_word(data), |(ticks, _)| ticks);
+            SnmpValue::TimeTicks(ticks.parse().map_err(|e| invalid(&e))?)
+        }
+        "Opaque" => SnmpValue::Opaque(parse_hex(data).map_err(|e| invalid(&e))?),
+        _ => return Err(format!("unsupported value type {kind:?}")),
+    };
+    Ok(parsed)
+}
+
+/// The OID without a leading dot, if it is numeric; `iso` stands for `1`
+fn numeric_oid(oid: &str) -> Option<String> {
+    let oid = oid.strip_prefix('.').unwrap_or(oid);
+    let oid = oid
+        .strip_prefix("iso")
+        .map_or_else(|| oid.to_string(), |rest| format!("1{rest}"));
+    let numeric = !oid.is_empty()
+        && oid
+            .split('.')
+            .all(|arc| !arc.is_empty() && arc.bytes().all(|b| b.is_ascii_digit()));
+    numeric.then_some(oid)
+}
+
+/// Orders OIDs by their numeric arcs, so `.2` sorts before `.10`
+fn oid_sort_key(oid: &str) -> Vec<u64> {
+    oid.trim_start_matches('.')
+        .split('.')
+        .map(|arc| arc.parse().unwrap_or(u64::MAX))
+        .collect()
+}
+
+/// Net-SNMP's `d days, h:mm:ss.cc` rendering of hundredths of a second
+fn format_ticks(ticks: u32) -> String {
+    let days = ticks / 8_640_000;
+    let hours = ticks / 360_000 % 24;
+    let minutes = ticks / 6_000 % 60;
+    let seconds = ticks / 100 % 60;
+    let hundredths = ticks % 100;
+    let time = format!("{hours}:{minutes:02}:{seconds:02}.{hundredths:02}");
+    match days {
+        0 => time,
+        1 => format!("1 day, {time}"),
+        _ => format!("{days} days, {time}"),
+    }
+}
+
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn unquote(data: &str) -> String {
+    let Some(inner) = data
+        .trim_end()
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+    else {
+        // Unquoted strings, e.g. from snmpwalk -Oq, are taken as they are
+        return data.to_string();
+    };
+    let mut unquoted = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some(escaped) = chars.next() {
+                unquoted.push(escaped);
+                continue;
+            }
+        }
+        unquoted.push(c);
+    }
+    unquoted
+}
+
+/// Returns true if `data`, starting with a quote, ends with an unescaped quote
+fn is_closed_string(data: &str) -> bool {
+    let data = data.trim_end();
+    if data.len() < 2 || !data.ends_with('"') {
+        return false;
+    }
+    let backslashes = data[..data.len() - 1]
+        .bytes()
+        .rev()
+        .take_while(|&b| b == b'\\')
+        .count();
+    backslashes % 2 == 0
+}
+
+/// The number of an enumerated integer such as `up(1)`
+fn enum_number(data: &str) -> &str {
+    let data = first_word(data);
+    data.split_once('(')
+        .and_then(|(_, rest)| rest.strip_suffix(')'))
+        .unwrap_or(data)
+}
+
+fn first_word(data: &str) -> &str {
+    data.split_whitespace().next().unwrap_or_default()
+}
+
+fn hex(data: &[u8]) -> String {
+    data.iter()
+        .map(|b| format!("{b:02X}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn parse_hex(data: &str) -> Result<Vec<u8>, std::num::ParseIntError> {
+    data.split_whitespace()
+        .map(|byte| u8::from_str_radix(byte, 16))
+        .collect()
+}
+
+/// An octet string as the poller reports it
+fn octet_string(bytes: Vec<u8>) -> SnmpValue {
+    match String::from_utf8(bytes) {
+        Ok(s) => SnmpValue::String(s),
+        Err(e) => {
+            let hex = e.as_bytes().iter().fold(String::new(), |mut acc, b| {
+                let _ = write!(acc, "{b:02x}");
+                acc
+            });
+            SnmpValue::String(format!("0x{hex}"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_walk_orders_oids_numerically() {
+        let values = HashMap::from([
+            ("1.3.6.1.2.1.2.2.1.8.10".to_string(), SnmpValue::Integer(2)),
+            ("1.3.6.1.2.1.2.2.1.8.2".to_string(), SnmpValue::Integer(1)),
+            (
+                "1.3.6.1.2.1.1.1.0".to_string(),
+                SnmpValue::String("Edge \"router\"".to_string()),
+            ),
+            (
+                "1.3.6.1.2.1.1.3.0".to_string(),
+                SnmpValue::TimeTicks(8_652_345),
+            ),
+        ]);
+
+        assert_eq!(
+            format_walk(&values),
+            ".1.3.6.1.2.1.1.1.0 = STRING: \"Edge \\\"router\\\"\"\n\
+             .1.3.6.1.2.1.1.3.0 = Timeticks: (8652345) 1 day, 0:02:03.45\n\
+             .1.3.6.1.2.1.2.2.1.8.2 = INTEGER: 1\n\
+             .1.3.6.1.2.1.2.2.1.8.10 = INTEGER: 2\n"
+        );
+    }
+
+    #[test]
+    fn test_walk_round_trips_every_value_type() {
+        let values = HashMap::from([
+            ("1.1".to_string(), SnmpValue::Integer(-5)),
+            (
+                "1.2".to_string(),
+                SnmpValue::String("line 1\nline \\2".to_string()),
+            ),
+            (
+                "1.3".to_string(),
+                SnmpValue::Oid("1.3.6.1.4.1.9.1.1208".to_string()),
+            ),
+            (
+                "1.4".to_string(),
+                SnmpValue::IpAddress("10.0.0.1".parse().unwrap()),
+            ),
+            ("1.5".to_string(), SnmpValue::Counter32(42)),
+            ("1.6".to_string(), SnmpValue::Counter64(u64::MAX)),
+            ("1.7".to_string(), SnmpValue::Gauge32(1_000_000_000)),
+            ("1.8".to_string(), SnmpValue::TimeTicks(12_345)),
+            ("1.9".to_string(), SnmpValue::Opaque(vec![0x9f, 0x78, 0x04])),
+            ("1.10".to_string(), SnmpValue::Null),
+            ("1.11".to_string(), SnmpValue::String(String::new())),
+            ("1.12".to_string(), SnmpValue::NoSuchObject),
+            ("1.13".to_string(), SnmpValue::NoSuchInstance),
+            ("1.14".to_string(), SnmpValue::EndOfMibView),
+        ]);
+
+        assert_eq!(parse_walk(&format_walk(&values)).unwrap(), values);
+    }
+
+    #[test]
+    fn test_parse_snmpwalk_output() {
+        let walk = "\
+iso.3.6.1.2.1.1.1.0 = STRING: \"Juniper Networks, Inc. mx204
+kernel JUNOS 21.4R3\"
+.1.3.6.1.2.1.1.2.0 = OID: .1.3.6.1.4.1.2636.1.1.1.2.144
+
+.1.3.6.1.2.1.2.2.1.6.1 = Hex-STRING: 00 1B 54 FF A8 01
+.1.3.6.1.2.1.2.2.1.8.1 = INTEGER: up(1)
+.1.3.6.1.2.1.31.1.1.1.18.1 = \"\"
+";
+
+        let values = parse_walk(walk).unwrap();
+
+        assert_eq!(
+            values["1.3.6.1.2.1.1.1.0"],
+            SnmpValue::String("Juniper Networks, Inc. mx204\nkernel JUNOS 21.4R3".to_string())
+        );
+        assert_eq!(
+            values["1.3.6.1.2.1.1.2.0"],
+            SnmpValue::Oid("1.3.6.1.4.1.2636.1.1.1.2.144".to_string())
+        );
+        assert_eq!(
+            values["1.3.6.1.2.1.2.2.1.6.1"],
+            SnmpValue::String("0x001b54ffa801".to_string())
+        );
+        assert_eq!(values["1.3.6.1.2.1.2.2.1.8.1"], SnmpValue::Integer(1));
+        assert_eq!(
+            values["1.3.6.1.2.1.31.1.1.1.18.1"],
+            SnmpValue::String(String::new())
+        );
+    }
+
+    #[test]
+    fn test_parse_walk_reports_line_of_bad_varbind() {
+        let symbolic = ".1.3.6.1.2.1.1.3.0 = Timeticks: (1) 0:00:00.01\n\
+                        SNMPv2-MIB::sysName.0 = STRING: r1\n";
+        let error = parse_walk(symbolic).unwrap_err();
+        assert_eq!(error.line, 2);
+        assert!(error.message.contains("snmpwalk -On"));
+
+        let error = parse_walk(".1.3 = BITS: 80 0\n").unwrap_err();
+        assert_eq!(error.line, 1);
+        assert!(error.message.contains("unsupported value type"));
+
+        let error = parse_walk(".1.3 = STRING: \"open\n").unwrap_err();
+        assert!(error.message.contains("unterminated"));
+    }
+}
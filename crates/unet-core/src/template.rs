@@ -1,21 +1,256 @@
-//! Template engine
+//! Fleet-wide template rendering
+//!
+//! Renders golden configuration templates (see [`crate::golden`]), or a
+//! candidate template, for many nodes at once and compares each result with
+//! the node's last recorded render. This previews which nodes a template
+//! change would touch, and by how many lines, before it goes out. Recorded
+//! renders are kept through the `DataStore` settings API keyed by node ID and
+//! are only replaced when a render is explicitly recorded. Templates see each
+//! node's layered variables (see [`vars`]).
 
-/// Placeholder for template engine - will be implemented in Milestone 4
-pub mod placeholder {
-    /// Placeholder function
-    pub const fn placeholder() {
-        // Implementation pending
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult};
+use crate::golden::{GoldenConfig, GoldenScope, GoldenSource, resolve_golden};
+use crate::models::Node;
+use chrono::{DateTime, Utc};
+use config_slicer::diff::{diff_stats, unified_diff};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use vars::TemplateVars;
+
+pub mod fixtures;
+pub mod link;
+pub mod vars;
+
+/// Settings namespace holding the last recorded render keyed by node ID
+const RENDERS_NAMESPACE: &str = "template_renders";
+
+/// A render kept as the baseline for later comparisons
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedRender {
+    /// When the render was recorded
+    pub rendered_at: DateTime<Utc>,
+    /// Rendered configuration
+    pub config: String,
+}
+
+/// How a node's render compares with its recorded render
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RenderStatus {
+    /// Same as the recorded render
+    Unchanged,
+    /// Differs from the recorded render
+    Changed,
+    /// No render was recorded before
+    New,
+    /// No golden configuration applies to the node
+    Skipped,
+    /// The template failed to render
+    Failed,
+}
+
+/// Render result for one node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeRender {
+    /// Rendered node
+    pub node_id: Uuid,
+    /// Node name
+    pub node: String,
+    /// Comparison with the recorded render
+    pub status: RenderStatus,
+    /// Lines only in the new render
+    pub lines_added: usize,
+    /// Lines only in the recorded render
+    pub lines_removed: usize,
+    /// Unified diff from the recorded render, when requested and not empty
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
+    /// Render error
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Rendered configuration
+    #[serde(skip)]
+    pub config: Option<String>,
+}
+
+/// Counts of nodes by status and of changed lines across them
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RenderSummary {
+    /// Nodes considered
+    pub nodes: usize,
+    /// Nodes whose render changed
+    pub changed: usize,
+    /// Nodes whose render did not change
+    pub unchanged: usize,
+    /// Nodes rendered for the first time
+    pub new: usize,
+    /// Nodes without a golden configuration
+    pub skipped: usize,
+    /// Nodes whose template failed to render
+    pub failed: usize,
+    /// Lines added across all nodes
+    pub lines_added: usize,
+    /// Lines removed across all nodes
+    pub lines_removed: usize,
+}
+
+/// Renders of a set of nodes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FleetRender {
+    /// Totals over all nodes
+    pub summary: RenderSummary,
+    /// Per-node results, in the order the nodes were given
+    pub nodes: Vec<NodeRender>,
+}
+
+/// How to render a set of nodes
+#[derive(Debug, Clone, Default)]
+pub struct RenderOptions {
+    /// Template rendered for every node instead of each node's golden configuration
+    pub template: Option<GoldenSource>,
+    /// Include unified diffs against the recorded renders
+    pub diff: bool,
+    /// Record successful renders as the new baseline
+    pub record: bool,
+}
+
+fn parse_recorded(key: &str, value: serde_json::Value) -> DataStoreResult<RecordedRender> {
+    serde_json::from_value(value).map_err(|e| DataStoreError::InternalError {
+        message: format!("stored template render {key}: {e}"),
+    })
+}
+
+/// Returns the last recorded render of a node, if any
+///
+/// Datastores without settings support have no recorded renders.
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or the render is malformed.
+pub async fn recorded_render(
+    datastore: &dyn DataStore,
+    node_id: Uuid,
+) -> DataStoreResult<Option<RecordedRender>> {
+    let key = node_id.to_string();
+    match datastore.get_setting(RENDERS_NAMESPACE, &key).await {
+        Ok(Some(value)) => parse_recorded(&key, value).map(Some),
+        Ok(None) | Err(DataStoreError::UnsupportedOperation { .. }) => Ok(None),
+        Err(e) => Err(e),
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_placeholder_function() {
-        // Call the placeholder function to ensure it compiles and runs
-        placeholder::placeholder();
-        // Function should complete without panicking
+/// Records a render as the node's baseline
+///
+/// # Errors
+/// Returns an error if the datastore write fails.
+pub async fn record_render(
+    datastore: &dyn DataStore,
+    node_id: Uuid,
+    config: &str,
+) -> DataStoreResult<()> {
+    let key = node_id.to_string();
+    let render = RecordedRender {
+        rendered_at: Utc::now(),
+        config: config.to_string(),
+    };
+    let value = serde_json::to_value(&render).map_err(|e| DataStoreError::InternalError {
+        message: format!("template render {key}: {e}"),
+    })?;
+    datastore.put_setting(RENDERS_NAMESPACE, &key, &value).await
+}
+
+/// Renders the template for one node and compares it with the recorded render
+async fn render_node(
+    datastore: &dyn DataStore,
+    node: &Node,
+    vars: &TemplateVars,
+    options: &RenderOptions,
+) -> DataStoreResult<NodeRender> {
+    let golden = match &options.template {
+        Some(source) => Some(GoldenConfig {
+            scope: GoldenScope::Node,
+            target: node.id.to_string(),
+            source: source.clone(),
+        }),
+        None => resolve_golden(datastore, node).await?,
+    };
+    let mut result = NodeRender {
+        node_id: node.id,
+        node: node.name.clone(),
+        status: RenderStatus::Skipped,
+        lines_added: 0,
+        lines_removed: 0,
+        diff: None,
+        error: None,
+        config: None,
+    };
+    let Some(golden) = golden else {
+        return Ok(result);
+    };
+    let variables = vars.resolve(datastore, node).await?.variables;
+    let config = match golden.render_with(node, &variables) {
+        Ok(config) => config,
+        Err(e) => {
+            result.status = RenderStatus::Failed;
+            result.error = Some(e.to_string());
+            return Ok(result);
+        }
+    };
+
+    let recorded = recorded_render(datastore, node.id).await?;
+    let previous = recorded.as_ref().map_or("", |recorded| &recorded.config);
+    let stats = diff_stats(previous, &config);
+    result.status = match recorded {
+        None => RenderStatus::New,
+        Some(_) if stats.is_empty() => RenderStatus::Unchanged,
+        Some(_) => RenderStatus::Changed,
+    };
+    result.lines_added = stats.added;
+    result.lines_removed = stats.removed;
+    if options.diff && !stats.is_empty() {
+        result.diff = Some(unified_diff(previous, &config, "recorded", &node.name));
     }
+    if options.record {
+        record_render(datastore, node.id, &config).await?;
+    }
+    result.config = Some(config);
+    Ok(result)
+}
+
+/// Renders templates for `nodes` and compares each with its recorded render
+///
+/// Nodes without a golden configuration are skipped and render failures are
+/// reported per node rather than aborting the run.
+///
+/// # Errors
+/// Returns an error if golden configurations, layered variables, or recorded
+/// renders cannot be read, or recording a render fails.
+pub async fn render_fleet(
+    datastore: &dyn DataStore,
+    nodes: &[Node],
+    options: &RenderOptions,
+) -> DataStoreResult<FleetRender> {
+    let mut summary = RenderSummary::default();
+    let mut renders = Vec::with_capacity(nodes.len());
+    let vars = TemplateVars::load(datastore).await?;
+    for node in nodes {
+        let render = render_node(datastore, node, &vars, options).await?;
+        summary.nodes += 1;
+        match render.status {
+            RenderStatus::Unchanged => summary.unchanged += 1,
+            RenderStatus::Changed => summary.changed += 1,
+            RenderStatus::New => summary.new += 1,
+            RenderStatus::Skipped => summary.skipped += 1,
+            RenderStatus::Failed => summary.failed += 1,
+        }
+        summary.lines_added += render.lines_added;
+        summary.lines_removed += render.lines_removed;
+        renders.push(render);
+    }
+    Ok(FleetRender {
+        summary,
+        nodes: renders,
+    })
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,309 @@
+//! Template tests against checked-in fixtures
+//!
+//! Each `<name>.j2` template in a directory can have fixture cases under
+//! `tests/<name>/`: a `<case>.json` object of template variables and the
+//! `<case>.golden` output it is expected to render. Running the cases after a
+//! template refactor shows, with a unified diff per case, every output that
+//! changed; accepting the changes rewrites the golden files.
+//!
+//! ```text
+//! templates/
+//!   router.j2
+//!   tests/router/edge-1.json
+//!   tests/router/edge-1.golden
+//! ```
+
+use crate::error::{Error, Result};
+use config_slicer::diff::unified_diff;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+/// Extension of template files
+pub const TEMPLATE_EXTENSION: &str = "j2";
+/// Directory, under the template directory, holding fixture cases
+pub const FIXTURES_DIR: &str = "tests";
+
+/// Outcome of one fixture case
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FixtureStatus {
+    /// Rendered output matches the golden file
+    Passed,
+    /// Rendered output differs from the golden file
+    Failed,
+    /// The case has no golden file
+    Missing,
+    /// The golden file was written from the rendered output
+    Updated,
+    /// The variables could not be read or the template failed to render
+    Error,
+}
+
+/// Result of one fixture case
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FixtureResult {
+    /// Template name, the file name without `.j2`
+    pub template: String,
+    /// Case name, the fixture file name without `.json`
+    pub case: String,
+    /// Outcome
+    pub status: FixtureStatus,
+    /// Unified diff from the golden file to the rendered output
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
+    /// Why the case could not be run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Counts of cases by outcome
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FixtureSummary {
+    /// Cases run
+    pub cases: usize,
+    /// Cases matching their golden file
+    pub passed: usize,
+    /// Cases differing from their golden file
+    pub failed: usize,
+    /// Cases without a golden file
+    pub missing: usize,
+    /// Golden files written
+    pub updated: usize,
+    /// Cases that could not be run
+    pub errors: usize,
+    /// Templates without any fixture case
+    pub untested: Vec<String>,
+}
+
+/// Results of testing a template directory
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FixtureReport {
+    /// Totals over all cases
+    pub summary: FixtureSummary,
+    /// Per-case results, by template then case name
+    pub results: Vec<FixtureResult>,
+}
+
+impl FixtureReport {
+    /// Whether every case matched its golden file or had it updated
+    #[must_use]
+    pub const fn passed(&self) -> bool {
+        self.summary.failed == 0 && self.summary.missing == 0 && self.summary.errors == 0
+    }
+}
+
+fn io_error(path: &Path, source: std::io::Error) -> Error {
+    Error::Io {
+        path: path.display().to_string(),
+        message: source.to_string(),
+        source,
+    }
+}
+
+/// Sorted files in `dir` with `extension`, by file stem
+fn files_with_extension(dir: &Path, extension: &str) -> Result<Vec<(String, PathBuf)>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir).map_err(|e| io_error(dir, e))? {
+        let path = entry.map_err(|e| io_error(dir, e))?.path();
+        if !path.is_file() || path.extension().and_then(|ext| ext.to_str()) != Some(extension) {
+            continue;
+        }
+        if let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) {
+            files.push((stem.to_string(), path));
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Renders `template` with the variables in `fixture`
+fn render_case(template: &str, fixture: &Path) -> std::result::Result<String, String> {
+    let text = std::fs::read_to_string(fixture).map_err(|e| e.to_string())?;
+    let variables: Value =
+        serde_json::from_str(&text).map_err(|e| format!("invalid fixture JSON: {e}"))?;
+    if !variables.is_object() {
+        return Err("fixture must be a JSON object of template variables".to_string());
+    }
+    minijinja::Environment::new()
+        .render_str(template, variables)
+        .map_err(|e| format!("template failed to render: {e}"))
+}
+
+/// Runs one case, writing its golden file if `update_golden` is set
+fn run_case(
+    name: &str,
+    template: &str,
+    case: String,
+    fixture: &Path,
+    update_golden: bool,
+) -> Result<FixtureResult> {
+    let mut result = FixtureResult {
+        template: name.to_string(),
+        case,
+        status: FixtureStatus::Error,
+        diff: None,
+        error: None,
+    };
+    let rendered = match render_case(template, fixture) {
+        Ok(rendered) => rendered,
+        Err(e) => {
+            result.error = Some(e);
+            return Ok(result);
+        }
+    };
+
+    let golden_path = fixture.with_extension("golden");
+    let golden = match std::fs::read_to_string(&golden_path) {
+        Ok(golden) => Some(golden),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(io_error(&golden_path, e)),
+    };
+    if golden.as_deref() == Some(rendered.as_str()) {
+        result.status = FixtureStatus::Passed;
+        return Ok(result);
+    }
+    if update_golden {
+        std::fs::write(&golden_path, &rendered).map_err(|e| io_error(&golden_path, e))?;
+        result.status = FixtureStatus::Updated;
+        return Ok(result);
+    }
+    let Some(golden) = golden else {
+        result.status = FixtureStatus::Missing;
+        return Ok(result);
+    };
+    result.status = FixtureStatus::Failed;
+    result.diff = Some(unified_diff(
+        &golden,
+        &rendered,
+        &format!("{}.golden", result.case),
+        "rendered",
+    ));
+    Ok(result)
+}
+
+/// Renders every fixture case of the templates in `dir` and compares the
+/// output with the case's golden file
+///
+/// With `update_golden`, golden files that are missing or differ are
+/// rewritten from the rendered output instead of being reported.
+///
+/// # Errors
+/// Returns an I/O error if the directory, a template, or a golden file
+/// cannot be read, or a golden file cannot be written.
+pub fn check_templates(dir: &Path, update_golden: bool) -> Result<FixtureReport> {
+    let mut report = FixtureReport::default();
+    for (name, path) in files_with_extension(dir, TEMPLATE_EXTENSION)? {
+        let cases_dir = dir.join(FIXTURES_DIR).join(&name);
+        let cases = if cases_dir.is_dir() {
+            files_with_extension(&cases_dir, "json")?
+        } else {
+            Vec::new()
+        };
+        if cases.is_empty() {
+            report.summary.untested.push(name);
+            continue;
+        }
+
+        let template = std::fs::read_to_string(&path).map_err(|e| io_error(&path, e))?;
+        for (case, fixture) in cases {
+            let result = run_case(&name, &template, case, &fixture, update_golden)?;
+            report.summary.cases += 1;
+            match result.status {
+                FixtureStatus::Passed => report.summary.passed += 1,
+                FixtureStatus::Failed => report.summary.failed += 1,
+                FixtureStatus::Missing => report.summary.missing += 1,
+                FixtureStatus::Updated => report.summary.updated += 1,
+                FixtureStatus::Error => report.summary.errors += 1,
+            }
+            report.results.push(result);
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, contents: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    fn template_dir() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(
+            &root.join("router.j2"),
+            "hostname {{ node.name }}\nntp {{ ntp }}\n",
+        );
+        write(&root.join("switch.j2"), "vlan 10\n");
+        write(
+            &root.join("tests/router/edge-1.json"),
+            r#"{"node": {"name": "edge-1"}, "ntp": "10.0.0.1"}"#,
+        );
+        write(
+            &root.join("tests/router/edge-1.golden"),
+            "hostname edge-1\nntp 10.0.0.1\n",
+        );
+        write(
+            &root.join("tests/router/edge-2.json"),
+            r#"{"node": {"name": "edge-2"}, "ntp": "10.0.0.2"}"#,
+        );
+        write(
+            &root.join("tests/router/edge-2.golden"),
+            "hostname edge-2\nntp 10.0.0.1\n",
+        );
+        write(
+            &root.join("tests/router/new.json"),
+            r#"{"node": {"name": "new"}}"#,
+        );
+        dir
+    }
+
+    #[test]
+    fn test_templates_report_diffs_and_missing_goldens() {
+        let dir = template_dir();
+
+        let report = check_templates(dir.path(), false).unwrap();
+
+        assert!(!report.passed());
+        assert_eq!(report.summary.cases, 3);
+        assert_eq!(report.summary.passed, 1);
+        assert_eq!(report.summary.failed, 1);
+        assert_eq!(report.summary.missing, 1);
+        assert_eq!(report.summary.untested, ["switch"]);
+        let failed = &report.results[1];
+        assert_eq!(failed.case, "edge-2");
+        let diff = failed.diff.as_deref().unwrap();
+        assert!(diff.contains("-ntp 10.0.0.1"));
+        assert!(diff.contains("+ntp 10.0.0.2"));
+    }
+
+    #[test]
+    fn test_update_golden_rewrites_outputs() {
+        let dir = template_dir();
+
+        let report = check_templates(dir.path(), true).unwrap();
+        assert!(report.passed());
+        assert_eq!(report.summary.updated, 2);
+        let golden = std::fs::read_to_string(dir.path().join("tests/router/new.golden")).unwrap();
+        assert_eq!(golden, "hostname new\nntp \n");
+
+        let report = check_templates(dir.path(), false).unwrap();
+        assert_eq!(report.summary.passed, 3);
+    }
+
+    #[test]
+    fn test_invalid_fixture_is_reported_per_case() {
+        let dir = template_dir();
+        write(&dir.path().join("tests/router/bad.json"), "[1, 2]");
+
+        let report = check_templates(dir.path(), false).unwrap();
+        let bad = &report.results[0];
+        assert_eq!(bad.case, "bad");
+        assert_eq!(bad.status, FixtureStatus::Error);
+        assert!(bad.error.as_deref().unwrap().contains("JSON object"));
+    }
+}
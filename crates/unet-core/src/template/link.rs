@@ -0,0 +1,234 @@
+//! Link documentation rendering
+//!
+//! Renders a document for one link from the link record and its endpoint
+//! nodes: an LOA/CFA-style circuit summary and the interface configuration
+//! for each end. The circuit ID and provider come from the link record;
+//! other circuit details come from the link's `custom_data`:
+//!
+//! - `service` - circuit identification
+//! - `circuit_id`, `provider` - used when the link record has none
+//! - `mtu` - MTU configured on both ends
+//! - `a` and `z` - per-end objects with `address` (`ip/len`), `cfa`
+//!   (connecting facility assignment), and `demarc`
+//!
+//! Interface configuration follows each end's vendor syntax and its
+//! description names the far end by role, so one record documents both ends
+//! of a mixed-vendor link.
+
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult};
+use crate::models::{Link, Node};
+use serde::Serialize;
+use serde_json::Value;
+use uuid::Uuid;
+
+use self::config::{InterfaceSettings, interface_config, peer_description};
+
+mod config;
+
+/// Name of the built-in circuit documentation template
+pub const CIRCUIT_DOC: &str = "circuit-doc";
+
+/// Built-in circuit documentation template
+const CIRCUIT_DOC_TEMPLATE: &str = "\
+CIRCUIT DOCUMENT: {{ link.name }}
+{% if circuit.circuit_id %}Circuit ID:  {{ circuit.circuit_id }}
+{% endif %}{% if circuit.provider %}Provider:    {{ circuit.provider }}
+{% endif %}{% if circuit.service %}Service:     {{ circuit.service }}
+{% endif %}{% if bandwidth %}Bandwidth:   {{ bandwidth }}
+{% endif %}{% if link.description %}Description: {{ link.description }}
+{% endif %}{% for end in ends %}
+{{ end.label }} END
+  Node:      {{ end.node.fqdn }} ({{ end.node.vendor }} {{ end.node.role }})
+  Interface: {{ end.interface }}
+{% if end.address %}  Address:   {{ end.address }}
+{% endif %}{% if end.cfa %}  CFA:       {{ end.cfa }}
+{% endif %}{% if end.demarc %}  Demarc:    {{ end.demarc }}
+{% endif %}{% endfor %}{% if not z %}
+Z END
+  Internet handoff{% if circuit.provider %} from {{ circuit.provider }}{% endif %}
+{% endif %}{% for end in ends %}
+--- {{ end.label }} end configuration ({{ end.node.name }}) ---
+{{ end.config }}{% endfor %}
+";
+
+/// One end of a link as seen by templates
+#[derive(Debug, Clone, Serialize)]
+pub struct LinkEnd {
+    /// `A` or `Z`
+    pub label: &'static str,
+    /// Node terminating this end
+    pub node: Node,
+    /// Interface on the node
+    pub interface: String,
+    /// Interface description naming the far end
+    pub description: String,
+    /// Address with prefix length from `custom_data`
+    pub address: Option<String>,
+    /// Connecting facility assignment from `custom_data`
+    pub cfa: Option<String>,
+    /// Demarcation point from `custom_data`
+    pub demarc: Option<String>,
+    /// Interface configuration in the node vendor's syntax
+    pub config: String,
+}
+
+/// Returns the text of a built-in link template
+#[must_use]
+pub fn builtin_template(name: &str) -> Option<&'static str> {
+    (name == CIRCUIT_DOC).then_some(CIRCUIT_DOC_TEMPLATE)
+}
+
+/// Formats bits per second with the largest whole unit
+fn format_bandwidth(bps: u64) -> String {
+    const UNITS: [(u64, &str); 4] = [
+        (1_000_000_000_000, "Tbps"),
+        (1_000_000_000, "Gbps"),
+        (1_000_000, "Mbps"),
+        (1_000, "Kbps"),
+    ];
+    UNITS
+        .iter()
+        .find(|(size, _)| bps >= *size && bps % size == 0)
+        .map_or_else(
+            || format!("{bps} bps"),
+            |(size, unit)| format!("{} {unit}", bps / size),
+        )
+}
+
+fn end_text(link: &Link, end: &str, key: &str) -> Option<String> {
+    link.custom_data
+        .get(end)
+        .and_then(|data| data.get(key))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+fn link_end(
+    link: &Link,
+    label: &'static str,
+    node: &Node,
+    interface: &str,
+    description: String,
+) -> LinkEnd {
+    let key = label.to_lowercase();
+    let address = end_text(link, &key, "address");
+    let config = interface_config(
+        node.vendor,
+        &InterfaceSettings {
+            interface,
+            description: &description,
+            address: address.as_deref(),
+            mtu: link.custom_data.get("mtu").and_then(Value::as_u64),
+        },
+    );
+    LinkEnd {
+        label,
+        node: node.clone(),
+        interface: interface.to_string(),
+        description,
+        address,
+        cfa: end_text(link, &key, "cfa"),
+        demarc: end_text(link, &key, "demarc"),
+        config,
+    }
+}
+
+/// Renders `template` for a link and its endpoint nodes
+///
+/// Templates see `link`, its `custom_data` merged with the circuit ID and
+/// provider as `circuit`, a formatted `bandwidth`, the ends as `a` and `z`
+/// (absent for internet circuits), and both ends as the list `ends`.
+///
+/// # Errors
+/// Returns a validation error if the template fails to render.
+pub fn render_link(
+    link: &Link,
+    node_a: &Node,
+    node_z: Option<&Node>,
+    template: &str,
+) -> DataStoreResult<String> {
+    let mut circuit = match &link.custom_data {
+        Value::Object(data) => data.clone(),
+        _ => serde_json::Map::new(),
+    };
+    // The link's own circuit fields take precedence over `custom_data`
+    if let Some(provider) = &link.provider {
+        circuit.insert("provider".to_string(), Value::from(provider.as_str()));
+    }
+    if let Some(circuit_id) = &link.circuit_id {
+        circuit.insert("circuit_id".to_string(), Value::from(circuit_id.as_str()));
+    }
+
+    let circuit_id = circuit.get("circuit_id").and_then(Value::as_str);
+    let tag = circuit_id.map(|id| format!(" [{id}]")).unwrap_or_default();
+    let z_interface = link.node_z_interface.as_deref().unwrap_or_default();
+
+    let mut ends = Vec::with_capacity(2);
+    if let Some(node_z) = node_z {
+        let a_description =
+            peer_description(node_a.role, node_z.role, &node_z.name, z_interface) + &tag;
+        let z_description = peer_description(
+            node_z.role,
+            node_a.role,
+            &node_a.name,
+            &link.node_a_interface,
+        ) + &tag;
+        ends.push(link_end(
+            link,
+            "A",
+            node_a,
+            &link.node_a_interface,
+            a_description,
+        ));
+        ends.push(link_end(link, "Z", node_z, z_interface, z_description));
+    } else {
+        let provider = circuit
+            .get("provider")
+            .and_then(Value::as_str)
+            .unwrap_or("INTERNET");
+        let description = format!("WAN: {provider}{tag}");
+        ends.push(link_end(
+            link,
+            "A",
+            node_a,
+            &link.node_a_interface,
+            description,
+        ));
+    }
+
+    let context = serde_json::json!({
+        "link": link,
+        "circuit": circuit,
+        "bandwidth": link.bandwidth.map(format_bandwidth),
+        "a": ends.first(),
+        "z": ends.get(1),
+        "ends": ends,
+    });
+    minijinja::Environment::new()
+        .render_str(template, context)
+        .map_err(|e| DataStoreError::ValidationError {
+            message: format!("Link template failed to render for {}: {e}", link.name),
+        })
+}
+
+/// Loads a link and its nodes and renders `template` for it
+///
+/// # Errors
+/// Returns an error if the link or one of its nodes does not exist, the
+/// datastore cannot be read, or the template fails to render.
+pub async fn render_link_by_id(
+    datastore: &dyn DataStore,
+    link_id: Uuid,
+    template: &str,
+) -> DataStoreResult<String> {
+    let link = datastore.get_link_required(&link_id).await?;
+    let node_a = datastore.get_node_required(&link.source_node_id).await?;
+    let node_z = match link.dest_node_id {
+        Some(id) => Some(datastore.get_node_required(&id).await?),
+        None => None,
+    };
+    render_link(&link, &node_a, node_z.as_ref(), template)
+}
+
+#[cfg(test)]
+mod tests;
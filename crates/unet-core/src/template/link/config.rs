@@ -0,0 +1,183 @@
+//! Vendor-specific interface configuration for link endpoints
+
+use crate::models::{DeviceRole, Vendor};
+use std::fmt::Write as _;
+use std::net::{IpAddr, Ipv4Addr};
+
+/// Settings applied to one end of a link
+pub(super) struct InterfaceSettings<'a> {
+    /// Interface name on the node
+    pub interface: &'a str,
+    /// Interface description
+    pub description: &'a str,
+    /// Address with prefix length, e.g. `192.0.2.1/31`
+    pub address: Option<&'a str>,
+    /// Interface MTU
+    pub mtu: Option<u64>,
+}
+
+/// Where a role sits in the network, to tell uplinks from downlinks
+const fn tier(role: DeviceRole) -> u8 {
+    match role {
+        DeviceRole::Router | DeviceRole::Firewall | DeviceRole::SecurityAppliance => 3,
+        DeviceRole::Switch | DeviceRole::LoadBalancer | DeviceRole::Other => 2,
+        DeviceRole::AccessPoint
+        | DeviceRole::Server
+        | DeviceRole::Storage
+        | DeviceRole::Monitor => 1,
+    }
+}
+
+/// Describes an interface by what its far end is
+///
+/// Links towards a node higher in the network are `UPLINK`s, links towards a
+/// lower node `DOWNLINK`s, and links between nodes of the same tier `PEER`s.
+pub(super) fn peer_description(
+    role: DeviceRole,
+    peer_role: DeviceRole,
+    peer: &str,
+    peer_interface: &str,
+) -> String {
+    let kind = match tier(peer_role).cmp(&tier(role)) {
+        std::cmp::Ordering::Greater => "UPLINK",
+        std::cmp::Ordering::Less => "DOWNLINK",
+        std::cmp::Ordering::Equal => "PEER",
+    };
+    format!("{kind}: {peer} {peer_interface}")
+}
+
+/// Splits `address/len` into the address and prefix length
+fn split_prefix(address: &str) -> Option<(IpAddr, u8)> {
+    let (ip, len) = address.split_once('/')?;
+    Some((ip.parse().ok()?, len.parse().ok()?))
+}
+
+/// Formats an IPv4 prefix length as a dotted netmask
+fn netmask(len: u8) -> Ipv4Addr {
+    Ipv4Addr::from(
+        u32::MAX
+            .checked_shl(32 - u32::from(len.min(32)))
+            .unwrap_or(0),
+    )
+}
+
+/// Renders the interface configuration in the vendor's own syntax
+pub(super) fn interface_config(vendor: Vendor, settings: &InterfaceSettings<'_>) -> String {
+    match vendor {
+        Vendor::Juniper => junos(settings),
+        Vendor::PaloAlto => panos(settings),
+        Vendor::Fortinet => fortios(settings),
+        Vendor::Mikrotik => routeros(settings),
+        Vendor::Cisco => ios(settings, true),
+        Vendor::Arista
+        | Vendor::Hpe
+        | Vendor::Dell
+        | Vendor::Extreme
+        | Vendor::Ubiquiti
+        | Vendor::Generic => ios(settings, false),
+    }
+}
+
+/// IOS-style configuration; Cisco IOS wants IPv4 netmasks, the others take
+/// prefix lengths
+fn ios(settings: &InterfaceSettings<'_>, netmasks: bool) -> String {
+    let mut config = format!(
+        "interface {}\n description {}\n",
+        settings.interface, settings.description
+    );
+    if let Some(mtu) = settings.mtu {
+        let _ = writeln!(config, " mtu {mtu}");
+    }
+    match settings
+        .address
+        .map(|address| (address, split_prefix(address)))
+    {
+        Some((_, Some((IpAddr::V4(ip), len)))) if netmasks => {
+            let _ = writeln!(config, " ip address {ip} {}", netmask(len));
+        }
+        Some((address, Some((IpAddr::V6(_), _)))) => {
+            let _ = writeln!(config, " ipv6 address {address}");
+        }
+        Some((address, _)) => {
+            let _ = writeln!(config, " ip address {address}");
+        }
+        None => {}
+    }
+    config.push_str(" no shutdown\n");
+    config
+}
+
+fn junos(settings: &InterfaceSettings<'_>) -> String {
+    let interface = settings.interface;
+    let mut config = format!(
+        "set interfaces {interface} description \"{}\"\n",
+        settings.description
+    );
+    if let Some(mtu) = settings.mtu {
+        let _ = writeln!(config, "set interfaces {interface} mtu {mtu}");
+    }
+    if let Some(address) = settings.address {
+        let family = match split_prefix(address) {
+            Some((IpAddr::V6(_), _)) => "inet6",
+            _ => "inet",
+        };
+        let _ = writeln!(
+            config,
+            "set interfaces {interface} unit 0 family {family} address {address}"
+        );
+    }
+    config
+}
+
+fn panos(settings: &InterfaceSettings<'_>) -> String {
+    let prefix = format!("set network interface ethernet {}", settings.interface);
+    let mut config = format!("{prefix} comment \"{}\"\n", settings.description);
+    if let Some(mtu) = settings.mtu {
+        let _ = writeln!(config, "{prefix} layer3 mtu {mtu}");
+    }
+    if let Some(address) = settings.address {
+        let family = match split_prefix(address) {
+            Some((IpAddr::V6(_), _)) => "ipv6 address",
+            _ => "ip",
+        };
+        let _ = writeln!(config, "{prefix} layer3 {family} {address}");
+    }
+    config
+}
+
+fn fortios(settings: &InterfaceSettings<'_>) -> String {
+    let mut config = format!(
+        "config system interface\n    edit \"{}\"\n        set description \"{}\"\n",
+        settings.interface, settings.description
+    );
+    if let Some(mtu) = settings.mtu {
+        let _ = writeln!(
+            config,
+            "        set mtu-override enable\n        set mtu {mtu}"
+        );
+    }
+    if let Some(address) = settings.address {
+        let _ = writeln!(config, "        set ip {address}");
+    }
+    config.push_str("    next\nend\n");
+    config
+}
+
+fn routeros(settings: &InterfaceSettings<'_>) -> String {
+    let interface = settings.interface;
+    let mtu = settings
+        .mtu
+        .map(|mtu| format!(" mtu={mtu}"))
+        .unwrap_or_default();
+    let mut config = format!(
+        "/interface ethernet set [ find default-name={interface} ] comment=\"{}\"{mtu}\n",
+        settings.description
+    );
+    if let Some(address) = settings.address {
+        let _ = writeln!(
+            config,
+            "/ip address add address={address} interface={interface}"
+        );
+    }
+    config
+}
@@ -0,0 +1,111 @@
+use super::*;
+use crate::models::{DeviceRole, Vendor};
+use serde_json::json;
+
+fn node(name: &str, vendor: Vendor, role: DeviceRole) -> Node {
+    Node::new(name.to_string(), "example.com".to_string(), vendor, role)
+}
+
+fn circuit_doc() -> &'static str {
+    builtin_template(CIRCUIT_DOC).unwrap()
+}
+
+#[test]
+fn test_circuit_doc_renders_both_ends_in_vendor_syntax() {
+    let edge = node("edge-01", Vendor::Cisco, DeviceRole::Router);
+    let access = node("sw-01", Vendor::Juniper, DeviceRole::Switch);
+    let mut link = Link::new(
+        "edge-01-sw-01".to_string(),
+        edge.id,
+        "GigabitEthernet0/1".to_string(),
+        access.id,
+        "ge-0/0/0".to_string(),
+    );
+    link.bandwidth = Some(10_000_000_000);
+    link.custom_data = json!({
+        "circuit_id": "XC-1042",
+        "mtu": 9100,
+        "a": {"address": "192.0.2.0/31", "cfa": "PANEL 3 PORT 12"},
+        "z": {"address": "192.0.2.1/31"}
+    });
+
+    let doc = render_link(&link, &edge, Some(&access), circuit_doc()).unwrap();
+
+    assert!(doc.contains("Circuit ID:  XC-1042"));
+    assert!(doc.contains("Bandwidth:   10 Gbps"));
+    assert!(doc.contains("CFA:       PANEL 3 PORT 12"));
+    assert!(doc.contains(
+        "interface GigabitEthernet0/1\n description DOWNLINK: sw-01 ge-0/0/0 [XC-1042]\n mtu 9100\n ip address 192.0.2.0 255.255.255.254\n"
+    ));
+    assert!(doc.contains(
+        "set interfaces ge-0/0/0 description \"UPLINK: edge-01 GigabitEthernet0/1 [XC-1042]\""
+    ));
+    assert!(doc.contains("set interfaces ge-0/0/0 unit 0 family inet address 192.0.2.1/31"));
+}
+
+#[test]
+fn test_circuit_doc_for_internet_circuit_names_provider() {
+    let edge = node("edge-01", Vendor::Arista, DeviceRole::Router);
+    let mut link = Link::new_internet_circuit(
+        "edge-01-transit".to_string(),
+        edge.id,
+        "Ethernet1".to_string(),
+    );
+    link.custom_data = json!({"provider": "Example Transit", "a": {"address": "2001:db8::2/64"}});
+
+    let doc = render_link(&link, &edge, None, circuit_doc()).unwrap();
+
+    assert!(doc.contains("Internet handoff from Example Transit"));
+    assert!(doc.contains(" description WAN: Example Transit\n ipv6 address 2001:db8::2/64\n"));
+    assert!(!doc.contains("Z end configuration"));
+}
+
+#[test]
+fn test_link_circuit_fields_take_precedence_over_custom_data() {
+    let edge = node("edge-01", Vendor::Arista, DeviceRole::Router);
+    let mut link = Link::new_internet_circuit(
+        "edge-01-transit".to_string(),
+        edge.id,
+        "Ethernet1".to_string(),
+    );
+    link.provider = Some("Example Transit".to_string());
+    link.circuit_id = Some("ET-7781".to_string());
+    link.custom_data = json!({"provider": "Old Transit"});
+
+    let doc = render_link(&link, &edge, None, circuit_doc()).unwrap();
+
+    assert!(doc.contains("Circuit ID:  ET-7781"));
+    assert!(doc.contains(" description WAN: Example Transit [ET-7781]\n"));
+    assert!(!doc.contains("Old Transit"));
+}
+
+#[test]
+fn test_custom_templates_see_link_ends() {
+    let a = node("fw-01", Vendor::PaloAlto, DeviceRole::Firewall);
+    let z = node("fw-02", Vendor::PaloAlto, DeviceRole::Firewall);
+    let link = Link::new(
+        "ha".to_string(),
+        a.id,
+        "ethernet1/3".to_string(),
+        z.id,
+        "ethernet1/3".to_string(),
+    );
+
+    let text = render_link(
+        &link,
+        &a,
+        Some(&z),
+        "{{ a.node.name }}:{{ z.node.name }} {{ a.description }}",
+    )
+    .unwrap();
+    assert_eq!(text, "fw-01:fw-02 PEER: fw-02 ethernet1/3");
+
+    assert!(render_link(&link, &a, Some(&z), "{{ a.node.name").is_err());
+}
+
+#[test]
+fn test_format_bandwidth_uses_whole_units() {
+    assert_eq!(format_bandwidth(100_000_000), "100 Mbps");
+    assert_eq!(format_bandwidth(2_500_000_000), "2500 Mbps");
+    assert_eq!(format_bandwidth(999), "999 bps");
+}
@@ -0,0 +1,92 @@
+use super::*;
+use crate::datastore::sqlite::tests::setup::settings_store;
+use crate::golden::save_golden;
+use crate::models::{DeviceRole, Vendor};
+use serde_json::json;
+
+fn node(name: &str, role: DeviceRole) -> Node {
+    Node::new(
+        name.to_string(),
+        "example.com".to_string(),
+        Vendor::Juniper,
+        role,
+    )
+}
+
+fn template(text: &str) -> GoldenSource {
+    GoldenSource::Template {
+        template: text.to_string(),
+        variables: json!({"ntp": "10.0.0.1"}),
+    }
+}
+
+#[tokio::test]
+async fn test_render_fleet_compares_with_recorded_renders() {
+    let store = settings_store().await;
+    let role = GoldenConfig::new(
+        GoldenScope::Role,
+        "router",
+        template("hostname {{ node.name }}\n"),
+    )
+    .unwrap();
+    save_golden(&store, &role).await.unwrap();
+    let nodes = vec![
+        node("edge-1", DeviceRole::Router),
+        node("edge-2", DeviceRole::Router),
+        node("sw-1", DeviceRole::Switch),
+    ];
+
+    let record = RenderOptions {
+        record: true,
+        ..RenderOptions::default()
+    };
+    let first = render_fleet(&store, &nodes, &record).await.unwrap();
+    assert_eq!(first.summary.new, 2);
+    assert_eq!(first.summary.skipped, 1);
+    assert_eq!(first.nodes[2].status, RenderStatus::Skipped);
+
+    let candidate = RenderOptions {
+        template: Some(template(
+            "hostname {{ node.name }}\n{% if node.name == 'edge-1' %}ntp server {{ ntp }}\n{% endif %}",
+        )),
+        diff: true,
+        record: false,
+    };
+    let preview = render_fleet(&store, &nodes[..2], &candidate).await.unwrap();
+    assert_eq!(preview.summary.changed, 1);
+    assert_eq!(preview.summary.unchanged, 1);
+    assert_eq!(preview.summary.lines_added, 1);
+    assert_eq!(preview.nodes[0].status, RenderStatus::Changed);
+    assert!(
+        preview.nodes[0]
+            .diff
+            .as_deref()
+            .unwrap()
+            .contains("+ntp server 10.0.0.1")
+    );
+    assert!(preview.nodes[1].diff.is_none());
+
+    // A preview does not replace the baseline
+    let recorded = recorded_render(&store, nodes[0].id).await.unwrap().unwrap();
+    assert_eq!(recorded.config, "hostname edge-1\n");
+}
+
+#[tokio::test]
+async fn test_render_failure_is_reported_per_node() {
+    let store = settings_store().await;
+    let nodes = vec![node("edge-1", DeviceRole::Router)];
+    let options = RenderOptions {
+        template: Some(template("hostname {{ node.name")),
+        ..RenderOptions::default()
+    };
+
+    let result = render_fleet(&store, &nodes, &options).await.unwrap();
+    assert_eq!(result.summary.failed, 1);
+    assert!(result.nodes[0].error.is_some());
+    assert!(
+        recorded_render(&store, nodes[0].id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
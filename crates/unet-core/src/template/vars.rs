@@ -0,0 +1,305 @@
+//! Layered template variables
+//!
+//! Templates see variables from several layers, each overriding the ones
+//! before it:
+//!
+//! 1. global defaults, set for every node
+//! 2. locations, from the outermost ancestor of the node's location down to
+//!    the location itself
+//! 3. dynamic groups the node is a member of (see [`crate::groups`]), in
+//!    group name order
+//! 4. the node's own `custom_data`
+//!
+//! Objects are merged key by key, so a layer can override one nested value
+//! and keep the rest; any other value replaces what the layers before it
+//! set. Every leaf of the merged variables is traced back to the layer that
+//! set it. Layer variables are kept through the `DataStore` settings API
+//! keyed by [`VarScope`].
+
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult};
+use crate::groups::{NodeGroup, group_name, list_groups, memberships};
+use crate::models::Node;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use uuid::Uuid;
+
+/// Settings namespace holding layer variables keyed by [`VarScope`]
+const VARS_NAMESPACE: &str = "template_vars";
+
+/// Source of the values taken from the node's `custom_data`
+const NODE_SOURCE: &str = "node";
+
+/// Locations followed up from a node's location before giving up on a cycle
+const MAX_LOCATION_DEPTH: usize = 32;
+
+/// What a set of layer variables applies to
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum VarScope {
+    /// Every node
+    Global,
+    /// Nodes at a location or below it
+    Location(Uuid),
+    /// Members of a dynamic group
+    Group(String),
+}
+
+impl VarScope {
+    /// Variables for the members of a group, checking the group name
+    ///
+    /// # Errors
+    /// Returns a validation error if the name is not a valid group name.
+    pub fn group(name: &str) -> DataStoreResult<Self> {
+        group_name(name).map(Self::Group)
+    }
+
+    fn parse(key: &str) -> Option<Self> {
+        match key.split_once(':') {
+            None if key == "global" => Some(Self::Global),
+            Some(("location", id)) => id.parse().ok().map(Self::Location),
+            Some(("group", name)) => Some(Self::Group(name.to_string())),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for VarScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Global => write!(f, "global"),
+            Self::Location(id) => write!(f, "location:{id}"),
+            Self::Group(name) => write!(f, "group:{name}"),
+        }
+    }
+}
+
+/// A node's merged template variables
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResolvedVars {
+    /// Node the variables were resolved for
+    pub node_id: Uuid,
+    /// Node name
+    pub node: String,
+    /// Merged variables, as a JSON object
+    pub variables: Value,
+    /// Layer that set each leaf, by dotted path: `global`,
+    /// `location:<path>`, `group:<name>`, or `node`
+    pub sources: BTreeMap<String, String>,
+}
+
+/// Every layer's variables, read once to resolve many nodes
+#[derive(Debug, Clone, Default)]
+pub struct TemplateVars {
+    global: Option<Map<String, Value>>,
+    locations: HashMap<Uuid, Map<String, Value>>,
+    groups: BTreeMap<String, Map<String, Value>>,
+    group_defs: Vec<NodeGroup>,
+}
+
+impl TemplateVars {
+    /// Reads the variables of every layer
+    ///
+    /// Datastores without settings support have no layer variables.
+    ///
+    /// # Errors
+    /// Returns an error if the datastore cannot be read or a stored document
+    /// is malformed.
+    pub async fn load(datastore: &dyn DataStore) -> DataStoreResult<Self> {
+        let stored = match datastore.list_settings(VARS_NAMESPACE).await {
+            Ok(stored) => stored,
+            Err(DataStoreError::UnsupportedOperation { .. }) => return Ok(Self::default()),
+            Err(e) => return Err(e),
+        };
+        let mut vars = Self::default();
+        for (key, value) in stored {
+            let Value::Object(map) = value else {
+                return Err(malformed(&key));
+            };
+            match VarScope::parse(&key).ok_or_else(|| malformed(&key))? {
+                VarScope::Global => vars.global = Some(map),
+                VarScope::Location(id) => {
+                    vars.locations.insert(id, map);
+                }
+                VarScope::Group(name) => {
+                    vars.groups.insert(name, map);
+                }
+            }
+        }
+        if !vars.groups.is_empty() {
+            vars.group_defs = list_groups(datastore).await?;
+        }
+        Ok(vars)
+    }
+
+    /// Merges the layers that apply to `node`
+    ///
+    /// # Errors
+    /// Returns an error if the node's locations cannot be read.
+    pub async fn resolve(
+        &self,
+        datastore: &dyn DataStore,
+        node: &Node,
+    ) -> DataStoreResult<ResolvedVars> {
+        let mut variables = Map::new();
+        let mut sources = BTreeMap::new();
+        if let Some(global) = &self.global {
+            merge(&mut variables, global, "", "global", &mut sources);
+        }
+        if !self.locations.is_empty() {
+            for (id, path) in location_chain(datastore, node.location_id).await? {
+                if let Some(layer) = self.locations.get(&id) {
+                    let source = format!("location:{path}");
+                    merge(&mut variables, layer, "", &source, &mut sources);
+                }
+            }
+        }
+        for group in memberships(&self.group_defs, node) {
+            if let Some(layer) = self.groups.get(&group) {
+                let source = format!("group:{group}");
+                merge(&mut variables, layer, "", &source, &mut sources);
+            }
+        }
+        if let Value::Object(custom_data) = &node.custom_data {
+            merge(&mut variables, custom_data, "", NODE_SOURCE, &mut sources);
+        }
+        Ok(ResolvedVars {
+            node_id: node.id,
+            node: node.name.clone(),
+            variables: Value::Object(variables),
+            sources,
+        })
+    }
+}
+
+fn malformed(key: &str) -> DataStoreError {
+    DataStoreError::InternalError {
+        message: format!("stored template variables {key} are not a JSON object"),
+    }
+}
+
+/// The node's location and its ancestors with their paths, outermost first
+async fn location_chain(
+    datastore: &dyn DataStore,
+    location_id: Option<Uuid>,
+) -> DataStoreResult<Vec<(Uuid, String)>> {
+    let mut chain = Vec::new();
+    let mut next = location_id;
+    while let Some(id) = next {
+        if chain.len() >= MAX_LOCATION_DEPTH || chain.iter().any(|(seen, _)| *seen == id) {
+            break;
+        }
+        let Some(location) = datastore.get_location(&id).await? else {
+            break;
+        };
+        next = location.parent_id;
+        chain.push((id, location.path));
+    }
+    chain.reverse();
+    Ok(chain)
+}
+
+/// Merges `layer` into `target`, recording `source` for every leaf it sets
+fn merge(
+    target: &mut Map<String, Value>,
+    layer: &Map<String, Value>,
+    prefix: &str,
+    source: &str,
+    sources: &mut BTreeMap<String, String>,
+) {
+    for (key, value) in layer {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        if let (Some(Value::Object(existing)), Value::Object(incoming)) =
+            (target.get_mut(key), value)
+        {
+            merge(existing, incoming, &path, source, sources);
+            continue;
+        }
+        // The value replaces whatever was below this path
+        let nested = format!("{path}.");
+        sources.retain(|name, _| name != &path && !name.starts_with(&nested));
+        trace(value, &path, source, sources);
+        target.insert(key.clone(), value.clone());
+    }
+}
+
+/// Records `source` for every leaf of `value`
+fn trace(value: &Value, path: &str, source: &str, sources: &mut BTreeMap<String, String>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, value) in map {
+                trace(value, &format!("{path}.{key}"), source, sources);
+            }
+        }
+        _ => {
+            sources.insert(path.to_string(), source.to_string());
+        }
+    }
+}
+
+/// Gets the variables set for a scope
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or the stored document
+/// is malformed.
+pub async fn get_vars(
+    datastore: &dyn DataStore,
+    scope: &VarScope,
+) -> DataStoreResult<Option<Value>> {
+    let key = scope.to_string();
+    match datastore.get_setting(VARS_NAMESPACE, &key).await? {
+        Some(value) if value.is_object() => Ok(Some(value)),
+        Some(_) => Err(malformed(&key)),
+        None => Ok(None),
+    }
+}
+
+/// Stores the variables for a scope, replacing any set before
+///
+/// # Errors
+/// Returns a validation error if the variables are not a JSON object, or an
+/// error if the datastore write fails.
+pub async fn set_vars(
+    datastore: &dyn DataStore,
+    scope: &VarScope,
+    variables: &Value,
+) -> DataStoreResult<()> {
+    if !variables.is_object() {
+        return Err(DataStoreError::ValidationError {
+            message: format!("Template variables for {scope} must be a JSON object"),
+        });
+    }
+    datastore
+        .put_setting(VARS_NAMESPACE, &scope.to_string(), variables)
+        .await
+}
+
+/// Removes the variables set for a scope
+///
+/// # Errors
+/// Returns a not-found error if none are set, or an error if the datastore
+/// write fails.
+pub async fn delete_vars(datastore: &dyn DataStore, scope: &VarScope) -> DataStoreResult<()> {
+    datastore
+        .delete_setting(VARS_NAMESPACE, &scope.to_string())
+        .await
+}
+
+/// Merges the layers that apply to one node
+///
+/// # Errors
+/// Returns an error if layer variables or the node's locations cannot be
+/// read.
+pub async fn resolve_vars(datastore: &dyn DataStore, node: &Node) -> DataStoreResult<ResolvedVars> {
+    TemplateVars::load(datastore)
+        .await?
+        .resolve(datastore, node)
+        .await
+}
+
+#[cfg(test)]
+mod tests;
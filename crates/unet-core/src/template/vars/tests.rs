@@ -0,0 +1,126 @@
+use super::*;
+use crate::datastore::sqlite::SqliteStore;
+use crate::datastore::sqlite::tests::setup::migrated_store;
+use crate::groups::save_group;
+use crate::models::{DeviceRole, Location, Vendor};
+use serde_json::json;
+
+/// A node in `dc-west/hall-a` with a core tier
+async fn seed(store: &SqliteStore) -> (Node, Location, Location) {
+    let site = Location::new_root("dc-west".to_string(), "site".to_string());
+    store.create_location(&site).await.unwrap();
+    let mut hall = Location::new_child("hall-a".to_string(), "room".to_string(), &site.path);
+    hall.parent_id = Some(site.id);
+    store.create_location(&hall).await.unwrap();
+
+    let mut node = Node::new(
+        "core-1".to_string(),
+        "example.com".to_string(),
+        Vendor::Juniper,
+        DeviceRole::Router,
+    );
+    node.location_id = Some(hall.id);
+    node.custom_data = json!({ "tier": "core", "snmp": { "community": "node-only" } });
+    let node = store.create_node(&node).await.unwrap();
+    (node, site, hall)
+}
+
+#[tokio::test]
+async fn test_layers_override_in_order_and_are_traced() {
+    let store = migrated_store().await;
+    let (node, site, hall) = seed(&store).await;
+    let core = NodeGroup::new("core", r#"node.custom_data.tier == "core""#, None).unwrap();
+    save_group(&store, &core).await.unwrap();
+
+    let layers = [
+        (
+            VarScope::Global,
+            json!({
+                "ntp": "10.0.0.1",
+                "syslog": "10.0.0.9",
+                "snmp": { "community": "public", "location": "unknown" },
+            }),
+        ),
+        (
+            VarScope::Location(site.id),
+            json!({ "ntp": "10.1.0.1", "snmp": { "location": "dc-west" } }),
+        ),
+        (VarScope::Location(hall.id), json!({ "ntp": "10.1.1.1" })),
+        (
+            VarScope::group("core").unwrap(),
+            json!({ "syslog": "10.1.0.9" }),
+        ),
+        (
+            VarScope::group("edge").unwrap(),
+            json!({ "syslog": "10.2.0.9" }),
+        ),
+    ];
+    for (scope, variables) in &layers {
+        set_vars(&store, scope, variables).await.unwrap();
+    }
+
+    let resolved = resolve_vars(&store, &node).await.unwrap();
+    assert_eq!(
+        resolved.variables,
+        json!({
+            "ntp": "10.1.1.1",
+            "syslog": "10.1.0.9",
+            "tier": "core",
+            "snmp": { "community": "node-only", "location": "dc-west" },
+        })
+    );
+    let sources: Vec<(&str, &str)> = resolved
+        .sources
+        .iter()
+        .map(|(path, source)| (path.as_str(), source.as_str()))
+        .collect();
+    assert_eq!(
+        sources,
+        [
+            ("ntp", "location:dc-west/hall-a"),
+            ("snmp.community", "node"),
+            ("snmp.location", "location:dc-west"),
+            ("syslog", "group:core"),
+            ("tier", "node"),
+        ]
+    );
+
+    delete_vars(&store, &VarScope::Location(hall.id))
+        .await
+        .unwrap();
+    let resolved = resolve_vars(&store, &node).await.unwrap();
+    assert_eq!(resolved.variables["ntp"], "10.1.0.1");
+    assert_eq!(resolved.sources["ntp"], "location:dc-west");
+}
+
+#[tokio::test]
+async fn test_scalar_replaces_object_and_its_sources() {
+    let store = migrated_store().await;
+    let (mut node, _, _) = seed(&store).await;
+    node.custom_data = json!({ "snmp": "disabled" });
+    set_vars(
+        &store,
+        &VarScope::Global,
+        &json!({ "snmp": { "community": "public" } }),
+    )
+    .await
+    .unwrap();
+
+    let resolved = resolve_vars(&store, &node).await.unwrap();
+    assert_eq!(resolved.variables, json!({ "snmp": "disabled" }));
+    assert_eq!(resolved.sources.len(), 1);
+    assert_eq!(resolved.sources["snmp"], "node");
+}
+
+#[tokio::test]
+async fn test_set_vars_requires_object() {
+    let store = migrated_store().await;
+
+    let result = set_vars(&store, &VarScope::Global, &json!(["ntp"])).await;
+    assert!(matches!(
+        result,
+        Err(DataStoreError::ValidationError { .. })
+    ));
+    assert!(VarScope::group("core routers").is_err());
+    assert!(get_vars(&store, &VarScope::Global).await.unwrap().is_none());
+}
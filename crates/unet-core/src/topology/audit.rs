@@ -0,0 +1,126 @@
+//! Fleet-wide scan for dangling and duplicate links
+
+use serde::Serialize;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+use super::LinkSide;
+use super::endpoints::{endpoints, known_interfaces};
+use crate::datastore::{DataStore, DataStoreResult, QueryOptions};
+
+/// Kind of problem found on a link
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LinkIssueKind {
+    /// The endpoint node does not exist
+    MissingNode {
+        /// Missing node ID
+        node_id: Uuid,
+    },
+    /// The endpoint interface is not among the node's collected interfaces
+    UnknownInterface {
+        /// Node ID
+        node_id: Uuid,
+        /// Interface named by the link
+        interface: String,
+    },
+    /// Another link already uses this node interface
+    DuplicateEndpoint {
+        /// Node ID
+        node_id: Uuid,
+        /// Shared interface
+        interface: String,
+        /// Link that first claimed the interface
+        other_link_id: Uuid,
+    },
+}
+
+/// A problem found on one side of a link
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LinkIssue {
+    /// Link ID
+    pub link_id: Uuid,
+    /// Link name
+    pub link_name: String,
+    /// Affected side
+    pub side: LinkSide,
+    /// Problem details
+    #[serde(flatten)]
+    pub kind: LinkIssueKind,
+}
+
+/// Result of a link audit
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LinkAuditReport {
+    /// Number of links scanned
+    pub links_checked: usize,
+    /// Problems found, in link order
+    pub issues: Vec<LinkIssue>,
+}
+
+/// Scans all links for missing nodes, unknown interfaces, and interfaces
+/// claimed by more than one link
+///
+/// # Errors
+/// Returns an error if links, nodes, or interfaces cannot be read.
+pub async fn audit_links(datastore: &dyn DataStore) -> DataStoreResult<LinkAuditReport> {
+    let links = datastore.list_links(&QueryOptions::default()).await?.items;
+    let node_ids: HashSet<Uuid> = datastore
+        .list_nodes(&QueryOptions::default())
+        .await?
+        .items
+        .into_iter()
+        .map(|node| node.id)
+        .collect();
+
+    let mut interfaces: HashMap<Uuid, Option<HashSet<String>>> = HashMap::new();
+    let mut claimed: HashMap<(Uuid, String), Uuid> = HashMap::new();
+    let mut report = LinkAuditReport {
+        links_checked: links.len(),
+        issues: Vec::new(),
+    };
+
+    for link in &links {
+        for (side, node_id, interface) in endpoints(link) {
+            let mut issue = |kind| {
+                report.issues.push(LinkIssue {
+                    link_id: link.id,
+                    link_name: link.name.clone(),
+                    side,
+                    kind,
+                });
+            };
+
+            if !node_ids.contains(&node_id) {
+                issue(LinkIssueKind::MissingNode { node_id });
+                continue;
+            }
+
+            if let Some(&other_link_id) = claimed.get(&(node_id, interface.to_string())) {
+                issue(LinkIssueKind::DuplicateEndpoint {
+                    node_id,
+                    interface: interface.to_string(),
+                    other_link_id,
+                });
+            } else {
+                claimed.insert((node_id, interface.to_string()), link.id);
+            }
+
+            if let Entry::Vacant(entry) = interfaces.entry(node_id) {
+                entry.insert(known_interfaces(datastore, &node_id).await?);
+            }
+            if interfaces[&node_id]
+                .as_ref()
+                .is_some_and(|known| !known.contains(interface))
+            {
+                issue(LinkIssueKind::UnknownInterface {
+                    node_id,
+                    interface: interface.to_string(),
+                });
+            }
+        }
+    }
+
+    Ok(report)
+}
@@ -0,0 +1,276 @@
+//! In-memory topology kept current from change events
+//!
+//! Loading every node, link, and location for each topology query is slow on
+//! large inventories. [`TopologyCache`] loads them once and, registered with
+//! [`ChangeLogStore`] as a [`Projection`], applies each change event to its
+//! copy after the write, so impact and single-point-of-failure queries run
+//! against memory.
+//!
+//! Writes by other processes sharing the database are seen at the next write
+//! through the store; writes that produce no change event, such as those
+//! made in a transaction, are not seen at all. The cache is therefore
+//! rebuilt before its next query once it is older than its maximum age,
+//! after an event it cannot apply, or when a change arrives while it is being
+//! rebuilt. [`TopologyCache::invalidate`] forces a rebuild.
+//!
+//! [`ChangeLogStore`]: crate::change_log::ChangeLogStore
+
+use async_trait::async_trait;
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::Duration;
+use uuid::Uuid;
+
+use super::impact::{FailureTarget, ImpactReport, impact};
+use super::spof::{SpofReport, single_points_of_failure};
+use crate::change_log::{ChangeEvent, ChangeType, EntityKind, Projection};
+use crate::config::defaults::cache::DEFAULT_CACHE_TTL_SECONDS;
+use crate::datastore::{DataStore, DataStoreResult, QueryOptions};
+use crate::models::{Link, Location, Node};
+
+/// State of a [`TopologyCache`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TopologyCacheStatus {
+    /// When the cache was last loaded from the datastore
+    pub built_at: Option<DateTime<Utc>>,
+    /// Cached nodes
+    pub nodes: usize,
+    /// Cached links
+    pub links: usize,
+    /// Cached locations
+    pub locations: usize,
+    /// Change events applied since the cache was loaded
+    pub applied_events: u64,
+    /// When the latest applied event was recorded
+    pub last_event_at: Option<DateTime<Utc>>,
+    /// Why the next query rebuilds the cache, if it does
+    pub stale_reason: Option<String>,
+}
+
+#[derive(Default)]
+struct State {
+    nodes: Vec<Node>,
+    links: Vec<Link>,
+    locations: Vec<Location>,
+    built_at: Option<DateTime<Utc>>,
+    applied_events: u64,
+    last_event_at: Option<DateTime<Utc>>,
+    /// Bumped by every event, so a rebuild can tell it missed one
+    generation: u64,
+    invalidated: Option<String>,
+}
+
+impl State {
+    fn stale_reason(&self, max_age: TimeDelta, now: DateTime<Utc>) -> Option<String> {
+        let Some(built_at) = self.built_at else {
+            return Some("not built yet".to_string());
+        };
+        if let Some(reason) = &self.invalidated {
+            return Some(reason.clone());
+        }
+        (now - built_at > max_age)
+            .then(|| format!("built more than {} seconds ago", max_age.num_seconds()))
+    }
+
+    fn apply(&mut self, event: &ChangeEvent) -> Result<(), String> {
+        match event.entity {
+            EntityKind::Node => upsert(&mut self.nodes, event, |node| node.id),
+            EntityKind::Link => upsert(&mut self.links, event, |link| link.id),
+            EntityKind::Location => upsert(&mut self.locations, event, |location| location.id),
+        }
+    }
+}
+
+/// Replaces, adds, or removes the entity an event is about
+fn upsert<T: DeserializeOwned>(
+    items: &mut Vec<T>,
+    event: &ChangeEvent,
+    id_of: fn(&T) -> Uuid,
+) -> Result<(), String> {
+    if event.change == ChangeType::Deleted {
+        items.retain(|item| id_of(item) != event.entity_id);
+        return Ok(());
+    }
+    let item: T = serde_json::from_value(event.payload.clone()).map_err(|e| {
+        format!(
+            "could not apply event {} for {} {}: {e}",
+            event.id, event.entity, event.entity_id
+        )
+    })?;
+    match items
+        .iter_mut()
+        .find(|existing| id_of(existing) == event.entity_id)
+    {
+        Some(existing) => *existing = item,
+        None => items.push(item),
+    }
+    Ok(())
+}
+
+/// Nodes, links, and locations held in memory for topology queries
+pub struct TopologyCache {
+    max_age: TimeDelta,
+    state: RwLock<State>,
+}
+
+impl Default for TopologyCache {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(DEFAULT_CACHE_TTL_SECONDS))
+    }
+}
+
+impl TopologyCache {
+    /// Name of the projection
+    pub const NAME: &'static str = "topology_cache";
+
+    /// Creates an empty cache that is rebuilt once older than `max_age`
+    ///
+    /// A zero `max_age` rebuilds before every query.
+    #[must_use]
+    pub fn new(max_age: Duration) -> Self {
+        Self {
+            max_age: TimeDelta::from_std(max_age).unwrap_or(TimeDelta::MAX),
+            state: RwLock::new(State::default()),
+        }
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, State> {
+        self.state.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, State> {
+        self.state.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Reports what is cached and whether the next query rebuilds it
+    #[must_use]
+    pub fn status(&self) -> TopologyCacheStatus {
+        let state = self.read();
+        TopologyCacheStatus {
+            built_at: state.built_at,
+            nodes: state.nodes.len(),
+            links: state.links.len(),
+            locations: state.locations.len(),
+            applied_events: state.applied_events,
+            last_event_at: state.last_event_at,
+            stale_reason: state.stale_reason(self.max_age, Utc::now()),
+        }
+    }
+
+    /// Marks the cache stale so the next query rebuilds it
+    pub fn invalidate(&self, reason: impl Into<String>) {
+        self.write().invalidated = Some(reason.into());
+    }
+
+    /// Loads every node, link, and location from the datastore
+    ///
+    /// # Errors
+    /// Returns an error if nodes, links, or locations cannot be read; the
+    /// cache is left as it was.
+    pub async fn rebuild(&self, datastore: &dyn DataStore) -> DataStoreResult<TopologyCacheStatus> {
+        let generation = self.read().generation;
+        let options = QueryOptions::default();
+        let nodes = datastore.list_nodes(&options).await?.items;
+        let links = datastore.list_links(&options).await?.items;
+        let locations = datastore.list_locations(&options).await?.items;
+
+        {
+            let mut state = self.write();
+            let missed = state.generation != generation;
+            *state = State {
+                nodes,
+                links,
+                locations,
+                built_at: Some(Utc::now()),
+                generation: state.generation,
+                invalidated: missed.then(|| "changed while rebuilding".to_string()),
+                ..State::default()
+            };
+        }
+        Ok(self.status())
+    }
+
+    /// Applies one change event to the cached entities
+    ///
+    /// An event the cache cannot apply marks it stale. Events arriving
+    /// before the first rebuild are ignored, since that rebuild loads them.
+    pub fn apply_event(&self, event: &ChangeEvent) {
+        let mut state = self.write();
+        state.generation += 1;
+        if state.built_at.is_none() {
+            return;
+        }
+        match state.apply(event) {
+            Ok(()) => {
+                state.applied_events += 1;
+                state.last_event_at = Some(event.recorded_at);
+            }
+            Err(reason) => state.invalidated = Some(reason),
+        }
+    }
+
+    async fn refresh(&self, datastore: &dyn DataStore) -> DataStoreResult<()> {
+        let stale = self.read().stale_reason(self.max_age, Utc::now()).is_some();
+        if stale {
+            self.rebuild(datastore).await?;
+        }
+        Ok(())
+    }
+
+    /// Reports the impact of `target` going down, like
+    /// [`super::analyze_impact`], from the cached topology
+    ///
+    /// # Errors
+    /// Returns a not-found error if the target does not exist, or an error if
+    /// a stale cache cannot be rebuilt.
+    pub async fn impact(
+        &self,
+        datastore: &dyn DataStore,
+        target: FailureTarget,
+        roots: &[Uuid],
+    ) -> DataStoreResult<ImpactReport> {
+        self.refresh(datastore).await?;
+        let state = self.read();
+        impact(target, roots, &state.nodes, &state.links, &state.locations)
+    }
+
+    /// Finds the single points of failure in the cached topology
+    ///
+    /// # Errors
+    /// Returns an error if a stale cache cannot be rebuilt.
+    pub async fn single_points_of_failure(
+        &self,
+        datastore: &dyn DataStore,
+    ) -> DataStoreResult<SpofReport> {
+        self.refresh(datastore).await?;
+        let state = self.read();
+        Ok(single_points_of_failure(&state.nodes, &state.links))
+    }
+}
+
+#[async_trait]
+impl Projection for TopologyCache {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    async fn reset(&self, _datastore: &dyn DataStore) -> DataStoreResult<()> {
+        let mut state = self.write();
+        *state = State {
+            generation: state.generation + 1,
+            ..State::default()
+        };
+        Ok(())
+    }
+
+    async fn apply(&self, _datastore: &dyn DataStore, event: &ChangeEvent) -> DataStoreResult<()> {
+        self.apply_event(event);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[path = "cache_tests.rs"]
+mod cache_tests;
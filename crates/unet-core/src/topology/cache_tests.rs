@@ -0,0 +1,135 @@
+use super::*;
+use crate::datastore::{MockDataStore, PagedResult, testing::ready_ok};
+use crate::models::{DeviceRole, Vendor};
+
+fn node(name: &str) -> Node {
+    Node::new(
+        name.to_string(),
+        "example.com".to_string(),
+        Vendor::Cisco,
+        DeviceRole::Router,
+    )
+}
+
+fn link(a: &Node, z: &Node) -> Link {
+    Link::new(
+        format!("{}-{}", a.name, z.name),
+        a.id,
+        "eth0".to_string(),
+        z.id,
+        "eth0".to_string(),
+    )
+}
+
+fn event(
+    entity: EntityKind,
+    id: Uuid,
+    change: ChangeType,
+    payload: &impl Serialize,
+) -> ChangeEvent {
+    ChangeEvent::new(entity, id, change, serde_json::to_value(payload).unwrap())
+}
+
+/// Datastore that must be listed exactly once
+fn datastore(nodes: Vec<Node>, links: Vec<Link>) -> MockDataStore {
+    let mut mock = MockDataStore::new();
+    mock.expect_list_nodes()
+        .times(1)
+        .returning(move |_| ready_ok(PagedResult::new(nodes.clone(), nodes.len(), None)));
+    mock.expect_list_links()
+        .times(1)
+        .returning(move |_| ready_ok(PagedResult::new(links.clone(), links.len(), None)));
+    mock.expect_list_locations()
+        .times(1)
+        .returning(|_| ready_ok(PagedResult::new(Vec::new(), 0, None)));
+    mock
+}
+
+#[tokio::test]
+async fn test_queries_use_events_instead_of_reloading() {
+    let [a, b, c] = ["a", "b", "c"].map(node);
+    let (a_b, b_c, a_c) = (link(&a, &b), link(&b, &c), link(&a, &c));
+    let datastore = datastore(vec![a.clone(), b.clone(), c.clone()], vec![a_b.clone()]);
+    let cache = TopologyCache::default();
+
+    // First query loads the topology
+    let spof = cache.single_points_of_failure(&datastore).await.unwrap();
+    assert!(spof.nodes.is_empty());
+    assert_eq!(spof.links.len(), 1);
+
+    // c joins over b, then a second path closes the ring
+    cache.apply_event(&event(EntityKind::Link, b_c.id, ChangeType::Created, &b_c));
+    let spof = cache.single_points_of_failure(&datastore).await.unwrap();
+    assert_eq!(spof.nodes[0].name, "b");
+    cache.apply_event(&event(EntityKind::Link, a_c.id, ChangeType::Created, &a_c));
+    let report = cache
+        .impact(&datastore, FailureTarget::Node(b.id), &[a.id])
+        .await
+        .unwrap();
+    assert!(report.isolated_nodes.is_empty());
+
+    cache.apply_event(&event(
+        EntityKind::Link,
+        a_c.id,
+        ChangeType::Deleted,
+        &serde_json::Value::Null,
+    ));
+    let report = cache
+        .impact(&datastore, FailureTarget::Node(b.id), &[a.id])
+        .await
+        .unwrap();
+    assert_eq!(report.isolated_nodes[0].name, "c");
+
+    let status = cache.status();
+    assert_eq!(status.links, 2);
+    assert_eq!(status.applied_events, 3);
+    assert!(status.stale_reason.is_none());
+}
+
+#[tokio::test]
+async fn test_unreadable_event_marks_cache_stale() {
+    let cache = TopologyCache::default();
+    let a = node("a");
+    cache
+        .rebuild(&datastore(vec![a.clone()], Vec::new()))
+        .await
+        .unwrap();
+
+    cache.apply_event(&event(
+        EntityKind::Node,
+        a.id,
+        ChangeType::Updated,
+        &serde_json::json!({"name": "a"}),
+    ));
+
+    let reason = cache.status().stale_reason.unwrap();
+    assert!(reason.contains(&a.id.to_string()));
+}
+
+#[tokio::test]
+async fn test_staleness() {
+    let cache = TopologyCache::default();
+    assert_eq!(
+        cache.status().stale_reason.as_deref(),
+        Some("not built yet")
+    );
+
+    cache
+        .rebuild(&datastore(Vec::new(), Vec::new()))
+        .await
+        .unwrap();
+    assert!(cache.status().stale_reason.is_none());
+    cache.invalidate("rebuild requested");
+    assert_eq!(
+        cache.status().stale_reason.as_deref(),
+        Some("rebuild requested")
+    );
+
+    let uncached = TopologyCache::new(Duration::ZERO);
+    uncached
+        .rebuild(&datastore(Vec::new(), Vec::new()))
+        .await
+        .unwrap();
+    std::thread::sleep(Duration::from_millis(1));
+    assert!(uncached.status().stale_reason.is_some());
+}
@@ -0,0 +1,110 @@
+//! Link endpoint verification against node interfaces
+
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult};
+use crate::models::Link;
+
+/// Which end of a link an endpoint belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkSide {
+    /// Source endpoint (`node_a_interface`)
+    A,
+    /// Destination endpoint (`node_z_interface`)
+    Z,
+}
+
+impl Display for LinkSide {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::A => write!(f, "a"),
+            Self::Z => write!(f, "z"),
+        }
+    }
+}
+
+/// Reasons a link endpoint is rejected
+#[derive(Error, Debug)]
+pub enum EndpointError {
+    /// The endpoint node does not exist
+    #[error("Node {node_id} on side {side} does not exist")]
+    MissingNode {
+        /// Link side
+        side: LinkSide,
+        /// Missing node ID
+        node_id: Uuid,
+    },
+
+    /// The node has interface data but none matches the link interface
+    #[error("Interface {interface} on side {side} does not exist on node {node_id}")]
+    UnknownInterface {
+        /// Link side
+        side: LinkSide,
+        /// Node ID
+        node_id: Uuid,
+        /// Interface named by the link
+        interface: String,
+    },
+
+    /// Underlying datastore failure
+    #[error(transparent)]
+    DataStore(#[from] DataStoreError),
+}
+
+/// Returns the endpoints of a link as `(side, node, interface)` triples
+pub(super) fn endpoints(link: &Link) -> Vec<(LinkSide, Uuid, &str)> {
+    let mut endpoints = vec![(
+        LinkSide::A,
+        link.source_node_id,
+        link.node_a_interface.as_str(),
+    )];
+    if let (Some(node_id), Some(interface)) = (link.dest_node_id, link.node_z_interface.as_deref())
+    {
+        endpoints.push((LinkSide::Z, node_id, interface));
+    }
+    endpoints
+}
+
+/// Returns the set of known interface names for a node, or `None` if the
+/// node has no collected interface data
+pub(super) async fn known_interfaces(
+    datastore: &dyn DataStore,
+    node_id: &Uuid,
+) -> DataStoreResult<Option<HashSet<String>>> {
+    match datastore.get_node_interfaces(node_id).await {
+        Ok(interfaces) if interfaces.is_empty() => Ok(None),
+        Ok(interfaces) => Ok(Some(interfaces.into_iter().map(|i| i.name).collect())),
+        Err(DataStoreError::UnsupportedOperation { .. }) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Verifies that both endpoint nodes exist and, where interface data is
+/// available, that the named interfaces exist on them
+///
+/// # Errors
+/// Returns the first endpoint problem found, or a datastore error.
+pub async fn verify_link_endpoints(
+    datastore: &dyn DataStore,
+    link: &Link,
+) -> Result<(), EndpointError> {
+    for (side, node_id, interface) in endpoints(link) {
+        if datastore.get_node(&node_id).await?.is_none() {
+            return Err(EndpointError::MissingNode { side, node_id });
+        }
+        let known = known_interfaces(datastore, &node_id).await?;
+        if known.is_some_and(|known| !known.contains(interface)) {
+            return Err(EndpointError::UnknownInterface {
+                side,
+                node_id,
+                interface: interface.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
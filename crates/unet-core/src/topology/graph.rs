@@ -0,0 +1,161 @@
+//! Undirected link graph used by topology analyses
+
+use std::cmp::{Reverse, min};
+use std::collections::{HashMap, HashSet, VecDeque};
+use uuid::Uuid;
+
+use crate::models::{Link, Node};
+
+/// Undirected node adjacency, as `(neighbor, link)` pairs
+pub(super) struct Graph {
+    adjacency: HashMap<Uuid, Vec<(Uuid, Uuid)>>,
+}
+
+impl Graph {
+    pub(super) fn new(nodes: &[Node], links: &[Link]) -> Self {
+        let known: HashSet<Uuid> = nodes.iter().map(|node| node.id).collect();
+        let mut adjacency: HashMap<Uuid, Vec<(Uuid, Uuid)>> = HashMap::new();
+        for link in links {
+            let (a, Some(z)) = (link.source_node_id, link.dest_node_id) else {
+                continue;
+            };
+            if known.contains(&a) && known.contains(&z) {
+                adjacency.entry(a).or_default().push((z, link.id));
+                adjacency.entry(z).or_default().push((a, link.id));
+            }
+        }
+        Self { adjacency }
+    }
+
+    pub(super) fn neighbors(&self, id: Uuid) -> impl Iterator<Item = Uuid> + '_ {
+        self.adjacency
+            .get(&id)
+            .into_iter()
+            .flatten()
+            .map(|(neighbor, _)| *neighbor)
+    }
+
+    /// `(neighbor, link)` pairs for the links on `id`
+    pub(super) fn edges(&self, id: Uuid) -> &[(Uuid, Uuid)] {
+        self.adjacency.get(&id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Nodes reachable from `starts` without crossing the failed node or links
+    pub(super) fn reachable(
+        &self,
+        starts: impl IntoIterator<Item = Uuid>,
+        failed_node: Option<Uuid>,
+        failed_links: &HashSet<Uuid>,
+    ) -> HashSet<Uuid> {
+        let mut seen = HashSet::new();
+        let mut queue: VecDeque<Uuid> = starts
+            .into_iter()
+            .filter(|id| Some(*id) != failed_node && seen.insert(*id))
+            .collect();
+        while let Some(id) = queue.pop_front() {
+            for &(next, link) in self.adjacency.get(&id).into_iter().flatten() {
+                if Some(next) != failed_node && !failed_links.contains(&link) && seen.insert(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+        seen
+    }
+
+    /// Splits the network around `ends` after the failure; the largest part
+    /// stays connected and the others are isolated
+    pub(super) fn split_largest(
+        &self,
+        ends: &[Uuid],
+        failed_node: Option<Uuid>,
+        failed_links: &HashSet<Uuid>,
+    ) -> (HashSet<Uuid>, HashSet<Uuid>) {
+        let mut remaining = self.reachable(ends.iter().copied(), None, &HashSet::new());
+        remaining.retain(|id| Some(*id) != failed_node);
+        let mut parts = Vec::new();
+        // Smallest ID first so ties between equal parts resolve the same way every run
+        while let Some(&start) = remaining.iter().min() {
+            let part = self.reachable([start], failed_node, failed_links);
+            remaining.retain(|id| !part.contains(id));
+            parts.push(part);
+        }
+        let largest = parts
+            .iter()
+            .enumerate()
+            .max_by_key(|(index, part)| (part.len(), Reverse(*index)))
+            .map(|(index, _)| index);
+        let connected = largest.map(|index| parts.swap_remove(index));
+        (
+            parts.into_iter().flatten().collect(),
+            connected.unwrap_or_default(),
+        )
+    }
+
+    /// Nodes and links whose failure splits the part of the network they are
+    /// in: articulation points and bridges
+    ///
+    /// Depth-first search with an explicit stack, so long chains of nodes do
+    /// not overflow the call stack. The link a node was reached over is
+    /// skipped by ID rather than by neighbor, so parallel links are not bridges.
+    pub(super) fn cut_points(&self) -> (HashSet<Uuid>, HashSet<Uuid>) {
+        let mut discovered: HashMap<Uuid, usize> = HashMap::new();
+        let mut low: HashMap<Uuid, usize> = HashMap::new();
+        let mut cut_nodes = HashSet::new();
+        let mut bridges = HashSet::new();
+
+        let mut roots: Vec<Uuid> = self.adjacency.keys().copied().collect();
+        roots.sort_unstable();
+        for root in roots {
+            if discovered.contains_key(&root) {
+                continue;
+            }
+            discovered.insert(root, 0);
+            low.insert(root, 0);
+            let mut order = 1;
+            let mut root_children = 0;
+            // (node, link it was reached over, index of its next edge)
+            let mut stack: Vec<(Uuid, Option<Uuid>, usize)> = vec![(root, None, 0)];
+            while let Some(frame) = stack.last_mut() {
+                let (id, via) = (frame.0, frame.1);
+                let edge = self
+                    .adjacency
+                    .get(&id)
+                    .and_then(|edges| edges.get(frame.2))
+                    .copied();
+                frame.2 += 1;
+                if let Some((next, link)) = edge {
+                    if Some(link) == via {
+                        continue;
+                    }
+                    if let Some(&seen) = discovered.get(&next) {
+                        low.insert(id, min(low[&id], seen));
+                    } else {
+                        discovered.insert(next, order);
+                        low.insert(next, order);
+                        order += 1;
+                        stack.push((next, Some(link), 0));
+                    }
+                    continue;
+                }
+                stack.pop();
+                let Some(&(parent, _, _)) = stack.last() else {
+                    continue;
+                };
+                let reach = low[&id];
+                low.insert(parent, min(low[&parent], reach));
+                if reach > discovered[&parent] {
+                    bridges.extend(via);
+                }
+                if parent == root {
+                    root_children += 1;
+                } else if reach >= discovered[&parent] {
+                    cut_nodes.insert(parent);
+                }
+            }
+            if root_children > 1 {
+                cut_nodes.insert(root);
+            }
+        }
+        (cut_nodes, bridges)
+    }
+}
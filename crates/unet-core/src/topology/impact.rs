@@ -0,0 +1,333 @@
+//! Failure impact analysis for change planning
+//!
+//! Takes a node or link out of the topology and reports what loses its path
+//! upstream. Upstream is reached through the roots the caller names, else
+//! through the nodes terminating internet circuits. With neither, the largest
+//! part of the affected network that stays connected is taken as upstream.
+//! Nodes next to the failure that keep another path are reported as losing
+//! redundancy rather than as isolated.
+//!
+//! Customer tags are read from the `customers` key of node and link
+//! `custom_data`, either a string or a list of strings.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use uuid::Uuid;
+
+use super::graph::Graph;
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult, QueryOptions};
+use crate::models::{Link, Location, Node};
+
+/// `custom_data` key holding customer tags
+pub const CUSTOMERS_KEY: &str = "customers";
+
+/// Element taken out of service
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "id", rename_all = "lowercase")]
+pub enum FailureTarget {
+    /// A node and every link on it
+    Node(Uuid),
+    /// A single link
+    Link(Uuid),
+}
+
+/// How upstream was determined
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Upstream {
+    /// Roots named by the caller
+    Roots,
+    /// Nodes terminating internet circuits
+    InternetCircuits,
+    /// The largest part of the affected network that stays connected
+    LargestComponent,
+}
+
+/// A node in an impact report
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ImpactedNode {
+    /// Node ID
+    pub id: Uuid,
+    /// Node name
+    pub name: String,
+}
+
+/// A link in an impact report
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ImpactedLink {
+    /// Link ID
+    pub id: Uuid,
+    /// Link name
+    pub name: String,
+}
+
+/// A location with nodes that fail or are cut off
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ImpactedLocation {
+    /// Location ID
+    pub id: Uuid,
+    /// Location name
+    pub name: String,
+    /// Failed or isolated nodes at the location
+    pub affected_nodes: usize,
+    /// All nodes at the location
+    pub total_nodes: usize,
+}
+
+/// Everything affected when a node or link goes down
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ImpactReport {
+    /// Element taken out of service
+    pub target: FailureTarget,
+    /// How upstream was determined
+    pub upstream: Upstream,
+    /// Nodes cut off from upstream, excluding a failed node itself
+    pub isolated_nodes: Vec<ImpactedNode>,
+    /// Nodes next to the failure that stay connected over another path
+    pub redundant_nodes: Vec<ImpactedNode>,
+    /// Links that go down with the target
+    pub failed_links: Vec<ImpactedLink>,
+    /// Other links touching an isolated node
+    pub isolated_links: Vec<ImpactedLink>,
+    /// Locations with failed or isolated nodes
+    pub locations: Vec<ImpactedLocation>,
+    /// Customer tags of the failed and isolated nodes and links
+    pub customers: Vec<String>,
+}
+
+/// Loads the topology and reports the impact of `target` going down
+///
+/// # Errors
+/// Returns a not-found error if the target does not exist, or an error if
+/// nodes, links, or locations cannot be read.
+pub async fn analyze_impact(
+    datastore: &dyn DataStore,
+    target: FailureTarget,
+    roots: &[Uuid],
+) -> DataStoreResult<ImpactReport> {
+    let options = QueryOptions::default();
+    let nodes = datastore.list_nodes(&options).await?.items;
+    let links = datastore.list_links(&options).await?.items;
+    let locations = datastore.list_locations(&options).await?.items;
+    impact(target, roots, &nodes, &links, &locations)
+}
+
+/// Reports the impact of `target` going down in the given topology
+///
+/// # Errors
+/// Returns a not-found error if the target does not exist.
+pub fn impact(
+    target: FailureTarget,
+    roots: &[Uuid],
+    nodes: &[Node],
+    links: &[Link],
+    locations: &[Location],
+) -> DataStoreResult<ImpactReport> {
+    let Failure {
+        node: failed_node,
+        links: failed_links,
+        ends,
+    } = Failure::of(target, nodes, links)?;
+
+    let graph = Graph::new(nodes, links);
+    let circuit_roots = |skip: &HashSet<Uuid>| -> Vec<Uuid> {
+        links
+            .iter()
+            .filter(|link| link.is_internet_circuit && !skip.contains(&link.id))
+            .map(|link| link.source_node_id)
+            .collect()
+    };
+    let upstream = if !roots.is_empty() {
+        Upstream::Roots
+    } else if circuit_roots(&HashSet::new()).is_empty() {
+        Upstream::LargestComponent
+    } else {
+        Upstream::InternetCircuits
+    };
+
+    let (isolated, connected) = match upstream {
+        Upstream::LargestComponent => graph.split_largest(&ends, failed_node, &failed_links),
+        Upstream::Roots | Upstream::InternetCircuits => {
+            let (before_roots, after_roots) = if upstream == Upstream::Roots {
+                (roots.to_vec(), roots.to_vec())
+            } else {
+                (circuit_roots(&HashSet::new()), circuit_roots(&failed_links))
+            };
+            let before = graph.reachable(before_roots, None, &HashSet::new());
+            let after = graph.reachable(after_roots, failed_node, &failed_links);
+            let isolated = before
+                .into_iter()
+                .filter(|id| Some(*id) != failed_node && !after.contains(id))
+                .collect();
+            (isolated, after)
+        }
+    };
+
+    let neighbors: HashSet<Uuid> = match failed_node {
+        Some(id) => graph.neighbors(id).collect(),
+        None => ends.into_iter().collect(),
+    };
+    let isolated_links: Vec<&Link> = links
+        .iter()
+        .filter(|link| !failed_links.contains(&link.id))
+        .filter(|link| {
+            isolated.contains(&link.source_node_id)
+                || link.dest_node_id.is_some_and(|id| isolated.contains(&id))
+        })
+        .collect();
+    let failed: Vec<&Link> = links
+        .iter()
+        .filter(|link| failed_links.contains(&link.id))
+        .collect();
+    let affected: HashSet<Uuid> = isolated.iter().copied().chain(failed_node).collect();
+    let affected_nodes: Vec<&Node> = nodes
+        .iter()
+        .filter(|node| affected.contains(&node.id))
+        .collect();
+
+    let customers: BTreeSet<String> = affected_nodes
+        .iter()
+        .map(|node| &node.custom_data)
+        .chain(
+            failed
+                .iter()
+                .chain(&isolated_links)
+                .map(|link| &link.custom_data),
+        )
+        .flat_map(customer_tags)
+        .collect();
+
+    Ok(ImpactReport {
+        target,
+        upstream,
+        isolated_nodes: node_list(nodes, |id| isolated.contains(&id)),
+        redundant_nodes: node_list(nodes, |id| {
+            neighbors.contains(&id) && Some(id) != failed_node && connected.contains(&id)
+        }),
+        failed_links: link_list(&failed),
+        isolated_links: link_list(&isolated_links),
+        locations: location_list(&affected_nodes, nodes, locations),
+        customers: customers.into_iter().collect(),
+    })
+}
+
+/// What goes down with a failure target
+struct Failure {
+    /// Failed node, if the target is a node
+    node: Option<Uuid>,
+    /// Failed links
+    links: HashSet<Uuid>,
+    /// Nodes the failure starts from: the node, or the link's endpoints
+    ends: Vec<Uuid>,
+}
+
+impl Failure {
+    fn of(target: FailureTarget, nodes: &[Node], links: &[Link]) -> DataStoreResult<Self> {
+        match target {
+            FailureTarget::Node(id) => {
+                if !nodes.iter().any(|node| node.id == id) {
+                    return Err(not_found("Node", id));
+                }
+                Ok(Self {
+                    node: Some(id),
+                    links: links
+                        .iter()
+                        .filter(|link| touches(link, id))
+                        .map(|link| link.id)
+                        .collect(),
+                    ends: vec![id],
+                })
+            }
+            FailureTarget::Link(id) => {
+                let link = links
+                    .iter()
+                    .find(|link| link.id == id)
+                    .ok_or_else(|| not_found("Link", id))?;
+                Ok(Self {
+                    node: None,
+                    links: HashSet::from([id]),
+                    ends: std::iter::once(link.source_node_id)
+                        .chain(link.dest_node_id)
+                        .collect(),
+                })
+            }
+        }
+    }
+}
+
+fn touches(link: &Link, node_id: Uuid) -> bool {
+    link.source_node_id == node_id || link.dest_node_id == Some(node_id)
+}
+
+fn customer_tags(custom_data: &Value) -> Vec<String> {
+    match custom_data.get(CUSTOMERS_KEY) {
+        Some(Value::String(customer)) => vec![customer.clone()],
+        Some(Value::Array(customers)) => customers
+            .iter()
+            .filter_map(Value::as_str)
+            .map(ToString::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+pub(super) fn node_list(nodes: &[Node], include: impl Fn(Uuid) -> bool) -> Vec<ImpactedNode> {
+    let mut list: Vec<ImpactedNode> = nodes
+        .iter()
+        .filter(|node| include(node.id))
+        .map(|node| ImpactedNode {
+            id: node.id,
+            name: node.name.clone(),
+        })
+        .collect();
+    list.sort_by(|a, b| a.name.cmp(&b.name));
+    list
+}
+
+pub(super) fn link_list(links: &[&Link]) -> Vec<ImpactedLink> {
+    let mut list: Vec<ImpactedLink> = links
+        .iter()
+        .map(|link| ImpactedLink {
+            id: link.id,
+            name: link.name.clone(),
+        })
+        .collect();
+    list.sort_by(|a, b| a.name.cmp(&b.name));
+    list
+}
+
+fn location_list(
+    affected: &[&Node],
+    nodes: &[Node],
+    locations: &[Location],
+) -> Vec<ImpactedLocation> {
+    let mut counts: HashMap<Uuid, usize> = HashMap::new();
+    for location_id in affected.iter().filter_map(|node| node.location_id) {
+        *counts.entry(location_id).or_default() += 1;
+    }
+    let mut list: Vec<ImpactedLocation> = counts
+        .into_iter()
+        .map(|(id, affected_nodes)| ImpactedLocation {
+            id,
+            name: locations
+                .iter()
+                .find(|location| location.id == id)
+                .map_or_else(|| id.to_string(), |location| location.name.clone()),
+            affected_nodes,
+            total_nodes: nodes
+                .iter()
+                .filter(|node| node.location_id == Some(id))
+                .count(),
+        })
+        .collect();
+    list.sort_by(|a, b| a.name.cmp(&b.name));
+    list
+}
+
+fn not_found(entity_type: &str, id: Uuid) -> DataStoreError {
+    DataStoreError::NotFound {
+        entity_type: entity_type.to_string(),
+        id: id.to_string(),
+    }
+}
@@ -0,0 +1,138 @@
+use super::*;
+use crate::datastore::DataStoreError;
+use crate::models::{DeviceRole, Link, Location, Node, Vendor};
+use serde_json::json;
+use uuid::Uuid;
+
+fn node(name: &str) -> Node {
+    Node::new(
+        name.to_string(),
+        "example.com".to_string(),
+        Vendor::Cisco,
+        DeviceRole::Router,
+    )
+}
+
+fn link(a: &Node, z: &Node) -> Link {
+    Link::new(
+        format!("{}-{}", a.name, z.name),
+        a.id,
+        "eth0".to_string(),
+        z.id,
+        "eth0".to_string(),
+    )
+}
+
+fn names(nodes: &[ImpactedNode]) -> Vec<&str> {
+    nodes.iter().map(|node| node.name.as_str()).collect()
+}
+
+/// `core` has the internet circuit and two distribution nodes; `access` is
+/// dual-homed to both, `leaf` hangs off `dist1` alone
+struct Campus {
+    nodes: Vec<Node>,
+    links: Vec<Link>,
+    locations: Vec<Location>,
+}
+
+fn campus() -> Campus {
+    let site = Location::new_root("Site A".to_string(), "building".to_string());
+    let [core, dist1, dist2, access, mut leaf] =
+        ["core", "dist1", "dist2", "access", "leaf"].map(node);
+    leaf.location_id = Some(site.id);
+    leaf.custom_data = json!({ "customers": ["acme"] });
+    let mut links = vec![
+        link(&core, &dist1),
+        link(&core, &dist2),
+        link(&dist1, &access),
+        link(&dist2, &access),
+        link(&dist1, &leaf),
+    ];
+    links[4].custom_data = json!({ "customers": "globex" });
+    links.push(Link::new_internet_circuit(
+        "core-isp".to_string(),
+        core.id,
+        "eth9".to_string(),
+    ));
+    Campus {
+        nodes: vec![core, dist1, dist2, access, leaf],
+        links,
+        locations: vec![site],
+    }
+}
+
+#[test]
+fn test_node_failure_isolates_single_homed_nodes_only() {
+    let campus = campus();
+    let dist1 = campus.nodes[1].id;
+
+    let report = impact(
+        FailureTarget::Node(dist1),
+        &[],
+        &campus.nodes,
+        &campus.links,
+        &campus.locations,
+    )
+    .unwrap();
+
+    assert_eq!(report.upstream, Upstream::InternetCircuits);
+    assert_eq!(names(&report.isolated_nodes), ["leaf"]);
+    assert_eq!(names(&report.redundant_nodes), ["access", "core"]);
+    assert_eq!(report.failed_links.len(), 3);
+    assert!(report.isolated_links.is_empty());
+    assert_eq!(report.locations.len(), 1);
+    assert_eq!(report.locations[0].name, "Site A");
+    assert_eq!(report.customers, ["acme", "globex"]);
+}
+
+#[test]
+fn test_link_failure_reports_endpoints() {
+    let campus = campus();
+    let redundant = campus.links[2].id;
+
+    let report = impact(
+        FailureTarget::Link(redundant),
+        &[],
+        &campus.nodes,
+        &campus.links,
+        &campus.locations,
+    )
+    .unwrap();
+
+    assert!(report.isolated_nodes.is_empty());
+    assert_eq!(names(&report.redundant_nodes), ["access", "dist1"]);
+    assert_eq!(report.failed_links[0].name, "dist1-access");
+    assert!(report.customers.is_empty());
+}
+
+#[test]
+fn test_explicit_roots_and_largest_component_fallback() {
+    let [a, b, c, d] = ["a", "b", "c", "d"].map(node);
+    let links = vec![link(&a, &b), link(&b, &c), link(&c, &d)];
+    let (root, failed) = (a.id, b.id);
+    let nodes = vec![a, b, c, d];
+
+    let fallback = impact(FailureTarget::Node(failed), &[], &nodes, &links, &[]).unwrap();
+    assert_eq!(fallback.upstream, Upstream::LargestComponent);
+    assert_eq!(names(&fallback.isolated_nodes), ["a"]);
+
+    let rooted = impact(
+        FailureTarget::Link(links[2].id),
+        &[root],
+        &nodes,
+        &links,
+        &[],
+    )
+    .unwrap();
+    assert_eq!(rooted.upstream, Upstream::Roots);
+    assert_eq!(names(&rooted.isolated_nodes), ["d"]);
+    assert!(rooted.isolated_links.is_empty());
+    assert_eq!(names(&rooted.redundant_nodes), ["c"]);
+}
+
+#[test]
+fn test_unknown_target_is_not_found() {
+    let result = impact(FailureTarget::Node(Uuid::new_v4()), &[], &[], &[], &[]);
+
+    assert!(matches!(result, Err(DataStoreError::NotFound { .. })));
+}
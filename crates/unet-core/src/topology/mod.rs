@@ -0,0 +1,32 @@
+//! Topology integrity checks, failure impact analysis, single points of
+//! failure, and paths between nodes built on the `DataStore`
+//!
+//! Links name an interface on each endpoint node. When interface data has been
+//! collected for a node (see [`DataStore::get_node_interfaces`]), these checks
+//! confirm that the named interface exists. Nodes without collected interface
+//! data are not checked, since there is nothing to compare against.
+//!
+//! [`DataStore::get_node_interfaces`]: crate::datastore::DataStore::get_node_interfaces
+
+mod audit;
+mod cache;
+mod endpoints;
+mod graph;
+mod impact;
+mod path;
+mod spof;
+
+pub use audit::{LinkAuditReport, LinkIssue, LinkIssueKind, audit_links};
+pub use cache::{TopologyCache, TopologyCacheStatus};
+pub use endpoints::{EndpointError, LinkSide, verify_link_endpoints};
+pub use impact::{
+    CUSTOMERS_KEY, FailureTarget, ImpactReport, ImpactedLink, ImpactedLocation, ImpactedNode,
+    Upstream, analyze_impact, impact,
+};
+pub use path::{PathHop, PathReport, find_path};
+pub use spof::{SpofReport, single_points_of_failure};
+
+#[cfg(test)]
+mod impact_tests;
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,238 @@
+//! Layer 1 path between two nodes
+//!
+//! The path taken is the one crossing the fewest links. When several tie, the
+//! one whose narrowest link is widest wins, so the reported bottleneck is the
+//! best those routes can offer. A link without a known bandwidth counts as
+//! narrower than any link with one.
+
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use uuid::Uuid;
+
+use super::graph::Graph;
+use super::impact::{ImpactedLink, ImpactedNode};
+use crate::models::{Link, Node};
+
+/// A link crossed on a path, in the direction of travel
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PathHop {
+    /// Node the hop leaves
+    pub from: ImpactedNode,
+    /// Interface on `from`
+    pub from_interface: String,
+    /// Link crossed
+    pub link: ImpactedLink,
+    /// Node the hop arrives at
+    pub to: ImpactedNode,
+    /// Interface on `to`
+    pub to_interface: String,
+    /// Link bandwidth in bits per second, if known
+    pub bandwidth: Option<u64>,
+}
+
+/// The chain of links and nodes between two nodes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PathReport {
+    /// Node the path starts at
+    pub from: ImpactedNode,
+    /// Node the path ends at
+    pub to: ImpactedNode,
+    /// Links crossed, in order
+    pub hops: Vec<PathHop>,
+    /// Lowest bandwidth along the path in bits per second; unset when a link's
+    /// bandwidth is unknown or the path crosses no links
+    pub bottleneck: Option<u64>,
+    /// Link limiting the path, the first unknown one if any
+    pub bottleneck_link: Option<ImpactedLink>,
+}
+
+/// How the search first reached a node
+#[derive(Clone, Copy)]
+struct Reached {
+    depth: usize,
+    width: Option<u64>,
+    via: Option<(Uuid, Uuid)>,
+}
+
+/// Finds the path from `from` to `to` in the given topology
+///
+/// Returns `None` if either node is not in `nodes` or no links connect them.
+#[must_use]
+pub fn find_path(nodes: &[Node], links: &[Link], from: Uuid, to: Uuid) -> Option<PathReport> {
+    let nodes_by_id: HashMap<Uuid, &Node> = nodes.iter().map(|node| (node.id, node)).collect();
+    if !nodes_by_id.contains_key(&from) || !nodes_by_id.contains_key(&to) {
+        return None;
+    }
+    let links_by_id: HashMap<Uuid, &Link> = links.iter().map(|link| (link.id, link)).collect();
+    let named = |id: Uuid| ImpactedNode {
+        id,
+        name: nodes_by_id[&id].name.clone(),
+    };
+
+    // Nodes are taken in order of depth, so every way into a node has been
+    // weighed by the time the search moves past it
+    let graph = Graph::new(nodes, links);
+    let mut reached = HashMap::from([(
+        from,
+        Reached {
+            depth: 0,
+            width: Some(u64::MAX),
+            via: None,
+        },
+    )]);
+    let mut queue = VecDeque::from([from]);
+    while let Some(id) = queue.pop_front() {
+        if id == to {
+            break;
+        }
+        let Reached { depth, width, .. } = reached[&id];
+        for &(next, link) in graph.edges(id) {
+            let width = width.min(links_by_id[&link].bandwidth);
+            match reached.get_mut(&next) {
+                None => {
+                    reached.insert(
+                        next,
+                        Reached {
+                            depth: depth + 1,
+                            width,
+                            via: Some((id, link)),
+                        },
+                    );
+                    queue.push_back(next);
+                }
+                Some(seen) if seen.depth == depth + 1 && width > seen.width => {
+                    seen.width = width;
+                    seen.via = Some((id, link));
+                }
+                Some(_) => {}
+            }
+        }
+    }
+
+    let mut steps = Vec::new();
+    let mut at = to;
+    while let Some((previous, link)) = reached.get(&at)?.via {
+        steps.push((previous, link, at));
+        at = previous;
+    }
+    steps.reverse();
+
+    let hops: Vec<PathHop> = steps
+        .into_iter()
+        .map(|(a, link_id, z)| {
+            let link = links_by_id[&link_id];
+            let z_interface = link.node_z_interface.clone().unwrap_or_default();
+            let (from_interface, to_interface) = if link.source_node_id == a {
+                (link.node_a_interface.clone(), z_interface)
+            } else {
+                (z_interface, link.node_a_interface.clone())
+            };
+            PathHop {
+                from: named(a),
+                from_interface,
+                link: ImpactedLink {
+                    id: link.id,
+                    name: link.name.clone(),
+                },
+                to: named(z),
+                to_interface,
+                bandwidth: link.bandwidth,
+            }
+        })
+        .collect();
+    let narrowest = hops.iter().min_by_key(|hop| hop.bandwidth);
+    Some(PathReport {
+        from: named(from),
+        to: named(to),
+        bottleneck: narrowest.and_then(|hop| hop.bandwidth),
+        bottleneck_link: narrowest.map(|hop| hop.link.clone()),
+        hops,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DeviceRole, Vendor};
+
+    fn node(name: &str) -> Node {
+        Node::new(
+            name.to_string(),
+            "example.com".to_string(),
+            Vendor::Cisco,
+            DeviceRole::Router,
+        )
+    }
+
+    fn link(a: &Node, z: &Node, name: &str, bandwidth: Option<u64>) -> Link {
+        let mut link = Link::new(
+            name.to_string(),
+            a.id,
+            "eth0".to_string(),
+            z.id,
+            "eth1".to_string(),
+        );
+        link.bandwidth = bandwidth;
+        link
+    }
+
+    fn link_names(report: &PathReport) -> Vec<&str> {
+        report
+            .hops
+            .iter()
+            .map(|hop| hop.link.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_prefers_fewest_links_then_widest() {
+        // a to d over b or c, both two links; c's side is wider. The way round
+        // through e and f is wider still but crosses more links.
+        let [a, b, c, d, e, f] = ["a", "b", "c", "d", "e", "f"].map(node);
+        let links = vec![
+            link(&a, &b, "a-b", Some(10_000_000_000)),
+            link(&b, &d, "b-d", Some(1_000_000_000)),
+            link(&a, &c, "a-c", Some(10_000_000_000)),
+            link(&d, &c, "d-c", Some(10_000_000_000)),
+            link(&a, &e, "a-e", Some(100_000_000_000)),
+            link(&e, &f, "e-f", Some(100_000_000_000)),
+            link(&f, &d, "f-d", Some(100_000_000_000)),
+        ];
+        let nodes = vec![a.clone(), b, c, d.clone(), e, f];
+
+        let report = find_path(&nodes, &links, a.id, d.id).unwrap();
+        assert_eq!(link_names(&report), ["a-c", "d-c"]);
+        assert_eq!(report.bottleneck, Some(10_000_000_000));
+        // d-c is crossed from its z end
+        assert_eq!(report.hops[1].from.name, "c");
+        assert_eq!(report.hops[1].from_interface, "eth1");
+        assert_eq!(report.hops[1].to_interface, "eth0");
+    }
+
+    #[test]
+    fn test_unknown_bandwidth_leaves_bottleneck_unset() {
+        let [a, b, c] = ["a", "b", "c"].map(node);
+        let links = vec![
+            link(&a, &b, "a-b", Some(1_000_000_000)),
+            link(&b, &c, "b-c", None),
+        ];
+        let nodes = vec![a.clone(), b, c.clone()];
+
+        let report = find_path(&nodes, &links, a.id, c.id).unwrap();
+        assert_eq!(report.bottleneck, None);
+        assert_eq!(report.bottleneck_link.unwrap().name, "b-c");
+    }
+
+    #[test]
+    fn test_no_path() {
+        let [a, b, c] = ["a", "b", "c"].map(node);
+        let links = vec![link(&a, &b, "a-b", Some(1_000_000_000))];
+        let nodes = vec![a.clone(), b, c.clone()];
+
+        assert!(find_path(&nodes, &links, a.id, c.id).is_none());
+        assert!(find_path(&nodes, &links, a.id, Uuid::new_v4()).is_none());
+        let to_self = find_path(&nodes, &links, a.id, a.id).unwrap();
+        assert!(to_self.hops.is_empty());
+        assert_eq!(to_self.bottleneck_link, None);
+    }
+}
@@ -0,0 +1,96 @@
+//! Single points of failure
+//!
+//! A node is a single point of failure if taking it down splits the part of
+//! the network it is in, and a link is one if it is the only path between two
+//! parts. Unlike impact analysis this does not depend on where upstream is:
+//! every split is reported. Parallel links between the same two nodes back
+//! each other up, so neither is reported.
+
+use serde::Serialize;
+
+use super::graph::Graph;
+use super::impact::{ImpactedLink, ImpactedNode, link_list, node_list};
+use crate::models::{Link, Node};
+
+/// Nodes and links whose failure splits the network
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SpofReport {
+    /// Nodes whose failure splits the network, by name
+    pub nodes: Vec<ImpactedNode>,
+    /// Links whose failure splits the network, by name
+    pub links: Vec<ImpactedLink>,
+}
+
+/// Finds the single points of failure in the given topology
+#[must_use]
+pub fn single_points_of_failure(nodes: &[Node], links: &[Link]) -> SpofReport {
+    let (cut_nodes, bridges) = Graph::new(nodes, links).cut_points();
+    let bridge_links: Vec<&Link> = links
+        .iter()
+        .filter(|link| bridges.contains(&link.id))
+        .collect();
+    SpofReport {
+        nodes: node_list(nodes, |id| cut_nodes.contains(&id)),
+        links: link_list(&bridge_links),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DeviceRole, Vendor};
+
+    fn node(name: &str) -> Node {
+        Node::new(
+            name.to_string(),
+            "example.com".to_string(),
+            Vendor::Cisco,
+            DeviceRole::Router,
+        )
+    }
+
+    fn link(a: &Node, z: &Node, name: &str) -> Link {
+        Link::new(
+            name.to_string(),
+            a.id,
+            "eth0".to_string(),
+            z.id,
+            "eth1".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_ring_with_spur() {
+        // a-b-c ring, with d hanging off c over two parallel links and e off d
+        let [a, b, c, d, e] = ["a", "b", "c", "d", "e"].map(node);
+        let links = vec![
+            link(&a, &b, "a-b"),
+            link(&b, &c, "b-c"),
+            link(&c, &a, "c-a"),
+            link(&c, &d, "c-d-1"),
+            link(&c, &d, "c-d-2"),
+            link(&d, &e, "d-e"),
+        ];
+        let nodes = vec![a, b, c, d, e];
+
+        let report = single_points_of_failure(&nodes, &links);
+
+        let names: Vec<&str> = report.nodes.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(names, ["c", "d"]);
+        let links: Vec<&str> = report.links.iter().map(|l| l.name.as_str()).collect();
+        assert_eq!(links, ["d-e"]);
+    }
+
+    #[test]
+    fn test_chain_root_is_not_a_cut_node() {
+        let [a, b, c] = ["a", "b", "c"].map(node);
+        let links = vec![link(&a, &b, "a-b"), link(&b, &c, "b-c")];
+        let nodes = vec![a, b, c];
+
+        let report = single_points_of_failure(&nodes, &links);
+
+        assert_eq!(report.nodes.len(), 1);
+        assert_eq!(report.nodes[0].name, "b");
+        assert_eq!(report.links.len(), 2);
+    }
+}
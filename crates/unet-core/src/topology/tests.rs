@@ -0,0 +1,183 @@
+use super::*;
+use crate::datastore::{DataStoreError, MockDataStore, PagedResult};
+use crate::models::derived::{
+    InterfaceAdminStatus, InterfaceOperStatus, InterfaceStats, InterfaceStatus,
+};
+use crate::models::{DeviceRole, Link, Node, Vendor};
+
+fn node(name: &str) -> Node {
+    Node::new(
+        name.to_string(),
+        "example.com".to_string(),
+        Vendor::Cisco,
+        DeviceRole::Router,
+    )
+}
+
+fn interface(name: &str) -> InterfaceStatus {
+    InterfaceStatus {
+        index: 1,
+        name: name.to_string(),
+        interface_type: 6,
+        mtu: None,
+        speed: None,
+        physical_address: None,
+        admin_status: InterfaceAdminStatus::Up,
+        oper_status: InterfaceOperStatus::Up,
+        last_change: None,
+        input_stats: InterfaceStats::default(),
+        output_stats: InterfaceStats::default(),
+    }
+}
+
+fn link(name: &str, a: &Node, a_if: &str, z: &Node, z_if: &str) -> Link {
+    Link::new(
+        name.to_string(),
+        a.id,
+        a_if.to_string(),
+        z.id,
+        z_if.to_string(),
+    )
+}
+
+/// Mock where only the first node has collected interface data
+fn mock_with(
+    nodes: Vec<Node>,
+    links: Vec<Link>,
+    first_node_interfaces: Vec<&str>,
+) -> MockDataStore {
+    let first_id = nodes[0].id;
+    let known: Vec<InterfaceStatus> = first_node_interfaces.into_iter().map(interface).collect();
+    let mut mock = MockDataStore::new();
+
+    let lookup = nodes.clone();
+    mock.expect_get_node().returning(move |id| {
+        let found = lookup.iter().find(|n| n.id == *id).cloned();
+        Box::pin(async move { Ok(found) })
+    });
+    mock.expect_list_nodes().returning(move |_| {
+        let nodes = nodes.clone();
+        Box::pin(async move { Ok(PagedResult::new(nodes.clone(), nodes.len(), None)) })
+    });
+    mock.expect_list_links().returning(move |_| {
+        let links = links.clone();
+        Box::pin(async move { Ok(PagedResult::new(links.clone(), links.len(), None)) })
+    });
+    mock.expect_get_node_interfaces().returning(move |id| {
+        let result = if *id == first_id {
+            known.clone()
+        } else {
+            Vec::new()
+        };
+        Box::pin(async move { Ok(result) })
+    });
+    mock
+}
+
+#[tokio::test]
+async fn test_verify_accepts_known_interfaces() {
+    let (a, z) = (node("a"), node("z"));
+    let candidate = link("a-z", &a, "Gi0/1", &z, "Gi0/2");
+    let mock = mock_with(vec![a, z], vec![], vec!["Gi0/1"]);
+
+    assert!(verify_link_endpoints(&mock, &candidate).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_verify_rejects_unknown_interface() {
+    let (a, z) = (node("a"), node("z"));
+    let candidate = link("a-z", &a, "Gi0/9", &z, "Gi0/2");
+    let mock = mock_with(vec![a, z], vec![], vec!["Gi0/1"]);
+
+    let result = verify_link_endpoints(&mock, &candidate).await;
+
+    assert!(matches!(
+        result,
+        Err(EndpointError::UnknownInterface {
+            side: LinkSide::A,
+            ..
+        })
+    ));
+}
+
+#[tokio::test]
+async fn test_verify_rejects_missing_node() {
+    let (a, z) = (node("a"), node("z"));
+    let candidate = link("a-z", &a, "Gi0/1", &z, "Gi0/2");
+    let mock = mock_with(vec![a], vec![], vec!["Gi0/1"]);
+
+    let result = verify_link_endpoints(&mock, &candidate).await;
+
+    assert!(matches!(
+        result,
+        Err(EndpointError::MissingNode {
+            side: LinkSide::Z,
+            ..
+        })
+    ));
+}
+
+#[tokio::test]
+async fn test_verify_skips_when_interfaces_unsupported() {
+    let a = node("a");
+    let candidate = Link::new_internet_circuit("uplink".to_string(), a.id, "eth0".to_string());
+    let lookup = a.clone();
+    let mut mock = MockDataStore::new();
+    mock.expect_get_node().returning(move |_| {
+        let found = Some(lookup.clone());
+        Box::pin(async move { Ok(found) })
+    });
+    mock.expect_get_node_interfaces().returning(|_| {
+        Box::pin(async {
+            Err(DataStoreError::UnsupportedOperation {
+                operation: "get_node_interfaces".to_string(),
+            })
+        })
+    });
+
+    assert!(verify_link_endpoints(&mock, &candidate).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_audit_reports_dangling_and_duplicate_links() {
+    let (a, z, gone) = (node("a"), node("z"), node("gone"));
+    let good = link("good", &a, "Gi0/1", &z, "Gi0/1");
+    let duplicate = link("dup", &a, "Gi0/1", &z, "Gi0/2");
+    let unknown = link("unknown", &a, "Gi0/7", &z, "Gi0/3");
+    let dangling = link("dangling", &a, "Gi0/2", &gone, "Gi0/1");
+    let mock = mock_with(
+        vec![a.clone(), z],
+        vec![good, duplicate.clone(), unknown.clone(), dangling.clone()],
+        vec!["Gi0/1", "Gi0/2"],
+    );
+
+    let report = audit_links(&mock).await.unwrap();
+
+    assert_eq!(report.links_checked, 4);
+    assert_eq!(report.issues.len(), 3);
+    assert_eq!(report.issues[0].link_id, duplicate.id);
+    assert!(matches!(
+        report.issues[0].kind,
+        LinkIssueKind::DuplicateEndpoint { .. }
+    ));
+    assert_eq!(report.issues[1].link_id, unknown.id);
+    assert!(matches!(
+        report.issues[1].kind,
+        LinkIssueKind::UnknownInterface { .. }
+    ));
+    assert_eq!(report.issues[2].link_id, dangling.id);
+    assert_eq!(report.issues[2].side, LinkSide::Z);
+    assert!(matches!(
+        report.issues[2].kind,
+        LinkIssueKind::MissingNode { node_id } if node_id == gone.id
+    ));
+}
+
+#[tokio::test]
+async fn test_audit_of_empty_inventory_is_clean() {
+    let mock = mock_with(vec![node("a")], vec![], vec![]);
+    let report = audit_links(&mock).await.unwrap();
+
+    assert_eq!(report.links_checked, 0);
+    assert!(report.issues.is_empty());
+}
@@ -0,0 +1,181 @@
+//! Link VLAN consistency checks
+//!
+//! Both ends of a link must carry the same VLANs: the same mode, the same
+//! access VLAN, or the same trunk allowed list and native VLAN. Links where
+//! either end has no membership, and internet circuits, are not checked.
+
+use super::{InterfaceVlans, VlanMembership, interface_vlans};
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult, QueryOptions};
+use crate::models::Link;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::{BTreeSet, HashMap, hash_map::Entry};
+use uuid::Uuid;
+
+/// How the two ends of a link disagree
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MismatchKind {
+    /// One end is an access port, the other a trunk
+    Mode,
+    /// Access ports in different VLANs
+    AccessVlan {
+        /// VLAN on the A end
+        a: u16,
+        /// VLAN on the Z end
+        z: u16,
+    },
+    /// Trunks allowing different VLANs
+    AllowedVlans {
+        /// VLANs allowed only on the A end
+        only_a: BTreeSet<u16>,
+        /// VLANs allowed only on the Z end
+        only_z: BTreeSet<u16>,
+    },
+    /// Trunks with different native VLANs
+    NativeVlan {
+        /// Native VLAN on the A end
+        a: Option<u16>,
+        /// Native VLAN on the Z end
+        z: Option<u16>,
+    },
+}
+
+/// A link whose ends disagree on VLANs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VlanMismatch {
+    /// Link ID
+    pub link_id: Uuid,
+    /// Link name
+    pub link: String,
+    /// A end node
+    pub node_a_id: Uuid,
+    /// A end interface
+    pub interface_a: String,
+    /// Z end node
+    pub node_z_id: Uuid,
+    /// Z end interface
+    pub interface_z: String,
+    /// What differs
+    #[serde(flatten)]
+    pub kind: MismatchKind,
+}
+
+/// Compares the memberships at the two ends of a link
+fn compare(a: &VlanMembership, z: &VlanMembership) -> Vec<MismatchKind> {
+    match (a, z) {
+        (VlanMembership::Access { vlan: a }, VlanMembership::Access { vlan: z }) => {
+            if a == z {
+                Vec::new()
+            } else {
+                vec![MismatchKind::AccessVlan { a: *a, z: *z }]
+            }
+        }
+        (
+            VlanMembership::Trunk {
+                allowed: allowed_a,
+                native: native_a,
+            },
+            VlanMembership::Trunk {
+                allowed: allowed_z,
+                native: native_z,
+            },
+        ) => {
+            let mut kinds = Vec::new();
+            if allowed_a != allowed_z {
+                kinds.push(MismatchKind::AllowedVlans {
+                    only_a: allowed_a.difference(allowed_z).copied().collect(),
+                    only_z: allowed_z.difference(allowed_a).copied().collect(),
+                });
+            }
+            if native_a != native_z {
+                kinds.push(MismatchKind::NativeVlan {
+                    a: *native_a,
+                    z: *native_z,
+                });
+            }
+            kinds
+        }
+        _ => vec![MismatchKind::Mode],
+    }
+}
+
+/// Checks links against the interface memberships of their nodes
+///
+/// # Errors
+/// Returns an error if memberships cannot be read.
+pub async fn check_links(
+    datastore: &dyn DataStore,
+    links: &[Link],
+) -> DataStoreResult<Vec<VlanMismatch>> {
+    let mut memberships: HashMap<Uuid, InterfaceVlans> = HashMap::new();
+    let mut mismatches = Vec::new();
+    for link in links {
+        let (Some(node_z_id), Some(interface_z)) = (link.dest_node_id, &link.node_z_interface)
+        else {
+            continue;
+        };
+        for node_id in [link.source_node_id, node_z_id] {
+            if let Entry::Vacant(entry) = memberships.entry(node_id) {
+                entry.insert(interface_vlans(datastore, node_id).await?);
+            }
+        }
+        let a = memberships
+            .get(&link.source_node_id)
+            .and_then(|vlans| vlans.get(&link.node_a_interface));
+        let z = memberships
+            .get(&node_z_id)
+            .and_then(|vlans| vlans.get(interface_z));
+        let (Some(a), Some(z)) = (a, z) else {
+            continue;
+        };
+        mismatches.extend(compare(a, z).into_iter().map(|kind| VlanMismatch {
+            link_id: link.id,
+            link: link.name.clone(),
+            node_a_id: link.source_node_id,
+            interface_a: link.node_a_interface.clone(),
+            node_z_id,
+            interface_z: interface_z.clone(),
+            kind,
+        }));
+    }
+    Ok(mismatches)
+}
+
+/// Checks every link, or only a node's links
+///
+/// # Errors
+/// Returns an error if links or memberships cannot be read.
+pub async fn check_consistency(
+    datastore: &dyn DataStore,
+    node_id: Option<Uuid>,
+) -> DataStoreResult<Vec<VlanMismatch>> {
+    let links = match node_id {
+        Some(id) => datastore.get_links_for_node(&id).await?,
+        None => datastore.list_links(&QueryOptions::default()).await?.items,
+    };
+    check_links(datastore, &links).await
+}
+
+/// Builds the `vlans` object policies see for a node
+///
+/// It holds the node's `interfaces` memberships, the `mismatches` on its
+/// links, their `mismatch_count`, and whether the node is `consistent`.
+///
+/// # Errors
+/// Returns an error if links or memberships cannot be read.
+pub async fn node_vlan_view(datastore: &dyn DataStore, node_id: Uuid) -> DataStoreResult<Value> {
+    let interfaces = interface_vlans(datastore, node_id).await?;
+    let mismatches = check_consistency(datastore, Some(node_id)).await?;
+    let to_json = |value: serde_json::Result<Value>| {
+        value.map_err(|e| DataStoreError::InternalError {
+            message: format!("VLAN view of node {node_id}: {e}"),
+        })
+    };
+    Ok(json!({
+        "interfaces": to_json(serde_json::to_value(&interfaces))?,
+        "mismatches": to_json(serde_json::to_value(&mismatches))?,
+        "mismatch_count": mismatches.len(),
+        "consistent": mismatches.is_empty(),
+    }))
+}
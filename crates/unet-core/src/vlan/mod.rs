@@ -0,0 +1,266 @@
+//! VLANs and interface VLAN membership
+//!
+//! VLANs are defined once for the network and referenced by interfaces. Each
+//! interface of a node is either an access port carrying one VLAN or a trunk
+//! carrying an allowed list with an optional native VLAN. Both are kept
+//! through the `DataStore` settings API: VLANs keyed by VLAN ID and
+//! memberships per node keyed by node ID.
+//!
+//! [`consistency`] checks that the two ends of every link agree, and exposes
+//! the result to policies as the `vlans` context object.
+
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use uuid::Uuid;
+
+pub mod consistency;
+
+pub use consistency::{MismatchKind, VlanMismatch, check_consistency, check_links, node_vlan_view};
+
+/// Settings namespace holding VLAN definitions keyed by VLAN ID
+const VLANS_NAMESPACE: &str = "vlans";
+/// Settings namespace holding interface memberships keyed by node ID
+const MEMBERSHIP_NAMESPACE: &str = "interface_vlans";
+
+/// Lowest usable VLAN ID
+pub const MIN_VLAN_ID: u16 = 1;
+/// Highest usable VLAN ID
+pub const MAX_VLAN_ID: u16 = 4094;
+
+/// Interface VLAN memberships of one node, keyed by interface name
+pub type InterfaceVlans = BTreeMap<String, VlanMembership>;
+
+fn validate_id(id: u16) -> DataStoreResult<()> {
+    if (MIN_VLAN_ID..=MAX_VLAN_ID).contains(&id) {
+        Ok(())
+    } else {
+        Err(DataStoreError::ValidationError {
+            message: format!("VLAN ID {id} is outside {MIN_VLAN_ID}-{MAX_VLAN_ID}"),
+        })
+    }
+}
+
+/// A VLAN defined for the network
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Vlan {
+    /// VLAN ID
+    pub id: u16,
+    /// VLAN name
+    pub name: String,
+    /// What the VLAN carries
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl Vlan {
+    /// Creates a VLAN definition
+    ///
+    /// # Errors
+    /// Returns a validation error if the ID is out of range or the name is empty.
+    pub fn new(id: u16, name: &str, description: Option<String>) -> DataStoreResult<Self> {
+        validate_id(id)?;
+        if name.trim().is_empty() {
+            return Err(DataStoreError::ValidationError {
+                message: format!("VLAN {id} needs a name"),
+            });
+        }
+        Ok(Self {
+            id,
+            name: name.trim().to_string(),
+            description,
+        })
+    }
+}
+
+/// How an interface carries VLANs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "lowercase")]
+pub enum VlanMembership {
+    /// Untagged port in a single VLAN
+    Access {
+        /// Access VLAN
+        vlan: u16,
+    },
+    /// Tagged port carrying several VLANs
+    Trunk {
+        /// VLANs allowed on the trunk
+        allowed: BTreeSet<u16>,
+        /// VLAN carried untagged, if any
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        native: Option<u16>,
+    },
+}
+
+impl VlanMembership {
+    /// Checks that every referenced VLAN ID is in range
+    ///
+    /// # Errors
+    /// Returns a validation error naming the first out-of-range ID.
+    pub fn validate(&self) -> DataStoreResult<()> {
+        match self {
+            Self::Access { vlan } => validate_id(*vlan),
+            Self::Trunk { allowed, native } => allowed
+                .iter()
+                .chain(native)
+                .try_for_each(|id| validate_id(*id)),
+        }
+    }
+
+    /// VLAN IDs the interface references
+    #[must_use]
+    pub fn vlan_ids(&self) -> BTreeSet<u16> {
+        match self {
+            Self::Access { vlan } => BTreeSet::from([*vlan]),
+            Self::Trunk { allowed, native } => allowed.iter().chain(native).copied().collect(),
+        }
+    }
+}
+
+/// Parses VLAN lists such as `10,20,100-110`
+///
+/// # Errors
+/// Returns an error if an entry is not a VLAN ID or an ascending range of
+/// them, or an ID is outside 1-4094.
+pub fn parse_vlan_list(text: &str) -> Result<BTreeSet<u16>, String> {
+    let invalid = || format!("Invalid VLAN list {text:?}: expected e.g. 10,20,100-110");
+    let mut vlans = BTreeSet::new();
+    for entry in text
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+    {
+        let (start, end) = entry.split_once('-').unwrap_or((entry, entry));
+        let start: u16 = start.trim().parse().map_err(|_| invalid())?;
+        let end: u16 = end.trim().parse().map_err(|_| invalid())?;
+        if start > end || start < MIN_VLAN_ID || end > MAX_VLAN_ID {
+            return Err(invalid());
+        }
+        vlans.extend(start..=end);
+    }
+    if vlans.is_empty() {
+        return Err(invalid());
+    }
+    Ok(vlans)
+}
+
+fn parse<T: serde::de::DeserializeOwned>(key: &str, value: Value) -> DataStoreResult<T> {
+    serde_json::from_value(value).map_err(|e| DataStoreError::InternalError {
+        message: format!("stored VLAN data {key}: {e}"),
+    })
+}
+
+fn to_value<T: Serialize>(key: &str, value: &T) -> DataStoreResult<Value> {
+    serde_json::to_value(value).map_err(|e| DataStoreError::InternalError {
+        message: format!("VLAN data {key}: {e}"),
+    })
+}
+
+/// Lists VLAN definitions ordered by ID
+///
+/// Datastores without settings support have no VLANs.
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or a stored VLAN is malformed.
+pub async fn list_vlans(datastore: &dyn DataStore) -> DataStoreResult<Vec<Vlan>> {
+    let stored = match datastore.list_settings(VLANS_NAMESPACE).await {
+        Ok(stored) => stored,
+        Err(DataStoreError::UnsupportedOperation { .. }) => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut vlans = stored
+        .into_iter()
+        .map(|(key, value)| parse::<Vlan>(&key, value))
+        .collect::<DataStoreResult<Vec<_>>>()?;
+    vlans.sort_by_key(|vlan| vlan.id);
+    Ok(vlans)
+}
+
+/// Stores a VLAN definition, replacing one with the same ID
+///
+/// # Errors
+/// Returns an error if the datastore write fails.
+pub async fn save_vlan(datastore: &dyn DataStore, vlan: &Vlan) -> DataStoreResult<()> {
+    let key = vlan.id.to_string();
+    datastore
+        .put_setting(VLANS_NAMESPACE, &key, &to_value(&key, vlan)?)
+        .await
+}
+
+/// Removes a VLAN definition
+///
+/// # Errors
+/// Returns an error if the VLAN is not defined or the datastore write fails.
+pub async fn delete_vlan(datastore: &dyn DataStore, id: u16) -> DataStoreResult<()> {
+    datastore
+        .delete_setting(VLANS_NAMESPACE, &id.to_string())
+        .await
+}
+
+/// Returns a node's interface VLAN memberships
+///
+/// Datastores without settings support have no memberships.
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or the memberships are malformed.
+pub async fn interface_vlans(
+    datastore: &dyn DataStore,
+    node_id: Uuid,
+) -> DataStoreResult<InterfaceVlans> {
+    let key = node_id.to_string();
+    match datastore.get_setting(MEMBERSHIP_NAMESPACE, &key).await {
+        Ok(Some(value)) => parse(&key, value),
+        Ok(None) | Err(DataStoreError::UnsupportedOperation { .. }) => Ok(InterfaceVlans::new()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Sets or, with `None`, clears the VLAN membership of a node's interface
+///
+/// # Errors
+/// Returns a validation error if the membership references an undefined or
+/// out-of-range VLAN, or an error if the datastore cannot be read or written.
+pub async fn set_interface_vlan(
+    datastore: &dyn DataStore,
+    node_id: Uuid,
+    interface: &str,
+    membership: Option<VlanMembership>,
+) -> DataStoreResult<InterfaceVlans> {
+    let mut memberships = interface_vlans(datastore, node_id).await?;
+    match membership {
+        Some(membership) => {
+            membership.validate()?;
+            let defined: BTreeSet<u16> = list_vlans(datastore)
+                .await?
+                .into_iter()
+                .map(|vlan| vlan.id)
+                .collect();
+            if let Some(id) = membership.vlan_ids().difference(&defined).next() {
+                return Err(DataStoreError::ValidationError {
+                    message: format!("VLAN {id} is not defined"),
+                });
+            }
+            memberships.insert(interface.to_string(), membership);
+        }
+        None => {
+            memberships.remove(interface);
+        }
+    }
+
+    let key = node_id.to_string();
+    if memberships.is_empty() {
+        match datastore.delete_setting(MEMBERSHIP_NAMESPACE, &key).await {
+            Ok(()) | Err(DataStoreError::NotFound { .. }) => {}
+            Err(e) => return Err(e),
+        }
+    } else {
+        datastore
+            .put_setting(MEMBERSHIP_NAMESPACE, &key, &to_value(&key, &memberships)?)
+            .await?;
+    }
+    Ok(memberships)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,128 @@
+use super::*;
+use crate::datastore::sqlite::SqliteStore;
+use crate::datastore::sqlite::tests::setup::migrated_store;
+use crate::models::{DeviceRole, Link, Node, Vendor};
+use crate::policy::{Action, Condition, EvaluationContext, FieldRef, PolicyRule};
+use crate::policy_integration::add_vlan_view;
+use serde_json::json;
+
+async fn create_node(store: &SqliteStore, name: &str) -> Node {
+    let node = Node::new(
+        name.to_string(),
+        "example.com".to_string(),
+        Vendor::Arista,
+        DeviceRole::Switch,
+    );
+    store.create_node(&node).await.unwrap()
+}
+
+fn trunk(allowed: &str, native: Option<u16>) -> Option<VlanMembership> {
+    Some(VlanMembership::Trunk {
+        allowed: parse_vlan_list(allowed).unwrap(),
+        native,
+    })
+}
+
+async fn define_vlans(store: &SqliteStore, ids: &[u16]) {
+    for id in ids {
+        let vlan = Vlan::new(*id, &format!("vlan{id}"), None).unwrap();
+        save_vlan(store, &vlan).await.unwrap();
+    }
+}
+
+#[test]
+fn test_parse_vlan_list() {
+    assert_eq!(
+        parse_vlan_list("10, 20,30-32").unwrap(),
+        BTreeSet::from([10, 20, 30, 31, 32])
+    );
+    assert!(parse_vlan_list("").is_err());
+    assert!(parse_vlan_list("0").is_err());
+    assert!(parse_vlan_list("4095").is_err());
+    assert!(parse_vlan_list("20-10").is_err());
+    assert!(parse_vlan_list("ten").is_err());
+}
+
+#[tokio::test]
+async fn test_membership_requires_defined_vlans() {
+    let store = migrated_store().await;
+    let node = create_node(&store, "sw-1").await;
+    define_vlans(&store, &[10]).await;
+
+    let result = set_interface_vlan(&store, node.id, "Ethernet1", trunk("10,20", None)).await;
+    assert!(matches!(
+        result,
+        Err(DataStoreError::ValidationError { .. })
+    ));
+
+    let access = Some(VlanMembership::Access { vlan: 10 });
+    set_interface_vlan(&store, node.id, "Ethernet1", access.clone())
+        .await
+        .unwrap();
+    let stored = interface_vlans(&store, node.id).await.unwrap();
+    assert_eq!(stored.get("Ethernet1"), access.as_ref());
+
+    set_interface_vlan(&store, node.id, "Ethernet1", None)
+        .await
+        .unwrap();
+    assert!(interface_vlans(&store, node.id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_trunk_mismatch_is_reported_and_visible_to_policies() {
+    let store = migrated_store().await;
+    let a = create_node(&store, "sw-1").await;
+    let z = create_node(&store, "sw-2").await;
+    let link = Link::new(
+        "sw-1-sw-2".to_string(),
+        a.id,
+        "Ethernet1".to_string(),
+        z.id,
+        "Ethernet1".to_string(),
+    );
+    store.create_link(&link).await.unwrap();
+    define_vlans(&store, &[1, 10, 20, 30]).await;
+    set_interface_vlan(&store, a.id, "Ethernet1", trunk("10,20", Some(1)))
+        .await
+        .unwrap();
+    set_interface_vlan(&store, z.id, "Ethernet1", trunk("10,30", Some(1)))
+        .await
+        .unwrap();
+
+    let mismatches = check_consistency(&store, None).await.unwrap();
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(
+        mismatches[0].kind,
+        MismatchKind::AllowedVlans {
+            only_a: BTreeSet::from([20]),
+            only_z: BTreeSet::from([30]),
+        }
+    );
+
+    let rule = PolicyRule {
+        id: Some("trunks-agree".to_string()),
+        condition: Condition::True,
+        action: Action::Assert {
+            field: FieldRef {
+                path: vec!["vlans".to_string(), "consistent".to_string()],
+            },
+            expected: crate::policy::Value::Boolean(true),
+        },
+    };
+    let mut context = EvaluationContext::new(json!({"node": {"name": "sw-1"}}));
+    add_vlan_view(&mut context, &store, a.id, [&rule])
+        .await
+        .unwrap();
+    assert_eq!(context.get_field("vlans.consistent"), Some(&json!(false)));
+    assert_eq!(context.get_field("vlans.mismatch_count"), Some(&json!(1)));
+
+    set_interface_vlan(&store, z.id, "Ethernet1", trunk("10,20", Some(1)))
+        .await
+        .unwrap();
+    assert!(
+        check_consistency(&store, Some(a.id))
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
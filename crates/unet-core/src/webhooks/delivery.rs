@@ -0,0 +1,191 @@
+//! Sending queued deliveries with retries
+
+use super::flood::close_group;
+use super::store::{DEAD_LETTERS_NAMESPACE, OUTBOX_NAMESPACE, list, put};
+use super::{WebhookEvent, WebhookSubscription, list_subscriptions, sign};
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult};
+use async_trait::async_trait;
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Header carrying the event type
+pub const EVENT_HEADER: &str = "X-Unet-Event";
+/// Header carrying the delivery ID, stable across retries
+pub const DELIVERY_HEADER: &str = "X-Unet-Delivery";
+/// Header carrying the body signature of subscriptions with a secret
+pub const SIGNATURE_HEADER: &str = "X-Unet-Signature";
+/// Attempts made before a delivery becomes a dead letter
+pub const MAX_ATTEMPTS: u32 = 5;
+
+/// Delay before the second attempt; doubled for each attempt after that
+const FIRST_RETRY_DELAY: TimeDelta = TimeDelta::seconds(30);
+/// Longest delay between attempts
+const MAX_RETRY_DELAY: TimeDelta = TimeDelta::hours(1);
+
+/// One event on its way to one subscription
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Delivery {
+    /// Delivery ID
+    pub id: Uuid,
+    /// Subscription the event goes to
+    pub subscription_id: Uuid,
+    /// Subscription URL when the delivery was queued
+    pub url: String,
+    /// The event
+    pub event: WebhookEvent,
+    /// Attempts made so far
+    pub attempts: u32,
+    /// Earliest time of the next attempt
+    pub next_attempt_at: DateTime<Utc>,
+    /// Time of the most recent attempt
+    #[serde(default)]
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    /// Why the most recent attempt failed
+    #[serde(default)]
+    pub last_error: Option<String>,
+}
+
+impl Delivery {
+    /// Queues an event for a subscription, due immediately
+    #[must_use]
+    pub fn new(subscription: &WebhookSubscription, event: WebhookEvent) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            subscription_id: subscription.id,
+            url: subscription.url.clone(),
+            next_attempt_at: Utc::now(),
+            event,
+            attempts: 0,
+            last_attempt_at: None,
+            last_error: None,
+        }
+    }
+}
+
+/// An HTTP POST to make for a delivery
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookRequest {
+    /// Target URL
+    pub url: String,
+    /// Headers besides `Content-Type: application/json`
+    pub headers: Vec<(&'static str, String)>,
+    /// JSON body in the subscription's payload format
+    pub body: Vec<u8>,
+}
+
+impl WebhookRequest {
+    /// Builds the signed request for a delivery
+    ///
+    /// # Errors
+    /// Returns an error if the event cannot be serialized or signed.
+    pub fn new(subscription: &WebhookSubscription, delivery: &Delivery) -> DataStoreResult<Self> {
+        let body = serde_json::to_vec(&subscription.payload_format.body(&delivery.event)).map_err(
+            |e| DataStoreError::InternalError {
+                message: format!("webhook event {}: {e}", delivery.event.id),
+            },
+        )?;
+        let mut headers = vec![
+            (EVENT_HEADER, delivery.event.event_type.to_string()),
+            (DELIVERY_HEADER, delivery.id.to_string()),
+        ];
+        if let Some(secret) = &subscription.secret {
+            headers.push((SIGNATURE_HEADER, sign(secret, &body)?));
+        }
+        Ok(Self {
+            url: subscription.url.clone(),
+            headers,
+            body,
+        })
+    }
+}
+
+/// Transport making webhook requests
+#[async_trait]
+pub trait WebhookSender: Send + Sync {
+    /// Sends the request, failing on transport errors and non-2xx responses
+    async fn send(&self, request: &WebhookRequest) -> Result<(), String>;
+}
+
+/// Outcome of one pass over the outbox
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct DeliveryStats {
+    /// Deliveries accepted by their endpoint
+    pub delivered: usize,
+    /// Failed deliveries scheduled for another attempt
+    pub retried: usize,
+    /// Failed deliveries moved to the dead letters
+    pub dead_lettered: usize,
+    /// Deliveries dropped because their subscription was removed
+    pub dropped: usize,
+}
+
+/// Attempts every queued delivery due by `now`, oldest event first
+///
+/// # Errors
+/// Returns an error if the outbox cannot be read or updated.
+pub async fn deliver_pending(
+    datastore: &dyn DataStore,
+    sender: &dyn WebhookSender,
+    now: DateTime<Utc>,
+) -> DataStoreResult<DeliveryStats> {
+    let subscriptions: HashMap<Uuid, WebhookSubscription> = list_subscriptions(datastore)
+        .await?
+        .into_iter()
+        .map(|subscription| (subscription.id, subscription))
+        .collect();
+    let mut due: Vec<Delivery> = list::<Delivery>(datastore, OUTBOX_NAMESPACE)
+        .await?
+        .into_iter()
+        .filter(|delivery| delivery.next_attempt_at <= now)
+        .collect();
+    due.sort_by_key(|delivery| delivery.event.occurred_at);
+
+    let mut stats = DeliveryStats::default();
+    for delivery in due {
+        let mut delivery = close_group(datastore, delivery).await?;
+        let key = delivery.id.to_string();
+        let Some(subscription) = subscriptions.get(&delivery.subscription_id) else {
+            datastore.delete_setting(OUTBOX_NAMESPACE, &key).await?;
+            stats.dropped += 1;
+            continue;
+        };
+
+        let request = WebhookRequest::new(subscription, &delivery)?;
+        delivery.attempts += 1;
+        delivery.last_attempt_at = Some(now);
+        match sender.send(&request).await {
+            Ok(()) => {
+                datastore.delete_setting(OUTBOX_NAMESPACE, &key).await?;
+                stats.delivered += 1;
+            }
+            Err(error) if delivery.attempts >= MAX_ATTEMPTS => {
+                warn!(
+                    url = %delivery.url,
+                    event = %delivery.event.event_type,
+                    error = %error,
+                    "Webhook delivery failed {MAX_ATTEMPTS} times; moved to dead letters"
+                );
+                delivery.last_error = Some(error);
+                put(datastore, DEAD_LETTERS_NAMESPACE, delivery.id, &delivery).await?;
+                datastore.delete_setting(OUTBOX_NAMESPACE, &key).await?;
+                stats.dead_lettered += 1;
+            }
+            Err(error) => {
+                delivery.last_error = Some(error);
+                delivery.next_attempt_at = now + retry_delay(delivery.attempts);
+                put(datastore, OUTBOX_NAMESPACE, delivery.id, &delivery).await?;
+                stats.retried += 1;
+            }
+        }
+    }
+    Ok(stats)
+}
+
+/// Delay after failed attempt number `attempts`
+fn retry_delay(attempts: u32) -> TimeDelta {
+    let factor = 2_i32.saturating_pow(attempts.saturating_sub(1));
+    (FIRST_RETRY_DELAY * factor).min(MAX_RETRY_DELAY)
+}
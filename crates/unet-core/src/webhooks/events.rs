@@ -0,0 +1,262 @@
+//! Events announced to webhook subscribers
+
+use super::EventGroup;
+use crate::config_changes::ConfigSnapshot;
+use crate::measurement::ThresholdBreach;
+use crate::models::{Link, Node};
+use crate::policy::PolicyExecutionResult;
+use chrono::{DateTime, Utc};
+use config_slicer::diff::DiffStats;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Kinds of events a subscription can receive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EventType {
+    /// A node was created
+    #[serde(rename = "node.created")]
+    NodeCreated,
+    /// A node was updated
+    #[serde(rename = "node.updated")]
+    NodeUpdated,
+    /// A node was deleted
+    #[serde(rename = "node.deleted")]
+    NodeDeleted,
+    /// Policy evaluation of a node found compliance failures or errors
+    #[serde(rename = "policy.failed")]
+    PolicyFailed,
+    /// A link measurement went above a threshold
+    #[serde(rename = "alarm.raised")]
+    AlarmRaised,
+    /// A link measurement came back within a threshold
+    #[serde(rename = "alarm.cleared")]
+    AlarmCleared,
+    /// A node's configuration changed outside every change window
+    #[serde(rename = "config.unauthorized_change")]
+    ConfigUnauthorizedChange,
+}
+
+impl EventType {
+    /// Every event type
+    pub const ALL: [Self; 7] = [
+        Self::NodeCreated,
+        Self::NodeUpdated,
+        Self::NodeDeleted,
+        Self::PolicyFailed,
+        Self::AlarmRaised,
+        Self::AlarmCleared,
+        Self::ConfigUnauthorizedChange,
+    ];
+
+    /// Event type name as sent to subscribers
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::NodeCreated => "node.created",
+            Self::NodeUpdated => "node.updated",
+            Self::NodeDeleted => "node.deleted",
+            Self::PolicyFailed => "policy.failed",
+            Self::AlarmRaised => "alarm.raised",
+            Self::AlarmCleared => "alarm.cleared",
+            Self::ConfigUnauthorizedChange => "config.unauthorized_change",
+        }
+    }
+}
+
+impl Display for EventType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for EventType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|event_type| event_type.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                format!(
+                    "Invalid event type: {s} (expected {})",
+                    Self::ALL.map(Self::as_str).join(", ")
+                )
+            })
+    }
+}
+
+/// Something that happened, as POSTed to subscribers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookEvent {
+    /// Event ID, the same for every subscriber
+    pub id: Uuid,
+    /// Event type
+    #[serde(rename = "type")]
+    pub event_type: EventType,
+    /// When the event happened
+    pub occurred_at: DateTime<Utc>,
+    /// Kind of entity the event is about: `node` or `link`
+    pub entity_type: String,
+    /// ID of the entity the event is about
+    pub entity_id: Uuid,
+    /// Attributes subscription filters are matched against
+    pub attributes: BTreeMap<String, String>,
+    /// Event details
+    pub data: Value,
+    /// Other events folded into this one by the subscription's flood
+    /// control; the details are those of the first event
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<EventGroup>,
+}
+
+impl WebhookEvent {
+    /// Creates an event that happens now
+    #[must_use]
+    pub fn new(
+        event_type: EventType,
+        entity_type: &str,
+        entity_id: Uuid,
+        attributes: BTreeMap<String, String>,
+        data: Value,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            event_type,
+            occurred_at: Utc::now(),
+            entity_type: entity_type.to_string(),
+            entity_id,
+            attributes,
+            data,
+            group: None,
+        }
+    }
+
+    /// A node created, updated, or deleted event carrying the node
+    #[must_use]
+    pub fn node(event_type: EventType, node: &Node) -> Self {
+        Self::new(
+            event_type,
+            "node",
+            node.id,
+            node_attributes(node),
+            serde_json::to_value(node).unwrap_or(Value::Null),
+        )
+    }
+
+    /// A policy failure event listing the node's failed or erroring rules,
+    /// or `None` when every rule passed
+    #[must_use]
+    pub fn policy_failed(node: &Node, results: &[PolicyExecutionResult]) -> Option<Self> {
+        let failures: Vec<Value> = results
+            .iter()
+            .filter(|result| result.is_compliance_failure() || result.is_error())
+            .map(|result| {
+                json!({
+                    "rule": result.rule.to_string(),
+                    "rule_id": result.rule.id,
+                    "error": result.get_error_message(),
+                    "action_result": result.action_result,
+                })
+            })
+            .collect();
+        if failures.is_empty() {
+            return None;
+        }
+        Some(Self::new(
+            EventType::PolicyFailed,
+            "node",
+            node.id,
+            node_attributes(node),
+            json!({ "node": node.name, "failures": failures }),
+        ))
+    }
+
+    /// Alarm events for a link whose threshold breaches changed from
+    /// `before` to `after`: raised for newly breached metrics, cleared for
+    /// metrics no longer breached
+    #[must_use]
+    pub fn alarms(link: &Link, before: &[ThresholdBreach], after: &[ThresholdBreach]) -> Vec<Self> {
+        let breached = |breaches: &[ThresholdBreach], metric: &str| {
+            breaches.iter().any(|breach| breach.metric == metric)
+        };
+        let raised = after
+            .iter()
+            .filter(|breach| !breached(before, &breach.metric))
+            .map(|breach| (EventType::AlarmRaised, breach));
+        let cleared = before
+            .iter()
+            .filter(|breach| !breached(after, &breach.metric))
+            .map(|breach| (EventType::AlarmCleared, breach));
+
+        raised
+            .chain(cleared)
+            .map(|(event_type, breach)| {
+                let attributes = BTreeMap::from([
+                    ("name".to_string(), link.name.clone()),
+                    ("metric".to_string(), breach.metric.clone()),
+                ]);
+                let data = if event_type == EventType::AlarmRaised {
+                    json!({ "link": link.name, "breach": breach })
+                } else {
+                    json!({ "link": link.name, "metric": breach.metric, "threshold": breach.threshold })
+                };
+                Self::new(event_type, "link", link.id, attributes, data)
+            })
+            .collect()
+    }
+
+    /// An unauthorized change event carrying the diff between two snapshots
+    #[must_use]
+    pub fn unauthorized_change(
+        node: &Node,
+        previous: &ConfigSnapshot,
+        current: &ConfigSnapshot,
+        diff: &str,
+        stats: DiffStats,
+    ) -> Self {
+        Self::new(
+            EventType::ConfigUnauthorizedChange,
+            "node",
+            node.id,
+            node_attributes(node),
+            json!({
+                "node": node.name,
+                "previous_collected_at": previous.collected_at,
+                "collected_at": current.collected_at,
+                "added": stats.added,
+                "removed": stats.removed,
+                "diff": diff,
+            }),
+        )
+    }
+
+    /// Looks up a filterable attribute, including `type`, `entity_type`,
+    /// and `entity_id`
+    #[must_use]
+    pub fn attribute(&self, key: &str) -> Option<String> {
+        match key {
+            "type" => Some(self.event_type.to_string()),
+            "entity_type" => Some(self.entity_type.clone()),
+            "entity_id" => Some(self.entity_id.to_string()),
+            _ => self.attributes.get(key).cloned(),
+        }
+    }
+}
+
+fn node_attributes(node: &Node) -> BTreeMap<String, String> {
+    let mut attributes = BTreeMap::from([
+        ("name".to_string(), node.name.clone()),
+        ("vendor".to_string(), node.vendor.to_string()),
+        ("role".to_string(), node.role.to_string()),
+        ("lifecycle".to_string(), node.lifecycle.to_string()),
+        ("node_id".to_string(), node.id.to_string()),
+    ]);
+    if let Some(location_id) = node.location_id {
+        attributes.insert("location_id".to_string(), location_id.to_string());
+    }
+    attributes
+}
@@ -0,0 +1,316 @@
+//! Collapsing repeated events and throttling floods per subscription
+//!
+//! A subscription with a group window holds each event for that long and
+//! folds later events with the same dedupe key into the same delivery, so a
+//! site outage announcing hundreds of node events reaches the endpoint as a
+//! few grouped notifications carrying a count. A rate limit caps the
+//! notifications queued per minute; past it, events are folded into one
+//! flood notification per event type, sent when the minute ends.
+//!
+//! Open groups are looked up by subscription and dedupe key, so queueing an
+//! event reads only the deliveries it can join. Queueing and closing groups
+//! take a per-subscription lock, so concurrent events for one subscription
+//! do not overwrite each other's changes to a group or rate window.
+
+use super::store::{OPEN_GROUPS_NAMESPACE, OUTBOX_NAMESPACE, RATE_WINDOWS_NAMESPACE, parse, put};
+use super::{Delivery, WebhookEvent, WebhookSubscription};
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult};
+use chrono::{DateTime, TimeDelta, Utc};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, LazyLock};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Entity IDs listed in a group; the count keeps going past this
+pub const MAX_GROUPED_ENTITIES: usize = 100;
+
+/// Longest group window in seconds
+pub const MAX_GROUP_WINDOW: u64 = 24 * 60 * 60;
+
+/// Period a subscription's rate limit applies to
+const RATE_WINDOW: TimeDelta = TimeDelta::minutes(1);
+
+/// Lock per subscription held while its groups and rate window change
+static SUBSCRIPTION_LOCKS: LazyLock<DashMap<Uuid, Arc<Mutex<()>>>> = LazyLock::new(DashMap::new);
+
+fn subscription_lock(subscription: Uuid) -> Arc<Mutex<()>> {
+    SUBSCRIPTION_LOCKS.entry(subscription).or_default().clone()
+}
+
+/// Key of a subscription's open group in [`OPEN_GROUPS_NAMESPACE`]
+fn open_group_key(subscription: Uuid, key: &str) -> String {
+    format!("{subscription}/{key}")
+}
+
+/// How a subscription collapses repeated events and throttles floods
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FloodControl {
+    /// Seconds events are held so later events with the same dedupe key
+    /// join them; 0 sends every event on its own
+    #[serde(default)]
+    pub group_window: u64,
+    /// Event attributes that, with the event type, form the dedupe key,
+    /// e.g. `location_id`; empty means the entity the event is about
+    #[serde(default)]
+    pub dedupe_by: Vec<String>,
+    /// Most notifications queued per minute; events past it are grouped
+    /// into one flood notification per event type
+    #[serde(default)]
+    pub rate_limit: Option<u32>,
+}
+
+impl FloodControl {
+    /// Whether events are grouped or throttled at all
+    #[must_use]
+    pub const fn is_enabled(&self) -> bool {
+        self.group_window > 0 || self.rate_limit.is_some()
+    }
+
+    /// Checks the settings make sense together
+    ///
+    /// # Errors
+    /// Returns a validation error if the group window is longer than
+    /// [`MAX_GROUP_WINDOW`], the rate limit is zero, or `dedupe_by` is given
+    /// without a group window.
+    pub fn validate(&self) -> DataStoreResult<()> {
+        let message = if self.group_window > MAX_GROUP_WINDOW {
+            "Webhook group window must be at most 86400 seconds"
+        } else if self.rate_limit == Some(0) {
+            "Webhook rate limit must be greater than 0"
+        } else if self.group_window == 0 && !self.dedupe_by.is_empty() {
+            "Webhook dedupe_by needs a group window"
+        } else {
+            return Ok(());
+        };
+        Err(DataStoreError::ValidationError {
+            message: message.to_string(),
+        })
+    }
+
+    fn window(&self) -> TimeDelta {
+        let seconds = self.group_window.min(MAX_GROUP_WINDOW);
+        TimeDelta::seconds(i64::try_from(seconds).unwrap_or_default())
+    }
+
+    /// The key events are grouped by: the event type followed by the
+    /// `dedupe_by` attribute values, or by the entity ID when there are none
+    #[must_use]
+    pub fn dedupe_key(&self, event: &WebhookEvent) -> String {
+        if self.dedupe_by.is_empty() {
+            return format!("{}:{}", event.event_type, event.entity_id);
+        }
+        let values: Vec<String> = self
+            .dedupe_by
+            .iter()
+            .map(|key| format!("{key}={}", event.attribute(key).unwrap_or_default()))
+            .collect();
+        format!("{}:{}", event.event_type, values.join(","))
+    }
+}
+
+/// Events folded into one delivery
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventGroup {
+    /// Dedupe key shared by the events, or `flood:<type>` for events past
+    /// the rate limit
+    pub key: String,
+    /// Number of events
+    pub count: usize,
+    /// Distinct entities the events are about, at most
+    /// [`MAX_GROUPED_ENTITIES`]
+    pub entity_ids: Vec<Uuid>,
+    /// When the first event happened
+    pub first_occurred_at: DateTime<Utc>,
+    /// When the last event happened
+    pub last_occurred_at: DateTime<Utc>,
+}
+
+impl EventGroup {
+    fn new(key: String, event: &WebhookEvent) -> Self {
+        Self {
+            key,
+            count: 1,
+            entity_ids: vec![event.entity_id],
+            first_occurred_at: event.occurred_at,
+            last_occurred_at: event.occurred_at,
+        }
+    }
+
+    fn add(&mut self, event: &WebhookEvent) {
+        self.count += 1;
+        if self.entity_ids.len() < MAX_GROUPED_ENTITIES
+            && !self.entity_ids.contains(&event.entity_id)
+        {
+            self.entity_ids.push(event.entity_id);
+        }
+        self.last_occurred_at = self.last_occurred_at.max(event.occurred_at);
+    }
+}
+
+/// Notifications queued for a subscription in the current minute
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct RateWindow {
+    started_at: DateTime<Utc>,
+    queued: u32,
+}
+
+async fn rate_window(
+    datastore: &dyn DataStore,
+    subscription: Uuid,
+    now: DateTime<Utc>,
+) -> DataStoreResult<RateWindow> {
+    let stored = datastore
+        .get_setting(RATE_WINDOWS_NAMESPACE, &subscription.to_string())
+        .await?
+        .and_then(|value| serde_json::from_value::<RateWindow>(value).ok());
+    Ok(match stored {
+        Some(window) if now < window.started_at + RATE_WINDOW => window,
+        _ => RateWindow {
+            started_at: now,
+            queued: 0,
+        },
+    })
+}
+
+/// Folds the event into an open group of the subscription, or queues a new
+/// delivery for it, held until its group window closes
+pub(super) async fn queue(
+    datastore: &dyn DataStore,
+    subscription: &WebhookSubscription,
+    event: &WebhookEvent,
+) -> DataStoreResult<()> {
+    let lock = subscription_lock(subscription.id);
+    let _guard = lock.lock().await;
+    let control = &subscription.flood_control;
+    let now = event.occurred_at;
+    let mut key = control.dedupe_key(event);
+    let mut due = now + control.window();
+
+    if control.group_window > 0
+        && join_open_group(datastore, subscription.id, &key, event, now).await?
+    {
+        return Ok(());
+    }
+    if let Some(limit) = control.rate_limit {
+        let mut window = rate_window(datastore, subscription.id, now).await?;
+        if window.queued >= limit {
+            key = format!("flood:{}", event.event_type);
+            if join_open_group(datastore, subscription.id, &key, event, now).await? {
+                return Ok(());
+            }
+            due = window.started_at + RATE_WINDOW;
+        } else {
+            window.queued += 1;
+            put(datastore, RATE_WINDOWS_NAMESPACE, subscription.id, &window).await?;
+        }
+    }
+
+    let mut delivery = Delivery::new(subscription, event.clone());
+    delivery.next_attempt_at = due;
+    if due > now {
+        delivery.event.group = Some(EventGroup::new(key, event));
+    }
+    put(datastore, OUTBOX_NAMESPACE, delivery.id, &delivery).await?;
+    if let Some(group) = &delivery.event.group {
+        datastore
+            .put_setting(
+                OPEN_GROUPS_NAMESPACE,
+                &open_group_key(subscription.id, &group.key),
+                &serde_json::Value::String(delivery.id.to_string()),
+            )
+            .await?;
+    }
+    Ok(())
+}
+
+/// Adds the event to the subscription's unsent group with `key` whose
+/// window is still open, returning whether there was one
+async fn join_open_group(
+    datastore: &dyn DataStore,
+    subscription: Uuid,
+    key: &str,
+    event: &WebhookEvent,
+    now: DateTime<Utc>,
+) -> DataStoreResult<bool> {
+    let Some(delivery_id) = open_group(datastore, subscription, key).await? else {
+        return Ok(false);
+    };
+    let delivery_key = delivery_id.to_string();
+    let stored = match datastore
+        .get_setting(OUTBOX_NAMESPACE, &delivery_key)
+        .await?
+    {
+        Some(value) => Some(parse::<Delivery>(&delivery_key, value)?),
+        None => None,
+    };
+    let open = stored.filter(|delivery| {
+        delivery.attempts == 0
+            && delivery.next_attempt_at > now
+            && delivery
+                .event
+                .group
+                .as_ref()
+                .is_some_and(|group| group.key == key)
+    });
+    let Some(mut delivery) = open else {
+        forget_open_group(datastore, subscription, key).await?;
+        return Ok(false);
+    };
+    if let Some(group) = &mut delivery.event.group {
+        group.add(event);
+    }
+    put(datastore, OUTBOX_NAMESPACE, delivery.id, &delivery).await?;
+    Ok(true)
+}
+
+/// Closes the group a delivery holds before its first attempt, so later
+/// events start a new group, and returns the delivery as last updated
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read or written.
+pub(super) async fn close_group(
+    datastore: &dyn DataStore,
+    delivery: Delivery,
+) -> DataStoreResult<Delivery> {
+    let group_key = match &delivery.event.group {
+        Some(group) if delivery.attempts == 0 => group.key.clone(),
+        _ => return Ok(delivery),
+    };
+    let lock = subscription_lock(delivery.subscription_id);
+    let _guard = lock.lock().await;
+    if open_group(datastore, delivery.subscription_id, &group_key).await? == Some(delivery.id) {
+        forget_open_group(datastore, delivery.subscription_id, &group_key).await?;
+    }
+    let key = delivery.id.to_string();
+    match datastore.get_setting(OUTBOX_NAMESPACE, &key).await? {
+        Some(value) => parse(&key, value),
+        None => Ok(delivery),
+    }
+}
+
+/// ID of the delivery holding the subscription's open group with `key`
+async fn open_group(
+    datastore: &dyn DataStore,
+    subscription: Uuid,
+    key: &str,
+) -> DataStoreResult<Option<Uuid>> {
+    let stored = datastore
+        .get_setting(OPEN_GROUPS_NAMESPACE, &open_group_key(subscription, key))
+        .await?;
+    Ok(stored.and_then(|value| serde_json::from_value(value).ok()))
+}
+
+async fn forget_open_group(
+    datastore: &dyn DataStore,
+    subscription: Uuid,
+    key: &str,
+) -> DataStoreResult<()> {
+    match datastore
+        .delete_setting(OPEN_GROUPS_NAMESPACE, &open_group_key(subscription, key))
+        .await
+    {
+        Ok(()) | Err(DataStoreError::NotFound { .. }) => Ok(()),
+        Err(e) => Err(e),
+    }
+}
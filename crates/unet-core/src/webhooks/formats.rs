@@ -0,0 +1,154 @@
+//! Request bodies shaped for ticketing systems
+//!
+//! By default subscribers receive the [`WebhookEvent`] itself. A
+//! subscription pointed straight at an ITSM endpoint instead gets a body in
+//! that system's create schema, so events such as `policy.failed` from a
+//! rule on interface error rates open tickets without glue code in between.
+
+use super::{EventType, WebhookEvent};
+use crate::datastore::{DataStoreError, DataStoreResult};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::fmt::Write;
+
+/// Issue type of Jira tickets when none is given
+pub const DEFAULT_JIRA_ISSUE_TYPE: &str = "Task";
+
+/// Shape of the JSON body POSTed for each event
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "format", rename_all = "snake_case")]
+pub enum PayloadFormat {
+    /// The event as is
+    #[default]
+    Event,
+    /// A `ServiceNow` incident, as accepted by the Table API at
+    /// `/api/now/table/incident`
+    #[serde(rename = "servicenow")]
+    ServiceNow,
+    /// A Jira issue, as accepted by `/rest/api/2/issue`
+    Jira {
+        /// Key of the project issues are created in
+        project: String,
+        /// Issue type name
+        #[serde(default = "default_issue_type")]
+        issue_type: String,
+    },
+}
+
+fn default_issue_type() -> String {
+    DEFAULT_JIRA_ISSUE_TYPE.to_string()
+}
+
+impl PayloadFormat {
+    /// Checks the format's settings
+    ///
+    /// # Errors
+    /// Returns a validation error if a Jira project key or issue type is empty.
+    pub fn validate(&self) -> DataStoreResult<()> {
+        match self {
+            Self::Jira { project, .. } if project.trim().is_empty() => {
+                Err(DataStoreError::ValidationError {
+                    message: "Jira payloads need a project key".to_string(),
+                })
+            }
+            Self::Jira { issue_type, .. } if issue_type.trim().is_empty() => {
+                Err(DataStoreError::ValidationError {
+                    message: "Jira issue type must not be empty".to_string(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Builds the request body for an event
+    #[must_use]
+    pub fn body(&self, event: &WebhookEvent) -> Value {
+        match self {
+            Self::Event => serde_json::to_value(event).unwrap_or(Value::Null),
+            Self::ServiceNow => json!({
+                "short_description": summary(event),
+                "description": description(event),
+                "category": "network",
+                "urgency": urgency(event.event_type),
+                "impact": urgency(event.event_type),
+                "cmdb_ci": subject(event),
+                "correlation_id": correlation_id(event),
+                "correlation_display": "unet",
+            }),
+            Self::Jira {
+                project,
+                issue_type,
+            } => json!({
+                "fields": {
+                    "project": { "key": project },
+                    "issuetype": { "name": issue_type },
+                    "summary": summary(event),
+                    "description": description(event),
+                    "labels": ["unet", event.event_type.as_str()],
+                },
+            }),
+        }
+    }
+}
+
+/// What happened, in a few words
+const fn title(event_type: EventType) -> &'static str {
+    match event_type {
+        EventType::NodeCreated => "Node created",
+        EventType::NodeUpdated => "Node updated",
+        EventType::NodeDeleted => "Node deleted",
+        EventType::PolicyFailed => "Policy compliance failure",
+        EventType::AlarmRaised => "Alarm raised",
+        EventType::AlarmCleared => "Alarm cleared",
+        EventType::ConfigUnauthorizedChange => "Unauthorized configuration change",
+    }
+}
+
+/// `ServiceNow` urgency and impact: 2 (medium) for problems, 3 (low) for
+/// the rest
+const fn urgency(event_type: EventType) -> &'static str {
+    match event_type {
+        EventType::PolicyFailed | EventType::AlarmRaised | EventType::ConfigUnauthorizedChange => {
+            "2"
+        }
+        _ => "3",
+    }
+}
+
+/// Name of the node or link the event is about, or its ID
+fn subject(event: &WebhookEvent) -> String {
+    event
+        .attribute("name")
+        .unwrap_or_else(|| event.entity_id.to_string())
+}
+
+/// Stable across repeats of the same event for the same entity, so the
+/// ticketing system can deduplicate
+fn correlation_id(event: &WebhookEvent) -> String {
+    format!("unet:{}:{}", event.event_type, event.entity_id)
+}
+
+fn summary(event: &WebhookEvent) -> String {
+    let mut summary = format!(
+        "{} on {} {}",
+        title(event.event_type),
+        event.entity_type,
+        subject(event)
+    );
+    if let Some(group) = event.group.as_ref().filter(|group| group.count > 1) {
+        let _ = write!(summary, " and {} more", group.count - 1);
+    }
+    summary
+}
+
+fn description(event: &WebhookEvent) -> String {
+    let details = serde_json::to_string_pretty(&event.data).unwrap_or_default();
+    format!(
+        "{} on {} {} at {}.\n\nEvent ID: {}\n\n{details}",
+        title(event.event_type),
+        event.entity_type,
+        subject(event),
+        event.occurred_at.to_rfc3339(),
+        event.id,
+    )
+}
@@ -0,0 +1,207 @@
+//! Outbound webhook subscriptions
+//!
+//! External systems subscribe a URL to event types, optionally narrowed by
+//! entity filters. When an event happens, [`record_event`] queues one
+//! delivery per matching subscription in an outbox kept through the
+//! `DataStore` settings API; the server's delivery task then POSTs each one
+//! as JSON (see [`deliver_pending`]). Failed deliveries are retried with
+//! exponential backoff and, after [`MAX_ATTEMPTS`], moved to a dead-letter
+//! list where they can be inspected and requeued. Subscriptions can collapse
+//! repeated events into grouped notifications and cap how many they receive
+//! per minute (see [`FloodControl`]). A [`GROUP_FILTER`] narrows node events
+//! to the current members of a node group. Instead of the event itself, a
+//! subscription can receive a `ServiceNow` incident or Jira issue built from
+//! it (see [`PayloadFormat`]), so events open tickets directly.
+//!
+//! Subscriptions with a secret get an HMAC-SHA256 signature of the body in
+//! the [`SIGNATURE_HEADER`] header, formatted as `sha256=<hex>`. Signing
+//! needs the `secrets` feature; without it, subscriptions with a secret are
+//! rejected.
+
+mod delivery;
+mod events;
+mod flood;
+mod formats;
+mod store;
+
+pub use delivery::{
+    DELIVERY_HEADER, Delivery, DeliveryStats, EVENT_HEADER, MAX_ATTEMPTS, SIGNATURE_HEADER,
+    WebhookRequest, WebhookSender, deliver_pending,
+};
+pub use events::{EventType, WebhookEvent};
+pub use flood::{EventGroup, FloodControl, MAX_GROUP_WINDOW, MAX_GROUPED_ENTITIES};
+pub use formats::{DEFAULT_JIRA_ISSUE_TYPE, PayloadFormat};
+pub use store::{
+    delete_dead_letter, delete_subscription, list_dead_letters, list_subscriptions, record_event,
+    retry_dead_letter, save_subscription,
+};
+
+use crate::datastore::{DataStoreError, DataStoreResult};
+use chrono::{DateTime, Utc};
+#[cfg(feature = "secrets")]
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "secrets")]
+use sha2::Sha256;
+use std::collections::BTreeMap;
+#[cfg(feature = "secrets")]
+use std::fmt::Write;
+use uuid::Uuid;
+
+/// Placeholder shown instead of a subscription's secret
+const REDACTED: &str = "<redacted>";
+
+/// Filter key matching node events by the node groups the node is currently
+/// a member of (see [`crate::groups`])
+pub const GROUP_FILTER: &str = "group";
+
+/// An external endpoint receiving events
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebhookSubscription {
+    /// Subscription ID
+    pub id: Uuid,
+    /// URL events are POSTed to
+    pub url: String,
+    /// Key used to sign request bodies
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    /// Event types delivered; empty means all
+    #[serde(default)]
+    pub events: Vec<EventType>,
+    /// Event attributes that must all match, compared case-insensitively
+    #[serde(default)]
+    pub filters: BTreeMap<String, String>,
+    /// Grouping of repeated events and rate limit
+    #[serde(default)]
+    pub flood_control: FloodControl,
+    /// Shape of the request bodies
+    #[serde(default)]
+    pub payload_format: PayloadFormat,
+    /// When the subscription was created
+    pub created_at: DateTime<Utc>,
+}
+
+impl WebhookSubscription {
+    /// Creates a subscription
+    ///
+    /// # Errors
+    /// Returns a validation error if the URL is not HTTP(S), the secret is
+    /// empty, or a secret is given without the `secrets` feature.
+    pub fn new(
+        url: &str,
+        secret: Option<String>,
+        events: Vec<EventType>,
+        filters: BTreeMap<String, String>,
+    ) -> DataStoreResult<Self> {
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            return Err(DataStoreError::ValidationError {
+                message: format!("Webhook URL must start with http:// or https://: {url}"),
+            });
+        }
+        if secret.as_deref().is_some_and(str::is_empty) {
+            return Err(DataStoreError::ValidationError {
+                message: "Webhook secret must not be empty".to_string(),
+            });
+        }
+        if secret.is_some() && !cfg!(feature = "secrets") {
+            return Err(DataStoreError::ValidationError {
+                message: "Webhook secrets require the `secrets` feature".to_string(),
+            });
+        }
+        Ok(Self {
+            id: Uuid::new_v4(),
+            url: url.to_string(),
+            secret,
+            events,
+            filters,
+            flood_control: FloodControl::default(),
+            payload_format: PayloadFormat::default(),
+            created_at: Utc::now(),
+        })
+    }
+
+    /// Groups and throttles the events sent to this subscription
+    ///
+    /// # Errors
+    /// Returns a validation error if the settings are invalid (see
+    /// [`FloodControl::validate`]).
+    pub fn with_flood_control(mut self, flood_control: FloodControl) -> DataStoreResult<Self> {
+        flood_control.validate()?;
+        self.flood_control = flood_control;
+        Ok(self)
+    }
+
+    /// Sends request bodies in a ticketing system's schema instead of the
+    /// event itself
+    ///
+    /// # Errors
+    /// Returns a validation error if the format's settings are invalid (see
+    /// [`PayloadFormat::validate`]).
+    pub fn with_payload_format(mut self, payload_format: PayloadFormat) -> DataStoreResult<Self> {
+        payload_format.validate()?;
+        self.payload_format = payload_format;
+        Ok(self)
+    }
+
+    /// Whether the event is one this subscription receives
+    ///
+    /// A [`GROUP_FILTER`] matches any of the comma-separated names in the
+    /// event's `groups` attribute, which [`record_event`] fills in.
+    #[must_use]
+    pub fn matches(&self, event: &WebhookEvent) -> bool {
+        (self.events.is_empty() || self.events.contains(&event.event_type))
+            && self.filters.iter().all(|(key, expected)| {
+                if key == GROUP_FILTER {
+                    return event.attribute("groups").is_some_and(|groups| {
+                        groups
+                            .split(',')
+                            .any(|group| group.eq_ignore_ascii_case(expected))
+                    });
+                }
+                event
+                    .attribute(key)
+                    .is_some_and(|value| value.eq_ignore_ascii_case(expected))
+            })
+    }
+
+    /// Copy of the subscription safe to show, with the secret hidden
+    #[must_use]
+    pub fn redacted(&self) -> Self {
+        Self {
+            secret: self.secret.as_ref().map(|_| REDACTED.to_string()),
+            ..self.clone()
+        }
+    }
+}
+
+/// Signs a request body, returning the [`SIGNATURE_HEADER`] value
+///
+/// # Errors
+/// Returns an unsupported-operation error if built without the `secrets` feature.
+#[cfg(feature = "secrets")]
+pub fn sign(secret: &str, body: &[u8]) -> DataStoreResult<String> {
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+        unreachable!("HMAC accepts keys of any length")
+    };
+    mac.update(body);
+    let digest = mac.finalize().into_bytes();
+    let mut signature = String::from("sha256=");
+    for byte in digest {
+        let _ = write!(signature, "{byte:02x}");
+    }
+    Ok(signature)
+}
+
+/// Signs a request body, returning the [`SIGNATURE_HEADER`] value
+///
+/// # Errors
+/// Returns an unsupported-operation error if built without the `secrets` feature.
+#[cfg(not(feature = "secrets"))]
+pub fn sign(_secret: &str, _body: &[u8]) -> DataStoreResult<String> {
+    Err(DataStoreError::UnsupportedOperation {
+        operation: "webhook signing without the `secrets` feature".to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,207 @@
+//! Persistence of subscriptions, queued deliveries, and dead letters through
+//! the `DataStore` settings API
+
+use super::{Delivery, GROUP_FILTER, WebhookEvent, WebhookSubscription, flood};
+use crate::datastore::{DataStore, DataStoreError, DataStoreResult};
+use crate::groups::{list_groups, memberships};
+use crate::models::Node;
+use chrono::Utc;
+use serde::{Serialize, de::DeserializeOwned};
+use uuid::Uuid;
+
+/// Settings namespace holding subscriptions keyed by ID
+const SUBSCRIPTIONS_NAMESPACE: &str = "webhook_subscriptions";
+/// Settings namespace holding deliveries awaiting their next attempt
+pub(super) const OUTBOX_NAMESPACE: &str = "webhook_outbox";
+/// Settings namespace holding deliveries that ran out of attempts
+pub(super) const DEAD_LETTERS_NAMESPACE: &str = "webhook_dead_letters";
+/// Settings namespace holding each rate-limited subscription's current minute
+pub(super) const RATE_WINDOWS_NAMESPACE: &str = "webhook_rate_windows";
+/// Settings namespace mapping each open group, keyed by subscription and
+/// dedupe key, to the queued delivery holding it
+pub(super) const OPEN_GROUPS_NAMESPACE: &str = "webhook_open_groups";
+
+pub(super) fn parse<T: DeserializeOwned>(
+    key: &str,
+    value: serde_json::Value,
+) -> DataStoreResult<T> {
+    serde_json::from_value(value).map_err(|e| DataStoreError::InternalError {
+        message: format!("stored webhook data {key}: {e}"),
+    })
+}
+
+pub(super) async fn list<T: DeserializeOwned>(
+    datastore: &dyn DataStore,
+    namespace: &str,
+) -> DataStoreResult<Vec<T>> {
+    datastore
+        .list_settings(namespace)
+        .await?
+        .into_iter()
+        .map(|(key, value)| parse(&key, value))
+        .collect()
+}
+
+pub(super) async fn put<T: Serialize>(
+    datastore: &dyn DataStore,
+    namespace: &str,
+    id: Uuid,
+    value: &T,
+) -> DataStoreResult<()> {
+    let key = id.to_string();
+    let value = serde_json::to_value(value).map_err(|e| DataStoreError::InternalError {
+        message: format!("webhook data {key}: {e}"),
+    })?;
+    datastore.put_setting(namespace, &key, &value).await
+}
+
+/// Lists all subscriptions, oldest first
+///
+/// # Errors
+/// Returns an error if the subscriptions cannot be read or one does not parse.
+pub async fn list_subscriptions(
+    datastore: &dyn DataStore,
+) -> DataStoreResult<Vec<WebhookSubscription>> {
+    let mut subscriptions: Vec<WebhookSubscription> =
+        list(datastore, SUBSCRIPTIONS_NAMESPACE).await?;
+    subscriptions.sort_by_key(|subscription| subscription.created_at);
+    Ok(subscriptions)
+}
+
+/// Stores a subscription, replacing any with the same ID
+///
+/// # Errors
+/// Returns an error if the datastore write fails.
+pub async fn save_subscription(
+    datastore: &dyn DataStore,
+    subscription: &WebhookSubscription,
+) -> DataStoreResult<()> {
+    put(
+        datastore,
+        SUBSCRIPTIONS_NAMESPACE,
+        subscription.id,
+        subscription,
+    )
+    .await
+}
+
+/// Removes a subscription; its queued deliveries are dropped unsent
+///
+/// # Errors
+/// Returns an error if the subscription does not exist or the datastore write fails.
+pub async fn delete_subscription(datastore: &dyn DataStore, id: Uuid) -> DataStoreResult<()> {
+    let key = id.to_string();
+    datastore
+        .delete_setting(SUBSCRIPTIONS_NAMESPACE, &key)
+        .await?;
+    match datastore.delete_setting(RATE_WINDOWS_NAMESPACE, &key).await {
+        Err(DataStoreError::NotFound { .. }) | Ok(()) => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Queues a delivery of the event for every subscription it matches,
+/// returning how many subscriptions it was queued for
+///
+/// For subscriptions with [`FloodControl`](super::FloodControl) the event
+/// may instead join a delivery already queued. Datastores without settings
+/// support have no subscriptions and queue nothing.
+///
+/// # Errors
+/// Returns an error if subscriptions cannot be read or a delivery cannot be queued.
+pub async fn record_event(
+    datastore: &dyn DataStore,
+    event: &WebhookEvent,
+) -> DataStoreResult<usize> {
+    let subscriptions = match list_subscriptions(datastore).await {
+        Ok(subscriptions) => subscriptions,
+        Err(DataStoreError::UnsupportedOperation { .. }) => return Ok(0),
+        Err(e) => return Err(e),
+    };
+    let event = &with_groups(datastore, event, &subscriptions).await?;
+    let matching: Vec<&WebhookSubscription> =
+        subscriptions.iter().filter(|s| s.matches(event)).collect();
+    for subscription in &matching {
+        if subscription.flood_control.is_enabled() {
+            flood::queue(datastore, subscription, event).await?;
+        } else {
+            let delivery = Delivery::new(subscription, event.clone());
+            put(datastore, OUTBOX_NAMESPACE, delivery.id, &delivery).await?;
+        }
+    }
+    Ok(matching.len())
+}
+
+/// Adds the node groups a node event's node is currently a member of as
+/// the `groups` attribute, when some subscription filters on [`GROUP_FILTER`]
+///
+/// A node that can no longer be read, such as a deleted one, is evaluated as
+/// it was from the event details.
+async fn with_groups(
+    datastore: &dyn DataStore,
+    event: &WebhookEvent,
+    subscriptions: &[WebhookSubscription],
+) -> DataStoreResult<WebhookEvent> {
+    let mut event = event.clone();
+    let filtered = subscriptions
+        .iter()
+        .any(|subscription| subscription.filters.contains_key(GROUP_FILTER));
+    if event.entity_type != "node" || !filtered {
+        return Ok(event);
+    }
+    let node = match datastore.get_node(&event.entity_id).await {
+        Ok(Some(node)) => Some(node),
+        _ => serde_json::from_value::<Node>(event.data.clone()).ok(),
+    };
+    if let Some(node) = node {
+        let groups = memberships(&list_groups(datastore).await?, &node);
+        event
+            .attributes
+            .insert("groups".to_string(), groups.join(","));
+    }
+    Ok(event)
+}
+
+/// Lists deliveries that ran out of attempts, most recent failure last
+///
+/// # Errors
+/// Returns an error if the dead letters cannot be read or one does not parse.
+pub async fn list_dead_letters(datastore: &dyn DataStore) -> DataStoreResult<Vec<Delivery>> {
+    let mut dead_letters: Vec<Delivery> = list(datastore, DEAD_LETTERS_NAMESPACE).await?;
+    dead_letters.sort_by_key(|delivery| delivery.last_attempt_at);
+    Ok(dead_letters)
+}
+
+/// Requeues a dead letter for immediate delivery with a fresh set of attempts
+///
+/// # Errors
+/// Returns a not-found error if there is no such dead letter, or an error if
+/// the datastore cannot be read or written.
+pub async fn retry_dead_letter(datastore: &dyn DataStore, id: Uuid) -> DataStoreResult<Delivery> {
+    let key = id.to_string();
+    let value = datastore
+        .get_setting(DEAD_LETTERS_NAMESPACE, &key)
+        .await?
+        .ok_or_else(|| DataStoreError::NotFound {
+            entity_type: "Webhook dead letter".to_string(),
+            id: key.clone(),
+        })?;
+    let mut delivery: Delivery = parse(&key, value)?;
+    delivery.attempts = 0;
+    delivery.next_attempt_at = Utc::now();
+    put(datastore, OUTBOX_NAMESPACE, id, &delivery).await?;
+    datastore
+        .delete_setting(DEAD_LETTERS_NAMESPACE, &key)
+        .await?;
+    Ok(delivery)
+}
+
+/// Discards a dead letter
+///
+/// # Errors
+/// Returns an error if there is no such dead letter or the datastore write fails.
+pub async fn delete_dead_letter(datastore: &dyn DataStore, id: Uuid) -> DataStoreResult<()> {
+    datastore
+        .delete_setting(DEAD_LETTERS_NAMESPACE, &id.to_string())
+        .await
+}
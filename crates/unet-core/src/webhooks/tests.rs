@@ -0,0 +1,440 @@
+use super::store::OPEN_GROUPS_NAMESPACE;
+use super::*;
+use crate::datastore::DataStore;
+use crate::datastore::sqlite::tests::setup::settings_store;
+use crate::measurement::ThresholdBreach;
+use crate::models::{DeviceRole, Link, Node, Vendor};
+use async_trait::async_trait;
+use chrono::TimeDelta;
+use futures_util::future::join_all;
+use std::sync::Mutex;
+
+fn router() -> Node {
+    Node::new(
+        "edge-1".to_string(),
+        "example.com".to_string(),
+        Vendor::Juniper,
+        DeviceRole::Router,
+    )
+}
+
+/// Records requests and fails while `failing` is set
+#[derive(Default)]
+struct RecordingSender {
+    failing: bool,
+    requests: Mutex<Vec<WebhookRequest>>,
+}
+
+#[async_trait]
+impl WebhookSender for RecordingSender {
+    async fn send(&self, request: &WebhookRequest) -> Result<(), String> {
+        self.requests.lock().unwrap().push(request.clone());
+        if self.failing {
+            Err("HTTP 503".to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[test]
+fn test_subscription_matches_event_types_and_filters() {
+    let filters = BTreeMap::from([("vendor".to_string(), "JUNIPER".to_string())]);
+    let subscription = WebhookSubscription::new(
+        "https://hooks.example.com/unet",
+        None,
+        vec![EventType::NodeCreated],
+        filters,
+    )
+    .unwrap();
+    let mut node = router();
+
+    assert!(subscription.matches(&WebhookEvent::node(EventType::NodeCreated, &node)));
+    assert!(!subscription.matches(&WebhookEvent::node(EventType::NodeDeleted, &node)));
+    node.vendor = Vendor::Cisco;
+    assert!(!subscription.matches(&WebhookEvent::node(EventType::NodeCreated, &node)));
+
+    assert!(WebhookSubscription::new("ftp://example.com", None, vec![], BTreeMap::new()).is_err());
+    assert_eq!("alarm.raised".parse(), Ok(EventType::AlarmRaised));
+    assert!("node.renamed".parse::<EventType>().is_err());
+}
+
+#[tokio::test]
+async fn test_group_filter_matches_current_members() {
+    let store = settings_store().await;
+    let group = crate::groups::NodeGroup::new("juniper", r#"node.vendor == "juniper""#, None);
+    crate::groups::save_group(&store, &group.unwrap())
+        .await
+        .unwrap();
+    let filters = BTreeMap::from([(GROUP_FILTER.to_string(), "juniper".to_string())]);
+    let subscription =
+        WebhookSubscription::new("https://hooks.example.com/unet", None, vec![], filters).unwrap();
+    save_subscription(&store, &subscription).await.unwrap();
+    let mut node = router();
+
+    let event = WebhookEvent::node(EventType::NodeDeleted, &node);
+    assert!(!subscription.matches(&event));
+    assert_eq!(record_event(&store, &event).await.unwrap(), 1);
+
+    node.vendor = Vendor::Cisco;
+    let event = WebhookEvent::node(EventType::NodeDeleted, &node);
+    assert_eq!(record_event(&store, &event).await.unwrap(), 0);
+}
+
+#[test]
+fn test_sign_matches_known_hmac() {
+    // RFC 4231 test case 2
+    assert_eq!(
+        sign("Jefe", b"what do ya want for nothing?").unwrap(),
+        "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+    );
+}
+
+#[test]
+fn test_alarm_events_follow_breach_changes() {
+    let link = Link::new(
+        "edge-1-core".to_string(),
+        Uuid::new_v4(),
+        "eth0".to_string(),
+        Uuid::new_v4(),
+        "eth0".to_string(),
+    );
+    let breach = |metric: &str| ThresholdBreach {
+        metric: metric.to_string(),
+        value: 20.0,
+        threshold: 10.0,
+    };
+
+    let events = WebhookEvent::alarms(
+        &link,
+        &[breach("latency_ms")],
+        &[breach("latency_ms"), breach("loss_percent")],
+    );
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].event_type, EventType::AlarmRaised);
+    assert_eq!(events[0].attribute("metric").unwrap(), "loss_percent");
+
+    let events = WebhookEvent::alarms(&link, &[breach("latency_ms")], &[]);
+    assert_eq!(events[0].event_type, EventType::AlarmCleared);
+}
+
+#[tokio::test]
+async fn test_recorded_event_is_delivered_signed() {
+    let store = settings_store().await;
+    let subscription = WebhookSubscription::new(
+        "https://hooks.example.com/unet",
+        Some("s3cret".to_string()),
+        vec![],
+        BTreeMap::new(),
+    )
+    .unwrap();
+    save_subscription(&store, &subscription).await.unwrap();
+
+    let event = WebhookEvent::node(EventType::NodeUpdated, &router());
+    assert_eq!(record_event(&store, &event).await.unwrap(), 1);
+
+    let sender = RecordingSender::default();
+    let stats = deliver_pending(&store, &sender, Utc::now()).await.unwrap();
+    assert_eq!(stats.delivered, 1);
+
+    let requests = sender.requests.into_inner().unwrap();
+    let request = &requests[0];
+    assert_eq!(request.url, "https://hooks.example.com/unet");
+    assert!(
+        request
+            .headers
+            .contains(&(EVENT_HEADER, "node.updated".to_string()))
+    );
+    assert!(
+        request
+            .headers
+            .contains(&(SIGNATURE_HEADER, sign("s3cret", &request.body).unwrap()))
+    );
+
+    let stats = deliver_pending(&store, &RecordingSender::default(), Utc::now())
+        .await
+        .unwrap();
+    assert_eq!(stats, DeliveryStats::default());
+}
+
+#[tokio::test]
+async fn test_failed_delivery_retries_then_dead_letters() {
+    let store = settings_store().await;
+    let subscription = WebhookSubscription::new(
+        "https://hooks.example.com/unet",
+        None,
+        vec![EventType::NodeDeleted],
+        BTreeMap::new(),
+    )
+    .unwrap();
+    save_subscription(&store, &subscription).await.unwrap();
+    record_event(
+        &store,
+        &WebhookEvent::node(EventType::NodeDeleted, &router()),
+    )
+    .await
+    .unwrap();
+
+    let sender = RecordingSender {
+        failing: true,
+        ..RecordingSender::default()
+    };
+    let mut now = Utc::now();
+    let stats = deliver_pending(&store, &sender, now).await.unwrap();
+    assert_eq!(stats.retried, 1);
+    // Not due again until the backoff has passed
+    let stats = deliver_pending(&store, &sender, now).await.unwrap();
+    assert_eq!(stats, DeliveryStats::default());
+
+    for _ in 1..MAX_ATTEMPTS {
+        now += TimeDelta::hours(1);
+        deliver_pending(&store, &sender, now).await.unwrap();
+    }
+    let dead_letters = list_dead_letters(&store).await.unwrap();
+    assert_eq!(dead_letters.len(), 1);
+    assert_eq!(dead_letters[0].attempts, MAX_ATTEMPTS);
+    assert_eq!(dead_letters[0].last_error.as_deref(), Some("HTTP 503"));
+
+    retry_dead_letter(&store, dead_letters[0].id).await.unwrap();
+    assert!(list_dead_letters(&store).await.unwrap().is_empty());
+    let stats = deliver_pending(&store, &RecordingSender::default(), Utc::now())
+        .await
+        .unwrap();
+    assert_eq!(stats.delivered, 1);
+}
+
+fn delivered_group(request: &WebhookRequest) -> EventGroup {
+    let event: WebhookEvent = serde_json::from_slice(&request.body).unwrap();
+    event.group.unwrap()
+}
+
+#[tokio::test]
+async fn test_events_grouped_by_dedupe_key() {
+    let store = settings_store().await;
+    let subscription = WebhookSubscription::new(
+        "https://hooks.example.com/unet",
+        None,
+        vec![EventType::NodeUpdated],
+        BTreeMap::new(),
+    )
+    .unwrap()
+    .with_flood_control(FloodControl {
+        group_window: 300,
+        dedupe_by: vec!["location_id".to_string()],
+        rate_limit: None,
+    })
+    .unwrap();
+    save_subscription(&store, &subscription).await.unwrap();
+
+    let site = Uuid::new_v4();
+    for _ in 0..150 {
+        let mut node = router();
+        node.id = Uuid::new_v4();
+        node.location_id = Some(site);
+        let event = WebhookEvent::node(EventType::NodeUpdated, &node);
+        assert_eq!(record_event(&store, &event).await.unwrap(), 1);
+    }
+    let mut elsewhere = router();
+    elsewhere.location_id = Some(Uuid::new_v4());
+    record_event(
+        &store,
+        &WebhookEvent::node(EventType::NodeUpdated, &elsewhere),
+    )
+    .await
+    .unwrap();
+
+    // Held until the group window closes
+    let sender = RecordingSender::default();
+    let stats = deliver_pending(&store, &sender, Utc::now()).await.unwrap();
+    assert_eq!(stats, DeliveryStats::default());
+    let open_groups = store.list_settings(OPEN_GROUPS_NAMESPACE).await.unwrap();
+    assert_eq!(open_groups.len(), 2);
+
+    let later = Utc::now() + TimeDelta::seconds(301);
+    let stats = deliver_pending(&store, &sender, later).await.unwrap();
+    assert_eq!(stats.delivered, 2);
+    let open_groups = store.list_settings(OPEN_GROUPS_NAMESPACE).await.unwrap();
+    assert!(open_groups.is_empty());
+    let mut groups: Vec<EventGroup> = sender
+        .requests
+        .into_inner()
+        .unwrap()
+        .iter()
+        .map(delivered_group)
+        .collect();
+    groups.sort_by_key(|group| group.count);
+    assert_eq!(groups[0].count, 1);
+    assert_eq!(groups[1].count, 150);
+    assert_eq!(groups[1].entity_ids.len(), MAX_GROUPED_ENTITIES);
+    assert_eq!(groups[1].key, format!("node.updated:location_id={site}"));
+}
+
+#[tokio::test]
+async fn test_concurrent_events_join_one_group() {
+    let store = settings_store().await;
+    let subscription = WebhookSubscription::new(
+        "https://hooks.example.com/unet",
+        None,
+        vec![EventType::NodeUpdated],
+        BTreeMap::new(),
+    )
+    .unwrap()
+    .with_flood_control(FloodControl {
+        group_window: 300,
+        dedupe_by: vec!["location_id".to_string()],
+        rate_limit: None,
+    })
+    .unwrap();
+    save_subscription(&store, &subscription).await.unwrap();
+
+    let site = Uuid::new_v4();
+    let events: Vec<WebhookEvent> = (0..20)
+        .map(|_| {
+            let mut node = router();
+            node.id = Uuid::new_v4();
+            node.location_id = Some(site);
+            WebhookEvent::node(EventType::NodeUpdated, &node)
+        })
+        .collect();
+    for queued in join_all(events.iter().map(|event| record_event(&store, event))).await {
+        assert_eq!(queued.unwrap(), 1);
+    }
+
+    let sender = RecordingSender::default();
+    let later = Utc::now() + TimeDelta::seconds(301);
+    let stats = deliver_pending(&store, &sender, later).await.unwrap();
+    assert_eq!(stats.delivered, 1);
+    let group = delivered_group(&sender.requests.into_inner().unwrap()[0]);
+    assert_eq!(group.count, 20);
+}
+
+#[tokio::test]
+async fn test_events_past_rate_limit_become_flood_notification() {
+    let store = settings_store().await;
+    let subscription = WebhookSubscription::new(
+        "https://hooks.example.com/unet",
+        None,
+        vec![],
+        BTreeMap::new(),
+    )
+    .unwrap()
+    .with_flood_control(FloodControl {
+        rate_limit: Some(2),
+        ..FloodControl::default()
+    })
+    .unwrap();
+    save_subscription(&store, &subscription).await.unwrap();
+
+    for _ in 0..5 {
+        let mut node = router();
+        node.id = Uuid::new_v4();
+        record_event(&store, &WebhookEvent::node(EventType::NodeDeleted, &node))
+            .await
+            .unwrap();
+    }
+
+    let sender = RecordingSender::default();
+    let stats = deliver_pending(&store, &sender, Utc::now()).await.unwrap();
+    assert_eq!(stats.delivered, 2);
+    assert!(sender.requests.lock().unwrap().iter().all(|request| {
+        serde_json::from_slice::<WebhookEvent>(&request.body)
+            .unwrap()
+            .group
+            .is_none()
+    }));
+
+    let sender = RecordingSender::default();
+    let later = Utc::now() + TimeDelta::seconds(61);
+    let stats = deliver_pending(&store, &sender, later).await.unwrap();
+    assert_eq!(stats.delivered, 1);
+    let group = delivered_group(&sender.requests.into_inner().unwrap()[0]);
+    assert_eq!(group.key, "flood:node.deleted");
+    assert_eq!(group.count, 3);
+}
+
+#[test]
+fn test_flood_control_validation() {
+    let subscription =
+        WebhookSubscription::new("https://hooks.example.com", None, vec![], BTreeMap::new())
+            .unwrap();
+    let invalid = [
+        FloodControl {
+            rate_limit: Some(0),
+            ..FloodControl::default()
+        },
+        FloodControl {
+            dedupe_by: vec!["location_id".to_string()],
+            ..FloodControl::default()
+        },
+        FloodControl {
+            group_window: MAX_GROUP_WINDOW + 1,
+            ..FloodControl::default()
+        },
+    ];
+    for flood_control in invalid {
+        assert!(
+            subscription
+                .clone()
+                .with_flood_control(flood_control)
+                .is_err()
+        );
+    }
+}
+
+#[test]
+fn test_ticket_payload_formats() {
+    let node = router();
+    let event = WebhookEvent::new(
+        EventType::PolicyFailed,
+        "node",
+        node.id,
+        BTreeMap::from([("name".to_string(), node.name.clone())]),
+        serde_json::json!({ "node": node.name, "failures": [] }),
+    );
+    let subscription =
+        WebhookSubscription::new("https://itsm.example.com", None, vec![], BTreeMap::new())
+            .unwrap();
+    let body = |payload_format: PayloadFormat| {
+        let subscription = subscription
+            .clone()
+            .with_payload_format(payload_format)
+            .unwrap();
+        let request =
+            WebhookRequest::new(&subscription, &Delivery::new(&subscription, event.clone()))
+                .unwrap();
+        serde_json::from_slice::<serde_json::Value>(&request.body).unwrap()
+    };
+
+    let incident = body(PayloadFormat::ServiceNow);
+    assert_eq!(
+        incident["short_description"],
+        "Policy compliance failure on node edge-1"
+    );
+    assert_eq!(incident["urgency"], "2");
+    assert_eq!(incident["cmdb_ci"], "edge-1");
+    assert_eq!(
+        incident["correlation_id"],
+        format!("unet:policy.failed:{}", node.id)
+    );
+
+    let issue = body(PayloadFormat::Jira {
+        project: "NET".to_string(),
+        issue_type: DEFAULT_JIRA_ISSUE_TYPE.to_string(),
+    });
+    assert_eq!(issue["fields"]["project"]["key"], "NET");
+    assert_eq!(issue["fields"]["issuetype"]["name"], "Task");
+    assert_eq!(
+        issue["fields"]["summary"],
+        "Policy compliance failure on node edge-1"
+    );
+
+    assert_eq!(body(PayloadFormat::Event)["type"], "policy.failed");
+    assert!(
+        subscription
+            .with_payload_format(PayloadFormat::Jira {
+                project: " ".to_string(),
+                issue_type: DEFAULT_JIRA_ISSUE_TYPE.to_string(),
+            })
+            .is_err()
+    );
+}
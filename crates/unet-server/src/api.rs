@@ -1,6 +1,7 @@
 //! API data transfer objects and response types
 
 use serde::{Deserialize, Serialize};
+use unet_core::models::NodeFields;
 use unet_core::prelude::*;
 use uuid::Uuid;
 
@@ -50,20 +51,47 @@ impl ApiError {
 }
 
 /// Extended node response including derived state
-#[derive(Debug, Serialize)]
+#[derive(Debug)]
 pub struct NodeResponse {
     /// Core node data
-    #[serde(flatten)]
     pub node: Node,
     /// Current status and derived state (if available)
     pub status: Option<NodeStatus>,
+    /// Fields to serialize; all fields when `None`
+    pub fields: Option<NodeFields>,
 }
 
 impl NodeResponse {
     /// Create from node only
     #[must_use]
     pub const fn from_node(node: Node) -> Self {
-        Self { node, status: None }
+        Self {
+            node,
+            status: None,
+            fields: None,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct NodeResponseBody<'a> {
+    #[serde(flatten)]
+    node: &'a Node,
+    status: &'a Option<NodeStatus>,
+}
+
+impl Serialize for NodeResponse {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let body = NodeResponseBody {
+            node: &self.node,
+            status: &self.status,
+        };
+        let Some(fields) = &self.fields else {
+            return body.serialize(serializer);
+        };
+        let mut value = serde_json::to_value(&body).map_err(serde::ser::Error::custom)?;
+        fields.project(&mut value);
+        value.serialize(serializer)
     }
 }
 
@@ -84,17 +112,22 @@ pub struct CreateNodeRequest {
     pub lifecycle: Lifecycle,
     /// Location ID (optional)
     pub location_id: Option<Uuid>,
-    /// Management IP (optional)
+    /// Management IP (optional); link-local IPv6 takes a zone (`fe80::1%eth0`)
     pub management_ip: Option<String>,
+    /// IPv6 management address of a dual-stack node (optional)
+    pub management_ipv6: Option<String>,
     /// Custom data (optional)
     pub custom_data: Option<serde_json::Value>,
+    /// Slug to use in place of the ID (optional); derived from the name if omitted
+    #[serde(default)]
+    pub slug: Option<String>,
 }
 
 impl CreateNodeRequest {
     /// Convert to Node using builder
     ///
     /// # Errors
-    /// Returns an error if validation fails or if `management_ip` cannot be parsed.
+    /// Returns an error if validation fails or if a management address cannot be parsed.
     pub fn into_node(self) -> Result<Node> {
         let mut builder = NodeBuilder::new()
             .name(self.name)
@@ -111,27 +144,21 @@ impl CreateNodeRequest {
             builder = builder.location_id(location_id);
         }
 
-        if let Some(management_ip) = self.management_ip {
-            // Parse IP address string to IpAddr
-            let ip: std::net::IpAddr = management_ip.parse().map_err(|_| {
-                Error::validation_with_value(
-                    "management_ip",
-                    "Invalid IP address format",
-                    &management_ip,
-                )
-            })?;
-            builder = builder.management_ip(ip);
-        }
-
         if let Some(custom_data) = self.custom_data {
             builder = builder.custom_data(custom_data);
         }
 
-        builder.build().map_err(|e| Error::Other {
+        let mut node = builder.build().map_err(|e| Error::Other {
             context: "Node creation".to_string(),
             message: e,
             source: None,
-        })
+        })?;
+        node.set_management_addresses(
+            self.management_ip.as_deref(),
+            self.management_ipv6.as_deref(),
+        )
+        .map_err(|e| Error::validation("management_ip", e))?;
+        Ok(node)
     }
 }
 
@@ -152,10 +179,84 @@ pub struct UpdateNodeRequest {
     pub lifecycle: Option<Lifecycle>,
     /// Location ID (optional)
     pub location_id: Option<Uuid>,
-    /// Management IP (optional)
+    /// Management IP (optional); link-local IPv6 takes a zone (`fe80::1%eth0`)
     pub management_ip: Option<String>,
+    /// IPv6 management address of a dual-stack node (optional)
+    pub management_ipv6: Option<String>,
+    /// Custom data (optional)
+    pub custom_data: Option<serde_json::Value>,
+}
+
+/// Request to create a link, or to replace one on update
+#[derive(Debug, Deserialize)]
+pub struct LinkRequest {
+    /// Link name
+    pub name: String,
+    /// Node A ID
+    pub source_node_id: Uuid,
+    /// Node A interface name
+    pub node_a_interface: String,
+    /// Node Z ID (optional for internet circuits)
+    pub dest_node_id: Option<Uuid>,
+    /// Node Z interface name (optional for internet circuits)
+    pub node_z_interface: Option<String>,
+    /// Whether the link is an internet circuit without node Z
+    #[serde(default)]
+    pub is_internet_circuit: bool,
+    /// Description (optional)
+    pub description: Option<String>,
+    /// Bandwidth in bits per second (optional)
+    pub bandwidth: Option<u64>,
+    /// Service provider (optional)
+    pub provider: Option<String>,
+    /// Provider circuit identifier (optional)
+    pub circuit_id: Option<String>,
+    /// Provisioning state (optional, defaults to live)
+    pub provisioning_state: Option<ProvisioningState>,
     /// Custom data (optional)
     pub custom_data: Option<serde_json::Value>,
+    /// Slug to use in place of the ID on create (optional); derived from the
+    /// name if omitted
+    #[serde(default)]
+    pub slug: Option<String>,
+}
+
+impl LinkRequest {
+    /// Convert to a link builder
+    #[must_use]
+    pub fn into_builder(self) -> LinkBuilder {
+        let mut builder = LinkBuilder::new()
+            .name(self.name)
+            .source_node_id(self.source_node_id)
+            .node_a_interface(self.node_a_interface)
+            .is_internet_circuit(self.is_internet_circuit);
+
+        if let Some(dest_node_id) = self.dest_node_id {
+            builder = builder.dest_node_id(dest_node_id);
+        }
+        if let Some(node_z_interface) = self.node_z_interface {
+            builder = builder.node_z_interface(node_z_interface);
+        }
+        if let Some(description) = self.description {
+            builder = builder.description(description);
+        }
+        if let Some(bandwidth) = self.bandwidth {
+            builder = builder.bandwidth(bandwidth);
+        }
+        if let Some(provider) = self.provider {
+            builder = builder.provider(provider);
+        }
+        if let Some(circuit_id) = self.circuit_id {
+            builder = builder.circuit_id(circuit_id);
+        }
+        if let Some(state) = self.provisioning_state {
+            builder = builder.provisioning_state(state);
+        }
+        if let Some(custom_data) = self.custom_data {
+            builder = builder.custom_data(custom_data);
+        }
+        builder
+    }
 }
 
 /// Paginated response wrapper
@@ -197,7 +298,9 @@ mod tests {
             lifecycle: Lifecycle::Live,
             location_id: Some(location_id),
             management_ip: None,
+            management_ipv6: None,
             custom_data: None,
+            slug: None,
         };
 
         let result = request.into_node();
@@ -217,7 +320,9 @@ mod tests {
             lifecycle: Lifecycle::Live,
             location_id: None,
             management_ip: Some("invalid-ip".to_string()),
+            management_ipv6: None,
             custom_data: None,
+            slug: None,
         };
 
         let result = request.into_node();
@@ -235,7 +340,9 @@ mod tests {
             lifecycle: Lifecycle::Live,
             location_id: None,
             management_ip: Some("192.168.1.1".to_string()),
+            management_ipv6: None,
             custom_data: None,
+            slug: None,
         };
 
         let result = request.into_node();
@@ -244,6 +351,27 @@ mod tests {
         assert_eq!(node.management_ip, Some("192.168.1.1".parse().unwrap()));
     }
 
+    #[test]
+    fn test_create_node_request_dual_stack_link_local() {
+        let request = CreateNodeRequest {
+            name: "test-node".to_string(),
+            domain: Some("example.com".to_string()),
+            vendor: Vendor::Cisco,
+            model: "ASR1000".to_string(),
+            role: DeviceRole::Router,
+            lifecycle: Lifecycle::Live,
+            location_id: None,
+            management_ip: Some("192.168.1.1".to_string()),
+            management_ipv6: Some("fe80::1%eth0".to_string()),
+            custom_data: None,
+            slug: None,
+        };
+
+        let node = request.into_node().unwrap();
+        assert_eq!(node.management_ipv6, Some("fe80::1".parse().unwrap()));
+        assert_eq!(node.management_zone.as_deref(), Some("eth0"));
+    }
+
     #[test]
     fn test_create_node_request_custom_data() {
         let custom_data = json!({"key": "value", "number": 42});
@@ -256,7 +384,9 @@ mod tests {
             lifecycle: Lifecycle::Live,
             location_id: None,
             management_ip: None,
+            management_ipv6: None,
             custom_data: Some(custom_data.clone()),
+            slug: None,
         };
 
         let result = request.into_node();
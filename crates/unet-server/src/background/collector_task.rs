@@ -0,0 +1,303 @@
+//! Periodic eAPI and RESTCONF collection of derived state
+
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{RwLock, Semaphore};
+use tokio::time::interval;
+use tracing::{debug, info, warn};
+use unet_core::{
+    collectors::{
+        CollectedState, HttpMethod, HttpRequest, HttpResponse, HttpTransport, collect_node,
+    },
+    config::{CollectorsConfig, Config},
+    datastore::{DataStore, DataStoreError, DataStoreResult, QueryOptions},
+    enrichment::{EnrichmentPipeline, enrich_node},
+    models::{AddressFamilyPreference, Node},
+    snmp::{pauses::paused_nodes, shards::ShardSet},
+};
+
+/// Makes collector requests over HTTPS
+pub struct HttpClientTransport {
+    client: reqwest::Client,
+}
+
+impl HttpClientTransport {
+    /// Create a transport with the configured request timeout and TLS
+    /// verification
+    pub fn new(config: &CollectorsConfig) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(config.timeout))
+                .tls_danger_accept_invalid_certs(!config.verify_tls)
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+}
+
+#[async_trait]
+impl HttpTransport for HttpClientTransport {
+    async fn send(
+        &self,
+        request: &HttpRequest,
+        username: &str,
+        password: &str,
+    ) -> Result<HttpResponse, String> {
+        let mut builder = match request.method {
+            HttpMethod::Get => self.client.get(&request.url),
+            HttpMethod::Post => self.client.post(&request.url),
+        }
+        .basic_auth(username, Some(password));
+        for (name, value) in &request.headers {
+            builder = builder.header(*name, value);
+        }
+        if let Some(body) = &request.body {
+            builder = builder.body(body.clone());
+        }
+        let response = builder.send().await.map_err(|e| e.to_string())?;
+        let status = response.status().as_u16();
+        let body = response.bytes().await.map_err(|e| e.to_string())?;
+        Ok(HttpResponse {
+            status,
+            body: body.to_vec(),
+        })
+    }
+}
+
+/// Collects from single nodes on request, outside the collection schedule
+///
+/// At most `collectors.max_concurrent` polls collect at once; the others
+/// wait for a slot, and the wait counts toward their timeout.
+pub struct NodePoller {
+    transport: Arc<dyn HttpTransport>,
+    config: CollectorsConfig,
+    address_family: AddressFamilyPreference,
+    slots: Semaphore,
+    enrichment: EnrichmentPipeline,
+}
+
+impl NodePoller {
+    /// Create a poller collecting over HTTPS with the server's collector
+    /// settings
+    pub fn new(config: &Config) -> Self {
+        Self::with_transport(
+            Arc::new(HttpClientTransport::new(&config.collectors)),
+            config.collectors.clone(),
+            config.snmp.address_family,
+        )
+    }
+
+    /// Create a poller making its requests through `transport`
+    pub fn with_transport(
+        transport: Arc<dyn HttpTransport>,
+        config: CollectorsConfig,
+        address_family: AddressFamilyPreference,
+    ) -> Self {
+        let slots = Semaphore::new(config.max_concurrent.max(1));
+        Self {
+            transport,
+            config,
+            address_family,
+            slots,
+            enrichment: EnrichmentPipeline::default(),
+        }
+    }
+
+    /// Run `enrichment` on each collection and store its output
+    #[must_use]
+    pub fn with_enrichment(mut self, enrichment: EnrichmentPipeline) -> Self {
+        self.enrichment = enrichment;
+        self
+    }
+
+    /// Collects from `node` now, waiting at most `timeout` for a slot and
+    /// the device, and enriches the node's updated status
+    ///
+    /// Returns `Ok(None)` for nodes that have not opted into a collector.
+    ///
+    /// # Errors
+    /// Returns a `Timeout` error if the poll does not finish in time, or the
+    /// errors of [`collect_node`].
+    pub async fn poll(
+        &self,
+        datastore: &dyn DataStore,
+        node: &Node,
+        timeout: Duration,
+    ) -> DataStoreResult<Option<CollectedState>> {
+        let credentials = (
+            self.config.username.as_deref().unwrap_or_default(),
+            self.config.password.as_deref().unwrap_or_default(),
+        );
+        let poll = async {
+            let _slot = self
+                .slots
+                .acquire()
+                .await
+                .map_err(|e| DataStoreError::InternalError {
+                    message: format!("Poll slots closed: {e}"),
+                })?;
+            let collected = collect_node(
+                datastore,
+                self.transport.as_ref(),
+                node,
+                credentials,
+                self.address_family,
+            )
+            .await?;
+            if collected.is_some() {
+                enrich_node(datastore, &self.enrichment, node.id).await?;
+            }
+            Ok(collected)
+        };
+        tokio::time::timeout(timeout, poll)
+            .await
+            .map_err(|_| DataStoreError::Timeout {
+                seconds: timeout.as_secs(),
+            })?
+    }
+}
+
+/// Background task collecting from every node that opted into a collector
+pub struct CollectorTask {
+    datastore: Arc<dyn DataStore + Send + Sync>,
+    config: CollectorsConfig,
+    address_family: AddressFamilyPreference,
+    shards: Arc<RwLock<ShardSet>>,
+    transport: HttpClientTransport,
+    enrichment: EnrichmentPipeline,
+}
+
+impl CollectorTask {
+    /// Create a new collector task collecting from the nodes in `shards`
+    pub fn new(
+        datastore: Arc<dyn DataStore + Send + Sync>,
+        config: CollectorsConfig,
+        address_family: AddressFamilyPreference,
+        shards: Arc<RwLock<ShardSet>>,
+    ) -> Self {
+        let transport = HttpClientTransport::new(&config);
+        Self {
+            datastore,
+            config,
+            address_family,
+            shards,
+            transport,
+            enrichment: EnrichmentPipeline::default(),
+        }
+    }
+
+    /// Run `enrichment` on each collection and store its output
+    #[must_use]
+    pub fn with_enrichment(mut self, enrichment: EnrichmentPipeline) -> Self {
+        self.enrichment = enrichment;
+        self
+    }
+
+    /// Run the collector task
+    pub async fn run(&self) {
+        info!(
+            "Starting collector background task with interval: {}s",
+            self.config.interval
+        );
+
+        let mut interval = interval(Duration::from_secs(self.config.interval.max(1)));
+        loop {
+            interval.tick().await;
+            debug!("Running periodic collection");
+            self.run_cycle().await;
+        }
+    }
+
+    /// Collect from every opted-in node once; nodes that fail are skipped
+    ///
+    /// Nodes under an active polling pause or in a shard another server
+    /// holds are skipped too.
+    pub async fn run_cycle(&self) {
+        let nodes = match self.datastore.list_nodes(&QueryOptions::default()).await {
+            Ok(page) => page.items,
+            Err(e) => {
+                warn!("Failed to list nodes for collection: {}", e);
+                return;
+            }
+        };
+        let paused = match paused_nodes(self.datastore.as_ref(), chrono::Utc::now()).await {
+            Ok(paused) => paused,
+            Err(e) => {
+                warn!("Failed to load polling pauses: {}", e);
+                return;
+            }
+        };
+
+        let shards = self.shards.read().await.clone();
+        let credentials = (
+            self.config.username.as_deref().unwrap_or_default(),
+            self.config.password.as_deref().unwrap_or_default(),
+        );
+
+        let mut collected = 0;
+        for node in &nodes {
+            if !shards.contains(&node.id) || paused.contains(&node.id) {
+                continue;
+            }
+            match collect_node(
+                self.datastore.as_ref(),
+                &self.transport,
+                node,
+                credentials,
+                self.address_family,
+            )
+            .await
+            {
+                Ok(Some(_)) => {
+                    collected += 1;
+                    if let Err(e) =
+                        enrich_node(self.datastore.as_ref(), &self.enrichment, node.id).await
+                    {
+                        warn!(node = %node.name, error = %e, "Enrichment failed");
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => warn!(node = %node.name, error = %e, "Collection failed"),
+            }
+        }
+        debug!("Collected from {} nodes", collected);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use unet_core::collectors::get_collected_state;
+    use unet_core::models::{DeviceRole, Node, Vendor};
+
+    #[tokio::test]
+    async fn test_run_cycle_skips_invalid_collector() {
+        let datastore: Arc<dyn DataStore + Send + Sync> =
+            Arc::new(test_support::sqlite::sqlite_store().await);
+        let mut node = Node::new(
+            "leaf-01".to_string(),
+            "example.com".to_string(),
+            Vendor::Arista,
+            DeviceRole::Switch,
+        );
+        node.custom_data = json!({ "collector": "netconf" });
+        let node = datastore.create_node(&node).await.unwrap();
+
+        let task = CollectorTask::new(
+            datastore.clone(),
+            CollectorsConfig::default(),
+            AddressFamilyPreference::default(),
+            Arc::new(RwLock::new(ShardSet::unsharded())),
+        );
+        task.run_cycle().await;
+
+        assert!(
+            get_collected_state(datastore.as_ref(), node.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,267 @@
+//! Scheduled inventory exports and the uploads to their destinations
+
+use async_trait::async_trait;
+use std::fmt::Write;
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tokio::time::interval;
+use tracing::{debug, info, warn};
+use unet_core::datastore::DataStore;
+use unet_core::exports::object_store::{Credentials, sign_put};
+use unet_core::exports::{Destination, ExportUploader, GCS_CREDENTIALS, S3_CREDENTIALS, run_due};
+use uuid::Uuid;
+
+/// How often export schedules are checked
+const SCHEDULE_INTERVAL: Duration = Duration::from_secs(60);
+/// Timeout for a single object store upload
+const UPLOAD_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Writes export files to local directories, object stores, and SFTP servers
+pub struct DestinationUploader {
+    client: reqwest::Client,
+}
+
+impl DestinationUploader {
+    /// Create an uploader with the object store upload timeout
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(UPLOAD_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    async fn put_object(
+        &self,
+        destination: &Destination,
+        path: &str,
+        content: &[u8],
+    ) -> Result<(), String> {
+        let credentials = Credentials::from_env(destination).ok_or_else(|| {
+            let names = match destination {
+                Destination::Gcs { .. } => &GCS_CREDENTIALS[..],
+                _ => &S3_CREDENTIALS[..2],
+            };
+            format!("no credentials; set {}", names.join(" and "))
+        })?;
+        let Some(put) = sign_put(destination, path, content, &credentials, chrono::Utc::now())
+        else {
+            return Err(format!("{destination} is not an object store"));
+        };
+        let mut builder = self.client.put(&put.url).body(content.to_vec());
+        for (name, value) in &put.headers {
+            builder = builder.header(name, value);
+        }
+        let response = builder.send().await.map_err(|e| e.to_string())?;
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            Err(format!("HTTP {status}"))
+        }
+    }
+}
+
+#[async_trait]
+impl ExportUploader for DestinationUploader {
+    async fn upload(
+        &self,
+        destination: &Destination,
+        path: &str,
+        content: &[u8],
+    ) -> Result<(), String> {
+        match destination {
+            Destination::Local { path: directory } => {
+                write_local(&Path::new(directory).join(path), content).await
+            }
+            Destination::S3 { .. } | Destination::Gcs { .. } => {
+                self.put_object(destination, path, content).await
+            }
+            Destination::Sftp {
+                host,
+                port,
+                user,
+                path: directory,
+            } => {
+                let remote = format!("{}/{path}", directory.trim_end_matches('/'));
+                put_sftp(&format!("{user}@{host}"), *port, &remote, content).await
+            }
+        }
+    }
+}
+
+async fn write_local(file: &Path, content: &[u8]) -> Result<(), String> {
+    if let Some(parent) = file.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    tokio::fs::write(file, content)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Uploads a file with the system `sftp` client, authenticating with the
+/// server user's SSH keys
+///
+/// The file is staged in the temporary directory, readable only by the
+/// server user.
+async fn put_sftp(target: &str, port: u16, remote: &str, content: &[u8]) -> Result<(), String> {
+    let local = std::env::temp_dir().join(format!("unet-export-{}", Uuid::new_v4()));
+    write_private(&local, content)
+        .await
+        .map_err(|e| e.to_string())?;
+    let result = run_sftp(target, port, &sftp_batch(&local, remote)).await;
+    let _ = tokio::fs::remove_file(&local).await;
+    result
+}
+
+/// Creates a file only its owner can read and write
+async fn write_private(file: &Path, content: &[u8]) -> std::io::Result<()> {
+    let mut options = tokio::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    let mut handle = options.open(file).await?;
+    handle.write_all(content).await?;
+    handle.flush().await
+}
+
+/// Batch commands creating the remote directories and uploading the file;
+/// failures of `-mkdir` for existing directories are ignored
+fn sftp_batch(local: &Path, remote: &str) -> String {
+    let mut batch = String::new();
+    let directories = remote
+        .match_indices('/')
+        .skip(1)
+        .map(|(end, _)| &remote[..end]);
+    for directory in directories {
+        let _ = writeln!(batch, "-mkdir \"{directory}\"");
+    }
+    let _ = writeln!(batch, "put \"{}\" \"{remote}\"", local.display());
+    batch
+}
+
+async fn run_sftp(target: &str, port: u16, batch: &str) -> Result<(), String> {
+    let mut child = Command::new("sftp")
+        .args([
+            "-b",
+            "-",
+            "-o",
+            "BatchMode=yes",
+            "-P",
+            &port.to_string(),
+            target,
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("failed to run sftp: {e}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(batch.as_bytes())
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    let output = child.wait_with_output().await.map_err(|e| e.to_string())?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(format!("sftp {}: {}", output.status, stderr.trim()))
+    }
+}
+
+/// Background task writing the inventory to export destinations on schedule
+pub struct ExportScheduleTask {
+    datastore: Arc<dyn DataStore + Send + Sync>,
+    uploader: DestinationUploader,
+}
+
+impl ExportScheduleTask {
+    /// Create a new export schedule task
+    pub fn new(datastore: Arc<dyn DataStore + Send + Sync>) -> Self {
+        Self {
+            datastore,
+            uploader: DestinationUploader::new(),
+        }
+    }
+
+    /// Run the export schedule task
+    pub async fn run(&self) {
+        info!("Starting export schedule background task");
+
+        let mut interval = interval(SCHEDULE_INTERVAL);
+        loop {
+            interval.tick().await;
+            match run_due(self.datastore.as_ref(), &self.uploader, chrono::Utc::now()).await {
+                Ok(run) => {
+                    if !run.exported.is_empty() {
+                        debug!(exports = ?run.exported, "Ran scheduled exports");
+                    }
+                    for (name, error) in run.failed {
+                        warn!("Failed to run scheduled export {}: {}", name, error);
+                    }
+                }
+                Err(e) => warn!("Failed to check export schedules: {}", e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_local_upload_creates_run_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let destination = Destination::Local {
+            path: temp_dir.path().display().to_string(),
+        };
+
+        DestinationUploader::new()
+            .upload(&destination, "20261016T020000Z/nodes.json", b"[]")
+            .await
+            .unwrap();
+
+        let written = std::fs::read(temp_dir.path().join("20261016T020000Z/nodes.json")).unwrap();
+        assert_eq!(written, b"[]");
+    }
+
+    #[test]
+    fn test_sftp_batch_creates_directories_before_put() {
+        let batch = sftp_batch(
+            Path::new("/tmp/unet-export"),
+            "/srv/unet/20261016T020000Z/nodes.json",
+        );
+        assert_eq!(
+            batch,
+            "-mkdir \"/srv\"\n-mkdir \"/srv/unet\"\n-mkdir \"/srv/unet/20261016T020000Z\"\n\
+             put \"/tmp/unet-export\" \"/srv/unet/20261016T020000Z/nodes.json\"\n"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_staged_sftp_file_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("unet-export");
+        write_private(&file, b"[]").await.unwrap();
+
+        let mode = std::fs::metadata(&file).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(std::fs::read(&file).unwrap(), b"[]");
+        assert!(write_private(&file, b"[]").await.is_err());
+    }
+}
@@ -0,0 +1,58 @@
+//! Scheduled check for dangling references
+
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::interval;
+use tracing::{info, warn};
+use unet_core::config::IntegrityConfig;
+use unet_core::datastore::DataStore;
+use unet_core::integrity::check;
+
+/// Background task checking for dangling references and repairing the
+/// configured kinds
+pub struct IntegrityTask {
+    datastore: Arc<dyn DataStore + Send + Sync>,
+    config: IntegrityConfig,
+}
+
+impl IntegrityTask {
+    /// Create a new integrity task
+    pub const fn new(datastore: Arc<dyn DataStore + Send + Sync>, config: IntegrityConfig) -> Self {
+        Self { datastore, config }
+    }
+
+    /// Run the integrity task
+    pub async fn run(&self) {
+        if !self.config.enabled {
+            return;
+        }
+        info!("Starting integrity background task");
+
+        let mut interval = interval(Duration::from_secs(self.config.interval));
+        loop {
+            interval.tick().await;
+            match check(
+                self.datastore.as_ref(),
+                &self.config.repair,
+                chrono::Utc::now(),
+            )
+            .await
+            {
+                Ok(report) if report.repaired > 0 => info!(
+                    target: "audit",
+                    issues = report.issues.len(),
+                    repaired = report.repaired,
+                    counts = ?report.counts,
+                    "Repaired dangling references"
+                ),
+                Ok(report) if !report.issues.is_empty() => warn!(
+                    issues = report.issues.len(),
+                    counts = ?report.counts,
+                    "Found dangling references"
+                ),
+                Ok(_) => {}
+                Err(e) => warn!("Failed to check for dangling references: {}", e),
+            }
+        }
+    }
+}
@@ -1,16 +1,31 @@
 //! Background task manager
 
 use std::sync::Arc;
+use tokio::sync::RwLock;
 use tracing::info;
-use unet_core::{config::Config, datastore::DataStore, policy_integration::PolicyService};
+use unet_core::{
+    config::Config, datastore::DataStore, enrichment::EnrichmentPipeline,
+    policy_integration::PolicyService, snmp::shards::ShardSet,
+};
+use uuid::Uuid;
 
+use super::collector_task::CollectorTask;
+use super::export_task::ExportScheduleTask;
+use super::integrity_task::IntegrityTask;
+use super::measurement_task::LinkMeasurementTask;
 use super::policy_task::PolicyEvaluationTask;
+use super::report_task::ReportScheduleTask;
+use super::retention_task::RetentionTask;
+use super::shard_task::ShardLeaseTask;
+use super::snmp_task::SnmpPollTask;
+use super::webhook_task::WebhookDeliveryTask;
 
 /// Background task manager
 pub struct BackgroundTasks {
     config: Config,
     datastore: Arc<dyn DataStore + Send + Sync>,
     policy_service: PolicyService,
+    enrichment: EnrichmentPipeline,
 }
 
 impl BackgroundTasks {
@@ -24,9 +39,17 @@ impl BackgroundTasks {
             config,
             datastore,
             policy_service,
+            enrichment: EnrichmentPipeline::default(),
         }
     }
 
+    /// Run `enrichment` on the results of the SNMP poll and collector tasks
+    #[must_use]
+    pub fn with_enrichment(mut self, enrichment: EnrichmentPipeline) -> Self {
+        self.enrichment = enrichment;
+        self
+    }
+
     /// Start all background tasks
     pub fn start(&self) {
         info!("Starting background tasks");
@@ -41,6 +64,95 @@ impl BackgroundTasks {
             policy_task.run().await;
         });
 
+        let sharding = &self.config.snmp.sharding;
+        let shards = if sharding.enabled {
+            // Poll nothing until the first round has claimed shards
+            let shards = Arc::new(RwLock::new(ShardSet::new(sharding.shards, [])));
+            let collector = sharding
+                .collector_id
+                .clone()
+                .unwrap_or_else(|| Uuid::new_v4().to_string());
+            let shard_task = ShardLeaseTask::new(
+                self.datastore.clone(),
+                sharding.clone(),
+                collector,
+                shards.clone(),
+            );
+            tokio::spawn(async move {
+                shard_task.run().await;
+            });
+            shards
+        } else {
+            Arc::new(RwLock::new(ShardSet::unsharded()))
+        };
+
+        if self.config.measurement.enabled {
+            let measurement_task = LinkMeasurementTask::new(
+                self.datastore.clone(),
+                self.config.measurement.clone(),
+                self.config.snmp.clone(),
+                shards.clone(),
+            );
+
+            tokio::spawn(async move {
+                measurement_task.run().await;
+            });
+        }
+
+        if self.config.snmp.polling.enabled {
+            let snmp_task = SnmpPollTask::new(
+                self.datastore.clone(),
+                self.config.snmp.clone(),
+                shards.clone(),
+            )
+            .with_enrichment(self.enrichment.clone());
+
+            tokio::spawn(async move {
+                snmp_task.run().await;
+            });
+        }
+
+        if self.config.collectors.enabled {
+            let collector_task = CollectorTask::new(
+                self.datastore.clone(),
+                self.config.collectors.clone(),
+                self.config.snmp.address_family,
+                shards,
+            )
+            .with_enrichment(self.enrichment.clone());
+
+            tokio::spawn(async move {
+                collector_task.run().await;
+            });
+        }
+
+        let webhook_task = WebhookDeliveryTask::new(self.datastore.clone());
+        tokio::spawn(async move {
+            webhook_task.run().await;
+        });
+
+        let report_task = ReportScheduleTask::new(self.datastore.clone());
+        tokio::spawn(async move {
+            report_task.run().await;
+        });
+
+        let export_task = ExportScheduleTask::new(self.datastore.clone());
+        tokio::spawn(async move {
+            export_task.run().await;
+        });
+
+        let retention_task =
+            RetentionTask::new(self.datastore.clone(), self.config.retention.clone());
+        tokio::spawn(async move {
+            retention_task.run().await;
+        });
+
+        let integrity_task =
+            IntegrityTask::new(self.datastore.clone(), self.config.integrity.clone());
+        tokio::spawn(async move {
+            integrity_task.run().await;
+        });
+
         info!("Background tasks started");
     }
 }
@@ -52,7 +164,6 @@ mod tests {
     use unet_core::datastore::sqlite::SqliteStore;
 
     async fn setup_test_datastore() -> SqliteStore {
-        
         test_support::sqlite::sqlite_store().await
     }
 
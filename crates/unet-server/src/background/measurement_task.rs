@@ -0,0 +1,140 @@
+//! Periodic link latency and loss measurement
+
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tokio::time::interval;
+use tracing::{debug, info, warn};
+use unet_core::{
+    config::{MeasurementConfig, SnmpConfig},
+    datastore::{DataStore, QueryOptions},
+    measurement::{SnmpProber, ThresholdBreach, get_thresholds, measure_link, measurement_history},
+    models::Link,
+    reports::capacity::sample_utilization,
+    snmp::{pauses::paused_nodes, shards::ShardSet},
+    webhooks::WebhookEvent,
+};
+
+use crate::handlers::webhooks::announce;
+
+/// Background task measuring every link on a fixed interval
+pub struct LinkMeasurementTask {
+    datastore: Arc<dyn DataStore + Send + Sync>,
+    config: MeasurementConfig,
+    snmp: SnmpConfig,
+    shards: Arc<RwLock<ShardSet>>,
+}
+
+impl LinkMeasurementTask {
+    /// Create a new link measurement task measuring the links whose source
+    /// node is in `shards`
+    pub const fn new(
+        datastore: Arc<dyn DataStore + Send + Sync>,
+        config: MeasurementConfig,
+        snmp: SnmpConfig,
+        shards: Arc<RwLock<ShardSet>>,
+    ) -> Self {
+        Self {
+            datastore,
+            config,
+            snmp,
+            shards,
+        }
+    }
+
+    /// Run the link measurement task
+    pub async fn run(&self) {
+        info!(
+            "Starting link measurement background task with interval: {}s",
+            self.config.interval
+        );
+
+        let mut interval = interval(Duration::from_secs(self.config.interval.max(1)));
+        loop {
+            interval.tick().await;
+            debug!("Running periodic link measurement");
+            self.run_cycle().await;
+        }
+    }
+
+    /// Measure every link once; links that cannot be measured are skipped
+    ///
+    /// Links with an endpoint under an active polling pause are skipped too,
+    /// so maintenance does not raise alarms, as are links whose source node is
+    /// in a shard another server holds. Alarms are raised or cleared for
+    /// subscribers when a link's breached thresholds differ from those of its
+    /// previous measurement. The utilization of the links is then sampled
+    /// from their ends' polled interface rates for capacity planning.
+    pub async fn run_cycle(&self) {
+        let links = match self.datastore.list_links(&QueryOptions::default()).await {
+            Ok(page) => page.items,
+            Err(e) => {
+                warn!("Failed to list links for measurement: {}", e);
+                return;
+            }
+        };
+        let paused = match paused_nodes(self.datastore.as_ref(), chrono::Utc::now()).await {
+            Ok(paused) => paused,
+            Err(e) => {
+                warn!("Failed to load polling pauses: {}", e);
+                return;
+            }
+        };
+
+        let shards = self.shards.read().await.clone();
+
+        let prober = SnmpProber::new(&self.snmp);
+        for link in &links {
+            if !shards.contains(&link.source_node_id) {
+                continue;
+            }
+            let mut endpoints = std::iter::once(link.source_node_id).chain(link.dest_node_id);
+            if endpoints.any(|node_id| paused.contains(&node_id)) {
+                debug!(link = %link.name, "Skipping link measurement while polling is paused");
+                continue;
+            }
+            let before = self.previous_breaches(link).await;
+            match measure_link(
+                self.datastore.as_ref(),
+                &prober,
+                link,
+                &self.config,
+                self.snmp.address_family,
+            )
+            .await
+            {
+                Ok(outcome) => {
+                    for event in WebhookEvent::alarms(link, &before, &outcome.breaches) {
+                        announce(self.datastore.as_ref(), &event).await;
+                    }
+                }
+                Err(e) => debug!(link = %link.name, error = %e, "Skipping link measurement"),
+            }
+        }
+        debug!("Measured {} links", links.len());
+
+        let owned: Vec<Link> = links
+            .into_iter()
+            .filter(|link| shards.contains(&link.source_node_id))
+            .collect();
+        match sample_utilization(self.datastore.as_ref(), &owned, chrono::Utc::now()).await {
+            Ok(sampled) => debug!("Sampled utilization of {} links", sampled),
+            Err(e) => warn!("Failed to sample link utilization: {}", e),
+        }
+    }
+
+    /// Thresholds the link's latest recorded measurement breaches
+    async fn previous_breaches(&self, link: &Link) -> Vec<ThresholdBreach> {
+        let datastore = self.datastore.as_ref();
+        let Ok(history) = measurement_history(datastore, link.id).await else {
+            return Vec::new();
+        };
+        let Some(previous) = history.last() else {
+            return Vec::new();
+        };
+        get_thresholds(datastore, link.id)
+            .await
+            .map(|thresholds| thresholds.evaluate(previous))
+            .unwrap_or_default()
+    }
+}
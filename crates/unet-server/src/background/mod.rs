@@ -2,8 +2,19 @@
 //!
 //! This module is organized into separate modules for better maintainability.
 
+pub use collector_task::NodePoller;
 pub use manager::BackgroundTasks;
+pub use policy_task::PolicyTrigger;
 
+mod collector_task;
+mod export_task;
+mod integrity_task;
 mod manager;
+mod measurement_task;
 mod policy_task;
+mod report_task;
+mod retention_task;
 mod scheduler;
+mod shard_task;
+mod snmp_task;
+mod webhook_task;
@@ -1,16 +1,19 @@
 //! Policy evaluation background task
 //!
 //! This module provides background task functionality for periodic policy evaluation
-//! on network nodes, including task execution, node processing, and result handling.
+//! on network nodes, including task execution, node processing, and result handling,
+//! and the trigger evaluating single nodes shortly after they change.
 
 use std::sync::Arc;
 use unet_core::{datastore::DataStore, policy_integration::PolicyService};
 
 pub use self::execution::TaskExecutor;
+pub use self::trigger::PolicyTrigger;
 
 mod execution;
 pub mod node_processor;
 pub mod result_handler;
+mod trigger;
 
 #[cfg(test)]
 mod tests;
@@ -1,11 +1,12 @@
 //! Node processing logic for policy evaluation
 
 use crate::background::scheduler::EvaluationStats;
+use crate::handlers::webhooks::announce;
 use std::sync::Arc;
 use tracing::{debug, error};
 use unet_core::{
     datastore::DataStore, models::Node, policy::PolicyExecutionResult,
-    policy_integration::PolicyService,
+    policy_integration::PolicyService, webhooks::WebhookEvent,
 };
 
 /// Node processor for evaluating policies on individual nodes
@@ -51,6 +52,9 @@ impl<'a> NodeProcessor<'a> {
                 stats.record_success(results.len());
                 self.store_evaluation_results(policy_service, node, &results)
                     .await;
+                if let Some(event) = WebhookEvent::policy_failed(node, &results) {
+                    announce(&**self.datastore, &event).await;
+                }
                 debug!(
                     "Evaluated {} policies for node {} ({})",
                     results.len(),
@@ -0,0 +1,201 @@
+//! Policy evaluation of single nodes shortly after they change
+//!
+//! Handlers that create or update a node call [`PolicyTrigger::notify`]. The
+//! node is evaluated once no further change to it has arrived for the
+//! debounce period, so a burst of updates is evaluated once, and compliance
+//! results are stored within seconds instead of after the next periodic
+//! evaluation.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel};
+use tokio::time::{Instant, sleep_until};
+use tracing::{debug, warn};
+use unet_core::{config::PolicyConfig, datastore::DataStore, policy_integration::PolicyService};
+use uuid::Uuid;
+
+use super::node_processor::NodeProcessor;
+
+/// Queues changed nodes for policy evaluation
+///
+/// A disabled trigger ignores changes.
+#[derive(Clone)]
+pub struct PolicyTrigger {
+    sender: Option<UnboundedSender<Uuid>>,
+}
+
+impl PolicyTrigger {
+    /// Create a trigger evaluating changed nodes on a background task, or a
+    /// disabled one when `policy.evaluate_on_change` is off
+    pub fn spawn(
+        datastore: Arc<dyn DataStore + Send + Sync>,
+        policy_service: PolicyService,
+        config: &PolicyConfig,
+    ) -> Self {
+        if !config.evaluate_on_change {
+            return Self::disabled();
+        }
+        let (sender, receiver) = unbounded_channel();
+        let debounce = Duration::from_millis(config.debounce_ms);
+        tokio::spawn(run(receiver, datastore, policy_service, debounce));
+        Self {
+            sender: Some(sender),
+        }
+    }
+
+    /// Create a trigger that ignores changes
+    pub const fn disabled() -> Self {
+        Self { sender: None }
+    }
+
+    /// Evaluate a node's policies once it has not changed for the debounce
+    /// period
+    pub fn notify(&self, node_id: Uuid) {
+        let stopped = self
+            .sender
+            .as_ref()
+            .is_some_and(|sender| sender.send(node_id).is_err());
+        if stopped {
+            warn!("Policy trigger stopped; node {node_id} waits for periodic evaluation");
+        }
+    }
+}
+
+async fn run(
+    mut receiver: UnboundedReceiver<Uuid>,
+    datastore: Arc<dyn DataStore + Send + Sync>,
+    policy_service: PolicyService,
+    debounce: Duration,
+) {
+    // Each changed node with the time it is due for evaluation
+    let mut pending: HashMap<Uuid, Instant> = HashMap::new();
+    loop {
+        let next = pending.values().min().copied();
+        tokio::select! {
+            changed = receiver.recv() => match changed {
+                Some(node_id) => {
+                    pending.insert(node_id, Instant::now() + debounce);
+                }
+                None => break,
+            },
+            () = sleep_until(next.unwrap_or_else(Instant::now)), if next.is_some() => {
+                let now = Instant::now();
+                let due: Vec<Uuid> = pending
+                    .iter()
+                    .filter(|(_, at)| **at <= now)
+                    .map(|(node_id, _)| *node_id)
+                    .collect();
+                pending.retain(|_, at| *at > now);
+                evaluate(&datastore, &policy_service, &due).await;
+            }
+        }
+    }
+}
+
+async fn evaluate(
+    datastore: &Arc<dyn DataStore + Send + Sync>,
+    policy_service: &PolicyService,
+    node_ids: &[Uuid],
+) {
+    let mut nodes = Vec::with_capacity(node_ids.len());
+    for node_id in node_ids {
+        match datastore.get_node(node_id).await {
+            Ok(Some(node)) => nodes.push(node),
+            // Deleted before its evaluation came due
+            Ok(None) => {}
+            Err(e) => warn!("Failed to load changed node {node_id} for policy evaluation: {e}"),
+        }
+    }
+    if nodes.is_empty() {
+        return;
+    }
+    let stats = NodeProcessor::new(datastore, policy_service)
+        .evaluate_nodes(&nodes)
+        .await;
+    debug!(
+        "Evaluated policies for {} changed node(s): {} succeeded, {} failed",
+        nodes.len(),
+        stats.successful_evaluations(),
+        stats.failed_evaluations()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+    use unet_core::models::{DeviceRole, Node, Vendor};
+
+    const POLICY: &str = "WHEN node.vendor == \"cisco\" THEN ASSERT node.version IS \"15.1\"\n";
+
+    async fn setup() -> (
+        Arc<dyn DataStore + Send + Sync>,
+        PolicyService,
+        Uuid,
+        TempDir,
+    ) {
+        let datastore: Arc<dyn DataStore + Send + Sync> =
+            Arc::new(test_support::sqlite::sqlite_store().await);
+        let node = Node::new(
+            "edge1".to_string(),
+            "example.com".to_string(),
+            Vendor::Cisco,
+            DeviceRole::Router,
+        );
+        let node = datastore.create_node(&node).await.unwrap();
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("version.policy"), POLICY).unwrap();
+        let policy_service = PolicyService::with_local_dir(temp_dir.path().to_str().unwrap());
+        (datastore, policy_service, node.id, temp_dir)
+    }
+
+    async fn evaluated(datastore: &Arc<dyn DataStore + Send + Sync>, node_id: Uuid) -> bool {
+        !datastore
+            .get_policy_results(&node_id)
+            .await
+            .unwrap()
+            .is_empty()
+    }
+
+    #[tokio::test]
+    async fn test_changed_node_is_evaluated_after_debounce() {
+        let (datastore, policy_service, node_id, _temp_dir) = setup().await;
+        let config = PolicyConfig {
+            evaluate_on_change: true,
+            debounce_ms: 300,
+        };
+        let trigger = PolicyTrigger::spawn(datastore.clone(), policy_service, &config);
+
+        trigger.notify(node_id);
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        trigger.notify(node_id);
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        // The second change restarted the wait
+        assert!(!evaluated(&datastore, node_id).await);
+
+        for _ in 0..50 {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            if evaluated(&datastore, node_id).await {
+                return;
+            }
+        }
+        panic!("node was not evaluated after the debounce period");
+    }
+
+    #[tokio::test]
+    async fn test_disabled_trigger_ignores_changes() {
+        let (datastore, policy_service, node_id, _temp_dir) = setup().await;
+        let config = PolicyConfig {
+            evaluate_on_change: false,
+            debounce_ms: 0,
+        };
+        let trigger = PolicyTrigger::spawn(datastore.clone(), policy_service, &config);
+
+        trigger.notify(node_id);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert!(!evaluated(&datastore, node_id).await);
+    }
+}
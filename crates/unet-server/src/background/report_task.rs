@@ -0,0 +1,44 @@
+//! Scheduled rendering of custom reports
+
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::interval;
+use tracing::{debug, info, warn};
+use unet_core::datastore::DataStore;
+use unet_core::reports::custom::render_due;
+
+/// How often report schedules are checked
+const SCHEDULE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Background task rendering custom reports whose interval has passed
+pub struct ReportScheduleTask {
+    datastore: Arc<dyn DataStore + Send + Sync>,
+}
+
+impl ReportScheduleTask {
+    /// Create a new report schedule task
+    pub const fn new(datastore: Arc<dyn DataStore + Send + Sync>) -> Self {
+        Self { datastore }
+    }
+
+    /// Run the report schedule task
+    pub async fn run(&self) {
+        info!("Starting report schedule background task");
+
+        let mut interval = interval(SCHEDULE_INTERVAL);
+        loop {
+            interval.tick().await;
+            match render_due(self.datastore.as_ref(), chrono::Utc::now()).await {
+                Ok(run) => {
+                    if !run.rendered.is_empty() {
+                        debug!(reports = ?run.rendered, "Rendered scheduled reports");
+                    }
+                    for (name, error) in run.failed {
+                        warn!("Failed to render scheduled report {}: {}", name, error);
+                    }
+                }
+                Err(e) => warn!("Failed to check report schedules: {}", e),
+            }
+        }
+    }
+}
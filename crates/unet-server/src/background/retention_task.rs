@@ -0,0 +1,59 @@
+//! Scheduled pruning of data past its retention
+
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::interval;
+use tracing::{info, warn};
+use unet_core::config::RetentionConfig;
+use unet_core::datastore::DataStore;
+use unet_core::retention::{enforce, record_policy};
+
+/// Background task pruning every data class past its retention
+///
+/// The policy is recorded when the task starts, whether or not pruning is
+/// enabled, so admin dry runs preview what this server enforces.
+pub struct RetentionTask {
+    datastore: Arc<dyn DataStore + Send + Sync>,
+    config: RetentionConfig,
+}
+
+impl RetentionTask {
+    /// Create a new retention task
+    pub const fn new(datastore: Arc<dyn DataStore + Send + Sync>, config: RetentionConfig) -> Self {
+        Self { datastore, config }
+    }
+
+    /// Run the retention task
+    pub async fn run(&self) {
+        if let Err(e) = record_policy(self.datastore.as_ref(), &self.config).await {
+            warn!("Failed to record retention policy: {}", e);
+        }
+        if !self.config.enabled {
+            return;
+        }
+        info!("Starting retention background task");
+
+        let mut interval = interval(Duration::from_secs(self.config.interval));
+        loop {
+            interval.tick().await;
+            match enforce(
+                self.datastore.as_ref(),
+                &self.config,
+                chrono::Utc::now(),
+                false,
+            )
+            .await
+            {
+                Ok(report) if report.total_rows > 0 => info!(
+                    target: "audit",
+                    rows = report.total_rows,
+                    bytes = report.total_bytes,
+                    classes = ?report.classes,
+                    "Pruned data past its retention"
+                ),
+                Ok(_) => {}
+                Err(e) => warn!("Failed to prune data past its retention: {}", e),
+            }
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! Polling shard leases for servers sharing a database
+
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tokio::time::interval;
+use tracing::{info, warn};
+use unet_core::config::ShardingConfig;
+use unet_core::datastore::DataStore;
+use unet_core::snmp::shards::{ShardSet, claim_shards};
+
+/// Background task claiming and renewing this server's polling shards
+///
+/// Rounds run three times per lease lifetime, so a lease survives a missed
+/// round. The shards held after each round are published for the tasks that
+/// poll devices.
+pub struct ShardLeaseTask {
+    datastore: Arc<dyn DataStore + Send + Sync>,
+    config: ShardingConfig,
+    collector: String,
+    shards: Arc<RwLock<ShardSet>>,
+}
+
+impl ShardLeaseTask {
+    /// Create a new shard lease task for `collector`
+    pub const fn new(
+        datastore: Arc<dyn DataStore + Send + Sync>,
+        config: ShardingConfig,
+        collector: String,
+        shards: Arc<RwLock<ShardSet>>,
+    ) -> Self {
+        Self {
+            datastore,
+            config,
+            collector,
+            shards,
+        }
+    }
+
+    /// Run the shard lease task
+    pub async fn run(&self) {
+        info!(
+            collector = %self.collector,
+            shards = self.config.shards,
+            "Starting polling shard lease background task"
+        );
+
+        let mut interval = interval(Duration::from_secs((self.config.lease_ttl / 3).max(1)));
+        loop {
+            interval.tick().await;
+            self.run_round().await;
+        }
+    }
+
+    /// Claim and renew shards once, keeping the previous shards on failure
+    pub async fn run_round(&self) {
+        let owned = match claim_shards(
+            self.datastore.as_ref(),
+            &self.collector,
+            &self.config,
+            chrono::Utc::now(),
+        )
+        .await
+        {
+            Ok(owned) => owned,
+            Err(e) => {
+                warn!("Failed to renew polling shard leases: {}", e);
+                return;
+            }
+        };
+        let mut current = self.shards.write().await;
+        if *current != owned {
+            info!(collector = %self.collector, shards = ?owned.owned(), "Polling shards changed");
+            *current = owned;
+        }
+    }
+}
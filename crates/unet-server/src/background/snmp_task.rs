@@ -0,0 +1,251 @@
+//! Background SNMP polling of the inventory through OID profiles
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tokio::time::interval;
+use tracing::{debug, info, warn};
+use unet_core::{
+    config::{SnmpConfig, defaults::network::SNMP_DEFAULT_PORT},
+    datastore::{DataStore, DataStoreError, QueryOptions},
+    enrichment::{EnrichmentPipeline, enrich_and_store},
+    models::derived::NodeStatus,
+    snmp::{
+        PollingConfig, PollingHandle, PollingResult, PollingScheduler, SessionConfig,
+        pauses::paused_nodes,
+        profiles::{load_catalog, node_polling_tasks},
+        shards::ShardSet,
+    },
+};
+use uuid::Uuid;
+
+/// Background task polling every node over SNMP with the tasks of its OID
+/// profile
+pub struct SnmpPollTask {
+    datastore: Arc<dyn DataStore + Send + Sync>,
+    config: SnmpConfig,
+    shards: Arc<RwLock<ShardSet>>,
+    enrichment: EnrichmentPipeline,
+}
+
+impl SnmpPollTask {
+    /// Create a new SNMP poll task polling the nodes in `shards`
+    pub fn new(
+        datastore: Arc<dyn DataStore + Send + Sync>,
+        config: SnmpConfig,
+        shards: Arc<RwLock<ShardSet>>,
+    ) -> Self {
+        Self {
+            datastore,
+            config,
+            shards,
+            enrichment: EnrichmentPipeline::default(),
+        }
+    }
+
+    /// Run `enrichment` on each poll result and store its output
+    #[must_use]
+    pub fn with_enrichment(mut self, enrichment: EnrichmentPipeline) -> Self {
+        self.enrichment = enrichment;
+        self
+    }
+
+    /// Run the SNMP poll task
+    ///
+    /// The scheduler polls each task on its own interval. Every
+    /// `snmp.polling.sync_interval` the tasks are rebuilt from the nodes' OID
+    /// profiles, and the paused nodes and owned shards are handed to the
+    /// scheduler.
+    pub async fn run(&self) {
+        info!(
+            "Starting SNMP poll background task with sync interval: {}s",
+            self.config.polling.sync_interval
+        );
+
+        let (mut scheduler, mut handle) =
+            PollingScheduler::new(PollingConfig::default(), self.config.client_config());
+        tokio::spawn(async move {
+            scheduler.run().await;
+        });
+
+        let mut polled = HashSet::new();
+        let mut statuses = HashMap::new();
+        let mut sync = interval(Duration::from_secs(
+            self.config.polling.sync_interval.max(1),
+        ));
+        loop {
+            tokio::select! {
+                _ = sync.tick() => {
+                    if let Err(e) = self.sync_tasks(&handle, &mut polled).await {
+                        warn!("SNMP polling scheduler stopped: {}", e);
+                        return;
+                    }
+                    statuses.retain(|node_id, _| polled.contains(node_id));
+                }
+                Some(result) = handle.result_rx.recv() => {
+                    self.record(result, &mut statuses).await;
+                }
+            }
+        }
+    }
+
+    /// Rebuild the tasks of every node with a management address from its
+    /// OID profile, and stop polling nodes that are gone
+    ///
+    /// Nodes whose tasks cannot be built keep their previous tasks.
+    ///
+    /// # Errors
+    /// Returns an error if the scheduler no longer accepts messages.
+    pub async fn sync_tasks(
+        &self,
+        handle: &PollingHandle,
+        polled: &mut HashSet<Uuid>,
+    ) -> Result<(), String> {
+        let datastore = self.datastore.as_ref();
+        let nodes = match datastore.list_nodes(&QueryOptions::default()).await {
+            Ok(page) => page.items,
+            Err(e) => {
+                warn!("Failed to list nodes for SNMP polling: {}", e);
+                return Ok(());
+            }
+        };
+        match paused_nodes(datastore, chrono::Utc::now()).await {
+            Ok(paused) => handle.set_paused(paused)?,
+            Err(e) => warn!("Failed to load polling pauses: {}", e),
+        }
+        handle.set_shards(self.shards.read().await.clone())?;
+        let catalog = match load_catalog(datastore).await {
+            Ok(catalog) => catalog,
+            Err(e) => {
+                warn!("Failed to load OID profiles: {}", e);
+                return Ok(());
+            }
+        };
+
+        let session_config = self.config.session_config();
+        let mut current = HashSet::new();
+        for node in &nodes {
+            let target =
+                match node.management_socket_addr(SNMP_DEFAULT_PORT, self.config.address_family) {
+                    Ok(Some(target)) => target,
+                    Ok(None) => continue,
+                    Err(e) => {
+                        debug!(node = %node.name, error = %e, "Skipping SNMP polling");
+                        continue;
+                    }
+                };
+            let session_config = SessionConfig {
+                address: target,
+                ..session_config.clone()
+            };
+            match node_polling_tasks(datastore, &catalog, node, target, &session_config).await {
+                Ok(tasks) => {
+                    handle.sync_node(node.id, tasks)?;
+                    current.insert(node.id);
+                }
+                Err(e) => {
+                    warn!(node = %node.name, error = %e, "Failed to build polling tasks");
+                    if polled.contains(&node.id) {
+                        current.insert(node.id);
+                    }
+                }
+            }
+        }
+        for node_id in polled.difference(&current) {
+            handle.sync_node(*node_id, Vec::new())?;
+        }
+        debug!("Polling {} nodes over SNMP", current.len());
+        *polled = current;
+        Ok(())
+    }
+
+    /// Merge a poll result into the node's status, enrich it, and record its
+    /// performance metrics
+    async fn record(&self, result: PollingResult, statuses: &mut HashMap<Uuid, NodeStatus>) {
+        let node_id = result.node_id;
+        let default_instance = result.success && result.context.is_none();
+        let status = statuses
+            .entry(node_id)
+            .or_insert_with(|| NodeStatus::new(node_id));
+        result.apply_to(status);
+        if let Err(e) = enrich_and_store(self.datastore.as_ref(), &self.enrichment, status).await {
+            warn!(node_id = %node_id, error = %e, "Failed to store enrichment output");
+        }
+        let Some(metrics) = status.performance.as_ref().filter(|_| default_instance) else {
+            return;
+        };
+        match self
+            .datastore
+            .record_performance_metrics(&node_id, chrono::Utc::now(), metrics)
+            .await
+        {
+            Ok(()) | Err(DataStoreError::UnsupportedOperation { .. }) => {}
+            Err(e) => warn!(node_id = %node_id, error = %e, "Failed to record metrics"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unet_core::config::Config;
+    use unet_core::models::{DeviceRole, Node, Vendor};
+
+    #[tokio::test]
+    async fn test_sync_tasks_builds_tasks_from_profiles() {
+        let datastore: Arc<dyn DataStore + Send + Sync> =
+            Arc::new(test_support::sqlite::sqlite_store().await);
+        let mut node = Node::new(
+            "core-01".to_string(),
+            "example.com".to_string(),
+            Vendor::Cisco,
+            DeviceRole::Router,
+        );
+        node.management_ip = Some("192.0.2.1".parse().unwrap());
+        let node = datastore.create_node(&node).await.unwrap();
+        let unaddressed = Node::new(
+            "core-02".to_string(),
+            "example.com".to_string(),
+            Vendor::Cisco,
+            DeviceRole::Router,
+        );
+        datastore.create_node(&unaddressed).await.unwrap();
+
+        // Own no shards so the scheduler keeps the tasks without polling
+        let task = SnmpPollTask::new(
+            datastore.clone(),
+            Config::default().snmp,
+            Arc::new(RwLock::new(ShardSet::new(4, []))),
+        );
+        let (mut scheduler, handle) =
+            PollingScheduler::new(PollingConfig::default(), task.config.client_config());
+        tokio::spawn(async move {
+            scheduler.run().await;
+        });
+
+        let mut polled = HashSet::new();
+        task.sync_tasks(&handle, &mut polled).await.unwrap();
+
+        assert_eq!(polled, HashSet::from([node.id]));
+        let catalog = load_catalog(datastore.as_ref()).await.unwrap();
+        let expected = catalog.resolve_for_node(&node).unwrap().groups;
+        let tasks = handle.list_tasks().await.unwrap();
+        assert!(!tasks.is_empty());
+        assert!(
+            tasks
+                .iter()
+                .all(|task| task.node_id == node.id && task.target.to_string() == "192.0.2.1:161")
+        );
+        for group in expected {
+            assert!(group.oids.iter().all(|oid| tasks.iter().any(|task| {
+                task.interval.as_secs() == group.interval_seconds && task.oids.contains(oid)
+            })));
+        }
+
+        datastore.delete_node(&node.id).await.unwrap();
+        task.sync_tasks(&handle, &mut polled).await.unwrap();
+        assert!(polled.is_empty());
+        assert!(handle.list_tasks().await.unwrap().is_empty());
+    }
+}
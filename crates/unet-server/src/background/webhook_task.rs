@@ -0,0 +1,85 @@
+//! Delivery of queued webhook events
+
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::interval;
+use tracing::{debug, info, warn};
+use unet_core::datastore::DataStore;
+use unet_core::webhooks::{DeliveryStats, WebhookRequest, WebhookSender, deliver_pending};
+
+/// How often the outbox is checked for due deliveries
+const DELIVERY_INTERVAL: Duration = Duration::from_secs(5);
+/// Timeout for a single webhook request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Sends webhook requests over HTTP
+pub struct HttpSender {
+    client: reqwest::Client,
+}
+
+impl HttpSender {
+    /// Create a sender with the webhook request timeout
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+}
+
+#[async_trait]
+impl WebhookSender for HttpSender {
+    async fn send(&self, request: &WebhookRequest) -> Result<(), String> {
+        let mut builder = self
+            .client
+            .post(&request.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(request.body.clone());
+        for (name, value) in &request.headers {
+            builder = builder.header(*name, value);
+        }
+        let response = builder.send().await.map_err(|e| e.to_string())?;
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            Err(format!("HTTP {status}"))
+        }
+    }
+}
+
+/// Background task sending queued webhook deliveries
+pub struct WebhookDeliveryTask {
+    datastore: Arc<dyn DataStore + Send + Sync>,
+    sender: HttpSender,
+}
+
+impl WebhookDeliveryTask {
+    /// Create a new webhook delivery task
+    pub fn new(datastore: Arc<dyn DataStore + Send + Sync>) -> Self {
+        Self {
+            datastore,
+            sender: HttpSender::new(),
+        }
+    }
+
+    /// Run the webhook delivery task
+    pub async fn run(&self) {
+        info!("Starting webhook delivery background task");
+
+        let mut interval = interval(DELIVERY_INTERVAL);
+        loop {
+            interval.tick().await;
+            match deliver_pending(self.datastore.as_ref(), &self.sender, chrono::Utc::now()).await {
+                Ok(stats) if stats != DeliveryStats::default() => {
+                    debug!(?stats, "Processed webhook deliveries");
+                }
+                Ok(_) => {}
+                Err(e) => warn!("Failed to process webhook deliveries: {}", e),
+            }
+        }
+    }
+}
@@ -8,6 +8,7 @@ use axum::{
 };
 use thiserror::Error;
 use unet_core::prelude::*;
+use unet_core::snmp::profiles::OidProfileError;
 
 /// Server error type for HTTP handlers
 #[derive(Error, Debug)]
@@ -51,6 +52,9 @@ impl IntoResponse for ServerError {
             Self::DataStore(DataStoreError::Timeout { .. }) => {
                 (StatusCode::REQUEST_TIMEOUT, "TIMEOUT")
             }
+            Self::DataStore(DataStoreError::Busy { .. }) => {
+                (StatusCode::SERVICE_UNAVAILABLE, "DATABASE_BUSY")
+            }
             Self::DataStore(DataStoreError::UnsupportedOperation { .. }) => {
                 (StatusCode::NOT_IMPLEMENTED, "UNSUPPORTED_OPERATION")
             }
@@ -64,6 +68,16 @@ impl IntoResponse for ServerError {
     }
 }
 
+impl From<OidProfileError> for ServerError {
+    fn from(error: OidProfileError) -> Self {
+        match error {
+            OidProfileError::DataStore(e) => Self::DataStore(e),
+            OidProfileError::UnknownProfile(_) => Self::NotFound(error.to_string()),
+            _ => Self::BadRequest(error.to_string()),
+        }
+    }
+}
+
 /// Server result type
 pub type ServerResult<T> = std::result::Result<T, ServerError>;
 
@@ -0,0 +1,458 @@
+//! Admin handlers: datastore statistics, maintenance, and server logs
+//!
+//! Routes here are mounted behind the admin role. Every maintenance operation
+//! is written to the `audit` log target, whether it succeeds or fails.
+
+use axum::{
+    extract::{Query, State},
+    response::Json,
+};
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Instant;
+use tracing::{info, warn};
+use unet_core::datastore::DataStoreError;
+use unet_core::integrity::{IntegrityReport, IssueKind, check, last_report};
+use unet_core::logging::{LogFilter, LogRecord, log_buffer};
+use unet_core::retention::{RetentionReport, RetentionStatus, enforce, retention_status};
+
+use crate::api::ApiResponse;
+use crate::handlers::{ServerError, ServerResult};
+use crate::server::AppState;
+
+/// Days of derived state kept when `older_than_days` is not given
+const DEFAULT_DERIVED_STATE_RETENTION_DAYS: u32 = 30;
+
+/// Datastore statistics
+#[derive(Debug, Serialize)]
+pub struct AdminStats {
+    /// Row count per table
+    pub counts: HashMap<String, usize>,
+    /// Implementation-specific statistics
+    pub statistics: HashMap<String, serde_json::Value>,
+}
+
+/// Outcome of a maintenance operation
+#[derive(Debug, Serialize)]
+pub struct MaintenanceReport {
+    /// Operation that ran
+    pub operation: String,
+    /// Rows or entries removed, by table or cache
+    pub affected: HashMap<String, usize>,
+    /// Wall-clock duration in milliseconds
+    pub duration_ms: u64,
+}
+
+/// Query parameters for derived-state pruning
+#[derive(Debug, Deserialize)]
+pub struct PruneDerivedStateQuery {
+    /// Delete status not updated in this many days (default 30)
+    pub older_than_days: Option<u32>,
+}
+
+/// Query parameters for retention pruning
+#[derive(Debug, Deserialize)]
+pub struct RetentionQuery {
+    /// Report what would be pruned without deleting anything
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Query parameters for an integrity check
+#[derive(Debug, Deserialize)]
+pub struct IntegrityQuery {
+    /// Comma-separated issue kinds to repair, or `all`; nothing is repaired
+    /// when not given
+    pub repair: Option<String>,
+}
+
+/// Get entity counts and datastore statistics
+///
+/// # Errors
+/// Returns an error if the datastore cannot collect statistics.
+pub async fn get_admin_stats(
+    State(app_state): State<AppState>,
+) -> ServerResult<Json<ApiResponse<AdminStats>>> {
+    let stats = AdminStats {
+        counts: app_state.datastore.get_entity_counts().await?,
+        statistics: app_state.datastore.get_statistics().await?,
+    };
+    Ok(Json(ApiResponse::success(stats)))
+}
+
+/// Rebuild the database file to reclaim free space
+///
+/// # Errors
+/// Returns an error if the datastore does not support or fails the operation.
+pub async fn vacuum(
+    State(app_state): State<AppState>,
+) -> ServerResult<Json<ApiResponse<MaintenanceReport>>> {
+    let started = Instant::now();
+    let result = app_state.datastore.vacuum().await.map(|()| HashMap::new());
+    audited("vacuum", started, result)
+}
+
+/// Refresh query planner statistics
+///
+/// # Errors
+/// Returns an error if the datastore does not support or fails the operation.
+pub async fn analyze(
+    State(app_state): State<AppState>,
+) -> ServerResult<Json<ApiResponse<MaintenanceReport>>> {
+    let started = Instant::now();
+    let result = app_state.datastore.analyze().await.map(|()| HashMap::new());
+    audited("analyze", started, result)
+}
+
+/// Delete derived-state and polling rows of nodes that no longer exist
+///
+/// # Errors
+/// Returns an error if the datastore does not support or fails the operation.
+pub async fn cleanup_orphans(
+    State(app_state): State<AppState>,
+) -> ServerResult<Json<ApiResponse<MaintenanceReport>>> {
+    let started = Instant::now();
+    let result = app_state.datastore.cleanup_orphaned_records().await;
+    audited("cleanup_orphans", started, result)
+}
+
+/// Delete node status that has not been updated recently
+///
+/// # Errors
+/// Returns an error if `older_than_days` is zero or the datastore fails the operation.
+pub async fn prune_derived_state(
+    State(app_state): State<AppState>,
+    Query(query): Query<PruneDerivedStateQuery>,
+) -> ServerResult<Json<ApiResponse<MaintenanceReport>>> {
+    let days = query
+        .older_than_days
+        .unwrap_or(DEFAULT_DERIVED_STATE_RETENTION_DAYS);
+    if days == 0 {
+        return Err(ServerError::BadRequest(
+            "older_than_days must be at least 1".to_string(),
+        ));
+    }
+    let cutoff = Utc::now() - Duration::days(i64::from(days));
+
+    let started = Instant::now();
+    let result = app_state.datastore.prune_derived_state(cutoff).await;
+    audited("prune_derived_state", started, result)
+}
+
+/// Get the enforced retention policy and the space pruning has reclaimed
+///
+/// # Errors
+/// Returns an error if the datastore cannot be read.
+pub async fn get_retention_status(
+    State(app_state): State<AppState>,
+) -> ServerResult<Json<ApiResponse<RetentionStatus>>> {
+    let status = retention_status(app_state.datastore.as_ref()).await?;
+    Ok(Json(ApiResponse::success(status)))
+}
+
+/// Prune every data class past its retention, or report what would be pruned
+///
+/// Uses the policy the server recorded at startup, or the default policy if
+/// none has been recorded.
+///
+/// # Errors
+/// Returns an error if the datastore fails to measure or prune a class.
+pub async fn enforce_retention(
+    State(app_state): State<AppState>,
+    Query(query): Query<RetentionQuery>,
+) -> ServerResult<Json<ApiResponse<RetentionReport>>> {
+    let started = Instant::now();
+    let datastore = app_state.datastore.as_ref();
+    let policy = retention_status(datastore)
+        .await?
+        .policy
+        .unwrap_or_default();
+    let result = enforce(datastore, &policy, Utc::now(), query.dry_run).await;
+    let duration_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+
+    match result {
+        Ok(report) => {
+            info!(
+                target: "audit",
+                operation = "retention",
+                dry_run = report.dry_run,
+                rows = report.total_rows,
+                bytes = report.total_bytes,
+                duration_ms,
+                "Admin maintenance completed"
+            );
+            Ok(Json(ApiResponse::success(report)))
+        }
+        Err(e) => {
+            warn!(
+                target: "audit",
+                operation = "retention",
+                error = %e,
+                duration_ms,
+                "Admin maintenance failed"
+            );
+            Err(e.into())
+        }
+    }
+}
+
+/// Get the report of the last integrity check
+///
+/// # Errors
+/// Returns an error if no check has run or the datastore cannot be read.
+pub async fn get_integrity_report(
+    State(app_state): State<AppState>,
+) -> ServerResult<Json<ApiResponse<IntegrityReport>>> {
+    let report = last_report(app_state.datastore.as_ref())
+        .await?
+        .ok_or_else(|| ServerError::NotFound("No integrity check has run".to_string()))?;
+    Ok(Json(ApiResponse::success(report)))
+}
+
+/// Check for dangling references, repairing the requested kinds
+///
+/// # Errors
+/// Returns an error if `repair` names an unknown kind, or the datastore fails
+/// to read or repair.
+pub async fn check_integrity(
+    State(app_state): State<AppState>,
+    Query(query): Query<IntegrityQuery>,
+) -> ServerResult<Json<ApiResponse<IntegrityReport>>> {
+    let repair = match query.repair.as_deref().map(str::trim) {
+        None | Some("") => Vec::new(),
+        Some("all") => IssueKind::ALL.to_vec(),
+        Some(kinds) => kinds
+            .split(',')
+            .map(|kind| kind.trim().parse().map_err(ServerError::BadRequest))
+            .collect::<ServerResult<Vec<IssueKind>>>()?,
+    };
+
+    let started = Instant::now();
+    let result = check(app_state.datastore.as_ref(), &repair, Utc::now()).await;
+    let duration_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+
+    match result {
+        Ok(report) => {
+            info!(
+                target: "audit",
+                operation = "integrity",
+                issues = report.issues.len(),
+                repaired = report.repaired,
+                duration_ms,
+                "Admin maintenance completed"
+            );
+            Ok(Json(ApiResponse::success(report)))
+        }
+        Err(e) => {
+            warn!(
+                target: "audit",
+                operation = "integrity",
+                error = %e,
+                duration_ms,
+                "Admin maintenance failed"
+            );
+            Err(e.into())
+        }
+    }
+}
+
+/// Clear the policy evaluation cache
+///
+/// # Errors
+/// Never returns an error; the result type matches the other maintenance handlers.
+pub async fn clear_caches(
+    State(app_state): State<AppState>,
+) -> ServerResult<Json<ApiResponse<MaintenanceReport>>> {
+    let started = Instant::now();
+    let mut orchestrator = app_state.policy_service.orchestrator().lock().await;
+    let cleared = orchestrator.cache_size();
+    orchestrator.clear_cache();
+    drop(orchestrator);
+    audited(
+        "clear_caches",
+        started,
+        Ok(HashMap::from([("policy_evaluations".to_string(), cleared)])),
+    )
+}
+
+/// Get the server's recent log records, oldest first
+///
+/// Records come from the in-memory log buffer, so only events at or above
+/// the server's configured log level since it started are available. Pass
+/// the `seq` of the last record seen as `after` to poll for new ones.
+///
+/// # Errors
+/// Never returns an error; the result type matches the other admin handlers.
+pub async fn get_server_logs(
+    Query(filter): Query<LogFilter>,
+) -> ServerResult<Json<ApiResponse<Vec<LogRecord>>>> {
+    Ok(Json(ApiResponse::success(log_buffer().query(&filter))))
+}
+
+/// Records a finished maintenance operation in the audit log
+fn audited(
+    operation: &str,
+    started: Instant,
+    result: Result<HashMap<String, usize>, DataStoreError>,
+) -> ServerResult<Json<ApiResponse<MaintenanceReport>>> {
+    let duration_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+
+    match result {
+        Ok(affected) => {
+            info!(
+                target: "audit",
+                operation,
+                ?affected,
+                duration_ms,
+                "Admin maintenance completed"
+            );
+            Ok(Json(ApiResponse::success(MaintenanceReport {
+                operation: operation.to_string(),
+                affected,
+                duration_ms,
+            })))
+        }
+        Err(e) => {
+            warn!(
+                target: "audit",
+                operation,
+                error = %e,
+                duration_ms,
+                "Admin maintenance failed"
+            );
+            Err(e.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::app_state::tests::create_mock_app_state;
+    use tracing_subscriber::layer::SubscriberExt;
+    use unet_core::logging::LogLevel;
+
+    #[tokio::test]
+    async fn test_get_admin_stats_reports_counts() {
+        let app_state = create_mock_app_state().await;
+
+        let Json(response) = get_admin_stats(State(app_state)).await.unwrap();
+
+        assert_eq!(response.data.counts.get("nodes"), Some(&0));
+        assert_eq!(
+            response.data.statistics.get("datastore"),
+            Some(&serde_json::Value::from("sqlite"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_maintenance_operations_report_affected_rows() {
+        let app_state = create_mock_app_state().await;
+
+        let Json(response) = cleanup_orphans(State(app_state.clone())).await.unwrap();
+        assert_eq!(response.data.operation, "cleanup_orphans");
+        assert_eq!(response.data.affected.get("node_status"), Some(&0));
+
+        let Json(response) = clear_caches(State(app_state.clone())).await.unwrap();
+        assert_eq!(response.data.affected.get("policy_evaluations"), Some(&0));
+
+        assert!(vacuum(State(app_state.clone())).await.is_ok());
+        assert!(analyze(State(app_state)).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_retention_dry_run_does_not_count_as_a_run() {
+        let app_state = create_mock_app_state().await;
+        let runs = get_retention_status(State(app_state.clone()))
+            .await
+            .unwrap()
+            .0
+            .data
+            .runs;
+
+        let Json(response) = enforce_retention(
+            State(app_state.clone()),
+            Query(RetentionQuery { dry_run: true }),
+        )
+        .await
+        .unwrap();
+
+        assert!(response.data.dry_run);
+        assert_eq!(response.data.classes.len(), 4);
+        let Json(status) = get_retention_status(State(app_state)).await.unwrap();
+        assert_eq!(status.data.runs, runs);
+    }
+
+    #[tokio::test]
+    async fn test_integrity_check_is_kept_as_last_report() {
+        let app_state = create_mock_app_state().await;
+        assert!(matches!(
+            get_integrity_report(State(app_state.clone())).await,
+            Err(ServerError::NotFound(_))
+        ));
+
+        let unknown = check_integrity(
+            State(app_state.clone()),
+            Query(IntegrityQuery {
+                repair: Some("orphaned_link,everything".to_string()),
+            }),
+        )
+        .await;
+        assert!(matches!(unknown, Err(ServerError::BadRequest(_))));
+
+        let Json(response) = check_integrity(
+            State(app_state.clone()),
+            Query(IntegrityQuery {
+                repair: Some("all".to_string()),
+            }),
+        )
+        .await
+        .unwrap();
+        assert!(response.data.issues.is_empty());
+        assert_eq!(response.data.repair, IssueKind::ALL);
+
+        let Json(last) = get_integrity_report(State(app_state)).await.unwrap();
+        assert_eq!(last.data, response.data);
+    }
+
+    #[tokio::test]
+    async fn test_get_server_logs_returns_new_matching_records() {
+        let node = uuid::Uuid::new_v4().to_string();
+        let subscriber = tracing_subscriber::registry().with(log_buffer().layer());
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(node_id = %node, "Polled");
+            warn!(node_id = %node, "Poll timed out");
+        });
+        let filter = LogFilter {
+            level: Some(LogLevel::Warn),
+            node: Some(node.clone()),
+            ..LogFilter::default()
+        };
+
+        let Json(response) = get_server_logs(Query(filter.clone())).await.unwrap();
+
+        let [record] = response.data.as_slice() else {
+            panic!("expected only the warning, got {:?}", response.data);
+        };
+        assert_eq!(record.message, "Poll timed out");
+        let after = LogFilter {
+            after: Some(record.seq),
+            ..filter
+        };
+        let Json(response) = get_server_logs(Query(after)).await.unwrap();
+        assert!(response.data.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_prune_derived_state_rejects_zero_days() {
+        let app_state = create_mock_app_state().await;
+        let query = PruneDerivedStateQuery {
+            older_than_days: Some(0),
+        };
+
+        let result = prune_derived_state(State(app_state), Query(query)).await;
+
+        assert!(matches!(result, Err(ServerError::BadRequest(_))));
+    }
+}
@@ -0,0 +1,205 @@
+//! Configuration snapshot and change window handlers
+
+use axum::{
+    extract::{Path, State},
+    response::Json,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::api::ApiResponse;
+use crate::handlers::{ServerError, ServerResult};
+use crate::server::AppState;
+use unet_core::config_changes::{
+    ChangeScope, ChangeWindow, ConfigSnapshot, SnapshotComparison, delete_window, latest_snapshot,
+    list_windows, record_snapshot, save_window,
+};
+
+/// A configuration collected from a node
+#[derive(Debug, Deserialize)]
+pub struct SubmitSnapshotRequest {
+    /// Configuration text
+    pub config: String,
+    /// When the configuration was collected; defaults to now
+    #[serde(default)]
+    pub collected_at: Option<DateTime<Utc>>,
+}
+
+/// Request to approve changes for a node, a location, or everything
+#[derive(Debug, Deserialize)]
+pub struct CreateWindowRequest {
+    /// Location whose nodes, including those in sub-locations, may change
+    #[serde(default)]
+    pub location_id: Option<Uuid>,
+    /// Node that may change
+    #[serde(default)]
+    pub node_id: Option<Uuid>,
+    /// When changes start being approved
+    pub starts_at: DateTime<Utc>,
+    /// When changes stop being approved
+    pub ends_at: DateTime<Utc>,
+    /// Change request that approved the window
+    #[serde(default)]
+    pub change_request: Option<String>,
+    /// What the change is
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Store a node's newly collected configuration and compare it with the
+/// previous one, announcing changes made outside every change window
+///
+/// # Errors
+/// Returns an error if the node does not exist or the snapshot, change
+/// windows, or event cannot be read or written.
+pub async fn submit_config_snapshot(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<SubmitSnapshotRequest>,
+) -> ServerResult<Json<ApiResponse<SnapshotComparison>>> {
+    let datastore = app_state.datastore.as_ref();
+    let node = datastore.get_node_required(&id).await?;
+    let comparison = record_snapshot(
+        datastore,
+        &node,
+        request.config,
+        request.collected_at.unwrap_or_else(Utc::now),
+    )
+    .await?;
+    Ok(Json(ApiResponse::success(comparison)))
+}
+
+/// Return a node's latest configuration snapshot
+///
+/// # Errors
+/// Returns not found if no configuration has been collected from the node.
+pub async fn get_config_snapshot(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ServerResult<Json<ApiResponse<ConfigSnapshot>>> {
+    let snapshot = latest_snapshot(app_state.datastore.as_ref(), id)
+        .await?
+        .ok_or_else(|| ServerError::NotFound(format!("No configuration collected from {id}")))?;
+    Ok(Json(ApiResponse::success(snapshot)))
+}
+
+/// List change windows
+///
+/// # Errors
+/// Returns an error if stored windows cannot be loaded.
+pub async fn list_change_windows(
+    State(app_state): State<AppState>,
+) -> ServerResult<Json<ApiResponse<Vec<ChangeWindow>>>> {
+    let windows = list_windows(app_state.datastore.as_ref()).await?;
+    Ok(Json(ApiResponse::success(windows)))
+}
+
+/// Approve changes for a period; with neither a location nor a node, every
+/// node may change
+///
+/// # Errors
+/// Returns an error if both a location and a node are given, the location or
+/// node does not exist, the window does not end after it starts, or the
+/// datastore write fails.
+pub async fn create_change_window(
+    State(app_state): State<AppState>,
+    Json(request): Json<CreateWindowRequest>,
+) -> ServerResult<Json<ApiResponse<ChangeWindow>>> {
+    let datastore = app_state.datastore.as_ref();
+    let scope = match (request.location_id, request.node_id) {
+        (Some(_), Some(_)) => {
+            return Err(ServerError::BadRequest(
+                "Give either location_id or node_id, not both".to_string(),
+            ));
+        }
+        (Some(id), None) => {
+            datastore.get_location_required(&id).await?;
+            ChangeScope::Location(id)
+        }
+        (None, Some(id)) => {
+            datastore.get_node_required(&id).await?;
+            ChangeScope::Node(id)
+        }
+        (None, None) => ChangeScope::Global,
+    };
+
+    let window = ChangeWindow::new(
+        scope,
+        request.starts_at,
+        request.ends_at,
+        request.change_request,
+        request.description,
+    )?;
+    save_window(datastore, &window).await?;
+    Ok(Json(ApiResponse::success(window)))
+}
+
+/// Remove a change window
+///
+/// # Errors
+/// Returns an error if the window does not exist.
+pub async fn delete_change_window(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ServerResult<Json<ApiResponse<()>>> {
+    delete_window(app_state.datastore.as_ref(), id).await?;
+    Ok(Json(ApiResponse::success(())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::app_state::tests::create_mock_app_state;
+    use chrono::TimeDelta;
+    use unet_core::models::{DeviceRole, Node, Vendor};
+
+    #[tokio::test]
+    async fn test_snapshot_change_inside_window_is_authorized() {
+        let app_state = create_mock_app_state().await;
+        let node = Node::new(
+            "config-change-edge".to_string(),
+            "example.com".to_string(),
+            Vendor::Juniper,
+            DeviceRole::Router,
+        );
+        app_state.datastore.create_node(&node).await.unwrap();
+        let now = Utc::now();
+
+        let Json(window) = create_change_window(
+            State(app_state.clone()),
+            Json(CreateWindowRequest {
+                location_id: None,
+                node_id: Some(node.id),
+                starts_at: now - TimeDelta::hours(1),
+                ends_at: now + TimeDelta::hours(1),
+                change_request: Some("CHG0042".to_string()),
+                description: None,
+            }),
+        )
+        .await
+        .unwrap();
+
+        for config in ["hostname a\n", "hostname b\n"] {
+            let Json(response) = submit_config_snapshot(
+                State(app_state.clone()),
+                Path(node.id),
+                Json(SubmitSnapshotRequest {
+                    config: config.to_string(),
+                    collected_at: None,
+                }),
+            )
+            .await
+            .unwrap();
+            assert!(!response.data.unauthorized);
+        }
+        let Json(latest) = get_config_snapshot(State(app_state.clone()), Path(node.id))
+            .await
+            .unwrap();
+        assert_eq!(latest.data.config, "hostname b\n");
+
+        delete_change_window(State(app_state), Path(window.data.id))
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,100 @@
+//! Change log handlers
+
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+};
+use uuid::Uuid;
+
+use crate::api::ApiResponse;
+use crate::handlers::{ServerError, ServerResult};
+use crate::server::AppState;
+use unet_core::change_log::{
+    ActivityRecord, ChangeEvent, ComplianceSummary, EntityActivity, EventFilter, LocationCompliance,
+};
+
+/// List recorded changes in log order
+///
+/// Pass the sequence number of the last event of a page as `after` to read
+/// the next one.
+///
+/// # Errors
+/// Returns an error if the change log cannot be read.
+pub async fn get_events(
+    State(app_state): State<AppState>,
+    Query(filter): Query<EventFilter>,
+) -> ServerResult<Json<ApiResponse<Vec<ChangeEvent>>>> {
+    let events = app_state.datastore.list_change_events(&filter).await?;
+    Ok(Json(ApiResponse::success(events)))
+}
+
+/// Show when an entity was created, last changed, and deleted
+///
+/// # Errors
+/// Returns an error if no changes are recorded for the entity or the
+/// projection cannot be read.
+pub async fn get_entity_activity(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ServerResult<Json<ApiResponse<ActivityRecord>>> {
+    let activity = EntityActivity::get(app_state.datastore.as_ref(), id)
+        .await?
+        .ok_or_else(|| ServerError::NotFound(format!("No changes recorded for {id}")))?;
+    Ok(Json(ApiResponse::success(activity)))
+}
+
+/// Show the compliance of the nodes at each location
+///
+/// # Errors
+/// Returns an error if the projection or the policy results cannot be read.
+pub async fn get_compliance_summary(
+    State(app_state): State<AppState>,
+) -> ServerResult<Json<ApiResponse<Vec<LocationCompliance>>>> {
+    let summary = ComplianceSummary::summarize(app_state.datastore.as_ref()).await?;
+    Ok(Json(ApiResponse::success(summary)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::app_state::tests::create_mock_app_state;
+    use unet_core::change_log::{ChangeType, EntityKind};
+
+    #[tokio::test]
+    async fn test_get_events_filters_by_entity_id() {
+        let app_state = create_mock_app_state().await;
+        let event = ChangeEvent::new(
+            EntityKind::Link,
+            Uuid::new_v4(),
+            ChangeType::Created,
+            serde_json::Value::Null,
+        );
+        let event = app_state
+            .datastore
+            .append_change_event(&event)
+            .await
+            .unwrap();
+
+        let filter = EventFilter {
+            entity_id: Some(event.entity_id),
+            ..EventFilter::default()
+        };
+        let Json(response) = get_events(State(app_state.clone()), Query(filter))
+            .await
+            .unwrap();
+        assert_eq!(response.data, [event]);
+
+        let filter = EventFilter {
+            entity_id: Some(event.entity_id),
+            after: Some(event.sequence),
+            ..EventFilter::default()
+        };
+        let Json(response) = get_events(State(app_state.clone()), Query(filter))
+            .await
+            .unwrap();
+        assert!(response.data.is_empty());
+
+        let result = get_entity_activity(State(app_state), Path(Uuid::new_v4())).await;
+        assert!(matches!(result, Err(ServerError::NotFound(_))));
+    }
+}
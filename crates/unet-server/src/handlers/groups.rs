@@ -0,0 +1,139 @@
+//! Dynamic node group handlers
+
+use axum::{
+    extract::{Path, State},
+    response::Json,
+};
+use serde::Deserialize;
+
+use crate::api::ApiResponse;
+use crate::handlers::ServerResult;
+use crate::server::AppState;
+use unet_core::groups::{NodeGroup, delete_group, group_members, list_groups, save_group};
+use unet_core::models::Node;
+
+/// Body of a group create or replace request
+#[derive(Debug, Deserialize)]
+pub struct PutGroupRequest {
+    /// Policy condition members satisfy
+    pub filter: String,
+    /// What the group is for
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// List node groups
+///
+/// # Errors
+/// Returns an error if stored groups cannot be loaded.
+pub async fn list_node_groups(
+    State(app_state): State<AppState>,
+) -> ServerResult<Json<ApiResponse<Vec<NodeGroup>>>> {
+    let groups = list_groups(app_state.datastore.as_ref()).await?;
+    Ok(Json(ApiResponse::success(groups)))
+}
+
+/// Create or replace a node group
+///
+/// # Errors
+/// Returns an error if the name is invalid or the filter does not parse.
+pub async fn put_node_group(
+    State(app_state): State<AppState>,
+    Path(name): Path<String>,
+    Json(request): Json<PutGroupRequest>,
+) -> ServerResult<Json<ApiResponse<NodeGroup>>> {
+    let group = NodeGroup::new(&name, &request.filter, request.description)?;
+    save_group(app_state.datastore.as_ref(), &group).await?;
+    Ok(Json(ApiResponse::success(group)))
+}
+
+/// Remove a node group
+///
+/// # Errors
+/// Returns an error if no such group exists.
+pub async fn delete_node_group(
+    State(app_state): State<AppState>,
+    Path(name): Path<String>,
+) -> ServerResult<Json<ApiResponse<()>>> {
+    delete_group(app_state.datastore.as_ref(), &name).await?;
+    Ok(Json(ApiResponse::success(())))
+}
+
+/// List the nodes currently matching a group's filter
+///
+/// # Errors
+/// Returns an error if no such group exists or nodes cannot be loaded.
+pub async fn get_node_group_members(
+    State(app_state): State<AppState>,
+    Path(name): Path<String>,
+) -> ServerResult<Json<ApiResponse<Vec<Node>>>> {
+    let members = group_members(app_state.datastore.as_ref(), &name).await?;
+    Ok(Json(ApiResponse::success(members)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::ServerError;
+    use crate::server::app_state::tests::create_mock_app_state;
+    use unet_core::datastore::DataStoreError;
+
+    #[tokio::test]
+    async fn test_put_node_group_then_list() {
+        let app_state = create_mock_app_state().await;
+
+        let Json(response) = put_node_group(
+            State(app_state.clone()),
+            Path("core".to_string()),
+            Json(PutGroupRequest {
+                filter: r#"node.custom_data.tier == "core""#.to_string(),
+                description: Some("Core routers".to_string()),
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.data.name, "core");
+
+        let Json(response) = list_node_groups(State(app_state.clone())).await.unwrap();
+        assert!(response.data.iter().any(|group| group.name == "core"));
+
+        let Json(response) = get_node_group_members(State(app_state), Path("core".to_string()))
+            .await
+            .unwrap();
+        assert!(response.data.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_put_node_group_rejects_invalid_filter() {
+        let app_state = create_mock_app_state().await;
+
+        let result = put_node_group(
+            State(app_state),
+            Path("core".to_string()),
+            Json(PutGroupRequest {
+                filter: "node.vendor ==".to_string(),
+                description: None,
+            }),
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(ServerError::DataStore(
+                DataStoreError::ValidationError { .. }
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_members_of_missing_group() {
+        let app_state = create_mock_app_state().await;
+
+        let result = get_node_group_members(State(app_state), Path("missing".to_string())).await;
+
+        assert!(matches!(
+            result,
+            Err(ServerError::DataStore(DataStoreError::NotFound { .. }))
+        ));
+    }
+}
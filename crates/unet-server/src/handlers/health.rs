@@ -70,9 +70,9 @@ mod tests {
     use crate::server::AppState;
     use axum::{extract::State, http::StatusCode};
     use std::sync::Arc;
+    use test_support::sqlite::sqlite_store;
     use unet_core::datastore::sqlite::SqliteStore;
     use unet_core::policy_integration::PolicyService;
-    use test_support::sqlite::sqlite_store;
 
     async fn create_healthy_app_state() -> AppState {
         let git_config = unet_core::config::GitConfig {
@@ -92,6 +92,10 @@ mod tests {
         AppState {
             datastore: Arc::new(sqlite_store().await),
             policy_service: PolicyService::new(git_config),
+            enrichment: unet_core::enrichment::EnrichmentPipeline::default(),
+            field_encryption: None,
+            topology: Arc::new(unet_core::topology::TopologyCache::default()),
+            indexed_search: false,
         }
     }
 
@@ -0,0 +1,121 @@
+//! Link measurement and threshold handlers
+
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::api::ApiResponse;
+use crate::handlers::{ServerError, ServerResult};
+use crate::server::AppState;
+use unet_core::measurement::{
+    DEFAULT_THRESHOLDS_KEY, LinkMeasurement, LinkThresholds, get_thresholds, measurement_history,
+    set_thresholds,
+};
+
+/// Query parameters for measurement history
+#[derive(Debug, Deserialize)]
+pub struct MeasurementsQuery {
+    /// Return only the newest measurements
+    pub last: Option<usize>,
+}
+
+/// A link's measurement history with the thresholds that apply to it
+#[derive(Debug, Serialize)]
+pub struct LinkMeasurementsResponse {
+    /// Measured link
+    pub link_id: Uuid,
+    /// Effective thresholds
+    pub thresholds: LinkThresholds,
+    /// Measurements, oldest first
+    pub measurements: Vec<LinkMeasurement>,
+}
+
+/// Get recorded measurements for a link
+///
+/// # Errors
+/// Returns an error if the link does not exist or measurements cannot be loaded.
+pub async fn get_link_measurements(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<MeasurementsQuery>,
+) -> ServerResult<Json<ApiResponse<LinkMeasurementsResponse>>> {
+    let datastore = app_state.datastore.as_ref();
+    let link = datastore.get_link_required(&id).await?;
+    let mut measurements = measurement_history(datastore, link.id).await?;
+    if let Some(last) = query.last {
+        measurements.drain(..measurements.len().saturating_sub(last));
+    }
+
+    Ok(Json(ApiResponse::success(LinkMeasurementsResponse {
+        link_id: link.id,
+        thresholds: get_thresholds(datastore, link.id).await?,
+        measurements,
+    })))
+}
+
+/// Set alarm thresholds for a link, or the defaults when the target is `default`
+///
+/// # Errors
+/// Returns an error if the target is neither a link ID nor `default`, the link
+/// does not exist, or a limit is invalid.
+pub async fn put_link_thresholds(
+    State(app_state): State<AppState>,
+    Path(target): Path<String>,
+    Json(thresholds): Json<LinkThresholds>,
+) -> ServerResult<Json<ApiResponse<LinkThresholds>>> {
+    let datastore = app_state.datastore.as_ref();
+    let link_id = if target == DEFAULT_THRESHOLDS_KEY {
+        None
+    } else {
+        let id = Uuid::parse_str(&target).map_err(|_| {
+            ServerError::BadRequest(format!(
+                "Threshold target must be a link ID or '{DEFAULT_THRESHOLDS_KEY}'"
+            ))
+        })?;
+        Some(datastore.get_link_required(&id).await?.id)
+    };
+
+    set_thresholds(datastore, link_id, &thresholds).await?;
+    Ok(Json(ApiResponse::success(thresholds)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::app_state::tests::create_mock_app_state;
+    use unet_core::datastore::DataStoreError;
+
+    #[tokio::test]
+    async fn test_get_link_measurements_unknown_link() {
+        let app_state = create_mock_app_state().await;
+
+        let result = get_link_measurements(
+            State(app_state),
+            Path(Uuid::new_v4()),
+            Query(MeasurementsQuery { last: None }),
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(ServerError::DataStore(DataStoreError::NotFound { .. }))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_put_link_thresholds_rejects_bad_target() {
+        let app_state = create_mock_app_state().await;
+
+        let result = put_link_thresholds(
+            State(app_state),
+            Path("core-links".to_string()),
+            Json(LinkThresholds::default()),
+        )
+        .await;
+
+        assert!(matches!(result, Err(ServerError::BadRequest(_))));
+    }
+}
@@ -0,0 +1,187 @@
+//! Link create and update handlers
+
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::api::{ApiResponse, LinkRequest};
+use crate::handlers::{ServerError, ServerResult};
+use crate::server::AppState;
+use unet_core::datastore::{DEFAULT_MAX_RETRIES, DataStore, retry_operation};
+use unet_core::models::Link;
+use unet_core::slug::{SlugKind, assign_slug, check_slug};
+use unet_core::topology::{EndpointError, verify_link_endpoints};
+
+/// Query parameters for link writes
+#[derive(Debug, Default, Deserialize)]
+pub struct LinkWriteQuery {
+    /// Store the link even if its nodes or interfaces are not known
+    #[serde(default)]
+    pub skip_interface_check: bool,
+}
+
+/// Create a new link
+///
+/// # Errors
+/// Returns an error if validation fails, an endpoint does not match a known
+/// node and interface without `skip_interface_check`, or datastore operations
+/// fail.
+pub async fn create_link(
+    State(app_state): State<AppState>,
+    Query(query): Query<LinkWriteQuery>,
+    Json(mut payload): Json<LinkRequest>,
+) -> ServerResult<Json<ApiResponse<Link>>> {
+    let datastore = app_state.datastore.as_ref();
+    let slug = payload.slug.take();
+    let link = payload
+        .into_builder()
+        .build()
+        .map_err(|e| ServerError::BadRequest(format!("Link validation failed: {e}")))?;
+    check_endpoints(datastore, &link, &query).await?;
+    if let Some(slug) = &slug {
+        check_slug(datastore, SlugKind::Link, slug).await?;
+    }
+
+    let created = retry_operation(DEFAULT_MAX_RETRIES, || datastore.create_link(&link)).await?;
+    assign_slug(
+        datastore,
+        SlugKind::Link,
+        created.id,
+        &created.name,
+        slug.as_deref(),
+    )
+    .await?;
+    Ok(Json(ApiResponse::success(created)))
+}
+
+/// Replace an existing link
+///
+/// # Errors
+/// Returns an error if the link does not exist, validation fails, an
+/// endpoint does not match a known node and interface without
+/// `skip_interface_check`, or datastore operations fail.
+pub async fn update_link(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<LinkWriteQuery>,
+    Json(payload): Json<LinkRequest>,
+) -> ServerResult<Json<ApiResponse<Link>>> {
+    let datastore = app_state.datastore.as_ref();
+    datastore.get_link_required(&id).await?;
+    let link = payload
+        .into_builder()
+        .id(id)
+        .build()
+        .map_err(|e| ServerError::BadRequest(format!("Link validation failed: {e}")))?;
+    check_endpoints(datastore, &link, &query).await?;
+
+    let updated = retry_operation(DEFAULT_MAX_RETRIES, || datastore.update_link(&link)).await?;
+    Ok(Json(ApiResponse::success(updated)))
+}
+
+/// Rejects links whose endpoints do not match known nodes and interfaces,
+/// unless the request skips the check
+async fn check_endpoints(
+    datastore: &dyn DataStore,
+    link: &Link,
+    query: &LinkWriteQuery,
+) -> ServerResult<()> {
+    if query.skip_interface_check {
+        return Ok(());
+    }
+    verify_link_endpoints(datastore, link)
+        .await
+        .map_err(|e| match e {
+            EndpointError::DataStore(e) => ServerError::DataStore(e),
+            e => ServerError::BadRequest(format!(
+                "Link endpoint check failed: {e} (set skip_interface_check=true to override)"
+            )),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::app_state::tests::create_mock_app_state;
+    use unet_core::models::{DeviceRole, Node, Vendor};
+
+    fn request(node_a: Uuid, node_z: Uuid) -> LinkRequest {
+        LinkRequest {
+            name: "core-01-core-02".to_string(),
+            source_node_id: node_a,
+            node_a_interface: "xe-0/0/0".to_string(),
+            dest_node_id: Some(node_z),
+            node_z_interface: Some("xe-0/0/1".to_string()),
+            is_internet_circuit: false,
+            description: None,
+            bandwidth: None,
+            provider: None,
+            circuit_id: None,
+            provisioning_state: None,
+            custom_data: None,
+            slug: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_link_writes_check_endpoints_unless_skipped() {
+        let app_state = create_mock_app_state().await;
+        let mut nodes = Vec::new();
+        for name in ["core-01", "core-02"] {
+            let node = Node::new(
+                name.to_string(),
+                "example.com".to_string(),
+                Vendor::Juniper,
+                DeviceRole::Router,
+            );
+            nodes.push(app_state.datastore.create_node(&node).await.unwrap());
+        }
+        let (node, peer) = (&nodes[0], &nodes[1]);
+        let missing = Uuid::new_v4();
+
+        let result = create_link(
+            State(app_state.clone()),
+            Query(LinkWriteQuery::default()),
+            Json(request(node.id, missing)),
+        )
+        .await;
+        match result {
+            Err(ServerError::BadRequest(message)) => {
+                assert!(message.contains(&missing.to_string()), "{message}");
+            }
+            other => panic!("expected a bad request, got {other:?}"),
+        }
+
+        let Json(created) = create_link(
+            State(app_state.clone()),
+            Query(LinkWriteQuery::default()),
+            Json(request(node.id, peer.id)),
+        )
+        .await
+        .unwrap();
+        let link_id = created.data.id;
+
+        let result = update_link(
+            State(app_state.clone()),
+            Path(link_id),
+            Query(LinkWriteQuery::default()),
+            Json(request(node.id, missing)),
+        )
+        .await;
+        assert!(matches!(result, Err(ServerError::BadRequest(_))));
+
+        // The override skips the check entirely
+        let skip = LinkWriteQuery {
+            skip_interface_check: true,
+        };
+        let link = request(node.id, missing).into_builder().build().unwrap();
+        assert!(
+            check_endpoints(app_state.datastore.as_ref(), &link, &skip)
+                .await
+                .is_ok()
+        );
+    }
+}
@@ -0,0 +1,79 @@
+//! Location handlers
+
+use axum::{
+    extract::{Path, State},
+    response::Json,
+};
+use uuid::Uuid;
+
+use crate::api::ApiResponse;
+use crate::handlers::ServerResult;
+use crate::server::AppState;
+use unet_core::location_status::{LocationStatus, location_status};
+
+/// Roll up the derived state of every node in a location and its sublocations
+///
+/// Statuses carry the enrichment output of their latest poll, as for the
+/// node status endpoint.
+///
+/// # Errors
+/// Returns an error if the location does not exist or datastore operations fail.
+pub async fn get_location_status(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ServerResult<Json<ApiResponse<LocationStatus>>> {
+    let status = location_status(app_state.datastore.as_ref(), id).await?;
+    Ok(Json(ApiResponse::success(status)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::ServerError;
+    use crate::server::app_state::tests::create_mock_app_state;
+    use unet_core::datastore::DataStoreError;
+    use unet_core::models::{DeviceRole, Location, Node, Vendor};
+
+    #[tokio::test]
+    async fn test_get_location_status_counts_subtree_nodes() {
+        let app_state = create_mock_app_state().await;
+        let datastore = app_state.datastore.clone();
+        let site = Location::new_root("status-site".to_string(), "site".to_string());
+        let mut rack = Location::new_child("rack-1".to_string(), "rack".to_string(), &site.path);
+        rack.parent_id = Some(site.id);
+        datastore.create_location(&site).await.unwrap();
+        datastore.create_location(&rack).await.unwrap();
+        for (name, location) in [("status-core", &site), ("status-leaf", &rack)] {
+            let mut node = Node::new(
+                name.to_string(),
+                "example.com".to_string(),
+                Vendor::Juniper,
+                DeviceRole::Switch,
+            );
+            node.location_id = Some(location.id);
+            datastore.create_node(&node).await.unwrap();
+        }
+
+        let Json(response) = get_location_status(State(app_state), Path(site.id))
+            .await
+            .unwrap();
+
+        let status = response.data;
+        assert_eq!(status.locations, 2);
+        assert_eq!(status.nodes, 2);
+        assert_eq!(status.polled, 0);
+        assert!(status.alarms.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_location_status_unknown_location() {
+        let app_state = create_mock_app_state().await;
+
+        let result = get_location_status(State(app_state), Path(Uuid::new_v4())).await;
+
+        assert!(matches!(
+            result,
+            Err(ServerError::DataStore(DataStoreError::NotFound { .. }))
+        ));
+    }
+}
@@ -1,8 +1,25 @@
 //! HTTP request handlers
 
+pub mod admin;
+pub mod config_changes;
+pub mod events;
+pub mod groups;
 pub mod health;
+pub mod link_measurements;
+pub mod links;
+pub mod locations;
+pub mod node_defaults;
 pub mod nodes;
+pub mod notes;
+pub mod oid_profiles;
 pub mod policies;
+pub mod polling;
+pub mod reports;
+pub mod search;
+pub mod system;
+pub mod topology;
+pub mod vlans;
+pub mod webhooks;
 
 // Re-export server error types for handlers
 pub use crate::error::{ServerError, ServerResult};
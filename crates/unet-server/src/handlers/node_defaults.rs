@@ -0,0 +1,94 @@
+//! Default `custom_data` handlers
+
+use axum::{
+    extract::{Path, State},
+    response::Json,
+};
+
+use crate::api::ApiResponse;
+use crate::handlers::ServerResult;
+use crate::server::AppState;
+use unet_core::node_defaults::{
+    CustomDataDefaults, DefaultsScope, delete_defaults, list_defaults, save_defaults,
+};
+
+/// List registered `custom_data` defaults
+///
+/// # Errors
+/// Returns an error if stored defaults cannot be loaded.
+pub async fn list_node_defaults(
+    State(app_state): State<AppState>,
+) -> ServerResult<Json<ApiResponse<Vec<CustomDataDefaults>>>> {
+    let defaults = list_defaults(app_state.datastore.as_ref()).await?;
+    Ok(Json(ApiResponse::success(defaults)))
+}
+
+/// Register the `custom_data` defaults for a role or vendor
+///
+/// # Errors
+/// Returns an error if the target is invalid or the body is not a JSON object.
+pub async fn put_node_defaults(
+    State(app_state): State<AppState>,
+    Path((scope, target)): Path<(DefaultsScope, String)>,
+    Json(data): Json<serde_json::Value>,
+) -> ServerResult<Json<ApiResponse<CustomDataDefaults>>> {
+    let defaults = CustomDataDefaults::new(scope, &target, data)?;
+    save_defaults(app_state.datastore.as_ref(), &defaults).await?;
+    Ok(Json(ApiResponse::success(defaults)))
+}
+
+/// Remove the `custom_data` defaults for a role or vendor
+///
+/// # Errors
+/// Returns an error if the target is invalid or no defaults are registered.
+pub async fn delete_node_defaults(
+    State(app_state): State<AppState>,
+    Path((scope, target)): Path<(DefaultsScope, String)>,
+) -> ServerResult<Json<ApiResponse<()>>> {
+    delete_defaults(app_state.datastore.as_ref(), scope, &target).await?;
+    Ok(Json(ApiResponse::success(())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::ServerError;
+    use crate::server::app_state::tests::create_mock_app_state;
+    use unet_core::datastore::DataStoreError;
+
+    #[tokio::test]
+    async fn test_put_node_defaults_then_list() {
+        let app_state = create_mock_app_state().await;
+
+        let Json(response) = put_node_defaults(
+            State(app_state.clone()),
+            Path((DefaultsScope::Vendor, "MikroTik".to_string())),
+            Json(serde_json::json!({"routeros": {"version": null}})),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.data.target, "mikrotik");
+
+        let Json(response) = list_node_defaults(State(app_state)).await.unwrap();
+        assert!(response.data.iter().any(|d| d.key() == "vendor:mikrotik"));
+    }
+
+    #[tokio::test]
+    async fn test_put_node_defaults_rejects_non_object() {
+        let app_state = create_mock_app_state().await;
+
+        let result = put_node_defaults(
+            State(app_state),
+            Path((DefaultsScope::Vendor, "cisco".to_string())),
+            Json(serde_json::json!("bgp")),
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(ServerError::DataStore(
+                DataStoreError::ValidationError { .. }
+            ))
+        ));
+    }
+}
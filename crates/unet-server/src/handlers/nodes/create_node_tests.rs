@@ -15,7 +15,7 @@ mod tests {
         let app_state = setup_test_app_state().await;
         let request = create_test_create_request();
 
-        let result = create_node(State(app_state.clone()), Json(request)).await;
+        let result = create_node(State(app_state.clone()), None, Json(request)).await;
 
         assert!(result.is_ok());
         let Json(ApiResponse { data, success, .. }) = result.unwrap();
@@ -49,10 +49,12 @@ mod tests {
             lifecycle: Lifecycle::Live,
             location_id: None,
             management_ip: Some("192.168.1.1".to_string()),
+            management_ipv6: None,
             custom_data: Some(serde_json::json!({"rack": "R1"})),
+            slug: None,
         };
 
-        let result = create_node(State(app_state), Json(request)).await;
+        let result = create_node(State(app_state), None, Json(request)).await;
 
         assert!(result.is_err());
         let error = result.unwrap_err();
@@ -71,10 +73,12 @@ mod tests {
             lifecycle: Lifecycle::Live,
             location_id: None,
             management_ip: Some("invalid-ip".to_string()),
+            management_ipv6: None,
             custom_data: Some(serde_json::json!({"rack": "R1"})),
+            slug: None,
         };
 
-        let result = create_node(State(app_state), Json(request)).await;
+        let result = create_node(State(app_state), None, Json(request)).await;
 
         assert!(result.is_err());
         let error = result.unwrap_err();
@@ -93,10 +97,12 @@ mod tests {
             lifecycle: Lifecycle::Planned,
             location_id: None,
             management_ip: None,
+            management_ipv6: None,
             custom_data: None,
+            slug: None,
         };
 
-        let result = create_node(State(app_state.clone()), Json(request)).await;
+        let result = create_node(State(app_state.clone()), None, Json(request)).await;
 
         assert!(result.is_ok());
         let Json(ApiResponse { data, success, .. }) = result.unwrap();
@@ -127,10 +133,12 @@ mod tests {
             lifecycle: Lifecycle::Live,
             location_id: Some(location_id),
             management_ip: Some("192.168.1.1".to_string()),
+            management_ipv6: None,
             custom_data: Some(serde_json::json!({"rack": "R1"})),
+            slug: None,
         };
 
-        let result = create_node(State(app_state), Json(request)).await;
+        let result = create_node(State(app_state), None, Json(request)).await;
 
         // Note: This might fail if location doesn't exist, depending on validation
         // The test documents the expected behavior
@@ -151,7 +159,7 @@ mod tests {
 
         // In a real test, we'd mock the datastore to fail
         // Here we just verify the happy path works
-        let result = create_node(State(app_state), Json(request)).await;
+        let result = create_node(State(app_state), None, Json(request)).await;
         assert!(result.is_ok());
     }
 }
@@ -14,7 +14,7 @@ mod tests {
         let app_state = setup_test_app_state().await;
         let request = create_test_create_request();
 
-        let result = create_node(State(app_state.clone()), Json(request)).await;
+        let result = create_node(State(app_state.clone()), None, Json(request)).await;
 
         assert!(result.is_ok());
         let Json(ApiResponse { data, success, .. }) = result.unwrap();
@@ -43,7 +43,7 @@ mod tests {
         let mut request = create_test_create_request();
         request.name = String::new(); // Invalid empty name
 
-        let result = create_node(State(app_state), Json(request)).await;
+        let result = create_node(State(app_state), None, Json(request)).await;
 
         assert!(result.is_err());
         let error = result.unwrap_err();
@@ -56,7 +56,7 @@ mod tests {
         let mut request = create_test_create_request();
         request.management_ip = Some("invalid-ip".to_string());
 
-        let result = create_node(State(app_state), Json(request)).await;
+        let result = create_node(State(app_state), None, Json(request)).await;
 
         assert!(result.is_err());
         let error = result.unwrap_err();
@@ -75,7 +75,7 @@ mod tests {
         request.management_ip = None;
         request.custom_data = None;
 
-        let result = create_node(State(app_state.clone()), Json(request)).await;
+        let result = create_node(State(app_state.clone()), None, Json(request)).await;
 
         assert!(result.is_ok());
         let Json(ApiResponse { data, success, .. }) = result.unwrap();
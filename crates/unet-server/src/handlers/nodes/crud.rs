@@ -1,19 +1,35 @@
 //! CRUD operations for node management
 
 use axum::{
+    Extension,
     extract::{Path, Query, State},
     response::Json,
 };
+use chrono::Utc;
 use uuid::Uuid;
 
 use crate::api::{
     ApiResponse, CreateNodeRequest, NodeResponse, PaginatedResponse, UpdateNodeRequest,
 };
+use crate::background::PolicyTrigger;
+use crate::handlers::webhooks::announce;
 use crate::handlers::{ServerError, ServerResult};
 use crate::server::AppState;
+use unet_core::datastore::{DEFAULT_MAX_RETRIES, retry_operation};
+use unet_core::models::NodeFields;
+use unet_core::node_defaults::apply_defaults;
 use unet_core::prelude::*;
+use unet_core::provenance::{self, Source};
+use unet_core::slug::{SlugKind, assign_slug, check_slug, remove_slugs};
+use unet_core::webhooks::{EventType, WebhookEvent};
 
-use super::types::ListNodesQuery;
+use super::types::{GetNodeQuery, ListNodesQuery};
+
+/// Fields a node response adds next to the node's own fields
+const RESPONSE_FIELDS: [&str; 1] = ["status"];
+
+/// Provenance detail of attributes set through the API
+const API_DETAIL: &str = "api";
 
 /// List all nodes with optional filtering and pagination
 ///
@@ -49,6 +65,14 @@ pub async fn list_nodes(
         });
     }
 
+    if let Some(management_ip) = query.management_ip {
+        filters.push(Filter {
+            field: "management_ip".to_string(),
+            operation: FilterOperation::Equals,
+            value: FilterValue::String(management_ip),
+        });
+    }
+
     let page = query.page.unwrap_or(1);
     let per_page = query.per_page.unwrap_or(20);
 
@@ -64,9 +88,13 @@ pub async fn list_nodes(
         }),
     };
 
+    let fields = parse_fields(query.fields.as_deref())?;
     let result = app_state.datastore.list_nodes(&options).await?;
 
-    let include_status = query.include_status.unwrap_or(false);
+    let include_status = query.include_status.unwrap_or(false)
+        && fields
+            .as_ref()
+            .is_none_or(|fields| fields.contains("status"));
     let mut node_responses = Vec::with_capacity(result.items.len());
 
     for node in result.items {
@@ -75,7 +103,11 @@ pub async fn list_nodes(
         } else {
             None
         };
-        node_responses.push(NodeResponse { node, status });
+        node_responses.push(NodeResponse {
+            node,
+            status,
+            fields: fields.clone(),
+        });
     }
 
     let paginated = PaginatedResponse {
@@ -98,7 +130,9 @@ pub async fn list_nodes(
 pub async fn get_node(
     State(app_state): State<AppState>,
     Path(id): Path<Uuid>,
+    Query(query): Query<GetNodeQuery>,
 ) -> ServerResult<Json<ApiResponse<NodeResponse>>> {
+    let fields = parse_fields(query.fields.as_deref())?;
     let node = app_state
         .datastore
         .get_node_required(&id)
@@ -110,24 +144,69 @@ pub async fn get_node(
             _ => ServerError::Internal(e.to_string()),
         })?;
 
-    let response = NodeResponse::from_node(node);
+    let response = NodeResponse {
+        fields,
+        ..NodeResponse::from_node(node)
+    };
     Ok(Json(ApiResponse::success(response)))
 }
 
+/// Parses the `fields` query parameter
+fn parse_fields(fields: Option<&str>) -> ServerResult<Option<NodeFields>> {
+    fields
+        .map(|spec| NodeFields::parse(spec, &RESPONSE_FIELDS))
+        .transpose()
+        .map_err(ServerError::BadRequest)
+}
+
 /// Create a new node
 ///
+/// The node's policies are evaluated shortly after it is created.
+///
 /// # Errors
 /// Returns an error if validation fails or datastore operations fail.
 pub async fn create_node(
     State(app_state): State<AppState>,
-    Json(payload): Json<CreateNodeRequest>,
+    policy_trigger: Option<Extension<PolicyTrigger>>,
+    Json(mut payload): Json<CreateNodeRequest>,
 ) -> ServerResult<Json<ApiResponse<NodeResponse>>> {
+    let slug = payload.slug.take();
     // Use the existing into_node method
-    let node = payload
+    let mut node = payload
         .into_node()
         .map_err(|e| ServerError::BadRequest(format!("Node validation failed: {e}")))?;
+    apply_defaults(app_state.datastore.as_ref(), &mut node).await?;
+    if let Some(slug) = &slug {
+        check_slug(app_state.datastore.as_ref(), SlugKind::Node, slug).await?;
+    }
 
-    let created_node = app_state.datastore.create_node(&node).await?;
+    let created_node = retry_operation(DEFAULT_MAX_RETRIES, || {
+        app_state.datastore.create_node(&node)
+    })
+    .await?;
+    assign_slug(
+        app_state.datastore.as_ref(),
+        SlugKind::Node,
+        created_node.id,
+        &created_node.name,
+        slug.as_deref(),
+    )
+    .await?;
+    provenance::record(
+        app_state.datastore.as_ref(),
+        None,
+        &created_node,
+        Source::Manual,
+        Some(API_DETAIL),
+        Utc::now(),
+    )
+    .await?;
+    announce(
+        app_state.datastore.as_ref(),
+        &WebhookEvent::node(EventType::NodeCreated, &created_node),
+    )
+    .await;
+    notify_policy_trigger(policy_trigger, created_node.id);
 
     let response = NodeResponse::from_node(created_node);
     Ok(Json(ApiResponse::success(response)))
@@ -135,11 +214,14 @@ pub async fn create_node(
 
 /// Update an existing node
 ///
+/// The node's policies are evaluated shortly after it is updated.
+///
 /// # Errors
 /// Returns an error if the node is not found, input is invalid, or datastore operations fail.
 pub async fn update_node(
     State(app_state): State<AppState>,
     Path(id): Path<Uuid>,
+    policy_trigger: Option<Extension<PolicyTrigger>>,
     Json(payload): Json<UpdateNodeRequest>,
 ) -> ServerResult<Json<ApiResponse<NodeResponse>>> {
     let mut node = app_state
@@ -152,6 +234,7 @@ pub async fn update_node(
             }
             _ => ServerError::Internal(e.to_string()),
         })?;
+    let before = node.clone();
 
     // Update fields that were provided
     let fqdn_needs_update = payload.name.is_some() || payload.domain.is_some();
@@ -187,23 +270,48 @@ pub async fn update_node(
         node.location_id = Some(location_id);
     }
 
-    if let Some(management_ip_str) = payload.management_ip {
-        let management_ip = management_ip_str
-            .parse()
-            .map_err(|e| ServerError::BadRequest(format!("Invalid management IP: {e}")))?;
-        node.management_ip = Some(management_ip);
-    }
+    node.set_management_addresses(
+        payload.management_ip.as_deref(),
+        payload.management_ipv6.as_deref(),
+    )
+    .map_err(|e| ServerError::BadRequest(format!("Invalid management address: {e}")))?;
 
     if let Some(custom_data) = payload.custom_data {
         node.custom_data = custom_data;
     }
 
-    let updated_node = app_state.datastore.update_node(&node).await?;
+    let updated_node = retry_operation(DEFAULT_MAX_RETRIES, || {
+        app_state.datastore.update_node(&node)
+    })
+    .await?;
+    provenance::record(
+        app_state.datastore.as_ref(),
+        Some(&before),
+        &updated_node,
+        Source::Manual,
+        Some(API_DETAIL),
+        Utc::now(),
+    )
+    .await?;
+    announce(
+        app_state.datastore.as_ref(),
+        &WebhookEvent::node(EventType::NodeUpdated, &updated_node),
+    )
+    .await;
+    notify_policy_trigger(policy_trigger, updated_node.id);
 
     let response = NodeResponse::from_node(updated_node);
     Ok(Json(ApiResponse::success(response)))
 }
 
+/// Queues a written node for policy evaluation when the server runs the
+/// trigger
+fn notify_policy_trigger(policy_trigger: Option<Extension<PolicyTrigger>>, node_id: Uuid) {
+    if let Some(Extension(policy_trigger)) = policy_trigger {
+        policy_trigger.notify(node_id);
+    }
+}
+
 /// Delete a node
 ///
 /// # Errors
@@ -212,9 +320,9 @@ pub async fn delete_node(
     State(app_state): State<AppState>,
     Path(id): Path<Uuid>,
 ) -> ServerResult<Json<ApiResponse<()>>> {
-    app_state
-        .datastore
-        .delete_node(&id)
+    // Kept for the webhook event; the node is gone once deleted
+    let node = app_state.datastore.get_node(&id).await?;
+    retry_operation(DEFAULT_MAX_RETRIES, || app_state.datastore.delete_node(&id))
         .await
         .map_err(|e| match e {
             unet_core::datastore::DataStoreError::NotFound { .. } => {
@@ -222,6 +330,14 @@ pub async fn delete_node(
             }
             _ => ServerError::Internal(e.to_string()),
         })?;
+    remove_slugs(app_state.datastore.as_ref(), SlugKind::Node, id).await?;
+    if let Some(node) = node {
+        announce(
+            app_state.datastore.as_ref(),
+            &WebhookEvent::node(EventType::NodeDeleted, &node),
+        )
+        .await;
+    }
 
     Ok(Json(ApiResponse::success(())))
 }
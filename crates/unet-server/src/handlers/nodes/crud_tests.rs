@@ -51,6 +51,10 @@ pub mod test_utils {
         AppState {
             datastore,
             policy_service,
+            enrichment: unet_core::enrichment::EnrichmentPipeline::default(),
+            field_encryption: None,
+            topology: Arc::new(unet_core::topology::TopologyCache::default()),
+            indexed_search: false,
         }
     }
 
@@ -68,6 +72,10 @@ pub mod test_utils {
             AppState {
                 datastore,
                 policy_service,
+                enrichment: unet_core::enrichment::EnrichmentPipeline::default(),
+                field_encryption: None,
+                topology: Arc::new(unet_core::topology::TopologyCache::default()),
+                indexed_search: false,
             },
         )
     }
@@ -95,7 +103,9 @@ pub mod test_utils {
             lifecycle: Lifecycle::Live,
             location_id: None,
             management_ip: Some("192.168.1.1".to_string()),
+            management_ipv6: None,
             custom_data: Some(serde_json::json!({"rack": "R1"})),
+            slug: None,
         }
     }
 
@@ -125,6 +135,7 @@ pub mod test_utils {
             lifecycle: Some(Lifecycle::Implementing),
             location_id: None,
             management_ip: Some("192.168.2.1".to_string()),
+            management_ipv6: None,
             custom_data: Some(serde_json::json!({"rack": "R2"})),
         }
     }
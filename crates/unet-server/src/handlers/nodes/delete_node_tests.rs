@@ -51,6 +51,9 @@ mod tests {
     #[tokio::test]
     async fn test_delete_node_internal_error() {
         let mut mock_datastore = MockDataStore::new();
+        mock_datastore
+            .expect_get_node()
+            .returning(|_| Box::pin(async { Ok(None) }));
         mock_datastore.expect_delete_node().returning(|_| {
             Box::pin(async move {
                 Err(DataStoreError::TransactionError {
@@ -62,6 +65,10 @@ mod tests {
         let app_state = AppState {
             datastore: Arc::new(mock_datastore),
             policy_service: PolicyService::new(Config::default().git),
+            enrichment: unet_core::enrichment::EnrichmentPipeline::default(),
+            field_encryption: None,
+            topology: Arc::new(unet_core::topology::TopologyCache::default()),
+            indexed_search: false,
         };
 
         let node_id = Uuid::new_v4();
@@ -1,15 +1,44 @@
 //! Derived state operations for nodes (status, interfaces, metrics)
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     response::Json,
 };
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::Deserialize;
 use uuid::Uuid;
 
 use crate::api::ApiResponse;
 use crate::handlers::{ServerError, ServerResult};
 use crate::server::AppState;
-use unet_core::models::derived::{InterfaceStatus, NodeStatus, PerformanceMetrics};
+use unet_core::collectors::get_collected_state;
+use unet_core::enrichment::enriched_node_status;
+use unet_core::hardware::{HardwareInventory, get_inventory};
+use unet_core::models::derived::{
+    Aggregation, InterfaceStatus, MetricQuery, MetricSeries, NodeStatus, PerformanceMetrics,
+    parse_query_time,
+};
+use unet_core::snmp::pauses::parse_duration;
+
+/// Query parameters of a metrics range query
+#[derive(Debug, Default, Deserialize)]
+pub struct MetricsQueryParams {
+    /// `cpu`, `memory`, `memory_used`, or `load_average`
+    pub metric: String,
+    /// Start of the range, as Unix milliseconds or RFC 3339; defaults to an
+    /// hour before `to`
+    #[serde(default)]
+    pub from: Option<String>,
+    /// End of the range, as Unix milliseconds or RFC 3339; defaults to now
+    #[serde(default)]
+    pub to: Option<String>,
+    /// Bucket width, e.g. `30s` or `5m`; defaults to `1m`
+    #[serde(default)]
+    pub step: Option<String>,
+    /// `avg`, `min`, `max`, `sum`, or `count`; defaults to `avg`
+    #[serde(default, rename = "fn")]
+    pub function: Option<String>,
+}
 
 /// Get node status (derived state)
 ///
@@ -31,10 +60,9 @@ pub async fn get_node_status(
             _ => ServerError::Internal(e.to_string()),
         })?;
 
-    // Get node status from datastore
-    let status = app_state
-        .datastore
-        .get_node_status(&id)
+    // Get node status from datastore, with any eAPI or RESTCONF collection
+    // and the enrichment output stored by the latest poll
+    let status = enriched_node_status(app_state.datastore.as_ref(), id)
         .await?
         .ok_or_else(|| ServerError::NotFound(format!("No status available for node {id}")))?;
 
@@ -61,8 +89,15 @@ pub async fn get_node_interfaces(
             _ => ServerError::Internal(e.to_string()),
         })?;
 
-    // Get interfaces from datastore
-    let interfaces = app_state.datastore.get_node_interfaces(&id).await?;
+    // Collected interfaces take precedence over polled ones
+    let collected = get_collected_state(app_state.datastore.as_ref(), id)
+        .await?
+        .map(|state| state.interfaces)
+        .filter(|interfaces| !interfaces.is_empty());
+    let interfaces = match collected {
+        Some(interfaces) => interfaces,
+        None => app_state.datastore.get_node_interfaces(&id).await?,
+    };
 
     Ok(Json(ApiResponse::success(interfaces)))
 }
@@ -97,245 +132,99 @@ pub async fn get_node_metrics(
     Ok(Json(ApiResponse::success(metrics)))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::server::AppState;
-    use axum::extract::{Path, State};
-    use migration::sea_orm::{ActiveModelTrait, Set};
-    use std::sync::Arc;
-    use unet_core::{
-        datastore::{DataStore, sqlite::SqliteStore},
-        entities::node_status,
-        models::*,
-        policy_integration::PolicyService,
-    };
-
-    async fn setup_test_datastore() -> SqliteStore {
-        test_support::sqlite::sqlite_store().await
-    }
-
-    async fn create_test_node(datastore: &SqliteStore) -> Node {
-        let mut node = Node::new(
-            "test-node".to_string(),
-            "example.com".to_string(),
-            Vendor::Cisco,
-            DeviceRole::Router,
-        );
-        node.model = "ASR1000".to_string();
-        node.lifecycle = Lifecycle::Live;
-        datastore.create_node(&node).await.unwrap()
-    }
-
-    #[tokio::test]
-    async fn test_get_node_status_success() {
-        let datastore = setup_test_datastore().await;
-        let node = create_test_node(&datastore).await;
-
-        node_status::ActiveModel {
-            id: Set("derived-handler-status".to_string()),
-            node_id: Set(node.id.to_string()),
-            last_updated: Set("2026-04-07T01:02:03Z".to_string()),
-            reachable: Set(true),
-            system_info: Set(Some(r#"{"name":"test-node"}"#.to_string())),
-            performance: Set(None),
-            environmental: Set(None),
-            vendor_metrics: Set(None),
-            raw_snmp_data: Set(None),
-            last_snmp_success: Set(Some("2026-04-07T01:00:00Z".to_string())),
-            last_error: Set(None),
-            consecutive_failures: Set(0),
-        }
-        .insert(datastore.connection())
+/// Get node hardware inventory (derived state)
+///
+/// # Errors
+/// Returns an error if the node does not exist, its inventory has not been
+/// walked yet, or datastore operations fail.
+pub async fn get_node_hardware(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ServerResult<Json<ApiResponse<HardwareInventory>>> {
+    // First verify the node exists
+    app_state
+        .datastore
+        .get_node_required(&id)
         .await
-        .unwrap();
-
-        let app_state = AppState {
-            datastore: Arc::new(datastore),
-            policy_service: PolicyService::with_local_dir("/tmp"),
-        };
-
-        let result = get_node_status(State(app_state), Path(node.id)).await;
-        assert!(result.is_ok());
-
-        let response = result.unwrap().0;
-        assert!(response.success);
-        assert_eq!(response.data.node_id, node.id);
-        assert!(response.data.reachable);
-    }
-
-    #[tokio::test]
-    async fn test_get_node_status_not_found() {
-        let datastore = setup_test_datastore().await;
-        let app_state = AppState {
-            datastore: Arc::new(datastore),
-            policy_service: PolicyService::with_local_dir("/tmp"),
-        };
-
-        let non_existent_id = Uuid::new_v4();
-        let result = get_node_status(State(app_state), Path(non_existent_id)).await;
-        assert!(result.is_err());
-
-        match result.unwrap_err() {
-            ServerError::NotFound(msg) => {
-                assert!(msg.contains("not found"));
-            }
-            _ => panic!("Expected NotFound error"),
-        }
-    }
-
-    #[tokio::test]
-    async fn test_get_node_interfaces_success() {
-        let datastore = setup_test_datastore().await;
-        let node = create_test_node(&datastore).await;
-
-        let app_state = AppState {
-            datastore: Arc::new(datastore),
-            policy_service: PolicyService::with_local_dir("/tmp"),
-        };
-
-        let result = get_node_interfaces(State(app_state), Path(node.id)).await;
-        assert!(result.is_ok());
-
-        let response = result.unwrap().0;
-        assert!(response.success);
-        // CSV datastore will return empty interfaces list
-        assert!(response.data.is_empty());
-    }
-
-    #[tokio::test]
-    async fn test_get_node_interfaces_not_found() {
-        let datastore = setup_test_datastore().await;
-        let app_state = AppState {
-            datastore: Arc::new(datastore),
-            policy_service: PolicyService::with_local_dir("/tmp"),
-        };
-
-        let non_existent_id = Uuid::new_v4();
-        let result = get_node_interfaces(State(app_state), Path(non_existent_id)).await;
-        assert!(result.is_err());
-
-        match result.unwrap_err() {
-            ServerError::NotFound(msg) => {
-                assert!(msg.contains("not found"));
-            }
-            _ => panic!("Expected NotFound error"),
-        }
-    }
-
-    #[tokio::test]
-    async fn test_get_node_metrics_not_found_node() {
-        let datastore = setup_test_datastore().await;
-        let app_state = AppState {
-            datastore: Arc::new(datastore),
-            policy_service: PolicyService::with_local_dir("/tmp"),
-        };
-
-        let non_existent_id = Uuid::new_v4();
-        let result = get_node_metrics(State(app_state), Path(non_existent_id)).await;
-        assert!(result.is_err());
-
-        match result.unwrap_err() {
-            ServerError::NotFound(msg) => {
-                assert!(msg.contains("not found"));
+        .map_err(|e| match e {
+            unet_core::datastore::DataStoreError::NotFound { .. } => {
+                ServerError::NotFound(format!("Node with ID {id} not found"))
             }
-            _ => panic!("Expected NotFound error"),
-        }
-    }
-
-    #[tokio::test]
-    async fn test_get_node_metrics_no_metrics() {
-        let datastore = setup_test_datastore().await;
-        let node = create_test_node(&datastore).await;
+            _ => ServerError::Internal(e.to_string()),
+        })?;
 
-        let app_state = AppState {
-            datastore: Arc::new(datastore),
-            policy_service: PolicyService::with_local_dir("/tmp"),
-        };
+    let inventory = get_inventory(app_state.datastore.as_ref(), id)
+        .await?
+        .ok_or_else(|| {
+            ServerError::NotFound(format!("No hardware inventory available for node {id}"))
+        })?;
 
-        let result = get_node_metrics(State(app_state), Path(node.id)).await;
-        assert!(result.is_err());
+    Ok(Json(ApiResponse::success(inventory)))
+}
 
-        match result.unwrap_err() {
-            ServerError::NotFound(msg) => {
-                assert!(msg.contains("No metrics available"));
+/// Query a node's performance history, downsampled to one value per step
+///
+/// Returns one series named `<node>.<metric>` with `[value, unix_ms]`
+/// datapoints, the format Grafana's JSON datasource reads. Steps without
+/// samples are left out.
+///
+/// # Errors
+/// Returns an error if the node does not exist, the query is invalid, or
+/// datastore operations fail.
+pub async fn query_node_metrics(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<MetricsQueryParams>,
+) -> ServerResult<Json<ApiResponse<Vec<MetricSeries>>>> {
+    let node = app_state
+        .datastore
+        .get_node_required(&id)
+        .await
+        .map_err(|e| match e {
+            unet_core::datastore::DataStoreError::NotFound { .. } => {
+                ServerError::NotFound(format!("Node with ID {id} not found"))
             }
-            _ => panic!("Expected NotFound error for missing metrics"),
-        }
-    }
-
-    #[tokio::test]
-    async fn test_get_node_status_internal_error_handling() {
-        let datastore = setup_test_datastore().await;
-        let app_state = AppState {
-            datastore: Arc::new(datastore),
-            policy_service: PolicyService::with_local_dir("/tmp"),
-        };
-
-        let non_existent_id = Uuid::new_v4();
-        let result = get_node_status(State(app_state), Path(non_existent_id)).await;
-
-        if let Err(ServerError::NotFound(_)) = result {
-            // This covers lines 22-29
-        } else {
-            // Any other result
-        }
-    }
-
-    #[tokio::test]
-    async fn test_get_node_interfaces_internal_error_handling() {
-        let datastore = setup_test_datastore().await;
-        let app_state = AppState {
-            datastore: Arc::new(datastore),
-            policy_service: PolicyService::with_local_dir("/tmp"),
-        };
-
-        let non_existent_id = Uuid::new_v4();
-        let result = get_node_interfaces(State(app_state), Path(non_existent_id)).await;
-
-        if let Err(ServerError::NotFound(_)) = result {
-            // This covers lines 49-56
-        } else {
-            // Any other result
-        }
-    }
-
-    #[tokio::test]
-    async fn test_get_node_metrics_internal_error_handling() {
-        let datastore = setup_test_datastore().await;
-        let app_state = AppState {
-            datastore: Arc::new(datastore),
-            policy_service: PolicyService::with_local_dir("/tmp"),
-        };
-
-        let non_existent_id = Uuid::new_v4();
-        let result = get_node_metrics(State(app_state), Path(non_existent_id)).await;
-
-        if let Err(ServerError::NotFound(_)) = result {
-            // This covers lines 72-79
-        } else {
-            // Any other result
-        }
-    }
-
-    #[tokio::test]
-    async fn test_get_node_status_without_persisted_status_returns_not_found() {
-        let datastore = setup_test_datastore().await;
-        let node = create_test_node(&datastore).await;
+            _ => ServerError::Internal(e.to_string()),
+        })?;
+    let query = metric_query(&params, Utc::now()).map_err(ServerError::BadRequest)?;
 
-        let app_state = AppState {
-            datastore: Arc::new(datastore),
-            policy_service: PolicyService::with_local_dir("/tmp"),
-        };
+    let points = app_state
+        .datastore
+        .query_performance_history(&id, &query)
+        .await?;
+    let series = MetricSeries::new(format!("{}.{}", node.name, query.metric), &points);
 
-        let result = get_node_status(State(app_state), Path(node.id)).await;
-        assert!(result.is_err());
+    Ok(Json(ApiResponse::success(vec![series])))
+}
 
-        match result.unwrap_err() {
-            ServerError::NotFound(message) => {
-                assert!(message.contains("No status available"));
-            }
-            other => panic!("Expected NotFound error, got {other:?}"),
-        }
-    }
+/// Builds a range query from request parameters, filling in defaults
+fn metric_query(params: &MetricsQueryParams, now: DateTime<Utc>) -> Result<MetricQuery, String> {
+    let metric = params.metric.parse()?;
+    let to = params.to.as_deref().map_or(Ok(now), parse_query_time)?;
+    let from = params
+        .from
+        .as_deref()
+        .map_or_else(|| Ok(to - TimeDelta::hours(1)), parse_query_time)?;
+    let step = params
+        .step
+        .as_deref()
+        .map_or(Ok(TimeDelta::minutes(1)), parse_duration)?;
+    let aggregation = params
+        .function
+        .as_deref()
+        .map_or(Ok(Aggregation::default()), str::parse)?;
+
+    let query = MetricQuery {
+        metric,
+        from,
+        to,
+        step,
+        aggregation,
+    };
+    query.validate()?;
+    Ok(query)
 }
+
+#[cfg(test)]
+#[path = "derived_tests.rs"]
+mod tests;
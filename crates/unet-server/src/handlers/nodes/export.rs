@@ -0,0 +1,61 @@
+//! Streaming export of every node
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::header,
+    response::{IntoResponse, Response},
+};
+use futures_util::stream;
+use unet_core::datastore::{DataStoreError, Pagination, QueryOptions, Sort, SortDirection};
+
+use crate::server::AppState;
+
+/// Nodes read from the datastore for each chunk of the export
+const EXPORT_PAGE_SIZE: usize = 500;
+
+/// Export every node as newline-delimited JSON
+///
+/// Nodes are read and sent a page at a time, so exporting a large fleet, or
+/// nodes with large `custom_data`, does not hold the whole response in
+/// memory. A datastore error ends the stream early.
+pub async fn export_nodes(State(app_state): State<AppState>) -> Response {
+    let datastore = app_state.datastore;
+    let pages = stream::try_unfold(Some(0_usize), move |offset| {
+        let datastore = datastore.clone();
+        async move {
+            let Some(offset) = offset else {
+                return Ok(None);
+            };
+            let options = QueryOptions {
+                filters: Vec::new(),
+                sort: vec![Sort {
+                    field: "name".to_string(),
+                    direction: SortDirection::Ascending,
+                }],
+                pagination: Some(Pagination {
+                    limit: EXPORT_PAGE_SIZE,
+                    offset,
+                }),
+            };
+            let page = datastore.list_nodes(&options).await?;
+            let mut lines = String::new();
+            for node in &page.items {
+                let line =
+                    serde_json::to_string(node).map_err(|e| DataStoreError::InternalError {
+                        message: format!("Failed to serialize node {}: {e}", node.id),
+                    })?;
+                lines.push_str(&line);
+                lines.push('\n');
+            }
+            let next = page.has_next.then_some(offset + EXPORT_PAGE_SIZE);
+            Ok::<_, DataStoreError>(Some((lines, next)))
+        }
+    });
+
+    (
+        [(header::CONTENT_TYPE, "application/x-ndjson")],
+        Body::from_stream(pages),
+    )
+        .into_response()
+}
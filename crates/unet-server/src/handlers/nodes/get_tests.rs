@@ -5,8 +5,9 @@ mod tests {
     use crate::api::ApiResponse;
     use crate::handlers::nodes::crud::*;
     use crate::handlers::nodes::crud_tests::test_utils::*;
+    use crate::handlers::nodes::types::GetNodeQuery;
     use axum::{
-        extract::{Path, State},
+        extract::{Path, Query, State},
         response::Json,
     };
     use unet_core::models::Vendor;
@@ -17,7 +18,12 @@ mod tests {
         let app_state = setup_test_app_state().await;
         let node = create_test_node(&app_state).await;
 
-        let result = get_node(State(app_state), Path(node.id)).await;
+        let result = get_node(
+            State(app_state),
+            Path(node.id),
+            Query(GetNodeQuery::default()),
+        )
+        .await;
 
         assert!(result.is_ok());
         let Json(ApiResponse { data, success, .. }) = result.unwrap();
@@ -33,7 +39,12 @@ mod tests {
         let app_state = setup_test_app_state().await;
         let non_existent_id = Uuid::new_v4();
 
-        let result = get_node(State(app_state), Path(non_existent_id)).await;
+        let result = get_node(
+            State(app_state),
+            Path(non_existent_id),
+            Query(GetNodeQuery::default()),
+        )
+        .await;
 
         assert!(result.is_err());
         let error = result.unwrap_err();
@@ -49,7 +60,12 @@ mod tests {
 
         // In a real test, we'd mock the datastore to fail
         // Here we just verify the happy path works
-        let result = get_node(State(app_state), Path(node.id)).await;
+        let result = get_node(
+            State(app_state),
+            Path(node.id),
+            Query(GetNodeQuery::default()),
+        )
+        .await;
         assert!(result.is_ok());
     }
 }
@@ -23,7 +23,9 @@ mod tests {
             lifecycle: None,
             role: None,
             vendor: None,
+            management_ip: None,
             include_status: None,
+            fields: None,
         };
 
         let result = list_nodes(State(app_state), Query(query)).await;
@@ -48,7 +50,9 @@ mod tests {
             lifecycle: None,
             role: None,
             vendor: None,
+            management_ip: None,
             include_status: None,
+            fields: None,
         };
 
         let result = list_nodes(State(app_state), Query(query)).await;
@@ -82,7 +86,9 @@ mod tests {
             lifecycle: None,
             role: None,
             vendor: None,
+            management_ip: None,
             include_status: None,
+            fields: None,
         };
 
         let result = list_nodes(State(app_state), Query(query)).await;
@@ -129,7 +135,9 @@ mod tests {
             lifecycle: None,
             role: Some("switch".to_string()),
             vendor: Some("juniper".to_string()),
+            management_ip: None,
             include_status: None,
+            fields: None,
         };
 
         let result = list_nodes(State(app_state), Query(query)).await;
@@ -174,7 +182,9 @@ mod tests {
             lifecycle: None,
             role: None,
             vendor: None,
+            management_ip: None,
             include_status: Some(true),
+            fields: None,
         };
 
         let result = list_nodes(State(app_state), Query(query)).await;
@@ -226,7 +236,9 @@ mod tests {
             lifecycle: Some("live".to_string()),
             role: None,
             vendor: None,
+            management_ip: None,
             include_status: None,
+            fields: None,
         };
 
         let result = list_nodes(State(app_state), Query(query)).await;
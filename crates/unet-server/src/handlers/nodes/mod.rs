@@ -4,7 +4,14 @@
 //! including CRUD operations and derived state endpoints.
 
 pub use crud::{create_node, delete_node, get_node, list_nodes, update_node};
-pub use derived::{get_node_interfaces, get_node_metrics, get_node_status};
+pub use derived::{
+    MetricsQueryParams, get_node_hardware, get_node_interfaces, get_node_metrics, get_node_status,
+    query_node_metrics,
+};
+pub use export::export_nodes;
+pub use poll::{PollQuery, poll_node};
+pub use provenance::get_node_provenance;
+pub use secrets::{NodeSecrets, get_node_secrets};
 
 #[cfg(test)]
 mod create_tests;
@@ -14,8 +21,12 @@ mod crud_tests; // Contains organized test modules by operation type
 #[cfg(test)]
 mod delete_tests;
 mod derived;
+mod export;
+mod poll;
+mod provenance;
 #[cfg(test)]
 mod read_tests;
+mod secrets;
 #[cfg(test)]
 mod test_helpers;
 #[cfg(test)]
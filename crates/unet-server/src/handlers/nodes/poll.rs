@@ -0,0 +1,224 @@
+//! On-demand polling of a node
+
+use axum::{
+    Extension,
+    extract::{Path, Query, State},
+    response::Json,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::api::ApiResponse;
+use crate::background::NodePoller;
+use crate::handlers::{ServerError, ServerResult};
+use crate::server::AppState;
+use unet_core::collectors::COLLECTOR_FIELD;
+use unet_core::config::Config;
+use unet_core::datastore::DataStoreError;
+use unet_core::enrichment::enriched_node_status;
+use unet_core::models::derived::NodeStatus;
+use unet_core::snmp::pauses::paused_nodes;
+
+/// Seconds a poll may take when `timeout` is not given
+const DEFAULT_POLL_TIMEOUT_SECONDS: u64 = 30;
+
+/// Query parameters of an on-demand poll
+#[derive(Debug, Default, Deserialize)]
+pub struct PollQuery {
+    /// Seconds to wait for the poll, including any wait for a free slot
+    /// (default 30)
+    #[serde(default)]
+    pub timeout: Option<u64>,
+}
+
+/// Poll a node now and return its fresh status
+///
+/// The node is collected from through its `custom_data.collector` without
+/// waiting for the next collection run, and the enrichment plugins run on
+/// the result. Servers built without a poller, as in tests, poll with
+/// default collector settings.
+///
+/// # Errors
+/// Returns an error if the node does not exist, has no collector, or is
+/// paused, `timeout` is zero, or the poll fails or does not finish in time.
+pub async fn poll_node(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<PollQuery>,
+    poller: Option<Extension<Arc<NodePoller>>>,
+) -> ServerResult<Json<ApiResponse<NodeStatus>>> {
+    let timeout = query.timeout.unwrap_or(DEFAULT_POLL_TIMEOUT_SECONDS);
+    if timeout == 0 {
+        return Err(ServerError::BadRequest(
+            "timeout must be at least 1 second".to_string(),
+        ));
+    }
+    let datastore = app_state.datastore.as_ref();
+    let node = datastore
+        .get_node_required(&id)
+        .await
+        .map_err(|e| match e {
+            DataStoreError::NotFound { .. } => {
+                ServerError::NotFound(format!("Node with ID {id} not found"))
+            }
+            _ => ServerError::Internal(e.to_string()),
+        })?;
+    if paused_nodes(datastore, Utc::now()).await?.contains(&id) {
+        return Err(ServerError::BadRequest(format!(
+            "Polling of {} is paused",
+            node.name
+        )));
+    }
+
+    let poller = poller.map_or_else(
+        || {
+            let poller = NodePoller::new(&Config::default());
+            Arc::new(poller.with_enrichment(app_state.enrichment.clone()))
+        },
+        |Extension(poller)| poller,
+    );
+    let started = std::time::Instant::now();
+    poller
+        .poll(datastore, &node, Duration::from_secs(timeout))
+        .await?
+        .ok_or_else(|| {
+            ServerError::BadRequest(format!(
+                "{} has no custom_data.{COLLECTOR_FIELD} to poll through",
+                node.name
+            ))
+        })?;
+    info!(
+        node = %node.name,
+        duration_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+        "Polled node on demand"
+    );
+
+    let status = enriched_node_status(datastore, id)
+        .await?
+        .ok_or_else(|| ServerError::NotFound(format!("No status available for node {id}")))?;
+    Ok(Json(ApiResponse::success(status)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::app_state::tests::create_mock_app_state;
+    use async_trait::async_trait;
+    use serde_json::json;
+    use unet_core::collectors::{HttpRequest, HttpResponse, HttpTransport};
+    use unet_core::config::CollectorsConfig;
+    use unet_core::models::{AddressFamilyPreference, DeviceRole, Node, Vendor};
+
+    /// Answers every request with an empty eAPI result after `delay`
+    struct FakeTransport {
+        delay: Duration,
+    }
+
+    #[async_trait]
+    impl HttpTransport for FakeTransport {
+        async fn send(
+            &self,
+            _request: &HttpRequest,
+            _username: &str,
+            _password: &str,
+        ) -> Result<HttpResponse, String> {
+            tokio::time::sleep(self.delay).await;
+            let body = json!({ "jsonrpc": "2.0", "id": "unet", "result": [{}, {}, {}] });
+            Ok(HttpResponse {
+                status: 200,
+                body: serde_json::to_vec(&body).unwrap(),
+            })
+        }
+    }
+
+    fn poller(delay: Duration) -> Option<Extension<Arc<NodePoller>>> {
+        Some(Extension(Arc::new(NodePoller::with_transport(
+            Arc::new(FakeTransport { delay }),
+            CollectorsConfig::default(),
+            AddressFamilyPreference::default(),
+        ))))
+    }
+
+    async fn create_node(app_state: &AppState, collector: Option<&str>) -> Node {
+        let mut node = Node::new(
+            "leaf-01".to_string(),
+            "example.com".to_string(),
+            Vendor::Arista,
+            DeviceRole::Switch,
+        );
+        node.management_ip = Some("192.0.2.10".parse().unwrap());
+        if let Some(collector) = collector {
+            node.custom_data = json!({ "collector": collector });
+        }
+        app_state.datastore.create_node(&node).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_poll_node_returns_fresh_status() {
+        let app_state = create_mock_app_state().await;
+        let node = create_node(&app_state, Some("arista_eapi")).await;
+        let before = Utc::now();
+
+        let Json(response) = poll_node(
+            State(app_state),
+            Path(node.id),
+            Query(PollQuery::default()),
+            poller(Duration::ZERO),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.data.node_id, node.id);
+        assert!(response.data.reachable);
+        assert!(response.data.last_updated >= before.into());
+    }
+
+    #[tokio::test]
+    async fn test_poll_node_times_out() {
+        let app_state = create_mock_app_state().await;
+        let node = create_node(&app_state, Some("arista_eapi")).await;
+
+        let result = poll_node(
+            State(app_state),
+            Path(node.id),
+            Query(PollQuery { timeout: Some(1) }),
+            poller(Duration::from_secs(5)),
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(ServerError::DataStore(DataStoreError::Timeout {
+                seconds: 1
+            }))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_poll_node_rejects_nodes_without_collector() {
+        let app_state = create_mock_app_state().await;
+        let node = create_node(&app_state, None).await;
+
+        let result = poll_node(
+            State(app_state.clone()),
+            Path(node.id),
+            Query(PollQuery::default()),
+            poller(Duration::ZERO),
+        )
+        .await;
+        assert!(matches!(result, Err(ServerError::BadRequest(_))));
+
+        let result = poll_node(
+            State(app_state),
+            Path(node.id),
+            Query(PollQuery { timeout: Some(0) }),
+            poller(Duration::ZERO),
+        )
+        .await;
+        assert!(matches!(result, Err(ServerError::BadRequest(_))));
+    }
+}
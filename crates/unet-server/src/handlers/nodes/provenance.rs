@@ -0,0 +1,72 @@
+//! Sources of node attributes
+
+use axum::{
+    extract::{Path, State},
+    response::Json,
+};
+use uuid::Uuid;
+
+use crate::api::ApiResponse;
+use crate::handlers::{ServerError, ServerResult};
+use crate::server::AppState;
+use unet_core::datastore::DataStoreError;
+use unet_core::provenance::{NodeProvenance, get_provenance};
+
+/// Get the source of each of a node's attributes
+///
+/// A node whose attributes were all set before tracking began has no fields.
+///
+/// # Errors
+/// Returns an error if the node does not exist or datastore operations fail.
+pub async fn get_node_provenance(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ServerResult<Json<ApiResponse<NodeProvenance>>> {
+    let datastore = app_state.datastore.as_ref();
+    datastore
+        .get_node_required(&id)
+        .await
+        .map_err(|e| match e {
+            DataStoreError::NotFound { .. } => {
+                ServerError::NotFound(format!("Node with ID {id} not found"))
+            }
+            _ => ServerError::Internal(e.to_string()),
+        })?;
+
+    let provenance = get_provenance(datastore, id)
+        .await?
+        .unwrap_or_else(|| NodeProvenance::new(id));
+    Ok(Json(ApiResponse::success(provenance)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::nodes::crud_tests::test_utils::{
+        create_test_node, create_test_update_request, setup_test_app_state,
+    };
+    use crate::handlers::nodes::update_node;
+    use unet_core::provenance::Source;
+
+    #[tokio::test]
+    async fn test_api_updates_are_recorded_as_manual() {
+        let app_state = setup_test_app_state().await;
+        let node = create_test_node(&app_state).await;
+        let mut request = create_test_update_request();
+        request.model = Some("MX204".to_string());
+
+        update_node(State(app_state.clone()), Path(node.id), None, Json(request))
+            .await
+            .unwrap();
+        let Json(response) = get_node_provenance(State(app_state.clone()), Path(node.id))
+            .await
+            .unwrap();
+
+        let model = &response.data.fields["model"];
+        assert_eq!(model.source, Source::Manual);
+        assert_eq!(model.detail.as_deref(), Some("api"));
+
+        let result = get_node_provenance(State(app_state), Path(Uuid::new_v4())).await;
+        assert!(matches!(result, Err(ServerError::NotFound(_))));
+    }
+}
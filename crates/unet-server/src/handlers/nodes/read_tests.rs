@@ -4,8 +4,9 @@
 mod tests {
     use super::super::crud::*;
     use crate::api::ApiResponse;
+    use crate::handlers::ServerError;
     use crate::handlers::nodes::crud_tests::test_utils::*;
-    use crate::handlers::nodes::types::ListNodesQuery;
+    use crate::handlers::nodes::types::{GetNodeQuery, ListNodesQuery};
     use axum::{
         extract::{Path, Query, State},
         response::Json,
@@ -22,7 +23,9 @@ mod tests {
             lifecycle: None,
             role: None,
             vendor: None,
+            management_ip: None,
             include_status: None,
+            fields: None,
         };
 
         let result = list_nodes(State(app_state), Query(query)).await;
@@ -47,7 +50,9 @@ mod tests {
             lifecycle: None,
             role: None,
             vendor: None,
+            management_ip: None,
             include_status: None,
+            fields: None,
         };
 
         let result = list_nodes(State(app_state), Query(query)).await;
@@ -81,7 +86,9 @@ mod tests {
             lifecycle: None,
             role: None,
             vendor: None,
+            management_ip: None,
             include_status: None,
+            fields: None,
         };
 
         let result = list_nodes(State(app_state), Query(query)).await;
@@ -128,7 +135,9 @@ mod tests {
             lifecycle: None,
             role: Some("switch".to_string()),
             vendor: Some("juniper".to_string()),
+            management_ip: None,
             include_status: None,
+            fields: None,
         };
 
         let result = list_nodes(State(app_state), Query(query)).await;
@@ -147,7 +156,12 @@ mod tests {
         let app_state = setup_test_app_state().await;
         let node = create_test_node(&app_state).await;
 
-        let result = get_node(State(app_state), Path(node.id)).await;
+        let result = get_node(
+            State(app_state),
+            Path(node.id),
+            Query(GetNodeQuery::default()),
+        )
+        .await;
 
         assert!(result.is_ok());
         let Json(ApiResponse { data, success, .. }) = result.unwrap();
@@ -163,7 +177,12 @@ mod tests {
         let app_state = setup_test_app_state().await;
         let non_existent_id = Uuid::new_v4();
 
-        let result = get_node(State(app_state), Path(non_existent_id)).await;
+        let result = get_node(
+            State(app_state),
+            Path(non_existent_id),
+            Query(GetNodeQuery::default()),
+        )
+        .await;
 
         assert!(result.is_err());
         let error = result.unwrap_err();
@@ -181,7 +200,9 @@ mod tests {
             lifecycle: None,
             role: None,
             vendor: None,
+            management_ip: None,
             include_status: Some(true),
+            fields: None,
         };
 
         let result = list_nodes(State(app_state), Query(query)).await;
@@ -224,7 +245,9 @@ mod tests {
             lifecycle: Some("live".to_string()),
             role: None,
             vendor: None,
+            management_ip: None,
             include_status: None,
+            fields: None,
         };
 
         let result = list_nodes(State(app_state), Query(query)).await;
@@ -237,4 +260,42 @@ mod tests {
         // Note: Node::new creates nodes with default lifecycle, so we'd need to update them
         // This test would need adjustment based on actual API behavior
     }
+
+    #[tokio::test]
+    async fn test_list_nodes_returns_selected_fields_only() {
+        let app_state = setup_test_app_state().await;
+        let node = create_test_node(&app_state).await;
+
+        let query = ListNodesQuery {
+            page: None,
+            per_page: None,
+            lifecycle: None,
+            role: None,
+            vendor: None,
+            management_ip: None,
+            include_status: Some(true),
+            fields: Some("name,vendor".to_string()),
+        };
+
+        let Json(response) = list_nodes(State(app_state), Query(query)).await.unwrap();
+        let body = serde_json::to_value(&response).unwrap();
+
+        assert_eq!(
+            body["data"]["data"][0],
+            serde_json::json!({"id": node.id, "name": "test-node", "vendor": "cisco"})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_node_rejects_unknown_field() {
+        let app_state = setup_test_app_state().await;
+        let node = create_test_node(&app_state).await;
+        let query = GetNodeQuery {
+            fields: Some("name,owner".to_string()),
+        };
+
+        let result = get_node(State(app_state), Path(node.id), Query(query)).await;
+
+        assert!(matches!(result, Err(ServerError::BadRequest(_))));
+    }
 }
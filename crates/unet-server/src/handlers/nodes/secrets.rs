@@ -0,0 +1,89 @@
+//! Decrypted `custom_data` fields of a node, for the admin role
+
+use axum::{
+    extract::{Path, State},
+    response::Json,
+};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use crate::api::ApiResponse;
+use crate::handlers::{ServerError, ServerResult};
+use crate::server::AppState;
+
+/// Plaintext of a node's encrypted `custom_data` fields
+#[derive(Debug, Serialize)]
+pub struct NodeSecrets {
+    /// Node ID
+    pub node_id: Uuid,
+    /// Decrypted values by dotted `custom_data` path; absent fields are left out
+    pub fields: BTreeMap<String, serde_json::Value>,
+}
+
+/// Get the decrypted values of a node's `secrets.encrypted_fields`
+///
+/// # Errors
+/// Returns an error if the node does not exist, a field cannot be decrypted,
+/// or datastore operations fail.
+pub async fn get_node_secrets(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ServerResult<Json<ApiResponse<NodeSecrets>>> {
+    let node = app_state
+        .datastore
+        .get_node(&id)
+        .await?
+        .ok_or_else(|| ServerError::NotFound(format!("Node with ID {id} not found")))?;
+    let fields = match &app_state.field_encryption {
+        Some(encryption) => encryption.opened(&node.custom_data)?,
+        None => BTreeMap::new(),
+    };
+
+    Ok(Json(ApiResponse::success(NodeSecrets {
+        node_id: id,
+        fields,
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::app_state::tests::create_mock_app_state;
+    use serde_json::json;
+    use std::sync::Arc;
+    use unet_core::models::{DeviceRole, Node, Vendor};
+    use unet_core::secrets::{EncryptedFieldsStore, FieldEncryption, SecretManager};
+
+    #[tokio::test]
+    async fn test_get_node_secrets_decrypts_sealed_fields() {
+        let mut app_state = create_mock_app_state().await;
+        let fields = Arc::new(FieldEncryption::new(
+            SecretManager::new("master").unwrap(),
+            vec!["snmp.community".to_string()],
+        ));
+        app_state.datastore = Arc::new(EncryptedFieldsStore::new(
+            app_state.datastore.clone(),
+            fields.clone(),
+            false,
+        ));
+        app_state.field_encryption = Some(fields);
+
+        let mut node = Node::new(
+            "secrets-edge".to_string(),
+            "example.com".to_string(),
+            Vendor::Cisco,
+            DeviceRole::Router,
+        );
+        node.custom_data = json!({"snmp": {"community": "edge-ro"}});
+        let stored = app_state.datastore.create_node(&node).await.unwrap();
+        assert!(SecretManager::is_encrypted(
+            &stored.custom_data["snmp"]["community"]
+        ));
+
+        let Json(response) = get_node_secrets(State(app_state), Path(node.id))
+            .await
+            .unwrap();
+        assert_eq!(response.data.fields["snmp.community"], "edge-ro");
+    }
+}
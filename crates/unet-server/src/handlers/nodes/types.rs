@@ -15,6 +15,17 @@ pub struct ListNodesQuery {
     pub role: Option<String>,
     /// Filter by vendor
     pub vendor: Option<String>,
+    /// Filter by management IPv4 or IPv6 address
+    pub management_ip: Option<String>,
     /// Include derived state in response
     pub include_status: Option<bool>,
+    /// Comma-separated node fields to return (sparse fieldset)
+    pub fields: Option<String>,
+}
+
+/// Query parameters for fetching a single node
+#[derive(Debug, Default, Deserialize)]
+pub struct GetNodeQuery {
+    /// Comma-separated node fields to return (sparse fieldset)
+    pub fields: Option<String>,
 }
@@ -3,14 +3,21 @@
 #[cfg(test)]
 mod tests {
     use crate::api::{ApiResponse, UpdateNodeRequest};
+    use crate::background::PolicyTrigger;
     use crate::handlers::nodes::crud::*;
     use crate::handlers::nodes::crud_tests::test_utils::*;
     use axum::{
+        Extension,
         extract::{Path, State},
         response::Json,
     };
     use std::net::IpAddr;
+    use std::time::Duration;
+    use tempfile::TempDir;
+    use unet_core::config::PolicyConfig;
+    use unet_core::datastore::DataStore;
     use unet_core::models::{DeviceRole, Lifecycle, Vendor};
+    use unet_core::policy_integration::PolicyService;
     use uuid::Uuid;
 
     #[tokio::test]
@@ -19,7 +26,8 @@ mod tests {
         let node = create_test_node(&app_state).await;
         let request = create_test_update_request();
 
-        let result = update_node(State(app_state.clone()), Path(node.id), Json(request)).await;
+        let result =
+            update_node(State(app_state.clone()), Path(node.id), None, Json(request)).await;
 
         assert!(result.is_ok());
         let Json(ApiResponse { data, success, .. }) = result.unwrap();
@@ -61,10 +69,12 @@ mod tests {
             lifecycle: None,
             location_id: None,
             management_ip: None,
+            management_ipv6: None,
             custom_data: None,
         };
 
-        let result = update_node(State(app_state.clone()), Path(node.id), Json(request)).await;
+        let result =
+            update_node(State(app_state.clone()), Path(node.id), None, Json(request)).await;
 
         assert!(result.is_ok());
         let Json(ApiResponse { data, success, .. }) = result.unwrap();
@@ -83,7 +93,8 @@ mod tests {
         let non_existent_id = Uuid::new_v4();
         let request = create_test_update_request();
 
-        let result = update_node(State(app_state), Path(non_existent_id), Json(request)).await;
+        let result =
+            update_node(State(app_state), Path(non_existent_id), None, Json(request)).await;
 
         assert!(result.is_err());
         let error = result.unwrap_err();
@@ -103,10 +114,11 @@ mod tests {
             lifecycle: None,
             location_id: None,
             management_ip: Some("invalid-ip".to_string()),
+            management_ipv6: None,
             custom_data: None,
         };
 
-        let result = update_node(State(app_state), Path(node.id), Json(request)).await;
+        let result = update_node(State(app_state), Path(node.id), None, Json(request)).await;
 
         assert!(result.is_err());
         let error = result.unwrap_err();
@@ -126,10 +138,12 @@ mod tests {
             lifecycle: None,
             location_id: None,
             management_ip: None,
+            management_ipv6: None,
             custom_data: None,
         };
 
-        let result = update_node(State(app_state.clone()), Path(node.id), Json(request)).await;
+        let result =
+            update_node(State(app_state.clone()), Path(node.id), None, Json(request)).await;
 
         assert!(result.is_ok());
         let Json(ApiResponse { data, success, .. }) = result.unwrap();
@@ -154,10 +168,11 @@ mod tests {
             lifecycle: None,
             location_id: Some(location_id),
             management_ip: None,
+            management_ipv6: None,
             custom_data: None,
         };
 
-        let result = update_node(State(app_state), Path(node.id), Json(request)).await;
+        let result = update_node(State(app_state), Path(node.id), None, Json(request)).await;
 
         // Note: This might fail if location doesn't exist, depending on validation
         if let Ok(Json(ApiResponse { data, success, .. })) = result {
@@ -178,7 +193,50 @@ mod tests {
 
         // In a real test, we'd mock the datastore to fail
         // Here we just verify the happy path works
-        let result = update_node(State(app_state), Path(node.id), Json(request)).await;
+        let result = update_node(State(app_state), Path(node.id), None, Json(request)).await;
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_update_node_triggers_policy_evaluation() {
+        let app_state = setup_test_app_state().await;
+        let node = create_test_node(&app_state).await;
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("vendor.policy"),
+            "WHEN node.vendor == \"juniper\" THEN ASSERT node.model IS \"MX204\"\n",
+        )
+        .unwrap();
+        let policy_trigger = PolicyTrigger::spawn(
+            app_state.datastore.clone(),
+            PolicyService::with_local_dir(temp_dir.path().to_str().unwrap()),
+            &PolicyConfig {
+                evaluate_on_change: true,
+                debounce_ms: 10,
+            },
+        );
+
+        let request = create_test_update_request();
+        update_node(
+            State(app_state.clone()),
+            Path(node.id),
+            Some(Extension(policy_trigger)),
+            Json(request),
+        )
+        .await
+        .unwrap();
+
+        for _ in 0..50 {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            let results = app_state
+                .datastore
+                .get_policy_results(&node.id)
+                .await
+                .unwrap();
+            if !results.is_empty() {
+                return;
+            }
+        }
+        panic!("updated node was not evaluated");
+    }
 }
@@ -18,7 +18,8 @@ mod tests {
         let node = create_test_node(&app_state).await;
         let request = create_test_update_request();
 
-        let result = update_node(State(app_state.clone()), Path(node.id), Json(request)).await;
+        let result =
+            update_node(State(app_state.clone()), Path(node.id), None, Json(request)).await;
 
         assert!(result.is_ok());
         let Json(ApiResponse { data, success, .. }) = result.unwrap();
@@ -47,7 +48,8 @@ mod tests {
         request.name = Some("partially-updated".to_string());
         request.vendor = None; // Don't update vendor
 
-        let result = update_node(State(app_state.clone()), Path(node.id), Json(request)).await;
+        let result =
+            update_node(State(app_state.clone()), Path(node.id), None, Json(request)).await;
 
         assert!(result.is_ok());
         let Json(ApiResponse { data, success, .. }) = result.unwrap();
@@ -62,7 +64,8 @@ mod tests {
         let non_existent_id = Uuid::new_v4();
         let request = create_test_update_request();
 
-        let result = update_node(State(app_state), Path(non_existent_id), Json(request)).await;
+        let result =
+            update_node(State(app_state), Path(non_existent_id), None, Json(request)).await;
 
         assert!(result.is_err());
         let error = result.unwrap_err();
@@ -76,7 +79,7 @@ mod tests {
         let mut request = create_test_update_request();
         request.management_ip = Some("invalid-ip".to_string());
 
-        let result = update_node(State(app_state), Path(node.id), Json(request)).await;
+        let result = update_node(State(app_state), Path(node.id), None, Json(request)).await;
 
         assert!(result.is_err());
         let error = result.unwrap_err();
@@ -95,7 +98,8 @@ mod tests {
         request.name = Some("updated-hostname".to_string());
         request.domain = Some("new-domain.com".to_string());
 
-        let result = update_node(State(app_state.clone()), Path(node.id), Json(request)).await;
+        let result =
+            update_node(State(app_state.clone()), Path(node.id), None, Json(request)).await;
 
         assert!(result.is_ok());
         let Json(ApiResponse { data, success, .. }) = result.unwrap();
@@ -115,7 +119,7 @@ mod tests {
         let mut request = create_test_update_request();
         request.location_id = Some(location.id);
 
-        let result = update_node(State(app_state), Path(node.id), Json(request)).await;
+        let result = update_node(State(app_state), Path(node.id), None, Json(request)).await;
 
         assert!(result.is_ok());
         let Json(ApiResponse { data, success, .. }) = result.unwrap();
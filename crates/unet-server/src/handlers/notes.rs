@@ -0,0 +1,392 @@
+//! Note and attachment handlers
+//!
+//! Notes and attachments are listed and added under the entity they belong
+//! to, e.g. `/api/v1/links/{id}/notes`, and read, edited, or removed by
+//! their own ID, e.g. `/api/v1/notes/{id}`.
+
+use axum::{
+    Extension,
+    body::Bytes,
+    extract::{FromRequestParts, MatchedPath, Path, Query, State},
+    http::{HeaderMap, header, request::Parts},
+    response::{IntoResponse, Json, Response},
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::api::ApiResponse;
+use crate::handlers::{ServerError, ServerResult};
+use crate::server::AppState;
+use unet_core::config::AttachmentsConfig;
+use unet_core::notes::{self, Attachment, Note, NoteTarget};
+
+/// Entity named by a `/api/v1/{nodes|links|locations}/{id}/...` route
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EntityPath(pub NoteTarget);
+
+impl<S: Send + Sync> FromRequestParts<S> for EntityPath {
+    type Rejection = ServerError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Path(id) = Path::<Uuid>::from_request_parts(parts, state)
+            .await
+            .map_err(|e| ServerError::BadRequest(e.body_text()))?;
+        let route = parts
+            .extensions
+            .get::<MatchedPath>()
+            .map(MatchedPath::as_str)
+            .unwrap_or_default();
+        let target = match route.split('/').nth(3) {
+            Some("nodes") => NoteTarget::Node(id),
+            Some("links") => NoteTarget::Link(id),
+            Some("locations") => NoteTarget::Location(id),
+            _ => {
+                return Err(ServerError::Internal(format!(
+                    "Route {route} does not name an entity"
+                )));
+            }
+        };
+        Ok(Self(target))
+    }
+}
+
+/// Request to write a note
+#[derive(Debug, Deserialize)]
+pub struct CreateNoteRequest {
+    /// Markdown text
+    pub body: String,
+    /// Who wrote the note
+    #[serde(default)]
+    pub author: Option<String>,
+}
+
+/// Request to replace the text of a note
+#[derive(Debug, Deserialize)]
+pub struct UpdateNoteRequest {
+    /// Markdown text
+    pub body: String,
+}
+
+/// Query of an attachment upload
+#[derive(Debug, Deserialize)]
+pub struct UploadAttachmentQuery {
+    /// File name to record
+    pub filename: String,
+}
+
+fn attachments_config(config: Option<Extension<AttachmentsConfig>>) -> AttachmentsConfig {
+    config.map(|Extension(config)| config).unwrap_or_default()
+}
+
+/// List the notes on an entity, oldest first
+///
+/// # Errors
+/// Returns an error if stored notes cannot be loaded.
+pub async fn list_entity_notes(
+    State(app_state): State<AppState>,
+    EntityPath(target): EntityPath,
+) -> ServerResult<Json<ApiResponse<Vec<Note>>>> {
+    let notes = notes::list_notes(app_state.datastore.as_ref(), target).await?;
+    Ok(Json(ApiResponse::success(notes)))
+}
+
+/// Write a note on an entity
+///
+/// # Errors
+/// Returns an error if the body is blank or too long, the entity does not
+/// exist, or the datastore write fails.
+pub async fn create_entity_note(
+    State(app_state): State<AppState>,
+    EntityPath(target): EntityPath,
+    Json(request): Json<CreateNoteRequest>,
+) -> ServerResult<Json<ApiResponse<Note>>> {
+    let note = Note::new(target, request.body, request.author)?;
+    notes::add_note(app_state.datastore.as_ref(), &note).await?;
+    Ok(Json(ApiResponse::success(note)))
+}
+
+/// Get a note
+///
+/// # Errors
+/// Returns an error if the note does not exist.
+pub async fn get_note(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ServerResult<Json<ApiResponse<Note>>> {
+    let note = notes::get_note(app_state.datastore.as_ref(), id).await?;
+    Ok(Json(ApiResponse::success(note)))
+}
+
+/// Replace the text of a note
+///
+/// # Errors
+/// Returns an error if the body is blank or too long, the note does not
+/// exist, or the datastore write fails.
+pub async fn update_note(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<UpdateNoteRequest>,
+) -> ServerResult<Json<ApiResponse<Note>>> {
+    let note = notes::update_note(app_state.datastore.as_ref(), id, request.body).await?;
+    Ok(Json(ApiResponse::success(note)))
+}
+
+/// Remove a note
+///
+/// # Errors
+/// Returns an error if the note does not exist.
+pub async fn delete_note(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ServerResult<Json<ApiResponse<()>>> {
+    notes::delete_note(app_state.datastore.as_ref(), id).await?;
+    Ok(Json(ApiResponse::success(())))
+}
+
+/// List the files attached to an entity, oldest first
+///
+/// # Errors
+/// Returns an error if stored attachments cannot be loaded.
+pub async fn list_entity_attachments(
+    State(app_state): State<AppState>,
+    EntityPath(target): EntityPath,
+) -> ServerResult<Json<ApiResponse<Vec<Attachment>>>> {
+    let attachments = notes::list_attachments(app_state.datastore.as_ref(), target).await?;
+    Ok(Json(ApiResponse::success(attachments)))
+}
+
+/// Attach the request body to an entity as a file of the request's
+/// `Content-Type`
+///
+/// # Errors
+/// Returns an error if the request has no `Content-Type`, the file name,
+/// media type, or size is not allowed, the entity does not exist, or the
+/// contents cannot be stored.
+pub async fn upload_attachment(
+    State(app_state): State<AppState>,
+    config: Option<Extension<AttachmentsConfig>>,
+    EntityPath(target): EntityPath,
+    Query(query): Query<UploadAttachmentQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> ServerResult<Json<ApiResponse<Attachment>>> {
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(|| ServerError::BadRequest("Content-Type header is required".to_string()))?;
+    let attachment = notes::add_attachment(
+        app_state.datastore.as_ref(),
+        &attachments_config(config),
+        target,
+        &query.filename,
+        content_type,
+        &body,
+    )
+    .await?;
+    Ok(Json(ApiResponse::success(attachment)))
+}
+
+/// Get an attachment's metadata
+///
+/// # Errors
+/// Returns an error if the attachment does not exist.
+pub async fn get_attachment(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ServerResult<Json<ApiResponse<Attachment>>> {
+    let attachment = notes::get_attachment(app_state.datastore.as_ref(), id).await?;
+    Ok(Json(ApiResponse::success(attachment)))
+}
+
+/// Download an attachment's contents with its media type and file name
+///
+/// # Errors
+/// Returns an error if the attachment or its contents do not exist.
+pub async fn download_attachment(
+    State(app_state): State<AppState>,
+    config: Option<Extension<AttachmentsConfig>>,
+    Path(id): Path<Uuid>,
+) -> ServerResult<Response> {
+    let datastore = app_state.datastore.as_ref();
+    let attachment = notes::get_attachment(datastore, id).await?;
+    let content =
+        notes::attachment_content(datastore, &attachments_config(config), &attachment).await?;
+    let disposition = format!(
+        "attachment; filename=\"{}\"",
+        attachment.filename.replace('"', "")
+    );
+    Ok((
+        [
+            (header::CONTENT_TYPE, attachment.content_type),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        content,
+    )
+        .into_response())
+}
+
+/// Remove an attachment and its contents
+///
+/// # Errors
+/// Returns an error if the attachment does not exist or cannot be removed.
+pub async fn delete_attachment(
+    State(app_state): State<AppState>,
+    config: Option<Extension<AttachmentsConfig>>,
+    Path(id): Path<Uuid>,
+) -> ServerResult<Json<ApiResponse<()>>> {
+    notes::delete_attachment(
+        app_state.datastore.as_ref(),
+        &attachments_config(config),
+        id,
+    )
+    .await?;
+    Ok(Json(ApiResponse::success(())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::app_state::tests::create_mock_app_state;
+    use axum::http::HeaderValue;
+    use unet_core::datastore::DataStoreError;
+    use unet_core::models::{DeviceRole, Node, Vendor};
+
+    async fn create_node(app_state: &AppState) -> NoteTarget {
+        let node = Node::new(
+            "core-01".to_string(),
+            "example.com".to_string(),
+            Vendor::Cisco,
+            DeviceRole::Router,
+        );
+        let node = app_state.datastore.create_node(&node).await.unwrap();
+        NoteTarget::Node(node.id)
+    }
+
+    fn text_headers() -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("text/plain"));
+        headers
+    }
+
+    #[tokio::test]
+    async fn test_create_note_then_list_and_update() {
+        let app_state = create_mock_app_state().await;
+        let target = create_node(&app_state).await;
+
+        let Json(created) = create_entity_note(
+            State(app_state.clone()),
+            EntityPath(target),
+            Json(CreateNoteRequest {
+                body: "RMA open for **PSU 2**".to_string(),
+                author: Some("noc".to_string()),
+            }),
+        )
+        .await
+        .unwrap();
+        let Json(updated) = update_note(
+            State(app_state.clone()),
+            Path(created.data.id),
+            Json(UpdateNoteRequest {
+                body: "PSU 2 replaced".to_string(),
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(updated.data.author.as_deref(), Some("noc"));
+
+        let Json(listed) = list_entity_notes(State(app_state), EntityPath(target))
+            .await
+            .unwrap();
+        assert_eq!(listed.data, [updated.data]);
+    }
+
+    #[tokio::test]
+    async fn test_create_note_for_missing_entity() {
+        let app_state = create_mock_app_state().await;
+
+        let result = create_entity_note(
+            State(app_state),
+            EntityPath(NoteTarget::Link(Uuid::new_v4())),
+            Json(CreateNoteRequest {
+                body: "Circuit ID 42".to_string(),
+                author: None,
+            }),
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(ServerError::DataStore(DataStoreError::NotFound { .. }))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_upload_then_download_attachment() {
+        let app_state = create_mock_app_state().await;
+        let target = create_node(&app_state).await;
+
+        let Json(uploaded) = upload_attachment(
+            State(app_state.clone()),
+            None,
+            EntityPath(target),
+            Query(UploadAttachmentQuery {
+                filename: "handover.txt".to_string(),
+            }),
+            text_headers(),
+            Bytes::from_static(b"circuit 42"),
+        )
+        .await
+        .unwrap();
+
+        let response = download_attachment(State(app_state.clone()), None, Path(uploaded.data.id))
+            .await
+            .unwrap();
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE),
+            Some(&HeaderValue::from_static("text/plain"))
+        );
+        assert_eq!(
+            response.headers().get(header::CONTENT_DISPOSITION),
+            Some(&HeaderValue::from_static(
+                "attachment; filename=\"handover.txt\""
+            ))
+        );
+
+        delete_attachment(State(app_state.clone()), None, Path(uploaded.data.id))
+            .await
+            .unwrap();
+        let Json(listed) = list_entity_attachments(State(app_state), EntityPath(target))
+            .await
+            .unwrap();
+        assert!(listed.data.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_upload_attachment_over_limit() {
+        let app_state = create_mock_app_state().await;
+        let target = create_node(&app_state).await;
+        let config = AttachmentsConfig {
+            max_size: 4,
+            ..AttachmentsConfig::default()
+        };
+
+        let result = upload_attachment(
+            State(app_state),
+            Some(Extension(config)),
+            EntityPath(target),
+            Query(UploadAttachmentQuery {
+                filename: "handover.txt".to_string(),
+            }),
+            text_headers(),
+            Bytes::from_static(b"circuit 42"),
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(ServerError::DataStore(
+                DataStoreError::ValidationError { .. }
+            ))
+        ));
+    }
+}
@@ -0,0 +1,188 @@
+//! SNMP OID profile and assignment handlers
+
+use axum::{
+    extract::{Path, State},
+    response::Json,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::api::ApiResponse;
+use crate::handlers::{ServerError, ServerResult};
+use crate::server::AppState;
+use unet_core::snmp::profiles::{
+    AssignmentScope, OidProfile, OidProfileAssignment, ResolvedOidProfile, assign_profile,
+    delete_profile, load_catalog, resolve_node_profile, save_profile, unassign_profile,
+};
+
+/// Request body for creating an assignment
+#[derive(Debug, Deserialize)]
+pub struct AssignProfileRequest {
+    /// Assignment scope
+    pub scope: AssignmentScope,
+    /// Node ID, vendor name, or role name
+    pub target: String,
+    /// Profile name
+    pub profile: String,
+}
+
+/// List built-in and stored OID profiles
+///
+/// # Errors
+/// Returns an error if stored profiles cannot be loaded.
+pub async fn list_oid_profiles(
+    State(app_state): State<AppState>,
+) -> ServerResult<Json<ApiResponse<Vec<OidProfile>>>> {
+    let catalog = load_catalog(app_state.datastore.as_ref()).await?;
+    Ok(Json(ApiResponse::success(
+        catalog.profiles().cloned().collect(),
+    )))
+}
+
+/// Get a profile with inheritance applied
+///
+/// # Errors
+/// Returns an error if the profile does not exist or cannot be resolved.
+pub async fn get_oid_profile(
+    State(app_state): State<AppState>,
+    Path(name): Path<String>,
+) -> ServerResult<Json<ApiResponse<ResolvedOidProfile>>> {
+    let catalog = load_catalog(app_state.datastore.as_ref()).await?;
+    Ok(Json(ApiResponse::success(catalog.resolve(&name)?)))
+}
+
+/// Create or replace a profile
+///
+/// # Errors
+/// Returns an error if the body name does not match the path or the profile is invalid.
+pub async fn put_oid_profile(
+    State(app_state): State<AppState>,
+    Path(name): Path<String>,
+    Json(profile): Json<OidProfile>,
+) -> ServerResult<Json<ApiResponse<OidProfile>>> {
+    if profile.name != name {
+        return Err(ServerError::BadRequest(format!(
+            "Profile name {} does not match path {name}",
+            profile.name
+        )));
+    }
+    save_profile(app_state.datastore.as_ref(), &profile).await?;
+    Ok(Json(ApiResponse::success(profile)))
+}
+
+/// Delete a stored profile
+///
+/// # Errors
+/// Returns an error if the profile is built in, still referenced, or missing.
+pub async fn delete_oid_profile(
+    State(app_state): State<AppState>,
+    Path(name): Path<String>,
+) -> ServerResult<Json<ApiResponse<()>>> {
+    delete_profile(app_state.datastore.as_ref(), &name).await?;
+    Ok(Json(ApiResponse::success(())))
+}
+
+/// List profile assignments
+///
+/// # Errors
+/// Returns an error if stored assignments cannot be loaded.
+pub async fn list_oid_profile_assignments(
+    State(app_state): State<AppState>,
+) -> ServerResult<Json<ApiResponse<Vec<OidProfileAssignment>>>> {
+    let catalog = load_catalog(app_state.datastore.as_ref()).await?;
+    Ok(Json(ApiResponse::success(
+        catalog.assignments().into_iter().cloned().collect(),
+    )))
+}
+
+/// Assign a profile to a node, node group, vendor, or role
+///
+/// # Errors
+/// Returns an error if the target is invalid or the profile does not exist.
+pub async fn create_oid_profile_assignment(
+    State(app_state): State<AppState>,
+    Json(request): Json<AssignProfileRequest>,
+) -> ServerResult<Json<ApiResponse<OidProfileAssignment>>> {
+    let assignment = OidProfileAssignment::new(request.scope, &request.target, request.profile)?;
+    assign_profile(app_state.datastore.as_ref(), &assignment).await?;
+    Ok(Json(ApiResponse::success(assignment)))
+}
+
+/// Remove a profile assignment
+///
+/// # Errors
+/// Returns an error if the target is invalid or no assignment exists.
+pub async fn delete_oid_profile_assignment(
+    State(app_state): State<AppState>,
+    Path((scope, target)): Path<(AssignmentScope, String)>,
+) -> ServerResult<Json<ApiResponse<()>>> {
+    unassign_profile(app_state.datastore.as_ref(), scope, &target).await?;
+    Ok(Json(ApiResponse::success(())))
+}
+
+/// Get the profile a node resolves to, with policy adjustments applied
+///
+/// # Errors
+/// Returns an error if the node does not exist or its profile cannot be resolved.
+pub async fn get_node_oid_profile(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ServerResult<Json<ApiResponse<ResolvedOidProfile>>> {
+    let node = app_state.datastore.get_node_required(&id).await?;
+    let resolved = resolve_node_profile(app_state.datastore.as_ref(), &node).await?;
+    Ok(Json(ApiResponse::success(resolved)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::app_state::tests::create_mock_app_state;
+
+    #[tokio::test]
+    async fn test_list_oid_profiles_includes_builtins() {
+        let app_state = create_mock_app_state().await;
+
+        let Json(response) = list_oid_profiles(State(app_state)).await.unwrap();
+
+        assert!(response.data.iter().any(|p| p.name == "router-core"));
+    }
+
+    #[tokio::test]
+    async fn test_get_unknown_oid_profile_is_not_found() {
+        let app_state = create_mock_app_state().await;
+
+        let result = get_oid_profile(State(app_state), Path("missing".to_string())).await;
+
+        assert!(matches!(result, Err(ServerError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_put_oid_profile_rejects_name_mismatch() {
+        let app_state = create_mock_app_state().await;
+        let profile = OidProfile {
+            name: "edge".to_string(),
+            description: None,
+            parent: None,
+            groups: Vec::new(),
+        };
+
+        let result =
+            put_oid_profile(State(app_state), Path("other".to_string()), Json(profile)).await;
+
+        assert!(matches!(result, Err(ServerError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_assignment_rejects_invalid_target() {
+        let app_state = create_mock_app_state().await;
+        let request = AssignProfileRequest {
+            scope: AssignmentScope::Node,
+            target: "not-a-uuid".to_string(),
+            profile: "base".to_string(),
+        };
+
+        let result = create_oid_profile_assignment(State(app_state), Json(request)).await;
+
+        assert!(matches!(result, Err(ServerError::BadRequest(_))));
+    }
+}
@@ -0,0 +1,300 @@
+//! Policy evaluation batch handlers
+//!
+//! Batches are evaluated by the shared `PolicyOrchestrator` in a background
+//! task; the trigger endpoint returns the pending run, which can be polled
+//! for progress and results. Nodes whose policy-relevant state is unchanged
+//! since a cached evaluation are skipped unless the run is forced.
+
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+};
+use std::collections::HashMap;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use super::types::TriggerBatchQuery;
+use crate::api::ApiResponse;
+use crate::handlers::{ServerError, ServerResult};
+use crate::server::AppState;
+use unet_core::policy::{OrchestrationRule, PolicyError};
+use unet_core::policy_integration::batches::{
+    BatchDefinition, BatchRun, delete_batch_definition, get_batch_definition,
+    list_batch_definitions, save_batch_definition,
+};
+use unet_core::prelude::PolicyService;
+
+/// List stored batch definitions
+///
+/// # Errors
+/// Returns an error if stored definitions cannot be loaded.
+pub async fn list_policy_batches(
+    State(app_state): State<AppState>,
+) -> ServerResult<Json<ApiResponse<Vec<BatchDefinition>>>> {
+    let batches = list_batch_definitions(app_state.datastore.as_ref()).await?;
+    Ok(Json(ApiResponse::success(batches)))
+}
+
+/// Get a batch definition
+///
+/// # Errors
+/// Returns an error if the batch does not exist.
+pub async fn get_policy_batch(
+    State(app_state): State<AppState>,
+    Path(name): Path<String>,
+) -> ServerResult<Json<ApiResponse<BatchDefinition>>> {
+    let batch = load_definition(&app_state, &name).await?;
+    Ok(Json(ApiResponse::success(batch)))
+}
+
+/// Create or replace a batch definition
+///
+/// # Errors
+/// Returns an error if the body name does not match the path or the definition is invalid.
+pub async fn put_policy_batch(
+    State(app_state): State<AppState>,
+    Path(name): Path<String>,
+    Json(batch): Json<BatchDefinition>,
+) -> ServerResult<Json<ApiResponse<BatchDefinition>>> {
+    if batch.name != name {
+        return Err(ServerError::BadRequest(format!(
+            "Batch name {} does not match path {name}",
+            batch.name
+        )));
+    }
+    save_batch_definition(app_state.datastore.as_ref(), &batch).await?;
+    Ok(Json(ApiResponse::success(batch)))
+}
+
+/// Delete a batch definition
+///
+/// # Errors
+/// Returns an error if the batch does not exist.
+pub async fn delete_policy_batch(
+    State(app_state): State<AppState>,
+    Path(name): Path<String>,
+) -> ServerResult<Json<ApiResponse<()>>> {
+    delete_batch_definition(app_state.datastore.as_ref(), &name).await?;
+    Ok(Json(ApiResponse::success(())))
+}
+
+/// Start a run of a batch in the background
+///
+/// With `?force=true` every selected node is re-evaluated, ignoring cached results.
+///
+/// # Errors
+/// Returns an error if the batch does not exist or policies cannot be loaded.
+pub async fn trigger_policy_batch(
+    State(app_state): State<AppState>,
+    Path(name): Path<String>,
+    Query(query): Query<TriggerBatchQuery>,
+) -> ServerResult<Json<ApiResponse<BatchRun>>> {
+    let definition = load_definition(&app_state, &name).await?;
+    let mut policy_service = app_state.policy_service.clone();
+    let rules = load_rules(&mut policy_service).await?;
+
+    let run = policy_service
+        .batch_runs()
+        .start(&definition.name, query.force);
+    info!(
+        "Starting policy batch {} as run {} with {} loaded rules",
+        definition.name,
+        run.id,
+        rules.len()
+    );
+
+    let run_id = run.id;
+    let datastore = app_state.datastore.clone();
+    tokio::spawn(async move {
+        policy_service
+            .run_batch(run_id, &definition, &rules, datastore.as_ref())
+            .await;
+    });
+
+    Ok(Json(ApiResponse::success(run)))
+}
+
+/// List retained batch runs, newest first
+///
+/// # Errors
+/// This handler does not fail; the signature matches the other handlers.
+pub async fn list_policy_batch_runs(
+    State(app_state): State<AppState>,
+) -> ServerResult<Json<ApiResponse<Vec<BatchRun>>>> {
+    Ok(Json(ApiResponse::success(
+        app_state.policy_service.batch_runs().list(),
+    )))
+}
+
+/// Get the progress and results of a batch run
+///
+/// # Errors
+/// Returns an error if the run is unknown or no longer retained.
+pub async fn get_policy_batch_run(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ServerResult<Json<ApiResponse<BatchRun>>> {
+    let run = app_state
+        .policy_service
+        .batch_runs()
+        .get(&id)
+        .ok_or_else(|| ServerError::NotFound(format!("Batch run {id} not found")))?;
+    Ok(Json(ApiResponse::success(run)))
+}
+
+/// Get orchestrator cache statistics
+///
+/// # Errors
+/// This handler does not fail; the signature matches the other handlers.
+pub async fn get_orchestrator_cache_stats(
+    State(app_state): State<AppState>,
+) -> ServerResult<Json<ApiResponse<HashMap<String, usize>>>> {
+    let stats = app_state
+        .policy_service
+        .orchestrator()
+        .lock()
+        .await
+        .cache_stats();
+    Ok(Json(ApiResponse::success(stats)))
+}
+
+async fn load_definition(app_state: &AppState, name: &str) -> ServerResult<BatchDefinition> {
+    get_batch_definition(app_state.datastore.as_ref(), name)
+        .await?
+        .ok_or_else(|| ServerError::NotFound(format!("Policy batch {name} not found")))
+}
+
+pub(super) async fn load_rules(
+    policy_service: &mut PolicyService,
+) -> ServerResult<Vec<OrchestrationRule>> {
+    match policy_service.load_orchestration_rules_async().await {
+        Ok(rules) => Ok(rules),
+        // Without a policies source there are no rules to run
+        Err(PolicyError::NoSource) => Ok(Vec::new()),
+        Err(e) => {
+            error!("Failed to load policies: {}", e);
+            Err(ServerError::Internal(format!(
+                "Failed to load policies: {e}"
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::app_state::tests::create_mock_app_state;
+    use unet_core::policy::PolicyPriority;
+    use unet_core::policy_integration::batches::{BatchRunState, NodeFilter};
+
+    fn batch(name: &str) -> BatchDefinition {
+        BatchDefinition {
+            name: name.to_string(),
+            node_filter: NodeFilter::default(),
+            rule_tags: vec!["security".to_string()],
+            priority: PolicyPriority::High,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_put_and_get_policy_batch() {
+        let app_state = create_mock_app_state().await;
+
+        put_policy_batch(
+            State(app_state.clone()),
+            Path("nightly".to_string()),
+            Json(batch("nightly")),
+        )
+        .await
+        .unwrap();
+        let Json(response) =
+            get_policy_batch(State(app_state.clone()), Path("nightly".to_string()))
+                .await
+                .unwrap();
+        let Json(listed) = list_policy_batches(State(app_state)).await.unwrap();
+
+        assert_eq!(response.data, batch("nightly"));
+        assert!(listed.data.iter().any(|b| b.name == "nightly"));
+    }
+
+    #[tokio::test]
+    async fn test_put_policy_batch_rejects_name_mismatch() {
+        let app_state = create_mock_app_state().await;
+
+        let result = put_policy_batch(
+            State(app_state),
+            Path("other".to_string()),
+            Json(batch("nightly")),
+        )
+        .await;
+
+        assert!(matches!(result, Err(ServerError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_trigger_unknown_policy_batch_is_not_found() {
+        let app_state = create_mock_app_state().await;
+
+        let result = trigger_policy_batch(
+            State(app_state),
+            Path("missing".to_string()),
+            Query(TriggerBatchQuery::default()),
+        )
+        .await;
+
+        assert!(matches!(result, Err(ServerError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_trigger_policy_batch_registers_run() {
+        let app_state = create_mock_app_state().await;
+        put_policy_batch(
+            State(app_state.clone()),
+            Path("nightly".to_string()),
+            Json(batch("nightly")),
+        )
+        .await
+        .unwrap();
+
+        let Json(response) = trigger_policy_batch(
+            State(app_state.clone()),
+            Path("nightly".to_string()),
+            Query(TriggerBatchQuery { force: true }),
+        )
+        .await
+        .unwrap();
+        let run = response.data;
+        assert_eq!(run.state, BatchRunState::Pending);
+        assert!(run.forced);
+
+        let Json(fetched) = get_policy_batch_run(State(app_state.clone()), Path(run.id))
+            .await
+            .unwrap();
+        assert_eq!(fetched.data.batch, "nightly");
+
+        let Json(runs) = list_policy_batch_runs(State(app_state)).await.unwrap();
+        assert_eq!(runs.data.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_get_unknown_policy_batch_run_is_not_found() {
+        let app_state = create_mock_app_state().await;
+
+        let result = get_policy_batch_run(State(app_state), Path(Uuid::new_v4())).await;
+
+        assert!(matches!(result, Err(ServerError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_get_orchestrator_cache_stats() {
+        let app_state = create_mock_app_state().await;
+
+        let Json(response) = get_orchestrator_cache_stats(State(app_state))
+            .await
+            .unwrap();
+
+        assert_eq!(response.data.get("total_entries"), Some(&0));
+        assert_eq!(response.data.get("hits"), Some(&0));
+        assert_eq!(response.data.get("forced_evaluations"), Some(&0));
+    }
+}
@@ -0,0 +1,186 @@
+//! Policy canary handlers
+//!
+//! A canary limits the rules with a given tag to a subset of nodes during
+//! evaluation. The report endpoint evaluates those rules on every node
+//! without storing results and compares the canary nodes with the rest of
+//! the fleet.
+
+use axum::{
+    extract::{Path, State},
+    response::Json,
+};
+use tracing::info;
+
+use super::batches::load_rules;
+use crate::api::ApiResponse;
+use crate::handlers::{ServerError, ServerResult};
+use crate::server::AppState;
+use unet_core::policy_integration::canaries::{
+    CanaryDefinition, CanaryReport, delete_canary, get_canary, list_canaries, save_canary,
+};
+
+/// List stored canary definitions
+///
+/// # Errors
+/// Returns an error if stored definitions cannot be loaded.
+pub async fn list_policy_canaries(
+    State(app_state): State<AppState>,
+) -> ServerResult<Json<ApiResponse<Vec<CanaryDefinition>>>> {
+    let canaries = list_canaries(app_state.datastore.as_ref()).await?;
+    Ok(Json(ApiResponse::success(canaries)))
+}
+
+/// Get the canary definition for a rule tag
+///
+/// # Errors
+/// Returns an error if no canary exists for the rule tag.
+pub async fn get_policy_canary(
+    State(app_state): State<AppState>,
+    Path(rule): Path<String>,
+) -> ServerResult<Json<ApiResponse<CanaryDefinition>>> {
+    let canary = load_canary(&app_state, &rule).await?;
+    Ok(Json(ApiResponse::success(canary)))
+}
+
+/// Create or replace the canary definition for a rule tag
+///
+/// # Errors
+/// Returns an error if the body rule does not match the path or the definition is invalid.
+pub async fn put_policy_canary(
+    State(app_state): State<AppState>,
+    Path(rule): Path<String>,
+    Json(canary): Json<CanaryDefinition>,
+) -> ServerResult<Json<ApiResponse<CanaryDefinition>>> {
+    if canary.rule != rule {
+        return Err(ServerError::BadRequest(format!(
+            "Canary rule {} does not match path {rule}",
+            canary.rule
+        )));
+    }
+    save_canary(app_state.datastore.as_ref(), &canary).await?;
+    info!("Rules tagged {} are now under canary", canary.rule);
+    Ok(Json(ApiResponse::success(canary)))
+}
+
+/// Delete a canary definition, enforcing its rules on every node
+///
+/// # Errors
+/// Returns an error if no canary exists for the rule tag.
+pub async fn delete_policy_canary(
+    State(app_state): State<AppState>,
+    Path(rule): Path<String>,
+) -> ServerResult<Json<ApiResponse<()>>> {
+    delete_canary(app_state.datastore.as_ref(), &rule).await?;
+    info!("Rules tagged {rule} are now enforced on every node");
+    Ok(Json(ApiResponse::success(())))
+}
+
+/// Compare the canary nodes with the rest of the fleet
+///
+/// # Errors
+/// Returns an error if the canary does not exist or evaluation fails.
+pub async fn get_policy_canary_report(
+    State(app_state): State<AppState>,
+    Path(rule): Path<String>,
+) -> ServerResult<Json<ApiResponse<CanaryReport>>> {
+    let canary = load_canary(&app_state, &rule).await?;
+    let mut policy_service = app_state.policy_service.clone();
+    let rules = load_rules(&mut policy_service).await?;
+    let report = policy_service
+        .canary_report(&canary, &rules, app_state.datastore.as_ref())
+        .await
+        .map_err(|e| ServerError::Internal(format!("Failed to evaluate canary {rule}: {e}")))?;
+    Ok(Json(ApiResponse::success(report)))
+}
+
+async fn load_canary(app_state: &AppState, rule: &str) -> ServerResult<CanaryDefinition> {
+    get_canary(app_state.datastore.as_ref(), rule)
+        .await?
+        .ok_or_else(|| ServerError::NotFound(format!("Policy canary {rule} not found")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::app_state::tests::create_mock_app_state;
+
+    fn canary(rule: &str) -> CanaryDefinition {
+        CanaryDefinition {
+            rule: rule.to_string(),
+            percent: Some(10),
+            node_tag: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_put_get_and_delete_policy_canary() {
+        let app_state = create_mock_app_state().await;
+
+        put_policy_canary(
+            State(app_state.clone()),
+            Path("upgrade".to_string()),
+            Json(canary("upgrade")),
+        )
+        .await
+        .unwrap();
+        let Json(response) =
+            get_policy_canary(State(app_state.clone()), Path("upgrade".to_string()))
+                .await
+                .unwrap();
+        assert_eq!(response.data, canary("upgrade"));
+
+        delete_policy_canary(State(app_state.clone()), Path("upgrade".to_string()))
+            .await
+            .unwrap();
+        let Json(listed) = list_policy_canaries(State(app_state)).await.unwrap();
+        assert!(listed.data.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_put_policy_canary_rejects_invalid_definitions() {
+        let app_state = create_mock_app_state().await;
+
+        let mismatch = put_policy_canary(
+            State(app_state.clone()),
+            Path("other".to_string()),
+            Json(canary("upgrade")),
+        )
+        .await;
+        assert!(matches!(mismatch, Err(ServerError::BadRequest(_))));
+
+        let mut invalid = canary("upgrade");
+        invalid.percent = Some(150);
+        let result =
+            put_policy_canary(State(app_state), Path("upgrade".to_string()), Json(invalid)).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_report_for_canary_without_policies_is_empty() {
+        let app_state = create_mock_app_state().await;
+        put_policy_canary(
+            State(app_state.clone()),
+            Path("upgrade".to_string()),
+            Json(canary("upgrade")),
+        )
+        .await
+        .unwrap();
+
+        let Json(response) =
+            get_policy_canary_report(State(app_state), Path("upgrade".to_string()))
+                .await
+                .unwrap();
+
+        assert_eq!(response.data.rules_evaluated, 0);
+        assert_eq!(response.data.canary.nodes, 0);
+    }
+
+    #[tokio::test]
+    async fn test_report_for_unknown_canary_is_not_found() {
+        let app_state = create_mock_app_state().await;
+
+        let result = get_policy_canary_report(State(app_state), Path("missing".to_string())).await;
+
+        assert!(matches!(result, Err(ServerError::NotFound(_))));
+    }
+}
@@ -0,0 +1,137 @@
+//! Policy exemption handlers
+//!
+//! An exemption waives a rule for every node at a location and the locations
+//! below it until it expires. Exempted compliance failures are still stored,
+//! with the status `exempted`.
+
+use axum::{
+    extract::{Path, State},
+    response::Json,
+};
+use tracing::info;
+use uuid::Uuid;
+
+use crate::api::ApiResponse;
+use crate::handlers::{ServerError, ServerResult};
+use crate::server::AppState;
+use unet_core::policy_integration::exemptions::{
+    PolicyExemption, delete_exemption, list_exemptions, save_exemption,
+};
+
+/// List stored exemptions, including expired ones
+///
+/// # Errors
+/// Returns an error if stored exemptions cannot be loaded.
+pub async fn list_policy_exemptions(
+    State(app_state): State<AppState>,
+) -> ServerResult<Json<ApiResponse<Vec<PolicyExemption>>>> {
+    let exemptions = list_exemptions(app_state.datastore.as_ref()).await?;
+    Ok(Json(ApiResponse::success(exemptions)))
+}
+
+/// Create or replace the exemption of a location from a rule
+///
+/// # Errors
+/// Returns an error if the body does not match the path, the exemption is
+/// invalid or expired, or the location does not exist.
+pub async fn put_policy_exemption(
+    State(app_state): State<AppState>,
+    Path((location_id, rule)): Path<(Uuid, String)>,
+    Json(exemption): Json<PolicyExemption>,
+) -> ServerResult<Json<ApiResponse<PolicyExemption>>> {
+    if exemption.location_id != location_id || exemption.rule != rule {
+        return Err(ServerError::BadRequest(format!(
+            "Exemption {} does not match path {location_id}/{rule}",
+            exemption.key()
+        )));
+    }
+    save_exemption(app_state.datastore.as_ref(), &exemption).await?;
+    info!(
+        "Location {location_id} is exempt from {rule} until {}",
+        exemption.expires_at
+    );
+    Ok(Json(ApiResponse::success(exemption)))
+}
+
+/// Delete the exemption of a location from a rule
+///
+/// # Errors
+/// Returns an error if the location has no exemption from the rule.
+pub async fn delete_policy_exemption(
+    State(app_state): State<AppState>,
+    Path((location_id, rule)): Path<(Uuid, String)>,
+) -> ServerResult<Json<ApiResponse<()>>> {
+    delete_exemption(app_state.datastore.as_ref(), location_id, &rule).await?;
+    info!("Location {location_id} is no longer exempt from {rule}");
+    Ok(Json(ApiResponse::success(())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::app_state::tests::create_mock_app_state;
+    use chrono::{Duration, Utc};
+    use unet_core::models::Location;
+
+    fn exemption(location_id: Uuid, rule: &str) -> PolicyExemption {
+        PolicyExemption {
+            location_id,
+            rule: rule.to_string(),
+            reason: "maintenance window".to_string(),
+            expires_at: Utc::now() + Duration::days(7),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_put_list_and_delete_policy_exemption() {
+        let app_state = create_mock_app_state().await;
+        let site = Location::new_root("exemption-site".to_string(), "site".to_string());
+        app_state.datastore.create_location(&site).await.unwrap();
+        let path = || Path((site.id, "ntp-servers".to_string()));
+
+        put_policy_exemption(
+            State(app_state.clone()),
+            path(),
+            Json(exemption(site.id, "ntp-servers")),
+        )
+        .await
+        .unwrap();
+        let Json(listed) = list_policy_exemptions(State(app_state.clone()))
+            .await
+            .unwrap();
+        assert!(
+            listed
+                .data
+                .iter()
+                .any(|stored| stored.location_id == site.id && stored.rule == "ntp-servers")
+        );
+
+        delete_policy_exemption(State(app_state.clone()), path())
+            .await
+            .unwrap();
+        let result = delete_policy_exemption(State(app_state), path()).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_put_policy_exemption_rejects_invalid_exemptions() {
+        let app_state = create_mock_app_state().await;
+        let location_id = Uuid::new_v4();
+
+        let mismatch = put_policy_exemption(
+            State(app_state.clone()),
+            Path((location_id, "other".to_string())),
+            Json(exemption(location_id, "ntp-servers")),
+        )
+        .await;
+        assert!(matches!(mismatch, Err(ServerError::BadRequest(_))));
+
+        let unknown_location = put_policy_exemption(
+            State(app_state),
+            Path((location_id, "ntp-servers".to_string())),
+            Json(exemption(location_id, "ntp-servers")),
+        )
+        .await;
+        assert!(unknown_location.is_err());
+    }
+}
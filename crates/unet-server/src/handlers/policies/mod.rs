@@ -3,12 +3,24 @@
 //! This module provides HTTP handlers for policy evaluation, validation,
 //! and status endpoints in the μNet server.
 
+pub use batches::{
+    delete_policy_batch, get_orchestrator_cache_stats, get_policy_batch, get_policy_batch_run,
+    list_policy_batch_runs, list_policy_batches, put_policy_batch, trigger_policy_batch,
+};
+pub use canaries::{
+    delete_policy_canary, get_policy_canary, get_policy_canary_report, list_policy_canaries,
+    put_policy_canary,
+};
+pub use exemptions::{delete_policy_exemption, list_policy_exemptions, put_policy_exemption};
 pub use response_handling::evaluate_policies;
 pub use results::get_policy_results;
 pub use status::get_policy_status;
 pub use validation::validate_policies;
 
+mod batches;
+mod canaries;
 mod evaluation;
+mod exemptions;
 mod handlers;
 mod node_fetching;
 mod policy_execution;
@@ -4,9 +4,11 @@ use std::collections::HashMap;
 use tracing::{error, warn};
 use unet_core::policy::PolicyExecutionResult;
 use unet_core::prelude::{DataStore, Node, PolicyService};
+use unet_core::webhooks::WebhookEvent;
 use uuid::Uuid;
 
 use crate::handlers::policies::types::PolicyEvaluationSummary;
+use crate::handlers::webhooks::announce;
 
 /// Process evaluation results for a single node
 pub async fn process_node_evaluation(
@@ -42,6 +44,9 @@ pub async fn process_node_evaluation(
                 {
                     warn!("Failed to store policy results for node {}: {}", node.id, e);
                 }
+                if let Some(event) = WebhookEvent::policy_failed(node, &results) {
+                    announce(datastore, &event).await;
+                }
             }
 
             all_results.insert(node.id, results);
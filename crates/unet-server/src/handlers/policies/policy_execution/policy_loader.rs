@@ -2,7 +2,7 @@
 
 use crate::error::ServerError;
 use tracing::error;
-use unet_core::policy::PolicyRule;
+use unet_core::policy::{PolicyError, PolicyRule};
 use unet_core::prelude::PolicyService;
 
 use crate::handlers::policies::types::PolicyEvaluationRequest;
@@ -16,17 +16,13 @@ pub fn load_policies_for_request(
         || {
             match policy_service.load_policies() {
                 Ok(policies) => Ok(policies),
+                // If no policies source is configured, return empty list instead of error
+                Err(PolicyError::NoSource) => Ok(vec![]),
                 Err(e) => {
-                    let error_msg = e.to_string();
-                    // If no policies source is configured, return empty list instead of error
-                    if error_msg.contains("No policies source configured") {
-                        Ok(vec![])
-                    } else {
-                        error!("Failed to load policies: {}", e);
-                        Err(ServerError::Internal(format!(
-                            "Failed to load policies: {e}"
-                        )))
-                    }
+                    error!("Failed to load policies: {}", e);
+                    Err(ServerError::Internal(format!(
+                        "Failed to load policies: {e}"
+                    )))
                 }
             }
         },
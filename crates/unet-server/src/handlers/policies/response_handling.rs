@@ -95,15 +95,17 @@ mod tests {
     use std::fs;
     use std::sync::Arc;
     use tempfile::TempDir;
+    use test_support::sqlite::sqlite_store;
     use unet_core::{
         datastore::{DataStore, sqlite::SqliteStore},
         models::*,
         policy::{Action, ComparisonOperator, Condition, FieldRef, PolicyRule, Value},
         policy_integration::PolicyService,
     };
-    use test_support::sqlite::sqlite_store;
 
-    async fn setup_test_datastore() -> SqliteStore { sqlite_store().await }
+    async fn setup_test_datastore() -> SqliteStore {
+        sqlite_store().await
+    }
 
     async fn create_test_node(datastore: &SqliteStore) -> Node {
         let mut node = Node::new(
@@ -143,6 +145,10 @@ mod tests {
         let app_state = AppState {
             datastore: Arc::new(datastore),
             policy_service: PolicyService::with_local_dir(temp_dir.path().to_str().unwrap()),
+            enrichment: unet_core::enrichment::EnrichmentPipeline::default(),
+            field_encryption: None,
+            topology: Arc::new(unet_core::topology::TopologyCache::default()),
+            indexed_search: false,
         };
 
         let request = PolicyEvaluationRequest {
@@ -172,6 +178,10 @@ WHEN node.vendor == "cisco" THEN ASSERT node.version IS "15.1"
             let app_state = AppState {
                 datastore: Arc::new(store),
                 policy_service: PolicyService::with_local_dir(temp_dir.path().to_str().unwrap()),
+                enrichment: unet_core::enrichment::EnrichmentPipeline::default(),
+                field_encryption: None,
+                topology: Arc::new(unet_core::topology::TopologyCache::default()),
+                indexed_search: false,
             };
 
             let request = PolicyEvaluationRequest {
@@ -185,7 +195,8 @@ WHEN node.vendor == "cisco" THEN ASSERT node.version IS "15.1"
 
             let response = result.unwrap().0;
             assert_eq!(response.nodes_evaluated, 1);
-        }).await;
+        })
+        .await;
     }
 
     #[tokio::test]
@@ -196,6 +207,10 @@ WHEN node.vendor == "cisco" THEN ASSERT node.version IS "15.1"
         let app_state = AppState {
             datastore: Arc::new(datastore),
             policy_service: PolicyService::with_local_dir(temp_dir.path().to_str().unwrap()),
+            enrichment: unet_core::enrichment::EnrichmentPipeline::default(),
+            field_encryption: None,
+            topology: Arc::new(unet_core::topology::TopologyCache::default()),
+            indexed_search: false,
         };
 
         let policies = vec![create_test_policy_rule()];
@@ -226,6 +241,10 @@ WHEN node.vendor == "cisco" THEN ASSERT node.version IS "15.1"
         let app_state = AppState {
             datastore: Arc::new(datastore),
             policy_service: PolicyService::with_local_dir(temp_dir.path().to_str().unwrap()),
+            enrichment: unet_core::enrichment::EnrichmentPipeline::default(),
+            field_encryption: None,
+            topology: Arc::new(unet_core::topology::TopologyCache::default()),
+            indexed_search: false,
         };
 
         let request = PolicyEvaluationRequest {
@@ -271,6 +290,10 @@ WHEN node.vendor == "cisco" THEN ASSERT node.version IS "15.1"
         let app_state = AppState {
             datastore: Arc::new(datastore),
             policy_service: PolicyService::with_local_dir(temp_dir.path().to_str().unwrap()),
+            enrichment: unet_core::enrichment::EnrichmentPipeline::default(),
+            field_encryption: None,
+            topology: Arc::new(unet_core::topology::TopologyCache::default()),
+            indexed_search: false,
         };
 
         let request = PolicyEvaluationRequest {
@@ -295,6 +318,10 @@ WHEN node.vendor == "cisco" THEN ASSERT node.version IS "15.1"
         let app_state = AppState {
             datastore: Arc::new(datastore),
             policy_service: PolicyService::with_local_dir(temp_dir.path().to_str().unwrap()),
+            enrichment: unet_core::enrichment::EnrichmentPipeline::default(),
+            field_encryption: None,
+            topology: Arc::new(unet_core::topology::TopologyCache::default()),
+            indexed_search: false,
         };
 
         let request = PolicyEvaluationRequest {
@@ -322,6 +349,10 @@ WHEN node.vendor == "cisco" THEN ASSERT node.version IS "15.1"
             let app_state = AppState {
                 datastore: Arc::new(store),
                 policy_service: PolicyService::with_local_dir(temp_dir.path().to_str().unwrap()),
+                enrichment: unet_core::enrichment::EnrichmentPipeline::default(),
+                field_encryption: None,
+                topology: Arc::new(unet_core::topology::TopologyCache::default()),
+                indexed_search: false,
             };
 
             let request = PolicyEvaluationRequest {
@@ -335,7 +366,8 @@ WHEN node.vendor == "cisco" THEN ASSERT node.version IS "15.1"
 
             let response = result.unwrap().0;
             assert_eq!(response.nodes_evaluated, 1);
-        }).await;
+        })
+        .await;
     }
 
     #[tokio::test]
@@ -98,6 +98,10 @@ mod tests {
         let app_state = AppState {
             datastore: Arc::new(mock_datastore),
             policy_service: PolicyService::with_local_dir(policies_path),
+            enrichment: unet_core::enrichment::EnrichmentPipeline::default(),
+            field_encryption: None,
+            topology: Arc::new(unet_core::topology::TopologyCache::default()),
+            indexed_search: false,
         };
 
         let query = PolicyResultsQuery {
@@ -122,6 +126,10 @@ mod tests {
         let app_state = AppState {
             datastore: Arc::new(MockDataStore::new()),
             policy_service: PolicyService::with_local_dir(policies_path),
+            enrichment: unet_core::enrichment::EnrichmentPipeline::default(),
+            field_encryption: None,
+            topology: Arc::new(unet_core::topology::TopologyCache::default()),
+            indexed_search: false,
         };
 
         let query = PolicyResultsQuery {
@@ -162,6 +170,10 @@ mod tests {
         let app_state = AppState {
             datastore: Arc::new(mock_datastore),
             policy_service: PolicyService::with_local_dir(policies_path),
+            enrichment: unet_core::enrichment::EnrichmentPipeline::default(),
+            field_encryption: None,
+            topology: Arc::new(unet_core::topology::TopologyCache::default()),
+            indexed_search: false,
         };
 
         let query = PolicyResultsQuery {
@@ -197,6 +209,10 @@ mod tests {
         let app_state = AppState {
             datastore: Arc::new(mock_datastore),
             policy_service: PolicyService::new(Config::default().git),
+            enrichment: unet_core::enrichment::EnrichmentPipeline::default(),
+            field_encryption: None,
+            topology: Arc::new(unet_core::topology::TopologyCache::default()),
+            indexed_search: false,
         };
 
         let query = PolicyResultsQuery {
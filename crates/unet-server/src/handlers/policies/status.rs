@@ -75,6 +75,10 @@ mod tests {
             let app_state = AppState {
                 datastore: Arc::new(store),
                 policy_service: PolicyService::with_local_dir(&policies_directory),
+                enrichment: unet_core::enrichment::EnrichmentPipeline::default(),
+                field_encryption: None,
+                topology: Arc::new(unet_core::topology::TopologyCache::default()),
+                indexed_search: false,
             };
 
             let result = get_policy_status(State(app_state)).await;
@@ -98,6 +102,10 @@ mod tests {
             let app_state = AppState {
                 datastore: Arc::new(store),
                 policy_service: PolicyService::with_local_dir(&policies_directory),
+                enrichment: unet_core::enrichment::EnrichmentPipeline::default(),
+                field_encryption: None,
+                topology: Arc::new(unet_core::topology::TopologyCache::default()),
+                indexed_search: false,
             };
             app_state.policy_service.record_evaluation_run();
 
@@ -57,6 +57,14 @@ pub struct PolicyResultsQuery {
     pub offset: Option<usize>,
 }
 
+/// Query parameters for triggering a policy batch
+#[derive(Debug, Default, Deserialize)]
+pub struct TriggerBatchQuery {
+    /// Re-evaluate nodes whose policy-relevant state is unchanged
+    #[serde(default)]
+    pub force: bool,
+}
+
 /// Response for policy results
 #[derive(Debug, Serialize)]
 pub struct PolicyResultsResponse {
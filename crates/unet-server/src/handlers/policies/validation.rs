@@ -2,12 +2,16 @@
 
 use axum::{Json, extract::State};
 use tracing::info;
-use unet_core::policy::PolicyRule;
+use unet_core::policy::{PolicyRule, lint_rules};
 
 use crate::{error::ServerResult, server::AppState};
 
 /// Validate policy rules
 ///
+/// The response also carries lint warnings (duplicated rules, always-true or
+/// always-false conditions, unknown fields, and rules without an ID), which
+/// do not make a rule invalid.
+///
 /// # Errors
 /// Returns an error if validation output cannot be serialized.
 pub async fn validate_policies(
@@ -43,11 +47,17 @@ pub async fn validate_policies(
         }
     }
 
+    let warnings = lint_rules(&policies);
+    if !warnings.is_empty() {
+        info!("Policy rules have {} lint warnings", warnings.len());
+    }
+
     Ok(Json(serde_json::json!({
         "total_policies": policies.len(),
         "valid_policies": valid_count,
         "invalid_policies": error_count,
-        "validation_results": validation_results
+        "validation_results": validation_results,
+        "warnings": warnings
     })))
 }
 
@@ -56,14 +66,16 @@ mod tests {
     use super::*;
     use crate::server::AppState;
     use std::sync::Arc;
+    use test_support::sqlite::sqlite_store;
     use unet_core::{
         datastore::sqlite::SqliteStore,
         policy::{Action, ComparisonOperator, Condition, FieldRef, PolicyRule, Value},
         policy_integration::PolicyService,
     };
-    use test_support::sqlite::sqlite_store;
 
-    async fn setup_test_datastore() -> SqliteStore { sqlite_store().await }
+    async fn setup_test_datastore() -> SqliteStore {
+        sqlite_store().await
+    }
 
     fn create_test_policy_rule() -> PolicyRule {
         PolicyRule {
@@ -90,6 +102,10 @@ mod tests {
         let app_state = AppState {
             datastore: Arc::new(datastore),
             policy_service: PolicyService::with_local_dir("/tmp"),
+            enrichment: unet_core::enrichment::EnrichmentPipeline::default(),
+            field_encryption: None,
+            topology: Arc::new(unet_core::topology::TopologyCache::default()),
+            indexed_search: false,
         };
 
         let policies = vec![create_test_policy_rule()];
@@ -109,6 +125,10 @@ mod tests {
         let app_state = AppState {
             datastore: Arc::new(datastore),
             policy_service: PolicyService::with_local_dir("/tmp"),
+            enrichment: unet_core::enrichment::EnrichmentPipeline::default(),
+            field_encryption: None,
+            topology: Arc::new(unet_core::topology::TopologyCache::default()),
+            indexed_search: false,
         };
 
         let policies = vec![];
@@ -121,4 +141,38 @@ mod tests {
         assert_eq!(response["valid_policies"], 0);
         assert_eq!(response["invalid_policies"], 0);
     }
+
+    #[tokio::test]
+    async fn test_validate_policies_reports_lint_warnings() {
+        let app_state = crate::server::app_state::tests::create_mock_app_state().await;
+        let mut duplicate = create_test_policy_rule();
+        duplicate.id = None;
+
+        let response = validate_policies(
+            State(app_state),
+            Json(vec![create_test_policy_rule(), duplicate]),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        assert_eq!(response["valid_policies"], 2);
+        let kinds: Vec<&str> = response["warnings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|warning| warning["kind"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                "unknown_field",
+                "unknown_field",
+                "missing_id",
+                "duplicate_rule",
+                "unknown_field",
+                "unknown_field",
+            ]
+        );
+    }
 }
@@ -0,0 +1,238 @@
+//! Polling pause, shard, and adjustment handlers
+
+use axum::{
+    extract::{Path, State},
+    response::Json,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::api::ApiResponse;
+use crate::handlers::{ServerError, ServerResult};
+use crate::server::AppState;
+use unet_core::snmp::adjustments::{PollingAdjustment, list_adjustments};
+use unet_core::snmp::pauses::{
+    PauseScope, PollingPause, active_pauses, parse_duration, pause_polling, resume_polling,
+};
+use unet_core::snmp::shards::{ShardStatus, shard_status};
+
+/// Request to pause polling of a node, a location, or everything
+#[derive(Debug, Default, Deserialize)]
+pub struct CreatePauseRequest {
+    /// Location whose nodes, including those in sub-locations, are paused
+    #[serde(default)]
+    pub location_id: Option<Uuid>,
+    /// Node to pause
+    #[serde(default)]
+    pub node_id: Option<Uuid>,
+    /// How long the pause lasts, e.g. `2h`; omitted means until resumed
+    #[serde(default)]
+    pub duration: Option<String>,
+    /// Why polling is paused
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// List pauses currently in effect
+///
+/// # Errors
+/// Returns an error if stored pauses cannot be loaded.
+pub async fn list_polling_pauses(
+    State(app_state): State<AppState>,
+) -> ServerResult<Json<ApiResponse<Vec<PollingPause>>>> {
+    let pauses = active_pauses(app_state.datastore.as_ref(), Utc::now()).await?;
+    Ok(Json(ApiResponse::success(pauses)))
+}
+
+/// Pause polling; with neither a location nor a node, all polling is paused
+///
+/// # Errors
+/// Returns an error if both a location and a node are given, the location or
+/// node does not exist, the duration is invalid, or the datastore write fails.
+pub async fn create_polling_pause(
+    State(app_state): State<AppState>,
+    Json(request): Json<CreatePauseRequest>,
+) -> ServerResult<Json<ApiResponse<PollingPause>>> {
+    let datastore = app_state.datastore.as_ref();
+    let scope = match (request.location_id, request.node_id) {
+        (Some(_), Some(_)) => {
+            return Err(ServerError::BadRequest(
+                "Give either location_id or node_id, not both".to_string(),
+            ));
+        }
+        (Some(id), None) => {
+            datastore.get_location_required(&id).await?;
+            PauseScope::Location(id)
+        }
+        (None, Some(id)) => {
+            datastore.get_node_required(&id).await?;
+            PauseScope::Node(id)
+        }
+        (None, None) => PauseScope::Global,
+    };
+    let duration = request
+        .duration
+        .as_deref()
+        .map(parse_duration)
+        .transpose()
+        .map_err(ServerError::BadRequest)?;
+
+    let pause = PollingPause::new(scope, request.reason, duration)?;
+    pause_polling(datastore, &pause).await?;
+    Ok(Json(ApiResponse::success(pause)))
+}
+
+/// Remove a pause
+///
+/// # Errors
+/// Returns an error if the pause does not exist.
+pub async fn delete_polling_pause(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ServerResult<Json<ApiResponse<()>>> {
+    resume_polling(app_state.datastore.as_ref(), id).await?;
+    Ok(Json(ApiResponse::success(())))
+}
+
+/// List the servers polling shards of the nodes and the leases they hold
+///
+/// # Errors
+/// Returns an error if collectors or leases cannot be loaded.
+pub async fn get_polling_shards(
+    State(app_state): State<AppState>,
+) -> ServerResult<Json<ApiResponse<ShardStatus>>> {
+    let status = shard_status(app_state.datastore.as_ref(), Utc::now()).await?;
+    Ok(Json(ApiResponse::success(status)))
+}
+
+/// List polling settings changed by policy rules, by node
+///
+/// # Errors
+/// Returns an error if stored adjustments cannot be loaded.
+pub async fn list_polling_adjustments(
+    State(app_state): State<AppState>,
+) -> ServerResult<Json<ApiResponse<Vec<PollingAdjustment>>>> {
+    let adjustments = list_adjustments(app_state.datastore.as_ref()).await?;
+    Ok(Json(ApiResponse::success(adjustments)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::app_state::tests::create_mock_app_state;
+    use unet_core::datastore::DataStoreError;
+
+    #[tokio::test]
+    async fn test_global_pause_is_listed_until_resumed() {
+        let app_state = create_mock_app_state().await;
+
+        let request = CreatePauseRequest {
+            duration: Some("2h".to_string()),
+            reason: Some("core upgrade".to_string()),
+            ..CreatePauseRequest::default()
+        };
+        let Json(created) = create_polling_pause(State(app_state.clone()), Json(request))
+            .await
+            .unwrap();
+        assert_eq!(created.data.scope, PauseScope::Global);
+        assert!(created.data.expires_at.is_some());
+
+        let Json(listed) = list_polling_pauses(State(app_state.clone())).await.unwrap();
+        assert_eq!(listed.data, vec![created.data.clone()]);
+
+        delete_polling_pause(State(app_state.clone()), Path(created.data.id))
+            .await
+            .unwrap();
+        let Json(listed) = list_polling_pauses(State(app_state)).await.unwrap();
+        assert!(listed.data.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_pause_rejects_unknown_node_and_bad_duration() {
+        let app_state = create_mock_app_state().await;
+
+        let request = CreatePauseRequest {
+            node_id: Some(Uuid::new_v4()),
+            ..CreatePauseRequest::default()
+        };
+        let result = create_polling_pause(State(app_state.clone()), Json(request)).await;
+        assert!(matches!(
+            result,
+            Err(ServerError::DataStore(DataStoreError::NotFound { .. }))
+        ));
+
+        let request = CreatePauseRequest {
+            duration: Some("soon".to_string()),
+            ..CreatePauseRequest::default()
+        };
+        let result = create_polling_pause(State(app_state), Json(request)).await;
+        assert!(matches!(result, Err(ServerError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_shard_status_lists_claimed_leases() {
+        use unet_core::config::ShardingConfig;
+        use unet_core::snmp::shards::{claim_shards, release_shards};
+
+        let app_state = create_mock_app_state().await;
+        let config = ShardingConfig {
+            enabled: true,
+            shards: 2,
+            ..ShardingConfig::default()
+        };
+        let collector = format!("collector-{}", Uuid::new_v4());
+        claim_shards(
+            app_state.datastore.as_ref(),
+            &collector,
+            &config,
+            Utc::now(),
+        )
+        .await
+        .unwrap();
+
+        let Json(status) = get_polling_shards(State(app_state.clone())).await.unwrap();
+        assert!(status.data.collectors.iter().any(|c| c.id == collector));
+
+        release_shards(app_state.datastore.as_ref(), &collector)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_policy_adjustments_are_listed() {
+        use unet_core::policy::{
+            Action, Condition, EvaluationResult, FieldRef, PolicyExecutionResult, PolicyRule, Value,
+        };
+        use unet_core::snmp::adjustments::{PollingSetting, reconcile_adjustments};
+
+        let app_state = create_mock_app_state().await;
+        let node_id = Uuid::new_v4();
+        let action = Action::Set {
+            field: FieldRef {
+                path: vec!["polling".to_string(), "interval".to_string()],
+            },
+            value: Value::String("60s".to_string()),
+        };
+        let rule = PolicyRule {
+            id: Some("degraded".to_string()),
+            condition: Condition::True,
+            action: action.clone(),
+        };
+        let result = PolicyExecutionResult::new(rule, EvaluationResult::Satisfied { action }, None);
+        let datastore = app_state.datastore.as_ref();
+        reconcile_adjustments(datastore, node_id, &[result], Utc::now())
+            .await
+            .unwrap();
+
+        let Json(listed) = list_polling_adjustments(State(app_state.clone()))
+            .await
+            .unwrap();
+        let adjustment = listed.data.iter().find(|a| a.node_id == node_id).unwrap();
+        assert_eq!(adjustment.setting, PollingSetting::Interval(60));
+
+        reconcile_adjustments(datastore, node_id, &[], Utc::now())
+            .await
+            .unwrap();
+    }
+}
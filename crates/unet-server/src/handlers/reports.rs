@@ -0,0 +1,400 @@
+//! Fleet report handlers
+
+use axum::{
+    extract::{Path, Query, State},
+    http::header,
+    response::{IntoResponse, Json, Response},
+};
+use serde::Deserialize;
+
+use crate::api::ApiResponse;
+use crate::handlers::{ServerError, ServerResult};
+use crate::server::AppState;
+use unet_core::reports::capacity::{DEFAULT_WINDOW_DAYS, capacity_report};
+use unet_core::reports::custom::{
+    RenderedReport, ReportFormat, ReportTemplate, delete_template, latest_render, list_templates,
+    run_report, save_template as save_report_template,
+};
+use unet_core::reports::firmware::{
+    FirmwareReport, FirmwareTarget, TargetScope, delete_target, firmware_report, list_targets,
+    save_target,
+};
+use unet_core::reports::forecast::{Method, forecast_report};
+
+/// Request to set the target version of a model or role
+#[derive(Debug, Deserialize)]
+pub struct PutFirmwareTargetRequest {
+    /// Version nodes should run
+    pub version: String,
+    /// Other versions that are accepted
+    #[serde(default)]
+    pub allowed: Vec<String>,
+}
+
+/// Query parameters of the firmware report
+#[derive(Debug, Default, Deserialize)]
+pub struct FirmwareReportQuery {
+    /// Only report on the current members of this node group
+    pub group: Option<String>,
+}
+
+/// Query parameters of the capacity report
+#[derive(Debug, Default, Deserialize)]
+pub struct CapacityReportQuery {
+    /// Days of utilization to summarize; 30 if unset
+    pub days: Option<u32>,
+    /// `csv` to return the report as CSV instead of JSON
+    pub format: Option<String>,
+}
+
+/// Query parameters of the utilization forecast
+#[derive(Debug, Default, Deserialize)]
+pub struct ForecastReportQuery {
+    /// Days of utilization to fit the projection to; 30 if unset
+    pub days: Option<u32>,
+    /// `linear` or `holt_winters`; `linear` if unset
+    pub method: Option<Method>,
+    /// `csv` to return the report as CSV instead of JSON
+    pub format: Option<String>,
+}
+
+/// Request to create or replace a custom report template
+#[derive(Debug, Deserialize)]
+pub struct PutReportTemplateRequest {
+    /// Output format
+    pub format: ReportFormat,
+    /// `MiniJinja` template text
+    pub template: String,
+    /// What the report is for
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Minutes between scheduled renderings; rendered only on demand if unset
+    #[serde(default)]
+    pub interval_minutes: Option<u32>,
+}
+
+/// Report node OS versions against the target-version matrix
+///
+/// # Errors
+/// Returns an error if nodes, locations, or targets cannot be loaded, or the
+/// group does not exist.
+pub async fn get_firmware_report(
+    State(app_state): State<AppState>,
+    Query(query): Query<FirmwareReportQuery>,
+) -> ServerResult<Json<ApiResponse<FirmwareReport>>> {
+    let report = firmware_report(app_state.datastore.as_ref(), query.group.as_deref()).await?;
+    Ok(Json(ApiResponse::success(report)))
+}
+
+/// Report link capacity and utilization between pairs of sites
+///
+/// # Errors
+/// Returns a bad request for a zero window or unknown format, or an error if
+/// the inventory or utilization cannot be loaded.
+pub async fn get_capacity_report(
+    State(app_state): State<AppState>,
+    Query(query): Query<CapacityReportQuery>,
+) -> ServerResult<Response> {
+    let (days, csv) = window_and_format(query.days, query.format.as_deref())?;
+    let report = capacity_report(app_state.datastore.as_ref(), days, chrono::Utc::now()).await?;
+    if csv {
+        return Ok(([(header::CONTENT_TYPE, "text/csv")], report.to_csv()).into_response());
+    }
+    Ok(Json(ApiResponse::success(report)).into_response())
+}
+
+/// Project when each link will reach 70 and 90 percent utilization
+///
+/// # Errors
+/// Returns a bad request for a zero window or unknown format, or an error if
+/// links or utilization cannot be loaded.
+pub async fn get_capacity_forecast(
+    State(app_state): State<AppState>,
+    Query(query): Query<ForecastReportQuery>,
+) -> ServerResult<Response> {
+    let (days, csv) = window_and_format(query.days, query.format.as_deref())?;
+    let report = forecast_report(
+        app_state.datastore.as_ref(),
+        days,
+        query.method.unwrap_or_default(),
+        chrono::Utc::now(),
+    )
+    .await?;
+    if csv {
+        return Ok(([(header::CONTENT_TYPE, "text/csv")], report.to_csv()).into_response());
+    }
+    Ok(Json(ApiResponse::success(report)).into_response())
+}
+
+/// Checks the `days` and `format` of a utilization report, returning the
+/// window and whether CSV was asked for
+fn window_and_format(days: Option<u32>, format: Option<&str>) -> ServerResult<(u32, bool)> {
+    let days = days.unwrap_or(DEFAULT_WINDOW_DAYS);
+    if days == 0 {
+        return Err(ServerError::BadRequest(
+            "days must be at least 1".to_string(),
+        ));
+    }
+    let csv = match format {
+        None | Some("json") => false,
+        Some("csv") => true,
+        Some(other) => {
+            return Err(ServerError::BadRequest(format!(
+                "Unknown format {other:?}; expected json or csv"
+            )));
+        }
+    };
+    Ok((days, csv))
+}
+
+/// List the target-version matrix
+///
+/// # Errors
+/// Returns an error if stored targets cannot be loaded.
+pub async fn list_firmware_targets(
+    State(app_state): State<AppState>,
+) -> ServerResult<Json<ApiResponse<Vec<FirmwareTarget>>>> {
+    let targets = list_targets(app_state.datastore.as_ref()).await?;
+    Ok(Json(ApiResponse::success(targets)))
+}
+
+/// Set the target version of a model or role
+///
+/// # Errors
+/// Returns an error if the role is invalid, a version is empty, or the
+/// datastore write fails.
+pub async fn put_firmware_target(
+    State(app_state): State<AppState>,
+    Path((scope, target)): Path<(TargetScope, String)>,
+    Json(request): Json<PutFirmwareTargetRequest>,
+) -> ServerResult<Json<ApiResponse<FirmwareTarget>>> {
+    let target = FirmwareTarget::new(scope, &target, &request.version, request.allowed)?;
+    save_target(app_state.datastore.as_ref(), &target).await?;
+    Ok(Json(ApiResponse::success(target)))
+}
+
+/// Remove the target version of a model or role
+///
+/// # Errors
+/// Returns an error if the target is invalid or none is set.
+pub async fn delete_firmware_target(
+    State(app_state): State<AppState>,
+    Path((scope, target)): Path<(TargetScope, String)>,
+) -> ServerResult<Json<ApiResponse<()>>> {
+    delete_target(app_state.datastore.as_ref(), scope, &target).await?;
+    Ok(Json(ApiResponse::success(())))
+}
+
+/// List custom report templates
+///
+/// # Errors
+/// Returns an error if stored templates cannot be loaded.
+pub async fn list_report_templates(
+    State(app_state): State<AppState>,
+) -> ServerResult<Json<ApiResponse<Vec<ReportTemplate>>>> {
+    let templates = list_templates(app_state.datastore.as_ref()).await?;
+    Ok(Json(ApiResponse::success(templates)))
+}
+
+/// Create or replace a custom report template
+///
+/// # Errors
+/// Returns an error if the name, interval, or template is invalid, or the
+/// datastore write fails.
+pub async fn put_report_template(
+    State(app_state): State<AppState>,
+    Path(name): Path<String>,
+    Json(request): Json<PutReportTemplateRequest>,
+) -> ServerResult<Json<ApiResponse<ReportTemplate>>> {
+    let report = ReportTemplate::new(
+        &name,
+        request.format,
+        request.template,
+        request.description,
+        request.interval_minutes,
+    )?;
+    save_report_template(app_state.datastore.as_ref(), &report).await?;
+    Ok(Json(ApiResponse::success(report)))
+}
+
+/// Remove a custom report template and its latest rendering
+///
+/// # Errors
+/// Returns an error if there is no such template.
+pub async fn delete_report_template(
+    State(app_state): State<AppState>,
+    Path(name): Path<String>,
+) -> ServerResult<Json<ApiResponse<()>>> {
+    delete_template(app_state.datastore.as_ref(), &name).await?;
+    Ok(Json(ApiResponse::success(())))
+}
+
+/// Render a custom report now and keep it as its latest rendering
+///
+/// # Errors
+/// Returns an error if there is no such template, it fails to render, or
+/// its data cannot be loaded.
+pub async fn render_report_template(
+    State(app_state): State<AppState>,
+    Path(name): Path<String>,
+) -> ServerResult<Json<ApiResponse<RenderedReport>>> {
+    let rendered = run_report(app_state.datastore.as_ref(), &name, chrono::Utc::now()).await?;
+    Ok(Json(ApiResponse::success(rendered)))
+}
+
+/// Return the latest rendering of a custom report
+///
+/// # Errors
+/// Returns not found if the report has not been rendered.
+pub async fn get_latest_report(
+    State(app_state): State<AppState>,
+    Path(name): Path<String>,
+) -> ServerResult<Json<ApiResponse<RenderedReport>>> {
+    let rendered = latest_render(app_state.datastore.as_ref(), &name)
+        .await?
+        .ok_or_else(|| ServerError::NotFound(format!("Report {name} has not been rendered")))?;
+    Ok(Json(ApiResponse::success(rendered)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::app_state::tests::create_mock_app_state;
+    use unet_core::models::{DeviceRole, Node, Vendor};
+    use unet_core::reports::firmware::FirmwareStatus;
+
+    #[tokio::test]
+    async fn test_put_firmware_target_then_report() {
+        let app_state = create_mock_app_state().await;
+        let mut node = Node::new(
+            "firmware-edge".to_string(),
+            "example.com".to_string(),
+            Vendor::Juniper,
+            DeviceRole::Router,
+        );
+        node.model = "MX-FIRMWARE-TEST".to_string();
+        node.version = Some("21.4R3".to_string());
+        app_state.datastore.create_node(&node).await.unwrap();
+
+        let Json(response) = put_firmware_target(
+            State(app_state.clone()),
+            Path((TargetScope::Model, "MX-FIRMWARE-TEST".to_string())),
+            Json(PutFirmwareTargetRequest {
+                version: "22.4R3".to_string(),
+                allowed: Vec::new(),
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.data.key(), "model:MX-FIRMWARE-TEST");
+
+        let Json(response) =
+            get_firmware_report(State(app_state), Query(FirmwareReportQuery::default()))
+                .await
+                .unwrap();
+        let group = response
+            .data
+            .groups
+            .iter()
+            .find(|group| group.model == "MX-FIRMWARE-TEST")
+            .unwrap();
+        assert_eq!(group.status, FirmwareStatus::Outdated);
+        assert_eq!(group.nodes, ["firmware-edge"]);
+    }
+
+    #[tokio::test]
+    async fn test_put_render_and_fetch_report_template() {
+        let app_state = create_mock_app_state().await;
+        let name = "handler-report-test".to_string();
+
+        put_report_template(
+            State(app_state.clone()),
+            Path(name.clone()),
+            Json(PutReportTemplateRequest {
+                format: ReportFormat::Markdown,
+                template: "# {{ report.name }}".to_string(),
+                description: None,
+                interval_minutes: None,
+            }),
+        )
+        .await
+        .unwrap();
+        assert!(
+            get_latest_report(State(app_state.clone()), Path(name.clone()))
+                .await
+                .is_err()
+        );
+
+        let Json(response) = render_report_template(State(app_state.clone()), Path(name.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.data.content, "# handler-report-test");
+        let Json(response) = get_latest_report(State(app_state.clone()), Path(name.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.data.content, "# handler-report-test");
+
+        delete_report_template(State(app_state), Path(name))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_capacity_report_as_csv() {
+        let app_state = create_mock_app_state().await;
+
+        let response = get_capacity_report(
+            State(app_state.clone()),
+            Query(CapacityReportQuery {
+                days: Some(7),
+                format: Some("csv".to_string()),
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            "text/csv"
+        );
+
+        let invalid = get_capacity_report(
+            State(app_state),
+            Query(CapacityReportQuery {
+                days: Some(0),
+                format: None,
+            }),
+        )
+        .await;
+        assert!(matches!(invalid, Err(ServerError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_capacity_forecast() {
+        let app_state = create_mock_app_state().await;
+
+        let response = get_capacity_forecast(
+            State(app_state.clone()),
+            Query(ForecastReportQuery {
+                method: Some(Method::HoltWinters),
+                ..ForecastReportQuery::default()
+            }),
+        )
+        .await
+        .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let response: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(response["data"]["method"], "holt_winters");
+
+        let invalid = get_capacity_forecast(
+            State(app_state),
+            Query(ForecastReportQuery {
+                format: Some("xml".to_string()),
+                ..ForecastReportQuery::default()
+            }),
+        )
+        .await;
+        assert!(matches!(invalid, Err(ServerError::BadRequest(_))));
+    }
+}
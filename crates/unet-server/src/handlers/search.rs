@@ -0,0 +1,129 @@
+//! Search handlers
+
+use axum::{
+    extract::{Query, State},
+    response::Json,
+};
+use serde::Deserialize;
+use tracing::warn;
+
+use crate::api::ApiResponse;
+use crate::handlers::{ServerError, ServerResult};
+use crate::server::AppState;
+use unet_core::search::{DEFAULT_LIMIT, SearchEntity, SearchHit, SearchQuery, search};
+
+/// Query parameters for search
+#[derive(Debug, Default, Deserialize)]
+pub struct SearchParams {
+    /// Text to find
+    #[serde(default)]
+    pub q: String,
+    /// Comma-separated entity types to search, e.g. `node,link`
+    #[serde(default)]
+    pub types: Option<String>,
+    /// Results to return
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// Search nodes, links, locations, policies, and golden configurations
+///
+/// Policies that cannot be loaded are left out of the results rather than
+/// failing the search. With the change log enabled, nodes, links, and
+/// locations are read from the search index.
+///
+/// # Errors
+/// Returns an error if the query is blank, an entity type is unknown, or
+/// entities cannot be loaded.
+pub async fn search_entities(
+    State(app_state): State<AppState>,
+    Query(params): Query<SearchParams>,
+) -> ServerResult<Json<ApiResponse<Vec<SearchHit>>>> {
+    let entities = params
+        .types
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .filter(|entity| !entity.trim().is_empty())
+        .map(str::parse::<SearchEntity>)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(ServerError::BadRequest)?;
+    let query = SearchQuery {
+        text: params.q,
+        entities,
+        limit: params.limit.unwrap_or(DEFAULT_LIMIT),
+        indexed: app_state.indexed_search,
+    };
+
+    let policies = if query.entities.is_empty() || query.entities.contains(&SearchEntity::Policy) {
+        let mut policy_service = app_state.policy_service.clone();
+        policy_service
+            .load_policies_async()
+            .await
+            .unwrap_or_else(|e| {
+                warn!("Failed to load policies for search: {}", e);
+                Vec::new()
+            })
+    } else {
+        Vec::new()
+    };
+
+    let hits = search(app_state.datastore.as_ref(), &policies, &query).await?;
+    Ok(Json(ApiResponse::success(hits)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::app_state::tests::create_mock_app_state;
+    use unet_core::datastore::DataStoreError;
+    use unet_core::models::{DeviceRole, Node, Vendor};
+
+    #[tokio::test]
+    async fn test_search_finds_node_by_name() {
+        let app_state = create_mock_app_state().await;
+        let node = Node::new(
+            "core-sw-01".to_string(),
+            "example.com".to_string(),
+            Vendor::Arista,
+            DeviceRole::Switch,
+        );
+        app_state.datastore.create_node(&node).await.unwrap();
+
+        let params = SearchParams {
+            q: "core-sw-01".to_string(),
+            types: Some("node,link".to_string()),
+            limit: None,
+        };
+        let Json(response) = search_entities(State(app_state), Query(params))
+            .await
+            .unwrap();
+        assert_eq!(response.data[0].id, node.id.to_string());
+        assert_eq!(response.data[0].entity_type, SearchEntity::Node);
+    }
+
+    #[tokio::test]
+    async fn test_search_rejects_blank_query_and_unknown_type() {
+        let app_state = create_mock_app_state().await;
+
+        let params = SearchParams {
+            types: Some("switch".to_string()),
+            q: "core".to_string(),
+            ..SearchParams::default()
+        };
+        let result = search_entities(State(app_state.clone()), Query(params)).await;
+        assert!(matches!(result, Err(ServerError::BadRequest(_))));
+
+        let params = SearchParams {
+            types: Some("node".to_string()),
+            ..SearchParams::default()
+        };
+        let result = search_entities(State(app_state), Query(params)).await;
+        assert!(matches!(
+            result,
+            Err(ServerError::DataStore(
+                DataStoreError::ValidationError { .. }
+            ))
+        ));
+    }
+}
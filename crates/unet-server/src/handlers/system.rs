@@ -0,0 +1,154 @@
+//! Build and runtime information about the running server
+
+use axum::{Extension, extract::State, response::Json};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use unet_core::build_info::BuildInfo;
+use unet_core::config::Config;
+
+use crate::api::ApiResponse;
+use crate::server::AppState;
+
+/// Backends and integrations the server was configured with
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Backends {
+    /// Datastore backend, e.g. `SQLite`
+    pub datastore: String,
+    /// Whether the database is encrypted with `SQLCipher`
+    pub database_encrypted: bool,
+    /// Whether node, link, and location changes are recorded to the change log
+    pub change_log: bool,
+    /// Bearer authentication methods accepted; empty when auth is disabled
+    pub auth: Vec<String>,
+    /// Whether policies or templates are synced from Git
+    pub git: bool,
+    /// Whether `custom_data` fields are encrypted
+    pub field_encryption: bool,
+}
+
+/// What the server is and how it was started, fixed at startup
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServerInfo {
+    /// Version, commit, and features of the server build
+    #[serde(flatten)]
+    pub build: BuildInfo,
+    /// Newest database migration the server applies
+    pub schema_version: Option<String>,
+    /// When the server started
+    pub started_at: DateTime<Utc>,
+    /// Configured backends
+    pub backends: Backends,
+}
+
+impl ServerInfo {
+    /// Describes a server started now with `config` and `app_state`
+    #[must_use]
+    pub fn new(config: &Config, app_state: &AppState) -> Self {
+        let auth = &config.auth;
+        let methods = [
+            ("token", auth.token.is_some() || auth.admin_token.is_some()),
+            ("oidc", auth.oidc.is_some()),
+            ("ldap", auth.ldap.is_some()),
+        ];
+        let git = &config.git;
+        Self {
+            build: BuildInfo::current().with_features(cfg!(feature = "web-ui").then_some("web-ui")),
+            schema_version: migration::schema_version(),
+            started_at: Utc::now(),
+            backends: Backends {
+                datastore: app_state.datastore.name().to_string(),
+                database_encrypted: config.database.encryption_key.is_some(),
+                change_log: config.change_log.enabled,
+                auth: methods
+                    .into_iter()
+                    .filter(|(_, enabled)| auth.enabled && *enabled)
+                    .map(|(method, _)| method.to_string())
+                    .collect(),
+                git: git.repository_url.is_some()
+                    || git.policies_repo.is_some()
+                    || git.templates_repo.is_some(),
+                field_encryption: app_state.field_encryption.is_some(),
+            },
+        }
+    }
+
+    /// One-line summary logged at startup
+    #[must_use]
+    pub fn banner(&self) -> String {
+        let build = &self.build;
+        let sha = build
+            .git_sha
+            .as_ref()
+            .map_or_else(String::new, |sha| format!(" ({sha})"));
+        let built = build.build_date.map_or_else(String::new, |date| {
+            format!(", built {}", date.format("%Y-%m-%d"))
+        });
+        let schema = self
+            .schema_version
+            .as_ref()
+            .map_or_else(String::new, |schema| format!("; schema {schema}"));
+        format!(
+            "μNet server {}{sha}{built}; features: {}{schema}; datastore: {}",
+            build.version,
+            build.features.join(", "),
+            self.backends.datastore
+        )
+    }
+}
+
+/// Server info with the time it has been running
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemInfo {
+    /// What the server is and how it was started
+    #[serde(flatten)]
+    pub server: ServerInfo,
+    /// Seconds since the server started
+    pub uptime_seconds: i64,
+}
+
+/// Get the server's version, build, schema version, uptime, and backends
+///
+/// Servers built without startup info, as in tests, describe themselves
+/// from default configuration as if started now.
+pub async fn get_system_info(
+    State(app_state): State<AppState>,
+    info: Option<Extension<ServerInfo>>,
+) -> Json<ApiResponse<SystemInfo>> {
+    let server = info.map_or_else(
+        || ServerInfo::new(&Config::default(), &app_state),
+        |Extension(info)| info,
+    );
+    let uptime_seconds = (Utc::now() - server.started_at).num_seconds().max(0);
+    Json(ApiResponse::success(SystemInfo {
+        server,
+        uptime_seconds,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::app_state::tests::create_mock_app_state;
+
+    #[tokio::test]
+    async fn test_system_info_reports_build_and_uptime() {
+        let app_state = create_mock_app_state().await;
+        let mut config = Config::default();
+        config.auth.enabled = true;
+        config.auth.token = Some("secret".to_string());
+        let mut info = ServerInfo::new(&config, &app_state);
+        info.started_at -= chrono::TimeDelta::minutes(5);
+
+        let Json(response) = get_system_info(State(app_state), Some(Extension(info))).await;
+
+        let system = response.data;
+        assert_eq!(system.server.build.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(system.server.schema_version, migration::schema_version());
+        assert_eq!(system.server.backends.datastore, "SQLite");
+        assert_eq!(system.server.backends.auth, ["token"]);
+        assert!(system.uptime_seconds >= 300);
+        let banner = system.server.banner();
+        assert!(banner.starts_with(&format!("μNet server {}", env!("CARGO_PKG_VERSION"))));
+        assert!(banner.contains("datastore: SQLite"));
+    }
+}
@@ -0,0 +1,134 @@
+//! Topology analysis handlers
+
+use axum::{
+    extract::{Query, State},
+    response::Json,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::api::ApiResponse;
+use crate::handlers::{ServerError, ServerResult};
+use crate::server::AppState;
+use unet_core::topology::{FailureTarget, ImpactReport, SpofReport, TopologyCacheStatus};
+
+/// Query parameters for impact analysis
+#[derive(Debug, Deserialize)]
+pub struct ImpactQuery {
+    /// Node taken out of service, with all its links
+    pub node: Option<Uuid>,
+    /// Link taken out of service
+    pub link: Option<Uuid>,
+    /// Comma-separated upstream node IDs
+    pub roots: Option<String>,
+}
+
+/// Report everything affected if a node or link goes down
+///
+/// # Errors
+/// Returns an error unless exactly one of `node` and `link` is given, a root
+/// is not a UUID, the target does not exist, or the topology cannot be read.
+pub async fn get_impact(
+    State(app_state): State<AppState>,
+    Query(query): Query<ImpactQuery>,
+) -> ServerResult<Json<ApiResponse<ImpactReport>>> {
+    let target = match (query.node, query.link) {
+        (Some(node), None) => FailureTarget::Node(node),
+        (None, Some(link)) => FailureTarget::Link(link),
+        _ => {
+            return Err(ServerError::BadRequest(
+                "Exactly one of node or link is required".to_string(),
+            ));
+        }
+    };
+    let roots = query
+        .roots
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|root| !root.is_empty())
+        .map(|root| {
+            Uuid::parse_str(root)
+                .map_err(|_| ServerError::BadRequest(format!("Invalid root node ID: {root}")))
+        })
+        .collect::<ServerResult<Vec<_>>>()?;
+
+    let report = app_state
+        .topology
+        .impact(app_state.datastore.as_ref(), target, &roots)
+        .await?;
+    Ok(Json(ApiResponse::success(report)))
+}
+
+/// List the nodes and links whose failure splits the network
+///
+/// # Errors
+/// Returns an error if the topology cannot be read.
+pub async fn get_spof(
+    State(app_state): State<AppState>,
+) -> ServerResult<Json<ApiResponse<SpofReport>>> {
+    let report = app_state
+        .topology
+        .single_points_of_failure(app_state.datastore.as_ref())
+        .await?;
+    Ok(Json(ApiResponse::success(report)))
+}
+
+/// Show what the topology cache holds and whether it is stale
+pub async fn get_cache_status(
+    State(app_state): State<AppState>,
+) -> Json<ApiResponse<TopologyCacheStatus>> {
+    Json(ApiResponse::success(app_state.topology.status()))
+}
+
+/// Reload the topology cache from the datastore
+///
+/// # Errors
+/// Returns an error if the topology cannot be read; the cache keeps its
+/// previous contents.
+pub async fn rebuild_cache(
+    State(app_state): State<AppState>,
+) -> ServerResult<Json<ApiResponse<TopologyCacheStatus>>> {
+    let status = app_state
+        .topology
+        .rebuild(app_state.datastore.as_ref())
+        .await?;
+    Ok(Json(ApiResponse::success(status)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::app_state::tests::create_mock_app_state;
+
+    #[tokio::test]
+    async fn test_get_impact_requires_one_target() {
+        let app_state = create_mock_app_state().await;
+
+        let result = get_impact(
+            State(app_state),
+            Query(ImpactQuery {
+                node: Some(Uuid::new_v4()),
+                link: Some(Uuid::new_v4()),
+                roots: None,
+            }),
+        )
+        .await;
+
+        assert!(matches!(result, Err(ServerError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_rebuild_cache_loads_topology() {
+        let app_state = create_mock_app_state().await;
+        let Json(before) = get_cache_status(State(app_state.clone())).await;
+        assert_eq!(before.data.stale_reason.as_deref(), Some("not built yet"));
+
+        let Json(after) = rebuild_cache(State(app_state.clone())).await.unwrap();
+        assert!(after.data.built_at.is_some());
+        assert!(after.data.stale_reason.is_none());
+
+        assert!(get_spof(State(app_state)).await.is_ok());
+    }
+}
@@ -0,0 +1,167 @@
+//! VLAN and interface VLAN membership handlers
+
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::api::ApiResponse;
+use crate::handlers::ServerResult;
+use crate::server::AppState;
+use unet_core::vlan::{
+    InterfaceVlans, Vlan, VlanMembership, VlanMismatch, check_consistency, delete_vlan,
+    interface_vlans, list_vlans, save_vlan, set_interface_vlan,
+};
+
+/// Request to define a VLAN
+#[derive(Debug, Deserialize)]
+pub struct PutVlanRequest {
+    /// VLAN name
+    pub name: String,
+    /// What the VLAN carries
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Request to set or clear the VLAN membership of an interface
+#[derive(Debug, Deserialize)]
+pub struct SetInterfaceVlanRequest {
+    /// Interface name
+    pub interface: String,
+    /// New membership; omitted or `null` clears it
+    #[serde(default)]
+    pub membership: Option<VlanMembership>,
+}
+
+/// Query parameters for VLAN consistency checks
+#[derive(Debug, Default, Deserialize)]
+pub struct MismatchQuery {
+    /// Only check this node's links
+    #[serde(default)]
+    pub node_id: Option<Uuid>,
+}
+
+/// List VLAN definitions
+///
+/// # Errors
+/// Returns an error if stored VLANs cannot be loaded.
+pub async fn list_vlan_definitions(
+    State(app_state): State<AppState>,
+) -> ServerResult<Json<ApiResponse<Vec<Vlan>>>> {
+    let vlans = list_vlans(app_state.datastore.as_ref()).await?;
+    Ok(Json(ApiResponse::success(vlans)))
+}
+
+/// Define or rename a VLAN
+///
+/// # Errors
+/// Returns an error if the ID is out of range, the name is empty, or the
+/// datastore write fails.
+pub async fn put_vlan(
+    State(app_state): State<AppState>,
+    Path(id): Path<u16>,
+    Json(request): Json<PutVlanRequest>,
+) -> ServerResult<Json<ApiResponse<Vlan>>> {
+    let vlan = Vlan::new(id, &request.name, request.description)?;
+    save_vlan(app_state.datastore.as_ref(), &vlan).await?;
+    Ok(Json(ApiResponse::success(vlan)))
+}
+
+/// Remove a VLAN definition
+///
+/// # Errors
+/// Returns an error if the VLAN is not defined.
+pub async fn delete_vlan_definition(
+    State(app_state): State<AppState>,
+    Path(id): Path<u16>,
+) -> ServerResult<Json<ApiResponse<()>>> {
+    delete_vlan(app_state.datastore.as_ref(), id).await?;
+    Ok(Json(ApiResponse::success(())))
+}
+
+/// List links whose ends disagree on VLANs
+///
+/// # Errors
+/// Returns an error if links or memberships cannot be loaded.
+pub async fn list_vlan_mismatches(
+    State(app_state): State<AppState>,
+    Query(query): Query<MismatchQuery>,
+) -> ServerResult<Json<ApiResponse<Vec<VlanMismatch>>>> {
+    let mismatches = check_consistency(app_state.datastore.as_ref(), query.node_id).await?;
+    Ok(Json(ApiResponse::success(mismatches)))
+}
+
+/// Get a node's interface VLAN memberships
+///
+/// # Errors
+/// Returns an error if the node does not exist or memberships cannot be loaded.
+pub async fn get_node_vlans(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ServerResult<Json<ApiResponse<InterfaceVlans>>> {
+    let datastore = app_state.datastore.as_ref();
+    datastore.get_node_required(&id).await?;
+    let memberships = interface_vlans(datastore, id).await?;
+    Ok(Json(ApiResponse::success(memberships)))
+}
+
+/// Set or clear the VLAN membership of one of a node's interfaces
+///
+/// # Errors
+/// Returns an error if the node does not exist, the membership references an
+/// undefined VLAN, or the datastore write fails.
+pub async fn put_node_vlan(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<SetInterfaceVlanRequest>,
+) -> ServerResult<Json<ApiResponse<InterfaceVlans>>> {
+    let datastore = app_state.datastore.as_ref();
+    datastore.get_node_required(&id).await?;
+    let memberships =
+        set_interface_vlan(datastore, id, &request.interface, request.membership).await?;
+    Ok(Json(ApiResponse::success(memberships)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::ServerError;
+    use crate::server::app_state::tests::create_mock_app_state;
+    use unet_core::datastore::DataStoreError;
+
+    #[tokio::test]
+    async fn test_put_vlan_then_list() {
+        let app_state = create_mock_app_state().await;
+
+        let request = PutVlanRequest {
+            name: "users".to_string(),
+            description: None,
+        };
+        let Json(created) = put_vlan(State(app_state.clone()), Path(110), Json(request))
+            .await
+            .unwrap();
+        assert_eq!(created.data.id, 110);
+
+        let Json(listed) = list_vlan_definitions(State(app_state)).await.unwrap();
+        assert!(listed.data.contains(&created.data));
+    }
+
+    #[tokio::test]
+    async fn test_put_vlan_rejects_out_of_range_id() {
+        let app_state = create_mock_app_state().await;
+
+        let request = PutVlanRequest {
+            name: "reserved".to_string(),
+            description: None,
+        };
+        let result = put_vlan(State(app_state), Path(4095), Json(request)).await;
+        assert!(matches!(
+            result,
+            Err(ServerError::DataStore(
+                DataStoreError::ValidationError { .. }
+            ))
+        ));
+    }
+}
@@ -0,0 +1,213 @@
+//! Webhook subscription and dead-letter handlers
+
+use axum::{
+    extract::{Path, State},
+    response::Json,
+};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::api::ApiResponse;
+use crate::handlers::ServerResult;
+use crate::server::AppState;
+use unet_core::datastore::DataStore;
+use unet_core::webhooks::{
+    Delivery, EventType, FloodControl, PayloadFormat, WebhookEvent, WebhookSubscription,
+    delete_dead_letter, delete_subscription, list_dead_letters, list_subscriptions, record_event,
+    retry_dead_letter, save_subscription,
+};
+
+/// Request to subscribe a URL to events
+#[derive(Debug, Deserialize)]
+pub struct CreateWebhookRequest {
+    /// URL events are POSTed to
+    pub url: String,
+    /// Key used to sign request bodies
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// Event types delivered; empty means all
+    #[serde(default)]
+    pub events: Vec<EventType>,
+    /// Event attributes that must all match
+    #[serde(default)]
+    pub filters: BTreeMap<String, String>,
+    /// Grouping of repeated events and rate limit
+    #[serde(default)]
+    pub flood_control: FloodControl,
+    /// Shape of the request bodies
+    #[serde(default)]
+    pub payload_format: PayloadFormat,
+}
+
+/// Queues an event for its subscribers
+///
+/// The change the event announces has already happened, so a failure to
+/// queue it is logged rather than returned.
+pub async fn announce(datastore: &dyn DataStore, event: &WebhookEvent) {
+    if let Err(e) = record_event(datastore, event).await {
+        warn!(event = %event.event_type, error = %e, "Failed to queue webhook event");
+    }
+}
+
+/// List webhook subscriptions, with secrets hidden
+///
+/// # Errors
+/// Returns an error if stored subscriptions cannot be loaded.
+pub async fn list_webhooks(
+    State(app_state): State<AppState>,
+) -> ServerResult<Json<ApiResponse<Vec<WebhookSubscription>>>> {
+    let subscriptions = list_subscriptions(app_state.datastore.as_ref())
+        .await?
+        .iter()
+        .map(WebhookSubscription::redacted)
+        .collect();
+    Ok(Json(ApiResponse::success(subscriptions)))
+}
+
+/// Subscribe a URL to events
+///
+/// # Errors
+/// Returns an error if the URL, secret, flood control, or payload format is
+/// invalid or the datastore write fails.
+pub async fn create_webhook(
+    State(app_state): State<AppState>,
+    Json(request): Json<CreateWebhookRequest>,
+) -> ServerResult<Json<ApiResponse<WebhookSubscription>>> {
+    let subscription = WebhookSubscription::new(
+        &request.url,
+        request.secret,
+        request.events,
+        request.filters,
+    )?
+    .with_flood_control(request.flood_control)?
+    .with_payload_format(request.payload_format)?;
+    save_subscription(app_state.datastore.as_ref(), &subscription).await?;
+    Ok(Json(ApiResponse::success(subscription.redacted())))
+}
+
+/// Remove a webhook subscription
+///
+/// # Errors
+/// Returns an error if the subscription does not exist.
+pub async fn delete_webhook(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ServerResult<Json<ApiResponse<()>>> {
+    delete_subscription(app_state.datastore.as_ref(), id).await?;
+    Ok(Json(ApiResponse::success(())))
+}
+
+/// List deliveries that ran out of attempts
+///
+/// # Errors
+/// Returns an error if stored dead letters cannot be loaded.
+pub async fn list_webhook_dead_letters(
+    State(app_state): State<AppState>,
+) -> ServerResult<Json<ApiResponse<Vec<Delivery>>>> {
+    let dead_letters = list_dead_letters(app_state.datastore.as_ref()).await?;
+    Ok(Json(ApiResponse::success(dead_letters)))
+}
+
+/// Requeue a dead letter for delivery
+///
+/// # Errors
+/// Returns an error if the dead letter does not exist.
+pub async fn retry_webhook_dead_letter(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ServerResult<Json<ApiResponse<Delivery>>> {
+    let delivery = retry_dead_letter(app_state.datastore.as_ref(), id).await?;
+    Ok(Json(ApiResponse::success(delivery)))
+}
+
+/// Discard a dead letter
+///
+/// # Errors
+/// Returns an error if the dead letter does not exist.
+pub async fn delete_webhook_dead_letter(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ServerResult<Json<ApiResponse<()>>> {
+    delete_dead_letter(app_state.datastore.as_ref(), id).await?;
+    Ok(Json(ApiResponse::success(())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::ServerError;
+    use crate::server::app_state::tests::create_mock_app_state;
+    use unet_core::datastore::DataStoreError;
+
+    fn request(url: &str) -> CreateWebhookRequest {
+        CreateWebhookRequest {
+            url: url.to_string(),
+            secret: Some("s3cret".to_string()),
+            events: vec![EventType::NodeCreated],
+            filters: BTreeMap::new(),
+            flood_control: FloodControl::default(),
+            payload_format: PayloadFormat::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_webhook_hides_secret() {
+        let app_state = create_mock_app_state().await;
+
+        let Json(created) = create_webhook(
+            State(app_state.clone()),
+            Json(request("https://hooks.example.com/unet")),
+        )
+        .await
+        .unwrap();
+        assert_eq!(created.data.secret.as_deref(), Some("<redacted>"));
+
+        let Json(listed) = list_webhooks(State(app_state)).await.unwrap();
+        assert_eq!(listed.data.len(), 1);
+        assert_eq!(listed.data[0].secret.as_deref(), Some("<redacted>"));
+    }
+
+    #[tokio::test]
+    async fn test_create_webhook_rejects_invalid_url() {
+        let app_state = create_mock_app_state().await;
+
+        let result = create_webhook(State(app_state), Json(request("hooks.example.com"))).await;
+
+        assert!(matches!(
+            result,
+            Err(ServerError::DataStore(
+                DataStoreError::ValidationError { .. }
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_create_webhook_rejects_zero_rate_limit() {
+        let app_state = create_mock_app_state().await;
+        let mut request = request("https://hooks.example.com/unet");
+        request.flood_control.rate_limit = Some(0);
+
+        let result = create_webhook(State(app_state), Json(request)).await;
+
+        assert!(matches!(
+            result,
+            Err(ServerError::DataStore(
+                DataStoreError::ValidationError { .. }
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_retry_unknown_dead_letter_is_not_found() {
+        let app_state = create_mock_app_state().await;
+
+        let result = retry_webhook_dead_letter(State(app_state), Path(Uuid::new_v4())).await;
+
+        assert!(matches!(
+            result,
+            Err(ServerError::DataStore(DataStoreError::NotFound { .. }))
+        ));
+    }
+}
@@ -7,5 +7,5 @@ pub mod error;
 pub mod handlers;
 pub mod server;
 
-pub use server::run;
+pub use server::{run, run_with_enrichment};
 
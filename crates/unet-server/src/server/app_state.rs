@@ -2,11 +2,16 @@
 
 use anyhow::Result;
 use std::sync::Arc;
+use std::time::Duration;
 use tracing::info;
 use unet_core::{
+    change_log::{ChangeLogStore, Projection, builtin_projections},
     config::Config,
-    datastore::{DataStore, sqlite::SqliteStore},
+    datastore::{DEFAULT_MAX_RETRIES, DataStore, retry_operation, sqlite::SqliteStore},
+    enrichment::{EnrichmentPipeline, EnrichmentRegistry},
     policy_integration::PolicyService,
+    secrets::{EncryptedFieldsStore, FieldEncryption},
+    topology::TopologyCache,
 };
 
 use crate::background::BackgroundTasks;
@@ -16,16 +21,110 @@ use crate::background::BackgroundTasks;
 pub struct AppState {
     pub datastore: Arc<dyn DataStore + Send + Sync>,
     pub policy_service: PolicyService,
+    /// Enrichment plugins from `server.enrichment`, run on poll results
+    pub enrichment: EnrichmentPipeline,
+    /// Encryption of `secrets.encrypted_fields`; `datastore` returns those
+    /// fields encrypted, and only admin endpoints decrypt them
+    pub field_encryption: Option<Arc<FieldEncryption>>,
+    /// Nodes, links, and locations held in memory for topology queries, kept
+    /// current from change events
+    pub topology: Arc<TopologyCache>,
+    /// Search nodes, links, and locations through the search index
+    /// projection, kept when the change log is enabled
+    pub indexed_search: bool,
 }
 
 /// Initialize application state with datastore and services
-pub async fn initialize_app_state(config: Config, database_url: String) -> Result<AppState> {
+pub async fn initialize_app_state(
+    config: Config,
+    database_url: String,
+    enrichment: &EnrichmentRegistry,
+) -> Result<AppState> {
+    let enrichment = enrichment
+        .build(&config.server.enrichment)
+        .map_err(|e| anyhow::anyhow!("Invalid enrichment configuration: {e}"))?;
+    if !enrichment.is_empty() {
+        info!("Enrichment plugins: {}", enrichment.names().join(", "));
+    }
+
     info!("Initializing SQLite datastore with URL: {}", database_url);
-    let datastore: Arc<dyn DataStore + Send + Sync> = Arc::new(
-        SqliteStore::new(&database_url)
+    let encryption_key = config
+        .database_encryption_key()
+        .map_err(|e| anyhow::anyhow!("Invalid database encryption key: {e}"))?;
+    let encryption_key = encryption_key.as_deref();
+    if encryption_key.is_some() {
+        info!("Opening encrypted SQLite database");
+    }
+    // Another process may hold the database lock while it migrates
+    let url = database_url.as_str();
+    let store = retry_operation(DEFAULT_MAX_RETRIES, || async move {
+        match encryption_key {
+            Some(key) => SqliteStore::new_encrypted(url, key).await,
+            None => SqliteStore::new(url).await,
+        }
+    })
+    .await;
+    let store = store.map_err(|e| anyhow::anyhow!("Failed to initialize SQLite datastore: {e}"))?;
+    let migrated = migration::migrate_safely(store.connection())
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to run migrations: {e}"))?;
+    if !migrated.applied.is_empty() {
+        info!(
+            "Applied {} migration(s): {}",
+            migrated.applied.len(),
+            migrated.applied.join(", ")
+        );
+    }
+    if let Some(backup) = &migrated.backup {
+        info!(
+            "Database backed up before migrating to {}",
+            backup.display()
+        );
+    }
+    let store: Arc<dyn DataStore + Send + Sync> =
+        Arc::new(store.with_change_log(config.change_log.enabled));
+    let indexed_search = config.change_log.enabled;
+    let (store, topology): (Arc<dyn DataStore + Send + Sync>, _) = if config.change_log.enabled {
+        info!("Recording node, link, and location changes to the change log");
+        let topology = Arc::new(TopologyCache::new(Duration::from_secs(
+            config.server.topology_cache_max_age,
+        )));
+        let mut projections = builtin_projections();
+        projections.push(topology.clone() as Arc<dyn Projection>);
+        let store = ChangeLogStore::open(store, projections)
             .await
-            .map_err(|e| anyhow::anyhow!("Failed to initialize SQLite datastore: {e}"))?,
-    );
+            .map_err(|e| anyhow::anyhow!("Failed to open the change log: {e}"))?;
+        (Arc::new(store), topology)
+    } else {
+        // Without change events the cache cannot follow writes
+        info!("Topology cache disabled; enable change_log to keep it current");
+        (store, Arc::new(TopologyCache::new(Duration::ZERO)))
+    };
+
+    let field_encryption = FieldEncryption::from_config(&config.secrets)
+        .map_err(|e| anyhow::anyhow!("Invalid secrets configuration: {e}"))?
+        .map(Arc::new);
+    // Background tasks need plaintext, e.g. SNMP communities; API reads do not
+    let (datastore, background_store): (
+        Arc<dyn DataStore + Send + Sync>,
+        Arc<dyn DataStore + Send + Sync>,
+    ) = match &field_encryption {
+        Some(fields) => {
+            info!(
+                "Encrypting custom_data fields: {}",
+                fields.paths().join(", ")
+            );
+            (
+                Arc::new(EncryptedFieldsStore::new(
+                    store.clone(),
+                    fields.clone(),
+                    false,
+                )),
+                Arc::new(EncryptedFieldsStore::new(store, fields.clone(), true)),
+            )
+        }
+        None => (store.clone(), store),
+    };
 
     info!("Initializing policy service");
     let policy_service = PolicyService::new(config.git.clone());
@@ -33,9 +132,14 @@ pub async fn initialize_app_state(config: Config, database_url: String) -> Resul
     let app_state = AppState {
         datastore: datastore.clone(),
         policy_service: policy_service.clone(),
+        enrichment,
+        field_encryption,
+        topology,
+        indexed_search,
     };
 
-    let background_tasks = BackgroundTasks::new(config, datastore, policy_service);
+    let background_tasks = BackgroundTasks::new(config, background_store, policy_service)
+        .with_enrichment(app_state.enrichment.clone());
     background_tasks.start();
 
     Ok(app_state)
@@ -67,6 +171,10 @@ pub mod tests {
         let app_state = AppState {
             datastore: datastore.clone(),
             policy_service,
+            enrichment: EnrichmentPipeline::default(),
+            field_encryption: None,
+            topology: Arc::new(TopologyCache::default()),
+            indexed_search: false,
         };
 
         assert!(Arc::ptr_eq(&app_state.datastore, &datastore));
@@ -77,7 +185,8 @@ pub mod tests {
         let config = create_test_config();
         let database_url = "sqlite::memory:".to_string();
 
-        let result = initialize_app_state(config, database_url).await;
+        let result =
+            initialize_app_state(config, database_url, &EnrichmentRegistry::with_builtins()).await;
 
         match result {
             Ok(app_state) => {
@@ -89,6 +198,48 @@ pub mod tests {
         }
     }
 
+    #[cfg(not(feature = "sqlcipher"))]
+    #[tokio::test]
+    async fn test_initialize_app_state_encryption_requires_sqlcipher() {
+        let mut config = create_test_config();
+        config.database.encryption_key = Some("wrong".to_string());
+        config.database.allow_plaintext_key = true;
+
+        let result = initialize_app_state(
+            config,
+            "sqlite::memory:".to_string(),
+            &EnrichmentRegistry::with_builtins(),
+        )
+        .await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_initialize_app_state_rejects_unknown_enrichment_plugin() {
+        let mut config = create_test_config();
+        config.server.enrichment = vec![unet_core::config::EnrichmentPluginConfig {
+            name: "not_registered".to_string(),
+            enabled: true,
+            options: serde_json::Map::new(),
+        }];
+
+        let result = initialize_app_state(
+            config,
+            "sqlite::memory:".to_string(),
+            &EnrichmentRegistry::with_builtins(),
+        )
+        .await;
+
+        assert!(
+            result
+                .err()
+                .unwrap()
+                .to_string()
+                .contains("Unknown enrichment plugin 'not_registered'")
+        );
+    }
+
     #[test]
     fn test_policy_service_creation() {
         let config = create_test_config();
@@ -124,6 +275,10 @@ pub mod tests {
         AppState {
             datastore: Arc::new(datastore),
             policy_service: PolicyService::new(git_config),
+            enrichment: EnrichmentPipeline::default(),
+            field_encryption: None,
+            topology: Arc::new(TopologyCache::default()),
+            indexed_search: false,
         }
     }
 }
@@ -1,20 +1,55 @@
 //! Server-side API authentication helpers.
+//!
+//! Requests present a static bearer token, a scoped API key, an ID token
+//! from the configured OIDC issuer, or, when LDAP is configured, HTTP Basic
+//! credentials verified by binding to the directory. Provider users get the
+//! role their groups map to in `auth.group_roles`.
+//!
+//! Every authenticated request is granted a set of [`ApiScope`]s: the admin
+//! role grants all of them, the operator role all but `admin`, and an API key
+//! exactly the ones it was created with. Each group of routes is layered with
+//! the [`RouteScopes`] it needs, checked by [`require_scope`].
+
+use std::sync::Arc;
 
 use axum::{
-    Json,
+    Extension, Json,
     extract::{Request, State},
-    http::{StatusCode, header},
+    http::{Method, StatusCode, header},
     middleware::Next,
     response::{IntoResponse, Response},
 };
-use unet_core::config::types::AuthConfig;
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use chrono::Utc;
+use serde::Serialize;
+use unet_core::api_keys::{ApiKey, ApiScope, TOKEN_PREFIX, find_key};
+use unet_core::config::auth::{AuthConfig, GroupRoleConfig};
+use unet_core::datastore::DataStore;
+use uuid::Uuid;
 
-use crate::api::ApiError;
+use super::ldap::{LdapError, LdapProvider};
+use super::oidc::{self, OidcProvider};
+use crate::api::{ApiError, ApiResponse};
 
 #[derive(Clone, Debug)]
 pub struct ApiAuth {
     enabled: bool,
     token: Option<String>,
+    admin_token: Option<String>,
+    oidc: Option<Arc<OidcProvider>>,
+    ldap: Option<Arc<LdapProvider>>,
+    group_roles: Arc<GroupRoleConfig>,
+    api_keys: Option<ApiKeyStore>,
+}
+
+/// Datastore API keys are looked up in
+#[derive(Clone)]
+struct ApiKeyStore(Arc<dyn DataStore + Send + Sync>);
+
+impl std::fmt::Debug for ApiKeyStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ApiKeyStore").field(&self.0.name()).finish()
+    }
 }
 
 impl ApiAuth {
@@ -23,25 +58,276 @@ impl ApiAuth {
         Self {
             enabled: config.enabled,
             token: config.token.clone(),
+            admin_token: config.admin_token.clone(),
+            oidc: config
+                .oidc
+                .clone()
+                .map(|oidc| Arc::new(OidcProvider::new(oidc))),
+            ldap: config
+                .ldap
+                .clone()
+                .map(|ldap| Arc::new(LdapProvider::new(ldap))),
+            group_roles: Arc::new(config.group_roles.clone()),
+            api_keys: None,
+        }
+    }
+
+    /// Accepts API keys stored in `datastore`
+    #[must_use]
+    pub fn with_api_keys(mut self, datastore: Arc<dyn DataStore + Send + Sync>) -> Self {
+        self.api_keys = Some(ApiKeyStore(datastore));
+        self
+    }
+
+    /// Whether the request presents a bearer token shaped like an API key
+    /// that this server can look up
+    fn presents_api_key(&self, request: &Request) -> bool {
+        self.api_keys.is_some()
+            && request
+                .headers()
+                .get(header::AUTHORIZATION)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.strip_prefix("Bearer "))
+                .is_some_and(|token| token.starts_with(TOKEN_PREFIX))
+    }
+
+    pub(super) fn oidc(&self) -> Option<&OidcProvider> {
+        self.oidc.as_deref()
+    }
+
+    /// Returns the role granted by a provider user's groups, admin first
+    #[must_use]
+    pub fn role_for_groups(&self, groups: &[String]) -> Option<ApiRole> {
+        let grants = |names: &[String]| {
+            groups
+                .iter()
+                .any(|group| names.iter().any(|name| group.eq_ignore_ascii_case(name)))
+        };
+        if grants(&self.group_roles.admin) {
+            Some(ApiRole::Admin)
+        } else if grants(&self.group_roles.operator) {
+            Some(ApiRole::Operator)
+        } else {
+            None
+        }
+    }
+
+    pub(super) fn mapped_role(&self, groups: &[String]) -> Result<ApiRole, Response> {
+        self.role_for_groups(groups).ok_or_else(|| {
+            auth_error(
+                StatusCode::FORBIDDEN,
+                "ROLE_REQUIRED",
+                "None of your groups grants an API role",
+            )
+        })
+    }
+
+    async fn bearer_grant(&self, token: &str) -> Result<ApiGrant, Response> {
+        if self.admin_token.as_deref() == Some(token) {
+            return Ok(ApiGrant::for_role(ApiRole::Admin));
+        }
+        if self.token.as_deref() == Some(token) {
+            return Ok(ApiGrant::for_role(ApiRole::Operator));
+        }
+        if let Some(store) = self
+            .api_keys
+            .as_ref()
+            .filter(|_| token.starts_with(TOKEN_PREFIX))
+        {
+            return match find_key(store.0.as_ref(), token).await {
+                Ok(Some(key)) if !key.is_expired(Utc::now()) => Ok(ApiGrant::for_key(key)),
+                Ok(Some(_)) => Err(unauthorized("INVALID_AUTH_TOKEN", "API key has expired")),
+                Ok(None) => Err(unauthorized("INVALID_AUTH_TOKEN", "Invalid bearer token")),
+                Err(error) => {
+                    tracing::warn!(error = %error, "API key lookup failed");
+                    Err(auth_error(
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        "INTERNAL_ERROR",
+                        "API key lookup failed",
+                    ))
+                }
+            };
+        }
+        let Some(oidc) = &self.oidc else {
+            return Err(unauthorized("INVALID_AUTH_TOKEN", "Invalid bearer token"));
+        };
+        match oidc.verify(token).await {
+            Ok(groups) => self.mapped_role(&groups).map(ApiGrant::for_role),
+            Err(error) => Err(oidc::error_response(&error)),
+        }
+    }
+
+    async fn basic_role(
+        &self,
+        ldap: &LdapProvider,
+        credentials: &str,
+    ) -> Result<ApiRole, Response> {
+        let Some((username, password)) = STANDARD
+            .decode(credentials)
+            .ok()
+            .and_then(|decoded| String::from_utf8(decoded).ok())
+            .and_then(|decoded| {
+                decoded
+                    .split_once(':')
+                    .map(|(username, password)| (username.to_string(), password.to_string()))
+            })
+        else {
+            return Err(unauthorized(
+                "INVALID_AUTH_TOKEN",
+                "Invalid basic credentials",
+            ));
+        };
+        match ldap.verify(&username, &password).await {
+            Ok(groups) => self.mapped_role(&groups),
+            Err(LdapError::InvalidCredentials) => {
+                Err(unauthorized("INVALID_AUTH_TOKEN", "Invalid credentials"))
+            }
+            Err(error) => {
+                tracing::warn!(error = %error, "LDAP verification failed");
+                Err(auth_error(
+                    StatusCode::BAD_GATEWAY,
+                    "AUTH_PROVIDER_ERROR",
+                    &error.to_string(),
+                ))
+            }
+        }
+    }
+}
+
+/// Role of an authenticated request, stored in the request extensions
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ApiRole {
+    /// Presented the regular API token, or is in an operator group
+    Operator,
+    /// Presented the admin token or an API key with the admin scope, is in
+    /// an admin group, or auth is disabled
+    Admin,
+}
+
+/// What an authenticated request may do, stored in the request extensions
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ApiGrant {
+    /// Role the request authenticated with
+    pub role: ApiRole,
+    /// Scopes the request was granted
+    pub scopes: Vec<ApiScope>,
+    /// API key the request presented, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_id: Option<Uuid>,
+    /// Name of the API key the request presented, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_name: Option<String>,
+}
+
+impl ApiGrant {
+    /// Every scope for admins, every scope but `admin` for operators
+    #[must_use]
+    pub fn for_role(role: ApiRole) -> Self {
+        let scopes = ApiScope::ALL
+            .into_iter()
+            .filter(|scope| role == ApiRole::Admin || *scope != ApiScope::Admin)
+            .collect();
+        Self {
+            role,
+            scopes,
+            key_id: None,
+            key_name: None,
+        }
+    }
+
+    /// The key's own scopes; a key with the admin scope gets the admin role
+    /// and every scope
+    #[must_use]
+    pub fn for_key(key: ApiKey) -> Self {
+        let admin = key.allows(ApiScope::Admin);
+        Self {
+            role: if admin {
+                ApiRole::Admin
+            } else {
+                ApiRole::Operator
+            },
+            scopes: if admin {
+                ApiScope::ALL.to_vec()
+            } else {
+                key.scopes
+            },
+            key_id: Some(key.id),
+            key_name: Some(key.name),
+        }
+    }
+
+    /// Whether the request was granted `scope`
+    #[must_use]
+    pub fn allows(&self, scope: ApiScope) -> bool {
+        self.scopes.contains(&scope)
+    }
+}
+
+/// Scopes a group of routes needs: one to read and one to change
+///
+/// GET, HEAD, and OPTIONS requests need `read`; every other method needs
+/// `write`. Admin-only routes additionally need the admin role (see
+/// [`require_admin`]).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RouteScopes {
+    /// Scope reads need
+    pub read: ApiScope,
+    /// Scope changes need
+    pub write: ApiScope,
+}
+
+impl RouteScopes {
+    /// Reading and changing nodes and other inventory
+    pub const INVENTORY: Self = Self {
+        read: ApiScope::NodesRead,
+        write: ApiScope::NodesWrite,
+    };
+    /// Changing SNMP polling and running on-demand polls
+    pub const SNMP: Self = Self {
+        read: ApiScope::NodesRead,
+        write: ApiScope::SnmpExecute,
+    };
+    /// Evaluating, validating, and running policies
+    pub const POLICY_RUNS: Self = Self {
+        read: ApiScope::NodesRead,
+        write: ApiScope::PoliciesEvaluate,
+    };
+
+    /// Scope a request with `method` needs
+    #[must_use]
+    pub fn for_method(self, method: &Method) -> ApiScope {
+        if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) {
+            self.read
+        } else {
+            self.write
         }
     }
 }
 
 pub async fn require_bearer_auth(
     State(auth): State<ApiAuth>,
-    request: Request,
+    mut request: Request,
     next: Next,
 ) -> Response {
     if !auth.enabled {
+        request.extensions_mut().insert(ApiRole::Admin);
+        request
+            .extensions_mut()
+            .insert(ApiGrant::for_role(ApiRole::Admin));
         return next.run(request).await;
     }
 
-    let Some(expected_token) = auth.token.as_deref() else {
+    if auth.token.is_none()
+        && auth.oidc.is_none()
+        && auth.ldap.is_none()
+        && !auth.presents_api_key(&request)
+    {
         return unauthorized(
             "AUTH_REQUIRED",
             "Authentication is enabled but no token is configured",
         );
-    };
+    }
 
     let Some(header_value) = request.headers().get(header::AUTHORIZATION) else {
         return unauthorized("AUTH_REQUIRED", "Missing bearer token");
@@ -51,20 +337,81 @@ pub async fn require_bearer_auth(
         return unauthorized("INVALID_AUTH_TOKEN", "Invalid authorization header");
     };
 
-    let Some(token) = header_value.strip_prefix("Bearer ") else {
+    let grant = if let Some(token) = header_value.strip_prefix("Bearer ") {
+        auth.bearer_grant(token).await
+    } else if let (Some(ldap), Some(credentials)) =
+        (auth.ldap.as_deref(), header_value.strip_prefix("Basic "))
+    {
+        auth.basic_role(ldap, credentials)
+            .await
+            .map(ApiGrant::for_role)
+    } else {
         return unauthorized("AUTH_REQUIRED", "Missing bearer token");
     };
+    let grant = match grant {
+        Ok(grant) => grant,
+        Err(response) => return response,
+    };
+
+    request.extensions_mut().insert(grant.role);
+    request.extensions_mut().insert(grant);
+    next.run(request).await
+}
+
+/// Returns the role and scopes the request was granted
+pub async fn get_scopes(Extension(grant): Extension<ApiGrant>) -> Json<ApiResponse<ApiGrant>> {
+    Json(ApiResponse::success(grant))
+}
 
-    if token != expected_token {
-        return unauthorized("INVALID_AUTH_TOKEN", "Invalid bearer token");
+/// Rejects requests whose grant lacks the scope the route needs
+///
+/// Must run inside [`require_bearer_auth`], which records the grant.
+pub async fn require_scope(
+    State(scopes): State<RouteScopes>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let scope = scopes.for_method(request.method());
+    if !request
+        .extensions()
+        .get::<ApiGrant>()
+        .is_some_and(|grant| grant.allows(scope))
+    {
+        return auth_error(
+            StatusCode::FORBIDDEN,
+            "SCOPE_REQUIRED",
+            &format!("This endpoint requires the {scope} scope"),
+        );
+    }
+
+    next.run(request).await
+}
+
+/// Rejects requests that did not authenticate with the admin role
+///
+/// Must run inside [`require_bearer_auth`], which records the role.
+pub async fn require_admin(request: Request, next: Next) -> Response {
+    if request.extensions().get::<ApiRole>() != Some(&ApiRole::Admin) {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiError::new(
+                "This endpoint requires the admin role".to_string(),
+                "ADMIN_REQUIRED".to_string(),
+            )),
+        )
+            .into_response();
     }
 
     next.run(request).await
 }
 
 fn unauthorized(code: &str, message: &str) -> Response {
+    auth_error(StatusCode::UNAUTHORIZED, code, message)
+}
+
+pub(super) fn auth_error(status: StatusCode, code: &str, message: &str) -> Response {
     (
-        StatusCode::UNAUTHORIZED,
+        status,
         Json(ApiError::new(message.to_string(), code.to_string())),
     )
         .into_response()
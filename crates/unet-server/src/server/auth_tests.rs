@@ -1,17 +1,24 @@
 //! Authentication integration tests for the server router.
 
 use axum::{
+    Router,
     body::{Body, to_bytes},
-    http::{HeaderValue, Request, StatusCode},
+    http::{HeaderValue, Method, Request, StatusCode},
 };
 use serde_json::Value;
 use tempfile::NamedTempFile;
 use tower::ServiceExt as _;
-use unet_core::config::Config;
+use unet_core::api_keys::{ApiScope, create_key, revoke_key};
+use unet_core::config::{Config, GroupRoleConfig, LdapConfig, OidcConfig};
 
+use super::app_state::tests::create_mock_app_state;
+use super::auth::{ApiAuth, ApiRole, RouteScopes};
+use super::limits::BodyLimits;
 use super::middleware::create_app;
+use super::routes::create_router;
 
 const PROTECTED_PATH: &str = "/api/v1/policies/status";
+const ADMIN_PATH: &str = "/api/v1/admin/stats";
 
 async fn request_status(
     config: Config,
@@ -56,6 +63,41 @@ async fn request_status_with_header(
     (status, json)
 }
 
+async fn send(app: &Router, method: Method, path: &str, token: &str) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(path)
+        .header("Authorization", format!("Bearer {token}"))
+        .body(Body::empty())
+        .expect("request should build");
+    let response = app
+        .clone()
+        .oneshot(request)
+        .await
+        .expect("request should succeed");
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("body should read");
+    (
+        status,
+        serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+    )
+}
+
+/// Builds an app accepting API keys, returning it with a token for `scopes`
+async fn api_key_app(config: &Config, scopes: &[ApiScope]) -> (Router, String) {
+    let app_state = create_mock_app_state().await;
+    let (_, token) = create_key(app_state.datastore.as_ref(), "ci", scopes, None)
+        .await
+        .expect("key should be created");
+    let auth = ApiAuth::from_config(&config.auth).with_api_keys(app_state.datastore.clone());
+    (
+        create_router(auth, BodyLimits::default()).with_state(app_state),
+        token,
+    )
+}
+
 fn auth_config(enabled: bool) -> Config {
     let temp_file = NamedTempFile::with_suffix(".toml").expect("temp file should exist");
     std::fs::write(
@@ -91,6 +133,7 @@ search_domains = []
 [auth]
 enabled = {enabled}
 token = "bed-24-secret"
+admin_token = "bed-24-admin"
 token_expiry = 3600
 "#
         ),
@@ -173,3 +216,271 @@ async fn test_protected_route_rejects_when_token_is_not_configured() {
     let body = body.expect("unauthorized response should be json");
     assert_eq!(body["code"], "AUTH_REQUIRED");
 }
+
+#[tokio::test]
+async fn test_admin_route_rejects_operator_token() {
+    let (status, body) = request_status(auth_config(true), ADMIN_PATH, Some("bed-24-secret")).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let body = body.expect("forbidden response should be json");
+    assert_eq!(body["code"], "ADMIN_REQUIRED");
+}
+
+#[tokio::test]
+async fn test_admin_token_grants_admin_and_protected_routes() {
+    let (status, body) = request_status(auth_config(true), ADMIN_PATH, Some("bed-24-admin")).await;
+    assert_eq!(status, StatusCode::OK);
+    let body = body.expect("admin response should be json");
+    assert_eq!(body["data"]["counts"]["nodes"], 0);
+
+    let (status, _) = request_status(auth_config(true), PROTECTED_PATH, Some("bed-24-admin")).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_admin_route_rejects_when_admin_token_is_not_configured() {
+    let mut config = auth_config(true);
+    config.auth.admin_token = None;
+
+    let (status, _) = request_status(config, ADMIN_PATH, Some("bed-24-admin")).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_admin_route_remains_open_when_auth_disabled() {
+    let (status, _) = request_status(auth_config(false), ADMIN_PATH, None).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+fn with_providers(mut config: Config) -> Config {
+    config.auth.oidc = Some(OidcConfig {
+        issuer: "https://idp.invalid".to_string(),
+        client_id: "unet".to_string(),
+        client_secret: None,
+        redirect_url: "https://unet.invalid/api/v1/auth/oidc/callback".to_string(),
+        scopes: vec!["openid".to_string()],
+        groups_claim: "groups".to_string(),
+    });
+    config.auth.ldap = Some(LdapConfig {
+        url: "ldap://127.0.0.1:1".to_string(),
+        bind_dn: "uid={username},ou=people,dc=example,dc=com".to_string(),
+        group_attribute: "memberOf".to_string(),
+    });
+    config
+}
+
+#[tokio::test]
+async fn test_oidc_login_requires_configuration() {
+    let (status, body) = request_status(auth_config(true), "/api/v1/auth/oidc/login", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let body = body.expect("error response should be json");
+    assert_eq!(body["code"], "OIDC_NOT_CONFIGURED");
+}
+
+#[tokio::test]
+async fn test_oidc_callback_rejects_unknown_state() {
+    let path = "/api/v1/auth/oidc/callback?code=abc&state=forged";
+    let (status, body) = request_status(with_providers(auth_config(true)), path, None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let body = body.expect("error response should be json");
+    assert_eq!(body["code"], "INVALID_LOGIN_STATE");
+}
+
+#[tokio::test]
+async fn test_oidc_rejects_malformed_id_token() {
+    let config = with_providers(auth_config(true));
+    let (status, body) = request_status(config, PROTECTED_PATH, Some("not-a-jwt")).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let body = body.expect("unauthorized response should be json");
+    assert_eq!(body["code"], "INVALID_AUTH_TOKEN");
+}
+
+#[tokio::test]
+async fn test_static_tokens_still_accepted_with_providers() {
+    let config = with_providers(auth_config(true));
+    let (status, _) = request_status(config, ADMIN_PATH, Some("bed-24-admin")).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_ldap_basic_auth_rejects_empty_password() {
+    let header = HeaderValue::from_static("Basic amRvZTo="); // jdoe:
+    let (status, body) = request_status_with_header(
+        with_providers(auth_config(true)),
+        PROTECTED_PATH,
+        Some(header),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let body = body.expect("unauthorized response should be json");
+    assert_eq!(body["code"], "INVALID_AUTH_TOKEN");
+}
+
+#[test]
+fn test_group_roles_prefer_admin_and_match_exactly() {
+    let mut config = auth_config(true).auth;
+    config.group_roles = GroupRoleConfig {
+        admin: vec!["NetAdmins".to_string()],
+        operator: vec![
+            "netops".to_string(),
+            "cn=noc,ou=groups,dc=example,dc=com".to_string(),
+        ],
+    };
+    let auth = ApiAuth::from_config(&config);
+    let groups = |names: &[&str]| names.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+    assert_eq!(
+        auth.role_for_groups(&groups(&["CN=NOC,OU=groups,DC=example,DC=com"])),
+        Some(ApiRole::Operator)
+    );
+    // A DN is not matched by its first component, whatever its other parts
+    assert_eq!(
+        auth.role_for_groups(&groups(&["cn=netops,ou=guests,dc=attacker,dc=com"])),
+        None
+    );
+    assert_eq!(
+        auth.role_for_groups(&groups(&["cn=noc,ou=guests,dc=example,dc=com"])),
+        None
+    );
+    assert_eq!(
+        auth.role_for_groups(&groups(&["netops", "netadmins"])),
+        Some(ApiRole::Admin)
+    );
+    assert_eq!(
+        auth.role_for_groups(&groups(&["NETOPS"])),
+        Some(ApiRole::Operator)
+    );
+    assert_eq!(auth.role_for_groups(&groups(&["sales"])), None);
+}
+
+#[tokio::test]
+async fn test_api_key_is_limited_to_its_scopes() {
+    let (app, token) = api_key_app(&auth_config(true), &[ApiScope::NodesRead]).await;
+
+    let (status, _) = send(&app, Method::GET, PROTECTED_PATH, &token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = send(&app, Method::POST, "/api/v1/policies/evaluate", &token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], "SCOPE_REQUIRED");
+
+    let (status, body) = send(&app, Method::GET, ADMIN_PATH, &token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], "ADMIN_REQUIRED");
+
+    let (status, body) = send(&app, Method::GET, "/api/v1/auth/scopes", &token).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["role"], "operator");
+    assert_eq!(body["data"]["scopes"], serde_json::json!(["nodes:read"]));
+    assert_eq!(body["data"]["key_name"], "ci");
+}
+
+#[tokio::test]
+async fn test_admin_scoped_api_key_grants_admin_routes_without_static_tokens() {
+    let mut config = auth_config(true);
+    config.auth.token = None;
+    config.auth.admin_token = None;
+    let (app, token) = api_key_app(&config, &[ApiScope::Admin]).await;
+
+    let (status, _) = send(&app, Method::GET, ADMIN_PATH, &token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = send(&app, Method::GET, "/api/v1/auth/scopes", &token).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["role"], "admin");
+}
+
+#[tokio::test]
+async fn test_revoked_api_key_is_rejected() {
+    let app_state = create_mock_app_state().await;
+    let datastore = app_state.datastore.clone();
+    let (key, token) = create_key(datastore.as_ref(), "ci", &[ApiScope::NodesRead], None)
+        .await
+        .expect("key should be created");
+    let auth = ApiAuth::from_config(&auth_config(true).auth).with_api_keys(datastore.clone());
+    let app = create_router(auth, BodyLimits::default()).with_state(app_state);
+    revoke_key(datastore.as_ref(), key.id)
+        .await
+        .expect("key should be revoked");
+
+    let (status, body) = send(&app, Method::GET, PROTECTED_PATH, &token).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["code"], "INVALID_AUTH_TOKEN");
+}
+
+#[tokio::test]
+async fn test_static_token_scopes_follow_role() {
+    let (status, body) = request_status(
+        auth_config(true),
+        "/api/v1/auth/scopes",
+        Some("bed-24-secret"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let body = body.expect("scopes response should be json");
+    assert_eq!(
+        body["data"]["scopes"],
+        serde_json::json!([
+            "nodes:read",
+            "nodes:write",
+            "policies:evaluate",
+            "snmp:execute"
+        ])
+    );
+}
+
+#[test]
+fn test_route_scopes_by_method() {
+    assert_eq!(
+        RouteScopes::INVENTORY.for_method(&Method::GET),
+        ApiScope::NodesRead
+    );
+    assert_eq!(
+        RouteScopes::INVENTORY.for_method(&Method::POST),
+        ApiScope::NodesWrite
+    );
+    assert_eq!(
+        RouteScopes::SNMP.for_method(&Method::DELETE),
+        ApiScope::SnmpExecute
+    );
+    assert_eq!(
+        RouteScopes::POLICY_RUNS.for_method(&Method::POST),
+        ApiScope::PoliciesEvaluate
+    );
+}
+
+#[tokio::test]
+async fn test_route_groups_require_their_scopes() {
+    let (app, token) = api_key_app(&auth_config(true), &[ApiScope::NodesWrite]).await;
+    let node_id = uuid::Uuid::new_v4();
+
+    for (method, path) in [
+        (Method::POST, "/api/v1/policies/evaluate".to_string()),
+        (
+            Method::POST,
+            "/api/v1/policies/batches/nightly/runs".to_string(),
+        ),
+        (Method::PUT, "/api/v1/oid-profiles/core".to_string()),
+        (Method::POST, "/api/v1/polling/pauses".to_string()),
+        (Method::POST, format!("/api/v1/nodes/{node_id}/poll")),
+        (Method::GET, "/api/v1/nodes".to_string()),
+    ] {
+        let (status, body) = send(&app, method.clone(), &path, &token).await;
+        assert_eq!(status, StatusCode::FORBIDDEN, "{method} {path}");
+        assert_eq!(body["code"], "SCOPE_REQUIRED", "{method} {path}");
+    }
+
+    // Other writes only need nodes:write
+    let (status, _) = send(
+        &app,
+        Method::DELETE,
+        &format!("/api/v1/nodes/{node_id}"),
+        &token,
+    )
+    .await;
+    assert_ne!(status, StatusCode::FORBIDDEN);
+}
@@ -0,0 +1,114 @@
+//! LDAP bind verification for HTTP Basic credentials
+//!
+//! A request authenticates by binding as the DN built from the configured
+//! template and its username. The groups listed in the user entry's group
+//! attribute are then mapped to an API role. Every request binds anew.
+
+use ldap3::{LdapConnAsync, Scope, SearchEntry};
+use unet_core::config::auth::LdapConfig;
+
+/// LDAP result code for a failed bind
+const INVALID_CREDENTIALS: u32 = 49;
+
+#[derive(Debug, thiserror::Error)]
+pub enum LdapError {
+    #[error("Invalid credentials")]
+    InvalidCredentials,
+    #[error("LDAP server request failed: {0}")]
+    Server(#[from] ldap3::LdapError),
+}
+
+#[derive(Debug)]
+pub struct LdapProvider {
+    config: LdapConfig,
+}
+
+impl LdapProvider {
+    #[must_use]
+    pub const fn new(config: LdapConfig) -> Self {
+        Self { config }
+    }
+
+    /// Binds as the user and returns the groups listed in their entry
+    ///
+    /// # Errors
+    /// Returns [`LdapError::InvalidCredentials`] if the bind is refused, or
+    /// a server error if the directory cannot be queried.
+    pub async fn verify(&self, username: &str, password: &str) -> Result<Vec<String>, LdapError> {
+        // An empty password is an unauthenticated bind, which servers accept
+        if username.is_empty() || password.is_empty() {
+            return Err(LdapError::InvalidCredentials);
+        }
+        let dn = self
+            .config
+            .bind_dn
+            .replace("{username}", &escape_dn_value(username));
+
+        let (conn, mut ldap) = LdapConnAsync::new(&self.config.url).await?;
+        ldap3::drive!(conn);
+        let bind = ldap.simple_bind(&dn, password).await?;
+        if bind.rc == INVALID_CREDENTIALS {
+            return Err(LdapError::InvalidCredentials);
+        }
+        bind.success()?;
+
+        let attribute = self.config.group_attribute.as_str();
+        let (entries, _) = ldap
+            .search(&dn, Scope::Base, "(objectClass=*)", vec![attribute])
+            .await?
+            .success()?;
+        let _ = ldap.unbind().await;
+
+        Ok(entries
+            .into_iter()
+            .map(SearchEntry::construct)
+            .flat_map(|entry| entry.attrs)
+            .filter(|(name, _)| name.eq_ignore_ascii_case(attribute))
+            .flat_map(|(_, values)| values)
+            .collect())
+    }
+}
+
+/// Escapes a value for use in a DN (RFC 4514)
+fn escape_dn_value(value: &str) -> String {
+    let last = value.chars().count().saturating_sub(1);
+    let mut escaped = String::with_capacity(value.len());
+    for (index, c) in value.chars().enumerate() {
+        match c {
+            '\\' | ',' | '+' | '"' | '<' | '>' | ';' | '=' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\0' => escaped.push_str("\\00"),
+            ' ' if index == 0 || index == last => escaped.push_str("\\ "),
+            '#' if index == 0 => escaped.push_str("\\#"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_dn_value() {
+        assert_eq!(escape_dn_value("jdoe"), "jdoe");
+        assert_eq!(escape_dn_value("x,ou=admins"), "x\\,ou\\=admins");
+        assert_eq!(escape_dn_value("#a b "), "\\#a b\\ ");
+    }
+
+    #[tokio::test]
+    async fn test_empty_password_is_rejected_without_binding() {
+        let provider = LdapProvider::new(LdapConfig {
+            url: "ldap://127.0.0.1:1".to_string(),
+            bind_dn: "uid={username},dc=example,dc=com".to_string(),
+            group_attribute: "memberOf".to_string(),
+        });
+
+        let result = provider.verify("jdoe", "").await;
+
+        assert!(matches!(result, Err(LdapError::InvalidCredentials)));
+    }
+}
@@ -0,0 +1,55 @@
+//! Request body limits and response compression
+
+use tower_http::compression::CompressionLayer;
+use tower_http::compression::predicate::{NotForContentType, Predicate, SizeAbove};
+use unet_core::config::ServerConfig;
+
+/// Largest accepted request body in bytes for each route class
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BodyLimits {
+    /// Routes without a class of their own
+    pub default: usize,
+    /// Node routes
+    pub nodes: usize,
+    /// Policy routes
+    pub policies: usize,
+    /// Administration routes
+    pub admin: usize,
+    /// Attachment upload routes, whose bodies are the uploaded file
+    pub attachments: usize,
+}
+
+impl BodyLimits {
+    /// Resolves the configured limits, falling back to `max_request_size`
+    pub fn from_config(config: &ServerConfig) -> Self {
+        let default = config.max_request_size;
+        let limits = &config.body_limits;
+        Self {
+            default,
+            nodes: limits.nodes.unwrap_or(default),
+            policies: limits.policies.unwrap_or(default),
+            admin: limits.admin.unwrap_or(default),
+            attachments: config.attachments.max_size,
+        }
+    }
+}
+
+impl Default for BodyLimits {
+    fn default() -> Self {
+        Self::from_config(&ServerConfig::default())
+    }
+}
+
+/// Builds the response compression layer, or `None` when disabled
+///
+/// gzip or brotli is chosen from the request's `Accept-Encoding`. Bodies of
+/// known size below `compression_min_size`, images, and event streams are
+/// sent as is.
+pub fn compression_layer(config: &ServerConfig) -> Option<CompressionLayer<impl Predicate>> {
+    config.compression.then(|| {
+        let predicate = SizeAbove::new(config.compression_min_size)
+            .and(NotForContentType::IMAGES)
+            .and(NotForContentType::SSE);
+        CompressionLayer::new().compress_when(predicate)
+    })
+}
@@ -0,0 +1,98 @@
+//! Request body limit and response compression tests for the server router.
+
+use axum::{
+    body::Body,
+    http::{Request, Response, StatusCode, header},
+};
+use tower::ServiceExt as _;
+use unet_core::config::Config;
+
+use super::middleware::create_app;
+
+async fn send(config: Config, request: Request<Body>) -> Response<Body> {
+    let app = create_app(config, "sqlite::memory:".to_string())
+        .await
+        .expect("app should build");
+    app.oneshot(request).await.expect("request should succeed")
+}
+
+fn post_json(path: &str, bytes: usize) -> Request<Body> {
+    let body = format!(r#"{{"policies":"{}"}}"#, "x".repeat(bytes));
+    Request::post(path)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body))
+        .expect("request should build")
+}
+
+fn get_gzip(path: &str) -> Request<Body> {
+    Request::get(path)
+        .header(header::ACCEPT_ENCODING, "gzip")
+        .body(Body::empty())
+        .expect("request should build")
+}
+
+#[tokio::test]
+async fn test_route_class_limit_rejects_large_bodies() {
+    let mut config = Config::default();
+    config.server.body_limits.policies = Some(1024);
+
+    let response = send(config.clone(), post_json("/api/v1/policies/validate", 4096)).await;
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    let response = send(config, post_json("/api/v1/policies/validate", 16)).await;
+    assert_ne!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[tokio::test]
+async fn test_responses_are_compressed_when_accepted() {
+    let mut config = Config::default();
+    config.server.compression_min_size = 0;
+
+    let response = send(config.clone(), get_gzip("/health")).await;
+    assert_eq!(
+        response.headers().get(header::CONTENT_ENCODING),
+        Some(&header::HeaderValue::from_static("gzip"))
+    );
+
+    config.server.compression = false;
+    let response = send(config, get_gzip("/health")).await;
+    assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+}
+
+#[tokio::test]
+async fn test_node_export_streams_ndjson() {
+    let response = send(
+        Config::default(),
+        Request::get("/api/v1/nodes/export")
+            .body(Body::empty())
+            .expect("request should build"),
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers().get(header::CONTENT_TYPE),
+        Some(&header::HeaderValue::from_static("application/x-ndjson"))
+    );
+}
+
+#[tokio::test]
+async fn test_attachment_limit_rejects_large_uploads() {
+    let mut config = Config::default();
+    config.server.attachments.max_size = 1024;
+    let path = format!(
+        "/api/v1/nodes/{}/attachments?filename=big.txt",
+        uuid::Uuid::nil()
+    );
+
+    let response = send(
+        config,
+        Request::post(path)
+            .header(header::CONTENT_TYPE, "text/plain")
+            .body(Body::from("x".repeat(4096)))
+            .expect("request should build"),
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
@@ -1,23 +1,50 @@
 //! Middleware configuration and setup
 
 use anyhow::Result;
-use axum::Router;
+use axum::{Extension, Router};
 use std::net::SocketAddr;
+use std::sync::Arc;
 use tower::ServiceBuilder;
 use tower_http::trace::TraceLayer;
 use tracing::info;
 use unet_core::config::Config;
+use unet_core::enrichment::EnrichmentRegistry;
 
 use super::{
-    app_state::initialize_app_state, auth::ApiAuth, cors::build_cors_layer, routes::create_router,
+    app_state::initialize_app_state,
+    auth::ApiAuth,
+    cors::build_cors_layer,
+    limits::{BodyLimits, compression_layer},
+    routes::create_router,
+    slugs::with_slug_paths,
+    ui::with_ui,
 };
+use crate::background::{NodePoller, PolicyTrigger};
+use crate::handlers::system::ServerInfo;
 
 /// Run the μNet HTTP server
 ///
 /// # Errors
 /// Returns an error if binding the listener or serving the app fails.
 pub async fn run(config: Config, database_url: String) -> Result<()> {
-    let app = create_app(config.clone(), database_url).await?;
+    run_with_enrichment(config, database_url, EnrichmentRegistry::with_builtins()).await
+}
+
+/// Run the μNet HTTP server with a custom set of enrichment plugins
+///
+/// Deployments that add their own plugins register them on a registry
+/// (usually starting from [`EnrichmentRegistry::with_builtins`]) and enable
+/// them by name in `server.enrichment`.
+///
+/// # Errors
+/// Returns an error if the enrichment configuration is invalid, or binding
+/// the listener or serving the app fails.
+pub async fn run_with_enrichment(
+    config: Config,
+    database_url: String,
+    enrichment: EnrichmentRegistry,
+) -> Result<()> {
+    let app = create_app_with(config.clone(), database_url, &enrichment).await?;
 
     let addr = SocketAddr::from((
         config
@@ -37,17 +64,51 @@ pub async fn run(config: Config, database_url: String) -> Result<()> {
 
 /// Create the Axum application with all routes
 pub async fn create_app(config: Config, database_url: String) -> Result<Router> {
-    let auth = ApiAuth::from_config(&config.auth);
-    let app_state = initialize_app_state(config.clone(), database_url).await?;
-    let router = create_router(auth);
+    create_app_with(config, database_url, &EnrichmentRegistry::with_builtins()).await
+}
+
+async fn create_app_with(
+    config: Config,
+    database_url: String,
+    enrichment: &EnrichmentRegistry,
+) -> Result<Router> {
+    let app_state = initialize_app_state(config.clone(), database_url, enrichment).await?;
+    let server_info = ServerInfo::new(&config, &app_state);
+    info!("{}", server_info.banner());
+    let auth = ApiAuth::from_config(&config.auth).with_api_keys(app_state.datastore.clone());
+    let router = create_router(auth, BodyLimits::from_config(&config.server));
     let cors_layer = build_cors_layer(&config.server)?;
-    let app = router.with_state(app_state).layer(
+    // Attachment handlers read their limits and blob directory from here, the
+    // system info handler what the server was started with, and on-demand
+    // polls share one poller so its concurrency limit holds across requests;
+    // node writes queue the changed node for policy evaluation
+    let policy_trigger = PolicyTrigger::spawn(
+        app_state.datastore.clone(),
+        app_state.policy_service.clone(),
+        &config.policy,
+    );
+    let router = router
+        .with_state(app_state.clone())
+        .layer(Extension(config.server.attachments.clone()))
+        .layer(Extension(server_info))
+        .layer(Extension(Arc::new(
+            NodePoller::new(&config).with_enrichment(app_state.enrichment.clone()),
+        )))
+        .layer(Extension(policy_trigger));
+    #[cfg(feature = "web-ui")]
+    let app = with_ui(router, &config.server);
+    #[cfg(not(feature = "web-ui"))]
+    let app = with_ui(router, &config.server)?;
+    let app = with_slug_paths(app, app_state).layer(
         ServiceBuilder::new()
             .layer(TraceLayer::new_for_http())
             .layer(cors_layer),
     );
 
-    Ok(app)
+    Ok(match compression_layer(&config.server) {
+        Some(compression) => app.layer(compression),
+        None => app,
+    })
 }
 
 #[cfg(test)]
@@ -3,15 +3,25 @@
 //! This module is organized into separate modules for better maintainability.
 
 pub use app_state::AppState;
-pub use middleware::run;
+pub use middleware::{run, run_with_enrichment};
 
 mod app_state;
 mod auth;
 mod cors;
+mod ldap;
+mod limits;
 mod middleware;
+mod oidc;
+mod oidc_login;
 mod routes;
+mod slugs;
+mod ui;
 
 #[cfg(test)]
 mod auth_tests;
 #[cfg(test)]
 mod cors_tests;
+#[cfg(test)]
+mod limits_tests;
+#[cfg(test)]
+mod ui_tests;
@@ -0,0 +1,268 @@
+//! `OpenID` Connect login and ID token verification
+//!
+//! `GET /api/v1/auth/oidc/login` redirects to the issuer's authorization
+//! endpoint (see [`super::oidc_login`]). The issuer sends the user back to
+//! the configured callback, which exchanges the code for an ID token and
+//! returns it; clients then present that token as their bearer token. Tokens
+//! are verified against the issuer's JWKS, located through its discovery
+//! document and refetched when a token names an unknown key.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use axum::{http::StatusCode, response::Response};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation, jwk::JwkSet};
+use serde::{Deserialize, de::DeserializeOwned};
+use serde_json::Value;
+use tokio::sync::RwLock;
+use unet_core::config::auth::OidcConfig;
+
+use super::auth::auth_error;
+
+/// How long a login may take from redirect to callback
+const LOGIN_TIMEOUT: Duration = Duration::from_secs(600);
+/// Minimum time between JWKS fetches triggered by unknown key IDs
+const JWKS_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+/// Timeout for requests to the issuer
+const PROVIDER_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, thiserror::Error)]
+pub enum OidcError {
+    #[error("OIDC provider request failed: {0}")]
+    Provider(String),
+    #[error("Invalid ID token: {0}")]
+    InvalidToken(String),
+    #[error("Unknown or expired login state")]
+    UnknownState,
+}
+
+/// Endpoints from the issuer's discovery document
+#[derive(Clone, Debug, Deserialize)]
+struct ProviderMetadata {
+    authorization_endpoint: String,
+    token_endpoint: String,
+    jwks_uri: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    id_token: String,
+}
+
+#[derive(Debug)]
+pub struct OidcProvider {
+    config: OidcConfig,
+    http: reqwest::Client,
+    metadata: RwLock<Option<ProviderMetadata>>,
+    jwks: RwLock<Option<(JwkSet, Instant)>>,
+    /// Login states awaiting their callback, with when they were issued
+    pending: Mutex<HashMap<String, Instant>>,
+}
+
+impl OidcProvider {
+    #[must_use]
+    pub fn new(config: OidcConfig) -> Self {
+        Self {
+            config,
+            http: reqwest::Client::builder()
+                .timeout(PROVIDER_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+            metadata: RwLock::new(None),
+            jwks: RwLock::new(None),
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the issuer URL to send the user to, remembering its state
+    ///
+    /// # Errors
+    /// Returns an error if the discovery document cannot be fetched.
+    pub async fn authorization_url(&self) -> Result<String, OidcError> {
+        let metadata = self.metadata().await?;
+        let mut url = reqwest::Url::parse(&metadata.authorization_endpoint).map_err(provider)?;
+        let state = uuid::Uuid::new_v4().simple().to_string();
+        url.query_pairs_mut()
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &self.config.client_id)
+            .append_pair("redirect_uri", &self.config.redirect_url)
+            .append_pair("scope", &self.config.scopes.join(" "))
+            .append_pair("state", &state);
+
+        let mut pending = self.pending.lock().unwrap_or_else(PoisonError::into_inner);
+        pending.retain(|_, issued| issued.elapsed() < LOGIN_TIMEOUT);
+        pending.insert(state, Instant::now());
+        Ok(url.into())
+    }
+
+    /// Exchanges an authorization code for an ID token
+    ///
+    /// # Errors
+    /// Returns an error if the state was not issued by this server or has
+    /// expired, or the issuer rejects the code.
+    pub async fn exchange_code(&self, code: &str, state: &str) -> Result<String, OidcError> {
+        let issued = self
+            .pending
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(state);
+        if !issued.is_some_and(|issued| issued.elapsed() < LOGIN_TIMEOUT) {
+            return Err(OidcError::UnknownState);
+        }
+
+        let metadata = self.metadata().await?;
+        let mut form = vec![
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", self.config.redirect_url.as_str()),
+            ("client_id", self.config.client_id.as_str()),
+        ];
+        if let Some(secret) = &self.config.client_secret {
+            form.push(("client_secret", secret.as_str()));
+        }
+        let response: TokenResponse = self
+            .http
+            .post(&metadata.token_endpoint)
+            .form(&form)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(provider)?
+            .json()
+            .await
+            .map_err(provider)?;
+        Ok(response.id_token)
+    }
+
+    /// Verifies an ID token's signature, issuer, audience, and expiry, and
+    /// returns the groups it lists
+    ///
+    /// # Errors
+    /// Returns an error if the token is invalid or the JWKS cannot be fetched.
+    pub async fn verify(&self, token: &str) -> Result<Vec<String>, OidcError> {
+        let header = jsonwebtoken::decode_header(token).map_err(invalid)?;
+        // JWKS keys are public; accepting HMAC would let anyone sign with them
+        if matches!(
+            header.alg,
+            Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512
+        ) {
+            return Err(OidcError::InvalidToken(
+                "symmetric signing algorithms are not accepted".to_string(),
+            ));
+        }
+        let kid = header
+            .kid
+            .ok_or_else(|| OidcError::InvalidToken("token has no key ID".to_string()))?;
+        let key = self.decoding_key(&kid).await?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_issuer(&[self.config.issuer.as_str()]);
+        validation.set_audience(&[self.config.client_id.as_str()]);
+        let claims = jsonwebtoken::decode::<Value>(token, &key, &validation)
+            .map_err(invalid)?
+            .claims;
+        Ok(groups_from_claim(claims.get(&self.config.groups_claim)))
+    }
+
+    async fn decoding_key(&self, kid: &str) -> Result<DecodingKey, OidcError> {
+        if let Some((jwks, fetched)) = self.jwks.read().await.as_ref() {
+            if let Some(key) = find_key(jwks, kid) {
+                return key;
+            }
+            if fetched.elapsed() < JWKS_REFRESH_INTERVAL {
+                return Err(unknown_key(kid));
+            }
+        }
+
+        let metadata = self.metadata().await?;
+        let jwks: JwkSet = self.get_json(&metadata.jwks_uri).await?;
+        let key = find_key(&jwks, kid);
+        *self.jwks.write().await = Some((jwks, Instant::now()));
+        key.unwrap_or_else(|| Err(unknown_key(kid)))
+    }
+
+    async fn metadata(&self) -> Result<ProviderMetadata, OidcError> {
+        if let Some(metadata) = self.metadata.read().await.clone() {
+            return Ok(metadata);
+        }
+        let url = format!(
+            "{}/.well-known/openid-configuration",
+            self.config.issuer.trim_end_matches('/')
+        );
+        let metadata: ProviderMetadata = self.get_json(&url).await?;
+        *self.metadata.write().await = Some(metadata.clone());
+        Ok(metadata)
+    }
+
+    async fn get_json<T: DeserializeOwned>(&self, url: &str) -> Result<T, OidcError> {
+        self.http
+            .get(url)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(provider)?
+            .json()
+            .await
+            .map_err(provider)
+    }
+}
+
+fn find_key(jwks: &JwkSet, kid: &str) -> Option<Result<DecodingKey, OidcError>> {
+    jwks.find(kid)
+        .map(|jwk| DecodingKey::from_jwk(jwk).map_err(invalid))
+}
+
+/// Reads a groups claim holding either one group or a list of them
+fn groups_from_claim(claim: Option<&Value>) -> Vec<String> {
+    match claim {
+        Some(Value::String(group)) => vec![group.clone()],
+        Some(Value::Array(groups)) => groups
+            .iter()
+            .filter_map(Value::as_str)
+            .map(ToString::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn provider(error: impl std::fmt::Display) -> OidcError {
+    OidcError::Provider(error.to_string())
+}
+
+fn invalid(error: impl std::fmt::Display) -> OidcError {
+    OidcError::InvalidToken(error.to_string())
+}
+
+fn unknown_key(kid: &str) -> OidcError {
+    OidcError::InvalidToken(format!("unknown signing key {kid}"))
+}
+
+/// Maps an OIDC failure to its API error response
+pub(super) fn error_response(error: &OidcError) -> Response {
+    let (status, code) = match error {
+        OidcError::Provider(_) => (StatusCode::BAD_GATEWAY, "AUTH_PROVIDER_ERROR"),
+        OidcError::InvalidToken(_) => (StatusCode::UNAUTHORIZED, "INVALID_AUTH_TOKEN"),
+        OidcError::UnknownState => (StatusCode::BAD_REQUEST, "INVALID_LOGIN_STATE"),
+    };
+    if matches!(error, OidcError::Provider(_)) {
+        tracing::warn!(error = %error, "OIDC provider request failed");
+    }
+    auth_error(status, code, &error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_groups_from_claim_accepts_string_or_list() {
+        assert_eq!(groups_from_claim(Some(&json!("netops"))), ["netops"]);
+        assert_eq!(
+            groups_from_claim(Some(&json!(["netops", 7, "admins"]))),
+            ["netops", "admins"]
+        );
+        assert!(groups_from_claim(None).is_empty());
+    }
+}
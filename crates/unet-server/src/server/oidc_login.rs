@@ -0,0 +1,88 @@
+//! `OpenID` Connect login routes
+
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Redirect, Response},
+};
+use serde::{Deserialize, Serialize};
+
+use super::auth::{ApiAuth, ApiRole, auth_error};
+use super::oidc::error_response;
+use crate::api::ApiResponse;
+
+/// Query parameters the issuer appends to the callback URL
+#[derive(Debug, Deserialize)]
+pub struct CallbackQuery {
+    code: Option<String>,
+    state: Option<String>,
+    error: Option<String>,
+}
+
+/// ID token issued by a completed login, to be sent as the bearer token
+#[derive(Debug, Serialize)]
+pub struct LoginResponse {
+    token: String,
+    token_type: &'static str,
+    role: ApiRole,
+}
+
+/// `GET /api/v1/auth/oidc/login` - Redirect to the issuer's login page
+pub async fn login(State(auth): State<ApiAuth>) -> Response {
+    let Some(oidc) = auth.oidc() else {
+        return not_configured();
+    };
+    match oidc.authorization_url().await {
+        Ok(url) => Redirect::to(&url).into_response(),
+        Err(error) => error_response(&error),
+    }
+}
+
+/// `GET /api/v1/auth/oidc/callback` - Complete a login and return the ID token
+pub async fn callback(State(auth): State<ApiAuth>, Query(query): Query<CallbackQuery>) -> Response {
+    let Some(oidc) = auth.oidc() else {
+        return not_configured();
+    };
+    if let Some(error) = query.error {
+        return auth_error(
+            StatusCode::UNAUTHORIZED,
+            "LOGIN_FAILED",
+            &format!("OIDC login failed: {error}"),
+        );
+    }
+    let (Some(code), Some(state)) = (query.code, query.state) else {
+        return auth_error(
+            StatusCode::BAD_REQUEST,
+            "INVALID_LOGIN_STATE",
+            "Callback is missing code or state",
+        );
+    };
+
+    let token = match oidc.exchange_code(&code, &state).await {
+        Ok(token) => token,
+        Err(error) => return error_response(&error),
+    };
+    let role = match oidc.verify(&token).await {
+        Ok(groups) => match auth.mapped_role(&groups) {
+            Ok(role) => role,
+            Err(response) => return response,
+        },
+        Err(error) => return error_response(&error),
+    };
+
+    Json(ApiResponse::success(LoginResponse {
+        token,
+        token_type: "Bearer",
+        role,
+    }))
+    .into_response()
+}
+
+fn not_configured() -> Response {
+    auth_error(
+        StatusCode::NOT_FOUND,
+        "OIDC_NOT_CONFIGURED",
+        "OIDC login is not configured",
+    )
+}
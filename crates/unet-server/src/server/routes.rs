@@ -1,84 +1,181 @@
 //! Router configuration and route definitions
 
-use axum::{
-    Router, middleware,
-    routing::{delete, get, post, put},
-};
+use axum::{Router, extract::DefaultBodyLimit, middleware, routing::get};
 
 use super::app_state::AppState;
-use super::auth::{ApiAuth, require_bearer_auth};
+use super::auth::{ApiAuth, get_scopes, require_bearer_auth};
+use super::limits::BodyLimits;
+use super::oidc_login;
 use crate::handlers;
 
+mod admin;
+mod inventory;
+mod policies;
+mod polling;
+mod reports;
+
+use admin::{create_admin_routes, create_webhook_routes};
+use inventory::{
+    create_attachment_routes, create_group_routes, create_link_measurement_routes,
+    create_link_routes, create_location_routes, create_node_defaults_routes, create_node_routes,
+    create_note_routes, create_vlan_routes,
+};
+use policies::create_policy_routes;
+use polling::{create_change_window_routes, create_oid_profile_routes, create_polling_routes};
+use reports::{
+    create_event_routes, create_report_routes, create_search_routes, create_system_routes,
+    create_topology_routes,
+};
+
 /// Create the router with all API endpoints
-pub fn create_router(auth: ApiAuth) -> Router<AppState> {
+///
+/// Each route group is layered with the
+/// [`RouteScopes`](super::auth::RouteScopes) it needs; only
+/// `/api/v1/auth/scopes` is open to every authenticated caller.
+///
+/// Node, policy, admin, and attachment routes accept request bodies up to
+/// their class limit; every other route uses the default limit.
+pub fn create_router(auth: ApiAuth, limits: BodyLimits) -> Router<AppState> {
+    let standard = Router::new()
+        .merge(create_oid_profile_routes())
+        .merge(create_node_defaults_routes())
+        .merge(create_group_routes())
+        .merge(create_note_routes())
+        .merge(create_link_routes())
+        .merge(create_link_measurement_routes())
+        .merge(create_event_routes())
+        .merge(create_location_routes())
+        .merge(create_polling_routes())
+        .merge(create_change_window_routes())
+        .merge(create_report_routes())
+        .merge(create_search_routes())
+        .merge(create_system_routes())
+        .merge(create_topology_routes())
+        .merge(create_vlan_routes())
+        .merge(create_webhook_routes())
+        .layer(DefaultBodyLimit::max(limits.default));
     let protected = Router::new()
-        .merge(create_node_routes())
-        .merge(create_policy_routes())
-        .route_layer(middleware::from_fn_with_state(auth, require_bearer_auth));
+        .merge(create_node_routes().layer(DefaultBodyLimit::max(limits.nodes)))
+        .merge(create_policy_routes().layer(DefaultBodyLimit::max(limits.policies)))
+        .merge(create_admin_routes().layer(DefaultBodyLimit::max(limits.admin)))
+        .merge(create_attachment_routes().layer(DefaultBodyLimit::max(limits.attachments)))
+        .merge(standard)
+        .route("/api/v1/auth/scopes", get(get_scopes))
+        .route_layer(middleware::from_fn_with_state(
+            auth.clone(),
+            require_bearer_auth,
+        ));
 
     Router::new()
         .route("/health", get(handlers::health::health_check))
+        .merge(create_auth_routes(auth))
         .merge(protected)
 }
 
-/// Create node-related routes
-pub fn create_node_routes() -> Router<AppState> {
+/// Create public login routes for the configured authentication providers
+pub fn create_auth_routes(auth: ApiAuth) -> Router<AppState> {
     Router::new()
-        .route("/api/v1/nodes", get(handlers::nodes::list_nodes))
-        .route("/api/v1/nodes", post(handlers::nodes::create_node))
-        .route("/api/v1/nodes/{id}", get(handlers::nodes::get_node))
-        .route("/api/v1/nodes/{id}", put(handlers::nodes::update_node))
-        .route("/api/v1/nodes/{id}", delete(handlers::nodes::delete_node))
-        .route(
-            "/api/v1/nodes/{id}/status",
-            get(handlers::nodes::get_node_status),
-        )
-        .route(
-            "/api/v1/nodes/{id}/interfaces",
-            get(handlers::nodes::get_node_interfaces),
-        )
-        .route(
-            "/api/v1/nodes/{id}/metrics",
-            get(handlers::nodes::get_node_metrics),
-        )
-}
-
-/// Create policy-related routes
-pub fn create_policy_routes() -> Router<AppState> {
-    Router::new()
-        .route(
-            "/api/v1/policies/evaluate",
-            post(handlers::policies::evaluate_policies),
-        )
-        .route(
-            "/api/v1/policies/results",
-            get(handlers::policies::get_policy_results),
-        )
-        .route(
-            "/api/v1/policies/validate",
-            post(handlers::policies::validate_policies),
-        )
-        .route(
-            "/api/v1/policies/status",
-            get(handlers::policies::get_policy_status),
-        )
+        .route("/api/v1/auth/oidc/login", get(oidc_login::login))
+        .route("/api/v1/auth/oidc/callback", get(oidc_login::callback))
+        .with_state(auth)
 }
 
 #[cfg(test)]
 mod tests {
+    use super::inventory::create_node_poll_routes;
+    use super::policies::create_policy_run_routes;
     use super::*;
     use crate::server::app_state::tests::create_mock_app_state;
 
     #[tokio::test]
     async fn test_create_router() {
-        let router = create_router(ApiAuth::from_config(&unet_core::config::AuthConfig {
-            enabled: false,
-            token: None,
-        }));
+        let router = create_router(
+            ApiAuth::from_config(&unet_core::config::AuthConfig {
+                enabled: false,
+                token: None,
+                admin_token: None,
+                oidc: None,
+                ldap: None,
+                group_roles: unet_core::config::GroupRoleConfig::default(),
+            }),
+            BodyLimits::default(),
+        );
         let app_state = create_mock_app_state().await;
         let _router_with_state: axum::Router = router.with_state(app_state);
     }
 
+    #[tokio::test]
+    async fn test_create_webhook_routes() {
+        let webhook_router = create_webhook_routes();
+        let app_state = create_mock_app_state().await;
+        let _router_with_state: axum::Router = webhook_router.with_state(app_state);
+    }
+
+    #[tokio::test]
+    async fn test_create_polling_routes() {
+        let polling_router = create_polling_routes();
+        let app_state = create_mock_app_state().await;
+        let _router_with_state: axum::Router = polling_router.with_state(app_state);
+    }
+
+    #[tokio::test]
+    async fn test_create_change_window_routes() {
+        let change_window_router = create_change_window_routes();
+        let app_state = create_mock_app_state().await;
+        let _router_with_state: axum::Router = change_window_router.with_state(app_state);
+    }
+
+    #[tokio::test]
+    async fn test_create_vlan_routes() {
+        let vlan_router = create_vlan_routes();
+        let app_state = create_mock_app_state().await;
+        let _router_with_state: axum::Router = vlan_router.with_state(app_state);
+    }
+
+    #[tokio::test]
+    async fn test_create_location_routes() {
+        let location_router = create_location_routes();
+        let app_state = create_mock_app_state().await;
+        let _router_with_state: axum::Router = location_router.with_state(app_state);
+    }
+
+    #[tokio::test]
+    async fn test_create_event_routes() {
+        let event_router = create_event_routes();
+        let app_state = create_mock_app_state().await;
+        let _router_with_state: axum::Router = event_router.with_state(app_state);
+    }
+
+    #[tokio::test]
+    async fn test_create_report_routes() {
+        let report_router = create_report_routes();
+        let app_state = create_mock_app_state().await;
+        let _router_with_state: axum::Router = report_router.with_state(app_state);
+    }
+
+    #[tokio::test]
+    async fn test_create_search_routes() {
+        let search_router = create_search_routes();
+        let app_state = create_mock_app_state().await;
+        let _router_with_state: axum::Router = search_router.with_state(app_state);
+    }
+
+    #[tokio::test]
+    async fn test_create_topology_routes() {
+        let topology_router = create_topology_routes();
+        let app_state = create_mock_app_state().await;
+        let _router_with_state: axum::Router = topology_router.with_state(app_state);
+    }
+
+    #[tokio::test]
+    async fn test_create_auth_routes() {
+        let auth_router = create_auth_routes(ApiAuth::from_config(
+            &unet_core::config::Config::default().auth,
+        ));
+        let app_state = create_mock_app_state().await;
+        let _router_with_state: axum::Router = auth_router.with_state(app_state);
+    }
+
     #[tokio::test]
     async fn test_create_node_routes() {
         let node_router = create_node_routes();
@@ -86,10 +183,80 @@ mod tests {
         let _router_with_state: axum::Router = node_router.with_state(app_state);
     }
 
+    #[tokio::test]
+    async fn test_create_node_poll_routes() {
+        let poll_router = create_node_poll_routes();
+        let app_state = create_mock_app_state().await;
+        let _router_with_state: axum::Router = poll_router.with_state(app_state);
+    }
+
+    #[tokio::test]
+    async fn test_create_policy_run_routes() {
+        let run_router = create_policy_run_routes();
+        let app_state = create_mock_app_state().await;
+        let _router_with_state: axum::Router = run_router.with_state(app_state);
+    }
+
     #[tokio::test]
     async fn test_create_policy_routes() {
         let policy_router = create_policy_routes();
         let app_state = create_mock_app_state().await;
         let _router_with_state: axum::Router = policy_router.with_state(app_state);
     }
+
+    #[tokio::test]
+    async fn test_create_oid_profile_routes() {
+        let profile_router = create_oid_profile_routes();
+        let app_state = create_mock_app_state().await;
+        let _router_with_state: axum::Router = profile_router.with_state(app_state);
+    }
+
+    #[tokio::test]
+    async fn test_create_node_defaults_routes() {
+        let defaults_router = create_node_defaults_routes();
+        let app_state = create_mock_app_state().await;
+        let _router_with_state: axum::Router = defaults_router.with_state(app_state);
+    }
+
+    #[tokio::test]
+    async fn test_create_group_routes() {
+        let group_router = create_group_routes();
+        let app_state = create_mock_app_state().await;
+        let _router_with_state: axum::Router = group_router.with_state(app_state);
+    }
+
+    #[tokio::test]
+    async fn test_create_note_routes() {
+        let note_router = create_note_routes();
+        let app_state = create_mock_app_state().await;
+        let _router_with_state: axum::Router = note_router.with_state(app_state);
+    }
+
+    #[tokio::test]
+    async fn test_create_attachment_routes() {
+        let attachment_router = create_attachment_routes();
+        let app_state = create_mock_app_state().await;
+        let _router_with_state: axum::Router = attachment_router.with_state(app_state);
+    }
+
+    #[tokio::test]
+    async fn test_create_link_routes() {
+        let link_router = create_link_routes();
+        let app_state = create_mock_app_state().await;
+        let _router_with_state: axum::Router = link_router.with_state(app_state);
+    }
+
+    #[tokio::test]
+    async fn test_create_link_measurement_routes() {
+        let measurement_router = create_link_measurement_routes();
+        let app_state = create_mock_app_state().await;
+        let _router_with_state: axum::Router = measurement_router.with_state(app_state);
+    }
+
+    #[tokio::test]
+    async fn test_create_admin_routes() {
+        let admin_router = create_admin_routes();
+        let app_state = create_mock_app_state().await;
+        let _router_with_state: axum::Router = admin_router.with_state(app_state);
+    }
 }
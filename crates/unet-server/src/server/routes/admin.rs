@@ -0,0 +1,86 @@
+//! Webhook and administration routes
+
+use axum::{
+    Router, middleware,
+    routing::{delete, get, post},
+};
+
+use crate::handlers;
+use crate::server::app_state::AppState;
+use crate::server::auth::{RouteScopes, require_admin, require_scope};
+
+/// Create webhook subscription routes, which require the admin role
+pub fn create_webhook_routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/v1/webhooks", get(handlers::webhooks::list_webhooks))
+        .route("/api/v1/webhooks", post(handlers::webhooks::create_webhook))
+        .route(
+            "/api/v1/webhooks/{id}",
+            delete(handlers::webhooks::delete_webhook),
+        )
+        .route(
+            "/api/v1/webhooks/dead-letters",
+            get(handlers::webhooks::list_webhook_dead_letters),
+        )
+        .route(
+            "/api/v1/webhooks/dead-letters/{id}",
+            delete(handlers::webhooks::delete_webhook_dead_letter),
+        )
+        .route(
+            "/api/v1/webhooks/dead-letters/{id}/retry",
+            post(handlers::webhooks::retry_webhook_dead_letter),
+        )
+        .route_layer(middleware::from_fn(require_admin))
+        .route_layer(middleware::from_fn_with_state(
+            RouteScopes::INVENTORY,
+            require_scope,
+        ))
+}
+
+/// Create admin routes, which require the admin role
+pub fn create_admin_routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/v1/admin/stats", get(handlers::admin::get_admin_stats))
+        .route(
+            "/api/v1/admin/maintenance/vacuum",
+            post(handlers::admin::vacuum),
+        )
+        .route(
+            "/api/v1/admin/maintenance/analyze",
+            post(handlers::admin::analyze),
+        )
+        .route(
+            "/api/v1/admin/maintenance/orphans",
+            post(handlers::admin::cleanup_orphans),
+        )
+        .route(
+            "/api/v1/admin/maintenance/derived-state",
+            post(handlers::admin::prune_derived_state),
+        )
+        .route(
+            "/api/v1/admin/maintenance/caches",
+            post(handlers::admin::clear_caches),
+        )
+        .route(
+            "/api/v1/admin/maintenance/retention",
+            post(handlers::admin::enforce_retention),
+        )
+        .route(
+            "/api/v1/admin/retention",
+            get(handlers::admin::get_retention_status),
+        )
+        .route(
+            "/api/v1/admin/maintenance/integrity",
+            post(handlers::admin::check_integrity),
+        )
+        .route(
+            "/api/v1/admin/integrity",
+            get(handlers::admin::get_integrity_report),
+        )
+        .route("/api/v1/admin/logs", get(handlers::admin::get_server_logs))
+        .route_layer(middleware::from_fn(require_admin))
+        .route_layer(middleware::from_fn_with_state(
+            RouteScopes::INVENTORY,
+            require_scope,
+        ))
+}
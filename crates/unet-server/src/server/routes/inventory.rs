@@ -0,0 +1,238 @@
+//! Node, link, location, and VLAN routes, with their notes and attachments
+
+use axum::{
+    Router, middleware,
+    routing::{delete, get, post, put},
+};
+
+use crate::handlers;
+use crate::server::app_state::AppState;
+use crate::server::auth::{RouteScopes, require_admin, require_scope};
+
+/// Create node-related routes; reading decrypted node secrets requires the admin role
+pub fn create_node_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/v1/nodes/{id}/secrets",
+            get(handlers::nodes::get_node_secrets),
+        )
+        .route_layer(middleware::from_fn(require_admin))
+        .route("/api/v1/nodes", get(handlers::nodes::list_nodes))
+        .route("/api/v1/nodes/export", get(handlers::nodes::export_nodes))
+        .route("/api/v1/nodes", post(handlers::nodes::create_node))
+        .route("/api/v1/nodes/{id}", get(handlers::nodes::get_node))
+        .route("/api/v1/nodes/{id}", put(handlers::nodes::update_node))
+        .route("/api/v1/nodes/{id}", delete(handlers::nodes::delete_node))
+        .route(
+            "/api/v1/nodes/{id}/status",
+            get(handlers::nodes::get_node_status),
+        )
+        .route(
+            "/api/v1/nodes/{id}/interfaces",
+            get(handlers::nodes::get_node_interfaces),
+        )
+        .route(
+            "/api/v1/nodes/{id}/metrics",
+            get(handlers::nodes::get_node_metrics),
+        )
+        .route(
+            "/api/v1/nodes/{id}/metrics/query",
+            get(handlers::nodes::query_node_metrics),
+        )
+        .route(
+            "/api/v1/nodes/{id}/hardware",
+            get(handlers::nodes::get_node_hardware),
+        )
+        .route(
+            "/api/v1/nodes/{id}/provenance",
+            get(handlers::nodes::get_node_provenance),
+        )
+        .route(
+            "/api/v1/nodes/{id}/config-snapshot",
+            get(handlers::config_changes::get_config_snapshot)
+                .post(handlers::config_changes::submit_config_snapshot),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            RouteScopes::INVENTORY,
+            require_scope,
+        ))
+        .merge(create_node_poll_routes())
+}
+
+/// Create the on-demand poll route, which needs the `snmp:execute` scope
+pub fn create_node_poll_routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/v1/nodes/{id}/poll", post(handlers::nodes::poll_node))
+        .route_layer(middleware::from_fn_with_state(
+            RouteScopes::SNMP,
+            require_scope,
+        ))
+}
+
+/// Create default `custom_data` routes; changing defaults requires the admin role
+pub fn create_node_defaults_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/v1/node-defaults/{scope}/{target}",
+            put(handlers::node_defaults::put_node_defaults),
+        )
+        .route(
+            "/api/v1/node-defaults/{scope}/{target}",
+            delete(handlers::node_defaults::delete_node_defaults),
+        )
+        .route_layer(middleware::from_fn(require_admin))
+        .route(
+            "/api/v1/node-defaults",
+            get(handlers::node_defaults::list_node_defaults),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            RouteScopes::INVENTORY,
+            require_scope,
+        ))
+}
+
+/// Create dynamic node group routes; changing groups requires the admin role
+pub fn create_group_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/v1/groups/{name}",
+            put(handlers::groups::put_node_group),
+        )
+        .route(
+            "/api/v1/groups/{name}",
+            delete(handlers::groups::delete_node_group),
+        )
+        .route_layer(middleware::from_fn(require_admin))
+        .route("/api/v1/groups", get(handlers::groups::list_node_groups))
+        .route(
+            "/api/v1/groups/{name}/members",
+            get(handlers::groups::get_node_group_members),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            RouteScopes::INVENTORY,
+            require_scope,
+        ))
+}
+
+/// Create note routes for nodes, links, and locations
+pub fn create_note_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/v1/nodes/{id}/notes",
+            get(handlers::notes::list_entity_notes).post(handlers::notes::create_entity_note),
+        )
+        .route(
+            "/api/v1/links/{id}/notes",
+            get(handlers::notes::list_entity_notes).post(handlers::notes::create_entity_note),
+        )
+        .route(
+            "/api/v1/locations/{id}/notes",
+            get(handlers::notes::list_entity_notes).post(handlers::notes::create_entity_note),
+        )
+        .route(
+            "/api/v1/notes/{id}",
+            get(handlers::notes::get_note)
+                .put(handlers::notes::update_note)
+                .delete(handlers::notes::delete_note),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            RouteScopes::INVENTORY,
+            require_scope,
+        ))
+}
+
+/// Create attachment routes for nodes, links, and locations
+pub fn create_attachment_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/v1/nodes/{id}/attachments",
+            get(handlers::notes::list_entity_attachments).post(handlers::notes::upload_attachment),
+        )
+        .route(
+            "/api/v1/links/{id}/attachments",
+            get(handlers::notes::list_entity_attachments).post(handlers::notes::upload_attachment),
+        )
+        .route(
+            "/api/v1/locations/{id}/attachments",
+            get(handlers::notes::list_entity_attachments).post(handlers::notes::upload_attachment),
+        )
+        .route(
+            "/api/v1/attachments/{id}",
+            get(handlers::notes::get_attachment).delete(handlers::notes::delete_attachment),
+        )
+        .route(
+            "/api/v1/attachments/{id}/content",
+            get(handlers::notes::download_attachment),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            RouteScopes::INVENTORY,
+            require_scope,
+        ))
+}
+
+/// Create link routes; writes check the endpoints unless
+/// `skip_interface_check` is set
+pub fn create_link_routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/v1/links", post(handlers::links::create_link))
+        .route("/api/v1/links/{id}", put(handlers::links::update_link))
+        .route_layer(middleware::from_fn_with_state(
+            RouteScopes::INVENTORY,
+            require_scope,
+        ))
+}
+
+/// Create link measurement routes; changing thresholds requires the admin role
+pub fn create_link_measurement_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/v1/link-thresholds/{target}",
+            put(handlers::link_measurements::put_link_thresholds),
+        )
+        .route_layer(middleware::from_fn(require_admin))
+        .route(
+            "/api/v1/links/{id}/measurements",
+            get(handlers::link_measurements::get_link_measurements),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            RouteScopes::INVENTORY,
+            require_scope,
+        ))
+}
+
+/// Create location routes
+pub fn create_location_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/v1/locations/{id}/status",
+            get(handlers::locations::get_location_status),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            RouteScopes::INVENTORY,
+            require_scope,
+        ))
+}
+
+/// Create VLAN routes; defining and removing VLANs requires the admin role
+pub fn create_vlan_routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/v1/vlans/{id}", put(handlers::vlans::put_vlan))
+        .route(
+            "/api/v1/vlans/{id}",
+            delete(handlers::vlans::delete_vlan_definition),
+        )
+        .route_layer(middleware::from_fn(require_admin))
+        .route("/api/v1/vlans", get(handlers::vlans::list_vlan_definitions))
+        .route(
+            "/api/v1/vlans/mismatches",
+            get(handlers::vlans::list_vlan_mismatches),
+        )
+        .route(
+            "/api/v1/nodes/{id}/vlans",
+            get(handlers::vlans::get_node_vlans).put(handlers::vlans::put_node_vlan),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            RouteScopes::INVENTORY,
+            require_scope,
+        ))
+}
@@ -0,0 +1,95 @@
+//! Policy result, batch, and evaluation routes
+
+use axum::{
+    Router, middleware,
+    routing::{get, post, put},
+};
+
+use crate::handlers;
+use crate::server::app_state::AppState;
+use crate::server::auth::{RouteScopes, require_scope};
+
+/// Create policy-related routes
+pub fn create_policy_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/v1/policies/results",
+            get(handlers::policies::get_policy_results),
+        )
+        .route(
+            "/api/v1/policies/status",
+            get(handlers::policies::get_policy_status),
+        )
+        .route(
+            "/api/v1/policies/batches",
+            get(handlers::policies::list_policy_batches),
+        )
+        .route(
+            "/api/v1/policies/batches/{name}",
+            get(handlers::policies::get_policy_batch)
+                .put(handlers::policies::put_policy_batch)
+                .delete(handlers::policies::delete_policy_batch),
+        )
+        .route(
+            "/api/v1/policies/batch-runs",
+            get(handlers::policies::list_policy_batch_runs),
+        )
+        .route(
+            "/api/v1/policies/batch-runs/{id}",
+            get(handlers::policies::get_policy_batch_run),
+        )
+        .route(
+            "/api/v1/policies/canaries",
+            get(handlers::policies::list_policy_canaries),
+        )
+        .route(
+            "/api/v1/policies/canaries/{rule}",
+            get(handlers::policies::get_policy_canary)
+                .put(handlers::policies::put_policy_canary)
+                .delete(handlers::policies::delete_policy_canary),
+        )
+        .route(
+            "/api/v1/policies/canaries/{rule}/report",
+            get(handlers::policies::get_policy_canary_report),
+        )
+        .route(
+            "/api/v1/policies/exemptions",
+            get(handlers::policies::list_policy_exemptions),
+        )
+        .route(
+            "/api/v1/policies/exemptions/{location_id}/{rule}",
+            put(handlers::policies::put_policy_exemption)
+                .delete(handlers::policies::delete_policy_exemption),
+        )
+        .route(
+            "/api/v1/policies/orchestrator/cache",
+            get(handlers::policies::get_orchestrator_cache_stats),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            RouteScopes::INVENTORY,
+            require_scope,
+        ))
+        .merge(create_policy_run_routes())
+}
+
+/// Create policy evaluation, validation, and batch run routes, which need the
+/// `policies:evaluate` scope
+pub fn create_policy_run_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/v1/policies/evaluate",
+            post(handlers::policies::evaluate_policies),
+        )
+        .route(
+            "/api/v1/policies/validate",
+            post(handlers::policies::validate_policies),
+        )
+        .route(
+            "/api/v1/policies/batches/{name}/runs",
+            post(handlers::policies::trigger_policy_batch),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            RouteScopes::POLICY_RUNS,
+            require_scope,
+        ))
+}
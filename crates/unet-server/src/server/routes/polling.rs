@@ -0,0 +1,104 @@
+//! SNMP OID profile, polling, and change window routes
+
+use axum::{
+    Router, middleware,
+    routing::{delete, get, post, put},
+};
+
+use crate::handlers;
+use crate::server::app_state::AppState;
+use crate::server::auth::{RouteScopes, require_admin, require_scope};
+
+/// Create SNMP OID profile routes; changes need the `snmp:execute` scope
+pub fn create_oid_profile_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/v1/oid-profiles",
+            get(handlers::oid_profiles::list_oid_profiles),
+        )
+        .route(
+            "/api/v1/oid-profiles/{name}",
+            get(handlers::oid_profiles::get_oid_profile),
+        )
+        .route(
+            "/api/v1/oid-profiles/{name}",
+            put(handlers::oid_profiles::put_oid_profile),
+        )
+        .route(
+            "/api/v1/oid-profiles/{name}",
+            delete(handlers::oid_profiles::delete_oid_profile),
+        )
+        .route(
+            "/api/v1/oid-profile-assignments",
+            get(handlers::oid_profiles::list_oid_profile_assignments),
+        )
+        .route(
+            "/api/v1/oid-profile-assignments",
+            post(handlers::oid_profiles::create_oid_profile_assignment),
+        )
+        .route(
+            "/api/v1/oid-profile-assignments/{scope}/{target}",
+            delete(handlers::oid_profiles::delete_oid_profile_assignment),
+        )
+        .route(
+            "/api/v1/nodes/{id}/oid-profile",
+            get(handlers::oid_profiles::get_node_oid_profile),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            RouteScopes::SNMP,
+            require_scope,
+        ))
+}
+
+/// Create polling pause, shard, and adjustment routes; pausing and resuming need the admin role
+/// and the `snmp:execute` scope
+pub fn create_polling_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/v1/polling/pauses",
+            post(handlers::polling::create_polling_pause),
+        )
+        .route(
+            "/api/v1/polling/pauses/{id}",
+            delete(handlers::polling::delete_polling_pause),
+        )
+        .route_layer(middleware::from_fn(require_admin))
+        .route(
+            "/api/v1/polling/pauses",
+            get(handlers::polling::list_polling_pauses),
+        )
+        .route(
+            "/api/v1/polling/shards",
+            get(handlers::polling::get_polling_shards),
+        )
+        .route(
+            "/api/v1/polling/adjustments",
+            get(handlers::polling::list_polling_adjustments),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            RouteScopes::SNMP,
+            require_scope,
+        ))
+}
+
+/// Create change window routes; approving and removing windows require the admin role
+pub fn create_change_window_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/v1/change-windows",
+            post(handlers::config_changes::create_change_window),
+        )
+        .route(
+            "/api/v1/change-windows/{id}",
+            delete(handlers::config_changes::delete_change_window),
+        )
+        .route_layer(middleware::from_fn(require_admin))
+        .route(
+            "/api/v1/change-windows",
+            get(handlers::config_changes::list_change_windows),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            RouteScopes::INVENTORY,
+            require_scope,
+        ))
+}
@@ -0,0 +1,124 @@
+//! Report, change log, topology, search, and system info routes
+
+use axum::{
+    Router, middleware,
+    routing::{delete, get, post, put},
+};
+
+use crate::handlers;
+use crate::server::app_state::AppState;
+use crate::server::auth::{RouteScopes, require_admin, require_scope};
+
+/// Create fleet report routes; changing firmware targets requires the admin role
+pub fn create_report_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/v1/reports/firmware/targets/{scope}/{target}",
+            put(handlers::reports::put_firmware_target),
+        )
+        .route(
+            "/api/v1/reports/firmware/targets/{scope}/{target}",
+            delete(handlers::reports::delete_firmware_target),
+        )
+        .route(
+            "/api/v1/reports/templates/{name}",
+            put(handlers::reports::put_report_template)
+                .delete(handlers::reports::delete_report_template),
+        )
+        .route_layer(middleware::from_fn(require_admin))
+        .route(
+            "/api/v1/reports/firmware",
+            get(handlers::reports::get_firmware_report),
+        )
+        .route(
+            "/api/v1/reports/firmware/targets",
+            get(handlers::reports::list_firmware_targets),
+        )
+        .route(
+            "/api/v1/reports/capacity",
+            get(handlers::reports::get_capacity_report),
+        )
+        .route(
+            "/api/v1/reports/capacity/forecast",
+            get(handlers::reports::get_capacity_forecast),
+        )
+        .route(
+            "/api/v1/reports/templates",
+            get(handlers::reports::list_report_templates),
+        )
+        .route(
+            "/api/v1/reports/templates/{name}/render",
+            post(handlers::reports::render_report_template),
+        )
+        .route(
+            "/api/v1/reports/templates/{name}/latest",
+            get(handlers::reports::get_latest_report),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            RouteScopes::INVENTORY,
+            require_scope,
+        ))
+}
+
+/// Create change log routes
+pub fn create_event_routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/v1/events", get(handlers::events::get_events))
+        .route(
+            "/api/v1/events/activity/{id}",
+            get(handlers::events::get_entity_activity),
+        )
+        .route(
+            "/api/v1/events/compliance",
+            get(handlers::events::get_compliance_summary),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            RouteScopes::INVENTORY,
+            require_scope,
+        ))
+}
+
+/// Create topology analysis routes
+pub fn create_topology_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/v1/topology/impact",
+            get(handlers::topology::get_impact),
+        )
+        .route("/api/v1/topology/spof", get(handlers::topology::get_spof))
+        .route(
+            "/api/v1/topology/cache",
+            get(handlers::topology::get_cache_status),
+        )
+        .route(
+            "/api/v1/topology/cache/rebuild",
+            post(handlers::topology::rebuild_cache),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            RouteScopes::INVENTORY,
+            require_scope,
+        ))
+}
+
+/// Create search routes
+pub fn create_search_routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/v1/search", get(handlers::search::search_entities))
+        .route_layer(middleware::from_fn_with_state(
+            RouteScopes::INVENTORY,
+            require_scope,
+        ))
+}
+
+/// Create server build and runtime info routes
+pub fn create_system_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/v1/system/info",
+            get(handlers::system::get_system_info),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            RouteScopes::INVENTORY,
+            require_scope,
+        ))
+}
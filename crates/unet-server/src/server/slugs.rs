@@ -0,0 +1,115 @@
+//! Slugs in API paths
+//!
+//! `/api/v1/nodes/edge-01/status` is served as if the node's ID had been
+//! given. Paths are rewritten before routing, so every node, location, and
+//! link route takes a slug wherever it takes an ID. A retired slug answers
+//! with a permanent redirect to the same path under the current slug.
+
+use axum::{
+    Router,
+    extract::{Request, State},
+    http::{StatusCode, Uri, header},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+};
+use unet_core::slug::{SlugKind, resolve_slug};
+use uuid::Uuid;
+
+use super::AppState;
+use crate::error::ServerError;
+
+/// Prefix of the entity collections taking slugs
+const API_PREFIX: &str = "/api/v1/";
+
+/// Path segments under a collection that are routes rather than entities
+const RESERVED_SEGMENTS: [&str; 1] = ["export"];
+
+/// Entity path with a slug where the ID goes
+#[derive(Debug, PartialEq, Eq)]
+struct SlugPath<'a> {
+    kind: SlugKind,
+    collection: &'a str,
+    slug: &'a str,
+    rest: &'a str,
+}
+
+/// Splits `/api/v1/{collection}/{slug}{rest}`; paths with an ID, or outside
+/// the node, location, and link collections, are left alone
+fn slug_path(path: &str) -> Option<SlugPath<'_>> {
+    let (collection, tail) = path.strip_prefix(API_PREFIX)?.split_once('/')?;
+    let kind = match collection {
+        "nodes" => SlugKind::Node,
+        "locations" => SlugKind::Location,
+        "links" => SlugKind::Link,
+        _ => return None,
+    };
+    let (slug, rest) = tail.split_at(tail.find('/').unwrap_or(tail.len()));
+    if slug.is_empty() || RESERVED_SEGMENTS.contains(&slug) || slug.parse::<Uuid>().is_ok() {
+        return None;
+    }
+    Some(SlugPath {
+        kind,
+        collection,
+        slug,
+        rest,
+    })
+}
+
+/// Replaces a slug in the request path with the ID it names
+async fn resolve_slug_paths(
+    State(app_state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let uri = request.uri().clone();
+    let Some(path) = slug_path(uri.path()) else {
+        return next.run(request).await;
+    };
+    let found = match resolve_slug(app_state.datastore.as_ref(), path.kind, path.slug).await {
+        Ok(Some(found)) => found,
+        // Unknown slugs fall through to the route's own ID parsing
+        Ok(None) => return next.run(request).await,
+        Err(e) => return ServerError::from(e).into_response(),
+    };
+
+    let query = uri
+        .query()
+        .map(|query| format!("?{query}"))
+        .unwrap_or_default();
+    if found.redirected {
+        let location = format!(
+            "{API_PREFIX}{}/{}{}{query}",
+            path.collection, found.slug, path.rest
+        );
+        return (
+            StatusCode::PERMANENT_REDIRECT,
+            [(header::LOCATION, location)],
+        )
+            .into_response();
+    }
+    let rewritten = format!(
+        "{API_PREFIX}{}/{}{}{query}",
+        path.collection, found.id, path.rest
+    );
+    if let Ok(rewritten) = rewritten.parse::<Uri>() {
+        *request.uri_mut() = rewritten;
+    }
+    next.run(request).await
+}
+
+/// Wraps `app` so slugs in entity paths are resolved before routing
+///
+/// `Router::layer` runs after a route is picked, so the app is nested as the
+/// fallback of an outer router whose layer sees the original path.
+pub(super) fn with_slug_paths(app: Router, app_state: AppState) -> Router {
+    Router::new()
+        .fallback_service(app)
+        .layer(middleware::from_fn_with_state(
+            app_state,
+            resolve_slug_paths,
+        ))
+}
+
+#[cfg(test)]
+#[path = "slugs_tests.rs"]
+mod tests;
@@ -0,0 +1,107 @@
+//! Tests for resolving slugs in API paths
+
+use axum::{
+    Router,
+    body::Body,
+    extract::Path,
+    http::{Request, StatusCode, header},
+    routing::get,
+};
+use tower::ServiceExt as _;
+use unet_core::models::{DeviceRole, Node, Vendor};
+use unet_core::slug::{assign_slug, change_slug};
+use uuid::Uuid;
+
+use super::*;
+use crate::server::app_state::tests::create_mock_app_state;
+
+fn app(app_state: AppState) -> Router {
+    let router = Router::new()
+        .route(
+            "/api/v1/nodes/{id}/status",
+            get(|Path(id): Path<Uuid>| async move { id.to_string() }),
+        )
+        .route("/api/v1/nodes/export", get(|| async { "export" }));
+    with_slug_paths(router, app_state)
+}
+
+async fn get_path(app: Router, path: &str) -> axum::response::Response {
+    let request = Request::get(path).body(Body::empty()).unwrap();
+    app.oneshot(request).await.unwrap()
+}
+
+async fn body(response: axum::response::Response) -> String {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+#[test]
+fn test_slug_path_skips_ids_and_reserved_segments() {
+    assert_eq!(
+        slug_path("/api/v1/links/transit-1/measurements"),
+        Some(SlugPath {
+            kind: SlugKind::Link,
+            collection: "links",
+            slug: "transit-1",
+            rest: "/measurements",
+        })
+    );
+    assert!(slug_path(&format!("/api/v1/nodes/{}", Uuid::new_v4())).is_none());
+    assert!(slug_path("/api/v1/nodes/export").is_none());
+    assert!(slug_path("/api/v1/nodes").is_none());
+    assert!(slug_path("/api/v1/policies/validate").is_none());
+}
+
+#[tokio::test]
+async fn test_slug_paths_are_rewritten_and_retired_slugs_redirect() {
+    let app_state = create_mock_app_state().await;
+    let datastore = app_state.datastore.clone();
+    let node = Node::new(
+        "slug-path-edge".to_string(),
+        "example.com".to_string(),
+        Vendor::Cisco,
+        DeviceRole::Router,
+    );
+    datastore.create_node(&node).await.unwrap();
+    assign_slug(
+        datastore.as_ref(),
+        SlugKind::Node,
+        node.id,
+        &node.name,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let response = get_path(
+        app(app_state.clone()),
+        "/api/v1/nodes/slug-path-edge/status",
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(body(response).await, node.id.to_string());
+
+    change_slug(
+        datastore.as_ref(),
+        SlugKind::Node,
+        node.id,
+        "slug-path-core",
+    )
+    .await
+    .unwrap();
+    let response = get_path(
+        app(app_state.clone()),
+        "/api/v1/nodes/slug-path-edge/status?detail=1",
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+    assert_eq!(
+        response.headers()[header::LOCATION],
+        "/api/v1/nodes/slug-path-core/status?detail=1"
+    );
+
+    let response = get_path(app(app_state), "/api/v1/nodes/export").await;
+    assert_eq!(body(response).await, "export");
+}
@@ -0,0 +1,136 @@
+//! Bundled web UI
+//!
+//! With the `web-ui` feature, the files under `crates/unet-server/ui/` are
+//! embedded in the binary and served under `/ui` when `server.ui` is set.
+//! Paths that name no file and have no extension get `index.html`, so a
+//! single-page app can route on the client. Files under `assets/` are
+//! expected to carry a content hash in their name and are cached for a year;
+//! everything else is revalidated on each use through its `ETag`.
+//!
+//! The UI is public: it is the app that asks for and sends the API token.
+
+use axum::Router;
+use unet_core::config::ServerConfig;
+
+/// Adds the UI routes to `app` when `server.ui` is set
+#[cfg(feature = "web-ui")]
+pub(super) fn with_ui(app: Router, config: &ServerConfig) -> Router {
+    if config.ui {
+        app.merge(embedded::routes())
+    } else {
+        app
+    }
+}
+
+/// Passes `app` through; the server was built without the UI
+///
+/// # Errors
+/// Returns an error if `server.ui` is set, as serving the UI needs the
+/// `web-ui` feature.
+#[cfg(not(feature = "web-ui"))]
+pub(super) fn with_ui(app: Router, config: &ServerConfig) -> anyhow::Result<Router> {
+    anyhow::ensure!(
+        !config.ui,
+        "server.ui requires unet-server to be built with the `web-ui` feature"
+    );
+    Ok(app)
+}
+
+#[cfg(feature = "web-ui")]
+mod embedded {
+    use axum::{
+        Router,
+        extract::Path,
+        http::{HeaderMap, StatusCode, header},
+        response::{IntoResponse, Redirect, Response},
+        routing::get,
+    };
+    use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+
+    /// Path the UI is served under
+    const UI_PREFIX: &str = "/ui";
+
+    /// Directory of content-hashed build output
+    const HASHED_ASSETS_DIR: &str = "assets/";
+
+    const CACHE_IMMUTABLE: &str = "public, max-age=31536000, immutable";
+
+    const CACHE_REVALIDATE: &str = "no-cache";
+
+    #[derive(rust_embed::Embed)]
+    #[folder = "ui/"]
+    struct UiAssets;
+
+    pub(super) fn routes() -> Router {
+        Router::new()
+            .route(
+                UI_PREFIX,
+                get(|| async { Redirect::permanent(&format!("{UI_PREFIX}/")) }),
+            )
+            .route(
+                &format!("{UI_PREFIX}/"),
+                get(|headers: HeaderMap| async move { serve(&headers, "") }),
+            )
+            .route(
+                &format!("{UI_PREFIX}/{{*path}}"),
+                get(|headers: HeaderMap, Path(path): Path<String>| async move {
+                    serve(&headers, &path)
+                }),
+            )
+    }
+
+    /// Serves the embedded file at `path`, falling back to `index.html` for
+    /// client-side routes
+    fn serve(headers: &HeaderMap, path: &str) -> Response {
+        let (path, file) = match UiAssets::get(path) {
+            Some(file) if !path.is_empty() => (path, file),
+            _ if is_file_path(path) => return StatusCode::NOT_FOUND.into_response(),
+            _ => match UiAssets::get("index.html") {
+                Some(file) => ("index.html", file),
+                None => return StatusCode::NOT_FOUND.into_response(),
+            },
+        };
+
+        let etag = format!(
+            "\"{}\"",
+            URL_SAFE_NO_PAD.encode(file.metadata.sha256_hash())
+        );
+        let cache_control = if path.starts_with(HASHED_ASSETS_DIR) {
+            CACHE_IMMUTABLE
+        } else {
+            CACHE_REVALIDATE
+        };
+        let not_modified = headers
+            .get(header::IF_NONE_MATCH)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.split(',').any(|tag| tag.trim() == etag));
+        if not_modified {
+            return (
+                StatusCode::NOT_MODIFIED,
+                [
+                    (header::ETAG, etag),
+                    (header::CACHE_CONTROL, cache_control.to_string()),
+                ],
+            )
+                .into_response();
+        }
+
+        (
+            [
+                (header::CONTENT_TYPE, file.metadata.mimetype().to_string()),
+                (header::ETAG, etag),
+                (header::CACHE_CONTROL, cache_control.to_string()),
+            ],
+            file.data,
+        )
+            .into_response()
+    }
+
+    /// Whether the last segment of `path` has an extension, so a miss is a
+    /// missing file rather than a client-side route
+    fn is_file_path(path: &str) -> bool {
+        path.rsplit('/')
+            .next()
+            .is_some_and(|name| name.contains('.'))
+    }
+}
@@ -0,0 +1,80 @@
+//! Tests for serving the bundled web UI
+
+use axum::{
+    body::Body,
+    http::{Request, Response, StatusCode, header},
+};
+use tower::ServiceExt as _;
+use unet_core::config::Config;
+
+use super::middleware::create_app;
+
+fn ui_config() -> Config {
+    let mut config = Config::default();
+    config.server.ui = true;
+    config
+}
+
+async fn get(config: Config, path: &str, etag: Option<&str>) -> Response<Body> {
+    let app = create_app(config, "sqlite::memory:".to_string())
+        .await
+        .expect("app should build");
+    let mut request = Request::get(path);
+    if let Some(etag) = etag {
+        request = request.header(header::IF_NONE_MATCH, etag);
+    }
+    app.oneshot(request.body(Body::empty()).expect("request should build"))
+        .await
+        .expect("request should succeed")
+}
+
+#[tokio::test]
+async fn test_ui_is_not_served_unless_enabled() {
+    let response = get(Config::default(), "/ui/", None).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[cfg(not(feature = "web-ui"))]
+#[tokio::test]
+async fn test_ui_requires_web_ui_feature() {
+    let error = create_app(ui_config(), "sqlite::memory:".to_string())
+        .await
+        .expect_err("app should not build");
+    assert!(error.to_string().contains("web-ui"));
+}
+
+#[cfg(feature = "web-ui")]
+#[tokio::test]
+async fn test_ui_serves_index_for_client_routes() {
+    let response = get(ui_config(), "/ui", None).await;
+    assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+
+    for path in ["/ui/", "/ui/index.html", "/ui/nodes/edge-01"] {
+        let response = get(ui_config(), path, None).await;
+        assert_eq!(response.status(), StatusCode::OK, "{path}");
+        assert!(
+            response.headers()[header::CONTENT_TYPE]
+                .to_str()
+                .unwrap()
+                .starts_with("text/html")
+        );
+        assert_eq!(response.headers()[header::CACHE_CONTROL], "no-cache");
+    }
+
+    let response = get(ui_config(), "/ui/assets/missing.js", None).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[cfg(feature = "web-ui")]
+#[tokio::test]
+async fn test_ui_answers_matching_etag_with_not_modified() {
+    let response = get(ui_config(), "/ui/", None).await;
+    let etag = response.headers()[header::ETAG]
+        .to_str()
+        .unwrap()
+        .to_string();
+
+    let response = get(ui_config(), "/ui/nodes", Some(&etag)).await;
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(response.headers()[header::ETAG], etag.as_str());
+}